@@ -1,21 +1,58 @@
+use std::collections::BTreeMap;
 use std::env;
-use std::fs::File;
-use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 
+use chrono::Utc;
+use tracing::{info, warn};
+
+use crate::app::config_reload::{DotenvVars, RuntimeConfig};
+use crate::app::credentials::{
+    credential_check_secs_from_env, credentials_fingerprint, mask_api_key, CredentialWatch,
+};
+use crate::app::inactivity::InactivityGuard;
+use crate::app::resource_profile::ResourceProfile;
+use crate::app::symbol_status::SymbolStatusWatch;
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
 use crate::error::exchange_error::ExchangeError;
 use crate::exchange::binance::auth::BinanceAuth;
+use crate::exchange::binance::cache::RestCacheTtls;
+use crate::exchange::binance::chaos::{ChaosConfig, ChaosTransport};
 use crate::exchange::binance::client::{BinanceExchange, BinanceHttpTransport, BinanceTransport};
 use crate::exchange::binance::demo::BinanceDemoHttpTransport;
+use crate::exchange::binance::metered::MeteredTransport;
+use crate::exchange::binance::network::NetworkConfig;
+use crate::exchange::binance::paper::{PaperTradingConfig, PaperTransport};
+use crate::exchange::binance::rate_budget::{BudgetedTransport, RateBudget};
+use crate::exchange::binance::user_stream::{
+    spawn_user_streams, BinanceUserStreamApi, UserStreamHandle,
+};
 use crate::exchange::facade::ExchangeFacade;
+use crate::exchange::types::{ApiPermissions, FuturesLeverage, MarginType};
 use crate::execution::service::ExecutionService;
+use crate::market_data::news_feed::NewsFeed;
+use crate::market_data::price_alert::{PriceAlertStore, DEFAULT_PRICE_ALERTS_PATH};
 use crate::market_data::price_store::PriceStore;
 use crate::market_data::service::MarketDataService;
+use crate::observability::rest_latency::RestLatencyStats;
+use crate::observability::self_metrics::SelfMetrics;
+use crate::portfolio::earn::EarnConfig;
 use crate::portfolio::store::PortfolioStateStore;
 use crate::portfolio::sync::PortfolioSyncService;
 use crate::record::coordination::RecorderCoordination;
-use crate::storage::event_log::EventLog;
+use crate::storage::event_log::{log, EventLog};
+use crate::storage::order_store::OrderStore;
+use crate::strategy::allocation::AutoAllocation;
+use crate::strategy::basis::BasisArbStore;
+use crate::strategy::group::{StrategyGroupStore, DEFAULT_STRATEGY_GROUPS_PATH};
+use crate::strategy::live::LiveBarStrategy;
+use crate::strategy::order_throttle::strategy_orders_per_min_from_env;
 use crate::strategy::store::StrategyStore;
+use crate::strategy::trade_stats::trade_stats_lookback_days_from_env;
+use crate::ui::locale::UiLocale;
+use crate::ui::theme::UiTheme;
+use crate::ui::timezone::DisplayTimeZone;
 
 #[derive(Debug)]
 pub struct AppBootstrap<E: ExchangeFacade> {
@@ -29,6 +66,35 @@ pub struct AppBootstrap<E: ExchangeFacade> {
     pub market_data: MarketDataService,
     pub recorder_coordination: RecorderCoordination,
     pub strategy_store: StrategyStore,
+    /// Saved group labels new watches rejoin.
+    pub strategy_groups: StrategyGroupStore,
+    /// Per-watch state of the script watches trading on minute closes.
+    pub live_bar_strategies: BTreeMap<u64, LiveBarStrategy>,
+    pub price_alerts: PriceAlertStore,
+    pub locale: UiLocale,
+    pub theme: UiTheme,
+    /// Zone the prompt clock and rendered timestamps are shown in.
+    pub time_zone: DisplayTimeZone,
+    pub self_metrics: SelfMetrics,
+    /// Fed by the `MeteredTransport` the Binance exchange is built with.
+    pub rest_latency: Arc<RestLatencyStats>,
+    /// The mode's shared REST weight budget; `None` for exchanges not
+    /// built from the environment.
+    pub rest_budget: Option<Arc<RateBudget>>,
+    pub inactivity: InactivityGuard,
+    pub credentials: CredentialWatch,
+    pub basis_arbs: BasisArbStore,
+    /// Sweep policy for idle quote; tracking itself lives on the store.
+    pub earn: EarnConfig,
+    /// Headlines for the `/news` pane; polled from the shell's idle tick.
+    pub news: NewsFeed,
+    /// Halt and delisting checks for traded symbols; run from the idle tick.
+    pub symbol_status: SymbolStatusWatch,
+    /// Buffer caps and refresh pacing; see `apply_resource_profile`.
+    pub resource_profile: ResourceProfile,
+    /// Proxy and extra CAs for the user streams; the REST client is built
+    /// with the same settings.
+    pub network: NetworkConfig,
 }
 
 impl<E: ExchangeFacade> AppBootstrap<E> {
@@ -44,10 +110,159 @@ impl<E: ExchangeFacade> AppBootstrap<E> {
             market_data: MarketDataService,
             recorder_coordination: RecorderCoordination::default(),
             strategy_store: StrategyStore::default(),
+            strategy_groups: StrategyGroupStore::default(),
+            live_bar_strategies: BTreeMap::new(),
+            price_alerts: PriceAlertStore::default(),
+            locale: UiLocale::default(),
+            theme: UiTheme::default(),
+            time_zone: DisplayTimeZone::default(),
+            self_metrics: SelfMetrics::default(),
+            rest_latency: Arc::default(),
+            rest_budget: None,
+            inactivity: InactivityGuard::default(),
+            credentials: CredentialWatch::default(),
+            basis_arbs: BasisArbStore::default(),
+            earn: EarnConfig::default(),
+            news: NewsFeed::default(),
+            symbol_status: SymbolStatusWatch::default(),
+            resource_profile: ResourceProfile::default(),
+            network: NetworkConfig::default(),
+        }
+    }
+
+    /// Caps the event log, order history, strategy traces, latency samples
+    /// and news pane to the profile's limits, trimming what they hold now.
+    ///
+    /// The event log is trimmed further only on `EventLog::compact`.
+    pub fn apply_resource_profile(&mut self, profile: ResourceProfile) {
+        let limits = profile.limits();
+        self.resource_profile = profile;
+        self.event_log.capacity = limits.event_records;
+        self.event_log.compact();
+        self.execution
+            .history
+            .set_capacity(limits.order_history_rows);
+        self.strategy_store.set_trace_capacity(limits.trace_entries);
+        self.self_metrics
+            .set_sample_capacity(limits.latency_samples);
+        self.news.set_pane_limit(limits.news_headlines);
+    }
+}
+
+impl<E> AppBootstrap<E>
+where
+    E: ExchangeFacade,
+    E::Error: std::fmt::Display,
+{
+    /// Asks the exchange what the API key may trade and holds execution to
+    /// it: read-only keys place no orders, and without futures permission
+    /// symbols resolve to spot.
+    ///
+    /// A failed probe is logged and leaves the previous permissions in
+    /// place.
+    pub fn probe_api_permissions(&mut self) -> Option<ApiPermissions> {
+        match self.exchange.load_api_permissions() {
+            Ok(permissions) => {
+                self.execution.api_permissions = permissions;
+                if let Some(permissions) = permissions {
+                    log(
+                        &mut self.event_log,
+                        "app.account.permissions_probed",
+                        serde_json::json!({
+                            "mode": self.mode.as_str(),
+                            "permissions": permissions.label(),
+                            "reading": permissions.reading,
+                            "spot": permissions.spot_trading,
+                            "futures": permissions.futures_trading,
+                            "options": permissions.options_trading,
+                            "read_only": permissions.read_only(),
+                        }),
+                    );
+                }
+                permissions
+            }
+            Err(error) => {
+                log(
+                    &mut self.event_log,
+                    "app.account.permissions_probe_failed",
+                    serde_json::json!({
+                        "mode": self.mode.as_str(),
+                        "error": error.to_string(),
+                    }),
+                );
+                self.execution.api_permissions
+            }
+        }
+    }
+
+    /// Sets the margin type, then the leverage, of every symbol in
+    /// `SANDBOX_QUANT_FUTURES_LEVERAGE`, and keeps what the venue confirmed
+    /// for the portfolio view.
+    ///
+    /// Skipped when the key may not trade futures. A symbol the venue
+    /// refuses, e.g. a margin type change with a position open, is logged
+    /// and the rest are still applied.
+    pub fn apply_futures_leverage(&mut self) {
+        if self
+            .execution
+            .api_permissions
+            .is_some_and(|permissions| !permissions.can_trade(Market::Futures))
+        {
+            return;
+        }
+        for (instrument, requested) in self.execution.futures_leverage.clone() {
+            match set_futures_leverage(&self.exchange, &instrument, requested) {
+                Ok(Some(effective)) => {
+                    log(
+                        &mut self.event_log,
+                        "app.account.leverage_applied",
+                        serde_json::json!({
+                            "mode": self.mode.as_str(),
+                            "instrument": instrument.0,
+                            "leverage": effective.leverage,
+                            "margin_type": effective.margin_type.map(MarginType::as_str),
+                        }),
+                    );
+                    self.portfolio_store
+                        .futures_leverage
+                        .insert(instrument, effective);
+                }
+                Ok(None) => {}
+                Err(error) => log(
+                    &mut self.event_log,
+                    "app.account.leverage_failed",
+                    serde_json::json!({
+                        "mode": self.mode.as_str(),
+                        "instrument": instrument.0,
+                        "requested": requested.label(),
+                        "error": error.to_string(),
+                    }),
+                ),
+            }
         }
     }
 }
 
+/// `None` when the venue has no futures leverage settings.
+fn set_futures_leverage<E: ExchangeFacade>(
+    exchange: &E,
+    instrument: &Instrument,
+    requested: FuturesLeverage,
+) -> Result<Option<FuturesLeverage>, E::Error> {
+    let margin_type = match requested.margin_type {
+        Some(margin_type) => exchange
+            .set_futures_margin_type(instrument, margin_type)?
+            .then_some(margin_type),
+        None => None,
+    };
+    Ok(exchange
+        .set_futures_leverage(instrument, requested.leverage)?
+        .map(|leverage| FuturesLeverage {
+            leverage,
+            margin_type,
+        }))
+}
+
 impl AppBootstrap<BinanceExchange> {
     /// Builds the real Binance-backed app bootstrap from environment variables.
     ///
@@ -62,25 +277,260 @@ impl AppBootstrap<BinanceExchange> {
     /// - `BINANCE_FUTURES_BASE_URL`
     /// - `BINANCE_OPTIONS_BASE_URL`
     /// - `BINANCE_MODE`
+    /// - `SANDBOX_QUANT_ASSET_EXPOSURE_CAPS` (e.g. `BTC=0.5,ETH=0.25`)
+    /// - `SANDBOX_QUANT_UI_LOCALE` (`en` or `ko`)
+    /// - `SANDBOX_QUANT_UI_THEME` (`dark`, `light`, `high-contrast` or `deuteranopia-safe`)
+    /// - `SANDBOX_QUANT_DISPLAY_TZ` (`utc`, `local` or an offset like `+09:00`)
+    /// - `SANDBOX_QUANT_PRICE_ALERTS_PATH` (default `var/price-alerts.json`)
+    /// - `SANDBOX_QUANT_STRATEGY_GROUPS_PATH` (default `var/strategy-groups.json`, saved strategy group labels)
+    /// - `SANDBOX_QUANT_SEAL` (`keyring` or `passphrase`, encrypts stored files)
+    /// - `SANDBOX_QUANT_TRAILING_STOP_MODE` (`exchange` or `internal`)
+    /// - `SANDBOX_QUANT_DEPTH_FILTER_BPS` (enables the ask-depth filter, e.g. `10`)
+    /// - `SANDBOX_QUANT_DEPTH_FILTER_MIN_COVER`, `_RETRY_SECS`, `_MAX_RETRIES`
+    /// - `SANDBOX_QUANT_MAX_SLIPPAGE_BPS` (sends entries as IOC limits within the band, e.g. `20`)
+    /// - `SANDBOX_QUANT_TAKE_PROFIT_PROFILES` (e.g. `scalp=0.5:0.5,1:0.3,trail:0.5`)
+    /// - `SANDBOX_QUANT_HOLD_EV_PROFILES` (e.g. `default=win:0.5,suggest:-0.25,exit:-0.6`)
+    /// - `SANDBOX_QUANT_SYNTHETIC_INSTRUMENTS` (e.g. `BTC_BASIS=BTCUSDT@futures-BTCUSDT@spot`)
+    /// - `SANDBOX_QUANT_FUTURES_LEVERAGE` (e.g. `BTCUSDT=10:isolated,ETHUSDT=5:cross`, set on startup)
+    /// - `SANDBOX_QUANT_ILLIQUID_WINDOWS` (e.g. `sat-sun 22:00-06:00`, UTC, queues strategy entries until the window ends)
+    /// - `SANDBOX_QUANT_MAX_POSITION_AGE_SECS` (e.g. `BTCUSDT=14400`, positions held longer are closed with reason `max_age`)
+    /// - `SANDBOX_QUANT_PAPER_SHADOW` (`on` or modeled slippage bps, e.g. `2`, paper-fills every live strategy order for `/shadow`)
+    /// - `SANDBOX_QUANT_EVENT_ROUTES` (e.g. `execution=file+notifier:info;market_data=memory`)
+    /// - `SANDBOX_QUANT_REST_CACHE_TTL_SECS` (default `3600`, `0` disables)
+    /// - `SANDBOX_QUANT_TAKER_FEE_RATE` (per side, for the position break-even, default `0.0005`)
+    /// - `SANDBOX_QUANT_PROXY` (`http://`, `socks5://` or `socks5h://` URL for REST and user streams)
+    /// - `SANDBOX_QUANT_CA_BUNDLE` (PEM file of CAs trusted on top of the bundled roots)
+    /// - `SANDBOX_QUANT_PAPER_TRADING` (`on` or starting USDT, e.g. `5000`; simulates the account against live prices, no API keys needed)
+    /// - `SANDBOX_QUANT_CHAOS` (demo only, e.g. `ws_drop=2,rest_timeout=10,fill_delay_ms=1500`)
+    /// - `SANDBOX_QUANT_CHAOS_SEED` (replays the same fault sequence)
+    /// - `SANDBOX_QUANT_INACTIVITY_FLAT_SECS` (flattens and disables entries after this long unattended)
+    /// - `SANDBOX_QUANT_STRATEGY_RAMP` (e.g. `0.25:5`, size ramp for new strategy watches)
+    /// - `SANDBOX_QUANT_STRATEGY_ADAPT` (e.g. `10:0.1:0.5`, online tuning for new strategy watches)
+    /// - `SANDBOX_QUANT_ORDER_STORE_PATH` (default `var/orders-<mode>.sqlite`, keeps order history and fills)
+    /// - `SANDBOX_QUANT_ORDER_RETENTION_DAYS` (default `90`, older orders and fills are compacted away at startup)
+    /// - `SANDBOX_QUANT_TRADE_STATS_LOOKBACK_DAYS` (default `30`, `0` for all, realized trades hold-EV models learn from)
+    /// - `SANDBOX_QUANT_AUTO_ALLOCATE` (e.g. `0.05:0.4:0.2`, min:max weight and shift for the daily strategy capital reallocation)
+    /// - `SANDBOX_QUANT_OVERTRADING` (e.g. `5:900:1800`, pauses a strategy watch for 30m after 5 trades within 15m; off when unset)
+    /// - `SANDBOX_QUANT_STRATEGY_ORDERS_PER_MIN` (default `3`, entries one strategy watch may send per rolling minute, `0` for no limit)
+    /// - `SANDBOX_QUANT_PLUGIN_DIR` (default `plugins`, where `strategy plugins` looks for `*.wasm`)
+    /// - `SANDBOX_QUANT_CREDENTIAL_CHECK_SECS` (default `60`, re-reads rotated API keys)
+    /// - `SANDBOX_QUANT_EARN_TRACKING` (`true` counts Simple Earn flexible savings in equity)
+    /// - `SANDBOX_QUANT_EARN_SWEEP_ABOVE_USDT` (e.g. `2000`, sweeps idle USDT above it to Earn)
+    /// - `SANDBOX_QUANT_REST_WEIGHT_PER_MIN` (default `1200`, REST weight shared by every client of the mode)
+    /// - `SANDBOX_QUANT_SYMBOL_STATUS_SECS` (default `300`, checks traded symbols for halts and delistings)
+    /// - `SANDBOX_QUANT_HALT_FLATTEN` (`true` closes positions on halted or delisted symbols)
+    /// - `SANDBOX_QUANT_REST_RESERVE` (default `order=240,account=60`, weight other calls leave free)
+    /// - `SANDBOX_QUANT_RESOURCE_PROFILE` (`low` caps buffers and slows refreshes for small hosts, `fast` puts the order path first for colocated servers)
+    ///
+    /// The `SANDBOX_QUANT_*` settings above except the alerts and groups
+    /// paths, the order store, trade stats, auto allocation and strategy order rate
+    /// settings, the cache TTL, the REST budget and the resource profile
+    /// can be reloaded at runtime; see `RuntimeConfig`.
     pub fn from_env(portfolio_store: PortfolioStateStore) -> Result<Self, ExchangeError> {
         let config = BinanceEnvConfig::from_env()?;
+        let rest_latency = Arc::new(RestLatencyStats::default());
         let mut app = Self::new(
-            BinanceExchange::new(config.build_transport()),
+            BinanceExchange::with_cache_ttls(
+                config.build_metered_transport(rest_latency.clone()),
+                RestCacheTtls::from_env(),
+            ),
             portfolio_store,
         );
         app.mode = config.mode;
+        app.network = config.network.clone();
+        app.rest_latency = rest_latency;
+        app.rest_budget = Some(RateBudget::shared(config.mode));
+        app.credentials = CredentialWatch {
+            fingerprint: Some(config.fingerprint()),
+            keep_base_urls: true,
+            check_secs: credential_check_secs_from_env(),
+            last_checked_at: Some(Instant::now()),
+            failing: false,
+        };
+        RuntimeConfig::from_env().apply(&mut app);
+        app.apply_resource_profile(ResourceProfile::from_env());
+        let alerts_path = env::var("SANDBOX_QUANT_PRICE_ALERTS_PATH")
+            .unwrap_or_else(|_| DEFAULT_PRICE_ALERTS_PATH.to_string());
+        app.price_alerts = PriceAlertStore::load(&alerts_path).unwrap_or_else(|error| {
+            warn!(
+                service = "trading-engine",
+                error = %error,
+                "price alerts not loaded: the file is left untouched and new alerts are not saved"
+            );
+            log(
+                &mut app.event_log,
+                "app.alert.store_unreadable",
+                serde_json::json!({ "path": alerts_path, "error": error.to_string() }),
+            );
+            PriceAlertStore::unloaded(&alerts_path)
+        });
+        let groups_path = env::var("SANDBOX_QUANT_STRATEGY_GROUPS_PATH")
+            .unwrap_or_else(|_| DEFAULT_STRATEGY_GROUPS_PATH.to_string());
+        app.strategy_groups = StrategyGroupStore::load(&groups_path).unwrap_or_else(|error| {
+            warn!(
+                service = "trading-engine",
+                error = %error,
+                "strategy groups not loaded: the file is left untouched and new labels are not saved"
+            );
+            log(
+                &mut app.event_log,
+                "app.strategy.group_store_unreadable",
+                serde_json::json!({ "path": groups_path, "error": error.to_string() }),
+            );
+            StrategyGroupStore::unloaded(&groups_path)
+        });
+        app.open_order_store();
+        app.strategy_store.auto_allocation = AutoAllocation::from_env();
+        app.execution.strategy_orders_per_min = strategy_orders_per_min_from_env();
         Ok(app)
     }
 
+    /// Opens the mode's order store, drops rows past retention and seeds
+    /// the in-memory history from it; without one, history is kept in
+    /// memory only and hold-EV models learn from this session alone.
+    fn open_order_store(&mut self) {
+        self.execution.trade_stats_lookback_days = trade_stats_lookback_days_from_env();
+        let opened = OrderStore::from_env(self.mode).and_then(|store| {
+            let removed = store.compact(Utc::now())?;
+            let rows = store.recent_orders(self.execution.history.capacity())?;
+            Ok((store, removed, rows))
+        });
+        match opened {
+            Ok((store, removed, rows)) => {
+                info!(
+                    service = "trading-engine",
+                    mode = self.mode.as_str(),
+                    path = %store.path().map(|path| path.display().to_string()).unwrap_or_default(),
+                    seeded = rows.len(),
+                    compacted = removed,
+                    "order store opened"
+                );
+                for row in rows {
+                    self.execution.history.record(row);
+                }
+                self.execution.order_store = Some(store);
+            }
+            Err(error) => warn!(
+                service = "trading-engine",
+                mode = self.mode.as_str(),
+                error = %error,
+                "order store unavailable: history kept in memory only"
+            ),
+        }
+    }
+
     pub fn switch_mode(&mut self, mode: BinanceMode) -> Result<(), ExchangeError> {
         let mut config = BinanceEnvConfig::from_mode(mode)?;
         config.spot_base_url = None;
         config.futures_base_url = None;
         config.options_base_url = None;
-        self.exchange = BinanceExchange::new(config.build_transport());
+        // Counters restart with the endpoints they describe.
+        self.rest_latency = Arc::new(RestLatencyStats::default());
+        self.exchange = BinanceExchange::with_cache_ttls(
+            config.build_metered_transport(self.rest_latency.clone()),
+            RestCacheTtls::from_env(),
+        );
         self.mode = mode;
+        self.network = config.network.clone();
+        self.rest_budget = Some(RateBudget::shared(mode));
+        self.credentials.fingerprint = Some(config.fingerprint());
+        self.credentials.keep_base_urls = false;
+        // Each mode keeps its own orders; rows already in memory stay.
+        self.execution.order_store = OrderStore::from_env(mode).ok();
         Ok(())
     }
+
+    /// Opens the spot and, when the key may trade them, futures user data
+    /// streams with the current client; restart after a mode switch or key
+    /// rotation.
+    pub fn start_user_streams(&self) -> UserStreamHandle {
+        let api = match self.mode {
+            BinanceMode::Real => BinanceUserStreamApi::real(),
+            BinanceMode::Demo => BinanceUserStreamApi::demo(),
+        }
+        .with_network(self.network.clone());
+        let markets = [Market::Spot, Market::Futures]
+            .into_iter()
+            .filter(|market| {
+                *market == Market::Spot
+                    || self
+                        .execution
+                        .api_permissions
+                        .is_none_or(|permissions| permissions.can_trade(*market))
+            })
+            .collect();
+        spawn_user_streams(self.exchange.clone(), api, markets)
+    }
+
+    /// Rebuilds the REST client when the current mode's key pair changed in
+    /// `.env` or the environment. Returns whether it was rebuilt.
+    ///
+    /// Cached responses are dropped with the old client; latency counters
+    /// are kept since the endpoints stay the same.
+    pub fn rotate_credentials(&mut self) -> Result<bool, ExchangeError> {
+        let dotenv = DotenvVars::read();
+        let mut config = BinanceEnvConfig::from_mode_with(self.mode, |key| dotenv.var(key))?;
+        let fingerprint = config.fingerprint();
+        if self.credentials.fingerprint == Some(fingerprint) {
+            return Ok(false);
+        }
+        if !self.credentials.keep_base_urls {
+            config.spot_base_url = None;
+            config.futures_base_url = None;
+            config.options_base_url = None;
+        }
+        self.exchange = BinanceExchange::with_cache_ttls(
+            config.build_metered_transport(self.rest_latency.clone()),
+            RestCacheTtls::from_env(),
+        );
+        self.credentials.fingerprint = Some(fingerprint);
+        self.network = config.network.clone();
+        log(
+            &mut self.event_log,
+            "app.config.credentials_rotated",
+            serde_json::json!({
+                "mode": self.mode.as_str(),
+                "api_key": mask_api_key(&config.api_key),
+            }),
+        );
+        Ok(true)
+    }
+
+    /// `rotate_credentials`, logging a failure once until a check succeeds.
+    ///
+    /// The current client is kept when the new keys cannot be read.
+    pub fn reload_credentials(&mut self) -> bool {
+        match self.rotate_credentials() {
+            Ok(rotated) => {
+                self.credentials.failing = false;
+                rotated
+            }
+            Err(error) => {
+                if !self.credentials.failing {
+                    log(
+                        &mut self.event_log,
+                        "app.config.credentials_rotation_failed",
+                        serde_json::json!({
+                            "mode": self.mode.as_str(),
+                            "error": error.to_string(),
+                        }),
+                    );
+                }
+                self.credentials.failing = true;
+                false
+            }
+        }
+    }
+
+    /// Runs `reload_credentials` once `SANDBOX_QUANT_CREDENTIAL_CHECK_SECS`
+    /// have passed since the last check; meant for idle ticks.
+    pub fn check_credentials(&mut self, now: Instant) -> bool {
+        if !self.credentials.is_due(now) {
+            return false;
+        }
+        self.credentials.last_checked_at = Some(now);
+        self.reload_credentials()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -97,24 +547,50 @@ pub struct BinanceEnvConfig {
     pub spot_base_url: Option<String>,
     pub futures_base_url: Option<String>,
     pub options_base_url: Option<String>,
+    /// Proxy and extra CAs the REST client and user streams connect with.
+    pub network: NetworkConfig,
 }
 
 impl BinanceEnvConfig {
+    pub fn fingerprint(&self) -> u64 {
+        credentials_fingerprint(&self.api_key, &self.secret_key)
+    }
+
     pub fn from_env() -> Result<Self, ExchangeError> {
         Self::from_mode(Self::mode_from_env())
     }
 
+    /// Paper trading needs no keys; missing ones are left empty.
     pub fn from_mode(mode: BinanceMode) -> Result<Self, ExchangeError> {
+        Self::from_mode_with(mode, Self::read_env_value)
+    }
+
+    /// `from_mode` with every variable looked up through `var`.
+    pub fn from_mode_with(
+        mode: BinanceMode,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ExchangeError> {
         let (api_key_var, secret_key_var) = mode.credentials_env_names();
-        let api_key = Self::read_required_env(api_key_var, "BINANCE_API_KEY")?;
-        let secret_key = Self::read_required_env(secret_key_var, "BINANCE_SECRET_KEY")?;
+        let paper = PaperTradingConfig::from_env().is_some();
+        let read_key =
+            |primary: &'static str, fallback: &str| match var(primary).or_else(|| var(fallback)) {
+                Some(key) => Ok(key),
+                None if paper => Ok(String::new()),
+                None => Err(ExchangeError::MissingConfiguration(primary)),
+            };
+        let api_key = read_key(api_key_var, "BINANCE_API_KEY")?;
+        let secret_key = read_key(secret_key_var, "BINANCE_SECRET_KEY")?;
         Ok(Self {
             api_key,
             secret_key,
             mode,
-            spot_base_url: Self::read_env_value("BINANCE_SPOT_BASE_URL"),
-            futures_base_url: Self::read_env_value("BINANCE_FUTURES_BASE_URL"),
-            options_base_url: Self::read_env_value("BINANCE_OPTIONS_BASE_URL"),
+            spot_base_url: var("BINANCE_SPOT_BASE_URL"),
+            futures_base_url: var("BINANCE_FUTURES_BASE_URL"),
+            options_base_url: var("BINANCE_OPTIONS_BASE_URL"),
+            network: NetworkConfig::parse(
+                var("SANDBOX_QUANT_PROXY").as_deref(),
+                var("SANDBOX_QUANT_CA_BUNDLE").as_deref(),
+            )?,
         })
     }
 
@@ -126,62 +602,79 @@ impl BinanceEnvConfig {
         }
     }
 
-    fn read_required_env(
-        primary: &'static str,
-        fallback: &'static str,
-    ) -> Result<String, ExchangeError> {
-        Self::read_env_value(primary)
-            .or_else(|| Self::read_env_value(fallback))
-            .ok_or(ExchangeError::MissingConfiguration(primary))
+    fn read_env_value(key: &str) -> Option<String> {
+        DotenvVars::read().var(key)
     }
 
-    fn read_env_value(key: &'static str) -> Option<String> {
-        env::var(key).ok().or_else(|| Self::read_dotenv_value(key))
-    }
-
-    fn read_dotenv_value(key: &'static str) -> Option<String> {
-        if env::var_os("SANDBOX_QUANT_DISABLE_DOTENV").is_some() {
-            return None;
+    /// Builds the HTTP transport, behind a `PaperTransport` when
+    /// `SANDBOX_QUANT_PAPER_TRADING` is set, and wrapped in `ChaosTransport`
+    /// when `SANDBOX_QUANT_CHAOS` is set and the mode is demo.
+    pub fn build_transport(&self) -> Arc<dyn BinanceTransport> {
+        let mut transport = self.build_http_transport();
+        if let Some(paper) = PaperTradingConfig::from_env() {
+            warn!(
+                service = "trading-engine",
+                mode = self.mode.as_str(),
+                starting_usdt = paper.starting_usdt,
+                "paper trading enabled: orders fill in memory against live prices"
+            );
+            transport = Arc::new(PaperTransport::new(transport, paper));
+        }
+        let chaos = ChaosConfig::from_env();
+        if chaos.enabled() && self.mode == BinanceMode::Real {
+            warn!(
+                service = "trading-engine",
+                "chaos mode ignored: SANDBOX_QUANT_CHAOS only applies to demo"
+            );
+        }
+        match chaos.for_mode(self.mode) {
+            Some(chaos) => {
+                warn!(
+                    service = "trading-engine",
+                    mode = self.mode.as_str(),
+                    chaos = chaos.summary(),
+                    "chaos mode enabled: injecting REST timeouts and delayed fills"
+                );
+                Arc::new(ChaosTransport::new(transport, chaos))
+            }
+            None => transport,
         }
-
-        Self::find_in_dotenv_iter(dotenvy::from_filename_iter(".env").ok(), key).or_else(|| {
-            let manifest_dotenv = Path::new(env!("CARGO_MANIFEST_DIR")).join(".env");
-            Self::find_in_dotenv_iter(dotenvy::from_path_iter(&manifest_dotenv).ok(), key)
-        })
     }
 
-    fn find_in_dotenv_iter(iter: Option<dotenvy::Iter<File>>, key: &'static str) -> Option<String> {
-        iter.and_then(|entries| {
-            entries
-                .filter_map(Result::ok)
-                .find_map(|(entry_key, entry_value)| {
-                    if entry_key == key {
-                        Some(entry_value)
-                    } else {
-                        None
-                    }
-                })
-        })
+    /// `build_transport` timed per endpoint into `stats` and charged
+    /// against the mode's shared `RateBudget`.
+    pub fn build_metered_transport(
+        &self,
+        stats: Arc<RestLatencyStats>,
+    ) -> Arc<dyn BinanceTransport> {
+        Arc::new(BudgetedTransport::new(
+            Arc::new(MeteredTransport::new(self.build_transport(), stats)),
+            RateBudget::shared(self.mode),
+        ))
     }
 
-    pub fn build_transport(&self) -> Arc<dyn BinanceTransport> {
+    fn build_http_transport(&self) -> Arc<dyn BinanceTransport> {
         let auth = BinanceAuth::new(self.api_key.clone(), self.secret_key.clone());
+        let client = self.network.http_client();
         match (
             &self.spot_base_url,
             &self.futures_base_url,
             &self.options_base_url,
         ) {
-            (Some(spot), Some(futures), Some(options)) => {
-                Arc::new(BinanceHttpTransport::with_base_urls(
+            (Some(spot), Some(futures), Some(options)) => Arc::new(
+                BinanceHttpTransport::with_base_urls(
                     auth,
                     spot.clone(),
                     futures.clone(),
                     options.clone(),
-                ))
-            }
+                )
+                .with_client(client),
+            ),
             _ => match self.mode {
-                BinanceMode::Real => Arc::new(BinanceHttpTransport::new(auth)),
-                BinanceMode::Demo => Arc::new(BinanceDemoHttpTransport::new(auth)),
+                BinanceMode::Real => Arc::new(BinanceHttpTransport::new(auth).with_client(client)),
+                BinanceMode::Demo => {
+                    Arc::new(BinanceDemoHttpTransport::new(auth).with_client(client))
+                }
             },
         }
     }
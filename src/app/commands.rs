@@ -1,12 +1,134 @@
+use crate::domain::instrument::Instrument;
+use crate::domain::order::{OpenOrder, OrderStatus};
+use crate::domain::position::Side;
 use crate::execution::command::ExecutionCommand;
+use crate::execution::dom_ladder::DomPrice;
+use crate::execution::history::OrderHistoryRow;
+use crate::market_data::price_alert::PriceAlertAction;
+use crate::storage::order_store::{HistoryQuery, TimeBucket};
+use crate::strategy::basis::BasisArbConfig;
 use crate::strategy::command::StrategyCommand;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum PortfolioView {
     Overview,
     Positions,
     Balances,
-    Orders,
+    Orders(OrderFilter),
+    History(OrderFilter),
+    /// Order and fill totals per time bucket from the order store.
+    ///
+    /// Example:
+    /// - `/history roi day tag=strategy:3`
+    HistoryRoi {
+        bucket: TimeBucket,
+        query: HistoryQuery,
+    },
+}
+
+/// Narrows the open-order view by structured order fields.
+///
+/// Example:
+/// - `/orders side=sell status=submitted tag=grid`
+/// - keeps only submitted sells whose client order id starts with `grid`
+///
+/// For `/history`, `tag` matches the history row tag (`manual`, `system`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrderFilter {
+    pub side: Option<Side>,
+    pub status: Option<OrderStatus>,
+    pub tag: Option<String>,
+}
+
+impl OrderFilter {
+    pub fn is_empty(&self) -> bool {
+        self.side.is_none() && self.status.is_none() && self.tag.is_none()
+    }
+
+    pub fn matches(&self, order: &OpenOrder) -> bool {
+        self.side.is_none_or(|side| order.side == side)
+            && self.status.is_none_or(|status| order.status == status)
+            && self
+                .tag
+                .as_deref()
+                .is_none_or(|tag| order.client_order_id.starts_with(tag))
+    }
+
+    /// Steps the side filter: any -> buy -> sell -> any.
+    pub fn cycle_side(&mut self) {
+        self.side = match self.side {
+            None => Some(Side::Buy),
+            Some(Side::Buy) => Some(Side::Sell),
+            Some(Side::Sell) => None,
+        };
+    }
+
+    /// Steps the status filter through every status, then back to any.
+    pub fn cycle_status(&mut self) {
+        self.status = match self.status {
+            None => Some(OrderStatus::PendingSubmit),
+            Some(OrderStatus::PendingSubmit) => Some(OrderStatus::Submitted),
+            Some(OrderStatus::Submitted) => Some(OrderStatus::Filled),
+            Some(OrderStatus::Filled) => Some(OrderStatus::Cancelled),
+            Some(OrderStatus::Cancelled) => Some(OrderStatus::Rejected),
+            Some(OrderStatus::Rejected) => None,
+        };
+    }
+
+    /// Steps the tag filter through `tags` in order, then back to any.
+    ///
+    /// Example:
+    /// - tags `[oco, strategy]`: any -> `oco` -> `strategy` -> any
+    pub fn cycle_tag(&mut self, tags: &BTreeSet<String>) {
+        self.tag = match &self.tag {
+            None => tags.iter().next().cloned(),
+            Some(current) => tags
+                .range::<String, _>((
+                    std::ops::Bound::Excluded(current),
+                    std::ops::Bound::Unbounded,
+                ))
+                .next()
+                .cloned(),
+        };
+    }
+
+    /// The `/orders` line that shows this filter.
+    ///
+    /// Example:
+    /// - side `Sell`, tag `oco` -> `/orders side=sell tag=oco`
+    pub fn orders_line(&self) -> String {
+        let mut line = "/orders".to_string();
+        if let Some(side) = self.side {
+            line.push_str(match side {
+                Side::Buy => " side=buy",
+                Side::Sell => " side=sell",
+            });
+        }
+        if let Some(status) = self.status {
+            line.push_str(match status {
+                OrderStatus::PendingSubmit => " status=pending",
+                OrderStatus::Submitted => " status=submitted",
+                OrderStatus::Filled => " status=filled",
+                OrderStatus::Cancelled => " status=cancelled",
+                OrderStatus::Rejected => " status=rejected",
+            });
+        }
+        if let Some(tag) = &self.tag {
+            line.push_str(&format!(" tag={tag}"));
+        }
+        line
+    }
+
+    pub fn matches_history(&self, row: &OrderHistoryRow) -> bool {
+        self.side.is_none_or(|side| row.side == side)
+            && self.status.is_none_or(|status| row.status == status)
+            && self
+                .tag
+                .as_deref()
+                .is_none_or(|tag| row.tag.starts_with(tag))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -14,5 +136,158 @@ pub enum AppCommand {
     Execution(ExecutionCommand),
     Strategy(StrategyCommand),
     Portfolio(PortfolioView),
+    Alert(AlertCommand),
+    Basis(BasisCommand),
     RefreshAuthoritativeState,
+    /// Re-reads non-credential settings and applies them in one step.
+    ReloadConfig,
+    /// Samples the host's own RSS, loop lag and strategy tick cost.
+    Diagnostics,
+    /// Zips redacted config, session state, recent events, network metrics
+    /// and version info to attach to a bug report.
+    ///
+    /// Example:
+    /// - `/diagnostics export` writes `var/diagnostics-<timestamp>.zip`
+    /// - `/diagnostics export /tmp/ws-drop.zip`
+    ExportDiagnostics {
+        /// `None` writes under `var/` with a timestamped name.
+        path: Option<PathBuf>,
+    },
+    Dom(DomCommand),
+    /// Polls OCO lists and shows their legs and repair state.
+    OcoStatus,
+    /// Polls iceberg orders and shows each parent's slice progress.
+    IcebergStatus,
+    /// Re-estimates and shows the EV of holding each bracketed position.
+    HoldEvStatus,
+    /// Compares each strategy watch's live fills and PnL with its paper twin.
+    ShadowStatus,
+    /// Cancels every open order on one instrument and closes it at market.
+    ///
+    /// Example:
+    /// - `/flatten` asks to confirm flattening the last instrument acted on
+    /// - `/flatten BTCUSDT confirm` flattens `BTCUSDT` right away
+    Flatten {
+        /// `None` targets the instrument of the most recent event.
+        instrument: Option<Instrument>,
+        confirmed: bool,
+    },
+    /// Disables every strategy watch and entries, then flattens every
+    /// instrument with a position or open order.
+    ///
+    /// Example:
+    /// - `/panic` shows what would be flattened and asks to confirm
+    /// - `/panic confirm` runs it and prints a reconciliation summary
+    Panic {
+        confirmed: bool,
+    },
+    /// Lists holdings opened outside the app alongside the adopted ones.
+    ExternalPositions,
+    /// Takes over an external holding under the `manual` tag.
+    ///
+    /// Example:
+    /// - `/adopt SOLUSDT 142.5` prices a spot `SOL` balance at `142.5`
+    AdoptPosition {
+        instrument: Instrument,
+        entry_price: f64,
+    },
+    /// Shows the inactivity flat mode timer and whether entries are disabled.
+    InactivityStatus,
+    /// Re-enables entries after the inactivity flat mode fired.
+    ResumeEntries,
+    News(NewsCommand),
+    /// Replays the persisted event file into the in-memory event log and
+    /// summarizes it, e.g. after a crash.
+    ///
+    /// Example:
+    /// - `/events rebuild` replays `SANDBOX_QUANT_EVENT_LOG_PATH`
+    /// - `/events rebuild domain=execution,strategy path=var/old.jsonl`
+    RebuildEvents {
+        /// `None` reads the configured event file.
+        path: Option<PathBuf>,
+        /// Event domains to replay; empty replays all of them.
+        domains: BTreeSet<String>,
+    },
+}
+
+/// Depth-of-market ladder, optionally placing or cancelling a limit order
+/// at one of its levels first.
+///
+/// Example:
+/// - `/dom BTCUSDT 20` shows 20 levels per side
+/// - `/dom BTCUSDT buy b1 0.01` joins the best bid
+/// - `/dom BTCUSDT cancel web-1` pulls a resting order
+#[derive(Debug, Clone, PartialEq)]
+pub struct DomCommand {
+    pub instrument: Instrument,
+    pub levels: usize,
+    pub action: Option<DomAction>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DomAction {
+    Place {
+        side: Side,
+        price: DomPrice,
+        qty: f64,
+    },
+    Cancel {
+        client_order_id: String,
+    },
+}
+
+/// The news pane and the entry blackout it offers.
+///
+/// Example:
+/// - `/news` lists recent headlines, traded assets in brackets
+/// - `/news blackout` (ctrl+b) holds new entries for the configured 30m
+/// - `/news blackout 10` holds them for 10m, `/news blackout off` lifts it
+#[derive(Debug, Clone, PartialEq)]
+pub enum NewsCommand {
+    Show,
+    Blackout {
+        /// `None` uses `SANDBOX_QUANT_NEWS_BLACKOUT_SECS`.
+        minutes: Option<u64>,
+    },
+    LiftBlackout,
+}
+
+/// Manages spot-perp basis watches, which hold a long spot / short perp
+/// hedge through the `<SYMBOL>_BASIS` synthetic.
+///
+/// Example:
+/// - `/basis start BTCUSDT entry=30 exit=5 target=0.1 funding=15`
+/// - `/basis stop 1` unwinds an open hedge, then removes the watch
+#[derive(Debug, Clone, PartialEq)]
+pub enum BasisCommand {
+    Start {
+        instrument: Instrument,
+        config: BasisArbConfig,
+    },
+    List,
+    Stop {
+        id: u64,
+    },
+}
+
+/// Manages horizontal price alert lines.
+///
+/// Example:
+/// - `/alert add BTCUSDT 70000 strategy price-sma-cross-long`
+/// - `/alert remove 3`
+/// - `/alert confirm 4` places the order a fired `target` alert is holding
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertCommand {
+    Add {
+        instrument: Instrument,
+        price: f64,
+        action: PriceAlertAction,
+    },
+    Remove {
+        id: u64,
+    },
+    Confirm {
+        id: u64,
+    },
+    List,
 }
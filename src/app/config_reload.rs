@@ -0,0 +1,528 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+
+use crate::app::bootstrap::AppBootstrap;
+use crate::app::inactivity::inactivity_flat_secs_from_vars;
+use crate::app::symbol_status::SymbolStatusConfig;
+use crate::domain::instrument::Instrument;
+use crate::exchange::facade::ExchangeFacade;
+use crate::exchange::types::FuturesLeverage;
+use crate::execution::depth_filter::DepthFilterConfig;
+use crate::execution::futures::leverage::futures_leverage_from_vars;
+use crate::execution::hold_ev::{hold_ev_profiles_from_vars, HoldEvProfile};
+use crate::execution::illiquid_window::{illiquid_windows_from_vars, IlliquidWindow};
+use crate::execution::max_age::max_position_age_from_vars;
+use crate::execution::paper_shadow::PaperShadowConfig;
+use crate::execution::service::parse_asset_exposure_caps;
+use crate::execution::slippage_guard::SlippageGuardConfig;
+use crate::execution::take_profit::{take_profit_profiles_from_vars, TakeProfitProfile};
+use crate::execution::trailing_stop::TrailingStopMode;
+use crate::market_data::news_feed::NewsFeedConfig;
+use crate::market_data::synthetic::{
+    register_synthetic_names, synthetic_instruments_from_vars, SyntheticInstrument,
+};
+use crate::observability::event_routing::{DomainRoute, EventRouting};
+use crate::portfolio::earn::EarnConfig;
+use crate::portfolio::entry_stats::taker_fee_rate_from_vars;
+use crate::strategy::overtrading::OvertradingLimit;
+use crate::ui::locale::UiLocale;
+use crate::ui::theme::UiTheme;
+use crate::ui::timezone::DisplayTimeZone;
+
+static RELOAD_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Settings that can change without restarting the process.
+///
+/// Credentials and endpoints are deliberately excluded; switching those
+/// still goes through `/mode` or a restart.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuntimeConfig {
+    pub asset_exposure_caps: BTreeMap<String, f64>,
+    pub locale: UiLocale,
+    pub theme: UiTheme,
+    pub time_zone: DisplayTimeZone,
+    pub trailing_stop_mode: TrailingStopMode,
+    pub depth_filter: Option<DepthFilterConfig>,
+    pub slippage_guard: Option<SlippageGuardConfig>,
+    pub take_profit_profiles: BTreeMap<String, TakeProfitProfile>,
+    pub hold_ev_profiles: BTreeMap<String, HoldEvProfile>,
+    pub synthetic_instruments: BTreeMap<Instrument, SyntheticInstrument>,
+    pub futures_leverage: BTreeMap<Instrument, FuturesLeverage>,
+    pub illiquid_windows: Vec<IlliquidWindow>,
+    pub max_position_age_secs: BTreeMap<Instrument, u64>,
+    pub paper_shadow: Option<PaperShadowConfig>,
+    pub event_routing: EventRouting,
+    pub inactivity_flat_secs: Option<u64>,
+    pub earn: EarnConfig,
+    pub news: NewsFeedConfig,
+    pub symbol_status: SymbolStatusConfig,
+    /// Per-side fee the entry EV and R figures are net of.
+    pub taker_fee_rate: f64,
+    pub overtrading: Option<OvertradingLimit>,
+}
+
+/// One setting whose value differs between two configs.
+///
+/// Example:
+/// - `asset_exposure_cap.BTC: 0.5 -> 0.25`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigChange {
+    pub key: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl RuntimeConfig {
+    /// Reads the reloadable settings from the process environment, falling
+    /// back to `.env` the way a reload does.
+    pub fn from_env() -> Self {
+        let dotenv = DotenvVars::read();
+        Self::from_vars(|key| dotenv.var(key))
+    }
+
+    /// Reads the reloadable settings through `var`, e.g. `DotenvVars::var`.
+    ///
+    /// Reads:
+    /// - `SANDBOX_QUANT_ASSET_EXPOSURE_CAPS`
+    /// - `SANDBOX_QUANT_UI_LOCALE`
+    /// - `SANDBOX_QUANT_UI_THEME`
+    /// - `SANDBOX_QUANT_DISPLAY_TZ`
+    /// - `SANDBOX_QUANT_TRAILING_STOP_MODE`
+    /// - `SANDBOX_QUANT_DEPTH_FILTER_*`
+    /// - `SANDBOX_QUANT_MAX_SLIPPAGE_BPS`
+    /// - `SANDBOX_QUANT_TAKE_PROFIT_PROFILES`
+    /// - `SANDBOX_QUANT_HOLD_EV_PROFILES`
+    /// - `SANDBOX_QUANT_SYNTHETIC_INSTRUMENTS`
+    /// - `SANDBOX_QUANT_FUTURES_LEVERAGE`
+    /// - `SANDBOX_QUANT_ILLIQUID_WINDOWS`
+    /// - `SANDBOX_QUANT_MAX_POSITION_AGE_SECS`
+    /// - `SANDBOX_QUANT_PAPER_SHADOW`
+    /// - `SANDBOX_QUANT_EVENT_ROUTES`
+    /// - `SANDBOX_QUANT_INACTIVITY_FLAT_SECS`
+    /// - `SANDBOX_QUANT_EARN_TRACKING`, `SANDBOX_QUANT_EARN_SWEEP_ABOVE_USDT`
+    /// - `SANDBOX_QUANT_NEWS_*`
+    /// - `SANDBOX_QUANT_SYMBOL_STATUS_SECS`, `SANDBOX_QUANT_HALT_FLATTEN`
+    /// - `SANDBOX_QUANT_TAKER_FEE_RATE`
+    /// - `SANDBOX_QUANT_OVERTRADING`
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            asset_exposure_caps: var("SANDBOX_QUANT_ASSET_EXPOSURE_CAPS")
+                .map(|raw| parse_asset_exposure_caps(&raw))
+                .unwrap_or_default(),
+            locale: UiLocale::from_vars(&var),
+            theme: UiTheme::from_vars(&var),
+            time_zone: DisplayTimeZone::from_vars(&var),
+            trailing_stop_mode: TrailingStopMode::from_vars(&var),
+            depth_filter: DepthFilterConfig::from_vars(&var),
+            slippage_guard: SlippageGuardConfig::from_vars(&var),
+            take_profit_profiles: take_profit_profiles_from_vars(&var),
+            hold_ev_profiles: hold_ev_profiles_from_vars(&var),
+            synthetic_instruments: synthetic_instruments_from_vars(&var),
+            futures_leverage: futures_leverage_from_vars(&var),
+            illiquid_windows: illiquid_windows_from_vars(&var),
+            max_position_age_secs: max_position_age_from_vars(&var),
+            paper_shadow: PaperShadowConfig::from_vars(&var),
+            event_routing: EventRouting::from_vars(&var),
+            inactivity_flat_secs: inactivity_flat_secs_from_vars(&var),
+            earn: EarnConfig::from_vars(&var),
+            news: NewsFeedConfig::from_vars(&var),
+            symbol_status: SymbolStatusConfig::from_vars(&var),
+            taker_fee_rate: taker_fee_rate_from_vars(&var),
+            overtrading: OvertradingLimit::from_vars(&var),
+        }
+    }
+
+    pub fn capture<E: ExchangeFacade>(app: &AppBootstrap<E>) -> Self {
+        Self {
+            asset_exposure_caps: app.execution.asset_exposure_caps.clone(),
+            locale: app.locale,
+            theme: app.theme,
+            time_zone: app.time_zone,
+            trailing_stop_mode: app.execution.trailing_stop_mode,
+            depth_filter: app.execution.depth_filter,
+            slippage_guard: app.execution.slippage_guard,
+            take_profit_profiles: app.execution.take_profit_profiles.clone(),
+            hold_ev_profiles: app.execution.hold_ev_profiles.clone(),
+            synthetic_instruments: app.execution.synthetic_instruments.clone(),
+            futures_leverage: app.execution.futures_leverage.clone(),
+            illiquid_windows: app.execution.illiquid_windows.clone(),
+            max_position_age_secs: app.execution.max_position_age_secs.clone(),
+            paper_shadow: app.execution.paper_shadow,
+            event_routing: app.event_log.routing.clone(),
+            inactivity_flat_secs: app.inactivity.timeout_secs,
+            earn: app.earn,
+            news: app.news.config.clone(),
+            symbol_status: app.symbol_status.config,
+            taker_fee_rate: app.portfolio_store.entry_stats.taker_fee_rate,
+            overtrading: app.strategy_store.overtrading,
+        }
+    }
+
+    pub fn apply<E: ExchangeFacade>(self, app: &mut AppBootstrap<E>) {
+        app.execution.asset_exposure_caps = self.asset_exposure_caps;
+        app.locale = self.locale;
+        app.theme = self.theme;
+        app.time_zone = self.time_zone;
+        app.execution.trailing_stop_mode = self.trailing_stop_mode;
+        app.execution.depth_filter = self.depth_filter;
+        app.execution.slippage_guard = self.slippage_guard;
+        app.execution.take_profit_profiles = self.take_profit_profiles;
+        app.execution.hold_ev_profiles = self.hold_ev_profiles;
+        register_synthetic_names(self.synthetic_instruments.keys());
+        app.execution.synthetic_instruments = self.synthetic_instruments;
+        app.execution.futures_leverage = self.futures_leverage;
+        app.execution.illiquid_windows = self.illiquid_windows;
+        app.execution.max_position_age_secs = self.max_position_age_secs;
+        app.execution.paper_shadow = self.paper_shadow;
+        app.event_log.routing = self.event_routing;
+        app.inactivity.timeout_secs = self.inactivity_flat_secs;
+        app.earn = self.earn;
+        app.news.config = self.news;
+        app.symbol_status.config = self.symbol_status;
+        app.portfolio_store.entry_stats.taker_fee_rate = self.taker_fee_rate;
+        app.strategy_store.overtrading = self.overtrading;
+        app.portfolio_store.earn_tracking = self.earn.tracking;
+        if !self.earn.tracking {
+            app.portfolio_store.earn_account = None;
+        }
+    }
+
+    /// Lists every setting that differs in `next`, caps keyed per asset.
+    pub fn diff(&self, next: &Self) -> Vec<ConfigChange> {
+        let mut changes = Vec::new();
+        let assets = self
+            .asset_exposure_caps
+            .keys()
+            .chain(next.asset_exposure_caps.keys())
+            .collect::<BTreeSet<_>>();
+        for asset in assets {
+            let before = self.asset_exposure_caps.get(asset);
+            let after = next.asset_exposure_caps.get(asset);
+            if before != after {
+                changes.push(ConfigChange {
+                    key: format!("asset_exposure_cap.{asset}"),
+                    before: format_cap(before),
+                    after: format_cap(after),
+                });
+            }
+        }
+        if self.locale != next.locale {
+            changes.push(ConfigChange {
+                key: "ui_locale".to_string(),
+                before: self.locale.as_str().to_string(),
+                after: next.locale.as_str().to_string(),
+            });
+        }
+        if self.theme != next.theme {
+            changes.push(ConfigChange {
+                key: "ui_theme".to_string(),
+                before: self.theme.as_str().to_string(),
+                after: next.theme.as_str().to_string(),
+            });
+        }
+        if self.time_zone != next.time_zone {
+            changes.push(ConfigChange {
+                key: "display_tz".to_string(),
+                before: self.time_zone.label(),
+                after: next.time_zone.label(),
+            });
+        }
+        if self.trailing_stop_mode != next.trailing_stop_mode {
+            changes.push(ConfigChange {
+                key: "trailing_stop_mode".to_string(),
+                before: format!("{:?}", self.trailing_stop_mode).to_ascii_lowercase(),
+                after: format!("{:?}", next.trailing_stop_mode).to_ascii_lowercase(),
+            });
+        }
+        let profiles = self
+            .take_profit_profiles
+            .keys()
+            .chain(next.take_profit_profiles.keys())
+            .collect::<BTreeSet<_>>();
+        for name in profiles {
+            let before = self.take_profit_profiles.get(name);
+            let after = next.take_profit_profiles.get(name);
+            if before != after {
+                changes.push(ConfigChange {
+                    key: format!("take_profit_profile.{name}"),
+                    before: format_profile(before),
+                    after: format_profile(after),
+                });
+            }
+        }
+        let hold_ev_profiles = self
+            .hold_ev_profiles
+            .keys()
+            .chain(next.hold_ev_profiles.keys())
+            .collect::<BTreeSet<_>>();
+        for name in hold_ev_profiles {
+            let before = self.hold_ev_profiles.get(name);
+            let after = next.hold_ev_profiles.get(name);
+            if before != after {
+                changes.push(ConfigChange {
+                    key: format!("hold_ev_profile.{name}"),
+                    before: before.map_or_else(|| "none".to_string(), HoldEvProfile::label),
+                    after: after.map_or_else(|| "none".to_string(), HoldEvProfile::label),
+                });
+            }
+        }
+        let synthetics = self
+            .synthetic_instruments
+            .keys()
+            .chain(next.synthetic_instruments.keys())
+            .collect::<BTreeSet<_>>();
+        for name in synthetics {
+            let before = self.synthetic_instruments.get(name);
+            let after = next.synthetic_instruments.get(name);
+            if before != after {
+                changes.push(ConfigChange {
+                    key: format!("synthetic_instrument.{}", name.0),
+                    before: format_synthetic(before),
+                    after: format_synthetic(after),
+                });
+            }
+        }
+        let leveraged = self
+            .futures_leverage
+            .keys()
+            .chain(next.futures_leverage.keys())
+            .collect::<BTreeSet<_>>();
+        for instrument in leveraged {
+            let before = self.futures_leverage.get(instrument);
+            let after = next.futures_leverage.get(instrument);
+            if before != after {
+                changes.push(ConfigChange {
+                    key: format!("futures_leverage.{}", instrument.0),
+                    before: before.map_or_else(|| "none".to_string(), FuturesLeverage::label),
+                    after: after.map_or_else(|| "none".to_string(), FuturesLeverage::label),
+                });
+            }
+        }
+        if self.illiquid_windows != next.illiquid_windows {
+            changes.push(ConfigChange {
+                key: "illiquid_windows".to_string(),
+                before: format_windows(&self.illiquid_windows),
+                after: format_windows(&next.illiquid_windows),
+            });
+        }
+        let aged = self
+            .max_position_age_secs
+            .keys()
+            .chain(next.max_position_age_secs.keys())
+            .collect::<BTreeSet<_>>();
+        for instrument in aged {
+            let before = self.max_position_age_secs.get(instrument);
+            let after = next.max_position_age_secs.get(instrument);
+            if before != after {
+                changes.push(ConfigChange {
+                    key: format!("max_position_age_secs.{}", instrument.0),
+                    before: before.map_or_else(|| "none".to_string(), u64::to_string),
+                    after: after.map_or_else(|| "none".to_string(), u64::to_string),
+                });
+            }
+        }
+        let domains = self
+            .event_routing
+            .routes
+            .keys()
+            .chain(next.event_routing.routes.keys())
+            .collect::<BTreeSet<_>>();
+        for domain in domains {
+            let before = self.event_routing.routes.get(domain);
+            let after = next.event_routing.routes.get(domain);
+            if before != after {
+                changes.push(ConfigChange {
+                    key: format!("event_route.{domain}"),
+                    before: format_route(before),
+                    after: format_route(after),
+                });
+            }
+        }
+        if self.depth_filter != next.depth_filter {
+            changes.push(ConfigChange {
+                key: "depth_filter".to_string(),
+                before: format_depth_filter(self.depth_filter),
+                after: format_depth_filter(next.depth_filter),
+            });
+        }
+        if self.slippage_guard != next.slippage_guard {
+            changes.push(ConfigChange {
+                key: "slippage_guard".to_string(),
+                before: format_slippage_guard(self.slippage_guard),
+                after: format_slippage_guard(next.slippage_guard),
+            });
+        }
+        if self.paper_shadow != next.paper_shadow {
+            let format_shadow = |shadow: Option<PaperShadowConfig>| {
+                shadow.map_or_else(|| "off".to_string(), |shadow| shadow.label())
+            };
+            changes.push(ConfigChange {
+                key: "paper_shadow".to_string(),
+                before: format_shadow(self.paper_shadow),
+                after: format_shadow(next.paper_shadow),
+            });
+        }
+        if self.inactivity_flat_secs != next.inactivity_flat_secs {
+            let format_secs =
+                |secs: Option<u64>| secs.map_or_else(|| "off".to_string(), |secs| secs.to_string());
+            changes.push(ConfigChange {
+                key: "inactivity_flat_secs".to_string(),
+                before: format_secs(self.inactivity_flat_secs),
+                after: format_secs(next.inactivity_flat_secs),
+            });
+        }
+        if self.earn != next.earn {
+            changes.push(ConfigChange {
+                key: "earn".to_string(),
+                before: self.earn.label(),
+                after: next.earn.label(),
+            });
+        }
+        if self.news != next.news {
+            changes.push(ConfigChange {
+                key: "news_feed".to_string(),
+                before: self.news.label(),
+                after: next.news.label(),
+            });
+        }
+        if self.symbol_status != next.symbol_status {
+            changes.push(ConfigChange {
+                key: "symbol_status".to_string(),
+                before: self.symbol_status.label(),
+                after: next.symbol_status.label(),
+            });
+        }
+        if self.taker_fee_rate != next.taker_fee_rate {
+            changes.push(ConfigChange {
+                key: "taker_fee_rate".to_string(),
+                before: self.taker_fee_rate.to_string(),
+                after: next.taker_fee_rate.to_string(),
+            });
+        }
+        if self.overtrading != next.overtrading {
+            let format_limit = |limit: Option<OvertradingLimit>| {
+                limit.map_or_else(|| "off".to_string(), |limit| limit.label())
+            };
+            changes.push(ConfigChange {
+                key: "overtrading".to_string(),
+                before: format_limit(self.overtrading),
+                after: format_limit(next.overtrading),
+            });
+        }
+        changes
+    }
+}
+
+fn format_profile(profile: Option<&TakeProfitProfile>) -> String {
+    profile
+        .map(TakeProfitProfile::label)
+        .unwrap_or_else(|| "none".to_string())
+}
+
+fn format_synthetic(synthetic: Option<&SyntheticInstrument>) -> String {
+    synthetic
+        .map(SyntheticInstrument::label)
+        .unwrap_or_else(|| "none".to_string())
+}
+
+fn format_route(route: Option<&DomainRoute>) -> String {
+    route
+        .map(DomainRoute::label)
+        .unwrap_or_else(|| "default".to_string())
+}
+
+fn format_depth_filter(filter: Option<DepthFilterConfig>) -> String {
+    filter
+        .map(|filter| filter.label())
+        .unwrap_or_else(|| "off".to_string())
+}
+
+fn format_slippage_guard(guard: Option<SlippageGuardConfig>) -> String {
+    guard
+        .map(|guard| guard.label())
+        .unwrap_or_else(|| "off".to_string())
+}
+
+fn format_windows(windows: &[IlliquidWindow]) -> String {
+    if windows.is_empty() {
+        return "off".to_string();
+    }
+    windows
+        .iter()
+        .map(IlliquidWindow::label)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn format_cap(cap: Option<&f64>) -> String {
+    cap.map(f64::to_string)
+        .unwrap_or_else(|| "none".to_string())
+}
+
+/// `.env` as it is on disk now, looked up behind the process environment,
+/// the same precedence startup reads with.
+///
+/// The environment itself is never written, so a reload cannot race other
+/// threads reading it.
+///
+/// Example:
+/// - `.env` has `SANDBOX_QUANT_UI_THEME=light`, nothing exported -> `Some("light")`
+/// - the shell also exports `SANDBOX_QUANT_UI_THEME=dark` -> `Some("dark")`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DotenvVars {
+    values: BTreeMap<String, String>,
+}
+
+impl DotenvVars {
+    /// Parses the nearest `.env`, then the crate's own `.env` for keys the
+    /// first one lacks; empty when both are missing or
+    /// `SANDBOX_QUANT_DISABLE_DOTENV` is set.
+    pub fn read() -> Self {
+        if env::var_os("SANDBOX_QUANT_DISABLE_DOTENV").is_some() {
+            return Self::default();
+        }
+        let manifest_dotenv = Path::new(env!("CARGO_MANIFEST_DIR")).join(".env");
+        let mut values = BTreeMap::new();
+        let entries = dotenvy::from_filename_iter(".env")
+            .into_iter()
+            .flatten()
+            .chain(
+                dotenvy::from_path_iter(manifest_dotenv)
+                    .into_iter()
+                    .flatten(),
+            );
+        for (key, value) in entries.filter_map(Result::ok) {
+            values.entry(key).or_insert(value);
+        }
+        Self { values }
+    }
+
+    pub fn var(&self, key: &str) -> Option<String> {
+        env::var(key).ok().or_else(|| self.values.get(key).cloned())
+    }
+}
+
+/// Routes `SIGHUP` to a reload request picked up by `take_reload_request`.
+#[cfg(unix)]
+pub fn install_sighup_reload_handler() {
+    extern "C" fn on_sighup(_signal: libc::c_int) {
+        RELOAD_REQUESTED.store(true, Ordering::Relaxed);
+    }
+    unsafe {
+        libc::signal(
+            libc::SIGHUP,
+            on_sighup as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+}
+
+#[cfg(not(unix))]
+pub fn install_sighup_reload_handler() {}
+
+pub fn take_reload_request() -> bool {
+    RELOAD_REQUESTED.swap(false, Ordering::Relaxed)
+}
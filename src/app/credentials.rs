@@ -0,0 +1,60 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::{Duration, Instant};
+
+/// Seconds between checks for rotated API keys when unset.
+pub const DEFAULT_CREDENTIAL_CHECK_SECS: u64 = 60;
+
+/// Reads:
+/// - `SANDBOX_QUANT_CREDENTIAL_CHECK_SECS` (default `60`; `0` checks only on reload)
+pub fn credential_check_secs_from_env() -> Option<u64> {
+    match std::env::var("SANDBOX_QUANT_CREDENTIAL_CHECK_SECS") {
+        Ok(value) => value.trim().parse::<u64>().ok().filter(|secs| *secs > 0),
+        Err(_) => Some(DEFAULT_CREDENTIAL_CHECK_SECS),
+    }
+}
+
+/// Notices a rotated API key pair so the REST client can be rebuilt
+/// without a restart.
+///
+/// Only a fingerprint of the active pair is kept, never the keys.
+///
+/// Example:
+/// - `check_secs=60`, a scheduled job rewrites `BINANCE_REAL_API_KEY` in `.env`
+/// - within a minute the client is rebuilt and `app.config.credentials_rotated` is logged
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CredentialWatch {
+    pub fingerprint: Option<u64>,
+    /// Keeps `BINANCE_*_BASE_URL` overrides on rebuild; `/mode` drops them.
+    pub keep_base_urls: bool,
+    /// Periodic checks are off when `None`; see `credential_check_secs_from_env`.
+    pub check_secs: Option<u64>,
+    pub last_checked_at: Option<Instant>,
+    /// Set after a failed check so a missing key is logged once, not every tick.
+    pub failing: bool,
+}
+
+impl CredentialWatch {
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.check_secs.is_some_and(|secs| {
+            self.last_checked_at
+                .is_none_or(|checked| now.duration_since(checked) >= Duration::from_secs(secs))
+        })
+    }
+}
+
+pub fn credentials_fingerprint(api_key: &str, secret_key: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (api_key, secret_key).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Example:
+/// - `vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A` -> `****Eh8A`
+pub fn mask_api_key(api_key: &str) -> String {
+    let tail = api_key
+        .char_indices()
+        .rev()
+        .nth(3)
+        .map_or(api_key, |(index, _)| &api_key[index..]);
+    format!("****{tail}")
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+
+/// Reads:
+/// - `SANDBOX_QUANT_INACTIVITY_FLAT_SECS` (e.g. `1800`; unset or `0` disables)
+pub fn inactivity_flat_secs_from_vars(var: impl Fn(&str) -> Option<String>) -> Option<u64> {
+    var("SANDBOX_QUANT_INACTIVITY_FLAT_SECS")
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+}
+
+/// Flattens an unattended session.
+///
+/// The guard fires once neither operator input nor a successful account
+/// refresh has been seen for `timeout_secs`, e.g. a frozen terminal or a
+/// dropped SSH session. It then stays tripped, with entries disabled, until
+/// the operator resumes.
+///
+/// Example:
+/// - `timeout_secs=1800`, last input `12:00`, last refresh `12:10`
+/// - due at `12:40`
+#[derive(Debug, Clone, PartialEq)]
+pub struct InactivityGuard {
+    /// Disabled when `None`; see `inactivity_flat_secs_from_vars`.
+    pub timeout_secs: Option<u64>,
+    pub last_input_at: DateTime<Utc>,
+    pub tripped_at: Option<DateTime<Utc>>,
+}
+
+impl Default for InactivityGuard {
+    fn default() -> Self {
+        Self {
+            timeout_secs: None,
+            last_input_at: Utc::now(),
+            tripped_at: None,
+        }
+    }
+}
+
+impl InactivityGuard {
+    pub fn record_input(&mut self, now: DateTime<Utc>) {
+        self.last_input_at = self.last_input_at.max(now);
+    }
+
+    /// Most recent sign of life from the operator or the exchange.
+    pub fn last_activity(&self, last_data_update: Option<DateTime<Utc>>) -> DateTime<Utc> {
+        last_data_update.map_or(self.last_input_at, |updated| {
+            updated.max(self.last_input_at)
+        })
+    }
+
+    pub fn idle_secs(&self, now: DateTime<Utc>, last_data_update: Option<DateTime<Utc>>) -> u64 {
+        (now - self.last_activity(last_data_update))
+            .num_seconds()
+            .max(0) as u64
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>, last_data_update: Option<DateTime<Utc>>) -> bool {
+        self.tripped_at.is_none()
+            && self
+                .timeout_secs
+                .is_some_and(|timeout| self.idle_secs(now, last_data_update) >= timeout)
+    }
+
+    pub fn trip(&mut self, now: DateTime<Utc>) {
+        self.tripped_at = Some(now);
+    }
+
+    /// Re-arms the guard; counts as operator input.
+    pub fn resume(&mut self, now: DateTime<Utc>) {
+        self.tripped_at = None;
+        self.record_input(now);
+    }
+}
@@ -1,6 +1,11 @@
 pub mod bootstrap;
 pub mod cli;
 pub mod commands;
+pub mod config_reload;
+pub mod credentials;
+pub mod inactivity;
 pub mod output;
+pub mod resource_profile;
 pub mod runtime;
 pub mod shell;
+pub mod symbol_status;
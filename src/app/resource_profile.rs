@@ -0,0 +1,260 @@
+use std::mem::size_of;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::app::bootstrap::AppBootstrap;
+use crate::exchange::facade::ExchangeFacade;
+use crate::execution::history::{OrderHistoryRow, DEFAULT_ORDER_HISTORY_CAPACITY};
+use crate::market_data::news_feed::{FlaggedHeadline, NEWS_PANE_LIMIT};
+use crate::observability::self_metrics::{process_rss_bytes, SAMPLE_CAPACITY};
+use crate::storage::models::EventRecord;
+use crate::strategy::trace::{StrategyTraceEntry, DEFAULT_TRACE_CAPACITY};
+use crate::visualization::annotations::{
+    ChartRetention, DEFAULT_MAX_ANNOTATIONS, DEFAULT_MAX_FILL_MARKERS,
+};
+
+/// How much memory and refresh work the host can spare.
+///
+/// `Low` targets Raspberry-Pi-class hosts: every in-memory buffer is capped,
+/// refreshes slow down and the GUI drops its optional panes.
+///
+/// `Fast` targets colocated servers running latency-sensitive strategies:
+/// the order path runs ahead of bookkeeping and the operator surface is cut
+/// down to a status line; see `latency_first`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResourceProfile {
+    #[default]
+    Standard,
+    Low,
+    Fast,
+}
+
+impl ResourceProfile {
+    /// Reads the profile from `SANDBOX_QUANT_RESOURCE_PROFILE`.
+    ///
+    /// Example:
+    /// - `low`, `pi` -> `Low`
+    /// - `fast`, `colo` -> `Fast`
+    /// - unset or anything else -> `Standard`
+    pub fn from_env() -> Self {
+        std::env::var("SANDBOX_QUANT_RESOURCE_PROFILE")
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "standard" | "default" => Some(Self::Standard),
+            "low" | "low-memory" | "pi" => Some(Self::Low),
+            "fast" | "colo" | "latency" => Some(Self::Fast),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Low => "low",
+            Self::Fast => "fast",
+        }
+    }
+
+    pub fn limits(self) -> BufferLimits {
+        match self {
+            Self::Standard => BufferLimits {
+                event_records: None,
+                order_history_rows: DEFAULT_ORDER_HISTORY_CAPACITY,
+                trace_entries: DEFAULT_TRACE_CAPACITY,
+                latency_samples: SAMPLE_CAPACITY,
+                news_headlines: NEWS_PANE_LIMIT,
+                chart_candles: None,
+                fill_markers: DEFAULT_MAX_FILL_MARKERS,
+                annotations: DEFAULT_MAX_ANNOTATIONS,
+            },
+            Self::Low => BufferLimits {
+                event_records: Some(500),
+                order_history_rows: 100,
+                trace_entries: 50,
+                latency_samples: 64,
+                news_headlines: 20,
+                chart_candles: Some(2_000),
+                fill_markers: 100,
+                annotations: 50,
+            },
+            // A bounded event log keeps the session's memory flat.
+            Self::Fast => BufferLimits {
+                event_records: Some(2_000),
+                ..Self::Standard.limits()
+            },
+        }
+    }
+
+    /// How long the shell waits for a key before its idle work (alerts,
+    /// news, credential and symbol checks), and the daemon supervisor period.
+    ///
+    /// `Fast` ticks often so pushed fills and order updates reach the
+    /// strategies with little delay.
+    pub fn idle_tick(self) -> Duration {
+        match self {
+            Self::Standard => Duration::from_secs(1),
+            Self::Low => Duration::from_secs(5),
+            Self::Fast => Duration::from_millis(100),
+        }
+    }
+
+    /// How often the GUI repaints without input.
+    pub fn gui_repaint(self) -> Duration {
+        match self {
+            Self::Standard | Self::Fast => Duration::from_secs(1),
+            Self::Low => Duration::from_secs(5),
+        }
+    }
+
+    /// Whether the signal-to-order path comes before everything else.
+    ///
+    /// Refreshes check exits and entries before alerts, external positions
+    /// and the earn sweep, and skip the daily PnL, funding and margin ratio
+    /// lookups; the shell stops polling news and prints a one-line intro
+    /// and a bare status prompt.
+    pub fn latency_first(self) -> bool {
+        self == Self::Fast
+    }
+
+    /// Whether the GUI offers its optional panes: custom charts, strategy
+    /// focus and the open interest and volume profile context panes.
+    pub fn full_panels(self) -> bool {
+        self == Self::Standard
+    }
+
+    /// Chart retention capped to this profile; explicit
+    /// `SANDBOX_QUANT_CHART_*` values lower than the caps are kept.
+    pub fn chart_retention(self, retention: ChartRetention) -> ChartRetention {
+        let limits = self.limits();
+        ChartRetention {
+            max_fill_markers: retention.max_fill_markers.min(limits.fill_markers),
+            max_annotations: retention.max_annotations.min(limits.annotations),
+            ..retention
+        }
+    }
+}
+
+/// Entry caps for the in-memory buffers of one profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BufferLimits {
+    /// `None` keeps every event of the session.
+    pub event_records: Option<usize>,
+    pub order_history_rows: usize,
+    /// Per debug-enabled watch.
+    pub trace_entries: usize,
+    /// Per self-metrics timing (loop lag, strategy tick).
+    pub latency_samples: usize,
+    pub news_headlines: usize,
+    /// Newest candles the GUI keeps per loaded range; `None` keeps all.
+    pub chart_candles: Option<usize>,
+    pub fill_markers: usize,
+    pub annotations: usize,
+}
+
+/// Entries held by one buffer and their approximate heap size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BufferUsage {
+    pub buffer: &'static str,
+    pub entries: usize,
+    pub limit: Option<usize>,
+    pub approx_bytes: u64,
+}
+
+/// Rough footprint of the operator host's buffers next to the process RSS.
+///
+/// Example:
+/// - `profile=low buffers=0.3MB rss=38.2MB events=500/500 orders=12/100`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MemoryEstimate {
+    pub profile: &'static str,
+    pub buffers: Vec<BufferUsage>,
+    pub buffer_bytes: u64,
+    pub rss_bytes: Option<u64>,
+}
+
+impl MemoryEstimate {
+    pub fn of<E: ExchangeFacade>(app: &AppBootstrap<E>) -> Self {
+        let limits = app.resource_profile.limits();
+        let records = &app.event_log.records;
+        let orders = app.execution.history.rows();
+        let traces = app.strategy_store.traces();
+        let headlines = app.news.headlines().collect::<Vec<_>>();
+        let buffers = vec![
+            BufferUsage {
+                buffer: "events",
+                entries: records.len(),
+                limit: app.event_log.capacity,
+                approx_bytes: records
+                    .iter()
+                    .map(|record| {
+                        size_of::<EventRecord>() + record.kind.len() + json_len(&record.payload)
+                    })
+                    .sum::<usize>() as u64,
+            },
+            BufferUsage {
+                buffer: "orders",
+                entries: orders.len(),
+                limit: Some(app.execution.history.capacity()),
+                approx_bytes: orders
+                    .iter()
+                    .map(|row| {
+                        size_of::<OrderHistoryRow>() + row.tag.len() + row.instrument.0.len()
+                    })
+                    .sum::<usize>() as u64,
+            },
+            BufferUsage {
+                buffer: "traces",
+                entries: traces.clone().map(|trace| trace.entries().len()).sum(),
+                limit: Some(limits.trace_entries * traces.clone().count()),
+                approx_bytes: traces
+                    .flat_map(|trace| trace.entries())
+                    .map(|entry| {
+                        size_of::<StrategyTraceEntry>()
+                            + entry.decision.len()
+                            + entry
+                                .indicators
+                                .keys()
+                                .map(|key| key.len() + size_of::<(String, f64)>())
+                                .sum::<usize>()
+                    })
+                    .sum::<usize>() as u64,
+            },
+            BufferUsage {
+                buffer: "latency_samples",
+                entries: app.self_metrics.sample_count(),
+                limit: Some(app.self_metrics.sample_capacity()),
+                approx_bytes: (app.self_metrics.sample_count() * size_of::<f64>()) as u64,
+            },
+            BufferUsage {
+                buffer: "news",
+                entries: headlines.len(),
+                limit: Some(app.news.pane_limit()),
+                approx_bytes: headlines
+                    .iter()
+                    .map(|flagged| {
+                        size_of::<FlaggedHeadline>()
+                            + flagged.headline.id.len()
+                            + flagged.headline.title.len()
+                            + flagged.assets.iter().map(String::len).sum::<usize>()
+                    })
+                    .sum::<usize>() as u64,
+            },
+        ];
+        Self {
+            profile: app.resource_profile.as_str(),
+            buffer_bytes: buffers.iter().map(|buffer| buffer.approx_bytes).sum(),
+            buffers,
+            rss_bytes: process_rss_bytes(),
+        }
+    }
+}
+
+fn json_len(value: &serde_json::Value) -> usize {
+    serde_json::to_vec(value).map_or(0, |bytes| bytes.len())
+}
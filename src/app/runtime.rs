@@ -1,13 +1,68 @@
 use crate::app::bootstrap::AppBootstrap;
-use crate::app::commands::AppCommand;
+use crate::app::commands::{
+    AlertCommand, AppCommand, BasisCommand, DomAction, DomCommand, NewsCommand, PortfolioView,
+};
+use crate::app::config_reload::{DotenvVars, RuntimeConfig};
+use crate::app::resource_profile::MemoryEstimate;
+use crate::app::symbol_status::is_halt_notice;
+use crate::domain::exposure::Exposure;
 use crate::domain::instrument::Instrument;
 use crate::domain::market::Market;
-use crate::execution::command::ExecutionCommand;
+use crate::domain::order_type::OrderType;
+use crate::domain::reason::ExitReason;
+use crate::error::alert_error::AlertError;
+use crate::error::error_class::ErrorClass;
+use crate::error::execution_error::ExecutionError;
+use crate::error::plugin_error::PluginError;
+use crate::error::strategy_error::StrategyError;
+use crate::error::sync_error::SyncError;
+use crate::exchange::binance::user_stream::{OrderUpdate, UserStreamEvent};
+use crate::exchange::types::{EarnPosition, EarnTransfer, EarnTransferKind};
+use crate::execution::command::{CommandSource, ExecutionCommand};
+use crate::execution::dom_ladder::build_dom_ladder;
+use crate::execution::funnel::{strategy_watch_id, WatchFunnel};
+use crate::execution::hold_ev::{HoldEvEstimate, HoldEvEvent};
+use crate::execution::iceberg::{IcebergEvent, IcebergOrder};
+use crate::execution::oco::{OcoEvent, OcoLeg};
+use crate::execution::paper_shadow::ShadowDivergence;
 use crate::execution::price_source::PriceSource;
-use crate::storage::event_log::log;
-use crate::strategy::command::StrategyCommand;
+use crate::execution::service::{command_source, ExecutionOutcome};
+use crate::execution::take_profit::TakeProfitEvent;
+use crate::market_data::news_feed::NewsHeadline;
+use crate::market_data::price_alert::{PriceAlert, PriceAlertAction};
+use crate::market_data::synthetic::SyntheticInstrument;
+use crate::observability::diagnostics_bundle::{
+    default_bundle_path, redacted_config, DiagnosticsBundle, DEFAULT_BUNDLE_EVENTS,
+};
+use crate::observability::event_routing::{event_domain, LogSink};
+use crate::portfolio::adoption::{
+    detect_external_positions, ExternalPosition, ADOPTED_POSITION_TAG,
+};
+use crate::portfolio::asset_exposure::base_asset;
+use crate::portfolio::earn::{plan_redeem, EARN_REDEEM_HEADROOM};
+use crate::storage::event_log::{event_log_path, log, EVENT_SCHEMA_VERSION};
+use crate::storage::event_replay::replay_event_file;
+use crate::storage::order_store::{FillRow, HISTORY_ROI_BUCKETS};
+use crate::storage::sealed;
+use crate::strategy::adaptive::{Adaptation, AdaptiveTuning};
+use crate::strategy::basis::{BasisArbWatch, BasisCarry, BasisDecision, BasisQuote};
+use crate::strategy::command::{
+    OpenPositionResolution, StrategyCommand, StrategyGroupAction, StrategyStartConfig,
+};
+use crate::strategy::filter::StrategyFilter;
+use crate::strategy::live::{entry_exposure, LiveBarEntry, LiveBarStrategy};
+use crate::strategy::model::{StrategyWatch, StrategyWatchState};
+use crate::strategy::plugin::{discover_plugins, plugin_dir_from_env};
+use crate::strategy::ramp::SizeRamp;
+use crate::strategy::registry::{StrategyRegistry, StrategySignal};
+use crate::strategy::trace::{evaluate_closes, StrategyTraceEntry};
+use crate::terminal::macros::ShellMacroStore;
+use chrono::{DateTime, Utc};
 use serde_json::json;
-use tracing::info;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Instant;
+use tracing::{info, warn};
 
 #[derive(Debug, Default)]
 pub struct AppRuntime {
@@ -27,18 +82,604 @@ impl AppRuntime {
         &mut self,
         app: &mut AppBootstrap<E>,
         command: AppCommand,
+    ) -> Result<(), crate::error::app_error::AppError> {
+        let started = Instant::now();
+        app.inactivity.record_input(Utc::now());
+        let result = self.dispatch(app, command);
+        app.self_metrics.record_loop_lag(started.elapsed());
+        result
+    }
+
+    /// Closes every position and disables entries once the session has been
+    /// unattended for the configured period. Returns true when it fired.
+    ///
+    /// Meant for the shell's idle tick, so it does not count as input.
+    pub fn check_inactivity<
+        E: crate::exchange::facade::ExchangeFacade<
+            Error = crate::error::exchange_error::ExchangeError,
+        >,
+    >(
+        &mut self,
+        app: &mut AppBootstrap<E>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let refreshed_at = app.portfolio_store.refreshed_at;
+        if !app.inactivity.is_due(now, refreshed_at) {
+            return false;
+        }
+        let idle_secs = app.inactivity.idle_secs(now, refreshed_at);
+        app.inactivity.trip(now);
+        app.execution.entries_disabled = true;
+        let dropped_deferred = std::mem::take(&mut app.execution.deferred_executions).len();
+        warn!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            idle_secs,
+            "session unattended; flattening and disabling entries"
+        );
+        let result = self.dispatch(
+            app,
+            AppCommand::Execution(ExecutionCommand::CloseAll {
+                source: CommandSource::System,
+            }),
+        );
+        let timeout_secs = app.inactivity.timeout_secs;
+        match result {
+            Ok(()) => {
+                let close_all = app
+                    .event_log
+                    .records
+                    .last()
+                    .filter(|record| record.kind == "app.execution.completed")
+                    .map(|record| record.payload.clone());
+                log(
+                    &mut app.event_log,
+                    "app.safety.inactivity_flattened",
+                    json!({
+                        "idle_secs": idle_secs,
+                        "timeout_secs": timeout_secs,
+                        "dropped_deferred": dropped_deferred,
+                        "close_all": close_all,
+                    }),
+                );
+            }
+            Err(error) => log(
+                &mut app.event_log,
+                "app.safety.inactivity_flatten_failed",
+                json!({
+                    "idle_secs": idle_secs,
+                    "timeout_secs": timeout_secs,
+                    "dropped_deferred": dropped_deferred,
+                    "error": error.to_string(),
+                }),
+            ),
+        }
+        true
+    }
+
+    /// Fires the price alerts crossed since the last look, logging a failed
+    /// check. Returns true when any alert fired.
+    ///
+    /// Meant for the shell's idle tick, so it does not count as input.
+    pub fn check_price_alerts<
+        E: crate::exchange::facade::ExchangeFacade<
+            Error = crate::error::exchange_error::ExchangeError,
+        >,
+    >(
+        &mut self,
+        app: &mut AppBootstrap<E>,
+    ) -> bool {
+        let events_before = app.event_log.records.len();
+        if let Err(error) = check_price_alerts(app) {
+            log(
+                &mut app.event_log,
+                "app.alert.check_failed",
+                json!({ "error": error.to_string() }),
+            );
+        }
+        app.event_log
+            .records
+            .iter()
+            .skip(events_before)
+            .any(|record| record.kind == "app.alert.triggered")
+    }
+
+    /// Checks every traded symbol's listing status once the watch is due.
+    ///
+    /// A symbol that stops trading holds back new entries, pauses the
+    /// strategy watches on it and, with `SANDBOX_QUANT_HALT_FLATTEN`, gets
+    /// its open position closed; one that trades again is released.
+    /// Returns true when any symbol changed.
+    ///
+    /// Meant for the shell's idle tick, so it does not count as input.
+    pub fn check_symbol_status<
+        E: crate::exchange::facade::ExchangeFacade<
+            Error = crate::error::exchange_error::ExchangeError,
+        >,
+    >(
+        &mut self,
+        app: &mut AppBootstrap<E>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        if !app.symbol_status.is_due(now) {
+            return false;
+        }
+        app.symbol_status.last_checked_at = Some(now);
+        let mut changed = false;
+        let mut stopped = Vec::new();
+        for instrument in traded_symbols(app) {
+            let market = match app.portfolio_store.snapshot.positions.get(&instrument) {
+                Some(position) => position.market,
+                None => match app.execution.order_market(
+                    &app.exchange,
+                    &app.portfolio_store,
+                    &instrument,
+                ) {
+                    Ok(market) => market,
+                    Err(_) => continue,
+                },
+            };
+            let status = match app.exchange.load_symbol_status(&instrument, market) {
+                Ok(Some(status)) => status,
+                Ok(None) => continue,
+                Err(error) => {
+                    log(
+                        &mut app.event_log,
+                        "app.symbol.status_check_failed",
+                        json!({
+                            "instrument": instrument.0,
+                            "market": format!("{market:?}").to_ascii_lowercase(),
+                            "error": error.to_string(),
+                        }),
+                    );
+                    continue;
+                }
+            };
+            if status.is_trading() {
+                if let Some(previous) = app.execution.halted_symbols.remove(&instrument) {
+                    changed = true;
+                    info!(
+                        service = "trading-engine",
+                        mode = app.mode.as_str(),
+                        instrument = instrument.0,
+                        "symbol trading again; entries released"
+                    );
+                    log(
+                        &mut app.event_log,
+                        "app.symbol.resumed",
+                        json!({
+                            "instrument": instrument.0,
+                            "market": format!("{market:?}").to_ascii_lowercase(),
+                            "previous_status": previous.status(),
+                        }),
+                    );
+                }
+                continue;
+            }
+            if app.execution.halted_symbols.get(&instrument) == Some(&status) {
+                continue;
+            }
+            changed = true;
+            warn!(
+                service = "trading-engine",
+                mode = app.mode.as_str(),
+                instrument = instrument.0,
+                status = status.status(),
+                "symbol {}; entries held and strategy watches paused",
+                status.kind()
+            );
+            log(
+                &mut app.event_log,
+                "app.symbol.halted",
+                json!({
+                    "instrument": instrument.0,
+                    "market": format!("{market:?}").to_ascii_lowercase(),
+                    "kind": status.kind(),
+                    "status": status.status(),
+                }),
+            );
+            app.execution
+                .halted_symbols
+                .insert(instrument.clone(), status);
+            stopped.push(instrument);
+        }
+        sync_paused_watches(app);
+        if app.symbol_status.config.flatten {
+            for instrument in stopped {
+                let is_open = app
+                    .portfolio_store
+                    .snapshot
+                    .positions
+                    .get(&instrument)
+                    .is_some_and(|position| !position.is_flat());
+                if is_open {
+                    self.flatten_halted_symbol(app, instrument);
+                }
+            }
+        }
+        changed
+    }
+
+    /// Closes the position on a symbol that stopped trading. A close the
+    /// venue refuses is logged and left for the operator.
+    fn flatten_halted_symbol<
+        E: crate::exchange::facade::ExchangeFacade<
+            Error = crate::error::exchange_error::ExchangeError,
+        >,
+    >(
+        &mut self,
+        app: &mut AppBootstrap<E>,
+        instrument: Instrument,
+    ) {
+        let result = self.dispatch(
+            app,
+            AppCommand::Execution(ExecutionCommand::CloseSymbol {
+                instrument: instrument.clone(),
+                source: CommandSource::System,
+            }),
+        );
+        match result {
+            Ok(()) => log(
+                &mut app.event_log,
+                "app.symbol.flattened",
+                json!({ "instrument": instrument.0 }),
+            ),
+            Err(error) => {
+                warn!(
+                    service = "trading-engine",
+                    mode = app.mode.as_str(),
+                    instrument = instrument.0,
+                    error = %error,
+                    "flatten of halted symbol failed"
+                );
+                log(
+                    &mut app.event_log,
+                    "app.symbol.flatten_failed",
+                    json!({
+                        "instrument": instrument.0,
+                        "error": error.to_string(),
+                    }),
+                );
+            }
+        }
+    }
+
+    /// Files a news poll into the `/news` pane and logs each fresh headline
+    /// that mentions a traded asset or a major keyword.
+    ///
+    /// Traded assets are the base assets of `traded_symbols`. A halt or
+    /// delisting notice for one moves the next symbol status check up.
+    pub fn ingest_news<
+        E: crate::exchange::facade::ExchangeFacade<
+            Error = crate::error::exchange_error::ExchangeError,
+        >,
+    >(
+        &mut self,
+        app: &mut AppBootstrap<E>,
+        fetched: anyhow::Result<Vec<NewsHeadline>>,
+        now: DateTime<Utc>,
+    ) {
+        let fetched = match fetched {
+            Ok(fetched) => fetched,
+            Err(error) => {
+                app.news.record_failure(now, format!("{error:#}"));
+                log(
+                    &mut app.event_log,
+                    "app.news.poll_failed",
+                    json!({ "error": format!("{error:#}") }),
+                );
+                return;
+            }
+        };
+        let traded_assets = traded_symbols(app)
+            .iter()
+            .filter_map(base_asset)
+            .map(str::to_string)
+            .collect::<BTreeSet<_>>();
+        for flagged in app.news.ingest(fetched, &traded_assets, now) {
+            if !flagged.is_notable() {
+                continue;
+            }
+            if !flagged.assets.is_empty() && is_halt_notice(&flagged.headline.title) {
+                app.symbol_status.expedite();
+                warn!(
+                    service = "trading-engine",
+                    mode = app.mode.as_str(),
+                    assets = %flagged.assets.join(","),
+                    title = %flagged.headline.title,
+                    "halt notice for a traded asset; checking symbol status"
+                );
+                log(
+                    &mut app.event_log,
+                    "app.symbol.halt_notice",
+                    json!({
+                        "title": flagged.headline.title,
+                        "assets": flagged.assets,
+                    }),
+                );
+            }
+            log(
+                &mut app.event_log,
+                "app.news.headline",
+                json!({
+                    "title": flagged.headline.title,
+                    "highlighted": flagged.highlighted_title(),
+                    "link": flagged.headline.link,
+                    "published_at": flagged.headline.published_at.map(|time| time.to_rfc3339()),
+                    "assets": flagged.assets,
+                    "major": flagged.major,
+                }),
+            );
+        }
+    }
+
+    /// Applies an account update pushed by the user data stream, so fills
+    /// and balances show without waiting for the next refresh.
+    ///
+    /// A dropped stream marks the account state stale until a refresh, as
+    /// updates sent while it was down are lost.
+    pub fn ingest_user_stream_event<E: crate::exchange::facade::ExchangeFacade>(
+        &mut self,
+        app: &mut AppBootstrap<E>,
+        event: UserStreamEvent,
+        now: DateTime<Utc>,
+    ) {
+        match event {
+            UserStreamEvent::Connected { market } => log(
+                &mut app.event_log,
+                "app.user_stream.connected",
+                json!({ "market": format!("{market:?}").to_ascii_lowercase() }),
+            ),
+            UserStreamEvent::Disconnected { market, error } => {
+                app.portfolio_store.mark_account_state_stale();
+                log(
+                    &mut app.event_log,
+                    "app.user_stream.disconnected",
+                    json!({
+                        "market": format!("{market:?}").to_ascii_lowercase(),
+                        "error": error,
+                    }),
+                );
+            }
+            UserStreamEvent::OrderUpdate(update) => {
+                let order = &update.order;
+                let payload = json!({
+                    "instrument": order.instrument.0,
+                    "market": format!("{:?}", order.market).to_ascii_lowercase(),
+                    "client_order_id": order.client_order_id,
+                    "side": format!("{:?}", order.side).to_ascii_lowercase(),
+                    "status": update.venue_status.to_ascii_lowercase(),
+                    "open": update.is_open(),
+                    "orig_qty": order.orig_qty,
+                    "executed_qty": order.executed_qty,
+                    "fill_qty": update.last_fill_qty,
+                    "fill_price": update.last_fill_price,
+                    "event_time_ms": update.event_time_ms,
+                });
+                if update.is_fill() {
+                    app.execution.funnel.on_fill(&order.client_order_id);
+                    persist_fill(app, &update, now);
+                    if let (Some(watch_id), Some(price)) = (
+                        strategy_watch_id(&order.client_order_id),
+                        update.last_fill_price,
+                    ) {
+                        app.strategy_store.record_fill(
+                            watch_id,
+                            order.side,
+                            update.last_fill_qty,
+                            price,
+                            fill_commission_usdt(&update),
+                        );
+                    }
+                }
+                let shadowed = update
+                    .last_fill_price
+                    .filter(|_| update.is_fill())
+                    .and_then(|price| {
+                        app.execution.apply_shadow_fill(
+                            &order.client_order_id,
+                            update.last_fill_qty,
+                            price,
+                        )
+                    })
+                    .map(|shadow| {
+                        json!({
+                            "watch_id": shadow.watch_id,
+                            "client_order_id": shadow.client_order_id,
+                            "instrument": shadow.instrument.0,
+                            "side": format!("{:?}", shadow.side).to_ascii_lowercase(),
+                            "decision_price": shadow.decision_price,
+                            "paper_price": shadow.paper_price,
+                            "fill_price": update.last_fill_price,
+                            "live_avg_price": shadow.live_avg_price(),
+                            "live_filled_qty": shadow.live_filled_qty,
+                            "qty": shadow.qty,
+                        })
+                    });
+                app.execution.apply_iceberg_report(&update.order);
+                app.portfolio_store.apply_order_update(update.order);
+                log(&mut app.event_log, "app.user_stream.order_update", payload);
+                if let Some(shadowed) = shadowed {
+                    log(&mut app.event_log, "app.shadow.live_fill", shadowed);
+                }
+            }
+            UserStreamEvent::BalanceUpdate(update) => {
+                let payload = json!({
+                    "market": format!("{:?}", update.market).to_ascii_lowercase(),
+                    "assets": update
+                        .balances
+                        .iter()
+                        .map(|balance| balance.asset.clone())
+                        .collect::<Vec<_>>(),
+                    "positions": update
+                        .positions
+                        .iter()
+                        .map(|position| position.instrument.0.clone())
+                        .collect::<Vec<_>>(),
+                    "event_time_ms": update.event_time_ms,
+                });
+                app.portfolio_store.apply_balance_update(
+                    update.market,
+                    update.balances,
+                    update.positions,
+                    now,
+                );
+                log(
+                    &mut app.event_log,
+                    "app.user_stream.balance_update",
+                    payload,
+                );
+            }
+        }
+    }
+
+    /// Runs the ladder's order action through the normal execution path,
+    /// then logs the ladder with the refreshed resting orders.
+    fn run_dom_command<
+        E: crate::exchange::facade::ExchangeFacade<
+            Error = crate::error::exchange_error::ExchangeError,
+        >,
+    >(
+        &mut self,
+        app: &mut AppBootstrap<E>,
+        command: DomCommand,
+    ) -> Result<(), crate::error::app_error::AppError> {
+        let market =
+            app.execution
+                .order_market(&app.exchange, &app.portfolio_store, &command.instrument)?;
+        let depth = app
+            .exchange
+            .load_order_book_depth(&command.instrument, market)?
+            .unwrap_or_default();
+        let action = match &command.action {
+            Some(DomAction::Place { side, price, qty }) => {
+                let price = price
+                    .resolve(&depth)
+                    .ok_or(ExecutionError::MissingPriceContext)?;
+                Some(ExecutionCommand::PlaceLimitOrder {
+                    instrument: command.instrument.clone(),
+                    side: *side,
+                    qty: *qty,
+                    price,
+                    source: CommandSource::User,
+                })
+            }
+            Some(DomAction::Cancel { client_order_id }) => Some(ExecutionCommand::CancelOrder {
+                instrument: command.instrument.clone(),
+                client_order_id: client_order_id.clone(),
+                source: CommandSource::User,
+            }),
+            None => None,
+        };
+        let action_payload = match action {
+            Some(execution) => {
+                self.dispatch(app, AppCommand::Execution(execution))?;
+                self.record_command(AppCommand::Dom(command.clone()));
+                app.event_log
+                    .records
+                    .last()
+                    .filter(|record| record.kind == "app.execution.completed")
+                    .map(|record| record.payload.clone())
+            }
+            None => {
+                app.portfolio_sync
+                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+                None
+            }
+        };
+        let open_orders = app
+            .portfolio_store
+            .snapshot
+            .open_orders
+            .get(&command.instrument)
+            .cloned()
+            .unwrap_or_default();
+        let ladder = build_dom_ladder(&depth, &open_orders, command.levels);
+        log(
+            &mut app.event_log,
+            "app.dom.loaded",
+            json!({
+                "instrument": command.instrument.0,
+                "market": format!("{market:?}"),
+                "levels": command.levels,
+                "resting_orders": open_orders.len(),
+                "action": action_payload,
+                "rows": ladder
+                    .iter()
+                    .map(|level| json!({
+                        "price": level.price,
+                        "bid_qty": level.bid_qty,
+                        "ask_qty": level.ask_qty,
+                        "own_orders": level
+                            .own_orders
+                            .iter()
+                            .map(|order| json!({
+                                "client_order_id": order.client_order_id,
+                                "side": format!("{:?}", order.side).to_ascii_lowercase(),
+                                "qty": order.orig_qty - order.executed_qty,
+                            }))
+                            .collect::<Vec<_>>(),
+                    }))
+                    .collect::<Vec<_>>(),
+            }),
+        );
+        Ok(())
+    }
+
+    fn dispatch<
+        E: crate::exchange::facade::ExchangeFacade<
+            Error = crate::error::exchange_error::ExchangeError,
+        >,
+    >(
+        &mut self,
+        app: &mut AppBootstrap<E>,
+        command: AppCommand,
     ) -> Result<(), crate::error::app_error::AppError> {
         self.record_command(command.clone());
 
         match command {
+            AppCommand::Portfolio(PortfolioView::History(_)) => {}
+            AppCommand::Portfolio(PortfolioView::HistoryRoi { bucket, query }) => {
+                let buckets = match &app.execution.order_store {
+                    Some(store) => Some(store.bucket_stats(bucket, &query, HISTORY_ROI_BUCKETS)?),
+                    None => None,
+                };
+                log(
+                    &mut app.event_log,
+                    "app.history.roi_listed",
+                    json!({
+                        "bucket": bucket.as_str(),
+                        "instrument": query.instrument.map(|instrument| instrument.0),
+                        "tag": query.tag,
+                        "store": if buckets.is_some() { "on" } else { "off" },
+                        "buckets": buckets
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|row| json!({
+                                "bucket": row.bucket,
+                                "orders": row.orders,
+                                "rejected": row.rejected,
+                                "notional": row.notional,
+                                "fills": row.fills,
+                                "filled_notional": row.filled_notional,
+                                "realized_pnl": row.realized_pnl,
+                                "commission": row.commission,
+                                "roi_pct": row.roi_pct(),
+                            }))
+                            .collect::<Vec<_>>(),
+                    }),
+                );
+            }
             AppCommand::Portfolio(_) => {
                 let report = app
                     .portfolio_sync
                     .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
                 refresh_position_prices(app)?;
-                let today_realized_pnl_usdt = app.exchange.load_today_realized_pnl_usdt().ok();
-                let today_funding_pnl_usdt = app.exchange.load_today_funding_pnl_usdt().ok();
-                let margin_ratio = app.exchange.load_margin_ratio().ok().flatten();
+                refresh_position_fee_rates(app);
+                refresh_synthetic_prices(app);
+                run_refresh_checks(app)?;
+                let (today_realized_pnl_usdt, today_funding_pnl_usdt, margin_ratio) =
+                    load_daily_account_figures(app);
                 log(
                     &mut app.event_log,
                     "app.portfolio.refreshed",
@@ -74,7 +715,10 @@ impl AppRuntime {
                     }),
                 );
 
-                if let ExecutionCommand::SetTargetExposure { instrument, .. } = &command {
+                if let ExecutionCommand::SetTargetExposure {
+                    instrument, target, ..
+                } = &command
+                {
                     let market = app
                         .portfolio_store
                         .snapshot
@@ -118,6 +762,9 @@ impl AppRuntime {
                                 "price": price,
                             }),
                         );
+                        if market == crate::domain::market::Market::Spot {
+                            fund_spot_entry_from_earn(app, instrument, target.value(), price);
+                        }
                     }
                 }
                 info!(service = "trading-engine", mode = app.mode.as_str(), command = ?command, "execution command started");
@@ -126,161 +773,3470 @@ impl AppRuntime {
                     "app.execution.started",
                     execution_request_payload(&command),
                 );
-                let outcome = app.execution.execute(
+                let submitted_from = Utc::now();
+                let result = app.execution.execute(
                     &app.exchange,
                     &app.portfolio_store,
                     &app.price_store,
                     command.clone(),
-                )?;
+                );
+                log_depth_check(app, &command);
+                log_slippage_check(app, &command);
+                let outcome = match result {
+                    Ok(outcome) => outcome,
+                    // A deferred command is accepted, not failed; the
+                    // `app.execution.deferred` event is its outcome.
+                    Err(error @ ExecutionError::ThinAskDepth { .. }) => {
+                        if defer_execution_retry(app, command.clone(), 1, &error) {
+                            return Ok(());
+                        }
+                        return Err(error.into());
+                    }
+                    Err(error) => return Err(error.into()),
+                };
+
+                let post_report = app
+                    .portfolio_sync
+                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+                refresh_position_prices(app)?;
+                log(
+                    &mut app.event_log,
+                    "app.portfolio.refreshed",
+                    json!({
+                        "positions": post_report.positions,
+                        "open_order_groups": post_report.open_order_groups,
+                        "balances": post_report.balances,
+                        "phase": "post_execution",
+                    }),
+                );
+                let mut payload = execution_payload(
+                    &command,
+                    &outcome,
+                    post_report.positions,
+                    remaining_gross_exposure_usdt(&app.portfolio_store, &app.price_store),
+                );
+                let latencies_ms = app
+                    .execution
+                    .history
+                    .rows()
+                    .iter()
+                    .filter(|row| row.time >= submitted_from)
+                    .filter_map(|row| row.latency_ms())
+                    .collect::<Vec<_>>();
+                if let (false, Some(object)) = (latencies_ms.is_empty(), payload.as_object_mut()) {
+                    object.insert("order_latency_ms".to_string(), json!(latencies_ms));
+                }
+                log(&mut app.event_log, "app.execution.completed", payload);
+                info!(service = "trading-engine", mode = app.mode.as_str(), command = ?command, outcome = ?outcome, "execution command completed");
+            }
+            AppCommand::Strategy(command) => match command {
+                StrategyCommand::Templates
+                | StrategyCommand::List { .. }
+                | StrategyCommand::History { .. }
+                | StrategyCommand::Groups
+                | StrategyCommand::Leaderboard => {}
+                StrategyCommand::Funnel { window_hours } => {
+                    // Quiet basis watches get a row too; they are the ones
+                    // that trade on their own.
+                    let mut funnels = app
+                        .basis_arbs
+                        .watches(app.mode)
+                        .into_iter()
+                        .map(|watch| {
+                            (
+                                watch.id,
+                                WatchFunnel {
+                                    watch_id: watch.id,
+                                    ..WatchFunnel::default()
+                                },
+                            )
+                        })
+                        .collect::<BTreeMap<_, _>>();
+                    funnels.extend(
+                        app.execution
+                            .funnel
+                            .summary(Utc::now(), window_hours)
+                            .into_iter()
+                            .map(|funnel| (funnel.watch_id, funnel)),
+                    );
+                    let watches = funnels
+                        .into_values()
+                        .map(|funnel| {
+                            let instrument = app
+                                .basis_arbs
+                                .watches(app.mode)
+                                .into_iter()
+                                .find(|watch| watch.id == funnel.watch_id)
+                                .map(|watch| watch.instrument.0.clone());
+                            json!({ "funnel": funnel, "instrument": instrument })
+                        })
+                        .collect::<Vec<_>>();
+                    log(
+                        &mut app.event_log,
+                        "app.strategy.funnel_listed",
+                        json!({ "window_hours": window_hours, "watches": watches }),
+                    );
+                }
+                StrategyCommand::Plugins => {
+                    let dir = plugin_dir_from_env();
+                    let plugins = discover_plugins(&dir)
+                        .into_iter()
+                        .map(|plugin| {
+                            let (exports, error) = match plugin.status {
+                                Ok(exports) => (exports, None),
+                                Err(error) => (Vec::new(), Some(error)),
+                            };
+                            json!({
+                                "name": plugin.name,
+                                "path": plugin.path.display().to_string(),
+                                "exports": exports,
+                                "reason": error.as_ref().map(PluginError::reason),
+                                "detail": error.map(|error| error.to_string()),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    log(
+                        &mut app.event_log,
+                        "app.strategy.plugins_listed",
+                        json!({ "dir": dir.display().to_string(), "plugins": plugins }),
+                    );
+                }
+                StrategyCommand::Show { watch_id } => {
+                    app.strategy_store.get(app.mode, watch_id).ok_or(
+                        crate::error::strategy_error::StrategyError::WatchNotFound(watch_id),
+                    )?;
+                }
+                StrategyCommand::Start {
+                    template,
+                    instrument,
+                    config,
+                } => {
+                    match app.execution.synthetic_instruments.get(&instrument) {
+                        Some(synthetic) => {
+                            for leg in &synthetic.legs {
+                                app.exchange
+                                    .load_symbol_rules(&leg.instrument, leg.market)?;
+                            }
+                        }
+                        None => {
+                            app.exchange
+                                .load_symbol_rules(&instrument, Market::Futures)?;
+                        }
+                    }
+                    let live =
+                        LiveBarStrategy::load(template, &config).map_err(StrategyError::from)?;
+                    let mut watch = app.strategy_store.create_watch(
+                        app.mode,
+                        template,
+                        instrument.clone(),
+                        config.clone(),
+                    )?;
+                    if let Some(group) = app.strategy_groups.group_for(&watch) {
+                        let group = Some(group.to_string());
+                        watch = app.strategy_store.set_group(app.mode, watch.id, group)?;
+                    }
+                    if let Some(live) = live {
+                        app.live_bar_strategies.insert(watch.id, live);
+                    }
+                    app.recorder_coordination.sync_strategy_symbols(
+                        app.mode,
+                        active_strategy_symbols(
+                            &app.strategy_store,
+                            &app.execution.synthetic_instruments,
+                            app.mode,
+                        ),
+                    )?;
+                    log(
+                        &mut app.event_log,
+                        "app.strategy.watch_started",
+                        json!({
+                            "watch_id": watch.id,
+                            "mode": format!("{:?}", watch.mode).to_ascii_lowercase(),
+                            "template": watch.template.slug(),
+                            "instrument": watch.instrument.0,
+                            "version": watch.version(),
+                            "state": watch.state.as_str(),
+                            "group": watch.group,
+                            "risk_pct": watch.config.risk_pct,
+                            "win_rate": watch.config.win_rate,
+                            "r_multiple": watch.config.r_multiple,
+                            "max_entry_slippage_pct": watch.config.max_entry_slippage_pct,
+                            "seed_capital_usdt": watch.config.seed_capital_usdt,
+                            "ramp": watch.ramp.as_ref().map(SizeRamp::label),
+                            "adapt": watch.adaptive.as_ref().map(AdaptiveTuning::label),
+                            "current_step": watch.current_step,
+                        }),
+                    );
+                    info!(
+                        service = "trading-engine",
+                        mode = app.mode.as_str(),
+                        watch_id = watch.id,
+                        instrument = watch.instrument.0,
+                        template = watch.template.slug(),
+                        "strategy watch started"
+                    );
+                }
+                StrategyCommand::Debug { watch_id, enabled } => {
+                    let watch = app.strategy_store.set_debug(app.mode, watch_id, enabled)?;
+                    log(
+                        &mut app.event_log,
+                        "app.strategy.debug_toggled",
+                        json!({
+                            "watch_id": watch.id,
+                            "instrument": watch.instrument.0,
+                            "debug": watch.debug,
+                        }),
+                    );
+                }
+                StrategyCommand::Trace {
+                    watch_id,
+                    dump_path,
+                } => {
+                    app.strategy_store.get(app.mode, watch_id).ok_or(
+                        crate::error::strategy_error::StrategyError::WatchNotFound(watch_id),
+                    )?;
+                    if let Some(path) = dump_path {
+                        let entries = app
+                            .strategy_store
+                            .trace(watch_id)
+                            .map(|trace| trace.dump(std::path::Path::new(&path)))
+                            .transpose()?
+                            .unwrap_or_default();
+                        log(
+                            &mut app.event_log,
+                            "app.strategy.trace_dumped",
+                            json!({
+                                "watch_id": watch_id,
+                                "path": path,
+                                "entries": entries,
+                            }),
+                        );
+                    }
+                }
+                StrategyCommand::Stop {
+                    watch_id,
+                    resolution,
+                } => stop_strategy_watch(app, watch_id, resolution)?,
+                StrategyCommand::Group { watch_id, group } => {
+                    let watch = app.strategy_store.set_group(app.mode, watch_id, group)?;
+                    app.strategy_groups.record(&watch);
+                    app.strategy_groups.save()?;
+                    log(
+                        &mut app.event_log,
+                        "app.strategy.group_set",
+                        json!({
+                            "watch_id": watch.id,
+                            "template": watch.template.slug(),
+                            "instrument": watch.instrument.0,
+                            "group": watch.group,
+                        }),
+                    );
+                }
+                StrategyCommand::Kpi { watch_id, targets } => {
+                    let watch = app.strategy_store.set_kpi(app.mode, watch_id, targets)?;
+                    log(
+                        &mut app.event_log,
+                        "app.strategy.kpi_set",
+                        json!({
+                            "watch_id": watch.id,
+                            "template": watch.template.slug(),
+                            "instrument": watch.instrument.0,
+                            "targets": watch.kpi.as_ref().map(|kpi| kpi.targets.to_string()),
+                        }),
+                    );
+                }
+                StrategyCommand::Rollback { watch_id } => {
+                    let (watch, adaptation) =
+                        app.strategy_store.rollback_adaptation(app.mode, watch_id)?;
+                    log(
+                        &mut app.event_log,
+                        "app.strategy.adaptation_rolled_back",
+                        json!({
+                            "watch_id": watch.id,
+                            "instrument": watch.instrument.0,
+                            "changes": adaptation_changes_json(&adaptation),
+                            "version": watch.version(),
+                            "risk_pct": watch.config.risk_pct,
+                            "r_multiple": watch.config.r_multiple,
+                        }),
+                    );
+                }
+                StrategyCommand::Bulk { group, action } => run_strategy_bulk(app, &group, action)?,
+                StrategyCommand::Select {
+                    filter,
+                    action,
+                    confirmed,
+                } => run_strategy_select(app, &filter, action, confirmed)?,
+            },
+            AppCommand::Alert(command) => run_alert_command(app, command)?,
+            AppCommand::Basis(command) => run_basis_command(app, command)?,
+            AppCommand::ReloadConfig => reload_config(app),
+            AppCommand::Diagnostics => sample_diagnostics(app),
+            AppCommand::ExportDiagnostics { path } => export_diagnostics(app, path)?,
+            AppCommand::Dom(command) => self.run_dom_command(app, command)?,
+            AppCommand::OcoStatus => {
+                app.portfolio_sync
+                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+                check_oco_lists(app);
+                let lists = app
+                    .execution
+                    .oco_lists
+                    .iter()
+                    .map(|list| {
+                        json!({
+                            "list_id": list.list_id,
+                            "instrument": list.instrument.0,
+                            "close_side": format!("{:?}", list.close_side).to_ascii_lowercase(),
+                            "qty": list.qty,
+                            "take_profit_price": list.take_profit_price,
+                            "stop_price": list.stop_price,
+                            "stop_limit_price": list.stop_limit_price,
+                            "exchange_list_id": list.exchange_list_id,
+                            "take_profit_order_id": list.take_profit_order_id,
+                            "stop_order_id": list.stop_order_id,
+                            "status": list.status.as_str(),
+                            "repairs": list.repairs,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                log(
+                    &mut app.event_log,
+                    "app.oco.listed",
+                    json!({ "lists": lists }),
+                );
+            }
+            AppCommand::IcebergStatus => {
+                app.portfolio_sync
+                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+                check_icebergs(app);
+                let icebergs = app
+                    .execution
+                    .icebergs
+                    .iter()
+                    .map(iceberg_payload)
+                    .collect::<Vec<_>>();
+                log(
+                    &mut app.event_log,
+                    "app.iceberg.listed",
+                    json!({ "icebergs": icebergs }),
+                );
+            }
+            AppCommand::ShadowStatus => {
+                let instruments = app
+                    .execution
+                    .shadow_orders
+                    .iter()
+                    .map(|order| (order.instrument.clone(), order.market))
+                    .collect::<BTreeSet<_>>();
+                for (instrument, market) in instruments {
+                    app.market_data.refresh_price(
+                        &app.exchange,
+                        &mut app.price_store,
+                        instrument,
+                        market,
+                    )?;
+                }
+                let runs = app
+                    .execution
+                    .shadow_divergence(&app.price_store)
+                    .iter()
+                    .map(shadow_payload)
+                    .collect::<Vec<_>>();
+                log(
+                    &mut app.event_log,
+                    "app.shadow.listed",
+                    json!({
+                        "modeled_slippage_bps": app
+                            .execution
+                            .paper_shadow
+                            .map(|config| config.modeled_slippage_bps),
+                        "runs": runs,
+                    }),
+                );
+            }
+            AppCommand::HoldEvStatus => {
+                app.portfolio_sync
+                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+                refresh_position_prices(app)?;
+                check_oco_lists(app);
+                check_hold_ev(app);
+                let estimates = app
+                    .execution
+                    .hold_ev
+                    .values()
+                    .map(hold_ev_payload)
+                    .collect::<Vec<_>>();
+                log(
+                    &mut app.event_log,
+                    "app.position.hold_ev_listed",
+                    json!({ "estimates": estimates }),
+                );
+            }
+            AppCommand::Flatten {
+                instrument,
+                confirmed,
+            } => {
+                let Some(instrument) = instrument.or_else(|| selected_instrument(app)) else {
+                    log(
+                        &mut app.event_log,
+                        "app.execution.flatten_unavailable",
+                        json!({ "reason": "no instrument selected" }),
+                    );
+                    return Ok(());
+                };
+                app.portfolio_sync
+                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+                if !confirmed {
+                    let snapshot = &app.portfolio_store.snapshot;
+                    log(
+                        &mut app.event_log,
+                        "app.execution.flatten_confirmation_required",
+                        json!({
+                            "instrument": instrument.0,
+                            "signed_qty": snapshot
+                                .positions
+                                .get(&instrument)
+                                .map_or(0.0, |position| position.signed_qty),
+                            "open_orders": snapshot
+                                .open_orders
+                                .get(&instrument)
+                                .map_or(0, Vec::len),
+                        }),
+                    );
+                    return Ok(());
+                }
+                let result = app.execution.emergency_close_position(
+                    &app.exchange,
+                    &app.portfolio_store,
+                    &instrument,
+                );
+                warn!(
+                    service = "trading-engine",
+                    mode = app.mode.as_str(),
+                    instrument = instrument.0,
+                    cancelled = result.cancelled.len(),
+                    cancel_failed = result.cancel_failed.len(),
+                    outcome = ?result.close,
+                    "instrument flattened"
+                );
+                log(
+                    &mut app.event_log,
+                    "app.execution.flattened",
+                    json!({
+                        "instrument": instrument.0,
+                        "cancelled": result.cancelled,
+                        "cancel_failed": result.cancel_failed,
+                        "outcome_kind": format!("{:?}", result.close),
+                    }),
+                );
+            }
+            AppCommand::Panic { confirmed } => run_panic(app, confirmed)?,
+            AppCommand::ExternalPositions => {
+                app.portfolio_sync
+                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+                check_external_positions(app);
+                let adopted = app
+                    .portfolio_store
+                    .adopted_positions
+                    .values()
+                    .map(|adopted| {
+                        json!({
+                            "instrument": adopted.instrument.0,
+                            "market": format!("{:?}", adopted.market),
+                            "entry_price": adopted.entry_price,
+                            "adopted_at": adopted.adopted_at.to_rfc3339(),
+                            "tag": ADOPTED_POSITION_TAG,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                log(
+                    &mut app.event_log,
+                    "app.portfolio.external_listed",
+                    json!({
+                        "external": app
+                            .portfolio_store
+                            .external_positions
+                            .iter()
+                            .map(external_position_payload)
+                            .collect::<Vec<_>>(),
+                        "adopted": adopted,
+                    }),
+                );
+            }
+            AppCommand::AdoptPosition {
+                instrument,
+                entry_price,
+            } => {
+                app.portfolio_sync
+                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+                let external = external_positions(app)
+                    .into_iter()
+                    .find(|external| external.instrument == instrument)
+                    .ok_or_else(|| SyncError::NoExternalPosition(instrument.0.clone()))?;
+                let adopted =
+                    app.portfolio_store
+                        .adopt_position(&external, entry_price, Utc::now());
+                info!(
+                    service = "trading-engine",
+                    mode = app.mode.as_str(),
+                    instrument = adopted.instrument.0,
+                    entry_price,
+                    "external position adopted"
+                );
+                log(
+                    &mut app.event_log,
+                    "app.portfolio.position_adopted",
+                    json!({
+                        "instrument": adopted.instrument.0,
+                        "market": format!("{:?}", adopted.market),
+                        "signed_qty": external.signed_qty,
+                        "entry_price": adopted.entry_price,
+                        "exchange_entry_price": external.exchange_entry_price,
+                        "tag": ADOPTED_POSITION_TAG,
+                    }),
+                );
+            }
+            AppCommand::InactivityStatus => {
+                let now = Utc::now();
+                let refreshed_at = app.portfolio_store.refreshed_at;
+                log(
+                    &mut app.event_log,
+                    "app.safety.inactivity_status",
+                    json!({
+                        "timeout_secs": app.inactivity.timeout_secs,
+                        "idle_secs": app.inactivity.idle_secs(now, refreshed_at),
+                        "last_input_at": app.inactivity.last_input_at.to_rfc3339(),
+                        "refreshed_at": refreshed_at.map(|time| time.to_rfc3339()),
+                        "tripped_at": app.inactivity.tripped_at.map(|time| time.to_rfc3339()),
+                        "entries_disabled": app.execution.entries_disabled,
+                    }),
+                );
+            }
+            AppCommand::ResumeEntries => {
+                let was_disabled = app.execution.entries_disabled;
+                app.inactivity.resume(Utc::now());
+                app.execution.entries_disabled = false;
+                log(
+                    &mut app.event_log,
+                    "app.safety.entries_resumed",
+                    json!({
+                        "timeout_secs": app.inactivity.timeout_secs,
+                        "was_disabled": was_disabled,
+                    }),
+                );
+            }
+            AppCommand::News(command) => run_news_command(app, command),
+            AppCommand::RebuildEvents { path, domains } => rebuild_events(app, path, domains)?,
+            AppCommand::RefreshAuthoritativeState => {
+                let report = app
+                    .portfolio_sync
+                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+                refresh_position_prices(app)?;
+                refresh_position_fee_rates(app);
+                refresh_synthetic_prices(app);
+                run_refresh_checks(app)?;
+                let (today_realized_pnl_usdt, today_funding_pnl_usdt, margin_ratio) =
+                    load_daily_account_figures(app);
+                log(
+                    &mut app.event_log,
+                    "app.portfolio.refreshed",
+                    json!({
+                        "positions": report.positions,
+                        "open_order_groups": report.open_order_groups,
+                        "balances": report.balances,
+                        "today_realized_pnl_usdt": today_realized_pnl_usdt,
+                        "today_funding_pnl_usdt": today_funding_pnl_usdt,
+                        "margin_ratio": margin_ratio,
+                    }),
+                );
+                info!(
+                    service = "trading-engine",
+                    mode = app.mode.as_str(),
+                    positions = report.positions,
+                    open_order_groups = report.open_order_groups,
+                    balances = report.balances,
+                    "authoritative state refreshed"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Replaces the file-backed part of the in-memory event log with the
+/// replayed file, keeping memory-only records and those outside `domains`.
+fn rebuild_events<E: crate::exchange::facade::ExchangeFacade>(
+    app: &mut AppBootstrap<E>,
+    path: Option<std::path::PathBuf>,
+    domains: BTreeSet<String>,
+) -> Result<(), crate::error::app_error::AppError> {
+    let path = path.unwrap_or_else(event_log_path);
+    let replay = replay_event_file(&path, sealed::process_sealer()?, &domains)?;
+    let summary = replay.summary();
+    let kept = std::mem::take(&mut app.event_log.records)
+        .into_iter()
+        .filter(|record| {
+            !app.event_log
+                .routing
+                .sinks_for(record)
+                .contains(&LogSink::File)
+                || !(domains.is_empty() || domains.contains(event_domain(&record.kind)))
+        })
+        .collect::<Vec<_>>();
+    let kept_in_memory = kept.len();
+    app.event_log.records = replay.records().cloned().chain(kept).collect();
+    log(
+        &mut app.event_log,
+        "app.events.rebuilt",
+        json!({
+            "path": path.display().to_string(),
+            "schema_version": EVENT_SCHEMA_VERSION,
+            "domain_filter": domains,
+            "events": summary.events,
+            "skipped": summary.skipped,
+            "kept_in_memory": kept_in_memory,
+            "versions": summary
+                .versions
+                .iter()
+                .map(|(version, count)| (format!("v{version}"), *count))
+                .collect::<BTreeMap<_, _>>(),
+            "domains": summary.domains,
+            "errors": summary.errors,
+            "first_ts": summary.first_ts.map(|ts| ts.to_rfc3339()),
+            "last_ts": summary.last_ts.map(|ts| ts.to_rfc3339()),
+            "outcomes": summary.outcomes,
+            "instruments": summary.instruments,
+        }),
+    );
+    Ok(())
+}
+
+/// Applies the reloaded config as a whole and logs what changed; changed
+/// futures leverage settings are sent to the venue straight away.
+fn reload_config<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let dotenv = DotenvVars::read();
+    let current = RuntimeConfig::capture(app);
+    let next = RuntimeConfig::from_vars(|key| dotenv.var(key));
+    let changes = current.diff(&next);
+    next.apply(app);
+    app.exchange.invalidate_static_cache();
+    for change in &changes {
+        info!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            key = change.key,
+            before = change.before,
+            after = change.after,
+            "config value reloaded"
+        );
+    }
+    log(
+        &mut app.event_log,
+        "app.config.reloaded",
+        json!({ "changes": changes }),
+    );
+    if changes
+        .iter()
+        .any(|change| change.key.starts_with("futures_leverage."))
+    {
+        app.apply_futures_leverage();
+    }
+}
+
+/// Lists the `/news` pane or starts and lifts the entry blackout.
+fn run_news_command<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    command: NewsCommand,
+) {
+    let now = Utc::now();
+    match command {
+        NewsCommand::Show => {
+            let headlines = app
+                .news
+                .headlines()
+                .map(|flagged| {
+                    json!({
+                        "title": flagged.headline.title,
+                        "highlighted": flagged.highlighted_title(),
+                        "link": flagged.headline.link,
+                        "published_at": flagged.headline.published_at.map(|time| time.to_rfc3339()),
+                        "assets": flagged.assets,
+                        "major": flagged.major,
+                    })
+                })
+                .collect::<Vec<_>>();
+            log(
+                &mut app.event_log,
+                "app.news.listed",
+                json!({
+                    "feed": app.news.config.label(),
+                    "last_polled_at": app.news.last_polled_at.map(|time| time.to_rfc3339()),
+                    "last_error": app.news.last_error,
+                    "blackout_until": app
+                        .execution
+                        .entries_blackout_until
+                        .filter(|until| *until > now)
+                        .map(|until| until.to_rfc3339()),
+                    "headlines": headlines,
+                }),
+            );
+        }
+        NewsCommand::Blackout { minutes } => {
+            let secs = minutes.map_or(app.news.config.blackout_secs, |minutes| minutes * 60);
+            let until = now + chrono::Duration::seconds(secs as i64);
+            app.execution.entries_blackout_until = Some(until);
+            warn!(
+                service = "trading-engine",
+                mode = app.mode.as_str(),
+                until = until.to_rfc3339(),
+                "news blackout started; new entries held"
+            );
+            log(
+                &mut app.event_log,
+                "app.news.blackout_started",
+                json!({
+                    "until": until.to_rfc3339(),
+                    "minutes": secs / 60,
+                }),
+            );
+        }
+        NewsCommand::LiftBlackout => {
+            let was_active = app
+                .execution
+                .entries_blackout_until
+                .take()
+                .is_some_and(|until| until > now);
+            log(
+                &mut app.event_log,
+                "app.news.blackout_lifted",
+                json!({ "was_active": was_active }),
+            );
+        }
+    }
+}
+
+fn run_alert_command<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    command: AlertCommand,
+) -> Result<(), crate::error::app_error::AppError> {
+    match command {
+        AlertCommand::Add {
+            instrument,
+            price,
+            action,
+        } => {
+            let last_price = app
+                .market_data
+                .refresh_price(
+                    &app.exchange,
+                    &mut app.price_store,
+                    instrument.clone(),
+                    Market::Futures,
+                )
+                .ok();
+            let alert = app.price_alerts.add(&instrument, price, action, last_price);
+            app.price_alerts.save()?;
+            log(&mut app.event_log, "app.alert.added", alert_payload(&alert));
+            info!(
+                service = "trading-engine",
+                mode = app.mode.as_str(),
+                alert_id = alert.id,
+                instrument = alert.instrument,
+                price = alert.price,
+                "price alert added"
+            );
+        }
+        AlertCommand::Remove { id } => {
+            let alert = app
+                .price_alerts
+                .remove(id)
+                .ok_or(AlertError::NotFound(id))?;
+            app.price_alerts.save()?;
+            log(
+                &mut app.event_log,
+                "app.alert.removed",
+                alert_payload(&alert),
+            );
+        }
+        AlertCommand::Confirm { id } => {
+            let alert = app
+                .price_alerts
+                .take_confirmation(id)
+                .ok_or(AlertError::NotAwaitingConfirmation(id))?;
+            app.price_alerts.save()?;
+            let PriceAlertAction::SetTargetExposure { target } = alert.action else {
+                return Err(AlertError::NotAwaitingConfirmation(id).into());
+            };
+            let outcome = submit_alert_target(app, &alert, target)?;
+            let mut payload = alert_payload(&alert);
+            payload["outcome"] = json!(format!("{outcome:?}"));
+            log(&mut app.event_log, "app.alert.confirmed", payload);
+        }
+        AlertCommand::List => {
+            log(
+                &mut app.event_log,
+                "app.alert.listed",
+                json!({
+                    "alerts": app
+                        .price_alerts
+                        .alerts()
+                        .iter()
+                        .map(alert_payload)
+                        .collect::<Vec<_>>(),
+                }),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn run_basis_command<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    command: BasisCommand,
+) -> Result<(), crate::error::app_error::AppError> {
+    match command {
+        BasisCommand::Start { instrument, config } => {
+            let watch = app
+                .basis_arbs
+                .start(app.mode, instrument.clone(), config)
+                .ok_or(StrategyError::DuplicateWatch {
+                    template: "spot-perp-basis",
+                    instrument: instrument.0,
+                })?;
+            log(
+                &mut app.event_log,
+                "app.strategy.basis_started",
+                basis_watch_payload(&watch),
+            );
+        }
+        BasisCommand::List => {
+            log(
+                &mut app.event_log,
+                "app.strategy.basis_listed",
+                json!({
+                    "watches": app
+                        .basis_arbs
+                        .watches(app.mode)
+                        .into_iter()
+                        .map(basis_watch_payload)
+                        .collect::<Vec<_>>(),
+                }),
+            );
+        }
+        BasisCommand::Stop { id } => {
+            let mut watch = app
+                .basis_arbs
+                .get_mut(app.mode, id)
+                .cloned()
+                .ok_or(StrategyError::BasisWatchNotFound(id))?;
+            if watch.carry.is_some() {
+                let basis_bps = watch.last_quote.map(|quote| quote.basis_bps());
+                submit_basis_target(app, &watch, 0.0)?;
+                close_basis_carry(app, &mut watch, basis_bps.unwrap_or_default());
+            }
+            app.basis_arbs.stop(app.mode, id);
+            log(
+                &mut app.event_log,
+                "app.strategy.basis_stopped",
+                basis_watch_payload(&watch),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Subscribes idle quote above the sweep threshold to flexible savings.
+///
+/// Runs last in a refresh so strategies and deferred entries spend first.
+fn sweep_idle_to_earn<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let transfer = app
+        .portfolio_store
+        .earn_account
+        .as_ref()
+        .and_then(|account| app.earn.plan_sweep(account));
+    if let Some(transfer) = transfer {
+        transfer_earn(app, &transfer, "sweep");
+    }
+}
+
+/// Redeems savings before a spot buy that the idle balance cannot cover.
+///
+/// Example:
+/// - target `0.25` of `8,000` equity, flat, `1,200` idle -> redeem about `820`
+fn fund_spot_entry_from_earn<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    instrument: &Instrument,
+    target: f64,
+    price: f64,
+) {
+    let Some(account) = app.portfolio_store.earn_account.as_ref() else {
+        return;
+    };
+    let balances = &app.portfolio_store.snapshot.balances;
+    let equity_usdt = balances.iter().map(|balance| balance.total()).sum::<f64>();
+    let held_qty = base_asset(instrument).map_or(0.0, |asset| {
+        balances
+            .iter()
+            .filter(|balance| balance.asset == asset)
+            .map(|balance| balance.total())
+            .sum()
+    });
+    let required_usdt = (target * equity_usdt - held_qty * price).max(0.0);
+    if let Some(transfer) = plan_redeem(account, required_usdt * (1.0 + EARN_REDEEM_HEADROOM)) {
+        transfer_earn(app, &transfer, "order");
+    }
+}
+
+/// Sends one savings transfer and mirrors it on the cached account so the
+/// next sweep does not act on stale balances. Failures are logged only.
+fn transfer_earn<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    transfer: &EarnTransfer,
+    reason: &str,
+) {
+    match app.exchange.transfer_earn(transfer) {
+        Ok(true) => {
+            let Some(account) = app.portfolio_store.earn_account.as_mut() else {
+                return;
+            };
+            let signed = match transfer.kind {
+                EarnTransferKind::Subscribe => transfer.amount,
+                EarnTransferKind::Redeem => -transfer.amount,
+            };
+            account.idle -= signed;
+            match account.positions.first_mut() {
+                Some(position) => position.amount += signed,
+                None => account.positions.push(EarnPosition {
+                    asset: transfer.asset.clone(),
+                    product_id: String::new(),
+                    amount: signed,
+                    apr_pct: 0.0,
+                }),
+            }
+            let (idle, saved) = (account.idle, account.saved());
+            log(
+                &mut app.event_log,
+                "app.earn.transferred",
+                json!({
+                    "kind": transfer.kind.as_str(),
+                    "asset": transfer.asset,
+                    "amount": transfer.amount,
+                    "reason": reason,
+                    "idle": idle,
+                    "saved": saved,
+                }),
+            );
+        }
+        Ok(false) => {}
+        Err(error) => log(
+            &mut app.event_log,
+            "app.earn.transfer_failed",
+            json!({
+                "kind": transfer.kind.as_str(),
+                "asset": transfer.asset,
+                "amount": transfer.amount,
+                "reason": reason,
+                "error": error.to_string(),
+            }),
+        ),
+    }
+}
+
+/// Re-quotes every basis watch, accrues funding on open hedges and enters
+/// or unwinds through the watch's synthetic.
+///
+/// Failures are logged per watch so one bad symbol does not stop the refresh.
+/// Exit, entry and bookkeeping checks run after every portfolio refresh.
+///
+/// Under a latency-first profile the exits and entries go out before price
+/// alerts, external position adoption and the earn sweep.
+fn run_refresh_checks<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) -> Result<(), crate::error::app_error::AppError> {
+    if app.resource_profile.latency_first() {
+        check_take_profit_ladders(app);
+        check_oco_lists(app);
+        check_hold_ev(app);
+        check_max_position_age(app);
+        check_internal_trailing_stops(app);
+        release_queued_entries(app);
+        check_basis_arbs(app);
+        tick_strategy_watches(app)?;
+        retry_deferred_executions(app);
+        check_icebergs(app);
+        check_external_positions(app);
+        check_price_alerts(app)?;
+        sweep_idle_to_earn(app);
+        return Ok(());
+    }
+    check_take_profit_ladders(app);
+    check_oco_lists(app);
+    check_hold_ev(app);
+    check_max_position_age(app);
+    check_external_positions(app);
+    check_internal_trailing_stops(app);
+    check_price_alerts(app)?;
+    release_queued_entries(app);
+    check_basis_arbs(app);
+    tick_strategy_watches(app)?;
+    retry_deferred_executions(app);
+    check_icebergs(app);
+    sweep_idle_to_earn(app);
+    Ok(())
+}
+
+/// Today's realized and funding PnL and the margin ratio for the refresh
+/// event; skipped under a latency-first profile to save three REST calls.
+fn load_daily_account_figures<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &AppBootstrap<E>,
+) -> (Option<f64>, Option<f64>, Option<f64>) {
+    if app.resource_profile.latency_first() {
+        return (None, None, None);
+    }
+    (
+        app.exchange.load_today_realized_pnl_usdt().ok(),
+        app.exchange.load_today_funding_pnl_usdt().ok(),
+        app.exchange.load_margin_ratio().ok().flatten(),
+    )
+}
+
+/// Keeps a fill in the order store, tagged with the strategy watch that
+/// sent it. Fees paid in an asset other than the symbol's quote are not
+/// counted.
+fn persist_fill<E: crate::exchange::facade::ExchangeFacade>(
+    app: &AppBootstrap<E>,
+    update: &OrderUpdate,
+    now: DateTime<Utc>,
+) {
+    let (Some(store), Some(price)) = (&app.execution.order_store, update.last_fill_price) else {
+        return;
+    };
+    let order = &update.order;
+    let fill = FillRow {
+        time: update
+            .event_time_ms
+            .and_then(DateTime::<Utc>::from_timestamp_millis)
+            .unwrap_or(now),
+        instrument: order.instrument.clone(),
+        market: order.market,
+        client_order_id: order.client_order_id.clone(),
+        order_id: order.order_id.map(|id| id.0),
+        tag: strategy_watch_id(&order.client_order_id).map_or_else(
+            || "manual".to_string(),
+            |watch_id| format!("strategy:{watch_id}"),
+        ),
+        side: order.side,
+        qty: update.last_fill_qty,
+        price,
+        realized_pnl: update.realized_pnl.unwrap_or(0.0),
+        commission: fill_commission_usdt(update),
+    };
+    if let Err(error) = store.record_fill(&fill) {
+        warn!(
+            service = "trading-engine",
+            error = %error,
+            "fill not persisted to the order store"
+        );
+    }
+}
+
+/// The fill's fee in the symbol's quote; fees in other assets count as `0`.
+fn fill_commission_usdt(update: &OrderUpdate) -> f64 {
+    update
+        .commission
+        .filter(|_| {
+            update
+                .commission_asset
+                .as_deref()
+                .is_some_and(|asset| update.order.instrument.0.ends_with(asset))
+        })
+        .unwrap_or(0.0)
+}
+
+fn check_basis_arbs<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let watches = app
+        .basis_arbs
+        .watches(app.mode)
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    let now = Utc::now();
+    for mut watch in watches {
+        let quote = match basis_quote(&app.exchange, &watch.instrument) {
+            Ok(quote) => quote,
+            Err(error) => {
+                log(
+                    &mut app.event_log,
+                    "app.strategy.basis_quote_failed",
+                    json!({
+                        "watch_id": watch.id,
+                        "instrument": watch.instrument.0,
+                        "error": error.to_string(),
+                    }),
+                );
+                continue;
+            }
+        };
+        if let Some(carry) = watch.carry.as_mut() {
+            carry.accrue_funding(quote.funding_rate, now);
+        }
+        watch.last_quote = Some(quote);
+        let decision = watch.decide(&quote);
+        log(
+            &mut app.event_log,
+            "app.strategy.basis_checked",
+            json!({
+                "watch_id": watch.id,
+                "instrument": watch.instrument.0,
+                "spot": quote.spot,
+                "perp": quote.perp,
+                "basis_bps": quote.basis_bps(),
+                "funding_rate": quote.funding_rate,
+                "funding_apr_pct": quote.funding_apr_pct(),
+                "decision": decision.as_str(),
+            }),
+        );
+        let target = match decision {
+            BasisDecision::Enter if app.execution.is_entry_queued(&watch.synthetic_name()) => {
+                store_basis_watch(app, watch);
+                continue;
+            }
+            BasisDecision::Enter => -watch.config.target,
+            BasisDecision::Exit => 0.0,
+            BasisDecision::Hold => {
+                store_basis_watch(app, watch);
+                continue;
+            }
+        };
+        match submit_basis_target(app, &watch, target) {
+            Ok(()) if decision == BasisDecision::Enter => {
+                open_basis_carry(app, &mut watch, &quote, now)
+            }
+            Ok(()) => close_basis_carry(app, &mut watch, quote.basis_bps()),
+            Err(ExecutionError::EntryQueued { until, .. }) => log(
+                &mut app.event_log,
+                "app.strategy.basis_entry_queued",
+                json!({
+                    "watch_id": watch.id,
+                    "instrument": watch.instrument.0,
+                    "version": watch.version(),
+                    "synthetic": watch.synthetic_name().0,
+                    "basis_bps": quote.basis_bps(),
+                    "until": until,
+                }),
+            ),
+            Err(error) => log(
+                &mut app.event_log,
+                "app.strategy.basis_failed",
+                json!({
+                    "watch_id": watch.id,
+                    "instrument": watch.instrument.0,
+                    "version": watch.version(),
+                    "decision": decision.as_str(),
+                    "error": error.to_string(),
+                }),
+            ),
+        }
+        store_basis_watch(app, watch);
+    }
+}
+
+/// Submits the basis entries held through an illiquid window, each only
+/// if its watch is unchanged, still flat and still signals an entry at the
+/// liquid open; the rest are dropped with the reason.
+fn release_queued_entries<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let now = Utc::now();
+    for queued in app.execution.take_released_entries(now) {
+        let mut payload = json!({
+            "instrument": execution_request_payload(&queued.command)["instrument"],
+            "queued_at": queued.queued_at.to_rfc3339(),
+            "waited_secs": (now - queued.queued_at).num_seconds().max(0),
+        });
+        let watch = match command_source(&queued.command) {
+            CommandSource::Strategy { watch_id, version } => app
+                .basis_arbs
+                .watches(app.mode)
+                .into_iter()
+                .find(|watch| watch.id == *watch_id)
+                .cloned()
+                .ok_or("watch_gone")
+                .and_then(|watch| {
+                    (watch.version() == *version)
+                        .then_some(watch)
+                        .ok_or("watch_changed")
+                }),
+            CommandSource::User | CommandSource::System => Err("watch_gone"),
+        };
+        let checked = watch.and_then(|watch| {
+            if watch.carry.is_some() {
+                return Err("already_entered");
+            }
+            let quote =
+                basis_quote(&app.exchange, &watch.instrument).map_err(|_| "quote_failed")?;
+            (watch.decide(&quote) == BasisDecision::Enter)
+                .then_some((watch, quote))
+                .ok_or("signal_gone")
+        });
+        let (mut watch, quote) = match checked {
+            Ok(checked) => checked,
+            Err(reason) => {
+                payload["reason"] = json!(reason);
+                log(
+                    &mut app.event_log,
+                    "app.execution.queued_entry_dropped",
+                    payload,
+                );
+                continue;
+            }
+        };
+        payload["watch_id"] = json!(watch.id);
+        payload["basis_bps"] = json!(quote.basis_bps());
+        watch.last_quote = Some(quote);
+        match submit_basis_target(app, &watch, -watch.config.target) {
+            Ok(()) => {
+                log(
+                    &mut app.event_log,
+                    "app.execution.queued_entry_released",
+                    payload,
+                );
+                open_basis_carry(app, &mut watch, &quote, now);
+                store_basis_watch(app, watch);
+            }
+            Err(error) => {
+                payload["error"] = json!(error.to_string());
+                log(
+                    &mut app.event_log,
+                    "app.execution.queued_entry_failed",
+                    payload,
+                );
+            }
+        }
+    }
+}
+
+fn open_basis_carry<E: crate::exchange::facade::ExchangeFacade>(
+    app: &mut AppBootstrap<E>,
+    watch: &mut BasisArbWatch,
+    quote: &BasisQuote,
+    now: DateTime<Utc>,
+) {
+    let equity_usdt: f64 = app
+        .portfolio_store
+        .snapshot
+        .balances
+        .iter()
+        .map(|balance| balance.total())
+        .sum();
+    let carry = BasisCarry::open(quote.basis_bps(), watch.config.target * equity_usdt, now);
+    log(
+        &mut app.event_log,
+        "app.strategy.basis_entered",
+        json!({
+            "watch_id": watch.id,
+            "instrument": watch.instrument.0,
+            "version": watch.version(),
+            "synthetic": watch.synthetic_name().0,
+            "basis_bps": carry.entry_basis_bps,
+            "funding_apr_pct": quote.funding_apr_pct(),
+            "target": watch.config.target,
+            "notional_usdt": carry.notional_usdt,
+        }),
+    );
+    watch.carry = Some(carry);
+}
+
+fn basis_quote<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    exchange: &E,
+    instrument: &Instrument,
+) -> Result<BasisQuote, crate::error::exchange_error::ExchangeError> {
+    Ok(BasisQuote {
+        spot: exchange.load_last_price(instrument, Market::Spot)?,
+        perp: exchange.load_last_price(instrument, Market::Futures)?,
+        funding_rate: exchange.load_funding_rate(instrument).ok().flatten(),
+    })
+}
+
+/// Sends both legs through the watch's synthetic, registering it first
+/// since config reloads replace the synthetic definitions.
+fn submit_basis_target<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    watch: &BasisArbWatch,
+    target: f64,
+) -> Result<(), ExecutionError> {
+    let synthetic = watch.synthetic();
+    let instrument = synthetic.name.clone();
+    app.execution
+        .synthetic_instruments
+        .entry(instrument.clone())
+        .or_insert(synthetic);
+    let target = Exposure::new(target).ok_or(ExecutionError::SyntheticLegOutOfRange {
+        synthetic: instrument.0.clone(),
+        leg: watch.instrument.0.clone(),
+        target,
+    })?;
+    app.execution.execute(
+        &app.exchange,
+        &app.portfolio_store,
+        &app.price_store,
+        ExecutionCommand::SetTargetExposure {
+            instrument,
+            target,
+            order_type: OrderType::Market,
+            source: CommandSource::Strategy {
+                watch_id: watch.id,
+                version: watch.version(),
+            },
+        },
+    )?;
+    Ok(())
+}
+
+fn close_basis_carry<E: crate::exchange::facade::ExchangeFacade>(
+    app: &mut AppBootstrap<E>,
+    watch: &mut BasisArbWatch,
+    basis_bps: f64,
+) {
+    let Some(carry) = watch.carry.take() else {
+        return;
+    };
+    let carry_pnl_usdt = carry.carry_pnl_usdt(basis_bps);
+    watch.round_trips += 1;
+    watch.realized_carry_usdt += carry_pnl_usdt;
+    log(
+        &mut app.event_log,
+        "app.strategy.basis_exited",
+        json!({
+            "watch_id": watch.id,
+            "instrument": watch.instrument.0,
+            "version": watch.version(),
+            "entry_basis_bps": carry.entry_basis_bps,
+            "exit_basis_bps": basis_bps,
+            "notional_usdt": carry.notional_usdt,
+            "basis_pnl_usdt": carry.basis_pnl_usdt(basis_bps),
+            "funding_accrued_usdt": carry.funding_accrued_usdt,
+            "funding_intervals": carry.funding_intervals,
+            "carry_pnl_usdt": carry_pnl_usdt,
+            "held_secs": (Utc::now() - carry.opened_at).num_seconds().max(0),
+        }),
+    );
+}
+
+fn store_basis_watch<E: crate::exchange::facade::ExchangeFacade>(
+    app: &mut AppBootstrap<E>,
+    watch: BasisArbWatch,
+) {
+    if let Some(stored) = app.basis_arbs.get_mut(app.mode, watch.id) {
+        *stored = watch;
+    }
+}
+
+fn basis_watch_payload(watch: &BasisArbWatch) -> serde_json::Value {
+    let basis_bps = watch.last_quote.map(|quote| quote.basis_bps());
+    json!({
+        "watch_id": watch.id,
+        "instrument": watch.instrument.0,
+        "synthetic": watch.synthetic_name().0,
+        "version": watch.version(),
+        "entry_bps": watch.config.entry_bps,
+        "exit_bps": watch.config.exit_bps,
+        "funding_apr_threshold_pct": watch.config.funding_apr_pct,
+        "target": watch.config.target,
+        "basis_bps": basis_bps,
+        "funding_apr_pct": watch.last_quote.and_then(|quote| quote.funding_apr_pct()),
+        "hedged": watch.carry.is_some(),
+        "entry_basis_bps": watch.carry.as_ref().map(|carry| carry.entry_basis_bps),
+        "notional_usdt": watch.carry.as_ref().map(|carry| carry.notional_usdt),
+        "funding_accrued_usdt": watch.carry.as_ref().map(|carry| carry.funding_accrued_usdt),
+        "carry_pnl_usdt": watch
+            .carry
+            .as_ref()
+            .zip(basis_bps)
+            .map(|(carry, basis_bps)| carry.carry_pnl_usdt(basis_bps)),
+        "round_trips": watch.round_trips,
+        "realized_carry_usdt": watch.realized_carry_usdt,
+    })
+}
+
+fn check_take_profit_ladders<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let events = app.execution.check_take_profit_ladders(
+        &app.exchange,
+        &app.portfolio_store,
+        &app.price_store,
+    );
+    for event in events {
+        let (kind, payload) = match event {
+            TakeProfitEvent::LevelFilled {
+                instrument,
+                level,
+                trigger_price,
+                qty,
+                result,
+                progress,
+            } => (
+                "app.execution.take_profit_filled",
+                json!({
+                    "instrument": instrument.0,
+                    "level": level,
+                    "trigger_price": trigger_price,
+                    "qty": qty,
+                    "outcome_kind": format!("{result:?}"),
+                    "take_profit_progress": progress,
+                }),
+            ),
+            TakeProfitEvent::RunnerArmed {
+                instrument,
+                callback_rate_pct,
+                on_exchange,
+                progress,
+            } => (
+                "app.execution.take_profit_runner_armed",
+                json!({
+                    "instrument": instrument.0,
+                    "callback_rate_pct": callback_rate_pct,
+                    "engine": if on_exchange { "exchange" } else { "internal" },
+                    "take_profit_progress": progress,
+                }),
+            ),
+            TakeProfitEvent::RunnerFailed {
+                instrument,
+                error,
+                retrying,
+            } => (
+                "app.execution.take_profit_runner_failed",
+                json!({
+                    "instrument": instrument.0,
+                    "error": error,
+                    "retrying": retrying,
+                }),
+            ),
+        };
+        info!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            event = kind,
+            payload = %payload,
+            "take-profit ladder progressed"
+        );
+        log(&mut app.event_log, kind, payload);
+    }
+}
+
+fn check_internal_trailing_stops<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let results = app.execution.check_internal_trailing_stops(
+        &app.exchange,
+        &app.portfolio_store,
+        &app.price_store,
+    );
+    for result in results {
+        log(
+            &mut app.event_log,
+            "app.execution.trailing_stop_triggered",
+            json!({
+                "instrument": result.instrument.0,
+                "outcome_kind": format!("{:?}", result.result),
+            }),
+        );
+        info!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            instrument = result.instrument.0,
+            outcome = ?result.result,
+            "internal trailing stop triggered"
+        );
+    }
+}
+
+/// Holdings the app did not open: no order history on the instrument and
+/// not adopted yet.
+fn external_positions<E: crate::exchange::facade::ExchangeFacade>(
+    app: &AppBootstrap<E>,
+) -> Vec<ExternalPosition> {
+    let history = app.execution.history.rows();
+    detect_external_positions(
+        &app.portfolio_store.snapshot,
+        &app.portfolio_store.adopted_positions,
+        |instrument| history.iter().any(|row| &row.instrument == instrument),
+    )
+}
+
+fn external_position_payload(external: &ExternalPosition) -> serde_json::Value {
+    json!({
+        "instrument": external.instrument.0,
+        "market": format!("{:?}", external.market),
+        "signed_qty": external.signed_qty,
+        "exchange_entry_price": external.exchange_entry_price,
+    })
+}
+
+/// Offers newly seen external holdings for adoption. Warns only when the
+/// set of instruments changes, so a holding left unadopted is not
+/// re-announced on every refresh.
+fn check_external_positions<E: crate::exchange::facade::ExchangeFacade>(app: &mut AppBootstrap<E>) {
+    let external = external_positions(app);
+    let changed = !external.iter().map(|position| &position.instrument).eq(app
+        .portfolio_store
+        .external_positions
+        .iter()
+        .map(|position| &position.instrument));
+    if changed && !external.is_empty() {
+        warn!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            instruments = external
+                .iter()
+                .map(|position| position.instrument.0.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            "positions opened outside the app; adopt with /adopt <instrument> <entry_price>"
+        );
+    }
+    app.portfolio_store.external_positions = external;
+}
+
+/// Polls OCO legs; a list that cannot be repaired is logged as
+/// `app.oco.repair_failed` so it reaches the error sinks.
+fn check_oco_lists<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let events = app
+        .execution
+        .check_oco_lists(&app.exchange, &app.portfolio_store);
+    for event in events {
+        let (kind, payload) = match event {
+            OcoEvent::Completed {
+                list_id,
+                instrument,
+                filled_leg,
+            } => (
+                "app.oco.completed",
+                json!({
+                    "list_id": list_id,
+                    "instrument": instrument.0,
+                    "filled_leg": filled_leg.map(OcoLeg::as_str),
+                }),
+            ),
+            OcoEvent::LegRearmed {
+                list_id,
+                instrument,
+                leg,
+                client_order_id,
+            } => (
+                "app.oco.leg_rearmed",
+                json!({
+                    "list_id": list_id,
+                    "instrument": instrument.0,
+                    "leg": leg.as_str(),
+                    "client_order_id": client_order_id,
+                }),
+            ),
+            OcoEvent::Resized {
+                list_id,
+                instrument,
+                previous_qty,
+                qty,
+            } => (
+                "app.oco.resized",
+                json!({
+                    "list_id": list_id,
+                    "instrument": instrument.0,
+                    "previous_qty": previous_qty,
+                    "qty": qty,
+                }),
+            ),
+            OcoEvent::RepairFailed {
+                list_id,
+                instrument,
+                reason,
+            } => {
+                warn!(
+                    service = "trading-engine",
+                    mode = app.mode.as_str(),
+                    list_id,
+                    instrument = instrument.0,
+                    reason,
+                    "oco list needs manual intervention"
+                );
+                (
+                    "app.oco.repair_failed",
+                    json!({
+                        "list_id": list_id,
+                        "instrument": instrument.0,
+                        "reason": reason,
+                        "needs_manual": true,
+                    }),
+                )
+            }
+        };
+        log(&mut app.event_log, kind, payload);
+    }
+}
+
+/// Works iceberg parents: posts the next slice after each fill and logs
+/// every filled slice, completion, or a parent stopped short as
+/// `app.iceberg.stopped`.
+fn check_icebergs<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let events = app
+        .execution
+        .check_icebergs(&app.exchange, &app.portfolio_store);
+    for event in events {
+        let (kind, payload) = match event {
+            IcebergEvent::SliceFilled {
+                parent_id,
+                instrument,
+                slice,
+                client_order_id,
+                slice_qty,
+                filled_qty,
+                total_qty,
+                next_order_id,
+            } => (
+                "app.iceberg.slice_filled",
+                json!({
+                    "parent_id": parent_id,
+                    "instrument": instrument.0,
+                    "slice": slice,
+                    "client_order_id": client_order_id,
+                    "slice_qty": slice_qty,
+                    "filled_qty": filled_qty,
+                    "total_qty": total_qty,
+                    "next_order_id": next_order_id,
+                }),
+            ),
+            IcebergEvent::Completed {
+                parent_id,
+                instrument,
+                filled_qty,
+                slices,
+            } => (
+                "app.iceberg.completed",
+                json!({
+                    "parent_id": parent_id,
+                    "instrument": instrument.0,
+                    "filled_qty": filled_qty,
+                    "slices": slices,
+                }),
+            ),
+            IcebergEvent::Stopped {
+                parent_id,
+                instrument,
+                filled_qty,
+                total_qty,
+                reason,
+            } => {
+                warn!(
+                    service = "trading-engine",
+                    mode = app.mode.as_str(),
+                    parent_id,
+                    instrument = instrument.0,
+                    reason,
+                    "iceberg stopped before completing"
+                );
+                (
+                    "app.iceberg.stopped",
+                    json!({
+                        "parent_id": parent_id,
+                        "instrument": instrument.0,
+                        "filled_qty": filled_qty,
+                        "total_qty": total_qty,
+                        "reason": reason,
+                    }),
+                )
+            }
+        };
+        log(&mut app.event_log, kind, payload);
+    }
+}
+
+fn iceberg_payload(iceberg: &IcebergOrder) -> serde_json::Value {
+    json!({
+        "parent_id": iceberg.parent_id,
+        "instrument": iceberg.instrument.0,
+        "side": format!("{:?}", iceberg.side).to_ascii_lowercase(),
+        "price": iceberg.price,
+        "total_qty": iceberg.total_qty,
+        "visible_qty": iceberg.visible_qty,
+        "filled_qty": iceberg.filled_qty + iceberg.child_executed_qty,
+        "progress_pct": iceberg.progress_pct(),
+        "slices": iceberg.slices,
+        "child_order_id": iceberg.child_order_id,
+    })
+}
+
+fn shadow_payload(run: &ShadowDivergence) -> serde_json::Value {
+    json!({
+        "watch_id": run.watch_id,
+        "orders": run.orders,
+        "paper_qty": run.paper_qty,
+        "live_qty": run.live_qty,
+        "modeled_slippage_bps": run.modeled_slippage_bps,
+        "realized_slippage_bps": run.realized_slippage_bps,
+        "model_error_bps": run.model_error_bps(),
+        "paper_pnl_usdt": run.paper_pnl_usdt,
+        "live_pnl_usdt": run.live_pnl_usdt,
+        "divergence_usdt": run.divergence_usdt(),
+        "slippage_usdt": run.slippage_usdt,
+        "fill_gap_usdt": run.fill_gap_usdt,
+    })
+}
+
+fn check_hold_ev<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let events = app
+        .execution
+        .check_hold_ev(&app.exchange, &app.portfolio_store, &app.price_store);
+    for event in events {
+        match event {
+            HoldEvEvent::SuggestExit(estimate) => {
+                info!(
+                    service = "trading-engine",
+                    mode = app.mode.as_str(),
+                    instrument = estimate.instrument.0,
+                    expected_value_r = estimate.expected_value_r,
+                    "holding ev below threshold; exit suggested"
+                );
+                log(
+                    &mut app.event_log,
+                    "app.position.hold_ev_exit_suggested",
+                    hold_ev_payload(&estimate),
+                );
+            }
+            HoldEvEvent::Exited { estimate, result } => {
+                warn!(
+                    service = "trading-engine",
+                    mode = app.mode.as_str(),
+                    instrument = estimate.instrument.0,
+                    expected_value_r = estimate.expected_value_r,
+                    outcome = ?result,
+                    "holding ev below exit threshold; position closed"
+                );
+                let mut payload = hold_ev_payload(&estimate);
+                payload["outcome_kind"] = json!(format!("{result:?}"));
+                log(&mut app.event_log, "app.position.hold_ev_exited", payload);
+            }
+        }
+    }
+}
+
+/// Flattens positions past their symbol's maximum age with reason `max_age`.
+fn check_max_position_age<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let exits =
+        app.execution
+            .check_max_position_age(&app.exchange, &app.portfolio_store, Utc::now());
+    for exit in exits {
+        warn!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            instrument = exit.instrument.0,
+            held_secs = exit.held_secs,
+            max_age_secs = exit.max_age_secs,
+            outcome = ?exit.result,
+            "position held past its maximum age; closed"
+        );
+        log(
+            &mut app.event_log,
+            "app.position.max_age_exited",
+            json!({
+                "instrument": exit.instrument.0,
+                "reason": ExitReason::MaxAge.as_str(),
+                "held_secs": exit.held_secs,
+                "max_age_secs": exit.max_age_secs,
+                "outcome_kind": format!("{:?}", exit.result),
+            }),
+        );
+    }
+}
+
+/// Instrument of the most recent event that names one, i.e. the one the
+/// operator last acted on or was told about.
+fn selected_instrument<E: crate::exchange::facade::ExchangeFacade>(
+    app: &AppBootstrap<E>,
+) -> Option<Instrument> {
+    app.event_log
+        .records
+        .iter()
+        .rev()
+        .find_map(|record| record.payload["instrument"].as_str())
+        .map(Instrument::new)
+}
+
+fn hold_ev_payload(estimate: &HoldEvEstimate) -> serde_json::Value {
+    json!({
+        "instrument": estimate.instrument.0,
+        "profile": estimate.profile_name,
+        "price": estimate.price,
+        "r_multiple": estimate.r_multiple,
+        "win_probability": estimate.win_probability,
+        "expected_value_r": estimate.expected_value_r,
+        "action": estimate.action.as_str(),
+        "model_version": estimate.model_version,
+        "trades": estimate.trades,
+    })
+}
+
+/// Logs the depth measured by the pre-trade filter, pass or block.
+fn log_depth_check<E: crate::exchange::facade::ExchangeFacade>(
+    app: &mut AppBootstrap<E>,
+    command: &ExecutionCommand,
+) {
+    let Some(check) = app.execution.last_depth_check.take() else {
+        return;
+    };
+    let instrument = match command {
+        ExecutionCommand::SetTargetExposure { instrument, .. } => instrument.0.clone(),
+        _ => return,
+    };
+    log(
+        &mut app.event_log,
+        "app.execution.depth_checked",
+        json!({
+            "instrument": instrument,
+            "bid_notional_usdt": check.bid_notional_usdt,
+            "ask_notional_usdt": check.ask_notional_usdt,
+            "required_ask_usdt": check.required_ask_usdt,
+            "imbalance": check.imbalance,
+            "passed": check.passed,
+        }),
+    );
+}
+
+fn log_slippage_check<E: crate::exchange::facade::ExchangeFacade>(
+    app: &mut AppBootstrap<E>,
+    command: &ExecutionCommand,
+) {
+    let Some(check) = app.execution.last_slippage_check.take() else {
+        return;
+    };
+    let instrument = match command {
+        ExecutionCommand::SetTargetExposure { instrument, .. } => instrument.0.clone(),
+        _ => return,
+    };
+    log(
+        &mut app.event_log,
+        "app.execution.slippage_checked",
+        json!({
+            "instrument": instrument,
+            "side": format!("{:?}", check.side),
+            "reference_price": check.reference_price,
+            "last_price": check.last_price,
+            "limit_price": check.limit_price,
+            "drift_bps": check.drift_bps,
+            "passed": check.passed,
+        }),
+    );
+}
+
+/// Queues a depth-blocked or transiently failed command for retry, or
+/// gives up past the budget; `false` when it was not queued.
+fn defer_execution_retry<E: crate::exchange::facade::ExchangeFacade>(
+    app: &mut AppBootstrap<E>,
+    command: ExecutionCommand,
+    attempts: u32,
+    error: &ExecutionError,
+) -> bool {
+    let payload = execution_request_payload(&command);
+    let deferred = app.execution.defer_execution(command, attempts);
+    log(
+        &mut app.event_log,
+        if deferred {
+            "app.execution.deferred"
+        } else {
+            "app.execution.deferral_abandoned"
+        },
+        json!({
+            "instrument": payload["instrument"],
+            "attempts": attempts,
+            "retry_secs": app.execution.depth_filter.map(|filter| filter.retry_secs),
+            "detail": error.to_string(),
+        }),
+    );
+    deferred
+}
+
+/// Re-runs depth-deferred commands whose retry window has passed.
+fn retry_deferred_executions<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    for deferred in app.execution.take_due_deferred(Utc::now()) {
+        let result = app.execution.execute(
+            &app.exchange,
+            &app.portfolio_store,
+            &app.price_store,
+            deferred.command.clone(),
+        );
+        log_depth_check(app, &deferred.command);
+        log_slippage_check(app, &deferred.command);
+        match result {
+            Ok(outcome) => log(
+                &mut app.event_log,
+                "app.execution.deferred_completed",
+                execution_payload(
+                    &deferred.command,
+                    &outcome,
+                    app.portfolio_store.snapshot.positions.len(),
+                    remaining_gross_exposure_usdt(&app.portfolio_store, &app.price_store),
+                ),
+            ),
+            Err(error)
+                if matches!(error, ExecutionError::ThinAskDepth { .. })
+                    || error.class() == Some(ErrorClass::Retryable) =>
+            {
+                defer_execution_retry(app, deferred.command, deferred.attempts + 1, &error);
+            }
+            Err(error) => log(
+                &mut app.event_log,
+                "app.execution.deferred_failed",
+                json!({
+                    "instrument": execution_request_payload(&deferred.command)["instrument"],
+                    "error": error.to_string(),
+                }),
+            ),
+        }
+    }
+}
+
+/// Appends one trace entry per debug-enabled watch: the latest price is
+/// taken as a close and fed, after the traced ones, through the watch's
+/// bar strategy, whose indicators and signal are recorded.
+fn trace_debug_watches<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let watches = app
+        .strategy_store
+        .debug_watches(app.mode)
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    for watch in watches {
+        let price = app
+            .market_data
+            .refresh_price(
+                &app.exchange,
+                &mut app.price_store,
+                watch.instrument.clone(),
+                Market::Futures,
+            )
+            .ok()
+            .or_else(|| app.price_store.current_price(&watch.instrument));
+        let closes = app
+            .strategy_store
+            .trace(watch.id)
+            .map(|trace| trace.prices().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let (mut indicators, decision) =
+            match (price, StrategyRegistry::builtin().create(watch.template)) {
+                (None, _) => (BTreeMap::new(), "skip: no price available".to_string()),
+                (Some(_), None) => (
+                    BTreeMap::new(),
+                    format!("skip: {} is not bar-driven", watch.template.slug()),
+                ),
+                (Some(price), Some(mut strategy)) => {
+                    evaluate_closes(strategy.as_mut(), closes.into_iter().chain([price]))
+                }
+            };
+        if let Some(price) = price {
+            indicators.insert("price".to_string(), price);
+        }
+        app.strategy_store.record_trace(
+            watch.id,
+            StrategyTraceEntry {
+                at: app
+                    .price_store
+                    .price_stamp(&watch.instrument)
+                    .map_or_else(Utc::now, |stamp| stamp.exchange_time_at(Utc::now())),
+                indicators,
+                decision,
+            },
+        );
+    }
+}
+
+/// Feeds every armed script watch the latest price and trades the signals
+/// of each finished minute: an entry targets the exposure its risk buys at
+/// `LIVE_BAR_STOP_DISTANCE_PCT`, an exit flattens. Watches with seed
+/// capital risk a share of their ledger's equity rather than the account's.
+///
+/// A script that fails to load or faults flattens and fails its watch; an
+/// order the venue rejects is logged and waits for the next signal.
+fn run_live_bar_strategies<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) -> Result<(), crate::error::app_error::AppError> {
+    let finished = app
+        .strategy_store
+        .history(app.mode)
+        .into_iter()
+        .map(|watch| watch.id)
+        .collect::<BTreeSet<_>>();
+    app.live_bar_strategies
+        .retain(|watch_id, _| !finished.contains(watch_id));
+    let watches = app
+        .strategy_store
+        .active_watches(app.mode)
+        .into_iter()
+        .filter(|watch| watch.state == StrategyWatchState::Armed)
+        .cloned()
+        .collect::<Vec<_>>();
+    for watch in watches {
+        // Watches restored from an earlier session load their script here.
+        if let Entry::Vacant(entry) = app.live_bar_strategies.entry(watch.id) {
+            match LiveBarStrategy::load(watch.template, &watch.config) {
+                Ok(Some(live)) => {
+                    entry.insert(live);
+                }
+                Ok(None) => continue,
+                Err(error) => {
+                    fail_live_bar_watch(app, &watch, &error.to_string())?;
+                    continue;
+                }
+            }
+        }
+        let Some(price) = app
+            .market_data
+            .refresh_price(
+                &app.exchange,
+                &mut app.price_store,
+                watch.instrument.clone(),
+                Market::Futures,
+            )
+            .ok()
+            .or_else(|| app.price_store.current_price(&watch.instrument))
+        else {
+            continue;
+        };
+        let at = app
+            .price_store
+            .price_stamp(&watch.instrument)
+            .map_or_else(Utc::now, |stamp| stamp.exchange_time_at(Utc::now()));
+        let Some(live) = app.live_bar_strategies.get_mut(&watch.id) else {
+            continue;
+        };
+        let closed = live.on_price(at, price);
+        if let Some(fault) = live.fault() {
+            fail_live_bar_watch(app, &watch, &fault)?;
+            continue;
+        }
+        let Some((close, signal)) = closed else {
+            continue;
+        };
+        let equity_usdt = app
+            .portfolio_store
+            .snapshot
+            .balances
+            .iter()
+            .map(|balance| balance.total())
+            .sum::<f64>();
+        let risk_usdt = watch
+            .ledger
+            .as_ref()
+            .map_or(equity_usdt * watch.effective_risk_pct(), |ledger| {
+                ledger.risk_budget_usdt(watch.effective_risk_pct())
+            });
+        let ev = live.entry_ev(
+            close,
+            watch.config.win_rate,
+            watch.config.r_multiple,
+            app.portfolio_store.entry_stats.taker_fee_rate,
+        );
+        let entry_gate = live.check_entry(at, &ev);
+        let target = match signal {
+            StrategySignal::Enter if !live.in_position() => match entry_gate {
+                Ok(()) => match entry_exposure(live.side(), risk_usdt, equity_usdt) {
+                    Some(target) => target,
+                    None => continue,
+                },
+                Err(reason) => {
+                    let cooldown_secs = live.cooldown_secs();
+                    log(
+                        &mut app.event_log,
+                        "app.strategy.bar_entry_skipped",
+                        json!({
+                            "watch_id": watch.id,
+                            "template": watch.template.slug(),
+                            "instrument": watch.instrument.0,
+                            "version": watch.version(),
+                            "close": close,
+                            "reason": reason.as_str(),
+                            "cooldown_secs": cooldown_secs,
+                            "expected_value_r": ev.expected_value_r,
+                            "ev_gate": ev.gate_label(),
+                        }),
+                    );
+                    continue;
+                }
+            },
+            StrategySignal::Exit if live.in_position() => 0.0,
+            _ => continue,
+        };
+        let indicators = live.indicators();
+        let mut payload = json!({
+            "watch_id": watch.id,
+            "template": watch.template.slug(),
+            "instrument": watch.instrument.0,
+            "version": watch.version(),
+            "signal": match signal {
+                StrategySignal::Enter => "enter",
+                StrategySignal::Exit => "exit",
+                StrategySignal::Hold => "hold",
+            },
+            "close": close,
+            "indicators": indicators,
+            "risk_usdt": risk_usdt,
+            "target": target,
+        });
+        if target != 0.0 {
+            payload["expected_value_r"] = json!(ev.expected_value_r);
+            payload["ev_gate"] = json!(ev.gate_label());
+        }
+        match submit_strategy_target(app, &watch, target) {
+            Ok(()) => {
+                let taker_fee_rate = app.portfolio_store.entry_stats.taker_fee_rate;
+                if let Some(live) = app.live_bar_strategies.get_mut(&watch.id) {
+                    if target == 0.0 {
+                        live.on_exited(at, close, taker_fee_rate);
+                    } else {
+                        live.on_entered(LiveBarEntry {
+                            close,
+                            notional_usdt: target.abs() * equity_usdt,
+                            risk_usdt,
+                        });
+                    }
+                }
+                log(&mut app.event_log, "app.strategy.bar_signal", payload);
+            }
+            Err(error) => {
+                payload["error"] = json!(error.to_string());
+                payload["reason"] = json!(error.reason());
+                log(&mut app.event_log, "app.strategy.bar_order_failed", payload);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Flattens what a live bar watch opened, then fails the watch with `reason`.
+fn fail_live_bar_watch<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    watch: &StrategyWatch,
+    reason: &str,
+) -> Result<(), crate::error::app_error::AppError> {
+    let live = app.live_bar_strategies.remove(&watch.id);
+    let flatten_error = live
+        .filter(LiveBarStrategy::in_position)
+        .and_then(|_| submit_strategy_target(app, watch, 0.0).err())
+        .map(|error| error.to_string());
+    let watch = app.strategy_store.fail_watch(app.mode, watch.id)?;
+    app.recorder_coordination.sync_strategy_symbols(
+        app.mode,
+        active_strategy_symbols(
+            &app.strategy_store,
+            &app.execution.synthetic_instruments,
+            app.mode,
+        ),
+    )?;
+    log(
+        &mut app.event_log,
+        "app.strategy.watch_failed",
+        json!({
+            "watch_id": watch.id,
+            "template": watch.template.slug(),
+            "instrument": watch.instrument.0,
+            "state": watch.state.as_str(),
+            "reason": reason,
+            "flatten_error": flatten_error,
+        }),
+    );
+    warn!(
+        service = "trading-engine",
+        mode = app.mode.as_str(),
+        watch_id = watch.id,
+        instrument = watch.instrument.0,
+        reason,
+        "strategy watch failed"
+    );
+    Ok(())
+}
+
+/// Sends a market order toward `target` exposure on the watch's instrument,
+/// tagged with the watch so its fills reach the watch's ledger.
+fn submit_strategy_target<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    watch: &StrategyWatch,
+    target: f64,
+) -> Result<(), ExecutionError> {
+    let target = Exposure::new(target).expect("live bar targets stay within one account");
+    app.execution.execute(
+        &app.exchange,
+        &app.portfolio_store,
+        &app.price_store,
+        ExecutionCommand::SetTargetExposure {
+            instrument: watch.instrument.clone(),
+            target,
+            order_type: OrderType::Market,
+            source: CommandSource::Strategy {
+                watch_id: watch.id,
+                version: watch.version(),
+            },
+        },
+    )?;
+    Ok(())
+}
+
+/// Runs the refresh-time strategy work and samples its cost per watch.
+fn tick_strategy_watches<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) -> Result<(), crate::error::app_error::AppError> {
+    let started = Instant::now();
+    let watches = app.strategy_store.active_watches(app.mode).len();
+    sync_paused_watches(app);
+    trace_debug_watches(app);
+    let result = run_live_bar_strategies(app)
+        .and_then(|()| finish_flatten_stops(app))
+        .and_then(|()| update_strategy_ledgers(app));
+    roll_strategy_capital(app);
+    app.self_metrics
+        .record_strategy_ticks(watches, started.elapsed(), Instant::now());
+    result
+}
+
+/// Logs a self-metrics sample for the diagnostics panel.
+fn sample_diagnostics<E: crate::exchange::facade::ExchangeFacade>(app: &mut AppBootstrap<E>) {
+    let active_watches = app.strategy_store.active_watches(app.mode).len();
+    let strategy_symbols = active_strategy_symbols(
+        &app.strategy_store,
+        &app.execution.synthetic_instruments,
+        app.mode,
+    )
+    .into_iter()
+    .collect::<BTreeSet<_>>()
+    .len();
+    let snapshot = app
+        .self_metrics
+        .snapshot(Instant::now(), active_watches, strategy_symbols);
+    let overloaded = snapshot.overloaded();
+    let memory = MemoryEstimate::of(app);
+    if !overloaded.is_empty() {
+        warn!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            overloaded = %overloaded.join(","),
+            active_watches,
+            strategy_symbols,
+            "operator host is overloaded"
+        );
+    }
+    log(
+        &mut app.event_log,
+        "app.diagnostics.sampled",
+        json!({
+            "snapshot": snapshot,
+            "overloaded": overloaded,
+            "rest": app.rest_latency.snapshot(),
+            "rest_budget": app.rest_budget.as_ref().map(|budget| budget.snapshot()),
+            "memory": memory,
+        }),
+    );
+}
+
+/// Samples diagnostics afresh and zips them with the redacted config,
+/// session state and recent events for a bug report.
+fn export_diagnostics<E: crate::exchange::facade::ExchangeFacade>(
+    app: &mut AppBootstrap<E>,
+    path: Option<std::path::PathBuf>,
+) -> Result<(), crate::error::app_error::AppError> {
+    sample_diagnostics(app);
+    let now = Utc::now();
+    let path = path.unwrap_or_else(|| default_bundle_path(now).into());
+    let mut bundle = DiagnosticsBundle::default();
+    bundle.add_json(
+        "version.json",
+        &json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+            "mode": app.mode.as_str(),
+            "exported_at": now.to_rfc3339(),
+        }),
+    );
+    bundle.add_json("config.json", &json!(redacted_config(std::env::vars())));
+    bundle.add_json(
+        "session.json",
+        &json!({
+            "mode": app.mode.as_str(),
+            "locale": app.locale.as_str(),
+            "theme": app.theme.as_str(),
+            "entries_blackout_until": app
+                .execution
+                .entries_blackout_until
+                .map(|until| until.to_rfc3339()),
+            "watches": app
+                .strategy_store
+                .active_watches(app.mode)
+                .into_iter()
+                .map(|watch| json!({
+                    "watch_id": watch.id,
+                    "template": watch.template.slug(),
+                    "instrument": watch.instrument.0,
+                    "state": watch.state.as_str(),
+                    "group": watch.group,
+                    "overtrading_clusters": app
+                        .strategy_store
+                        .overtrading_guard(watch.id)
+                        .map(|guard| guard
+                            .clusters()
+                            .iter()
+                            .map(|cluster| json!({
+                                "start_ms": cluster.start_ms,
+                                "trades": cluster.trades,
+                                "span_secs": cluster.span_secs(),
+                            }))
+                            .collect::<Vec<_>>()),
+                }))
+                .collect::<Vec<_>>(),
+            "price_alerts": app.price_alerts.alerts(),
+        }),
+    );
+    bundle.add_json(
+        "network.json",
+        &json!({
+            "diagnostics": app
+                .event_log
+                .records
+                .iter()
+                .rev()
+                .find(|record| record.kind == "app.diagnostics.sampled")
+                .map(|record| record.payload.clone()),
+            "degraded_symbols": app
+                .recorder_coordination
+                .degraded_symbols(app.mode, now.timestamp_millis())
+                .unwrap_or_default(),
+        }),
+    );
+    let events = &app.event_log.records[app
+        .event_log
+        .records
+        .len()
+        .saturating_sub(DEFAULT_BUNDLE_EVENTS)..];
+    let lines = events
+        .iter()
+        .map(|record| json!({ "kind": record.kind, "payload": record.payload }).to_string())
+        .collect::<Vec<_>>();
+    let event_count = lines.len();
+    bundle.add("events.jsonl", lines.join("\n") + "\n");
+    bundle.add_file("macros.json", &ShellMacroStore::default_path());
+    let bytes = bundle.write(&path)?;
+    info!(
+        service = "trading-engine",
+        mode = app.mode.as_str(),
+        path = %path.display(),
+        bytes,
+        "diagnostics bundle exported"
+    );
+    log(
+        &mut app.event_log,
+        "app.diagnostics.exported",
+        json!({
+            "path": path.display().to_string(),
+            "bytes": bytes,
+            "events": event_count,
+            "files": bundle.names(),
+        }),
+    );
+    Ok(())
+}
+
+/// Pauses watches on instruments the recorder marked degraded after a
+/// reconnect storm or the venue halted, and re-arms them once both clear.
+fn sync_paused_watches<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let degraded = app
+        .recorder_coordination
+        .degraded_symbols(app.mode, Utc::now().timestamp_millis())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|degraded| degraded.symbol)
+        .collect::<BTreeSet<_>>();
+    let execution = &app.execution;
+    let changed = app.strategy_store.apply_degraded(app.mode, |instrument| {
+        execution.halted_status(instrument).is_some()
+            || match execution.synthetic_instruments.get(instrument) {
+                Some(synthetic) => synthetic
+                    .legs
+                    .iter()
+                    .any(|leg| degraded.contains(&leg.instrument.0)),
+                None => degraded.contains(&instrument.0),
+            }
+    });
+    for watch in changed {
+        let paused = watch.state == StrategyWatchState::Paused;
+        let halted = app
+            .execution
+            .halted_status(&watch.instrument)
+            .map(|(symbol, status)| (symbol.0.clone(), status.status().to_string()));
+        let kind = match (paused, &halted) {
+            (true, Some(_)) => "app.strategy.paused_halted",
+            (true, None) => "app.strategy.paused_degraded",
+            (false, _) => "app.strategy.resumed",
+        };
+        log(
+            &mut app.event_log,
+            kind,
+            json!({
+                "watch_id": watch.id,
+                "template": watch.template.slug(),
+                "instrument": watch.instrument.0,
+                "state": watch.state.as_str(),
+                "halted_symbol": halted.as_ref().map(|(symbol, _)| symbol),
+                "status": halted.as_ref().map(|(_, status)| status),
+            }),
+        );
+        if let (true, Some((symbol, status))) = (paused, &halted) {
+            warn!(
+                service = "trading-engine",
+                mode = app.mode.as_str(),
+                watch_id = watch.id,
+                instrument = watch.instrument.0,
+                symbol = %symbol,
+                status = %status,
+                "strategy watch paused: symbol halted by the exchange"
+            );
+        } else if paused {
+            warn!(
+                service = "trading-engine",
+                mode = app.mode.as_str(),
+                watch_id = watch.id,
+                instrument = watch.instrument.0,
+                "strategy watch paused: instrument degraded by reconnect breaker"
+            );
+        } else {
+            info!(
+                service = "trading-engine",
+                mode = app.mode.as_str(),
+                watch_id = watch.id,
+                instrument = watch.instrument.0,
+                "strategy watch resumed: instrument recovered"
+            );
+        }
+    }
+}
+
+/// Attributes position PnL to watches with seed capital and stops the ones
+/// whose allocation is used up, and moves size ramps up after trades that
+/// did not lose.
+fn adaptation_changes_json(adaptation: &Adaptation) -> Vec<serde_json::Value> {
+    adaptation
+        .changes
+        .iter()
+        .map(|change| {
+            json!({
+                "param": change.param.as_str(),
+                "from": change.from,
+                "to": change.to,
+            })
+        })
+        .collect()
+}
+
+fn update_strategy_ledgers<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) -> Result<(), crate::error::app_error::AppError> {
+    let instruments = app
+        .strategy_store
+        .active_watches(app.mode)
+        .into_iter()
+        .map(|watch| watch.instrument.clone())
+        .collect::<BTreeSet<_>>();
+    let mut exhausted = Vec::new();
+    let mut ramped = Vec::new();
+    let mut adapted = Vec::new();
+    let mut scored = Vec::new();
+    for instrument in instruments {
+        let position = app
+            .portfolio_store
+            .snapshot
+            .positions
+            .get(&instrument)
+            .cloned();
+        let mark_price = app.price_store.current_price(&instrument);
+        exhausted.extend(
+            app.strategy_store
+                .update_ledgers(app.mode, &instrument, mark_price),
+        );
+        ramped.extend(app.strategy_store.update_ramps(
+            app.mode,
+            &instrument,
+            position.as_ref(),
+            mark_price,
+        ));
+        adapted.extend(app.strategy_store.update_adaptations(
+            app.mode,
+            &instrument,
+            position.as_ref(),
+            mark_price,
+        ));
+        app.strategy_store
+            .update_kpis(app.mode, &instrument, position.as_ref(), mark_price);
+        scored.extend(
+            app.strategy_store
+                .update_r_multiples(
+                    app.mode,
+                    &instrument,
+                    position.as_ref(),
+                    mark_price,
+                    app.execution.protective_stop_price(&instrument),
+                )
+                .into_iter()
+                .map(|(watch_id, r)| (watch_id, instrument.clone(), r)),
+        );
+    }
+    for (watch_id, instrument, r_multiple) in scored {
+        let stats = app
+            .strategy_store
+            .get(app.mode, watch_id)
+            .map(|watch| watch.r_multiples.stats.clone())
+            .unwrap_or_default();
+        log(
+            &mut app.event_log,
+            "app.strategy.trade_scored",
+            json!({
+                "watch_id": watch_id,
+                "instrument": instrument.0,
+                "r_multiple": r_multiple,
+                "trades": stats.trades,
+                "average_r": stats.average_r(),
+                "expectancy_r": stats.expectancy_r(),
+            }),
+        );
+        let Some((watch, cluster)) =
+            app.strategy_store
+                .record_trade(app.mode, watch_id, Utc::now().timestamp_millis())
+        else {
+            continue;
+        };
+        let paused_until_ms = app
+            .strategy_store
+            .overtrading_guard(watch_id)
+            .map(|guard| guard.paused_until_ms());
+        log(
+            &mut app.event_log,
+            "app.strategy.overtrading_paused",
+            json!({
+                "watch_id": watch_id,
+                "instrument": instrument.0,
+                "state": watch.state.as_str(),
+                "trades": cluster.trades,
+                "span_secs": cluster.span_secs(),
+                "paused_until": paused_until_ms
+                    .and_then(DateTime::<Utc>::from_timestamp_millis)
+                    .map(|until| until.to_rfc3339()),
+            }),
+        );
+        warn!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            watch_id,
+            instrument = instrument.0,
+            trades = cluster.trades,
+            span_secs = cluster.span_secs(),
+            "strategy watch paused: overtrading"
+        );
+    }
+    for watch in ramped {
+        let Some(ramp) = watch.ramp.as_ref() else {
+            continue;
+        };
+        log(
+            &mut app.event_log,
+            "app.strategy.ramp_advanced",
+            json!({
+                "watch_id": watch.id,
+                "instrument": watch.instrument.0,
+                "qualifying_trades": ramp.qualifying_trades,
+                "full_after_trades": ramp.full_after_trades,
+                "size_fraction": ramp.fraction(),
+                "effective_risk_pct": watch.effective_risk_pct(),
+                "complete": ramp.is_complete(),
+            }),
+        );
+        info!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            watch_id = watch.id,
+            instrument = watch.instrument.0,
+            size_fraction = ramp.fraction(),
+            "strategy size ramp advanced"
+        );
+    }
+    for (watch, adaptation) in adapted {
+        log(
+            &mut app.event_log,
+            "app.strategy.adapted",
+            json!({
+                "watch_id": watch.id,
+                "instrument": watch.instrument.0,
+                "trades": adaptation.trades,
+                "win_rate": adaptation.win_rate,
+                "pnl": adaptation.pnl,
+                "changes": adaptation_changes_json(&adaptation),
+                "version": watch.version(),
+            }),
+        );
+        info!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            watch_id = watch.id,
+            instrument = watch.instrument.0,
+            risk_pct = watch.config.risk_pct,
+            r_multiple = watch.config.r_multiple,
+            "strategy parameters adapted"
+        );
+    }
+    if exhausted.is_empty() {
+        return Ok(());
+    }
+    for watch_id in exhausted {
+        let watch = app.strategy_store.stop_watch(app.mode, watch_id)?;
+        let Some(ledger) = watch.ledger.as_ref() else {
+            continue;
+        };
+        log(
+            &mut app.event_log,
+            "app.strategy.capital_exhausted",
+            json!({
+                "watch_id": watch.id,
+                "template": watch.template.slug(),
+                "instrument": watch.instrument.0,
+                "seed_capital_usdt": ledger.seed_capital_usdt,
+                "realized_pnl_usdt": ledger.realized_pnl_usdt,
+                "unrealized_pnl_usdt": ledger.unrealized_pnl_usdt,
+                "equity_usdt": ledger.equity_usdt(),
+                "roi": ledger.roi(),
+            }),
+        );
+        info!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            watch_id = watch.id,
+            instrument = watch.instrument.0,
+            equity_usdt = ledger.equity_usdt(),
+            "strategy watch stopped: seed capital exhausted"
+        );
+    }
+    app.recorder_coordination.sync_strategy_symbols(
+        app.mode,
+        active_strategy_symbols(
+            &app.strategy_store,
+            &app.execution.synthetic_instruments,
+            app.mode,
+        ),
+    )?;
+    Ok(())
+}
+
+/// Closes the UTC day on the strategy ledgers and logs any capital the
+/// auto allocation moved between watches.
+fn roll_strategy_capital<E: crate::exchange::facade::ExchangeFacade>(app: &mut AppBootstrap<E>) {
+    let shifts = app
+        .strategy_store
+        .roll_ledger_days(app.mode, Utc::now().date_naive());
+    if shifts.is_empty() {
+        return;
+    }
+    info!(
+        service = "trading-engine",
+        mode = app.mode.as_str(),
+        watches = shifts.len(),
+        "strategy capital reallocated"
+    );
+    log(
+        &mut app.event_log,
+        "app.strategy.capital_reallocated",
+        json!({
+            "shifts": shifts
+                .iter()
+                .map(|shift| json!({
+                    "watch_id": shift.watch_id,
+                    "from_weight": shift.from_weight,
+                    "to_weight": shift.to_weight,
+                    "delta_usdt": shift.delta_usdt,
+                }))
+                .collect::<Vec<_>>(),
+        }),
+    );
+}
+
+/// Refreshes prices for instruments with armed alert lines and fires the
+/// alerts whose level was crossed since the previous observation.
+fn check_price_alerts<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) -> Result<(), crate::error::app_error::AppError> {
+    let instruments = app.price_alerts.armed_instruments();
+    if instruments.is_empty() {
+        return Ok(());
+    }
+    let mut triggered = Vec::new();
+    for instrument in instruments {
+        let price = app
+            .market_data
+            .refresh_price(
+                &app.exchange,
+                &mut app.price_store,
+                instrument.clone(),
+                Market::Futures,
+            )
+            .ok()
+            .or_else(|| app.price_store.current_price(&instrument));
+        if let Some(price) = price {
+            triggered.extend(app.price_alerts.observe(&instrument, price));
+        }
+    }
+    app.price_alerts.save()?;
+
+    for alert in triggered {
+        log(
+            &mut app.event_log,
+            "app.alert.triggered",
+            alert_payload(&alert),
+        );
+        info!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            alert_id = alert.id,
+            instrument = alert.instrument,
+            price = alert.price,
+            action = alert.action.label(),
+            "price alert triggered"
+        );
+        run_alert_action(app, &alert)?;
+    }
+    Ok(())
+}
+
+fn run_alert_action<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    alert: &PriceAlert,
+) -> Result<(), crate::error::app_error::AppError> {
+    let instrument = Instrument::new(alert.instrument.clone());
+    match &alert.action {
+        PriceAlertAction::Notify => {}
+        PriceAlertAction::StartStrategy { template } => {
+            let template =
+                alert
+                    .action
+                    .strategy_template()
+                    .ok_or_else(|| AlertError::UnknownTemplate {
+                        id: alert.id,
+                        template: template.clone(),
+                    })?;
+            let watch = app.strategy_store.create_watch(
+                app.mode,
+                template,
+                instrument,
+                StrategyStartConfig::default(),
+            )?;
+            app.recorder_coordination.sync_strategy_symbols(
+                app.mode,
+                active_strategy_symbols(
+                    &app.strategy_store,
+                    &app.execution.synthetic_instruments,
+                    app.mode,
+                ),
+            )?;
+            log(
+                &mut app.event_log,
+                "app.alert.action_completed",
+                json!({
+                    "alert_id": alert.id,
+                    "action": alert.action.label(),
+                    "watch_id": watch.id,
+                }),
+            );
+        }
+        PriceAlertAction::SetTargetExposure { .. } => {
+            app.price_alerts.hold_for_confirmation(alert.id);
+            app.price_alerts.save()?;
+            log(
+                &mut app.event_log,
+                "app.alert.confirmation_required",
+                json!({
+                    "alert_id": alert.id,
+                    "instrument": instrument.0,
+                    "action": alert.action.label(),
+                }),
+            );
+            warn!(
+                service = "trading-engine",
+                mode = app.mode.as_str(),
+                alert_id = alert.id,
+                instrument = instrument.0,
+                action = alert.action.label(),
+                "price alert holds its order until /alert confirm"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Places the market order a confirmed `target` alert was holding.
+fn submit_alert_target<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    alert: &PriceAlert,
+    target: f64,
+) -> Result<ExecutionOutcome, crate::error::app_error::AppError> {
+    let target = Exposure::new(target).ok_or(AlertError::InvalidTarget {
+        id: alert.id,
+        target,
+    })?;
+    Ok(app.execution.execute(
+        &app.exchange,
+        &app.portfolio_store,
+        &app.price_store,
+        ExecutionCommand::SetTargetExposure {
+            instrument: Instrument::new(alert.instrument.clone()),
+            target,
+            order_type: OrderType::Market,
+            source: CommandSource::User,
+        },
+    )?)
+}
+
+fn alert_payload(alert: &PriceAlert) -> serde_json::Value {
+    json!({
+        "alert_id": alert.id,
+        "instrument": alert.instrument,
+        "price": alert.price,
+        "action": alert.action.label(),
+        "last_price": alert.last_price,
+        "triggered_at": alert.triggered_at,
+        "awaiting_confirmation": alert.awaiting_confirmation,
+    })
+}
+
+/// Applies `action` to every active watch in `group` and logs one summary.
+///
+/// A bulk stop or delete never resolves open positions; those watches stay
+/// active, keep their saved group and are listed as `needs_confirmation`.
+fn run_strategy_bulk<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    group: &str,
+    action: StrategyGroupAction,
+) -> Result<(), crate::error::app_error::AppError> {
+    let watch_ids = app.strategy_store.group_watch_ids(app.mode, group);
+    if watch_ids.is_empty() {
+        return Err(
+            crate::error::strategy_error::StrategyError::GroupNotFound(group.to_string()).into(),
+        );
+    }
+    let (changed, needs_confirmation) = apply_strategy_action(app, &watch_ids, action)?;
+    log(
+        &mut app.event_log,
+        "app.strategy.bulk_applied",
+        json!({
+            "group": group,
+            "action": action.as_str(),
+            "watches": watch_ids.len(),
+            "changed": changed,
+            "needs_confirmation": needs_confirmation,
+        }),
+    );
+    info!(
+        service = "trading-engine",
+        mode = app.mode.as_str(),
+        group = group,
+        action = action.as_str(),
+        changed = changed.len(),
+        "strategy group bulk action applied"
+    );
+    Ok(())
+}
+
+/// Lists the watches `filter` selects, then applies `action` to them once
+/// confirmed.
+///
+/// The preview logs `app.strategy.select_previewed`; applying logs
+/// `app.strategy.bulk_applied` with the filter in place of a group, so it
+/// reads like any other bulk action.
+fn run_strategy_select<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    filter: &StrategyFilter,
+    action: Option<StrategyGroupAction>,
+    confirmed: bool,
+) -> Result<(), crate::error::app_error::AppError> {
+    let watch_ids = app.strategy_store.matching_watch_ids(app.mode, filter);
+    let Some(action) = action.filter(|_| confirmed) else {
+        log(
+            &mut app.event_log,
+            "app.strategy.select_previewed",
+            json!({
+                "filter": filter.to_string(),
+                "action": action.map(StrategyGroupAction::as_str),
+                "matched": watch_ids,
+            }),
+        );
+        return Ok(());
+    };
+    let (changed, needs_confirmation) = apply_strategy_action(app, &watch_ids, action)?;
+    log(
+        &mut app.event_log,
+        "app.strategy.bulk_applied",
+        json!({
+            "group": null,
+            "filter": filter.to_string(),
+            "action": action.as_str(),
+            "watches": watch_ids.len(),
+            "changed": changed,
+            "needs_confirmation": needs_confirmation,
+        }),
+    );
+    info!(
+        service = "trading-engine",
+        mode = app.mode.as_str(),
+        filter = %filter,
+        action = action.as_str(),
+        changed = changed.len(),
+        "strategy filter bulk action applied"
+    );
+    Ok(())
+}
+
+/// Returns the watches `action` changed and those still waiting on a stop
+/// resolution.
+fn apply_strategy_action<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    watch_ids: &[u64],
+    action: StrategyGroupAction,
+) -> Result<(Vec<u64>, Vec<u64>), crate::error::app_error::AppError> {
+    let mut changed = Vec::new();
+    let mut needs_confirmation = Vec::new();
+    let mut forgotten = false;
+    for watch_id in watch_ids.iter().copied() {
+        match action {
+            StrategyGroupAction::Enable | StrategyGroupAction::Disable => {
+                let enabled = action == StrategyGroupAction::Enable;
+                if app
+                    .strategy_store
+                    .set_enabled(app.mode, watch_id, enabled)?
+                    .is_some()
+                {
+                    changed.push(watch_id);
+                }
+            }
+            StrategyGroupAction::Stop | StrategyGroupAction::Delete => {
+                stop_strategy_watch(app, watch_id, None)?;
+                match app.strategy_store.get(app.mode, watch_id) {
+                    Some(watch) if watch.state != StrategyWatchState::Stopped => {
+                        needs_confirmation.push(watch_id);
+                    }
+                    Some(watch) => {
+                        if action == StrategyGroupAction::Delete {
+                            forgotten |= app.strategy_groups.forget(watch);
+                        }
+                        changed.push(watch_id);
+                    }
+                    None => changed.push(watch_id),
+                }
+            }
+        }
+    }
+    if forgotten {
+        app.strategy_groups.save()?;
+    }
+    Ok((changed, needs_confirmation))
+}
+
+/// Global risk-off: disables every watch and new entries, then cancels and
+/// closes instrument by instrument, logging `app.safety.panic_progress`
+/// after each, and reconciles against a fresh refresh at the end.
+///
+/// Unconfirmed, it only logs what would be touched.
+fn run_panic<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    confirmed: bool,
+) -> Result<(), crate::error::app_error::AppError> {
+    app.portfolio_sync
+        .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+    let instruments = panic_instruments(app);
+    let watch_ids = app
+        .strategy_store
+        .active_watches(app.mode)
+        .iter()
+        .map(|watch| watch.id)
+        .collect::<Vec<_>>();
+    if !confirmed {
+        log(
+            &mut app.event_log,
+            "app.safety.panic_confirmation_required",
+            json!({
+                "instruments": instruments.iter().map(|instrument| instrument.0.as_str()).collect::<Vec<_>>(),
+                "open_orders": app.portfolio_store.snapshot.open_orders.values().map(Vec::len).sum::<usize>(),
+                "active_watches": watch_ids.len(),
+            }),
+        );
+        return Ok(());
+    }
+
+    warn!(
+        service = "trading-engine",
+        mode = app.mode.as_str(),
+        instruments = instruments.len(),
+        watches = watch_ids.len(),
+        "panic: disabling strategies and flattening every instrument"
+    );
+    let mut disabled_watches = Vec::new();
+    for watch_id in watch_ids {
+        if app
+            .strategy_store
+            .set_enabled(app.mode, watch_id, false)?
+            .is_some()
+        {
+            disabled_watches.push(watch_id);
+        }
+    }
+    app.execution.entries_disabled = true;
+    let dropped_deferred = std::mem::take(&mut app.execution.deferred_executions).len();
+
+    let total = instruments.len();
+    let mut steps = Vec::new();
+    for (index, instrument) in instruments.iter().enumerate() {
+        let result =
+            app.execution
+                .emergency_close_position(&app.exchange, &app.portfolio_store, instrument);
+        let step = json!({
+            "instrument": instrument.0,
+            "step": index + 1,
+            "total": total,
+            "cancelled": result.cancelled,
+            "cancel_failed": result.cancel_failed,
+            "outcome_kind": format!("{:?}", result.close),
+        });
+        log(
+            &mut app.event_log,
+            "app.safety.panic_progress",
+            step.clone(),
+        );
+        steps.push(step);
+    }
+
+    app.portfolio_sync
+        .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
+    let snapshot = &app.portfolio_store.snapshot;
+    let remaining_positions = snapshot
+        .positions
+        .values()
+        .filter(|position| position.signed_qty.abs() > f64::EPSILON)
+        .map(|position| position.instrument.0.clone())
+        .collect::<Vec<_>>();
+    let remaining_open_orders = snapshot.open_orders.values().map(Vec::len).sum::<usize>();
+    warn!(
+        service = "trading-engine",
+        mode = app.mode.as_str(),
+        instruments = total,
+        remaining_positions = remaining_positions.len(),
+        remaining_open_orders,
+        "panic completed"
+    );
+    log(
+        &mut app.event_log,
+        "app.safety.panic_completed",
+        json!({
+            "disabled_watches": disabled_watches,
+            "dropped_deferred": dropped_deferred,
+            "steps": steps,
+            "remaining_positions": remaining_positions,
+            "remaining_open_orders": remaining_open_orders,
+            "entries_disabled": true,
+        }),
+    );
+    Ok(())
+}
+
+/// Instruments holding a position or an open order, in symbol order.
+fn panic_instruments<E: crate::exchange::facade::ExchangeFacade>(
+    app: &AppBootstrap<E>,
+) -> Vec<Instrument> {
+    let snapshot = &app.portfolio_store.snapshot;
+    snapshot
+        .positions
+        .values()
+        .filter(|position| position.signed_qty.abs() > f64::EPSILON)
+        .map(|position| position.instrument.clone())
+        .chain(
+            snapshot
+                .open_orders
+                .iter()
+                .filter(|(_, orders)| !orders.is_empty())
+                .map(|(instrument, _)| instrument.clone()),
+        )
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Stops a watch, previewing the PnL impact first when it holds a position.
+///
+/// Example:
+/// - watch `3` on `BTCUSDT` with a live short and no resolution
+/// - logs `app.strategy.stop_confirmation_required` and leaves the watch armed
+/// - `flatten` closes the position before the watch is stopped
+///
+/// Any open position on the watch instrument counts as held by the watch.
+fn stop_strategy_watch<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    watch_id: u64,
+    resolution: Option<OpenPositionResolution>,
+) -> Result<(), crate::error::app_error::AppError> {
+    let watch = app
+        .strategy_store
+        .active_watches(app.mode)
+        .into_iter()
+        .find(|watch| watch.id == watch_id)
+        .cloned()
+        .ok_or(crate::error::strategy_error::StrategyError::WatchNotFound(
+            watch_id,
+        ))?;
+    let position = app
+        .portfolio_store
+        .snapshot
+        .positions
+        .get(&watch.instrument)
+        .filter(|position| !position.is_flat())
+        .cloned();
 
-                let post_report = app
-                    .portfolio_sync
-                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
-                refresh_position_prices(app)?;
+    if let Some(position) = &position {
+        match resolution {
+            None => {
+                let mark_price = app.price_store.current_price(&position.instrument);
+                let unrealized_pnl_usdt = mark_price.and_then(|mark| position.unrealized_pnl(mark));
                 log(
                     &mut app.event_log,
-                    "app.portfolio.refreshed",
+                    "app.strategy.stop_confirmation_required",
                     json!({
-                        "positions": post_report.positions,
-                        "open_order_groups": post_report.open_order_groups,
-                        "balances": post_report.balances,
-                        "phase": "post_execution",
+                        "watch_id": watch.id,
+                        "template": watch.template.slug(),
+                        "instrument": watch.instrument.0,
+                        "market": format!("{:?}", position.market),
+                        "side": position.side().map(|side| format!("{side:?}")),
+                        "qty": position.abs_qty(),
+                        "entry_price": position.entry_price,
+                        "mark_price": mark_price,
+                        "unrealized_pnl_usdt": unrealized_pnl_usdt,
+                        "options": ["keep", "flatten", "manual"],
                     }),
                 );
+                return Ok(());
+            }
+            Some(OpenPositionResolution::Keep) => {
                 log(
                     &mut app.event_log,
-                    "app.execution.completed",
-                    execution_payload(
-                        &command,
-                        &outcome,
-                        post_report.positions,
-                        remaining_gross_exposure_usdt(&app.portfolio_store, &app.price_store),
-                    ),
+                    "app.strategy.stop_cancelled",
+                    json!({
+                        "watch_id": watch.id,
+                        "instrument": watch.instrument.0,
+                        "position_resolution": "keep",
+                    }),
                 );
-                info!(service = "trading-engine", mode = app.mode.as_str(), command = ?command, outcome = ?outcome, "execution command completed");
+                return Ok(());
             }
-            AppCommand::Strategy(command) => match command {
-                StrategyCommand::Templates | StrategyCommand::List | StrategyCommand::History => {}
-                StrategyCommand::Show { watch_id } => {
-                    app.strategy_store.get(app.mode, watch_id).ok_or(
-                        crate::error::strategy_error::StrategyError::WatchNotFound(watch_id),
-                    )?;
-                }
-                StrategyCommand::Start {
-                    template,
-                    instrument,
-                    config,
-                } => {
-                    app.exchange
-                        .load_symbol_rules(&instrument, Market::Futures)?;
-                    let watch = app.strategy_store.create_watch(
-                        app.mode,
-                        template,
-                        instrument.clone(),
-                        config.clone(),
-                    )?;
-                    app.recorder_coordination.sync_strategy_symbols(
-                        app.mode,
-                        active_strategy_symbols(&app.strategy_store, app.mode),
-                    )?;
-                    log(
-                        &mut app.event_log,
-                        "app.strategy.watch_started",
-                        json!({
-                            "watch_id": watch.id,
-                            "mode": format!("{:?}", watch.mode).to_ascii_lowercase(),
-                            "template": watch.template.slug(),
-                            "instrument": watch.instrument.0,
-                            "state": watch.state.as_str(),
-                            "risk_pct": watch.config.risk_pct,
-                            "win_rate": watch.config.win_rate,
-                            "r_multiple": watch.config.r_multiple,
-                            "max_entry_slippage_pct": watch.config.max_entry_slippage_pct,
-                            "current_step": watch.current_step,
-                        }),
-                    );
-                    info!(
-                        service = "trading-engine",
-                        mode = app.mode.as_str(),
-                        watch_id = watch.id,
-                        instrument = watch.instrument.0,
-                        template = watch.template.slug(),
-                        "strategy watch started"
-                    );
-                }
-                StrategyCommand::Stop { watch_id } => {
-                    let watch = app.strategy_store.stop_watch(app.mode, watch_id)?;
-                    app.recorder_coordination.sync_strategy_symbols(
-                        app.mode,
-                        active_strategy_symbols(&app.strategy_store, app.mode),
+            Some(OpenPositionResolution::Flatten) => {
+                if app.strategy_store.hold_stop_until_flat(watch.id) {
+                    app.execution.execute(
+                        &app.exchange,
+                        &app.portfolio_store,
+                        &app.price_store,
+                        ExecutionCommand::CloseSymbol {
+                            instrument: watch.instrument.clone(),
+                            source: CommandSource::System,
+                        },
                     )?;
-                    log(
-                        &mut app.event_log,
-                        "app.strategy.watch_stopped",
-                        json!({
-                            "watch_id": watch.id,
-                            "mode": format!("{:?}", watch.mode).to_ascii_lowercase(),
-                            "template": watch.template.slug(),
-                            "instrument": watch.instrument.0,
-                            "state": watch.state.as_str(),
-                        }),
-                    );
-                    info!(
-                        service = "trading-engine",
-                        mode = app.mode.as_str(),
-                        watch_id = watch.id,
-                        instrument = watch.instrument.0,
-                        template = watch.template.slug(),
-                        "strategy watch stopped"
-                    );
                 }
-            },
-            AppCommand::RefreshAuthoritativeState => {
-                let report = app
-                    .portfolio_sync
-                    .refresh_authoritative(&app.exchange, &mut app.portfolio_store)?;
-                refresh_position_prices(app)?;
-                let today_realized_pnl_usdt = app.exchange.load_today_realized_pnl_usdt().ok();
-                let today_funding_pnl_usdt = app.exchange.load_today_funding_pnl_usdt().ok();
-                let margin_ratio = app.exchange.load_margin_ratio().ok().flatten();
                 log(
                     &mut app.event_log,
-                    "app.portfolio.refreshed",
+                    "app.strategy.stop_pending",
                     json!({
-                        "positions": report.positions,
-                        "open_order_groups": report.open_order_groups,
-                        "balances": report.balances,
-                        "today_realized_pnl_usdt": today_realized_pnl_usdt,
-                        "today_funding_pnl_usdt": today_funding_pnl_usdt,
-                        "margin_ratio": margin_ratio,
+                        "watch_id": watch.id,
+                        "instrument": watch.instrument.0,
+                        "position_resolution": "flatten",
                     }),
                 );
-                info!(
-                    service = "trading-engine",
-                    mode = app.mode.as_str(),
-                    positions = report.positions,
-                    open_order_groups = report.open_order_groups,
-                    balances = report.balances,
-                    "authoritative state refreshed"
+                return Ok(());
+            }
+            Some(OpenPositionResolution::Manual) => {
+                let entry_price = position
+                    .entry_price
+                    .or_else(|| app.price_store.current_price(&position.instrument))
+                    .ok_or(
+                        crate::error::strategy_error::StrategyError::NoHandoverPrice(watch.id),
+                    )?;
+                app.portfolio_store.adopt_position(
+                    &ExternalPosition {
+                        instrument: position.instrument.clone(),
+                        market: position.market,
+                        signed_qty: position.signed_qty,
+                        exchange_entry_price: position.entry_price,
+                    },
+                    entry_price,
+                    Utc::now(),
                 );
             }
         }
+    }
 
-        Ok(())
+    let position_resolution = match (&position, resolution) {
+        (Some(_), Some(resolution)) => resolution.as_str(),
+        _ => "none",
+    };
+    finish_strategy_stop(app, watch_id, position_resolution)
+}
+
+/// Stops watches held by `flatten` once their position has closed.
+fn finish_flatten_stops<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) -> Result<(), crate::error::app_error::AppError> {
+    for watch_id in app.strategy_store.flatten_stops() {
+        let Some(watch) = app.strategy_store.get(app.mode, watch_id) else {
+            continue;
+        };
+        let flat = app
+            .portfolio_store
+            .snapshot
+            .positions
+            .get(&watch.instrument)
+            .is_none_or(|position| position.is_flat());
+        if flat {
+            finish_strategy_stop(app, watch_id, "flatten")?;
+        }
     }
+    Ok(())
 }
 
+fn finish_strategy_stop<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+    watch_id: u64,
+    position_resolution: &str,
+) -> Result<(), crate::error::app_error::AppError> {
+    let watch = app.strategy_store.stop_watch(app.mode, watch_id)?;
+    app.recorder_coordination.sync_strategy_symbols(
+        app.mode,
+        active_strategy_symbols(
+            &app.strategy_store,
+            &app.execution.synthetic_instruments,
+            app.mode,
+        ),
+    )?;
+    log(
+        &mut app.event_log,
+        "app.strategy.watch_stopped",
+        json!({
+            "watch_id": watch.id,
+            "mode": format!("{:?}", watch.mode).to_ascii_lowercase(),
+            "template": watch.template.slug(),
+            "instrument": watch.instrument.0,
+            "state": watch.state.as_str(),
+            "position_resolution": position_resolution,
+        }),
+    );
+    info!(
+        service = "trading-engine",
+        mode = app.mode.as_str(),
+        watch_id = watch.id,
+        instrument = watch.instrument.0,
+        template = watch.template.slug(),
+        position_resolution,
+        "strategy watch stopped"
+    );
+    Ok(())
+}
+
+/// Symbols the recorder should collect; synthetic watches expand to their legs.
 fn active_strategy_symbols(
     store: &crate::strategy::store::StrategyStore,
+    synthetics: &BTreeMap<Instrument, SyntheticInstrument>,
     mode: crate::app::bootstrap::BinanceMode,
 ) -> Vec<String> {
     store
         .active_watches(mode)
         .into_iter()
-        .map(|watch| watch.instrument.0.clone())
+        .flat_map(|watch| match synthetics.get(&watch.instrument) {
+            Some(synthetic) => synthetic
+                .legs
+                .iter()
+                .map(|leg| leg.instrument.0.clone())
+                .collect(),
+            None => vec![watch.instrument.0.clone()],
+        })
+        .collect()
+}
+
+/// Symbols the session is exposed to: open positions, resting orders and
+/// active strategy watches, with spreads broken into their legs.
+fn traded_symbols<E: crate::exchange::facade::ExchangeFacade>(
+    app: &AppBootstrap<E>,
+) -> BTreeSet<Instrument> {
+    let snapshot = &app.portfolio_store.snapshot;
+    snapshot
+        .positions
+        .iter()
+        .filter(|(_, position)| !position.is_flat())
+        .map(|(instrument, _)| instrument.clone())
+        .chain(
+            snapshot
+                .open_orders
+                .iter()
+                .filter(|(_, orders)| !orders.is_empty())
+                .map(|(instrument, _)| instrument.clone()),
+        )
+        .chain(
+            active_strategy_symbols(
+                &app.strategy_store,
+                &app.execution.synthetic_instruments,
+                app.mode,
+            )
+            .into_iter()
+            .map(Instrument),
+        )
         .collect()
 }
 
+/// Recomputes ticks for synthetic instruments that an active watch
+/// subscribes to, storing each under the synthetic name.
+fn refresh_synthetic_prices<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let subscribed = app
+        .strategy_store
+        .active_watches(app.mode)
+        .into_iter()
+        .filter_map(|watch| app.execution.synthetic_instruments.get(&watch.instrument))
+        .map(|synthetic| (synthetic.name.clone(), synthetic.clone()))
+        .collect::<BTreeMap<_, _>>();
+    for synthetic in subscribed.into_values() {
+        match app.market_data.refresh_synthetic_price(
+            &app.exchange,
+            &mut app.price_store,
+            &synthetic,
+        ) {
+            Ok(tick) => log(
+                &mut app.event_log,
+                "app.market_data.synthetic_tick",
+                json!({
+                    "instrument": tick.instrument.0,
+                    "price": tick.price,
+                    "legs": tick
+                        .leg_prices
+                        .iter()
+                        .map(|(instrument, market, price)| json!({
+                            "instrument": instrument.0,
+                            "market": format!("{market:?}"),
+                            "price": price,
+                        }))
+                        .collect::<Vec<_>>(),
+                }),
+            ),
+            Err(error) => log(
+                &mut app.event_log,
+                "app.market_data.synthetic_tick_failed",
+                json!({
+                    "instrument": synthetic.name.0,
+                    "error": error.to_string(),
+                }),
+            ),
+        }
+    }
+}
+
 fn refresh_position_prices<
     E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
 >(
@@ -302,6 +4258,37 @@ fn refresh_position_prices<
     Ok(())
 }
 
+/// Looks up the venue's taker commission for each open position; the rates
+/// are cached by the exchange, so this costs a request per symbol and TTL.
+fn refresh_position_fee_rates<
+    E: crate::exchange::facade::ExchangeFacade<Error = crate::error::exchange_error::ExchangeError>,
+>(
+    app: &mut AppBootstrap<E>,
+) {
+    let positions = app
+        .portfolio_store
+        .snapshot
+        .positions
+        .values()
+        .map(|position| (position.instrument.clone(), position.market))
+        .collect::<Vec<_>>();
+    for (instrument, market) in positions {
+        match app.exchange.load_taker_fee_rate(&instrument, market) {
+            Ok(Some(rate)) => app
+                .portfolio_store
+                .entry_stats
+                .set_venue_fee_rate(instrument, rate),
+            Ok(None) => {}
+            Err(error) => warn!(
+                service = "trading-engine",
+                instrument = %instrument.0,
+                error = %error,
+                "commission rate lookup failed; break-even uses the configured fee"
+            ),
+        }
+    }
+}
+
 fn execution_payload(
     command: &ExecutionCommand,
     outcome: &crate::execution::service::ExecutionOutcome,
@@ -422,6 +4409,135 @@ fn execution_payload(
                 "outcome_kind": "batch_completed",
             })
         }
+        (
+            ExecutionCommand::PlaceTrailingStop {
+                instrument, trail, ..
+            },
+            crate::execution::service::ExecutionOutcome::TrailingStopPlaced {
+                on_exchange,
+                callback_rate_pct,
+                ..
+            },
+        ) => json!({
+            "command_kind": "place_trailing_stop",
+            "instrument": instrument.0,
+            "trail": trail.label(),
+            "callback_rate_pct": callback_rate_pct,
+            "engine": if *on_exchange { "exchange" } else { "internal" },
+            "remaining_positions": remaining_positions,
+            "flat_confirmed": remaining_positions == 0,
+            "remaining_gross_exposure_usdt": remaining_gross_exposure_usdt,
+            "outcome_kind": "placed",
+        }),
+        (
+            ExecutionCommand::PlaceTakeProfitLadder {
+                instrument,
+                profile,
+                ..
+            },
+            crate::execution::service::ExecutionOutcome::TakeProfitLadderPlaced {
+                progress, ..
+            },
+        ) => json!({
+            "command_kind": "place_take_profit_ladder",
+            "instrument": instrument.0,
+            "profile": profile,
+            "take_profit_progress": progress,
+            "remaining_positions": remaining_positions,
+            "flat_confirmed": remaining_positions == 0,
+            "remaining_gross_exposure_usdt": remaining_gross_exposure_usdt,
+            "outcome_kind": "placed",
+        }),
+        (
+            ExecutionCommand::PlaceLimitOrder {
+                instrument,
+                side,
+                qty,
+                ..
+            },
+            crate::execution::service::ExecutionOutcome::LimitOrderPlaced { market, price, .. },
+        ) => json!({
+            "command_kind": "place_limit_order",
+            "instrument": instrument.0,
+            "market": format!("{market:?}"),
+            "side": format!("{side:?}"),
+            "qty": qty,
+            "price": price,
+            "remaining_positions": remaining_positions,
+            "flat_confirmed": remaining_positions == 0,
+            "remaining_gross_exposure_usdt": remaining_gross_exposure_usdt,
+            "outcome_kind": "placed",
+        }),
+        (
+            ExecutionCommand::PlaceIceberg {
+                instrument,
+                side,
+                qty,
+                visible_qty,
+                ..
+            },
+            crate::execution::service::ExecutionOutcome::IcebergPlaced {
+                parent_id,
+                market,
+                price,
+                first_order_id,
+                ..
+            },
+        ) => json!({
+            "command_kind": "place_iceberg",
+            "instrument": instrument.0,
+            "market": format!("{market:?}"),
+            "side": format!("{side:?}"),
+            "qty": qty,
+            "visible_qty": visible_qty,
+            "price": price,
+            "parent_id": parent_id,
+            "child_order_id": first_order_id,
+            "remaining_positions": remaining_positions,
+            "flat_confirmed": remaining_positions == 0,
+            "remaining_gross_exposure_usdt": remaining_gross_exposure_usdt,
+            "outcome_kind": "placed",
+        }),
+        (
+            ExecutionCommand::CancelOrder { instrument, .. },
+            crate::execution::service::ExecutionOutcome::OrderCancelled {
+                client_order_id, ..
+            },
+        ) => json!({
+            "command_kind": "cancel_order",
+            "instrument": instrument.0,
+            "client_order_id": client_order_id,
+            "remaining_positions": remaining_positions,
+            "flat_confirmed": remaining_positions == 0,
+            "remaining_gross_exposure_usdt": remaining_gross_exposure_usdt,
+            "outcome_kind": "cancelled",
+        }),
+        (
+            ExecutionCommand::PlaceOco {
+                instrument,
+                take_profit_price,
+                stop_price,
+                stop_limit_price,
+                ..
+            },
+            crate::execution::service::ExecutionOutcome::OcoPlaced {
+                list_id,
+                exchange_list_id,
+                ..
+            },
+        ) => json!({
+            "command_kind": "place_oco",
+            "instrument": instrument.0,
+            "take_profit_price": take_profit_price,
+            "stop_price": stop_price,
+            "stop_limit_price": stop_limit_price,
+            "list_id": list_id,
+            "exchange_list_id": exchange_list_id,
+            "remaining_positions": remaining_positions,
+            "flat_confirmed": remaining_positions == 0,
+            "remaining_gross_exposure_usdt": remaining_gross_exposure_usdt,
+            "outcome_kind": "placed",
+        }),
         _ => json!({
             "command_kind": "unknown",
             "outcome_kind": "unknown",
@@ -449,6 +4565,22 @@ fn execution_request_payload(command: &ExecutionCommand) -> serde_json::Value {
         ExecutionCommand::CloseAll { .. } => json!({
             "command_kind": "close_all",
         }),
+        ExecutionCommand::PlaceTrailingStop {
+            instrument, trail, ..
+        } => json!({
+            "command_kind": "place_trailing_stop",
+            "instrument": instrument.0,
+            "trail": trail.label(),
+        }),
+        ExecutionCommand::PlaceTakeProfitLadder {
+            instrument,
+            profile,
+            ..
+        } => json!({
+            "command_kind": "place_take_profit_ladder",
+            "instrument": instrument.0,
+            "profile": profile,
+        }),
         ExecutionCommand::SubmitOptionOrder {
             instrument,
             side,
@@ -462,6 +4594,56 @@ fn execution_request_payload(command: &ExecutionCommand) -> serde_json::Value {
             "qty": qty,
             "order_type": format_order_type(*order_type),
         }),
+        ExecutionCommand::PlaceLimitOrder {
+            instrument,
+            side,
+            qty,
+            price,
+            ..
+        } => json!({
+            "command_kind": "place_limit_order",
+            "instrument": instrument.0,
+            "side": format!("{side:?}"),
+            "qty": qty,
+            "price": price,
+        }),
+        ExecutionCommand::PlaceIceberg {
+            instrument,
+            side,
+            qty,
+            visible_qty,
+            price,
+            ..
+        } => json!({
+            "command_kind": "place_iceberg",
+            "instrument": instrument.0,
+            "side": format!("{side:?}"),
+            "qty": qty,
+            "visible_qty": visible_qty,
+            "price": price,
+        }),
+        ExecutionCommand::CancelOrder {
+            instrument,
+            client_order_id,
+            ..
+        } => json!({
+            "command_kind": "cancel_order",
+            "instrument": instrument.0,
+            "client_order_id": client_order_id,
+        }),
+        ExecutionCommand::PlaceOco {
+            instrument,
+            take_profit_price,
+            stop_price,
+            stop_limit_price,
+            ..
+        } => json!({
+            "command_kind": "place_oco",
+            "instrument": instrument.0,
+            "take_profit_price": take_profit_price,
+            "stop_price": stop_price,
+            "stop_limit_price": stop_limit_price,
+        }),
     }
 }
 
@@ -469,6 +4651,16 @@ fn format_order_type(order_type: crate::domain::order_type::OrderType) -> String
     match order_type {
         crate::domain::order_type::OrderType::Market => "market".to_string(),
         crate::domain::order_type::OrderType::Limit { price } => format!("limit@{price:.2}"),
+        crate::domain::order_type::OrderType::LimitIoc { price } => format!("limit_ioc@{price:.2}"),
+        crate::domain::order_type::OrderType::StopMarket { stop_price } => {
+            format!("stop_market@{stop_price:.2}")
+        }
+        crate::domain::order_type::OrderType::StopLimit { stop_price, price } => {
+            format!("stop_limit@{stop_price:.2}->{price:.2}")
+        }
+        crate::domain::order_type::OrderType::TrailingStopMarket { callback_rate_pct } => {
+            format!("trailing@{callback_rate_pct}%")
+        }
     }
 }
 
@@ -2,18 +2,34 @@ use crate::app::bootstrap::{AppBootstrap, BinanceMode};
 use crate::app::cli::{
     complete_shell_input_with_market_data, parse_shell_input, shell_help_text, ShellInput,
 };
-use crate::app::output::render_command_output;
+use crate::app::commands::{AppCommand, OrderFilter};
+use crate::app::output::{
+    render_command_output, render_credential_rotation, render_event_notices,
+    render_inactivity_output, render_news_headlines, render_symbol_status, render_triggered_alerts,
+    render_user_stream_updates,
+};
 use crate::app::runtime::AppRuntime;
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::error::app_error::AppError;
 use crate::exchange::binance::client::BinanceExchange;
+use crate::exchange::binance::user_stream::UserStreamHandle;
+use crate::market_data::news_feed::fetch_news_feed;
+use crate::market_data::range_stats::SymbolRangeStats;
 use crate::terminal::app::{TerminalApp, TerminalEvent};
 use crate::terminal::completion::ShellCompletion;
 pub use crate::terminal::completion::{
     format_completion_line, next_completion_index, previous_completion_index, scroll_lines_needed,
 };
 use crate::terminal::loop_shell::run_terminal;
+use crate::terminal::macros::{MacroCommand, ShellMacroStore};
+use crate::ui::locale::{text, UiLocale, UiText};
 use crate::ui::operator_terminal::{
     mode_name, operator_prompt, prompt_status_from_store, shell_intro_panel,
 };
+use crate::ui::theme::UiTheme;
+use crate::ui::timezone::DisplayTimeZone;
+use chrono::{DateTime, Utc};
 use std::collections::BTreeSet;
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
@@ -22,69 +38,469 @@ pub fn run_shell(
     app: &mut AppBootstrap<BinanceExchange>,
     runtime: &mut AppRuntime,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut terminal = OperatorTerminal { app, runtime };
+    let macros_path = ShellMacroStore::default_path();
+    let (macros, startup_notice) = match ShellMacroStore::load(&macros_path) {
+        Ok(macros) => (macros, None),
+        Err(error) => (
+            ShellMacroStore::unloaded(&macros_path),
+            Some(format!(
+                "{} {error}\n{}",
+                text(app.locale, UiText::MacroLibraryNotLoaded),
+                text(app.locale, UiText::MacroLibraryUnsaved)
+            )),
+        ),
+    };
+    let user_stream = app.start_user_streams();
+    let mut terminal = OperatorTerminal {
+        app,
+        runtime,
+        macros,
+        macro_depth: 0,
+        startup_notice,
+        armed_confirmation: None,
+        orders_filter: OrderFilter::default(),
+        news_http: reqwest::blocking::Client::new(),
+        user_stream,
+    };
     run_terminal(&mut terminal)
 }
 
 struct OperatorTerminal<'a> {
     app: &'a mut AppBootstrap<BinanceExchange>,
     runtime: &'a mut AppRuntime,
+    macros: ShellMacroStore,
+    /// `/macro play` calls currently running, nested ones included.
+    macro_depth: usize,
+    /// Shown under the intro panel, e.g. a macro library that failed to load.
+    startup_notice: Option<String>,
+    /// Hotkey whose confirmation is pending, so pressing it again within
+    /// `HOTKEY_CONFIRM_WINDOW` confirms it.
+    armed_confirmation: Option<(char, Instant)>,
+    /// Open-order filter the `ctrl+o`/`ctrl+u`/`ctrl+t` hotkeys step through.
+    orders_filter: OrderFilter,
+    news_http: reqwest::blocking::Client,
+    /// Pushed order and balance updates, applied on the idle tick.
+    user_stream: UserStreamHandle,
 }
 
+const HOTKEY_CONFIRM_WINDOW: Duration = Duration::from_secs(10);
+
+/// Deepest `/macro play` nesting, so a macro that plays itself stops.
+const MAX_MACRO_DEPTH: usize = 4;
+
+/// Confirmation events and the hotkey that confirms each.
+const HOTKEY_CONFIRMATIONS: [(&str, char); 2] = [
+    ("app.execution.flatten_confirmation_required", 'x'),
+    ("app.safety.panic_confirmation_required", 'p'),
+];
+
 impl TerminalApp for OperatorTerminal<'_> {
     fn intro_panel(&self) -> String {
-        shell_intro_panel(mode_name(current_mode(self.app)), "~/project/sandbox-quant")
+        let panel = if self.app.resource_profile.latency_first() {
+            format!(
+                "sandbox-quant {} profile=fast ({})",
+                mode_name(current_mode(self.app)),
+                text(self.app.locale, UiText::IntroHelpHint)
+            )
+        } else {
+            shell_intro_panel(
+                mode_name(current_mode(self.app)),
+                "~/project/sandbox-quant",
+                self.app.locale,
+            )
+        };
+        match &self.startup_notice {
+            Some(notice) => format!("{panel}\n{notice}"),
+            None => panel,
+        }
     }
 
     fn help_text(&self) -> String {
         shell_help_text().to_string()
     }
 
+    fn theme(&self) -> UiTheme {
+        self.app.theme
+    }
+
     fn prompt(&self) -> String {
         let mode = current_mode(self.app);
         let status = prompt_status(self.app);
-        operator_prompt(mode, &status)
+        operator_prompt(mode, &status, self.app.theme)
     }
 
     fn complete(&self, line: &str) -> Vec<ShellCompletion> {
         current_completions(self.app, line)
     }
 
+    fn hotkey_line(&mut self, key: char) -> Option<String> {
+        let armed = self
+            .armed_confirmation
+            .is_some_and(|(armed_key, armed_at)| {
+                armed_key == key && armed_at.elapsed() <= HOTKEY_CONFIRM_WINDOW
+            });
+        let line = match (key, armed) {
+            ('r', _) => "/reload-config",
+            ('x', true) => "/flatten confirm",
+            ('x', false) => "/flatten",
+            ('p', true) => "/panic confirm",
+            ('p', false) => "/panic",
+            ('b', _) => "/news blackout",
+            ('e', _) => "/diagnostics export",
+            ('o', _) => {
+                self.orders_filter.cycle_side();
+                return Some(self.orders_filter.orders_line());
+            }
+            ('u', _) => {
+                self.orders_filter.cycle_status();
+                return Some(self.orders_filter.orders_line());
+            }
+            ('t', _) => {
+                let tags = open_order_tags(&self.app.portfolio_store);
+                self.orders_filter.cycle_tag(&tags);
+                return Some(self.orders_filter.orders_line());
+            }
+            _ => return None,
+        };
+        Some(line.to_string())
+    }
+
+    fn function_key_line(&self, key: u8) -> Option<String> {
+        self.macros
+            .name_for_hotkey(key)
+            .map(|name| format!("/macro play {name}"))
+    }
+
+    fn idle_tick_every(&self) -> Duration {
+        self.app.resource_profile.idle_tick()
+    }
+
+    fn idle_tick(&mut self) -> Option<String> {
+        self.app.event_log.compact();
+        let events_before = self.app.event_log.records.len();
+        if self.app.check_credentials(Instant::now()) {
+            self.app.probe_api_permissions();
+            self.user_stream = self.app.start_user_streams();
+        }
+        let rotation = render_credential_rotation(&self.app.event_log, events_before);
+        let now = Utc::now();
+        let events_before = self.app.event_log.records.len();
+        for event in self.user_stream.drain() {
+            self.runtime.ingest_user_stream_event(self.app, event, now);
+        }
+        let user_stream = render_user_stream_updates(&self.app.event_log, events_before);
+        let news = match self.app.news.config.url.clone() {
+            Some(url)
+                if self.app.news.is_due(now) && !self.app.resource_profile.latency_first() =>
+            {
+                let events_before = self.app.event_log.records.len();
+                let fetched = fetch_news_feed(&self.news_http, &url);
+                self.runtime.ingest_news(self.app, fetched, now);
+                render_news_headlines(&self.app.event_log, events_before)
+            }
+            _ => None,
+        };
+        let events_before = self.app.event_log.records.len();
+        let symbol_status = self
+            .runtime
+            .check_symbol_status(self.app, now)
+            .then(|| render_symbol_status(&self.app.event_log, events_before))
+            .flatten();
+        let events_before = self.app.event_log.records.len();
+        let alerts = self
+            .runtime
+            .check_price_alerts(self.app)
+            .then(|| render_triggered_alerts(&self.app.event_log, events_before))
+            .flatten();
+        let inactivity = self
+            .runtime
+            .check_inactivity(self.app, now)
+            .then(|| render_inactivity_output(&self.app.event_log));
+        let lines = [
+            rotation,
+            user_stream,
+            news,
+            symbol_status,
+            alerts,
+            inactivity,
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
     fn execute_line(&mut self, line: &str) -> Result<TerminalEvent, String> {
-        match parse_shell_input(line) {
-            Ok(ShellInput::Empty) => Ok(TerminalEvent::NoOutput),
-            Ok(ShellInput::Help) => Ok(TerminalEvent::Output(shell_help_text().to_string())),
-            Ok(ShellInput::Exit) => Ok(TerminalEvent::Exit),
-            Ok(ShellInput::Mode(mode)) => self
-                .app
-                .switch_mode(mode)
-                .map(|_| TerminalEvent::Output(format!("mode switched to {}", mode_name(mode))))
-                .map_err(|error| error.to_string()),
-            Ok(ShellInput::Command(command)) => {
+        let input = parse_shell_input(line)?;
+        if let ShellInput::Macro(command) = input {
+            return self.run_macro_command(command);
+        }
+        let recordable = matches!(input, ShellInput::Mode(_) | ShellInput::Command(_));
+        self.app.event_log.compact();
+        let event = self.execute_input(input);
+        self.armed_confirmation = self
+            .app
+            .event_log
+            .records
+            .last()
+            .and_then(|record| {
+                HOTKEY_CONFIRMATIONS
+                    .iter()
+                    .find(|(kind, _)| record.kind == *kind)
+            })
+            .map(|(_, key)| (*key, Instant::now()));
+        let event = event?;
+        if recordable {
+            self.macros.capture(line);
+        }
+        Ok(event)
+    }
+}
+
+impl OperatorTerminal<'_> {
+    fn execute_input(&mut self, input: ShellInput) -> Result<TerminalEvent, String> {
+        match input {
+            ShellInput::Empty => Ok(TerminalEvent::NoOutput),
+            ShellInput::Help => Ok(TerminalEvent::Output(shell_help_text().to_string())),
+            ShellInput::Exit => Ok(TerminalEvent::Exit),
+            ShellInput::Macro(command) => self.run_macro_command(command),
+            ShellInput::Mode(mode) => {
+                self.app
+                    .switch_mode(mode)
+                    .map_err(|error| error.to_string())?;
+                let events_before = self.app.event_log.records.len();
+                self.app.probe_api_permissions();
+                self.user_stream = self.app.start_user_streams();
+                let output = format!(
+                    "{} {}",
+                    text(self.app.locale, UiText::ModeSwitched),
+                    mode_name(mode)
+                );
+                Ok(TerminalEvent::Output(
+                    match render_credential_rotation(&self.app.event_log, events_before) {
+                        Some(permissions) => format!("{output}\n{permissions}"),
+                        None => output,
+                    },
+                ))
+            }
+            ShellInput::Command(command) => {
+                let command = *command;
                 let rendered_command = command.clone();
+                let events_before = self.app.event_log.records.len();
                 self.runtime
                     .run(self.app, command)
-                    .map_err(|error| error.to_string())?;
-                Ok(TerminalEvent::Output(render_command_output(
+                    .map_err(|error| describe_app_error(&error, self.app.locale))?;
+                if matches!(rendered_command, AppCommand::ReloadConfig)
+                    && self.app.reload_credentials()
+                {
+                    self.app.probe_api_permissions();
+                    self.user_stream = self.app.start_user_streams();
+                }
+                let output = render_command_output(
                     &rendered_command,
                     &self.app.portfolio_store,
                     &self.app.price_store,
                     &self.app.event_log,
                     &self.app.strategy_store,
                     self.app.mode,
-                )))
+                    &self.app.execution.history,
+                );
+                let output = match render_triggered_alerts(&self.app.event_log, events_before) {
+                    Some(alerts) => format!("{alerts}\n{output}"),
+                    None => output,
+                };
+                let output = match render_credential_rotation(&self.app.event_log, events_before) {
+                    Some(rotation) => format!("{output}\n{rotation}"),
+                    None => output,
+                };
+                Ok(TerminalEvent::Output(
+                    match render_event_notices(&self.app.event_log, events_before) {
+                        Some(notices) => format!("{output}\n{notices}"),
+                        None => output,
+                    },
+                ))
             }
-            Err(error) => Err(error),
         }
     }
+
+    /// Handles `/macro`; replay stops at the first failing step and at
+    /// `MAX_MACRO_DEPTH` nested plays.
+    ///
+    /// Example:
+    /// - `/macro play eth-setup`
+    /// - prints `macro played`, then `> /mode demo` and its output per step
+    fn run_macro_command(&mut self, command: MacroCommand) -> Result<TerminalEvent, String> {
+        let save = |macros: &ShellMacroStore| macros.save().map_err(|error| error.to_string());
+        let locale = self.app.locale;
+        let unknown = |name: &str| format!("{} {name}", text(locale, UiText::MacroUnknown));
+        let output = match command {
+            MacroCommand::Record { name } => {
+                self.macros.start_recording(&name);
+                format!(
+                    "{}\nname={name}\n{}",
+                    text(locale, UiText::MacroRecordingStarted),
+                    text(locale, UiText::MacroFinishHint)
+                )
+            }
+            MacroCommand::Stop => {
+                let (name, steps) = self
+                    .macros
+                    .finish_recording()
+                    .ok_or(text(locale, UiText::MacroNotRecording))?;
+                save(&self.macros)?;
+                format!(
+                    "{}\nname={name}\nsteps={steps}",
+                    text(locale, UiText::MacroSaved)
+                )
+            }
+            MacroCommand::Play { name } => {
+                let lines = self
+                    .macros
+                    .get(&name)
+                    .map(|shell_macro| shell_macro.lines.clone())
+                    .ok_or_else(|| unknown(&name))?;
+                if self.macro_depth >= MAX_MACRO_DEPTH {
+                    return Err(format!(
+                        "{} {MAX_MACRO_DEPTH}: {name}",
+                        text(locale, UiText::MacroTooDeep)
+                    ));
+                }
+                let mut blocks = Vec::new();
+                let mut completed = 0;
+                let mut failure = None;
+                self.macro_depth += 1;
+                for line in &lines {
+                    match self.execute_line(line) {
+                        Ok(TerminalEvent::Output(output)) => {
+                            blocks.push(format!("> {line}\n{output}"))
+                        }
+                        Ok(_) => blocks.push(format!("> {line}")),
+                        Err(error) => {
+                            failure = Some(format!("> {line}\nerror: {error}"));
+                            break;
+                        }
+                    }
+                    completed += 1;
+                }
+                self.macro_depth -= 1;
+                let mut output = vec![
+                    text(locale, UiText::MacroPlayed).to_string(),
+                    format!("name={name}"),
+                    format!("steps={completed}/{}", lines.len()),
+                ];
+                output.extend(blocks);
+                output.extend(failure);
+                output.join("\n")
+            }
+            MacroCommand::List => {
+                let mut output = vec![format!(
+                    "{} ({})",
+                    text(locale, UiText::MacroList),
+                    self.macros.macros().len()
+                )];
+                output.extend(self.macros.macros().iter().map(|(name, shell_macro)| {
+                    format!(
+                        "  - {name} steps={} hotkey={}",
+                        shell_macro.lines.len(),
+                        shell_macro
+                            .hotkey
+                            .map(|slot| format!("F{slot}"))
+                            .unwrap_or_else(|| "-".to_string())
+                    )
+                }));
+                if let Some(name) = self.macros.recording_name() {
+                    output.push(format!("recording={name}"));
+                }
+                output.join("\n")
+            }
+            MacroCommand::Remove { name } => {
+                self.macros.remove(&name).ok_or_else(|| unknown(&name))?;
+                save(&self.macros)?;
+                format!("{}\nname={name}", text(locale, UiText::MacroRemoved))
+            }
+            MacroCommand::Bind { name, slot } => {
+                if !self.macros.bind(&name, slot) {
+                    return Err(unknown(&name));
+                }
+                save(&self.macros)?;
+                format!(
+                    "{}\nname={name}\nhotkey=F{slot}",
+                    text(locale, UiText::MacroBound)
+                )
+            }
+        };
+        Ok(TerminalEvent::Output(output))
+    }
+}
+
+/// Appends the targeted hint for known exchange errors.
+///
+/// Example:
+/// - `exchange error: remote rejected request: code=-2010 ...`
+/// - `hint: insufficient balance for this order (user_actionable)`
+fn describe_app_error(error: &AppError, locale: UiLocale) -> String {
+    match (error.hint(), error.class()) {
+        (Some(hint), Some(class)) => format!(
+            "{error}\n{} {hint} ({})",
+            text(locale, UiText::ErrorHint),
+            class.as_str()
+        ),
+        _ => error.to_string(),
+    }
 }
 
 fn current_mode(app: &AppBootstrap<BinanceExchange>) -> BinanceMode {
     app.mode
 }
 
+/// Ends with the key's permissions when it cannot trade everything and
+/// with a news blackout while one holds entries, e.g.
+/// `[fresh|0 pos|0 ord][key:spot][blackout]`.
+///
+/// A latency-first profile shows only the portfolio status.
+/// Client order id prefixes of the open orders, e.g. `strategy` for
+/// `strategy-3-12`; the tags `ctrl+t` cycles through.
+fn open_order_tags(store: &crate::portfolio::store::PortfolioStateStore) -> BTreeSet<String> {
+    store
+        .snapshot
+        .open_orders
+        .values()
+        .flatten()
+        .filter_map(|order| order.client_order_id.split('-').next())
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 fn prompt_status(app: &AppBootstrap<BinanceExchange>) -> String {
-    prompt_status_from_store(&app.portfolio_store)
+    let mut status = prompt_status_from_store(&app.portfolio_store, app.locale);
+    if app.resource_profile.latency_first() {
+        return status;
+    }
+    if let Some(permissions) = app.execution.api_permissions {
+        if permissions.read_only() || !permissions.futures_trading {
+            status.push_str(&format!(
+                "[{}:{}]",
+                text(app.locale, UiText::StatusKey),
+                permissions.label()
+            ));
+        }
+    }
+    if app
+        .execution
+        .entries_blackout_until
+        .is_some_and(|until| until > Utc::now())
+    {
+        status.push_str(&format!("[{}]", text(app.locale, UiText::StatusBlackout)));
+    }
+    status.push_str(&prompt_clock(app.time_zone, Utc::now()));
+    status
+}
+
+/// Example:
+/// - `[08:30 +09:00]`, `[23:30 UTC]`
+fn prompt_clock(zone: DisplayTimeZone, now: DateTime<Utc>) -> String {
+    format!("[{} {}]", zone.format(now, "%H:%M"), zone.label())
 }
 
 fn current_completions(app: &AppBootstrap<BinanceExchange>, buffer: &str) -> Vec<ShellCompletion> {
@@ -100,7 +516,52 @@ fn current_completions(app: &AppBootstrap<BinanceExchange>, buffer: &str) -> Vec
         .into_iter()
         .map(|(instrument, price)| (instrument.0, price))
         .collect::<Vec<_>>();
-    complete_shell_input_with_market_data(buffer, &instruments, &priced_instruments)
+    let mut completions =
+        complete_shell_input_with_market_data(buffer, &instruments, &priced_instruments);
+    annotate_symbol_stats(&mut completions, |symbol| {
+        let instrument = Instrument::new(symbol);
+        let market = app
+            .portfolio_store
+            .snapshot
+            .positions
+            .get(&instrument)
+            .map_or(Market::Futures, |position| position.market);
+        app.exchange
+            .load_daily_bars(&instrument, market)
+            .ok()
+            .and_then(|bars| SymbolRangeStats::from_daily_bars(&bars))
+    });
+    completions
+}
+
+/// Appends 24h change, ATR% and average volume to every completion that
+/// ends in an instrument, so the selector helps pick what to trade.
+///
+/// Example:
+/// - `/close-symbol BTCUSDT` -> `submit a close order for this instrument · 24h +1.10% atr 3.20% vol 9.8B`
+fn annotate_symbol_stats(
+    completions: &mut [ShellCompletion],
+    stats: impl Fn(&str) -> Option<SymbolRangeStats>,
+) {
+    for completion in completions {
+        let mut parts = completion.value.split_whitespace();
+        let (Some(_), Some(symbol), None) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        if !symbol
+            .chars()
+            .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit())
+        {
+            continue;
+        }
+        if let Some(stats) = stats(symbol) {
+            completion.description = if completion.description.is_empty() {
+                stats.label()
+            } else {
+                format!("{} · {}", completion.description, stats.label())
+            };
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -181,14 +642,23 @@ fn completion_instruments(
 
 #[cfg(test)]
 mod tests {
-    use super::{completion_instruments, prompt_status_from_store};
+    use super::{
+        annotate_symbol_stats, completion_instruments, describe_app_error, prompt_clock,
+        prompt_status_from_store,
+    };
     use crate::domain::balance::BalanceSnapshot;
     use crate::domain::instrument::Instrument;
     use crate::domain::market::Market;
     use crate::domain::order::{OpenOrder, OrderStatus};
     use crate::domain::position::{PositionSnapshot, Side};
+    use crate::error::app_error::AppError;
+    use crate::error::exchange_error::ExchangeError;
+    use crate::market_data::range_stats::SymbolRangeStats;
     use crate::portfolio::store::PortfolioStateStore;
     use crate::storage::event_log::{log, EventLog};
+    use crate::terminal::completion::ShellCompletion;
+    use crate::ui::locale::UiLocale;
+    use crate::ui::timezone::DisplayTimeZone;
     use serde_json::json;
 
     #[test]
@@ -212,6 +682,7 @@ mod tests {
                 instrument: Instrument::new("ETHUSDT"),
                 market: Market::Futures,
                 side: Side::Sell,
+                price: None,
                 orig_qty: 1.0,
                 executed_qty: 0.0,
                 reduce_only: false,
@@ -271,6 +742,7 @@ mod tests {
                 instrument: Instrument::new("BTCUSDT"),
                 market: Market::Futures,
                 side: Side::Sell,
+                price: None,
                 orig_qty: 0.25,
                 executed_qty: 0.0,
                 reduce_only: false,
@@ -278,6 +750,76 @@ mod tests {
             }],
         });
 
-        assert_eq!(prompt_status_from_store(&store), "[fresh|1 pos|1 ord]");
+        assert_eq!(
+            prompt_status_from_store(&store, UiLocale::English),
+            "[fresh|1 pos|1 ord]"
+        );
+        assert_eq!(
+            prompt_status_from_store(&store, UiLocale::Korean),
+            "[최신|1 포지션|1 주문]"
+        );
+    }
+
+    #[test]
+    fn error_hints_are_labelled_in_the_operator_locale() {
+        let error = AppError::Exchange(ExchangeError::NetworkTimeout);
+
+        assert_eq!(
+            describe_app_error(&error, UiLocale::English),
+            "exchange error: network timeout\nhint: network timeout; refresh before retrying (retryable)"
+        );
+        assert_eq!(
+            describe_app_error(&error, UiLocale::Korean),
+            "exchange error: network timeout\n힌트: network timeout; refresh before retrying (retryable)"
+        );
+    }
+
+    #[test]
+    fn annotate_symbol_stats_only_touches_instrument_completions() {
+        let completion = |value: &str, description: &str| ShellCompletion {
+            value: value.to_string(),
+            description: description.to_string(),
+        };
+        let mut completions = vec![
+            completion(
+                "/close-symbol BTCUSDT",
+                "submit a close order for this instrument",
+            ),
+            completion("/inactivity resume", "re-enable entries"),
+            completion(
+                "/close-symbol ETHUSDT",
+                "submit a close order for this instrument",
+            ),
+        ];
+
+        annotate_symbol_stats(&mut completions, |symbol| {
+            (symbol == "BTCUSDT").then_some(SymbolRangeStats {
+                change_24h_pct: 1.1,
+                atr_pct: 3.2,
+                avg_quote_volume: 9.8e9,
+            })
+        });
+
+        assert_eq!(
+            completions[0].description,
+            "submit a close order for this instrument · 24h +1.10% atr 3.20% vol 9.8B"
+        );
+        assert_eq!(completions[1].description, "re-enable entries");
+        assert_eq!(
+            completions[2].description,
+            "submit a close order for this instrument"
+        );
+    }
+
+    #[test]
+    fn prompt_clock_shows_display_zone_time_and_label() {
+        let now = chrono::TimeZone::with_ymd_and_hms(&chrono::Utc, 2026, 3, 1, 23, 30, 0).unwrap();
+        let seoul = chrono::FixedOffset::east_opt(9 * 3_600).expect("offset");
+
+        assert_eq!(prompt_clock(DisplayTimeZone::Utc, now), "[23:30 UTC]");
+        assert_eq!(
+            prompt_clock(DisplayTimeZone::Fixed(seoul), now),
+            "[08:30 +09:00]"
+        );
     }
 }
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+
+/// Seconds between exchangeInfo status checks when unset.
+pub const DEFAULT_SYMBOL_STATUS_CHECK_SECS: u64 = 300;
+/// Headline words that send the traded symbols they mention to an early
+/// status check.
+const HALT_NOTICE_KEYWORDS: [&str; 4] = ["delist", "halt", "suspend", "trading pause"];
+
+/// How traded symbols are watched for venue halts and delistings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolStatusConfig {
+    /// Periodic checks are off when `None`.
+    pub check_secs: Option<u64>,
+    /// Closes an open position once its symbol stops trading.
+    pub flatten: bool,
+}
+
+impl Default for SymbolStatusConfig {
+    fn default() -> Self {
+        Self {
+            check_secs: Some(DEFAULT_SYMBOL_STATUS_CHECK_SECS),
+            flatten: false,
+        }
+    }
+}
+
+impl SymbolStatusConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_SYMBOL_STATUS_SECS` (default `300`; `0` disables)
+    /// - `SANDBOX_QUANT_HALT_FLATTEN` (`true` closes positions on halted symbols)
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let defaults = Self::default();
+        Self {
+            check_secs: match var("SANDBOX_QUANT_SYMBOL_STATUS_SECS") {
+                Some(value) => value.trim().parse::<u64>().ok().filter(|secs| *secs > 0),
+                None => defaults.check_secs,
+            },
+            flatten: var("SANDBOX_QUANT_HALT_FLATTEN")
+                .map(|value| matches!(value.trim(), "1" | "true" | "yes"))
+                .unwrap_or(defaults.flatten),
+        }
+    }
+
+    /// Example:
+    /// - `every=300s flatten=off`
+    /// - `off`
+    pub fn label(&self) -> String {
+        match self.check_secs {
+            Some(secs) => format!(
+                "every={secs}s flatten={}",
+                if self.flatten { "on" } else { "off" }
+            ),
+            None => "off".to_string(),
+        }
+    }
+}
+
+/// Schedules the status checks; the halted symbols themselves live on
+/// `ExecutionService::halted_symbols`, where they hold back entries.
+///
+/// Example:
+/// - `check_secs=300`, a `BTC delisting` headline at `12:01`
+/// - checked at `12:00`, again at `12:01` instead of `12:05`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolStatusWatch {
+    pub config: SymbolStatusConfig,
+    pub last_checked_at: Option<DateTime<Utc>>,
+}
+
+impl SymbolStatusWatch {
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.config.check_secs.is_some_and(|secs| {
+            self.last_checked_at
+                .is_none_or(|checked| (now - checked).num_seconds() >= secs as i64)
+        })
+    }
+
+    /// Checks on the next idle tick, e.g. after a halt notice.
+    pub fn expedite(&mut self) {
+        self.last_checked_at = None;
+    }
+}
+
+/// Whether a headline reads like a halt or delisting notice.
+///
+/// Example:
+/// - `Binance Will Delist BTCDOWN on 2026-04-20` -> `true`
+/// - `Bitcoin ETF inflows hit a record` -> `false`
+pub fn is_halt_notice(title: &str) -> bool {
+    let title = title.to_ascii_lowercase();
+    HALT_NOTICE_KEYWORDS
+        .iter()
+        .any(|keyword| title.contains(keyword))
+}
@@ -0,0 +1,157 @@
+use crate::backtest_app::runner::BacktestReport;
+
+/// Two entries this close together count as the same signal.
+pub const SIGNAL_OVERLAP_WINDOW_MS: i64 = 60_000;
+
+/// One side of an A/B comparison, reduced from a stored run.
+///
+/// Example:
+/// - closed trades `+2.0`, `-1.0`, `+1.5` on `10,000`
+/// - `win_rate=0.67`, `average_net_pnl=0.83`
+/// - `equity_curve=[10000, 10002, 10001, 10002.5]`, `max_drawdown=1.0`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestVariant {
+    pub run_id: Option<i64>,
+    pub template: String,
+    pub instrument: String,
+    pub closed_trades: usize,
+    pub win_rate: f64,
+    pub average_net_pnl: f64,
+    /// Average closed trade in units of its initial stop distance, which
+    /// compares variants on different symbols or sizes.
+    pub average_r: Option<f64>,
+    pub net_pnl: f64,
+    /// EV per trade implied by the configured win-rate assumption.
+    pub configured_expected_value: f64,
+    /// Starting equity, then equity after each closed trade by exit time.
+    pub equity_curve: Vec<f64>,
+    pub max_drawdown: f64,
+}
+
+impl BacktestVariant {
+    pub fn from_report(report: &BacktestReport) -> Self {
+        let mut closed = report
+            .trades
+            .iter()
+            .filter_map(|trade| Some((trade.exit_time?, trade.net_pnl?)))
+            .collect::<Vec<_>>();
+        closed.sort_by_key(|(exit_time, _)| *exit_time);
+        let mut equity_curve = vec![report.starting_equity];
+        let mut peak = report.starting_equity;
+        let mut max_drawdown = 0.0_f64;
+        for (_, net_pnl) in &closed {
+            let equity = equity_curve.last().copied().unwrap_or_default() + net_pnl;
+            peak = peak.max(equity);
+            max_drawdown = max_drawdown.max(peak - equity);
+            equity_curve.push(equity);
+        }
+        let wins = closed.iter().filter(|(_, net_pnl)| *net_pnl > 0.0).count();
+        let net_pnl = closed.iter().map(|(_, net_pnl)| net_pnl).sum::<f64>();
+        Self {
+            run_id: report.run_id,
+            template: report.template.slug().to_string(),
+            instrument: report.instrument.clone(),
+            closed_trades: closed.len(),
+            win_rate: ratio(wins as f64, closed.len() as f64),
+            average_net_pnl: ratio(net_pnl, closed.len() as f64),
+            average_r: report.r_multiple_stats().average_r(),
+            net_pnl,
+            configured_expected_value: report.configured_expected_value,
+            equity_curve,
+            max_drawdown,
+        }
+    }
+
+    /// Realized minus configured EV per trade; negative means the variant's
+    /// assumptions were too optimistic.
+    pub fn ev_gap(&self) -> f64 {
+        self.average_net_pnl - self.configured_expected_value
+    }
+
+    /// Example:
+    /// - `run 7 price-sma-cross-long`
+    pub fn label(&self) -> String {
+        match self.run_id {
+            Some(run_id) => format!("run {run_id} {}", self.template),
+            None => self.template.clone(),
+        }
+    }
+}
+
+/// How often two variants entered at the same time on one instrument.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignalOverlap {
+    pub a_entries: usize,
+    pub b_entries: usize,
+    /// Entries matched one-to-one within `SIGNAL_OVERLAP_WINDOW_MS`.
+    pub shared: usize,
+}
+
+impl SignalOverlap {
+    /// Shared entries over all distinct entries, e.g. `50` when half of
+    /// the combined signals fired in both variants.
+    pub fn overlap_pct(&self) -> f64 {
+        ratio(
+            self.shared as f64,
+            (self.a_entries + self.b_entries - self.shared) as f64,
+        ) * 100.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestComparison {
+    pub a: BacktestVariant,
+    pub b: BacktestVariant,
+    /// `None` when the runs traded different instruments.
+    pub overlap: Option<SignalOverlap>,
+}
+
+/// Lines two stored runs up side by side, e.g. a profile and its fork.
+///
+/// Signal overlap uses trade entry times, since blocked signals are not
+/// persisted with a run.
+pub fn compare_backtests(a: &BacktestReport, b: &BacktestReport) -> BacktestComparison {
+    let overlap = (a.instrument == b.instrument).then(|| signal_overlap(a, b));
+    BacktestComparison {
+        a: BacktestVariant::from_report(a),
+        b: BacktestVariant::from_report(b),
+        overlap,
+    }
+}
+
+fn signal_overlap(a: &BacktestReport, b: &BacktestReport) -> SignalOverlap {
+    let entries = |report: &BacktestReport| {
+        let mut times = report
+            .trades
+            .iter()
+            .map(|trade| trade.entry_time.timestamp_millis())
+            .collect::<Vec<_>>();
+        times.sort_unstable();
+        times
+    };
+    let (a_times, b_times) = (entries(a), entries(b));
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a_times.len() && j < b_times.len() {
+        if (a_times[i] - b_times[j]).abs() <= SIGNAL_OVERLAP_WINDOW_MS {
+            shared += 1;
+            i += 1;
+            j += 1;
+        } else if a_times[i] < b_times[j] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    SignalOverlap {
+        a_entries: a_times.len(),
+        b_entries: b_times.len(),
+        shared,
+    }
+}
+
+fn ratio(value: f64, base: f64) -> f64 {
+    if base.abs() <= f64::EPSILON {
+        return 0.0;
+    }
+    value / base
+}
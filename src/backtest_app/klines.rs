@@ -0,0 +1,197 @@
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+use crate::app::bootstrap::BinanceMode;
+use crate::command::backtest::BacktestKlineSource;
+use crate::dataset::types::DerivedKlineRow;
+use crate::domain::market::Market;
+use crate::error::storage_error::StorageError;
+use crate::market_data::binance_kline_backfill::{
+    fetch_klines_between, parse_kline_rows, KlineEndpoint, DEFAULT_BINANCE_BACKFILL_INTERVAL,
+};
+
+/// Loads the candles for a `run` that does not read the recorder dataset,
+/// with the label its report stores in place of a database path.
+///
+/// Example:
+/// - `Rest` -> `binance-rest:/fapi/v1/klines?interval=1m`, from `mode`'s
+///   futures venue
+/// - `Dataset` -> `None`
+pub fn load_backtest_klines(
+    source: &BacktestKlineSource,
+    mode: BinanceMode,
+    instrument: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Option<(PathBuf, Vec<DerivedKlineRow>)>, StorageError> {
+    match source {
+        BacktestKlineSource::Dataset => Ok(None),
+        BacktestKlineSource::File(path) => {
+            let path = PathBuf::from(path);
+            let klines = load_klines_from_file(&path, instrument, from, to)?;
+            Ok(Some((path, klines)))
+        }
+        BacktestKlineSource::Rest => {
+            let http = Client::builder().build().map_err(to_storage_error)?;
+            let endpoint = KlineEndpoint::for_market(mode, Market::Futures);
+            let (start_ms, end_ms) = range_millis(from, to);
+            let klines = fetch_klines_between(
+                &http,
+                &endpoint,
+                instrument,
+                DEFAULT_BINANCE_BACKFILL_INTERVAL,
+                start_ms,
+                end_ms,
+            )
+            .map_err(|error| to_storage_error(format!("{error:#}")))?;
+            Ok(Some((
+                PathBuf::from(format!(
+                    "binance-rest:{}?interval={DEFAULT_BINANCE_BACKFILL_INTERVAL}",
+                    endpoint.path()
+                )),
+                klines,
+            )))
+        }
+    }
+}
+
+/// Reads klines opening between `from` and the end of `to`, oldest first.
+///
+/// Accepts a data.binance.vision CSV (header optional, microsecond times
+/// included) or a saved `/fapi/v1/klines` JSON array.
+pub fn load_klines_from_file(
+    path: &Path,
+    instrument: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<DerivedKlineRow>, StorageError> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|error| to_storage_error(format!("failed to read {}: {error}", path.display())))?;
+    let rows = if raw.trim_start().starts_with('[') {
+        serde_json::from_str::<Vec<Vec<Value>>>(&raw).map_err(|error| {
+            to_storage_error(format!("invalid kline JSON in {}: {error}", path.display()))
+        })?
+    } else {
+        raw.lines()
+            .map(str::trim)
+            .filter(|line| {
+                line.split(',')
+                    .next()
+                    .is_some_and(|first| first.parse::<i64>().is_ok())
+            })
+            .map(|line| {
+                line.split(',')
+                    .map(|value| Value::String(value.trim().to_string()))
+                    .collect()
+            })
+            .collect()
+    };
+    let (start_ms, end_ms) = range_millis(from, to);
+    let mut klines = parse_kline_rows(instrument, DEFAULT_BINANCE_BACKFILL_INTERVAL, rows)
+        .map_err(|error| to_storage_error(format!("{}: {error:#}", path.display())))?
+        .into_iter()
+        .map(|mut row| {
+            row.open_time_ms = normalize_epoch_millis(row.open_time_ms);
+            row.close_time_ms = normalize_epoch_millis(row.close_time_ms);
+            row
+        })
+        .filter(|row| (start_ms..=end_ms).contains(&row.open_time_ms))
+        .collect::<Vec<_>>();
+    klines.sort_by_key(|row| row.open_time_ms);
+    klines.dedup_by_key(|row| row.open_time_ms);
+    Ok(klines)
+}
+
+/// The same window the dataset queries use: `from 00:00:00` to `to 23:59:59`.
+fn range_millis(from: NaiveDate, to: NaiveDate) -> (i64, i64) {
+    let start = from.and_time(chrono::NaiveTime::MIN).and_utc();
+    let end = to
+        .and_hms_opt(23, 59, 59)
+        .map(|end| end.and_utc())
+        .unwrap_or(start);
+    (start.timestamp_millis(), end.timestamp_millis())
+}
+
+fn normalize_epoch_millis(value: i64) -> i64 {
+    if value >= 1_000_000_000_000_000 {
+        value / 1_000
+    } else {
+        value
+    }
+}
+
+fn to_storage_error(error: impl std::fmt::Display) -> StorageError {
+    StorageError::WriteFailedWithContext {
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique_path(name: &str, extension: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "sandbox-quant-{name}-{}.{extension}",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn load_klines_from_file_reads_binance_csv_with_header_and_microseconds() {
+        let path = unique_path("klines-csv", "csv");
+        std::fs::write(
+            &path,
+            "open_time,open,high,low,close,volume,close_time,quote_volume,count,taker_buy_volume,taker_buy_quote_volume,ignore\n\
+             1773446460000000,101,102,100,101.5,3,1773446519999999,304.5,9,1,101,0\n\
+             1773446400000,100,101,99,100.5,2,1773446459999,201,7,1,100,0\n\
+             1773532800000,105,106,104,105.5,1,1773532859999,105.5,3,0,0,0\n",
+        )
+        .expect("write csv");
+
+        let klines = load_klines_from_file(
+            &path,
+            "BTCUSDT",
+            NaiveDate::from_ymd_opt(2026, 3, 14).expect("date"),
+            NaiveDate::from_ymd_opt(2026, 3, 14).expect("date"),
+        )
+        .expect("klines");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            klines
+                .iter()
+                .map(|row| (row.open_time_ms, row.close))
+                .collect::<Vec<_>>(),
+            vec![(1_773_446_400_000, 100.5), (1_773_446_460_000, 101.5)]
+        );
+        assert_eq!(klines[1].close_time_ms, 1_773_446_519_999);
+        assert_eq!(klines[1].trade_count, 9);
+    }
+
+    #[test]
+    fn load_klines_from_file_reads_saved_rest_json() {
+        let path = unique_path("klines-json", "json");
+        std::fs::write(
+            &path,
+            r#"[[1773446400000,"100","101","99","100.5","2",1773446459999,"201",7,"1","100","0"]]"#,
+        )
+        .expect("write json");
+
+        let klines = load_klines_from_file(
+            &path,
+            "BTCUSDT",
+            NaiveDate::from_ymd_opt(2026, 3, 14).expect("date"),
+            NaiveDate::from_ymd_opt(2026, 3, 14).expect("date"),
+        )
+        .expect("klines");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(klines.len(), 1);
+        assert_eq!(klines[0].high, 101.0);
+        assert_eq!(klines[0].quote_volume, 201.0);
+    }
+}
@@ -1,4 +1,7 @@
+pub mod compare;
 pub mod export;
+pub mod klines;
+pub mod monthly;
 pub mod runner;
 pub mod snapshot;
 pub mod terminal;
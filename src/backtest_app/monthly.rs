@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::backtest_app::runner::BacktestReport;
+use crate::ui::timezone::DisplayTimeZone;
+
+/// Reads the annual risk-free rate used as the monthly benchmark.
+///
+/// Reads:
+/// - `SANDBOX_QUANT_RISK_FREE_RATE_PCT` (e.g. `4.5`, default `0`)
+pub fn risk_free_rate_pct_from_env() -> f64 {
+    std::env::var("SANDBOX_QUANT_RISK_FREE_RATE_PCT")
+        .ok()
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|value| value.is_finite())
+        .unwrap_or(0.0)
+}
+
+/// Realized results for one calendar month, bucketed by exit time.
+///
+/// Example:
+/// - two March exits with gross `12.00` and fees `1.20`
+/// - `net_pnl=10.80`, `fee_drag_pct=10.00`
+/// - on `10,000` equity at `4.5%` a year the benchmark earns `38.22`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthlyPerformance {
+    pub year: i32,
+    pub month: u32,
+    pub closed_trades: usize,
+    /// Equity carried into the month from earlier realized results.
+    pub starting_equity: f64,
+    pub gross_pnl: f64,
+    pub fees: f64,
+    /// Funding paid; backtests do not model funding, so this stays `0`.
+    pub funding: f64,
+    pub net_pnl: f64,
+    /// Entry plus exit notional.
+    pub turnover: f64,
+    /// What `starting_equity` would have earned at the risk-free rate.
+    pub benchmark_pnl: f64,
+}
+
+impl MonthlyPerformance {
+    /// Example:
+    /// - `2026-03`
+    pub fn label(&self) -> String {
+        format!("{}-{:02}", self.year, self.month)
+    }
+
+    /// Fees as a share of absolute gross PnL; `None` when gross is flat.
+    pub fn fee_drag_pct(&self) -> Option<f64> {
+        (self.gross_pnl.abs() > f64::EPSILON).then(|| self.fees / self.gross_pnl.abs() * 100.0)
+    }
+
+    pub fn return_pct(&self) -> f64 {
+        pct_of(self.net_pnl, self.starting_equity)
+    }
+
+    pub fn benchmark_pct(&self) -> f64 {
+        pct_of(self.benchmark_pnl, self.starting_equity)
+    }
+
+    pub fn excess_return_pct(&self) -> f64 {
+        self.return_pct() - self.benchmark_pct()
+    }
+}
+
+/// Groups the report's closed trades into calendar months of `zone`.
+///
+/// Open trades are left out because they have no realized fees or PnL yet.
+pub fn monthly_performance(
+    report: &BacktestReport,
+    risk_free_rate_pct: f64,
+    zone: DisplayTimeZone,
+) -> Vec<MonthlyPerformance> {
+    let mut months: BTreeMap<(i32, u32), MonthlyPerformance> = BTreeMap::new();
+    for trade in &report.trades {
+        let (Some(exit_time), Some(exit_price), Some(net_pnl)) =
+            (trade.exit_time, trade.exit_price, trade.net_pnl)
+        else {
+            continue;
+        };
+        let exit_date = zone.date(exit_time);
+        let key = (exit_date.year(), exit_date.month());
+        let row = months.entry(key).or_insert_with(|| MonthlyPerformance {
+            year: key.0,
+            month: key.1,
+            closed_trades: 0,
+            starting_equity: 0.0,
+            gross_pnl: 0.0,
+            fees: 0.0,
+            funding: 0.0,
+            net_pnl: 0.0,
+            turnover: 0.0,
+            benchmark_pnl: 0.0,
+        });
+        row.closed_trades += 1;
+        row.gross_pnl += trade.gross_pnl.unwrap_or(net_pnl);
+        row.fees += trade.fees.unwrap_or_default();
+        row.net_pnl += net_pnl;
+        row.turnover += trade.qty.abs() * (trade.entry_price + exit_price);
+    }
+
+    let mut equity = report.starting_equity;
+    months
+        .into_values()
+        .map(|mut row| {
+            row.net_pnl -= row.funding;
+            row.starting_equity = equity;
+            row.benchmark_pnl = equity * risk_free_rate_pct / 100.0
+                * days_in_month(row.year, row.month) as f64
+                / 365.0;
+            equity += row.net_pnl;
+            row
+        })
+        .collect()
+}
+
+fn days_in_month(year: i32, month: u32) -> i64 {
+    let start = NaiveDate::from_ymd_opt(year, month, 1);
+    let next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    };
+    match (start, next) {
+        (Some(start), Some(next)) => (next - start).num_days(),
+        _ => 30,
+    }
+}
+
+fn pct_of(value: f64, base: f64) -> f64 {
+    if base.abs() <= f64::EPSILON {
+        return 0.0;
+    }
+    value / base * 100.0
+}
@@ -1,9 +1,10 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, TimeZone, Utc};
 
 use crate::app::bootstrap::BinanceMode;
+use crate::command::backtest::BacktestCostOverrides;
 use crate::dataset::query::{
     backtest_summary_for_path, load_book_ticker_rows_for_path, load_liquidation_events_for_path,
     load_raw_kline_rows_for_path,
@@ -11,11 +12,24 @@ use crate::dataset::query::{
 use crate::dataset::types::{
     BacktestDatasetSummary, BookTickerRow, DerivedKlineRow, LiquidationEventRow,
 };
+use crate::domain::position::Side;
+use crate::domain::reason::{ExitReason, SkipReason};
 use crate::error::storage_error::StorageError;
+use crate::market_data::candle_mode::CandleMode;
+use crate::market_data::volatility::{VolatilityEstimator, VolatilityModel};
 use crate::storage::postgres_market_data::{
     backtest_summary_for_postgres_url, load_raw_kline_rows_for_postgres_url, mask_postgres_url,
 };
+use crate::strategy::cooldown::{CooldownScaling, ExpectancyCooldown, DEFAULT_COOLDOWN_SECS};
+use crate::strategy::htf_confirm::{HtfConfirm, HtfTrend};
+use crate::strategy::ev::{entry_expected_value_r, EvGate, EvSnapshotUpdate};
 use crate::strategy::model::StrategyTemplate;
+use crate::strategy::order_throttle::{OrderThrottle, DEFAULT_MAX_ORDERS_PER_MIN};
+use crate::strategy::overtrading::{OvertradingGuard, OvertradingLimit};
+use crate::strategy::r_multiple::{r_multiple, RMultipleStats};
+use crate::strategy::registry::{Strategy, StrategyRegistry, StrategySignal};
+use crate::strategy::plugin::WasmStrategy;
+use crate::strategy::script::ScriptStrategy;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct BacktestConfig {
@@ -29,13 +43,37 @@ pub struct BacktestConfig {
     pub cluster_lookback_secs: i64,
     pub failed_hold_timeout_secs: i64,
     pub breakdown_confirm_bps: f64,
+    /// Wait after each exit before the next entry.
     pub cooldown_secs: i64,
+    /// Scales `cooldown_secs` by recent expectancy; `None` keeps it fixed.
+    pub cooldown_scaling: Option<CooldownScaling>,
     pub taker_fee_rate: f64,
     pub stop_slippage_pct: f64,
     pub tp_slippage_pct: f64,
+    /// Pauses entries after a burst of trades; `None` is off.
+    pub overtrading: Option<OvertradingLimit>,
+    /// Entry orders allowed per rolling minute; excess signals are dropped.
+    pub max_orders_per_min: usize,
+    pub volatility_model: VolatilityModel,
+    /// Stop distance in per-bar sigmas; `0` keeps the fixed `stop_distance_pct`.
+    pub volatility_stop_sigmas: f64,
+    /// Series the SMA-cross templates average; fills still use raw candles.
+    pub signal_candles: CandleMode,
+    /// Higher-timeframe trend SMA-cross entries must agree with; `None` is off.
+    pub htf_confirm: Option<HtfConfirm>,
+    /// Rhai script the `script-long`/`script-short` templates evaluate.
+    pub strategy_script: Option<PathBuf>,
+    /// WASM module the `plugin-long`/`plugin-short` templates run.
+    pub strategy_plugin: Option<PathBuf>,
+    /// Estimates entries' expected R, and rejects low ones when active.
+    pub ev_gate: EvGate,
 }
 
 impl Default for BacktestConfig {
+    /// The cooldown scaling, EV gate, signal candles, HTF confirmation and
+    /// overtrading guard default to `CooldownScaling::from_env`,
+    /// `EvGate::from_env`, `CandleMode::signal_from_env`,
+    /// `HtfConfirm::from_env` and `OvertradingLimit::from_env`.
     fn default() -> Self {
         Self {
             starting_equity: 10_000.0,
@@ -48,36 +86,43 @@ impl Default for BacktestConfig {
             cluster_lookback_secs: 60,
             failed_hold_timeout_secs: 30,
             breakdown_confirm_bps: 5.0,
-            cooldown_secs: 30,
+            cooldown_secs: DEFAULT_COOLDOWN_SECS,
+            cooldown_scaling: CooldownScaling::from_env(),
             taker_fee_rate: 0.0005,
             stop_slippage_pct: 0.0008,
             tp_slippage_pct: 0.0003,
+            overtrading: OvertradingLimit::from_env(),
+            max_orders_per_min: DEFAULT_MAX_ORDERS_PER_MIN,
+            volatility_model: VolatilityModel::default(),
+            volatility_stop_sigmas: 0.0,
+            signal_candles: CandleMode::signal_from_env(),
+            htf_confirm: HtfConfirm::from_env(),
+            strategy_script: None,
+            strategy_plugin: None,
+            ev_gate: EvGate::from_env(),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum BacktestExitReason {
-    TakeProfit,
-    StopLoss,
-    OpenAtEnd,
-    SignalExit,
-}
-
-impl BacktestExitReason {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::TakeProfit => "take_profit",
-            Self::StopLoss => "stop_loss",
-            Self::OpenAtEnd => "open_at_end",
-            Self::SignalExit => "signal_exit",
-        }
+impl BacktestConfig {
+    /// Example:
+    /// - `--taker-fee 0.0004` -> `taker_fee_rate=0.0004`, other costs unchanged
+    pub fn with_costs(mut self, costs: &BacktestCostOverrides) -> Self {
+        self.taker_fee_rate = costs.taker_fee_rate.unwrap_or(self.taker_fee_rate);
+        self.max_entry_slippage_pct = costs
+            .max_entry_slippage_pct
+            .unwrap_or(self.max_entry_slippage_pct);
+        self.stop_slippage_pct = costs.stop_slippage_pct.unwrap_or(self.stop_slippage_pct);
+        self.tp_slippage_pct = costs.tp_slippage_pct.unwrap_or(self.tp_slippage_pct);
+        self
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct BacktestTrade {
     pub trade_id: usize,
+    /// `Sell` for shorts, which close with a buy to cover.
+    pub side: Side,
     pub trigger_time: DateTime<Utc>,
     pub entry_time: DateTime<Utc>,
     pub entry_price: f64,
@@ -86,12 +131,82 @@ pub struct BacktestTrade {
     pub qty: f64,
     pub exit_time: Option<DateTime<Utc>>,
     pub exit_price: Option<f64>,
-    pub exit_reason: Option<BacktestExitReason>,
+    pub exit_reason: Option<ExitReason>,
     pub gross_pnl: Option<f64>,
     pub fees: Option<f64>,
     pub net_pnl: Option<f64>,
 }
 
+impl BacktestTrade {
+    /// What the initial stop put at risk.
+    pub fn initial_risk_usdt(&self) -> f64 {
+        (self.entry_price - self.stop_price).abs() * self.qty
+    }
+
+    /// Net outcome in units of `initial_risk_usdt`; `None` while open.
+    ///
+    /// Example:
+    /// - entry `100`, stop `99`, qty `2`, net `+3` -> `Some(1.5)`
+    pub fn r_multiple(&self) -> Option<f64> {
+        r_multiple(self.net_pnl?, self.initial_risk_usdt())
+    }
+}
+
+/// What became of one entry signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalOutcome {
+    /// The signal opened a trade.
+    Ordered,
+    /// A gate held it back, e.g. the overtrading pause or the order rate limit.
+    Blocked,
+    /// Sizing produced no valid order.
+    Rejected,
+}
+
+impl SignalOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ordered => "ordered",
+            Self::Blocked => "blocked",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+/// An entry signal with its outcome, fired or not.
+///
+/// Example:
+/// - SMA cross at `12:05` while the order rate limit is full
+/// - `outcome=Blocked`, `reason=Some(SkipReason::Throttle)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestSignal {
+    pub time_ms: i64,
+    pub price: f64,
+    pub outcome: SignalOutcome,
+    /// `None` when ordered.
+    pub reason: Option<SkipReason>,
+    /// The EV gate's view of an entry; `None` for signals held back before
+    /// it and for the liquidation template, which it does not gate.
+    pub ev: Option<EvSnapshotUpdate>,
+}
+
+impl BacktestSignal {
+    fn new(time_ms: i64, price: f64, outcome: SignalOutcome, reason: Option<SkipReason>) -> Self {
+        Self {
+            time_ms,
+            price,
+            outcome,
+            reason,
+            ev: None,
+        }
+    }
+
+    fn with_ev(mut self, ev: Option<EvSnapshotUpdate>) -> Self {
+        self.ev = ev;
+        self
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct BacktestReport {
     pub run_id: Option<i64>,
@@ -109,6 +224,11 @@ pub struct BacktestReport {
     pub losses: usize,
     pub open_trades: usize,
     pub skipped_triggers: usize,
+    /// Entry signals dropped by the per-strategy order rate limit.
+    pub throttled_signals: usize,
+    /// Every entry signal, ordered or not. Not persisted, so reports loaded
+    /// from storage have none.
+    pub signals: Vec<BacktestSignal>,
     pub starting_equity: f64,
     pub ending_equity: f64,
     pub net_pnl: f64,
@@ -117,6 +237,59 @@ pub struct BacktestReport {
     pub configured_expected_value: f64,
 }
 
+/// Closed trades and their net PnL for one exit reason.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ExitReasonStats {
+    pub trades: usize,
+    pub net_pnl: f64,
+}
+
+impl ExitReasonStats {
+    pub fn average_net_pnl(&self) -> f64 {
+        if self.trades == 0 {
+            0.0
+        } else {
+            self.net_pnl / self.trades as f64
+        }
+    }
+}
+
+impl BacktestReport {
+    /// Example:
+    /// - two take profits at `+1.8` and one stop at `-1.2`
+    /// - `take_profit trades=2 net_pnl=3.60`, `stop_loss trades=1 net_pnl=-1.20`
+    pub fn exit_reason_stats(&self) -> BTreeMap<ExitReason, ExitReasonStats> {
+        let mut stats = BTreeMap::<ExitReason, ExitReasonStats>::new();
+        for trade in &self.trades {
+            if let Some(reason) = trade.exit_reason {
+                let entry = stats.entry(reason).or_default();
+                entry.trades += 1;
+                entry.net_pnl += trade.net_pnl.unwrap_or_default();
+            }
+        }
+        stats
+    }
+
+    /// Closed trades in R; a trade without stop distance counts as unrisked.
+    pub fn r_multiple_stats(&self) -> RMultipleStats {
+        RMultipleStats::from_r_values(
+            self.trades
+                .iter()
+                .filter(|trade| trade.net_pnl.is_some())
+                .map(BacktestTrade::r_multiple),
+        )
+    }
+
+    /// Signals that did not order, counted by reason.
+    pub fn skip_reason_counts(&self) -> BTreeMap<SkipReason, usize> {
+        let mut counts = BTreeMap::new();
+        for reason in self.signals.iter().filter_map(|signal| signal.reason) {
+            *counts.entry(reason).or_default() += 1;
+        }
+        counts
+    }
+}
+
 #[derive(Debug, Clone)]
 struct PendingCluster {
     formed_at_ms: i64,
@@ -135,6 +308,13 @@ struct OpenTrade {
     entry_fee: f64,
 }
 
+impl OpenTrade {
+    /// What the stop puts at risk, for R multiples.
+    fn risk_usdt(&self) -> f64 {
+        (self.entry_price - self.stop_price).abs() * self.qty
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ReplayEventKind {
     Liquidation(usize),
@@ -147,6 +327,22 @@ enum PriceCrossDirection {
     Short,
 }
 
+impl PriceCrossDirection {
+    fn from_side(side: Side) -> Self {
+        match side {
+            Side::Buy => Self::Long,
+            Side::Sell => Self::Short,
+        }
+    }
+
+    fn side(self) -> Side {
+        match self {
+            Self::Long => Side::Buy,
+            Self::Short => Side::Sell,
+        }
+    }
+}
+
 pub fn run_backtest_for_path(
     db_path: &Path,
     mode: BinanceMode,
@@ -171,93 +367,45 @@ pub fn run_backtest_for_path(
                 book_tickers.len(),
             )?;
             Ok(run_backtest_on_events(
-                template,
-                instrument.to_string(),
-                mode,
-                from,
-                to,
-                db_path.to_path_buf(),
-                dataset,
+                BacktestRun {
+                    template,
+                    instrument: instrument.to_string(),
+                    mode,
+                    from,
+                    to,
+                    db_path: db_path.to_path_buf(),
+                    dataset,
+                    config,
+                },
                 liquidation_events,
                 book_tickers,
-                config,
             ))
         }
-        StrategyTemplate::PriceSmaCrossLong => {
+        _ => {
+            let strategy = bar_strategy(template, &config)?;
             let (_, klines) = load_raw_kline_rows_for_path(db_path, instrument, from, to)?
                 .unwrap_or_else(|| ("none".to_string(), Vec::new()));
-            ensure_price_cross_dataset_ready(template, instrument, &dataset, klines.len(), 50)?;
-            Ok(run_price_sma_cross_on_klines(
+            ensure_price_cross_dataset_ready(
                 template,
-                PriceCrossDirection::Long,
-                20,
-                50,
-                instrument.to_string(),
-                mode,
-                from,
-                to,
-                db_path.to_path_buf(),
-                dataset,
-                klines,
-                config,
-            ))
-        }
-        StrategyTemplate::PriceSmaCrossShort => {
-            let (_, klines) = load_raw_kline_rows_for_path(db_path, instrument, from, to)?
-                .unwrap_or_else(|| ("none".to_string(), Vec::new()));
-            ensure_price_cross_dataset_ready(template, instrument, &dataset, klines.len(), 50)?;
-            Ok(run_price_sma_cross_on_klines(
-                template,
-                PriceCrossDirection::Short,
-                20,
-                50,
-                instrument.to_string(),
-                mode,
-                from,
-                to,
-                db_path.to_path_buf(),
-                dataset,
-                klines,
-                config,
-            ))
-        }
-        StrategyTemplate::PriceSmaCrossLongFast => {
-            let (_, klines) = load_raw_kline_rows_for_path(db_path, instrument, from, to)?
-                .unwrap_or_else(|| ("none".to_string(), Vec::new()));
-            ensure_price_cross_dataset_ready(template, instrument, &dataset, klines.len(), 21)?;
-            Ok(run_price_sma_cross_on_klines(
-                template,
-                PriceCrossDirection::Long,
-                9,
-                21,
-                instrument.to_string(),
-                mode,
-                from,
-                to,
-                db_path.to_path_buf(),
-                dataset,
-                klines,
-                config,
-            ))
-        }
-        StrategyTemplate::PriceSmaCrossShortFast => {
-            let (_, klines) = load_raw_kline_rows_for_path(db_path, instrument, from, to)?
-                .unwrap_or_else(|| ("none".to_string(), Vec::new()));
-            ensure_price_cross_dataset_ready(template, instrument, &dataset, klines.len(), 21)?;
-            Ok(run_price_sma_cross_on_klines(
-                template,
-                PriceCrossDirection::Short,
-                9,
-                21,
-                instrument.to_string(),
-                mode,
-                from,
-                to,
-                db_path.to_path_buf(),
-                dataset,
+                instrument,
+                &dataset,
+                klines.len(),
+                strategy.warmup_bars(),
+            )?;
+            run_bar_strategy_on_klines(
+                BacktestRun {
+                    template,
+                    instrument: instrument.to_string(),
+                    mode,
+                    from,
+                    to,
+                    db_path: db_path.to_path_buf(),
+                    dataset,
+                    config,
+                },
+                strategy,
                 klines,
-                config,
-            ))
+            )
         }
     }
 }
@@ -279,89 +427,141 @@ pub fn run_backtest_for_postgres_url(
             message: "liquidation-breakdown-short is not supported in PostgreSQL direct mode yet"
                 .to_string(),
         }),
-        StrategyTemplate::PriceSmaCrossLong => {
-            let (_, klines) =
-                load_raw_kline_rows_for_postgres_url(postgres_url, mode, instrument, from, to)?
-                    .unwrap_or_else(|| ("none".to_string(), Vec::new()));
-            ensure_price_cross_dataset_ready(template, instrument, &dataset, klines.len(), 50)?;
-            Ok(run_price_sma_cross_on_klines(
-                template,
-                PriceCrossDirection::Long,
-                20,
-                50,
-                instrument.to_string(),
-                mode,
-                from,
-                to,
-                db_path,
-                dataset,
-                klines,
-                config,
-            ))
-        }
-        StrategyTemplate::PriceSmaCrossShort => {
-            let (_, klines) =
-                load_raw_kline_rows_for_postgres_url(postgres_url, mode, instrument, from, to)?
-                    .unwrap_or_else(|| ("none".to_string(), Vec::new()));
-            ensure_price_cross_dataset_ready(template, instrument, &dataset, klines.len(), 50)?;
-            Ok(run_price_sma_cross_on_klines(
-                template,
-                PriceCrossDirection::Short,
-                20,
-                50,
-                instrument.to_string(),
-                mode,
-                from,
-                to,
-                db_path,
-                dataset,
-                klines,
-                config,
-            ))
-        }
-        StrategyTemplate::PriceSmaCrossLongFast => {
+        _ => {
+            let strategy = bar_strategy(template, &config)?;
             let (_, klines) =
                 load_raw_kline_rows_for_postgres_url(postgres_url, mode, instrument, from, to)?
                     .unwrap_or_else(|| ("none".to_string(), Vec::new()));
-            ensure_price_cross_dataset_ready(template, instrument, &dataset, klines.len(), 21)?;
-            Ok(run_price_sma_cross_on_klines(
+            ensure_price_cross_dataset_ready(
                 template,
-                PriceCrossDirection::Long,
-                9,
-                21,
-                instrument.to_string(),
-                mode,
-                from,
-                to,
-                db_path,
-                dataset,
-                klines,
-                config,
-            ))
-        }
-        StrategyTemplate::PriceSmaCrossShortFast => {
-            let (_, klines) =
-                load_raw_kline_rows_for_postgres_url(postgres_url, mode, instrument, from, to)?
-                    .unwrap_or_else(|| ("none".to_string(), Vec::new()));
-            ensure_price_cross_dataset_ready(template, instrument, &dataset, klines.len(), 21)?;
-            Ok(run_price_sma_cross_on_klines(
-                template,
-                PriceCrossDirection::Short,
-                9,
-                21,
-                instrument.to_string(),
-                mode,
-                from,
-                to,
-                db_path,
-                dataset,
+                instrument,
+                &dataset,
+                klines.len(),
+                strategy.warmup_bars(),
+            )?;
+            run_bar_strategy_on_klines(
+                BacktestRun {
+                    template,
+                    instrument: instrument.to_string(),
+                    mode,
+                    from,
+                    to,
+                    db_path,
+                    dataset,
+                    config,
+                },
+                strategy,
                 klines,
-                config,
-            ))
+            )
         }
     }
 }
 
+/// Replays candles loaded outside the recorder dataset, e.g. from a file
+/// or the REST API; `source` is stored where reports keep the db path.
+///
+/// Only templates in the strategy registry run here: the liquidation
+/// template also needs liquidation and book ticker events.
+#[allow(clippy::too_many_arguments)]
+pub fn run_backtest_on_klines(
+    template: StrategyTemplate,
+    instrument: &str,
+    mode: BinanceMode,
+    from: chrono::NaiveDate,
+    to: chrono::NaiveDate,
+    source: PathBuf,
+    klines: Vec<DerivedKlineRow>,
+    config: BacktestConfig,
+) -> Result<BacktestReport, StorageError> {
+    let dataset = BacktestDatasetSummary {
+        mode,
+        symbol: instrument.to_string(),
+        symbol_found: !klines.is_empty(),
+        from: from.to_string(),
+        to: to.to_string(),
+        liquidation_events: 0,
+        book_ticker_events: 0,
+        agg_trade_events: 0,
+        derived_kline_1s_bars: 0,
+    };
+    ensure_symbol_found(&dataset, template, instrument)?;
+    let strategy = bar_strategy(template, &config)?;
+    ensure_price_cross_dataset_ready(
+        template,
+        instrument,
+        &dataset,
+        klines.len(),
+        strategy.warmup_bars(),
+    )?;
+    run_bar_strategy_on_klines(
+        BacktestRun {
+            template,
+            instrument: instrument.to_string(),
+            mode,
+            from,
+            to,
+            db_path: source,
+            dataset,
+            config,
+        },
+        strategy,
+        klines,
+    )
+}
+
+/// A fresh bar-driven strategy for `template`: the builtin registry's,
+/// the Rhai script in `config.strategy_script` for the script templates, or the
+/// module in `config.strategy_plugin` for the plugin templates.
+fn bar_strategy(
+    template: StrategyTemplate,
+    config: &BacktestConfig,
+) -> Result<Box<dyn Strategy>, StorageError> {
+    if matches!(
+        template,
+        StrategyTemplate::ScriptLong | StrategyTemplate::ScriptShort
+    ) {
+        let path = config.strategy_script.as_deref().ok_or_else(|| {
+            StorageError::WriteFailedWithContext {
+                message: format!(
+                    "backtest failed: template={} needs --script <path>",
+                    template.slug()
+                ),
+            }
+        })?;
+        return ScriptStrategy::load(path, template.entry_side())
+            .map(|strategy| Box::new(strategy) as Box<dyn Strategy>)
+            .map_err(|error| StorageError::WriteFailedWithContext {
+                message: format!("backtest failed: {error}"),
+            });
+    }
+    if matches!(
+        template,
+        StrategyTemplate::PluginLong | StrategyTemplate::PluginShort
+    ) {
+        let path = config.strategy_plugin.as_deref().ok_or_else(|| {
+            StorageError::WriteFailedWithContext {
+                message: format!(
+                    "backtest failed: template={} needs --plugin <name>",
+                    template.slug()
+                ),
+            }
+        })?;
+        return WasmStrategy::load(path, template.entry_side())
+            .map(|strategy| Box::new(strategy) as Box<dyn Strategy>)
+            .map_err(|error| StorageError::WriteFailedWithContext {
+                message: format!("backtest failed: {error}"),
+            });
+    }
+    StrategyRegistry::builtin()
+        .create(template)
+        .ok_or_else(|| StorageError::WriteFailedWithContext {
+            message: format!(
+                "backtest failed: template={} needs liquidation and book ticker events; run it on the recorder dataset",
+                template.slug()
+            ),
+        })
+}
+
 fn ensure_symbol_found(
     dataset: &BacktestDatasetSummary,
     template: StrategyTemplate,
@@ -427,7 +627,8 @@ fn ensure_price_cross_dataset_ready(
     })
 }
 
-fn run_backtest_on_events(
+/// What a replay runs and reports on, shared by the event and bar runners.
+struct BacktestRun {
     template: StrategyTemplate,
     instrument: String,
     mode: BinanceMode,
@@ -435,10 +636,24 @@ fn run_backtest_on_events(
     to: chrono::NaiveDate,
     db_path: PathBuf,
     dataset: BacktestDatasetSummary,
+    config: BacktestConfig,
+}
+
+fn run_backtest_on_events(
+    run: BacktestRun,
     liquidation_events: Vec<LiquidationEventRow>,
     book_tickers: Vec<BookTickerRow>,
-    config: BacktestConfig,
 ) -> BacktestReport {
+    let BacktestRun {
+        template,
+        instrument,
+        mode,
+        from,
+        to,
+        db_path,
+        dataset,
+        config,
+    } = run;
     let mut replay = liquidation_events
         .iter()
         .enumerate()
@@ -466,8 +681,12 @@ fn run_backtest_on_events(
     let mut completed_trades = Vec::new();
     let mut trigger_count = 0usize;
     let mut skipped_triggers = 0usize;
-    let mut next_allowed_entry_ms = 0i64;
+    let mut signals = Vec::new();
+    let mut cooldown =
+        ExpectancyCooldown::new(config.cooldown_secs, config.cooldown_scaling.clone());
     let mut equity = config.starting_equity;
+    let mut overtrading = overtrading_guard(&config);
+    let mut throttle = OrderThrottle::new(config.max_orders_per_min);
 
     for (event_time_ms, kind) in replay {
         match kind {
@@ -488,7 +707,7 @@ fn run_backtest_on_events(
                     .map(|item| item.notional)
                     .sum::<f64>();
                 if open_trade.is_none()
-                    && event_time_ms >= next_allowed_entry_ms
+                    && cooldown.allows_entry(event_time_ms)
                     && total_notional >= config.min_cluster_notional
                 {
                     let zone_low = liquidation_window
@@ -513,6 +732,7 @@ fn run_backtest_on_events(
                         equity += net_pnl;
                         completed_trades.push(BacktestTrade {
                             trade_id: trade.trade_id,
+                            side: template.entry_side(),
                             trigger_time: timestamp_utc(trade.trigger_time_ms),
                             entry_time: timestamp_utc(trade.entry_time_ms),
                             entry_price: trade.entry_price,
@@ -521,13 +741,13 @@ fn run_backtest_on_events(
                             qty: trade.qty,
                             exit_time: Some(timestamp_utc(tick.event_time_ms)),
                             exit_price: Some(exit_price),
-                            exit_reason: Some(BacktestExitReason::StopLoss),
+                            exit_reason: Some(ExitReason::StopLoss),
                             gross_pnl: Some(gross_pnl),
                             fees: Some(fees),
                             net_pnl: Some(net_pnl),
                         });
+                        cooldown.record_exit(tick.event_time_ms, net_pnl, trade.risk_usdt());
                         open_trade = None;
-                        next_allowed_entry_ms = tick.event_time_ms + config.cooldown_secs * 1_000;
                         continue;
                     }
                     if tick.ask <= trade.take_profit_price {
@@ -539,6 +759,7 @@ fn run_backtest_on_events(
                         equity += net_pnl;
                         completed_trades.push(BacktestTrade {
                             trade_id: trade.trade_id,
+                            side: template.entry_side(),
                             trigger_time: timestamp_utc(trade.trigger_time_ms),
                             entry_time: timestamp_utc(trade.entry_time_ms),
                             entry_price: trade.entry_price,
@@ -547,13 +768,13 @@ fn run_backtest_on_events(
                             qty: trade.qty,
                             exit_time: Some(timestamp_utc(tick.event_time_ms)),
                             exit_price: Some(exit_price),
-                            exit_reason: Some(BacktestExitReason::TakeProfit),
+                            exit_reason: Some(ExitReason::TakeProfit),
                             gross_pnl: Some(gross_pnl),
                             fees: Some(fees),
                             net_pnl: Some(net_pnl),
                         });
+                        cooldown.record_exit(tick.event_time_ms, net_pnl, trade.risk_usdt());
                         open_trade = None;
-                        next_allowed_entry_ms = tick.event_time_ms + config.cooldown_secs * 1_000;
                         continue;
                     }
                 }
@@ -561,7 +782,7 @@ fn run_backtest_on_events(
                 let Some(cluster) = pending_cluster.clone() else {
                     continue;
                 };
-                if open_trade.is_some() || tick.event_time_ms < next_allowed_entry_ms {
+                if open_trade.is_some() || !cooldown.allows_entry(tick.event_time_ms) {
                     continue;
                 }
                 if tick.event_time_ms
@@ -575,8 +796,28 @@ fn run_backtest_on_events(
                 if tick.bid > breakdown_price {
                     continue;
                 }
-                if equity <= 0.0 {
+                if equity <= 0.0 || !overtrading.allows_entry(tick.event_time_ms) {
                     skipped_triggers += 1;
+                    signals.push(BacktestSignal::new(
+                        tick.event_time_ms,
+                        tick.bid,
+                        SignalOutcome::Blocked,
+                        Some(if equity <= 0.0 {
+                            SkipReason::NoEquity
+                        } else {
+                            SkipReason::Overtrading
+                        }),
+                    ));
+                    pending_cluster = None;
+                    continue;
+                }
+                if !throttle.try_admit(tick.event_time_ms) {
+                    signals.push(BacktestSignal::new(
+                        tick.event_time_ms,
+                        tick.bid,
+                        SignalOutcome::Blocked,
+                        Some(SkipReason::Throttle),
+                    ));
                     pending_cluster = None;
                     continue;
                 }
@@ -587,9 +828,21 @@ fn run_backtest_on_events(
                 let qty = risk_amount / (entry_price * config.stop_distance_pct);
                 if !(qty.is_finite() && qty > 0.0) {
                     skipped_triggers += 1;
+                    signals.push(BacktestSignal::new(
+                        tick.event_time_ms,
+                        tick.bid,
+                        SignalOutcome::Rejected,
+                        Some(SkipReason::InvalidQty),
+                    ));
                     pending_cluster = None;
                     continue;
                 }
+                signals.push(BacktestSignal::new(
+                    tick.event_time_ms,
+                    tick.bid,
+                    SignalOutcome::Ordered,
+                    None,
+                ));
                 let entry_fee = entry_price * qty * config.taker_fee_rate;
                 let stop_price = entry_price * (1.0 + config.stop_distance_pct);
                 let take_profit_price =
@@ -604,6 +857,7 @@ fn run_backtest_on_events(
                     qty,
                     entry_fee,
                 });
+                let _ = overtrading.record_entry(tick.event_time_ms);
                 pending_cluster = None;
             }
         }
@@ -634,6 +888,7 @@ fn run_backtest_on_events(
         };
         trades.push(BacktestTrade {
             trade_id: trade.trade_id,
+            side: template.entry_side(),
             trigger_time: timestamp_utc(trade.trigger_time_ms),
             entry_time: timestamp_utc(trade.entry_time_ms),
             entry_price: trade.entry_price,
@@ -642,7 +897,7 @@ fn run_backtest_on_events(
             qty: trade.qty,
             exit_time,
             exit_price,
-            exit_reason: Some(BacktestExitReason::OpenAtEnd),
+            exit_reason: Some(ExitReason::OpenAtEnd),
             gross_pnl,
             fees,
             net_pnl,
@@ -651,11 +906,11 @@ fn run_backtest_on_events(
 
     let wins = completed_trades
         .iter()
-        .filter(|trade| trade.exit_reason == Some(BacktestExitReason::TakeProfit))
+        .filter(|trade| trade.exit_reason == Some(ExitReason::TakeProfit))
         .count();
     let losses = completed_trades
         .iter()
-        .filter(|trade| trade.exit_reason == Some(BacktestExitReason::StopLoss))
+        .filter(|trade| trade.exit_reason == Some(ExitReason::StopLoss))
         .count();
     let net_pnl = trades.iter().filter_map(|trade| trade.net_pnl).sum::<f64>();
     let realized_trade_count = trades
@@ -672,8 +927,8 @@ fn run_backtest_on_events(
     } else {
         wins as f64 / completed_trades.len() as f64
     };
-    let average_win = average_net_of(&completed_trades, BacktestExitReason::TakeProfit);
-    let average_loss = average_net_of(&completed_trades, BacktestExitReason::StopLoss).abs();
+    let average_win = average_net_of(&completed_trades, ExitReason::TakeProfit);
+    let average_loss = average_net_of(&completed_trades, ExitReason::StopLoss).abs();
     let configured_expected_value = config.win_rate_assumption * average_win
         - (1.0 - config.win_rate_assumption) * average_loss;
 
@@ -693,6 +948,8 @@ fn run_backtest_on_events(
         losses,
         open_trades: 0,
         skipped_triggers,
+        throttled_signals: throttle.dropped(),
+        signals,
         starting_equity: config.starting_equity,
         ending_equity: equity,
         net_pnl,
@@ -702,10 +959,10 @@ fn run_backtest_on_events(
     }
 }
 
-fn average_net_of(trades: &[BacktestTrade], reason: BacktestExitReason) -> f64 {
+fn average_net_of(trades: &[BacktestTrade], reason: ExitReason) -> f64 {
     let values = trades
         .iter()
-        .filter(|trade| trade.exit_reason == Some(reason.clone()))
+        .filter(|trade| trade.exit_reason == Some(reason))
         .filter_map(|trade| trade.net_pnl)
         .collect::<Vec<_>>();
     if values.is_empty() {
@@ -715,44 +972,49 @@ fn average_net_of(trades: &[BacktestTrade], reason: BacktestExitReason) -> f64 {
     }
 }
 
-fn run_price_sma_cross_on_klines(
-    template: StrategyTemplate,
-    direction: PriceCrossDirection,
-    fast_window: usize,
-    slow_window: usize,
-    instrument: String,
-    mode: BinanceMode,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
-    db_path: PathBuf,
-    dataset: BacktestDatasetSummary,
+fn run_bar_strategy_on_klines(
+    run: BacktestRun,
+    mut strategy: Box<dyn Strategy>,
     klines: Vec<DerivedKlineRow>,
-    config: BacktestConfig,
-) -> BacktestReport {
-    let closes = klines.iter().map(|row| row.close).collect::<Vec<_>>();
+) -> Result<BacktestReport, StorageError> {
+    let BacktestRun {
+        template,
+        instrument,
+        mode,
+        from,
+        to,
+        db_path,
+        dataset,
+        config,
+    } = run;
+    let closes = config.signal_candles.signal_closes(&klines);
+    let direction = PriceCrossDirection::from_side(strategy.side());
+    strategy.reset();
     let mut open_trade: Option<OpenTrade> = None;
     let mut trades = Vec::new();
     let mut trigger_count = 0usize;
     let mut skipped_triggers = 0usize;
+    let mut signals = Vec::new();
     let mut equity = config.starting_equity;
+    let mut overtrading = overtrading_guard(&config);
+    let mut throttle = OrderThrottle::new(config.max_orders_per_min);
+    let mut volatility = VolatilityEstimator::new(config.volatility_model);
+    let mut htf_trend = config.htf_confirm.map(HtfTrend::new);
+    let mut cooldown =
+        ExpectancyCooldown::for_bars(config.cooldown_secs, config.cooldown_scaling.clone());
 
     for index in 0..klines.len() {
-        let Some(fast_now) = simple_moving_average(&closes, fast_window, index) else {
-            continue;
-        };
-        let Some(slow_now) = simple_moving_average(&closes, slow_window, index) else {
-            continue;
-        };
-        let Some(fast_prev) = index
-            .checked_sub(1)
-            .and_then(|value| simple_moving_average(&closes, fast_window, value))
-        else {
-            continue;
-        };
-        let Some(slow_prev) = index
-            .checked_sub(1)
-            .and_then(|value| simple_moving_average(&closes, slow_window, value))
-        else {
+        volatility.on_candle_close(&klines[index]);
+        if let Some(trend) = htf_trend.as_mut() {
+            trend.on_candle_close(&klines[index]);
+        }
+        let signal = strategy.on_tick(closes[index]);
+        if let Some(fault) = strategy.fault() {
+            return Err(StorageError::WriteFailedWithContext {
+                message: format!("backtest failed: strategy stopped: {fault}"),
+            });
+        }
+        let Some(signal) = signal else {
             continue;
         };
         let candle = &klines[index];
@@ -778,6 +1040,7 @@ fn run_price_sma_cross_on_klines(
                 equity += net_pnl;
                 trades.push(BacktestTrade {
                     trade_id: trade.trade_id,
+                    side: direction.side(),
                     trigger_time: timestamp_utc(trade.trigger_time_ms),
                     entry_time: timestamp_utc(trade.entry_time_ms),
                     entry_price: trade.entry_price,
@@ -786,11 +1049,12 @@ fn run_price_sma_cross_on_klines(
                     qty: trade.qty,
                     exit_time: Some(timestamp_utc(candle.close_time_ms)),
                     exit_price: Some(exit_price),
-                    exit_reason: Some(BacktestExitReason::StopLoss),
+                    exit_reason: Some(ExitReason::StopLoss),
                     gross_pnl: Some(gross_pnl),
                     fees: Some(fees),
                     net_pnl: Some(net_pnl),
                 });
+                cooldown.record_exit(candle.close_time_ms, net_pnl, trade.risk_usdt());
                 open_trade = None;
                 continue;
             }
@@ -814,6 +1078,7 @@ fn run_price_sma_cross_on_klines(
                 equity += net_pnl;
                 trades.push(BacktestTrade {
                     trade_id: trade.trade_id,
+                    side: direction.side(),
                     trigger_time: timestamp_utc(trade.trigger_time_ms),
                     entry_time: timestamp_utc(trade.entry_time_ms),
                     entry_price: trade.entry_price,
@@ -822,33 +1087,58 @@ fn run_price_sma_cross_on_klines(
                     qty: trade.qty,
                     exit_time: Some(timestamp_utc(candle.close_time_ms)),
                     exit_price: Some(exit_price),
-                    exit_reason: Some(BacktestExitReason::TakeProfit),
+                    exit_reason: Some(ExitReason::TakeProfit),
                     gross_pnl: Some(gross_pnl),
                     fees: Some(fees),
                     net_pnl: Some(net_pnl),
                 });
+                cooldown.record_exit(candle.close_time_ms, net_pnl, trade.risk_usdt());
                 open_trade = None;
                 continue;
             }
         }
 
-        let cross_up = fast_prev <= slow_prev && fast_now > slow_now;
-        let cross_down = fast_prev >= slow_prev && fast_now < slow_now;
-        let entry_signal = match direction {
-            PriceCrossDirection::Long => cross_up,
-            PriceCrossDirection::Short => cross_down,
-        };
-        let exit_signal = match direction {
-            PriceCrossDirection::Long => cross_down,
-            PriceCrossDirection::Short => cross_up,
-        };
+        let entry_signal = signal == StrategySignal::Enter;
+        let exit_signal = signal == StrategySignal::Exit;
 
         if open_trade.is_none() {
             if !entry_signal {
                 continue;
             }
-            if equity <= 0.0 {
+            if equity <= 0.0 || !overtrading.allows_entry(candle.close_time_ms) {
+                skipped_triggers += 1;
+                signals.push(BacktestSignal::new(
+                    candle.close_time_ms,
+                    candle.close,
+                    SignalOutcome::Blocked,
+                    Some(if equity <= 0.0 {
+                        SkipReason::NoEquity
+                    } else {
+                        SkipReason::Overtrading
+                    }),
+                ));
+                continue;
+            }
+            if !cooldown.allows_entry(candle.close_time_ms) {
+                signals.push(BacktestSignal::new(
+                    candle.close_time_ms,
+                    candle.close,
+                    SignalOutcome::Blocked,
+                    Some(SkipReason::Cooldown),
+                ));
+                continue;
+            }
+            if htf_trend
+                .as_ref()
+                .is_some_and(|trend| !trend.confirms(direction.side()))
+            {
                 skipped_triggers += 1;
+                signals.push(BacktestSignal::new(
+                    candle.close_time_ms,
+                    candle.close,
+                    SignalOutcome::Blocked,
+                    Some(SkipReason::HtfDisagrees),
+                ));
                 continue;
             }
             let entry_price = match direction {
@@ -859,12 +1149,75 @@ fn run_price_sma_cross_on_klines(
                     candle.close * (1.0 - config.max_entry_slippage_pct * 0.5)
                 }
             };
+            let stop_distance_pct = stop_distance_pct(&config, &volatility);
+            let stop_price = match direction {
+                PriceCrossDirection::Long => entry_price * (1.0 - stop_distance_pct),
+                PriceCrossDirection::Short => entry_price * (1.0 + stop_distance_pct),
+            };
+            let take_profit_price = match direction {
+                PriceCrossDirection::Long => {
+                    entry_price * (1.0 + stop_distance_pct * config.r_multiple)
+                }
+                PriceCrossDirection::Short => {
+                    entry_price * (1.0 - stop_distance_pct * config.r_multiple)
+                }
+            };
+            let ev = config.ev_gate.evaluate(entry_expected_value_r(
+                entry_price,
+                stop_price,
+                take_profit_price,
+                config.win_rate_assumption,
+                config.taker_fee_rate,
+            ));
+            if ev.gate_blocked {
+                skipped_triggers += 1;
+                signals.push(
+                    BacktestSignal::new(
+                        candle.close_time_ms,
+                        candle.close,
+                        SignalOutcome::Blocked,
+                        Some(SkipReason::LowExpectedValue),
+                    )
+                    .with_ev(Some(ev)),
+                );
+                continue;
+            }
+            if !throttle.try_admit(candle.close_time_ms) {
+                signals.push(
+                    BacktestSignal::new(
+                        candle.close_time_ms,
+                        candle.close,
+                        SignalOutcome::Blocked,
+                        Some(SkipReason::Throttle),
+                    )
+                    .with_ev(Some(ev)),
+                );
+                continue;
+            }
             let risk_amount = equity * config.risk_pct;
-            let qty = risk_amount / (entry_price * config.stop_distance_pct);
+            let qty = risk_amount / (entry_price * stop_distance_pct);
             if !(qty.is_finite() && qty > 0.0) {
                 skipped_triggers += 1;
+                signals.push(
+                    BacktestSignal::new(
+                        candle.close_time_ms,
+                        candle.close,
+                        SignalOutcome::Rejected,
+                        Some(SkipReason::InvalidQty),
+                    )
+                    .with_ev(Some(ev)),
+                );
                 continue;
             }
+            signals.push(
+                BacktestSignal::new(
+                    candle.close_time_ms,
+                    candle.close,
+                    SignalOutcome::Ordered,
+                    None,
+                )
+                .with_ev(Some(ev)),
+            );
             trigger_count += 1;
             let entry_fee = entry_price * qty * config.taker_fee_rate;
             open_trade = Some(OpenTrade {
@@ -872,24 +1225,23 @@ fn run_price_sma_cross_on_klines(
                 trigger_time_ms: candle.open_time_ms,
                 entry_time_ms: candle.close_time_ms,
                 entry_price,
-                stop_price: match direction {
-                    PriceCrossDirection::Long => entry_price * (1.0 - config.stop_distance_pct),
-                    PriceCrossDirection::Short => entry_price * (1.0 + config.stop_distance_pct),
-                },
-                take_profit_price: match direction {
-                    PriceCrossDirection::Long => {
-                        entry_price * (1.0 + config.stop_distance_pct * config.r_multiple)
-                    }
-                    PriceCrossDirection::Short => {
-                        entry_price * (1.0 - config.stop_distance_pct * config.r_multiple)
-                    }
-                },
+                stop_price,
+                take_profit_price,
                 qty,
                 entry_fee,
             });
+            let _ = overtrading.record_entry(candle.close_time_ms);
             continue;
         }
 
+        if entry_signal {
+            signals.push(BacktestSignal::new(
+                candle.close_time_ms,
+                candle.close,
+                SignalOutcome::Blocked,
+                Some(SkipReason::PositionOpen),
+            ));
+        }
         if exit_signal {
             let trade = open_trade.take().expect("open trade");
             let exit_price = match direction {
@@ -907,6 +1259,7 @@ fn run_price_sma_cross_on_klines(
             equity += net_pnl;
             trades.push(BacktestTrade {
                 trade_id: trade.trade_id,
+                side: direction.side(),
                 trigger_time: timestamp_utc(trade.trigger_time_ms),
                 entry_time: timestamp_utc(trade.entry_time_ms),
                 entry_price: trade.entry_price,
@@ -915,11 +1268,12 @@ fn run_price_sma_cross_on_klines(
                 qty: trade.qty,
                 exit_time: Some(timestamp_utc(candle.close_time_ms)),
                 exit_price: Some(exit_price),
-                exit_reason: Some(BacktestExitReason::SignalExit),
+                exit_reason: Some(ExitReason::SignalExit),
                 gross_pnl: Some(gross_pnl),
                 fees: Some(fees),
                 net_pnl: Some(net_pnl),
             });
+            cooldown.record_exit(candle.close_time_ms, net_pnl, trade.risk_usdt());
         }
     }
 
@@ -933,6 +1287,7 @@ fn run_price_sma_cross_on_klines(
             equity += net_pnl;
             trades.push(BacktestTrade {
                 trade_id: trade.trade_id,
+                side: direction.side(),
                 trigger_time: timestamp_utc(trade.trigger_time_ms),
                 entry_time: timestamp_utc(trade.entry_time_ms),
                 entry_price: trade.entry_price,
@@ -941,7 +1296,7 @@ fn run_price_sma_cross_on_klines(
                 qty: trade.qty,
                 exit_time: Some(timestamp_utc(last.close_time_ms)),
                 exit_price: Some(exit_price),
-                exit_reason: Some(BacktestExitReason::OpenAtEnd),
+                exit_reason: Some(ExitReason::OpenAtEnd),
                 gross_pnl: Some(gross_pnl),
                 fees: Some(fees),
                 net_pnl: Some(net_pnl),
@@ -971,7 +1326,7 @@ fn run_price_sma_cross_on_klines(
     let configured_expected_value = config.win_rate_assumption * average_win
         - (1.0 - config.win_rate_assumption) * average_loss;
 
-    BacktestReport {
+    Ok(BacktestReport {
         run_id: None,
         template,
         instrument,
@@ -987,29 +1342,42 @@ fn run_price_sma_cross_on_klines(
         losses,
         open_trades: 0,
         skipped_triggers,
+        throttled_signals: throttle.dropped(),
+        signals,
         starting_equity: config.starting_equity,
         ending_equity: equity,
         net_pnl,
         observed_win_rate,
         average_net_pnl,
         configured_expected_value,
-    }
+    })
 }
 
 fn gross_pnl(direction: PriceCrossDirection, entry_price: f64, exit_price: f64, qty: f64) -> f64 {
-    match direction {
-        PriceCrossDirection::Long => (exit_price - entry_price) * qty,
-        PriceCrossDirection::Short => (entry_price - exit_price) * qty,
-    }
+    direction.side().pnl(entry_price, exit_price, qty)
 }
 
-fn simple_moving_average(values: &[f64], window: usize, end_index: usize) -> Option<f64> {
-    if end_index + 1 < window {
-        return None;
+/// Stop distance as a fraction of entry price.
+///
+/// Example:
+/// - `volatility_stop_sigmas=3`, per-bar sigma `0.002` -> `0.006`
+/// - sigmas `0` or no sigma yet -> `stop_distance_pct`
+fn stop_distance_pct(config: &BacktestConfig, volatility: &VolatilityEstimator) -> f64 {
+    if config.volatility_stop_sigmas <= f64::EPSILON {
+        return config.stop_distance_pct;
     }
-    let start = end_index + 1 - window;
-    let sum = values[start..=end_index].iter().sum::<f64>();
-    Some(sum / window as f64)
+    volatility
+        .sigma()
+        .map(|sigma| sigma * config.volatility_stop_sigmas)
+        .filter(|distance| distance.is_finite() && *distance > f64::EPSILON)
+        .unwrap_or(config.stop_distance_pct)
+}
+
+fn overtrading_guard(config: &BacktestConfig) -> OvertradingGuard {
+    config
+        .overtrading
+        .map(|limit| limit.guard())
+        .unwrap_or_else(|| OvertradingGuard::new(0, 0, 0))
 }
 
 fn average_positive(values: &[f64]) -> f64 {
@@ -1048,25 +1416,179 @@ fn timestamp_utc(event_time_ms: i64) -> DateTime<Utc> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn liquidation_breakdown_backtest_shortens_cooldown_after_a_winner() {
+        let run = |config: BacktestConfig| {
+            run_backtest_on_events(
+                BacktestRun {
+                    template: StrategyTemplate::LiquidationBreakdownShort,
+                    instrument: "BTCUSDT".to_string(),
+                    mode: BinanceMode::Demo,
+                    from: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                    to: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                    db_path: PathBuf::from("/tmp/test.duckdb"),
+                    dataset: BacktestDatasetSummary {
+                        mode: BinanceMode::Demo,
+                        symbol: "BTCUSDT".to_string(),
+                        symbol_found: true,
+                        from: "2026-03-13".to_string(),
+                        to: "2026-03-13".to_string(),
+                        liquidation_events: 2,
+                        book_ticker_events: 4,
+                        agg_trade_events: 0,
+                        derived_kline_1s_bars: 0,
+                    },
+                    config,
+                },
+                [1_000, 15_000]
+                    .into_iter()
+                    .map(|event_time_ms| LiquidationEventRow {
+                        event_time_ms,
+                        force_side: "BUY".to_string(),
+                        price: 100.0,
+                        qty: 100.0,
+                        notional: 10_000.0,
+                    })
+                    .collect(),
+                [2_000, 16_000]
+                    .into_iter()
+                    .flat_map(|entry_ms| {
+                        [
+                            BookTickerRow {
+                                event_time_ms: entry_ms,
+                                bid: 99.9,
+                                ask: 100.0,
+                            },
+                            BookTickerRow {
+                                event_time_ms: entry_ms + 1_000,
+                                bid: 98.0,
+                                ask: 98.0,
+                            },
+                        ]
+                    })
+                    .collect(),
+            )
+        };
+
+        let fixed = run(BacktestConfig {
+            cooldown_scaling: None,
+            ..BacktestConfig::default()
+        });
+        let scaled = run(BacktestConfig {
+            cooldown_scaling: CooldownScaling::parse("5:300"),
+            ..BacktestConfig::default()
+        });
+
+        assert_eq!(fixed.trades.len(), 1);
+        assert_eq!(scaled.trades.len(), 2);
+        assert_eq!(
+            scaled.trades[1].entry_time.timestamp_millis()
+                - scaled.trades[0].entry_time.timestamp_millis(),
+            14_000
+        );
+    }
+
+    #[test]
+    fn liquidation_breakdown_backtest_pauses_entries_after_overtrading_alarm() {
+        let run = |config: BacktestConfig| {
+            run_backtest_on_events(
+                BacktestRun {
+                    template: StrategyTemplate::LiquidationBreakdownShort,
+                    instrument: "BTCUSDT".to_string(),
+                    mode: BinanceMode::Demo,
+                    from: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                    to: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                    db_path: PathBuf::from("/tmp/test.duckdb"),
+                    dataset: BacktestDatasetSummary {
+                        mode: BinanceMode::Demo,
+                        symbol: "BTCUSDT".to_string(),
+                        symbol_found: true,
+                        from: "2026-03-13".to_string(),
+                        to: "2026-03-13".to_string(),
+                        liquidation_events: 2,
+                        book_ticker_events: 4,
+                        agg_trade_events: 0,
+                        derived_kline_1s_bars: 0,
+                    },
+                    config,
+                },
+                [1_000, 40_000]
+                    .into_iter()
+                    .map(|event_time_ms| LiquidationEventRow {
+                        event_time_ms,
+                        force_side: "BUY".to_string(),
+                        price: 100.0,
+                        qty: 100.0,
+                        notional: 10_000.0,
+                    })
+                    .collect(),
+                [2_000, 41_000]
+                    .into_iter()
+                    .flat_map(|entry_ms| {
+                        [
+                            BookTickerRow {
+                                event_time_ms: entry_ms,
+                                bid: 99.9,
+                                ask: 100.0,
+                            },
+                            BookTickerRow {
+                                event_time_ms: entry_ms + 1_000,
+                                bid: 98.0,
+                                ask: 98.0,
+                            },
+                        ]
+                    })
+                    .collect(),
+            )
+        };
+
+        let unguarded = run(BacktestConfig {
+            overtrading: None,
+            ..BacktestConfig::default()
+        });
+        let guarded = run(BacktestConfig {
+            overtrading: OvertradingLimit::parse("1:900"),
+            ..BacktestConfig::default()
+        });
+
+        assert_eq!(unguarded.trades.len(), 2);
+        assert_eq!(guarded.trades.len(), 1);
+        assert_eq!(guarded.skipped_triggers, 1);
+        assert_eq!(
+            guarded
+                .signals
+                .iter()
+                .map(|signal| (signal.outcome, signal.reason))
+                .collect::<Vec<_>>(),
+            vec![
+                (SignalOutcome::Ordered, None),
+                (SignalOutcome::Blocked, Some(SkipReason::Overtrading)),
+            ]
+        );
+    }
+
     #[test]
     fn liquidation_breakdown_backtest_records_take_profit_trade() {
         let report = run_backtest_on_events(
-            StrategyTemplate::LiquidationBreakdownShort,
-            "BTCUSDT".to_string(),
-            BinanceMode::Demo,
-            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
-            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
-            PathBuf::from("/tmp/test.duckdb"),
-            BacktestDatasetSummary {
+            BacktestRun {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: "BTCUSDT".to_string(),
                 mode: BinanceMode::Demo,
-                symbol: "BTCUSDT".to_string(),
-                symbol_found: true,
-                from: "2026-03-13".to_string(),
-                to: "2026-03-13".to_string(),
-                liquidation_events: 1,
-                book_ticker_events: 3,
-                agg_trade_events: 0,
-                derived_kline_1s_bars: 0,
+                from: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                to: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                db_path: PathBuf::from("/tmp/test.duckdb"),
+                dataset: BacktestDatasetSummary {
+                    mode: BinanceMode::Demo,
+                    symbol: "BTCUSDT".to_string(),
+                    symbol_found: true,
+                    from: "2026-03-13".to_string(),
+                    to: "2026-03-13".to_string(),
+                    liquidation_events: 1,
+                    book_ticker_events: 3,
+                    agg_trade_events: 0,
+                    derived_kline_1s_bars: 0,
+                },
+                config: BacktestConfig::default(),
             },
             vec![LiquidationEventRow {
                 event_time_ms: 1_000,
@@ -1087,7 +1609,6 @@ mod tests {
                     ask: 98.0,
                 },
             ],
-            BacktestConfig::default(),
         );
 
         assert_eq!(report.wins, 1);
@@ -1098,22 +1619,25 @@ mod tests {
     #[test]
     fn liquidation_breakdown_backtest_records_stop_loss_trade() {
         let report = run_backtest_on_events(
-            StrategyTemplate::LiquidationBreakdownShort,
-            "BTCUSDT".to_string(),
-            BinanceMode::Demo,
-            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
-            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
-            PathBuf::from("/tmp/test.duckdb"),
-            BacktestDatasetSummary {
+            BacktestRun {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: "BTCUSDT".to_string(),
                 mode: BinanceMode::Demo,
-                symbol: "BTCUSDT".to_string(),
-                symbol_found: true,
-                from: "2026-03-13".to_string(),
-                to: "2026-03-13".to_string(),
-                liquidation_events: 1,
-                book_ticker_events: 3,
-                agg_trade_events: 0,
-                derived_kline_1s_bars: 0,
+                from: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                to: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                db_path: PathBuf::from("/tmp/test.duckdb"),
+                dataset: BacktestDatasetSummary {
+                    mode: BinanceMode::Demo,
+                    symbol: "BTCUSDT".to_string(),
+                    symbol_found: true,
+                    from: "2026-03-13".to_string(),
+                    to: "2026-03-13".to_string(),
+                    liquidation_events: 1,
+                    book_ticker_events: 3,
+                    agg_trade_events: 0,
+                    derived_kline_1s_bars: 0,
+                },
+                config: BacktestConfig::default(),
             },
             vec![LiquidationEventRow {
                 event_time_ms: 1_000,
@@ -1134,7 +1658,6 @@ mod tests {
                     ask: 101.2,
                 },
             ],
-            BacktestConfig::default(),
         );
 
         assert_eq!(report.wins, 0);
@@ -1164,30 +1687,33 @@ mod tests {
             });
         }
 
-        let report = run_price_sma_cross_on_klines(
-            StrategyTemplate::PriceSmaCrossLong,
-            PriceCrossDirection::Long,
-            20,
-            50,
-            "BTCUSDT".to_string(),
-            BinanceMode::Demo,
-            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
-            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
-            PathBuf::from("/tmp/test.duckdb"),
-            BacktestDatasetSummary {
+        let report = run_bar_strategy_on_klines(
+            BacktestRun {
+                template: StrategyTemplate::PriceSmaCrossLong,
+                instrument: "BTCUSDT".to_string(),
                 mode: BinanceMode::Demo,
-                symbol: "BTCUSDT".to_string(),
-                symbol_found: true,
-                from: "2026-03-13".to_string(),
-                to: "2026-03-13".to_string(),
-                liquidation_events: 0,
-                book_ticker_events: 0,
-                agg_trade_events: 0,
-                derived_kline_1s_bars: 0,
+                from: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                to: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                db_path: PathBuf::from("/tmp/test.duckdb"),
+                dataset: BacktestDatasetSummary {
+                    mode: BinanceMode::Demo,
+                    symbol: "BTCUSDT".to_string(),
+                    symbol_found: true,
+                    from: "2026-03-13".to_string(),
+                    to: "2026-03-13".to_string(),
+                    liquidation_events: 0,
+                    book_ticker_events: 0,
+                    agg_trade_events: 0,
+                    derived_kline_1s_bars: 0,
+                },
+                config: BacktestConfig::default(),
             },
+            StrategyRegistry::builtin()
+                .create(StrategyTemplate::PriceSmaCrossLong)
+                .unwrap(),
             klines,
-            BacktestConfig::default(),
-        );
+        )
+        .expect("bar backtest");
 
         assert!(report.trigger_count >= 1);
         assert!(!report.trades.is_empty());
@@ -1216,34 +1742,44 @@ mod tests {
             });
         }
 
-        let report = run_price_sma_cross_on_klines(
-            StrategyTemplate::PriceSmaCrossShort,
-            PriceCrossDirection::Short,
-            20,
-            50,
-            "BTCUSDT".to_string(),
-            BinanceMode::Demo,
-            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
-            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
-            PathBuf::from("/tmp/test.duckdb"),
-            BacktestDatasetSummary {
+        let report = run_bar_strategy_on_klines(
+            BacktestRun {
+                template: StrategyTemplate::PriceSmaCrossShort,
+                instrument: "BTCUSDT".to_string(),
                 mode: BinanceMode::Demo,
-                symbol: "BTCUSDT".to_string(),
-                symbol_found: true,
-                from: "2026-03-13".to_string(),
-                to: "2026-03-13".to_string(),
-                liquidation_events: 0,
-                book_ticker_events: 0,
-                agg_trade_events: 0,
-                derived_kline_1s_bars: 0,
+                from: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                to: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                db_path: PathBuf::from("/tmp/test.duckdb"),
+                dataset: BacktestDatasetSummary {
+                    mode: BinanceMode::Demo,
+                    symbol: "BTCUSDT".to_string(),
+                    symbol_found: true,
+                    from: "2026-03-13".to_string(),
+                    to: "2026-03-13".to_string(),
+                    liquidation_events: 0,
+                    book_ticker_events: 0,
+                    agg_trade_events: 0,
+                    derived_kline_1s_bars: 0,
+                },
+                config: BacktestConfig::default(),
             },
+            StrategyRegistry::builtin()
+                .create(StrategyTemplate::PriceSmaCrossShort)
+                .unwrap(),
             klines,
-            BacktestConfig::default(),
-        );
+        )
+        .expect("bar backtest");
 
         assert!(report.trigger_count >= 1);
         assert!(!report.trades.is_empty());
         assert!(report.net_pnl.is_finite());
+        for trade in &report.trades {
+            assert_eq!(trade.side, Side::Sell);
+            assert!(trade.stop_price > trade.entry_price);
+            if let (Some(exit_price), Some(gross_pnl)) = (trade.exit_price, trade.gross_pnl) {
+                assert!(((trade.entry_price - exit_price) * trade.qty - gross_pnl).abs() < 1e-9);
+            }
+        }
     }
 
     #[test]
@@ -1268,33 +1804,385 @@ mod tests {
             });
         }
 
-        let report = run_price_sma_cross_on_klines(
-            StrategyTemplate::PriceSmaCrossLongFast,
-            PriceCrossDirection::Long,
-            9,
-            21,
-            "BTCUSDT".to_string(),
-            BinanceMode::Demo,
-            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
-            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
-            PathBuf::from("/tmp/test.duckdb"),
-            BacktestDatasetSummary {
+        let report = run_bar_strategy_on_klines(
+            BacktestRun {
+                template: StrategyTemplate::PriceSmaCrossLongFast,
+                instrument: "BTCUSDT".to_string(),
                 mode: BinanceMode::Demo,
-                symbol: "BTCUSDT".to_string(),
-                symbol_found: true,
-                from: "2026-03-13".to_string(),
-                to: "2026-03-13".to_string(),
-                liquidation_events: 0,
-                book_ticker_events: 0,
-                agg_trade_events: 0,
-                derived_kline_1s_bars: 0,
+                from: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                to: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+                db_path: PathBuf::from("/tmp/test.duckdb"),
+                dataset: BacktestDatasetSummary {
+                    mode: BinanceMode::Demo,
+                    symbol: "BTCUSDT".to_string(),
+                    symbol_found: true,
+                    from: "2026-03-13".to_string(),
+                    to: "2026-03-13".to_string(),
+                    liquidation_events: 0,
+                    book_ticker_events: 0,
+                    agg_trade_events: 0,
+                    derived_kline_1s_bars: 0,
+                },
+                config: BacktestConfig::default(),
             },
+            StrategyRegistry::builtin()
+                .create(StrategyTemplate::PriceSmaCrossLongFast)
+                .unwrap(),
             klines,
-            BacktestConfig::default(),
-        );
+        )
+        .expect("bar backtest");
 
         assert!(report.trigger_count >= 1);
         assert!(!report.trades.is_empty());
         assert!(report.net_pnl.is_finite());
     }
+
+    #[test]
+    fn script_template_replays_the_builtin_sma_cross_it_spells_out() {
+        let klines = (0..90)
+            .map(|index| {
+                let close = match index {
+                    0..=24 => 100.0,
+                    25..=54 => 100.0 + (index - 24) as f64 * 0.8,
+                    _ => 124.0 - (index - 54) as f64 * 0.9,
+                };
+                DerivedKlineRow {
+                    open_time_ms: index as i64 * 60_000,
+                    close_time_ms: index as i64 * 60_000 + 59_000,
+                    open: close - 0.3,
+                    high: close + 0.4,
+                    low: close - 0.4,
+                    close,
+                    volume: 1000.0,
+                    quote_volume: close * 1000.0,
+                    trade_count: 100,
+                }
+            })
+            .collect::<Vec<_>>();
+        let script = std::env::temp_dir().join(format!(
+            "sandbox-quant-script-{}-{}.rhai",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::write(
+            &script,
+            "// price-sma-cross-long-fast\n\
+             fn warmup() { 22 }\n\
+             fn enter() { sma(9) > sma(21) && sma(9, 1) <= sma(21, 1) }\n\
+             fn exit() { sma(9) < sma(21) && sma(9, 1) >= sma(21, 1) }\n",
+        )
+        .expect("write script");
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap();
+        let run = |template, strategy_script: Option<PathBuf>| {
+            run_backtest_on_klines(
+                template,
+                "BTCUSDT",
+                BinanceMode::Demo,
+                date,
+                date,
+                PathBuf::from("var/BTCUSDT-1m.csv"),
+                klines.clone(),
+                BacktestConfig {
+                    strategy_script,
+                    ..BacktestConfig::default()
+                },
+            )
+        };
+
+        let builtin = run(StrategyTemplate::PriceSmaCrossLongFast, None).expect("builtin");
+        let scripted = run(StrategyTemplate::ScriptLong, Some(script.clone())).expect("script");
+        let missing = run(StrategyTemplate::ScriptLong, None);
+        let _ = std::fs::remove_file(&script);
+
+        assert!(!builtin.trades.is_empty());
+        assert_eq!(scripted.trades, builtin.trades);
+        assert_eq!(scripted.signals.len(), builtin.signals.len());
+        assert!(missing
+            .expect_err("script templates need a script file")
+            .to_string()
+            .contains("needs --script <path>"));
+    }
+
+    #[test]
+    fn plugin_templates_fail_the_run_when_the_plugin_traps() {
+        let klines = (0..30)
+            .map(|index| DerivedKlineRow {
+                open_time_ms: index * 60_000,
+                close_time_ms: index * 60_000 + 59_000,
+                open: 100.0,
+                high: 100.5,
+                low: 99.5,
+                close: 100.0,
+                volume: 1000.0,
+                quote_volume: 100_000.0,
+                trade_count: 100,
+            })
+            .collect::<Vec<_>>();
+        let plugin = std::env::temp_dir().join(format!(
+            "sandbox-quant-plugin-{}-{}.wasm",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::write(
+            &plugin,
+            wat::parse_str(
+                r#"(module
+                    (func (export "warmup_bars") (result i32) (i32.const 1))
+                    (func (export "reset"))
+                    (func (export "on_tick") (param f64) (result i32)
+                        (loop $spin (br $spin)) (i32.const 0)))"#,
+            )
+            .expect("valid wat"),
+        )
+        .expect("write plugin");
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap();
+
+        let error = run_backtest_on_klines(
+            StrategyTemplate::PluginLong,
+            "BTCUSDT",
+            BinanceMode::Demo,
+            date,
+            date,
+            PathBuf::from("var/BTCUSDT-1m.csv"),
+            klines,
+            BacktestConfig {
+                strategy_plugin: Some(plugin.clone()),
+                ..BacktestConfig::default()
+            },
+        )
+        .expect_err("a spinning plugin runs out of fuel");
+        let _ = std::fs::remove_file(&plugin);
+
+        assert!(error
+            .to_string()
+            .contains("strategy stopped: plugin trapped in on_tick"));
+    }
+
+    #[test]
+    fn htf_confirm_holds_back_crosses_against_a_flat_higher_timeframe() {
+        // Flat for 25 minutes, then a climb the fast SMA crosses up on.
+        let klines = (0..60)
+            .map(|index| {
+                let close = if index < 25 {
+                    100.0
+                } else {
+                    100.0 + (index - 24) as f64 * 0.8
+                };
+                DerivedKlineRow {
+                    open_time_ms: index as i64 * 60_000,
+                    close_time_ms: index as i64 * 60_000 + 59_000,
+                    open: close - 0.3,
+                    high: close + 1.5,
+                    low: close - 0.8,
+                    close,
+                    volume: 1000.0,
+                    quote_volume: close * 1000.0,
+                    trade_count: 100,
+                }
+            })
+            .collect::<Vec<_>>();
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap();
+        let run = |htf_confirm: Option<HtfConfirm>| {
+            run_backtest_on_klines(
+                StrategyTemplate::PriceSmaCrossLongFast,
+                "BTCUSDT",
+                BinanceMode::Demo,
+                date,
+                date,
+                PathBuf::from("var/BTCUSDT-1m.csv"),
+                klines.clone(),
+                BacktestConfig {
+                    htf_confirm,
+                    ..BacktestConfig::default()
+                },
+            )
+            .expect("backtest")
+        };
+
+        let unfiltered = run(None);
+        // The 5m SMA2 is still flat when the 1m cross fires.
+        let confirmed = run(HtfConfirm::parse("5m:sma2"));
+
+        assert!(!unfiltered.trades.is_empty());
+        assert!(confirmed.trades.is_empty());
+        assert_eq!(
+            (confirmed.signals[0].outcome, confirmed.signals[0].reason),
+            (SignalOutcome::Blocked, Some(SkipReason::HtfDisagrees))
+        );
+    }
+
+    #[test]
+    fn active_ev_gate_rejects_buy_entries_below_its_threshold() {
+        let klines = (0..60)
+            .map(|index| {
+                let close = if index < 25 {
+                    100.0
+                } else {
+                    100.0 + (index - 24) as f64 * 0.8
+                };
+                DerivedKlineRow {
+                    open_time_ms: index as i64 * 60_000,
+                    close_time_ms: index as i64 * 60_000 + 59_000,
+                    open: close - 0.3,
+                    high: close + 1.5,
+                    low: close - 0.8,
+                    close,
+                    volume: 1000.0,
+                    quote_volume: close * 1000.0,
+                    trade_count: 100,
+                }
+            })
+            .collect::<Vec<_>>();
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap();
+        let run = |gate: &str| {
+            run_backtest_on_klines(
+                StrategyTemplate::PriceSmaCrossLongFast,
+                "BTCUSDT",
+                BinanceMode::Demo,
+                date,
+                date,
+                PathBuf::from("var/BTCUSDT-1m.csv"),
+                klines.clone(),
+                BacktestConfig {
+                    ev_gate: EvGate::parse(gate).unwrap(),
+                    ..BacktestConfig::default()
+                },
+            )
+            .expect("backtest")
+        };
+
+        // 0.8 * 1.5 - 0.2 - 2 * 0.0005 / 0.012 = 0.9167R
+        let shadow = run("shadow:0.95");
+        let active = run("active:0.95");
+        let lenient = run("active:0.9");
+
+        let ev = shadow.signals[0].ev.expect("long entry snapshot");
+        assert!((ev.expected_value_r - 0.9167).abs() < 1e-4);
+        assert!(!ev.gate_blocked);
+        assert_eq!(ev.gate_label(), "below");
+        assert!(!shadow.trades.is_empty());
+        assert!(active.trades.is_empty());
+        assert_eq!(
+            (active.signals[0].outcome, active.signals[0].reason),
+            (SignalOutcome::Blocked, Some(SkipReason::LowExpectedValue))
+        );
+        assert!(active.signals[0].ev.is_some_and(|ev| ev.gate_blocked));
+        assert_eq!(lenient.trades.len(), shadow.trades.len());
+    }
+
+    #[test]
+    fn active_ev_gate_rejects_short_entries_below_its_threshold() {
+        let klines = (0..60)
+            .map(|index| {
+                let close = if index < 25 {
+                    100.0
+                } else {
+                    100.0 - (index - 24) as f64 * 0.8
+                };
+                DerivedKlineRow {
+                    open_time_ms: index as i64 * 60_000,
+                    close_time_ms: index as i64 * 60_000 + 59_000,
+                    open: close + 0.3,
+                    high: close + 0.8,
+                    low: close - 1.5,
+                    close,
+                    volume: 1000.0,
+                    quote_volume: close * 1000.0,
+                    trade_count: 100,
+                }
+            })
+            .collect::<Vec<_>>();
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap();
+        let run = |gate: &str| {
+            run_backtest_on_klines(
+                StrategyTemplate::PriceSmaCrossShortFast,
+                "BTCUSDT",
+                BinanceMode::Demo,
+                date,
+                date,
+                PathBuf::from("var/BTCUSDT-1m.csv"),
+                klines.clone(),
+                BacktestConfig {
+                    ev_gate: EvGate::parse(gate).unwrap(),
+                    ..BacktestConfig::default()
+                },
+            )
+            .expect("backtest")
+        };
+
+        let shadow = run("shadow:0.95");
+        let active = run("active:0.95");
+
+        let ev = shadow.signals[0].ev.expect("short entry snapshot");
+        assert!((ev.expected_value_r - 0.9167).abs() < 1e-4);
+        assert!(!shadow.trades.is_empty());
+        assert_eq!(shadow.trades[0].side, Side::Sell);
+        assert!(active.trades.is_empty());
+        assert_eq!(
+            (active.signals[0].outcome, active.signals[0].reason),
+            (SignalOutcome::Blocked, Some(SkipReason::LowExpectedValue))
+        );
+    }
+
+    #[test]
+    fn kline_backtest_applies_cost_overrides_and_rejects_liquidation_template() {
+        let klines = (0..80)
+            .map(|index| {
+                let close = if index < 55 {
+                    100.0
+                } else {
+                    100.0 + (index - 54) as f64
+                };
+                DerivedKlineRow {
+                    open_time_ms: index as i64 * 60_000,
+                    close_time_ms: index as i64 * 60_000 + 59_000,
+                    open: close - 0.5,
+                    high: close + 2.0,
+                    low: close - 1.0,
+                    close,
+                    volume: 1000.0,
+                    quote_volume: close * 1000.0,
+                    trade_count: 100,
+                }
+            })
+            .collect::<Vec<_>>();
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap();
+        let run = |template, config| {
+            run_backtest_on_klines(
+                template,
+                "BTCUSDT",
+                BinanceMode::Demo,
+                date,
+                date,
+                PathBuf::from("var/BTCUSDT-1m.csv"),
+                klines.clone(),
+                config,
+            )
+        };
+
+        let default_fees = run(
+            StrategyTemplate::PriceSmaCrossLong,
+            BacktestConfig::default(),
+        )
+        .expect("default costs");
+        let free = run(
+            StrategyTemplate::PriceSmaCrossLong,
+            BacktestConfig::default().with_costs(&BacktestCostOverrides {
+                taker_fee_rate: Some(0.0),
+                ..BacktestCostOverrides::default()
+            }),
+        )
+        .expect("fee-free run");
+
+        assert_eq!(free.config.taker_fee_rate, 0.0);
+        assert_eq!(free.config.stop_slippage_pct, 0.0008);
+        assert_eq!(free.db_path, PathBuf::from("var/BTCUSDT-1m.csv"));
+        assert!(!free.trades.is_empty());
+        assert!(free.net_pnl > default_fees.net_pnl);
+        assert!(run(
+            StrategyTemplate::LiquidationBreakdownShort,
+            BacktestConfig::default()
+        )
+        .is_err());
+    }
 }
@@ -1,19 +1,30 @@
+use std::path::PathBuf;
+
 use crate::app::bootstrap::BinanceMode;
+use crate::backtest_app::compare::compare_backtests;
 use crate::backtest_app::export::maybe_export_report_to_postgres;
-use crate::backtest_app::runner::{run_backtest_for_path, BacktestConfig};
+use crate::backtest_app::klines::load_backtest_klines;
+use crate::backtest_app::monthly::{monthly_performance, risk_free_rate_pct_from_env};
+use crate::backtest_app::runner::{run_backtest_for_path, run_backtest_on_klines, BacktestConfig};
 use crate::backtest_app::snapshot::maybe_prepare_snapshot_from_postgres;
 use crate::command::backtest::{
     backtest_help_text, complete_backtest_input, parse_backtest_shell_input, BacktestCommand,
     BacktestShellInput,
 };
 use crate::dataset::query::{
-    load_backtest_report, load_backtest_run_summaries, persist_backtest_report,
+    load_backtest_report, load_backtest_run_summaries, load_exit_reason_breakdown,
+    persist_backtest_report,
 };
 use crate::dataset::schema::init_schema_for_path;
 use crate::record::coordination::RecorderCoordination;
+use crate::strategy::plugin::plugin_path;
 use crate::terminal::app::{TerminalApp, TerminalEvent, TerminalMode};
 use crate::terminal::completion::ShellCompletion;
-use crate::ui::backtest_output::{render_backtest_run, render_backtest_run_list};
+use crate::ui::backtest_output::{
+    render_backtest_comparison, render_backtest_monthly, render_backtest_run,
+    render_backtest_run_list, render_exit_reason_breakdown,
+};
+use crate::ui::timezone::DisplayTimeZone;
 
 pub struct BacktestTerminal {
     pub mode: BinanceMode,
@@ -67,15 +78,49 @@ impl TerminalApp for BacktestTerminal {
                     self.mode.as_str()
                 )))
             }
-            Ok(BacktestShellInput::Command(command)) => match command {
+            Ok(BacktestShellInput::Command(command)) => match *command {
                 BacktestCommand::Run {
                     template,
                     instrument,
                     from,
                     to,
+                    source,
+                    costs,
+                    script,
+                    plugin,
+                    overtrading,
                 } => {
                     let db_path =
                         RecorderCoordination::new(self.base_dir.clone()).db_path(self.mode);
+                    let defaults = BacktestConfig::default().with_costs(&costs);
+                    let config = BacktestConfig {
+                        strategy_script: script.map(PathBuf::from),
+                        strategy_plugin: plugin.as_deref().map(plugin_path),
+                        overtrading: overtrading.or(defaults.overtrading),
+                        ..defaults
+                    };
+                    if let Some((source, klines)) =
+                        load_backtest_klines(&source, self.mode, &instrument, from, to)
+                            .map_err(|error| error.to_string())?
+                    {
+                        init_schema_for_path(&db_path).map_err(|error| error.to_string())?;
+                        let mut report = run_backtest_on_klines(
+                            template,
+                            &instrument,
+                            self.mode,
+                            from,
+                            to,
+                            source,
+                            klines,
+                            config,
+                        )
+                        .map_err(|error| error.to_string())?;
+                        report.run_id = Some(
+                            persist_backtest_report(&db_path, &report)
+                                .map_err(|error| error.to_string())?,
+                        );
+                        return Ok(TerminalEvent::Output(render_backtest_run(&report)));
+                    }
                     if let Some(message) = maybe_prepare_snapshot_from_postgres(
                         self.mode,
                         &self.base_dir,
@@ -95,7 +140,7 @@ impl TerminalApp for BacktestTerminal {
                         &instrument,
                         from,
                         to,
-                        BacktestConfig::default(),
+                        config,
                     )
                     .map_err(|error| error.to_string())?;
                     let run_id = persist_backtest_report(&db_path, &report)
@@ -132,6 +177,48 @@ impl TerminalApp for BacktestTerminal {
                         ))
                     }
                 }
+                BacktestCommand::ReportMonthly { run_id } => {
+                    let db_path =
+                        RecorderCoordination::new(self.base_dir.clone()).db_path(self.mode);
+                    let report =
+                        load_backtest_report(&db_path, run_id).map_err(|error| error.to_string())?;
+                    if let Some(report) = report {
+                        let rate = risk_free_rate_pct_from_env();
+                        Ok(TerminalEvent::Output(render_backtest_monthly(
+                            &report,
+                            &monthly_performance(&report, rate, DisplayTimeZone::from_env()),
+                            rate,
+                        )))
+                    } else {
+                        Ok(TerminalEvent::Output(
+                            "backtest monthly report\nstate=missing".to_string(),
+                        ))
+                    }
+                }
+                BacktestCommand::ReportExits => {
+                    let db_path =
+                        RecorderCoordination::new(self.base_dir.clone()).db_path(self.mode);
+                    let rows =
+                        load_exit_reason_breakdown(&db_path).map_err(|error| error.to_string())?;
+                    Ok(TerminalEvent::Output(render_exit_reason_breakdown(&rows)))
+                }
+                BacktestCommand::Compare { run_a, run_b } => {
+                    let db_path =
+                        RecorderCoordination::new(self.base_dir.clone()).db_path(self.mode);
+                    let load = |run_id| {
+                        load_backtest_report(&db_path, Some(run_id))
+                            .map_err(|error| error.to_string())
+                    };
+                    match (load(run_a)?, load(run_b)?) {
+                        (Some(a), Some(b)) => Ok(TerminalEvent::Output(
+                            render_backtest_comparison(&compare_backtests(&a, &b)),
+                        )),
+                        (a, _) => Ok(TerminalEvent::Output(format!(
+                            "backtest comparison\nrun_id={}\nstate=missing",
+                            if a.is_none() { run_a } else { run_b }
+                        ))),
+                    }
+                }
                 BacktestCommand::ReportShow { run_id } => {
                     let db_path =
                         RecorderCoordination::new(self.base_dir.clone()).db_path(self.mode);
@@ -70,15 +70,17 @@ fn main() -> Result<()> {
             let inserted = backfill_pair(
                 &http,
                 &mut client,
-                &api_key,
-                mode,
-                &product,
-                &interval,
+                &BackfillTarget {
+                    api_key: &api_key,
+                    mode,
+                    product: &product,
+                    interval: &interval,
+                    fallback_start_ms,
+                    request_gap,
+                    rate_limit_sleep,
+                },
                 pair,
-                fallback_start_ms,
                 max_open_time_ms,
-                request_gap,
-                rate_limit_sleep,
             )?;
             cycle_rows += inserted;
             info!(
@@ -110,19 +112,33 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// The kline series and request pacing every pair in a cycle shares.
+struct BackfillTarget<'a> {
+    api_key: &'a str,
+    mode: BinanceMode,
+    product: &'a str,
+    interval: &'a str,
+    fallback_start_ms: i64,
+    request_gap: std::time::Duration,
+    rate_limit_sleep: std::time::Duration,
+}
+
 fn backfill_pair(
     http: &Client,
     client: &mut postgres::Client,
-    api_key: &str,
-    mode: BinanceMode,
-    product: &str,
-    interval: &str,
+    target: &BackfillTarget<'_>,
     pair: &str,
-    fallback_start_ms: i64,
     max_open_time_ms: i64,
-    request_gap: std::time::Duration,
-    rate_limit_sleep: std::time::Duration,
 ) -> Result<u64> {
+    let &BackfillTarget {
+        api_key,
+        mode,
+        product,
+        interval,
+        fallback_start_ms,
+        request_gap,
+        rate_limit_sleep,
+    } = target;
     let interval_ms = interval_millis(interval)?;
     let start_ms = latest_kline_open_time_ms(client, mode, product, pair, "1m")?
         .map(|value| value + interval_ms)
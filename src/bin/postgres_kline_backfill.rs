@@ -45,11 +45,13 @@ fn main() -> Result<()> {
             let inserted = backfill_symbol(
                 &http,
                 &mut client,
-                mode,
-                &product,
-                &interval,
+                &BackfillTarget {
+                    mode,
+                    product: &product,
+                    interval: &interval,
+                    fallback_start_ms,
+                },
                 symbol,
-                fallback_start_ms,
                 now_minute,
             )?;
             cycle_rows += inserted;
@@ -81,16 +83,27 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// The kline series every symbol in a cycle is backfilled into.
+struct BackfillTarget<'a> {
+    mode: BinanceMode,
+    product: &'a str,
+    interval: &'a str,
+    fallback_start_ms: i64,
+}
+
 fn backfill_symbol(
     http: &Client,
     client: &mut postgres::Client,
-    mode: BinanceMode,
-    product: &str,
-    interval: &str,
+    target: &BackfillTarget<'_>,
     symbol: &str,
-    fallback_start_ms: i64,
     max_open_time_ms: i64,
 ) -> Result<u64> {
+    let &BackfillTarget {
+        mode,
+        product,
+        interval,
+        fallback_start_ms,
+    } = target;
     let mut start_ms = latest_kline_open_time_ms(client, mode, product, symbol, interval)?
         .unwrap_or(fallback_start_ms);
     if start_ms < fallback_start_ms {
@@ -114,14 +114,16 @@ fn main() -> Result<()> {
                 let inserted = backfill_market(
                     &http,
                     &mut client,
-                    mode,
-                    exchange,
-                    &interval,
+                    &BackfillTarget {
+                        mode,
+                        exchange,
+                        interval: &interval,
+                        fallback_start_ms,
+                        request_gap,
+                        rate_limit_sleep,
+                    },
                     market,
-                    fallback_start_ms,
                     max_open_time_ms,
-                    request_gap,
-                    rate_limit_sleep,
                 )?;
                 cycle_rows += inserted;
                 info!(
@@ -175,18 +177,31 @@ fn resolve_market<'a>(markets: &'a [String], asset: &str) -> Option<&'a str> {
         .map(String::as_str)
 }
 
+/// The exchange series and request pacing every market in a cycle shares.
+struct BackfillTarget<'a> {
+    mode: BinanceMode,
+    exchange: &'a ExchangeSpec,
+    interval: &'a str,
+    fallback_start_ms: i64,
+    request_gap: StdDuration,
+    rate_limit_sleep: StdDuration,
+}
+
 fn backfill_market(
     http: &Client,
     client: &mut postgres::Client,
-    mode: BinanceMode,
-    exchange: &ExchangeSpec,
-    interval: &str,
+    target: &BackfillTarget<'_>,
     market: &str,
-    fallback_start_ms: i64,
     max_open_time_ms: i64,
-    request_gap: StdDuration,
-    rate_limit_sleep: StdDuration,
 ) -> Result<u64> {
+    let &BackfillTarget {
+        mode,
+        exchange,
+        interval,
+        fallback_start_ms,
+        request_gap,
+        rate_limit_sleep,
+    } = target;
     let interval_ms = interval_millis(interval)?;
     let start_ms = latest_kline_open_time_ms(client, mode, exchange.product, market, interval)?
         .map(|value| value + interval_ms)
@@ -1,20 +1,29 @@
 use sandbox_quant::app::bootstrap::BinanceMode;
+use sandbox_quant::backtest_app::compare::compare_backtests;
 use sandbox_quant::backtest_app::export::{export_report_to_postgres, maybe_export_report_to_postgres};
+use sandbox_quant::backtest_app::monthly::{monthly_performance, risk_free_rate_pct_from_env};
+use sandbox_quant::backtest_app::klines::load_backtest_klines;
 use sandbox_quant::backtest_app::runner::{
-    run_backtest_for_path, run_backtest_for_postgres_url, BacktestConfig,
+    run_backtest_for_path, run_backtest_for_postgres_url, run_backtest_on_klines, BacktestConfig,
 };
 use sandbox_quant::backtest_app::snapshot::maybe_prepare_snapshot_from_postgres;
 use sandbox_quant::backtest_app::terminal::BacktestTerminal;
 use sandbox_quant::command::backtest::{parse_backtest_command, BacktestCommand, BacktestSweepWindow};
 use sandbox_quant::dataset::query::{
-    load_backtest_report, load_backtest_run_summaries, persist_backtest_report,
+    load_backtest_report, load_backtest_run_summaries, load_exit_reason_breakdown,
+    persist_backtest_report,
 };
 use sandbox_quant::dataset::schema::init_schema_for_path;
 use sandbox_quant::observability::logging::init_logging;
 use sandbox_quant::record::coordination::RecorderCoordination;
 use sandbox_quant::storage::postgres_market_data::postgres_url_from_env;
+use sandbox_quant::strategy::plugin::plugin_path;
 use sandbox_quant::terminal::loop_shell::run_terminal;
-use sandbox_quant::ui::backtest_output::{render_backtest_run, render_backtest_run_list};
+use sandbox_quant::ui::backtest_output::{
+    render_backtest_comparison, render_backtest_monthly, render_backtest_run,
+    render_backtest_run_list, render_exit_reason_breakdown,
+};
+use sandbox_quant::ui::timezone::DisplayTimeZone;
 use tracing::{error, info, warn};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -32,7 +41,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         run_terminal(&mut terminal)
     } else {
         match args.first().map(String::as_str) {
-            Some("run") | Some("sweep") | Some("list") | Some("report") => {
+            Some("run") | Some("sweep") | Some("list") | Some("report") | Some("compare") => {
                 run_backtest_command(&args)
             }
             Some("--mode") | Some("--base-dir") => {
@@ -42,7 +51,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
             _ => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                "usage: sandbox-quant-backtest run <template> <instrument> --from <YYYY-MM-DD> --to <YYYY-MM-DD> [--mode <demo|real>] [--base-dir <path>]\n       sandbox-quant-backtest sweep --templates <csv> --instruments <csv> --windows <from:to,...> [--output-dir <path>] [--mode <demo|real>] [--base-dir <path>]\n       sandbox-quant-backtest list [--mode <demo|real>] [--base-dir <path>]\n       sandbox-quant-backtest report latest|show <run_id> [--mode <demo|real>] [--base-dir <path>]",
+                "usage: sandbox-quant-backtest run <template> <instrument> --from <YYYY-MM-DD> --to <YYYY-MM-DD> [--klines <path|rest>] [--taker-fee <rate>] [--max-entry-slippage <pct>] [--stop-slippage <pct>] [--tp-slippage <pct>] [--script <path>] [--mode <demo|real>] [--base-dir <path>]\n       sandbox-quant-backtest sweep --templates <csv> --instruments <csv> --windows <from:to,...> [--output-dir <path>] [--mode <demo|real>] [--base-dir <path>]\n       sandbox-quant-backtest list [--mode <demo|real>] [--base-dir <path>]\n       sandbox-quant-backtest report latest|show <run_id>|monthly [run_id] [--mode <demo|real>] [--base-dir <path>]\n       sandbox-quant-backtest compare <run_id_a> <run_id_b> [--mode <demo|real>] [--base-dir <path>]",
             )
             .into()),
         }
@@ -93,7 +102,27 @@ fn run_backtest_command(args: &[String]) -> Result<(), Box<dyn std::error::Error
             instrument,
             from,
             to,
+            source,
+            costs,
+            script,
+            plugin,
+            overtrading,
         } => {
+            let defaults = BacktestConfig::default().with_costs(&costs);
+            let config = BacktestConfig {
+                strategy_script: script.map(std::path::PathBuf::from),
+                strategy_plugin: plugin.as_deref().map(plugin_path),
+                overtrading: overtrading.or(defaults.overtrading),
+                ..defaults
+            };
+            if let Some((source, klines)) = load_backtest_klines(&source, mode, &instrument, from, to)? {
+                init_schema_for_path(&db_path)?;
+                let mut report =
+                    run_backtest_on_klines(template, &instrument, mode, from, to, source, klines, config)?;
+                report.run_id = Some(persist_backtest_report(&db_path, &report)?);
+                println!("{}", render_backtest_run(&report));
+                return Ok(());
+            }
             if backtest_source_is_postgres() {
                 let postgres_url = postgres_url_from_env()?;
                 let report = run_backtest_for_postgres_url(
@@ -103,7 +132,7 @@ fn run_backtest_command(args: &[String]) -> Result<(), Box<dyn std::error::Error
                     &instrument,
                     from,
                     to,
-                    BacktestConfig::default(),
+                    config,
                 )?;
                 let export_run_id = maybe_export_report_to_postgres(&report)?
                     .ok_or("PostgreSQL direct mode expected PostgreSQL export to succeed")?;
@@ -131,7 +160,7 @@ fn run_backtest_command(args: &[String]) -> Result<(), Box<dyn std::error::Error
                 &instrument,
                 from,
                 to,
-                BacktestConfig::default(),
+                config,
             )?;
             let run_id = persist_backtest_report(&db_path, &report)?;
             let mut report = report;
@@ -179,6 +208,37 @@ fn run_backtest_command(args: &[String]) -> Result<(), Box<dyn std::error::Error
                 println!("backtest report\nstate=missing");
             }
         }
+        BacktestCommand::ReportMonthly { run_id } => {
+            if let Some(report) = load_backtest_report(&db_path, run_id)? {
+                let rate = risk_free_rate_pct_from_env();
+                println!(
+                    "{}",
+                    render_backtest_monthly(&report, &monthly_performance(&report, rate, DisplayTimeZone::from_env()), rate)
+                );
+            } else {
+                println!("backtest monthly report\nstate=missing");
+            }
+        }
+        BacktestCommand::ReportExits => {
+            println!(
+                "{}",
+                render_exit_reason_breakdown(&load_exit_reason_breakdown(&db_path)?)
+            );
+        }
+        BacktestCommand::Compare { run_a, run_b } => {
+            match (
+                load_backtest_report(&db_path, Some(run_a))?,
+                load_backtest_report(&db_path, Some(run_b))?,
+            ) {
+                (Some(a), Some(b)) => {
+                    println!("{}", render_backtest_comparison(&compare_backtests(&a, &b)))
+                }
+                (a, _) => println!(
+                    "backtest comparison\nrun_id={}\nstate=missing",
+                    if a.is_none() { run_a } else { run_b }
+                ),
+            }
+        }
         BacktestCommand::ReportShow { run_id } => {
             if let Some(report) = load_backtest_report(&db_path, Some(run_id))? {
                 println!("{}", render_backtest_run(&report));
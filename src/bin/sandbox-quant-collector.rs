@@ -294,12 +294,9 @@ fn parse_import_args(args: &[String]) -> Result<BatchImportConfig, Box<dyn std::
     })
 }
 
-fn parse_summary_args(
-    args: &[String],
-) -> Result<
-    (BinanceMode, String, CollectorStorageBackend, Option<String>),
-    Box<dyn std::error::Error>,
-> {
+type SummaryArgs = (BinanceMode, String, CollectorStorageBackend, Option<String>);
+
+fn parse_summary_args(args: &[String]) -> Result<SummaryArgs, Box<dyn std::error::Error>> {
     let mut mode = BinanceMode::Demo;
     let mut base_dir = "var".to_string();
     let mut storage_backend = CollectorStorageBackend::DuckDb;
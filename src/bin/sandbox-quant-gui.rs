@@ -20,6 +20,7 @@ struct GuiCliConfig {
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = parse_launch_config(&std::env::args().skip(1).collect::<Vec<_>>())?;
+    sandbox_quant::storage::sealed::process_sealer()?;
     if let Some(export_dir) = &config.headless_debug_export_dir {
         export_headless_debug(&config.launch, export_dir)?;
         return Ok(());
@@ -31,7 +32,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     eframe::run_native(
         "Sandbox Quant GUI",
         native_options,
-        Box::new(move |_cc| Ok(Box::new(SandboxQuantGuiApp::new(config.launch)))),
+        Box::new(move |cc| {
+            let app = SandboxQuantGuiApp::new(config.launch);
+            app.install_theme(&cc.egui_ctx);
+            Ok(Box::new(app))
+        }),
     )?;
     Ok(())
 }
@@ -15,8 +15,9 @@ use chrono::Utc;
 use reqwest::blocking::Client;
 use sandbox_quant::app::bootstrap::BinanceMode;
 use sandbox_quant::app::cli::normalize_instrument_symbol;
+use sandbox_quant::domain::market::Market;
 use sandbox_quant::market_data::binance_kline_backfill::{
-    parse_start_time, run_binance_kline_backfill, BinanceKlineBackfillConfig,
+    parse_start_time, run_binance_kline_backfill, BinanceKlineBackfillConfig, KlineEndpoint,
     DEFAULT_BINANCE_BACKFILL_INTERVAL, DEFAULT_BINANCE_BACKFILL_PRODUCT,
     DEFAULT_BINANCE_BACKFILL_START_DATE,
 };
@@ -287,10 +288,18 @@ fn spawn_backfill_worker(
 ) -> Result<BackfillWorker, Box<dyn std::error::Error>> {
     let stop_flag = Arc::new(AtomicBool::new(false));
     let worker_stop_flag = stop_flag.clone();
+    // The `um` product is USD-M futures; a configured host replaces the
+    // mode's own venue.
+    let endpoint = KlineEndpoint::for_market(RECORDER_RUNTIME_MODE, Market::Futures);
+    let endpoint = match std::env::var("SANDBOX_QUANT_BACKFILL_REST_URL") {
+        Ok(base_url) if !base_url.trim().is_empty() => endpoint.with_base_url(&base_url),
+        _ => endpoint,
+    };
     let backfill_config = BinanceKlineBackfillConfig {
         postgres_url: std::env::var("SANDBOX_QUANT_POSTGRES_URL")?,
         symbols: config.symbols.clone(),
         mode: RECORDER_RUNTIME_MODE,
+        endpoint,
         product: DEFAULT_BINANCE_BACKFILL_PRODUCT.to_string(),
         interval: DEFAULT_BINANCE_BACKFILL_INTERVAL.to_string(),
         fallback_start_ms: parse_start_time(DEFAULT_BINANCE_BACKFILL_START_DATE)?,
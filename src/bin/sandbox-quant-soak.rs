@@ -0,0 +1,716 @@
+use std::collections::BTreeMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use chrono::{NaiveDate, Utc};
+use sandbox_quant::app::bootstrap::AppBootstrap;
+use sandbox_quant::app::commands::AppCommand;
+use sandbox_quant::app::runtime::AppRuntime;
+use sandbox_quant::dataset::query::load_raw_kline_rows_for_path;
+use sandbox_quant::domain::balance::BalanceSnapshot;
+use sandbox_quant::domain::exposure::Exposure;
+use sandbox_quant::domain::instrument::Instrument;
+use sandbox_quant::domain::market::Market;
+use sandbox_quant::domain::order_type::OrderType;
+use sandbox_quant::domain::position::{PositionSnapshot, Side};
+use sandbox_quant::exchange::fake::FakeExchange;
+use sandbox_quant::exchange::symbol_rules::SymbolRules;
+use sandbox_quant::exchange::types::AuthoritativeSnapshot;
+use sandbox_quant::execution::command::{CommandSource, ExecutionCommand};
+use sandbox_quant::observability::event_routing::{event_level, LogLevel};
+use sandbox_quant::portfolio::store::PortfolioStateStore;
+use serde::Serialize;
+
+const EXIT_STABLE: u8 = 0;
+const EXIT_UNSTABLE: u8 = 20;
+const EXIT_ERROR: u8 = 30;
+
+const STARTING_USDT: f64 = 100_000.0;
+const TARGETS: [f64; 7] = [-0.3, -0.2, -0.1, 0.0, 0.1, 0.2, 0.3];
+/// Only the first violations are kept in the report; the total is always counted.
+const MAX_REPORTED_VIOLATIONS: usize = 20;
+const RSS_SAMPLE_EVERY_TICKS: u64 = 1_000;
+
+fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    match run() {
+        Ok(code) => ExitCode::from(code),
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn run() -> Result<u8, Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("run") => {
+            let config = SoakConfig::parse(&args[1..])?;
+            // Hours of simulated trading should not land in the operator's event file.
+            if std::env::var_os("SANDBOX_QUANT_EVENT_LOG_PATH").is_none() {
+                std::env::set_var(
+                    "SANDBOX_QUANT_EVENT_LOG_PATH",
+                    std::env::temp_dir()
+                        .join(format!("sandbox-quant-soak-{}.jsonl", std::process::id())),
+                );
+            }
+            let feed = PriceFeed::from_config(&config)?;
+            let report = run_soak(&config, feed);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            Ok(if report.status == "stable" {
+                EXIT_STABLE
+            } else {
+                EXIT_UNSTABLE
+            })
+        }
+        _ => Err(
+            "usage: sandbox-quant-soak run [--sim-hours <hours>] [--tick-secs <secs>] [--symbols <csv>] [--seed <n>] [--command-every <ticks>] [--refresh-every <ticks>] [--stuck-after <ticks>] [--max-rss-growth-mb <mb>] [--max-wall-secs <secs>] [--klines-db <path> --from <YYYY-MM-DD> --to <YYYY-MM-DD>]"
+                .into(),
+        ),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SoakConfig {
+    sim_hours: f64,
+    tick_secs: u64,
+    symbols: Vec<String>,
+    seed: u64,
+    command_every: u64,
+    refresh_every: u64,
+    /// An open order or deferred execution older than this many ticks is stuck.
+    stuck_after: u64,
+    max_rss_growth_mb: f64,
+    max_wall_secs: Option<u64>,
+    /// Replays recorded 1m closes instead of a random walk.
+    klines: Option<(PathBuf, NaiveDate, NaiveDate)>,
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        Self {
+            sim_hours: 24.0,
+            tick_secs: 1,
+            symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+            seed: 7,
+            command_every: 60,
+            refresh_every: 5,
+            stuck_after: 600,
+            max_rss_growth_mb: 64.0,
+            max_wall_secs: None,
+            klines: None,
+        }
+    }
+}
+
+impl SoakConfig {
+    fn parse(args: &[String]) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config = Self::default();
+        let mut klines_db = None;
+        let mut from = None;
+        let mut to = None;
+
+        let mut index = 0usize;
+        while index < args.len() {
+            let flag = args[index].as_str();
+            let value = args
+                .get(index + 1)
+                .ok_or_else(|| format!("missing value for {flag}"))?;
+            match flag {
+                "--sim-hours" => config.sim_hours = value.parse()?,
+                "--tick-secs" => config.tick_secs = value.parse::<u64>()?.max(1),
+                "--symbols" => {
+                    config.symbols = value
+                        .split(',')
+                        .map(|symbol| symbol.trim().to_ascii_uppercase())
+                        .filter(|symbol| !symbol.is_empty())
+                        .collect()
+                }
+                "--seed" => config.seed = value.parse()?,
+                "--command-every" => config.command_every = value.parse::<u64>()?.max(1),
+                "--refresh-every" => config.refresh_every = value.parse::<u64>()?.max(1),
+                "--stuck-after" => config.stuck_after = value.parse()?,
+                "--max-rss-growth-mb" => config.max_rss_growth_mb = value.parse()?,
+                "--max-wall-secs" => config.max_wall_secs = Some(value.parse()?),
+                "--klines-db" => klines_db = Some(PathBuf::from(value)),
+                "--from" => from = Some(NaiveDate::parse_from_str(value, "%Y-%m-%d")?),
+                "--to" => to = Some(NaiveDate::parse_from_str(value, "%Y-%m-%d")?),
+                other => return Err(format!("unsupported arg: {other}").into()),
+            }
+            index += 2;
+        }
+
+        if config.symbols.is_empty() {
+            return Err("--symbols needs at least one symbol".into());
+        }
+        config.klines = match (klines_db, from, to) {
+            (Some(db), Some(from), Some(to)) => Some((db, from, to)),
+            (None, None, None) => None,
+            _ => return Err("--klines-db needs --from and --to".into()),
+        };
+        Ok(config)
+    }
+
+    fn ticks(&self) -> u64 {
+        (self.sim_hours.max(0.0) * 3_600.0 / self.tick_secs as f64) as u64
+    }
+}
+
+/// xorshift64*, so a seed reproduces the same run.
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform in `[-1, 1)`.
+    fn next_signed(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }
+}
+
+/// One price per symbol per tick.
+#[derive(Debug, Clone)]
+enum PriceFeed {
+    /// Random walk of about 5bps per tick.
+    Synthetic { prices: Vec<f64>, rng: Rng },
+    /// Recorded closes, replayed from the start once exhausted.
+    Recorded {
+        closes: Vec<Vec<f64>>,
+        cursor: usize,
+    },
+}
+
+impl PriceFeed {
+    fn from_config(config: &SoakConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let Some((db_path, from, to)) = &config.klines else {
+            return Ok(Self::Synthetic {
+                prices: vec![100.0; config.symbols.len()],
+                rng: Rng::new(config.seed),
+            });
+        };
+        let mut closes = Vec::new();
+        for symbol in &config.symbols {
+            let rows = load_raw_kline_rows_for_path(db_path, symbol, *from, *to)?
+                .map(|(_, rows)| rows)
+                .unwrap_or_default();
+            if rows.is_empty() {
+                return Err(format!("no recorded klines for {symbol} in {from}..{to}").into());
+            }
+            closes.push(rows.into_iter().map(|row| row.close).collect());
+        }
+        Ok(Self::Recorded { closes, cursor: 0 })
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Synthetic { .. } => "synthetic",
+            Self::Recorded { .. } => "recorded",
+        }
+    }
+
+    fn next(&mut self) -> Vec<f64> {
+        match self {
+            Self::Synthetic { prices, rng } => {
+                for price in prices.iter_mut() {
+                    *price = (*price * (1.0 + rng.next_signed() * 0.000_5)).max(0.01);
+                }
+                prices.clone()
+            }
+            Self::Recorded { closes, cursor } => {
+                let prices = closes
+                    .iter()
+                    .map(|series| series[*cursor % series.len()])
+                    .collect();
+                *cursor += 1;
+                prices
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Violation {
+    tick: u64,
+    kind: String,
+    detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SoakReport {
+    status: String,
+    checked_at: String,
+    feed: String,
+    symbols: Vec<String>,
+    seed: u64,
+    ticks: u64,
+    simulated_secs: u64,
+    wall_secs: f64,
+    ticks_per_sec: f64,
+    commands: u64,
+    command_errors: u64,
+    panics: u64,
+    fills: u64,
+    events: u64,
+    error_events: BTreeMap<String, u64>,
+    rss_baseline_mb: Option<f64>,
+    rss_peak_mb: Option<f64>,
+    rss_end_mb: Option<f64>,
+    violations_total: usize,
+    violations: Vec<Violation>,
+}
+
+/// Plays the exchange's side: fills every order at the last price and
+/// feeds the resulting positions back through the fake snapshot.
+struct SoakHarness {
+    config: SoakConfig,
+    instruments: Vec<Instrument>,
+    app: AppBootstrap<FakeExchange>,
+    runtime: AppRuntime,
+    /// What the fills add up to; the store must agree after every refresh.
+    ledger: BTreeMap<Instrument, PositionSnapshot>,
+    last_prices: BTreeMap<Instrument, f64>,
+    submits_seen: usize,
+    closes_seen: usize,
+    /// Tick each open order or the deferred queue was first seen non-empty.
+    open_since: BTreeMap<String, u64>,
+    deferred_since: Option<u64>,
+    rss_baseline: Option<f64>,
+    rss_peak: Option<f64>,
+    violations: Vec<Violation>,
+    violations_total: usize,
+    commands: u64,
+    command_errors: u64,
+    panics: u64,
+    fills: u64,
+    events: u64,
+    error_events: BTreeMap<String, u64>,
+}
+
+impl SoakHarness {
+    fn new(config: &SoakConfig) -> Self {
+        let instruments = config
+            .symbols
+            .iter()
+            .map(|symbol| Instrument::new(symbol.clone()))
+            .collect::<Vec<_>>();
+        let exchange = FakeExchange::new(AuthoritativeSnapshot {
+            balances: vec![usdt_balance()],
+            positions: vec![],
+            open_orders: vec![],
+        });
+        for instrument in &instruments {
+            exchange.set_symbol_rules(
+                instrument.clone(),
+                Market::Futures,
+                SymbolRules {
+                    min_qty: 0.001,
+                    max_qty: 1_000_000.0,
+                    step_size: 0.001,
+                    tick_size: 0.01,
+                },
+            );
+        }
+        Self {
+            config: config.clone(),
+            instruments,
+            app: AppBootstrap::new(exchange, PortfolioStateStore::default()),
+            runtime: AppRuntime::default(),
+            ledger: BTreeMap::new(),
+            last_prices: BTreeMap::new(),
+            submits_seen: 0,
+            closes_seen: 0,
+            open_since: BTreeMap::new(),
+            deferred_since: None,
+            rss_baseline: None,
+            rss_peak: None,
+            violations: Vec::new(),
+            violations_total: 0,
+            commands: 0,
+            command_errors: 0,
+            panics: 0,
+            fills: 0,
+            events: 0,
+            error_events: BTreeMap::new(),
+        }
+    }
+
+    fn violate(&mut self, tick: u64, kind: &str, detail: String) {
+        self.violations_total += 1;
+        if self.violations.len() < MAX_REPORTED_VIOLATIONS {
+            self.violations.push(Violation {
+                tick,
+                kind: kind.to_string(),
+                detail,
+            });
+        }
+    }
+
+    fn step(&mut self, tick: u64, command: AppCommand) {
+        self.commands += 1;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            self.runtime.run(&mut self.app, command.clone())
+        }));
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) => self.command_errors += 1,
+            Err(panic) => {
+                self.panics += 1;
+                let message = panic
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| panic.downcast_ref::<&str>().map(|text| text.to_string()))
+                    .unwrap_or_default();
+                self.violate(tick, "panic", format!("{command:?}: {message}"));
+            }
+        }
+        self.apply_fills();
+        self.drain_events();
+    }
+
+    fn set_prices(&mut self, prices: &[f64]) {
+        for (instrument, price) in self.instruments.iter().zip(prices) {
+            self.app
+                .exchange
+                .set_last_price(instrument.clone(), Market::Futures, *price);
+            self.last_prices.insert(instrument.clone(), *price);
+        }
+    }
+
+    fn apply_fills(&mut self) {
+        let submits = self.app.exchange.submit_requests();
+        let closes = self.app.exchange.close_requests();
+        let new_requests = submits[self.submits_seen..]
+            .iter()
+            .chain(&closes[self.closes_seen..])
+            .cloned()
+            .collect::<Vec<_>>();
+        self.submits_seen = submits.len();
+        self.closes_seen = closes.len();
+        if new_requests.is_empty() {
+            return;
+        }
+        for request in new_requests {
+            let price = self
+                .last_prices
+                .get(&request.instrument)
+                .copied()
+                .unwrap_or_default();
+            let delta = match request.side {
+                Side::Buy => request.qty,
+                Side::Sell => -request.qty,
+            };
+            let position =
+                self.ledger
+                    .entry(request.instrument.clone())
+                    .or_insert(PositionSnapshot {
+                        instrument: request.instrument.clone(),
+                        market: request.market,
+                        signed_qty: 0.0,
+                        entry_price: None,
+                    });
+            // Venues report quantities on a fixed grid, not accumulated float error.
+            let next_qty = ((position.signed_qty + delta) * 1e8).round() / 1e8;
+            position.entry_price = if next_qty.abs() <= f64::EPSILON {
+                None
+            } else if position.signed_qty * next_qty <= 0.0 {
+                Some(price)
+            } else if next_qty.abs() > position.signed_qty.abs() {
+                position.entry_price.map(|entry| {
+                    (entry * position.signed_qty.abs() + price * delta.abs()) / next_qty.abs()
+                })
+            } else {
+                position.entry_price
+            };
+            position.signed_qty = next_qty;
+            self.fills += 1;
+        }
+        self.ledger
+            .retain(|_, position| position.signed_qty.abs() > f64::EPSILON);
+        self.app.exchange.replace_snapshot(AuthoritativeSnapshot {
+            balances: vec![usdt_balance()],
+            positions: self.ledger.values().cloned().collect(),
+            open_orders: vec![],
+        });
+    }
+
+    /// Counts and drops what the runtime logged, so memory reflects the
+    /// pipeline rather than an ever-growing in-memory log.
+    fn drain_events(&mut self) {
+        for record in self.app.event_log.records.drain(..) {
+            self.events += 1;
+            if event_level(&record.kind) == LogLevel::Error {
+                *self.error_events.entry(record.kind).or_default() += 1;
+            }
+        }
+    }
+
+    fn check_invariants(&mut self, tick: u64) {
+        for instrument in self.instruments.clone() {
+            let expected = self
+                .ledger
+                .get(&instrument)
+                .map_or(0.0, |position| position.signed_qty);
+            let stored = self
+                .app
+                .portfolio_store
+                .snapshot
+                .positions
+                .get(&instrument)
+                .map_or(0.0, |position| position.signed_qty);
+            if (expected - stored).abs() > 1e-9 {
+                self.violate(
+                    tick,
+                    "position_mismatch",
+                    format!("{} filled={expected} stored={stored}", instrument.0),
+                );
+            }
+        }
+
+        let open = self
+            .app
+            .portfolio_store
+            .snapshot
+            .open_orders
+            .values()
+            .flatten()
+            .map(|order| order.client_order_id.clone())
+            .collect::<Vec<_>>();
+        self.open_since.retain(|id, _| open.contains(id));
+        for id in open {
+            let since = *self.open_since.entry(id.clone()).or_insert(tick);
+            if tick - since == self.config.stuck_after {
+                self.violate(
+                    tick,
+                    "stuck_order",
+                    format!("{id} open for {} ticks", tick - since),
+                );
+            }
+        }
+
+        if self.app.execution.deferred_executions.is_empty() {
+            self.deferred_since = None;
+        } else {
+            let since = *self.deferred_since.get_or_insert(tick);
+            if tick - since == self.config.stuck_after {
+                self.violate(
+                    tick,
+                    "stuck_deferred",
+                    format!(
+                        "{} deferred executions pending for {} ticks",
+                        self.app.execution.deferred_executions.len(),
+                        tick - since
+                    ),
+                );
+            }
+        }
+    }
+
+    fn sample_rss(&mut self, tick: u64, warmup_ticks: u64) {
+        let Some(rss) = rss_mb() else {
+            return;
+        };
+        if tick < warmup_ticks {
+            return;
+        }
+        let baseline = *self.rss_baseline.get_or_insert(rss);
+        self.rss_peak = Some(self.rss_peak.map_or(rss, |peak| peak.max(rss)));
+        if rss - baseline > self.config.max_rss_growth_mb {
+            self.violate(
+                tick,
+                "memory_growth",
+                format!("rss {rss:.1}MB grew {:.1}MB past warmup", rss - baseline),
+            );
+            // Report the growth once, then measure against the new level.
+            self.rss_baseline = Some(rss);
+        }
+    }
+}
+
+fn usdt_balance() -> BalanceSnapshot {
+    BalanceSnapshot {
+        asset: "USDT".to_string(),
+        free: STARTING_USDT,
+        locked: 0.0,
+    }
+}
+
+/// Resident set size from `/proc/self/statm`; `None` off Linux.
+fn rss_mb() -> Option<f64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages = statm.split_whitespace().nth(1)?.parse::<f64>().ok()?;
+    // SAFETY: `sysconf` only reads a system constant.
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    (page_size > 0).then(|| pages * page_size as f64 / (1024.0 * 1024.0))
+}
+
+/// Runs the runtime tick by tick over `feed`, rotating target exposures,
+/// then flattens and checks that every position closed.
+fn run_soak(config: &SoakConfig, mut feed: PriceFeed) -> SoakReport {
+    let started = Instant::now();
+    let ticks = config.ticks();
+    let warmup_ticks = ticks / 10;
+    let mut harness = SoakHarness::new(config);
+    let mut targets = Rng::new(config.seed ^ 0x5EED);
+    let mut ran = 0u64;
+
+    for tick in 0..ticks {
+        if config
+            .max_wall_secs
+            .is_some_and(|limit| started.elapsed().as_secs() >= limit)
+        {
+            break;
+        }
+        harness.set_prices(&feed.next());
+        if tick % config.command_every == 0 {
+            let instrument = harness.instruments
+                [targets.next_u64() as usize % harness.instruments.len()]
+            .clone();
+            let target = TARGETS[targets.next_u64() as usize % TARGETS.len()];
+            harness.step(
+                tick,
+                AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+                    instrument,
+                    target: Exposure::new(target).expect("target within bounds"),
+                    order_type: OrderType::Market,
+                    source: CommandSource::System,
+                }),
+            );
+        }
+        if tick % config.refresh_every == 0 {
+            harness.step(tick, AppCommand::RefreshAuthoritativeState);
+            harness.check_invariants(tick);
+        }
+        if tick % RSS_SAMPLE_EVERY_TICKS == 0 {
+            harness.sample_rss(tick, warmup_ticks);
+        }
+        ran = tick + 1;
+    }
+
+    harness.step(
+        ran,
+        AppCommand::Execution(ExecutionCommand::CloseAll {
+            source: CommandSource::System,
+        }),
+    );
+    harness.step(ran, AppCommand::RefreshAuthoritativeState);
+    harness.check_invariants(ran);
+    if let Some(position) = harness.ledger.values().next().cloned() {
+        harness.violate(
+            ran,
+            "not_flat_after_close_all",
+            format!(
+                "{} signed_qty={}",
+                position.instrument.0, position.signed_qty
+            ),
+        );
+    }
+    harness.sample_rss(ran.max(warmup_ticks), warmup_ticks);
+
+    let wall_secs = started.elapsed().as_secs_f64();
+    SoakReport {
+        status: if harness.violations_total == 0 {
+            "stable".to_string()
+        } else {
+            "unstable".to_string()
+        },
+        checked_at: Utc::now().to_rfc3339(),
+        feed: feed.label().to_string(),
+        symbols: config.symbols.clone(),
+        seed: config.seed,
+        ticks: ran,
+        simulated_secs: ran * config.tick_secs,
+        wall_secs,
+        ticks_per_sec: if wall_secs > 0.0 {
+            ran as f64 / wall_secs
+        } else {
+            0.0
+        },
+        commands: harness.commands,
+        command_errors: harness.command_errors,
+        panics: harness.panics,
+        fills: harness.fills,
+        events: harness.events,
+        error_events: harness.error_events,
+        rss_baseline_mb: harness.rss_baseline,
+        rss_peak_mb: harness.rss_peak,
+        rss_end_mb: rss_mb(),
+        violations_total: harness.violations_total,
+        violations: harness.violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(raw: &str) -> Vec<String> {
+        raw.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn config_parses_overrides_and_requires_a_full_kline_range() {
+        let config = SoakConfig::parse(&args(
+            "--sim-hours 2 --tick-secs 5 --symbols btcusdt --seed 3",
+        ))
+        .expect("config");
+        assert_eq!(config.symbols, vec!["BTCUSDT".to_string()]);
+        assert_eq!(config.ticks(), 1_440);
+        assert_eq!(config.seed, 3);
+        assert!(SoakConfig::parse(&args("--klines-db var/demo.duckdb")).is_err());
+        assert!(SoakConfig::parse(&args("--sim-hours")).is_err());
+    }
+
+    #[test]
+    fn short_synthetic_soak_fills_orders_and_stays_stable() {
+        let config = SoakConfig {
+            sim_hours: 0.5,
+            command_every: 30,
+            ..SoakConfig::default()
+        };
+        let feed = PriceFeed::from_config(&config).expect("synthetic feed");
+
+        let report = run_soak(&config, feed);
+
+        assert_eq!(report.status, "stable", "{:?}", report.violations);
+        assert_eq!(report.ticks, 1_800);
+        assert_eq!(report.panics, 0);
+        assert!(report.fills > 10);
+        assert!(report.events > 0);
+    }
+
+    #[test]
+    fn mismatched_positions_are_reported() {
+        let config = SoakConfig::default();
+        let mut harness = SoakHarness::new(&config);
+        harness.set_prices(&[100.0, 50.0]);
+        harness.step(0, AppCommand::RefreshAuthoritativeState);
+        harness.ledger.insert(
+            Instrument::new("BTCUSDT"),
+            PositionSnapshot {
+                instrument: Instrument::new("BTCUSDT"),
+                market: Market::Futures,
+                signed_qty: 1.0,
+                entry_price: Some(100.0),
+            },
+        );
+
+        harness.check_invariants(1);
+
+        assert_eq!(harness.violations_total, 1);
+        assert_eq!(harness.violations[0].kind, "position_mismatch");
+    }
+}
@@ -374,6 +374,12 @@ fn load_metrics(url: &str) -> Result<RecorderMetrics, StorageError> {
         book_ticker_events: query_count_for_table(&mut client, "raw_book_ticker")?,
         agg_trade_events: query_count_for_table(&mut client, "raw_agg_trades")?,
         derived_kline_1s_bars: 0,
+        trade_id_gaps: 0,
+        missed_trades: 0,
+        backfilled_trades: 0,
+        duplicate_ticks: 0,
+        reordered_ticks: 0,
+        late_ticks: 0,
         schema_version: query_schema_version(&mut client)?,
         last_liquidation_event_time: query_latest_timestamp_for_table(
             &mut client,
@@ -1,14 +1,20 @@
 use chrono::Datelike;
 
-use crate::backtest_app::runner::BacktestReport;
+use crate::backtest_app::runner::{BacktestReport, BacktestSignal, BacktestTrade, SignalOutcome};
 use crate::charting::scene::{
     Bar, BarSeries, Candle, CandleSeries, ChartScene, EpochMs, HoverModel, LinePoint, LineSeries,
-    Marker, MarkerSeries, MarkerShape, Pane, Series, TooltipModel, ValueFormatter, Viewport,
-    YAxisSpec,
+    Marker, MarkerSeries, MarkerShape, Pane, ProfileBlock, ProfileSeries, Series, TooltipModel,
+    ValueFormatter, Viewport, YAxisSpec,
 };
 use crate::charting::style::{ChartTheme, RgbColor};
+use crate::dataset::types::{DerivedKlineRow, LiquidationEventRow};
+use crate::market_data::candle_mode::CandleMode;
+use crate::ui::theme::UiTheme;
+use crate::ui::timezone::DisplayTimeZone;
+use crate::visualization::annotations::{lifecycle_annotations, AnnotationKind, ChartRetention};
+use crate::visualization::replay::{ReplayDecision, TradeReplay};
 use crate::visualization::service::VisualizationService;
-use crate::visualization::types::{DashboardSnapshot, SignalKind};
+use crate::visualization::types::{DashboardSnapshot, SignalKind, StrategyFocusSeries};
 
 const PRICE: RgbColor = RgbColor::new(120, 220, 180);
 const LIQ_BUY: RgbColor = RgbColor::new(255, 140, 90);
@@ -22,6 +28,25 @@ const EQUITY: RgbColor = RgbColor::new(120, 180, 255);
 const VOLUME_UP: RgbColor = RgbColor::new(70, 150, 110);
 const VOLUME_DOWN: RgbColor = RgbColor::new(160, 90, 90);
 const SECONDARY_LINE: RgbColor = RgbColor::new(255, 215, 90);
+const PRICE_ALERT: RgbColor = RgbColor::new(255, 120, 200);
+const REPLAY_SIGNAL: RgbColor = RgbColor::new(255, 170, 60);
+const OPEN_INTEREST: RgbColor = RgbColor::new(180, 140, 255);
+const VOLUME_PROFILE: RgbColor = RgbColor::new(90, 160, 200);
+const VOLUME_PROFILE_BUCKETS: usize = 24;
+const SMA_FAST: RgbColor = RgbColor::new(255, 215, 90);
+const SMA_SLOW: RgbColor = RgbColor::new(120, 180, 255);
+const SIGNAL_ORDERED: RgbColor = TAKE_PROFIT;
+const SIGNAL_BLOCKED: RgbColor = RgbColor::new(240, 200, 80);
+const SIGNAL_REJECTED: RgbColor = STOP_LOSS;
+const STOP_ARMED: RgbColor = RgbColor::new(255, 150, 150);
+const TARGET_ARMED: RgbColor = RgbColor::new(150, 235, 180);
+
+/// Optional context panes stacked under the market chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MarketContextPanes {
+    pub open_interest: bool,
+    pub volume_profile: bool,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MarketTimeframe {
@@ -146,10 +171,24 @@ pub fn market_scene_from_snapshot(snapshot: &DashboardSnapshot) -> ChartScene {
 pub fn market_scene_from_snapshot_with_timeframe(
     snapshot: &DashboardSnapshot,
     timeframe: MarketTimeframe,
+) -> ChartScene {
+    market_scene_from_snapshot_with_candle_mode(snapshot, timeframe, CandleMode::Standard)
+}
+
+/// The market chart with its bars redrawn in `candle_mode`, after they are
+/// aggregated to `timeframe`.
+///
+/// Example:
+/// - `Renko(Price(50.0))` on `1m` -> `50` USDT bricks built from 1m closes
+pub fn market_scene_from_snapshot_with_candle_mode(
+    snapshot: &DashboardSnapshot,
+    timeframe: MarketTimeframe,
+    candle_mode: CandleMode,
 ) -> ChartScene {
     build_market_scene(
         snapshot,
         timeframe,
+        candle_mode,
         MarketSeriesKind::Candles,
         Some(MarketSeriesKind::MidPrice),
         true,
@@ -162,16 +201,25 @@ pub fn market_scene_from_snapshot_with_overlay(
     primary: MarketSeriesKind,
     secondary: Option<MarketSeriesKind>,
 ) -> ChartScene {
-    build_market_scene(snapshot, timeframe, primary, secondary, false)
+    build_market_scene(
+        snapshot,
+        timeframe,
+        CandleMode::Standard,
+        primary,
+        secondary,
+        false,
+    )
 }
 
 fn build_market_scene(
     snapshot: &DashboardSnapshot,
     timeframe: MarketTimeframe,
+    candle_mode: CandleMode,
     primary: MarketSeriesKind,
     secondary: Option<MarketSeriesKind>,
     include_default_annotations: bool,
 ) -> ChartScene {
+    let time_zone = DisplayTimeZone::from_env();
     let effective_timeframe = snapshot
         .market_series
         .kline_interval
@@ -179,8 +227,15 @@ fn build_market_scene(
         .and_then(MarketTimeframe::from_interval_label)
         .filter(|source| source.rank() > timeframe.rank())
         .unwrap_or(timeframe);
-    let display_klines =
-        aggregate_klines_for_timeframe(&snapshot.market_series.klines, effective_timeframe);
+    let display_klines = candle_mode.apply(&aggregate_klines_for_timeframe(
+        &snapshot.market_series.klines,
+        effective_timeframe,
+        time_zone,
+    ));
+    let market_title = match candle_mode {
+        CandleMode::Standard => format!("Market ({})", effective_timeframe.label()),
+        mode => format!("Market ({}, {})", effective_timeframe.label(), mode.title()),
+    };
     let mut price_series = build_overlay_series(snapshot, &display_klines, primary, false);
     if let Some(secondary) = secondary.filter(|kind| *kind != primary) {
         price_series.extend(build_overlay_series(
@@ -192,7 +247,7 @@ fn build_market_scene(
     }
 
     if include_default_annotations {
-        let mut markers = liquidation_marker_series(snapshot);
+        let mut markers = liquidation_marker_series(&snapshot.market_series.liquidations);
         if let Some(report) = snapshot
             .selected_report
             .as_ref()
@@ -222,7 +277,7 @@ fn build_market_scene(
 
     let mut panes = vec![Pane {
         id: "market".to_string(),
-        title: Some(format!("Market ({})", effective_timeframe.label())),
+        title: Some(market_title),
         weight: 4,
         y_axis: usdt_axis(2, false),
         series: price_series,
@@ -264,6 +319,7 @@ fn build_market_scene(
             display_klines.len(),
         ),
         time_label_format: "%m-%d %H:%M".to_string(),
+        time_zone,
         theme: ChartTheme::default(),
         viewport: focused_market_viewport(snapshot),
         hover: Some(
@@ -277,6 +333,207 @@ fn build_market_scene(
     }
 }
 
+/// Draws armed price alert levels as flat lines across the market pane.
+///
+/// Example:
+/// - levels `[70000.0]` on a pane spanning `12:00..13:00`
+/// - adds `alert 70000.00` from `12:00` to `13:00` at `70000`
+pub fn add_price_alert_lines(scene: &mut ChartScene, levels: &[f64]) {
+    let Some(pane) = scene.panes.iter_mut().find(|pane| pane.id == "market") else {
+        return;
+    };
+    let mut start = i64::MAX;
+    let mut end = i64::MIN;
+    for series in &pane.series {
+        let times: Vec<i64> = match series {
+            Series::Candles(series) => series
+                .candles
+                .iter()
+                .flat_map(|candle| [candle.open_time_ms.as_i64(), candle.close_time_ms.as_i64()])
+                .collect(),
+            Series::Line(series) => series
+                .points
+                .iter()
+                .map(|point| point.time_ms.as_i64())
+                .collect(),
+            Series::Markers(series) => series
+                .markers
+                .iter()
+                .map(|marker| marker.time_ms.as_i64())
+                .collect(),
+            Series::Bars(series) => series
+                .bars
+                .iter()
+                .flat_map(|bar| [bar.open_time_ms.as_i64(), bar.close_time_ms.as_i64()])
+                .collect(),
+            Series::Profile(series) => series
+                .blocks
+                .iter()
+                .flat_map(|block| [block.open_time_ms.as_i64(), block.close_time_ms.as_i64()])
+                .collect(),
+        };
+        for time in times {
+            start = start.min(time);
+            end = end.max(time);
+        }
+    }
+    if start > end {
+        return;
+    }
+    for level in levels {
+        pane.series.push(Series::Line(LineSeries {
+            name: format!("alert {level:.2}"),
+            color: PRICE_ALERT,
+            width: 1,
+            points: vec![
+                LinePoint {
+                    time_ms: EpochMs::new(start),
+                    value: *level,
+                },
+                LinePoint {
+                    time_ms: EpochMs::new(end),
+                    value: *level,
+                },
+            ],
+        }));
+    }
+}
+
+/// Caps every marker series on the first pane to the newest
+/// `max_fill_markers` and draws the trades' lifecycle annotations there as
+/// a `lifecycle` marker series.
+///
+/// Example:
+/// - `max_fill_markers=2`, `signals` holds entries at `t1 t2 t3`
+/// - `signals` keeps `t2 t3`; `lifecycle` gets `stop armed #n` and
+///   `target armed #n` at each kept entry time
+pub fn apply_chart_retention(
+    scene: &mut ChartScene,
+    trades: &[BacktestTrade],
+    retention: &ChartRetention,
+) {
+    let Some(pane) = scene.panes.first_mut() else {
+        return;
+    };
+    for series in &mut pane.series {
+        if let Series::Markers(series) = series {
+            retain_newest_markers(&mut series.markers, retention.max_fill_markers);
+        }
+    }
+    let mut annotations = lifecycle_annotations(trades);
+    retention.retain_annotations(&mut annotations);
+    if annotations.is_empty() {
+        return;
+    }
+    pane.series.push(Series::Markers(MarkerSeries {
+        name: "lifecycle".to_string(),
+        markers: annotations
+            .into_iter()
+            .map(|annotation| Marker {
+                label: annotation.label,
+                time_ms: EpochMs::new(annotation.time_ms),
+                value: annotation.price,
+                color: match annotation.kind {
+                    AnnotationKind::StopArmed => STOP_ARMED,
+                    AnnotationKind::TargetArmed => TARGET_ARMED,
+                },
+                size: 5,
+                shape: MarkerShape::Circle,
+            })
+            .collect(),
+    }));
+}
+
+/// Drops the oldest markers past `max`, keeping the rest in their order.
+fn retain_newest_markers(markers: &mut Vec<Marker>, max: usize) {
+    let excess = markers.len().saturating_sub(max);
+    if excess == 0 {
+        return;
+    }
+    let mut oldest = (0..markers.len()).collect::<Vec<_>>();
+    oldest.sort_by_key(|index| markers[*index].time_ms.as_i64());
+    let mut dropped = vec![false; markers.len()];
+    for index in &oldest[..excess] {
+        dropped[*index] = true;
+    }
+    let mut index = 0;
+    markers.retain(|_| {
+        index += 1;
+        !dropped[index - 1]
+    });
+}
+
+/// Appends the enabled context panes below the market and volume panes.
+///
+/// Example:
+/// - `open_interest` on, 3 recorded samples -> an `open_interest` line pane
+/// - `volume_profile` on, klines over 2 UTC days -> one histogram per day
+///   in a `volume_profile` pane, each bar scaled to that day's busiest level
+pub fn add_market_context_panes(
+    scene: &mut ChartScene,
+    snapshot: &DashboardSnapshot,
+    panes: MarketContextPanes,
+) {
+    let series = &snapshot.market_series;
+    if panes.open_interest && !series.open_interest.is_empty() {
+        scene.panes.push(Pane {
+            id: "open_interest".to_string(),
+            title: Some("Open interest".to_string()),
+            weight: 1,
+            y_axis: compact_axis("OI", 1, false),
+            series: vec![Series::Line(LineSeries {
+                name: "open interest".to_string(),
+                color: OPEN_INTEREST,
+                width: 2,
+                points: series
+                    .open_interest
+                    .iter()
+                    .map(|row| LinePoint {
+                        time_ms: EpochMs::from(row.event_time_ms),
+                        value: row.open_interest,
+                    })
+                    .collect(),
+            })],
+        });
+    }
+    if panes.volume_profile && !series.klines.is_empty() {
+        let blocks =
+            VisualizationService::session_volume_profiles(&series.klines, VOLUME_PROFILE_BUCKETS)
+                .into_iter()
+                .flat_map(|profile| {
+                    let peak = profile
+                        .levels
+                        .iter()
+                        .map(|level| level.volume)
+                        .fold(0.0, f64::max);
+                    profile.levels.into_iter().map(move |level| ProfileBlock {
+                        open_time_ms: EpochMs::from(profile.session_start_ms),
+                        close_time_ms: EpochMs::from(profile.session_end_ms),
+                        price_low: level.price_low,
+                        price_high: level.price_high,
+                        value: level.volume,
+                        fill_ratio: if peak > f64::EPSILON {
+                            level.volume / peak
+                        } else {
+                            0.0
+                        },
+                    })
+                })
+                .collect();
+        scene.panes.push(Pane {
+            id: "volume_profile".to_string(),
+            title: Some("Session volume profile (UTC)".to_string()),
+            weight: 2,
+            y_axis: usdt_axis(2, false),
+            series: vec![Series::Profile(ProfileSeries {
+                name: "volume profile".to_string(),
+                color: VOLUME_PROFILE,
+                blocks,
+            })],
+        });
+    }
+}
+
 fn focused_market_viewport(snapshot: &DashboardSnapshot) -> Viewport {
     let Some(report) = snapshot
         .selected_report
@@ -319,6 +576,50 @@ fn focused_market_viewport(snapshot: &DashboardSnapshot) -> Viewport {
     }
 }
 
+/// Recolors a scene built by this adapter for `theme`.
+///
+/// The adapter paints in the dark palette; gain and loss colors move to the
+/// theme's `positive`/`negative` roles, so `deuteranopia-safe` turns green
+/// take-profits and up-volume blue and red stops and down-volume orange.
+pub fn apply_ui_theme(scene: &mut ChartScene, theme: UiTheme) {
+    let palette = theme.palette();
+    scene.theme = palette.chart_theme();
+    if theme == UiTheme::Dark {
+        return;
+    }
+    let recolor = |color: RgbColor| match color {
+        TAKE_PROFIT | VOLUME_UP => palette.positive,
+        STOP_LOSS | VOLUME_DOWN => palette.negative,
+        SIGNAL_BLOCKED | OPEN_AT_END => palette.warning,
+        other => other,
+    };
+    for series in scene
+        .panes
+        .iter_mut()
+        .flat_map(|pane| pane.series.iter_mut())
+    {
+        match series {
+            Series::Candles(series) => {
+                series.up_color = series.up_color.map(recolor);
+                series.down_color = series.down_color.map(recolor);
+            }
+            Series::Bars(series) => {
+                series.color = recolor(series.color);
+                for bar in &mut series.bars {
+                    bar.color = bar.color.map(recolor);
+                }
+            }
+            Series::Line(series) => series.color = recolor(series.color),
+            Series::Markers(series) => {
+                for marker in &mut series.markers {
+                    marker.color = recolor(marker.color);
+                }
+            }
+            Series::Profile(series) => series.color = recolor(series.color),
+        }
+    }
+}
+
 pub fn equity_scene_from_report(report: &BacktestReport) -> ChartScene {
     let mut points = VisualizationService::equity_curve(report.starting_equity, &report.trades)
         .into_iter()
@@ -344,6 +645,7 @@ pub fn equity_scene_from_report(report: &BacktestReport) -> ChartScene {
             report.net_pnl,
         ),
         time_label_format: "%m-%d %H:%M".to_string(),
+        time_zone: DisplayTimeZone::from_env(),
         theme: ChartTheme::default(),
         viewport: Viewport::default(),
         hover: Some(
@@ -368,6 +670,281 @@ pub fn equity_scene_from_report(report: &BacktestReport) -> ChartScene {
     }
 }
 
+/// Charts a strategy on the bars it evaluates, with that strategy's overlays.
+///
+/// Signals come from `report` only when it ran the same template on the
+/// same symbol. Besides trade markers, every entry signal gets a small dot
+/// colored by outcome: green ordered, amber blocked by a gate, red rejected.
+///
+/// Example:
+/// - `price-sma-cross-long-fast` over `5m` klines -> candles plus `SMA 9` / `SMA 21`
+/// - `liquidation-breakdown-short` -> `1s` candles plus liquidation markers
+pub fn strategy_focus_scene(
+    focus: &StrategyFocusSeries,
+    report: Option<&BacktestReport>,
+) -> ChartScene {
+    let interval = focus.interval.as_deref().unwrap_or("n/a");
+    let mut price_series = vec![Series::Candles(CandleSeries {
+        name: "candles".to_string(),
+        up_color: None,
+        down_color: None,
+        candles: focus
+            .klines
+            .iter()
+            .map(|row| Candle {
+                open_time_ms: EpochMs::from(row.open_time_ms),
+                close_time_ms: EpochMs::from(row.close_time_ms),
+                open: row.open,
+                high: row.high,
+                low: row.low,
+                close: row.close,
+            })
+            .collect(),
+    })];
+    if let Some((fast, slow)) = focus.template.sma_windows() {
+        for (window, color) in [(fast, SMA_FAST), (slow, SMA_SLOW)] {
+            price_series.push(Series::Line(LineSeries {
+                name: format!("SMA {window}"),
+                color,
+                width: 2,
+                points: sma_points(&focus.klines, window),
+            }));
+        }
+    }
+    let mut markers = liquidation_marker_series(&focus.liquidations);
+    let report = report
+        .filter(|report| report.template == focus.template && report.instrument == focus.symbol);
+    if let Some(report) = report.filter(|report| !report.signals.is_empty()) {
+        price_series.push(Series::Markers(MarkerSeries {
+            name: "signal outcomes".to_string(),
+            markers: signal_outcome_markers(&report.signals),
+        }));
+    }
+    if let Some(report) = report {
+        markers.extend(
+            VisualizationService::signal_markers(&report.trades)
+                .into_iter()
+                .map(|marker| Marker {
+                    label: marker.label,
+                    time_ms: EpochMs::from(marker.time_ms),
+                    value: marker.price,
+                    color: signal_color(marker.kind),
+                    size: 8,
+                    shape: MarkerShape::Cross,
+                }),
+        );
+    }
+    if !markers.is_empty() {
+        price_series.push(Series::Markers(MarkerSeries {
+            name: "signals".to_string(),
+            markers,
+        }));
+    }
+
+    let mut panes = vec![Pane {
+        id: "strategy".to_string(),
+        title: Some(format!("{} ({interval})", focus.template.slug())),
+        weight: 4,
+        y_axis: usdt_axis(2, false),
+        series: price_series,
+    }];
+    if !focus.klines.is_empty() {
+        panes.push(Pane {
+            id: "volume".to_string(),
+            title: Some(format!("Volume ({interval})")),
+            weight: 1,
+            y_axis: compact_axis("Volume", 1, true),
+            series: vec![Series::Bars(BarSeries {
+                name: "volume".to_string(),
+                color: VOLUME_UP,
+                bars: focus
+                    .klines
+                    .iter()
+                    .map(|row| Bar {
+                        open_time_ms: EpochMs::from(row.open_time_ms),
+                        close_time_ms: EpochMs::from(row.close_time_ms),
+                        value: row.volume,
+                        color: Some(if row.close >= row.open {
+                            VOLUME_UP
+                        } else {
+                            VOLUME_DOWN
+                        }),
+                    })
+                    .collect(),
+            })],
+        });
+    }
+
+    ChartScene {
+        title: format!(
+            "{} | {} | {} bars {}",
+            focus.symbol,
+            focus.template.slug(),
+            interval,
+            focus.klines.len(),
+        ),
+        time_label_format: "%m-%d %H:%M".to_string(),
+        time_zone: DisplayTimeZone::from_env(),
+        theme: ChartTheme::default(),
+        viewport: Viewport::default(),
+        hover: Some(
+            TooltipModel {
+                title: "Strategy".to_string(),
+                sections: Vec::new(),
+            }
+            .into(),
+        ),
+        panes,
+    }
+}
+
+/// Draws a trade replay up to its cursor.
+///
+/// The viewport spans the whole trade so stepping does not rescale the chart.
+///
+/// Example:
+/// - cursor on step `3/12` -> price line through steps `1..=3`
+/// - entry, stop and take-profit levels drawn across the trade
+/// - non-`hold` steps marked, current step circled
+pub fn replay_scene(replay: &TradeReplay) -> ChartScene {
+    let trade = &replay.trade;
+    let start = replay
+        .steps
+        .first()
+        .map(|step| step.time_ms)
+        .unwrap_or_default();
+    let end = replay
+        .steps
+        .last()
+        .map(|step| step.time_ms)
+        .unwrap_or_default()
+        .max(start + 1);
+    let level = |name: &str, color: RgbColor, value: f64| {
+        Series::Line(LineSeries {
+            name: format!("{name} {value:.2}"),
+            color,
+            width: 1,
+            points: vec![
+                LinePoint {
+                    time_ms: EpochMs::new(start),
+                    value,
+                },
+                LinePoint {
+                    time_ms: EpochMs::new(end),
+                    value,
+                },
+            ],
+        })
+    };
+    let visible = replay.visible_steps();
+    let mut markers = visible
+        .iter()
+        .filter(|step| step.decision != ReplayDecision::Hold)
+        .map(|step| Marker {
+            label: step.decision.as_str().to_string(),
+            time_ms: EpochMs::from(step.time_ms),
+            value: step.price,
+            color: replay_color(step.decision),
+            size: 8,
+            shape: MarkerShape::Cross,
+        })
+        .collect::<Vec<_>>();
+    let current = replay.current();
+    markers.push(Marker {
+        label: replay.describe_current(),
+        time_ms: EpochMs::from(current.time_ms),
+        value: current.price,
+        color: replay_color(current.decision),
+        size: 6,
+        shape: MarkerShape::Circle,
+    });
+    let padding = ((end - start) / 20).max(1_000);
+
+    ChartScene {
+        title: format!(
+            "Replay trade #{} | {}",
+            trade.trade_id,
+            replay.describe_current()
+        ),
+        time_label_format: "%m-%d %H:%M:%S".to_string(),
+        time_zone: DisplayTimeZone::from_env(),
+        theme: ChartTheme::default(),
+        viewport: Viewport {
+            x_range: Some((
+                EpochMs::new(start.saturating_sub(padding)),
+                EpochMs::new(end.saturating_add(padding)),
+            )),
+        },
+        hover: Some(
+            TooltipModel {
+                title: "Replay".to_string(),
+                sections: Vec::new(),
+            }
+            .into(),
+        ),
+        panes: vec![Pane {
+            id: "replay".to_string(),
+            title: Some(format!("Trade #{}", trade.trade_id)),
+            weight: 1,
+            y_axis: usdt_axis(2, false),
+            series: vec![
+                level("entry", ENTRY, trade.entry_price),
+                level("stop", STOP_LOSS, trade.stop_price),
+                level("take_profit", TAKE_PROFIT, trade.take_profit_price),
+                Series::Line(LineSeries {
+                    name: "price".to_string(),
+                    color: PRICE,
+                    width: 2,
+                    points: visible
+                        .iter()
+                        .map(|step| LinePoint {
+                            time_ms: EpochMs::from(step.time_ms),
+                            value: step.price,
+                        })
+                        .collect(),
+                }),
+                Series::Markers(MarkerSeries {
+                    name: "decisions".to_string(),
+                    markers,
+                }),
+            ],
+        }],
+    }
+}
+
+fn replay_color(decision: ReplayDecision) -> RgbColor {
+    match decision {
+        ReplayDecision::Signal => REPLAY_SIGNAL,
+        ReplayDecision::Enter => ENTRY,
+        ReplayDecision::Hold => PRICE,
+        ReplayDecision::TakeProfit => TAKE_PROFIT,
+        ReplayDecision::StopLoss => STOP_LOSS,
+        ReplayDecision::SignalExit => SIGNAL_EXIT,
+        ReplayDecision::OpenAtEnd => OPEN_AT_END,
+    }
+}
+
+fn signal_outcome_markers(signals: &[BacktestSignal]) -> Vec<Marker> {
+    signals
+        .iter()
+        .map(|signal| Marker {
+            label: match signal.reason {
+                Some(reason) => format!("{} {}", signal.outcome.as_str(), reason.as_str()),
+                None => signal.outcome.as_str().to_string(),
+            },
+            time_ms: EpochMs::from(signal.time_ms),
+            value: signal.price,
+            color: match signal.outcome {
+                SignalOutcome::Ordered => SIGNAL_ORDERED,
+                SignalOutcome::Blocked => SIGNAL_BLOCKED,
+                SignalOutcome::Rejected => SIGNAL_REJECTED,
+            },
+            size: 3,
+            shape: MarkerShape::Circle,
+        })
+        .collect()
+}
+
 fn signal_color(kind: SignalKind) -> RgbColor {
     match kind {
         SignalKind::Entry => ENTRY,
@@ -463,7 +1040,7 @@ fn build_overlay_series(
             }
         }
         MarketSeriesKind::Liquidations => {
-            let markers = liquidation_marker_series(snapshot);
+            let markers = liquidation_marker_series(&snapshot.market_series.liquidations);
             if markers.is_empty() {
                 Vec::new()
             } else {
@@ -481,10 +1058,8 @@ fn build_overlay_series(
     }
 }
 
-fn liquidation_marker_series(snapshot: &DashboardSnapshot) -> Vec<Marker> {
-    snapshot
-        .market_series
-        .liquidations
+fn liquidation_marker_series(liquidations: &[LiquidationEventRow]) -> Vec<Marker> {
+    liquidations
         .iter()
         .map(|row| Marker {
             label: row.force_side.clone(),
@@ -522,6 +1097,28 @@ fn ema_points(
         .collect()
 }
 
+/// Trailing mean of `window` closes, starting once the window is full.
+fn sma_points(klines: &[DerivedKlineRow], window: usize) -> Vec<LinePoint> {
+    if window == 0 {
+        return Vec::new();
+    }
+    let mut sum = 0.0;
+    klines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, row)| {
+            sum += row.close;
+            if index >= window {
+                sum -= klines[index - window].close;
+            }
+            (index + 1 >= window).then(|| LinePoint {
+                time_ms: EpochMs::from(row.close_time_ms),
+                value: sum / window as f64,
+            })
+        })
+        .collect()
+}
+
 fn vwap_points(display_klines: &[crate::dataset::types::DerivedKlineRow]) -> Vec<LinePoint> {
     let mut cumulative_quote = 0.0;
     let mut cumulative_volume = 0.0;
@@ -615,28 +1212,40 @@ fn sampled_mid_price_segments(
     segments
 }
 
+/// Buckets by the display zone's clock, so day, week and month candles
+/// open at local midnight.
 fn aggregate_klines_for_timeframe(
     klines: &[crate::dataset::types::DerivedKlineRow],
     timeframe: MarketTimeframe,
+    zone: DisplayTimeZone,
 ) -> Vec<crate::dataset::types::DerivedKlineRow> {
+    let minutes = |minutes: i64| move |ms| bucket_start_span(ms, minutes * 60_000, zone);
     match timeframe {
         MarketTimeframe::Tick1s => klines.to_vec(),
         MarketTimeframe::Minute1m => aggregate_klines(klines, bucket_start_minute),
-        MarketTimeframe::Minute3m => aggregate_klines(klines, |ms| bucket_start_minutes(ms, 3)),
-        MarketTimeframe::Minute5m => aggregate_klines(klines, |ms| bucket_start_minutes(ms, 5)),
-        MarketTimeframe::Minute15m => aggregate_klines(klines, |ms| bucket_start_minutes(ms, 15)),
-        MarketTimeframe::Minute30m => aggregate_klines(klines, |ms| bucket_start_minutes(ms, 30)),
-        MarketTimeframe::Hour1h => aggregate_klines(klines, |ms| bucket_start_hours(ms, 1)),
-        MarketTimeframe::Hour4h => aggregate_klines(klines, |ms| bucket_start_hours(ms, 4)),
-        MarketTimeframe::Week1w => aggregate_klines(klines, bucket_start_week),
-        MarketTimeframe::Day1d => aggregate_klines(klines, bucket_start_day),
-        MarketTimeframe::Month1mo => aggregate_klines(klines, bucket_start_month),
+        MarketTimeframe::Minute3m => aggregate_klines(klines, minutes(3)),
+        MarketTimeframe::Minute5m => aggregate_klines(klines, minutes(5)),
+        MarketTimeframe::Minute15m => aggregate_klines(klines, minutes(15)),
+        MarketTimeframe::Minute30m => aggregate_klines(klines, minutes(30)),
+        MarketTimeframe::Hour1h => aggregate_klines(klines, minutes(60)),
+        MarketTimeframe::Hour4h => aggregate_klines(klines, minutes(240)),
+        MarketTimeframe::Week1w => aggregate_klines(klines, |ms| {
+            bucket_start_date(ms, zone, |date| {
+                date.checked_sub_days(chrono::Days::new(u64::from(
+                    date.weekday().num_days_from_monday(),
+                )))
+            })
+        }),
+        MarketTimeframe::Day1d => aggregate_klines(klines, |ms| bucket_start_date(ms, zone, Some)),
+        MarketTimeframe::Month1mo => aggregate_klines(klines, |ms| {
+            bucket_start_date(ms, zone, |date| date.with_day(1))
+        }),
     }
 }
 
 fn aggregate_klines(
     klines: &[crate::dataset::types::DerivedKlineRow],
-    bucket_start: fn(i64) -> i64,
+    bucket_start: impl Fn(i64) -> i64,
 ) -> Vec<crate::dataset::types::DerivedKlineRow> {
     if klines.is_empty() {
         return Vec::new();
@@ -671,45 +1280,25 @@ fn bucket_start_minute(ms: i64) -> i64 {
     (ms / 60_000) * 60_000
 }
 
-fn bucket_start_minutes(ms: i64, minutes: i64) -> i64 {
-    let bucket_ms = minutes * 60_000;
-    (ms / bucket_ms) * bucket_ms
-}
-
-fn bucket_start_hours(ms: i64, hours: i64) -> i64 {
-    let bucket_ms = hours * 60 * 60_000;
-    (ms / bucket_ms) * bucket_ms
-}
-
-fn bucket_start_day(ms: i64) -> i64 {
-    chrono::DateTime::<chrono::Utc>::from_timestamp_millis(ms)
-        .and_then(|dt| {
-            dt.date_naive()
-                .and_hms_opt(0, 0, 0)
-                .map(|naive| naive.and_utc().timestamp_millis())
-        })
-        .unwrap_or(ms)
-}
-
-fn bucket_start_week(ms: i64) -> i64 {
-    chrono::DateTime::<chrono::Utc>::from_timestamp_millis(ms)
-        .and_then(|dt| {
-            let date = dt.date_naive();
-            let weekday_offset = i64::from(date.weekday().num_days_from_monday());
-            date.checked_sub_days(chrono::Days::new(weekday_offset as u64))
-                .and_then(|start| start.and_hms_opt(0, 0, 0))
-                .map(|naive| naive.and_utc().timestamp_millis())
-        })
-        .unwrap_or(ms)
+/// Fixed-width buckets aligned to the zone's clock, e.g. 4h candles from
+/// `00:00` in `+05:30`.
+fn bucket_start_span(ms: i64, span_ms: i64, zone: DisplayTimeZone) -> i64 {
+    let Some(at) = chrono::DateTime::<chrono::Utc>::from_timestamp_millis(ms) else {
+        return ms;
+    };
+    let offset_ms = i64::from(zone.offset_at(at).local_minus_utc()) * 1_000;
+    (ms + offset_ms).div_euclid(span_ms) * span_ms - offset_ms
 }
 
-fn bucket_start_month(ms: i64) -> i64 {
+/// Midnight in the zone of the day `first_day` picks from the bar's date.
+fn bucket_start_date(
+    ms: i64,
+    zone: DisplayTimeZone,
+    first_day: impl Fn(chrono::NaiveDate) -> Option<chrono::NaiveDate>,
+) -> i64 {
     chrono::DateTime::<chrono::Utc>::from_timestamp_millis(ms)
-        .and_then(|dt| {
-            chrono::NaiveDate::from_ymd_opt(dt.year(), dt.month(), 1)
-                .and_then(|date| date.and_hms_opt(0, 0, 0))
-                .map(|naive| naive.and_utc().timestamp_millis())
-        })
+        .and_then(|at| first_day(zone.date(at)))
+        .map(|date| zone.start_of_date(date).timestamp_millis())
         .unwrap_or(ms)
 }
 
@@ -1,6 +1,33 @@
+use std::time::Instant;
+
 use eframe::egui::{self, ColorImage, Response, TextureHandle, TextureOptions, Ui, Vec2};
 
-use crate::charting::scene::RenderedFrame;
+use crate::charting::frame_limiter::{FrameDecision, FrameLimiter};
+use crate::charting::render::{ChartRenderer, RenderError};
+use crate::charting::scene::{ChartScene, RenderRequest, RenderedFrame};
+use crate::charting::style::RgbColor;
+use crate::ui::theme::UiTheme;
+
+pub fn color32(color: RgbColor) -> egui::Color32 {
+    egui::Color32::from_rgb(color.r, color.g, color.b)
+}
+
+/// egui widget colors for `theme`, matched to its chart background.
+pub fn theme_visuals(theme: UiTheme) -> egui::Visuals {
+    let palette = theme.palette();
+    let mut visuals = match theme {
+        UiTheme::Light => egui::Visuals::light(),
+        _ => egui::Visuals::dark(),
+    };
+    if theme == UiTheme::HighContrast {
+        visuals.override_text_color = Some(color32(palette.text));
+        visuals.panel_fill = color32(palette.background);
+        visuals.window_fill = color32(palette.background);
+    }
+    visuals.error_fg_color = color32(palette.negative);
+    visuals.warn_fg_color = color32(palette.warning);
+    visuals
+}
 
 pub fn color_image(frame: &RenderedFrame) -> ColorImage {
     ColorImage::from_rgb(
@@ -12,11 +39,52 @@ pub fn color_image(frame: &RenderedFrame) -> ColorImage {
 #[derive(Default)]
 pub struct RetainedChartTexture {
     texture: Option<TextureHandle>,
+    /// Scene and request behind the current texture.
+    drawn: Option<(ChartScene, RenderRequest)>,
+    limiter: FrameLimiter,
 }
 
 impl RetainedChartTexture {
     pub fn clear(&mut self) {
         self.texture = None;
+        self.drawn = None;
+        self.limiter.reset();
+    }
+
+    /// Rasterises `scene` only when it differs from the drawn one and the
+    /// frame budget allows; otherwise the last texture stays up.
+    ///
+    /// A change held back by the budget schedules a repaint, so the chart
+    /// still catches up once the UI goes quiet.
+    pub fn render_limited(
+        &mut self,
+        ctx: &egui::Context,
+        id: &str,
+        renderer: &impl ChartRenderer,
+        scene: &ChartScene,
+        request: &RenderRequest,
+    ) -> Result<(), RenderError> {
+        let now = Instant::now();
+        let changed = self
+            .drawn
+            .as_ref()
+            .is_none_or(|(drawn_scene, drawn_request)| {
+                drawn_scene != scene || drawn_request != request
+            });
+        match self.limiter.decide(changed || self.texture.is_none(), now) {
+            FrameDecision::Skip if self.texture.is_some() => Ok(()),
+            FrameDecision::Defer(delay) if self.texture.is_some() => {
+                ctx.request_repaint_after(delay);
+                Ok(())
+            }
+            _ => {
+                let frame = renderer.render(scene, request)?;
+                self.update(ctx, id, &frame);
+                self.drawn = Some((scene.clone(), request.clone()));
+                self.limiter.record_draw(now);
+                Ok(())
+            }
+        }
     }
 
     pub fn update(&mut self, ctx: &egui::Context, id: &str, frame: &RenderedFrame) {
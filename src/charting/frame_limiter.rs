@@ -0,0 +1,80 @@
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_MAX_FPS: u32 = 30;
+
+/// Reads:
+/// - `SANDBOX_QUANT_GUI_MAX_FPS` (e.g. `30`; default `30`, `0` uncapped)
+pub fn max_fps_from_env() -> u32 {
+    std::env::var("SANDBOX_QUANT_GUI_MAX_FPS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .unwrap_or(DEFAULT_MAX_FPS)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameDecision {
+    /// Rasterise now.
+    Draw,
+    /// Nothing visible changed; keep showing the last frame.
+    Skip,
+    /// Changed, but the frame budget is spent; check again after the delay.
+    Defer(Duration),
+}
+
+/// Caps how often a chart is rasterised, independent of how often the UI
+/// loop runs or data arrives.
+///
+/// Example:
+/// - `max_fps=20`, last draw at `t=0ms`
+/// - changed at `t=30ms` -> `Defer(20ms)`, changed at `t=60ms` -> `Draw`
+/// - unchanged at any time -> `Skip`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameLimiter {
+    /// Zero when uncapped.
+    min_interval: Duration,
+    last_drawn_at: Option<Instant>,
+}
+
+impl Default for FrameLimiter {
+    fn default() -> Self {
+        Self::new(max_fps_from_env())
+    }
+}
+
+impl FrameLimiter {
+    pub fn new(max_fps: u32) -> Self {
+        Self {
+            min_interval: if max_fps == 0 {
+                Duration::ZERO
+            } else {
+                Duration::from_secs(1) / max_fps
+            },
+            last_drawn_at: None,
+        }
+    }
+
+    /// The first frame is always drawn.
+    pub fn decide(&self, changed: bool, now: Instant) -> FrameDecision {
+        let Some(last_drawn_at) = self.last_drawn_at else {
+            return FrameDecision::Draw;
+        };
+        if !changed {
+            return FrameDecision::Skip;
+        }
+        let elapsed = now.saturating_duration_since(last_drawn_at);
+        if elapsed >= self.min_interval {
+            FrameDecision::Draw
+        } else {
+            FrameDecision::Defer(self.min_interval - elapsed)
+        }
+    }
+
+    pub fn record_draw(&mut self, now: Instant) {
+        self.last_drawn_at = Some(now);
+    }
+
+    /// Forces the next frame to draw.
+    pub fn reset(&mut self) {
+        self.last_drawn_at = None;
+    }
+}
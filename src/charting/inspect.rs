@@ -1,8 +1,6 @@
-use chrono::{DateTime, Utc};
-
 use crate::charting::scene::{
     BarSeries, CandleSeries, ChartScene, Crosshair, EpochMs, HoverModel, LineSeries, MarkerSeries,
-    Pane, Series, TooltipModel, TooltipRow, TooltipSection, ValueFormatter,
+    Pane, ProfileSeries, Series, TooltipModel, TooltipRow, TooltipSection, ValueFormatter,
 };
 
 const OUTER_MARGIN: f32 = 12.0;
@@ -118,10 +116,15 @@ pub fn tooltip_for_time(scene: &ChartScene, pane: &Pane, time_ms: EpochMs) -> To
             Series::Bars(series) => append_bar_tooltip(sections.as_mut(), series, pane, time_ms),
             Series::Line(series) => append_line_tooltip(sections.as_mut(), series, pane, time_ms),
             Series::Markers(series) => append_marker_tooltip(sections.as_mut(), series, time_ms),
+            Series::Profile(series) => {
+                append_profile_tooltip(sections.as_mut(), series, pane, time_ms)
+            }
         }
     }
     TooltipModel {
-        title: format_time(time_ms, &scene.time_label_format),
+        title: scene
+            .time_zone
+            .format_ms(time_ms.as_i64(), &scene.time_label_format),
         sections,
     }
 }
@@ -298,6 +301,16 @@ fn pane_points(pane: &Pane) -> impl Iterator<Item = (EpochMs, f64)> + '_ {
             .iter()
             .map(|marker| (marker.time_ms, marker.value))
             .collect::<Vec<_>>(),
+        Series::Profile(series) => series
+            .blocks
+            .iter()
+            .flat_map(|block| {
+                [
+                    (block.open_time_ms, block.price_low),
+                    (block.close_time_ms, block.price_high),
+                ]
+            })
+            .collect::<Vec<_>>(),
     })
 }
 
@@ -379,6 +392,48 @@ fn append_bar_tooltip(
     });
 }
 
+fn append_profile_tooltip(
+    sections: &mut Vec<TooltipSection>,
+    series: &ProfileSeries,
+    pane: &Pane,
+    time_ms: EpochMs,
+) {
+    let session = series
+        .blocks
+        .iter()
+        .filter(|block| block.open_time_ms <= time_ms && time_ms < block.close_time_ms)
+        .collect::<Vec<_>>();
+    let Some(peak) = session
+        .iter()
+        .max_by(|left, right| left.value.total_cmp(&right.value))
+    else {
+        return;
+    };
+    sections.push(TooltipSection {
+        title: title_case(&series.name),
+        rows: vec![
+            TooltipRow {
+                label: "POC".to_string(),
+                value: format_value(
+                    (peak.price_low + peak.price_high) * 0.5,
+                    &pane.y_axis.formatter,
+                ),
+            },
+            TooltipRow {
+                label: "Volume".to_string(),
+                value: format_value(
+                    session.iter().map(|block| block.value).sum(),
+                    &ValueFormatter::Compact {
+                        decimals: 1,
+                        prefix: String::new(),
+                        suffix: String::new(),
+                    },
+                ),
+            },
+        ],
+    });
+}
+
 fn append_line_tooltip(
     sections: &mut Vec<TooltipSection>,
     series: &LineSeries,
@@ -475,6 +530,16 @@ fn nearest_series_time(
             max_x,
             target,
         ),
+        Series::Profile(series) => nearest_time_in_sorted(
+            &series
+                .blocks
+                .iter()
+                .map(|block| block.open_time_ms)
+                .collect::<Vec<_>>(),
+            min_x,
+            max_x,
+            target,
+        ),
     }
 }
 
@@ -529,12 +594,6 @@ fn interpolate_time(min: EpochMs, max: EpochMs, t: f32) -> EpochMs {
     EpochMs::new((min_i + span * f64::from(t)).round() as i64)
 }
 
-fn format_time(time_ms: EpochMs, fmt: &str) -> String {
-    DateTime::<Utc>::from_timestamp_millis(time_ms.as_i64())
-        .map(|value| value.format(fmt).to_string())
-        .unwrap_or_else(|| "-".to_string())
-}
-
 fn distance(left: EpochMs, right: EpochMs) -> u64 {
     left.as_i64().abs_diff(right.as_i64())
 }
@@ -571,6 +630,7 @@ mod tests {
         ChartScene, LinePoint, LineSeries, Pane, Series, Viewport, YAxisSpec,
     };
     use crate::charting::style::{ChartTheme, RgbColor};
+    use crate::ui::timezone::DisplayTimeZone;
 
     #[test]
     fn distance_handles_extreme_epoch_values() {
@@ -596,6 +656,7 @@ mod tests {
         let mut scene = ChartScene {
             title: "test".to_string(),
             time_label_format: "%H:%M:%S".to_string(),
+            time_zone: DisplayTimeZone::Utc,
             theme: ChartTheme::default(),
             viewport: Viewport::default(),
             hover: None,
@@ -1,3 +1,4 @@
+pub mod frame_limiter;
 pub mod render;
 pub mod scene;
 pub mod style;
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use plotters::coord::types::RangedCoordf64;
 use plotters::coord::Shift;
 use plotters::prelude::*;
@@ -7,13 +7,34 @@ use crate::charting::inspect::{format_value, visible_time_bounds};
 use crate::charting::render::{ChartRenderer, RenderError};
 use crate::charting::scene::{
     BarSeries, CandleSeries, ChartScene, EpochMs, LineSeries, MarkerSeries, MarkerShape, Pane,
-    RenderRequest, RenderedFrame, Series, YAxisSpec,
+    ProfileSeries, RenderRequest, RenderedFrame, Series, YAxisSpec,
 };
 use crate::charting::style::{ChartTheme, RgbColor};
+use crate::ui::timezone::DisplayTimeZone;
+
+/// How the time axis, footer and crosshair label their timestamps.
+#[derive(Clone, Copy)]
+struct TimeLabels<'a> {
+    format: &'a str,
+    zone: DisplayTimeZone,
+}
+
+impl<'a> TimeLabels<'a> {
+    fn of(scene: &'a ChartScene) -> Self {
+        Self {
+            format: &scene.time_label_format,
+            zone: scene.time_zone,
+        }
+    }
+
+    fn label(self, time_ms: i64) -> String {
+        self.zone.format_ms(time_ms, self.format)
+    }
+}
 
 struct CrosshairOverlay<'a> {
     origin_x: i64,
-    time_label_format: &'a str,
+    time_labels: TimeLabels<'a>,
     time_ms: EpochMs,
     value: Option<f64>,
     color: Option<RgbColor>,
@@ -118,7 +139,7 @@ fn render_pane<DB: DrawingBackend>(
         &mut chart,
         width_px,
         origin_x,
-        &scene.time_label_format,
+        TimeLabels::of(scene),
         scene.theme,
         &pane.y_axis,
         show_x_labels,
@@ -129,7 +150,7 @@ fn render_pane<DB: DrawingBackend>(
             width_px,
             origin_x,
             max_offset_x,
-            &scene.time_label_format,
+            TimeLabels::of(scene),
             scene.theme,
         )?;
     }
@@ -152,6 +173,7 @@ fn render_pane<DB: DrawingBackend>(
             )?,
             Series::Line(series) => draw_line(&mut chart, series, origin_x)?,
             Series::Markers(series) => draw_markers(&mut chart, series, origin_x)?,
+            Series::Profile(series) => draw_profile(&mut chart, series, origin_x)?,
         }
     }
     if let Some(crosshair) = scene
@@ -164,7 +186,7 @@ fn render_pane<DB: DrawingBackend>(
             pane,
             CrosshairOverlay {
                 origin_x,
-                time_label_format: &scene.time_label_format,
+                time_labels: TimeLabels::of(scene),
                 time_ms: crosshair.time_ms,
                 value: crosshair.value,
                 color: crosshair.color,
@@ -180,18 +202,13 @@ fn configure_mesh<DB: DrawingBackend>(
     chart: &mut ChartContext<'_, DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
     _width_px: u32,
     origin_x: i64,
-    time_label_format: &str,
+    time_labels: TimeLabels<'_>,
     theme: ChartTheme,
     y_axis: &YAxisSpec,
     _show_x_labels: bool,
 ) -> Result<(), RenderError> {
     let mut mesh = chart.configure_mesh();
-    let formatter = |value: &f64| {
-        format_epoch_ms(
-            origin_x.saturating_add(value.round() as i64),
-            time_label_format,
-        )
-    };
+    let formatter = |value: &f64| time_labels.label(origin_x.saturating_add(value.round() as i64));
     let y_formatter = |value: &f64| format_value(*value, &y_axis.formatter);
     mesh.bold_line_style(to_plotters(theme.grid).mix(0.5))
         .light_line_style(to_plotters(theme.grid).mix(0.2))
@@ -347,6 +364,31 @@ fn draw_markers<DB: DrawingBackend>(
     Ok(())
 }
 
+fn draw_profile<DB: DrawingBackend>(
+    chart: &mut ChartContext<'_, DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+    series: &ProfileSeries,
+    origin_x: i64,
+) -> Result<(), RenderError> {
+    let color = to_plotters(series.color);
+    chart
+        .draw_series(series.blocks.iter().map(|block| {
+            let left = block.open_time_ms.as_i64().saturating_sub(origin_x) as f64;
+            let width = (block.close_time_ms.as_i64() - block.open_time_ms.as_i64()) as f64;
+            Rectangle::new(
+                [
+                    (left, block.price_low),
+                    (
+                        left + width * block.fill_ratio.clamp(0.0, 1.0),
+                        block.price_high,
+                    ),
+                ],
+                color.mix(0.75).filled(),
+            )
+        }))
+        .map_err(plotters_err)?;
+    Ok(())
+}
+
 fn draw_crosshair<DB: DrawingBackend>(
     chart: &mut ChartContext<'_, DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
     pane: &Pane,
@@ -417,10 +459,9 @@ fn draw_crosshair<DB: DrawingBackend>(
     }
 
     if overlay_config.show_x_labels {
-        let time_text = format_epoch_ms(
-            overlay_config.origin_x.saturating_add(x.round() as i64),
-            overlay_config.time_label_format,
-        );
+        let time_text = overlay_config
+            .time_labels
+            .label(overlay_config.origin_x.saturating_add(x.round() as i64));
         let label_width = (time_text.len() as i32 * 8).max(80);
         let left =
             (x_pixel - label_width / 2).clamp(x_range.start + 4, x_range.end - label_width - 4);
@@ -455,7 +496,7 @@ fn draw_time_footer_labels<DB: DrawingBackend>(
     width_px: u32,
     origin_x: i64,
     max_offset_x: i64,
-    time_label_format: &str,
+    time_labels: TimeLabels<'_>,
     theme: ChartTheme,
 ) -> Result<(), RenderError> {
     let overlay = chart.plotting_area().strip_coord_spec();
@@ -473,7 +514,7 @@ fn draw_time_footer_labels<DB: DrawingBackend>(
             offset_ms as f64 / max_offset_x as f64
         };
         let x = x_range.start + (ratio * f64::from(available_width)).round() as i32;
-        let label = format_epoch_ms(origin_x.saturating_add(offset_ms), time_label_format);
+        let label = time_labels.label(origin_x.saturating_add(offset_ms));
         let label_width = (label.len() as i32 * 7).max(56);
         let left = (x - label_width / 2).clamp(x_range.start + 4, x_range.end - label_width - 4);
         let right = left + label_width;
@@ -544,6 +585,16 @@ fn collect_points<'a>(pane: &'a Pane) -> impl Iterator<Item = (i64, f64)> + 'a {
             .iter()
             .map(|marker| (marker.time_ms.as_i64(), marker.value))
             .collect::<Vec<_>>(),
+        Series::Profile(series) => series
+            .blocks
+            .iter()
+            .flat_map(|block| {
+                [
+                    (block.open_time_ms.as_i64(), block.price_low),
+                    (block.close_time_ms.as_i64(), block.price_high),
+                ]
+            })
+            .collect::<Vec<_>>(),
     })
 }
 
@@ -642,12 +693,6 @@ fn footer_label_offsets(max_offset_x: i64, count: usize) -> Vec<i64> {
         .collect()
 }
 
-fn format_epoch_ms(ms: i64, fmt: &str) -> String {
-    DateTime::<Utc>::from_timestamp_millis(ms)
-        .map(|value| value.format(fmt).to_string())
-        .unwrap_or_else(|| "-".to_string())
-}
-
 fn to_plotters(color: RgbColor) -> RGBColor {
     RGBColor(color.r, color.g, color.b)
 }
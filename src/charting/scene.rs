@@ -1,4 +1,5 @@
 use crate::charting::style::{ChartTheme, RgbColor};
+use crate::ui::timezone::DisplayTimeZone;
 
 /// Millisecond timestamp wrapper kept backend-neutral for future extractability.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -39,6 +40,8 @@ pub struct RenderedFrame {
 pub struct ChartScene {
     pub title: String,
     pub time_label_format: String,
+    /// Zone the time axis and tooltips are labeled in.
+    pub time_zone: DisplayTimeZone,
     pub theme: ChartTheme,
     pub viewport: Viewport,
     /// Optional shared hover/crosshair state that backends may visualize.
@@ -105,6 +108,7 @@ pub enum Series {
     Bars(BarSeries),
     Line(LineSeries),
     Markers(MarkerSeries),
+    Profile(ProfileSeries),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -154,6 +158,30 @@ pub struct LinePoint {
     pub value: f64,
 }
 
+/// Horizontal histogram drawn per session, e.g. a volume profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileSeries {
+    pub name: String,
+    pub color: RgbColor,
+    pub blocks: Vec<ProfileBlock>,
+}
+
+/// One price bucket, drawn from the session start rightwards.
+///
+/// Example:
+/// - session `00:00..24:00`, `fill_ratio` `0.5`
+/// - the bar spans `00:00..12:00` between `price_low` and `price_high`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileBlock {
+    pub open_time_ms: EpochMs,
+    pub close_time_ms: EpochMs,
+    pub price_low: f64,
+    pub price_high: f64,
+    pub value: f64,
+    /// Share of the session width the bar fills, `0.0..=1.0`.
+    pub fill_ratio: f64,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct MarkerSeries {
     pub name: String,
@@ -3,15 +3,24 @@ use chrono::NaiveDate;
 use crate::app::bootstrap::BinanceMode;
 use crate::app::cli::normalize_instrument_symbol;
 use crate::strategy::model::StrategyTemplate;
+use crate::strategy::overtrading::OvertradingLimit;
 use crate::terminal::completion::ShellCompletion;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BacktestCommand {
     Run {
         template: StrategyTemplate,
         instrument: String,
         from: NaiveDate,
         to: NaiveDate,
+        source: BacktestKlineSource,
+        costs: BacktestCostOverrides,
+        /// Rhai script for the script templates, from `--script <path>`.
+        script: Option<String>,
+        /// Plugin the plugin templates run, from `--plugin <name>`.
+        plugin: Option<String>,
+        /// Overtrading guard from `--overtrading <max:window[:pause]>`.
+        overtrading: Option<OvertradingLimit>,
     },
     Sweep {
         templates: Vec<StrategyTemplate>,
@@ -24,6 +33,17 @@ pub enum BacktestCommand {
     ReportShow {
         run_id: i64,
     },
+    /// Monthly gross/fees/funding/net summary; the latest run when `run_id` is `None`.
+    ReportMonthly {
+        run_id: Option<i64>,
+    },
+    /// Trade count and PnL per exit reason for each template, over every stored run.
+    ReportExits,
+    /// Side-by-side A/B view of two stored runs, e.g. a profile and its fork.
+    Compare {
+        run_a: i64,
+        run_b: i64,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -32,17 +52,45 @@ pub struct BacktestSweepWindow {
     pub to: NaiveDate,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Where `run` reads its candles from.
+///
+/// Example:
+/// - `--klines rest` -> `Rest`
+/// - `--klines var/BTCUSDT-1m-2026-03.csv` -> `File`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BacktestKlineSource {
+    /// The recorder's dataset for the current mode.
+    #[default]
+    Dataset,
+    /// A Binance kline CSV export or a saved `/fapi/v1/klines` JSON response.
+    File(String),
+    /// USD-M futures 1m klines fetched from the Binance REST API.
+    Rest,
+}
+
+/// Fee and slippage settings replacing the `BacktestConfig` defaults.
+///
+/// Example:
+/// - `--taker-fee 0.0004 --stop-slippage 0.0015`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BacktestCostOverrides {
+    pub taker_fee_rate: Option<f64>,
+    pub max_entry_slippage_pct: Option<f64>,
+    pub stop_slippage_pct: Option<f64>,
+    pub tp_slippage_pct: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum BacktestShellInput {
     Empty,
     Help,
     Exit,
     Mode(BinanceMode),
-    Command(BacktestCommand),
+    Command(Box<BacktestCommand>),
 }
 
 pub fn backtest_help_text() -> &'static str {
-    "/run <template> <instrument> --from <YYYY-MM-DD> --to <YYYY-MM-DD>\n/sweep --templates <csv> --instruments <csv> --windows <from:to,...> [--output-dir <path>]\n/list\n/report latest\n/report show <run_id>\n/mode <real|demo>\n/help\n/exit"
+    "/run <template> <instrument> --from <YYYY-MM-DD> --to <YYYY-MM-DD> [--klines <path|rest>] [--taker-fee <rate>] [--max-entry-slippage <pct>] [--stop-slippage <pct>] [--tp-slippage <pct>] [--script <path>] [--plugin <name>] [--overtrading <max:window[:pause]>]\n/sweep --templates <csv> --instruments <csv> --windows <from:to,...> [--output-dir <path>]\n/list\n/report latest\n/report show <run_id>\n/report monthly [run_id]\n/report exits\n/compare <run_id_a> <run_id_b>\n/mode <real|demo>\n/help\n/exit"
 }
 
 pub fn parse_backtest_shell_input(line: &str) -> Result<BacktestShellInput, String> {
@@ -72,7 +120,7 @@ pub fn parse_backtest_shell_input(line: &str) -> Result<BacktestShellInput, Stri
         return Ok(BacktestShellInput::Mode(mode));
     }
 
-    parse_backtest_command(&args).map(BacktestShellInput::Command)
+    parse_backtest_command(&args).map(|command| BacktestShellInput::Command(Box::new(command)))
 }
 
 pub fn parse_backtest_command(args: &[String]) -> Result<BacktestCommand, String> {
@@ -84,6 +132,10 @@ pub fn parse_backtest_command(args: &[String]) -> Result<BacktestCommand, String
                 Some("price-sma-cross-short") => StrategyTemplate::PriceSmaCrossShort,
                 Some("price-sma-cross-long-fast") => StrategyTemplate::PriceSmaCrossLongFast,
                 Some("price-sma-cross-short-fast") => StrategyTemplate::PriceSmaCrossShortFast,
+                Some("script-long") => StrategyTemplate::ScriptLong,
+                Some("script-short") => StrategyTemplate::ScriptShort,
+                Some("plugin-long") => StrategyTemplate::PluginLong,
+                Some("plugin-short") => StrategyTemplate::PluginShort,
                 Some(other) => return Err(format!("unsupported template: {other}")),
                 None => {
                     return Err(
@@ -95,12 +147,45 @@ pub fn parse_backtest_command(args: &[String]) -> Result<BacktestCommand, String
             let instrument = normalize_instrument_symbol(args.get(2).ok_or(
                 "usage: run <template> <instrument> --from <YYYY-MM-DD> --to <YYYY-MM-DD>",
             )?);
-            let (from, to) = parse_dates(&args[3..])?;
+            let RunArgs {
+                from,
+                to,
+                source,
+                costs,
+                script,
+                plugin,
+                overtrading,
+            } = parse_run_args(&args[3..])?;
+            let scripted = matches!(
+                template,
+                StrategyTemplate::ScriptLong | StrategyTemplate::ScriptShort
+            );
+            if scripted && script.is_none() {
+                return Err(format!("template {} needs --script <path>", template.slug()));
+            }
+            if !scripted && script.is_some() {
+                return Err("--script only applies to script-long and script-short".to_string());
+            }
+            let plugged = matches!(
+                template,
+                StrategyTemplate::PluginLong | StrategyTemplate::PluginShort
+            );
+            if plugged && plugin.is_none() {
+                return Err(format!("template {} needs --plugin <name>", template.slug()));
+            }
+            if !plugged && plugin.is_some() {
+                return Err("--plugin only applies to plugin-long and plugin-short".to_string());
+            }
             Ok(BacktestCommand::Run {
                 template,
                 instrument,
                 from,
                 to,
+                source,
+                costs,
+                script,
+                plugin,
+                overtrading,
             })
         }
         Some("sweep") => parse_backtest_sweep_command(&args[1..]),
@@ -119,8 +204,35 @@ pub fn parse_backtest_command(args: &[String]) -> Result<BacktestCommand, String
                     .map_err(|_| format!("invalid run id: {}", args[2]))?;
                 Ok(BacktestCommand::ReportShow { run_id })
             }
-            _ => Err("usage: report latest | report show <run_id>".to_string()),
+            Some("monthly") if args.len() <= 3 => {
+                let run_id = args
+                    .get(2)
+                    .map(|raw| {
+                        raw.parse::<i64>()
+                            .map_err(|_| format!("invalid run id: {raw}"))
+                    })
+                    .transpose()?;
+                Ok(BacktestCommand::ReportMonthly { run_id })
+            }
+            Some("exits") if args.len() == 2 => Ok(BacktestCommand::ReportExits),
+            _ => Err(
+                "usage: report latest | report show <run_id> | report monthly [run_id] | report exits"
+                    .to_string(),
+            ),
         },
+        Some("compare") => {
+            let [_, raw_a, raw_b] = args else {
+                return Err("usage: compare <run_id_a> <run_id_b>".to_string());
+            };
+            let run_id = |raw: &String| {
+                raw.parse::<i64>()
+                    .map_err(|_| format!("invalid run id: {raw}"))
+            };
+            Ok(BacktestCommand::Compare {
+                run_a: run_id(raw_a)?,
+                run_b: run_id(raw_b)?,
+            })
+        }
         Some(other) => Err(format!("unsupported command: {other}")),
         None => Err("missing backtest command".to_string()),
     }
@@ -137,13 +249,16 @@ pub fn complete_backtest_input(line: &str) -> Vec<ShellCompletion> {
             completion("/run", "run a backtest over a date range"),
             completion("/list", "list stored backtest runs"),
             completion("/report", "show stored backtest reports"),
+            completion("/compare", "compare two stored runs side by side"),
             completion("/mode", "switch dataset mode"),
             completion("/help", "show help"),
             completion("/exit", "exit"),
         ];
     }
     if parts.len() == 1 && !trailing_space {
-        return ["/run", "/list", "/report", "/mode", "/help", "/exit"]
+        return [
+            "/run", "/list", "/report", "/compare", "/mode", "/help", "/exit",
+        ]
             .into_iter()
             .filter(|item| item.trim_start_matches('/').starts_with(parts[0]))
             .map(|item| completion(item, ""))
@@ -158,6 +273,12 @@ pub fn complete_backtest_input(line: &str) -> Vec<ShellCompletion> {
             .collect(),
         Some("run") if parts.len() <= 2 => StrategyTemplate::all()
             .into_iter()
+            .chain([
+                StrategyTemplate::ScriptLong,
+                StrategyTemplate::ScriptShort,
+                StrategyTemplate::PluginLong,
+                StrategyTemplate::PluginShort,
+            ])
             .map(|template| {
                 completion(
                     &format!("/run {}", template.slug()),
@@ -172,6 +293,8 @@ pub fn complete_backtest_input(line: &str) -> Vec<ShellCompletion> {
         Some("report") if parts.len() <= 2 => vec![
             completion("/report latest", "show latest stored run"),
             completion("/report show ", "show a stored run by id"),
+            completion("/report monthly ", "monthly fees, funding and net pnl"),
+            completion("/report exits", "pnl and frequency per exit reason"),
         ],
         _ => Vec::new(),
     }
@@ -285,9 +408,25 @@ fn parse_windows_csv(raw: &str) -> Result<Vec<BacktestSweepWindow>, String> {
     Ok(windows)
 }
 
-fn parse_dates(args: &[String]) -> Result<(NaiveDate, NaiveDate), String> {
+/// Flags of `backtest run` after the template and instrument.
+struct RunArgs {
+    from: NaiveDate,
+    to: NaiveDate,
+    source: BacktestKlineSource,
+    costs: BacktestCostOverrides,
+    script: Option<String>,
+    plugin: Option<String>,
+    overtrading: Option<OvertradingLimit>,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
     let mut from = None;
+    let mut script = None;
+    let mut plugin = None;
+    let mut overtrading = None;
     let mut to = None;
+    let mut source = BacktestKlineSource::Dataset;
+    let mut costs = BacktestCostOverrides::default();
     let mut index = 0usize;
     while index < args.len() {
         match args[index].as_str() {
@@ -307,6 +446,54 @@ fn parse_dates(args: &[String]) -> Result<(NaiveDate, NaiveDate), String> {
                 );
                 index += 2;
             }
+            "--klines" => {
+                let value = args.get(index + 1).ok_or("missing value for --klines")?;
+                source = match value.as_str() {
+                    "rest" => BacktestKlineSource::Rest,
+                    "dataset" => BacktestKlineSource::Dataset,
+                    path => BacktestKlineSource::File(path.to_string()),
+                };
+                index += 2;
+            }
+            "--script" => {
+                let value = args.get(index + 1).ok_or("missing value for --script")?;
+                script = Some(value.clone());
+                index += 2;
+            }
+            "--plugin" => {
+                let value = args.get(index + 1).ok_or("missing value for --plugin")?;
+                plugin = Some(value.clone());
+                index += 2;
+            }
+            "--overtrading" => {
+                let value = args
+                    .get(index + 1)
+                    .ok_or("missing value for --overtrading")?;
+                overtrading = Some(
+                    OvertradingLimit::parse(value)
+                        .ok_or_else(|| format!("invalid value for --overtrading: {value}"))?,
+                );
+                index += 2;
+            }
+            flag @ ("--taker-fee" | "--max-entry-slippage" | "--stop-slippage"
+            | "--tp-slippage") => {
+                let value = args
+                    .get(index + 1)
+                    .ok_or_else(|| format!("missing value for {flag}"))?;
+                let parsed = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|parsed| (0.0..1.0).contains(parsed))
+                    .ok_or_else(|| format!("invalid value for {flag}: {value}"))?;
+                let slot = match flag {
+                    "--taker-fee" => &mut costs.taker_fee_rate,
+                    "--max-entry-slippage" => &mut costs.max_entry_slippage_pct,
+                    "--stop-slippage" => &mut costs.stop_slippage_pct,
+                    _ => &mut costs.tp_slippage_pct,
+                };
+                *slot = Some(parsed);
+                index += 2;
+            }
             other => return Err(format!("unsupported arg: {other}")),
         }
     }
@@ -317,7 +504,15 @@ fn parse_dates(args: &[String]) -> Result<(NaiveDate, NaiveDate), String> {
             "invalid date range: from ({from}) must be on or before to ({to})"
         ));
     }
-    Ok((from, to))
+    Ok(RunArgs {
+        from,
+        to,
+        source,
+        costs,
+        script,
+        plugin,
+        overtrading,
+    })
 }
 
 fn completion(value: &str, description: &str) -> ShellCompletion {
@@ -372,6 +567,11 @@ mod tests {
                 instrument: "BTCUSDT".to_string(),
                 from: NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
                 to: NaiveDate::from_ymd_opt(2026, 3, 14).expect("date"),
+                source: BacktestKlineSource::Dataset,
+                costs: BacktestCostOverrides::default(),
+                script: None,
+                plugin: None,
+                overtrading: None,
             }
         );
     }
@@ -397,6 +597,11 @@ mod tests {
                 instrument: "ETHUSDT".to_string(),
                 from: NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
                 to: NaiveDate::from_ymd_opt(2026, 3, 14).expect("date"),
+                source: BacktestKlineSource::Dataset,
+                costs: BacktestCostOverrides::default(),
+                script: None,
+                plugin: None,
+                overtrading: None,
             }
         );
     }
@@ -422,6 +627,11 @@ mod tests {
                 instrument: "XRPUSDT".to_string(),
                 from: NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
                 to: NaiveDate::from_ymd_opt(2026, 3, 14).expect("date"),
+                source: BacktestKlineSource::Dataset,
+                costs: BacktestCostOverrides::default(),
+                script: None,
+                plugin: None,
+                overtrading: None,
             }
         );
     }
@@ -447,6 +657,11 @@ mod tests {
                 instrument: "SOLUSDT".to_string(),
                 from: NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
                 to: NaiveDate::from_ymd_opt(2026, 3, 14).expect("date"),
+                source: BacktestKlineSource::Dataset,
+                costs: BacktestCostOverrides::default(),
+                script: None,
+                plugin: None,
+                overtrading: None,
             }
         );
     }
@@ -472,6 +687,11 @@ mod tests {
                 instrument: "BNBUSDT".to_string(),
                 from: NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
                 to: NaiveDate::from_ymd_opt(2026, 3, 14).expect("date"),
+                source: BacktestKlineSource::Dataset,
+                costs: BacktestCostOverrides::default(),
+                script: None,
+                plugin: None,
+                overtrading: None,
             }
         );
     }
@@ -512,4 +732,132 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn parse_backtest_command_accepts_monthly_report_with_optional_run_id() {
+        let latest = parse_backtest_command(&["report".to_string(), "monthly".to_string()])
+            .expect("monthly latest");
+        let by_id =
+            parse_backtest_command(&["report".to_string(), "monthly".to_string(), "7".to_string()])
+                .expect("monthly by id");
+
+        assert_eq!(latest, BacktestCommand::ReportMonthly { run_id: None });
+        assert_eq!(by_id, BacktestCommand::ReportMonthly { run_id: Some(7) });
+        assert_eq!(
+            parse_backtest_command(&["report".to_string(), "exits".to_string()]),
+            Ok(BacktestCommand::ReportExits)
+        );
+        assert!(parse_backtest_command(&[
+            "report".to_string(),
+            "monthly".to_string(),
+            "seven".to_string(),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_backtest_command_accepts_two_run_ids_for_compare() {
+        let args = |raw: &str| {
+            raw.split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            parse_backtest_command(&args("compare 3 7")),
+            Ok(BacktestCommand::Compare { run_a: 3, run_b: 7 })
+        );
+        assert_eq!(
+            parse_backtest_command(&args("compare 3 x")),
+            Err("invalid run id: x".to_string())
+        );
+        assert!(parse_backtest_command(&args("compare 3")).is_err());
+    }
+
+    #[test]
+    fn parse_backtest_command_accepts_kline_source_and_cost_overrides() {
+        let args = |raw: &str| {
+            raw.split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let Ok(BacktestCommand::Run { source, costs, .. }) = parse_backtest_command(&args(
+            "run price-sma-cross-long btcusdt --from 2026-03-13 --to 2026-03-14 --klines rest --taker-fee 0.0004 --stop-slippage 0.0015",
+        )) else {
+            panic!("expected run command");
+        };
+        assert_eq!(source, BacktestKlineSource::Rest);
+        assert_eq!(
+            costs,
+            BacktestCostOverrides {
+                taker_fee_rate: Some(0.0004),
+                stop_slippage_pct: Some(0.0015),
+                ..BacktestCostOverrides::default()
+            }
+        );
+
+        let Ok(BacktestCommand::Run { source, .. }) = parse_backtest_command(&args(
+            "run price-sma-cross-long btcusdt --klines var/BTCUSDT-1m.csv --from 2026-03-13 --to 2026-03-14",
+        )) else {
+            panic!("expected run command");
+        };
+        assert_eq!(
+            source,
+            BacktestKlineSource::File("var/BTCUSDT-1m.csv".to_string())
+        );
+        assert_eq!(
+            parse_backtest_command(&args(
+                "run price-sma-cross-long btcusdt --from 2026-03-13 --to 2026-03-14 --taker-fee 2"
+            )),
+            Err("invalid value for --taker-fee: 2".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_backtest_command_pairs_script_templates_with_a_script_path() {
+        let args = |raw: &str| {
+            raw.split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let Ok(BacktestCommand::Run {
+            template, script, ..
+        }) = parse_backtest_command(&args(
+            "run script-short btcusdt --script strategies/fade.rhai --from 2026-03-13 --to 2026-03-14",
+        ))
+        else {
+            panic!("expected run command");
+        };
+        assert_eq!(template, StrategyTemplate::ScriptShort);
+        assert_eq!(script.as_deref(), Some("strategies/fade.rhai"));
+        assert_eq!(
+            parse_backtest_command(&args(
+                "run script-long btcusdt --from 2026-03-13 --to 2026-03-14"
+            )),
+            Err("template script-long needs --script <path>".to_string())
+        );
+        assert!(parse_backtest_command(&args(
+            "run price-sma-cross-long btcusdt --script a.rhai --from 2026-03-13 --to 2026-03-14"
+        ))
+        .is_err());
+
+        let Ok(BacktestCommand::Run {
+            template, plugin, ..
+        }) = parse_backtest_command(&args(
+            "run plugin-long btcusdt --plugin mean-revert --from 2026-03-13 --to 2026-03-14",
+        ))
+        else {
+            panic!("expected run command");
+        };
+        assert_eq!(template, StrategyTemplate::PluginLong);
+        assert_eq!(plugin.as_deref(), Some("mean-revert"));
+        assert_eq!(
+            parse_backtest_command(&args(
+                "run plugin-short btcusdt --from 2026-03-13 --to 2026-03-14"
+            )),
+            Err("template plugin-short needs --plugin <name>".to_string())
+        );
+    }
 }
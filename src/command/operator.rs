@@ -1,13 +1,36 @@
 use crate::app::bootstrap::BinanceMode;
-use crate::app::commands::{AppCommand, PortfolioView};
+use crate::app::commands::{
+    AlertCommand, AppCommand, BasisCommand, DomAction, DomCommand, NewsCommand, OrderFilter,
+    PortfolioView,
+};
 use crate::domain::exposure::Exposure;
 use crate::domain::instrument::Instrument;
+use crate::domain::order::OrderStatus;
 use crate::domain::order_type::OrderType;
 use crate::domain::position::Side;
 use crate::execution::command::{CommandSource, ExecutionCommand};
-use crate::strategy::command::{StrategyCommand, StrategyStartConfig};
+use crate::execution::dom_ladder::{DomPrice, DEFAULT_DOM_LEVELS, MAX_DOM_LEVELS};
+use crate::execution::funnel::DEFAULT_FUNNEL_WINDOW_HOURS;
+use crate::execution::take_profit::DEFAULT_TAKE_PROFIT_PROFILE;
+use crate::execution::trailing_stop::TrailDistance;
+use crate::market_data::price_alert::PriceAlertAction;
+use crate::market_data::synthetic::is_registered_synthetic;
+use crate::storage::order_store::{HistoryQuery, TimeBucket};
+use crate::strategy::adaptive::{
+    AdaptiveTuning, DEFAULT_ADAPT_MAX_DRIFT, DEFAULT_ADAPT_STEP, DEFAULT_ADAPT_TRADES,
+};
+use crate::strategy::basis::BasisArbConfig;
+use crate::strategy::command::{
+    OpenPositionResolution, StrategyCommand, StrategyGroupAction, StrategyStartConfig,
+};
+use crate::strategy::filter::{StrategyFilter, StrategyListQuery};
+use crate::strategy::kpi::KpiTargets;
 use crate::strategy::model::StrategyTemplate;
+use crate::strategy::ramp::{SizeRamp, DEFAULT_RAMP_START_FRACTION, DEFAULT_RAMP_TRADES};
 use crate::terminal::completion::ShellCompletion;
+use crate::terminal::macros::MacroCommand;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ShellInput {
@@ -15,15 +38,27 @@ pub enum ShellInput {
     Help,
     Exit,
     Mode(BinanceMode),
-    Command(AppCommand),
+    Macro(MacroCommand),
+    Command(Box<AppCommand>),
 }
 
 pub fn parse_app_command(args: &[String]) -> Result<AppCommand, String> {
     match args.first().map(String::as_str).unwrap_or("refresh") {
-        "refresh" | "portfolio" => Ok(parse_portfolio_command(args)),
+        "refresh" | "portfolio" => parse_portfolio_command(args),
         "positions" => Ok(AppCommand::Portfolio(PortfolioView::Positions)),
         "balances" => Ok(AppCommand::Portfolio(PortfolioView::Balances)),
-        "orders" => Ok(AppCommand::Portfolio(PortfolioView::Orders)),
+        "orders" => Ok(AppCommand::Portfolio(PortfolioView::Orders(
+            parse_order_filter(&args[1..])?,
+        ))),
+        "history" => Ok(AppCommand::Portfolio(parse_history_view(&args[1..])?)),
+        "reload-config" => Ok(AppCommand::ReloadConfig),
+        "diagnostics" => match args.get(1).map(String::as_str) {
+            None => Ok(AppCommand::Diagnostics),
+            Some("export") if args.len() <= 3 => Ok(AppCommand::ExportDiagnostics {
+                path: args.get(2).map(PathBuf::from),
+            }),
+            Some(_) => Err("usage: diagnostics [export [path]]".to_string()),
+        },
         "close-all" => Ok(AppCommand::Execution(ExecutionCommand::CloseAll {
             source: CommandSource::User,
         })),
@@ -121,42 +156,530 @@ pub fn parse_app_command(args: &[String]) -> Result<AppCommand, String> {
                 source: CommandSource::User,
             }))
         }
+        "trailing-stop" => {
+            let instrument = args
+                .get(1)
+                .ok_or("usage: trailing-stop <instrument> <callback_pct|atr_multiple>")?;
+            let raw_trail = args
+                .get(2)
+                .ok_or("usage: trailing-stop <instrument> <callback_pct|atr_multiple>")?;
+            Ok(AppCommand::Execution(ExecutionCommand::PlaceTrailingStop {
+                instrument: Instrument::new(normalize_instrument_symbol(instrument)),
+                trail: TrailDistance::parse(raw_trail)?,
+                source: CommandSource::User,
+            }))
+        }
+        "take-profit" => {
+            let instrument = args
+                .get(1)
+                .ok_or("usage: take-profit <instrument> [profile]")?;
+            let profile = args
+                .get(2)
+                .map(|profile| profile.to_ascii_lowercase())
+                .unwrap_or_else(|| DEFAULT_TAKE_PROFIT_PROFILE.to_string());
+            Ok(AppCommand::Execution(ExecutionCommand::PlaceTakeProfitLadder {
+                instrument: Instrument::new(normalize_instrument_symbol(instrument)),
+                profile,
+                source: CommandSource::User,
+            }))
+        }
         "strategy" => parse_strategy_command(args),
+        "alert" => parse_alert_command(args),
+        "basis" => parse_basis_command(args),
+        "dom" => parse_dom_command(args),
+        "oco" => parse_oco_command(args),
+        "iceberg" => parse_iceberg_command(args),
+        "adopt" => parse_adopt_command(args),
+        "flatten" => parse_flatten_command(args),
+        "panic" => match args.get(1).map(String::as_str) {
+            None => Ok(AppCommand::Panic { confirmed: false }),
+            Some("confirm") => Ok(AppCommand::Panic { confirmed: true }),
+            Some(_) => Err("usage: panic [confirm]".to_string()),
+        },
+        "inactivity" => match args.get(1).map(String::as_str) {
+            None | Some("status") => Ok(AppCommand::InactivityStatus),
+            Some("resume") => Ok(AppCommand::ResumeEntries),
+            Some(_) => Err("usage: inactivity [status|resume]".to_string()),
+        },
+        "shadow" => match args.get(1).map(String::as_str) {
+            None | Some("status") => Ok(AppCommand::ShadowStatus),
+            Some(_) => Err("usage: shadow [status]".to_string()),
+        },
+        "events" => parse_events_command(args),
+        "news" => parse_news_command(args),
         other => Err(format!(
-            "unsupported command: {other}. supported commands: portfolio, positions, balances, orders, close-all, close-symbol, set-target-exposure, option-order, strategy"
+            "unsupported command: {other}. supported commands: portfolio, positions, balances, orders, close-all, close-symbol, set-target-exposure, option-order, trailing-stop, take-profit, oco, iceberg, dom, adopt, flatten, panic, inactivity, shadow, events, news, strategy, alert, basis"
         )),
     }
 }
 
+fn parse_news_command(args: &[String]) -> Result<AppCommand, String> {
+    const USAGE: &str = "usage: news [blackout [minutes|off]]";
+    let command = match (
+        args.get(1).map(String::as_str),
+        args.get(2).map(String::as_str),
+    ) {
+        (None, None) => NewsCommand::Show,
+        (Some("blackout"), None) => NewsCommand::Blackout { minutes: None },
+        (Some("blackout"), Some("off")) => NewsCommand::LiftBlackout,
+        (Some("blackout"), Some(raw)) => NewsCommand::Blackout {
+            minutes: Some(
+                raw.parse::<u64>()
+                    .ok()
+                    .filter(|minutes| *minutes > 0)
+                    .ok_or(USAGE)?,
+            ),
+        },
+        _ => return Err(USAGE.to_string()),
+    };
+    if args.len() > 3 {
+        return Err(USAGE.to_string());
+    }
+    Ok(AppCommand::News(command))
+}
+
+fn parse_oco_command(args: &[String]) -> Result<AppCommand, String> {
+    const USAGE: &str =
+        "usage: oco [list|ev] | oco <instrument> <take_profit_price> <stop_price> [stop_limit_price]";
+    let instrument = match args.get(1).map(String::as_str) {
+        None | Some("list") => return Ok(AppCommand::OcoStatus),
+        Some("ev") => return Ok(AppCommand::HoldEvStatus),
+        Some(instrument) => instrument,
+    };
+    let price = |raw: Option<&String>, label: &str| {
+        let raw = raw.ok_or(USAGE)?;
+        raw.parse::<f64>()
+            .ok()
+            .filter(|price| *price > f64::EPSILON)
+            .ok_or(format!("invalid {label} price: {raw}"))
+    };
+    Ok(AppCommand::Execution(ExecutionCommand::PlaceOco {
+        instrument: Instrument::new(normalize_instrument_symbol(instrument)),
+        take_profit_price: price(args.get(2), "take-profit")?,
+        stop_price: price(args.get(3), "stop")?,
+        stop_limit_price: match args.get(4) {
+            Some(raw) => Some(price(Some(raw), "stop-limit")?),
+            None => None,
+        },
+        source: CommandSource::User,
+    }))
+}
+
+fn parse_iceberg_command(args: &[String]) -> Result<AppCommand, String> {
+    const USAGE: &str =
+        "usage: iceberg [list] | iceberg <instrument> <buy|sell> <qty> <visible_qty> <price>";
+    let instrument = match args.get(1).map(String::as_str) {
+        None | Some("list") => return Ok(AppCommand::IcebergStatus),
+        Some(instrument) => instrument,
+    };
+    let side = match args.get(2).map(String::as_str) {
+        Some("buy") => Side::Buy,
+        Some("sell") => Side::Sell,
+        Some(other) => {
+            return Err(format!(
+                "unsupported iceberg side: {other}. expected buy or sell"
+            ))
+        }
+        None => return Err(USAGE.to_string()),
+    };
+    let positive = |raw: Option<&String>, label: &str| {
+        let raw = raw.ok_or(USAGE)?;
+        raw.parse::<f64>()
+            .ok()
+            .filter(|value| *value > f64::EPSILON)
+            .ok_or(format!("invalid {label}: {raw}"))
+    };
+    let qty = positive(args.get(3), "iceberg quantity")?;
+    let visible_qty = positive(args.get(4), "visible quantity")?;
+    if visible_qty > qty {
+        return Err(format!(
+            "visible quantity {visible_qty} exceeds the iceberg quantity {qty}"
+        ));
+    }
+    Ok(AppCommand::Execution(ExecutionCommand::PlaceIceberg {
+        instrument: Instrument::new(normalize_instrument_symbol(instrument)),
+        side,
+        qty,
+        visible_qty,
+        price: positive(args.get(5), "limit price")?,
+        source: CommandSource::User,
+    }))
+}
+
+fn parse_flatten_command(args: &[String]) -> Result<AppCommand, String> {
+    let mut instrument = None;
+    let mut confirmed = false;
+    for arg in &args[1..] {
+        match arg.as_str() {
+            "confirm" => confirmed = true,
+            raw if instrument.is_none() => {
+                instrument = Some(Instrument::new(normalize_instrument_symbol(raw)))
+            }
+            _ => return Err("usage: flatten [instrument] [confirm]".to_string()),
+        }
+    }
+    Ok(AppCommand::Flatten {
+        instrument,
+        confirmed,
+    })
+}
+
+fn parse_events_command(args: &[String]) -> Result<AppCommand, String> {
+    const USAGE: &str = "usage: events rebuild [domain=<domain,...>] [path=<file>]";
+    if args.get(1).map(String::as_str) != Some("rebuild") {
+        return Err(USAGE.to_string());
+    }
+    let mut path = None;
+    let mut domains = BTreeSet::new();
+    for arg in &args[2..] {
+        match arg.split_once('=') {
+            Some(("path", value)) if !value.is_empty() => path = Some(PathBuf::from(value)),
+            Some(("domain", value)) => domains.extend(
+                value
+                    .split(',')
+                    .map(|domain| domain.trim().to_ascii_lowercase())
+                    .filter(|domain| !domain.is_empty()),
+            ),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    Ok(AppCommand::RebuildEvents { path, domains })
+}
+
+fn parse_adopt_command(args: &[String]) -> Result<AppCommand, String> {
+    const USAGE: &str = "usage: adopt [<instrument> <entry_price>]";
+    let Some(instrument) = args.get(1) else {
+        return Ok(AppCommand::ExternalPositions);
+    };
+    let raw_price = args.get(2).ok_or(USAGE)?;
+    let entry_price = raw_price
+        .parse::<f64>()
+        .ok()
+        .filter(|price| *price > f64::EPSILON)
+        .ok_or(format!("invalid entry price: {raw_price}"))?;
+    Ok(AppCommand::AdoptPosition {
+        instrument: Instrument::new(normalize_instrument_symbol(instrument)),
+        entry_price,
+    })
+}
+
+fn parse_dom_command(args: &[String]) -> Result<AppCommand, String> {
+    const USAGE: &str = "usage: dom <instrument> [levels|<buy|sell> <price|b<n>|a<n>> <qty>|cancel <client_order_id>]";
+    let instrument = args.get(1).ok_or(USAGE)?;
+    let mut command = DomCommand {
+        instrument: Instrument::new(normalize_instrument_symbol(instrument)),
+        levels: DEFAULT_DOM_LEVELS,
+        action: None,
+    };
+    match args.get(2).map(String::as_str) {
+        None => {}
+        Some(raw_side @ ("buy" | "sell")) => {
+            let raw_price = args.get(3).ok_or(USAGE)?;
+            let price = DomPrice::parse(raw_price).ok_or(format!(
+                "invalid ladder price: {raw_price}. expected a price, b<level> or a<level>"
+            ))?;
+            let raw_qty = args.get(4).ok_or(USAGE)?;
+            let qty = raw_qty
+                .parse::<f64>()
+                .ok()
+                .filter(|qty| *qty > f64::EPSILON)
+                .ok_or(format!("invalid order quantity: {raw_qty}"))?;
+            command.action = Some(DomAction::Place {
+                side: if raw_side == "buy" {
+                    Side::Buy
+                } else {
+                    Side::Sell
+                },
+                price,
+                qty,
+            });
+        }
+        Some("cancel") => {
+            command.action = Some(DomAction::Cancel {
+                client_order_id: args.get(3).ok_or(USAGE)?.clone(),
+            });
+        }
+        Some(raw_levels) => {
+            command.levels = raw_levels
+                .parse::<usize>()
+                .ok()
+                .filter(|levels| (1..=MAX_DOM_LEVELS).contains(levels))
+                .ok_or(format!(
+                    "invalid ladder levels: {raw_levels}. expected 1..={MAX_DOM_LEVELS}"
+                ))?;
+        }
+    }
+    Ok(AppCommand::Dom(command))
+}
+
+fn parse_alert_command(args: &[String]) -> Result<AppCommand, String> {
+    const USAGE: &str =
+        "usage: alert add <instrument> <price> [notify|strategy <template>|target <exposure>]";
+    match args.get(1).map(String::as_str) {
+        None | Some("list") => Ok(AppCommand::Alert(AlertCommand::List)),
+        Some("remove") => {
+            let raw = args.get(2).ok_or("usage: alert remove <alert_id>")?;
+            let id = raw
+                .parse::<u64>()
+                .map_err(|_| format!("invalid alert id: {raw}"))?;
+            Ok(AppCommand::Alert(AlertCommand::Remove { id }))
+        }
+        Some("confirm") => {
+            let raw = args.get(2).ok_or("usage: alert confirm <alert_id>")?;
+            let id = raw
+                .parse::<u64>()
+                .map_err(|_| format!("invalid alert id: {raw}"))?;
+            Ok(AppCommand::Alert(AlertCommand::Confirm { id }))
+        }
+        Some("add") => {
+            let instrument = args.get(2).ok_or(USAGE)?;
+            let raw_price = args.get(3).ok_or(USAGE)?;
+            let price = raw_price
+                .parse::<f64>()
+                .map_err(|_| format!("invalid alert price: {raw_price}"))?;
+            if price <= f64::EPSILON {
+                return Err(format!("invalid alert price: {raw_price}"));
+            }
+            let action = match args.get(4).map(String::as_str) {
+                None | Some("notify") => PriceAlertAction::Notify,
+                Some("strategy") => PriceAlertAction::StartStrategy {
+                    template: parse_strategy_template(args.get(5), USAGE)?
+                        .slug()
+                        .to_string(),
+                },
+                Some("target") => {
+                    let raw_target = args.get(5).ok_or(USAGE)?;
+                    let target = raw_target
+                        .parse::<f64>()
+                        .map_err(|_| format!("invalid target exposure: {raw_target}"))?;
+                    Exposure::new(target).ok_or(format!(
+                        "target exposure out of range: {target}. expected -1.0..=1.0"
+                    ))?;
+                    PriceAlertAction::SetTargetExposure { target }
+                }
+                Some(other) => return Err(format!("unsupported alert action: {other}")),
+            };
+            Ok(AppCommand::Alert(AlertCommand::Add {
+                instrument: Instrument::new(normalize_instrument_symbol(instrument)),
+                price,
+                action,
+            }))
+        }
+        Some(_) => Err("usage: alert <add|remove|list|confirm>".to_string()),
+    }
+}
+
+fn parse_basis_command(args: &[String]) -> Result<AppCommand, String> {
+    const USAGE: &str = "usage: basis start <instrument> entry=<bps> [exit=<bps>] target=<exposure> [funding=<apr_pct>]";
+    match args.get(1).map(String::as_str) {
+        None | Some("list") => Ok(AppCommand::Basis(BasisCommand::List)),
+        Some("stop") => {
+            let raw = args.get(2).ok_or("usage: basis stop <watch_id>")?;
+            let id = raw
+                .parse::<u64>()
+                .map_err(|_| format!("invalid basis watch id: {raw}"))?;
+            Ok(AppCommand::Basis(BasisCommand::Stop { id }))
+        }
+        Some("start") => {
+            let instrument = args.get(2).ok_or(USAGE)?;
+            let mut values = BTreeMap::new();
+            for arg in &args[3..] {
+                let (key, raw) = arg.split_once('=').ok_or(USAGE)?;
+                if !["entry", "exit", "target", "funding"].contains(&key) {
+                    return Err(USAGE.to_string());
+                }
+                let value = raw
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|value| value.is_finite())
+                    .ok_or(format!("invalid basis {key}: {raw}"))?;
+                values.insert(key, value);
+            }
+            let entry_bps = *values.get("entry").ok_or(USAGE)?;
+            let exit_bps = values.get("exit").copied().unwrap_or_default();
+            let target = *values.get("target").ok_or(USAGE)?;
+            if exit_bps >= entry_bps {
+                return Err(format!(
+                    "basis exit must be below entry: entry={entry_bps} exit={exit_bps}"
+                ));
+            }
+            if !(target > f64::EPSILON && target <= 1.0) {
+                return Err(format!(
+                    "basis target out of range: {target}. expected 0.0 < target <= 1.0"
+                ));
+            }
+            Ok(AppCommand::Basis(BasisCommand::Start {
+                instrument: Instrument::new(normalize_instrument_symbol(instrument)),
+                config: BasisArbConfig {
+                    entry_bps,
+                    exit_bps,
+                    funding_apr_pct: values.get("funding").copied(),
+                    target,
+                },
+            }))
+        }
+        Some(_) => Err("usage: basis <start|list|stop>".to_string()),
+    }
+}
+
 fn parse_strategy_command(args: &[String]) -> Result<AppCommand, String> {
     match args.get(1).map(String::as_str) {
         Some("templates") => Ok(AppCommand::Strategy(StrategyCommand::Templates)),
-        Some("list") => Ok(AppCommand::Strategy(StrategyCommand::List)),
-        Some("history") => Ok(AppCommand::Strategy(StrategyCommand::History)),
+        Some("list") => Ok(AppCommand::Strategy(StrategyCommand::List {
+            query: StrategyListQuery::parse(&args[2..])?,
+        })),
+        Some("history") => Ok(AppCommand::Strategy(StrategyCommand::History {
+            query: StrategyListQuery::parse(&args[2..])?,
+        })),
         Some("show") => {
             let watch_id = parse_watch_id(args.get(2), "usage: strategy show <watch_id>")?;
             Ok(AppCommand::Strategy(StrategyCommand::Show { watch_id }))
         }
         Some("stop") => {
-            let watch_id = parse_watch_id(args.get(2), "usage: strategy stop <watch_id>")?;
-            Ok(AppCommand::Strategy(StrategyCommand::Stop { watch_id }))
+            const USAGE: &str = "usage: strategy stop <watch_id> [keep|flatten|manual]";
+            let watch_id = parse_watch_id(args.get(2), USAGE)?;
+            let resolution = match args.get(3) {
+                Some(raw) => Some(OpenPositionResolution::parse(raw).ok_or(USAGE)?),
+                None => None,
+            };
+            Ok(AppCommand::Strategy(StrategyCommand::Stop {
+                watch_id,
+                resolution,
+            }))
+        }
+        Some("debug") => {
+            const USAGE: &str = "usage: strategy debug <watch_id> <on|off>";
+            let watch_id = parse_watch_id(args.get(2), USAGE)?;
+            let enabled = match args.get(3).map(String::as_str) {
+                Some("on") => true,
+                Some("off") => false,
+                _ => return Err(USAGE.to_string()),
+            };
+            Ok(AppCommand::Strategy(StrategyCommand::Debug {
+                watch_id,
+                enabled,
+            }))
+        }
+        Some("trace") => {
+            const USAGE: &str = "usage: strategy trace <watch_id> [dump [path]]";
+            let watch_id = parse_watch_id(args.get(2), USAGE)?;
+            let dump_path = match args.get(3).map(String::as_str) {
+                None => None,
+                Some("dump") => Some(
+                    args.get(4)
+                        .cloned()
+                        .unwrap_or_else(|| format!("var/strategy-trace-{watch_id}.jsonl")),
+                ),
+                Some(_) => return Err(USAGE.to_string()),
+            };
+            Ok(AppCommand::Strategy(StrategyCommand::Trace {
+                watch_id,
+                dump_path,
+            }))
+        }
+        Some("group") => {
+            const USAGE: &str = "usage: strategy group <watch_id> <name|none>";
+            let watch_id = parse_watch_id(args.get(2), USAGE)?;
+            let group = match args.get(3).map(String::as_str) {
+                Some("none") => None,
+                Some(raw) => Some(parse_group_name(raw)?),
+                None => return Err(USAGE.to_string()),
+            };
+            Ok(AppCommand::Strategy(StrategyCommand::Group { watch_id, group }))
+        }
+        Some("rollback") => {
+            let watch_id = parse_watch_id(args.get(2), "usage: strategy rollback <watch_id>")?;
+            Ok(AppCommand::Strategy(StrategyCommand::Rollback { watch_id }))
+        }
+        Some("kpi") => {
+            const USAGE: &str =
+                "usage: strategy kpi <watch_id> <off|[win=<pct>] [dd=<usdt>] [trades=<per_week>]>";
+            let watch_id = parse_watch_id(args.get(2), USAGE)?;
+            let targets = match args.get(3..).unwrap_or_default() {
+                [] => return Err(USAGE.to_string()),
+                [off] if off == "off" => None,
+                terms => Some(KpiTargets::parse(terms)?),
+            };
+            Ok(AppCommand::Strategy(StrategyCommand::Kpi { watch_id, targets }))
+        }
+        Some("groups") => Ok(AppCommand::Strategy(StrategyCommand::Groups)),
+        Some("leaderboard") => Ok(AppCommand::Strategy(StrategyCommand::Leaderboard)),
+        Some("funnel") => {
+            let window_hours = match args.get(2) {
+                Some(raw) => raw
+                    .trim_end_matches('h')
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|hours| *hours >= 1)
+                    .ok_or("usage: strategy funnel [hours]")?,
+                None => DEFAULT_FUNNEL_WINDOW_HOURS,
+            };
+            Ok(AppCommand::Strategy(StrategyCommand::Funnel { window_hours }))
+        }
+        Some("plugins") => Ok(AppCommand::Strategy(StrategyCommand::Plugins)),
+        Some("bulk") => {
+            const USAGE: &str = "usage: strategy bulk <group> <enable|disable|stop|delete>";
+            let group = parse_group_name(args.get(2).ok_or(USAGE)?)?;
+            let action = args
+                .get(3)
+                .and_then(|raw| StrategyGroupAction::parse(raw))
+                .ok_or(USAGE)?;
+            Ok(AppCommand::Strategy(StrategyCommand::Bulk { group, action }))
+        }
+        Some("select") => {
+            const USAGE: &str =
+                "usage: strategy select <filter...> [enable|disable|stop|delete] [confirm]";
+            let mut terms = &args[2..];
+            let confirmed = terms.last().is_some_and(|term| term == "confirm");
+            if confirmed {
+                terms = &terms[..terms.len() - 1];
+            }
+            let action = terms
+                .last()
+                .and_then(|term| StrategyGroupAction::parse(term));
+            if action.is_some() {
+                terms = &terms[..terms.len() - 1];
+            } else if confirmed {
+                return Err(USAGE.to_string());
+            }
+            if terms.is_empty() {
+                return Err(USAGE.to_string());
+            }
+            let filter = StrategyFilter::parse(terms)?;
+            Ok(AppCommand::Strategy(StrategyCommand::Select {
+                filter,
+                action,
+                confirmed,
+            }))
         }
         Some("start") => {
             let template = parse_strategy_template(
                 args.get(2),
-                "usage: strategy start <template> <instrument> --risk-pct <value> --win-rate <value> --r <value> --max-entry-slippage <value>",
+                "usage: strategy start <template> <instrument> --risk-pct <value> --win-rate <value> --r <value> --max-entry-slippage <value> [--capital <usdt>] [--ramp-start <fraction>] [--ramp-trades <n>] [--adapt-trades <n>] [--adapt-step <fraction>] [--adapt-drift <fraction>] [--script <path>]",
             )?;
             let instrument = args
                 .get(3)
-                .ok_or("usage: strategy start <template> <instrument> --risk-pct <value> --win-rate <value> --r <value> --max-entry-slippage <value>")?;
+                .ok_or("usage: strategy start <template> <instrument> --risk-pct <value> --win-rate <value> --r <value> --max-entry-slippage <value> [--capital <usdt>] [--ramp-start <fraction>] [--ramp-trades <n>] [--adapt-trades <n>] [--adapt-step <fraction>] [--adapt-drift <fraction>] [--script <path>]")?;
             let config = parse_strategy_start_flags(&args[4..])?;
+            let scripted = matches!(
+                template,
+                StrategyTemplate::ScriptLong | StrategyTemplate::ScriptShort
+            );
+            if scripted && config.script.is_none() {
+                return Err(format!("template {} needs --script <path>", template.slug()));
+            }
+            if !scripted && config.script.is_some() {
+                return Err("--script only applies to script-long and script-short".to_string());
+            }
             Ok(AppCommand::Strategy(StrategyCommand::Start {
                 template,
                 instrument: Instrument::new(normalize_instrument_symbol(instrument)),
                 config,
             }))
         }
-        _ => Err("usage: strategy <templates|start|list|show|stop|history>".to_string()),
+        _ => {
+            Err("usage: strategy <templates|start|list|show|stop|history|debug|trace|rollback|group|kpi|groups|leaderboard|funnel|plugins|bulk|select>".to_string())
+        }
     }
 }
 
@@ -166,6 +689,24 @@ fn parse_watch_id(raw: Option<&String>, usage: &str) -> Result<u64, String> {
         .map_err(|_| format!("invalid watch id: {raw}"))
 }
 
+/// Example:
+/// - `Trend` -> `trend`
+/// - `mean-rev` -> `mean-rev`
+fn parse_group_name(raw: &str) -> Result<String, String> {
+    let name = raw.trim().to_ascii_lowercase();
+    if name.is_empty()
+        || name == "none"
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "invalid strategy group: {raw}. expected letters, digits, '-' or '_'"
+        ));
+    }
+    Ok(name)
+}
+
 fn parse_strategy_template(raw: Option<&String>, usage: &str) -> Result<StrategyTemplate, String> {
     match raw.map(String::as_str) {
         Some("liquidation-breakdown-short") => Ok(StrategyTemplate::LiquidationBreakdownShort),
@@ -173,18 +714,31 @@ fn parse_strategy_template(raw: Option<&String>, usage: &str) -> Result<Strategy
         Some("price-sma-cross-short") => Ok(StrategyTemplate::PriceSmaCrossShort),
         Some("price-sma-cross-long-fast") => Ok(StrategyTemplate::PriceSmaCrossLongFast),
         Some("price-sma-cross-short-fast") => Ok(StrategyTemplate::PriceSmaCrossShortFast),
+        Some("script-long") => Ok(StrategyTemplate::ScriptLong),
+        Some("script-short") => Ok(StrategyTemplate::ScriptShort),
+        Some(slug @ ("plugin-long" | "plugin-short")) => Err(
+            format!("template {slug} only runs in backtests: backtest run {slug} <instrument> ..."),
+        ),
         Some(other) => Err(format!(
-            "unsupported strategy template: {other}. expected liquidation-breakdown-short, price-sma-cross-long, price-sma-cross-short, price-sma-cross-long-fast, or price-sma-cross-short-fast"
+            "unsupported strategy template: {other}. expected liquidation-breakdown-short, price-sma-cross-long, price-sma-cross-short, price-sma-cross-long-fast, price-sma-cross-short-fast, script-long, or script-short"
         )),
         None => Err(usage.to_string()),
     }
 }
 
 fn parse_strategy_start_flags(args: &[String]) -> Result<StrategyStartConfig, String> {
-    let mut risk_pct = 0.005;
-    let mut win_rate = 0.8;
-    let mut r_multiple = 1.5;
-    let mut max_entry_slippage_pct = 0.001;
+    let defaults = StrategyStartConfig::default();
+    let mut risk_pct = defaults.risk_pct;
+    let mut win_rate = defaults.win_rate;
+    let mut r_multiple = defaults.r_multiple;
+    let mut max_entry_slippage_pct = defaults.max_entry_slippage_pct;
+    let mut seed_capital_usdt = defaults.seed_capital_usdt;
+    let mut ramp_start = defaults.ramp.as_ref().map(|ramp| ramp.start_fraction);
+    let mut ramp_trades = defaults.ramp.as_ref().map(|ramp| ramp.full_after_trades);
+    let mut adapt_trades = defaults.adapt.as_ref().map(|adapt| adapt.window_trades);
+    let mut adapt_step = defaults.adapt.as_ref().map(|adapt| adapt.step);
+    let mut adapt_drift = defaults.adapt.as_ref().map(|adapt| adapt.max_drift);
+    let mut script = defaults.script;
     let mut index = 0usize;
 
     while index < args.len() {
@@ -195,6 +749,11 @@ fn parse_strategy_start_flags(args: &[String]) -> Result<StrategyStartConfig, St
         let value = args
             .get(index + 1)
             .ok_or_else(|| format!("missing value for {flag}"))?;
+        if flag == "--script" {
+            script = Some(PathBuf::from(value));
+            index += 2;
+            continue;
+        }
         let parsed = value
             .parse::<f64>()
             .map_err(|_| format!("invalid value for {flag}: {value}"))?;
@@ -203,6 +762,22 @@ fn parse_strategy_start_flags(args: &[String]) -> Result<StrategyStartConfig, St
             "--win-rate" => win_rate = parsed,
             "--r" => r_multiple = parsed,
             "--max-entry-slippage" => max_entry_slippage_pct = parsed,
+            "--capital" => seed_capital_usdt = Some(parsed),
+            "--ramp-start" => ramp_start = Some(parsed),
+            "--ramp-trades" => {
+                if parsed < 0.0 || parsed.fract() != 0.0 {
+                    return Err(format!("invalid value for {flag}: {value}"));
+                }
+                ramp_trades = Some(parsed as u32)
+            }
+            "--adapt-trades" => {
+                if parsed < 0.0 || parsed.fract() != 0.0 {
+                    return Err(format!("invalid value for {flag}: {value}"));
+                }
+                adapt_trades = Some(parsed as u32)
+            }
+            "--adapt-step" => adapt_step = Some(parsed),
+            "--adapt-drift" => adapt_drift = Some(parsed),
             _ => return Err(format!("unsupported strategy flag: {flag}")),
         }
         index += 2;
@@ -213,6 +788,23 @@ fn parse_strategy_start_flags(args: &[String]) -> Result<StrategyStartConfig, St
         win_rate,
         r_multiple,
         max_entry_slippage_pct,
+        seed_capital_usdt,
+        ramp: match (ramp_start, ramp_trades) {
+            (None, None) | (_, Some(0)) => None,
+            (start, trades) => Some(SizeRamp::new(
+                start.unwrap_or(DEFAULT_RAMP_START_FRACTION),
+                trades.unwrap_or(DEFAULT_RAMP_TRADES),
+            )),
+        },
+        adapt: match (adapt_trades, adapt_step, adapt_drift) {
+            (None, None, None) | (Some(0), _, _) => None,
+            (trades, step, drift) => Some(AdaptiveTuning::new(
+                trades.unwrap_or(DEFAULT_ADAPT_TRADES),
+                step.unwrap_or(DEFAULT_ADAPT_STEP),
+                drift.unwrap_or(DEFAULT_ADAPT_MAX_DRIFT),
+            )),
+        },
+        script,
     };
 
     if !(0.0 < config.risk_pct && config.risk_pct <= 1.0) {
@@ -233,6 +825,37 @@ fn parse_strategy_start_flags(args: &[String]) -> Result<StrategyStartConfig, St
             config.r_multiple
         ));
     }
+    if config
+        .seed_capital_usdt
+        .is_some_and(|capital| capital <= f64::EPSILON)
+    {
+        return Err(format!(
+            "invalid strategy capital: {}. expected capital > 0",
+            config.seed_capital_usdt.unwrap_or_default()
+        ));
+    }
+    if config
+        .ramp
+        .as_ref()
+        .is_some_and(|ramp| !(0.0 < ramp.start_fraction && ramp.start_fraction < 1.0))
+    {
+        return Err(format!(
+            "invalid strategy ramp start: {}. expected 0 < start < 1",
+            ramp_start.unwrap_or_default()
+        ));
+    }
+    if !adapt_step.is_none_or(|step| 0.0 < step && step < 1.0) {
+        return Err(format!(
+            "invalid strategy adapt step: {}. expected 0 < step < 1",
+            adapt_step.unwrap_or_default()
+        ));
+    }
+    if !adapt_drift.is_none_or(|drift| 0.0 < drift && drift < 1.0) {
+        return Err(format!(
+            "invalid strategy adapt drift: {}. expected 0 < drift < 1",
+            adapt_drift.unwrap_or_default()
+        ));
+    }
     if config.max_entry_slippage_pct <= f64::EPSILON {
         return Err(format!(
             "invalid strategy max_entry_slippage_pct: {}. expected slippage > 0",
@@ -243,15 +866,87 @@ fn parse_strategy_start_flags(args: &[String]) -> Result<StrategyStartConfig, St
     Ok(config)
 }
 
-fn parse_portfolio_command(args: &[String]) -> AppCommand {
-    match args.get(1).map(String::as_str) {
+fn parse_portfolio_command(args: &[String]) -> Result<AppCommand, String> {
+    Ok(match args.get(1).map(String::as_str) {
         None => AppCommand::Portfolio(PortfolioView::Overview),
         Some("positions") => AppCommand::Portfolio(PortfolioView::Positions),
         Some("balances") => AppCommand::Portfolio(PortfolioView::Balances),
-        Some("orders") => AppCommand::Portfolio(PortfolioView::Orders),
+        Some("orders") => AppCommand::Portfolio(PortfolioView::Orders(parse_order_filter(
+            args.get(2..).unwrap_or_default(),
+        )?)),
+        Some("history") => {
+            AppCommand::Portfolio(parse_history_view(args.get(2..).unwrap_or_default())?)
+        }
         Some("refresh") => AppCommand::Portfolio(PortfolioView::Overview),
         Some(_) => AppCommand::Portfolio(PortfolioView::Overview),
+    })
+}
+
+/// Parses `history [filters]` or `history roi <hour|day|month> [filters]`.
+///
+/// Example:
+/// - `roi day instrument=btcusdt tag=strategy:3`
+fn parse_history_view(args: &[String]) -> Result<PortfolioView, String> {
+    if args.first().map(String::as_str) != Some("roi") {
+        return Ok(PortfolioView::History(parse_order_filter(args)?));
     }
+    let usage = "usage: history roi <hour|day|month> [instrument=<symbol>] [tag=<prefix>]";
+    let bucket = args
+        .get(1)
+        .and_then(|raw| TimeBucket::parse(raw))
+        .ok_or(usage)?;
+    let mut query = HistoryQuery::default();
+    for arg in &args[2..] {
+        match arg.split_once('=') {
+            Some(("instrument", value)) if !value.is_empty() => {
+                query.instrument = Some(Instrument::new(normalize_instrument_symbol(value)));
+            }
+            Some(("tag", value)) if !value.is_empty() => query.tag = Some(value.to_string()),
+            _ => return Err(usage.to_string()),
+        }
+    }
+    Ok(PortfolioView::HistoryRoi { bucket, query })
+}
+
+/// Parses `key=value` order filters.
+///
+/// Example:
+/// - `side=buy status=filled tag=grid`
+fn parse_order_filter(args: &[String]) -> Result<OrderFilter, String> {
+    let mut filter = OrderFilter::default();
+    for arg in args {
+        let Some((key, value)) = arg.split_once('=') else {
+            return Err(format!(
+                "invalid order filter: {arg}. expected side=<buy|sell>, status=<status> or tag=<prefix>"
+            ));
+        };
+        match key {
+            "side" => {
+                filter.side = Some(match value.to_ascii_lowercase().as_str() {
+                    "buy" => Side::Buy,
+                    "sell" => Side::Sell,
+                    other => {
+                        return Err(format!(
+                            "unsupported order side filter: {other}. expected buy or sell"
+                        ))
+                    }
+                })
+            }
+            "status" => {
+                filter.status = Some(match value.to_ascii_lowercase().as_str() {
+                    "pending" | "pending-submit" => OrderStatus::PendingSubmit,
+                    "submitted" | "open" => OrderStatus::Submitted,
+                    "filled" => OrderStatus::Filled,
+                    "cancelled" | "canceled" => OrderStatus::Cancelled,
+                    "rejected" => OrderStatus::Rejected,
+                    other => return Err(format!("unsupported order status filter: {other}")),
+                })
+            }
+            "tag" if !value.is_empty() => filter.tag = Some(value.to_string()),
+            other => return Err(format!("unsupported order filter: {other}")),
+        }
+    }
+    Ok(filter)
 }
 
 pub fn parse_shell_input(line: &str) -> Result<ShellInput, String> {
@@ -284,11 +979,49 @@ pub fn parse_shell_input(line: &str) -> Result<ShellInput, String> {
         };
         return Ok(ShellInput::Mode(mode));
     }
-    parse_app_command(&args).map(ShellInput::Command)
+    if args.first().map(String::as_str) == Some("macro") {
+        return parse_macro_command(&args).map(ShellInput::Macro);
+    }
+    parse_app_command(&args).map(|command| ShellInput::Command(Box::new(command)))
+}
+
+/// Parses `/macro` subcommands.
+///
+/// Example:
+/// - `/macro record eth-setup` ... `/macro stop`
+/// - `/macro bind eth-setup 2` replays it on `F2`
+fn parse_macro_command(args: &[String]) -> Result<MacroCommand, String> {
+    const USAGE: &str =
+        "usage: /macro <record <name>|stop|play <name>|list|remove <name>|bind <name> <1-9>>";
+    let name = || {
+        args.get(2)
+            .map(|name| name.to_ascii_lowercase())
+            .ok_or_else(|| USAGE.to_string())
+    };
+    match args.get(1).map(String::as_str) {
+        Some("record") => Ok(MacroCommand::Record { name: name()? }),
+        Some("stop") => Ok(MacroCommand::Stop),
+        Some("play") => Ok(MacroCommand::Play { name: name()? }),
+        Some("list") | None => Ok(MacroCommand::List),
+        Some("remove") => Ok(MacroCommand::Remove { name: name()? }),
+        Some("bind") => {
+            let raw_slot = args.get(3).ok_or(USAGE)?;
+            let slot = raw_slot
+                .parse::<u8>()
+                .ok()
+                .filter(|slot| (1..=9).contains(slot))
+                .ok_or_else(|| format!("invalid macro hotkey: {raw_slot}. expected 1-9"))?;
+            Ok(MacroCommand::Bind {
+                name: name()?,
+                slot,
+            })
+        }
+        Some(_) => Err(USAGE.to_string()),
+    }
 }
 
 pub fn shell_help_text() -> &'static str {
-    "/portfolio [positions|balances|orders]\n/positions\n/balances\n/orders\n/close-all\n/close-symbol <instrument>\n/set-target-exposure <instrument> <target> [market|limit <price>]\n/option-order <symbol> <buy|sell> <qty> <limit_price>\n/strategy <templates|start|list|show|stop|history>\n/mode <real|demo>\n/help\n/exit"
+    "/portfolio [positions|balances|orders]\n/positions\n/balances\n/orders [side=<buy|sell>] [status=<status>] [tag=<prefix>] (ctrl+o/u/t)\n/history [side=<buy|sell>] [status=<status>] [tag=<prefix>]\n/history roi <hour|day|month> [instrument=<symbol>] [tag=<prefix>]\n/close-all\n/close-symbol <instrument>\n/set-target-exposure <instrument> <target> [market|limit <price>]\n/option-order <symbol> <buy|sell> <qty> <limit_price>\n/trailing-stop <instrument> <callback_pct|<n>atr>\n/take-profit <instrument> [profile]\n/oco [list|ev|<instrument> <take_profit_price> <stop_price> [stop_limit_price]]\n/iceberg [list|<instrument> <buy|sell> <qty> <visible_qty> <price>]\n/dom <instrument> [levels|<buy|sell> <price|b<n>|a<n>> <qty>|cancel <client_order_id>]\n/adopt [<instrument> <entry_price>]\n/flatten [instrument] [confirm] (ctrl+x)\n/panic [confirm] (ctrl+p)\n/inactivity [status|resume]\n/shadow [status]\n/news [blackout [minutes|off]] (ctrl+b)\n/events rebuild [domain=<domain,...>] [path=<file>]\n/strategy <templates|start|list|show|stop|history|debug|trace|group|kpi|groups|bulk|select>\n/alert <add|remove|list|confirm>\n/basis <start|list|stop>\n/reload-config\n/diagnostics [export [path]] (ctrl+e)\n/macro <record|stop|play|list|remove|bind>\n/mode <real|demo>\n/help\n/exit"
 }
 
 pub fn complete_shell_input(line: &str, instruments: &[String]) -> Vec<String> {
@@ -358,7 +1091,7 @@ pub fn complete_shell_input_with_market_data(
                 .to_string(),
             })
             .collect(),
-        "portfolio" => ["positions", "balances", "orders"]
+        "portfolio" => ["positions", "balances", "orders", "history"]
             .into_iter()
             .filter(|section| section.starts_with(current))
             .map(|section| ShellCompletion {
@@ -366,13 +1099,117 @@ pub fn complete_shell_input_with_market_data(
                 description: match section {
                     "positions" => "show non-flat positions after refresh",
                     "balances" => "show visible balances after refresh",
+                    "history" => "show submitted order history",
                     "orders" => "show open orders after refresh",
                     _ => "",
                 }
                 .to_string(),
             })
             .collect(),
-        "close-symbol" => {
+        "macro" if parts.len() + usize::from(trailing_space) == 2 => {
+            ["record", "stop", "play", "list", "remove", "bind"]
+                .into_iter()
+                .filter(|section| section.starts_with(current))
+                .map(|section| ShellCompletion {
+                    value: format!("/macro {section}"),
+                    description: match section {
+                        "record" => "start recording commands under a name",
+                        "stop" => "finish and save the current recording",
+                        "play" => "replay a saved macro",
+                        "list" => "show saved macros and hotkeys",
+                        "remove" => "delete a saved macro",
+                        "bind" => "replay a macro with F1-F9",
+                        _ => "",
+                    }
+                    .to_string(),
+                })
+                .collect()
+        }
+        "alert" if parts.len() + usize::from(trailing_space) == 2 => {
+            ["add", "remove", "list", "confirm"]
+                .into_iter()
+                .filter(|section| section.starts_with(current))
+                .map(|section| ShellCompletion {
+                    value: format!("/alert {section}"),
+                    description: match section {
+                        "add" => "place an alert line: <instrument> <price> [action]",
+                        "remove" => "delete one alert line",
+                        "list" => "show alert lines",
+                        "confirm" => "place the order a fired alert is holding",
+                        _ => "",
+                    }
+                    .to_string(),
+                })
+                .collect()
+        }
+        "basis" if parts.len() + usize::from(trailing_space) == 2 => ["start", "list", "stop"]
+            .into_iter()
+            .filter(|section| section.starts_with(current))
+            .map(|section| ShellCompletion {
+                value: format!("/basis {section}"),
+                description: match section {
+                    "start" => "watch a basis: <instrument> entry=<bps> [exit=<bps>] target=<exposure> [funding=<apr_pct>]",
+                    "list" => "show basis watches and their carry",
+                    "stop" => "unwind and remove one basis watch",
+                    _ => "",
+                }
+                .to_string(),
+            })
+            .collect(),
+        "events" if parts.len() + usize::from(trailing_space) == 2 => ["rebuild"]
+            .into_iter()
+            .filter(|section| section.starts_with(current))
+            .map(|section| ShellCompletion {
+                value: format!("/events {section}"),
+                description: "replay the event file: [domain=<domain,...>] [path=<file>]"
+                    .to_string(),
+            })
+            .collect(),
+        "inactivity" if parts.len() + usize::from(trailing_space) == 2 => ["status", "resume"]
+            .into_iter()
+            .filter(|section| section.starts_with(current))
+            .map(|section| ShellCompletion {
+                value: format!("/inactivity {section}"),
+                description: match section {
+                    "status" => "show the unattended-session timer",
+                    "resume" => "re-enable entries after the session was flattened",
+                    _ => "",
+                }
+                .to_string(),
+            })
+            .collect(),
+        "diagnostics" if parts.len() + usize::from(trailing_space) == 2 => ["export"]
+            .into_iter()
+            .filter(|section| section.starts_with(current))
+            .map(|section| ShellCompletion {
+                value: format!("/diagnostics {section}"),
+                description: "zip a bug report bundle: [path]".to_string(),
+            })
+            .collect(),
+        "news" if parts.len() + usize::from(trailing_space) == 2 => ["blackout"]
+            .into_iter()
+            .filter(|section| section.starts_with(current))
+            .map(|section| ShellCompletion {
+                value: format!("/news {section}"),
+                description: "hold new entries: [minutes|off]".to_string(),
+            })
+            .collect(),
+        "dom" if parts.len() + usize::from(trailing_space) == 3 => ["buy", "sell", "cancel"]
+            .into_iter()
+            .filter(|action| action.starts_with(current))
+            .map(|action| ShellCompletion {
+                value: format!("/dom {} {action}", parts[1]),
+                description: match action {
+                    "buy" => "rest a buy limit: <price|b<n>|a<n>> <qty>",
+                    "sell" => "rest a sell limit: <price|b<n>|a<n>> <qty>",
+                    "cancel" => "cancel a resting order: <client_order_id>",
+                    _ => "",
+                }
+                .to_string(),
+            })
+            .collect(),
+        "close-symbol" | "trailing-stop" | "take-profit" | "oco" | "iceberg" | "dom" | "adopt"
+        | "flatten" => {
             let normalized_prefix = fallback_base_symbol(current)
                 .as_deref()
                 .map(normalize_instrument_symbol);
@@ -399,6 +1236,13 @@ pub fn complete_shell_input_with_market_data(
                     description: match command {
                         "close-symbol" => "submit a close order for this instrument",
                         "set-target-exposure" => "plan and submit toward target exposure",
+                        "trailing-stop" => "protect this position with a trailing stop",
+                        "take-profit" => "scale out of this position with a take-profit ladder",
+                        "oco" => "bracket this position: <take_profit_price> <stop_price> [stop_limit_price]",
+                        "iceberg" => "work a limit in slices: <buy|sell> <qty> <visible_qty> <price>",
+                        "dom" => "open the depth ladder for this instrument",
+                        "adopt" => "adopt this external holding: <entry_price>",
+                        "flatten" => "cancel orders and close this instrument",
                         _ => "",
                     }
                     .to_string(),
@@ -445,28 +1289,58 @@ fn complete_strategy_input(
     };
 
     match arg_index {
-        1 => ["templates", "start", "list", "show", "stop", "history"]
-            .into_iter()
-            .filter(|item| item.starts_with(current))
-            .map(|item| ShellCompletion {
-                value: format!("/strategy {item}"),
-                description: match item {
-                    "templates" => "show available strategy templates",
-                    "start" => "arm a strategy watch",
-                    "list" => "show active strategy watches",
-                    "show" => "show one strategy watch",
-                    "stop" => "stop one active strategy watch",
-                    "history" => "show finished strategy watches",
-                    _ => "",
-                }
-                .to_string(),
-            })
-            .collect(),
+        1 => [
+            "templates",
+            "start",
+            "list",
+            "show",
+            "stop",
+            "history",
+            "debug",
+            "trace",
+            "rollback",
+            "group",
+            "kpi",
+            "groups",
+            "leaderboard",
+            "funnel",
+            "plugins",
+            "bulk",
+            "select",
+        ]
+        .into_iter()
+        .filter(|item| item.starts_with(current))
+        .map(|item| ShellCompletion {
+            value: format!("/strategy {item}"),
+            description: match item {
+                "templates" => "show available strategy templates",
+                "start" => "arm a strategy watch",
+                "list" => "search and page active strategy watches",
+                "show" => "show one strategy watch",
+                "stop" => "stop one active strategy watch",
+                "history" => "search and page finished strategy watches",
+                "debug" => "toggle the decision trace for one watch",
+                "trace" => "show or dump one watch's decision trace",
+                "rollback" => "undo one watch's latest parameter adaptation",
+                "group" => "label a watch with a group name",
+                "kpi" => "set win rate, drawdown and trade rate targets",
+                "groups" => "show state and PnL per group",
+                "leaderboard" => "rank watches by risk-adjusted return and show capital weights",
+                "funnel" => "show signals, risk passes, submits and fills per watch",
+                "plugins" => "list wasm strategy plugins and check their exports",
+                "bulk" => "enable, disable, stop or delete every watch in a group",
+                "select" => "preview or act on watches matching a filter",
+                _ => "",
+            }
+            .to_string(),
+        })
+        .collect(),
         2 if parts.first().copied() == Some("strategy")
             && parts.get(1).copied() == Some("start") =>
         {
             StrategyTemplate::all()
                 .into_iter()
+                .chain([StrategyTemplate::ScriptLong, StrategyTemplate::ScriptShort])
                 .filter(|template| template.slug().starts_with(current))
                 .map(|template| ShellCompletion {
                     value: format!("/strategy start {}", template.slug()),
@@ -536,9 +1410,11 @@ fn complete_strategy_start_instrument(
         .collect()
 }
 
+/// Appends `USDT` to a bare base asset; option symbols and registered
+/// synthetic names pass through unchanged.
 pub fn normalize_instrument_symbol(raw: &str) -> String {
     let upper = raw.trim().to_ascii_uppercase();
-    if looks_like_option_symbol(&upper) {
+    if looks_like_option_symbol(&upper) || is_registered_synthetic(&upper) {
         return upper;
     }
     let known_quotes = ["USDT", "USDC", "BUSD", "FDUSD"];
@@ -875,7 +1751,7 @@ struct ShellCommandSpec {
     description: &'static str,
 }
 
-fn shell_commands() -> [ShellCommandSpec; 12] {
+fn shell_commands() -> [ShellCommandSpec; 30] {
     [
         ShellCommandSpec {
             name: "portfolio",
@@ -893,6 +1769,10 @@ fn shell_commands() -> [ShellCommandSpec; 12] {
             name: "orders",
             description: "refresh and show open orders",
         },
+        ShellCommandSpec {
+            name: "history",
+            description: "show submitted order history",
+        },
         ShellCommandSpec {
             name: "close-all",
             description: "submit close orders for all currently open instruments",
@@ -909,10 +1789,79 @@ fn shell_commands() -> [ShellCommandSpec; 12] {
             name: "option-order",
             description: "submit a Binance options limit order",
         },
+        ShellCommandSpec {
+            name: "trailing-stop",
+            description: "protect a position with a trailing stop",
+        },
+        ShellCommandSpec {
+            name: "take-profit",
+            description: "scale out at profile targets and trail the runner",
+        },
+        ShellCommandSpec {
+            name: "oco",
+            description: "bracket a futures position with a take-profit and stop",
+        },
+        ShellCommandSpec {
+            name: "iceberg",
+            description: "work a large futures limit order in small visible slices",
+        },
+        ShellCommandSpec {
+            name: "dom",
+            description: "depth ladder with limit entry and cancel at its levels",
+        },
+        ShellCommandSpec {
+            name: "adopt",
+            description: "list or adopt positions opened outside the app",
+        },
+        ShellCommandSpec {
+            name: "flatten",
+            description: "cancel orders and close one instrument at market (ctrl+x)",
+        },
+        ShellCommandSpec {
+            name: "panic",
+            description:
+                "disable all strategies, cancel all orders and flatten everything (ctrl+p)",
+        },
+        ShellCommandSpec {
+            name: "inactivity",
+            description: "unattended-session flat mode status and resume",
+        },
+        ShellCommandSpec {
+            name: "shadow",
+            description: "compare live strategy fills and PnL with their paper twins",
+        },
+        ShellCommandSpec {
+            name: "events",
+            description: "replay the persisted event file into the event log",
+        },
+        ShellCommandSpec {
+            name: "news",
+            description: "recent headlines and a 30m entry blackout (ctrl+b)",
+        },
         ShellCommandSpec {
             name: "strategy",
             description: "manage event-driven strategy watches",
         },
+        ShellCommandSpec {
+            name: "alert",
+            description: "manage price alert lines and their actions",
+        },
+        ShellCommandSpec {
+            name: "basis",
+            description: "hold long spot / short perp hedges while the basis or funding pays",
+        },
+        ShellCommandSpec {
+            name: "reload-config",
+            description: "reload risk caps, locale, theme and exit settings (ctrl+r)",
+        },
+        ShellCommandSpec {
+            name: "diagnostics",
+            description: "show host metrics, or export a bug report bundle (ctrl+e)",
+        },
+        ShellCommandSpec {
+            name: "macro",
+            description: "record and replay command sequences (F1-F9)",
+        },
         ShellCommandSpec {
             name: "mode",
             description: "switch between real and demo Binance endpoints",
@@ -3,11 +3,13 @@ use std::path::Path;
 use duckdb::{params, AccessMode, Config, Connection};
 
 use crate::app::bootstrap::BinanceMode;
-use crate::backtest_app::runner::{BacktestExitReason, BacktestReport, BacktestTrade};
+use crate::backtest_app::runner::{BacktestReport, BacktestTrade};
 use crate::dataset::types::{
     BacktestDatasetSummary, BacktestRunSummaryRow, BookTickerRow, DerivedKlineRow,
-    LiquidationEventRow, RecorderMetrics,
+    ExitReasonBreakdownRow, LiquidationEventRow, OpenInterestRow, RecorderMetrics,
 };
+use crate::domain::position::Side;
+use crate::domain::reason::ExitReason;
 use crate::error::storage_error::StorageError;
 use crate::strategy::model::StrategyTemplate;
 
@@ -42,6 +44,12 @@ pub fn metrics_for_path(db_path: &Path) -> Result<RecorderMetrics, StorageError>
         book_ticker_events: query_count(&connection, "raw_book_ticker")?,
         agg_trade_events: query_count(&connection, "raw_agg_trades")?,
         derived_kline_1s_bars: query_count(&connection, "derived_kline_1s")?,
+        trade_id_gaps: 0,
+        missed_trades: 0,
+        backfilled_trades: 0,
+        duplicate_ticks: 0,
+        reordered_ticks: 0,
+        late_ticks: 0,
         schema_version: query_schema_version(&connection)?,
         last_liquidation_event_time: query_latest_timestamp(
             &connection,
@@ -249,6 +257,56 @@ pub fn load_book_ticker_rows_for_path(
     Ok(result)
 }
 
+pub fn load_open_interest_rows_for_path(
+    db_path: &Path,
+    symbol: &str,
+    from: chrono::NaiveDate,
+    to: chrono::NaiveDate,
+) -> Result<Vec<OpenInterestRow>, StorageError> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+    let connection = open_dataset_connection_read_only(db_path)?;
+    let from_ts = format!("{from} 00:00:00");
+    let to_ts = format!("{to} 23:59:59");
+    let mut statement = connection
+        .prepare(
+            "SELECT epoch_ms(event_time), open_interest
+             FROM raw_open_interest
+             WHERE symbol = ? AND event_time >= CAST(? AS TIMESTAMP) AND event_time <= CAST(? AS TIMESTAMP)
+             ORDER BY event_time ASC",
+        )
+        .map_err(|error| StorageError::WriteFailedWithContext {
+            message: error.to_string(),
+        })?;
+    let mut rows = statement
+        .query(params![symbol, from_ts, to_ts])
+        .map_err(|error| StorageError::WriteFailedWithContext {
+            message: error.to_string(),
+        })?;
+    let mut result = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .map_err(|error| StorageError::WriteFailedWithContext {
+            message: error.to_string(),
+        })?
+    {
+        result.push(OpenInterestRow {
+            event_time_ms: row
+                .get(0)
+                .map_err(|error| StorageError::WriteFailedWithContext {
+                    message: error.to_string(),
+                })?,
+            open_interest: row
+                .get(1)
+                .map_err(|error| StorageError::WriteFailedWithContext {
+                    message: error.to_string(),
+                })?,
+        });
+    }
+    Ok(result)
+}
+
 pub fn load_derived_kline_rows_for_path(
     db_path: &Path,
     symbol: &str,
@@ -565,6 +623,41 @@ pub fn load_backtest_run_summaries(
     Ok(result)
 }
 
+/// Closed trades grouped by template and exit reason across stored runs.
+pub fn load_exit_reason_breakdown(
+    db_path: &Path,
+) -> Result<Vec<ExitReasonBreakdownRow>, StorageError> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+    let connection = open_dataset_connection_read_only(db_path)?;
+    let mut statement = connection
+        .prepare(
+            "SELECT r.template, t.exit_reason, COUNT(*),
+                    COALESCE(SUM(t.net_pnl), 0),
+                    COALESCE(SUM(CASE WHEN t.net_pnl < 0 THEN t.net_pnl ELSE 0 END), 0)
+             FROM backtest_trades t
+             JOIN backtest_runs r ON r.run_id = t.run_id
+             WHERE t.exit_reason IS NOT NULL
+             GROUP BY r.template, t.exit_reason
+             ORDER BY r.template, t.exit_reason",
+        )
+        .map_err(storage_err)?;
+    let mut rows = statement.query([]).map_err(storage_err)?;
+    let mut result = Vec::new();
+    while let Some(row) = rows.next().map_err(storage_err)? {
+        let exit_reason_raw: String = row.get(1).map_err(storage_err)?;
+        result.push(ExitReasonBreakdownRow {
+            template: row.get(0).map_err(storage_err)?,
+            exit_reason: parse_exit_reason(&exit_reason_raw)?,
+            trades: positive_i64_to_u64(row.get::<_, i64>(2).map_err(storage_err)?),
+            net_pnl: row.get(3).map_err(storage_err)?,
+            loss_pnl: row.get(4).map_err(storage_err)?,
+        });
+    }
+    Ok(result)
+}
+
 pub fn load_backtest_report(
     db_path: &Path,
     requested_run_id: Option<i64>,
@@ -604,10 +697,11 @@ pub fn load_backtest_report(
     let template_raw: String = row.get(2).map_err(storage_err)?;
     let from_raw: String = row.get(4).map_err(storage_err)?;
     let to_raw: String = row.get(5).map_err(storage_err)?;
-    let trades = load_backtest_trades(&connection, run_id)?;
+    let template = parse_template(&template_raw)?;
+    let trades = load_backtest_trades(&connection, run_id, template.entry_side())?;
     Ok(Some(BacktestReport {
         run_id: Some(run_id),
-        template: parse_template(&template_raw)?,
+        template,
         instrument: row.get(3).map_err(storage_err)?,
         mode: parse_mode(&mode_raw)?,
         from: chrono::NaiveDate::parse_from_str(&from_raw, "%Y-%m-%d").map_err(|error| {
@@ -647,6 +741,8 @@ pub fn load_backtest_report(
         losses: positive_i64_to_u64(row.get::<_, i64>(14).map_err(storage_err)?) as usize,
         open_trades: positive_i64_to_u64(row.get::<_, i64>(12).map_err(storage_err)?) as usize,
         skipped_triggers: positive_i64_to_u64(row.get::<_, i64>(15).map_err(storage_err)?) as usize,
+        throttled_signals: 0,
+        signals: Vec::new(),
         starting_equity: row.get(16).map_err(storage_err)?,
         ending_equity: row.get(17).map_err(storage_err)?,
         net_pnl: row.get(18).map_err(storage_err)?,
@@ -705,7 +801,7 @@ fn insert_backtest_trade(
                 trade.qty,
                 trade.exit_time.map(|value| value.to_rfc3339()),
                 trade.exit_price,
-                trade.exit_reason.as_ref().map(|reason| reason.as_str()),
+                trade.exit_reason.map(ExitReason::as_str),
                 trade.gross_pnl,
                 trade.fees,
                 trade.net_pnl,
@@ -715,9 +811,12 @@ fn insert_backtest_trade(
     Ok(())
 }
 
+/// Trades carry no side column; every trade of a run opens on its
+/// template's entry side.
 fn load_backtest_trades(
     connection: &Connection,
     run_id: i64,
+    side: Side,
 ) -> Result<Vec<BacktestTrade>, StorageError> {
     let mut statement = connection
         .prepare(
@@ -736,6 +835,7 @@ fn load_backtest_trades(
         let exit_reason_raw: Option<String> = row.get(9).map_err(storage_err)?;
         result.push(BacktestTrade {
             trade_id: positive_i64_to_u64(row.get::<_, i64>(0).map_err(storage_err)?) as usize,
+            side,
             trigger_time: parse_timestamp_string(&trigger_time_raw)?,
             entry_time: parse_timestamp_string(&entry_time_raw)?,
             entry_price: row.get(3).map_err(storage_err)?,
@@ -776,22 +876,20 @@ fn parse_template(raw: &str) -> Result<StrategyTemplate, StorageError> {
         "price-sma-cross-short" => Ok(StrategyTemplate::PriceSmaCrossShort),
         "price-sma-cross-long-fast" => Ok(StrategyTemplate::PriceSmaCrossLongFast),
         "price-sma-cross-short-fast" => Ok(StrategyTemplate::PriceSmaCrossShortFast),
+        "script-long" => Ok(StrategyTemplate::ScriptLong),
+        "script-short" => Ok(StrategyTemplate::ScriptShort),
+        "plugin-long" => Ok(StrategyTemplate::PluginLong),
+        "plugin-short" => Ok(StrategyTemplate::PluginShort),
         other => Err(StorageError::WriteFailedWithContext {
             message: format!("unsupported backtest template: {other}"),
         }),
     }
 }
 
-fn parse_exit_reason(raw: &str) -> Result<BacktestExitReason, StorageError> {
-    match raw {
-        "take_profit" => Ok(BacktestExitReason::TakeProfit),
-        "stop_loss" => Ok(BacktestExitReason::StopLoss),
-        "open_at_end" => Ok(BacktestExitReason::OpenAtEnd),
-        "signal_exit" => Ok(BacktestExitReason::SignalExit),
-        other => Err(StorageError::WriteFailedWithContext {
-            message: format!("unsupported backtest exit reason: {other}"),
-        }),
-    }
+fn parse_exit_reason(raw: &str) -> Result<ExitReason, StorageError> {
+    ExitReason::parse(raw).ok_or_else(|| StorageError::WriteFailedWithContext {
+        message: format!("unsupported backtest exit reason: {raw}"),
+    })
 }
 
 fn positive_i64_to_u64(value: i64) -> u64 {
@@ -986,4 +1084,112 @@ mod tests {
         drop(write_connection);
         let _ = std::fs::remove_file(&db_path);
     }
+
+    #[test]
+    fn exit_reason_breakdown_groups_stored_trades_by_template_and_reason() {
+        use crate::backtest_app::runner::BacktestConfig;
+        use chrono::TimeZone;
+
+        let db_path = std::env::temp_dir().join(format!(
+            "sandbox-quant-query-{}.duckdb",
+            uuid::Uuid::new_v4()
+        ));
+        init_schema_for_path(&db_path).expect("init schema");
+        let at = chrono::Utc
+            .timestamp_millis_opt(1_000)
+            .single()
+            .expect("time");
+        let trade = |trade_id, exit_reason, net_pnl| BacktestTrade {
+            trade_id,
+            side: Side::Sell,
+            trigger_time: at,
+            entry_time: at,
+            entry_price: 100.0,
+            stop_price: 101.0,
+            take_profit_price: 98.0,
+            qty: 1.0,
+            exit_time: Some(at),
+            exit_price: Some(100.0),
+            exit_reason: Some(exit_reason),
+            gross_pnl: Some(net_pnl),
+            fees: Some(0.0),
+            net_pnl: Some(net_pnl),
+        };
+        let report = |template, trades| BacktestReport {
+            run_id: None,
+            template,
+            instrument: "BTCUSDT".to_string(),
+            mode: BinanceMode::Demo,
+            from: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
+            to: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
+            db_path: db_path.clone(),
+            dataset: BacktestDatasetSummary {
+                mode: BinanceMode::Demo,
+                symbol: "BTCUSDT".to_string(),
+                symbol_found: true,
+                from: "2026-03-13".to_string(),
+                to: "2026-03-13".to_string(),
+                liquidation_events: 0,
+                book_ticker_events: 0,
+                agg_trade_events: 0,
+                derived_kline_1s_bars: 0,
+            },
+            config: BacktestConfig::default(),
+            trigger_count: 0,
+            trades,
+            wins: 0,
+            losses: 0,
+            open_trades: 0,
+            skipped_triggers: 0,
+            throttled_signals: 0,
+            signals: Vec::new(),
+            starting_equity: 10_000.0,
+            ending_equity: 10_000.0,
+            net_pnl: 0.0,
+            observed_win_rate: 0.0,
+            average_net_pnl: 0.0,
+            configured_expected_value: 0.0,
+        };
+        let long = StrategyTemplate::PriceSmaCrossLong;
+        persist_backtest_report(
+            &db_path,
+            &report(
+                long,
+                vec![
+                    trade(1, ExitReason::SignalExit, -3.0),
+                    trade(2, ExitReason::SignalExit, 1.0),
+                    trade(3, ExitReason::StopLoss, -1.0),
+                ],
+            ),
+        )
+        .expect("persist first run");
+        persist_backtest_report(
+            &db_path,
+            &report(long, vec![trade(1, ExitReason::SignalExit, -2.0)]),
+        )
+        .expect("persist second run");
+
+        let rows = load_exit_reason_breakdown(&db_path).expect("breakdown");
+
+        assert_eq!(
+            rows,
+            vec![
+                ExitReasonBreakdownRow {
+                    template: "price-sma-cross-long".to_string(),
+                    exit_reason: ExitReason::SignalExit,
+                    trades: 3,
+                    net_pnl: -4.0,
+                    loss_pnl: -5.0,
+                },
+                ExitReasonBreakdownRow {
+                    template: "price-sma-cross-long".to_string(),
+                    exit_reason: ExitReason::StopLoss,
+                    trades: 1,
+                    net_pnl: -1.0,
+                    loss_pnl: -1.0,
+                },
+            ]
+        );
+        let _ = std::fs::remove_file(&db_path);
+    }
 }
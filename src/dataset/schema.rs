@@ -39,6 +39,13 @@ CREATE TABLE IF NOT EXISTS raw_book_ticker (
   ask_qty DOUBLE NOT NULL
 );
 
+CREATE TABLE IF NOT EXISTS raw_open_interest (
+  mode VARCHAR NOT NULL,
+  symbol VARCHAR NOT NULL,
+  event_time TIMESTAMP NOT NULL,
+  open_interest DOUBLE NOT NULL
+);
+
 CREATE TABLE IF NOT EXISTS raw_agg_trades (
   trade_id BIGINT,
   mode VARCHAR NOT NULL,
@@ -1,4 +1,5 @@
 use crate::app::bootstrap::BinanceMode;
+use crate::domain::reason::ExitReason;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct RecorderMetrics {
@@ -6,6 +7,17 @@ pub struct RecorderMetrics {
     pub book_ticker_events: u64,
     pub agg_trade_events: u64,
     pub derived_kline_1s_bars: u64,
+    /// aggTrade id gaps seen by the running worker; not stored.
+    pub trade_id_gaps: u64,
+    pub missed_trades: u64,
+    /// Missed trades fetched back over REST.
+    pub backfilled_trades: u64,
+    /// aggTrades dropped as repeats of one already recorded; not stored.
+    pub duplicate_ticks: u64,
+    /// aggTrades put back in event order inside the reorder window.
+    pub reordered_ticks: u64,
+    /// aggTrades that arrived after the window and were recorded out of order.
+    pub late_ticks: u64,
     pub schema_version: Option<String>,
     pub last_liquidation_event_time: Option<String>,
     pub last_book_ticker_event_time: Option<String>,
@@ -31,6 +43,13 @@ pub struct BookTickerRow {
     pub ask: f64,
 }
 
+/// Futures open interest sampled from `/fapi/v1/openInterest`, in contracts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenInterestRow {
+    pub event_time_ms: i64,
+    pub open_interest: f64,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DerivedKlineRow {
     pub open_time_ms: i64,
@@ -74,3 +93,15 @@ pub struct BacktestRunSummaryRow {
     pub net_pnl: f64,
     pub ending_equity: f64,
 }
+
+/// Closed trades of one strategy template that ended for one reason,
+/// summed over every stored run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitReasonBreakdownRow {
+    pub template: String,
+    pub exit_reason: ExitReason,
+    pub trades: u64,
+    pub net_pnl: f64,
+    /// Net PnL of the losing trades only, `0` or below.
+    pub loss_pnl: f64,
+}
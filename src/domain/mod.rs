@@ -6,3 +6,4 @@ pub mod market;
 pub mod order;
 pub mod order_type;
 pub mod position;
+pub mod reason;
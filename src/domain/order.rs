@@ -19,6 +19,8 @@ pub struct OpenOrder {
     pub instrument: Instrument,
     pub market: Market,
     pub side: Side,
+    /// Resting limit price; `None` for market and stop orders.
+    pub price: Option<f64>,
     pub orig_qty: f64,
     pub executed_qty: f64,
     pub reduce_only: bool,
@@ -1,5 +1,26 @@
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OrderType {
     Market,
-    Limit { price: f64 },
+    Limit {
+        price: f64,
+    },
+    /// Immediate-or-cancel limit; the slippage guard sends this in place of
+    /// a market order so nothing fills beyond `price`.
+    LimitIoc {
+        price: f64,
+    },
+    /// Futures-only stop that sends a market order once `stop_price` trades.
+    StopMarket {
+        stop_price: f64,
+    },
+    /// Rests a GTC limit at `price` once `stop_price` trades: `STOP` on
+    /// futures, `STOP_LOSS_LIMIT` on spot.
+    StopLimit {
+        stop_price: f64,
+        price: f64,
+    },
+    /// Futures-only native trailing stop; `callback_rate_pct` is in percent.
+    TrailingStopMarket {
+        callback_rate_pct: f64,
+    },
 }
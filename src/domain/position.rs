@@ -7,6 +7,33 @@ pub enum Side {
     Sell,
 }
 
+impl Side {
+    /// `+1` for longs and `-1` for shorts, the sign `signed_qty` carries.
+    pub fn sign(self) -> f64 {
+        match self {
+            Self::Buy => 1.0,
+            Self::Sell => -1.0,
+        }
+    }
+
+    /// `long` or `short`, the position this side opens.
+    pub fn position_label(self) -> &'static str {
+        match self {
+            Self::Buy => "long",
+            Self::Sell => "short",
+        }
+    }
+
+    /// PnL of `qty` opened on this side at `entry` and closed at `exit`.
+    ///
+    /// Example:
+    /// - `Buy`, `100 -> 103`, qty `2` -> `+6`
+    /// - `Sell`, `100 -> 103`, qty `2` -> `-6`
+    pub fn pnl(self, entry_price: f64, exit_price: f64, qty: f64) -> f64 {
+        (exit_price - entry_price) * qty * self.sign()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PositionSnapshot {
     pub instrument: Instrument,
@@ -39,4 +66,13 @@ impl PositionSnapshot {
     pub fn is_flat(&self) -> bool {
         self.signed_qty.abs() <= f64::EPSILON
     }
+
+    /// Open PnL at `mark_price`; the signed quantity covers shorts.
+    ///
+    /// Example:
+    /// - `-0.5 @ 50000`, mark `49000` -> `Some(500.0)`
+    pub fn unrealized_pnl(&self, mark_price: f64) -> Option<f64> {
+        self.entry_price
+            .map(|entry| (mark_price - entry) * self.signed_qty)
+    }
 }
@@ -0,0 +1,126 @@
+/// Why a trade closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ExitReason {
+    TakeProfit,
+    StopLoss,
+    SignalExit,
+    OpenAtEnd,
+    /// Held past the symbol's hard maximum age, whatever the strategy said.
+    MaxAge,
+}
+
+impl ExitReason {
+    pub const ALL: [Self; 5] = [
+        Self::TakeProfit,
+        Self::StopLoss,
+        Self::SignalExit,
+        Self::OpenAtEnd,
+        Self::MaxAge,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TakeProfit => "take_profit",
+            Self::StopLoss => "stop_loss",
+            Self::SignalExit => "signal_exit",
+            Self::OpenAtEnd => "open_at_end",
+            Self::MaxAge => "max_age",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|reason| reason.as_str() == raw)
+    }
+}
+
+/// Why an entry signal did not turn into an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SkipReason {
+    NoEquity,
+    Overtrading,
+    Throttle,
+    PositionOpen,
+    InvalidQty,
+    /// Inside the wait after the previous exit.
+    Cooldown,
+    /// Higher-timeframe trend flat, unknown or against the signal.
+    HtfDisagrees,
+    /// Expected R below the active EV gate's threshold.
+    LowExpectedValue,
+}
+
+impl SkipReason {
+    pub const ALL: [Self; 8] = [
+        Self::NoEquity,
+        Self::Overtrading,
+        Self::Throttle,
+        Self::PositionOpen,
+        Self::InvalidQty,
+        Self::Cooldown,
+        Self::HtfDisagrees,
+        Self::LowExpectedValue,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NoEquity => "no_equity",
+            Self::Overtrading => "overtrading",
+            Self::Throttle => "throttle",
+            Self::PositionOpen => "position_open",
+            Self::InvalidQty => "invalid_qty",
+            Self::Cooldown => "cooldown",
+            Self::HtfDisagrees => "htf_disagree",
+            Self::LowExpectedValue => "low_ev",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|reason| reason.as_str() == raw)
+    }
+}
+
+/// Every reason code with its stored string form.
+///
+/// The strings are written to the database and event payloads, so they
+/// never change once released.
+///
+/// Example:
+/// - `ReasonCode::parse("stop_loss")` -> `Exit(StopLoss)`
+/// - `ReasonCode::parse("throttle")` -> `Skip(Throttle)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ReasonCode {
+    Exit(ExitReason),
+    Skip(SkipReason),
+}
+
+impl ReasonCode {
+    pub fn all() -> impl Iterator<Item = Self> {
+        ExitReason::ALL
+            .into_iter()
+            .map(Self::Exit)
+            .chain(SkipReason::ALL.into_iter().map(Self::Skip))
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Exit(reason) => reason.as_str(),
+            Self::Skip(reason) => reason.as_str(),
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::all().find(|code| code.as_str() == raw)
+    }
+}
+
+impl From<ExitReason> for ReasonCode {
+    fn from(reason: ExitReason) -> Self {
+        Self::Exit(reason)
+    }
+}
+
+impl From<SkipReason> for ReasonCode {
+    fn from(reason: SkipReason) -> Self {
+        Self::Skip(reason)
+    }
+}
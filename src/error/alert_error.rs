@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum AlertError {
+    #[error("price alert not found: id={0}")]
+    NotFound(u64),
+    #[error("price alert has no order waiting for confirmation: id={0}")]
+    NotAwaitingConfirmation(u64),
+    #[error("price alert names an unknown strategy template: id={id} template={template}")]
+    UnknownTemplate { id: u64, template: String },
+    #[error("price alert target exposure is outside -1..=1: id={id} target={target}")]
+    InvalidTarget { id: u64, target: f64 },
+}
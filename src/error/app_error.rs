@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::error::alert_error::AlertError;
+use crate::error::error_class::ErrorClass;
 use crate::error::exchange_error::ExchangeError;
 use crate::error::execution_error::ExecutionError;
 use crate::error::storage_error::StorageError;
@@ -19,6 +21,28 @@ pub enum AppError {
     Storage(#[from] StorageError),
     #[error("strategy error: {0}")]
     Strategy(#[from] StrategyError),
+    #[error("alert error: {0}")]
+    Alert(#[from] AlertError),
     #[error("ui error: {0}")]
     Ui(#[from] UiError),
 }
+
+impl AppError {
+    /// The exchange failure underneath, if this error came from the venue.
+    pub fn exchange_error(&self) -> Option<&ExchangeError> {
+        match self {
+            Self::Exchange(error)
+            | Self::Execution(ExecutionError::SubmitFailed(error))
+            | Self::Sync(SyncError::SnapshotFetchFailed(error)) => Some(error),
+            _ => None,
+        }
+    }
+
+    pub fn class(&self) -> Option<ErrorClass> {
+        self.exchange_error().map(ExchangeError::class)
+    }
+
+    pub fn hint(&self) -> Option<&'static str> {
+        self.exchange_error().and_then(ExchangeError::hint)
+    }
+}
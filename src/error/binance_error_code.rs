@@ -0,0 +1,146 @@
+use crate::error::error_class::ErrorClass;
+
+/// Binance REST error codes the operator can act on.
+///
+/// Example:
+/// - `{"code":-2010,"msg":"Account has insufficient balance..."}` -> `NewOrderRejected`
+/// - `{"code":-1021,...}` -> `InvalidTimestamp` (clock skew)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinanceErrorCode {
+    Unknown,
+    Disconnected,
+    TooManyRequests,
+    Timeout,
+    ServerBusy,
+    FilterFailure,
+    TooManyOrders,
+    InvalidTimestamp,
+    InvalidSignature,
+    MandatoryParamMissing,
+    BadPrecision,
+    InvalidSymbol,
+    NewOrderRejected,
+    CancelRejected,
+    NoSuchOrder,
+    BadApiKeyFormat,
+    RejectedApiKey,
+    MarginInsufficient,
+    ReduceOnlyRejected,
+    MinNotional,
+}
+
+impl BinanceErrorCode {
+    pub fn from_code(code: i64) -> Option<Self> {
+        Some(match code {
+            -1000 => Self::Unknown,
+            -1001 => Self::Disconnected,
+            -1003 => Self::TooManyRequests,
+            -1007 => Self::Timeout,
+            -1008 => Self::ServerBusy,
+            -1013 => Self::FilterFailure,
+            -1015 => Self::TooManyOrders,
+            -1021 => Self::InvalidTimestamp,
+            -1022 => Self::InvalidSignature,
+            -1102 => Self::MandatoryParamMissing,
+            -1111 => Self::BadPrecision,
+            -1121 => Self::InvalidSymbol,
+            -2010 => Self::NewOrderRejected,
+            -2011 => Self::CancelRejected,
+            -2013 => Self::NoSuchOrder,
+            -2014 => Self::BadApiKeyFormat,
+            -2015 => Self::RejectedApiKey,
+            -2019 => Self::MarginInsufficient,
+            -2022 => Self::ReduceOnlyRejected,
+            -4164 => Self::MinNotional,
+            _ => return None,
+        })
+    }
+
+    pub fn class(self) -> ErrorClass {
+        match self {
+            Self::Unknown
+            | Self::Disconnected
+            | Self::TooManyRequests
+            | Self::Timeout
+            | Self::ServerBusy
+            | Self::TooManyOrders => ErrorClass::Retryable,
+            Self::FilterFailure
+            | Self::InvalidTimestamp
+            | Self::BadPrecision
+            | Self::InvalidSymbol
+            | Self::NewOrderRejected
+            | Self::CancelRejected
+            | Self::NoSuchOrder
+            | Self::MarginInsufficient
+            | Self::ReduceOnlyRejected
+            | Self::MinNotional => ErrorClass::UserActionable,
+            Self::InvalidSignature
+            | Self::MandatoryParamMissing
+            | Self::BadApiKeyFormat
+            | Self::RejectedApiKey => ErrorClass::Fatal,
+        }
+    }
+
+    /// Rejections for a symbol filter, which the cached rules may have
+    /// missed after the venue changed them.
+    pub fn is_filter_rejection(self) -> bool {
+        matches!(
+            self,
+            Self::FilterFailure | Self::BadPrecision | Self::MinNotional
+        )
+    }
+
+    /// Short operator-facing explanation shown next to the raw error.
+    pub fn hint(self) -> &'static str {
+        match self {
+            Self::Unknown => "exchange reported an unknown error; retry shortly",
+            Self::Disconnected => "exchange backend disconnected; retry shortly",
+            Self::TooManyRequests => "request weight exhausted; wait before retrying",
+            Self::Timeout => "exchange timed out; order status is unknown, refresh before retrying",
+            Self::ServerBusy => "exchange is overloaded; retry shortly",
+            Self::FilterFailure => "order violates symbol filters; adjust size or price",
+            Self::TooManyOrders => "order rate limit hit; wait before placing more orders",
+            Self::InvalidTimestamp => {
+                "clock skew: local time is out of sync with the exchange; sync system time"
+            }
+            Self::InvalidSignature => "request signature rejected; check the secret key",
+            Self::MandatoryParamMissing => "request is missing a required parameter",
+            Self::BadPrecision => "quantity or price has too many decimals for this symbol",
+            Self::InvalidSymbol => "symbol is not listed on this market",
+            Self::NewOrderRejected => "new order rejected; see the exchange message",
+            Self::CancelRejected => "cancel rejected; the order may already be filled or gone",
+            Self::NoSuchOrder => "order not found on the exchange",
+            Self::BadApiKeyFormat => "API key format is invalid",
+            Self::RejectedApiKey => "API key, IP whitelist or permissions rejected",
+            Self::MarginInsufficient => "insufficient margin for this order",
+            Self::ReduceOnlyRejected => "reduce-only order would open or grow the position",
+            Self::MinNotional => "order notional is below the exchange minimum",
+        }
+    }
+
+    /// `hint` refined by the response message, since `-2010` covers
+    /// several unrelated rejections.
+    ///
+    /// Example:
+    /// - `-2010 "Account has insufficient balance..."` -> `insufficient balance for this order`
+    /// - `-2010 "Order would immediately match and take."` -> post-only hint
+    pub fn hint_for(self, message: &str) -> &'static str {
+        if self != Self::NewOrderRejected {
+            return self.hint();
+        }
+        let message = message.to_ascii_lowercase();
+        if message.contains("insufficient balance") {
+            "insufficient balance for this order"
+        } else if message.contains("immediately match") {
+            "post-only order would take liquidity; move the price away from the book"
+        } else if message.contains("trigger immediately") {
+            "stop price would trigger immediately; move it past the last price"
+        } else if message.contains("market is closed") {
+            "market is closed for this symbol"
+        } else if message.contains("disabled") || message.contains("not supported") {
+            "order type is not allowed for this account or symbol"
+        } else {
+            self.hint()
+        }
+    }
+}
@@ -0,0 +1,20 @@
+/// How the operator or caller should react to a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// Transient; the same request may succeed after a short wait.
+    Retryable,
+    /// The request itself must change (size, balance, clock, symbol).
+    UserActionable,
+    /// Configuration or credentials are wrong; retrying will not help.
+    Fatal,
+}
+
+impl ErrorClass {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Retryable => "retryable",
+            Self::UserActionable => "user_actionable",
+            Self::Fatal => "fatal",
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use thiserror::Error;
 
+use crate::error::binance_error_code::BinanceErrorCode;
+use crate::error::error_class::ErrorClass;
+
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum ExchangeError {
     #[error("network timeout")]
@@ -11,6 +14,12 @@ pub enum ExchangeError {
         endpoint: String,
         message: String,
     },
+    #[error("rest budget exhausted: venue={venue} category={category} retry_in_ms={retry_in_ms}")]
+    RateBudgetExhausted {
+        venue: &'static str,
+        category: &'static str,
+        retry_in_ms: u64,
+    },
     #[error("authentication failed: status={status} code={code:?} endpoint={endpoint} message={message}")]
     AuthenticationFailed {
         status: u16,
@@ -20,6 +29,8 @@ pub enum ExchangeError {
     },
     #[error("missing configuration: {0}")]
     MissingConfiguration(&'static str),
+    #[error("invalid configuration: {key}: {message}")]
+    InvalidConfiguration { key: &'static str, message: String },
     #[error("invalid timestamp")]
     InvalidTimestamp,
     #[error("invalid response")]
@@ -31,3 +42,58 @@ pub enum ExchangeError {
     #[error("unsupported market operation")]
     UnsupportedMarketOperation,
 }
+
+impl ExchangeError {
+    /// Binance error code carried by the response, when it is a known one.
+    pub fn binance_code(&self) -> Option<BinanceErrorCode> {
+        match self {
+            Self::InvalidTimestamp => Some(BinanceErrorCode::InvalidTimestamp),
+            Self::RemoteReject { code, .. } => BinanceErrorCode::from_code(*code),
+            Self::RateLimited { code, .. } | Self::AuthenticationFailed { code, .. } => {
+                code.and_then(BinanceErrorCode::from_code)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn class(&self) -> ErrorClass {
+        if let Some(code) = self.binance_code() {
+            return code.class();
+        }
+        match self {
+            Self::NetworkTimeout
+            | Self::RateLimited { .. }
+            | Self::RateBudgetExhausted { .. }
+            | Self::InvalidResponse
+            | Self::TransportFailure => ErrorClass::Retryable,
+            Self::InvalidTimestamp | Self::RemoteReject { .. } => ErrorClass::UserActionable,
+            Self::AuthenticationFailed { .. }
+            | Self::MissingConfiguration(_)
+            | Self::InvalidConfiguration { .. }
+            | Self::UnsupportedMarketOperation => ErrorClass::Fatal,
+        }
+    }
+
+    /// Targeted message for the operator, e.g. `insufficient balance for this order`.
+    pub fn hint(&self) -> Option<&'static str> {
+        if let Some(code) = self.binance_code() {
+            return Some(match self {
+                Self::RemoteReject { message, .. }
+                | Self::RateLimited { message, .. }
+                | Self::AuthenticationFailed { message, .. } => code.hint_for(message),
+                _ => code.hint(),
+            });
+        }
+        match self {
+            Self::NetworkTimeout => Some("network timeout; refresh before retrying"),
+            Self::RateLimited { .. } => Some("rate limited; wait before retrying"),
+            Self::RateBudgetExhausted { .. } => {
+                Some("local REST weight budget spent; wait before retrying")
+            }
+            Self::AuthenticationFailed { .. } => {
+                Some("API key, IP whitelist or permissions rejected")
+            }
+            _ => None,
+        }
+    }
+}
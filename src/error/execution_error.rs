@@ -1,5 +1,6 @@
 use thiserror::Error;
 
+use crate::error::error_class::ErrorClass;
 use crate::error::exchange_error::ExchangeError;
 
 #[derive(Debug, Error, Clone, PartialEq)]
@@ -27,6 +28,123 @@ pub enum ExecutionError {
         min_qty: f64,
         step_size: f64,
     },
+    #[error(
+        "asset exposure cap exceeded for {asset}: projected_exposure={projected_exposure:.4} cap={cap:.4}"
+    )]
+    AssetExposureCapExceeded {
+        asset: String,
+        projected_exposure: f64,
+        cap: f64,
+    },
+    #[error(
+        "ask depth too thin for {instrument}: asks_in_band_usdt={ask_notional_usdt:.2} required_usdt={required_ask_usdt:.2} imbalance={imbalance:.3}"
+    )]
+    ThinAskDepth {
+        instrument: String,
+        ask_notional_usdt: f64,
+        required_ask_usdt: f64,
+        imbalance: f64,
+    },
+    #[error(
+        "price moved beyond the slippage band for {instrument}: sized_at={reference_price:.8} last={last_price:.8} drift_bps={drift_bps:.2} max_bps={max_slippage_bps:.2}"
+    )]
+    SlippageBandExceeded {
+        instrument: String,
+        reference_price: f64,
+        last_price: f64,
+        drift_bps: f64,
+        max_slippage_bps: f64,
+    },
+    #[error("synthetic {synthetic} leg {leg} target {target:.4} is outside -1..=1")]
+    SyntheticLegOutOfRange {
+        synthetic: String,
+        leg: String,
+        target: f64,
+    },
+    #[error("no resting order {client_order_id} on {instrument}")]
+    OpenOrderNotFound {
+        instrument: String,
+        client_order_id: String,
+    },
+    #[error(
+        "oco prices out of order for a {side} exit: take_profit={take_profit_price} stop={stop_price}"
+    )]
+    InvalidOcoPrices {
+        side: String,
+        take_profit_price: f64,
+        stop_price: f64,
+    },
+    #[error("stop limit {limit_price} would not fill a {side} exit triggered at {stop_price}")]
+    InvalidStopLimitPrice {
+        side: String,
+        stop_price: f64,
+        limit_price: f64,
+    },
+    #[error("limit price must be positive: {0}")]
+    InvalidLimitPrice(f64),
+    #[error("iceberg visible size {visible_qty} must be positive and at most the total {qty}")]
+    InvalidIcebergSize { qty: f64, visible_qty: f64 },
+    #[error("entries disabled by inactivity flat mode; re-enable with /inactivity resume")]
+    EntriesDisabled,
+    #[error("new entries blacked out until {0}; lift with /news blackout off")]
+    EntriesBlackout(String),
+    #[error("{instrument} is not trading ({status}); entries refused until it resumes")]
+    SymbolHalted { instrument: String, status: String },
+    #[error("{instrument} entry queued until {until} (illiquid window)")]
+    EntryQueued { instrument: String, until: String },
+    #[error("strategy watch #{watch_id} already sent {max_orders_per_min} entries this minute; entry dropped")]
+    StrategyThrottled {
+        watch_id: u64,
+        max_orders_per_min: usize,
+    },
+    #[error("API key is read-only; orders are disabled ({0})")]
+    ReadOnlyApiKey(String),
+    #[error("API key has no {market} trading permission ({permissions})")]
+    MarketNotPermitted { market: String, permissions: String },
+    #[error("no daily ATR for {0} to size an ATR trail; give a callback percent")]
+    MissingAtr(String),
+    #[error("unknown take-profit profile: {0}")]
+    UnknownTakeProfitProfile(String),
     #[error("exchange submit failed: {0}")]
     SubmitFailed(#[from] ExchangeError),
 }
+
+impl ExecutionError {
+    /// Short, stable name for counting refusals, e.g. `blackout`.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::CloseQtyTooSmall | Self::OrderQtyTooSmall { .. } => "qty_too_small",
+            Self::NoOpenPosition => "no_position",
+            Self::UnknownInstrument(_) => "unknown_instrument",
+            Self::MissingPriceContext => "no_price",
+            Self::AssetExposureCapExceeded { .. } => "exposure_cap",
+            Self::ThinAskDepth { .. } => "thin_depth",
+            Self::SlippageBandExceeded { .. } => "slippage_band",
+            Self::SyntheticLegOutOfRange { .. } => "synthetic_leg_range",
+            Self::OpenOrderNotFound { .. } => "order_not_found",
+            Self::InvalidOcoPrices { .. }
+            | Self::InvalidStopLimitPrice { .. }
+            | Self::InvalidLimitPrice(_)
+            | Self::InvalidIcebergSize { .. } => "invalid_order",
+            Self::EntriesDisabled => "entries_disabled",
+            Self::EntriesBlackout(_) => "blackout",
+            Self::SymbolHalted { .. } => "symbol_halted",
+            Self::EntryQueued { .. } => "queued_illiquid",
+            Self::StrategyThrottled { .. } => "throttled",
+            Self::ReadOnlyApiKey(_) => "read_only_key",
+            Self::MarketNotPermitted { .. } => "market_not_permitted",
+            Self::MissingAtr(_) => "missing_atr",
+            Self::UnknownTakeProfitProfile(_) => "unknown_profile",
+            Self::SubmitFailed(_) => "exchange_rejected",
+        }
+    }
+
+    /// Class of the exchange failure behind a failed submit; `None` for
+    /// refusals raised locally.
+    pub fn class(&self) -> Option<ErrorClass> {
+        match self {
+            Self::SubmitFailed(error) => Some(error.class()),
+            _ => None,
+        }
+    }
+}
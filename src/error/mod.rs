@@ -1,7 +1,12 @@
+pub mod alert_error;
 pub mod app_error;
+pub mod binance_error_code;
+pub mod error_class;
 pub mod error_code;
 pub mod exchange_error;
 pub mod execution_error;
+pub mod plugin_error;
+pub mod script_error;
 pub mod severity;
 pub mod storage_error;
 pub mod strategy_error;
@@ -0,0 +1,40 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PluginError {
+    #[error("plugin read failed: path={path} error={message}")]
+    Read { path: String, message: String },
+    #[error("plugin is not a wasm module")]
+    NotWasm,
+    #[error("plugin wasm malformed: {0}")]
+    Malformed(String),
+    #[error("plugin imports host functions, so it is not sandboxed: {0}")]
+    Imports(String),
+    #[error("plugin is missing export: {0}")]
+    MissingExport(&'static str),
+    #[error("plugin failed to instantiate: {0}")]
+    Instantiate(String),
+    #[error("plugin trapped in {export}: {message}")]
+    Trap {
+        export: &'static str,
+        message: String,
+    },
+    #[error("plugin returned an unknown signal code: {0}")]
+    UnknownSignal(i32),
+}
+
+impl PluginError {
+    /// Short snake_case label for listings.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::Read { .. } => "read_failed",
+            Self::NotWasm => "not_wasm",
+            Self::Malformed(_) => "malformed",
+            Self::Imports(_) => "imports",
+            Self::MissingExport(_) => "missing_export",
+            Self::Instantiate(_) => "instantiate_failed",
+            Self::Trap { .. } => "trapped",
+            Self::UnknownSignal(_) => "unknown_signal",
+        }
+    }
+}
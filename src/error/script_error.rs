@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    #[error("strategy script read failed: path={path} error={message}")]
+    Read { path: String, message: String },
+    #[error("strategy script invalid: line={line} {message}")]
+    Parse { line: usize, message: String },
+    #[error("strategy script has no {0}() function")]
+    MissingRule(&'static str),
+    #[error("strategy script failed: {0}")]
+    Runtime(String),
+}
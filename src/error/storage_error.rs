@@ -6,10 +6,22 @@ pub enum StorageError {
     WriteFailed,
     #[error("write failed: {message}")]
     WriteFailedWithContext { message: String },
+    #[error("read failed: path={path} message={message}")]
+    ReadFailed { path: String, message: String },
+    #[error("parse failed: path={path} message={message}")]
+    ParseFailed { path: String, message: String },
+    #[error("store was not loaded, refusing to overwrite it: {path}")]
+    NotLoaded { path: String },
     #[error("recorder already running: mode={mode}")]
     RecorderAlreadyRunning { mode: String },
     #[error("recorder not running: mode={mode}")]
     RecorderNotRunning { mode: String },
     #[error("database init failed: path={path} message={message}")]
     DatabaseInitFailed { path: String, message: String },
+    #[error("query failed: {message}")]
+    QueryFailed { message: String },
+    #[error("sealed storage failed: {message}")]
+    SealFailed { message: String },
+    #[error("store file corrupted with no good backup: {path}")]
+    CorruptStore { path: String },
 }
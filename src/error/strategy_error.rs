@@ -1,12 +1,24 @@
 use thiserror::Error;
 
+use crate::error::script_error::ScriptError;
+
 #[derive(Debug, Error, Clone, PartialEq)]
 pub enum StrategyError {
     #[error("strategy watch not found: id={0}")]
     WatchNotFound(u64),
+    #[error("basis watch not found: id={0}")]
+    BasisWatchNotFound(u64),
+    #[error("strategy watch has no adaptation to roll back: id={0}")]
+    NoAdaptation(u64),
+    #[error("strategy group has no active watches: {0}")]
+    GroupNotFound(String),
+    #[error("strategy watch position has no entry or mark price to hand over: id={0}")]
+    NoHandoverPrice(u64),
     #[error("strategy watch already armed: template={template} instrument={instrument}")]
     DuplicateWatch {
         template: &'static str,
         instrument: String,
     },
+    #[error("{0}")]
+    Script(#[from] ScriptError),
 }
@@ -6,6 +6,8 @@ use crate::error::exchange_error::ExchangeError;
 pub enum SyncError {
     #[error("stream stale")]
     StreamStale,
+    #[error("no external position to adopt for {0}")]
+    NoExternalPosition(String),
     #[error("snapshot fetch failed")]
     SnapshotFetchFailed(#[from] ExchangeError),
 }
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::env;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::domain::market::Market;
+use crate::error::exchange_error::ExchangeError;
+use crate::exchange::binance::orders::{RawCommissionRates, RawSymbolRules};
+use crate::exchange::types::DailyBar;
+
+pub const DEFAULT_STATIC_CACHE_TTL_SECS: u64 = 3_600;
+pub const DEFAULT_DAILY_BARS_CACHE_TTL_SECS: u64 = 300;
+
+/// How long each static REST response may be reused.
+///
+/// A zero TTL disables caching for that endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestCacheTtls {
+    /// `exchangeInfo` lot-size filters per symbol.
+    pub symbol_rules: Duration,
+    /// The options `exchangeInfo` symbol list.
+    pub option_symbols: Duration,
+    /// Maker/taker commission per symbol.
+    pub commission_rates: Duration,
+    /// Daily klines behind the symbol selector stats; not static, so
+    /// `SANDBOX_QUANT_REST_CACHE_TTL_SECS` leaves it alone.
+    pub daily_bars: Duration,
+}
+
+impl Default for RestCacheTtls {
+    fn default() -> Self {
+        let ttl = Duration::from_secs(DEFAULT_STATIC_CACHE_TTL_SECS);
+        Self {
+            symbol_rules: ttl,
+            option_symbols: ttl,
+            commission_rates: ttl,
+            daily_bars: Duration::from_secs(DEFAULT_DAILY_BARS_CACHE_TTL_SECS),
+        }
+    }
+}
+
+impl RestCacheTtls {
+    /// Reads:
+    /// - `SANDBOX_QUANT_REST_CACHE_TTL_SECS` (applies to every static endpoint, `0` disables)
+    pub fn from_env() -> Self {
+        env::var("SANDBOX_QUANT_REST_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|raw| raw.trim().parse::<u64>().ok())
+            .map(|secs| {
+                let ttl = Duration::from_secs(secs);
+                Self {
+                    symbol_rules: ttl,
+                    option_symbols: ttl,
+                    commission_rates: ttl,
+                    ..Self::default()
+                }
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Shared cache for REST endpoints whose responses rarely change.
+///
+/// Clones of `BinanceExchange` share one cache, so every caller sizing
+/// orders on the same symbol spends one `exchangeInfo` request per TTL.
+/// Failed loads are never cached.
+#[derive(Debug, Default)]
+pub struct RestResponseCache {
+    symbol_rules: TtlMap<(String, Market), RawSymbolRules>,
+    option_symbols: TtlMap<(), Vec<String>>,
+    commission_rates: TtlMap<(String, Market), Option<RawCommissionRates>>,
+    daily_bars: TtlMap<(String, Market), Vec<DailyBar>>,
+}
+
+impl RestResponseCache {
+    pub fn new(ttls: RestCacheTtls) -> Self {
+        Self {
+            symbol_rules: TtlMap::new(ttls.symbol_rules),
+            option_symbols: TtlMap::new(ttls.option_symbols),
+            commission_rates: TtlMap::new(ttls.commission_rates),
+            daily_bars: TtlMap::new(ttls.daily_bars),
+        }
+    }
+
+    pub fn symbol_rules(
+        &self,
+        symbol: &str,
+        market: Market,
+        load: impl FnOnce() -> Result<RawSymbolRules, ExchangeError>,
+    ) -> Result<RawSymbolRules, ExchangeError> {
+        self.symbol_rules
+            .get_or_load((symbol.to_string(), market), load)
+    }
+
+    pub fn option_symbols(
+        &self,
+        load: impl FnOnce() -> Result<Vec<String>, ExchangeError>,
+    ) -> Result<Vec<String>, ExchangeError> {
+        self.option_symbols.get_or_load((), load)
+    }
+
+    pub fn commission_rates(
+        &self,
+        symbol: &str,
+        market: Market,
+        load: impl FnOnce() -> Result<Option<RawCommissionRates>, ExchangeError>,
+    ) -> Result<Option<RawCommissionRates>, ExchangeError> {
+        self.commission_rates
+            .get_or_load((symbol.to_string(), market), load)
+    }
+
+    pub fn daily_bars(
+        &self,
+        symbol: &str,
+        market: Market,
+        load: impl FnOnce() -> Result<Vec<DailyBar>, ExchangeError>,
+    ) -> Result<Vec<DailyBar>, ExchangeError> {
+        self.daily_bars
+            .get_or_load((symbol.to_string(), market), load)
+    }
+
+    /// Drops the filters and commission cached for one symbol, e.g. after
+    /// the exchange rejects an order for a filter it no longer matches.
+    pub fn invalidate_symbol(&self, symbol: &str, market: Market) {
+        let key = (symbol.to_string(), market);
+        self.symbol_rules.remove(&key);
+        self.commission_rates.remove(&key);
+    }
+
+    pub fn invalidate_all(&self) {
+        self.symbol_rules.clear();
+        self.option_symbols.clear();
+        self.commission_rates.clear();
+        self.daily_bars.clear();
+    }
+}
+
+#[derive(Debug)]
+struct TtlMap<K, T> {
+    ttl: Duration,
+    entries: Mutex<HashMap<K, (Instant, T)>>,
+}
+
+impl<K, T> Default for TtlMap<K, T> {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_STATIC_CACHE_TTL_SECS))
+    }
+}
+
+impl<K, T> TtlMap<K, T> {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn clear(&self) {
+        self.entries.lock().expect("lock rest cache").clear();
+    }
+}
+
+impl<K: Eq + Hash, T: Clone> TtlMap<K, T> {
+    fn get_or_load(
+        &self,
+        key: K,
+        load: impl FnOnce() -> Result<T, ExchangeError>,
+    ) -> Result<T, ExchangeError> {
+        if let Some((stored_at, value)) = self.entries.lock().expect("lock rest cache").get(&key) {
+            if stored_at.elapsed() < self.ttl {
+                return Ok(value.clone());
+            }
+        }
+        let value = load()?;
+        self.entries
+            .lock()
+            .expect("lock rest cache")
+            .insert(key, (Instant::now(), value.clone()));
+        Ok(value)
+    }
+
+    fn remove(&self, key: &K) {
+        self.entries.lock().expect("lock rest cache").remove(key);
+    }
+}
@@ -0,0 +1,346 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::app::bootstrap::BinanceMode;
+use crate::domain::market::Market;
+use crate::error::exchange_error::ExchangeError;
+use crate::exchange::binance::account::RawAccountState;
+use crate::exchange::binance::client::BinanceTransport;
+use crate::exchange::binance::orders::{
+    RawCloseOrderAck, RawCloseOrderRequest, RawCommissionRates, RawOcoListAck, RawOcoListRequest,
+    RawOpenOrder, RawSymbolRules,
+};
+use crate::exchange::types::{
+    ApiPermissions, DailyBar, EarnPosition, EarnTransfer, LastTrade, MarginType, OrderBookDepth,
+    SymbolTradingStatus,
+};
+
+/// Faults injected into the demo path to exercise reconnect, retry and
+/// risk-gate handling.
+///
+/// Example:
+/// - `SANDBOX_QUANT_CHAOS=ws_drop=2,rest_timeout=10,fill_delay_ms=1500`
+/// - 2% of recorder stream messages drop the symbol stream
+/// - 10% of REST calls fail with `network timeout` before reaching Binance
+/// - every order ack is held back 1.5s
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChaosConfig {
+    pub ws_drop_pct: f64,
+    pub rest_timeout_pct: f64,
+    pub fill_delay_ms: u64,
+    /// Fixes the fault sequence so a failing run can be replayed.
+    pub seed: u64,
+}
+
+impl ChaosConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_CHAOS` (unset or invalid disables chaos mode)
+    ///
+    /// Optional:
+    /// - `SANDBOX_QUANT_CHAOS_SEED` (default: current time)
+    pub fn from_env() -> Self {
+        let Some(mut config) = std::env::var("SANDBOX_QUANT_CHAOS")
+            .ok()
+            .and_then(|raw| Self::parse(&raw).ok())
+        else {
+            return Self::default();
+        };
+        config.seed = std::env::var("SANDBOX_QUANT_CHAOS_SEED")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_nanos_opt().unwrap_or(1) as u64);
+        config
+    }
+
+    /// Example:
+    /// - `ws_drop=2,rest_timeout=10` -> `ws_drop_pct=2`, `rest_timeout_pct=10`
+    /// - `rest_timeout=150` -> error, percentages must be within `0..=100`
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+        for entry in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (key, value) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("invalid chaos entry: {entry}. expected key=value"))?;
+            let value = value.trim();
+            match key.trim() {
+                "ws_drop" => config.ws_drop_pct = parse_pct(key, value)?,
+                "rest_timeout" => config.rest_timeout_pct = parse_pct(key, value)?,
+                "fill_delay_ms" => {
+                    config.fill_delay_ms = value
+                        .parse::<u64>()
+                        .map_err(|_| format!("invalid chaos fill_delay_ms: {value}"))?
+                }
+                other => {
+                    return Err(format!(
+                        "unsupported chaos key: {other}. expected ws_drop, rest_timeout or fill_delay_ms"
+                    ))
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.ws_drop_pct > 0.0 || self.rest_timeout_pct > 0.0 || self.fill_delay_ms > 0
+    }
+
+    /// Chaos only ever runs against demo; real mode gets `None`.
+    pub fn for_mode(self, mode: BinanceMode) -> Option<Self> {
+        (self.enabled() && mode == BinanceMode::Demo).then_some(self)
+    }
+
+    /// Human-readable summary, e.g. `ws_drop=2% rest_timeout=10% fill_delay_ms=1500`.
+    pub fn summary(&self) -> String {
+        format!(
+            "ws_drop={}% rest_timeout={}% fill_delay_ms={}",
+            self.ws_drop_pct, self.rest_timeout_pct, self.fill_delay_ms
+        )
+    }
+}
+
+fn parse_pct(key: &str, value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|pct| (0.0..=100.0).contains(pct))
+        .ok_or_else(|| format!("invalid chaos {key}: {value}. expected 0..=100"))
+}
+
+/// Seeded fault dice shared by every caller of one chaos transport.
+#[derive(Debug)]
+pub struct ChaosDice {
+    state: Mutex<u64>,
+}
+
+impl ChaosDice {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Mutex::new(seed),
+        }
+    }
+
+    /// True with probability `pct` percent.
+    pub fn roll(&self, pct: f64) -> bool {
+        if pct <= 0.0 {
+            return false;
+        }
+        let mut state = self.state.lock().expect("lock chaos dice");
+        // splitmix64
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64 * 100.0 < pct
+    }
+}
+
+/// Wraps a transport and injects REST timeouts and delayed order acks.
+///
+/// Timeouts are raised before the inner call, so an injected failure never
+/// leaves an order at the venue.
+pub struct ChaosTransport {
+    inner: Arc<dyn BinanceTransport>,
+    config: ChaosConfig,
+    dice: ChaosDice,
+}
+
+impl ChaosTransport {
+    pub fn new(inner: Arc<dyn BinanceTransport>, config: ChaosConfig) -> Self {
+        Self {
+            inner,
+            config,
+            dice: ChaosDice::new(config.seed),
+        }
+    }
+
+    fn inject_timeout(&self) -> Result<(), ExchangeError> {
+        if self.dice.roll(self.config.rest_timeout_pct) {
+            return Err(ExchangeError::NetworkTimeout);
+        }
+        Ok(())
+    }
+}
+
+impl BinanceTransport for ChaosTransport {
+    fn transport_name(&self) -> &'static str {
+        self.inner.transport_name()
+    }
+
+    fn load_account_state(&self, market: Market) -> Result<RawAccountState, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_account_state(market)
+    }
+
+    fn load_last_price(&self, symbol: &str, market: Market) -> Result<f64, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_last_price(symbol, market)
+    }
+
+    fn load_last_trade(&self, symbol: &str, market: Market) -> Result<LastTrade, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_last_trade(symbol, market)
+    }
+
+    fn load_symbol_rules(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<RawSymbolRules, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_symbol_rules(symbol, market)
+    }
+
+    fn load_option_symbols(&self) -> Result<Vec<String>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_option_symbols()
+    }
+
+    fn submit_close_order(
+        &self,
+        request: RawCloseOrderRequest,
+    ) -> Result<RawCloseOrderAck, ExchangeError> {
+        self.inject_timeout()?;
+        if self.config.fill_delay_ms > 0 {
+            std::thread::sleep(Duration::from_millis(self.config.fill_delay_ms));
+        }
+        self.inner.submit_close_order(request)
+    }
+
+    fn cancel_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<(), ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.cancel_order(symbol, market, client_order_id)
+    }
+
+    fn load_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<Option<RawOpenOrder>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_order(symbol, market, client_order_id)
+    }
+
+    fn submit_oco_list(
+        &self,
+        request: RawOcoListRequest,
+    ) -> Result<Option<RawOcoListAck>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.submit_oco_list(request)
+    }
+
+    fn load_today_realized_pnl_usdt(&self) -> Result<f64, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_today_realized_pnl_usdt()
+    }
+
+    fn load_today_funding_pnl_usdt(&self) -> Result<f64, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_today_funding_pnl_usdt()
+    }
+
+    fn load_margin_ratio(&self) -> Result<Option<f64>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_margin_ratio()
+    }
+
+    fn load_order_book(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<OrderBookDepth>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_order_book(symbol, market)
+    }
+
+    fn load_commission_rates(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<RawCommissionRates>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_commission_rates(symbol, market)
+    }
+
+    fn load_daily_bars(
+        &self,
+        symbol: &str,
+        market: Market,
+        days: usize,
+    ) -> Result<Vec<DailyBar>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_daily_bars(symbol, market, days)
+    }
+
+    fn load_funding_rate(&self, symbol: &str) -> Result<Option<f64>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_funding_rate(symbol)
+    }
+
+    fn load_earn_positions(&self, asset: &str) -> Result<Option<Vec<EarnPosition>>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_earn_positions(asset)
+    }
+
+    fn transfer_earn(&self, transfer: &EarnTransfer) -> Result<bool, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.transfer_earn(transfer)
+    }
+
+    fn load_api_permissions(&self) -> Result<Option<ApiPermissions>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_api_permissions()
+    }
+
+    fn set_futures_margin_type(
+        &self,
+        symbol: &str,
+        margin_type: MarginType,
+    ) -> Result<bool, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.set_futures_margin_type(symbol, margin_type)
+    }
+
+    fn set_futures_leverage(
+        &self,
+        symbol: &str,
+        leverage: u32,
+    ) -> Result<Option<u32>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.set_futures_leverage(symbol, leverage)
+    }
+
+    fn load_symbol_status(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<SymbolTradingStatus>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.load_symbol_status(symbol, market)
+    }
+
+    fn create_listen_key(&self, market: Market) -> Result<Option<String>, ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.create_listen_key(market)
+    }
+
+    fn keepalive_listen_key(&self, market: Market, listen_key: &str) -> Result<(), ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.keepalive_listen_key(market, listen_key)
+    }
+
+    fn close_listen_key(&self, market: Market, listen_key: &str) -> Result<(), ExchangeError> {
+        self.inject_timeout()?;
+        self.inner.close_listen_key(market, listen_key)
+    }
+}
@@ -3,19 +3,28 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::domain::instrument::Instrument;
 use crate::domain::market::Market;
+use crate::domain::order::OpenOrder;
+use crate::error::binance_error_code::BinanceErrorCode;
 use crate::error::exchange_error::ExchangeError;
 use crate::exchange::binance::account::RawAccountState;
 use crate::exchange::binance::auth::BinanceAuth;
+use crate::exchange::binance::cache::{RestCacheTtls, RestResponseCache};
 use crate::exchange::binance::mapper::BinanceMapper;
 use crate::exchange::binance::orders::{
-    RawCloseOrderAck, RawCloseOrderRequest, RawOpenOrder, RawSymbolRules,
+    RawCloseOrderAck, RawCloseOrderRequest, RawCommissionRates, RawOcoListAck, RawOcoListRequest,
+    RawOpenOrder, RawSymbolRules,
 };
 use crate::exchange::facade::ExchangeFacade;
 use crate::exchange::symbol_rules::SymbolRules;
 use crate::exchange::types::{
-    AuthoritativeSnapshot, CloseOrderAccepted, CloseOrderRequest, SubmitOrderAccepted,
+    ApiPermissions, AuthoritativeSnapshot, CloseOrderAccepted, CloseOrderRequest, DailyBar,
+    EarnAccount, EarnPosition, EarnTransfer, EarnTransferKind, LastTrade, MarginType,
+    OcoListAccepted, OcoListRequest, OrderBookDepth, SubmitOrderAccepted, SymbolTradingStatus,
 };
+use crate::market_data::range_stats::{SymbolRangeStats, SYMBOL_STATS_DAYS};
+use crate::ui::timezone::DisplayTimeZone;
 use reqwest::blocking::{Client, Response};
+use reqwest::Method;
 use serde::Deserialize;
 use serde_json::Value;
 
@@ -26,6 +35,13 @@ pub trait BinanceTransport: Send + Sync {
 
     fn load_account_state(&self, market: Market) -> Result<RawAccountState, ExchangeError>;
     fn load_last_price(&self, symbol: &str, market: Market) -> Result<f64, ExchangeError>;
+    /// `load_last_price` with the venue's trade time, where it reports one.
+    fn load_last_trade(&self, symbol: &str, market: Market) -> Result<LastTrade, ExchangeError> {
+        self.load_last_price(symbol, market).map(|price| LastTrade {
+            price,
+            exchange_time_ms: None,
+        })
+    }
     fn load_symbol_rules(
         &self,
         symbol: &str,
@@ -36,22 +52,137 @@ pub trait BinanceTransport: Send + Sync {
         &self,
         request: RawCloseOrderRequest,
     ) -> Result<RawCloseOrderAck, ExchangeError>;
+    /// Cancels one resting order by the client order id it was placed with.
+    fn cancel_order(
+        &self,
+        _symbol: &str,
+        _market: Market,
+        _client_order_id: &str,
+    ) -> Result<(), ExchangeError> {
+        Err(ExchangeError::UnsupportedMarketOperation)
+    }
+    /// Status of one order, resting or finished, by the client order id it
+    /// was placed with; `None` where the venue cannot look orders up.
+    fn load_order(
+        &self,
+        _symbol: &str,
+        _market: Market,
+        _client_order_id: &str,
+    ) -> Result<Option<RawOpenOrder>, ExchangeError> {
+        Ok(None)
+    }
+    /// Places both legs as one native OCO list; `None` where the market has
+    /// none, e.g. futures.
+    fn submit_oco_list(
+        &self,
+        _request: RawOcoListRequest,
+    ) -> Result<Option<RawOcoListAck>, ExchangeError> {
+        Ok(None)
+    }
     fn load_today_realized_pnl_usdt(&self) -> Result<f64, ExchangeError>;
     fn load_today_funding_pnl_usdt(&self) -> Result<f64, ExchangeError>;
     fn load_margin_ratio(&self) -> Result<Option<f64>, ExchangeError>;
+    fn load_order_book(
+        &self,
+        _symbol: &str,
+        _market: Market,
+    ) -> Result<Option<OrderBookDepth>, ExchangeError> {
+        Ok(None)
+    }
+    /// Returns `None` when the venue exposes no per-symbol commission.
+    fn load_commission_rates(
+        &self,
+        _symbol: &str,
+        _market: Market,
+    ) -> Result<Option<RawCommissionRates>, ExchangeError> {
+        Ok(None)
+    }
+    /// Last `days` daily klines, oldest first, including today's open one.
+    fn load_daily_bars(
+        &self,
+        _symbol: &str,
+        _market: Market,
+        _days: usize,
+    ) -> Result<Vec<DailyBar>, ExchangeError> {
+        Ok(Vec::new())
+    }
+    /// Last settled funding rate of a perpetual, e.g. `0.0001` per 8h.
+    fn load_funding_rate(&self, _symbol: &str) -> Result<Option<f64>, ExchangeError> {
+        Ok(None)
+    }
+    /// Simple Earn flexible holdings of `asset`; `None` without Simple Earn.
+    fn load_earn_positions(
+        &self,
+        _asset: &str,
+    ) -> Result<Option<Vec<EarnPosition>>, ExchangeError> {
+        Ok(None)
+    }
+    /// Returns `false` without Simple Earn.
+    fn transfer_earn(&self, _transfer: &EarnTransfer) -> Result<bool, ExchangeError> {
+        Ok(false)
+    }
+    /// Returns `false` where futures margin cannot be configured.
+    fn set_futures_margin_type(
+        &self,
+        _symbol: &str,
+        _margin_type: MarginType,
+    ) -> Result<bool, ExchangeError> {
+        Ok(false)
+    }
+    /// The leverage the venue applied; `None` where it cannot be configured.
+    fn set_futures_leverage(
+        &self,
+        _symbol: &str,
+        _leverage: u32,
+    ) -> Result<Option<u32>, ExchangeError> {
+        Ok(None)
+    }
+    /// Opens a user data stream; `None` where the market has none.
+    fn create_listen_key(&self, _market: Market) -> Result<Option<String>, ExchangeError> {
+        Ok(None)
+    }
+    /// Keeps a listen key alive another 60 minutes.
+    fn keepalive_listen_key(
+        &self,
+        _market: Market,
+        _listen_key: &str,
+    ) -> Result<(), ExchangeError> {
+        Ok(())
+    }
+    fn close_listen_key(&self, _market: Market, _listen_key: &str) -> Result<(), ExchangeError> {
+        Ok(())
+    }
+    /// `None` where the key's restrictions cannot be read, e.g. demo.
+    fn load_api_permissions(&self) -> Result<Option<ApiPermissions>, ExchangeError> {
+        Ok(None)
+    }
+    /// Uncached exchangeInfo `status`; `None` where it is not published.
+    fn load_symbol_status(
+        &self,
+        _symbol: &str,
+        _market: Market,
+    ) -> Result<Option<SymbolTradingStatus>, ExchangeError> {
+        Ok(None)
+    }
 }
 
 #[derive(Clone)]
 pub struct BinanceExchange {
     transport: Arc<dyn BinanceTransport>,
     mapper: BinanceMapper,
+    cache: Arc<RestResponseCache>,
 }
 
 impl BinanceExchange {
     pub fn new(transport: Arc<dyn BinanceTransport>) -> Self {
+        Self::with_cache_ttls(transport, RestCacheTtls::default())
+    }
+
+    pub fn with_cache_ttls(transport: Arc<dyn BinanceTransport>, ttls: RestCacheTtls) -> Self {
         Self {
             transport,
             mapper: BinanceMapper,
+            cache: Arc::new(RestResponseCache::new(ttls)),
         }
     }
 
@@ -60,7 +191,62 @@ impl BinanceExchange {
     }
 
     pub fn load_option_symbols(&self) -> Result<Vec<String>, ExchangeError> {
-        self.transport.load_option_symbols()
+        self.cache
+            .option_symbols(|| self.transport.load_option_symbols())
+    }
+
+    pub fn load_commission_rates(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<Option<RawCommissionRates>, ExchangeError> {
+        self.cache.commission_rates(&instrument.0, market, || {
+            self.transport.load_commission_rates(&instrument.0, market)
+        })
+    }
+
+    /// Daily bars behind the symbol selector stats, cached for a few minutes.
+    pub fn load_daily_bars(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<Vec<DailyBar>, ExchangeError> {
+        self.cache.daily_bars(&instrument.0, market, || {
+            self.transport
+                .load_daily_bars(&instrument.0, market, SYMBOL_STATS_DAYS + 1)
+        })
+    }
+
+    /// Forces the next lookup for this symbol back to the exchange.
+    pub fn invalidate_symbol_cache(&self, instrument: &Instrument, market: Market) {
+        self.cache.invalidate_symbol(&instrument.0, market);
+    }
+
+    fn submit_raw_order(
+        &self,
+        request: CloseOrderRequest,
+    ) -> Result<RawCloseOrderAck, ExchangeError> {
+        let (instrument, market) = (request.instrument.clone(), request.market);
+        let raw = self.mapper.map_close_request(request);
+        self.transport
+            .submit_close_order(raw)
+            .inspect_err(|error| self.invalidate_on_filter_rejection(error, &instrument, market))
+    }
+
+    /// A filter rejection means the cached rules no longer match the venue,
+    /// so the next sizing reloads them.
+    fn invalidate_on_filter_rejection(
+        &self,
+        error: &ExchangeError,
+        instrument: &Instrument,
+        market: Market,
+    ) {
+        if error
+            .binance_code()
+            .is_some_and(BinanceErrorCode::is_filter_rejection)
+        {
+            self.invalidate_symbol_cache(instrument, market);
+        }
     }
 }
 
@@ -102,6 +288,13 @@ impl BinanceHttpTransport {
         }
     }
 
+    /// Same endpoints reached through `client`, e.g. one from
+    /// `NetworkConfig::http_client`.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     fn signed_get(
         &self,
         market: Market,
@@ -160,6 +353,44 @@ impl BinanceHttpTransport {
         parse_json_response(response, path)
     }
 
+    fn signed_delete(
+        &self,
+        market: Market,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<Value, ExchangeError> {
+        let mut signed_params: Vec<(&str, String)> = params.to_vec();
+        signed_params.push(("timestamp", timestamp_millis().to_string()));
+        signed_params.push(("recvWindow", self.recv_window_ms.to_string()));
+        let query = self.auth.signed_query(&signed_params);
+        let response = self
+            .client
+            .delete(format!("{}{}?{}", self.base_url(market), path, query))
+            .header("X-MBX-APIKEY", self.auth.api_key())
+            .send()
+            .map_err(map_reqwest_error)?;
+        parse_json_response(response, path)
+    }
+
+    /// Sends `params` with the API key header but no signature, as the
+    /// listen key endpoints expect.
+    fn keyed_request(
+        &self,
+        method: Method,
+        market: Market,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<Value, ExchangeError> {
+        let response = self
+            .client
+            .request(method, format!("{}{}", self.base_url(market), path))
+            .header("X-MBX-APIKEY", self.auth.api_key())
+            .query(params)
+            .send()
+            .map_err(map_reqwest_error)?;
+        parse_json_response(response, path)
+    }
+
     fn base_url(&self, market: Market) -> &str {
         match market {
             Market::Spot => &self.spot_base_url,
@@ -179,7 +410,10 @@ impl BinanceTransport for BinanceHttpTransport {
             Market::Futures => {
                 let account = self.signed_get(Market::Futures, "/fapi/v2/account", &[])?;
                 let positions = self.signed_get(Market::Futures, "/fapi/v2/positionRisk", &[])?;
-                parse_futures_account_state(account, positions)
+                let open_orders = self.signed_get(Market::Futures, "/fapi/v1/openOrders", &[])?;
+                let mut state = parse_futures_account_state(account, positions)?;
+                state.open_orders = parse_open_orders(open_orders, Market::Futures)?;
+                Ok(state)
             }
             Market::Options => {
                 let account = self.signed_get(Market::Options, "/eapi/v1/marginAccount", &[])?;
@@ -200,6 +434,36 @@ impl BinanceTransport for BinanceHttpTransport {
         parse_last_price(value, market)
     }
 
+    /// Spot's price ticker carries no time, so spot reads the newest
+    /// aggregate trade instead.
+    fn load_last_trade(&self, symbol: &str, market: Market) -> Result<LastTrade, ExchangeError> {
+        match market {
+            Market::Spot => {
+                let value = self.public_get(
+                    market,
+                    "/api/v3/aggTrades",
+                    &[("symbol", symbol.to_string()), ("limit", "1".to_string())],
+                )?;
+                parse_last_agg_trade(&value)
+            }
+            Market::Futures => {
+                let value = self.public_get(
+                    market,
+                    "/fapi/v1/ticker/price",
+                    &[("symbol", symbol.to_string())],
+                )?;
+                Ok(LastTrade {
+                    price: parse_decimal_value(&value["price"])?,
+                    exchange_time_ms: value["time"].as_i64(),
+                })
+            }
+            Market::Options => self.load_last_price(symbol, market).map(|price| LastTrade {
+                price,
+                exchange_time_ms: None,
+            }),
+        }
+    }
+
     fn load_symbol_rules(
         &self,
         symbol: &str,
@@ -219,6 +483,32 @@ impl BinanceTransport for BinanceHttpTransport {
         parse_option_symbols(value)
     }
 
+    /// Spot rejects a symbol it no longer lists with `-1121`; futures
+    /// returns every contract, so a missing one has been delisted.
+    fn load_symbol_status(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<SymbolTradingStatus>, ExchangeError> {
+        let value = match market {
+            Market::Spot => {
+                match self.public_get(
+                    market,
+                    "/api/v3/exchangeInfo",
+                    &[("symbol", symbol.to_string())],
+                ) {
+                    Err(error) if error.binance_code() == Some(BinanceErrorCode::InvalidSymbol) => {
+                        return Ok(Some(SymbolTradingStatus::from_exchange("DELISTED")));
+                    }
+                    result => result?,
+                }
+            }
+            Market::Futures => self.public_get(market, "/fapi/v1/exchangeInfo", &[])?,
+            Market::Options => return Ok(None),
+        };
+        parse_symbol_status(&value, symbol).map(Some)
+    }
+
     fn submit_close_order(
         &self,
         request: RawCloseOrderRequest,
@@ -238,10 +528,43 @@ impl BinanceTransport for BinanceHttpTransport {
                 params.push(("timeInForce", "GTC".to_string()));
                 params.push(("price", price.to_string()));
             }
+            crate::domain::order_type::OrderType::LimitIoc { price } => {
+                params.push(("type", "LIMIT".to_string()));
+                params.push(("timeInForce", "IOC".to_string()));
+                params.push(("price", price.to_string()));
+            }
+            crate::domain::order_type::OrderType::StopMarket { stop_price } => {
+                if request.market != Market::Futures {
+                    return Err(ExchangeError::UnsupportedMarketOperation);
+                }
+                params.push(("type", "STOP_MARKET".to_string()));
+                params.push(("stopPrice", stop_price.to_string()));
+            }
+            crate::domain::order_type::OrderType::StopLimit { stop_price, price } => {
+                let order_type = match request.market {
+                    Market::Spot => "STOP_LOSS_LIMIT",
+                    Market::Futures => "STOP",
+                    Market::Options => return Err(ExchangeError::UnsupportedMarketOperation),
+                };
+                params.push(("type", order_type.to_string()));
+                params.push(("timeInForce", "GTC".to_string()));
+                params.push(("price", price.to_string()));
+                params.push(("stopPrice", stop_price.to_string()));
+            }
+            crate::domain::order_type::OrderType::TrailingStopMarket { callback_rate_pct } => {
+                if request.market != Market::Futures {
+                    return Err(ExchangeError::UnsupportedMarketOperation);
+                }
+                params.push(("type", "TRAILING_STOP_MARKET".to_string()));
+                params.push(("callbackRate", callback_rate_pct.to_string()));
+            }
         }
         if request.market == Market::Futures && request.reduce_only {
             params.push(("reduceOnly", "true".to_string()));
         }
+        if let Some(client_order_id) = request.client_order_id {
+            params.push(("newClientOrderId", client_order_id));
+        }
         let path = match request.market {
             Market::Spot => "/api/v3/order",
             Market::Futures => "/fapi/v1/order",
@@ -250,6 +573,106 @@ impl BinanceTransport for BinanceHttpTransport {
         parse_order_ack(self.signed_post(request.market, path, &params)?)
     }
 
+    fn cancel_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<(), ExchangeError> {
+        let path = match market {
+            Market::Spot => "/api/v3/order",
+            Market::Futures => "/fapi/v1/order",
+            Market::Options => "/eapi/v1/order",
+        };
+        self.signed_delete(
+            market,
+            path,
+            &[
+                ("symbol", symbol.to_string()),
+                ("origClientOrderId", client_order_id.to_string()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn load_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<Option<RawOpenOrder>, ExchangeError> {
+        let path = match market {
+            Market::Spot => "/api/v3/order",
+            Market::Futures => "/fapi/v1/order",
+            Market::Options => "/eapi/v1/order",
+        };
+        let value = match self.signed_get(
+            market,
+            path,
+            &[
+                ("symbol", symbol.to_string()),
+                ("origClientOrderId", client_order_id.to_string()),
+            ],
+        ) {
+            Err(error) if error.binance_code() == Some(BinanceErrorCode::NoSuchOrder) => {
+                return Ok(None);
+            }
+            result => result?,
+        };
+        parse_open_order(&value, market).map(Some)
+    }
+
+    /// A sell list rests the take-profit as `LIMIT_MAKER` above the price
+    /// and the stop below it; a buy list mirrors the two.
+    fn submit_oco_list(
+        &self,
+        request: RawOcoListRequest,
+    ) -> Result<Option<RawOcoListAck>, ExchangeError> {
+        if request.market != Market::Spot {
+            return Ok(None);
+        }
+        let (take_profit, stop) = if request.side == "SELL" {
+            ("above", "below")
+        } else {
+            ("below", "above")
+        };
+        let mut params = vec![
+            ("symbol".to_string(), request.symbol),
+            ("side".to_string(), request.side.to_string()),
+            ("quantity".to_string(), request.qty),
+            (
+                "listClientOrderId".to_string(),
+                request.list_client_order_id,
+            ),
+            ("newOrderRespType".to_string(), "ACK".to_string()),
+            (format!("{take_profit}Type"), "LIMIT_MAKER".to_string()),
+            (
+                format!("{take_profit}Price"),
+                request.take_profit_price.to_string(),
+            ),
+            (
+                format!("{take_profit}ClientOrderId"),
+                request.take_profit_client_order_id,
+            ),
+            (format!("{stop}StopPrice"), request.stop_price.to_string()),
+            (format!("{stop}ClientOrderId"), request.stop_client_order_id),
+        ];
+        match request.stop_limit_price {
+            Some(price) => {
+                params.push((format!("{stop}Type"), "STOP_LOSS_LIMIT".to_string()));
+                params.push((format!("{stop}Price"), price.to_string()));
+                params.push((format!("{stop}TimeInForce"), "GTC".to_string()));
+            }
+            None => params.push((format!("{stop}Type"), "STOP_LOSS".to_string())),
+        }
+        let params = params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.clone()))
+            .collect::<Vec<_>>();
+        parse_oco_list_ack(self.signed_post(Market::Spot, "/api/v3/orderList/oco", &params)?)
+            .map(Some)
+    }
+
     fn load_today_realized_pnl_usdt(&self) -> Result<f64, ExchangeError> {
         load_income_total(self, "REALIZED_PNL")
     }
@@ -262,6 +685,199 @@ impl BinanceTransport for BinanceHttpTransport {
         let account = self.signed_get(Market::Futures, "/fapi/v2/account", &[])?;
         parse_margin_ratio(account)
     }
+
+    fn load_order_book(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<OrderBookDepth>, ExchangeError> {
+        let path = match market {
+            Market::Spot => "/api/v3/depth",
+            Market::Futures => "/fapi/v1/depth",
+            Market::Options => return Ok(None),
+        };
+        let value = self.public_get(
+            market,
+            path,
+            &[("symbol", symbol.to_string()), ("limit", "50".to_string())],
+        )?;
+        parse_order_book(value).map(Some)
+    }
+
+    fn load_commission_rates(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<RawCommissionRates>, ExchangeError> {
+        let params = [("symbol", symbol.to_string())];
+        match market {
+            Market::Spot => {
+                let value = self.signed_get(Market::Spot, "/api/v3/account/commission", &params)?;
+                parse_commission_rates(&value["standardCommission"], "maker", "taker").map(Some)
+            }
+            Market::Futures => {
+                let value = self.signed_get(Market::Futures, "/fapi/v1/commissionRate", &params)?;
+                parse_commission_rates(&value, "makerCommissionRate", "takerCommissionRate")
+                    .map(Some)
+            }
+            Market::Options => Ok(None),
+        }
+    }
+
+    fn load_daily_bars(
+        &self,
+        symbol: &str,
+        market: Market,
+        days: usize,
+    ) -> Result<Vec<DailyBar>, ExchangeError> {
+        let path = match market {
+            Market::Spot => "/api/v3/klines",
+            Market::Futures => "/fapi/v1/klines",
+            Market::Options => return Ok(Vec::new()),
+        };
+        let value = self.public_get(
+            market,
+            path,
+            &[
+                ("symbol", symbol.to_string()),
+                ("interval", "1d".to_string()),
+                ("limit", days.to_string()),
+            ],
+        )?;
+        parse_daily_bars(value)
+    }
+
+    fn load_funding_rate(&self, symbol: &str) -> Result<Option<f64>, ExchangeError> {
+        let value = self.public_get(
+            Market::Futures,
+            "/fapi/v1/premiumIndex",
+            &[("symbol", symbol.to_string())],
+        )?;
+        parse_decimal_value(&value["lastFundingRate"]).map(Some)
+    }
+
+    fn load_earn_positions(&self, asset: &str) -> Result<Option<Vec<EarnPosition>>, ExchangeError> {
+        let value = self.signed_get(
+            Market::Spot,
+            "/sapi/v1/simple-earn/flexible/position",
+            &[("asset", asset.to_string()), ("size", "100".to_string())],
+        )?;
+        parse_earn_positions(value).map(Some)
+    }
+
+    fn transfer_earn(&self, transfer: &EarnTransfer) -> Result<bool, ExchangeError> {
+        let products = self.signed_get(
+            Market::Spot,
+            "/sapi/v1/simple-earn/flexible/list",
+            &[("asset", transfer.asset.clone())],
+        )?;
+        let product_id = products["rows"]
+            .as_array()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row["productId"].as_str())
+            .ok_or(ExchangeError::UnsupportedMarketOperation)?;
+        let path = match transfer.kind {
+            EarnTransferKind::Subscribe => "/sapi/v1/simple-earn/flexible/subscribe",
+            EarnTransferKind::Redeem => "/sapi/v1/simple-earn/flexible/redeem",
+        };
+        let value = self.signed_post(
+            Market::Spot,
+            path,
+            &[
+                ("productId", product_id.to_string()),
+                ("amount", format!("{:.8}", transfer.amount)),
+            ],
+        )?;
+        if value["success"].as_bool() == Some(false) {
+            return Err(ExchangeError::RemoteReject {
+                code: 0,
+                message: format!("simple earn {} rejected", transfer.kind.as_str()),
+            });
+        }
+        Ok(true)
+    }
+
+    fn load_api_permissions(&self) -> Result<Option<ApiPermissions>, ExchangeError> {
+        let value = self.signed_get(Market::Spot, "/sapi/v1/account/apiRestrictions", &[])?;
+        parse_api_permissions(&value).map(Some)
+    }
+
+    fn set_futures_margin_type(
+        &self,
+        symbol: &str,
+        margin_type: MarginType,
+    ) -> Result<bool, ExchangeError> {
+        match self.signed_post(
+            Market::Futures,
+            "/fapi/v1/marginType",
+            &[
+                ("symbol", symbol.to_string()),
+                ("marginType", margin_type.binance_value().to_string()),
+            ],
+        ) {
+            Ok(_) => Ok(true),
+            Err(ExchangeError::RemoteReject { code, .. }) if code == NO_MARGIN_TYPE_CHANGE => {
+                Ok(true)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    fn set_futures_leverage(
+        &self,
+        symbol: &str,
+        leverage: u32,
+    ) -> Result<Option<u32>, ExchangeError> {
+        let value = self.signed_post(
+            Market::Futures,
+            "/fapi/v1/leverage",
+            &[
+                ("symbol", symbol.to_string()),
+                ("leverage", leverage.to_string()),
+            ],
+        )?;
+        value["leverage"]
+            .as_u64()
+            .and_then(|leverage| u32::try_from(leverage).ok())
+            .map(Some)
+            .ok_or(ExchangeError::InvalidResponse)
+    }
+
+    fn create_listen_key(&self, market: Market) -> Result<Option<String>, ExchangeError> {
+        let value = self.keyed_request(Method::POST, market, listen_key_path(market), &[])?;
+        value["listenKey"]
+            .as_str()
+            .map(|listen_key| Some(listen_key.to_string()))
+            .ok_or(ExchangeError::InvalidResponse)
+    }
+
+    fn keepalive_listen_key(&self, market: Market, listen_key: &str) -> Result<(), ExchangeError> {
+        self.keyed_request(
+            Method::PUT,
+            market,
+            listen_key_path(market),
+            &[("listenKey", listen_key.to_string())],
+        )
+        .map(|_| ())
+    }
+
+    fn close_listen_key(&self, market: Market, listen_key: &str) -> Result<(), ExchangeError> {
+        self.keyed_request(
+            Method::DELETE,
+            market,
+            listen_key_path(market),
+            &[("listenKey", listen_key.to_string())],
+        )
+        .map(|_| ())
+    }
+}
+
+fn listen_key_path(market: Market) -> &'static str {
+    match market {
+        Market::Spot => "/api/v3/userDataStream",
+        Market::Futures => "/fapi/v1/listenKey",
+        Market::Options => "/eapi/v1/listenKey",
+    }
 }
 
 impl ExchangeFacade for BinanceExchange {
@@ -304,12 +920,22 @@ impl ExchangeFacade for BinanceExchange {
         self.transport.load_last_price(&instrument.0, market)
     }
 
+    fn load_last_trade(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<LastTrade, Self::Error> {
+        self.transport.load_last_trade(&instrument.0, market)
+    }
+
     fn load_symbol_rules(
         &self,
         instrument: &Instrument,
         market: Market,
     ) -> Result<SymbolRules, Self::Error> {
-        let rules = self.transport.load_symbol_rules(&instrument.0, market)?;
+        let rules = self.cache.symbol_rules(&instrument.0, market, || {
+            self.transport.load_symbol_rules(&instrument.0, market)
+        })?;
         Ok(self.mapper.map_symbol_rules(rules))
     }
 
@@ -317,18 +943,150 @@ impl ExchangeFacade for BinanceExchange {
         &self,
         request: CloseOrderRequest,
     ) -> Result<CloseOrderAccepted, Self::Error> {
-        let raw = self.mapper.map_close_request(request);
-        let ack = self.transport.submit_close_order(raw)?;
+        let ack = self.submit_raw_order(request)?;
         Ok(self.mapper.map_close_ack(ack))
     }
 
     fn submit_order(&self, request: CloseOrderRequest) -> Result<SubmitOrderAccepted, Self::Error> {
-        let raw = self.mapper.map_close_request(request);
-        let ack = self.transport.submit_close_order(raw)?;
+        let ack = self.submit_raw_order(request)?;
         Ok(SubmitOrderAccepted {
             remote_order_id: ack.remote_order_id,
+            exchange_time_ms: ack.exchange_time_ms,
         })
     }
+
+    fn cancel_order(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<(), Self::Error> {
+        self.transport
+            .cancel_order(&instrument.0, market, client_order_id)
+    }
+
+    fn load_order(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<Option<OpenOrder>, Self::Error> {
+        Ok(self
+            .transport
+            .load_order(&instrument.0, market, client_order_id)?
+            .map(|order| self.mapper.map_order(order)))
+    }
+
+    fn submit_oco_list(
+        &self,
+        request: OcoListRequest,
+    ) -> Result<Option<OcoListAccepted>, Self::Error> {
+        let (instrument, market) = (request.instrument.clone(), request.market);
+        let raw = self.mapper.map_oco_request(request);
+        Ok(self
+            .transport
+            .submit_oco_list(raw)
+            .inspect_err(|error| self.invalidate_on_filter_rejection(error, &instrument, market))?
+            .map(|ack| self.mapper.map_oco_ack(ack)))
+    }
+
+    fn load_order_book_depth(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<Option<OrderBookDepth>, Self::Error> {
+        self.transport.load_order_book(&instrument.0, market)
+    }
+
+    fn load_funding_rate(&self, instrument: &Instrument) -> Result<Option<f64>, Self::Error> {
+        self.transport.load_funding_rate(&instrument.0)
+    }
+
+    /// Flexible savings come from Simple Earn; the idle balance is the spot
+    /// wallet's free amount, which is what a subscription draws from.
+    fn load_earn_account(&self, asset: &str) -> Result<Option<EarnAccount>, Self::Error> {
+        let Some(positions) = self.transport.load_earn_positions(asset)? else {
+            return Ok(None);
+        };
+        let idle = self
+            .transport
+            .load_account_state(Market::Spot)?
+            .balances
+            .into_iter()
+            .filter(|balance| balance.asset == asset)
+            .map(|balance| balance.free)
+            .sum();
+        Ok(Some(EarnAccount { idle, positions }))
+    }
+
+    fn transfer_earn(&self, transfer: &EarnTransfer) -> Result<bool, Self::Error> {
+        self.transport.transfer_earn(transfer)
+    }
+
+    fn set_futures_margin_type(
+        &self,
+        instrument: &Instrument,
+        margin_type: MarginType,
+    ) -> Result<bool, Self::Error> {
+        self.transport
+            .set_futures_margin_type(&instrument.0, margin_type)
+    }
+
+    fn set_futures_leverage(
+        &self,
+        instrument: &Instrument,
+        leverage: u32,
+    ) -> Result<Option<u32>, Self::Error> {
+        self.transport.set_futures_leverage(&instrument.0, leverage)
+    }
+
+    fn load_api_permissions(&self) -> Result<Option<ApiPermissions>, Self::Error> {
+        self.transport.load_api_permissions()
+    }
+
+    fn create_listen_key(&self, market: Market) -> Result<Option<String>, Self::Error> {
+        self.transport.create_listen_key(market)
+    }
+
+    fn keepalive_listen_key(&self, market: Market, listen_key: &str) -> Result<(), Self::Error> {
+        self.transport.keepalive_listen_key(market, listen_key)
+    }
+
+    fn close_listen_key(&self, market: Market, listen_key: &str) -> Result<(), Self::Error> {
+        self.transport.close_listen_key(market, listen_key)
+    }
+
+    fn load_symbol_status(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<Option<SymbolTradingStatus>, Self::Error> {
+        self.transport.load_symbol_status(&instrument.0, market)
+    }
+
+    fn load_symbol_range_stats(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<Option<SymbolRangeStats>, Self::Error> {
+        Ok(SymbolRangeStats::from_daily_bars(
+            &self.load_daily_bars(instrument, market)?,
+        ))
+    }
+
+    fn load_taker_fee_rate(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<Option<f64>, Self::Error> {
+        Ok(self
+            .load_commission_rates(instrument, market)?
+            .map(|rates| rates.taker))
+    }
+
+    fn invalidate_static_cache(&self) {
+        self.cache.invalidate_all();
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -337,6 +1095,9 @@ struct BinanceErrorBody {
     msg: String,
 }
 
+/// `-4046 No need to change margin type.`: the symbol already uses it.
+const NO_MARGIN_TYPE_CHANGE: i64 = -4046;
+
 pub fn map_binance_http_error(status: u16, body: &str, endpoint: &str) -> ExchangeError {
     if status == 429 || status == 418 {
         let (code, message) = parse_error_body(body)
@@ -531,7 +1292,7 @@ fn parse_options_account_state(
         .filter_map(parse_options_position)
         .collect::<Result<Vec<_>, ExchangeError>>()?;
 
-    let open_orders = parse_options_open_orders(open_orders_value)?;
+    let open_orders = parse_open_orders(open_orders_value, Market::Options)?;
 
     Ok(RawAccountState {
         balances,
@@ -557,6 +1318,13 @@ fn parse_symbol_rules(value: Value, market: Market) -> Result<RawSymbolRules, Ex
         .iter()
         .find(|filter| filter["filterType"].as_str() == Some("LOT_SIZE"))
         .ok_or(ExchangeError::InvalidResponse)?;
+    let tick_size = filters
+        .iter()
+        .find(|filter| filter["filterType"].as_str() == Some("PRICE_FILTER"))
+        .and_then(|filter| filter["tickSize"].as_str())
+        .map(parse_decimal)
+        .transpose()?
+        .unwrap_or(0.0);
 
     Ok(RawSymbolRules {
         min_qty: parse_decimal(
@@ -574,9 +1342,25 @@ fn parse_symbol_rules(value: Value, market: Market) -> Result<RawSymbolRules, Ex
                 .as_str()
                 .ok_or(ExchangeError::InvalidResponse)?,
         )?,
+        tick_size,
     })
 }
 
+/// Example:
+/// - `{"symbols":[{"symbol":"BTCUSDT","status":"BREAK"}]}` -> `Halted("BREAK")`
+/// - `{"symbols":[]}` -> `Delisted("DELISTED")`
+fn parse_symbol_status(value: &Value, symbol: &str) -> Result<SymbolTradingStatus, ExchangeError> {
+    let symbols = value["symbols"]
+        .as_array()
+        .ok_or(ExchangeError::InvalidResponse)?;
+    let status = symbols
+        .iter()
+        .find(|item| item["symbol"].as_str() == Some(symbol))
+        .map_or(Some("DELISTED"), |item| item["status"].as_str())
+        .ok_or(ExchangeError::InvalidResponse)?;
+    Ok(SymbolTradingStatus::from_exchange(status))
+}
+
 fn parse_option_symbols(value: Value) -> Result<Vec<String>, ExchangeError> {
     Ok(value["optionSymbols"]
         .as_array()
@@ -598,6 +1382,66 @@ fn parse_last_price(value: Value, market: Market) -> Result<f64, ExchangeError>
     parse_decimal(price)
 }
 
+/// Parses `[{"p":"64000.1","T":1775980800123,...}]`.
+fn parse_last_agg_trade(value: &Value) -> Result<LastTrade, ExchangeError> {
+    let trade = value
+        .as_array()
+        .and_then(|trades| trades.first())
+        .ok_or(ExchangeError::InvalidResponse)?;
+    Ok(LastTrade {
+        price: parse_decimal_value(&trade["p"])?,
+        exchange_time_ms: trade["T"].as_i64(),
+    })
+}
+
+/// Parses `{"<maker_key>": "0.0002", "<taker_key>": "0.0004"}`.
+fn parse_commission_rates(
+    value: &Value,
+    maker_key: &str,
+    taker_key: &str,
+) -> Result<RawCommissionRates, ExchangeError> {
+    Ok(RawCommissionRates {
+        maker: parse_decimal_value(&value[maker_key])?,
+        taker: parse_decimal_value(&value[taker_key])?,
+    })
+}
+
+/// Parses `[[open_time, "open", "high", "low", "close", "volume", close_time, "quote_volume", ...], ...]`.
+fn parse_daily_bars(value: Value) -> Result<Vec<DailyBar>, ExchangeError> {
+    value
+        .as_array()
+        .ok_or(ExchangeError::InvalidResponse)?
+        .iter()
+        .map(|kline| {
+            Ok(DailyBar {
+                high: parse_decimal_value(&kline[2])?,
+                low: parse_decimal_value(&kline[3])?,
+                close: parse_decimal_value(&kline[4])?,
+                quote_volume: parse_decimal_value(&kline[7])?,
+            })
+        })
+        .collect()
+}
+
+/// Parses `{"bids": [["price", "qty"], ...], "asks": [...]}`.
+fn parse_order_book(value: Value) -> Result<OrderBookDepth, ExchangeError> {
+    let parse_side = |side: &Value| -> Result<Vec<(f64, f64)>, ExchangeError> {
+        side.as_array()
+            .ok_or(ExchangeError::InvalidResponse)?
+            .iter()
+            .map(|level| {
+                let price = level[0].as_str().ok_or(ExchangeError::InvalidResponse)?;
+                let qty = level[1].as_str().ok_or(ExchangeError::InvalidResponse)?;
+                Ok((parse_decimal(price)?, parse_decimal(qty)?))
+            })
+            .collect()
+    };
+    Ok(OrderBookDepth {
+        bids: parse_side(&value["bids"])?,
+        asks: parse_side(&value["asks"])?,
+    })
+}
+
 fn parse_options_position(
     item: &Value,
 ) -> Option<Result<crate::exchange::binance::account::RawPosition, ExchangeError>> {
@@ -643,46 +1487,125 @@ fn parse_options_position(
     }))
 }
 
-fn parse_options_open_orders(value: Value) -> Result<Vec<RawOpenOrder>, ExchangeError> {
-    value
+/// Futures report `origQty`, options report `quantity`.
+fn parse_earn_positions(value: Value) -> Result<Vec<EarnPosition>, ExchangeError> {
+    let rows = value["rows"]
         .as_array()
-        .ok_or(ExchangeError::InvalidResponse)?
-        .iter()
-        .map(|item| {
-            Ok(RawOpenOrder {
-                order_id: item["orderId"]
-                    .as_i64()
-                    .map(|id| id.to_string())
-                    .or_else(|| item["orderId"].as_str().map(str::to_string)),
-                client_order_id: item["clientOrderId"]
+        .ok_or(ExchangeError::InvalidResponse)?;
+    rows.iter()
+        .map(|row| {
+            Ok(EarnPosition {
+                asset: row["asset"]
                     .as_str()
-                    .unwrap_or_default()
+                    .ok_or(ExchangeError::InvalidResponse)?
                     .to_string(),
-                symbol: item["symbol"]
+                product_id: row["productId"]
                     .as_str()
                     .ok_or(ExchangeError::InvalidResponse)?
                     .to_string(),
-                market: Market::Options,
-                side: match item["side"].as_str().unwrap_or("BUY") {
-                    "SELL" => "SELL",
-                    _ => "BUY",
-                },
-                orig_qty: parse_decimal_value(&item["quantity"])?,
-                executed_qty: parse_decimal_value(&item["executedQty"])?,
-                reduce_only: item["reduceOnly"].as_bool().unwrap_or(false),
-                status: item["status"].as_str().unwrap_or("NEW").to_string(),
+                amount: parse_decimal_value(&row["totalAmount"])?,
+                apr_pct: parse_decimal_value(&row["latestAnnualPercentageRate"])? * 100.0,
             })
         })
         .collect()
 }
 
+/// Options trading is read from `enableVanillaOptions`; keys created before
+/// it existed omit the flag and count as without options.
+fn parse_api_permissions(value: &Value) -> Result<ApiPermissions, ExchangeError> {
+    let flag = |key: &str| value[key].as_bool();
+    Ok(ApiPermissions {
+        reading: flag("enableReading").ok_or(ExchangeError::InvalidResponse)?,
+        spot_trading: flag("enableSpotAndMarginTrading").ok_or(ExchangeError::InvalidResponse)?,
+        futures_trading: flag("enableFutures").ok_or(ExchangeError::InvalidResponse)?,
+        options_trading: flag("enableVanillaOptions").unwrap_or(false),
+    })
+}
+
+fn parse_open_orders(value: Value, market: Market) -> Result<Vec<RawOpenOrder>, ExchangeError> {
+    value
+        .as_array()
+        .ok_or(ExchangeError::InvalidResponse)?
+        .iter()
+        .map(|item| parse_open_order(item, market))
+        .collect()
+}
+
+fn parse_open_order(item: &Value, market: Market) -> Result<RawOpenOrder, ExchangeError> {
+    Ok(RawOpenOrder {
+        order_id: item["orderId"]
+            .as_i64()
+            .map(|id| id.to_string())
+            .or_else(|| item["orderId"].as_str().map(str::to_string)),
+        client_order_id: item["clientOrderId"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        symbol: item["symbol"]
+            .as_str()
+            .ok_or(ExchangeError::InvalidResponse)?
+            .to_string(),
+        market,
+        side: match item["side"].as_str().unwrap_or("BUY") {
+            "SELL" => "SELL",
+            _ => "BUY",
+        },
+        price: item
+            .get("price")
+            .and_then(|value| parse_decimal_value(value).ok())
+            .unwrap_or(0.0),
+        orig_qty: parse_decimal_value(item.get("origQty").unwrap_or(&item["quantity"]))?,
+        executed_qty: parse_decimal_value(&item["executedQty"])?,
+        reduce_only: item["reduceOnly"].as_bool().unwrap_or(false),
+        status: item["status"].as_str().unwrap_or("NEW").to_string(),
+    })
+}
+
 fn parse_order_ack(value: Value) -> Result<RawCloseOrderAck, ExchangeError> {
     let remote_order_id = value["orderId"]
         .as_i64()
         .map(|id| id.to_string())
         .or_else(|| value["clientOrderId"].as_str().map(str::to_string))
         .ok_or(ExchangeError::InvalidResponse)?;
-    Ok(RawCloseOrderAck { remote_order_id })
+    let exchange_time_ms = value["transactTime"]
+        .as_i64()
+        .or_else(|| value["updateTime"].as_i64());
+    Ok(RawCloseOrderAck {
+        remote_order_id,
+        exchange_time_ms,
+    })
+}
+
+fn parse_oco_list_ack(value: Value) -> Result<RawOcoListAck, ExchangeError> {
+    let order_list_id = value["orderListId"]
+        .as_i64()
+        .map(|id| id.to_string())
+        .or_else(|| value["listClientOrderId"].as_str().map(str::to_string))
+        .ok_or(ExchangeError::InvalidResponse)?;
+    let legs = value["orders"]
+        .as_array()
+        .ok_or(ExchangeError::InvalidResponse)?
+        .iter()
+        .map(|order| {
+            let client_order_id = order["clientOrderId"]
+                .as_str()
+                .ok_or(ExchangeError::InvalidResponse)?;
+            let remote_order_id = order["orderId"]
+                .as_i64()
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| client_order_id.to_string());
+            Ok((client_order_id.to_string(), remote_order_id))
+        })
+        .collect::<Result<Vec<_>, ExchangeError>>()?;
+    Ok(RawOcoListAck {
+        order_list_id,
+        list_status: value["listStatusType"]
+            .as_str()
+            .unwrap_or("EXEC_STARTED")
+            .to_string(),
+        exchange_time_ms: value["transactionTime"].as_i64(),
+        legs,
+    })
 }
 
 fn parse_income_total(value: Value) -> Result<f64, ExchangeError> {
@@ -699,13 +1622,9 @@ fn load_income_total(
     transport: &BinanceHttpTransport,
     income_type: &str,
 ) -> Result<f64, ExchangeError> {
-    let start_time = chrono::Local::now()
-        .date_naive()
-        .and_hms_opt(0, 0, 0)
-        .expect("valid start of day")
-        .and_local_timezone(chrono::Local)
-        .single()
-        .expect("single local datetime")
+    // "Today" is the display zone's day, so it matches the UI clock.
+    let start_time = DisplayTimeZone::from_env()
+        .start_of_day(chrono::Utc::now())
         .timestamp_millis();
     let value = transport.signed_get(
         Market::Futures,
@@ -757,3 +1676,123 @@ fn timestamp_millis() -> u128 {
         .expect("time went backwards")
         .as_millis()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oco_list_ack_reads_list_id_status_and_both_legs() {
+        let value = serde_json::json!({
+            "orderListId": 1929,
+            "contingencyType": "OCO",
+            "listStatusType": "EXEC_STARTED",
+            "listOrderStatus": "EXECUTING",
+            "listClientOrderId": "oco-1",
+            "transactionTime": 1775980800123i64,
+            "symbol": "BTCUSDT",
+            "orders": [
+                { "symbol": "BTCUSDT", "orderId": 10, "clientOrderId": "oco-1-sl" },
+                { "symbol": "BTCUSDT", "orderId": 11, "clientOrderId": "oco-1-tp" }
+            ],
+            "orderReports": []
+        });
+
+        let ack = parse_oco_list_ack(value).expect("ack");
+
+        assert_eq!(ack.order_list_id, "1929");
+        assert_eq!(ack.list_status, "EXEC_STARTED");
+        assert_eq!(ack.exchange_time_ms, Some(1_775_980_800_123));
+        assert_eq!(
+            ack.legs,
+            vec![
+                ("oco-1-sl".to_string(), "10".to_string()),
+                ("oco-1-tp".to_string(), "11".to_string())
+            ]
+        );
+        assert!(parse_oco_list_ack(serde_json::json!({ "orderListId": 1 })).is_err());
+    }
+
+    #[test]
+    fn symbol_status_reads_the_matching_symbol_and_treats_missing_as_delisted() {
+        let value = serde_json::json!({
+            "symbols": [
+                { "symbol": "ETHUSDT", "status": "TRADING" },
+                { "symbol": "BTCUSDT", "status": "BREAK" },
+                { "symbol": "LUNAUSDT", "status": "CLOSE" },
+            ],
+        });
+
+        assert_eq!(
+            parse_symbol_status(&value, "ETHUSDT").unwrap(),
+            SymbolTradingStatus::Trading
+        );
+        assert_eq!(
+            parse_symbol_status(&value, "BTCUSDT").unwrap(),
+            SymbolTradingStatus::Halted("BREAK".to_string())
+        );
+        assert_eq!(
+            parse_symbol_status(&value, "LUNAUSDT").unwrap().kind(),
+            "delisted"
+        );
+        assert_eq!(
+            parse_symbol_status(&value, "XYZUSDT").unwrap(),
+            SymbolTradingStatus::Delisted("DELISTED".to_string())
+        );
+        assert!(parse_symbol_status(&serde_json::json!({}), "BTCUSDT").is_err());
+    }
+
+    #[test]
+    fn order_book_parses_string_levels_best_first() {
+        let book = parse_order_book(serde_json::json!({
+            "lastUpdateId": 1,
+            "bids": [["100.0", "2.5"], ["99.5", "1"]],
+            "asks": [["100.5", "0.4"]],
+        }))
+        .expect("depth should parse");
+
+        assert_eq!(book.bids, vec![(100.0, 2.5), (99.5, 1.0)]);
+        assert_eq!(book.asks, vec![(100.5, 0.4)]);
+        assert!(parse_order_book(serde_json::json!({ "bids": [] })).is_err());
+    }
+
+    #[test]
+    fn earn_positions_parse_amount_and_annual_rate() {
+        let positions = parse_earn_positions(serde_json::json!({
+            "rows": [{
+                "totalAmount": "5000.12",
+                "latestAnnualPercentageRate": "0.042",
+                "asset": "USDT",
+                "productId": "USDT001",
+                "canRedeem": true
+            }],
+            "total": 1
+        }))
+        .expect("earn positions should parse");
+
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].product_id, "USDT001");
+        assert_eq!(positions[0].amount, 5000.12);
+        assert!((positions[0].apr_pct - 4.2).abs() < 1e-9);
+        assert!(parse_earn_positions(serde_json::json!({ "total": 0 })).is_err());
+    }
+
+    #[test]
+    fn api_permissions_parse_restriction_flags() {
+        let permissions = parse_api_permissions(&serde_json::json!({
+            "ipRestrict": false,
+            "enableReading": true,
+            "enableWithdrawals": false,
+            "enableFutures": false,
+            "enableSpotAndMarginTrading": true,
+            "enableMargin": false
+        }))
+        .expect("restrictions should parse");
+
+        assert!(permissions.can_trade(Market::Spot));
+        assert!(!permissions.can_trade(Market::Futures));
+        assert!(!permissions.can_trade(Market::Options));
+        assert_eq!(permissions.label(), "spot");
+        assert!(parse_api_permissions(&serde_json::json!({ "enableReading": true })).is_err());
+    }
+}
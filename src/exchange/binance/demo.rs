@@ -1,9 +1,17 @@
+use reqwest::blocking::Client;
+
 use crate::domain::market::Market;
 use crate::error::exchange_error::ExchangeError;
 use crate::exchange::binance::account::RawAccountState;
 use crate::exchange::binance::auth::BinanceAuth;
 use crate::exchange::binance::client::{BinanceHttpTransport, BinanceTransport};
-use crate::exchange::binance::orders::{RawCloseOrderAck, RawCloseOrderRequest, RawSymbolRules};
+use crate::exchange::binance::orders::{
+    RawCloseOrderAck, RawCloseOrderRequest, RawCommissionRates, RawOcoListAck, RawOcoListRequest,
+    RawOpenOrder, RawSymbolRules,
+};
+use crate::exchange::types::{
+    DailyBar, LastTrade, MarginType, OrderBookDepth, SymbolTradingStatus,
+};
 
 pub const BINANCE_DEMO_SPOT_BASE_URL: &str = "https://demo-api.binance.com";
 pub const BINANCE_DEMO_FUTURES_BASE_URL: &str = "https://demo-fapi.binance.com";
@@ -25,6 +33,12 @@ impl BinanceDemoHttpTransport {
             ),
         }
     }
+
+    pub fn with_client(self, client: Client) -> Self {
+        Self {
+            inner: self.inner.with_client(client),
+        }
+    }
 }
 
 impl BinanceTransport for BinanceDemoHttpTransport {
@@ -40,6 +54,39 @@ impl BinanceTransport for BinanceDemoHttpTransport {
         self.inner.load_last_price(symbol, market)
     }
 
+    fn load_last_trade(&self, symbol: &str, market: Market) -> Result<LastTrade, ExchangeError> {
+        self.inner.load_last_trade(symbol, market)
+    }
+
+    fn load_order_book(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<OrderBookDepth>, ExchangeError> {
+        self.inner.load_order_book(symbol, market)
+    }
+
+    fn load_commission_rates(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<RawCommissionRates>, ExchangeError> {
+        self.inner.load_commission_rates(symbol, market)
+    }
+
+    fn load_daily_bars(
+        &self,
+        symbol: &str,
+        market: Market,
+        days: usize,
+    ) -> Result<Vec<DailyBar>, ExchangeError> {
+        self.inner.load_daily_bars(symbol, market, days)
+    }
+
+    fn load_funding_rate(&self, symbol: &str) -> Result<Option<f64>, ExchangeError> {
+        self.inner.load_funding_rate(symbol)
+    }
+
     fn load_symbol_rules(
         &self,
         symbol: &str,
@@ -48,6 +95,14 @@ impl BinanceTransport for BinanceDemoHttpTransport {
         self.inner.load_symbol_rules(symbol, market)
     }
 
+    fn load_symbol_status(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<SymbolTradingStatus>, ExchangeError> {
+        self.inner.load_symbol_status(symbol, market)
+    }
+
     fn load_option_symbols(&self) -> Result<Vec<String>, ExchangeError> {
         self.inner.load_option_symbols()
     }
@@ -59,6 +114,31 @@ impl BinanceTransport for BinanceDemoHttpTransport {
         self.inner.submit_close_order(request)
     }
 
+    fn cancel_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<(), ExchangeError> {
+        self.inner.cancel_order(symbol, market, client_order_id)
+    }
+
+    fn load_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<Option<RawOpenOrder>, ExchangeError> {
+        self.inner.load_order(symbol, market, client_order_id)
+    }
+
+    fn submit_oco_list(
+        &self,
+        request: RawOcoListRequest,
+    ) -> Result<Option<RawOcoListAck>, ExchangeError> {
+        self.inner.submit_oco_list(request)
+    }
+
     fn load_today_realized_pnl_usdt(&self) -> Result<f64, ExchangeError> {
         self.inner.load_today_realized_pnl_usdt()
     }
@@ -70,4 +150,32 @@ impl BinanceTransport for BinanceDemoHttpTransport {
     fn load_margin_ratio(&self) -> Result<Option<f64>, ExchangeError> {
         self.inner.load_margin_ratio()
     }
+
+    fn set_futures_margin_type(
+        &self,
+        symbol: &str,
+        margin_type: MarginType,
+    ) -> Result<bool, ExchangeError> {
+        self.inner.set_futures_margin_type(symbol, margin_type)
+    }
+
+    fn set_futures_leverage(
+        &self,
+        symbol: &str,
+        leverage: u32,
+    ) -> Result<Option<u32>, ExchangeError> {
+        self.inner.set_futures_leverage(symbol, leverage)
+    }
+
+    fn create_listen_key(&self, market: Market) -> Result<Option<String>, ExchangeError> {
+        self.inner.create_listen_key(market)
+    }
+
+    fn keepalive_listen_key(&self, market: Market, listen_key: &str) -> Result<(), ExchangeError> {
+        self.inner.keepalive_listen_key(market, listen_key)
+    }
+
+    fn close_listen_key(&self, market: Market, listen_key: &str) -> Result<(), ExchangeError> {
+        self.inner.close_listen_key(market, listen_key)
+    }
 }
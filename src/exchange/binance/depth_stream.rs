@@ -0,0 +1,268 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde_json::Value;
+use tokio::time::{interval, sleep, Instant};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::warn;
+
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::exchange::binance::network::NetworkConfig;
+use crate::exchange::binance::user_stream::{
+    BINANCE_DEMO_FUTURES_USER_STREAM_URL, BINANCE_DEMO_SPOT_USER_STREAM_URL,
+    BINANCE_FUTURES_USER_STREAM_URL, BINANCE_SPOT_USER_STREAM_URL,
+};
+use crate::exchange::types::OrderBookDepth;
+
+/// Book levels the partial depth streams offer per side.
+pub const PARTIAL_DEPTH_LEVELS: [usize; 3] = [5, 10, 20];
+const RECONNECT_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(60);
+const SHUTDOWN_POLL: Duration = Duration::from_millis(500);
+
+/// WebSocket bases the partial depth stream names are appended to; the
+/// same hosts as the user streams.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinanceDepthStreamApi {
+    pub spot_url: String,
+    pub futures_url: String,
+    pub network: NetworkConfig,
+}
+
+impl Default for BinanceDepthStreamApi {
+    fn default() -> Self {
+        Self::real()
+    }
+}
+
+impl BinanceDepthStreamApi {
+    pub fn real() -> Self {
+        Self {
+            spot_url: BINANCE_SPOT_USER_STREAM_URL.to_string(),
+            futures_url: BINANCE_FUTURES_USER_STREAM_URL.to_string(),
+            network: NetworkConfig::default(),
+        }
+    }
+
+    pub fn demo() -> Self {
+        Self {
+            spot_url: BINANCE_DEMO_SPOT_USER_STREAM_URL.to_string(),
+            futures_url: BINANCE_DEMO_FUTURES_USER_STREAM_URL.to_string(),
+            network: NetworkConfig::default(),
+        }
+    }
+
+    pub fn with_network(self, network: NetworkConfig) -> Self {
+        Self { network, ..self }
+    }
+
+    /// `None` for options. `levels` is rounded up to the nearest offered
+    /// depth, capped at 20.
+    ///
+    /// Example:
+    /// - futures `BTCUSDT`, `8` -> `wss://fstream.binance.com/ws/btcusdt@depth10@100ms`
+    pub fn stream_url(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+        levels: usize,
+    ) -> Option<String> {
+        let base = match market {
+            Market::Spot => &self.spot_url,
+            Market::Futures => &self.futures_url,
+            Market::Options => return None,
+        };
+        Some(format!(
+            "{base}/{}@depth{}@100ms",
+            instrument.0.to_ascii_lowercase(),
+            partial_depth_levels(levels)
+        ))
+    }
+}
+
+/// Smallest offered partial depth holding `levels`.
+///
+/// Example:
+/// - `3` -> `5`; `10` -> `10`; `50` -> `20`
+pub fn partial_depth_levels(levels: usize) -> usize {
+    PARTIAL_DEPTH_LEVELS
+        .into_iter()
+        .find(|offered| *offered >= levels)
+        .unwrap_or(PARTIAL_DEPTH_LEVELS[PARTIAL_DEPTH_LEVELS.len() - 1])
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DepthStreamEvent {
+    Connected,
+    /// The stream dropped; the book shown is stale until it reconnects.
+    Disconnected {
+        error: String,
+    },
+    /// The top of the book as it stands, replacing the previous one.
+    Depth {
+        depth: OrderBookDepth,
+        event_time_ms: Option<i64>,
+    },
+}
+
+/// Reads a spot partial book (`bids`/`asks`) or a futures `depthUpdate`
+/// (`b`/`a`); anything else is `None`.
+pub fn parse_depth_message(value: &Value) -> Option<DepthStreamEvent> {
+    let (bids, asks) = if value["e"] == "depthUpdate" {
+        (&value["b"], &value["a"])
+    } else if value.get("lastUpdateId").is_some() {
+        (&value["bids"], &value["asks"])
+    } else {
+        return None;
+    };
+    Some(DepthStreamEvent::Depth {
+        depth: OrderBookDepth {
+            bids: parse_levels(bids)?,
+            asks: parse_levels(asks)?,
+        },
+        event_time_ms: value["E"].as_i64(),
+    })
+}
+
+fn parse_levels(levels: &Value) -> Option<Vec<(f64, f64)>> {
+    levels
+        .as_array()?
+        .iter()
+        .map(|level| {
+            let price = level[0].as_str()?.parse::<f64>().ok()?;
+            let qty = level[1].as_str()?.parse::<f64>().ok()?;
+            Some((price, qty))
+        })
+        .collect()
+}
+
+/// Receives depth events; dropping it stops the stream.
+pub struct DepthStreamHandle {
+    events: Receiver<DepthStreamEvent>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl DepthStreamHandle {
+    /// Events received since the last call, oldest first.
+    pub fn drain(&self) -> Vec<DepthStreamEvent> {
+        self.events.try_iter().collect()
+    }
+}
+
+impl Drop for DepthStreamHandle {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Streams the top `levels` of one book from a background thread,
+/// reconnecting with backoff whenever the stream drops.
+///
+/// Returns `None` for markets without a depth stream.
+pub fn spawn_depth_stream(
+    api: BinanceDepthStreamApi,
+    instrument: &Instrument,
+    market: Market,
+    levels: usize,
+) -> Option<DepthStreamHandle> {
+    let url = api.stream_url(instrument, market, levels)?;
+    let (sender, events) = mpsc::channel();
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let stop = shutdown.clone();
+    thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(error) => {
+                warn!(service = "trading-engine", error = %error, "depth stream runtime failed to start");
+                return;
+            }
+        };
+        runtime.block_on(run_depth_stream(api.network, url, sender, stop));
+    });
+    Some(DepthStreamHandle { events, shutdown })
+}
+
+async fn run_depth_stream(
+    network: NetworkConfig,
+    url: String,
+    sender: Sender<DepthStreamEvent>,
+    shutdown: Arc<AtomicBool>,
+) {
+    let mut backoff = RECONNECT_MIN;
+    while !shutdown.load(Ordering::Relaxed) {
+        let error = match stream_once(&network, &url, &sender, &shutdown, &mut backoff).await {
+            Ok(()) => return,
+            Err(error) => error,
+        };
+        warn!(
+            service = "trading-engine",
+            url = %url,
+            error = %error,
+            retry_in_ms = backoff.as_millis() as u64,
+            "depth stream dropped; reconnecting"
+        );
+        if sender
+            .send(DepthStreamEvent::Disconnected { error })
+            .is_err()
+        {
+            return;
+        }
+        let deadline = Instant::now() + backoff;
+        while Instant::now() < deadline && !shutdown.load(Ordering::Relaxed) {
+            sleep(SHUTDOWN_POLL).await;
+        }
+        backoff = (backoff * 2).min(RECONNECT_MAX);
+    }
+}
+
+/// Runs one connection until it drops. `Ok` means stop for good: shut
+/// down or nobody listening.
+async fn stream_once(
+    network: &NetworkConfig,
+    url: &str,
+    sender: &Sender<DepthStreamEvent>,
+    shutdown: &AtomicBool,
+    backoff: &mut Duration,
+) -> Result<(), String> {
+    let mut stream = network.connect_websocket(url).await?;
+    *backoff = RECONNECT_MIN;
+    if sender.send(DepthStreamEvent::Connected).is_err() {
+        return Ok(());
+    }
+    let mut shutdown_poll = interval(SHUTDOWN_POLL);
+    let result = loop {
+        tokio::select! {
+            message = stream.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    let Some(event) = serde_json::from_str::<Value>(&text)
+                        .ok()
+                        .and_then(|value| parse_depth_message(&value))
+                    else {
+                        continue;
+                    };
+                    if sender.send(event).is_err() {
+                        break Ok(());
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => break Err("stream closed".to_string()),
+                Some(Ok(_)) => {}
+                Some(Err(error)) => break Err(format!("stream failed: {error}")),
+            },
+            _ = shutdown_poll.tick() => {
+                if shutdown.load(Ordering::Relaxed) {
+                    break Ok(());
+                }
+            }
+        }
+    };
+    let _ = stream.close(None).await;
+    result
+}
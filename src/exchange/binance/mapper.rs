@@ -5,11 +5,15 @@ use crate::domain::order::{OpenOrder, OrderStatus};
 use crate::domain::position::PositionSnapshot;
 use crate::domain::position::Side;
 use crate::exchange::symbol_rules::SymbolRules;
-use crate::exchange::types::{AuthoritativeSnapshot, CloseOrderAccepted, CloseOrderRequest};
+use crate::exchange::types::{
+    AuthoritativeSnapshot, CloseOrderAccepted, CloseOrderRequest, OcoLegAccepted, OcoListAccepted,
+    OcoListRequest,
+};
 
 use crate::exchange::binance::account::{RawAccountState, RawBalance, RawPosition};
 use crate::exchange::binance::orders::{
-    RawCloseOrderAck, RawCloseOrderRequest, RawOpenOrder, RawSymbolRules,
+    RawCloseOrderAck, RawCloseOrderRequest, RawOcoListAck, RawOcoListRequest, RawOpenOrder,
+    RawSymbolRules,
 };
 
 #[derive(Debug, Default, Clone)]
@@ -37,6 +41,7 @@ impl BinanceMapper {
             min_qty: rules.min_qty,
             max_qty: rules.max_qty,
             step_size: rules.step_size,
+            tick_size: rules.tick_size,
         }
     }
 
@@ -51,12 +56,52 @@ impl BinanceMapper {
             qty: request.qty_text,
             order_type: request.order_type,
             reduce_only: request.reduce_only,
+            client_order_id: request.client_order_id,
         }
     }
 
     pub fn map_close_ack(&self, ack: RawCloseOrderAck) -> CloseOrderAccepted {
         CloseOrderAccepted {
             remote_order_id: ack.remote_order_id,
+            exchange_time_ms: ack.exchange_time_ms,
+        }
+    }
+
+    pub fn map_oco_request(&self, request: OcoListRequest) -> RawOcoListRequest {
+        RawOcoListRequest {
+            symbol: request.instrument.0,
+            market: request.market,
+            side: match request.side {
+                Side::Buy => "BUY",
+                Side::Sell => "SELL",
+            },
+            qty: request.qty_text,
+            take_profit_price: request.take_profit_price,
+            stop_price: request.stop_price,
+            stop_limit_price: request.stop_limit_price,
+            list_client_order_id: request.list_client_order_id,
+            take_profit_client_order_id: request.take_profit_client_order_id,
+            stop_client_order_id: request.stop_client_order_id,
+        }
+    }
+
+    pub fn map_order(&self, order: RawOpenOrder) -> OpenOrder {
+        map_open_order(order)
+    }
+
+    pub fn map_oco_ack(&self, ack: RawOcoListAck) -> OcoListAccepted {
+        OcoListAccepted {
+            remote_list_id: ack.order_list_id,
+            list_status: ack.list_status,
+            exchange_time_ms: ack.exchange_time_ms,
+            legs: ack
+                .legs
+                .into_iter()
+                .map(|(client_order_id, remote_order_id)| OcoLegAccepted {
+                    client_order_id,
+                    remote_order_id,
+                })
+                .collect(),
         }
     }
 }
@@ -91,6 +136,7 @@ fn map_open_order(order: RawOpenOrder) -> OpenOrder {
             "SELL" => Side::Sell,
             _ => Side::Buy,
         },
+        price: (order.price > 0.0).then_some(order.price),
         orig_qty: order.orig_qty,
         executed_qty: order.executed_qty,
         reduce_only: order.reduce_only,
@@ -98,11 +144,11 @@ fn map_open_order(order: RawOpenOrder) -> OpenOrder {
     }
 }
 
-fn map_order_status(status: &str) -> OrderStatus {
+pub(crate) fn map_order_status(status: &str) -> OrderStatus {
     match status {
-        "NEW" | "ACCEPTED" => OrderStatus::Submitted,
+        "NEW" | "ACCEPTED" | "PARTIALLY_FILLED" => OrderStatus::Submitted,
         "FILLED" => OrderStatus::Filled,
-        "CANCELED" | "CANCELLED" => OrderStatus::Cancelled,
+        "CANCELED" | "CANCELLED" | "EXPIRED" | "EXPIRED_IN_MATCH" => OrderStatus::Cancelled,
         "REJECTED" => OrderStatus::Rejected,
         _ => OrderStatus::PendingSubmit,
     }
@@ -0,0 +1,232 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::domain::market::Market;
+use crate::error::exchange_error::ExchangeError;
+use crate::exchange::binance::account::RawAccountState;
+use crate::exchange::binance::client::BinanceTransport;
+use crate::exchange::binance::orders::{
+    RawCloseOrderAck, RawCloseOrderRequest, RawCommissionRates, RawOcoListAck, RawOcoListRequest,
+    RawOpenOrder, RawSymbolRules,
+};
+use crate::exchange::types::{
+    ApiPermissions, DailyBar, EarnPosition, EarnTransfer, LastTrade, MarginType, OrderBookDepth,
+    SymbolTradingStatus,
+};
+use crate::observability::rest_latency::{RestEndpoint, RestLatencyStats};
+
+/// Wraps a transport and times every call into `RestLatencyStats`.
+///
+/// Sits outside `ChaosTransport`, so injected timeouts are counted like
+/// real ones.
+pub struct MeteredTransport {
+    inner: Arc<dyn BinanceTransport>,
+    stats: Arc<RestLatencyStats>,
+}
+
+impl MeteredTransport {
+    pub fn new(inner: Arc<dyn BinanceTransport>, stats: Arc<RestLatencyStats>) -> Self {
+        Self { inner, stats }
+    }
+
+    fn timed<T>(
+        &self,
+        endpoint: RestEndpoint,
+        call: impl FnOnce(&dyn BinanceTransport) -> Result<T, ExchangeError>,
+    ) -> Result<T, ExchangeError> {
+        let started = Instant::now();
+        let result = call(self.inner.as_ref());
+        self.stats
+            .record(endpoint, started.elapsed(), result.as_ref().err());
+        result
+    }
+}
+
+impl BinanceTransport for MeteredTransport {
+    fn transport_name(&self) -> &'static str {
+        self.inner.transport_name()
+    }
+
+    fn load_account_state(&self, market: Market) -> Result<RawAccountState, ExchangeError> {
+        self.timed(RestEndpoint::Account, |inner| {
+            inner.load_account_state(market)
+        })
+    }
+
+    fn load_last_price(&self, symbol: &str, market: Market) -> Result<f64, ExchangeError> {
+        self.timed(RestEndpoint::Price, |inner| {
+            inner.load_last_price(symbol, market)
+        })
+    }
+
+    fn load_last_trade(&self, symbol: &str, market: Market) -> Result<LastTrade, ExchangeError> {
+        self.timed(RestEndpoint::Price, |inner| {
+            inner.load_last_trade(symbol, market)
+        })
+    }
+
+    fn load_symbol_rules(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<RawSymbolRules, ExchangeError> {
+        self.timed(RestEndpoint::ExchangeInfo, |inner| {
+            inner.load_symbol_rules(symbol, market)
+        })
+    }
+
+    fn load_option_symbols(&self) -> Result<Vec<String>, ExchangeError> {
+        self.timed(RestEndpoint::ExchangeInfo, |inner| {
+            inner.load_option_symbols()
+        })
+    }
+
+    fn submit_close_order(
+        &self,
+        request: RawCloseOrderRequest,
+    ) -> Result<RawCloseOrderAck, ExchangeError> {
+        self.timed(RestEndpoint::Order, |inner| {
+            inner.submit_close_order(request)
+        })
+    }
+
+    fn cancel_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<(), ExchangeError> {
+        self.timed(RestEndpoint::Order, |inner| {
+            inner.cancel_order(symbol, market, client_order_id)
+        })
+    }
+
+    fn load_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<Option<RawOpenOrder>, ExchangeError> {
+        self.timed(RestEndpoint::Order, |inner| {
+            inner.load_order(symbol, market, client_order_id)
+        })
+    }
+
+    fn submit_oco_list(
+        &self,
+        request: RawOcoListRequest,
+    ) -> Result<Option<RawOcoListAck>, ExchangeError> {
+        self.timed(RestEndpoint::Order, |inner| inner.submit_oco_list(request))
+    }
+
+    fn load_today_realized_pnl_usdt(&self) -> Result<f64, ExchangeError> {
+        self.timed(RestEndpoint::Income, |inner| {
+            inner.load_today_realized_pnl_usdt()
+        })
+    }
+
+    fn load_today_funding_pnl_usdt(&self) -> Result<f64, ExchangeError> {
+        self.timed(RestEndpoint::Income, |inner| {
+            inner.load_today_funding_pnl_usdt()
+        })
+    }
+
+    fn load_margin_ratio(&self) -> Result<Option<f64>, ExchangeError> {
+        self.timed(RestEndpoint::Account, |inner| inner.load_margin_ratio())
+    }
+
+    fn load_order_book(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<OrderBookDepth>, ExchangeError> {
+        self.timed(RestEndpoint::Depth, |inner| {
+            inner.load_order_book(symbol, market)
+        })
+    }
+
+    fn load_commission_rates(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<RawCommissionRates>, ExchangeError> {
+        self.timed(RestEndpoint::Commission, |inner| {
+            inner.load_commission_rates(symbol, market)
+        })
+    }
+
+    fn load_daily_bars(
+        &self,
+        symbol: &str,
+        market: Market,
+        days: usize,
+    ) -> Result<Vec<DailyBar>, ExchangeError> {
+        self.timed(RestEndpoint::Klines, |inner| {
+            inner.load_daily_bars(symbol, market, days)
+        })
+    }
+
+    fn load_funding_rate(&self, symbol: &str) -> Result<Option<f64>, ExchangeError> {
+        self.timed(RestEndpoint::Price, |inner| inner.load_funding_rate(symbol))
+    }
+
+    fn load_earn_positions(&self, asset: &str) -> Result<Option<Vec<EarnPosition>>, ExchangeError> {
+        self.timed(RestEndpoint::Earn, |inner| inner.load_earn_positions(asset))
+    }
+
+    fn transfer_earn(&self, transfer: &EarnTransfer) -> Result<bool, ExchangeError> {
+        self.timed(RestEndpoint::Earn, |inner| inner.transfer_earn(transfer))
+    }
+
+    fn load_api_permissions(&self) -> Result<Option<ApiPermissions>, ExchangeError> {
+        self.timed(RestEndpoint::Account, |inner| inner.load_api_permissions())
+    }
+
+    fn set_futures_margin_type(
+        &self,
+        symbol: &str,
+        margin_type: MarginType,
+    ) -> Result<bool, ExchangeError> {
+        self.timed(RestEndpoint::Account, |inner| {
+            inner.set_futures_margin_type(symbol, margin_type)
+        })
+    }
+
+    fn set_futures_leverage(
+        &self,
+        symbol: &str,
+        leverage: u32,
+    ) -> Result<Option<u32>, ExchangeError> {
+        self.timed(RestEndpoint::Account, |inner| {
+            inner.set_futures_leverage(symbol, leverage)
+        })
+    }
+
+    fn load_symbol_status(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<SymbolTradingStatus>, ExchangeError> {
+        self.timed(RestEndpoint::ExchangeInfo, |inner| {
+            inner.load_symbol_status(symbol, market)
+        })
+    }
+
+    fn create_listen_key(&self, market: Market) -> Result<Option<String>, ExchangeError> {
+        self.timed(RestEndpoint::Account, |inner| {
+            inner.create_listen_key(market)
+        })
+    }
+
+    fn keepalive_listen_key(&self, market: Market, listen_key: &str) -> Result<(), ExchangeError> {
+        self.timed(RestEndpoint::Account, |inner| {
+            inner.keepalive_listen_key(market, listen_key)
+        })
+    }
+
+    fn close_listen_key(&self, market: Market, listen_key: &str) -> Result<(), ExchangeError> {
+        self.timed(RestEndpoint::Account, |inner| {
+            inner.close_listen_key(market, listen_key)
+        })
+    }
+}
@@ -1,8 +1,15 @@
 pub mod account;
 pub mod auth;
+pub mod cache;
+pub mod chaos;
 pub mod client;
 pub mod demo;
+pub mod depth_stream;
 pub mod mapper;
+pub mod metered;
 pub mod market_data;
+pub mod network;
 pub mod orders;
+pub mod paper;
+pub mod rate_budget;
 pub mod user_stream;
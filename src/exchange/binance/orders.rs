@@ -6,11 +6,21 @@ pub struct RawSymbolRules {
     pub min_qty: f64,
     pub max_qty: f64,
     pub step_size: f64,
+    pub tick_size: f64,
+}
+
+/// Maker/taker fee rates as fractions, e.g. `0.0002` for 2 bps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RawCommissionRates {
+    pub maker: f64,
+    pub taker: f64,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct RawCloseOrderAck {
     pub remote_order_id: String,
+    /// `transactTime` on spot, `updateTime` on futures.
+    pub exchange_time_ms: Option<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -20,6 +30,8 @@ pub struct RawOpenOrder {
     pub symbol: String,
     pub market: Market,
     pub side: &'static str,
+    /// `0` when the order has no limit price.
+    pub price: f64,
     pub orig_qty: f64,
     pub executed_qty: f64,
     pub reduce_only: bool,
@@ -34,4 +46,30 @@ pub struct RawCloseOrderRequest {
     pub qty: String,
     pub order_type: OrderType,
     pub reduce_only: bool,
+    pub client_order_id: Option<String>,
+}
+
+/// Spot `orderList/oco` parameters for closing `qty` of `symbol`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawOcoListRequest {
+    pub symbol: String,
+    pub market: Market,
+    pub side: &'static str,
+    pub qty: String,
+    pub take_profit_price: f64,
+    pub stop_price: f64,
+    pub stop_limit_price: Option<f64>,
+    pub list_client_order_id: String,
+    pub take_profit_client_order_id: String,
+    pub stop_client_order_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawOcoListAck {
+    pub order_list_id: String,
+    pub list_status: String,
+    /// `transactionTime` of the list.
+    pub exchange_time_ms: Option<i64>,
+    /// `(clientOrderId, orderId)` of each leg.
+    pub legs: Vec<(String, String)>,
 }
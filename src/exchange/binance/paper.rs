@@ -0,0 +1,621 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{NaiveDate, Utc};
+
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::order_type::OrderType;
+use crate::error::exchange_error::ExchangeError;
+use crate::exchange::binance::account::{RawAccountState, RawBalance, RawPosition};
+use crate::exchange::binance::client::BinanceTransport;
+use crate::exchange::binance::orders::{
+    RawCloseOrderAck, RawCloseOrderRequest, RawCommissionRates, RawOpenOrder, RawSymbolRules,
+};
+use crate::exchange::types::{DailyBar, LastTrade, OrderBookDepth, SymbolTradingStatus};
+use crate::portfolio::asset_exposure::base_asset;
+use crate::portfolio::entry_stats::{taker_fee_rate_from_env, DEFAULT_TAKER_FEE_RATE};
+
+pub const DEFAULT_PAPER_BALANCE_USDT: f64 = 10_000.0;
+
+/// Binance's default futures leverage, used until one is set.
+pub const DEFAULT_PAPER_LEVERAGE: u32 = 20;
+
+/// A simulated account trading against live public prices.
+///
+/// Spot and futures each start with `starting_usdt`. Market orders fill
+/// at the last trade price, paying `taker_fee_rate` in USDT; limit and stop
+/// orders rest until a later account load sees the price reach them.
+///
+/// Example:
+/// - `SANDBOX_QUANT_PAPER_TRADING=5000`
+/// - a futures market buy of `0.01 BTCUSDT` at `64,000` opens a long and
+///   charges `0.32` USDT at the default `0.0005` fee
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaperTradingConfig {
+    pub starting_usdt: f64,
+    pub taker_fee_rate: f64,
+}
+
+impl PaperTradingConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_PAPER_TRADING` (`on` for `10000` USDT, or the
+    ///   starting USDT, e.g. `5000`; unset or `off` trades for real)
+    /// - `SANDBOX_QUANT_TAKER_FEE_RATE`
+    pub fn from_env() -> Option<Self> {
+        std::env::var("SANDBOX_QUANT_PAPER_TRADING")
+            .ok()
+            .and_then(|raw| Self::parse(&raw))
+            .map(|config| Self {
+                taker_fee_rate: taker_fee_rate_from_env(),
+                ..config
+            })
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let starting_usdt = match raw.to_ascii_lowercase().as_str() {
+            "on" | "true" => DEFAULT_PAPER_BALANCE_USDT,
+            _ => raw
+                .parse::<f64>()
+                .ok()
+                .filter(|usdt| usdt.is_finite() && *usdt > 0.0)?,
+        };
+        Some(Self {
+            starting_usdt,
+            taker_fee_rate: DEFAULT_TAKER_FEE_RATE,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PaperOrder {
+    order_id: u64,
+    client_order_id: String,
+    symbol: String,
+    market: Market,
+    side: &'static str,
+    qty: f64,
+    order_type: OrderType,
+    reduce_only: bool,
+}
+
+impl PaperOrder {
+    fn is_buy(&self) -> bool {
+        self.side == "BUY"
+    }
+
+    /// Where the order fills at `last`, turning a triggered stop-limit
+    /// into the limit it rests as.
+    fn fill_price(&mut self, last: f64) -> Option<f64> {
+        let buy = self.is_buy();
+        let stop_hit = |stop_price: f64| {
+            if buy {
+                last >= stop_price
+            } else {
+                last <= stop_price
+            }
+        };
+        match self.order_type {
+            OrderType::Market => Some(last),
+            OrderType::Limit { price } | OrderType::LimitIoc { price } => {
+                let marketable = if buy { last <= price } else { last >= price };
+                marketable.then_some(last)
+            }
+            OrderType::StopMarket { stop_price } => stop_hit(stop_price).then_some(last),
+            OrderType::StopLimit { stop_price, price } => {
+                if !stop_hit(stop_price) {
+                    return None;
+                }
+                self.order_type = OrderType::Limit { price };
+                self.fill_price(last)
+            }
+            OrderType::TrailingStopMarket { .. } => None,
+        }
+    }
+
+    fn raw(&self) -> RawOpenOrder {
+        RawOpenOrder {
+            order_id: Some(self.order_id.to_string()),
+            client_order_id: self.client_order_id.clone(),
+            symbol: self.symbol.clone(),
+            market: self.market,
+            side: self.side,
+            price: match self.order_type {
+                OrderType::Limit { price } | OrderType::StopLimit { price, .. } => price,
+                _ => 0.0,
+            },
+            orig_qty: self.qty,
+            executed_qty: 0.0,
+            reduce_only: self.reduce_only,
+            status: "NEW".to_string(),
+        }
+    }
+
+    /// The order as reported once it left the book, all or nothing.
+    fn finished(&self, status: &str) -> RawOpenOrder {
+        RawOpenOrder {
+            executed_qty: if status == "FILLED" { self.qty } else { 0.0 },
+            status: status.to_string(),
+            ..self.raw()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PaperPosition {
+    signed_qty: f64,
+    entry_price: f64,
+}
+
+#[derive(Debug, Default)]
+struct PaperBook {
+    spot_balances: BTreeMap<String, f64>,
+    futures_wallet_usdt: f64,
+    positions: BTreeMap<String, PaperPosition>,
+    leverage: BTreeMap<String, u32>,
+    resting: Vec<PaperOrder>,
+    /// Orders that left the book, by client order id, for `load_order`.
+    finished: BTreeMap<String, RawOpenOrder>,
+    last_order_id: u64,
+    pnl_day: Option<NaiveDate>,
+    realized_pnl_usdt: f64,
+}
+
+impl PaperBook {
+    fn margin_used_usdt(&self) -> f64 {
+        self.positions
+            .iter()
+            .map(|(symbol, position)| {
+                let leverage = self
+                    .leverage
+                    .get(symbol)
+                    .copied()
+                    .unwrap_or(DEFAULT_PAPER_LEVERAGE);
+                position.signed_qty.abs() * position.entry_price / f64::from(leverage)
+            })
+            .sum()
+    }
+
+    fn book_realized(&mut self, pnl: f64) {
+        let today = Utc::now().date_naive();
+        if self.pnl_day != Some(today) {
+            self.pnl_day = Some(today);
+            self.realized_pnl_usdt = 0.0;
+        }
+        self.realized_pnl_usdt += pnl;
+    }
+}
+
+/// An in-process venue standing in for Binance's signed endpoints.
+///
+/// Prices, symbol rules, depth and bars still come from `market_data`,
+/// whose public endpoints need no API key. Balances, positions and orders
+/// live in memory only and start over with the client, e.g. after a mode
+/// switch. No user data stream is offered; fills show up on refresh.
+pub struct PaperTransport {
+    market_data: Arc<dyn BinanceTransport>,
+    config: PaperTradingConfig,
+    book: Mutex<PaperBook>,
+}
+
+impl PaperTransport {
+    pub fn new(market_data: Arc<dyn BinanceTransport>, config: PaperTradingConfig) -> Self {
+        let book = PaperBook {
+            spot_balances: BTreeMap::from([("USDT".to_string(), config.starting_usdt)]),
+            futures_wallet_usdt: config.starting_usdt,
+            ..PaperBook::default()
+        };
+        Self {
+            market_data,
+            config,
+            book: Mutex::new(book),
+        }
+    }
+
+    fn book(&self) -> std::sync::MutexGuard<'_, PaperBook> {
+        self.book.lock().expect("lock paper book")
+    }
+
+    /// Fills resting orders of `market` whose price has been reached.
+    fn match_resting(&self, market: Market) -> Result<(), ExchangeError> {
+        let mut book = self.book();
+        let mut prices = BTreeMap::new();
+        let mut index = 0;
+        while index < book.resting.len() {
+            if book.resting[index].market != market {
+                index += 1;
+                continue;
+            }
+            let symbol = book.resting[index].symbol.clone();
+            let last = match prices.get(&symbol) {
+                Some(last) => *last,
+                None => {
+                    let last = self.market_data.load_last_price(&symbol, market)?;
+                    prices.insert(symbol, last);
+                    last
+                }
+            };
+            let Some(price) = book.resting[index].fill_price(last) else {
+                index += 1;
+                continue;
+            };
+            let order = book.resting.remove(index);
+            // A resting order that no longer fits the account is dropped,
+            // as the venue would expire it.
+            let status = match self.fill(&mut book, &order, price) {
+                Ok(()) => "FILLED",
+                Err(_) => "EXPIRED",
+            };
+            book.finished
+                .insert(order.client_order_id.clone(), order.finished(status));
+        }
+        Ok(())
+    }
+
+    fn fill(
+        &self,
+        book: &mut PaperBook,
+        order: &PaperOrder,
+        price: f64,
+    ) -> Result<(), ExchangeError> {
+        match order.market {
+            Market::Spot => self.fill_spot(book, order, price),
+            Market::Futures => self.fill_futures(book, order, price),
+            Market::Options => Err(ExchangeError::UnsupportedMarketOperation),
+        }
+    }
+
+    fn fill_spot(
+        &self,
+        book: &mut PaperBook,
+        order: &PaperOrder,
+        price: f64,
+    ) -> Result<(), ExchangeError> {
+        let base = base_asset(&Instrument::new(order.symbol.clone()))
+            .ok_or(ExchangeError::UnsupportedMarketOperation)?
+            .to_string();
+        let quote = order.symbol[base.len()..].to_string();
+        let notional = order.qty * price;
+        let fee = notional * self.config.taker_fee_rate;
+        let balance = |book: &PaperBook, asset: &str| {
+            book.spot_balances.get(asset).copied().unwrap_or_default()
+        };
+        if order.is_buy() {
+            if balance(book, &quote) + f64::EPSILON < notional + fee {
+                return Err(insufficient_balance());
+            }
+            *book.spot_balances.entry(quote).or_default() -= notional + fee;
+            *book.spot_balances.entry(base).or_default() += order.qty;
+        } else {
+            if balance(book, &base) + f64::EPSILON < order.qty {
+                return Err(insufficient_balance());
+            }
+            *book.spot_balances.entry(base).or_default() -= order.qty;
+            *book.spot_balances.entry(quote).or_default() += notional - fee;
+        }
+        Ok(())
+    }
+
+    fn fill_futures(
+        &self,
+        book: &mut PaperBook,
+        order: &PaperOrder,
+        price: f64,
+    ) -> Result<(), ExchangeError> {
+        let current = book.positions.get(&order.symbol).copied();
+        let held = current.map_or(0.0, |position| position.signed_qty);
+        let sign = if order.is_buy() { 1.0 } else { -1.0 };
+        let mut qty = order.qty;
+        if order.reduce_only {
+            if held * sign >= 0.0 {
+                return Err(ExchangeError::RemoteReject {
+                    code: -2022,
+                    message: "ReduceOnly Order is rejected.".to_string(),
+                });
+            }
+            qty = qty.min(held.abs());
+        }
+        let fee = qty * price * self.config.taker_fee_rate;
+        let closing = if held * sign < 0.0 {
+            qty.min(held.abs())
+        } else {
+            0.0
+        };
+        let opening = qty - closing;
+        if opening > 0.0 {
+            let leverage = book
+                .leverage
+                .get(&order.symbol)
+                .copied()
+                .unwrap_or(DEFAULT_PAPER_LEVERAGE);
+            let available = book.futures_wallet_usdt - book.margin_used_usdt();
+            if available + f64::EPSILON < opening * price / f64::from(leverage) + fee {
+                return Err(ExchangeError::RemoteReject {
+                    code: -2019,
+                    message: "Margin is insufficient.".to_string(),
+                });
+            }
+        }
+        let entry_price = current.map_or(price, |position| position.entry_price);
+        let realized = -sign * closing * (price - entry_price);
+        let signed_qty = held + sign * qty;
+        let next_entry = if signed_qty.abs() <= 1e-12 {
+            None
+        } else if held * sign >= 0.0 {
+            Some((held.abs() * entry_price + qty * price) / signed_qty.abs())
+        } else if opening > 0.0 {
+            Some(price)
+        } else {
+            Some(entry_price)
+        };
+        match next_entry {
+            Some(entry_price) => {
+                book.positions.insert(
+                    order.symbol.clone(),
+                    PaperPosition {
+                        signed_qty,
+                        entry_price,
+                    },
+                );
+            }
+            None => {
+                book.positions.remove(&order.symbol);
+            }
+        }
+        book.futures_wallet_usdt += realized - fee;
+        book.book_realized(realized);
+        Ok(())
+    }
+}
+
+impl BinanceTransport for PaperTransport {
+    fn transport_name(&self) -> &'static str {
+        "paper"
+    }
+
+    fn load_account_state(&self, market: Market) -> Result<RawAccountState, ExchangeError> {
+        self.match_resting(market)?;
+        let book = self.book();
+        let open_orders = book
+            .resting
+            .iter()
+            .filter(|order| order.market == market)
+            .map(PaperOrder::raw)
+            .collect();
+        match market {
+            Market::Spot => Ok(RawAccountState {
+                balances: book
+                    .spot_balances
+                    .iter()
+                    .filter(|(_, free)| **free > f64::EPSILON)
+                    .map(|(asset, free)| RawBalance {
+                        asset: asset.clone(),
+                        free: *free,
+                        locked: 0.0,
+                    })
+                    .collect(),
+                positions: Vec::new(),
+                open_orders,
+            }),
+            Market::Futures => {
+                let margin = book
+                    .margin_used_usdt()
+                    .min(book.futures_wallet_usdt.max(0.0));
+                Ok(RawAccountState {
+                    balances: vec![RawBalance {
+                        asset: "USDT".to_string(),
+                        free: book.futures_wallet_usdt - margin,
+                        locked: margin,
+                    }],
+                    positions: book
+                        .positions
+                        .iter()
+                        .map(|(symbol, position)| RawPosition {
+                            symbol: symbol.clone(),
+                            signed_qty: position.signed_qty,
+                            entry_price: Some(position.entry_price),
+                        })
+                        .collect(),
+                    open_orders,
+                })
+            }
+            Market::Options => Err(ExchangeError::UnsupportedMarketOperation),
+        }
+    }
+
+    fn load_last_price(&self, symbol: &str, market: Market) -> Result<f64, ExchangeError> {
+        self.market_data.load_last_price(symbol, market)
+    }
+
+    fn load_last_trade(&self, symbol: &str, market: Market) -> Result<LastTrade, ExchangeError> {
+        self.market_data.load_last_trade(symbol, market)
+    }
+
+    fn load_symbol_rules(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<RawSymbolRules, ExchangeError> {
+        self.market_data.load_symbol_rules(symbol, market)
+    }
+
+    fn load_option_symbols(&self) -> Result<Vec<String>, ExchangeError> {
+        self.market_data.load_option_symbols()
+    }
+
+    fn submit_close_order(
+        &self,
+        request: RawCloseOrderRequest,
+    ) -> Result<RawCloseOrderAck, ExchangeError> {
+        if request.market == Market::Options
+            || matches!(request.order_type, OrderType::TrailingStopMarket { .. })
+        {
+            return Err(ExchangeError::UnsupportedMarketOperation);
+        }
+        let qty = request
+            .qty
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|qty| qty.is_finite() && *qty > 0.0)
+            .ok_or(ExchangeError::RemoteReject {
+                code: -1013,
+                message: "Invalid quantity.".to_string(),
+            })?;
+        let last = self
+            .market_data
+            .load_last_price(&request.symbol, request.market)?;
+        let mut book = self.book();
+        book.last_order_id += 1;
+        let order_id = book.last_order_id;
+        let mut order = PaperOrder {
+            order_id,
+            client_order_id: request
+                .client_order_id
+                .unwrap_or_else(|| format!("paper-{order_id}")),
+            symbol: request.symbol,
+            market: request.market,
+            side: request.side,
+            qty,
+            order_type: request.order_type,
+            reduce_only: request.reduce_only,
+        };
+        match order.fill_price(last) {
+            Some(price) => {
+                self.fill(&mut book, &order, price)?;
+                book.finished
+                    .insert(order.client_order_id.clone(), order.finished("FILLED"));
+            }
+            // An IOC limit that cannot fill now expires instead of resting.
+            None if matches!(order.order_type, OrderType::LimitIoc { .. }) => {
+                book.finished
+                    .insert(order.client_order_id.clone(), order.finished("EXPIRED"));
+            }
+            None => book.resting.push(order),
+        }
+        Ok(RawCloseOrderAck {
+            remote_order_id: order_id.to_string(),
+            exchange_time_ms: Some(Utc::now().timestamp_millis()),
+        })
+    }
+
+    fn cancel_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<(), ExchangeError> {
+        let mut book = self.book();
+        let Some(index) = book.resting.iter().position(|order| {
+            order.symbol == symbol
+                && order.market == market
+                && order.client_order_id == client_order_id
+        }) else {
+            return Err(ExchangeError::RemoteReject {
+                code: -2011,
+                message: "Unknown order sent.".to_string(),
+            });
+        };
+        let order = book.resting.remove(index);
+        book.finished
+            .insert(order.client_order_id.clone(), order.finished("CANCELED"));
+        Ok(())
+    }
+
+    /// Matches the book first, so a resting order reached by the price
+    /// reports as filled.
+    fn load_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<Option<RawOpenOrder>, ExchangeError> {
+        self.match_resting(market)?;
+        let book = self.book();
+        let order = book
+            .resting
+            .iter()
+            .find(|order| order.client_order_id == client_order_id)
+            .map(PaperOrder::raw)
+            .or_else(|| book.finished.get(client_order_id).cloned());
+        Ok(order.filter(|order| order.symbol == symbol && order.market == market))
+    }
+
+    fn load_today_realized_pnl_usdt(&self) -> Result<f64, ExchangeError> {
+        let book = self.book();
+        Ok(if book.pnl_day == Some(Utc::now().date_naive()) {
+            book.realized_pnl_usdt
+        } else {
+            0.0
+        })
+    }
+
+    fn load_today_funding_pnl_usdt(&self) -> Result<f64, ExchangeError> {
+        Ok(0.0)
+    }
+
+    /// Margin in use over the futures wallet.
+    fn load_margin_ratio(&self) -> Result<Option<f64>, ExchangeError> {
+        let book = self.book();
+        Ok((book.futures_wallet_usdt > f64::EPSILON)
+            .then(|| book.margin_used_usdt() / book.futures_wallet_usdt))
+    }
+
+    fn load_order_book(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<OrderBookDepth>, ExchangeError> {
+        self.market_data.load_order_book(symbol, market)
+    }
+
+    fn load_commission_rates(
+        &self,
+        _symbol: &str,
+        _market: Market,
+    ) -> Result<Option<RawCommissionRates>, ExchangeError> {
+        Ok(Some(RawCommissionRates {
+            maker: self.config.taker_fee_rate,
+            taker: self.config.taker_fee_rate,
+        }))
+    }
+
+    fn load_daily_bars(
+        &self,
+        symbol: &str,
+        market: Market,
+        days: usize,
+    ) -> Result<Vec<DailyBar>, ExchangeError> {
+        self.market_data.load_daily_bars(symbol, market, days)
+    }
+
+    fn load_funding_rate(&self, symbol: &str) -> Result<Option<f64>, ExchangeError> {
+        self.market_data.load_funding_rate(symbol)
+    }
+
+    fn set_futures_leverage(
+        &self,
+        symbol: &str,
+        leverage: u32,
+    ) -> Result<Option<u32>, ExchangeError> {
+        let leverage = leverage.clamp(1, 125);
+        self.book().leverage.insert(symbol.to_string(), leverage);
+        Ok(Some(leverage))
+    }
+
+    fn load_symbol_status(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<SymbolTradingStatus>, ExchangeError> {
+        self.market_data.load_symbol_status(symbol, market)
+    }
+}
+
+fn insufficient_balance() -> ExchangeError {
+    ExchangeError::RemoteReject {
+        code: -2010,
+        message: "Account has insufficient balance for requested action.".to_string(),
+    }
+}
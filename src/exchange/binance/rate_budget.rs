@@ -0,0 +1,627 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::app::bootstrap::BinanceMode;
+use crate::domain::market::Market;
+use crate::error::exchange_error::ExchangeError;
+use crate::exchange::binance::account::RawAccountState;
+use crate::exchange::binance::client::BinanceTransport;
+use crate::exchange::binance::orders::{
+    RawCloseOrderAck, RawCloseOrderRequest, RawCommissionRates, RawOcoListAck, RawOcoListRequest,
+    RawOpenOrder, RawSymbolRules,
+};
+use crate::exchange::types::{
+    ApiPermissions, DailyBar, EarnPosition, EarnTransfer, LastTrade, MarginType, OrderBookDepth,
+    SymbolTradingStatus,
+};
+use crate::observability::rest_latency::RestEndpoint;
+
+pub const DEFAULT_REST_WEIGHT_PER_MIN: f64 = 1_200.0;
+pub const DEFAULT_REST_RESERVE: &str = "order=240,account=60";
+/// Longest a call waits for tokens before it is refused locally.
+pub const DEFAULT_REST_MAX_WAIT: Duration = Duration::from_secs(2);
+
+/// What a REST call is for; each may hold back part of the budget from
+/// the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RequestCategory {
+    /// Order submits and cancels.
+    Order,
+    /// Balances, positions, open orders, margin, permissions and Earn.
+    Account,
+    /// Income history and klines, often fetched for many symbols at once.
+    History,
+    /// Prices, depth, exchange info and commission rates.
+    MarketData,
+}
+
+impl RequestCategory {
+    pub const ALL: [Self; 4] = [Self::Order, Self::Account, Self::History, Self::MarketData];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Order => "order",
+            Self::Account => "account",
+            Self::History => "history",
+            Self::MarketData => "market_data",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str() == raw.trim())
+    }
+
+    pub fn of(endpoint: RestEndpoint) -> Self {
+        match endpoint {
+            RestEndpoint::Order => Self::Order,
+            RestEndpoint::Account | RestEndpoint::Earn => Self::Account,
+            RestEndpoint::Income | RestEndpoint::Klines => Self::History,
+            RestEndpoint::Price
+            | RestEndpoint::Depth
+            | RestEndpoint::ExchangeInfo
+            | RestEndpoint::Commission => Self::MarketData,
+        }
+    }
+}
+
+/// Binance REST host family; each meters request weight on its own, so
+/// spot traffic never spends the futures allowance or the reverse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RestVenue {
+    /// `api/*` and `sapi/*`: spot, wallet and Simple Earn.
+    Spot,
+    /// `fapi/*`: USD-M futures.
+    Futures,
+    /// `eapi/*`: options.
+    Options,
+}
+
+impl RestVenue {
+    pub const ALL: [Self; 3] = [Self::Spot, Self::Futures, Self::Options];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Spot => "api",
+            Self::Futures => "fapi",
+            Self::Options => "eapi",
+        }
+    }
+
+    pub fn of(market: Market) -> Self {
+        match market {
+            Market::Spot => Self::Spot,
+            Market::Futures => Self::Futures,
+            Market::Options => Self::Options,
+        }
+    }
+}
+
+/// Request weight charged per call, rounded up from Binance's published
+/// weights for the requests behind each endpoint family.
+pub fn endpoint_weight(endpoint: RestEndpoint) -> f64 {
+    match endpoint {
+        RestEndpoint::Order | RestEndpoint::ExchangeInfo | RestEndpoint::Earn => 1.0,
+        RestEndpoint::Price | RestEndpoint::Klines => 2.0,
+        RestEndpoint::Account | RestEndpoint::Depth => 5.0,
+        RestEndpoint::Commission => 20.0,
+        RestEndpoint::Income => 30.0,
+    }
+}
+
+/// Account-wide REST weight budget shared by every client of one mode.
+///
+/// Each `RestVenue` gets its own bucket of `weight_per_min` with the same
+/// reservations. A category can only spend tokens above what the other
+/// categories reserve, so history syncs across many symbols leave the
+/// order reserve untouched.
+///
+/// Example:
+/// - `SANDBOX_QUANT_REST_WEIGHT_PER_MIN=1200`, `SANDBOX_QUANT_REST_RESERVE=order=240,account=60`
+/// - futures klines for 100 symbols drain the `fapi` bucket down to `300`
+///   and then wait; futures order submits can still spend all but the
+///   `60` account reserve, and spot calls are not touched
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateBudgetConfig {
+    /// `0` disables the budget.
+    pub weight_per_min: f64,
+    pub reservations: BTreeMap<RequestCategory, f64>,
+    pub max_wait: Duration,
+}
+
+impl Default for RateBudgetConfig {
+    fn default() -> Self {
+        Self {
+            weight_per_min: DEFAULT_REST_WEIGHT_PER_MIN,
+            reservations: parse_reservations(DEFAULT_REST_RESERVE, DEFAULT_REST_WEIGHT_PER_MIN)
+                .unwrap_or_default(),
+            max_wait: DEFAULT_REST_MAX_WAIT,
+        }
+    }
+}
+
+impl RateBudgetConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_REST_WEIGHT_PER_MIN` (default `1200`, `0` disables)
+    /// - `SANDBOX_QUANT_REST_RESERVE` (default `order=240,account=60`;
+    ///   invalid values keep the default)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let weight_per_min = std::env::var("SANDBOX_QUANT_REST_WEIGHT_PER_MIN")
+            .ok()
+            .and_then(|raw| raw.trim().parse::<f64>().ok())
+            .filter(|weight| weight.is_finite() && *weight >= 0.0)
+            .unwrap_or(defaults.weight_per_min);
+        let reservations = std::env::var("SANDBOX_QUANT_REST_RESERVE")
+            .ok()
+            .and_then(|raw| parse_reservations(&raw, weight_per_min).ok())
+            .or_else(|| parse_reservations(DEFAULT_REST_RESERVE, weight_per_min).ok())
+            .unwrap_or_default();
+        Self {
+            weight_per_min,
+            reservations,
+            ..defaults
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.weight_per_min > 0.0
+    }
+
+    /// Tokens `category` must leave in the bucket for the others.
+    fn reserved_for_others(&self, category: RequestCategory) -> f64 {
+        self.reservations
+            .iter()
+            .filter(|(reserved_for, _)| **reserved_for != category)
+            .map(|(_, weight)| weight)
+            .sum()
+    }
+}
+
+/// `<category>=<weight>,...`; the reservations must leave some weight
+/// unreserved.
+///
+/// Example:
+/// - `order=240,account=60` with `1200/min` -> ok
+/// - `order=1200` with `1200/min` -> error
+pub fn parse_reservations(
+    raw: &str,
+    weight_per_min: f64,
+) -> Result<BTreeMap<RequestCategory, f64>, String> {
+    let mut reservations = BTreeMap::new();
+    for entry in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (key, value) = entry.split_once('=').ok_or_else(|| {
+            format!("invalid rest reserve entry: {entry}. expected category=weight")
+        })?;
+        let category = RequestCategory::parse(key).ok_or_else(|| {
+            format!(
+                "unsupported rest reserve category: {}. expected order, account, history or market_data",
+                key.trim()
+            )
+        })?;
+        let weight = value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|weight| weight.is_finite() && *weight >= 0.0)
+            .ok_or_else(|| format!("invalid rest reserve weight: {}", value.trim()))?;
+        reservations.insert(category, weight);
+    }
+    if weight_per_min > 0.0 && reservations.values().sum::<f64>() >= weight_per_min {
+        return Err(format!(
+            "rest reservations must stay below {weight_per_min} weight/min"
+        ));
+    }
+    Ok(reservations)
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    refilled_at: Instant,
+    granted: BTreeMap<RequestCategory, u64>,
+    refused: BTreeMap<RequestCategory, u64>,
+}
+
+/// One token bucket per venue, each refilled at `weight_per_min` and
+/// starting full.
+#[derive(Debug)]
+pub struct RateBudget {
+    config: RateBudgetConfig,
+    state: Mutex<BTreeMap<RestVenue, BucketState>>,
+}
+
+impl RateBudget {
+    pub fn new(config: RateBudgetConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            state: Mutex::new(
+                RestVenue::ALL
+                    .into_iter()
+                    .map(|venue| {
+                        let bucket = BucketState {
+                            tokens: config.weight_per_min,
+                            refilled_at: now,
+                            granted: BTreeMap::new(),
+                            refused: BTreeMap::new(),
+                        };
+                        (venue, bucket)
+                    })
+                    .collect(),
+            ),
+            config,
+        }
+    }
+
+    /// The budget every client of `mode` in this process draws from,
+    /// configured from the environment on first use.
+    pub fn shared(mode: BinanceMode) -> Arc<Self> {
+        static SHARED: OnceLock<Mutex<BTreeMap<BinanceMode, Arc<RateBudget>>>> = OnceLock::new();
+        SHARED
+            .get_or_init(|| Mutex::new(BTreeMap::new()))
+            .lock()
+            .expect("lock shared rate budgets")
+            .entry(mode)
+            .or_insert_with(|| Arc::new(Self::new(RateBudgetConfig::from_env())))
+            .clone()
+    }
+
+    pub fn config(&self) -> &RateBudgetConfig {
+        &self.config
+    }
+
+    /// Takes `weight` tokens of `venue` for `category` at `now`, or returns
+    /// how long until enough are free.
+    pub fn try_acquire_at(
+        &self,
+        venue: RestVenue,
+        category: RequestCategory,
+        weight: f64,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        if !self.config.enabled() {
+            return Ok(());
+        }
+        let mut buckets = self.state.lock().expect("lock rate budget");
+        let state = buckets.get_mut(&venue).expect("every venue has a bucket");
+        let per_sec = self.config.weight_per_min / 60.0;
+        let elapsed = now.saturating_duration_since(state.refilled_at);
+        state.tokens =
+            (state.tokens + elapsed.as_secs_f64() * per_sec).min(self.config.weight_per_min);
+        state.refilled_at = state.refilled_at.max(now);
+        let available = state.tokens - self.config.reserved_for_others(category);
+        if available >= weight {
+            state.tokens -= weight;
+            *state.granted.entry(category).or_default() += 1;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((weight - available) / per_sec))
+        }
+    }
+
+    /// Waits up to `max_wait` for tokens, then refuses the call locally
+    /// instead of letting Binance ban the IP.
+    pub fn acquire(
+        &self,
+        venue: RestVenue,
+        category: RequestCategory,
+        weight: f64,
+    ) -> Result<(), ExchangeError> {
+        let mut waited = Duration::ZERO;
+        loop {
+            match self.try_acquire_at(venue, category, weight, Instant::now()) {
+                Ok(()) => return Ok(()),
+                Err(wait) if waited + wait <= self.config.max_wait => {
+                    std::thread::sleep(wait);
+                    waited += wait;
+                }
+                Err(wait) => {
+                    *self
+                        .state
+                        .lock()
+                        .expect("lock rate budget")
+                        .get_mut(&venue)
+                        .expect("every venue has a bucket")
+                        .refused
+                        .entry(category)
+                        .or_default() += 1;
+                    return Err(ExchangeError::RateBudgetExhausted {
+                        venue: venue.as_str(),
+                        category: category.as_str(),
+                        retry_in_ms: wait.as_millis() as u64,
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> RateBudgetSnapshot {
+        let buckets = self.state.lock().expect("lock rate budget");
+        RateBudgetSnapshot {
+            weight_per_min: self.config.weight_per_min,
+            venues: buckets
+                .iter()
+                .map(|(venue, state)| VenueBudget {
+                    venue: venue.as_str(),
+                    tokens: state.tokens,
+                    categories: RequestCategory::ALL
+                        .into_iter()
+                        .map(|category| CategoryBudget {
+                            category: category.as_str(),
+                            reserved: self
+                                .config
+                                .reservations
+                                .get(&category)
+                                .copied()
+                                .unwrap_or_default(),
+                            granted: state.granted.get(&category).copied().unwrap_or_default(),
+                            refused: state.refused.get(&category).copied().unwrap_or_default(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RateBudgetSnapshot {
+    /// Per venue.
+    pub weight_per_min: f64,
+    pub venues: Vec<VenueBudget>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VenueBudget {
+    pub venue: &'static str,
+    /// As of the last call; refills between calls are not counted.
+    pub tokens: f64,
+    pub categories: Vec<CategoryBudget>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategoryBudget {
+    pub category: &'static str,
+    pub reserved: f64,
+    pub granted: u64,
+    pub refused: u64,
+}
+
+/// Charges every call against a shared `RateBudget` before passing it on.
+///
+/// Sits outside `MeteredTransport`, so waiting for tokens does not count
+/// as endpoint latency and locally refused calls never reach it.
+pub struct BudgetedTransport {
+    inner: Arc<dyn BinanceTransport>,
+    budget: Arc<RateBudget>,
+}
+
+impl BudgetedTransport {
+    pub fn new(inner: Arc<dyn BinanceTransport>, budget: Arc<RateBudget>) -> Self {
+        Self { inner, budget }
+    }
+
+    /// Charges the bucket of the venue serving `market`.
+    fn charged<T>(
+        &self,
+        endpoint: RestEndpoint,
+        market: Market,
+        call: impl FnOnce(&dyn BinanceTransport) -> Result<T, ExchangeError>,
+    ) -> Result<T, ExchangeError> {
+        self.budget.acquire(
+            RestVenue::of(market),
+            RequestCategory::of(endpoint),
+            endpoint_weight(endpoint),
+        )?;
+        call(self.inner.as_ref())
+    }
+}
+
+impl BinanceTransport for BudgetedTransport {
+    fn transport_name(&self) -> &'static str {
+        self.inner.transport_name()
+    }
+
+    fn load_account_state(&self, market: Market) -> Result<RawAccountState, ExchangeError> {
+        self.charged(RestEndpoint::Account, market, |inner| {
+            inner.load_account_state(market)
+        })
+    }
+
+    fn load_last_price(&self, symbol: &str, market: Market) -> Result<f64, ExchangeError> {
+        self.charged(RestEndpoint::Price, market, |inner| {
+            inner.load_last_price(symbol, market)
+        })
+    }
+
+    fn load_last_trade(&self, symbol: &str, market: Market) -> Result<LastTrade, ExchangeError> {
+        self.charged(RestEndpoint::Price, market, |inner| {
+            inner.load_last_trade(symbol, market)
+        })
+    }
+
+    fn load_symbol_rules(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<RawSymbolRules, ExchangeError> {
+        self.charged(RestEndpoint::ExchangeInfo, market, |inner| {
+            inner.load_symbol_rules(symbol, market)
+        })
+    }
+
+    fn load_option_symbols(&self) -> Result<Vec<String>, ExchangeError> {
+        self.charged(RestEndpoint::ExchangeInfo, Market::Options, |inner| {
+            inner.load_option_symbols()
+        })
+    }
+
+    fn submit_close_order(
+        &self,
+        request: RawCloseOrderRequest,
+    ) -> Result<RawCloseOrderAck, ExchangeError> {
+        self.charged(RestEndpoint::Order, request.market, |inner| {
+            inner.submit_close_order(request)
+        })
+    }
+
+    fn cancel_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<(), ExchangeError> {
+        self.charged(RestEndpoint::Order, market, |inner| {
+            inner.cancel_order(symbol, market, client_order_id)
+        })
+    }
+
+    fn load_order(
+        &self,
+        symbol: &str,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<Option<RawOpenOrder>, ExchangeError> {
+        self.charged(RestEndpoint::Order, market, |inner| {
+            inner.load_order(symbol, market, client_order_id)
+        })
+    }
+
+    fn submit_oco_list(
+        &self,
+        request: RawOcoListRequest,
+    ) -> Result<Option<RawOcoListAck>, ExchangeError> {
+        self.charged(RestEndpoint::Order, request.market, |inner| {
+            inner.submit_oco_list(request)
+        })
+    }
+
+    fn load_today_realized_pnl_usdt(&self) -> Result<f64, ExchangeError> {
+        self.charged(RestEndpoint::Income, Market::Futures, |inner| {
+            inner.load_today_realized_pnl_usdt()
+        })
+    }
+
+    fn load_today_funding_pnl_usdt(&self) -> Result<f64, ExchangeError> {
+        self.charged(RestEndpoint::Income, Market::Futures, |inner| {
+            inner.load_today_funding_pnl_usdt()
+        })
+    }
+
+    fn load_margin_ratio(&self) -> Result<Option<f64>, ExchangeError> {
+        self.charged(RestEndpoint::Account, Market::Futures, |inner| {
+            inner.load_margin_ratio()
+        })
+    }
+
+    fn load_order_book(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<OrderBookDepth>, ExchangeError> {
+        self.charged(RestEndpoint::Depth, market, |inner| {
+            inner.load_order_book(symbol, market)
+        })
+    }
+
+    fn load_commission_rates(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<RawCommissionRates>, ExchangeError> {
+        self.charged(RestEndpoint::Commission, market, |inner| {
+            inner.load_commission_rates(symbol, market)
+        })
+    }
+
+    fn load_daily_bars(
+        &self,
+        symbol: &str,
+        market: Market,
+        days: usize,
+    ) -> Result<Vec<DailyBar>, ExchangeError> {
+        self.charged(RestEndpoint::Klines, market, |inner| {
+            inner.load_daily_bars(symbol, market, days)
+        })
+    }
+
+    fn load_funding_rate(&self, symbol: &str) -> Result<Option<f64>, ExchangeError> {
+        self.charged(RestEndpoint::Price, Market::Futures, |inner| {
+            inner.load_funding_rate(symbol)
+        })
+    }
+
+    fn load_earn_positions(&self, asset: &str) -> Result<Option<Vec<EarnPosition>>, ExchangeError> {
+        self.charged(RestEndpoint::Earn, Market::Spot, |inner| {
+            inner.load_earn_positions(asset)
+        })
+    }
+
+    fn transfer_earn(&self, transfer: &EarnTransfer) -> Result<bool, ExchangeError> {
+        self.charged(RestEndpoint::Earn, Market::Spot, |inner| {
+            inner.transfer_earn(transfer)
+        })
+    }
+
+    fn load_api_permissions(&self) -> Result<Option<ApiPermissions>, ExchangeError> {
+        self.charged(RestEndpoint::Account, Market::Spot, |inner| {
+            inner.load_api_permissions()
+        })
+    }
+
+    fn set_futures_margin_type(
+        &self,
+        symbol: &str,
+        margin_type: MarginType,
+    ) -> Result<bool, ExchangeError> {
+        self.charged(RestEndpoint::Account, Market::Futures, |inner| {
+            inner.set_futures_margin_type(symbol, margin_type)
+        })
+    }
+
+    fn set_futures_leverage(
+        &self,
+        symbol: &str,
+        leverage: u32,
+    ) -> Result<Option<u32>, ExchangeError> {
+        self.charged(RestEndpoint::Account, Market::Futures, |inner| {
+            inner.set_futures_leverage(symbol, leverage)
+        })
+    }
+
+    fn load_symbol_status(
+        &self,
+        symbol: &str,
+        market: Market,
+    ) -> Result<Option<SymbolTradingStatus>, ExchangeError> {
+        self.charged(RestEndpoint::ExchangeInfo, market, |inner| {
+            inner.load_symbol_status(symbol, market)
+        })
+    }
+
+    fn create_listen_key(&self, market: Market) -> Result<Option<String>, ExchangeError> {
+        self.charged(RestEndpoint::Account, market, |inner| {
+            inner.create_listen_key(market)
+        })
+    }
+
+    fn keepalive_listen_key(&self, market: Market, listen_key: &str) -> Result<(), ExchangeError> {
+        self.charged(RestEndpoint::Account, market, |inner| {
+            inner.keepalive_listen_key(market, listen_key)
+        })
+    }
+
+    fn close_listen_key(&self, market: Market, listen_key: &str) -> Result<(), ExchangeError> {
+        self.charged(RestEndpoint::Account, market, |inner| {
+            inner.close_listen_key(market, listen_key)
+        })
+    }
+}
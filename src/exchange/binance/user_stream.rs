@@ -1,2 +1,532 @@
-#[derive(Debug, Default, Clone)]
-pub struct BinanceUserStreamApi;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use futures_util::future::join_all;
+use futures_util::StreamExt;
+use serde_json::Value;
+use tokio::time::{interval, interval_at, sleep, Instant};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::warn;
+
+use crate::domain::balance::BalanceSnapshot;
+use crate::domain::identifiers::OrderId;
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::order::{OpenOrder, OrderStatus};
+use crate::domain::position::{PositionSnapshot, Side};
+use crate::error::exchange_error::ExchangeError;
+use crate::exchange::binance::mapper::map_order_status;
+use crate::exchange::binance::network::NetworkConfig;
+use crate::exchange::facade::ExchangeFacade;
+
+pub const BINANCE_SPOT_USER_STREAM_URL: &str = "wss://stream.binance.com:9443/ws";
+pub const BINANCE_FUTURES_USER_STREAM_URL: &str = "wss://fstream.binance.com/ws";
+pub const BINANCE_DEMO_SPOT_USER_STREAM_URL: &str = "wss://demo-stream.binance.com/ws";
+pub const BINANCE_DEMO_FUTURES_USER_STREAM_URL: &str = "wss://fstream.binancefuture.com/ws";
+
+/// Binance expires a listen key 60 minutes after its last keep-alive.
+pub const LISTEN_KEY_KEEPALIVE: Duration = Duration::from_secs(30 * 60);
+const RECONNECT_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(60);
+const SHUTDOWN_POLL: Duration = Duration::from_millis(500);
+
+/// WebSocket bases the listen key is appended to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinanceUserStreamApi {
+    pub spot_url: String,
+    pub futures_url: String,
+    /// Proxy and trust roots the streams connect with.
+    pub network: NetworkConfig,
+}
+
+impl Default for BinanceUserStreamApi {
+    fn default() -> Self {
+        Self::real()
+    }
+}
+
+impl BinanceUserStreamApi {
+    pub fn real() -> Self {
+        Self {
+            spot_url: BINANCE_SPOT_USER_STREAM_URL.to_string(),
+            futures_url: BINANCE_FUTURES_USER_STREAM_URL.to_string(),
+            network: NetworkConfig::default(),
+        }
+    }
+
+    pub fn demo() -> Self {
+        Self {
+            spot_url: BINANCE_DEMO_SPOT_USER_STREAM_URL.to_string(),
+            futures_url: BINANCE_DEMO_FUTURES_USER_STREAM_URL.to_string(),
+            network: NetworkConfig::default(),
+        }
+    }
+
+    pub fn with_network(self, network: NetworkConfig) -> Self {
+        Self { network, ..self }
+    }
+
+    /// `None` for options, which are not streamed.
+    ///
+    /// Example:
+    /// - futures, `abc` -> `wss://fstream.binance.com/ws/abc`
+    pub fn stream_url(&self, market: Market, listen_key: &str) -> Option<String> {
+        let base = match market {
+            Market::Spot => &self.spot_url,
+            Market::Futures => &self.futures_url,
+            Market::Options => return None,
+        };
+        Some(format!("{base}/{listen_key}"))
+    }
+}
+
+/// One `executionReport` or `ORDER_TRADE_UPDATE`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderUpdate {
+    /// The order as it stands after this update.
+    pub order: OpenOrder,
+    /// Status as the venue sent it, e.g. `PARTIALLY_FILLED`.
+    pub venue_status: String,
+    /// Fill reported by this update; `0` when it reports none.
+    pub last_fill_qty: f64,
+    pub last_fill_price: Option<f64>,
+    /// PnL the fill realized; futures only.
+    pub realized_pnl: Option<f64>,
+    /// Fee charged on the fill, in `commission_asset`.
+    pub commission: Option<f64>,
+    pub commission_asset: Option<String>,
+    pub event_time_ms: Option<i64>,
+}
+
+impl OrderUpdate {
+    /// Whether the order still rests on the book.
+    pub fn is_open(&self) -> bool {
+        self.order.status == OrderStatus::Submitted
+    }
+
+    pub fn is_fill(&self) -> bool {
+        self.last_fill_qty > 0.0
+    }
+}
+
+/// One `outboundAccountPosition` or `ACCOUNT_UPDATE`, holding only the
+/// assets and positions that changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceUpdate {
+    pub market: Market,
+    pub balances: Vec<BalanceSnapshot>,
+    /// Futures positions; always empty on spot.
+    pub positions: Vec<PositionSnapshot>,
+    pub event_time_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserStreamEvent {
+    Connected {
+        market: Market,
+    },
+    /// The stream dropped; updates may be missed until it reconnects.
+    Disconnected {
+        market: Market,
+        error: String,
+    },
+    OrderUpdate(OrderUpdate),
+    BalanceUpdate(BalanceUpdate),
+}
+
+/// Reads `executionReport` and `outboundAccountPosition` on spot, and
+/// `ORDER_TRADE_UPDATE` and `ACCOUNT_UPDATE` on futures; anything else is
+/// `None`.
+pub fn parse_user_stream_event(value: &Value, market: Market) -> Option<UserStreamEvent> {
+    let event_time_ms = value["E"].as_i64();
+    match value["e"].as_str()? {
+        "executionReport" => parse_order_update(value, market, event_time_ms),
+        "ORDER_TRADE_UPDATE" => parse_order_update(&value["o"], market, event_time_ms),
+        "outboundAccountPosition" => {
+            let balances = value["B"]
+                .as_array()?
+                .iter()
+                .filter_map(|balance| {
+                    Some(BalanceSnapshot {
+                        asset: balance["a"].as_str()?.to_string(),
+                        free: decimal(&balance["f"])?,
+                        locked: decimal(&balance["l"])?,
+                    })
+                })
+                .collect();
+            Some(UserStreamEvent::BalanceUpdate(BalanceUpdate {
+                market,
+                balances,
+                positions: Vec::new(),
+                event_time_ms,
+            }))
+        }
+        "ACCOUNT_UPDATE" => {
+            let account = &value["a"];
+            // Same split as the REST account: cross wallet is free, the rest
+            // of the wallet is held.
+            let balances = account["B"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|balance| {
+                    let wallet = decimal(&balance["wb"])?;
+                    let free = decimal(&balance["cw"])?;
+                    Some(BalanceSnapshot {
+                        asset: balance["a"].as_str()?.to_string(),
+                        free,
+                        locked: (wallet - free).max(0.0),
+                    })
+                })
+                .collect();
+            let positions = account["P"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|position| {
+                    Some(PositionSnapshot {
+                        instrument: Instrument::new(position["s"].as_str()?),
+                        market,
+                        signed_qty: decimal(&position["pa"])?,
+                        entry_price: decimal(&position["ep"]).filter(|price| *price > 0.0),
+                    })
+                })
+                .collect();
+            Some(UserStreamEvent::BalanceUpdate(BalanceUpdate {
+                market,
+                balances,
+                positions,
+                event_time_ms,
+            }))
+        }
+        _ => None,
+    }
+}
+
+fn parse_order_update(
+    order: &Value,
+    market: Market,
+    event_time_ms: Option<i64>,
+) -> Option<UserStreamEvent> {
+    let venue_status = order["X"].as_str()?.to_string();
+    // A spot cancel carries the cancel request's id in `c` and the
+    // order's own in `C`.
+    let client_order_id = order["C"]
+        .as_str()
+        .filter(|id| !id.is_empty())
+        .or_else(|| order["c"].as_str())?;
+    Some(UserStreamEvent::OrderUpdate(OrderUpdate {
+        order: OpenOrder {
+            order_id: order["i"].as_u64().map(OrderId),
+            client_order_id: client_order_id.to_string(),
+            instrument: Instrument::new(order["s"].as_str()?),
+            market,
+            side: match order["S"].as_str()? {
+                "SELL" => Side::Sell,
+                _ => Side::Buy,
+            },
+            price: decimal(&order["p"]).filter(|price| *price > 0.0),
+            orig_qty: decimal(&order["q"])?,
+            executed_qty: decimal(&order["z"])?,
+            reduce_only: order["R"].as_bool().unwrap_or(false),
+            status: map_order_status(&venue_status),
+        },
+        venue_status,
+        last_fill_qty: decimal(&order["l"]).unwrap_or(0.0),
+        last_fill_price: decimal(&order["L"]).filter(|price| *price > 0.0),
+        realized_pnl: decimal(&order["rp"]),
+        commission: decimal(&order["n"]),
+        commission_asset: order["N"].as_str().map(str::to_string),
+        event_time_ms,
+    }))
+}
+
+fn decimal(value: &Value) -> Option<f64> {
+    value.as_str()?.parse().ok()
+}
+
+/// Receives what the user data streams push; dropping it stops them.
+#[derive(Debug)]
+pub struct UserStreamHandle {
+    events: Receiver<UserStreamEvent>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl UserStreamHandle {
+    /// Events received since the last call, oldest first.
+    pub fn drain(&self) -> Vec<UserStreamEvent> {
+        self.events.try_iter().collect()
+    }
+}
+
+impl Drop for UserStreamHandle {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Streams each of `markets` from a background thread, reconnecting with
+/// a fresh listen key whenever a stream drops.
+///
+/// A market the exchange opens no listen key for is left out.
+pub fn spawn_user_streams<E>(
+    exchange: E,
+    api: BinanceUserStreamApi,
+    markets: Vec<Market>,
+) -> UserStreamHandle
+where
+    E: ExchangeFacade<Error = ExchangeError> + Clone + Send + Sync + 'static,
+{
+    let (sender, events) = mpsc::channel();
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let stop = shutdown.clone();
+    thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(error) => {
+                warn!(service = "trading-engine", error = %error, "user data stream runtime failed to start");
+                return;
+            }
+        };
+        runtime.block_on(join_all(markets.into_iter().map(|market| {
+            run_user_stream(
+                exchange.clone(),
+                api.clone(),
+                market,
+                sender.clone(),
+                stop.clone(),
+            )
+        })));
+    });
+    UserStreamHandle { events, shutdown }
+}
+
+async fn run_user_stream<E>(
+    exchange: E,
+    api: BinanceUserStreamApi,
+    market: Market,
+    sender: Sender<UserStreamEvent>,
+    shutdown: Arc<AtomicBool>,
+) where
+    E: ExchangeFacade<Error = ExchangeError> + Clone + Send + Sync + 'static,
+{
+    let mut backoff = RECONNECT_MIN;
+    while !shutdown.load(Ordering::Relaxed) {
+        let error =
+            match stream_once(&exchange, &api, market, &sender, &shutdown, &mut backoff).await {
+                Ok(()) => return,
+                Err(error) => error,
+            };
+        warn!(
+            service = "trading-engine",
+            market = format!("{market:?}").to_ascii_lowercase(),
+            error = %error,
+            retry_in_ms = backoff.as_millis() as u64,
+            "user data stream dropped; reconnecting"
+        );
+        if sender
+            .send(UserStreamEvent::Disconnected { market, error })
+            .is_err()
+        {
+            return;
+        }
+        let deadline = Instant::now() + backoff;
+        while Instant::now() < deadline && !shutdown.load(Ordering::Relaxed) {
+            sleep(SHUTDOWN_POLL).await;
+        }
+        backoff = (backoff * 2).min(RECONNECT_MAX);
+    }
+}
+
+/// Runs one listen key until the stream drops. `Ok` means stop for good:
+/// shut down, nobody listening, or nothing to stream.
+async fn stream_once<E>(
+    exchange: &E,
+    api: &BinanceUserStreamApi,
+    market: Market,
+    sender: &Sender<UserStreamEvent>,
+    shutdown: &AtomicBool,
+    backoff: &mut Duration,
+) -> Result<(), String>
+where
+    E: ExchangeFacade<Error = ExchangeError> + Clone + Send + Sync + 'static,
+{
+    let Some(listen_key) =
+        blocking(exchange, move |exchange| exchange.create_listen_key(market)).await?
+    else {
+        return Ok(());
+    };
+    let Some(url) = api.stream_url(market, &listen_key) else {
+        return Ok(());
+    };
+    let mut stream = api.network.connect_websocket(&url).await?;
+    *backoff = RECONNECT_MIN;
+    if sender.send(UserStreamEvent::Connected { market }).is_err() {
+        return Ok(());
+    }
+    let mut keepalive = interval_at(Instant::now() + LISTEN_KEY_KEEPALIVE, LISTEN_KEY_KEEPALIVE);
+    let mut shutdown_poll = interval(SHUTDOWN_POLL);
+    let result = loop {
+        tokio::select! {
+            message = stream.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    let Ok(value) = serde_json::from_str::<Value>(&text) else {
+                        continue;
+                    };
+                    if value["e"] == "listenKeyExpired" {
+                        break Err("listen key expired".to_string());
+                    }
+                    if let Some(event) = parse_user_stream_event(&value, market) {
+                        if sender.send(event).is_err() {
+                            break Ok(());
+                        }
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => break Err("stream closed".to_string()),
+                Some(Ok(_)) => {}
+                Some(Err(error)) => break Err(format!("stream failed: {error}")),
+            },
+            _ = keepalive.tick() => {
+                let key = listen_key.clone();
+                if let Err(error) = blocking(exchange, move |exchange| {
+                    exchange.keepalive_listen_key(market, &key)
+                })
+                .await
+                {
+                    break Err(format!("keep-alive failed: {error}"));
+                }
+            }
+            _ = shutdown_poll.tick() => {
+                if shutdown.load(Ordering::Relaxed) {
+                    break Ok(());
+                }
+            }
+        }
+    };
+    let _ = stream.close(None).await;
+    if result.is_ok() {
+        // The venue hands the same key to every stream of the account, so
+        // a dropped stream leaves it to expire rather than closing it.
+        let _ = blocking(exchange, move |exchange| {
+            exchange.close_listen_key(market, &listen_key)
+        })
+        .await;
+    }
+    result
+}
+
+/// Runs a blocking REST call off the stream's runtime.
+async fn blocking<E, T>(
+    exchange: &E,
+    call: impl FnOnce(&E) -> Result<T, ExchangeError> + Send + 'static,
+) -> Result<T, String>
+where
+    E: Clone + Send + 'static,
+    T: Send + 'static,
+{
+    let exchange = exchange.clone();
+    tokio::task::spawn_blocking(move || call(&exchange))
+        .await
+        .map_err(|error| error.to_string())?
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn spot_execution_report_reads_fill_and_original_client_id_on_cancel() {
+        let fill = json!({
+            "e": "executionReport", "E": 1_700_000_000_100_i64, "s": "BTCUSDT",
+            "c": "sq-entry-1", "C": "", "S": "BUY", "q": "0.01000000",
+            "p": "0.00000000", "X": "PARTIALLY_FILLED", "i": 42,
+            "l": "0.00400000", "z": "0.00400000", "L": "64000.50"
+        });
+        let Some(UserStreamEvent::OrderUpdate(update)) =
+            parse_user_stream_event(&fill, Market::Spot)
+        else {
+            panic!("expected an order update");
+        };
+        assert_eq!(update.order.client_order_id, "sq-entry-1");
+        assert_eq!(update.order.order_id, Some(OrderId(42)));
+        assert_eq!(update.order.price, None);
+        assert_eq!(update.order.executed_qty, 0.004);
+        assert_eq!(update.last_fill_price, Some(64000.5));
+        assert_eq!(update.realized_pnl, None);
+        assert!(update.is_open() && update.is_fill());
+
+        let cancel = json!({
+            "e": "executionReport", "s": "BTCUSDT", "c": "cancel-7",
+            "C": "sq-entry-1", "S": "BUY", "q": "0.01", "p": "63000",
+            "X": "CANCELED", "l": "0", "z": "0.004", "L": "0"
+        });
+        let Some(UserStreamEvent::OrderUpdate(update)) =
+            parse_user_stream_event(&cancel, Market::Spot)
+        else {
+            panic!("expected an order update");
+        };
+        assert_eq!(update.order.client_order_id, "sq-entry-1");
+        assert_eq!(update.order.status, OrderStatus::Cancelled);
+        assert!(!update.is_open() && !update.is_fill());
+    }
+
+    #[test]
+    fn futures_order_trade_update_reads_realized_pnl_and_commission() {
+        let value = json!({
+            "e": "ORDER_TRADE_UPDATE", "E": 1_700_000_000_300_i64,
+            "o": {
+                "s": "ETHUSDT", "c": "strategy-3-7", "S": "SELL", "q": "0.5",
+                "p": "0", "X": "FILLED", "i": 9, "l": "0.5", "z": "0.5",
+                "L": "3250.0", "n": "0.8125", "N": "USDT", "rp": "25.0", "R": true
+            }
+        });
+        let Some(UserStreamEvent::OrderUpdate(update)) =
+            parse_user_stream_event(&value, Market::Futures)
+        else {
+            panic!("expected an order update");
+        };
+        assert_eq!(update.realized_pnl, Some(25.0));
+        assert_eq!(update.commission, Some(0.8125));
+        assert_eq!(update.commission_asset.as_deref(), Some("USDT"));
+        assert!(update.order.reduce_only && update.is_fill());
+    }
+
+    #[test]
+    fn futures_account_update_reads_wallet_split_and_positions() {
+        let value = json!({
+            "e": "ACCOUNT_UPDATE", "E": 1_700_000_000_200_i64,
+            "a": {
+                "B": [{ "a": "USDT", "wb": "1000.0", "cw": "940.0" }],
+                "P": [
+                    { "s": "ETHUSDT", "pa": "-0.5", "ep": "3200.0", "ps": "BOTH" },
+                    { "s": "BTCUSDT", "pa": "0", "ep": "0.0", "ps": "BOTH" }
+                ]
+            }
+        });
+        let Some(UserStreamEvent::BalanceUpdate(update)) =
+            parse_user_stream_event(&value, Market::Futures)
+        else {
+            panic!("expected a balance update");
+        };
+        assert_eq!(update.balances[0].free, 940.0);
+        assert_eq!(update.balances[0].locked, 60.0);
+        assert_eq!(update.positions[0].signed_qty, -0.5);
+        assert_eq!(update.positions[0].entry_price, Some(3200.0));
+        assert_eq!(update.positions[1].entry_price, None);
+        assert_eq!(update.event_time_ms, Some(1_700_000_000_200));
+    }
+
+    #[test]
+    fn unknown_events_are_ignored() {
+        let value = json!({ "e": "balanceUpdate", "a": "BTC", "d": "0.1" });
+        assert_eq!(parse_user_stream_event(&value, Market::Spot), None);
+    }
+}
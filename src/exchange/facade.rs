@@ -1,9 +1,13 @@
 use crate::domain::instrument::Instrument;
 use crate::domain::market::Market;
+use crate::domain::order::OpenOrder;
 use crate::exchange::symbol_rules::SymbolRules;
 use crate::exchange::types::{
-    AuthoritativeSnapshot, CloseOrderAccepted, CloseOrderRequest, SubmitOrderAccepted,
+    ApiPermissions, AuthoritativeSnapshot, CloseOrderAccepted, CloseOrderRequest, EarnAccount,
+    EarnTransfer, LastTrade, MarginType, OcoListAccepted, OcoListRequest, OrderBookDepth,
+    SubmitOrderAccepted, SymbolTradingStatus,
 };
+use crate::market_data::range_stats::SymbolRangeStats;
 
 pub trait ExchangeFacade {
     type Error;
@@ -13,6 +17,20 @@ pub trait ExchangeFacade {
     fn load_today_funding_pnl_usdt(&self) -> Result<f64, Self::Error>;
     fn load_margin_ratio(&self) -> Result<Option<f64>, Self::Error>;
     fn load_last_price(&self, instrument: &Instrument, market: Market) -> Result<f64, Self::Error>;
+    /// `load_last_price` stamped with the venue's trade time.
+    ///
+    /// The time is `None` when the venue does not report one.
+    fn load_last_trade(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<LastTrade, Self::Error> {
+        self.load_last_price(instrument, market)
+            .map(|price| LastTrade {
+                price,
+                exchange_time_ms: None,
+            })
+    }
     fn load_symbol_rules(
         &self,
         instrument: &Instrument,
@@ -23,4 +41,133 @@ pub trait ExchangeFacade {
         request: CloseOrderRequest,
     ) -> Result<CloseOrderAccepted, Self::Error>;
     fn submit_order(&self, request: CloseOrderRequest) -> Result<SubmitOrderAccepted, Self::Error>;
+    fn cancel_order(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<(), Self::Error>;
+    /// Status of one order, resting or finished, by its client order id.
+    ///
+    /// Returns `None` when the venue cannot look orders up or does not know
+    /// the id.
+    fn load_order(
+        &self,
+        _instrument: &Instrument,
+        _market: Market,
+        _client_order_id: &str,
+    ) -> Result<Option<OpenOrder>, Self::Error> {
+        Ok(None)
+    }
+    /// Places a take-profit and a stop as one venue-side OCO list.
+    ///
+    /// Returns `None` when the market has no native list, in which case the
+    /// caller places and links the two legs itself.
+    fn submit_oco_list(
+        &self,
+        _request: OcoListRequest,
+    ) -> Result<Option<OcoListAccepted>, Self::Error> {
+        Ok(None)
+    }
+    /// Returns `None` when the venue exposes no depth for this market.
+    fn load_order_book_depth(
+        &self,
+        _instrument: &Instrument,
+        _market: Market,
+    ) -> Result<Option<OrderBookDepth>, Self::Error> {
+        Ok(None)
+    }
+    /// Last settled funding rate of a perpetual, e.g. `0.0001` per 8h.
+    ///
+    /// Returns `None` when the venue exposes no funding.
+    fn load_funding_rate(&self, _instrument: &Instrument) -> Result<Option<f64>, Self::Error> {
+        Ok(None)
+    }
+    /// Idle spot balance and flexible savings held in `asset`.
+    ///
+    /// Returns `None` when the venue has no savings product.
+    fn load_earn_account(&self, _asset: &str) -> Result<Option<EarnAccount>, Self::Error> {
+        Ok(None)
+    }
+    /// Moves funds between spot and flexible savings.
+    ///
+    /// Returns `false` when the venue has no savings product.
+    fn transfer_earn(&self, _transfer: &EarnTransfer) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+    /// Sets how a futures symbol's positions draw margin; already being on
+    /// `margin_type` counts as set.
+    ///
+    /// Returns `false` when the venue has no futures margin settings.
+    fn set_futures_margin_type(
+        &self,
+        _instrument: &Instrument,
+        _margin_type: MarginType,
+    ) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+    /// Sets a futures symbol's leverage and returns the one now in effect.
+    ///
+    /// Returns `None` when the venue has no futures leverage settings.
+    fn set_futures_leverage(
+        &self,
+        _instrument: &Instrument,
+        _leverage: u32,
+    ) -> Result<Option<u32>, Self::Error> {
+        Ok(None)
+    }
+    /// Opens a user data stream for `market`.
+    ///
+    /// Returns `None` when the venue pushes no account updates.
+    fn create_listen_key(&self, _market: Market) -> Result<Option<String>, Self::Error> {
+        Ok(None)
+    }
+    /// Keeps a listen key from expiring; Binance drops it after 60 minutes.
+    fn keepalive_listen_key(&self, _market: Market, _listen_key: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn close_listen_key(&self, _market: Market, _listen_key: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    /// What the API key may trade.
+    ///
+    /// Returns `None` when the venue cannot say, in which case nothing is
+    /// held back.
+    fn load_api_permissions(&self) -> Result<Option<ApiPermissions>, Self::Error> {
+        Ok(None)
+    }
+    /// Live listing status, read past the static cache so a halt or
+    /// delisting shows up on the next check.
+    ///
+    /// Returns `None` when the venue does not report one.
+    fn load_symbol_status(
+        &self,
+        _instrument: &Instrument,
+        _market: Market,
+    ) -> Result<Option<SymbolTradingStatus>, Self::Error> {
+        Ok(None)
+    }
+    /// Daily ATR, 24h change and volume, as shown in the symbol selector.
+    ///
+    /// Returns `None` when the venue has no daily bars for the symbol.
+    fn load_symbol_range_stats(
+        &self,
+        _instrument: &Instrument,
+        _market: Market,
+    ) -> Result<Option<SymbolRangeStats>, Self::Error> {
+        Ok(None)
+    }
+    /// The account's taker commission on the symbol, e.g. `0.0004`.
+    ///
+    /// Returns `None` when the venue does not publish one.
+    fn load_taker_fee_rate(
+        &self,
+        _instrument: &Instrument,
+        _market: Market,
+    ) -> Result<Option<f64>, Self::Error> {
+        Ok(None)
+    }
+    /// Drops cached static responses (filters, symbol lists, fees) so the
+    /// next lookups go back to the venue.
+    fn invalidate_static_cache(&self) {}
 }
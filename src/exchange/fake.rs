@@ -3,25 +3,47 @@ use std::sync::Mutex;
 
 use crate::domain::instrument::Instrument;
 use crate::domain::market::Market;
+use crate::domain::order::OpenOrder;
 use crate::error::exchange_error::ExchangeError;
 use crate::exchange::facade::ExchangeFacade;
 use crate::exchange::symbol_rules::SymbolRules;
 use crate::exchange::types::{
-    AuthoritativeSnapshot, CloseOrderAccepted, CloseOrderRequest, SubmitOrderAccepted,
+    ApiPermissions, AuthoritativeSnapshot, CloseOrderAccepted, CloseOrderRequest, EarnAccount,
+    EarnPosition, EarnTransfer, EarnTransferKind, LastTrade, MarginType, OcoLegAccepted,
+    OcoListAccepted, OcoListRequest, OrderBookDepth, SubmitOrderAccepted, SymbolTradingStatus,
 };
+use crate::market_data::range_stats::SymbolRangeStats;
 
 #[derive(Debug)]
 pub struct FakeExchange {
     snapshot: Mutex<AuthoritativeSnapshot>,
     symbol_rules: Mutex<BTreeMap<(Instrument, Market), SymbolRules>>,
     last_prices: Mutex<BTreeMap<(Instrument, Market), f64>>,
+    order_books: Mutex<BTreeMap<(Instrument, Market), OrderBookDepth>>,
     close_requests: Mutex<Vec<CloseOrderRequest>>,
     submit_requests: Mutex<Vec<CloseOrderRequest>>,
+    cancel_requests: Mutex<Vec<(Instrument, Market, String)>>,
+    oco_requests: Mutex<Vec<OcoListRequest>>,
     next_close_submit_result: Mutex<Option<Result<CloseOrderAccepted, ExchangeError>>>,
     next_order_submit_result: Mutex<Option<Result<SubmitOrderAccepted, ExchangeError>>>,
     today_realized_pnl_usdt: Mutex<f64>,
     today_funding_pnl_usdt: Mutex<f64>,
     margin_ratio: Mutex<Option<f64>>,
+    funding_rates: Mutex<BTreeMap<Instrument, f64>>,
+    symbol_statuses: Mutex<BTreeMap<(Instrument, Market), SymbolTradingStatus>>,
+    /// How far the venue's clock runs ahead of the local one; trades and
+    /// acks are stamped on it, or left unstamped when `None`.
+    exchange_clock_offset_ms: Mutex<Option<i64>>,
+    /// `None` until savings are enabled with `set_earn_positions`.
+    earn_positions: Mutex<Option<Vec<EarnPosition>>>,
+    earn_transfers: Mutex<Vec<EarnTransfer>>,
+    /// `None` until restricted with `set_api_permissions`.
+    api_permissions: Mutex<Option<ApiPermissions>>,
+    range_stats: Mutex<BTreeMap<Instrument, SymbolRangeStats>>,
+    futures_margin_types: Mutex<BTreeMap<Instrument, MarginType>>,
+    futures_leverages: Mutex<BTreeMap<Instrument, u32>>,
+    /// Order statuses answered by `load_order`, keyed by client order id.
+    order_statuses: Mutex<BTreeMap<String, OpenOrder>>,
 }
 
 impl FakeExchange {
@@ -30,13 +52,26 @@ impl FakeExchange {
             snapshot: Mutex::new(snapshot),
             symbol_rules: Mutex::new(BTreeMap::new()),
             last_prices: Mutex::new(BTreeMap::new()),
+            order_books: Mutex::new(BTreeMap::new()),
             close_requests: Mutex::new(Vec::new()),
             submit_requests: Mutex::new(Vec::new()),
+            cancel_requests: Mutex::new(Vec::new()),
+            oco_requests: Mutex::new(Vec::new()),
             next_close_submit_result: Mutex::new(None),
             next_order_submit_result: Mutex::new(None),
             today_realized_pnl_usdt: Mutex::new(0.0),
             today_funding_pnl_usdt: Mutex::new(0.0),
             margin_ratio: Mutex::new(None),
+            funding_rates: Mutex::new(BTreeMap::new()),
+            symbol_statuses: Mutex::new(BTreeMap::new()),
+            exchange_clock_offset_ms: Mutex::new(None),
+            earn_positions: Mutex::new(None),
+            earn_transfers: Mutex::new(Vec::new()),
+            api_permissions: Mutex::new(None),
+            range_stats: Mutex::new(BTreeMap::new()),
+            futures_margin_types: Mutex::new(BTreeMap::new()),
+            futures_leverages: Mutex::new(BTreeMap::new()),
+            order_statuses: Mutex::new(BTreeMap::new()),
         }
     }
 
@@ -54,6 +89,18 @@ impl FakeExchange {
             .insert((instrument, market), price);
     }
 
+    pub fn set_order_book_depth(
+        &self,
+        instrument: Instrument,
+        market: Market,
+        depth: OrderBookDepth,
+    ) {
+        self.order_books
+            .lock()
+            .expect("lock order_books")
+            .insert((instrument, market), depth);
+    }
+
     pub fn set_next_submit_result(&self, result: Result<CloseOrderAccepted, ExchangeError>) {
         *self
             .next_close_submit_result
@@ -82,6 +129,33 @@ impl FakeExchange {
             .clone()
     }
 
+    pub fn cancel_requests(&self) -> Vec<(Instrument, Market, String)> {
+        self.cancel_requests
+            .lock()
+            .expect("lock cancel_requests")
+            .clone()
+    }
+
+    pub fn oco_requests(&self) -> Vec<OcoListRequest> {
+        self.oco_requests.lock().expect("lock oco_requests").clone()
+    }
+
+    /// Leverage and margin type last set per futures symbol.
+    pub fn futures_settings(&self, instrument: &Instrument) -> (Option<u32>, Option<MarginType>) {
+        (
+            self.futures_leverages
+                .lock()
+                .expect("lock futures_leverages")
+                .get(instrument)
+                .copied(),
+            self.futures_margin_types
+                .lock()
+                .expect("lock futures_margin_types")
+                .get(instrument)
+                .copied(),
+        )
+    }
+
     pub fn replace_snapshot(&self, snapshot: AuthoritativeSnapshot) {
         *self.snapshot.lock().expect("lock snapshot") = snapshot;
     }
@@ -100,9 +174,74 @@ impl FakeExchange {
             .expect("lock today_funding_pnl_usdt") = value;
     }
 
+    pub fn set_api_permissions(&self, permissions: Option<ApiPermissions>) {
+        *self.api_permissions.lock().expect("lock api_permissions") = permissions;
+    }
+
     pub fn set_margin_ratio(&self, value: Option<f64>) {
         *self.margin_ratio.lock().expect("lock margin_ratio") = value;
     }
+
+    pub fn set_funding_rate(&self, instrument: Instrument, rate: f64) {
+        self.funding_rates
+            .lock()
+            .expect("lock funding_rates")
+            .insert(instrument, rate);
+    }
+
+    pub fn set_range_stats(&self, instrument: Instrument, stats: SymbolRangeStats) {
+        self.range_stats
+            .lock()
+            .expect("lock range_stats")
+            .insert(instrument, stats);
+    }
+
+    pub fn set_symbol_status(
+        &self,
+        instrument: Instrument,
+        market: Market,
+        status: SymbolTradingStatus,
+    ) {
+        self.symbol_statuses
+            .lock()
+            .expect("lock symbol_statuses")
+            .insert((instrument, market), status);
+    }
+
+    /// Sets what `load_order` reports for the order's client order id.
+    pub fn set_order_status(&self, order: OpenOrder) {
+        self.order_statuses
+            .lock()
+            .expect("lock order_statuses")
+            .insert(order.client_order_id.clone(), order);
+    }
+
+    pub fn set_exchange_clock_offset_ms(&self, offset_ms: Option<i64>) {
+        *self
+            .exchange_clock_offset_ms
+            .lock()
+            .expect("lock exchange_clock_offset_ms") = offset_ms;
+    }
+
+    fn exchange_time_ms(&self) -> Option<i64> {
+        self.exchange_clock_offset_ms
+            .lock()
+            .expect("lock exchange_clock_offset_ms")
+            .map(|offset_ms| chrono::Utc::now().timestamp_millis() + offset_ms)
+    }
+
+    /// Enables flexible savings; the idle balance is the snapshot's free
+    /// balance of the asset.
+    pub fn set_earn_positions(&self, positions: Vec<EarnPosition>) {
+        *self.earn_positions.lock().expect("lock earn_positions") = Some(positions);
+    }
+
+    pub fn earn_transfers(&self) -> Vec<EarnTransfer> {
+        self.earn_transfers
+            .lock()
+            .expect("lock earn_transfers")
+            .clone()
+    }
 }
 
 impl ExchangeFacade for FakeExchange {
@@ -139,6 +278,17 @@ impl ExchangeFacade for FakeExchange {
             .ok_or(ExchangeError::InvalidResponse)
     }
 
+    fn load_last_trade(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<LastTrade, Self::Error> {
+        Ok(LastTrade {
+            price: self.load_last_price(instrument, market)?,
+            exchange_time_ms: self.exchange_time_ms(),
+        })
+    }
+
     fn load_symbol_rules(
         &self,
         instrument: &Instrument,
@@ -171,6 +321,7 @@ impl ExchangeFacade for FakeExchange {
         } else {
             Ok(CloseOrderAccepted {
                 remote_order_id: "fake-close-1".to_string(),
+                exchange_time_ms: self.exchange_time_ms(),
             })
         }
     }
@@ -190,7 +341,232 @@ impl ExchangeFacade for FakeExchange {
         } else {
             Ok(SubmitOrderAccepted {
                 remote_order_id: "fake-submit-1".to_string(),
+                exchange_time_ms: self.exchange_time_ms(),
             })
         }
     }
+
+    /// Drops the order from the snapshot; unknown ids are rejected like
+    /// Binance `-2011`.
+    fn cancel_order(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<(), Self::Error> {
+        self.cancel_requests
+            .lock()
+            .expect("lock cancel_requests")
+            .push((instrument.clone(), market, client_order_id.to_string()));
+        let mut snapshot = self.snapshot.lock().expect("lock snapshot");
+        let before = snapshot.open_orders.len();
+        snapshot.open_orders.retain(|order| {
+            !(order.instrument == *instrument
+                && order.market == market
+                && order.client_order_id == client_order_id)
+        });
+        if snapshot.open_orders.len() == before {
+            return Err(ExchangeError::RemoteReject {
+                code: -2011,
+                message: "Unknown order sent.".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn load_order(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+        client_order_id: &str,
+    ) -> Result<Option<OpenOrder>, Self::Error> {
+        Ok(self
+            .order_statuses
+            .lock()
+            .expect("lock order_statuses")
+            .get(client_order_id)
+            .filter(|order| order.instrument == *instrument && order.market == market)
+            .cloned())
+    }
+
+    /// Accepts spot lists like Binance `orderList/oco`; futures has none.
+    fn submit_oco_list(
+        &self,
+        request: OcoListRequest,
+    ) -> Result<Option<OcoListAccepted>, Self::Error> {
+        let market = request.market;
+        let legs = [
+            &request.take_profit_client_order_id,
+            &request.stop_client_order_id,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, client_order_id)| OcoLegAccepted {
+            client_order_id: client_order_id.clone(),
+            remote_order_id: format!("fake-oco-leg-{}", index + 1),
+        })
+        .collect();
+        self.oco_requests
+            .lock()
+            .expect("lock oco_requests")
+            .push(request);
+        if market != Market::Spot {
+            return Ok(None);
+        }
+        Ok(Some(OcoListAccepted {
+            remote_list_id: "fake-oco-1".to_string(),
+            list_status: "EXEC_STARTED".to_string(),
+            exchange_time_ms: self.exchange_time_ms(),
+            legs,
+        }))
+    }
+
+    fn load_order_book_depth(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<Option<OrderBookDepth>, Self::Error> {
+        Ok(self
+            .order_books
+            .lock()
+            .expect("lock order_books")
+            .get(&(instrument.clone(), market))
+            .cloned())
+    }
+
+    fn load_funding_rate(&self, instrument: &Instrument) -> Result<Option<f64>, Self::Error> {
+        Ok(self
+            .funding_rates
+            .lock()
+            .expect("lock funding_rates")
+            .get(instrument)
+            .copied())
+    }
+
+    fn load_symbol_status(
+        &self,
+        instrument: &Instrument,
+        market: Market,
+    ) -> Result<Option<SymbolTradingStatus>, Self::Error> {
+        Ok(self
+            .symbol_statuses
+            .lock()
+            .expect("lock symbol_statuses")
+            .get(&(instrument.clone(), market))
+            .cloned())
+    }
+
+    fn load_symbol_range_stats(
+        &self,
+        instrument: &Instrument,
+        _market: Market,
+    ) -> Result<Option<SymbolRangeStats>, Self::Error> {
+        Ok(self
+            .range_stats
+            .lock()
+            .expect("lock range_stats")
+            .get(instrument)
+            .copied())
+    }
+
+    fn load_api_permissions(&self) -> Result<Option<ApiPermissions>, Self::Error> {
+        Ok(*self.api_permissions.lock().expect("lock api_permissions"))
+    }
+
+    fn set_futures_margin_type(
+        &self,
+        instrument: &Instrument,
+        margin_type: MarginType,
+    ) -> Result<bool, Self::Error> {
+        self.futures_margin_types
+            .lock()
+            .expect("lock futures_margin_types")
+            .insert(instrument.clone(), margin_type);
+        Ok(true)
+    }
+
+    fn set_futures_leverage(
+        &self,
+        instrument: &Instrument,
+        leverage: u32,
+    ) -> Result<Option<u32>, Self::Error> {
+        self.futures_leverages
+            .lock()
+            .expect("lock futures_leverages")
+            .insert(instrument.clone(), leverage);
+        Ok(Some(leverage))
+    }
+
+    fn load_earn_account(&self, asset: &str) -> Result<Option<EarnAccount>, Self::Error> {
+        let Some(positions) = self
+            .earn_positions
+            .lock()
+            .expect("lock earn_positions")
+            .clone()
+        else {
+            return Ok(None);
+        };
+        let idle = self
+            .snapshot
+            .lock()
+            .expect("lock snapshot")
+            .balances
+            .iter()
+            .find(|balance| balance.asset == asset)
+            .map_or(0.0, |balance| balance.free);
+        Ok(Some(EarnAccount {
+            idle,
+            positions: positions
+                .into_iter()
+                .filter(|position| position.asset == asset)
+                .collect(),
+        }))
+    }
+
+    /// Moves the amount between the asset's first snapshot balance and its
+    /// first savings position, rejecting more than either side holds.
+    fn transfer_earn(&self, transfer: &EarnTransfer) -> Result<bool, Self::Error> {
+        let mut earn_positions = self.earn_positions.lock().expect("lock earn_positions");
+        let Some(positions) = earn_positions.as_mut() else {
+            return Ok(false);
+        };
+        let mut snapshot = self.snapshot.lock().expect("lock snapshot");
+        let balance = snapshot
+            .balances
+            .iter_mut()
+            .find(|balance| balance.asset == transfer.asset)
+            .ok_or(ExchangeError::InvalidResponse)?;
+        if !positions
+            .iter()
+            .any(|position| position.asset == transfer.asset)
+        {
+            positions.push(EarnPosition {
+                asset: transfer.asset.clone(),
+                product_id: format!("{}001", transfer.asset),
+                amount: 0.0,
+                apr_pct: 0.0,
+            });
+        }
+        let position = positions
+            .iter_mut()
+            .find(|position| position.asset == transfer.asset)
+            .ok_or(ExchangeError::InvalidResponse)?;
+        let (from, to) = match transfer.kind {
+            EarnTransferKind::Subscribe => (&mut balance.free, &mut position.amount),
+            EarnTransferKind::Redeem => (&mut position.amount, &mut balance.free),
+        };
+        if transfer.amount > *from + f64::EPSILON {
+            return Err(ExchangeError::RemoteReject {
+                code: -6005,
+                message: "insufficient balance".to_string(),
+            });
+        }
+        *from -= transfer.amount;
+        *to += transfer.amount;
+        self.earn_transfers
+            .lock()
+            .expect("lock earn_transfers")
+            .push(transfer.clone());
+        Ok(true)
+    }
 }
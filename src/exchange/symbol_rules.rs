@@ -3,4 +3,6 @@ pub struct SymbolRules {
     pub min_qty: f64,
     pub max_qty: f64,
     pub step_size: f64,
+    /// Price increment from `PRICE_FILTER`; `0` when the venue reports none.
+    pub tick_size: f64,
 }
@@ -13,6 +13,60 @@ pub struct AuthoritativeSnapshot {
     pub open_orders: Vec<OpenOrder>,
 }
 
+/// One daily kline, oldest first in any list of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DailyBar {
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Traded volume in the quote asset, e.g. USDT.
+    pub quote_volume: f64,
+}
+
+/// Resting liquidity as `(price, qty)` levels, best price first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrderBookDepth {
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+impl OrderBookDepth {
+    pub fn mid_price(&self) -> Option<f64> {
+        let (best_bid, _) = self.bids.first()?;
+        let (best_ask, _) = self.asks.first()?;
+        Some((best_bid + best_ask) / 2.0)
+    }
+
+    /// Best ask minus best bid.
+    pub fn spread(&self) -> Option<f64> {
+        let (best_bid, _) = self.bids.first()?;
+        let (best_ask, _) = self.asks.first()?;
+        Some(best_ask - best_bid)
+    }
+
+    /// Quote notional resting on each side within `band_bps` of the mid.
+    ///
+    /// Example:
+    /// - mid `100`, band `10` bps -> bids `>= 99.9`, asks `<= 100.1`
+    pub fn notional_within_bps(&self, band_bps: f64) -> Option<(f64, f64)> {
+        let mid = self.mid_price()?;
+        let band = mid * band_bps / 10_000.0;
+        let bid_notional = self
+            .bids
+            .iter()
+            .filter(|(price, _)| *price >= mid - band)
+            .map(|(price, qty)| price * qty)
+            .sum();
+        let ask_notional = self
+            .asks
+            .iter()
+            .filter(|(price, _)| *price <= mid + band)
+            .map(|(price, qty)| price * qty)
+            .sum();
+        Some((bid_notional, ask_notional))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct CloseOrderRequest {
     pub instrument: Instrument,
@@ -22,16 +76,269 @@ pub struct CloseOrderRequest {
     pub qty_text: String,
     pub order_type: OrderType,
     pub reduce_only: bool,
+    /// Sent as `newClientOrderId`; the venue assigns one when `None`.
+    pub client_order_id: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CloseOrderAccepted {
     pub remote_order_id: String,
+    /// When the venue accepted the order, on its own clock.
+    pub exchange_time_ms: Option<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SubmitOrderAccepted {
     pub remote_order_id: String,
+    /// When the venue accepted the order, on its own clock.
+    pub exchange_time_ms: Option<i64>,
+}
+
+/// A take-profit and a stop on one position that the venue links into a
+/// single order list, so a fill on either leg cancels the other there.
+///
+/// Example:
+/// - sell `0.2 BTCUSDT`, take-profit `66000`, stop `62000`, limit `61900`
+/// - spot `LIMIT_MAKER` above and `STOP_LOSS_LIMIT` below
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcoListRequest {
+    pub instrument: Instrument,
+    pub market: Market,
+    pub side: Side,
+    pub qty: f64,
+    pub qty_text: String,
+    pub take_profit_price: f64,
+    pub stop_price: f64,
+    /// Limit rested once the stop trades; a stop-market leg when `None`.
+    pub stop_limit_price: Option<f64>,
+    pub list_client_order_id: String,
+    pub take_profit_client_order_id: String,
+    pub stop_client_order_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OcoListAccepted {
+    pub remote_list_id: String,
+    /// `listStatusType`, e.g. `EXEC_STARTED`.
+    pub list_status: String,
+    pub exchange_time_ms: Option<i64>,
+    pub legs: Vec<OcoLegAccepted>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OcoLegAccepted {
+    pub client_order_id: String,
+    pub remote_order_id: String,
+}
+
+/// Latest traded price, stamped with the venue's trade time where the
+/// endpoint reports one.
+///
+/// Example:
+/// - futures ticker `{"price":"64000.1","time":1775980800123}`
+/// - `price=64000.1`, `exchange_time_ms=Some(1775980800123)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LastTrade {
+    pub price: f64,
+    pub exchange_time_ms: Option<i64>,
+}
+
+/// Funds parked in a flexible savings product, redeemable on demand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarnPosition {
+    pub asset: String,
+    pub product_id: String,
+    pub amount: f64,
+    /// Latest annual rate, e.g. `4.2` for 4.2%.
+    pub apr_pct: f64,
+}
+
+/// Flexible savings for one asset next to the spot balance they sweep from.
+///
+/// Example:
+/// - `idle=1800` USDT free in spot, `positions=[USDT001 5000 @ 4.2%]`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EarnAccount {
+    pub idle: f64,
+    pub positions: Vec<EarnPosition>,
+}
+
+impl EarnAccount {
+    pub fn saved(&self) -> f64 {
+        self.positions.iter().map(|position| position.amount).sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EarnTransferKind {
+    /// Spot balance into savings.
+    Subscribe,
+    /// Savings back into the spot balance.
+    Redeem,
+}
+
+impl EarnTransferKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Subscribe => "subscribe",
+            Self::Redeem => "redeem",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarnTransfer {
+    pub kind: EarnTransferKind,
+    pub asset: String,
+    pub amount: f64,
+}
+
+/// How a futures symbol's positions draw margin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarginType {
+    /// Each position holds its own margin; a liquidation stops there.
+    Isolated,
+    /// Positions share the account's margin balance.
+    Cross,
+}
+
+impl MarginType {
+    /// Example:
+    /// - `isolated` -> `Isolated`
+    /// - `cross`, `crossed` -> `Cross`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "isolated" => Some(Self::Isolated),
+            "cross" | "crossed" => Some(Self::Cross),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Isolated => "isolated",
+            Self::Cross => "cross",
+        }
+    }
+
+    /// The `marginType` value Binance futures expects, e.g. `CROSSED`.
+    pub fn binance_value(self) -> &'static str {
+        match self {
+            Self::Isolated => "ISOLATED",
+            Self::Cross => "CROSSED",
+        }
+    }
+}
+
+/// Leverage and margin type one futures symbol trades at.
+///
+/// Example:
+/// - `BTCUSDT=10:isolated` -> `10x isolated`
+/// - `ETHUSDT=5` -> `5x`, margin type left as the venue has it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuturesLeverage {
+    pub leverage: u32,
+    pub margin_type: Option<MarginType>,
+}
+
+impl FuturesLeverage {
+    pub fn label(&self) -> String {
+        match self.margin_type {
+            Some(margin_type) => format!("{}x {}", self.leverage, margin_type.as_str()),
+            None => format!("{}x", self.leverage),
+        }
+    }
+}
+
+/// What the API key is allowed to do.
+///
+/// Example:
+/// - `enableReading=true enableSpotAndMarginTrading=true enableFutures=false`
+/// - `label() == "spot"`, futures orders are refused before they are sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiPermissions {
+    pub reading: bool,
+    pub spot_trading: bool,
+    pub futures_trading: bool,
+    pub options_trading: bool,
+}
+
+impl ApiPermissions {
+    pub fn can_trade(&self, market: Market) -> bool {
+        match market {
+            Market::Spot => self.spot_trading,
+            Market::Futures => self.futures_trading,
+            Market::Options => self.options_trading,
+        }
+    }
+
+    pub fn read_only(&self) -> bool {
+        !(self.spot_trading || self.futures_trading || self.options_trading)
+    }
+
+    /// Example:
+    /// - `spot+futures`, `spot`, `read-only`
+    pub fn label(&self) -> String {
+        if self.read_only() {
+            return "read-only".to_string();
+        }
+        [
+            (self.spot_trading, "spot"),
+            (self.futures_trading, "futures"),
+            (self.options_trading, "options"),
+        ]
+        .into_iter()
+        .filter_map(|(allowed, name)| allowed.then_some(name))
+        .collect::<Vec<_>>()
+        .join("+")
+    }
+}
+
+/// Whether a listed symbol can still be traded, from exchangeInfo `status`.
+///
+/// Example:
+/// - `TRADING` -> `Trading`
+/// - `BREAK`, `HALT`, `SETTLING` -> `Halted("BREAK")`
+/// - `CLOSE`, `DELIVERED`, or no longer listed -> `Delisted("CLOSE")`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolTradingStatus {
+    Trading,
+    /// Paused by the venue; expected to resume.
+    Halted(String),
+    /// Closed for good, or missing from exchangeInfo (`DELISTED`).
+    Delisted(String),
+}
+
+impl SymbolTradingStatus {
+    pub fn from_exchange(status: &str) -> Self {
+        match status {
+            "TRADING" => Self::Trading,
+            "CLOSE" | "DELIVERED" | "END_OF_DAY" | "DELISTED" => Self::Delisted(status.to_string()),
+            other => Self::Halted(other.to_string()),
+        }
+    }
+
+    pub fn is_trading(&self) -> bool {
+        matches!(self, Self::Trading)
+    }
+
+    /// Example:
+    /// - `trading`, `halted`, `delisted`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Trading => "trading",
+            Self::Halted(_) => "halted",
+            Self::Delisted(_) => "delisted",
+        }
+    }
+
+    /// The venue's own status, e.g. `BREAK`.
+    pub fn status(&self) -> &str {
+        match self {
+            Self::Trading => "TRADING",
+            Self::Halted(status) | Self::Delisted(status) => status,
+        }
+    }
 }
 
 impl From<ExecutionPlan> for CloseOrderRequest {
@@ -44,6 +351,7 @@ impl From<ExecutionPlan> for CloseOrderRequest {
             qty_text: plan.qty.to_string(),
             order_type: OrderType::Market,
             reduce_only: plan.reduce_only,
+            client_order_id: None,
         }
     }
 }
@@ -12,3 +12,14 @@ pub struct CloseSymbolResult {
     pub instrument: Instrument,
     pub result: CloseSubmitResult,
 }
+
+/// Outcome of flattening one instrument: its open orders, then its position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmergencyCloseResult {
+    pub instrument: Instrument,
+    /// Client order ids cancelled at the venue.
+    pub cancelled: Vec<String>,
+    /// Client order ids whose cancel failed, e.g. already filled.
+    pub cancel_failed: Vec<String>,
+    pub close: CloseSubmitResult,
+}
@@ -2,11 +2,31 @@ use crate::domain::exposure::Exposure;
 use crate::domain::instrument::Instrument;
 use crate::domain::order_type::OrderType;
 use crate::domain::position::Side;
+use crate::execution::trailing_stop::TrailDistance;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum CommandSource {
     User,
     System,
+    /// Placed by a strategy watch; `version` fingerprints its parameters.
+    Strategy {
+        watch_id: u64,
+        version: String,
+    },
+}
+
+impl CommandSource {
+    /// Order history tag for orders placed on behalf of this source.
+    ///
+    /// Example:
+    /// - `Strategy { watch_id: 3, version: "3f9a0c1d" }` -> `strategy:3@3f9a0c1d`
+    pub fn tag(&self) -> String {
+        match self {
+            Self::User => "manual".to_string(),
+            Self::System => "system".to_string(),
+            Self::Strategy { watch_id, version } => format!("strategy:{watch_id}@{version}"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -31,4 +51,45 @@ pub enum ExecutionCommand {
     CloseAll {
         source: CommandSource,
     },
+    PlaceTrailingStop {
+        instrument: Instrument,
+        trail: TrailDistance,
+        source: CommandSource,
+    },
+    PlaceTakeProfitLadder {
+        instrument: Instrument,
+        profile: String,
+        source: CommandSource,
+    },
+    /// GTC limit order resting at `price`, e.g. from the depth ladder.
+    PlaceLimitOrder {
+        instrument: Instrument,
+        side: Side,
+        qty: f64,
+        price: f64,
+        source: CommandSource,
+    },
+    CancelOrder {
+        instrument: Instrument,
+        client_order_id: String,
+        source: CommandSource,
+    },
+    /// Limit order worked as slices of `visible_qty` until `qty` fills.
+    PlaceIceberg {
+        instrument: Instrument,
+        side: Side,
+        qty: f64,
+        visible_qty: f64,
+        price: f64,
+        source: CommandSource,
+    },
+    /// Reduce-only take-profit limit plus stop-market on the open position,
+    /// or stop-limit when `stop_limit_price` is set.
+    PlaceOco {
+        instrument: Instrument,
+        take_profit_price: f64,
+        stop_price: f64,
+        stop_limit_price: Option<f64>,
+        source: CommandSource,
+    },
 }
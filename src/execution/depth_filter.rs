@@ -0,0 +1,129 @@
+use std::env;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::exchange::types::OrderBookDepth;
+use crate::execution::command::ExecutionCommand;
+
+pub const DEFAULT_DEPTH_MIN_COVER: f64 = 3.0;
+pub const DEFAULT_DEPTH_RETRY_SECS: i64 = 5;
+pub const DEFAULT_DEPTH_MAX_RETRIES: u32 = 3;
+
+/// Pre-trade filter that holds market buys back from a thin ask book.
+///
+/// Example:
+/// - band `10` bps, min cover `3`
+/// - a `1,000 USDT` buy needs at least `3,000 USDT` of asks within 0.1% of mid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthFilterConfig {
+    pub band_bps: f64,
+    pub min_cover: f64,
+    pub retry_secs: i64,
+    pub max_retries: u32,
+}
+
+impl DepthFilterConfig {
+    pub fn new(band_bps: f64) -> Self {
+        Self {
+            band_bps,
+            min_cover: DEFAULT_DEPTH_MIN_COVER,
+            retry_secs: DEFAULT_DEPTH_RETRY_SECS,
+            max_retries: DEFAULT_DEPTH_MAX_RETRIES,
+        }
+    }
+
+    /// Reads the filter from the environment; unset band means disabled.
+    ///
+    /// Reads:
+    /// - `SANDBOX_QUANT_DEPTH_FILTER_BPS` (e.g. `10`)
+    /// - `SANDBOX_QUANT_DEPTH_FILTER_MIN_COVER` (default `3`)
+    /// - `SANDBOX_QUANT_DEPTH_FILTER_RETRY_SECS` (default `5`)
+    /// - `SANDBOX_QUANT_DEPTH_FILTER_MAX_RETRIES` (default `3`)
+    pub fn from_env() -> Option<Self> {
+        Self::from_vars(|key| env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let read = |key: &str| var(key).map(|value| value.trim().to_string());
+        let band_bps = read("SANDBOX_QUANT_DEPTH_FILTER_BPS")?
+            .parse::<f64>()
+            .ok()
+            .filter(|value| *value > 0.0)?;
+        let mut config = Self::new(band_bps);
+        if let Some(value) = read("SANDBOX_QUANT_DEPTH_FILTER_MIN_COVER")
+            .and_then(|value| value.parse::<f64>().ok())
+            .filter(|value| *value > 0.0)
+        {
+            config.min_cover = value;
+        }
+        if let Some(value) = read("SANDBOX_QUANT_DEPTH_FILTER_RETRY_SECS")
+            .and_then(|value| value.parse::<i64>().ok())
+            .filter(|value| *value >= 0)
+        {
+            config.retry_secs = value;
+        }
+        if let Some(value) =
+            read("SANDBOX_QUANT_DEPTH_FILTER_MAX_RETRIES").and_then(|value| value.parse().ok())
+        {
+            config.max_retries = value;
+        }
+        Some(config)
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "band_bps={} min_cover={} retry_secs={} max_retries={}",
+            self.band_bps, self.min_cover, self.retry_secs, self.max_retries
+        )
+    }
+
+    /// Measures the book around mid for an order of `order_notional_usdt`.
+    pub fn check(&self, book: &OrderBookDepth, order_notional_usdt: f64) -> Option<DepthCheck> {
+        let (bid_notional_usdt, ask_notional_usdt) = book.notional_within_bps(self.band_bps)?;
+        let total = bid_notional_usdt + ask_notional_usdt;
+        let required_ask_usdt = order_notional_usdt * self.min_cover;
+        Some(DepthCheck {
+            bid_notional_usdt,
+            ask_notional_usdt,
+            required_ask_usdt,
+            imbalance: if total > f64::EPSILON {
+                (bid_notional_usdt - ask_notional_usdt) / total
+            } else {
+                0.0
+            },
+            passed: ask_notional_usdt >= required_ask_usdt,
+        })
+    }
+}
+
+/// Measured depth around mid at submit time.
+///
+/// `imbalance` is `(bids - asks) / (bids + asks)` within the band, so
+/// `+1` means only bids and `-1` means only asks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthCheck {
+    pub bid_notional_usdt: f64,
+    pub ask_notional_usdt: f64,
+    pub required_ask_usdt: f64,
+    pub imbalance: f64,
+    pub passed: bool,
+}
+
+/// A command held back by the depth filter until `retry_at`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeferredExecution {
+    pub command: ExecutionCommand,
+    pub retry_at: DateTime<Utc>,
+    pub attempts: u32,
+}
+
+impl DeferredExecution {
+    pub fn new(command: ExecutionCommand, retry_secs: i64, attempts: u32) -> Self {
+        Self {
+            command,
+            retry_at: Utc::now() + Duration::seconds(retry_secs),
+            attempts,
+        }
+    }
+}
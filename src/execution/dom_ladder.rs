@@ -0,0 +1,114 @@
+use crate::domain::order::OpenOrder;
+use crate::exchange::types::OrderBookDepth;
+use crate::execution::slippage_guard::tick_decimals;
+
+pub const DEFAULT_DOM_LEVELS: usize = 10;
+pub const MAX_DOM_LEVELS: usize = 50;
+
+/// Limit price for a ladder order, either absolute or a book level.
+///
+/// Example:
+/// - `64000.5` -> `Exact(64000.5)`
+/// - `b1` -> best bid, `a3` -> third ask level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DomPrice {
+    Exact(f64),
+    Bid(usize),
+    Ask(usize),
+}
+
+impl DomPrice {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let level = |digits: &str| digits.parse::<usize>().ok().filter(|level| *level >= 1);
+        if let Some(digits) = raw.strip_prefix('b') {
+            return level(digits).map(Self::Bid);
+        }
+        if let Some(digits) = raw.strip_prefix('a') {
+            return level(digits).map(Self::Ask);
+        }
+        raw.parse::<f64>()
+            .ok()
+            .filter(|price| price.is_finite() && *price > 0.0)
+            .map(Self::Exact)
+    }
+
+    /// `None` when the book is shallower than the requested level.
+    pub fn resolve(&self, depth: &OrderBookDepth) -> Option<f64> {
+        match self {
+            Self::Exact(price) => Some(*price),
+            Self::Bid(level) => depth.bids.get(level - 1).map(|(price, _)| *price),
+            Self::Ask(level) => depth.asks.get(level - 1).map(|(price, _)| *price),
+        }
+    }
+}
+
+/// One price row of the depth ladder.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DomLevel {
+    pub price: f64,
+    pub bid_qty: f64,
+    pub ask_qty: f64,
+    /// Our resting limit orders at this price.
+    pub own_orders: Vec<OpenOrder>,
+}
+
+/// Price ladder with asks above bids, highest price first.
+///
+/// Resting orders priced off the displayed book still get a row, so every
+/// working order can be seen and cancelled from the ladder.
+///
+/// Example:
+/// - asks `[(101, 2), (102, 1)]`, bids `[(100, 3)]`, own buy `0.5 @ 99`
+/// - rows `102 ask=1`, `101 ask=2`, `100 bid=3`, `99 own=[buy 0.5]`
+pub fn build_dom_ladder(
+    depth: &OrderBookDepth,
+    open_orders: &[OpenOrder],
+    levels: usize,
+) -> Vec<DomLevel> {
+    let mut rows: Vec<DomLevel> = Vec::new();
+    for (price, qty) in depth.asks.iter().take(levels) {
+        level_at(&mut rows, *price).ask_qty += qty;
+    }
+    for (price, qty) in depth.bids.iter().take(levels) {
+        level_at(&mut rows, *price).bid_qty += qty;
+    }
+    for order in open_orders {
+        if let Some(price) = order.price {
+            level_at(&mut rows, price).own_orders.push(order.clone());
+        }
+    }
+    rows.sort_by(|left, right| right.price.total_cmp(&left.price));
+    rows
+}
+
+fn level_at(rows: &mut Vec<DomLevel>, price: f64) -> &mut DomLevel {
+    let tolerance = price.abs() * 1e-9;
+    let index = match rows
+        .iter()
+        .position(|row| (row.price - price).abs() <= tolerance)
+    {
+        Some(index) => index,
+        None => {
+            rows.push(DomLevel {
+                price,
+                ..DomLevel::default()
+            });
+            rows.len() - 1
+        }
+    };
+    &mut rows[index]
+}
+
+/// Snaps a limit price to the nearest tick.
+///
+/// Example:
+/// - `100.26` with tick `0.1` -> `100.3`
+pub fn round_to_tick(price: f64, tick_size: f64) -> f64 {
+    if tick_size <= f64::EPSILON {
+        return price;
+    }
+    let rounded = (price / tick_size).round() * tick_size;
+    format!("{:.*}", tick_decimals(tick_size), rounded)
+        .parse()
+        .unwrap_or(rounded)
+}
@@ -0,0 +1,146 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::error::execution_error::ExecutionError;
+use crate::execution::paper_shadow::shadow_watch_id;
+
+/// Signals kept for the funnel; the oldest drop out first.
+pub const MAX_FUNNEL_SIGNALS: usize = 10_000;
+
+/// Hours `strategy funnel` looks back when none are given.
+pub const DEFAULT_FUNNEL_WINDOW_HOURS: u32 = 24;
+
+/// Client order id of a strategy order that is not shadowed, e.g.
+/// `strategy-3-12` for the twelfth tagged order, sent by watch `3`.
+pub fn strategy_client_order_id(watch_id: u64, seq: u64) -> String {
+    format!("strategy-{watch_id}-{seq}")
+}
+
+/// Watch id carried by a strategy order's client order id, shadowed or not.
+///
+/// Example:
+/// - `strategy-3-12` -> `Some(3)`; `shadow-3-12` -> `Some(3)`
+/// - `oco-1-tp` -> `None`
+pub fn strategy_watch_id(client_order_id: &str) -> Option<u64> {
+    match client_order_id.strip_prefix("strategy-") {
+        Some(rest) => rest.split('-').next()?.parse().ok(),
+        None => shadow_watch_id(client_order_id),
+    }
+}
+
+/// How far one strategy signal made it towards a fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunnelStage {
+    Signal,
+    PassedRisk,
+    Submitted,
+    Filled,
+}
+
+/// One command a strategy watch sent to execution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunnelSignal {
+    pub at: DateTime<Utc>,
+    pub watch_id: u64,
+    pub reached: FunnelStage,
+    /// Why it stopped short of an order; `None` while it can still fill.
+    pub drop_reason: Option<&'static str>,
+    pub client_order_id: Option<String>,
+}
+
+/// Counts per stage for one watch; each stage includes the ones after it.
+///
+/// Example:
+/// - `signals=5 passed_risk=3 submitted=3 filled=2 drops={blackout: 2}`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WatchFunnel {
+    pub watch_id: u64,
+    pub signals: usize,
+    pub passed_risk: usize,
+    pub submitted: usize,
+    pub filled: usize,
+    pub drops: BTreeMap<&'static str, usize>,
+}
+
+/// Signal-to-fill funnel of every strategy watch, so a quiet watch shows
+/// whether it stopped signaling or something downstream held it back.
+///
+/// Every strategy command is a signal. It passes the risk gate unless an
+/// entry guard or order check refuses it, is submitted once the venue
+/// takes an order, and fills on the first fill reported for that order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SignalFunnel {
+    signals: VecDeque<FunnelSignal>,
+}
+
+impl SignalFunnel {
+    /// Records how the command from `watch_id` ended; `client_order_id` is
+    /// the order it was sent as, when one went out.
+    pub fn record(
+        &mut self,
+        watch_id: u64,
+        at: DateTime<Utc>,
+        result: Result<bool, &ExecutionError>,
+        client_order_id: Option<String>,
+    ) {
+        let (reached, drop_reason) = match result {
+            Ok(true) => (FunnelStage::Submitted, None),
+            Ok(false) => (FunnelStage::PassedRisk, Some("already_at_target")),
+            Err(error @ ExecutionError::SubmitFailed(_)) => {
+                (FunnelStage::PassedRisk, Some(error.reason()))
+            }
+            Err(error) => (FunnelStage::Signal, Some(error.reason())),
+        };
+        if self.signals.len() == MAX_FUNNEL_SIGNALS {
+            self.signals.pop_front();
+        }
+        self.signals.push_back(FunnelSignal {
+            at,
+            watch_id,
+            reached,
+            drop_reason,
+            client_order_id: client_order_id.filter(|_| reached == FunnelStage::Submitted),
+        });
+    }
+
+    /// Moves the submitted signal behind `client_order_id` to filled;
+    /// returns its watch, or `None` for other orders and repeat fills.
+    pub fn on_fill(&mut self, client_order_id: &str) -> Option<u64> {
+        strategy_watch_id(client_order_id)?;
+        let signal = self.signals.iter_mut().rev().find(|signal| {
+            signal.reached == FunnelStage::Submitted
+                && signal.client_order_id.as_deref() == Some(client_order_id)
+        })?;
+        signal.reached = FunnelStage::Filled;
+        Some(signal.watch_id)
+    }
+
+    pub fn signals(&self) -> &VecDeque<FunnelSignal> {
+        &self.signals
+    }
+
+    /// Per-watch counts over the `window_hours` before `now`.
+    pub fn summary(&self, now: DateTime<Utc>, window_hours: u32) -> Vec<WatchFunnel> {
+        let since = now - Duration::hours(i64::from(window_hours));
+        let mut watches = BTreeMap::<u64, WatchFunnel>::new();
+        for signal in self.signals.iter().filter(|signal| signal.at >= since) {
+            let funnel = watches
+                .entry(signal.watch_id)
+                .or_insert_with(|| WatchFunnel {
+                    watch_id: signal.watch_id,
+                    ..WatchFunnel::default()
+                });
+            funnel.signals += 1;
+            funnel.passed_risk += usize::from(signal.reached >= FunnelStage::PassedRisk);
+            funnel.submitted += usize::from(signal.reached >= FunnelStage::Submitted);
+            funnel.filled += usize::from(signal.reached == FunnelStage::Filled);
+            if let Some(reason) = signal.drop_reason {
+                *funnel.drops.entry(reason).or_default() += 1;
+            }
+        }
+        watches.into_values().collect()
+    }
+}
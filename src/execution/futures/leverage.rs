@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+
+use crate::domain::instrument::Instrument;
+use crate::exchange::types::{FuturesLeverage, MarginType};
+
+/// Binance futures accepts `1..=125`; symbols with lower brackets reject
+/// the excess when it is applied.
+pub const MAX_FUTURES_LEVERAGE: u32 = 125;
+
+/// Reads the per-symbol settings applied on startup and config reloads.
+///
+/// Reads:
+/// - `SANDBOX_QUANT_FUTURES_LEVERAGE` (e.g. `BTCUSDT=10:isolated,ETHUSDT=5:cross`)
+pub fn futures_leverage_from_vars(
+    var: impl Fn(&str) -> Option<String>,
+) -> BTreeMap<Instrument, FuturesLeverage> {
+    parse_futures_leverage(&var("SANDBOX_QUANT_FUTURES_LEVERAGE").unwrap_or_default())
+}
+
+/// Malformed entries, and leverage outside `1..=125`, are skipped.
+pub fn parse_futures_leverage(raw: &str) -> BTreeMap<Instrument, FuturesLeverage> {
+    raw.split(',')
+        .filter_map(|entry| {
+            let (symbol, body) = entry.split_once('=')?;
+            let symbol = symbol.trim().to_ascii_uppercase();
+            let (leverage, margin_type) = match body.split_once(':') {
+                Some((leverage, margin_type)) => (leverage, Some(MarginType::parse(margin_type)?)),
+                None => (body, None),
+            };
+            let leverage = leverage.trim().parse::<u32>().ok()?;
+            (!symbol.is_empty() && (1..=MAX_FUTURES_LEVERAGE).contains(&leverage)).then(|| {
+                (
+                    Instrument::new(symbol),
+                    FuturesLeverage {
+                        leverage,
+                        margin_type,
+                    },
+                )
+            })
+        })
+        .collect()
+}
@@ -1,2 +1,3 @@
 pub mod executor;
+pub mod leverage;
 pub mod planner;
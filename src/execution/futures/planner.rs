@@ -47,12 +47,17 @@ impl FuturesExecutionPlanner {
         } else {
             Side::Sell
         };
+        // Shrinking a position without flipping it only ever closes, so the
+        // venue must not let it open the other side (`-0.5 -> 0` buys reduce-only).
+        let reduce_only = current_qty.abs() > f64::EPSILON
+            && target_qty * current_qty >= 0.0
+            && target_qty.abs() < current_qty.abs();
 
         Ok(ExecutionPlan {
             instrument: position.instrument.clone(),
             side,
             qty: delta_qty.abs(),
-            reduce_only: false,
+            reduce_only,
         })
     }
 }
@@ -0,0 +1,129 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::order::OrderStatus;
+use crate::domain::position::Side;
+
+pub const DEFAULT_ORDER_HISTORY_CAPACITY: usize = 500;
+
+/// One submitted (or rejected) order, kept as data so views can filter and
+/// sort it before formatting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderHistoryRow {
+    pub time: DateTime<Utc>,
+    pub instrument: Instrument,
+    pub market: Market,
+    pub side: Side,
+    pub qty: f64,
+    pub price: Option<f64>,
+    pub status: OrderStatus,
+    pub tag: String,
+    /// Book imbalance measured by the depth filter before submit, if any.
+    pub depth_imbalance: Option<f64>,
+    /// OCO list the order is a leg of.
+    pub list_id: Option<u64>,
+    /// Iceberg parent the order is a visible slice of.
+    pub parent_id: Option<u64>,
+    /// When the order was decided, on the venue's clock; see
+    /// `PriceStamp::exchange_time_at`.
+    pub signal_time: Option<DateTime<Utc>>,
+    /// When the venue accepted the order.
+    pub exchange_time: Option<DateTime<Utc>>,
+}
+
+impl OrderHistoryRow {
+    /// Decision-to-exchange latency, measured on the venue's clock alone so
+    /// local clock skew drops out. It includes how old the price already
+    /// was when it arrived.
+    ///
+    /// Example:
+    /// - decided `12:00:00.150`, accepted `12:00:00.212` -> `62`
+    pub fn latency_ms(&self) -> Option<i64> {
+        Some((self.exchange_time? - self.signal_time?).num_milliseconds())
+    }
+}
+
+/// Strategy orders placed under one parameter version.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StrategyVersionStats {
+    pub orders: usize,
+    pub rejected: usize,
+    /// `qty * price` over the orders with a known price.
+    pub notional: f64,
+}
+
+/// Groups strategy orders by their `strategy:<watch_id>@<version>` tag, so a
+/// watch's orders before and after an edit are counted apart.
+///
+/// Example:
+/// - `strategy:3@3f9a0c1d orders=4`, `strategy:3@8b21e7aa orders=1` after an adaptation
+pub fn strategy_version_stats<'a>(
+    rows: impl IntoIterator<Item = &'a OrderHistoryRow>,
+) -> BTreeMap<&'a str, StrategyVersionStats> {
+    let mut stats = BTreeMap::<&str, StrategyVersionStats>::new();
+    for row in rows
+        .into_iter()
+        .filter(|row| row.tag.starts_with("strategy:"))
+    {
+        let entry = stats.entry(row.tag.as_str()).or_default();
+        entry.orders += 1;
+        if row.status == OrderStatus::Rejected {
+            entry.rejected += 1;
+        }
+        entry.notional += row.price.map_or(0.0, |price| price * row.qty);
+    }
+    stats
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderHistory {
+    /// Oldest first.
+    rows: VecDeque<OrderHistoryRow>,
+    capacity: usize,
+}
+
+impl Default for OrderHistory {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_ORDER_HISTORY_CAPACITY)
+    }
+}
+
+impl OrderHistory {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            rows: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Appends a row, dropping the oldest one once capacity is reached.
+    pub fn record(&mut self, row: OrderHistoryRow) {
+        if self.rows.len() >= self.capacity {
+            self.rows.pop_front();
+        }
+        self.rows.push_back(row);
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the capacity, dropping the oldest rows over it.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        let excess = self.rows.len().saturating_sub(self.capacity);
+        self.rows.drain(..excess);
+    }
+
+    pub fn rows(&self) -> &VecDeque<OrderHistoryRow> {
+        &self.rows
+    }
+
+    /// Rows ordered newest first.
+    pub fn latest_first(&self) -> impl Iterator<Item = &OrderHistoryRow> {
+        self.rows.iter().rev()
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+
+use crate::domain::instrument::Instrument;
+use crate::execution::close_symbol::CloseSubmitResult;
+use crate::strategy::ev::EvModel;
+
+pub const DEFAULT_HOLD_EV_PROFILE: &str = "default";
+
+/// Bracket outcomes kept per profile for its probability model.
+pub const MAX_EV_OUTCOMES: usize = 500;
+
+/// What the conditional EV of an open position calls for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HoldEvAction {
+    Hold,
+    SuggestExit,
+    AutoExit,
+}
+
+impl HoldEvAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hold => "hold",
+            Self::SuggestExit => "suggest_exit",
+            Self::AutoExit => "auto_exit",
+        }
+    }
+}
+
+/// Thresholds for re-estimating the EV of holding an open position.
+///
+/// Example:
+/// - `win:0.55,suggest:-0.2,exit:-0.5` -> suggest an exit below `-0.2R`,
+///   close the position below `-0.5R`
+/// - `win:0.55,suggest:-0.2,model:bootstrap/0.25` -> win probability from
+///   the profile's own bracket outcomes, with `0.55` until there are enough
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoldEvProfile {
+    /// Prior win rate; the model may move away from it as outcomes arrive.
+    pub win_rate: f64,
+    pub suggest_exit_below_r: f64,
+    /// Closes the position on its own below this; suggest-only when `None`.
+    pub auto_exit_below_r: Option<f64>,
+    pub model: EvModel,
+}
+
+impl Default for HoldEvProfile {
+    fn default() -> Self {
+        Self {
+            win_rate: 0.5,
+            suggest_exit_below_r: -0.25,
+            auto_exit_below_r: None,
+            model: EvModel::default(),
+        }
+    }
+}
+
+impl HoldEvProfile {
+    /// Parses one profile body; `win` and `suggest` are required.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut win_rate = None;
+        let mut suggest_exit_below_r = None;
+        let mut auto_exit_below_r = None;
+        let mut model = EvModel::default();
+        for step in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|step| !step.is_empty())
+        {
+            let (key, value) = step.split_once(':')?;
+            let key = key.trim().to_ascii_lowercase();
+            if key == "model" {
+                model = EvModel::parse(value)?;
+                continue;
+            }
+            let value = value.trim().parse::<f64>().ok()?;
+            match key.as_str() {
+                "win" => win_rate = Some(value),
+                "suggest" => suggest_exit_below_r = Some(value),
+                "exit" => auto_exit_below_r = Some(value),
+                _ => return None,
+            }
+        }
+        let profile = Self {
+            win_rate: win_rate?,
+            suggest_exit_below_r: suggest_exit_below_r?,
+            auto_exit_below_r,
+            model,
+        };
+        profile.is_valid().then_some(profile)
+    }
+
+    fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.win_rate)
+            && self.suggest_exit_below_r.is_finite()
+            && self
+                .auto_exit_below_r
+                .is_none_or(|exit| exit.is_finite() && exit <= self.suggest_exit_below_r)
+    }
+
+    pub fn action(&self, expected_value_r: f64) -> HoldEvAction {
+        if self
+            .auto_exit_below_r
+            .is_some_and(|exit| expected_value_r < exit)
+        {
+            HoldEvAction::AutoExit
+        } else if expected_value_r < self.suggest_exit_below_r {
+            HoldEvAction::SuggestExit
+        } else {
+            HoldEvAction::Hold
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let mut label = format!(
+            "win:{},suggest:{}",
+            self.win_rate, self.suggest_exit_below_r
+        );
+        if let Some(exit) = self.auto_exit_below_r {
+            label.push_str(&format!(",exit:{exit}"));
+        }
+        if self.model != EvModel::default() {
+            label.push_str(&format!(",model:{}", self.model.label()));
+        }
+        label
+    }
+}
+
+/// Reads the named profiles.
+///
+/// Reads:
+/// - `SANDBOX_QUANT_HOLD_EV_PROFILES` (e.g. `default=win:0.5,suggest:-0.25;scalp=win:0.6,suggest:-0.1,exit:-0.4,model:bootstrap/0.25`)
+///
+/// Malformed entries are skipped. Without a `default=` entry the service
+/// falls back to `HoldEvProfile::default()`, which only suggests.
+pub fn hold_ev_profiles_from_vars(
+    var: impl Fn(&str) -> Option<String>,
+) -> BTreeMap<String, HoldEvProfile> {
+    parse_hold_ev_profiles(&var("SANDBOX_QUANT_HOLD_EV_PROFILES").unwrap_or_default())
+}
+
+pub fn parse_hold_ev_profiles(raw: &str) -> BTreeMap<String, HoldEvProfile> {
+    let mut profiles = BTreeMap::new();
+    for entry in raw.split(';') {
+        let Some((name, body)) = entry.split_once('=') else {
+            continue;
+        };
+        let name = name.trim().to_ascii_lowercase();
+        if name.is_empty() {
+            continue;
+        }
+        if let Some(profile) = HoldEvProfile::parse(body) {
+            profiles.insert(name, profile);
+        }
+    }
+    profiles
+}
+
+/// Latest EV re-estimate for one bracketed position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoldEvEstimate {
+    pub instrument: Instrument,
+    pub profile_name: String,
+    pub price: f64,
+    pub r_multiple: f64,
+    pub win_probability: f64,
+    pub expected_value_r: f64,
+    pub action: HoldEvAction,
+    /// `WinProbabilityModel::version` of the model behind the estimate.
+    pub model_version: String,
+    /// Outcomes the model learned from.
+    pub trades: usize,
+}
+
+/// An estimate that crossed into a worse action since the last refresh.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HoldEvEvent {
+    SuggestExit(HoldEvEstimate),
+    Exited {
+        estimate: HoldEvEstimate,
+        result: CloseSubmitResult,
+    },
+}
@@ -0,0 +1,106 @@
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::order::OrderStatus;
+use crate::domain::position::Side;
+
+/// Client order id for one visible slice, e.g. `ice-4-3` for the third
+/// slice of parent `4`.
+pub fn iceberg_client_order_id(parent_id: u64, slice: u32) -> String {
+    format!("ice-{parent_id}-{slice}")
+}
+
+/// Parent id carried by a slice's client order id.
+///
+/// Example:
+/// - `ice-4-3` -> `Some(4)`
+/// - `oco-7-sl` -> `None`
+pub fn iceberg_parent_id(client_order_id: &str) -> Option<u64> {
+    client_order_id
+        .strip_prefix("ice-")?
+        .split('-')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// A large limit order worked as a series of small visible slices.
+///
+/// Only one slice rests at a time. Once it fills the next one is posted at
+/// the same price, until `total_qty` is done. The venue sees only
+/// `visible_qty`; the rest stays in the app.
+///
+/// Example:
+/// - buy `1.0 BTCUSDT @ 64000` showing `0.2`
+/// - `ice-1-1` rests `0.2`; after it fills `ice-1-2` rests the next `0.2`
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcebergOrder {
+    pub parent_id: u64,
+    pub instrument: Instrument,
+    pub market: Market,
+    pub side: Side,
+    pub price: f64,
+    pub total_qty: f64,
+    pub visible_qty: f64,
+    /// Done by completed slices; the resting slice's fills are not in it.
+    pub filled_qty: f64,
+    /// Slices posted so far, the resting one included.
+    pub slices: u32,
+    pub child_order_id: String,
+    pub child_qty: f64,
+    /// Fill of the resting slice as of the last poll or execution report.
+    pub child_executed_qty: f64,
+    /// Status of the resting slice as last reported for its client order
+    /// id; a terminal one makes `child_executed_qty` the slice's final fill.
+    pub child_status: OrderStatus,
+}
+
+impl IcebergOrder {
+    /// Rounded to `1e-9` so float dust such as `0.5 - 0.4` does not floor
+    /// to one step short when the last slice is sized.
+    pub fn remaining_qty(&self) -> f64 {
+        ((self.total_qty - self.filled_qty) * 1e9).round().max(0.0) / 1e9
+    }
+
+    /// Filled share of the parent, counting the resting slice's fills.
+    ///
+    /// Example:
+    /// - `0.4` of `1.0` done and `0.1` of the resting slice -> `50.0`
+    pub fn progress_pct(&self) -> f64 {
+        if self.total_qty <= f64::EPSILON {
+            return 0.0;
+        }
+        ((self.filled_qty + self.child_executed_qty) / self.total_qty * 100.0).min(100.0)
+    }
+}
+
+/// What a status poll did to one parent order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IcebergEvent {
+    /// A slice filled in full; the next one was posted unless it was the
+    /// last.
+    SliceFilled {
+        parent_id: u64,
+        instrument: Instrument,
+        slice: u32,
+        client_order_id: String,
+        slice_qty: f64,
+        filled_qty: f64,
+        total_qty: f64,
+        next_order_id: Option<String>,
+    },
+    Completed {
+        parent_id: u64,
+        instrument: Instrument,
+        filled_qty: f64,
+        slices: u32,
+    },
+    /// The parent stopped short of its size: a slice was cancelled outside
+    /// the app or the next one could not be posted.
+    Stopped {
+        parent_id: u64,
+        instrument: Instrument,
+        filled_qty: f64,
+        total_qty: f64,
+        reason: String,
+    },
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Timelike, Utc};
+
+use crate::execution::command::ExecutionCommand;
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// A weekly UTC span whose books are too thin to pay for a strategy entry.
+///
+/// A span whose end is not after its start runs into the next day, which
+/// counts as part of the day it started on.
+///
+/// Example:
+/// - `sat-sun 22:00-06:00` covers Saturday 22:00 to Sunday 06:00 and Sunday 22:00 to Monday 06:00
+/// - `sun 00:00-24:00` covers all of Sunday
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IlliquidWindow {
+    /// Bit `n` marks the weekday `n` days after Monday.
+    days: u8,
+    start_minute: u32,
+    end_minute: u32,
+}
+
+impl IlliquidWindow {
+    /// Example:
+    /// - `fri-mon 23:30-01:00`, `tue+thu 02:00-04:00`, `* 03:00-04:00`
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (days, span) = raw.trim().split_once(' ')?;
+        let days = match days.trim() {
+            "*" => 0b111_1111,
+            days => days
+                .split('+')
+                .try_fold(0, |days, part| Some(days | parse_days(part)?))?,
+        };
+        let (start, end) = span.trim().split_once('-')?;
+        let start_minute = parse_minute(start)?;
+        let end_minute = parse_minute(end)?;
+        (start_minute < MINUTES_PER_DAY && start_minute != end_minute).then_some(Self {
+            days,
+            start_minute,
+            end_minute,
+        })
+    }
+
+    /// Example:
+    /// - `sat+sun 22:00-06:00`
+    pub fn label(&self) -> String {
+        let days = WEEKDAYS
+            .iter()
+            .enumerate()
+            .filter(|(day, _)| self.covers_day(*day as u32))
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>()
+            .join("+");
+        format!(
+            "{days} {}-{}",
+            format_minute(self.start_minute),
+            format_minute(self.end_minute)
+        )
+    }
+
+    /// When the span covering `now` ends; `None` outside it.
+    pub fn end_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let today = now.weekday().num_days_from_monday();
+        let minute = now.hour() * 60 + now.minute();
+        let midnight = now.date_naive().and_time(NaiveTime::MIN).and_utc();
+        let end_today = midnight + Duration::minutes(self.end_minute as i64);
+        if self.start_minute < self.end_minute {
+            let covered =
+                self.covers_day(today) && (self.start_minute..self.end_minute).contains(&minute);
+            return covered.then_some(end_today);
+        }
+        if self.covers_day(today) && minute >= self.start_minute {
+            return Some(end_today + Duration::days(1));
+        }
+        let yesterday = (today + 6) % 7;
+        (self.covers_day(yesterday) && minute < self.end_minute).then_some(end_today)
+    }
+
+    fn covers_day(&self, day: u32) -> bool {
+        self.days & (1 << day) != 0
+    }
+}
+
+/// Reads:
+/// - `SANDBOX_QUANT_ILLIQUID_WINDOWS` (e.g. `sat-sun 22:00-06:00,* 03:00-04:00`, UTC)
+pub fn illiquid_windows_from_vars(var: impl Fn(&str) -> Option<String>) -> Vec<IlliquidWindow> {
+    parse_illiquid_windows(&var("SANDBOX_QUANT_ILLIQUID_WINDOWS").unwrap_or_default())
+}
+
+/// Malformed entries are skipped.
+pub fn parse_illiquid_windows(raw: &str) -> Vec<IlliquidWindow> {
+    raw.split(',').filter_map(IlliquidWindow::parse).collect()
+}
+
+/// When trading turns liquid again; `None` when `now` is in no window.
+///
+/// Back-to-back windows are followed through, so `fri 22:00-24:00` and
+/// `sat 00:00-24:00` together end on Sunday 00:00.
+pub fn illiquid_until(windows: &[IlliquidWindow], now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let mut until = windows
+        .iter()
+        .filter_map(|window| window.end_after(now))
+        .max()?;
+    for _ in 0..windows.len() {
+        match windows
+            .iter()
+            .filter_map(|window| window.end_after(until))
+            .max()
+        {
+            Some(later) if later > until => until = later,
+            _ => break,
+        }
+    }
+    Some(until)
+}
+
+/// A strategy entry signalled inside an illiquid window, held for the
+/// next liquid open instead of crossing a wide spread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedEntry {
+    pub command: ExecutionCommand,
+    pub queued_at: DateTime<Utc>,
+    /// Where the window ended when the entry was queued.
+    pub release_at: DateTime<Utc>,
+}
+
+/// One day or a range of days, wrapping past Sunday; e.g. `fri-mon`.
+fn parse_days(raw: &str) -> Option<u8> {
+    match raw.split_once('-') {
+        Some((first, last)) => {
+            let first = weekday_index(first)?;
+            let last = weekday_index(last)?;
+            Some(
+                (0..=(last + 7 - first) % 7)
+                    .map(|offset| (first + offset) % 7)
+                    .fold(0, |days, day| days | 1 << day),
+            )
+        }
+        None => Some(1 << weekday_index(raw)?),
+    }
+}
+
+fn weekday_index(raw: &str) -> Option<u32> {
+    let raw = raw.trim().to_ascii_lowercase();
+    WEEKDAYS
+        .iter()
+        .position(|day| raw.starts_with(day))
+        .map(|day| day as u32)
+}
+
+fn parse_minute(raw: &str) -> Option<u32> {
+    let (hour, minute) = raw.trim().split_once(':')?;
+    let hour = hour.parse::<u32>().ok()?;
+    let minute = minute.parse::<u32>().ok()?;
+    let total = hour * 60 + minute;
+    (minute < 60 && total <= MINUTES_PER_DAY).then_some(total)
+}
+
+fn format_minute(minute: u32) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
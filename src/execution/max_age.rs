@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+
+use crate::domain::instrument::Instrument;
+use crate::execution::close_symbol::CloseSubmitResult;
+
+/// Reads the hard holding limits enforced on every refresh.
+///
+/// Reads:
+/// - `SANDBOX_QUANT_MAX_POSITION_AGE_SECS` (e.g. `BTCUSDT=14400,ETHUSDT=3600`)
+pub fn max_position_age_from_vars(
+    var: impl Fn(&str) -> Option<String>,
+) -> BTreeMap<Instrument, u64> {
+    parse_max_position_age(&var("SANDBOX_QUANT_MAX_POSITION_AGE_SECS").unwrap_or_default())
+}
+
+/// Malformed entries, and zero ages, are skipped.
+pub fn parse_max_position_age(raw: &str) -> BTreeMap<Instrument, u64> {
+    raw.split(',')
+        .filter_map(|entry| {
+            let (symbol, secs) = entry.split_once('=')?;
+            let symbol = symbol.trim().to_ascii_uppercase();
+            let secs = secs.trim().parse::<u64>().ok()?;
+            (!symbol.is_empty() && secs > 0).then(|| (Instrument::new(symbol), secs))
+        })
+        .collect()
+}
+
+/// A position flattened for outliving its symbol's maximum age.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaxAgeExit {
+    pub instrument: Instrument,
+    pub held_secs: u64,
+    pub max_age_secs: u64,
+    pub result: CloseSubmitResult,
+}
@@ -1,9 +1,22 @@
 pub mod close_all;
 pub mod close_symbol;
 pub mod command;
+pub mod depth_filter;
+pub mod dom_ladder;
+pub mod funnel;
 pub mod futures;
+pub mod history;
+pub mod hold_ev;
+pub mod iceberg;
+pub mod illiquid_window;
+pub mod max_age;
+pub mod oco;
+pub mod paper_shadow;
 pub mod planner;
 pub mod price_source;
 pub mod service;
+pub mod slippage_guard;
 pub mod spot;
+pub mod take_profit;
 pub mod target_translation;
+pub mod trailing_stop;
@@ -0,0 +1,156 @@
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::order::OpenOrder;
+use crate::domain::position::Side;
+
+/// Re-arms allowed per list before it is handed to the operator.
+pub const MAX_OCO_REPAIRS: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcoLeg {
+    TakeProfit,
+    StopLoss,
+}
+
+impl OcoLeg {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TakeProfit => "take_profit",
+            Self::StopLoss => "stop_loss",
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::TakeProfit => "tp",
+            Self::StopLoss => "sl",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcoListStatus {
+    Working,
+    /// Repair failed or ran out of attempts; the operator has to step in.
+    NeedsManual,
+}
+
+impl OcoListStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Working => "working",
+            Self::NeedsManual => "needs_manual",
+        }
+    }
+}
+
+/// Client order id for one leg, e.g. `oco-7-sl` or `oco-7-sl-r2` after two
+/// re-arms.
+pub fn oco_client_order_id(list_id: u64, leg: OcoLeg, repairs: u32) -> String {
+    if repairs == 0 {
+        format!("oco-{list_id}-{}", leg.code())
+    } else {
+        format!("oco-{list_id}-{}-r{repairs}", leg.code())
+    }
+}
+
+/// Client order id for a leg placed again at a new size, e.g. `oco-7-tp-s1`
+/// after the first resize.
+pub fn oco_resized_order_id(list_id: u64, leg: OcoLeg, resizes: u32) -> String {
+    format!("oco-{list_id}-{}-s{resizes}", leg.code())
+}
+
+/// List id carried by an OCO leg's client order id.
+///
+/// Example:
+/// - `oco-7-sl-r2` -> `Some(7)`
+/// - `web-1` -> `None`
+pub fn oco_list_id(client_order_id: &str) -> Option<u64> {
+    client_order_id
+        .strip_prefix("oco-")?
+        .split('-')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// A take-profit limit and a protective stop on one position where a fill
+/// on either leg cancels the other.
+///
+/// Binance futures has no native OCO list, so there both legs are
+/// reduce-only orders tied together by `list_id` through their client order
+/// ids. On spot the venue links them itself under `exchange_list_id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcoOrderList {
+    pub list_id: u64,
+    pub instrument: Instrument,
+    pub market: Market,
+    pub close_side: Side,
+    /// Filled position size the legs were sized for; grows with the
+    /// position when a partially filled entry fills further.
+    pub qty: f64,
+    pub take_profit_price: f64,
+    pub stop_price: f64,
+    /// Limit the stop leg rests once `stop_price` trades; stop-market when
+    /// `None`.
+    pub stop_limit_price: Option<f64>,
+    pub take_profit_order_id: String,
+    pub stop_order_id: String,
+    /// `orderListId` of a native venue list, which the venue keeps
+    /// one-cancels-other; such lists are never re-armed or resized leg by leg.
+    pub exchange_list_id: Option<String>,
+    pub status: OcoListStatus,
+    pub repairs: u32,
+    pub resizes: u32,
+}
+
+impl OcoOrderList {
+    pub fn leg_order_id(&self, leg: OcoLeg) -> &str {
+        match leg {
+            OcoLeg::TakeProfit => &self.take_profit_order_id,
+            OcoLeg::StopLoss => &self.stop_order_id,
+        }
+    }
+
+    /// Legs no longer resting at the venue.
+    pub fn missing_legs(&self, open_orders: &[OpenOrder]) -> Vec<OcoLeg> {
+        [OcoLeg::TakeProfit, OcoLeg::StopLoss]
+            .into_iter()
+            .filter(|leg| {
+                !open_orders
+                    .iter()
+                    .any(|order| order.client_order_id == self.leg_order_id(*leg))
+            })
+            .collect()
+    }
+}
+
+/// What a status poll did to one list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OcoEvent {
+    /// One leg filled and the other was cancelled, or the position closed.
+    Completed {
+        list_id: u64,
+        instrument: Instrument,
+        filled_leg: Option<OcoLeg>,
+    },
+    /// A leg vanished without a fill and was placed again.
+    LegRearmed {
+        list_id: u64,
+        instrument: Instrument,
+        leg: OcoLeg,
+        client_order_id: String,
+    },
+    /// The position grew, so both legs were replaced at the new size.
+    Resized {
+        list_id: u64,
+        instrument: Instrument,
+        previous_qty: f64,
+        qty: f64,
+    },
+    RepairFailed {
+        list_id: u64,
+        instrument: Instrument,
+        reason: String,
+    },
+}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::env;
+
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::position::Side;
+use crate::execution::slippage_guard::SlippageGuardConfig;
+
+/// Shadowed orders kept for the comparison; the oldest drop out first.
+pub const MAX_SHADOW_ORDERS: usize = 1_000;
+
+/// Client order id of a live strategy order with a paper twin, e.g.
+/// `shadow-3-12` for the twelfth shadowed order, sent by watch `3`.
+pub fn shadow_client_order_id(watch_id: u64, seq: u64) -> String {
+    format!("shadow-{watch_id}-{seq}")
+}
+
+/// Watch id carried by a shadowed order's client order id.
+///
+/// Example:
+/// - `shadow-3-12` -> `Some(3)`
+/// - `ice-4-3` -> `None`
+pub fn shadow_watch_id(client_order_id: &str) -> Option<u64> {
+    client_order_id
+        .strip_prefix("shadow-")?
+        .split('-')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// A paper run of the same strategies alongside the live one.
+///
+/// Every market order a strategy watch sends also fills on paper, at the
+/// price it was decided at moved `modeled_slippage_bps` against it. Live
+/// fills are matched back by client order id, so the two books differ only
+/// by what the paper model got wrong.
+///
+/// Example:
+/// - `2` bps model, buy `0.1 BTCUSDT` decided at `64,000`
+/// - paper fills at `64,012.8`; live at `64,025.6` -> `4` bps realized, `+2` bps model error
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaperShadowConfig {
+    pub modeled_slippage_bps: f64,
+}
+
+impl PaperShadowConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_PAPER_SHADOW` (`on` for a `0` bps model, or the
+    ///   modeled slippage in bps, e.g. `2`; unset or `off` disables it)
+    pub fn from_env() -> Option<Self> {
+        Self::from_vars(|key| env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        var("SANDBOX_QUANT_PAPER_SHADOW").and_then(|raw| Self::parse(&raw))
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let modeled_slippage_bps = match raw.to_ascii_lowercase().as_str() {
+            "on" | "true" | "1" => 0.0,
+            _ => raw
+                .parse::<f64>()
+                .ok()
+                .filter(|bps| bps.is_finite() && *bps >= 0.0)?,
+        };
+        Some(Self {
+            modeled_slippage_bps,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        format!("modeled_slippage_bps={}", self.modeled_slippage_bps)
+    }
+
+    /// Where the paper twin of an order decided at `decision_price` fills.
+    pub fn paper_price(&self, side: Side, decision_price: f64) -> f64 {
+        let slippage = self.modeled_slippage_bps / 10_000.0;
+        match side {
+            Side::Buy => decision_price * (1.0 + slippage),
+            Side::Sell => decision_price * (1.0 - slippage),
+        }
+    }
+}
+
+/// One live strategy order and its paper twin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowOrder {
+    pub client_order_id: String,
+    pub watch_id: u64,
+    pub instrument: Instrument,
+    pub market: Market,
+    pub side: Side,
+    /// Sent live and filled in full on paper.
+    pub qty: f64,
+    pub decision_price: f64,
+    pub paper_price: f64,
+    pub live_filled_qty: f64,
+    pub live_notional: f64,
+}
+
+impl ShadowOrder {
+    pub fn live_avg_price(&self) -> Option<f64> {
+        (self.live_filled_qty > f64::EPSILON).then(|| self.live_notional / self.live_filled_qty)
+    }
+
+    fn sign(&self) -> f64 {
+        match self.side {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        }
+    }
+}
+
+/// How far one watch's live results drifted from its paper twin.
+///
+/// PnL is marked to the same price on both sides and counts only the
+/// shadowed orders. `slippage_usdt` and `fill_gap_usdt` add up to
+/// `divergence_usdt`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowDivergence {
+    pub watch_id: u64,
+    pub orders: usize,
+    pub paper_qty: f64,
+    pub live_qty: f64,
+    pub modeled_slippage_bps: f64,
+    /// Quantity-weighted adverse move from the decision price to the live
+    /// fills; `None` before the first live fill.
+    pub realized_slippage_bps: Option<f64>,
+    pub paper_pnl_usdt: f64,
+    pub live_pnl_usdt: f64,
+    /// Part of the divergence from live fill prices differing from paper.
+    pub slippage_usdt: f64,
+    /// Part from live quantity that did not fill.
+    pub fill_gap_usdt: f64,
+}
+
+impl ShadowDivergence {
+    pub fn divergence_usdt(&self) -> f64 {
+        self.live_pnl_usdt - self.paper_pnl_usdt
+    }
+
+    /// Realized minus modeled slippage; positive when paper was too kind.
+    pub fn model_error_bps(&self) -> Option<f64> {
+        self.realized_slippage_bps
+            .map(|realized| realized - self.modeled_slippage_bps)
+    }
+}
+
+/// Compares the paper and live books per watch. Orders on instruments
+/// without a mark price are left out.
+///
+/// Example:
+/// - paper buy `1 @ 100`, live filled `0.5 @ 101`, mark `104`
+/// - paper `+4.00`, live `+1.50`; slippage `-0.50`, fill gap `-2.00`
+pub fn shadow_divergence(
+    orders: &[ShadowOrder],
+    modeled_slippage_bps: f64,
+    mark_price: impl Fn(&Instrument) -> Option<f64>,
+) -> Vec<ShadowDivergence> {
+    let mut runs = BTreeMap::<u64, (ShadowDivergence, f64)>::new();
+    for order in orders {
+        let Some(mark) = mark_price(&order.instrument) else {
+            continue;
+        };
+        let (run, slippage_weight) = runs.entry(order.watch_id).or_insert_with(|| {
+            (
+                ShadowDivergence {
+                    watch_id: order.watch_id,
+                    orders: 0,
+                    paper_qty: 0.0,
+                    live_qty: 0.0,
+                    modeled_slippage_bps,
+                    realized_slippage_bps: None,
+                    paper_pnl_usdt: 0.0,
+                    live_pnl_usdt: 0.0,
+                    slippage_usdt: 0.0,
+                    fill_gap_usdt: 0.0,
+                },
+                0.0,
+            )
+        });
+        let sign = order.sign();
+        run.orders += 1;
+        run.paper_qty += order.qty;
+        run.live_qty += order.live_filled_qty;
+        run.paper_pnl_usdt += sign * order.qty * (mark - order.paper_price);
+        run.fill_gap_usdt +=
+            sign * (order.live_filled_qty - order.qty) * (mark - order.paper_price);
+        if let Some(live_price) = order.live_avg_price() {
+            run.live_pnl_usdt += sign * order.live_filled_qty * (mark - live_price);
+            run.slippage_usdt += sign * order.live_filled_qty * (order.paper_price - live_price);
+            let bps = SlippageGuardConfig::adverse_drift_bps(
+                order.side,
+                order.decision_price,
+                live_price,
+            );
+            let weighted = run.realized_slippage_bps.unwrap_or_default() * *slippage_weight
+                + bps * order.live_filled_qty;
+            *slippage_weight += order.live_filled_qty;
+            run.realized_slippage_bps = Some(weighted / *slippage_weight);
+        }
+    }
+    runs.into_values().map(|(run, _)| run).collect()
+}
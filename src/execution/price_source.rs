@@ -1,5 +1,32 @@
+use chrono::{DateTime, Utc};
+
 use crate::domain::instrument::Instrument;
 
 pub trait PriceSource {
     fn current_price(&self, instrument: &Instrument) -> Option<f64>;
+
+    /// When the current price traded on the venue; `None` when unknown.
+    fn price_stamp(&self, _instrument: &Instrument) -> Option<PriceStamp> {
+        None
+    }
+}
+
+/// Venue trade time of a price, with the local time it arrived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceStamp {
+    pub exchange_time_ms: i64,
+    pub received_at: DateTime<Utc>,
+}
+
+impl PriceStamp {
+    /// `at` on the venue's clock: the trade time plus the local time spent
+    /// since the price arrived, so skew between the clocks cancels out.
+    ///
+    /// Example:
+    /// - traded `12:00:00.100` venue time, received `12:00:02.000` local
+    /// - decided `12:00:02.050` local -> `12:00:00.150` venue time
+    pub fn exchange_time_at(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let elapsed_ms = (at - self.received_at).num_milliseconds().max(0);
+        DateTime::<Utc>::from_timestamp_millis(self.exchange_time_ms + elapsed_ms).unwrap_or(at)
+    }
 }
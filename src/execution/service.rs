@@ -1,23 +1,64 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::warn;
+
 use crate::domain::exposure::Exposure;
 use crate::domain::identifiers::BatchId;
 use crate::domain::instrument::Instrument;
 use crate::domain::market::Market;
+use crate::domain::order::{OpenOrder, OrderStatus};
 use crate::domain::order_type::OrderType;
-use crate::domain::position::PositionSnapshot;
+use crate::domain::position::{PositionSnapshot, Side};
+use crate::error::error_class::ErrorClass;
 use crate::error::exchange_error::ExchangeError;
 use crate::error::execution_error::ExecutionError;
 use crate::exchange::facade::ExchangeFacade;
 use crate::exchange::symbol_rules::SymbolRules;
-use crate::exchange::types::CloseOrderRequest;
+use crate::exchange::types::{
+    ApiPermissions, CloseOrderRequest, FuturesLeverage, OcoListRequest, SymbolTradingStatus,
+};
 use crate::execution::close_all::CloseAllBatchResult;
-use crate::execution::close_symbol::{CloseSubmitResult, CloseSymbolResult};
+use crate::execution::close_symbol::{CloseSubmitResult, CloseSymbolResult, EmergencyCloseResult};
 use crate::execution::command::{CommandSource, ExecutionCommand};
+use crate::execution::depth_filter::{DeferredExecution, DepthCheck, DepthFilterConfig};
+use crate::execution::dom_ladder::round_to_tick;
+use crate::execution::funnel::{strategy_client_order_id, SignalFunnel};
 use crate::execution::futures::planner::FuturesExecutionPlanner;
+use crate::execution::history::{OrderHistory, OrderHistoryRow};
+use crate::execution::hold_ev::{
+    HoldEvAction, HoldEvEstimate, HoldEvEvent, HoldEvProfile, DEFAULT_HOLD_EV_PROFILE,
+    MAX_EV_OUTCOMES,
+};
+use crate::execution::iceberg::{
+    iceberg_client_order_id, iceberg_parent_id, IcebergEvent, IcebergOrder,
+};
+use crate::execution::illiquid_window::{illiquid_until, IlliquidWindow, QueuedEntry};
+use crate::execution::max_age::MaxAgeExit;
+use crate::execution::oco::{
+    oco_client_order_id, oco_list_id, oco_resized_order_id, OcoEvent, OcoLeg, OcoListStatus,
+    OcoOrderList, MAX_OCO_REPAIRS,
+};
+use crate::execution::paper_shadow::{
+    shadow_client_order_id, shadow_divergence, shadow_watch_id, PaperShadowConfig,
+    ShadowDivergence, ShadowOrder, MAX_SHADOW_ORDERS,
+};
 use crate::execution::planner::ExecutionPlan;
 use crate::execution::price_source::PriceSource;
+use crate::execution::slippage_guard::{SlippageCheck, SlippageGuardConfig};
 use crate::execution::spot::planner::SpotExecutionPlanner;
+use crate::execution::take_profit::{
+    TakeProfitEvent, TakeProfitLadder, TakeProfitProfile, DEFAULT_TAKE_PROFIT_PROFILE,
+};
 use crate::execution::target_translation::exposure_to_notional;
+use crate::execution::trailing_stop::{InternalTrailingStop, TrailDistance, TrailingStopMode};
+use crate::market_data::synthetic::SyntheticInstrument;
+use crate::portfolio::asset_exposure::{aggregate_base_asset_exposure, base_asset};
 use crate::portfolio::store::PortfolioStateStore;
+use crate::storage::order_store::{HistoryQuery, OrderStore};
+use crate::strategy::ev::EvEstimator;
+use crate::strategy::order_throttle::OrderThrottle;
+use crate::strategy::trade_stats::TradeStatsReader;
 
 #[derive(Debug, Clone, PartialEq)]
 struct NormalizedOrderQty {
@@ -28,15 +69,138 @@ struct NormalizedOrderQty {
 #[derive(Debug, Default)]
 pub struct ExecutionService {
     pub last_command: Option<ExecutionCommand>,
+    /// Max consolidated exposure per base asset as a fraction of equity.
+    ///
+    /// Example:
+    /// - `BTC=0.5` rejects targets that push spot + futures BTC beyond 50% of equity
+    pub asset_exposure_caps: BTreeMap<String, f64>,
+    pub history: OrderHistory,
+    /// Keeps every history row across restarts; `None` keeps them in memory only.
+    pub order_store: Option<OrderStore>,
+    pub trailing_stop_mode: TrailingStopMode,
+    /// Stops tracked in-process; spot always lands here.
+    pub internal_trailing_stops: Vec<InternalTrailingStop>,
+    /// Disabled when `None`; see `DepthFilterConfig::from_env`.
+    pub depth_filter: Option<DepthFilterConfig>,
+    /// Depth measured for the most recent filtered submit.
+    pub last_depth_check: Option<DepthCheck>,
+    /// Entries held back by the depth filter, retried on refresh.
+    pub deferred_executions: Vec<DeferredExecution>,
+    /// Disabled when `None`; see `SlippageGuardConfig::from_env`.
+    pub slippage_guard: Option<SlippageGuardConfig>,
+    /// Guard result for the most recent target-exposure submit.
+    pub last_slippage_check: Option<SlippageCheck>,
+    /// Named exit plans; see `take_profit_profiles_from_vars`.
+    pub take_profit_profiles: BTreeMap<String, TakeProfitProfile>,
+    /// Ladders still working levels; removed once the runner is armed.
+    pub take_profit_ladders: Vec<TakeProfitLadder>,
+    /// Thresholds for holding EV; see `hold_ev_profiles_from_vars`.
+    pub hold_ev_profiles: BTreeMap<String, HoldEvProfile>,
+    /// Bracket results per hold-EV profile, `true` for a take-profit fill;
+    /// the profiles' probability models learn from them.
+    pub ev_outcomes: BTreeMap<String, Vec<bool>>,
+    /// How far back hold-EV models read realized trades from the order
+    /// store; `0` reads every trade it keeps.
+    pub trade_stats_lookback_days: u32,
+    /// Latest holding EV per bracketed position, refreshed with the OCO poll.
+    pub hold_ev: BTreeMap<Instrument, HoldEvEstimate>,
+    /// Spread definitions; see `synthetic_instruments_from_vars`.
+    pub synthetic_instruments: BTreeMap<Instrument, SyntheticInstrument>,
+    /// Per-symbol futures settings; see `futures_leverage_from_vars`.
+    pub futures_leverage: BTreeMap<Instrument, FuturesLeverage>,
+    /// Hard holding limit per symbol; see `max_position_age_from_vars`.
+    pub max_position_age_secs: BTreeMap<Instrument, u64>,
+    /// OCO lists still guarding a position, polled on refresh.
+    pub oco_lists: Vec<OcoOrderList>,
+    /// Last list id handed out; ids are never reused within a session.
+    pub last_oco_list_id: u64,
+    /// Iceberg parents still working a slice, polled on refresh.
+    pub icebergs: Vec<IcebergOrder>,
+    /// Last iceberg parent id handed out; never reused within a session.
+    pub last_iceberg_id: u64,
+    /// Set by the inactivity flat mode; commands that add exposure are
+    /// rejected while closes, stops and cancels still go through.
+    pub entries_disabled: bool,
+    /// News blackout; like `entries_disabled`, but lifts on its own.
+    pub entries_blackout_until: Option<DateTime<Utc>>,
+    /// Probed at startup; `None` holds nothing back.
+    pub api_permissions: Option<ApiPermissions>,
+    /// Symbols the venue reports as halted or delisted; entries on them,
+    /// or on spreads with such a leg, are refused until they trade again.
+    pub halted_symbols: BTreeMap<Instrument, SymbolTradingStatus>,
+    /// Signal-to-fill progress of every strategy command.
+    pub funnel: SignalFunnel,
+    /// Last `strategy-` order number handed out; like the shadow numbers.
+    pub last_strategy_order_id: u64,
+    /// Client order id of the order the running strategy command sent;
+    /// `None` between commands.
+    pub strategy_order_sent: Option<String>,
+    /// Weekly spans where strategy entries wait instead of crossing wide
+    /// spreads; see `illiquid_windows_from_vars`.
+    pub illiquid_windows: Vec<IlliquidWindow>,
+    /// Strategy entries held for the next liquid open, one per instrument.
+    pub queued_entries: Vec<QueuedEntry>,
+    /// Entries each strategy watch may send per rolling minute; `0` is
+    /// off. See `strategy_orders_per_min_from_env`.
+    pub strategy_orders_per_min: usize,
+    /// Entry throttle per strategy watch, made on its first entry.
+    pub strategy_throttles: BTreeMap<u64, OrderThrottle>,
+    /// Disabled when `None`; see `PaperShadowConfig::from_env`.
+    pub paper_shadow: Option<PaperShadowConfig>,
+    /// Live strategy orders with their paper twins, oldest first.
+    pub shadow_orders: Vec<ShadowOrder>,
+    /// Last shadowed order number handed out; never reused within a session.
+    pub last_shadow_order_id: u64,
+    /// Venue-clock time the running command was decided at, taken from the
+    /// stamp of the price it acted on; `None` between commands.
+    pub signal_time: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ExecutionOutcome {
-    TargetExposureSubmitted { instrument: Instrument },
-    TargetExposureAlreadyAtTarget { instrument: Instrument },
-    OptionOrderSubmitted { instrument: Instrument },
+    TargetExposureSubmitted {
+        instrument: Instrument,
+    },
+    TargetExposureAlreadyAtTarget {
+        instrument: Instrument,
+    },
+    OptionOrderSubmitted {
+        instrument: Instrument,
+    },
     CloseSymbol(CloseSymbolResult),
     CloseAll(CloseAllBatchResult),
+    TrailingStopPlaced {
+        instrument: Instrument,
+        on_exchange: bool,
+        /// The trail as placed; ATR multiples are resolved to a percent.
+        callback_rate_pct: f64,
+    },
+    TakeProfitLadderPlaced {
+        instrument: Instrument,
+        progress: String,
+    },
+    LimitOrderPlaced {
+        instrument: Instrument,
+        market: Market,
+        price: f64,
+    },
+    OrderCancelled {
+        instrument: Instrument,
+        client_order_id: String,
+    },
+    IcebergPlaced {
+        instrument: Instrument,
+        parent_id: u64,
+        market: Market,
+        price: f64,
+        first_order_id: String,
+    },
+    OcoPlaced {
+        instrument: Instrument,
+        list_id: u64,
+        /// Venue order list id when the legs were placed as a native OCO.
+        exchange_list_id: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -46,67 +210,1552 @@ pub enum TargetExposureSubmitResult {
 }
 
 impl ExecutionService {
+    /// The halted symbol behind `instrument`, itself or a spread leg.
+    pub fn halted_status(
+        &self,
+        instrument: &Instrument,
+    ) -> Option<(&Instrument, &SymbolTradingStatus)> {
+        match self.synthetic_instruments.get(instrument) {
+            Some(synthetic) => synthetic
+                .legs
+                .iter()
+                .find_map(|leg| self.halted_symbols.get_key_value(&leg.instrument)),
+            None => self.halted_symbols.get_key_value(instrument),
+        }
+    }
+
     fn record(&mut self, command: ExecutionCommand) {
         self.last_command = Some(command);
     }
 
-    pub fn execute<E: ExchangeFacade<Error = ExchangeError>>(
+    /// Keeps a typed history row for every order handed to the exchange,
+    /// with the venue's accept time when `submitted` carries one.
+    ///
+    /// Example:
+    /// - user `set-target-exposure BTCUSDT 0.5` accepted by the exchange
+    /// - row `BTCUSDT Buy qty=0.26 status=Submitted tag=manual`
+    fn record_history(
+        &mut self,
+        request: &CloseOrderRequest,
+        reference_price: Option<f64>,
+        depth_imbalance: Option<f64>,
+        submitted: Result<Option<i64>, &ExchangeError>,
+    ) {
+        let tag = self
+            .last_command
+            .as_ref()
+            .map_or(CommandSource::User.tag(), |command| {
+                command_source(command).tag()
+            });
+        let row = OrderHistoryRow {
+            time: Utc::now(),
+            instrument: request.instrument.clone(),
+            market: request.market,
+            side: request.side,
+            qty: request.qty,
+            price: match request.order_type {
+                OrderType::Limit { price }
+                | OrderType::LimitIoc { price }
+                | OrderType::StopMarket { stop_price: price }
+                | OrderType::StopLimit { price, .. } => Some(price),
+                OrderType::Market | OrderType::TrailingStopMarket { .. } => reference_price,
+            },
+            status: if submitted.is_ok() {
+                OrderStatus::Submitted
+            } else {
+                OrderStatus::Rejected
+            },
+            tag,
+            depth_imbalance,
+            list_id: request.client_order_id.as_deref().and_then(oco_list_id),
+            parent_id: request
+                .client_order_id
+                .as_deref()
+                .and_then(iceberg_parent_id),
+            signal_time: self.signal_time,
+            exchange_time: submitted
+                .ok()
+                .flatten()
+                .and_then(DateTime::<Utc>::from_timestamp_millis),
+        };
+        if let Some(Err(error)) = self
+            .order_store
+            .as_ref()
+            .map(|store| store.record_order(&row))
+        {
+            warn!(
+                service = "trading-engine",
+                error = %error,
+                "order history row not persisted"
+            );
+        }
+        self.history.record(row);
+    }
+
+    pub fn execute<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+        command: ExecutionCommand,
+    ) -> Result<ExecutionOutcome, ExecutionError> {
+        let watch_id = match command_source(&command) {
+            CommandSource::Strategy { watch_id, .. } => Some(*watch_id),
+            CommandSource::User | CommandSource::System => None,
+        };
+        let outcome = self.execute_guarded(exchange, store, price_source, command);
+        let sent = self.strategy_order_sent.take();
+        if let Some(watch_id) = watch_id {
+            let submitted = outcome.as_ref().map(|outcome| {
+                !matches!(
+                    outcome,
+                    ExecutionOutcome::TargetExposureAlreadyAtTarget { .. }
+                )
+            });
+            self.funnel.record(watch_id, Utc::now(), submitted, sent);
+        }
+        outcome
+    }
+
+    fn execute_guarded<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+        command: ExecutionCommand,
+    ) -> Result<ExecutionOutcome, ExecutionError> {
+        if self.entries_disabled && opens_exposure(&command) {
+            return Err(ExecutionError::EntriesDisabled);
+        }
+        if let Some(until) = self
+            .entries_blackout_until
+            .filter(|until| *until > Utc::now() && opens_exposure(&command))
+        {
+            return Err(ExecutionError::EntriesBlackout(until.to_rfc3339()));
+        }
+        if let Some((instrument, status)) = command_instrument(&command)
+            .filter(|_| opens_exposure(&command))
+            .and_then(|instrument| self.halted_status(instrument))
+        {
+            return Err(ExecutionError::SymbolHalted {
+                instrument: instrument.0.clone(),
+                status: status.status().to_string(),
+            });
+        }
+        if let Some(permissions) = self.api_permissions.filter(ApiPermissions::read_only) {
+            return Err(ExecutionError::ReadOnlyApiKey(permissions.label()));
+        }
+        if let Some((instrument, until)) = command_instrument(&command)
+            .filter(|_| {
+                matches!(command_source(&command), CommandSource::Strategy { .. })
+                    && opens_exposure(&command)
+            })
+            .zip(illiquid_until(&self.illiquid_windows, Utc::now()))
+        {
+            let instrument = instrument.clone();
+            return Err(self.queue_entry(instrument, command, until));
+        }
+        if let CommandSource::Strategy { watch_id, .. } = command_source(&command) {
+            let limit = self.strategy_orders_per_min;
+            if limit > 0
+                && opens_exposure(&command)
+                && !self
+                    .strategy_throttles
+                    .entry(*watch_id)
+                    .or_insert_with(|| OrderThrottle::new(limit))
+                    .try_admit(Utc::now().timestamp_millis())
+            {
+                return Err(ExecutionError::StrategyThrottled {
+                    watch_id: *watch_id,
+                    max_orders_per_min: limit,
+                });
+            }
+        }
+        self.record(command.clone());
+        self.signal_time = command_instrument(&command)
+            .and_then(|instrument| price_source.price_stamp(instrument))
+            .map(|stamp| stamp.exchange_time_at(Utc::now()));
+        let outcome = self.run_command(exchange, store, price_source, command);
+        self.signal_time = None;
+        outcome
+    }
+
+    fn run_command<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+        command: ExecutionCommand,
+    ) -> Result<ExecutionOutcome, ExecutionError> {
+        match command {
+            ExecutionCommand::SetTargetExposure {
+                instrument,
+                target,
+                order_type,
+                source: _source,
+            } if self.synthetic_instruments.contains_key(&instrument) => {
+                match self.submit_synthetic_target_exposure(
+                    exchange,
+                    store,
+                    price_source,
+                    &instrument,
+                    target,
+                    order_type,
+                )? {
+                    TargetExposureSubmitResult::Submitted => {
+                        Ok(ExecutionOutcome::TargetExposureSubmitted { instrument })
+                    }
+                    TargetExposureSubmitResult::AlreadyAtTarget => {
+                        Ok(ExecutionOutcome::TargetExposureAlreadyAtTarget { instrument })
+                    }
+                }
+            }
+            ExecutionCommand::SetTargetExposure {
+                instrument,
+                target,
+                order_type,
+                source: _source,
+            } => match self.submit_target_exposure(
+                exchange,
+                store,
+                price_source,
+                &instrument,
+                target,
+                order_type,
+            )? {
+                TargetExposureSubmitResult::Submitted => {
+                    Ok(ExecutionOutcome::TargetExposureSubmitted { instrument })
+                }
+                TargetExposureSubmitResult::AlreadyAtTarget => {
+                    Ok(ExecutionOutcome::TargetExposureAlreadyAtTarget { instrument })
+                }
+            },
+            ExecutionCommand::SubmitOptionOrder {
+                instrument,
+                side,
+                qty,
+                order_type,
+                source: _source,
+            } => {
+                self.submit_option_order(exchange, &instrument, side, qty, order_type)?;
+                Ok(ExecutionOutcome::OptionOrderSubmitted { instrument })
+            }
+            ExecutionCommand::CloseSymbol {
+                instrument,
+                source: _source,
+            } => Ok(ExecutionOutcome::CloseSymbol(self.close_symbol(
+                exchange,
+                store,
+                &instrument,
+            )?)),
+            ExecutionCommand::CloseAll { source } => {
+                let batch_id = match source {
+                    CommandSource::User => BatchId(1),
+                    CommandSource::System | CommandSource::Strategy { .. } => BatchId(2),
+                };
+                Ok(ExecutionOutcome::CloseAll(
+                    self.close_all(exchange, store, batch_id),
+                ))
+            }
+            ExecutionCommand::PlaceTrailingStop {
+                instrument,
+                trail,
+                source: _source,
+            } => {
+                let (on_exchange, callback_rate_pct) =
+                    self.place_trailing_stop(exchange, store, price_source, &instrument, trail)?;
+                Ok(ExecutionOutcome::TrailingStopPlaced {
+                    instrument,
+                    on_exchange,
+                    callback_rate_pct,
+                })
+            }
+            ExecutionCommand::PlaceTakeProfitLadder {
+                instrument,
+                profile,
+                source: _source,
+            } => {
+                let progress = self.place_take_profit_ladder(
+                    exchange,
+                    store,
+                    price_source,
+                    &instrument,
+                    &profile,
+                )?;
+                Ok(ExecutionOutcome::TakeProfitLadderPlaced {
+                    instrument,
+                    progress,
+                })
+            }
+            ExecutionCommand::PlaceLimitOrder {
+                instrument,
+                side,
+                qty,
+                price,
+                source: _source,
+            } => {
+                let (market, price) =
+                    self.place_limit_order(exchange, store, &instrument, side, qty, price)?;
+                Ok(ExecutionOutcome::LimitOrderPlaced {
+                    instrument,
+                    market,
+                    price,
+                })
+            }
+            ExecutionCommand::PlaceIceberg {
+                instrument,
+                side,
+                qty,
+                visible_qty,
+                price,
+                source: _source,
+            } => {
+                let parent_id = self.place_iceberg(
+                    exchange,
+                    store,
+                    &instrument,
+                    side,
+                    qty,
+                    visible_qty,
+                    price,
+                )?;
+                let iceberg = self
+                    .icebergs
+                    .iter()
+                    .find(|iceberg| iceberg.parent_id == parent_id)
+                    .expect("iceberg just placed");
+                Ok(ExecutionOutcome::IcebergPlaced {
+                    market: iceberg.market,
+                    price: iceberg.price,
+                    first_order_id: iceberg.child_order_id.clone(),
+                    instrument,
+                    parent_id,
+                })
+            }
+            ExecutionCommand::CancelOrder {
+                instrument,
+                client_order_id,
+                source: _source,
+            } => {
+                self.cancel_order(exchange, store, &instrument, &client_order_id)?;
+                Ok(ExecutionOutcome::OrderCancelled {
+                    instrument,
+                    client_order_id,
+                })
+            }
+            ExecutionCommand::PlaceOco {
+                instrument,
+                take_profit_price,
+                stop_price,
+                stop_limit_price,
+                source: _source,
+            } => {
+                let list_id = self.place_oco(
+                    exchange,
+                    store,
+                    &instrument,
+                    take_profit_price,
+                    stop_price,
+                    stop_limit_price,
+                )?;
+                let exchange_list_id = self
+                    .oco_lists
+                    .iter()
+                    .find(|list| list.list_id == list_id)
+                    .and_then(|list| list.exchange_list_id.clone());
+                Ok(ExecutionOutcome::OcoPlaced {
+                    instrument,
+                    list_id,
+                    exchange_list_id,
+                })
+            }
+        }
+    }
+
+    /// Brackets the open position with a reduce-only take-profit limit and
+    /// a stop tied together as one OCO list.
+    ///
+    /// Example:
+    /// - long `BTCUSDT` `0.2`, take-profit `66000`, stop `62000`
+    /// - sells `0.2` limit at `66000` as `oco-1-tp` and stop-market at `62000` as `oco-1-sl`
+    /// - with stop limit `61900` the stop leg rests a limit at `61900` once `62000` trades
+    ///
+    /// Where the venue has a native OCO list (Binance spot) both legs go out
+    /// as one list; otherwise a rejected stop leg pulls the take-profit leg
+    /// again. A new list replaces the previous one on the same instrument.
+    pub fn place_oco<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        instrument: &Instrument,
+        take_profit_price: f64,
+        stop_price: f64,
+        stop_limit_price: Option<f64>,
+    ) -> Result<u64, ExecutionError> {
+        let market = store
+            .snapshot
+            .positions
+            .get(instrument)
+            .filter(|position| !position.is_flat())
+            .map(|position| position.market)
+            .ok_or(ExecutionError::NoOpenPosition)?;
+        if market == Market::Options {
+            return Err(ExecutionError::SubmitFailed(
+                ExchangeError::UnsupportedMarketOperation,
+            ));
+        }
+        let plan = self.plan_close(store, instrument)?;
+        let ordered = match plan.side {
+            Side::Sell => take_profit_price > stop_price,
+            Side::Buy => take_profit_price < stop_price,
+        };
+        if !ordered || stop_price <= 0.0 {
+            return Err(ExecutionError::InvalidOcoPrices {
+                side: format!("{:?}", plan.side).to_ascii_lowercase(),
+                take_profit_price,
+                stop_price,
+            });
+        }
+        if let Some(limit_price) = stop_limit_price {
+            let fills = match plan.side {
+                Side::Sell => limit_price <= stop_price,
+                Side::Buy => limit_price >= stop_price,
+            };
+            if !fills {
+                return Err(ExecutionError::InvalidStopLimitPrice {
+                    side: format!("{:?}", plan.side).to_ascii_lowercase(),
+                    stop_price,
+                    limit_price,
+                });
+            }
+        }
+        let tick_size = exchange.load_symbol_rules(instrument, market)?.tick_size;
+        self.last_oco_list_id += 1;
+        let list_id = self.last_oco_list_id;
+        let mut list = OcoOrderList {
+            list_id,
+            instrument: instrument.clone(),
+            market,
+            close_side: plan.side,
+            qty: plan.qty,
+            take_profit_price: round_to_tick(take_profit_price, tick_size),
+            stop_price: round_to_tick(stop_price, tick_size),
+            stop_limit_price: stop_limit_price.map(|price| round_to_tick(price, tick_size)),
+            take_profit_order_id: oco_client_order_id(list_id, OcoLeg::TakeProfit, 0),
+            stop_order_id: oco_client_order_id(list_id, OcoLeg::StopLoss, 0),
+            exchange_list_id: None,
+            status: OcoListStatus::Working,
+            repairs: 0,
+            resizes: 0,
+        };
+        list.exchange_list_id = self.submit_native_oco_list(exchange, &list)?;
+        if list.exchange_list_id.is_none() {
+            if market != Market::Futures {
+                return Err(ExecutionError::SubmitFailed(
+                    ExchangeError::UnsupportedMarketOperation,
+                ));
+            }
+            self.submit_oco_leg(exchange, &list, OcoLeg::TakeProfit, plan.qty)?;
+            if let Err(error) = self.submit_oco_leg(exchange, &list, OcoLeg::StopLoss, plan.qty) {
+                let _ = exchange.cancel_order(instrument, market, &list.take_profit_order_id);
+                return Err(error);
+            }
+        }
+        for previous in self
+            .oco_lists
+            .iter()
+            .filter(|previous| &previous.instrument == instrument)
+        {
+            for leg in [OcoLeg::TakeProfit, OcoLeg::StopLoss] {
+                let _ = exchange.cancel_order(instrument, market, previous.leg_order_id(leg));
+            }
+        }
+        self.oco_lists
+            .retain(|previous| &previous.instrument != instrument);
+        self.oco_lists.push(list);
+        Ok(list_id)
+    }
+
+    /// Places both legs as one venue-side list, recording each in the order
+    /// history; `None` when the venue has no native OCO for the market.
+    fn submit_native_oco_list<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        list: &OcoOrderList,
+    ) -> Result<Option<String>, ExecutionError> {
+        let normalized_qty =
+            self.normalize_direct_order_qty(exchange, &list.instrument, list.market, list.qty)?;
+        self.ensure_market_permitted(list.market)?;
+        let submitted = exchange.submit_oco_list(OcoListRequest {
+            instrument: list.instrument.clone(),
+            market: list.market,
+            side: list.close_side,
+            qty: normalized_qty.qty,
+            qty_text: normalized_qty.qty_text.clone(),
+            take_profit_price: list.take_profit_price,
+            stop_price: list.stop_price,
+            stop_limit_price: list.stop_limit_price,
+            list_client_order_id: format!("oco-{}", list.list_id),
+            take_profit_client_order_id: list.take_profit_order_id.clone(),
+            stop_client_order_id: list.stop_order_id.clone(),
+        });
+        if let Ok(None) = submitted {
+            return Ok(None);
+        }
+        for leg in [OcoLeg::TakeProfit, OcoLeg::StopLoss] {
+            self.record_history(
+                &oco_leg_request(list, leg, normalized_qty.clone()),
+                None,
+                None,
+                submitted
+                    .as_ref()
+                    .map(|accepted| accepted.as_ref().and_then(|list| list.exchange_time_ms)),
+            );
+        }
+        Ok(submitted?.map(|accepted| accepted.remote_list_id))
+    }
+
+    fn submit_oco_leg<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        list: &OcoOrderList,
+        leg: OcoLeg,
+        qty: f64,
+    ) -> Result<(), ExecutionError> {
+        let normalized_qty =
+            self.normalize_direct_order_qty(exchange, &list.instrument, list.market, qty)?;
+        let request = oco_leg_request(list, leg, normalized_qty);
+        self.ensure_market_permitted(request.market)?;
+        let submitted = exchange.submit_order(request.clone());
+        self.record_history(
+            &request,
+            None,
+            None,
+            submitted.as_ref().map(|ack| ack.exchange_time_ms),
+        );
+        submitted?;
+        Ok(())
+    }
+
+    /// Polls every OCO list against the refreshed open orders.
+    ///
+    /// - a missing leg with the position closed or reduced counts as the
+    ///   fill: the other leg is cancelled and the list completes
+    /// - a missing leg with the position intact was cancelled outside the
+    ///   app and is placed again, up to `MAX_OCO_REPAIRS` times
+    /// - a position that grew past the list size, e.g. an entry limit that
+    ///   kept filling, gets both legs replaced at the filled size
+    /// - a failed repair or resize, or both legs gone under an open
+    ///   position, marks the list `needs_manual` until the position closes
+    /// - a native venue list cancels its other leg itself, so it completes
+    ///   once the position is reduced and is never re-armed or resized
+    pub fn check_oco_lists<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+    ) -> Vec<OcoEvent> {
+        let mut events = Vec::new();
+        let mut kept = Vec::new();
+        for mut list in std::mem::take(&mut self.oco_lists) {
+            let open_orders = store
+                .snapshot
+                .open_orders
+                .get(&list.instrument)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let position_qty = store
+                .snapshot
+                .positions
+                .get(&list.instrument)
+                .filter(|position| !position.is_flat())
+                .map(|position| position.abs_qty())
+                .unwrap_or(0.0);
+            let missing = list.missing_legs(open_orders);
+            let reduced = list.qty - position_qty > list.qty * 1e-6;
+            let native = list.exchange_list_id.is_some();
+            if position_qty <= f64::EPSILON
+                || (reduced && (missing.len() == 1 || (native && !missing.is_empty())))
+            {
+                for leg in [OcoLeg::TakeProfit, OcoLeg::StopLoss] {
+                    if !missing.contains(&leg) {
+                        let _ = exchange.cancel_order(
+                            &list.instrument,
+                            list.market,
+                            list.leg_order_id(leg),
+                        );
+                    }
+                }
+                let filled_leg = (missing.len() == 1).then(|| missing[0]);
+                if let Some(leg) = filled_leg {
+                    self.record_ev_outcome(&list.instrument, leg == OcoLeg::TakeProfit);
+                }
+                events.push(OcoEvent::Completed {
+                    list_id: list.list_id,
+                    instrument: list.instrument.clone(),
+                    filled_leg,
+                });
+                continue;
+            }
+            if native {
+                if list.status == OcoListStatus::Working && !missing.is_empty() {
+                    list.status = OcoListStatus::NeedsManual;
+                    events.push(OcoEvent::RepairFailed {
+                        list_id: list.list_id,
+                        instrument: list.instrument.clone(),
+                        reason: "native list cancelled while the position is open".to_string(),
+                    });
+                }
+                kept.push(list);
+                continue;
+            }
+            let grown = position_qty - list.qty > list.qty * 1e-6;
+            if list.status == OcoListStatus::Working && missing.is_empty() && grown {
+                let previous_qty = list.qty;
+                match self.resize_oco_list(exchange, &mut list, position_qty) {
+                    Ok(()) => events.push(OcoEvent::Resized {
+                        list_id: list.list_id,
+                        instrument: list.instrument.clone(),
+                        previous_qty,
+                        qty: position_qty,
+                    }),
+                    Err(error) => {
+                        list.status = OcoListStatus::NeedsManual;
+                        events.push(OcoEvent::RepairFailed {
+                            list_id: list.list_id,
+                            instrument: list.instrument.clone(),
+                            reason: format!("resize to {position_qty} failed: {error}"),
+                        });
+                    }
+                }
+                kept.push(list);
+                continue;
+            }
+            if list.status == OcoListStatus::NeedsManual || missing.is_empty() {
+                kept.push(list);
+                continue;
+            }
+            let failure = if missing.len() == 2 {
+                Some("both legs missing while the position is open".to_string())
+            } else if list.repairs >= MAX_OCO_REPAIRS {
+                Some(format!(
+                    "{} leg cancelled again after {MAX_OCO_REPAIRS} re-arms",
+                    missing[0].as_str()
+                ))
+            } else {
+                let leg = missing[0];
+                list.repairs += 1;
+                let client_order_id = oco_client_order_id(list.list_id, leg, list.repairs);
+                match leg {
+                    OcoLeg::TakeProfit => list.take_profit_order_id = client_order_id.clone(),
+                    OcoLeg::StopLoss => list.stop_order_id = client_order_id.clone(),
+                }
+                match self.submit_oco_leg(exchange, &list, leg, position_qty) {
+                    Ok(()) => {
+                        events.push(OcoEvent::LegRearmed {
+                            list_id: list.list_id,
+                            instrument: list.instrument.clone(),
+                            leg,
+                            client_order_id,
+                        });
+                        None
+                    }
+                    Err(error) => Some(format!("re-arm {} failed: {error}", leg.as_str())),
+                }
+            };
+            if let Some(reason) = failure {
+                list.status = OcoListStatus::NeedsManual;
+                events.push(OcoEvent::RepairFailed {
+                    list_id: list.list_id,
+                    instrument: list.instrument.clone(),
+                    reason,
+                });
+            }
+            kept.push(list);
+        }
+        self.oco_lists = kept;
+        events
+    }
+
+    /// Places both legs at `qty` under new ids, then pulls the old ones, so
+    /// the smaller stop keeps protecting until its replacement rests.
+    fn resize_oco_list<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        list: &mut OcoOrderList,
+        qty: f64,
+    ) -> Result<(), ExecutionError> {
+        let mut resized = list.clone();
+        resized.resizes += 1;
+        resized.take_profit_order_id =
+            oco_resized_order_id(list.list_id, OcoLeg::TakeProfit, resized.resizes);
+        resized.stop_order_id =
+            oco_resized_order_id(list.list_id, OcoLeg::StopLoss, resized.resizes);
+        self.submit_oco_leg(exchange, &resized, OcoLeg::StopLoss, qty)?;
+        if let Err(error) = self.submit_oco_leg(exchange, &resized, OcoLeg::TakeProfit, qty) {
+            let _ = exchange.cancel_order(&list.instrument, list.market, &resized.stop_order_id);
+            return Err(error);
+        }
+        for leg in [OcoLeg::TakeProfit, OcoLeg::StopLoss] {
+            let _ = exchange.cancel_order(&list.instrument, list.market, list.leg_order_id(leg));
+        }
+        resized.qty = qty;
+        *list = resized;
+        Ok(())
+    }
+
+    /// Stop currently protecting `instrument`: the OCO stop leg, else the
+    /// level an in-process trailing stop would fire at.
+    pub fn protective_stop_price(&self, instrument: &Instrument) -> Option<f64> {
+        self.oco_lists
+            .iter()
+            .find(|list| &list.instrument == instrument)
+            .map(|list| list.stop_price)
+            .or_else(|| {
+                self.internal_trailing_stops
+                    .iter()
+                    .find(|stop| &stop.instrument == instrument)
+                    .map(|stop| stop.stop_price())
+            })
+    }
+
+    /// Market the depth ladder and limit orders use for `instrument`:
+    /// the open position's market, else futures, else spot.
+    pub fn order_market<E: ExchangeFacade<Error = ExchangeError>>(
+        &self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        instrument: &Instrument,
+    ) -> Result<Market, ExecutionError> {
+        self.resolve_target_context(exchange, store, instrument, None)
+            .map(|(_, market, _)| market)
+    }
+
+    /// Rests a GTC limit order at `price`, snapped to the symbol tick.
+    ///
+    /// Example:
+    /// - `BTCUSDT` buy `0.01 @ 64,000.04`, tick `0.1`
+    /// - submits `LIMIT GTC` buy `0.010 @ 64,000.0` on futures
+    ///
+    /// Returns the market and the submitted price.
+    pub fn place_limit_order<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        instrument: &Instrument,
+        side: Side,
+        qty: f64,
+        price: f64,
+    ) -> Result<(Market, f64), ExecutionError> {
+        if !price.is_finite() || price <= 0.0 {
+            return Err(ExecutionError::InvalidLimitPrice(price));
+        }
+        let market = self.order_market(exchange, store, instrument)?;
+        let tick_size = exchange.load_symbol_rules(instrument, market)?.tick_size;
+        let price = round_to_tick(price, tick_size);
+        let normalized_qty = self.normalize_direct_order_qty(exchange, instrument, market, qty)?;
+        let request = CloseOrderRequest {
+            instrument: instrument.clone(),
+            market,
+            side,
+            qty: normalized_qty.qty,
+            qty_text: normalized_qty.qty_text,
+            order_type: OrderType::Limit { price },
+            reduce_only: false,
+            client_order_id: None,
+        };
+        self.ensure_market_permitted(request.market)?;
+        let submitted = exchange.submit_order(request.clone());
+        self.record_history(
+            &request,
+            None,
+            None,
+            submitted.as_ref().map(|ack| ack.exchange_time_ms),
+        );
+        submitted?;
+        Ok((market, price))
+    }
+
+    /// Starts an iceberg: posts the first visible slice and tracks the rest.
+    ///
+    /// Example:
+    /// - buy `1.0 BTCUSDT @ 64000` showing `0.25`
+    /// - rests `ice-1-1` for `0.25`; `check_icebergs` posts `ice-1-2` once it fills
+    ///
+    /// Futures only, like the other resting-order tools.
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_iceberg<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        instrument: &Instrument,
+        side: Side,
+        qty: f64,
+        visible_qty: f64,
+        price: f64,
+    ) -> Result<u64, ExecutionError> {
+        if !price.is_finite() || price <= 0.0 {
+            return Err(ExecutionError::InvalidLimitPrice(price));
+        }
+        if !(visible_qty > 0.0 && visible_qty <= qty) {
+            return Err(ExecutionError::InvalidIcebergSize { qty, visible_qty });
+        }
+        let market = self.order_market(exchange, store, instrument)?;
+        if market != Market::Futures {
+            return Err(ExecutionError::SubmitFailed(
+                ExchangeError::UnsupportedMarketOperation,
+            ));
+        }
+        let tick_size = exchange.load_symbol_rules(instrument, market)?.tick_size;
+        let mut iceberg = IcebergOrder {
+            parent_id: self.last_iceberg_id + 1,
+            instrument: instrument.clone(),
+            market,
+            side,
+            price: round_to_tick(price, tick_size),
+            total_qty: qty,
+            visible_qty,
+            filled_qty: 0.0,
+            slices: 0,
+            child_order_id: String::new(),
+            child_qty: 0.0,
+            child_executed_qty: 0.0,
+            child_status: OrderStatus::Submitted,
+        };
+        self.post_iceberg_slice(exchange, &mut iceberg)?;
+        self.last_iceberg_id = iceberg.parent_id;
+        let parent_id = iceberg.parent_id;
+        self.icebergs.push(iceberg);
+        Ok(parent_id)
+    }
+
+    /// Rests the next slice: the visible size, or what is left if smaller.
+    fn post_iceberg_slice<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        iceberg: &mut IcebergOrder,
+    ) -> Result<(), ExecutionError> {
+        let qty = iceberg.visible_qty.min(iceberg.remaining_qty());
+        let normalized_qty =
+            self.normalize_direct_order_qty(exchange, &iceberg.instrument, iceberg.market, qty)?;
+        let slice = iceberg.slices + 1;
+        let request = CloseOrderRequest {
+            instrument: iceberg.instrument.clone(),
+            market: iceberg.market,
+            side: iceberg.side,
+            qty: normalized_qty.qty,
+            qty_text: normalized_qty.qty_text,
+            order_type: OrderType::Limit {
+                price: iceberg.price,
+            },
+            reduce_only: false,
+            client_order_id: Some(iceberg_client_order_id(iceberg.parent_id, slice)),
+        };
+        self.ensure_market_permitted(request.market)?;
+        let submitted = exchange.submit_order(request.clone());
+        self.record_history(
+            &request,
+            None,
+            None,
+            submitted.as_ref().map(|ack| ack.exchange_time_ms),
+        );
+        submitted?;
+        iceberg.slices = slice;
+        iceberg.child_order_id = request.client_order_id.unwrap_or_default();
+        iceberg.child_qty = request.qty;
+        iceberg.child_executed_qty = 0.0;
+        iceberg.child_status = OrderStatus::Submitted;
+        Ok(())
+    }
+
+    /// Applies a user-stream execution report to the iceberg slice it
+    /// belongs to, matched by client order id.
+    pub fn apply_iceberg_report(&mut self, order: &OpenOrder) {
+        if let Some(iceberg) = self
+            .icebergs
+            .iter_mut()
+            .find(|iceberg| iceberg.child_order_id == order.client_order_id)
+        {
+            iceberg.child_executed_qty = iceberg.child_executed_qty.max(order.executed_qty);
+            iceberg.child_status = order.status;
+        }
+    }
+
+    /// Final fill of a slice that left the book, by its client order id:
+    /// the stream's terminal report, else the venue's order status.
+    ///
+    /// `None` while the venue still shows the slice open or the lookup
+    /// failed; the next poll asks again. A venue that cannot look orders
+    /// up leaves the last fill seen.
+    fn finished_slice_qty<E: ExchangeFacade<Error = ExchangeError>>(
+        exchange: &E,
+        iceberg: &IcebergOrder,
+    ) -> Option<f64> {
+        let terminal = |status| {
+            matches!(
+                status,
+                OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected
+            )
+        };
+        if terminal(iceberg.child_status) {
+            return Some(iceberg.child_executed_qty);
+        }
+        match exchange.load_order(&iceberg.instrument, iceberg.market, &iceberg.child_order_id) {
+            Ok(Some(order)) if terminal(order.status) => {
+                Some(order.executed_qty.max(iceberg.child_executed_qty))
+            }
+            Ok(Some(_)) | Err(_) => None,
+            Ok(None) => Some(iceberg.child_executed_qty),
+        }
+    }
+
+    /// Polls every iceberg against the refreshed open orders.
+    ///
+    /// - a resting slice only updates the reported progress
+    /// - a slice gone from the book is settled by its own client order id,
+    ///   from the user stream's execution report or the venue's order
+    ///   status; a full fill posts the next slice, or completes the parent
+    ///   once nothing is left
+    /// - a slice that left short of its size was cancelled outside the app,
+    ///   which stops the parent; cancelling the resting slice is how an
+    ///   operator pulls an iceberg
+    pub fn check_icebergs<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+    ) -> Vec<IcebergEvent> {
+        let mut events = Vec::new();
+        let mut kept = Vec::new();
+        for mut iceberg in std::mem::take(&mut self.icebergs) {
+            let resting = store
+                .snapshot
+                .open_orders
+                .get(&iceberg.instrument)
+                .and_then(|orders| {
+                    orders
+                        .iter()
+                        .find(|order| order.client_order_id == iceberg.child_order_id)
+                });
+            if let Some(order) = resting {
+                iceberg.child_executed_qty = order.executed_qty;
+                kept.push(iceberg);
+                continue;
+            }
+            let Some(slice_filled) = Self::finished_slice_qty(exchange, &iceberg) else {
+                kept.push(iceberg);
+                continue;
+            };
+            let slice_filled = slice_filled.clamp(0.0, iceberg.child_qty);
+            iceberg.filled_qty += slice_filled;
+            iceberg.child_executed_qty = 0.0;
+            if slice_filled < iceberg.child_qty * (1.0 - 1e-6) {
+                events.push(IcebergEvent::Stopped {
+                    parent_id: iceberg.parent_id,
+                    instrument: iceberg.instrument.clone(),
+                    filled_qty: iceberg.filled_qty,
+                    total_qty: iceberg.total_qty,
+                    reason: format!(
+                        "{} left the book with {slice_filled} of {} filled",
+                        iceberg.child_order_id, iceberg.child_qty
+                    ),
+                });
+                continue;
+            }
+            let slice = iceberg.slices;
+            let filled_slice = |iceberg: &IcebergOrder, next_order_id| IcebergEvent::SliceFilled {
+                parent_id: iceberg.parent_id,
+                instrument: iceberg.instrument.clone(),
+                slice,
+                client_order_id: iceberg_client_order_id(iceberg.parent_id, slice),
+                slice_qty: slice_filled,
+                filled_qty: iceberg.filled_qty,
+                total_qty: iceberg.total_qty,
+                next_order_id,
+            };
+            if iceberg.remaining_qty() <= 0.0 {
+                events.push(filled_slice(&iceberg, None));
+                events.push(IcebergEvent::Completed {
+                    parent_id: iceberg.parent_id,
+                    instrument: iceberg.instrument.clone(),
+                    filled_qty: iceberg.filled_qty,
+                    slices: iceberg.slices,
+                });
+                continue;
+            }
+            match self.post_iceberg_slice(exchange, &mut iceberg) {
+                Ok(()) => {
+                    events.push(filled_slice(&iceberg, Some(iceberg.child_order_id.clone())));
+                    kept.push(iceberg);
+                }
+                Err(error) => {
+                    events.push(filled_slice(&iceberg, None));
+                    events.push(IcebergEvent::Stopped {
+                        parent_id: iceberg.parent_id,
+                        instrument: iceberg.instrument.clone(),
+                        filled_qty: iceberg.filled_qty,
+                        total_qty: iceberg.total_qty,
+                        reason: format!("posting slice {} failed: {error}", slice + 1),
+                    });
+                }
+            }
+        }
+        self.icebergs = kept;
+        events
+    }
+
+    /// Cancels one of our resting orders on `instrument`.
+    pub fn cancel_order<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        instrument: &Instrument,
+        client_order_id: &str,
+    ) -> Result<(), ExecutionError> {
+        let order = store
+            .snapshot
+            .open_orders
+            .get(instrument)
+            .and_then(|orders| {
+                orders
+                    .iter()
+                    .find(|order| order.client_order_id == client_order_id)
+            })
+            .ok_or_else(|| ExecutionError::OpenOrderNotFound {
+                instrument: instrument.0.clone(),
+                client_order_id: client_order_id.to_string(),
+            })?;
+        exchange.cancel_order(instrument, order.market, client_order_id)?;
+        Ok(())
+    }
+
+    /// Risk-off for one instrument: cancels every open order on it, stops
+    /// tracking its stops, ladders, OCO lists and deferred entries, then
+    /// closes the position at market.
+    ///
+    /// Cancels go first so a resting order cannot add to the position while
+    /// it closes; a failed cancel does not hold the close back.
+    pub fn emergency_close_position<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        instrument: &Instrument,
+    ) -> EmergencyCloseResult {
+        let mut cancelled = Vec::new();
+        let mut cancel_failed = Vec::new();
+        for order in store
+            .snapshot
+            .open_orders
+            .get(instrument)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+        {
+            match exchange.cancel_order(instrument, order.market, &order.client_order_id) {
+                Ok(()) => cancelled.push(order.client_order_id.clone()),
+                Err(_) => cancel_failed.push(order.client_order_id.clone()),
+            }
+        }
+        self.oco_lists.retain(|list| &list.instrument != instrument);
+        self.icebergs
+            .retain(|iceberg| &iceberg.instrument != instrument);
+        self.take_profit_ladders
+            .retain(|ladder| &ladder.instrument != instrument);
+        self.internal_trailing_stops
+            .retain(|stop| &stop.instrument != instrument);
+        self.deferred_executions
+            .retain(|deferred| command_instrument(&deferred.command) != Some(instrument));
+        self.queued_entries
+            .retain(|queued| command_instrument(&queued.command) != Some(instrument));
+        self.hold_ev.remove(instrument);
+        let close = self
+            .close_symbol(exchange, store, instrument)
+            .map_or(CloseSubmitResult::Rejected, |closed| closed.result);
+        EmergencyCloseResult {
+            instrument: instrument.clone(),
+            cancelled,
+            cancel_failed,
+            close,
+        }
+    }
+
+    /// Protects the current position with a trailing stop.
+    ///
+    /// Example:
+    /// - futures long `BTCUSDT`, callback `1.5` in `Exchange` mode
+    /// - submits a reduce-only `TRAILING_STOP_MARKET` sell with `callbackRate=1.5`
+    /// - spot `ETHUSDT` registers an internal stop instead
+    ///
+    /// Returns true when the stop was handed to the exchange.
+    pub fn place_trailing_stop<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+        instrument: &Instrument,
+        trail: TrailDistance,
+    ) -> Result<(bool, f64), ExecutionError> {
+        let market = store
+            .snapshot
+            .positions
+            .get(instrument)
+            .map(|position| position.market)
+            .ok_or(ExecutionError::NoOpenPosition)?;
+        let atr_pct = match trail {
+            TrailDistance::Percent(_) => None,
+            TrailDistance::AtrMultiple(_) => exchange
+                .load_symbol_range_stats(instrument, market)?
+                .map(|stats| stats.atr_pct),
+        };
+        let callback_rate_pct = trail
+            .callback_rate_pct(atr_pct)
+            .ok_or_else(|| ExecutionError::MissingAtr(instrument.0.clone()))?;
+        if self.trailing_stop_mode.uses_exchange(market) {
+            let result = self.submit_close(
+                exchange,
+                store,
+                instrument,
+                OrderType::TrailingStopMarket { callback_rate_pct },
+            )?;
+            return match result.result {
+                CloseSubmitResult::SkippedNoPosition => Err(ExecutionError::NoOpenPosition),
+                _ => Ok((true, callback_rate_pct)),
+            };
+        }
+
+        let plan = self.plan_close(store, instrument)?;
+        let current_price = price_source
+            .current_price(instrument)
+            .or_else(|| exchange.load_last_price(instrument, market).ok())
+            .ok_or(ExecutionError::MissingPriceContext)?;
+        self.internal_trailing_stops
+            .retain(|stop| &stop.instrument != instrument);
+        self.internal_trailing_stops.push(InternalTrailingStop {
+            instrument: instrument.clone(),
+            market,
+            close_side: plan.side,
+            callback_rate_pct,
+            extreme_price: current_price,
+        });
+        Ok((false, callback_rate_pct))
+    }
+
+    /// Feeds current prices to internal trailing stops and closes the
+    /// positions whose stop was hit. Stops without a position are dropped.
+    pub fn check_internal_trailing_stops<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+    ) -> Vec<CloseSymbolResult> {
+        let mut triggered = Vec::new();
+        self.internal_trailing_stops.retain_mut(|stop| {
+            if store
+                .snapshot
+                .positions
+                .get(&stop.instrument)
+                .is_none_or(|position| position.is_flat())
+            {
+                return false;
+            }
+            let Some(price) = price_source.current_price(&stop.instrument) else {
+                return true;
+            };
+            if stop.update(price) {
+                triggered.push(stop.instrument.clone());
+                return false;
+            }
+            true
+        });
+
+        triggered
+            .into_iter()
+            .map(
+                |instrument| match self.close_symbol(exchange, store, &instrument) {
+                    Ok(result) => result,
+                    Err(_) => CloseSymbolResult {
+                        instrument,
+                        result: CloseSubmitResult::Rejected,
+                    },
+                },
+            )
+            .collect()
+    }
+
+    /// Re-estimates the EV of holding each bracketed position at the
+    /// current price.
+    ///
+    /// The profile is the one named like the position's take-profit ladder,
+    /// else `default`. Events fire only when the action gets worse than on
+    /// the previous refresh, so a position sitting below the threshold is
+    /// suggested once; `AutoExit` closes it at market.
+    pub fn check_hold_ev<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+    ) -> Vec<HoldEvEvent> {
+        let mut events = Vec::new();
+        let mut estimates = BTreeMap::new();
+        for list in &self.oco_lists {
+            let Some(entry_price) = store
+                .snapshot
+                .positions
+                .get(&list.instrument)
+                .filter(|position| !position.is_flat())
+                .and_then(|position| position.entry_price)
+            else {
+                continue;
+            };
+            let Some(price) = price_source.current_price(&list.instrument) else {
+                continue;
+            };
+            let (profile_name, profile) = self.hold_ev_profile_for(&list.instrument);
+            let outcomes = self.hold_ev_outcomes(&list.instrument, &profile_name);
+            let estimator = EvEstimator::with_model(
+                entry_price,
+                list.stop_price,
+                list.take_profit_price,
+                profile.model.build(profile.win_rate, &outcomes),
+            );
+            let expected_value_r = estimator.expected_value_r(price);
+            estimates.insert(
+                list.instrument.clone(),
+                HoldEvEstimate {
+                    instrument: list.instrument.clone(),
+                    profile_name,
+                    price,
+                    r_multiple: estimator.r_multiple(price),
+                    win_probability: estimator.win_probability(price),
+                    expected_value_r,
+                    action: profile.action(expected_value_r),
+                    model_version: estimator.model.version(),
+                    trades: outcomes.len(),
+                },
+            );
+        }
+
+        for (instrument, estimate) in &estimates {
+            let previous = self
+                .hold_ev
+                .get(instrument)
+                .map_or(HoldEvAction::Hold, |previous| previous.action);
+            if estimate.action <= previous {
+                continue;
+            }
+            match estimate.action {
+                HoldEvAction::Hold => {}
+                HoldEvAction::SuggestExit => {
+                    events.push(HoldEvEvent::SuggestExit(estimate.clone()))
+                }
+                HoldEvAction::AutoExit => {
+                    let result = self
+                        .close_symbol(exchange, store, instrument)
+                        .map_or(CloseSubmitResult::Rejected, |closed| closed.result);
+                    events.push(HoldEvEvent::Exited {
+                        estimate: estimate.clone(),
+                        result,
+                    });
+                }
+            }
+        }
+        self.hold_ev = estimates;
+        events
+    }
+
+    /// Flattens every position held longer than its symbol's maximum age,
+    /// independently of the strategy that opened it.
+    ///
+    /// Example:
+    /// - `BTCUSDT=14400`, long opened at 08:00, checked at 12:01
+    /// - market close submitted, reported with `held_secs=14460`
+    ///
+    /// A position still open on the next refresh is closed again.
+    pub fn check_max_position_age<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        now: DateTime<Utc>,
+    ) -> Vec<MaxAgeExit> {
+        let mut exits = Vec::new();
+        for (instrument, max_age_secs) in self.max_position_age_secs.clone() {
+            if store
+                .snapshot
+                .positions
+                .get(&instrument)
+                .is_none_or(|position| position.is_flat())
+            {
+                continue;
+            }
+            let Some(stats) = store.entry_stats.get(&instrument) else {
+                continue;
+            };
+            let held_secs = (now - stats.opened_at()).num_seconds().max(0) as u64;
+            if held_secs < max_age_secs {
+                continue;
+            }
+            let result = self
+                .close_symbol(exchange, store, &instrument)
+                .map_or(CloseSubmitResult::Rejected, |closed| closed.result);
+            exits.push(MaxAgeExit {
+                instrument,
+                held_secs,
+                max_age_secs,
+                result,
+            });
+        }
+        exits
+    }
+
+    /// Keeps the latest `MAX_EV_OUTCOMES` bracket results of the profile
+    /// that governs `instrument`.
+    fn record_ev_outcome(&mut self, instrument: &Instrument, won: bool) {
+        let (profile_name, _) = self.hold_ev_profile_for(instrument);
+        let outcomes = self.ev_outcomes.entry(profile_name).or_default();
+        outcomes.push(won);
+        if outcomes.len() > MAX_EV_OUTCOMES {
+            outcomes.remove(0);
+        }
+    }
+
+    /// The latest `MAX_EV_OUTCOMES` realized trades on `instrument` inside
+    /// the lookback when an order store is open, else the bracket results
+    /// recorded this session under `profile_name`.
+    ///
+    /// Example:
+    /// - store open, lookback `30`: every BTCUSDT futures close of the last
+    ///   30 days, whichever strategy or user sent it
+    fn hold_ev_outcomes(&self, instrument: &Instrument, profile_name: &str) -> Vec<bool> {
+        let session = || {
+            self.ev_outcomes
+                .get(profile_name)
+                .cloned()
+                .unwrap_or_default()
+        };
+        let Some(store) = &self.order_store else {
+            return session();
+        };
+        let query = HistoryQuery {
+            instrument: Some(instrument.clone()),
+            tag: None,
+        };
+        let since = (self.trade_stats_lookback_days > 0)
+            .then(|| Utc::now() - Duration::days(i64::from(self.trade_stats_lookback_days)));
+        match store.outcomes(&query, since) {
+            Ok(mut outcomes) => {
+                let excess = outcomes.len().saturating_sub(MAX_EV_OUTCOMES);
+                outcomes.drain(..excess);
+                outcomes
+            }
+            Err(error) => {
+                warn!(
+                    service = "trading-engine",
+                    instrument = instrument.0,
+                    error = %error,
+                    "trade stats unavailable: hold ev uses this session's brackets"
+                );
+                session()
+            }
+        }
+    }
+
+    fn hold_ev_profile_for(&self, instrument: &Instrument) -> (String, HoldEvProfile) {
+        self.take_profit_ladders
+            .iter()
+            .find(|ladder| &ladder.instrument == instrument)
+            .and_then(|ladder| self.hold_ev_profiles.get_key_value(&ladder.profile_name))
+            .or_else(|| self.hold_ev_profiles.get_key_value(DEFAULT_HOLD_EV_PROFILE))
+            .map_or_else(
+                || {
+                    (
+                        DEFAULT_HOLD_EV_PROFILE.to_string(),
+                        HoldEvProfile::default(),
+                    )
+                },
+                |(name, profile)| (name.clone(), *profile),
+            )
+    }
+
+    /// Attaches a take-profit ladder to the current position.
+    ///
+    /// Example:
+    /// - long `BTCUSDT` `0.2` entered at `50000`, profile `default`
+    /// - sells `0.1` at `50500`, `0.06` at `51000`, then trails the last `0.04`
+    ///
+    /// Falls back to the current price when the position has no entry.
+    /// Returns the initial progress line.
+    pub fn place_take_profit_ladder<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+        instrument: &Instrument,
+        profile_name: &str,
+    ) -> Result<String, ExecutionError> {
+        let profile = self
+            .take_profit_profiles
+            .get(profile_name)
+            .cloned()
+            .or_else(|| {
+                (profile_name == DEFAULT_TAKE_PROFIT_PROFILE).then(TakeProfitProfile::default)
+            })
+            .ok_or_else(|| ExecutionError::UnknownTakeProfitProfile(profile_name.to_string()))?;
+        let plan = self.plan_close(store, instrument)?;
+        let position = store
+            .snapshot
+            .positions
+            .get(instrument)
+            .ok_or(ExecutionError::NoOpenPosition)?;
+        let entry_price = match position.entry_price.filter(|price| *price > f64::EPSILON) {
+            Some(price) => price,
+            None => price_source
+                .current_price(instrument)
+                .or_else(|| exchange.load_last_price(instrument, position.market).ok())
+                .ok_or(ExecutionError::MissingPriceContext)?,
+        };
+        let ladder = TakeProfitLadder {
+            instrument: instrument.clone(),
+            market: position.market,
+            close_side: plan.side,
+            profile_name: profile_name.to_string(),
+            profile,
+            entry_price,
+            initial_qty: plan.qty,
+            filled_levels: 0,
+        };
+        let progress = ladder.progress(false);
+        self.take_profit_ladders
+            .retain(|ladder| &ladder.instrument != instrument);
+        self.take_profit_ladders.push(ladder);
+        Ok(progress)
+    }
+
+    /// Works every ladder against current prices.
+    ///
+    /// Each reached level sends one reduce-only market order for its slice
+    /// of the initial size. Once all levels are out, the next refresh hands
+    /// the remaining runner to `place_trailing_stop` so it sees the reduced
+    /// position, and the ladder is dropped. A rejected level stays armed and
+    /// a runner that fails to arm keeps its ladder, so both are retried on
+    /// the next refresh, unless the exchange failure is fatal (credentials
+    /// or configuration). Ladders without a position are dropped silently.
+    /// Until the first level fires, the initial size follows the position up
+    /// as a partially filled entry keeps filling.
+    pub fn check_take_profit_ladders<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+    ) -> Vec<TakeProfitEvent> {
+        let mut events = Vec::new();
+        let mut kept = Vec::new();
+        for mut ladder in std::mem::take(&mut self.take_profit_ladders) {
+            let Some(position) = store
+                .snapshot
+                .positions
+                .get(&ladder.instrument)
+                .filter(|position| !position.is_flat())
+            else {
+                continue;
+            };
+            if ladder.filled_levels == 0 {
+                ladder.initial_qty = ladder.initial_qty.max(position.abs_qty());
+            }
+            if ladder.levels_done() {
+                let callback_rate_pct = ladder.profile.runner_callback_pct;
+                events.push(
+                    match self.place_trailing_stop(
+                        exchange,
+                        store,
+                        price_source,
+                        &ladder.instrument,
+                        TrailDistance::Percent(callback_rate_pct),
+                    ) {
+                        Ok((on_exchange, _)) => TakeProfitEvent::RunnerArmed {
+                            instrument: ladder.instrument.clone(),
+                            callback_rate_pct,
+                            on_exchange,
+                            progress: ladder.progress(true),
+                        },
+                        Err(error) => {
+                            let retrying = error.class() != Some(ErrorClass::Fatal);
+                            let event = TakeProfitEvent::RunnerFailed {
+                                instrument: ladder.instrument.clone(),
+                                error: error.to_string(),
+                                retrying,
+                            };
+                            if retrying {
+                                kept.push(ladder);
+                            }
+                            event
+                        }
+                    },
+                );
+                continue;
+            }
+            let Some(price) = price_source.current_price(&ladder.instrument) else {
+                kept.push(ladder);
+                continue;
+            };
+            while let Some((level_index, level)) = ladder.next_triggered(price) {
+                let qty = ladder.initial_qty * level.fraction;
+                let result = match self.submit_partial_close(exchange, store, &ladder, qty) {
+                    Ok(()) => CloseSubmitResult::Submitted,
+                    Err(_) => CloseSubmitResult::Rejected,
+                };
+                let submitted = result == CloseSubmitResult::Submitted;
+                if submitted {
+                    ladder.filled_levels += 1;
+                }
+                events.push(TakeProfitEvent::LevelFilled {
+                    instrument: ladder.instrument.clone(),
+                    level: level_index + 1,
+                    trigger_price: ladder.level_price(&level),
+                    qty,
+                    result,
+                    progress: ladder.progress(false),
+                });
+                if !submitted {
+                    break;
+                }
+            }
+            kept.push(ladder);
+        }
+        self.take_profit_ladders = kept;
+        events
+    }
+
+    fn submit_partial_close<E: ExchangeFacade<Error = ExchangeError>>(
         &mut self,
         exchange: &E,
         store: &PortfolioStateStore,
-        price_source: &impl PriceSource,
-        command: ExecutionCommand,
-    ) -> Result<ExecutionOutcome, ExecutionError> {
-        self.record(command.clone());
-        match command {
-            ExecutionCommand::SetTargetExposure {
-                instrument,
-                target,
-                order_type,
-                source: _source,
-            } => match self.submit_target_exposure(
-                exchange,
-                store,
-                price_source,
-                &instrument,
-                target,
-                order_type,
-            )? {
-                TargetExposureSubmitResult::Submitted => {
-                    Ok(ExecutionOutcome::TargetExposureSubmitted { instrument })
-                }
-                TargetExposureSubmitResult::AlreadyAtTarget => {
-                    Ok(ExecutionOutcome::TargetExposureAlreadyAtTarget { instrument })
-                }
-            },
-            ExecutionCommand::SubmitOptionOrder {
-                instrument,
-                side,
-                qty,
-                order_type,
-                source: _source,
-            } => {
-                self.submit_option_order(exchange, &instrument, side, qty, order_type)?;
-                Ok(ExecutionOutcome::OptionOrderSubmitted { instrument })
-            }
-            ExecutionCommand::CloseSymbol {
-                instrument,
-                source: _source,
-            } => Ok(ExecutionOutcome::CloseSymbol(self.close_symbol(
-                exchange,
-                store,
-                &instrument,
-            )?)),
-            ExecutionCommand::CloseAll { source } => {
-                let batch_id = match source {
-                    CommandSource::User => BatchId(1),
-                    CommandSource::System => BatchId(2),
-                };
-                Ok(ExecutionOutcome::CloseAll(
-                    self.close_all(exchange, store, batch_id),
-                ))
-            }
-        }
+        ladder: &TakeProfitLadder,
+        qty: f64,
+    ) -> Result<(), ExecutionError> {
+        let plan = self.plan_close(store, &ladder.instrument)?;
+        let qty = self.normalize_direct_order_qty(
+            exchange,
+            &plan.instrument,
+            ladder.market,
+            qty.min(plan.qty),
+        )?;
+        let request = CloseOrderRequest {
+            instrument: plan.instrument,
+            market: ladder.market,
+            side: plan.side,
+            qty: qty.qty,
+            qty_text: qty.qty_text,
+            order_type: OrderType::Market,
+            reduce_only: plan.reduce_only,
+            client_order_id: None,
+        };
+        self.ensure_market_permitted(request.market)?;
+        let submitted = exchange.submit_close_order(request.clone());
+        self.record_history(
+            &request,
+            None,
+            None,
+            submitted.as_ref().map(|ack| ack.exchange_time_ms),
+        );
+        submitted?;
+        Ok(())
     }
 
     fn plan_close(
@@ -137,7 +1786,7 @@ impl ExecutionService {
         _order_type: OrderType,
     ) -> Result<ExecutionPlan, ExecutionError> {
         let (resolved_instrument, market, current_qty) =
-            self.resolve_target_context(exchange, store, instrument)?;
+            self.resolve_target_context(exchange, store, instrument, None)?;
         let current_price = price_source
             .current_price(&resolved_instrument)
             .or_else(|| exchange.load_last_price(&resolved_instrument, market).ok())
@@ -177,14 +1826,101 @@ impl ExecutionService {
         target: Exposure,
         order_type: OrderType,
     ) -> Result<TargetExposureSubmitResult, ExecutionError> {
+        self.submit_target_exposure_on(
+            exchange,
+            store,
+            price_source,
+            instrument,
+            None,
+            target,
+            order_type,
+        )
+    }
+
+    /// Routes a synthetic target to its legs, scaled by each leg weight.
+    ///
+    /// Example:
+    /// - `BTC_BASIS=BTCUSDT@futures-BTCUSDT@spot`, target `-0.2`
+    /// - futures `BTCUSDT` -> `-0.2`, spot `BTCUSDT` -> `+0.2`
+    ///
+    /// Every leg target is checked before the first order goes out.
+    fn submit_synthetic_target_exposure<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+        instrument: &Instrument,
+        target: Exposure,
+        order_type: OrderType,
+    ) -> Result<TargetExposureSubmitResult, ExecutionError> {
+        let synthetic = self
+            .synthetic_instruments
+            .get(instrument)
+            .cloned()
+            .ok_or_else(|| ExecutionError::UnknownInstrument(instrument.0.clone()))?;
+        let legs = synthetic
+            .leg_targets(target.value())
+            .into_iter()
+            .map(|(leg, leg_target)| {
+                Exposure::new(leg_target)
+                    .map(|exposure| (leg.clone(), exposure))
+                    .ok_or_else(|| ExecutionError::SyntheticLegOutOfRange {
+                        synthetic: instrument.0.clone(),
+                        leg: leg.instrument.0.clone(),
+                        target: leg_target,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut result = TargetExposureSubmitResult::AlreadyAtTarget;
+        for (leg, exposure) in legs {
+            if self.submit_target_exposure_on(
+                exchange,
+                store,
+                price_source,
+                &leg.instrument,
+                Some(leg.market),
+                exposure,
+                order_type,
+            )? == TargetExposureSubmitResult::Submitted
+            {
+                result = TargetExposureSubmitResult::Submitted;
+            }
+        }
+        Ok(result)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn submit_target_exposure_on<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+        instrument: &Instrument,
+        market: Option<Market>,
+        target: Exposure,
+        order_type: OrderType,
+    ) -> Result<TargetExposureSubmitResult, ExecutionError> {
+        let leg = market.is_some();
         let (resolved_instrument, market, current_qty) =
-            self.resolve_target_context(exchange, store, instrument)?;
+            self.resolve_target_context(exchange, store, instrument, market)?;
+        // A synthetic unwind leaves legs that are already flat alone.
+        if leg && target.value().abs() <= f64::EPSILON && current_qty.abs() <= f64::EPSILON {
+            return Ok(TargetExposureSubmitResult::AlreadyAtTarget);
+        }
         let current_price = price_source
             .current_price(&resolved_instrument)
             .or_else(|| exchange.load_last_price(&resolved_instrument, market).ok())
             .ok_or(ExecutionError::MissingPriceContext)?;
         let equity_usdt: f64 = store.snapshot.balances.iter().map(|b| b.total()).sum();
         let target_notional = exposure_to_notional(target, equity_usdt);
+        self.check_asset_exposure_cap(
+            store,
+            price_source,
+            &resolved_instrument,
+            current_qty * current_price,
+            target_notional.target_usdt,
+            equity_usdt,
+        )?;
         let synthetic_position = PositionSnapshot {
             instrument: resolved_instrument.clone(),
             market,
@@ -213,10 +1949,12 @@ impl ExecutionService {
             &plan.instrument,
             market,
             plan.qty,
-            target.value(),
-            equity_usdt,
-            current_price,
-            target_notional.target_usdt,
+            SizingContext {
+                target_exposure: target.value(),
+                equity_usdt,
+                current_price,
+                target_notional_usdt: target_notional.target_usdt,
+            },
         ) {
             Ok(qty) => qty,
             Err(ExecutionError::OrderQtyTooSmall {
@@ -232,7 +1970,7 @@ impl ExecutionService {
             Err(error) => return Err(error),
         };
 
-        exchange.submit_order(CloseOrderRequest {
+        let mut request = CloseOrderRequest {
             instrument: plan.instrument,
             market,
             side: plan.side,
@@ -240,23 +1978,373 @@ impl ExecutionService {
             qty_text: qty.qty_text,
             order_type,
             reduce_only: plan.reduce_only,
-        })?;
+            client_order_id: self.next_strategy_client_order_id(),
+        };
+        self.ensure_market_permitted(request.market)?;
+        let depth_check = self.check_ask_depth(exchange, &request, current_price)?;
+        self.cap_market_slippage(exchange, &mut request, current_price)?;
+        let submitted = exchange.submit_order(request.clone());
+        self.record_history(
+            &request,
+            Some(current_price),
+            depth_check.map(|check| check.imbalance),
+            submitted.as_ref().map(|ack| ack.exchange_time_ms),
+        );
+        submitted?;
+        self.record_shadow_order(&request, current_price);
         Ok(TargetExposureSubmitResult::Submitted)
     }
 
+    /// Client order id for an order the running strategy command sends, so
+    /// its fills can be traced back: `shadow-` when the paper shadow is on,
+    /// `strategy-` otherwise. `None` for operator and system orders.
+    fn next_strategy_client_order_id(&mut self) -> Option<String> {
+        let CommandSource::Strategy { watch_id, .. } = command_source(self.last_command.as_ref()?)
+        else {
+            return None;
+        };
+        let client_order_id = if self.paper_shadow.is_some() {
+            self.last_shadow_order_id += 1;
+            shadow_client_order_id(*watch_id, self.last_shadow_order_id)
+        } else {
+            self.last_strategy_order_id += 1;
+            strategy_client_order_id(*watch_id, self.last_strategy_order_id)
+        };
+        self.strategy_order_sent = Some(client_order_id.clone());
+        Some(client_order_id)
+    }
+
+    /// Fills the paper twin of an accepted shadowed order.
+    fn record_shadow_order(&mut self, request: &CloseOrderRequest, decision_price: f64) {
+        let (Some(config), Some(client_order_id)) =
+            (self.paper_shadow, request.client_order_id.as_deref())
+        else {
+            return;
+        };
+        let Some(watch_id) = shadow_watch_id(client_order_id) else {
+            return;
+        };
+        self.shadow_orders.push(ShadowOrder {
+            client_order_id: client_order_id.to_string(),
+            watch_id,
+            instrument: request.instrument.clone(),
+            market: request.market,
+            side: request.side,
+            qty: request.qty,
+            decision_price,
+            paper_price: config.paper_price(request.side, decision_price),
+            live_filled_qty: 0.0,
+            live_notional: 0.0,
+        });
+        let excess = self.shadow_orders.len().saturating_sub(MAX_SHADOW_ORDERS);
+        self.shadow_orders.drain(..excess);
+    }
+
+    /// Books a live fill against its paper twin; `None` for orders that
+    /// are not shadowed.
+    pub fn apply_shadow_fill(
+        &mut self,
+        client_order_id: &str,
+        fill_qty: f64,
+        fill_price: f64,
+    ) -> Option<&ShadowOrder> {
+        let order = self
+            .shadow_orders
+            .iter_mut()
+            .find(|order| order.client_order_id == client_order_id)?;
+        order.live_filled_qty += fill_qty;
+        order.live_notional += fill_qty * fill_price;
+        Some(order)
+    }
+
+    /// Paper against live per watch, marked at the current prices.
+    pub fn shadow_divergence(&self, price_source: &impl PriceSource) -> Vec<ShadowDivergence> {
+        shadow_divergence(
+            &self.shadow_orders,
+            self.paper_shadow
+                .map_or(0.0, |config| config.modeled_slippage_bps),
+            |instrument| price_source.current_price(instrument),
+        )
+    }
+
+    /// Measures the ask side before an opening market buy and rejects the
+    /// order when resting asks near mid cannot absorb it.
+    ///
+    /// Example:
+    /// - band `10` bps, min cover `3`, buy worth `1,000 USDT`
+    /// - `2,400 USDT` of asks within 0.1% of mid -> `ThinAskDepth`
+    ///
+    /// Skipped when the filter is off or the venue returns no depth.
+    fn check_ask_depth<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        request: &CloseOrderRequest,
+        current_price: f64,
+    ) -> Result<Option<DepthCheck>, ExecutionError> {
+        self.last_depth_check = None;
+        let Some(filter) = self.depth_filter else {
+            return Ok(None);
+        };
+        if request.order_type != OrderType::Market
+            || request.side != Side::Buy
+            || request.reduce_only
+        {
+            return Ok(None);
+        }
+        let Some(check) = exchange
+            .load_order_book_depth(&request.instrument, request.market)
+            .ok()
+            .flatten()
+            .and_then(|book| filter.check(&book, request.qty * current_price))
+        else {
+            return Ok(None);
+        };
+        self.last_depth_check = Some(check);
+        if !check.passed {
+            return Err(ExecutionError::ThinAskDepth {
+                instrument: request.instrument.0.clone(),
+                ask_notional_usdt: check.ask_notional_usdt,
+                required_ask_usdt: check.required_ask_usdt,
+                imbalance: check.imbalance,
+            });
+        }
+        Ok(Some(check))
+    }
+
+    /// Swaps a market order for an IOC limit at the last price plus the
+    /// slippage band, and rejects it when the price already ran past the
+    /// band since sizing.
+    ///
+    /// Example:
+    /// - band `20` bps, sized at `50,000`, last `50,040`
+    /// - buy goes out as `LIMIT IOC @ 50,140.08`
+    /// - last `50,150` instead -> `SlippageBandExceeded`
+    ///
+    /// Closes, take-profit steps and trailing stops stay market orders so a
+    /// position can always be flattened.
+    fn cap_market_slippage<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        request: &mut CloseOrderRequest,
+        reference_price: f64,
+    ) -> Result<(), ExecutionError> {
+        self.last_slippage_check = None;
+        let Some(guard) = self.slippage_guard else {
+            return Ok(());
+        };
+        if request.order_type != OrderType::Market {
+            return Ok(());
+        }
+        let last_price = exchange
+            .load_last_price(&request.instrument, request.market)
+            .unwrap_or(reference_price);
+        let tick_size = exchange
+            .load_symbol_rules(&request.instrument, request.market)
+            .map(|rules| rules.tick_size)
+            .unwrap_or(0.0);
+        let check = SlippageCheck {
+            side: request.side,
+            reference_price,
+            last_price,
+            limit_price: guard.limit_price(request.side, last_price, tick_size),
+            drift_bps: SlippageGuardConfig::adverse_drift_bps(
+                request.side,
+                reference_price,
+                last_price,
+            ),
+            passed: !guard.exceeds_band(request.side, reference_price, last_price),
+        };
+        self.last_slippage_check = Some(check);
+        if !check.passed {
+            return Err(ExecutionError::SlippageBandExceeded {
+                instrument: request.instrument.0.clone(),
+                reference_price,
+                last_price,
+                drift_bps: check.drift_bps,
+                max_slippage_bps: guard.max_slippage_bps,
+            });
+        }
+        request.order_type = OrderType::LimitIoc {
+            price: check.limit_price,
+        };
+        Ok(())
+    }
+
+    /// Holds a command blocked by the depth filter for a later retry.
+    ///
+    /// Returns false once `attempts` exceeds the configured retry budget.
+    /// A newer deferral for the same instrument replaces the older one.
+    pub fn defer_execution(&mut self, command: ExecutionCommand, attempts: u32) -> bool {
+        let Some(filter) = self.depth_filter else {
+            return false;
+        };
+        if attempts > filter.max_retries {
+            return false;
+        }
+        let instrument = command_instrument(&command);
+        self.deferred_executions
+            .retain(|deferred| command_instrument(&deferred.command) != instrument);
+        self.deferred_executions
+            .push(DeferredExecution::new(command, filter.retry_secs, attempts));
+        true
+    }
+
+    /// Holds a strategy entry until `until`; a newer signal on the same
+    /// instrument replaces the older one but keeps its queue time.
+    fn queue_entry(
+        &mut self,
+        instrument: Instrument,
+        command: ExecutionCommand,
+        until: DateTime<Utc>,
+    ) -> ExecutionError {
+        let now = Utc::now();
+        let queued_at = self
+            .queued_entries
+            .iter()
+            .find(|queued| command_instrument(&queued.command) == Some(&instrument))
+            .map_or(now, |queued| queued.queued_at);
+        self.queued_entries
+            .retain(|queued| command_instrument(&queued.command) != Some(&instrument));
+        self.queued_entries.push(QueuedEntry {
+            command,
+            queued_at,
+            release_at: until,
+        });
+        ExecutionError::EntryQueued {
+            instrument: instrument.0,
+            until: until.to_rfc3339(),
+        }
+    }
+
+    pub fn is_entry_queued(&self, instrument: &Instrument) -> bool {
+        self.queued_entries
+            .iter()
+            .any(|queued| command_instrument(&queued.command) == Some(instrument))
+    }
+
+    /// Removes and returns every queued entry once `now` is outside all
+    /// illiquid windows; the caller re-validates each before submitting.
+    pub fn take_released_entries(&mut self, now: DateTime<Utc>) -> Vec<QueuedEntry> {
+        if illiquid_until(&self.illiquid_windows, now).is_some() {
+            return Vec::new();
+        }
+        std::mem::take(&mut self.queued_entries)
+    }
+
+    /// Removes and returns the deferred commands whose retry window passed.
+    pub fn take_due_deferred(&mut self, now: DateTime<Utc>) -> Vec<DeferredExecution> {
+        let (due, pending) = std::mem::take(&mut self.deferred_executions)
+            .into_iter()
+            .partition(|deferred| deferred.retry_at <= now);
+        self.deferred_executions = pending;
+        due
+    }
+
+    /// Rejects a target whose consolidated base-asset exposure would exceed the cap.
+    ///
+    /// Example:
+    /// - cap `BTC=0.5`, equity `1000`, spot BTC worth `300`
+    /// - futures `BTCUSDT` target of `0.4` projects `700 / 1000 = 0.7` and is rejected
+    ///
+    /// Targets that shrink the consolidated exposure are always allowed.
+    fn check_asset_exposure_cap(
+        &self,
+        store: &PortfolioStateStore,
+        price_source: &impl PriceSource,
+        instrument: &Instrument,
+        current_notional_usdt: f64,
+        target_notional_usdt: f64,
+        equity_usdt: f64,
+    ) -> Result<(), ExecutionError> {
+        let Some(asset) = base_asset(instrument) else {
+            return Ok(());
+        };
+        let Some(cap) = self.asset_exposure_caps.get(asset).copied() else {
+            return Ok(());
+        };
+        if equity_usdt <= f64::EPSILON {
+            return Ok(());
+        }
+
+        let current_asset_usdt = aggregate_base_asset_exposure(&store.snapshot, |instrument| {
+            price_source.current_price(instrument)
+        })
+        .get(asset)
+        .and_then(|exposure| exposure.net_exposure_usdt())
+        .unwrap_or(current_notional_usdt);
+        let projected_usdt = current_asset_usdt - current_notional_usdt + target_notional_usdt;
+        let projected_exposure = projected_usdt / equity_usdt;
+        if projected_exposure.abs() > cap && projected_usdt.abs() > current_asset_usdt.abs() {
+            return Err(ExecutionError::AssetExposureCapExceeded {
+                asset: asset.to_string(),
+                projected_exposure,
+                cap,
+            });
+        }
+        Ok(())
+    }
+
+    /// Refuses orders on a market the API key cannot trade, so they fail
+    /// here with the reason instead of as an exchange reject.
+    fn ensure_market_permitted(&self, market: Market) -> Result<(), ExecutionError> {
+        match self.api_permissions {
+            Some(permissions) if !permissions.can_trade(market) => {
+                Err(ExecutionError::MarketNotPermitted {
+                    market: format!("{market:?}"),
+                    permissions: permissions.label(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn market_permitted(&self, market: Market) -> bool {
+        self.api_permissions
+            .is_none_or(|permissions| permissions.can_trade(market))
+    }
+
+    /// Picks the market to trade `instrument` on.
+    ///
+    /// A `market` hint (synthetic legs) pins the venue; an open position on
+    /// another market then counts as flat here, and a spot leg without a
+    /// tracked position is sized from the base asset balance.
     fn resolve_target_context<E: ExchangeFacade<Error = ExchangeError>>(
         &self,
         exchange: &E,
         store: &PortfolioStateStore,
         instrument: &Instrument,
+        market: Option<Market>,
     ) -> Result<(Instrument, Market, f64), ExecutionError> {
-        if let Some(position) = store.snapshot.positions.get(instrument) {
+        let position = store.snapshot.positions.get(instrument);
+        if let Some(market) = market {
+            if let Some(position) = position.filter(|position| position.market == market) {
+                return Ok((instrument.clone(), market, position.signed_qty));
+            }
+            exchange
+                .load_symbol_rules(instrument, market)
+                .map_err(|_| ExecutionError::UnknownInstrument(instrument.0.clone()))?;
+            let held = match (market, base_asset(instrument)) {
+                (Market::Spot, Some(asset)) => store
+                    .snapshot
+                    .balances
+                    .iter()
+                    .filter(|balance| balance.asset == asset)
+                    .map(|balance| balance.total())
+                    .sum(),
+                _ => 0.0,
+            };
+            return Ok((instrument.clone(), market, held));
+        }
+        if let Some(position) = position {
             return Ok((instrument.clone(), position.market, position.signed_qty));
         }
 
-        if exchange
-            .load_symbol_rules(instrument, Market::Futures)
-            .is_ok()
+        // Without futures permission a listed perpetual would only be
+        // rejected, so such symbols fall through to spot.
+        if self.market_permitted(Market::Futures)
+            && exchange
+                .load_symbol_rules(instrument, Market::Futures)
+                .is_ok()
         {
             return Ok((instrument.clone(), Market::Futures, 0.0));
         }
@@ -278,7 +2366,7 @@ impl ExecutionService {
     ) -> Result<(), ExecutionError> {
         let normalized_qty =
             self.normalize_direct_order_qty(exchange, instrument, Market::Options, qty)?;
-        exchange.submit_order(CloseOrderRequest {
+        let request = CloseOrderRequest {
             instrument: instrument.clone(),
             market: Market::Options,
             side,
@@ -286,7 +2374,17 @@ impl ExecutionService {
             qty_text: normalized_qty.qty_text,
             order_type,
             reduce_only: false,
-        })?;
+            client_order_id: None,
+        };
+        self.ensure_market_permitted(request.market)?;
+        let submitted = exchange.submit_order(request.clone());
+        self.record_history(
+            &request,
+            None,
+            None,
+            submitted.as_ref().map(|ack| ack.exchange_time_ms),
+        );
+        submitted?;
         Ok(())
     }
 
@@ -301,6 +2399,16 @@ impl ExecutionService {
         exchange: &E,
         store: &PortfolioStateStore,
         instrument: &Instrument,
+    ) -> Result<CloseSymbolResult, ExecutionError> {
+        self.submit_close(exchange, store, instrument, OrderType::Market)
+    }
+
+    fn submit_close<E: ExchangeFacade<Error = ExchangeError>>(
+        &mut self,
+        exchange: &E,
+        store: &PortfolioStateStore,
+        instrument: &Instrument,
+        order_type: OrderType,
     ) -> Result<CloseSymbolResult, ExecutionError> {
         let plan = match self.plan_close(store, instrument) {
             Ok(plan) => plan,
@@ -330,20 +2438,27 @@ impl ExecutionService {
             &plan.instrument,
             market,
             plan.qty,
-            0.0,
-            0.0,
-            0.0,
-            0.0,
+            SizingContext::default(),
         )?;
-        exchange.submit_close_order(CloseOrderRequest {
+        let request = CloseOrderRequest {
             instrument: plan.instrument.clone(),
             market,
             side: plan.side,
             qty: qty.qty,
             qty_text: qty.qty_text,
-            order_type: OrderType::Market,
+            order_type,
             reduce_only: plan.reduce_only,
-        })?;
+            client_order_id: None,
+        };
+        self.ensure_market_permitted(request.market)?;
+        let submitted = exchange.submit_close_order(request.clone());
+        self.record_history(
+            &request,
+            None,
+            None,
+            submitted.as_ref().map(|ack| ack.exchange_time_ms),
+        );
+        submitted?;
 
         Ok(CloseSymbolResult {
             instrument: instrument.clone(),
@@ -377,24 +2492,12 @@ impl ExecutionService {
         instrument: &Instrument,
         market: Market,
         raw_qty: f64,
-        target_exposure: f64,
-        equity_usdt: f64,
-        current_price: f64,
-        target_notional_usdt: f64,
+        sizing: SizingContext,
     ) -> Result<NormalizedOrderQty, ExecutionError> {
         let rules = exchange.load_symbol_rules(instrument, market)?;
         let normalized_qty = floor_to_step(raw_qty, rules.step_size);
-        let validated_qty = validate_normalized_qty(
-            instrument,
-            market,
-            raw_qty,
-            normalized_qty,
-            rules,
-            target_exposure,
-            equity_usdt,
-            current_price,
-            target_notional_usdt,
-        )?;
+        let validated_qty =
+            validate_normalized_qty(instrument, market, raw_qty, normalized_qty, rules, sizing)?;
 
         Ok(NormalizedOrderQty {
             qty: validated_qty,
@@ -417,10 +2520,7 @@ impl ExecutionService {
             raw_qty,
             normalized_qty,
             rules,
-            0.0,
-            0.0,
-            0.0,
-            0.0,
+            SizingContext::default(),
         )?;
 
         Ok(NormalizedOrderQty {
@@ -430,6 +2530,100 @@ impl ExecutionService {
     }
 }
 
+/// Reduce-only take-profit limit, or stop-market / stop-limit for the stop.
+fn oco_leg_request(
+    list: &OcoOrderList,
+    leg: OcoLeg,
+    normalized_qty: NormalizedOrderQty,
+) -> CloseOrderRequest {
+    CloseOrderRequest {
+        instrument: list.instrument.clone(),
+        market: list.market,
+        side: list.close_side,
+        qty: normalized_qty.qty,
+        qty_text: normalized_qty.qty_text,
+        order_type: match (leg, list.stop_limit_price) {
+            (OcoLeg::TakeProfit, _) => OrderType::Limit {
+                price: list.take_profit_price,
+            },
+            (OcoLeg::StopLoss, None) => OrderType::StopMarket {
+                stop_price: list.stop_price,
+            },
+            (OcoLeg::StopLoss, Some(price)) => OrderType::StopLimit {
+                stop_price: list.stop_price,
+                price,
+            },
+        },
+        reduce_only: true,
+        client_order_id: Some(list.leg_order_id(leg).to_string()),
+    }
+}
+
+pub fn command_source(command: &ExecutionCommand) -> &CommandSource {
+    match command {
+        ExecutionCommand::SetTargetExposure { source, .. }
+        | ExecutionCommand::SubmitOptionOrder { source, .. }
+        | ExecutionCommand::CloseSymbol { source, .. }
+        | ExecutionCommand::CloseAll { source }
+        | ExecutionCommand::PlaceTrailingStop { source, .. }
+        | ExecutionCommand::PlaceTakeProfitLadder { source, .. }
+        | ExecutionCommand::PlaceLimitOrder { source, .. }
+        | ExecutionCommand::PlaceIceberg { source, .. }
+        | ExecutionCommand::CancelOrder { source, .. }
+        | ExecutionCommand::PlaceOco { source, .. } => source,
+    }
+}
+
+/// Commands that can add exposure.
+///
+/// Example:
+/// - `set-target-exposure BTCUSDT 0.5` -> true
+/// - `set-target-exposure BTCUSDT 0` -> false
+fn opens_exposure(command: &ExecutionCommand) -> bool {
+    match command {
+        ExecutionCommand::SetTargetExposure { target, .. } => target.value() != 0.0,
+        ExecutionCommand::SubmitOptionOrder { .. }
+        | ExecutionCommand::PlaceLimitOrder { .. }
+        | ExecutionCommand::PlaceIceberg { .. } => true,
+        ExecutionCommand::CloseSymbol { .. }
+        | ExecutionCommand::CloseAll { .. }
+        | ExecutionCommand::PlaceTrailingStop { .. }
+        | ExecutionCommand::PlaceTakeProfitLadder { .. }
+        | ExecutionCommand::CancelOrder { .. }
+        | ExecutionCommand::PlaceOco { .. } => false,
+    }
+}
+
+fn command_instrument(command: &ExecutionCommand) -> Option<&Instrument> {
+    match command {
+        ExecutionCommand::SetTargetExposure { instrument, .. }
+        | ExecutionCommand::SubmitOptionOrder { instrument, .. }
+        | ExecutionCommand::CloseSymbol { instrument, .. }
+        | ExecutionCommand::PlaceTrailingStop { instrument, .. }
+        | ExecutionCommand::PlaceTakeProfitLadder { instrument, .. }
+        | ExecutionCommand::PlaceLimitOrder { instrument, .. }
+        | ExecutionCommand::PlaceIceberg { instrument, .. }
+        | ExecutionCommand::CancelOrder { instrument, .. }
+        | ExecutionCommand::PlaceOco { instrument, .. } => Some(instrument),
+        ExecutionCommand::CloseAll { .. } => None,
+    }
+}
+
+/// Parses per-asset exposure caps.
+///
+/// Example:
+/// - `BTC=0.5,ETH=0.25` -> `{BTC: 0.5, ETH: 0.25}`
+pub fn parse_asset_exposure_caps(raw: &str) -> BTreeMap<String, f64> {
+    raw.split(',')
+        .filter_map(|entry| {
+            let (asset, cap) = entry.split_once('=')?;
+            let asset = asset.trim().to_ascii_uppercase();
+            let cap = cap.trim().parse::<f64>().ok()?;
+            (!asset.is_empty() && cap.is_finite() && cap >= 0.0).then_some((asset, cap))
+        })
+        .collect()
+}
+
 fn floor_to_step(raw_qty: f64, step_size: f64) -> f64 {
     if raw_qty <= f64::EPSILON || step_size <= f64::EPSILON {
         return 0.0;
@@ -456,25 +2650,32 @@ fn step_precision(step_size: f64) -> usize {
     precision
 }
 
+/// Sizing inputs echoed by `OrderQtyTooSmall`; all zero for orders placed
+/// by quantity rather than sized from a target.
+#[derive(Debug, Clone, Copy, Default)]
+struct SizingContext {
+    target_exposure: f64,
+    equity_usdt: f64,
+    current_price: f64,
+    target_notional_usdt: f64,
+}
+
 fn validate_normalized_qty(
     instrument: &Instrument,
     market: Market,
     raw_qty: f64,
     normalized_qty: f64,
     rules: SymbolRules,
-    target_exposure: f64,
-    equity_usdt: f64,
-    current_price: f64,
-    target_notional_usdt: f64,
+    sizing: SizingContext,
 ) -> Result<f64, ExecutionError> {
     if normalized_qty <= f64::EPSILON || normalized_qty < rules.min_qty {
         return Err(ExecutionError::OrderQtyTooSmall {
             instrument: instrument.0.clone(),
             market: format!("{market:?}"),
-            target_exposure,
-            equity_usdt,
-            current_price,
-            target_notional_usdt,
+            target_exposure: sizing.target_exposure,
+            equity_usdt: sizing.equity_usdt,
+            current_price: sizing.current_price,
+            target_notional_usdt: sizing.target_notional_usdt,
             raw_qty,
             normalized_qty,
             min_qty: rules.min_qty,
@@ -0,0 +1,110 @@
+use std::env;
+
+use crate::domain::position::Side;
+
+/// Pre-submit guard that turns market orders into IOC limits.
+///
+/// The limit sits `max_slippage_bps` through the last traded price, so a
+/// flash move can only fill up to the band and the rest is cancelled.
+///
+/// Example:
+/// - band `20` bps, last `50,000`
+/// - a market buy goes out as `LIMIT IOC @ 50,100`, a market sell as `@ 49,900`
+/// - sized at `50,000` but last already `50,150` -> buy rejected before submit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlippageGuardConfig {
+    pub max_slippage_bps: f64,
+}
+
+impl SlippageGuardConfig {
+    pub fn new(max_slippage_bps: f64) -> Self {
+        Self { max_slippage_bps }
+    }
+
+    /// Reads the guard from the environment; unset or non-positive means disabled.
+    ///
+    /// Reads:
+    /// - `SANDBOX_QUANT_MAX_SLIPPAGE_BPS` (e.g. `20`)
+    pub fn from_env() -> Option<Self> {
+        Self::from_vars(|key| env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        var("SANDBOX_QUANT_MAX_SLIPPAGE_BPS")
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .filter(|value| *value > 0.0)
+            .map(Self::new)
+    }
+
+    pub fn label(&self) -> String {
+        format!("max_slippage_bps={}", self.max_slippage_bps)
+    }
+
+    /// Adverse move from `reference_price` to `last_price`, in bps.
+    ///
+    /// Positive when a buy got dearer or a sell got cheaper.
+    pub fn adverse_drift_bps(side: Side, reference_price: f64, last_price: f64) -> f64 {
+        if reference_price <= f64::EPSILON {
+            return 0.0;
+        }
+        let drift = (last_price - reference_price) / reference_price * 10_000.0;
+        match side {
+            Side::Buy => drift,
+            Side::Sell => -drift,
+        }
+    }
+
+    pub fn exceeds_band(&self, side: Side, reference_price: f64, last_price: f64) -> bool {
+        Self::adverse_drift_bps(side, reference_price, last_price) > self.max_slippage_bps
+    }
+
+    /// Worst acceptable fill price, rounded inside the band to `tick_size`.
+    ///
+    /// Example:
+    /// - buy, last `50,000`, band `20` bps, tick `0.1` -> `50,100.0`
+    /// - sell, last `1.2345`, band `20` bps, tick `0.0001` -> `1.2321`
+    pub fn limit_price(&self, side: Side, last_price: f64, tick_size: f64) -> f64 {
+        let band = self.max_slippage_bps / 10_000.0;
+        let raw = match side {
+            Side::Buy => last_price * (1.0 + band),
+            Side::Sell => last_price * (1.0 - band),
+        };
+        if tick_size <= f64::EPSILON {
+            return raw;
+        }
+        // Nudge before rounding so an exact multiple is not pushed a tick inward.
+        let ticks = raw / tick_size;
+        let ticks = match side {
+            Side::Buy => (ticks + 1e-9).floor(),
+            Side::Sell => (ticks - 1e-9).ceil(),
+        };
+        let decimals = tick_decimals(tick_size);
+        format!("{:.*}", decimals, ticks * tick_size)
+            .parse()
+            .unwrap_or(raw)
+    }
+}
+
+pub(crate) fn tick_decimals(tick_size: f64) -> usize {
+    let mut decimals = 0;
+    let mut scaled = tick_size;
+    while decimals < 12 && (scaled - scaled.round()).abs() > 1e-9 {
+        scaled *= 10.0;
+        decimals += 1;
+    }
+    decimals
+}
+
+/// Guard result for the most recent protected submit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlippageCheck {
+    pub side: Side,
+    /// Price the order was sized at.
+    pub reference_price: f64,
+    /// Last traded price fetched right before submit.
+    pub last_price: f64,
+    pub limit_price: f64,
+    pub drift_bps: f64,
+    pub passed: bool,
+}
@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::position::Side;
+use crate::execution::close_symbol::CloseSubmitResult;
+
+pub const DEFAULT_TAKE_PROFIT_PROFILE: &str = "default";
+
+/// One rung of the ladder: close `fraction` of the entry size once price
+/// has moved `gain_pct` in favour of the position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TakeProfitLevel {
+    pub gain_pct: f64,
+    pub fraction: f64,
+}
+
+/// Named exit plan applied by `take-profit <instrument> [profile]`.
+///
+/// Example:
+/// - `default` -> 50% at +1%, 30% at +2%, the last 20% trails by 1%
+#[derive(Debug, Clone, PartialEq)]
+pub struct TakeProfitProfile {
+    pub levels: Vec<TakeProfitLevel>,
+    /// Callback rate of the trailing stop that manages the runner.
+    pub runner_callback_pct: f64,
+}
+
+impl Default for TakeProfitProfile {
+    fn default() -> Self {
+        Self {
+            levels: vec![
+                TakeProfitLevel {
+                    gain_pct: 1.0,
+                    fraction: 0.5,
+                },
+                TakeProfitLevel {
+                    gain_pct: 2.0,
+                    fraction: 0.3,
+                },
+            ],
+            runner_callback_pct: 1.0,
+        }
+    }
+}
+
+impl TakeProfitProfile {
+    /// Parses one profile body.
+    ///
+    /// Example:
+    /// - `1:0.5,2:0.3,trail:1` -> 50% at +1%, 30% at +2%, runner trails by 1%
+    ///
+    /// Levels must rise, fractions must leave a runner, and the callback
+    /// must fit the `0.1..=10` range accepted by `trailing-stop`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut levels = Vec::new();
+        let mut runner_callback_pct = None;
+        for step in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|step| !step.is_empty())
+        {
+            let (key, value) = step.split_once(':')?;
+            let value = value.trim().parse::<f64>().ok()?;
+            if key.trim().eq_ignore_ascii_case("trail") {
+                runner_callback_pct = Some(value);
+                continue;
+            }
+            levels.push(TakeProfitLevel {
+                gain_pct: key.trim().parse().ok()?,
+                fraction: value,
+            });
+        }
+        let profile = Self {
+            levels,
+            runner_callback_pct: runner_callback_pct?,
+        };
+        profile.is_valid().then_some(profile)
+    }
+
+    fn is_valid(&self) -> bool {
+        let rising = self
+            .levels
+            .windows(2)
+            .all(|pair| pair[0].gain_pct < pair[1].gain_pct);
+        let total = self.levels.iter().map(|level| level.fraction).sum::<f64>();
+        !self.levels.is_empty()
+            && rising
+            && self
+                .levels
+                .iter()
+                .all(|level| level.gain_pct > 0.0 && level.fraction > 0.0)
+            && total < 1.0
+            && (0.1..=10.0).contains(&self.runner_callback_pct)
+    }
+
+    pub fn runner_fraction(&self) -> f64 {
+        1.0 - self.levels.iter().map(|level| level.fraction).sum::<f64>()
+    }
+
+    pub fn label(&self) -> String {
+        let mut steps = self
+            .levels
+            .iter()
+            .map(|level| format!("{}:{}", level.gain_pct, level.fraction))
+            .collect::<Vec<_>>();
+        steps.push(format!("trail:{}", self.runner_callback_pct));
+        steps.join(",")
+    }
+}
+
+/// Reads the named profiles.
+///
+/// Reads:
+/// - `SANDBOX_QUANT_TAKE_PROFIT_PROFILES` (e.g. `scalp=0.5:0.5,1:0.3,trail:0.5;swing=3:0.5,6:0.3,trail:2`)
+///
+/// Malformed entries are skipped. Without a `default=` entry the service
+/// falls back to `TakeProfitProfile::default()`.
+pub fn take_profit_profiles_from_vars(
+    var: impl Fn(&str) -> Option<String>,
+) -> BTreeMap<String, TakeProfitProfile> {
+    parse_take_profit_profiles(&var("SANDBOX_QUANT_TAKE_PROFIT_PROFILES").unwrap_or_default())
+}
+
+pub fn parse_take_profit_profiles(raw: &str) -> BTreeMap<String, TakeProfitProfile> {
+    let mut profiles = BTreeMap::new();
+    for entry in raw.split(';') {
+        let Some((name, body)) = entry.split_once('=') else {
+            continue;
+        };
+        let name = name.trim().to_ascii_lowercase();
+        if name.is_empty() {
+            continue;
+        }
+        if let Some(profile) = TakeProfitProfile::parse(body) {
+            profiles.insert(name, profile);
+        }
+    }
+    profiles
+}
+
+/// Exit plan attached to one open position.
+///
+/// Level quantities are fractions of `initial_qty`, the size when the
+/// ladder was placed, so partial fills do not shrink later rungs. Entry
+/// fills that arrive before the first rung still raise `initial_qty`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TakeProfitLadder {
+    pub instrument: Instrument,
+    pub market: Market,
+    /// Side of the closing orders; `Sell` takes profit on a long.
+    pub close_side: Side,
+    pub profile_name: String,
+    pub profile: TakeProfitProfile,
+    pub entry_price: f64,
+    pub initial_qty: f64,
+    /// Number of levels already sent to the exchange.
+    pub filled_levels: usize,
+}
+
+impl TakeProfitLadder {
+    /// Example:
+    /// - long entry `100`, level `+2%` -> `102`
+    /// - short entry `100`, level `+2%` -> `98`
+    pub fn level_price(&self, level: &TakeProfitLevel) -> f64 {
+        match self.close_side {
+            Side::Sell => self.entry_price * (1.0 + level.gain_pct / 100.0),
+            Side::Buy => self.entry_price * (1.0 - level.gain_pct / 100.0),
+        }
+    }
+
+    /// Returns the next unfilled level once `price` has reached it.
+    pub fn next_triggered(&self, price: f64) -> Option<(usize, TakeProfitLevel)> {
+        let level = *self.profile.levels.get(self.filled_levels)?;
+        let reached = match self.close_side {
+            Side::Sell => price >= self.level_price(&level),
+            Side::Buy => price <= self.level_price(&level),
+        };
+        reached.then_some((self.filled_levels, level))
+    }
+
+    pub fn levels_done(&self) -> bool {
+        self.filled_levels >= self.profile.levels.len()
+    }
+
+    /// Compact progress for the position panel.
+    ///
+    /// Example:
+    /// - `tp1@101.00=filled tp2@102.00=open runner=waiting`
+    pub fn progress(&self, runner_armed: bool) -> String {
+        let mut parts = self
+            .profile
+            .levels
+            .iter()
+            .enumerate()
+            .map(|(index, level)| {
+                format!(
+                    "tp{}@{:.2}={}",
+                    index + 1,
+                    self.level_price(level),
+                    if index < self.filled_levels {
+                        "filled"
+                    } else {
+                        "open"
+                    }
+                )
+            })
+            .collect::<Vec<_>>();
+        parts.push(format!(
+            "runner={}",
+            if runner_armed { "trailing" } else { "waiting" }
+        ));
+        parts.join(" ")
+    }
+}
+
+/// What the ladder engine did for one instrument during a refresh.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TakeProfitEvent {
+    LevelFilled {
+        instrument: Instrument,
+        level: usize,
+        trigger_price: f64,
+        qty: f64,
+        result: CloseSubmitResult,
+        progress: String,
+    },
+    RunnerArmed {
+        instrument: Instrument,
+        callback_rate_pct: f64,
+        on_exchange: bool,
+        progress: String,
+    },
+    RunnerFailed {
+        instrument: Instrument,
+        error: String,
+        /// `false` when the failure is fatal and the ladder was dropped.
+        retrying: bool,
+    },
+}
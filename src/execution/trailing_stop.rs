@@ -0,0 +1,143 @@
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::position::Side;
+
+/// Where trailing stops live once placed.
+///
+/// Example:
+/// - `Exchange` -> futures stops become Binance `TRAILING_STOP_MARKET` orders
+/// - `Internal` -> every stop is tracked in-process against refreshed prices
+///
+/// Spot always uses the internal engine because Binance has no native
+/// trailing stop market order there.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingStopMode {
+    #[default]
+    Exchange,
+    Internal,
+}
+
+impl TrailingStopMode {
+    /// Reads `SANDBOX_QUANT_TRAILING_STOP_MODE` (`exchange` or `internal`).
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        match var("SANDBOX_QUANT_TRAILING_STOP_MODE")
+            .map(|value| value.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("internal") => Self::Internal,
+            _ => Self::Exchange,
+        }
+    }
+
+    pub fn uses_exchange(self, market: Market) -> bool {
+        self == Self::Exchange && market == Market::Futures
+    }
+}
+
+/// Callback rates Binance accepts on a native trailing stop, in percent.
+pub const CALLBACK_RATE_RANGE_PCT: (f64, f64) = (0.1, 10.0);
+
+/// How far a trailing stop trails the best price since it was placed.
+///
+/// An ATR multiple is turned into a callback rate once, from the symbol's
+/// daily ATR% at placement, and kept within `CALLBACK_RATE_RANGE_PCT`.
+///
+/// Example:
+/// - `1.5` or `1.5%` -> `Percent(1.5)`
+/// - `2atr` with a `1.2%` daily ATR -> a `2.4%` callback
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrailDistance {
+    Percent(f64),
+    AtrMultiple(f64),
+}
+
+impl TrailDistance {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let raw = raw.trim().to_ascii_lowercase();
+        if let Some(multiple) = raw.strip_suffix("atr") {
+            let multiple = multiple
+                .trim_end_matches('x')
+                .parse::<f64>()
+                .ok()
+                .filter(|multiple| multiple.is_finite() && *multiple > 0.0 && *multiple <= 10.0)
+                .ok_or_else(|| format!("invalid atr multiple: {raw}. expected e.g. 2atr"))?;
+            return Ok(Self::AtrMultiple(multiple));
+        }
+        let pct = raw
+            .trim_end_matches('%')
+            .parse::<f64>()
+            .map_err(|_| format!("invalid callback rate: {raw}"))?;
+        let (min, max) = CALLBACK_RATE_RANGE_PCT;
+        if !(min..=max).contains(&pct) {
+            return Err(format!(
+                "callback rate out of range: {pct}. expected 0.1..=10"
+            ));
+        }
+        Ok(Self::Percent(pct))
+    }
+
+    /// Example:
+    /// - `1.5%`, `2atr`
+    pub fn label(&self) -> String {
+        match self {
+            Self::Percent(pct) => format!("{pct}%"),
+            Self::AtrMultiple(multiple) => format!("{multiple}atr"),
+        }
+    }
+
+    /// Callback rate in percent; `None` for an ATR multiple without a daily
+    /// ATR to size it from.
+    pub fn callback_rate_pct(&self, atr_pct: Option<f64>) -> Option<f64> {
+        match *self {
+            Self::Percent(pct) => Some(pct),
+            Self::AtrMultiple(multiple) => {
+                let (min, max) = CALLBACK_RATE_RANGE_PCT;
+                atr_pct
+                    .filter(|atr_pct| atr_pct.is_finite() && *atr_pct > 0.0)
+                    .map(|atr_pct| (multiple * atr_pct).clamp(min, max))
+            }
+        }
+    }
+}
+
+/// A trailing stop tracked by the process instead of the exchange.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InternalTrailingStop {
+    pub instrument: Instrument,
+    pub market: Market,
+    /// Side of the closing order; `Sell` protects a long.
+    pub close_side: Side,
+    pub callback_rate_pct: f64,
+    pub extreme_price: f64,
+}
+
+impl InternalTrailingStop {
+    pub fn stop_price(&self) -> f64 {
+        match self.close_side {
+            Side::Sell => self.extreme_price * (1.0 - self.callback_rate_pct / 100.0),
+            Side::Buy => self.extreme_price * (1.0 + self.callback_rate_pct / 100.0),
+        }
+    }
+
+    /// Moves the extreme with the market and returns true once price
+    /// retraces by the callback rate.
+    ///
+    /// Example:
+    /// - long stop, callback `1%`, peak `200`
+    /// - price `198.5` keeps it armed, `197.9` triggers
+    pub fn update(&mut self, price: f64) -> bool {
+        match self.close_side {
+            Side::Sell => self.extreme_price = self.extreme_price.max(price),
+            Side::Buy => self.extreme_price = self.extreme_price.min(price),
+        }
+        match self.close_side {
+            Side::Sell => price <= self.stop_price(),
+            Side::Buy => price >= self.stop_price(),
+        }
+    }
+}
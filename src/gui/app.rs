@@ -1,19 +1,41 @@
 use eframe::egui::{self, vec2, Color32, ComboBox, Grid, RichText, SidePanel, TopBottomPanel, Ui};
 
 use crate::app::bootstrap::BinanceMode;
+use crate::app::resource_profile::ResourceProfile;
 use crate::backtest_app::runner::{BacktestConfig, BacktestReport};
 use crate::charting::adapters::sandbox::{
-    equity_scene_from_report, market_scene_from_snapshot_with_overlay,
-    market_scene_from_snapshot_with_timeframe, MarketSeriesKind, MarketTimeframe,
+    add_market_context_panes, add_price_alert_lines, apply_chart_retention, apply_ui_theme,
+    equity_scene_from_report, market_scene_from_snapshot_with_candle_mode,
+    market_scene_from_snapshot_with_overlay, replay_scene, strategy_focus_scene,
+    MarketContextPanes, MarketSeriesKind, MarketTimeframe,
 };
-use crate::charting::egui::RetainedChartTexture;
+use crate::charting::egui::{color32, theme_visuals, RetainedChartTexture};
 use crate::charting::inspect::{hover_model_at, pan_scene, visible_time_bounds, zoom_scene};
 use crate::charting::plotters::PlottersRenderer;
-use crate::charting::render::ChartRenderer;
 use crate::charting::scene::{ChartScene, RenderRequest, TooltipModel, Viewport};
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::exchange::binance::depth_stream::{
+    spawn_depth_stream, BinanceDepthStreamApi, DepthStreamEvent, DepthStreamHandle,
+};
+use crate::exchange::binance::network::NetworkConfig;
+use crate::exchange::types::OrderBookDepth;
+use crate::execution::dom_ladder::{build_dom_ladder, DEFAULT_DOM_LEVELS};
+use crate::market_data::candle_mode::{CandleMode, RenkoBrick};
+use crate::market_data::price_alert::{PriceAlertStore, DEFAULT_PRICE_ALERTS_PATH};
+use crate::market_data::volatility::{VolatilityEstimator, VolatilityModel};
 use crate::strategy::model::StrategyTemplate;
+use crate::ui::theme::UiTheme;
+use crate::ui::timezone::DisplayTimeZone;
+use crate::visualization::annotations::ChartRetention;
+use crate::visualization::replay::TradeReplay;
 use crate::visualization::service::VisualizationService;
-use crate::visualization::types::{BacktestRunRequest, DashboardQuery, DashboardSnapshot};
+use crate::visualization::types::{
+    BacktestRunRequest, DashboardQuery, DashboardSnapshot, StrategyFocusSeries,
+};
+
+/// Trade, replay and chart-period timestamps, in the display zone.
+const TRADE_TIME_FORMAT: &str = "%m-%d %H:%M:%S";
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct GuiLaunchConfig {
@@ -31,6 +53,7 @@ enum GuiTab {
     Market,
     Pnl,
     Trades,
+    Focus,
 }
 
 impl GuiTab {
@@ -40,11 +63,18 @@ impl GuiTab {
             Self::Market => "Market",
             Self::Pnl => "PnL",
             Self::Trades => "Trades",
+            Self::Focus => "Strategy Focus",
         }
     }
 
-    fn all() -> [Self; 4] {
-        [Self::Overview, Self::Market, Self::Pnl, Self::Trades]
+    fn all() -> [Self; 5] {
+        [
+            Self::Overview,
+            Self::Market,
+            Self::Pnl,
+            Self::Trades,
+            Self::Focus,
+        ]
     }
 }
 
@@ -58,8 +88,14 @@ pub struct SandboxQuantGuiApp {
     to_input: String,
     run_limit: usize,
     market_timeframe: MarketTimeframe,
+    context_panes: MarketContextPanes,
+    /// How the market chart draws its bars; `H` switches it.
+    candle_mode: CandleMode,
+    /// Brick the `H` hotkey's Renko step uses.
+    renko_brick: RenkoBrick,
     selected_tab: GuiTab,
     snapshot: Option<DashboardSnapshot>,
+    volatility: VolatilityCache,
     status_message: String,
     market_chart: RetainedChartTexture,
     equity_chart: RetainedChartTexture,
@@ -67,6 +103,44 @@ pub struct SandboxQuantGuiApp {
     equity_viewport: Viewport,
     custom_charts: Vec<CustomChartPanel>,
     next_chart_id: u32,
+    price_alerts: PriceAlertStore,
+    trade_replay: Option<TradeReplay>,
+    replay_chart: RetainedChartTexture,
+    strategy_focus: Option<StrategyFocusSeries>,
+    focus_chart: RetainedChartTexture,
+    focus_viewport: Viewport,
+    theme: UiTheme,
+    time_zone: DisplayTimeZone,
+    chart_retention: ChartRetention,
+    /// Candle cap, repaint pace and which optional panes are offered.
+    resource_profile: ResourceProfile,
+    /// Live futures book of the selected symbol; `None` while the DOM
+    /// panel is off. `D` switches it.
+    depth_stream: Option<DepthStreamHandle>,
+    dom_depth: Option<OrderBookDepth>,
+    dom_status: String,
+}
+
+/// Annualized sigma of the loaded candles, refit only when a new candle
+/// closes rather than on every frame.
+#[derive(Default)]
+struct VolatilityCache {
+    key: Option<(usize, i64)>,
+    sigma: Option<f64>,
+}
+
+impl VolatilityCache {
+    fn sigma(&mut self, snapshot: &DashboardSnapshot) -> Option<f64> {
+        let klines = &snapshot.market_series.klines;
+        let key = klines
+            .last()
+            .map(|candle| (klines.len(), candle.close_time_ms));
+        if key != self.key {
+            self.key = key;
+            self.sigma = annualized_volatility(snapshot);
+        }
+        self.sigma
+    }
 }
 
 struct CustomChartPanel {
@@ -83,6 +157,8 @@ struct CustomChartPanel {
 impl SandboxQuantGuiApp {
     pub fn new(launch: GuiLaunchConfig) -> Self {
         let launch_symbol = launch.symbol.clone();
+        let resource_profile = ResourceProfile::from_env();
+        let candle_mode = CandleMode::chart_from_env();
         let mut app = Self {
             service: VisualizationService,
             mode: launch.mode,
@@ -93,8 +169,15 @@ impl SandboxQuantGuiApp {
             to_input: launch.to.to_string(),
             run_limit: 24,
             market_timeframe: launch.market_timeframe,
+            context_panes: MarketContextPanes::default(),
+            candle_mode,
+            renko_brick: match candle_mode {
+                CandleMode::Renko(brick) => brick,
+                _ => RenkoBrick::default(),
+            },
             selected_tab: GuiTab::Overview,
             snapshot: None,
+            volatility: VolatilityCache::default(),
             status_message: "Ready".to_string(),
             market_chart: RetainedChartTexture::default(),
             equity_chart: RetainedChartTexture::default(),
@@ -111,16 +194,80 @@ impl SandboxQuantGuiApp {
                 texture: RetainedChartTexture::default(),
             }],
             next_chart_id: 2,
+            price_alerts: {
+                let path = std::env::var("SANDBOX_QUANT_PRICE_ALERTS_PATH")
+                    .unwrap_or_else(|_| DEFAULT_PRICE_ALERTS_PATH.to_string());
+                PriceAlertStore::load(&path).unwrap_or_else(|_| PriceAlertStore::unloaded(&path))
+            },
+            trade_replay: None,
+            replay_chart: RetainedChartTexture::default(),
+            strategy_focus: None,
+            focus_chart: RetainedChartTexture::default(),
+            focus_viewport: Viewport::default(),
+            theme: UiTheme::from_env(),
+            time_zone: DisplayTimeZone::from_env(),
+            chart_retention: resource_profile.chart_retention(ChartRetention::from_env()),
+            resource_profile,
+            depth_stream: None,
+            dom_depth: None,
+            dom_status: String::new(),
         };
         app.refresh_dashboard(None);
         app
     }
 
+    /// Applies `SANDBOX_QUANT_UI_THEME` to the egui widgets; charts follow it
+    /// on their own.
+    pub fn install_theme(&self, ctx: &egui::Context) {
+        ctx.set_visuals(theme_visuals(self.theme));
+    }
+
     fn reset_viewports(&mut self) {
         self.market_chart.clear();
         self.equity_chart.clear();
         self.market_viewport = Viewport::default();
         self.equity_viewport = Viewport::default();
+        self.replay_chart.clear();
+        self.trade_replay = None;
+        self.focus_chart.clear();
+        self.focus_viewport = Viewport::default();
+        self.strategy_focus = None;
+    }
+
+    /// Starts replaying `trade_id` from the selected report against the loaded ticks.
+    fn open_trade_replay(&mut self, trade_id: usize) {
+        let Some(snapshot) = &self.snapshot else {
+            return;
+        };
+        let Some(report) = &snapshot.selected_report else {
+            return;
+        };
+        let Some(trade) = report
+            .trades
+            .iter()
+            .find(|trade| trade.trade_id == trade_id)
+        else {
+            self.status_message = format!("trade #{trade_id} not found in the selected run");
+            return;
+        };
+        let empty_series;
+        let series = if report.instrument == snapshot.symbol {
+            &snapshot.market_series
+        } else {
+            empty_series = crate::visualization::types::MarketSeries {
+                symbol: report.instrument.clone(),
+                liquidations: Vec::new(),
+                book_tickers: Vec::new(),
+                klines: Vec::new(),
+                kline_interval: None,
+                open_interest: Vec::new(),
+            };
+            &empty_series
+        };
+        let replay = TradeReplay::build(trade, &report.config, series);
+        self.status_message = format!("Replaying trade #{trade_id} | {} steps", replay.steps.len());
+        self.trade_replay = Some(replay);
+        self.replay_chart.clear();
     }
 
     fn apply_today_preset(&mut self) {
@@ -241,7 +388,10 @@ impl SandboxQuantGuiApp {
                         status_message.push_str(&format!(" | source={source_interval}"));
                     }
                     self.status_message = status_message;
-                    self.snapshot = Some(snapshot);
+                    self.snapshot = Some(retain_newest_candles(
+                        snapshot,
+                        self.resource_profile.limits().chart_candles,
+                    ));
                     self.reset_viewports();
                 }
                 Err(error) => {
@@ -299,7 +449,10 @@ impl SandboxQuantGuiApp {
                         .map(|report| report.ending_equity)
                         .unwrap_or_default(),
                 );
-                self.snapshot = Some(snapshot);
+                self.snapshot = Some(retain_newest_candles(
+                    snapshot,
+                    self.resource_profile.limits().chart_candles,
+                ));
                 self.reset_viewports();
                 self.selected_tab = GuiTab::Pnl;
             }
@@ -366,7 +519,7 @@ impl SandboxQuantGuiApp {
                     self.market_timeframe.label(),
                     source_interval
                 ))
-                .color(Color32::from_rgb(255, 210, 120))
+                .color(color32(self.theme.palette().warning))
                 .strong(),
             );
         }
@@ -386,8 +539,38 @@ impl SandboxQuantGuiApp {
                     .color(Color32::from_rgb(120, 180, 255))
                     .strong(),
             );
+            ui.separator();
+            if ui
+                .button(format!("{} [H]", self.candle_mode.title()))
+                .on_hover_text("Candles, Heikin-Ashi or Renko")
+                .clicked()
+            {
+                self.candle_mode = self.candle_mode.next(self.renko_brick);
+            }
+            if self.resource_profile.full_panels() {
+                ui.separator();
+                ui.checkbox(&mut self.context_panes.open_interest, "Open interest [O]");
+                ui.checkbox(&mut self.context_panes.volume_profile, "Volume profile [V]");
+                let mut dom_open = self.depth_stream.is_some();
+                if ui
+                    .checkbox(&mut dom_open, "Depth of market [D]")
+                    .on_hover_text("Live futures book of the selected symbol")
+                    .changed()
+                {
+                    self.toggle_dom();
+                }
+            }
         });
-        self.show_market_chart(ui, snapshot, 520.0);
+        if self.depth_stream.is_some() {
+            self.show_dom(ui);
+        }
+        let context_height = 160.0
+            * (u8::from(self.context_panes.open_interest)
+                + u8::from(self.context_panes.volume_profile)) as f32;
+        self.show_market_chart(ui, snapshot, 520.0 + context_height);
+        if !self.resource_profile.full_panels() {
+            return;
+        }
         ui.separator();
         ui.horizontal(|ui| {
             ui.heading("Custom Charts");
@@ -473,11 +656,11 @@ impl SandboxQuantGuiApp {
                 });
                 match panel_snapshot {
                     Some(panel_snapshot) => {
-                        show_custom_market_chart(ui, &panel_snapshot, panel, 300.0);
+                        show_custom_market_chart(ui, &panel_snapshot, panel, 300.0, self.theme);
                     }
                     None => {
                         ui.colored_label(
-                            Color32::from_rgb(255, 120, 120),
+                            color32(self.theme.palette().negative),
                             "Unable to load symbol snapshot for this chart panel.",
                         );
                     }
@@ -493,6 +676,95 @@ impl SandboxQuantGuiApp {
         }
     }
 
+    /// Starts the depth stream for the selected symbol, or stops it.
+    fn toggle_dom(&mut self) {
+        if self.depth_stream.take().is_some() {
+            self.dom_depth = None;
+            return;
+        }
+        let api = match self.mode {
+            BinanceMode::Real => BinanceDepthStreamApi::real(),
+            BinanceMode::Demo => BinanceDepthStreamApi::demo(),
+        };
+        let network = NetworkConfig::parse(
+            std::env::var("SANDBOX_QUANT_PROXY").ok().as_deref(),
+            std::env::var("SANDBOX_QUANT_CA_BUNDLE").ok().as_deref(),
+        )
+        .unwrap_or_default();
+        let symbol = self.symbol_input.trim().to_ascii_uppercase();
+        self.depth_stream = spawn_depth_stream(
+            api.with_network(network),
+            &Instrument::new(symbol.as_str()),
+            Market::Futures,
+            DEFAULT_DOM_LEVELS,
+        );
+        self.dom_status = format!("{symbol} connecting");
+    }
+
+    fn apply_depth_events(&mut self, events: Vec<DepthStreamEvent>) {
+        let symbol = self.symbol_input.trim().to_ascii_uppercase();
+        for event in events {
+            match event {
+                DepthStreamEvent::Connected => self.dom_status = format!("{symbol} live"),
+                DepthStreamEvent::Disconnected { error } => {
+                    self.dom_status = format!("{symbol} reconnecting: {error}");
+                }
+                DepthStreamEvent::Depth { depth, .. } => self.dom_depth = Some(depth),
+            }
+        }
+    }
+
+    /// Top-of-book ladder, asks above bids, with the spread between them.
+    fn show_dom(&self, ui: &mut Ui) {
+        let palette = self.theme.palette();
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading("Depth of Market");
+                ui.label(RichText::new(self.dom_status.as_str()).color(color32(palette.muted)));
+            });
+            let Some(depth) = &self.dom_depth else {
+                ui.label("Waiting for the first book update.");
+                return;
+            };
+            if let (Some(spread), Some(mid)) = (depth.spread(), depth.mid_price()) {
+                ui.monospace(format!(
+                    "spread {spread} ({:.2} bps)  mid {mid}",
+                    spread / mid * 10_000.0
+                ));
+            }
+            Grid::new("dom_ladder")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Bid");
+                    ui.strong("Price");
+                    ui.strong("Ask");
+                    ui.end_row();
+                    for level in build_dom_ladder(depth, &[], DEFAULT_DOM_LEVELS) {
+                        let qty = |qty: f64| {
+                            if qty > 0.0 {
+                                format!("{qty:.4}")
+                            } else {
+                                String::new()
+                            }
+                        };
+                        ui.label(
+                            RichText::new(qty(level.bid_qty))
+                                .monospace()
+                                .color(color32(palette.positive)),
+                        );
+                        ui.monospace(level.price.to_string());
+                        ui.label(
+                            RichText::new(qty(level.ask_qty))
+                                .monospace()
+                                .color(color32(palette.negative)),
+                        );
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
     fn render_pnl(&mut self, ui: &mut Ui, snapshot: &DashboardSnapshot) {
         let Some(report) = &snapshot.selected_report else {
             ui.label("No backtest report selected.");
@@ -520,7 +792,276 @@ impl SandboxQuantGuiApp {
             ui.label("Skipped");
             ui.strong(report.skipped_triggers.to_string());
             ui.end_row();
+            ui.label("Throttled");
+            ui.strong(report.throttled_signals.to_string());
+            ui.end_row();
+        });
+    }
+
+    /// Charts the focused strategy on its own bars: the selected run's
+    /// template when it traded this symbol, otherwise the template picker.
+    fn render_focus(&mut self, ui: &mut Ui, snapshot: &DashboardSnapshot) {
+        let report = snapshot
+            .selected_report
+            .as_ref()
+            .filter(|report| report.instrument == snapshot.symbol);
+        let template = report.map_or(self.template, |report| report.template);
+        let stale = self.strategy_focus.as_ref().is_none_or(|focus| {
+            focus.template != template
+                || focus.symbol != snapshot.symbol
+                || focus.from != snapshot.from
+                || focus.to != snapshot.to
+        });
+        if stale {
+            let query = DashboardQuery {
+                mode: snapshot.mode,
+                base_dir: snapshot.base_dir.clone(),
+                symbol: snapshot.symbol.clone(),
+                from: snapshot.from,
+                to: snapshot.to,
+                selected_run_id: None,
+                run_limit: self.run_limit,
+            };
+            match self.service.load_strategy_focus(&query, template) {
+                Ok(focus) => self.strategy_focus = Some(focus),
+                Err(error) => {
+                    self.status_message = format!("Strategy focus load failed: {error}");
+                    self.strategy_focus = None;
+                }
+            }
+            self.focus_chart.clear();
+            self.focus_viewport = Viewport::default();
+        }
+        let Some(focus) = self.strategy_focus.clone() else {
+            ui.label("Strategy bars could not be loaded.");
+            return;
+        };
+        ui.heading(format!("{} | {}", template.slug(), focus.symbol));
+        ui.label(format!(
+            "timeframe={} | bars={} | overlays={}",
+            focus.interval.as_deref().unwrap_or("n/a"),
+            focus.klines.len(),
+            match template.sma_windows() {
+                Some((fast, slow)) => format!("SMA {fast}/{slow}"),
+                None => "liquidations".to_string(),
+            }
+        ));
+        if focus.klines.is_empty() {
+            ui.group(|ui| {
+                ui.label(
+                    RichText::new("No bars stored for this strategy's source.")
+                        .color(color32(self.theme.palette().warning))
+                        .strong(),
+                );
+                ui.label(if template.sma_windows().is_some() {
+                    "Price-cross templates read raw klines; import them with the collector."
+                } else {
+                    "The liquidation template reads 1s bars derived from recorded trades."
+                });
+            });
+            return;
+        }
+        let size = vec2(ui.available_width().max(320.0), 520.0);
+        let request = render_request(ui, size);
+        let renderer = PlottersRenderer;
+        let mut scene = strategy_focus_scene(&focus, report);
+        let trades = report.map_or(&[][..], |report| report.trades.as_slice());
+        apply_chart_retention(&mut scene, trades, &self.chart_retention);
+        apply_ui_theme(&mut scene, self.theme);
+        if self.focus_viewport.x_range.is_some() {
+            scene.viewport = self.focus_viewport.clone();
+        }
+        render_chart_period_label(ui, &scene, focus.interval.as_deref().unwrap_or("n/a"));
+        match self
+            .focus_chart
+            .render_limited(ui.ctx(), "focus-chart", &renderer, &scene, &request)
+        {
+            Ok(()) => {
+                if let Some(response) = self.focus_chart.show(ui, size) {
+                    apply_hover(
+                        ui,
+                        &renderer,
+                        &mut scene,
+                        &request,
+                        ChartInteraction {
+                            response,
+                            texture: &mut self.focus_chart,
+                            viewport: &mut self.focus_viewport,
+                        },
+                    );
+                }
+            }
+            Err(error) => {
+                ui.colored_label(color32(self.theme.palette().negative), error.to_string());
+            }
+        }
+    }
+
+    fn render_trades(&mut self, ui: &mut Ui, snapshot: &DashboardSnapshot) {
+        let Some(report) = &snapshot.selected_report else {
+            ui.label("No backtest report selected.");
+            return;
+        };
+        if self.trade_replay.is_some() {
+            self.show_trade_replay(ui, 320.0);
+            ui.separator();
+        }
+        let mut replay_trade_id = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            Grid::new("trades_table")
+                .striped(true)
+                .num_columns(9)
+                .show(ui, |ui| {
+                    ui.strong("Trade");
+                    ui.strong("Trigger");
+                    ui.strong("Entry");
+                    ui.strong("Exit");
+                    ui.strong("Reason");
+                    ui.strong("Qty");
+                    ui.strong("Net PnL");
+                    ui.strong("Price");
+                    ui.strong("");
+                    ui.end_row();
+
+                    for trade in &report.trades {
+                        ui.label(trade.trade_id.to_string());
+                        ui.label(self.time_zone.format(trade.trigger_time, TRADE_TIME_FORMAT));
+                        ui.label(self.time_zone.format(trade.entry_time, TRADE_TIME_FORMAT));
+                        ui.label(
+                            trade
+                                .exit_time
+                                .map(|value| self.time_zone.format(value, TRADE_TIME_FORMAT))
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        ui.label(
+                            trade
+                                .exit_reason
+                                .as_ref()
+                                .map(|value| value.as_str())
+                                .unwrap_or("-"),
+                        );
+                        ui.label(format!("{:.4}", trade.qty));
+                        ui.label(
+                            trade
+                                .net_pnl
+                                .map(|value| format!("{value:.2}"))
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        ui.label(format!(
+                            "{:.2} -> {}",
+                            trade.entry_price,
+                            trade
+                                .exit_price
+                                .map(|value| format!("{value:.2}"))
+                                .unwrap_or_else(|| "-".to_string())
+                        ));
+                        if ui.small_button("Replay").clicked() {
+                            replay_trade_id = Some(trade.trade_id);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+        if let Some(trade_id) = replay_trade_id {
+            self.open_trade_replay(trade_id);
+        }
+    }
+
+    fn show_trade_replay(&mut self, ui: &mut Ui, height: f32) {
+        let Some(replay) = self.trade_replay.as_mut() else {
+            return;
+        };
+        let mut close = false;
+        ui.horizontal(|ui| {
+            ui.heading(format!("Replay trade #{}", replay.trade.trade_id));
+            if ui.button("⏮").on_hover_text("First step").clicked() {
+                replay.seek(0);
+            }
+            if ui.button("◀").on_hover_text("Previous step").clicked() {
+                replay.step_back();
+            }
+            if ui.button("▶").on_hover_text("Next step").clicked() {
+                replay.step_forward();
+            }
+            if ui.button("⏭").on_hover_text("Next decision").clicked() {
+                replay.seek_next_decision();
+            }
+            let last = replay.steps.len() - 1;
+            let mut cursor = replay.cursor;
+            if ui
+                .add(egui::Slider::new(&mut cursor, 0..=last).show_value(false))
+                .changed()
+            {
+                replay.seek(cursor);
+            }
+            if ui.button("Close").clicked() {
+                close = true;
+            }
+        });
+        if close {
+            self.trade_replay = None;
+            self.replay_chart.clear();
+            return;
+        }
+
+        let step = replay.current().clone();
+        Grid::new("replay_step").num_columns(4).show(ui, |ui| {
+            ui.label("Step");
+            ui.strong(format!("{}/{}", replay.cursor + 1, replay.steps.len()));
+            ui.label("Time");
+            ui.strong(self.time_zone.format_ms(step.time_ms, TRADE_TIME_FORMAT));
+            ui.end_row();
+            ui.label("Decision");
+            ui.strong(step.decision.as_str());
+            ui.label("Price");
+            ui.strong(format!("{:.2}", step.price));
+            ui.end_row();
+            ui.label("R");
+            ui.strong(format!("{:.2}", step.r_multiple));
+            ui.label("EV");
+            ui.strong(format!("{:.2}R", step.expected_value_r));
+            ui.end_row();
+            ui.label("Exit");
+            ui.strong(
+                replay
+                    .trade
+                    .exit_reason
+                    .as_ref()
+                    .map(|value| value.as_str())
+                    .unwrap_or("-"),
+            );
+            ui.label("Net PnL");
+            ui.strong(
+                replay
+                    .trade
+                    .net_pnl
+                    .map(|value| format!("{value:.2}"))
+                    .unwrap_or_else(|| "-".to_string()),
+            );
+            ui.end_row();
+            ui.label("Model");
+            ui.strong(&replay.model_version);
+            ui.end_row();
         });
+
+        let size = vec2(ui.available_width().max(320.0), height);
+        let request = render_request(ui, size);
+        let mut scene = replay_scene(replay);
+        apply_ui_theme(&mut scene, self.theme);
+        match self.replay_chart.render_limited(
+            ui.ctx(),
+            "replay-chart",
+            &PlottersRenderer,
+            &scene,
+            &request,
+        ) {
+            Ok(()) => {
+                self.replay_chart.show(ui, size);
+            }
+            Err(error) => {
+                ui.colored_label(color32(self.theme.palette().negative), error.to_string());
+            }
+        }
     }
 
     fn show_market_chart(&mut self, ui: &mut Ui, snapshot: &DashboardSnapshot, height: f32) {
@@ -531,7 +1072,7 @@ impl SandboxQuantGuiApp {
             ui.group(|ui| {
                 ui.label(
                     RichText::new("No market data for the current filters.")
-                        .color(Color32::from_rgb(255, 210, 120))
+                        .color(color32(self.theme.palette().warning))
                         .strong(),
                 );
                 ui.label("Try a different symbol/date range or import/load more data.");
@@ -541,15 +1082,33 @@ impl SandboxQuantGuiApp {
         let size = vec2(ui.available_width().max(320.0), height);
         let request = render_request(ui, size);
         let renderer = PlottersRenderer;
-        let mut scene = market_scene_from_snapshot_with_timeframe(snapshot, self.market_timeframe);
+        let mut scene = market_scene_from_snapshot_with_candle_mode(
+            snapshot,
+            self.market_timeframe,
+            self.candle_mode,
+        );
+        add_price_alert_lines(&mut scene, &self.price_alerts.levels_for(&snapshot.symbol));
+        let trades = snapshot
+            .selected_report
+            .as_ref()
+            .filter(|report| report.instrument == snapshot.symbol)
+            .map_or(&[][..], |report| report.trades.as_slice());
+        apply_chart_retention(&mut scene, trades, &self.chart_retention);
+        add_market_context_panes(&mut scene, snapshot, self.context_panes);
+        apply_ui_theme(&mut scene, self.theme);
         if self.market_viewport.x_range.is_some() {
             scene.viewport = self.market_viewport.clone();
         }
         let interval_label = market_period_unit_label(snapshot, self.market_timeframe);
         render_chart_period_label(ui, &scene, &interval_label);
-        match renderer.render(&scene, &request) {
-            Ok(frame) => {
-                self.market_chart.update(ui.ctx(), "market-chart", &frame);
+        match self.market_chart.render_limited(
+            ui.ctx(),
+            "market-chart",
+            &renderer,
+            &scene,
+            &request,
+        ) {
+            Ok(()) => {
                 if let Some(response) = self.market_chart.show(ui, size) {
                     apply_hover(
                         ui,
@@ -565,7 +1124,7 @@ impl SandboxQuantGuiApp {
                 }
             }
             Err(error) => {
-                ui.colored_label(Color32::from_rgb(255, 120, 120), error.to_string());
+                ui.colored_label(color32(self.theme.palette().negative), error.to_string());
             }
         }
     }
@@ -575,7 +1134,7 @@ impl SandboxQuantGuiApp {
             ui.group(|ui| {
                 ui.label(
                     RichText::new("No realized trades to chart yet.")
-                        .color(Color32::from_rgb(255, 210, 120))
+                        .color(color32(self.theme.palette().warning))
                         .strong(),
                 );
                 ui.label("Run a strategy with matching data or choose a different backtest run.");
@@ -586,13 +1145,19 @@ impl SandboxQuantGuiApp {
         let request = render_request(ui, size);
         let renderer = PlottersRenderer;
         let mut scene = equity_scene_from_report(report);
+        apply_ui_theme(&mut scene, self.theme);
         if self.equity_viewport.x_range.is_some() {
             scene.viewport = self.equity_viewport.clone();
         }
         render_chart_period_label(ui, &scene, "realized equity");
-        match renderer.render(&scene, &request) {
-            Ok(frame) => {
-                self.equity_chart.update(ui.ctx(), "equity-chart", &frame);
+        match self.equity_chart.render_limited(
+            ui.ctx(),
+            "equity-chart",
+            &renderer,
+            &scene,
+            &request,
+        ) {
+            Ok(()) => {
                 if let Some(response) = self.equity_chart.show(ui, size) {
                     apply_hover(
                         ui,
@@ -608,7 +1173,7 @@ impl SandboxQuantGuiApp {
                 }
             }
             Err(error) => {
-                ui.colored_label(Color32::from_rgb(255, 120, 120), error.to_string());
+                ui.colored_label(color32(self.theme.palette().negative), error.to_string());
             }
         }
     }
@@ -619,6 +1184,7 @@ fn show_custom_market_chart(
     snapshot: &DashboardSnapshot,
     panel: &mut CustomChartPanel,
     height: f32,
+    theme: UiTheme,
 ) {
     let size = vec2(ui.available_width().max(320.0), height);
     let request = render_request(ui, size);
@@ -629,18 +1195,20 @@ fn show_custom_market_chart(
         panel.primary,
         panel.secondary,
     );
+    apply_ui_theme(&mut scene, theme);
     if panel.viewport.x_range.is_some() {
         scene.viewport = panel.viewport.clone();
     }
     let interval_label = market_period_unit_label(snapshot, panel.timeframe);
     render_chart_period_label(ui, &scene, &interval_label);
-    match renderer.render(&scene, &request) {
-        Ok(frame) => {
-            panel.texture.update(
-                ui.ctx(),
-                format!("custom-market-chart-{}", panel.id).as_str(),
-                &frame,
-            );
+    match panel.texture.render_limited(
+        ui.ctx(),
+        format!("custom-market-chart-{}", panel.id).as_str(),
+        &renderer,
+        &scene,
+        &request,
+    ) {
+        Ok(()) => {
             if let Some(response) = panel.texture.show(ui, size) {
                 apply_hover(
                     ui,
@@ -656,27 +1224,60 @@ fn show_custom_market_chart(
             }
         }
         Err(error) => {
-            ui.colored_label(Color32::from_rgb(255, 120, 120), error.to_string());
+            ui.colored_label(color32(theme.palette().negative), error.to_string());
         }
     }
 }
 
 impl eframe::App for SandboxQuantGuiApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if !ctx.wants_keyboard_input() {
+            ctx.input(|input| {
+                if input.key_pressed(egui::Key::H) {
+                    self.candle_mode = self.candle_mode.next(self.renko_brick);
+                }
+                if !self.resource_profile.full_panels() {
+                    return;
+                }
+                if input.key_pressed(egui::Key::O) {
+                    self.context_panes.open_interest = !self.context_panes.open_interest;
+                }
+                if input.key_pressed(egui::Key::V) {
+                    self.context_panes.volume_profile = !self.context_panes.volume_profile;
+                }
+                if input.key_pressed(egui::Key::D) {
+                    self.toggle_dom();
+                }
+            });
+        }
+        if let Some(stream) = &self.depth_stream {
+            let events = stream.drain();
+            self.apply_depth_events(events);
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
         TopBottomPanel::top("toolbar").show(ctx, |ui| {
             ui.horizontal_wrapped(|ui| {
                 ui.heading("Sandbox Quant GUI");
                 ui.label(
                     RichText::new("plotters-backed candlesticks, signals, and pnl")
-                        .color(Color32::from_rgb(120, 140, 160)),
+                        .color(color32(self.theme.palette().muted)),
                 );
                 ui.separator();
                 ui.label(
                     RichText::new(self.status_message.as_str())
-                        .color(Color32::from_rgb(180, 220, 255)),
+                        .color(color32(self.theme.palette().accent)),
                 );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.monospace(format!(
+                        "{} {}",
+                        self.time_zone
+                            .format(chrono::Utc::now(), "%Y-%m-%d %H:%M:%S"),
+                        self.time_zone.label()
+                    ));
+                });
             });
         });
+        ctx.request_repaint_after(self.resource_profile.gui_repaint());
 
         SidePanel::left("controls")
             .resizable(true)
@@ -713,7 +1314,7 @@ impl eframe::App for SandboxQuantGuiApp {
                 });
                 ui.small(
                     RichText::new("Date filters use UTC day boundaries.")
-                        .color(Color32::from_rgb(150, 170, 190)),
+                        .color(color32(self.theme.palette().muted)),
                 );
                 if let Some(snapshot) = &self.snapshot {
                     if let Some(source_interval) =
@@ -727,7 +1328,7 @@ impl eframe::App for SandboxQuantGuiApp {
                                     self.market_timeframe.label(),
                                     source_interval
                                 ))
-                                .color(Color32::from_rgb(255, 210, 120))
+                                .color(color32(self.theme.palette().warning))
                                 .strong(),
                             );
                             ui.horizontal_wrapped(|ui| {
@@ -826,7 +1427,8 @@ impl eframe::App for SandboxQuantGuiApp {
                 ui.separator();
 
                 if let Some(snapshot) = &self.snapshot {
-                    render_metric_cards(ui, snapshot);
+                    let sigma = self.volatility.sigma(snapshot);
+                    render_metric_cards(ui, snapshot, sigma);
                     ui.separator();
                     ui.collapsing("Recent Backtests", |ui| {
                         egui::ScrollArea::vertical()
@@ -893,7 +1495,10 @@ impl eframe::App for SandboxQuantGuiApp {
 
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.horizontal(|ui| {
-                for tab in GuiTab::all() {
+                for tab in GuiTab::all()
+                    .into_iter()
+                    .filter(|tab| *tab != GuiTab::Focus || self.resource_profile.full_panels())
+                {
                     ui.selectable_value(&mut self.selected_tab, tab, tab.title());
                 }
             });
@@ -903,7 +1508,7 @@ impl eframe::App for SandboxQuantGuiApp {
                 ui.group(|ui| {
                     ui.label(
                         RichText::new("No dashboard snapshot loaded.")
-                            .color(Color32::from_rgb(255, 210, 120))
+                            .color(color32(self.theme.palette().warning))
                             .strong(),
                     );
                     ui.label("Choose filters on the left, then click Load Chart or Run Backtest.");
@@ -915,13 +1520,14 @@ impl eframe::App for SandboxQuantGuiApp {
                 GuiTab::Overview => self.render_overview(ui, &snapshot),
                 GuiTab::Market => self.render_market(ui, &snapshot),
                 GuiTab::Pnl => self.render_pnl(ui, &snapshot),
-                GuiTab::Trades => render_trades(ui, &snapshot),
+                GuiTab::Trades => self.render_trades(ui, &snapshot),
+                GuiTab::Focus => self.render_focus(ui, &snapshot),
             }
         });
     }
 }
 
-fn render_metric_cards(ui: &mut Ui, snapshot: &DashboardSnapshot) {
+fn render_metric_cards(ui: &mut Ui, snapshot: &DashboardSnapshot, sigma: Option<f64>) {
     ui.heading("Recorder");
     Grid::new("metric_cards").num_columns(2).show(ui, |ui| {
         ui.label("DB");
@@ -939,65 +1545,37 @@ fn render_metric_cards(ui: &mut Ui, snapshot: &DashboardSnapshot) {
         ui.label("1s bars");
         ui.strong(snapshot.recorder_metrics.derived_kline_1s_bars.to_string());
         ui.end_row();
+        ui.label("σ (annualized)");
+        ui.strong(
+            sigma
+                .map(|sigma| format!("{:.1}%", sigma * 100.0))
+                .unwrap_or_else(|| "-".to_string()),
+        );
+        ui.end_row();
     });
 }
 
-fn render_trades(ui: &mut Ui, snapshot: &DashboardSnapshot) {
-    let Some(report) = &snapshot.selected_report else {
-        ui.label("No backtest report selected.");
-        return;
-    };
-    egui::ScrollArea::vertical().show(ui, |ui| {
-        Grid::new("trades_table")
-            .striped(true)
-            .num_columns(8)
-            .show(ui, |ui| {
-                ui.strong("Trade");
-                ui.strong("Trigger");
-                ui.strong("Entry");
-                ui.strong("Exit");
-                ui.strong("Reason");
-                ui.strong("Qty");
-                ui.strong("Net PnL");
-                ui.strong("Price");
-                ui.end_row();
-
-                for trade in &report.trades {
-                    ui.label(trade.trade_id.to_string());
-                    ui.label(trade.trigger_time.format("%m-%d %H:%M:%S").to_string());
-                    ui.label(trade.entry_time.format("%m-%d %H:%M:%S").to_string());
-                    ui.label(
-                        trade
-                            .exit_time
-                            .map(|value| value.format("%m-%d %H:%M:%S").to_string())
-                            .unwrap_or_else(|| "-".to_string()),
-                    );
-                    ui.label(
-                        trade
-                            .exit_reason
-                            .as_ref()
-                            .map(|value| value.as_str())
-                            .unwrap_or("-"),
-                    );
-                    ui.label(format!("{:.4}", trade.qty));
-                    ui.label(
-                        trade
-                            .net_pnl
-                            .map(|value| format!("{value:.2}"))
-                            .unwrap_or_else(|| "-".to_string()),
-                    );
-                    ui.label(format!(
-                        "{:.2} -> {}",
-                        trade.entry_price,
-                        trade
-                            .exit_price
-                            .map(|value| format!("{value:.2}"))
-                            .unwrap_or_else(|| "-".to_string())
-                    ));
-                    ui.end_row();
-                }
-            });
-    });
+/// Keeps the newest `limit` candles of the loaded range.
+///
+/// Example:
+/// - `limit=Some(2000)` on a 2-day 1s range -> the last 2000 seconds
+fn retain_newest_candles(
+    mut snapshot: DashboardSnapshot,
+    limit: Option<usize>,
+) -> DashboardSnapshot {
+    if let Some(limit) = limit {
+        let klines = &mut snapshot.market_series.klines;
+        klines.drain(..klines.len().saturating_sub(limit));
+    }
+    snapshot
+}
+
+fn annualized_volatility(snapshot: &DashboardSnapshot) -> Option<f64> {
+    let mut estimator = VolatilityEstimator::new(VolatilityModel::from_env());
+    for candle in &snapshot.market_series.klines {
+        estimator.on_candle_close(candle);
+    }
+    estimator.annualized_sigma()
 }
 
 fn parse_date(label: &str, value: &str) -> Result<chrono::NaiveDate, String> {
@@ -1088,8 +1666,11 @@ fn apply_hover(
     scene.hover = Some(hover);
     *viewport = scene.viewport.clone();
     if should_rerender {
-        if let Ok(frame) = renderer.render(scene, request) {
-            texture.update(ui.ctx(), response.id.value().to_string().as_str(), &frame);
+        let id = response.id.value().to_string();
+        if texture
+            .render_limited(ui.ctx(), &id, renderer, scene, request)
+            .is_ok()
+        {
             ui.ctx().request_repaint();
         }
     }
@@ -1139,8 +1720,8 @@ fn render_chart_period_label(ui: &mut Ui, scene: &ChartScene, interval_label: &s
     if let Some((from, to)) = visible_time_bounds(scene) {
         ui.small(format!(
             "Period: {} → {} ({}) | Unit: {}",
-            format_epoch_ms(from.as_i64()),
-            format_epoch_ms(to.as_i64()),
+            scene.time_zone.format_ms(from.as_i64(), TRADE_TIME_FORMAT),
+            scene.time_zone.format_ms(to.as_i64(), TRADE_TIME_FORMAT),
             human_duration(to.as_i64().saturating_sub(from.as_i64())),
             interval_label,
         ));
@@ -1148,12 +1729,6 @@ fn render_chart_period_label(ui: &mut Ui, scene: &ChartScene, interval_label: &s
     }
 }
 
-fn format_epoch_ms(value: i64) -> String {
-    chrono::DateTime::<chrono::Utc>::from_timestamp_millis(value)
-        .map(|time| time.format("%m-%d %H:%M:%S").to_string())
-        .unwrap_or_else(|| "-".to_string())
-}
-
 fn human_duration(span_ms: i64) -> String {
     let total_seconds = (span_ms / 1_000).max(0);
     let hours = total_seconds / 3_600;
@@ -1291,6 +1866,7 @@ mod tests {
                     trade_count: 1,
                 }],
                 kline_interval: Some("1d".to_string()),
+                open_interest: Vec::new(),
             },
             recent_runs: Vec::new(),
             selected_report: None,
@@ -1342,6 +1918,7 @@ mod tests {
                     trade_count: 1,
                 }],
                 kline_interval: Some("15m".to_string()),
+                open_interest: Vec::new(),
             },
             recent_runs: Vec::new(),
             selected_report: None,
@@ -1389,6 +1966,43 @@ mod tests {
         assert_eq!(app.to_input, today.to_string());
     }
 
+    #[test]
+    fn depth_events_replace_the_dom_book_and_track_the_connection() {
+        let today = chrono::Utc::now().date_naive();
+        let mut app = SandboxQuantGuiApp::new(GuiLaunchConfig {
+            mode: BinanceMode::Demo,
+            base_dir: "var".to_string(),
+            symbol: "btcusdt".to_string(),
+            from: today,
+            to: today,
+            market_timeframe: MarketTimeframe::Tick1s,
+        });
+        let book = |bid: f64| OrderBookDepth {
+            bids: vec![(bid, 1.0)],
+            asks: vec![(bid + 0.5, 2.0)],
+        };
+
+        app.apply_depth_events(vec![
+            DepthStreamEvent::Connected,
+            DepthStreamEvent::Depth {
+                depth: book(100.0),
+                event_time_ms: None,
+            },
+            DepthStreamEvent::Depth {
+                depth: book(101.0),
+                event_time_ms: Some(1),
+            },
+        ]);
+        assert_eq!(app.dom_status, "BTCUSDT live");
+        assert_eq!(app.dom_depth, Some(book(101.0)));
+
+        app.apply_depth_events(vec![DepthStreamEvent::Disconnected {
+            error: "stream closed".to_string(),
+        }]);
+        assert_eq!(app.dom_status, "BTCUSDT reconnecting: stream closed");
+        assert_eq!(app.dom_depth, Some(book(101.0)));
+    }
+
     #[test]
     fn reset_viewports_clears_chart_ranges() {
         let today = chrono::Utc::now().date_naive();
@@ -1495,6 +2109,71 @@ mod tests {
         std::fs::remove_dir_all(base_dir).ok();
     }
 
+    #[test]
+    fn retain_newest_candles_keeps_the_tail_of_the_range() {
+        let snapshot = DashboardSnapshot {
+            mode: BinanceMode::Demo,
+            base_dir: std::path::PathBuf::from("var"),
+            db_path: std::path::PathBuf::from("var/demo.duckdb"),
+            symbol: "BTCUSDT".to_string(),
+            from: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
+            to: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
+            available_symbols: vec!["BTCUSDT".to_string()],
+            recorder_metrics: crate::dataset::types::RecorderMetrics::default(),
+            dataset_summary: crate::dataset::types::BacktestDatasetSummary {
+                mode: BinanceMode::Demo,
+                symbol: "BTCUSDT".to_string(),
+                symbol_found: true,
+                from: "2026-03-13".to_string(),
+                to: "2026-03-13".to_string(),
+                liquidation_events: 0,
+                book_ticker_events: 0,
+                agg_trade_events: 0,
+                derived_kline_1s_bars: 0,
+            },
+            market_series: crate::visualization::types::MarketSeries {
+                symbol: "BTCUSDT".to_string(),
+                liquidations: Vec::new(),
+                book_tickers: Vec::new(),
+                klines: (0..5)
+                    .map(|index| crate::dataset::types::DerivedKlineRow {
+                        open_time_ms: index * 60_000,
+                        close_time_ms: index * 60_000 + 59_999,
+                        open: 1.0,
+                        high: 1.0,
+                        low: 1.0,
+                        close: 1.0,
+                        volume: 1.0,
+                        quote_volume: 1.0,
+                        trade_count: 1,
+                    })
+                    .collect(),
+                kline_interval: Some("1m".to_string()),
+                open_interest: Vec::new(),
+            },
+            recent_runs: Vec::new(),
+            selected_report: None,
+            selected_run_id: None,
+        };
+
+        let kept = retain_newest_candles(snapshot.clone(), Some(2));
+        assert_eq!(
+            kept.market_series
+                .klines
+                .iter()
+                .map(|kline| kline.open_time_ms)
+                .collect::<Vec<_>>(),
+            vec![180_000, 240_000]
+        );
+        assert_eq!(
+            retain_newest_candles(snapshot, None)
+                .market_series
+                .klines
+                .len(),
+            5
+        );
+    }
+
     #[test]
     fn duplicate_custom_chart_panel_copies_settings() {
         let today = chrono::NaiveDate::from_ymd_opt(2026, 3, 13).expect("date");
@@ -16,9 +16,10 @@ use reqwest::blocking::Client;
 use sandbox_quant::app::bootstrap::AppBootstrap;
 use sandbox_quant::app::bootstrap::BinanceMode;
 use sandbox_quant::app::commands::AppCommand;
+use sandbox_quant::app::config_reload::{install_sighup_reload_handler, take_reload_request};
 use sandbox_quant::app::cli::normalize_instrument_symbol;
 use sandbox_quant::app::cli::parse_app_command;
-use sandbox_quant::app::output::render_command_output;
+use sandbox_quant::app::output::{render_command_output, render_credential_rotation};
 use sandbox_quant::app::runtime::AppRuntime;
 use sandbox_quant::app::shell::run_shell;
 use sandbox_quant::dataset::query::metrics_for_path;
@@ -26,10 +27,13 @@ use sandbox_quant::domain::exposure::Exposure;
 use sandbox_quant::domain::instrument::Instrument;
 use sandbox_quant::domain::order_type::OrderType;
 use sandbox_quant::exchange::binance::client::BinanceExchange;
+use sandbox_quant::exchange::binance::user_stream::UserStreamHandle;
 use sandbox_quant::execution::command::{CommandSource, ExecutionCommand};
 use sandbox_quant::observability::logging::init_logging;
 use sandbox_quant::portfolio::store::PortfolioStateStore;
 use sandbox_quant::record::coordination::RecorderCoordination;
+use sandbox_quant::storage::sealed;
+use sandbox_quant::ui::locale::UiLocale;
 use sandbox_quant::ui::operator_terminal::prompt_status_from_store;
 use serde::Serialize;
 use serde_json::json;
@@ -41,6 +45,9 @@ type TradingBootstrap = AppBootstrap<BinanceExchange>;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenvy::dotenv().ok();
+    // The event file drops lines it cannot seal, so a store key that cannot
+    // be resolved stops the process here instead of losing the audit trail.
+    sealed::process_sealer()?;
 
     let args: Vec<String> = std::env::args().skip(1).collect();
     let mut app = AppBootstrap::from_env(PortfolioStateStore::default())?;
@@ -91,6 +98,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     &app.event_log,
                     &app.strategy_store,
                     app.mode,
+                    &app.execution.history,
                 )
             );
             Ok(())
@@ -114,6 +122,7 @@ struct TradingEngineDaemon {
     runtime: AppRuntime,
     base_dir: String,
     last_heartbeat_log: Instant,
+    user_stream: UserStreamHandle,
 }
 
 #[derive(Clone)]
@@ -167,6 +176,17 @@ fn serve_trading_engine(
 ) -> Result<(), Box<dyn std::error::Error>> {
     let config = parse_serve_args(args)?;
     configure_runtime(&mut app, config.mode, &config.base_dir)?;
+    if let Some(permissions) = app.probe_api_permissions() {
+        info!(
+            service = "trading-engine",
+            mode = app.mode.as_str(),
+            permissions = %permissions.label(),
+            "api key permissions probed"
+        );
+    }
+    app.apply_futures_leverage();
+    let supervisor_tick = app.resource_profile.idle_tick();
+    let user_stream = app.start_user_streams();
     let daemon = Arc::new(Mutex::new(TradingEngineDaemon {
         app,
         runtime: AppRuntime::default(),
@@ -174,8 +194,10 @@ fn serve_trading_engine(
         last_heartbeat_log: Instant::now()
             .checked_sub(Duration::from_secs(5))
             .unwrap_or_else(Instant::now),
+        user_stream,
     }));
     let shutdown = Arc::new(AtomicBool::new(false));
+    install_sighup_reload_handler();
     let state = TradingEngineServerState {
         daemon: daemon.clone(),
         shutdown: shutdown.clone(),
@@ -213,11 +235,41 @@ fn serve_trading_engine(
                 if supervisor_state.shutdown.load(Ordering::Relaxed) {
                     break;
                 }
-                tokio::time::sleep(Duration::from_secs(1)).await;
+                tokio::time::sleep(supervisor_tick).await;
                 let mut daemon = match supervisor_state.daemon.lock() {
                     Ok(daemon) => daemon,
                     Err(_) => continue,
                 };
+                daemon.app.event_log.compact();
+                if take_reload_request() {
+                    let daemon_ref = &mut *daemon;
+                    if let Err(error) = daemon_ref
+                        .runtime
+                        .run(&mut daemon_ref.app, AppCommand::ReloadConfig)
+                    {
+                        error!(service = "trading-engine", error = %error, "config reload failed");
+                    }
+                    if daemon_ref.app.reload_credentials() {
+                        info!(service = "trading-engine", "api keys rotated");
+                        daemon_ref.user_stream = daemon_ref.app.start_user_streams();
+                    }
+                } else if daemon.app.check_credentials(Instant::now()) {
+                    info!(service = "trading-engine", "api keys rotated");
+                    daemon.user_stream = daemon.app.start_user_streams();
+                }
+                let daemon_ref = &mut *daemon;
+                for event in daemon_ref.user_stream.drain() {
+                    daemon_ref
+                        .runtime
+                        .ingest_user_stream_event(&mut daemon_ref.app, event, Utc::now());
+                }
+                daemon_ref
+                    .runtime
+                    .check_symbol_status(&mut daemon_ref.app, Utc::now());
+                daemon_ref.runtime.check_price_alerts(&mut daemon_ref.app);
+                daemon_ref
+                    .runtime
+                    .check_inactivity(&mut daemon_ref.app, Utc::now());
                 if daemon.last_heartbeat_log.elapsed() >= Duration::from_secs(5) {
                     let db_path =
                         RecorderCoordination::new(daemon.base_dir.clone()).db_path(daemon.app.mode);
@@ -236,7 +288,7 @@ fn serve_trading_engine(
                         ping_at = %Utc::now().to_rfc3339(),
                         pong = "alive",
                         heartbeat_age_sec = 0,
-                        portfolio_status = %prompt_status_from_store(&daemon.app.portfolio_store),
+                        portfolio_status = %prompt_status_from_store(&daemon.app.portfolio_store, UiLocale::English),
                         positions = daemon.app.portfolio_store.snapshot.positions.len(),
                         open_order_groups = daemon.app.portfolio_store.snapshot.open_orders.len(),
                         last_event_kind = last_event_kind,
@@ -288,7 +340,7 @@ async fn trading_engine_health_handler(
         mode: daemon.app.mode.as_str().to_string(),
         state: "running".to_string(),
         heartbeat_age_sec: 0,
-        portfolio_status: prompt_status_from_store(&daemon.app.portfolio_store),
+        portfolio_status: prompt_status_from_store(&daemon.app.portfolio_store, UiLocale::English),
         positions: daemon
             .app
             .portfolio_store
@@ -412,6 +464,7 @@ async fn execute_trading_engine_command(
             &daemon_ref.app.event_log,
             &daemon_ref.app.strategy_store,
             daemon_ref.app.mode,
+            &daemon_ref.app.execution.history,
         );
         Ok(Json(TradingEngineCommandResponse {
             status: "ok".to_string(),
@@ -467,7 +520,7 @@ fn render_trading_engine_status(daemon: &TradingEngineDaemon) -> String {
         "heartbeat_age_sec=0".to_string(),
         format!(
             "portfolio_status={}",
-            prompt_status_from_store(&daemon.app.portfolio_store)
+            prompt_status_from_store(&daemon.app.portfolio_store, UiLocale::English)
         ),
         format!("positions={positions}"),
         format!("open_order_groups={open_order_groups}"),
@@ -500,6 +553,12 @@ fn run_interactive(
     runtime: &mut AppRuntime,
     base_dir: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let events_before = app.event_log.records.len();
+    app.probe_api_permissions();
+    app.apply_futures_leverage();
+    if let Some(permissions) = render_credential_rotation(&app.event_log, events_before) {
+        println!("{permissions}");
+    }
     let heartbeat_stop = Arc::new(AtomicBool::new(false));
     let heartbeat_handle = spawn_trading_engine_heartbeat(
         app.mode,
@@ -11,6 +11,11 @@ use serde_json::Value;
 use tracing::info;
 
 use crate::app::bootstrap::BinanceMode;
+use crate::dataset::types::DerivedKlineRow;
+use crate::domain::market::Market;
+use crate::exchange::binance::demo::{
+    BINANCE_DEMO_FUTURES_BASE_URL, BINANCE_DEMO_OPTIONS_BASE_URL, BINANCE_DEMO_SPOT_BASE_URL,
+};
 use crate::storage::postgres_market_data::{
     connect as connect_postgres, init_schema as init_postgres_schema, insert_kline,
     latest_shared_kline_open_time_ms, PostgresKlineRecord,
@@ -23,11 +28,61 @@ pub const DEFAULT_BINANCE_BACKFILL_START_DATE: &str = "2023-03-15";
 pub const DEFAULT_BINANCE_BACKFILL_POLL_SECONDS: u64 = 30;
 const DEFAULT_LIMIT: usize = 1_500;
 
+/// REST venue serving klines for one market.
+///
+/// Example:
+/// - real futures -> `https://fapi.binance.com/fapi/v1/klines`
+/// - real spot -> `https://api.binance.com/api/v3/klines`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KlineEndpoint {
+    pub market: Market,
+    pub base_url: String,
+}
+
+impl KlineEndpoint {
+    /// The venue's own host for `market` in `mode`.
+    pub fn for_market(mode: BinanceMode, market: Market) -> Self {
+        let base_url = match (mode, market) {
+            (BinanceMode::Real, Market::Spot) => "https://api.binance.com",
+            (BinanceMode::Real, Market::Futures) => "https://fapi.binance.com",
+            (BinanceMode::Real, Market::Options) => "https://eapi.binance.com",
+            (BinanceMode::Demo, Market::Spot) => BINANCE_DEMO_SPOT_BASE_URL,
+            (BinanceMode::Demo, Market::Futures) => BINANCE_DEMO_FUTURES_BASE_URL,
+            (BinanceMode::Demo, Market::Options) => BINANCE_DEMO_OPTIONS_BASE_URL,
+        };
+        Self {
+            market,
+            base_url: base_url.to_string(),
+        }
+    }
+
+    /// The same market served from a configured host, e.g. a proxy.
+    pub fn with_base_url(self, base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            ..self
+        }
+    }
+
+    pub fn path(&self) -> &'static str {
+        match self.market {
+            Market::Spot => "/api/v3/klines",
+            Market::Futures => "/fapi/v1/klines",
+            Market::Options => "/eapi/v1/klines",
+        }
+    }
+
+    fn url(&self, query: &str) -> String {
+        format!("{}{}?{query}", self.base_url, self.path())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct BinanceKlineBackfillConfig {
     pub postgres_url: String,
     pub symbols: Vec<String>,
     pub mode: BinanceMode,
+    pub endpoint: KlineEndpoint,
     pub product: String,
     pub interval: String,
     pub fallback_start_ms: i64,
@@ -48,6 +103,8 @@ pub fn run_binance_kline_backfill(
     info!(
         service = "postgres-kline-backfill",
         mode = config.mode.as_str(),
+        base_url = config.endpoint.base_url,
+        path = config.endpoint.path(),
         product = config.product,
         interval = config.interval,
         symbols = %config.symbols.join(","),
@@ -67,16 +124,7 @@ pub fn run_binance_kline_backfill(
         let now_minute = current_closed_minute_open_ms();
         let mut cycle_rows = 0u64;
         for symbol in &config.symbols {
-            let inserted = backfill_symbol(
-                &http,
-                &mut client,
-                config.mode,
-                &config.product,
-                &config.interval,
-                symbol,
-                config.fallback_start_ms,
-                now_minute,
-            )?;
+            let inserted = backfill_symbol(&http, &mut client, config, symbol, now_minute)?;
             cycle_rows += inserted;
             info!(
                 service = "postgres-kline-backfill",
@@ -141,16 +189,128 @@ pub fn current_closed_minute_open_ms() -> i64 {
     closed.timestamp_millis()
 }
 
-fn backfill_symbol(
+/// Fetches the exchange-official kline whose bucket opens at `open_time_ms`
+/// from `endpoint`.
+pub fn fetch_reference_kline(
     http: &Client,
-    client: &mut postgres::Client,
+    endpoint: &KlineEndpoint,
     mode: BinanceMode,
-    product: &str,
+    symbol: &str,
+    interval: &str,
+    open_time_ms: i64,
+) -> Result<Option<DerivedKlineRow>> {
+    let url = endpoint.url(&format!(
+        "symbol={symbol}&interval={interval}&startTime={open_time_ms}&limit=1"
+    ));
+    let rows = http
+        .get(&url)
+        .send()
+        .with_context(|| format!("failed to fetch reference kline for {symbol}"))?
+        .error_for_status()
+        .with_context(|| format!("reference kline HTTP status error for {symbol}"))?
+        .json::<Vec<Vec<Value>>>()
+        .with_context(|| format!("failed to decode reference kline for {symbol}"))?;
+    let Some(row) = rows.into_iter().next() else {
+        return Ok(None);
+    };
+    let record = parse_kline_row(
+        mode,
+        DEFAULT_BINANCE_BACKFILL_PRODUCT,
+        symbol,
+        interval,
+        row,
+    )?;
+    if record.open_time_ms != open_time_ms {
+        return Ok(None);
+    }
+    Ok(Some(derived_kline_row(&record)))
+}
+
+/// Fetches every kline opening in `start_ms..=end_ms` from `endpoint`,
+/// paging through the exchange's 1500-row limit.
+pub fn fetch_klines_between(
+    http: &Client,
+    endpoint: &KlineEndpoint,
+    symbol: &str,
+    interval: &str,
+    start_ms: i64,
+    end_ms: i64,
+) -> Result<Vec<DerivedKlineRow>> {
+    let mut klines = Vec::new();
+    let mut cursor_ms = start_ms;
+    while cursor_ms <= end_ms {
+        let url = endpoint.url(&format!(
+            "symbol={symbol}&interval={interval}&startTime={cursor_ms}&endTime={end_ms}&limit={DEFAULT_LIMIT}"
+        ));
+        let rows = http
+            .get(&url)
+            .send()
+            .with_context(|| format!("failed to fetch klines for {symbol}"))?
+            .error_for_status()
+            .with_context(|| format!("kline HTTP status error for {symbol}"))?
+            .json::<Vec<Vec<Value>>>()
+            .with_context(|| format!("failed to decode kline response for {symbol}"))?;
+        let page = parse_kline_rows(symbol, interval, rows)?;
+        let Some(last_open_time_ms) = page.last().map(|row| row.open_time_ms) else {
+            break;
+        };
+        klines.extend(page.into_iter().filter(|row| row.open_time_ms <= end_ms));
+        if last_open_time_ms < cursor_ms {
+            break;
+        }
+        cursor_ms = last_open_time_ms + 1;
+    }
+    Ok(klines)
+}
+
+/// Decodes rows shaped like a `/fapi/v1/klines` or `/api/v3/klines` response, with numbers
+/// either quoted or bare.
+pub fn parse_kline_rows(
+    symbol: &str,
     interval: &str,
+    rows: Vec<Vec<Value>>,
+) -> Result<Vec<DerivedKlineRow>> {
+    rows.into_iter()
+        .map(|row| {
+            parse_kline_row(
+                BinanceMode::Real,
+                DEFAULT_BINANCE_BACKFILL_PRODUCT,
+                symbol,
+                interval,
+                row,
+            )
+            .map(|record| derived_kline_row(&record))
+        })
+        .collect()
+}
+
+fn derived_kline_row(record: &PostgresKlineRecord) -> DerivedKlineRow {
+    DerivedKlineRow {
+        open_time_ms: record.open_time_ms,
+        close_time_ms: record.close_time_ms,
+        open: record.open,
+        high: record.high,
+        low: record.low,
+        close: record.close,
+        volume: record.volume,
+        quote_volume: record.quote_volume,
+        trade_count: record.trade_count.max(0) as u64,
+    }
+}
+
+fn backfill_symbol(
+    http: &Client,
+    client: &mut postgres::Client,
+    config: &BinanceKlineBackfillConfig,
     symbol: &str,
-    fallback_start_ms: i64,
     max_open_time_ms: i64,
 ) -> Result<u64> {
+    let (mode, product, interval, fallback_start_ms) = (
+        config.mode,
+        config.product.as_str(),
+        config.interval.as_str(),
+        config.fallback_start_ms,
+    );
     let mut start_ms = latest_shared_kline_open_time_ms(client, product, symbol, interval)?
         .unwrap_or(fallback_start_ms);
     if start_ms < fallback_start_ms {
@@ -167,9 +327,9 @@ fn backfill_symbol(
     let interval_ms = interval_millis(interval)?;
 
     while start_ms <= max_open_time_ms {
-        let url = format!(
-            "https://fapi.binance.com/fapi/v1/klines?symbol={symbol}&interval={interval}&startTime={start_ms}&limit={DEFAULT_LIMIT}"
-        );
+        let url = config.endpoint.url(&format!(
+            "symbol={symbol}&interval={interval}&startTime={start_ms}&limit={DEFAULT_LIMIT}"
+        ));
         let rows = http
             .get(&url)
             .send()
@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+
+use crate::dataset::types::DerivedKlineRow;
+
+pub const DEFAULT_CANDLE_CHECK_INTERVAL: &str = "1m";
+pub const DEFAULT_CANDLE_CHECK_TOLERANCE_BPS: f64 = 5.0;
+pub const DEFAULT_CANDLE_CHECK_EVERY_SECS: u64 = 60;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandleConsistencyConfig {
+    pub enabled: bool,
+    pub interval: String,
+    pub tolerance_bps: f64,
+    pub check_every_secs: u64,
+    /// Host serving the reference klines in place of the mode's own venue,
+    /// e.g. a proxy; the kline path still follows the market checked.
+    pub rest_base_url: Option<String>,
+}
+
+impl Default for CandleConsistencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: DEFAULT_CANDLE_CHECK_INTERVAL.to_string(),
+            tolerance_bps: DEFAULT_CANDLE_CHECK_TOLERANCE_BPS,
+            check_every_secs: DEFAULT_CANDLE_CHECK_EVERY_SECS,
+            rest_base_url: None,
+        }
+    }
+}
+
+impl CandleConsistencyConfig {
+    /// Reads the consistency check settings from the environment.
+    ///
+    /// Optional:
+    /// - `SANDBOX_QUANT_CANDLE_CHECK` (`1`/`true` enables the check)
+    /// - `SANDBOX_QUANT_CANDLE_CHECK_INTERVAL` (`1s`, `1m`, ... `1h`)
+    /// - `SANDBOX_QUANT_CANDLE_CHECK_TOLERANCE_BPS`
+    /// - `SANDBOX_QUANT_CANDLE_CHECK_EVERY_SECS`
+    /// - `SANDBOX_QUANT_CANDLE_CHECK_REST_URL`
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let enabled = std::env::var("SANDBOX_QUANT_CANDLE_CHECK")
+            .ok()
+            .is_some_and(|value| matches!(value.trim(), "1" | "true" | "yes" | "on"));
+        let interval = std::env::var("SANDBOX_QUANT_CANDLE_CHECK_INTERVAL")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| candle_interval_ms(value).is_some())
+            .unwrap_or(defaults.interval.clone());
+        let tolerance_bps = std::env::var("SANDBOX_QUANT_CANDLE_CHECK_TOLERANCE_BPS")
+            .ok()
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .filter(|value| value.is_finite() && *value >= 0.0)
+            .unwrap_or(defaults.tolerance_bps);
+        let check_every_secs = std::env::var("SANDBOX_QUANT_CANDLE_CHECK_EVERY_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(defaults.check_every_secs);
+        let rest_base_url = std::env::var("SANDBOX_QUANT_CANDLE_CHECK_REST_URL")
+            .ok()
+            .map(|value| value.trim().trim_end_matches('/').to_string())
+            .filter(|value| !value.is_empty())
+            .or(defaults.rest_base_url.clone());
+        Self {
+            enabled,
+            interval,
+            tolerance_bps,
+            check_every_secs,
+            rest_base_url,
+        }
+    }
+
+    pub fn interval_ms(&self) -> i64 {
+        candle_interval_ms(&self.interval).unwrap_or(60_000)
+    }
+}
+
+/// Bucket length of a kline interval the check supports.
+///
+/// Example:
+/// - `5m` -> `Some(300_000)`
+/// - `1w` -> `None`
+pub fn candle_interval_ms(interval: &str) -> Option<i64> {
+    match interval {
+        "1s" => Some(1_000),
+        "1m" => Some(60_000),
+        "3m" => Some(3 * 60_000),
+        "5m" => Some(5 * 60_000),
+        "15m" => Some(15 * 60_000),
+        "30m" => Some(30 * 60_000),
+        "1h" => Some(60 * 60_000),
+        _ => None,
+    }
+}
+
+/// Builds interval candles from individual trades so they can be checked
+/// against the exchange-official kline for the same bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalCandleAggregator {
+    interval_ms: i64,
+    current: BTreeMap<String, DerivedKlineRow>,
+    closed: BTreeMap<String, Vec<DerivedKlineRow>>,
+}
+
+impl LocalCandleAggregator {
+    pub fn new(interval_ms: i64) -> Self {
+        Self {
+            interval_ms: interval_ms.max(1),
+            current: BTreeMap::new(),
+            closed: BTreeMap::new(),
+        }
+    }
+
+    /// Folds one trade into the open bucket for its symbol.
+    ///
+    /// Example:
+    /// - bucket `12:00` holds BTCUSDT trades at `100`, `105`, `99`
+    /// - a trade at `12:01:02` closes that bucket as `O=100 H=105 L=99 C=99`
+    pub fn apply_trade(&mut self, symbol: &str, event_time_ms: i64, price: f64, qty: f64) {
+        if !(price.is_finite() && price > 0.0) {
+            return;
+        }
+        let open_time_ms = event_time_ms - event_time_ms.rem_euclid(self.interval_ms);
+        if let Some(candle) = self.current.get_mut(symbol) {
+            if candle.open_time_ms == open_time_ms {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+                candle.volume += qty;
+                candle.quote_volume += price * qty;
+                candle.trade_count += 1;
+                return;
+            }
+            if open_time_ms < candle.open_time_ms {
+                return;
+            }
+        }
+
+        let next = DerivedKlineRow {
+            open_time_ms,
+            close_time_ms: open_time_ms + self.interval_ms - 1,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: qty,
+            quote_volume: price * qty,
+            trade_count: 1,
+        };
+        if let Some(previous) = self.current.insert(symbol.to_string(), next) {
+            self.closed
+                .entry(symbol.to_string())
+                .or_default()
+                .push(previous);
+        }
+    }
+
+    pub fn current_candle(&self, symbol: &str) -> Option<&DerivedKlineRow> {
+        self.current.get(symbol)
+    }
+
+    /// Drains candles whose bucket has closed and that have not been checked yet.
+    pub fn take_closed(&mut self) -> Vec<(String, DerivedKlineRow)> {
+        std::mem::take(&mut self.closed)
+            .into_iter()
+            .flat_map(|(symbol, candles)| {
+                candles
+                    .into_iter()
+                    .map(move |candle| (symbol.clone(), candle))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandleConsistencyCheck {
+    pub symbol: String,
+    pub local: DerivedKlineRow,
+    pub reference: DerivedKlineRow,
+    pub tolerance_bps: f64,
+    pub max_drift_bps: f64,
+    pub drifted_fields: Vec<&'static str>,
+}
+
+impl CandleConsistencyCheck {
+    pub fn within_tolerance(&self) -> bool {
+        self.drifted_fields.is_empty()
+    }
+
+    /// The candle to store: the local one, or the exchange reference when the
+    /// drift was beyond tolerance.
+    pub fn corrected_candle(&self) -> DerivedKlineRow {
+        if self.within_tolerance() {
+            self.local.clone()
+        } else {
+            self.reference.clone()
+        }
+    }
+}
+
+/// Compares a locally aggregated candle with the REST kline for the same bucket.
+///
+/// Example:
+/// - local close `100.00`, REST close `100.10`, tolerance `5 bps`
+/// - drift is `~9.99 bps`, so `close` is reported as drifted
+pub fn compare_candles(
+    symbol: &str,
+    local: &DerivedKlineRow,
+    reference: &DerivedKlineRow,
+    tolerance_bps: f64,
+) -> CandleConsistencyCheck {
+    let fields = [
+        ("open", local.open, reference.open),
+        ("high", local.high, reference.high),
+        ("low", local.low, reference.low),
+        ("close", local.close, reference.close),
+    ];
+    let mut max_drift_bps = 0.0f64;
+    let mut drifted_fields = Vec::new();
+    for (name, local_value, reference_value) in fields {
+        let drift = drift_bps(local_value, reference_value);
+        max_drift_bps = max_drift_bps.max(drift);
+        if drift > tolerance_bps {
+            drifted_fields.push(name);
+        }
+    }
+
+    CandleConsistencyCheck {
+        symbol: symbol.to_string(),
+        local: local.clone(),
+        reference: reference.clone(),
+        tolerance_bps,
+        max_drift_bps,
+        drifted_fields,
+    }
+}
+
+fn drift_bps(local: f64, reference: f64) -> f64 {
+    if reference.abs() <= f64::EPSILON {
+        return if local.abs() <= f64::EPSILON {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+    }
+    ((local - reference) / reference).abs() * 10_000.0
+}
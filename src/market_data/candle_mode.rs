@@ -0,0 +1,241 @@
+use std::env;
+
+use crate::dataset::types::DerivedKlineRow;
+
+/// Brick used when Renko is picked without a size.
+pub const DEFAULT_RENKO_BRICK_PCT: f64 = 0.1;
+
+/// Renko brick height, either in price or as a percent of the first close.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenkoBrick {
+    Price(f64),
+    Percent(f64),
+}
+
+impl Default for RenkoBrick {
+    fn default() -> Self {
+        Self::Percent(DEFAULT_RENKO_BRICK_PCT)
+    }
+}
+
+impl RenkoBrick {
+    /// Example:
+    /// - `50` -> `Price(50.0)`; `0.2%` -> `Percent(0.2)`
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let (value, percent) = match raw.strip_suffix('%') {
+            Some(value) => (value, true),
+            None => (raw, false),
+        };
+        let value = value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite() && *value > 0.0)?;
+        Some(if percent {
+            Self::Percent(value)
+        } else {
+            Self::Price(value)
+        })
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Price(size) => format!("{size}"),
+            Self::Percent(pct) => format!("{pct}%"),
+        }
+    }
+
+    fn size(&self, anchor: f64) -> f64 {
+        match *self {
+            Self::Price(size) => size,
+            Self::Percent(pct) => anchor.abs() * pct / 100.0,
+        }
+    }
+}
+
+/// How raw candles are redrawn before they are charted or fed to a
+/// strategy.
+///
+/// Example:
+/// - `heikin-ashi` averages each bar with the one before it
+/// - `renko:50` draws a `50` USDT brick each time the close moves a brick
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CandleMode {
+    #[default]
+    Standard,
+    HeikinAshi,
+    Renko(RenkoBrick),
+}
+
+impl CandleMode {
+    /// Reads `SANDBOX_QUANT_CANDLE_MODE`, the market chart's starting mode.
+    pub fn chart_from_env() -> Self {
+        Self::from_env_var("SANDBOX_QUANT_CANDLE_MODE")
+    }
+
+    /// Reads `SANDBOX_QUANT_SIGNAL_CANDLES`, the series SMA-cross templates
+    /// compute their averages on.
+    pub fn signal_from_env() -> Self {
+        Self::from_env_var("SANDBOX_QUANT_SIGNAL_CANDLES")
+    }
+
+    fn from_env_var(name: &str) -> Self {
+        env::var(name)
+            .ok()
+            .and_then(|raw| Self::parse(&raw))
+            .unwrap_or_default()
+    }
+
+    /// Example:
+    /// - `candles`, `heikin-ashi`, `renko` (a `0.1%` brick), `renko:50`
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim().to_ascii_lowercase();
+        let (kind, brick) = match raw.split_once(':') {
+            Some((kind, brick)) => (kind, Some(brick)),
+            None => (raw.as_str(), None),
+        };
+        match (kind, brick) {
+            ("candles" | "standard", None) => Some(Self::Standard),
+            ("heikin-ashi" | "heikin_ashi" | "ha", None) => Some(Self::HeikinAshi),
+            ("renko", None) => Some(Self::Renko(RenkoBrick::default())),
+            ("renko", Some(brick)) => RenkoBrick::parse(brick).map(Self::Renko),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Standard => "candles".to_string(),
+            Self::HeikinAshi => "heikin-ashi".to_string(),
+            Self::Renko(brick) => format!("renko:{}", brick.label()),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Standard => "Candles".to_string(),
+            Self::HeikinAshi => "Heikin-Ashi".to_string(),
+            Self::Renko(brick) => format!("Renko {}", brick.label()),
+        }
+    }
+
+    /// The mode the chart hotkey switches to; Renko uses `brick`.
+    pub fn next(self, brick: RenkoBrick) -> Self {
+        match self {
+            Self::Standard => Self::HeikinAshi,
+            Self::HeikinAshi => Self::Renko(brick),
+            Self::Renko(_) => Self::Standard,
+        }
+    }
+
+    pub fn apply(&self, klines: &[DerivedKlineRow]) -> Vec<DerivedKlineRow> {
+        match self {
+            Self::Standard => klines.to_vec(),
+            Self::HeikinAshi => heikin_ashi(klines),
+            Self::Renko(brick) => renko(klines, *brick),
+        }
+    }
+
+    /// One close per raw candle, as the transformed series stood when that
+    /// candle closed, so signals stay aligned with the bars they trade on.
+    ///
+    /// Example:
+    /// - brick `1`, raw closes `100, 100.4, 101.2` -> Renko closes `100, 100, 101`
+    pub fn signal_closes(&self, klines: &[DerivedKlineRow]) -> Vec<f64> {
+        match self {
+            Self::Standard => klines.iter().map(|row| row.close).collect(),
+            Self::HeikinAshi => heikin_ashi(klines).iter().map(|row| row.close).collect(),
+            Self::Renko(brick) => renko_walk(klines, *brick).1,
+        }
+    }
+}
+
+/// Each bar averaged with its predecessor; times and volume are kept.
+///
+/// Example:
+/// - first bar `o=100 h=104 l=98 c=102` -> `o=101 c=101 h=104 l=98`
+pub fn heikin_ashi(klines: &[DerivedKlineRow]) -> Vec<DerivedKlineRow> {
+    let mut rows = Vec::with_capacity(klines.len());
+    let mut previous: Option<(f64, f64)> = None;
+    for row in klines {
+        let close = (row.open + row.high + row.low + row.close) / 4.0;
+        let open = match previous {
+            Some((open, close)) => (open + close) / 2.0,
+            None => (row.open + row.close) / 2.0,
+        };
+        previous = Some((open, close));
+        rows.push(DerivedKlineRow {
+            open,
+            high: row.high.max(open).max(close),
+            low: row.low.min(open).min(close),
+            close,
+            ..row.clone()
+        });
+    }
+    rows
+}
+
+/// Renko bricks on closes, reversing only after a two-brick move.
+///
+/// Bricks completed by one candle split its time span and the volume traded
+/// since the previous brick.
+///
+/// Example:
+/// - brick `1`, closes `100, 102.5, 101.8, 99.9`
+/// - up bricks `100-101`, `101-102`, then a down brick `101-100`
+pub fn renko(klines: &[DerivedKlineRow], brick: RenkoBrick) -> Vec<DerivedKlineRow> {
+    renko_walk(klines, brick).0
+}
+
+fn renko_walk(klines: &[DerivedKlineRow], brick: RenkoBrick) -> (Vec<DerivedKlineRow>, Vec<f64>) {
+    let Some(first) = klines.first() else {
+        return (Vec::new(), Vec::new());
+    };
+    let size = brick.size(first.close);
+    if size <= 0.0 || !size.is_finite() {
+        return (Vec::new(), vec![first.close; klines.len()]);
+    }
+    let (mut top, mut bottom, mut last_close) = (first.close, first.close, first.close);
+    let mut bricks = Vec::new();
+    let mut closes = Vec::with_capacity(klines.len());
+    let (mut volume, mut quote_volume, mut trade_count) = (0.0, 0.0, 0u64);
+    for row in klines {
+        volume += row.volume;
+        quote_volume += row.quote_volume;
+        trade_count += row.trade_count;
+        let mut moves = Vec::new();
+        while row.close >= top + size {
+            moves.push((top, top + size));
+            bottom = top;
+            top += size;
+        }
+        while row.close <= bottom - size {
+            moves.push((bottom, bottom - size));
+            top = bottom;
+            bottom -= size;
+        }
+        if !moves.is_empty() {
+            let count = moves.len() as i64;
+            let span = (row.close_time_ms - row.open_time_ms).max(count);
+            for (index, (open, close)) in moves.into_iter().enumerate() {
+                let index = index as i64;
+                bricks.push(DerivedKlineRow {
+                    open_time_ms: row.open_time_ms + span * index / count,
+                    close_time_ms: row.open_time_ms + span * (index + 1) / count,
+                    open,
+                    high: open.max(close),
+                    low: open.min(close),
+                    close,
+                    volume: volume / count as f64,
+                    quote_volume: quote_volume / count as f64,
+                    trade_count: trade_count / count as u64,
+                });
+                last_close = close;
+            }
+            (volume, quote_volume, trade_count) = (0.0, 0.0, 0);
+        }
+        closes.push(last_close);
+    }
+    (bricks, closes)
+}
@@ -1,3 +1,14 @@
 pub mod binance_kline_backfill;
+pub mod candle_consistency;
+pub mod candle_mode;
+pub mod news_feed;
+pub mod open_interest;
+pub mod price_alert;
 pub mod price_store;
+pub mod range_stats;
+pub mod reconnect_breaker;
 pub mod service;
+pub mod synthetic;
+pub mod tick_sequencer;
+pub mod trade_gaps;
+pub mod volatility;
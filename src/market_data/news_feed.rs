@@ -0,0 +1,447 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+pub const DEFAULT_NEWS_POLL_SECS: u64 = 300;
+pub const DEFAULT_NEWS_BLACKOUT_SECS: u64 = 30 * 60;
+pub const DEFAULT_NEWS_MAJOR_KEYWORDS: &str = "delist,hack,exploit,halt,suspend,emergency,outage";
+/// Headlines kept for the `/news` pane.
+pub const NEWS_PANE_LIMIT: usize = 50;
+/// Headline ids remembered so a feed that re-lists old items stays quiet.
+const NEWS_SEEN_LIMIT: usize = 1_000;
+
+/// Where headlines come from and what counts as a major one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewsFeedConfig {
+    /// RSS, Atom or JSON feed; `None` leaves the pane empty.
+    pub url: Option<String>,
+    pub poll_every_secs: u64,
+    /// Lower-case word stems that flag a headline as major, e.g. `delist`
+    /// also matches `delisting`.
+    pub major_keywords: Vec<String>,
+    /// How long the blackout hotkey holds new entries.
+    pub blackout_secs: u64,
+}
+
+impl Default for NewsFeedConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            poll_every_secs: DEFAULT_NEWS_POLL_SECS,
+            major_keywords: parse_keywords(DEFAULT_NEWS_MAJOR_KEYWORDS),
+            blackout_secs: DEFAULT_NEWS_BLACKOUT_SECS,
+        }
+    }
+}
+
+impl NewsFeedConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_NEWS_FEED_URL` (unset disables the feed)
+    /// - `SANDBOX_QUANT_NEWS_POLL_SECS` (default `300`)
+    /// - `SANDBOX_QUANT_NEWS_MAJOR_KEYWORDS` (comma separated)
+    /// - `SANDBOX_QUANT_NEWS_BLACKOUT_SECS` (default `1800`)
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let defaults = Self::default();
+        let secs = |key: &str| {
+            var(key)
+                .and_then(|raw| raw.trim().parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+        };
+        Self {
+            url: var("SANDBOX_QUANT_NEWS_FEED_URL")
+                .map(|url| url.trim().to_string())
+                .filter(|url| !url.is_empty()),
+            poll_every_secs: secs("SANDBOX_QUANT_NEWS_POLL_SECS")
+                .unwrap_or(defaults.poll_every_secs),
+            major_keywords: var("SANDBOX_QUANT_NEWS_MAJOR_KEYWORDS")
+                .map(|raw| parse_keywords(&raw))
+                .unwrap_or(defaults.major_keywords),
+            blackout_secs: secs("SANDBOX_QUANT_NEWS_BLACKOUT_SECS")
+                .unwrap_or(defaults.blackout_secs),
+        }
+    }
+
+    /// Example:
+    /// - `https://example.com/feed.xml every=300s blackout=1800s`
+    /// - `off`
+    pub fn label(&self) -> String {
+        match &self.url {
+            Some(url) => format!(
+                "{url} every={}s blackout={}s",
+                self.poll_every_secs, self.blackout_secs
+            ),
+            None => "off".to_string(),
+        }
+    }
+}
+
+fn parse_keywords(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|keyword| keyword.trim().to_ascii_lowercase())
+        .filter(|keyword| !keyword.is_empty())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewsHeadline {
+    /// Feed guid, article code or link; the title when there is none.
+    pub id: String,
+    pub title: String,
+    pub link: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// A headline with the traded assets it mentions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlaggedHeadline {
+    pub headline: NewsHeadline,
+    pub assets: Vec<String>,
+    /// Mentions one of the major keywords.
+    pub major: bool,
+}
+
+impl FlaggedHeadline {
+    /// The title with mentioned assets bracketed.
+    ///
+    /// Example:
+    /// - `Binance Will Delist [BTC] Margin Pairs`
+    pub fn highlighted_title(&self) -> String {
+        split_words(&self.headline.title)
+            .map(|(word, is_word)| {
+                if is_word
+                    && self
+                        .assets
+                        .iter()
+                        .any(|asset| word.eq_ignore_ascii_case(asset))
+                {
+                    format!("[{word}]")
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect()
+    }
+
+    /// Worth printing above the prompt, not just listing in the pane.
+    pub fn is_notable(&self) -> bool {
+        self.major || !self.assets.is_empty()
+    }
+}
+
+/// The `/news` pane: recent headlines, newest first, flagged against the
+/// assets the session trades.
+///
+/// The first poll only fills the pane; later polls also return the new
+/// headlines so the shell can announce them.
+///
+/// Example:
+/// - trading `BTCUSDT`, new headline `Binance Will Delist BTC Margin Pairs`
+/// - `assets=["BTC"]`, `major=true` (keyword `delist`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NewsFeed {
+    pub config: NewsFeedConfig,
+    headlines: VecDeque<FlaggedHeadline>,
+    seen: VecDeque<String>,
+    pub last_polled_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    /// Headlines kept; `None` uses `NEWS_PANE_LIMIT`.
+    pane_limit: Option<usize>,
+}
+
+impl NewsFeed {
+    pub fn new(config: NewsFeedConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.config.url.is_some()
+            && self.last_polled_at.is_none_or(|polled_at| {
+                (now - polled_at).num_seconds() >= self.config.poll_every_secs as i64
+            })
+    }
+
+    pub fn pane_limit(&self) -> usize {
+        self.pane_limit.unwrap_or(NEWS_PANE_LIMIT)
+    }
+
+    /// Keeps at most `limit` headlines, dropping the oldest.
+    pub fn set_pane_limit(&mut self, limit: usize) {
+        self.pane_limit = Some(limit);
+        self.headlines.truncate(limit);
+    }
+
+    /// Newest first.
+    pub fn headlines(&self) -> impl Iterator<Item = &FlaggedHeadline> {
+        self.headlines.iter()
+    }
+
+    /// Adds unseen headlines and returns them oldest first, or nothing on
+    /// the first poll.
+    pub fn ingest(
+        &mut self,
+        fetched: Vec<NewsHeadline>,
+        traded_assets: &BTreeSet<String>,
+        now: DateTime<Utc>,
+    ) -> Vec<FlaggedHeadline> {
+        let first_poll = self.last_polled_at.is_none();
+        self.last_polled_at = Some(now);
+        self.last_error = None;
+        let mut fetched = fetched;
+        fetched.sort_by_key(|headline| headline.published_at);
+        let mut fresh = Vec::new();
+        for headline in fetched {
+            if self.seen.contains(&headline.id) {
+                continue;
+            }
+            self.seen.push_back(headline.id.clone());
+            if self.seen.len() > NEWS_SEEN_LIMIT {
+                let _ = self.seen.pop_front();
+            }
+            let flagged = self.flag(headline, traded_assets);
+            self.headlines.push_front(flagged.clone());
+            fresh.push(flagged);
+        }
+        self.headlines.truncate(self.pane_limit());
+        if first_poll {
+            Vec::new()
+        } else {
+            fresh
+        }
+    }
+
+    pub fn record_failure(&mut self, now: DateTime<Utc>, error: String) {
+        self.last_polled_at = Some(now);
+        self.last_error = Some(error);
+    }
+
+    fn flag(&self, headline: NewsHeadline, traded_assets: &BTreeSet<String>) -> FlaggedHeadline {
+        let words = split_words(&headline.title)
+            .filter(|(_, is_word)| *is_word)
+            .map(|(word, _)| word.to_ascii_lowercase())
+            .collect::<Vec<_>>();
+        let assets = traded_assets
+            .iter()
+            .filter(|asset| words.iter().any(|word| word.eq_ignore_ascii_case(asset)))
+            .cloned()
+            .collect();
+        let major = self
+            .config
+            .major_keywords
+            .iter()
+            .any(|keyword| words.iter().any(|word| word.starts_with(keyword.as_str())));
+        FlaggedHeadline {
+            headline,
+            assets,
+            major,
+        }
+    }
+}
+
+/// Splits text into alternating word and separator runs.
+fn split_words(text: &str) -> impl Iterator<Item = (&str, bool)> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let is_word = first.is_alphanumeric();
+        let end = rest
+            .find(|c: char| c.is_alphanumeric() != is_word)
+            .unwrap_or(rest.len());
+        let (run, tail) = rest.split_at(end);
+        rest = tail;
+        Some((run, is_word))
+    })
+}
+
+pub fn fetch_news_feed(http: &Client, url: &str) -> Result<Vec<NewsHeadline>> {
+    let body = http
+        .get(url)
+        .timeout(Duration::from_secs(5))
+        .send()
+        .with_context(|| format!("failed to fetch news feed {url}"))?
+        .error_for_status()
+        .with_context(|| format!("news feed HTTP status error for {url}"))?
+        .text()
+        .with_context(|| format!("failed to read news feed {url}"))?;
+    Ok(parse_news_feed(&body))
+}
+
+/// Reads RSS `<item>`s, Atom `<entry>`s or JSON objects with a `title`,
+/// such as Binance's announcement list.
+///
+/// Example:
+/// - `<item><title>Binance Will List FOO</title><guid>42</guid></item>`
+/// - `{"data":{"catalogs":[{"articles":[{"code":"abc","title":"..."}]}]}}`
+pub fn parse_news_feed(body: &str) -> Vec<NewsHeadline> {
+    let trimmed = body.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        let mut headlines = Vec::new();
+        if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+            collect_json_headlines(&value, &mut headlines);
+        }
+        return headlines;
+    }
+    let items = xml_blocks(body, "item");
+    let items = if items.is_empty() {
+        xml_blocks(body, "entry")
+    } else {
+        items
+    };
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let title = xml_text(item, "title").filter(|title| !title.is_empty())?;
+            let link = xml_text(item, "link")
+                .filter(|link| !link.is_empty())
+                .or_else(|| xml_attr(item, "link", "href"));
+            let published_at = ["pubDate", "published", "updated"]
+                .into_iter()
+                .find_map(|tag| xml_text(item, tag))
+                .and_then(|raw| parse_feed_time(&raw));
+            let id = xml_text(item, "guid")
+                .or_else(|| xml_text(item, "id"))
+                .or_else(|| link.clone())
+                .unwrap_or_else(|| title.clone());
+            Some(NewsHeadline {
+                id,
+                title,
+                link,
+                published_at,
+            })
+        })
+        .collect()
+}
+
+fn collect_json_headlines(value: &Value, headlines: &mut Vec<NewsHeadline>) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                collect_json_headlines(item, headlines);
+            }
+        }
+        Value::Object(fields) => {
+            if let Some(title) = fields.get("title").and_then(Value::as_str) {
+                let text = |key: &str| {
+                    fields.get(key).and_then(|value| match value {
+                        Value::String(text) => Some(text.clone()),
+                        Value::Number(number) => Some(number.to_string()),
+                        _ => None,
+                    })
+                };
+                let link = text("url").or_else(|| text("link"));
+                let published_at = ["releaseDate", "publishDate", "published_at", "time"]
+                    .into_iter()
+                    .find_map(|key| fields.get(key))
+                    .and_then(|value| match value {
+                        Value::Number(number) => number
+                            .as_i64()
+                            .and_then(|ms| Utc.timestamp_millis_opt(ms).single()),
+                        Value::String(raw) => parse_feed_time(raw),
+                        _ => None,
+                    });
+                headlines.push(NewsHeadline {
+                    id: text("code")
+                        .or_else(|| text("id"))
+                        .or_else(|| link.clone())
+                        .unwrap_or_else(|| title.to_string()),
+                    title: title.trim().to_string(),
+                    link,
+                    published_at,
+                });
+                return;
+            }
+            for item in fields.values() {
+                collect_json_headlines(item, headlines);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_feed_time(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    DateTime::parse_from_rfc2822(raw)
+        .or_else(|_| DateTime::parse_from_rfc3339(raw))
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// Bodies of every `<tag ...>...</tag>`, not descending into nested ones.
+fn xml_blocks<'a>(body: &'a str, tag: &str) -> Vec<&'a str> {
+    let close = format!("</{tag}>");
+    let mut blocks = Vec::new();
+    let mut rest = body;
+    while let Some(content_start) = xml_open(rest, tag) {
+        let Some(end) = rest[content_start..].find(&close) else {
+            break;
+        };
+        blocks.push(&rest[content_start..content_start + end]);
+        rest = &rest[content_start + end + close.len()..];
+    }
+    blocks
+}
+
+/// Where the content of the first `<tag>` or `<tag attr..>` starts;
+/// self-closing tags are skipped.
+fn xml_open(body: &str, tag: &str) -> Option<usize> {
+    let open = format!("<{tag}");
+    let mut offset = 0;
+    while let Some(found) = body[offset..].find(&open) {
+        let start = offset + found;
+        let after = &body[start + open.len()..];
+        if after.starts_with(['>', ' ', '\t', '\n', '\r', '/']) {
+            let tag_end = after.find('>')?;
+            if after[..tag_end].ends_with('/') {
+                offset = start + open.len();
+                continue;
+            }
+            return Some(start + open.len() + tag_end + 1);
+        }
+        offset = start + open.len();
+    }
+    None
+}
+
+fn xml_text(block: &str, tag: &str) -> Option<String> {
+    let content_start = xml_open(block, tag)?;
+    let end = block[content_start..].find(&format!("</{tag}>"))?;
+    let raw = block[content_start..content_start + end].trim();
+    let raw = raw
+        .strip_prefix("<![CDATA[")
+        .and_then(|inner| inner.strip_suffix("]]>"))
+        .unwrap_or(raw);
+    Some(decode_entities(raw.trim()))
+}
+
+/// `attr` of the first `<tag ...>`, for Atom's `<link href=".."/>`.
+fn xml_attr(block: &str, tag: &str, attr: &str) -> Option<String> {
+    let open = format!("<{tag} ");
+    let start = block.find(&open)? + open.len();
+    let tag_body = &block[start..start + block[start..].find('>')?];
+    let key = format!("{attr}=\"");
+    let value_start = tag_body.find(&key)? + key.len();
+    let value_end = tag_body[value_start..].find('"')?;
+    Some(decode_entities(
+        &tag_body[value_start..value_start + value_end],
+    ))
+}
+
+fn decode_entities(raw: &str) -> String {
+    raw.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,68 @@
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+use crate::dataset::types::OpenInterestRow;
+
+pub const DEFAULT_OPEN_INTEREST_POLL_SECS: u64 = 60;
+
+/// How often the recorder samples futures open interest over REST.
+///
+/// Binance has no open interest stream, so the chart pane is fed by polling
+/// `/fapi/v1/openInterest` for each watched symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenInterestPollConfig {
+    pub poll_every_secs: u64,
+}
+
+impl Default for OpenInterestPollConfig {
+    fn default() -> Self {
+        Self {
+            poll_every_secs: DEFAULT_OPEN_INTEREST_POLL_SECS,
+        }
+    }
+}
+
+impl OpenInterestPollConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_OPEN_INTEREST_POLL_SECS` (default `60`, `0` disables polling)
+    pub fn from_env() -> Self {
+        std::env::var("SANDBOX_QUANT_OPEN_INTEREST_POLL_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(|poll_every_secs| Self { poll_every_secs })
+            .unwrap_or_default()
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.poll_every_secs > 0
+    }
+}
+
+/// Fetches the current open interest for one futures symbol.
+pub fn fetch_open_interest(http: &Client, symbol: &str) -> Result<OpenInterestRow> {
+    let url = format!("https://fapi.binance.com/fapi/v1/openInterest?symbol={symbol}");
+    let value = http
+        .get(&url)
+        .send()
+        .with_context(|| format!("failed to fetch open interest for {symbol}"))?
+        .error_for_status()
+        .with_context(|| format!("open interest HTTP status error for {symbol}"))?
+        .json::<Value>()
+        .with_context(|| format!("failed to decode open interest for {symbol}"))?;
+    parse_open_interest(&value)
+        .with_context(|| format!("open interest response for {symbol} is missing fields"))
+}
+
+/// Example:
+/// - `{"openInterest":"10659.509","symbol":"BTCUSDT","time":1589437530011}`
+pub fn parse_open_interest(value: &Value) -> Option<OpenInterestRow> {
+    let open_interest = match value.get("openInterest")? {
+        Value::String(raw) => raw.trim().parse::<f64>().ok()?,
+        other => other.as_f64()?,
+    };
+    Some(OpenInterestRow {
+        event_time_ms: value.get("time")?.as_i64()?,
+        open_interest,
+    })
+}
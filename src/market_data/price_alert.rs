@@ -0,0 +1,238 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::instrument::Instrument;
+use crate::error::storage_error::StorageError;
+use crate::storage::{atomic_file, sealed};
+use crate::strategy::model::StrategyTemplate;
+
+pub const DEFAULT_PRICE_ALERTS_PATH: &str = "var/price-alerts.json";
+
+/// What happens when an alert line is crossed, beyond the notification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PriceAlertAction {
+    Notify,
+    StartStrategy { template: String },
+    SetTargetExposure { target: f64 },
+}
+
+impl PriceAlertAction {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Notify => "notify".to_string(),
+            Self::StartStrategy { template } => format!("strategy:{template}"),
+            Self::SetTargetExposure { target } => format!("target:{target}"),
+        }
+    }
+
+    pub fn strategy_template(&self) -> Option<StrategyTemplate> {
+        match self {
+            Self::StartStrategy { template } => StrategyTemplate::all()
+                .into_iter()
+                .find(|candidate| candidate.slug() == template),
+            _ => None,
+        }
+    }
+}
+
+/// A horizontal price line that fires once when the market crosses it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceAlert {
+    pub id: u64,
+    pub instrument: String,
+    pub price: f64,
+    pub action: PriceAlertAction,
+    pub last_price: Option<f64>,
+    pub created_at: String,
+    pub triggered_at: Option<String>,
+    /// Set when an order-placing action fired and waits for `/alert confirm`.
+    #[serde(default)]
+    pub awaiting_confirmation: bool,
+}
+
+impl PriceAlert {
+    pub fn is_armed(&self) -> bool {
+        self.triggered_at.is_none()
+    }
+
+    /// Returns true when the move from `last_price` to `price` touches or
+    /// passes the alert level.
+    ///
+    /// Example:
+    /// - alert at `100`, last `99`, now `100.5` -> crossed upward
+    /// - alert at `100`, last `101`, now `100.5` -> not crossed
+    pub fn crossed_by(&self, price: f64) -> bool {
+        let Some(last) = self.last_price else {
+            return false;
+        };
+        (last < self.price && price >= self.price) || (last > self.price && price <= self.price)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct PriceAlertFile {
+    next_id: u64,
+    alerts: Vec<PriceAlert>,
+}
+
+/// Alert lines persisted as JSON so they survive restarts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceAlertStore {
+    path: PathBuf,
+    next_id: u64,
+    alerts: Vec<PriceAlert>,
+    /// `false` when the file on disk failed to load, so it is never saved
+    /// over.
+    writable: bool,
+}
+
+impl Default for PriceAlertStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_PRICE_ALERTS_PATH)
+    }
+}
+
+impl PriceAlertStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            next_id: 1,
+            alerts: Vec::new(),
+            writable: true,
+        }
+    }
+
+    /// An empty store standing in for one that failed to load; `save`
+    /// refuses so the file on disk is left for the operator to repair.
+    pub fn unloaded(path: impl Into<PathBuf>) -> Self {
+        Self {
+            writable: false,
+            ..Self::new(path)
+        }
+    }
+
+    /// Loads alerts from `path`, starting empty when the file does not exist.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, StorageError> {
+        let mut store = Self::new(path);
+        if !atomic_file::store_exists(&store.path) {
+            return Ok(store);
+        }
+        let bytes = sealed::read_store_file(&store.path, sealed::process_sealer()?)?;
+        let file: PriceAlertFile =
+            serde_json::from_slice(&bytes).map_err(|error| StorageError::ParseFailed {
+                path: store.path.display().to_string(),
+                message: error.to_string(),
+            })?;
+        store.next_id = file.next_id.max(1);
+        store.alerts = file.alerts;
+        Ok(store)
+    }
+
+    pub fn save(&self) -> Result<(), StorageError> {
+        if !self.writable {
+            return Err(StorageError::NotLoaded {
+                path: self.path.display().to_string(),
+            });
+        }
+        let file = PriceAlertFile {
+            next_id: self.next_id,
+            alerts: self.alerts.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&file).map_err(|error| {
+            StorageError::WriteFailedWithContext {
+                message: error.to_string(),
+            }
+        })?;
+        sealed::write_store_file(&self.path, &json, sealed::process_sealer()?)
+    }
+
+    pub fn add(
+        &mut self,
+        instrument: &Instrument,
+        price: f64,
+        action: PriceAlertAction,
+        last_price: Option<f64>,
+    ) -> PriceAlert {
+        let alert = PriceAlert {
+            id: self.next_id,
+            instrument: instrument.0.clone(),
+            price,
+            action,
+            last_price,
+            created_at: Utc::now().to_rfc3339(),
+            triggered_at: None,
+            awaiting_confirmation: false,
+        };
+        self.next_id += 1;
+        self.alerts.push(alert.clone());
+        alert
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<PriceAlert> {
+        let index = self.alerts.iter().position(|alert| alert.id == id)?;
+        Some(self.alerts.remove(index))
+    }
+
+    /// Holds a fired alert's order until the operator confirms it.
+    pub fn hold_for_confirmation(&mut self, id: u64) {
+        if let Some(alert) = self.alerts.iter_mut().find(|alert| alert.id == id) {
+            alert.awaiting_confirmation = true;
+        }
+    }
+
+    /// Releases a held alert for its order, or `None` when it is not held.
+    pub fn take_confirmation(&mut self, id: u64) -> Option<PriceAlert> {
+        let alert = self
+            .alerts
+            .iter_mut()
+            .find(|alert| alert.id == id && alert.awaiting_confirmation)?;
+        alert.awaiting_confirmation = false;
+        Some(alert.clone())
+    }
+
+    pub fn alerts(&self) -> &[PriceAlert] {
+        &self.alerts
+    }
+
+    pub fn armed_instruments(&self) -> Vec<Instrument> {
+        let mut instruments = self
+            .alerts
+            .iter()
+            .filter(|alert| alert.is_armed())
+            .map(|alert| Instrument::new(alert.instrument.clone()))
+            .collect::<Vec<_>>();
+        instruments.sort();
+        instruments.dedup();
+        instruments
+    }
+
+    /// Armed alert levels for one instrument, used to draw chart lines.
+    pub fn levels_for(&self, instrument: &str) -> Vec<f64> {
+        self.alerts
+            .iter()
+            .filter(|alert| alert.is_armed() && alert.instrument == instrument)
+            .map(|alert| alert.price)
+            .collect()
+    }
+
+    /// Feeds a new price and returns the alerts it triggered.
+    pub fn observe(&mut self, instrument: &Instrument, price: f64) -> Vec<PriceAlert> {
+        let now = Utc::now().to_rfc3339();
+        let mut triggered = Vec::new();
+        for alert in self
+            .alerts
+            .iter_mut()
+            .filter(|alert| alert.is_armed() && alert.instrument == instrument.0)
+        {
+            if alert.crossed_by(price) {
+                alert.triggered_at = Some(now.clone());
+                triggered.push(alert.clone());
+            }
+            alert.last_price = Some(price);
+        }
+        triggered
+    }
+}
@@ -1,20 +1,56 @@
 use std::collections::BTreeMap;
 
+use chrono::{DateTime, Utc};
+
 use crate::domain::instrument::Instrument;
-use crate::execution::price_source::PriceSource;
+use crate::execution::price_source::{PriceSource, PriceStamp};
 
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct PriceStore {
     prices: BTreeMap<Instrument, f64>,
+    /// Venue trade time of each price, kept only where it was reported.
+    stamps: BTreeMap<Instrument, PriceStamp>,
 }
 
 impl PriceStore {
+    /// Stores a price with the venue's trade time and the local time it
+    /// arrived; an unstamped price drops any older stamp.
+    pub fn set_trade(
+        &mut self,
+        instrument: Instrument,
+        price: f64,
+        exchange_time_ms: Option<i64>,
+        received_at: DateTime<Utc>,
+    ) {
+        if price <= f64::EPSILON {
+            return;
+        }
+        match exchange_time_ms {
+            Some(exchange_time_ms) => self.stamps.insert(
+                instrument.clone(),
+                PriceStamp {
+                    exchange_time_ms,
+                    received_at,
+                },
+            ),
+            None => self.stamps.remove(&instrument),
+        };
+        self.prices.insert(instrument, price);
+    }
+
     pub fn set_price(&mut self, instrument: Instrument, price: f64) {
         if price > f64::EPSILON {
             self.prices.insert(instrument, price);
         }
     }
 
+    /// Stores a synthetic spread, which may be zero or negative.
+    pub fn set_spread_price(&mut self, instrument: Instrument, price: f64) {
+        if price.is_finite() {
+            self.prices.insert(instrument, price);
+        }
+    }
+
     pub fn snapshot(&self) -> Vec<(Instrument, f64)> {
         self.prices
             .iter()
@@ -27,4 +63,8 @@ impl PriceSource for PriceStore {
     fn current_price(&self, instrument: &Instrument) -> Option<f64> {
         self.prices.get(instrument).copied()
     }
+
+    fn price_stamp(&self, instrument: &Instrument) -> Option<PriceStamp> {
+        self.stamps.get(instrument).copied()
+    }
 }
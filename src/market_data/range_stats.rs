@@ -0,0 +1,73 @@
+use crate::exchange::types::DailyBar;
+
+/// Completed daily bars averaged into ATR and volume.
+pub const SYMBOL_STATS_DAYS: usize = 14;
+
+/// Range and liquidity summary shown next to a symbol in the selector.
+///
+/// Example:
+/// - closes `100 -> 102.5`, true ranges around `3`, `1.2B` quote a day
+/// - `24h +2.50% atr 2.93% vol 1.2B`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymbolRangeStats {
+    /// Latest price against the previous daily close.
+    pub change_24h_pct: f64,
+    /// Average true range of the completed days over the latest price.
+    pub atr_pct: f64,
+    /// Mean quote volume per completed day.
+    pub avg_quote_volume: f64,
+}
+
+impl SymbolRangeStats {
+    /// Reads bars oldest first, the last one being today's open bar.
+    ///
+    /// Returns `None` until there is at least one completed day.
+    pub fn from_daily_bars(bars: &[DailyBar]) -> Option<Self> {
+        let (today, completed) = bars.split_last()?;
+        let completed = &completed[completed.len().saturating_sub(SYMBOL_STATS_DAYS)..];
+        let previous = completed.last()?;
+        if previous.close <= f64::EPSILON || today.close <= f64::EPSILON {
+            return None;
+        }
+        let true_ranges = completed
+            .iter()
+            .enumerate()
+            .map(|(index, bar)| match index.checked_sub(1) {
+                Some(prior) => {
+                    let prior_close = completed[prior].close;
+                    (bar.high - bar.low)
+                        .max((bar.high - prior_close).abs())
+                        .max((bar.low - prior_close).abs())
+                }
+                None => bar.high - bar.low,
+            })
+            .collect::<Vec<_>>();
+        let days = completed.len() as f64;
+        Some(Self {
+            change_24h_pct: (today.close - previous.close) / previous.close * 100.0,
+            atr_pct: true_ranges.iter().sum::<f64>() / days / today.close * 100.0,
+            avg_quote_volume: completed.iter().map(|bar| bar.quote_volume).sum::<f64>() / days,
+        })
+    }
+
+    /// Example:
+    /// - `24h -1.20% atr 4.05% vol 350.0M`
+    pub fn label(&self) -> String {
+        format!(
+            "24h {:+.2}% atr {:.2}% vol {}",
+            self.change_24h_pct,
+            self.atr_pct,
+            compact_volume(self.avg_quote_volume)
+        )
+    }
+}
+
+/// Example:
+/// - `1_234_000_000` -> `1.2B`, `8_500` -> `8.5K`, `950` -> `950`
+fn compact_volume(value: f64) -> String {
+    [(1e9, "B"), (1e6, "M"), (1e3, "K")]
+        .into_iter()
+        .find(|(scale, _)| value.abs() >= *scale)
+        .map(|(scale, suffix)| format!("{:.1}{suffix}", value / scale))
+        .unwrap_or_else(|| format!("{value:.0}"))
+}
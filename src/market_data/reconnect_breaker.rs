@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, VecDeque};
+
+pub const DEFAULT_RECONNECT_BREAKER_ATTEMPTS: usize = 5;
+pub const DEFAULT_RECONNECT_BREAKER_WINDOW_SECS: u64 = 60;
+pub const DEFAULT_RECONNECT_BREAKER_COOLDOWN_SECS: u64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectBreakerConfig {
+    /// Connect attempts allowed inside `window_secs`; `0` disables the breaker.
+    pub max_attempts: usize,
+    pub window_secs: u64,
+    pub cooldown_secs: u64,
+}
+
+impl Default for ReconnectBreakerConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_RECONNECT_BREAKER_ATTEMPTS,
+            window_secs: DEFAULT_RECONNECT_BREAKER_WINDOW_SECS,
+            cooldown_secs: DEFAULT_RECONNECT_BREAKER_COOLDOWN_SECS,
+        }
+    }
+}
+
+impl ReconnectBreakerConfig {
+    /// Reads the websocket reconnect breaker settings from the environment.
+    ///
+    /// Optional:
+    /// - `SANDBOX_QUANT_WS_BREAKER_ATTEMPTS` (default `5`, `0` disables)
+    /// - `SANDBOX_QUANT_WS_BREAKER_WINDOW_SECS` (default `60`)
+    /// - `SANDBOX_QUANT_WS_BREAKER_COOLDOWN_SECS` (default `300`)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let max_attempts = std::env::var("SANDBOX_QUANT_WS_BREAKER_ATTEMPTS")
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(defaults.max_attempts);
+        let window_secs = std::env::var("SANDBOX_QUANT_WS_BREAKER_WINDOW_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(defaults.window_secs);
+        let cooldown_secs = std::env::var("SANDBOX_QUANT_WS_BREAKER_COOLDOWN_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(defaults.cooldown_secs);
+        Self {
+            max_attempts,
+            window_secs,
+            cooldown_secs,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.max_attempts > 0
+    }
+}
+
+/// Counts websocket connect attempts per stream key and opens a cool-down
+/// once a key reconnects too often.
+///
+/// Example:
+/// - 5 attempts, 60s window, 300s cool-down
+/// - `BTCUSDT` connects at 0s, 5s, 10s, 15s and 20s -> all allowed
+/// - a sixth attempt at 25s trips it -> open until 325s
+/// - the recorder skips `BTCUSDT` until then and reports it as degraded
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReconnectBreaker {
+    config: ReconnectBreakerConfig,
+    attempts: BTreeMap<String, VecDeque<i64>>,
+    open_until: BTreeMap<String, i64>,
+}
+
+impl ReconnectBreaker {
+    pub fn new(config: ReconnectBreakerConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Records one connect attempt and returns the cool-down end when this
+    /// attempt goes over the limit; the caller should not connect then.
+    pub fn record_attempt(&mut self, key: &str, now_ms: i64) -> Option<i64> {
+        if !self.config.enabled() {
+            return None;
+        }
+        let window_ms = self.config.window_secs as i64 * 1_000;
+        let attempts = self.attempts.entry(key.to_string()).or_default();
+        attempts.push_back(now_ms);
+        while attempts
+            .front()
+            .is_some_and(|first| now_ms - *first >= window_ms)
+        {
+            attempts.pop_front();
+        }
+        if attempts.len() <= self.config.max_attempts {
+            return None;
+        }
+        attempts.clear();
+        let until_ms = now_ms + self.config.cooldown_secs as i64 * 1_000;
+        self.open_until.insert(key.to_string(), until_ms);
+        Some(until_ms)
+    }
+
+    pub fn open_until(&self, key: &str, now_ms: i64) -> Option<i64> {
+        self.open_until
+            .get(key)
+            .copied()
+            .filter(|until_ms| *until_ms > now_ms)
+    }
+
+    pub fn is_open(&self, key: &str, now_ms: i64) -> bool {
+        self.open_until(key, now_ms).is_some()
+    }
+
+    /// Keys still cooling down, with the time each cool-down ends.
+    pub fn degraded(&self, now_ms: i64) -> Vec<(String, i64)> {
+        self.open_until
+            .iter()
+            .filter(|(_, until_ms)| **until_ms > now_ms)
+            .map(|(key, until_ms)| (key.clone(), *until_ms))
+            .collect()
+    }
+}
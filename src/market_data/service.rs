@@ -1,9 +1,12 @@
+use chrono::Utc;
+
 use crate::domain::instrument::Instrument;
 use crate::domain::market::Market;
 use crate::error::exchange_error::ExchangeError;
 use crate::exchange::facade::ExchangeFacade;
 use crate::execution::price_source::PriceSource;
 use crate::market_data::price_store::PriceStore;
+use crate::market_data::synthetic::{SyntheticInstrument, SyntheticTick};
 
 #[derive(Debug, Default)]
 pub struct MarketDataService;
@@ -18,6 +21,8 @@ impl MarketDataService {
         store.set_price(instrument, price);
     }
 
+    /// Fetches the last trade and stores it with the venue's trade time,
+    /// so decisions on it can be timed on the venue's clock.
     pub fn refresh_price<E: ExchangeFacade<Error = ExchangeError>>(
         &self,
         exchange: &E,
@@ -25,9 +30,42 @@ impl MarketDataService {
         instrument: Instrument,
         market: Market,
     ) -> Result<f64, ExchangeError> {
-        let price = exchange.load_last_price(&instrument, market)?;
-        store.set_price(instrument, price);
-        Ok(price)
+        let trade = exchange.load_last_trade(&instrument, market)?;
+        store.set_trade(instrument, trade.price, trade.exchange_time_ms, Utc::now());
+        Ok(trade.price)
+    }
+
+    /// Computes one synthetic tick from fresh leg prices.
+    ///
+    /// Leg prices are not written to `store`: the same symbol can appear on
+    /// both spot and futures and the store is keyed by instrument only.
+    pub fn refresh_synthetic_price<E: ExchangeFacade<Error = ExchangeError>>(
+        &self,
+        exchange: &E,
+        store: &mut PriceStore,
+        synthetic: &SyntheticInstrument,
+    ) -> Result<SyntheticTick, ExchangeError> {
+        let leg_prices = synthetic
+            .legs
+            .iter()
+            .map(|leg| {
+                exchange
+                    .load_last_price(&leg.instrument, leg.market)
+                    .map(|price| (leg.instrument.clone(), leg.market, price))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let price = synthetic.price(
+            &leg_prices
+                .iter()
+                .map(|(_, _, price)| *price)
+                .collect::<Vec<_>>(),
+        );
+        store.set_spread_price(synthetic.name.clone(), price);
+        Ok(SyntheticTick {
+            instrument: synthetic.name.clone(),
+            price,
+            leg_prices,
+        })
     }
 
     pub fn current_price(&self, store: &impl PriceSource, instrument: &Instrument) -> Option<f64> {
@@ -0,0 +1,175 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Mutex, OnceLock};
+
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+
+/// One constituent of a synthetic instrument.
+///
+/// `weight` scales both the leg price in the spread and the share of a
+/// target exposure routed to this leg.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticLeg {
+    pub instrument: Instrument,
+    pub market: Market,
+    pub weight: f64,
+}
+
+/// Instrument whose price is a weighted sum of other feeds.
+///
+/// Example:
+/// - `BTC_BASIS=BTCUSDT@futures-BTCUSDT@spot`
+/// - futures `50100`, spot `50000` -> tick `100`
+/// - `set-target-exposure BTC_BASIS -0.2` -> futures `-0.2`, spot `+0.2`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticInstrument {
+    pub name: Instrument,
+    pub legs: Vec<SyntheticLeg>,
+}
+
+impl SyntheticInstrument {
+    /// Parses a leg expression such as `BTCUSDT@futures-BTCUSDT@spot` or
+    /// `ETHUSDT@futures-0.05*BTCUSDT@futures`.
+    ///
+    /// Needs at least two legs with non-zero weights.
+    pub fn parse(name: &str, body: &str) -> Option<Self> {
+        let mut legs = Vec::new();
+        let mut sign = 1.0;
+        let mut term = String::new();
+        for ch in body.trim().chars().chain(std::iter::once('+')) {
+            if (ch == '+' || ch == '-') && !term.trim().is_empty() {
+                legs.push(parse_leg(term.trim(), sign)?);
+                term.clear();
+                sign = if ch == '-' { -1.0 } else { 1.0 };
+            } else if ch == '+' || ch == '-' {
+                sign = if ch == '-' { -sign } else { sign };
+            } else {
+                term.push(ch);
+            }
+        }
+        (legs.len() >= 2).then(|| Self {
+            name: Instrument::new(name.trim().to_ascii_uppercase()),
+            legs,
+        })
+    }
+
+    /// Combines leg prices given in `legs` order.
+    pub fn price(&self, leg_prices: &[f64]) -> f64 {
+        self.legs
+            .iter()
+            .zip(leg_prices)
+            .map(|(leg, price)| leg.weight * price)
+            .sum()
+    }
+
+    /// Splits a synthetic target exposure into per-leg targets.
+    pub fn leg_targets(&self, target: f64) -> Vec<(&SyntheticLeg, f64)> {
+        self.legs
+            .iter()
+            .map(|leg| (leg, target * leg.weight))
+            .collect()
+    }
+
+    /// Example:
+    /// - `BTCUSDT@futures-BTCUSDT@spot`
+    pub fn label(&self) -> String {
+        let mut label = String::new();
+        for (index, leg) in self.legs.iter().enumerate() {
+            let weight = leg.weight.abs();
+            if leg.weight < 0.0 {
+                label.push('-');
+            } else if index > 0 {
+                label.push('+');
+            }
+            if (weight - 1.0).abs() > f64::EPSILON {
+                label.push_str(&format!("{weight}*"));
+            }
+            label.push_str(&format!(
+                "{}@{}",
+                leg.instrument.0,
+                market_label(leg.market)
+            ));
+        }
+        label
+    }
+}
+
+/// Reads the synthetic instrument definitions.
+///
+/// Reads:
+/// - `SANDBOX_QUANT_SYNTHETIC_INSTRUMENTS` (e.g. `BTC_BASIS=BTCUSDT@futures-BTCUSDT@spot;ETH_BASIS=ETHUSDT@futures-ETHUSDT@spot`)
+///
+/// Names keep their `_` and, once registered, are taken verbatim by the
+/// shell. Malformed entries are skipped.
+pub fn synthetic_instruments_from_vars(
+    var: impl Fn(&str) -> Option<String>,
+) -> BTreeMap<Instrument, SyntheticInstrument> {
+    parse_synthetic_instruments(&var("SANDBOX_QUANT_SYNTHETIC_INSTRUMENTS").unwrap_or_default())
+}
+
+/// Names the shell keeps verbatim instead of appending a quote asset;
+/// replaced whenever the runtime config is applied.
+fn registered_names() -> &'static Mutex<BTreeSet<String>> {
+    static NAMES: OnceLock<Mutex<BTreeSet<String>>> = OnceLock::new();
+    NAMES.get_or_init(|| Mutex::new(BTreeSet::new()))
+}
+
+pub fn register_synthetic_names<'a>(names: impl IntoIterator<Item = &'a Instrument>) {
+    *registered_names().lock().expect("lock synthetic names") =
+        names.into_iter().map(|name| name.0.clone()).collect();
+}
+
+/// Whether `name` (upper case) is a configured synthetic instrument.
+pub fn is_registered_synthetic(name: &str) -> bool {
+    registered_names()
+        .lock()
+        .expect("lock synthetic names")
+        .contains(name)
+}
+
+pub fn parse_synthetic_instruments(raw: &str) -> BTreeMap<Instrument, SyntheticInstrument> {
+    raw.split(';')
+        .filter_map(|entry| entry.split_once('='))
+        .filter(|(name, _)| !name.trim().is_empty())
+        .filter_map(|(name, body)| SyntheticInstrument::parse(name, body))
+        .map(|synthetic| (synthetic.name.clone(), synthetic))
+        .collect()
+}
+
+fn parse_leg(term: &str, sign: f64) -> Option<SyntheticLeg> {
+    let (weight, leg) = match term.split_once('*') {
+        Some((weight, leg)) => (weight.trim().parse::<f64>().ok()?, leg.trim()),
+        None => (1.0, term),
+    };
+    let (symbol, market) = leg.split_once('@')?;
+    let market = match market.trim().to_ascii_lowercase().as_str() {
+        "spot" => Market::Spot,
+        "futures" | "fut" | "perp" => Market::Futures,
+        _ => return None,
+    };
+    let symbol = symbol.trim().to_ascii_uppercase();
+    if symbol.is_empty() || weight.abs() <= f64::EPSILON || !weight.is_finite() {
+        return None;
+    }
+    Some(SyntheticLeg {
+        instrument: Instrument::new(symbol),
+        market,
+        weight: sign * weight,
+    })
+}
+
+fn market_label(market: Market) -> &'static str {
+    match market {
+        Market::Spot => "spot",
+        Market::Futures => "futures",
+        Market::Options => "options",
+    }
+}
+
+/// One computed synthetic price and the leg prices behind it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticTick {
+    pub instrument: Instrument,
+    pub price: f64,
+    pub leg_prices: Vec<(Instrument, Market, f64)>,
+}
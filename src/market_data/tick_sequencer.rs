@@ -0,0 +1,177 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const DEFAULT_TICK_REORDER_WINDOW_MS: i64 = 250;
+
+/// Trade ids remembered per symbol for spotting repeats.
+const SEEN_IDS_PER_SYMBOL: usize = 4_096;
+
+/// How long a tick may wait for earlier ones that arrive after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickSequencerConfig {
+    pub reorder_window_ms: i64,
+}
+
+impl Default for TickSequencerConfig {
+    fn default() -> Self {
+        Self {
+            reorder_window_ms: DEFAULT_TICK_REORDER_WINDOW_MS,
+        }
+    }
+}
+
+impl TickSequencerConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_TICK_REORDER_WINDOW_MS` (`0` passes ticks straight
+    ///   through; repeats are still dropped)
+    pub fn from_env() -> Self {
+        std::env::var("SANDBOX_QUANT_TICK_REORDER_WINDOW_MS")
+            .ok()
+            .and_then(|raw| raw.trim().parse::<i64>().ok())
+            .filter(|window_ms| *window_ms >= 0)
+            .map(|reorder_window_ms| Self { reorder_window_ms })
+            .unwrap_or_default()
+    }
+}
+
+/// One aggTrade on its way from the stream to storage and the candles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequencedTrade {
+    pub symbol: String,
+    /// `f`; ticks without it are ordered but never treated as repeats.
+    pub first_id: Option<i64>,
+    pub last_id: Option<i64>,
+    pub event_time_ms: i64,
+    pub receive_time_ms: i64,
+    pub price: f64,
+    pub qty: f64,
+    pub is_buyer_maker: bool,
+}
+
+impl SequencedTrade {
+    fn key(&self) -> (i64, i64) {
+        (self.event_time_ms, self.first_id.unwrap_or(i64::MIN))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickAdmission {
+    InOrder,
+    /// Arrived after a later tick but inside the window, so it is put back
+    /// in place.
+    Reordered,
+    /// Arrived after later ticks were already released; it goes out next,
+    /// out of order.
+    Late,
+    /// Same symbol and trade id as a tick already seen; dropped.
+    Duplicate,
+}
+
+/// Drops repeated ticks and puts slightly late ones back in event order.
+///
+/// Reconnecting workers replay the last few trades of the old stream and
+/// deliver the odd message behind a later one. Each tick is held for
+/// `reorder_window_ms` after it arrives; once one is due, every held tick
+/// of that symbol up to its event time is released sorted by
+/// `(event_time, first trade id)`.
+///
+/// Example:
+/// - window `250`, trades `f=2 @t=20` then `f=1 @t=10` arrive at `0ms`
+/// - `release(100)` -> nothing, `release(250)` -> `f=1`, `f=2`
+/// - `f=2` arriving again -> `Duplicate`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TickSequencer {
+    config: TickSequencerConfig,
+    seen: BTreeMap<String, BTreeSet<i64>>,
+    newest: BTreeMap<String, (i64, i64)>,
+    released_through: BTreeMap<String, (i64, i64)>,
+    /// With the time each arrived.
+    pending: Vec<(i64, SequencedTrade)>,
+}
+
+impl TickSequencer {
+    pub fn new(config: TickSequencerConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn push(&mut self, trade: SequencedTrade, now_ms: i64) -> TickAdmission {
+        if let Some(first_id) = trade.first_id {
+            let seen = self.seen.entry(trade.symbol.clone()).or_default();
+            if !seen.insert(first_id) {
+                return TickAdmission::Duplicate;
+            }
+            if seen.len() > SEEN_IDS_PER_SYMBOL {
+                seen.pop_first();
+            }
+        }
+        let key = trade.key();
+        let admission = if self
+            .released_through
+            .get(&trade.symbol)
+            .is_some_and(|released| key < *released)
+        {
+            TickAdmission::Late
+        } else if self
+            .newest
+            .get(&trade.symbol)
+            .is_some_and(|newest| key < *newest)
+        {
+            TickAdmission::Reordered
+        } else {
+            self.newest.insert(trade.symbol.clone(), key);
+            TickAdmission::InOrder
+        };
+        self.pending.push((now_ms, trade));
+        admission
+    }
+
+    /// Ticks whose wait is over, plus the earlier ones of the same symbol,
+    /// in event order.
+    pub fn release(&mut self, now_ms: i64) -> Vec<SequencedTrade> {
+        let mut watermarks = BTreeMap::<String, (i64, i64)>::new();
+        for (arrived_ms, trade) in &self.pending {
+            if arrived_ms.saturating_add(self.config.reorder_window_ms) <= now_ms {
+                let watermark = watermarks
+                    .entry(trade.symbol.clone())
+                    .or_insert(trade.key());
+                *watermark = (*watermark).max(trade.key());
+            }
+        }
+        if watermarks.is_empty() {
+            return Vec::new();
+        }
+        let (due, held): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|(_, trade)| {
+                watermarks
+                    .get(&trade.symbol)
+                    .is_some_and(|watermark| trade.key() <= *watermark)
+            });
+        self.pending = held;
+        let mut released = due.into_iter().map(|(_, trade)| trade).collect::<Vec<_>>();
+        released.sort_by(|left, right| {
+            left.key()
+                .cmp(&right.key())
+                .then_with(|| left.symbol.cmp(&right.symbol))
+        });
+        for trade in &released {
+            let through = self
+                .released_through
+                .entry(trade.symbol.clone())
+                .or_insert(trade.key());
+            *through = (*through).max(trade.key());
+        }
+        released
+    }
+
+    /// Everything still held, in event order; for shutdown.
+    pub fn flush(&mut self) -> Vec<SequencedTrade> {
+        self.release(i64::MAX)
+    }
+}
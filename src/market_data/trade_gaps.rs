@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+/// Most rows `/fapi/v1/aggTrades` returns per request.
+pub const MAX_TRADE_BACKFILL_ROWS: usize = 1_000;
+
+/// Whether missed trades are fetched back over REST once a gap is seen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TradeGapConfig {
+    pub backfill: bool,
+}
+
+impl TradeGapConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_TRADE_GAP_BACKFILL` (`1`/`true` backfills missed trades)
+    pub fn from_env() -> Self {
+        Self {
+            backfill: std::env::var("SANDBOX_QUANT_TRADE_GAP_BACKFILL")
+                .ok()
+                .is_some_and(|value| matches!(value.trim(), "1" | "true" | "yes" | "on")),
+        }
+    }
+}
+
+/// Trade ids the stream skipped for one symbol, with the event times on
+/// either side so the range can be fetched back.
+///
+/// Example:
+/// - last aggTrade ended at trade `100`, the next starts at `104`
+/// - `from_id=101 to_id=103`, `missed() == 3`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeIdGap {
+    pub symbol: String,
+    pub from_id: i64,
+    pub to_id: i64,
+    pub after_time_ms: i64,
+    pub before_time_ms: i64,
+}
+
+impl TradeIdGap {
+    pub fn missed(&self) -> u64 {
+        (self.to_id - self.from_id + 1).max(0) as u64
+    }
+}
+
+/// Last trade id seen per symbol on the aggTrade stream.
+///
+/// Each aggTrade covers trade ids `f..=l`, so the next one has to start at
+/// `l + 1`; anything later is a gap. The ids survive reconnects, so trades
+/// lost while the stream was down show up as a gap on the first message
+/// after it comes back, next to the disconnect itself.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TradeIdTracker {
+    last: BTreeMap<String, (i64, i64)>,
+}
+
+impl TradeIdTracker {
+    /// Returns the gap in front of `first_id..=last_id`, if any; repeated or
+    /// older ids are ignored.
+    pub fn observe(
+        &mut self,
+        symbol: &str,
+        first_id: i64,
+        last_id: i64,
+        event_time_ms: i64,
+    ) -> Option<TradeIdGap> {
+        let previous = self.last.get(symbol).copied();
+        if previous.is_some_and(|(seen_id, _)| last_id <= seen_id) {
+            return None;
+        }
+        self.last
+            .insert(symbol.to_string(), (last_id, event_time_ms));
+        let (seen_id, seen_time_ms) = previous?;
+        (first_id > seen_id + 1).then(|| TradeIdGap {
+            symbol: symbol.to_string(),
+            from_id: seen_id + 1,
+            to_id: first_id - 1,
+            after_time_ms: seen_time_ms,
+            before_time_ms: event_time_ms,
+        })
+    }
+}
+
+/// One row of `/fapi/v1/aggTrades`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggTradeRow {
+    pub first_id: i64,
+    pub last_id: i64,
+    pub event_time_ms: i64,
+    pub price: f64,
+    pub qty: f64,
+    pub is_buyer_maker: bool,
+}
+
+/// Fetches the aggTrades between the two sides of `gap` and keeps the ones
+/// inside its trade id range.
+pub fn fetch_gap_trades(http: &Client, gap: &TradeIdGap) -> Result<Vec<AggTradeRow>> {
+    let url = format!(
+        "https://fapi.binance.com/fapi/v1/aggTrades?symbol={}&startTime={}&endTime={}&limit={MAX_TRADE_BACKFILL_ROWS}",
+        gap.symbol, gap.after_time_ms, gap.before_time_ms
+    );
+    let value = http
+        .get(&url)
+        .send()
+        .with_context(|| format!("failed to fetch aggTrades for {}", gap.symbol))?
+        .error_for_status()
+        .with_context(|| format!("aggTrades HTTP status error for {}", gap.symbol))?
+        .json::<Value>()
+        .with_context(|| format!("failed to decode aggTrades for {}", gap.symbol))?;
+    let rows = parse_agg_trades(&value)
+        .with_context(|| format!("aggTrades response for {} is malformed", gap.symbol))?;
+    Ok(rows
+        .into_iter()
+        .filter(|row| row.first_id >= gap.from_id && row.last_id <= gap.to_id)
+        .collect())
+}
+
+/// Example:
+/// - `[{"a":26129,"p":"0.01633102","q":"4.70443515","f":27781,"l":27781,"T":1498793709153,"m":true}]`
+pub fn parse_agg_trades(value: &Value) -> Option<Vec<AggTradeRow>> {
+    let number = |row: &Value, key: &str| match row.get(key)? {
+        Value::String(raw) => raw.trim().parse::<f64>().ok(),
+        other => other.as_f64(),
+    };
+    value
+        .as_array()?
+        .iter()
+        .map(|row| {
+            Some(AggTradeRow {
+                first_id: row.get("f")?.as_i64()?,
+                last_id: row.get("l")?.as_i64()?,
+                event_time_ms: row.get("T")?.as_i64()?,
+                price: number(row, "p")?,
+                qty: number(row, "q")?,
+                is_buyer_maker: row.get("m")?.as_bool()?,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,130 @@
+use crate::dataset::types::DerivedKlineRow;
+
+pub const DEFAULT_EWMA_LAMBDA: f64 = 0.94;
+pub const DEFAULT_GARCH_ALPHA: f64 = 0.08;
+pub const DEFAULT_GARCH_BETA: f64 = 0.90;
+const MS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0 * 1_000.0;
+
+/// Variance recursion applied to log returns on each candle close.
+///
+/// GARCH is variance-targeted: `omega = (1 - alpha - beta) * sample_var`,
+/// so the long-run level follows the observed bars at any interval.
+///
+/// Example:
+/// - `Ewma { lambda: 0.94 }` -> `var = 0.94 * var + 0.06 * r^2`
+/// - `Garch { alpha, beta }` -> `var = omega + alpha * r^2 + beta * var`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VolatilityModel {
+    Ewma { lambda: f64 },
+    Garch { alpha: f64, beta: f64 },
+}
+
+impl Default for VolatilityModel {
+    fn default() -> Self {
+        Self::Ewma {
+            lambda: DEFAULT_EWMA_LAMBDA,
+        }
+    }
+}
+
+impl VolatilityModel {
+    pub fn garch() -> Self {
+        Self::Garch {
+            alpha: DEFAULT_GARCH_ALPHA,
+            beta: DEFAULT_GARCH_BETA,
+        }
+    }
+
+    /// Reads `SANDBOX_QUANT_VOLATILITY_MODEL` (`ewma` or `garch`).
+    pub fn from_env() -> Self {
+        match std::env::var("SANDBOX_QUANT_VOLATILITY_MODEL")
+            .ok()
+            .map(|value| value.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("garch") => Self::garch(),
+            _ => Self::default(),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Ewma { .. } => "ewma",
+            Self::Garch { .. } => "garch",
+        }
+    }
+
+    fn next_variance(&self, variance: f64, squared_return: f64, sample_variance: f64) -> f64 {
+        match *self {
+            Self::Ewma { lambda } => lambda * variance + (1.0 - lambda) * squared_return,
+            Self::Garch { alpha, beta } => {
+                let omega = (1.0 - alpha - beta).max(0.0) * sample_variance;
+                omega + alpha * squared_return + beta * variance
+            }
+        }
+    }
+}
+
+/// Per-bar volatility estimate for one instrument, updated on candle close.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolatilityEstimator {
+    model: VolatilityModel,
+    last_close: Option<f64>,
+    variance: Option<f64>,
+    bar_ms: Option<i64>,
+    samples: usize,
+    sum_squared_returns: f64,
+}
+
+impl VolatilityEstimator {
+    pub fn new(model: VolatilityModel) -> Self {
+        Self {
+            model,
+            last_close: None,
+            variance: None,
+            bar_ms: None,
+            samples: 0,
+            sum_squared_returns: 0.0,
+        }
+    }
+
+    pub fn on_candle_close(&mut self, candle: &DerivedKlineRow) {
+        if candle.close <= f64::EPSILON || !candle.close.is_finite() {
+            return;
+        }
+        let bar_ms = candle.close_time_ms - candle.open_time_ms + 1;
+        if bar_ms > 0 {
+            self.bar_ms = Some(bar_ms);
+        }
+        if let Some(last_close) = self.last_close {
+            let log_return = (candle.close / last_close).ln();
+            let squared = log_return * log_return;
+            self.samples += 1;
+            self.sum_squared_returns += squared;
+            let sample_variance = self.sum_squared_returns / self.samples as f64;
+            self.variance = Some(match self.variance {
+                Some(variance) => self.model.next_variance(variance, squared, sample_variance),
+                None => squared,
+            });
+        }
+        self.last_close = Some(candle.close);
+    }
+
+    /// Standard deviation of one bar's log return.
+    pub fn sigma(&self) -> Option<f64> {
+        self.variance.map(f64::sqrt)
+    }
+
+    /// Sigma scaled to one year from the observed bar length.
+    ///
+    /// Example:
+    /// - 1m bars with per-bar sigma `0.001` -> about `0.725` (72.5%)
+    pub fn annualized_sigma(&self) -> Option<f64> {
+        let bars_per_year = MS_PER_YEAR / self.bar_ms? as f64;
+        Some(self.sigma()? * bars_per_year.sqrt())
+    }
+
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::app::credentials::mask_api_key;
+use crate::error::storage_error::StorageError;
+
+/// Most recent in-memory events written to `events.jsonl`.
+pub const DEFAULT_BUNDLE_EVENTS: usize = 500;
+/// Env prefixes copied into `config.json`; everything else is left out.
+const CONFIG_PREFIXES: [&str; 3] = ["SANDBOX_QUANT_", "BINANCE_", "RUST_LOG"];
+/// Key fragments whose values never leave the host in full.
+const SECRET_MARKERS: [&str; 6] = [
+    "SECRET",
+    "KEY",
+    "TOKEN",
+    "PASSWORD",
+    "PASSPHRASE",
+    "WEBHOOK",
+];
+/// Query parameters whose values never leave the host.
+const SECRET_QUERY_PARAMS: [&str; 3] = ["signature", "apiKey", "listenKey"];
+
+/// Example:
+/// - `var/diagnostics-20260412-093015.zip`
+pub fn default_bundle_path(now: DateTime<Utc>) -> String {
+    format!("var/diagnostics-{}.zip", now.format("%Y%m%d-%H%M%S"))
+}
+
+/// The app's own settings from `vars`, with secrets masked.
+///
+/// API keys keep their last four characters like the rotation log;
+/// other secrets and URL passwords are replaced outright.
+///
+/// Example:
+/// - `BINANCE_API_SECRET=abc...` -> `<redacted>`
+/// - `BINANCE_API_KEY=vmPU...Eh8A` -> `****Eh8A`
+/// - `SANDBOX_QUANT_PG_URL=postgres://quant:pw@db/market` -> `postgres://quant:****@db/market`
+pub fn redacted_config(
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> BTreeMap<String, String> {
+    vars.into_iter()
+        .filter(|(key, _)| CONFIG_PREFIXES.iter().any(|prefix| key.starts_with(prefix)))
+        .map(|(key, value)| {
+            let value = redact_value(&key, &value);
+            (key, value)
+        })
+        .collect()
+}
+
+fn redact_value(key: &str, value: &str) -> String {
+    let upper = key.to_ascii_uppercase();
+    if upper.ends_with("API_KEY") {
+        return mask_api_key(value);
+    }
+    if SECRET_MARKERS.iter().any(|marker| upper.contains(marker)) {
+        return "<redacted>".to_string();
+    }
+    match url::Url::parse(value) {
+        Ok(mut url) if url.password().is_some() => {
+            let _ = url.set_password(Some("****"));
+            url.to_string()
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// `text` with the secrets of every URL query in it replaced, e.g. a
+/// signed request quoted in a logged error.
+///
+/// `signature`, `apiKey` and `listenKey` are always replaced; `timestamp`
+/// only in a signed query, where it would pin down the signed request.
+///
+/// Example:
+/// - `/fapi/v1/order?symbol=BTCUSDT&timestamp=1712&signature=9f3a` ->
+///   `/fapi/v1/order?symbol=BTCUSDT&timestamp=<redacted>&signature=<redacted>`
+pub fn redact_query_secrets(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('?') {
+        let (before, after) = rest.split_at(start + 1);
+        redacted.push_str(before);
+        let end = after
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | '#' | ')' | '>'))
+            .unwrap_or(after.len());
+        let (query, tail) = after.split_at(end);
+        redacted.push_str(&redact_query(query));
+        rest = tail;
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+fn redact_query(query: &str) -> String {
+    let signed = query.split('&').any(|pair| pair.starts_with("signature="));
+    query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _))
+                if SECRET_QUERY_PARAMS.contains(&name) || (signed && name == "timestamp") =>
+            {
+                format!("{name}=<redacted>")
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Files collected for a bug report, written out as one zip.
+///
+/// Text entries pass through `redact_query_secrets`, so signed URLs quoted
+/// in events or errors never reach the zip.
+///
+/// Example:
+/// - `version.json`, `config.json`, `session.json`, `network.json`
+/// - `events.jsonl` with the last 500 events
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiagnosticsBundle {
+    entries: Vec<(String, Vec<u8>)>,
+}
+
+impl DiagnosticsBundle {
+    pub fn add(&mut self, name: impl Into<String>, bytes: impl Into<Vec<u8>>) {
+        let bytes = match String::from_utf8(bytes.into()) {
+            Ok(text) => redact_query_secrets(&text).into_bytes(),
+            Err(error) => error.into_bytes(),
+        };
+        self.entries.push((name.into(), bytes));
+    }
+
+    pub fn add_json(&mut self, name: impl Into<String>, value: &serde_json::Value) {
+        let bytes = serde_json::to_vec_pretty(value).unwrap_or_default();
+        self.add(name, bytes);
+    }
+
+    /// Copies a file from disk; a missing file is skipped.
+    pub fn add_file(&mut self, name: impl Into<String>, path: &Path) -> bool {
+        match fs::read(path) {
+            Ok(bytes) => {
+                self.add(name, bytes);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Writes the zip and returns its size in bytes.
+    pub fn write(&self, path: &Path) -> Result<u64, StorageError> {
+        let to_storage_error =
+            |error: &dyn std::fmt::Display| StorageError::WriteFailedWithContext {
+                message: error.to_string(),
+            };
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|error| to_storage_error(&error))?;
+        }
+        let file = fs::File::create(path).map_err(|error| to_storage_error(&error))?;
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        for (name, bytes) in &self.entries {
+            zip.start_file(name.as_str(), options)
+                .map_err(|error| to_storage_error(&error))?;
+            zip.write_all(bytes)
+                .map_err(|error| to_storage_error(&error))?;
+        }
+        let file = zip.finish().map_err(|error| to_storage_error(&error))?;
+        file.metadata()
+            .map(|metadata| metadata.len())
+            .map_err(|error| to_storage_error(&error))
+    }
+}
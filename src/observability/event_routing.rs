@@ -0,0 +1,203 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+
+use crate::storage::models::EventRecord;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Where a routed event goes besides the in-memory log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogSink {
+    /// The operator event file (`SANDBOX_QUANT_EVENT_LOG_PATH`).
+    File,
+    /// A notice line printed under the next shell output.
+    Notifier,
+}
+
+impl LogSink {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Notifier => "notifier",
+        }
+    }
+}
+
+/// Sinks and minimum level for one event domain.
+///
+/// An empty sink set keeps the domain in memory only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainRoute {
+    pub sinks: BTreeSet<LogSink>,
+    pub level: LogLevel,
+}
+
+impl Default for DomainRoute {
+    fn default() -> Self {
+        Self {
+            sinks: BTreeSet::from([LogSink::File]),
+            level: LogLevel::Debug,
+        }
+    }
+}
+
+impl DomainRoute {
+    /// Parses `file+notifier:info`, `file:warn` or `memory`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (sinks, level) = match raw.split_once(':') {
+            Some((sinks, level)) => (sinks, LogLevel::parse(level)?),
+            None => (raw, LogLevel::Debug),
+        };
+        let mut parsed = BTreeSet::new();
+        for sink in sinks.split('+').map(str::trim) {
+            match sink.to_ascii_lowercase().as_str() {
+                "file" => {
+                    parsed.insert(LogSink::File);
+                }
+                "notifier" | "notify" => {
+                    parsed.insert(LogSink::Notifier);
+                }
+                "memory" => {}
+                _ => return None,
+            }
+        }
+        Some(Self {
+            sinks: parsed,
+            level,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        let sinks = if self.sinks.is_empty() {
+            "memory".to_string()
+        } else {
+            self.sinks
+                .iter()
+                .map(|sink| sink.as_str())
+                .collect::<Vec<_>>()
+                .join("+")
+        };
+        format!("{sinks}:{}", self.level.as_str())
+    }
+}
+
+/// Per-domain routing for operator events.
+///
+/// Every record stays in `EventLog::records` because shell output is
+/// rendered from it; routes only decide what is persisted or surfaced.
+///
+/// Example:
+/// - `execution=file+notifier:info;market_data=file:warn;config=memory`
+/// - an `app.market_data.synthetic_tick` (debug) stays in memory only
+/// - an `app.execution.deferred_failed` (error) is written and shown as a notice
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventRouting {
+    pub routes: BTreeMap<String, DomainRoute>,
+}
+
+impl EventRouting {
+    /// Reads:
+    /// - `SANDBOX_QUANT_EVENT_ROUTES` (e.g. `execution=file+notifier:info;market_data=file:warn`)
+    ///
+    /// Domains without a route keep the default of `file:debug`.
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        Self::parse(&var("SANDBOX_QUANT_EVENT_ROUTES").unwrap_or_default())
+    }
+
+    /// Malformed entries are skipped.
+    pub fn parse(raw: &str) -> Self {
+        let routes = raw
+            .split(';')
+            .filter_map(|entry| entry.split_once('='))
+            .filter(|(domain, _)| !domain.trim().is_empty())
+            .filter_map(|(domain, route)| {
+                DomainRoute::parse(route).map(|route| (domain.trim().to_ascii_lowercase(), route))
+            })
+            .collect();
+        Self { routes }
+    }
+
+    pub fn route(&self, kind: &str) -> DomainRoute {
+        self.routes
+            .get(event_domain(kind))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Sinks that should receive `record`; empty below the domain level.
+    pub fn sinks_for(&self, record: &EventRecord) -> BTreeSet<LogSink> {
+        let route = self.route(&record.kind);
+        if event_level(&record.kind) < route.level {
+            return BTreeSet::new();
+        }
+        route.sinks
+    }
+}
+
+/// Example:
+/// - `app.execution.deferred_failed` -> `execution`
+pub fn event_domain(kind: &str) -> &str {
+    let mut segments = kind.split('.');
+    match (segments.next(), segments.next()) {
+        (Some(_), Some(domain)) => domain,
+        (Some(domain), None) => domain,
+        _ => "",
+    }
+}
+
+/// Severity implied by the event name.
+///
+/// Example:
+/// - `*_failed` -> `error`
+/// - `*_deferred`, `*_abandoned`, `*_exhausted` -> `warn`
+/// - `*_tick`, `*_checked`, `*_refreshed` -> `debug`
+pub fn event_level(kind: &str) -> LogLevel {
+    let name = kind.rsplit('.').next().unwrap_or(kind);
+    if name.contains("failed") || name.contains("error") {
+        LogLevel::Error
+    } else if ["deferred", "abandoned", "exhausted", "rejected", "stale"]
+        .iter()
+        .any(|marker| name.ends_with(marker))
+    {
+        LogLevel::Warn
+    } else if ["tick", "checked", "refreshed"]
+        .iter()
+        .any(|marker| name.contains(marker))
+    {
+        LogLevel::Debug
+    } else {
+        LogLevel::Info
+    }
+}
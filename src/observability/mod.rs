@@ -1 +1,5 @@
+pub mod diagnostics_bundle;
+pub mod event_routing;
 pub mod logging;
+pub mod rest_latency;
+pub mod self_metrics;
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::error::exchange_error::ExchangeError;
+
+/// Upper bounds of the latency buckets; the last bucket is open-ended.
+pub const LATENCY_BUCKETS_MS: [u64; 7] = [25, 50, 100, 250, 500, 1_000, 2_500];
+
+/// REST call families timed by `MeteredTransport`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RestEndpoint {
+    /// Balances, positions, open orders and the margin ratio.
+    Account,
+    /// Order submits and cancels.
+    Order,
+    /// Realized PnL and funding from the income history.
+    Income,
+    Price,
+    Depth,
+    ExchangeInfo,
+    Commission,
+    Klines,
+    /// Simple Earn positions, subscriptions and redemptions.
+    Earn,
+}
+
+impl RestEndpoint {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Account => "account",
+            Self::Order => "order",
+            Self::Income => "income",
+            Self::Price => "price",
+            Self::Depth => "depth",
+            Self::ExchangeInfo => "exchange_info",
+            Self::Commission => "commission",
+            Self::Klines => "klines",
+            Self::Earn => "earn",
+        }
+    }
+}
+
+/// Call, error and latency counters for one endpoint.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndpointLatency {
+    pub calls: u64,
+    pub errors: u64,
+    /// Subset of `errors` that never got a response.
+    pub timeouts: u64,
+    /// One count per `LATENCY_BUCKETS_MS` bound plus the overflow bucket.
+    pub buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    pub total_ms: f64,
+    pub max_ms: f64,
+}
+
+impl EndpointLatency {
+    pub fn record(&mut self, elapsed: Duration, error: Option<&ExchangeError>) {
+        let elapsed_ms = elapsed.as_secs_f64() * 1_000.0;
+        self.calls += 1;
+        if let Some(error) = error {
+            self.errors += 1;
+            if matches!(error, ExchangeError::NetworkTimeout) {
+                self.timeouts += 1;
+            }
+        }
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| elapsed_ms <= *bound as f64)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[bucket] += 1;
+        self.total_ms += elapsed_ms;
+        self.max_ms = self.max_ms.max(elapsed_ms);
+    }
+
+    pub fn mean_ms(&self) -> Option<f64> {
+        (self.calls > 0).then(|| self.total_ms / self.calls as f64)
+    }
+
+    /// Bucket bound holding the nearest-rank percentile; `max_ms` when it
+    /// falls in the overflow bucket.
+    ///
+    /// Example:
+    /// - 19 calls under `100ms`, 1 call at `700ms`, `p=95` -> `100`
+    pub fn percentile_ms(&self, p: f64) -> Option<f64> {
+        if self.calls == 0 {
+            return None;
+        }
+        let rank = ((p / 100.0) * self.calls as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Some(
+                    LATENCY_BUCKETS_MS
+                        .get(index)
+                        .map_or(self.max_ms, |bound| *bound as f64),
+                );
+            }
+        }
+        Some(self.max_ms)
+    }
+
+    pub fn snapshot(&self, endpoint: RestEndpoint) -> EndpointLatencySnapshot {
+        EndpointLatencySnapshot {
+            endpoint: endpoint.as_str(),
+            calls: self.calls,
+            errors: self.errors,
+            timeouts: self.timeouts,
+            mean_ms: self.mean_ms(),
+            p95_ms: self.percentile_ms(95.0),
+            max_ms: (self.calls > 0).then_some(self.max_ms),
+            buckets: self.buckets.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EndpointLatencySnapshot {
+    pub endpoint: &'static str,
+    pub calls: u64,
+    pub errors: u64,
+    pub timeouts: u64,
+    pub mean_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    pub buckets: Vec<u64>,
+}
+
+/// Per-endpoint REST counters, shared between the transport that records
+/// them and the app that reports them.
+///
+/// Slow calls on one endpoint point at Binance; timeouts spread across
+/// every endpoint point at the network.
+#[derive(Debug, Default)]
+pub struct RestLatencyStats {
+    endpoints: Mutex<BTreeMap<RestEndpoint, EndpointLatency>>,
+}
+
+impl RestLatencyStats {
+    pub fn record(&self, endpoint: RestEndpoint, elapsed: Duration, error: Option<&ExchangeError>) {
+        self.endpoints
+            .lock()
+            .expect("lock rest latency")
+            .entry(endpoint)
+            .or_default()
+            .record(elapsed, error);
+    }
+
+    pub fn endpoint(&self, endpoint: RestEndpoint) -> Option<EndpointLatency> {
+        self.endpoints
+            .lock()
+            .expect("lock rest latency")
+            .get(&endpoint)
+            .cloned()
+    }
+
+    /// Endpoints called at least once, in `RestEndpoint` order.
+    pub fn snapshot(&self) -> Vec<EndpointLatencySnapshot> {
+        self.endpoints
+            .lock()
+            .expect("lock rest latency")
+            .iter()
+            .map(|(endpoint, latency)| latency.snapshot(*endpoint))
+            .collect()
+    }
+}
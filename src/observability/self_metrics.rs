@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+/// Samples kept per timing unless `SelfMetrics::set_sample_capacity` lowers it.
+pub const SAMPLE_CAPACITY: usize = 256;
+const TICK_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Loop lag p95 above this means commands or refreshes are stalling the host.
+pub const LOOP_LAG_WARN_MS: f64 = 250.0;
+/// Per-watch strategy tick p95 above this means too many watches per refresh.
+pub const STRATEGY_TICK_WARN_US: f64 = 50_000.0;
+
+/// Recent samples of one timing, kept in a bounded ring.
+#[derive(Debug, Clone, PartialEq)]
+struct SampleWindow {
+    capacity: usize,
+    samples: VecDeque<f64>,
+}
+
+impl Default for SampleWindow {
+    fn default() -> Self {
+        Self {
+            capacity: SAMPLE_CAPACITY,
+            samples: VecDeque::new(),
+        }
+    }
+}
+
+impl SampleWindow {
+    fn push(&mut self, value: f64) {
+        if self.samples.len() >= self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        let excess = self.samples.len().saturating_sub(self.capacity);
+        self.samples.drain(..excess);
+    }
+
+    fn last(&self) -> Option<f64> {
+        self.samples.back().copied()
+    }
+
+    fn max(&self) -> Option<f64> {
+        self.samples.iter().copied().reduce(f64::max)
+    }
+
+    /// Nearest-rank percentile.
+    ///
+    /// Example:
+    /// - samples `1..=20`, `p=95` -> `19`
+    fn percentile(&self, p: f64) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted = self.samples.iter().copied().collect::<Vec<_>>();
+        sorted.sort_by(f64::total_cmp);
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted.get(rank.saturating_sub(1)).copied()
+    }
+}
+
+/// Self-measured load of the operator host process.
+///
+/// - loop lag: how long one command kept the shell (or the daemon lock)
+///   busy, or how late the daemon supervisor tick fired
+/// - strategy tick: the refresh-time strategy work divided by active watches
+/// - ticks: one per active watch per refresh
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfMetrics {
+    started_at: Instant,
+    loop_lag_ms: SampleWindow,
+    strategy_tick_us: SampleWindow,
+    ticks: VecDeque<(Instant, usize)>,
+}
+
+impl Default for SelfMetrics {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            loop_lag_ms: SampleWindow::default(),
+            strategy_tick_us: SampleWindow::default(),
+            ticks: VecDeque::new(),
+        }
+    }
+}
+
+impl SelfMetrics {
+    /// Caps each timing's samples, dropping the oldest over it.
+    pub fn set_sample_capacity(&mut self, capacity: usize) {
+        self.loop_lag_ms.set_capacity(capacity);
+        self.strategy_tick_us.set_capacity(capacity);
+    }
+
+    /// Samples held at most across all timings.
+    pub fn sample_capacity(&self) -> usize {
+        self.loop_lag_ms.capacity + self.strategy_tick_us.capacity
+    }
+
+    /// Samples held across all timings.
+    pub fn sample_count(&self) -> usize {
+        self.loop_lag_ms.samples.len() + self.strategy_tick_us.samples.len()
+    }
+
+    pub fn record_loop_lag(&mut self, lag: Duration) {
+        self.loop_lag_ms.push(lag.as_secs_f64() * 1_000.0);
+    }
+
+    /// Records one refresh that ticked `watches` strategy watches in `elapsed`.
+    pub fn record_strategy_ticks(&mut self, watches: usize, elapsed: Duration, now: Instant) {
+        if watches == 0 {
+            return;
+        }
+        self.strategy_tick_us
+            .push(elapsed.as_secs_f64() * 1_000_000.0 / watches as f64);
+        self.ticks.push_back((now, watches));
+        while self
+            .ticks
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > TICK_RATE_WINDOW)
+        {
+            self.ticks.pop_front();
+        }
+    }
+
+    /// Watch ticks per second over the trailing minute.
+    pub fn ticks_per_sec(&self, now: Instant) -> f64 {
+        let window = now
+            .duration_since(self.started_at)
+            .min(TICK_RATE_WINDOW)
+            .as_secs_f64();
+        if window <= f64::EPSILON {
+            return 0.0;
+        }
+        let ticks = self
+            .ticks
+            .iter()
+            .filter(|(at, _)| now.duration_since(*at) <= TICK_RATE_WINDOW)
+            .map(|(_, watches)| watches)
+            .sum::<usize>();
+        ticks as f64 / window
+    }
+
+    pub fn snapshot(
+        &self,
+        now: Instant,
+        active_watches: usize,
+        strategy_symbols: usize,
+    ) -> SelfMetricsSnapshot {
+        SelfMetricsSnapshot {
+            rss_bytes: process_rss_bytes(),
+            uptime_secs: now.duration_since(self.started_at).as_secs(),
+            loop_lag_last_ms: self.loop_lag_ms.last(),
+            loop_lag_p95_ms: self.loop_lag_ms.percentile(95.0),
+            loop_lag_max_ms: self.loop_lag_ms.max(),
+            ticks_per_sec: self.ticks_per_sec(now),
+            strategy_tick_p95_us: self.strategy_tick_us.percentile(95.0),
+            strategy_tick_samples: self.strategy_tick_us.samples.len(),
+            active_watches,
+            strategy_symbols,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SelfMetricsSnapshot {
+    pub rss_bytes: Option<u64>,
+    pub uptime_secs: u64,
+    pub loop_lag_last_ms: Option<f64>,
+    pub loop_lag_p95_ms: Option<f64>,
+    pub loop_lag_max_ms: Option<f64>,
+    pub ticks_per_sec: f64,
+    pub strategy_tick_p95_us: Option<f64>,
+    pub strategy_tick_samples: usize,
+    pub active_watches: usize,
+    pub strategy_symbols: usize,
+}
+
+impl SelfMetricsSnapshot {
+    /// Names the measurements over their warn thresholds.
+    pub fn overloaded(&self) -> Vec<&'static str> {
+        let mut over = Vec::new();
+        if self
+            .loop_lag_p95_ms
+            .is_some_and(|lag| lag > LOOP_LAG_WARN_MS)
+        {
+            over.push("loop_lag");
+        }
+        if self
+            .strategy_tick_p95_us
+            .is_some_and(|tick| tick > STRATEGY_TICK_WARN_US)
+        {
+            over.push("strategy_tick");
+        }
+        over
+    }
+}
+
+/// Resident set size of this process; `None` where `/proc` is unavailable.
+pub fn process_rss_bytes() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let resident_pages = statm.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(resident_pages * page_size())
+}
+
+#[cfg(unix)]
+fn page_size() -> u64 {
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    u64::try_from(size).unwrap_or(4_096)
+}
+
+#[cfg(not(unix))]
+fn page_size() -> u64 {
+    4_096
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::position::PositionSnapshot;
+use crate::portfolio::asset_exposure::{base_asset, QUOTE_ASSETS};
+use crate::portfolio::snapshot::PortfolioStateSnapshot;
+
+/// History/position tag carried by adopted positions.
+pub const ADOPTED_POSITION_TAG: &str = "manual";
+
+/// A holding found at reconciliation that the app did not open.
+///
+/// Spot holdings come from non-quote balances and are quoted in `USDT`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalPosition {
+    pub instrument: Instrument,
+    pub market: Market,
+    pub signed_qty: f64,
+    /// Entry reported by the exchange; spot balances have none.
+    pub exchange_entry_price: Option<f64>,
+}
+
+/// An external holding taken over with an operator-provided entry price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdoptedPosition {
+    pub instrument: Instrument,
+    pub market: Market,
+    pub entry_price: f64,
+    pub adopted_at: DateTime<Utc>,
+}
+
+/// Positions and spot balances that are neither adopted nor traded by the app.
+///
+/// Example:
+/// - futures `ETHUSDT -1.0` opened on the exchange UI, spot balance `SOL=12`
+/// - app only traded `BTCUSDT`
+/// - external `ETHUSDT@futures -1.0`, `SOLUSDT@spot 12.0`
+pub fn detect_external_positions(
+    snapshot: &PortfolioStateSnapshot,
+    adopted: &BTreeMap<Instrument, AdoptedPosition>,
+    traded_by_app: impl Fn(&Instrument) -> bool,
+) -> Vec<ExternalPosition> {
+    let mut external = snapshot
+        .positions
+        .values()
+        .filter(|position| position.market != Market::Spot && !position.is_flat())
+        .map(|position| ExternalPosition {
+            instrument: position.instrument.clone(),
+            market: position.market,
+            signed_qty: position.signed_qty,
+            exchange_entry_price: position.entry_price,
+        })
+        .collect::<Vec<_>>();
+    for (asset, qty) in spot_holdings(snapshot) {
+        let instrument = Instrument::new(format!("{asset}USDT"));
+        if !snapshot.positions.contains_key(&instrument) {
+            external.push(ExternalPosition {
+                instrument,
+                market: Market::Spot,
+                signed_qty: qty,
+                exchange_entry_price: None,
+            });
+        }
+    }
+    external.retain(|position| {
+        !adopted.contains_key(&position.instrument) && !traded_by_app(&position.instrument)
+    });
+    external
+}
+
+/// Re-applies adopted entries to a fresh snapshot.
+///
+/// Futures and options keep the exchange quantity with the adopted entry;
+/// spot holdings become a spot position sized from the base balance. An
+/// adoption whose holding is gone is dropped.
+pub fn overlay_adopted_positions(
+    snapshot: &mut PortfolioStateSnapshot,
+    adopted: &mut BTreeMap<Instrument, AdoptedPosition>,
+) {
+    let spot = spot_holdings(snapshot);
+    adopted.retain(|instrument, adoption| match adoption.market {
+        Market::Spot => {
+            let Some(qty) = base_asset(instrument).and_then(|asset| spot.get(asset)) else {
+                return false;
+            };
+            snapshot.positions.insert(
+                instrument.clone(),
+                PositionSnapshot {
+                    instrument: instrument.clone(),
+                    market: Market::Spot,
+                    signed_qty: *qty,
+                    entry_price: Some(adoption.entry_price),
+                },
+            );
+            true
+        }
+        market => match snapshot
+            .positions
+            .get_mut(instrument)
+            .filter(|position| position.market == market && !position.is_flat())
+        {
+            Some(position) => {
+                position.entry_price = Some(adoption.entry_price);
+                true
+            }
+            None => false,
+        },
+    });
+}
+
+fn spot_holdings(snapshot: &PortfolioStateSnapshot) -> BTreeMap<String, f64> {
+    let mut holdings: BTreeMap<String, f64> = BTreeMap::new();
+    for balance in snapshot.balances.iter().filter(|balance| {
+        !QUOTE_ASSETS.contains(&balance.asset.as_str()) && balance.total() > f64::EPSILON
+    }) {
+        *holdings.entry(balance.asset.clone()).or_default() += balance.total();
+    }
+    holdings
+}
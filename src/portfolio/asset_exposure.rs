@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::portfolio::snapshot::PortfolioStateSnapshot;
+
+pub const QUOTE_ASSETS: &[&str] = &["USDT", "USDC", "FDUSD", "BUSD"];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssetExposure {
+    pub asset: String,
+    pub spot_qty: f64,
+    pub futures_qty: f64,
+    pub price: Option<f64>,
+    pub instruments: Vec<Instrument>,
+}
+
+impl AssetExposure {
+    pub fn net_qty(&self) -> f64 {
+        self.spot_qty + self.futures_qty
+    }
+
+    pub fn net_exposure_usdt(&self) -> Option<f64> {
+        self.price.map(|price| self.net_qty() * price)
+    }
+}
+
+/// Splits a quote-denominated symbol into its base asset.
+///
+/// Example:
+/// - `BTCUSDT` -> `BTC`
+/// - `ETHUSDC` -> `ETH`
+pub fn base_asset(instrument: &Instrument) -> Option<&str> {
+    QUOTE_ASSETS.iter().find_map(|quote| {
+        instrument
+            .0
+            .strip_suffix(quote)
+            .filter(|base| !base.is_empty())
+    })
+}
+
+/// Consolidates spot holdings and futures positions that share a base asset.
+///
+/// Example:
+/// - spot balance `BTC=0.5`, futures `BTCUSDT signed_qty=-0.2`
+/// - consolidated `BTC net_qty=0.3`
+///
+/// Options are excluded because their quantity is not a delta in the base asset.
+/// A spot position (an adopted holding) already carries its balance, so that
+/// balance is not counted again.
+pub fn aggregate_base_asset_exposure(
+    snapshot: &PortfolioStateSnapshot,
+    price_for: impl Fn(&Instrument) -> Option<f64>,
+) -> BTreeMap<String, AssetExposure> {
+    let mut aggregated: BTreeMap<String, AssetExposure> = BTreeMap::new();
+    let mut spot_position_assets = Vec::new();
+
+    for position in snapshot
+        .positions
+        .values()
+        .filter(|position| position.market != Market::Options && !position.is_flat())
+    {
+        let Some(asset) = base_asset(&position.instrument) else {
+            continue;
+        };
+        let entry = entry_for(&mut aggregated, asset);
+        match position.market {
+            Market::Spot => {
+                entry.spot_qty += position.signed_qty;
+                spot_position_assets.push(asset.to_string());
+            }
+            _ => entry.futures_qty += position.signed_qty,
+        }
+        if !entry.instruments.contains(&position.instrument) {
+            entry.instruments.push(position.instrument.clone());
+        }
+        if entry.price.is_none() {
+            entry.price = price_for(&position.instrument).or(position.entry_price);
+        }
+    }
+
+    for balance in snapshot.balances.iter().filter(|balance| {
+        !QUOTE_ASSETS.contains(&balance.asset.as_str())
+            && balance.total().abs() > f64::EPSILON
+            && !spot_position_assets.contains(&balance.asset)
+    }) {
+        let entry = entry_for(&mut aggregated, &balance.asset);
+        entry.spot_qty += balance.total();
+        if entry.price.is_none() {
+            entry.price = price_for(&Instrument::new(format!("{}USDT", balance.asset)));
+        }
+    }
+
+    aggregated
+}
+
+fn entry_for<'a>(
+    aggregated: &'a mut BTreeMap<String, AssetExposure>,
+    asset: &str,
+) -> &'a mut AssetExposure {
+    aggregated
+        .entry(asset.to_string())
+        .or_insert_with(|| AssetExposure {
+            asset: asset.to_string(),
+            ..AssetExposure::default()
+        })
+}
@@ -0,0 +1,99 @@
+use std::env;
+
+use crate::domain::balance::BalanceSnapshot;
+use crate::exchange::types::{EarnAccount, EarnTransfer, EarnTransferKind};
+
+/// Quote asset kept in flexible savings between trades.
+pub const EARN_ASSET: &str = "USDT";
+
+/// Smaller sweeps and redemptions are skipped; the venue rejects dust.
+pub const EARN_MIN_TRANSFER_USDT: f64 = 1.0;
+
+/// Extra redeemed on top of an order's notional to cover fees and drift.
+pub const EARN_REDEEM_HEADROOM: f64 = 0.01;
+
+/// Whether flexible savings count towards equity, and when idle quote is
+/// swept into them.
+///
+/// Example:
+/// - `SANDBOX_QUANT_EARN_SWEEP_ABOVE_USDT=2000`, `1800` idle and `5000` saved
+/// - equity counts all `6800`; nothing is swept until idle passes `2000`
+/// - a spot buy needing `2500` first redeems `700`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EarnConfig {
+    pub tracking: bool,
+    /// Idle quote kept in spot; anything above it is subscribed.
+    pub sweep_above_usdt: Option<f64>,
+}
+
+impl EarnConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_EARN_TRACKING` (`true` counts flexible savings in equity)
+    /// - `SANDBOX_QUANT_EARN_SWEEP_ABOVE_USDT` (e.g. `2000`, also turns tracking on)
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let sweep_above_usdt = var("SANDBOX_QUANT_EARN_SWEEP_ABOVE_USDT")
+            .and_then(|raw| raw.trim().parse::<f64>().ok())
+            .filter(|threshold| threshold.is_finite() && *threshold >= 0.0);
+        let tracking = var("SANDBOX_QUANT_EARN_TRACKING")
+            .map(|raw| matches!(raw.trim(), "1" | "true" | "yes" | "on"))
+            .unwrap_or(false);
+        Self {
+            tracking: tracking || sweep_above_usdt.is_some(),
+            sweep_above_usdt,
+        }
+    }
+
+    /// Example:
+    /// - `off`, `tracking`, `sweep>2000`
+    pub fn label(&self) -> String {
+        match (self.tracking, self.sweep_above_usdt) {
+            (_, Some(threshold)) => format!("sweep>{threshold}"),
+            (true, None) => "tracking".to_string(),
+            (false, None) => "off".to_string(),
+        }
+    }
+
+    /// Subscribes idle quote above the threshold.
+    pub fn plan_sweep(&self, account: &EarnAccount) -> Option<EarnTransfer> {
+        let excess = account.idle - self.sweep_above_usdt?;
+        (excess >= EARN_MIN_TRANSFER_USDT).then(|| EarnTransfer {
+            kind: EarnTransferKind::Subscribe,
+            asset: EARN_ASSET.to_string(),
+            amount: excess,
+        })
+    }
+}
+
+/// Redeems what an order needs beyond the idle balance, capped at what is
+/// saved.
+///
+/// Example:
+/// - idle `1800`, saved `5000`, order needs `2500` -> redeem `700`
+pub fn plan_redeem(account: &EarnAccount, required_usdt: f64) -> Option<EarnTransfer> {
+    let shortfall = (required_usdt - account.idle).min(account.saved());
+    (shortfall >= EARN_MIN_TRANSFER_USDT).then(|| EarnTransfer {
+        kind: EarnTransferKind::Redeem,
+        asset: EARN_ASSET.to_string(),
+        amount: shortfall,
+    })
+}
+
+/// Savings as locked balances, so equity counts them while orders do not
+/// treat them as free.
+pub fn earn_balances(account: &EarnAccount) -> Vec<BalanceSnapshot> {
+    account
+        .positions
+        .iter()
+        .filter(|position| position.amount > f64::EPSILON)
+        .map(|position| BalanceSnapshot {
+            asset: position.asset.clone(),
+            free: 0.0,
+            locked: position.amount,
+        })
+        .collect()
+}
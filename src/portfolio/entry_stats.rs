@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::instrument::Instrument;
+use crate::domain::position::PositionSnapshot;
+
+/// Matches the backtest default of 0.05% per side.
+pub const DEFAULT_TAKER_FEE_RATE: f64 = 0.0005;
+
+/// Reads:
+/// - `SANDBOX_QUANT_TAKER_FEE_RATE` (per side, e.g. `0.0004`, default `0.0005`)
+pub fn taker_fee_rate_from_env() -> f64 {
+    taker_fee_rate_from_vars(|key| std::env::var(key).ok())
+}
+
+/// `taker_fee_rate_from_env` with the variable looked up through `var`.
+pub fn taker_fee_rate_from_vars(var: impl Fn(&str) -> Option<String>) -> f64 {
+    var("SANDBOX_QUANT_TAKER_FEE_RATE")
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|value| (0.0..0.1).contains(value))
+        .unwrap_or(DEFAULT_TAKER_FEE_RATE)
+}
+
+/// One increase of a position seen between two snapshots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryFill {
+    pub time: DateTime<Utc>,
+    pub qty: f64,
+    pub price: f64,
+}
+
+/// How one open position was built up.
+///
+/// Fills are inferred from successive snapshots: when the size grows, the
+/// added quantity is priced from the change in the exchange's average entry.
+///
+/// Example:
+/// - long `1 @ 100` at 12:00, then `+1 @ 110` at 12:30, checked at 13:00
+/// - `vwap_entry=105`
+/// - `twap_entry=102.5`: held at `100` for 30m and at `105` for 30m
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionEntryStats {
+    pub fills: Vec<EntryFill>,
+    /// `1.0` for long, `-1.0` for short.
+    direction: f64,
+    abs_qty: f64,
+    entry_price: f64,
+    /// Running entry price integrated over time, in price-seconds.
+    entry_area: f64,
+    last_update: DateTime<Utc>,
+}
+
+impl PositionEntryStats {
+    fn open(position: &PositionSnapshot, entry_price: f64, now: DateTime<Utc>) -> Self {
+        Self {
+            fills: vec![EntryFill {
+                time: now,
+                qty: position.abs_qty(),
+                price: entry_price,
+            }],
+            direction: position.signed_qty.signum(),
+            abs_qty: position.abs_qty(),
+            entry_price,
+            entry_area: 0.0,
+            last_update: now,
+        }
+    }
+
+    fn advance(&mut self, now: DateTime<Utc>) {
+        let held_secs = seconds_between(self.last_update, now);
+        self.entry_area += self.entry_price * held_secs;
+        self.last_update = now;
+    }
+
+    pub fn opened_at(&self) -> DateTime<Utc> {
+        self.fills
+            .first()
+            .map_or(self.last_update, |fill| fill.time)
+    }
+
+    /// Quantity-weighted average of the entry fills.
+    pub fn vwap_entry(&self) -> f64 {
+        let qty = self.fills.iter().map(|fill| fill.qty).sum::<f64>();
+        if qty <= f64::EPSILON {
+            return self.entry_price;
+        }
+        self.fills
+            .iter()
+            .map(|fill| fill.qty * fill.price)
+            .sum::<f64>()
+            / qty
+    }
+
+    /// Average entry price over the time the position has been held.
+    pub fn twap_entry(&self, now: DateTime<Utc>) -> f64 {
+        let total_secs = seconds_between(self.opened_at(), now);
+        if total_secs <= f64::EPSILON {
+            return self.entry_price;
+        }
+        let area = self.entry_area + self.entry_price * seconds_between(self.last_update, now);
+        area / total_secs
+    }
+
+    /// Exit price at which the position is flat after paying `fee_rate` on
+    /// both the entry and the exit.
+    ///
+    /// Example:
+    /// - long at `100` with `0.0005` per side -> `100.1001`
+    pub fn break_even_price(&self, fee_rate: f64) -> f64 {
+        if self.direction >= 0.0 {
+            self.entry_price * (1.0 + fee_rate) / (1.0 - fee_rate)
+        } else {
+            self.entry_price * (1.0 - fee_rate) / (1.0 + fee_rate)
+        }
+    }
+}
+
+/// Per-instrument entry statistics for the open positions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionEntryTracker {
+    /// Configured fallback for symbols the venue reports no commission for.
+    pub taker_fee_rate: f64,
+    venue_fee_rates: BTreeMap<Instrument, f64>,
+    stats: BTreeMap<Instrument, PositionEntryStats>,
+}
+
+impl Default for PositionEntryTracker {
+    fn default() -> Self {
+        Self {
+            taker_fee_rate: DEFAULT_TAKER_FEE_RATE,
+            venue_fee_rates: BTreeMap::new(),
+            stats: BTreeMap::new(),
+        }
+    }
+}
+
+impl PositionEntryTracker {
+    pub fn get(&self, instrument: &Instrument) -> Option<&PositionEntryStats> {
+        self.stats.get(instrument)
+    }
+
+    /// Taker fee used for the break-even estimate of `instrument`: the
+    /// account's commission on the venue when known, else the configured one.
+    pub fn fee_rate(&self, instrument: &Instrument) -> f64 {
+        self.venue_fee_rates
+            .get(instrument)
+            .copied()
+            .unwrap_or(self.taker_fee_rate)
+    }
+
+    pub fn set_venue_fee_rate(&mut self, instrument: Instrument, rate: f64) {
+        self.venue_fee_rates.insert(instrument, rate);
+    }
+
+    /// Drops the statistics so the next observation starts over.
+    pub fn forget(&mut self, instrument: &Instrument) {
+        self.stats.remove(instrument);
+    }
+
+    /// Folds a fresh set of open positions in at `now`.
+    ///
+    /// A new or flipped position starts over with one fill; a reduction
+    /// keeps the fills since the exchange entry price does not move.
+    pub fn observe(
+        &mut self,
+        positions: &BTreeMap<Instrument, PositionSnapshot>,
+        now: DateTime<Utc>,
+    ) {
+        self.stats.retain(|instrument, _| {
+            positions
+                .get(instrument)
+                .is_some_and(|position| !position.is_flat())
+        });
+        for (instrument, position) in positions.iter().filter(|(_, position)| !position.is_flat()) {
+            let Some(entry_price) = position.entry_price.filter(|price| *price > 0.0) else {
+                self.stats.remove(instrument);
+                continue;
+            };
+            let Some(stats) = self
+                .stats
+                .get_mut(instrument)
+                .filter(|stats| stats.direction == position.signed_qty.signum())
+            else {
+                self.stats.insert(
+                    instrument.clone(),
+                    PositionEntryStats::open(position, entry_price, now),
+                );
+                continue;
+            };
+            stats.advance(now);
+            let added_qty = position.abs_qty() - stats.abs_qty;
+            if added_qty > f64::EPSILON {
+                let implied_price = (entry_price * position.abs_qty()
+                    - stats.entry_price * stats.abs_qty)
+                    / added_qty;
+                stats.fills.push(EntryFill {
+                    time: now,
+                    qty: added_qty,
+                    price: if implied_price.is_finite() && implied_price > 0.0 {
+                        implied_price
+                    } else {
+                        entry_price
+                    },
+                });
+            }
+            stats.abs_qty = position.abs_qty();
+            stats.entry_price = entry_price;
+        }
+    }
+}
+
+fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    ((to - from).num_milliseconds().max(0) as f64) / 1_000.0
+}
@@ -1,3 +1,7 @@
+pub mod adoption;
+pub mod asset_exposure;
+pub mod earn;
+pub mod entry_stats;
 pub mod reconcile;
 pub mod snapshot;
 pub mod staleness;
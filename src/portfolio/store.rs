@@ -1,6 +1,19 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::balance::BalanceSnapshot;
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::order::{OpenOrder, OrderStatus};
+use crate::domain::position::PositionSnapshot;
+
 use crate::error::exchange_error::ExchangeError;
 use crate::exchange::facade::ExchangeFacade;
-use crate::exchange::types::AuthoritativeSnapshot;
+use crate::exchange::types::{AuthoritativeSnapshot, EarnAccount, FuturesLeverage};
+use crate::portfolio::adoption::{overlay_adopted_positions, AdoptedPosition, ExternalPosition};
+use crate::portfolio::earn::{earn_balances, EARN_ASSET};
+use crate::portfolio::entry_stats::PositionEntryTracker;
 use crate::portfolio::reconcile::apply_authoritative_snapshot;
 use crate::portfolio::snapshot::PortfolioStateSnapshot;
 use crate::portfolio::staleness::StalenessState;
@@ -9,6 +22,20 @@ use crate::portfolio::staleness::StalenessState;
 pub struct PortfolioStateStore {
     pub snapshot: PortfolioStateSnapshot,
     pub staleness: StalenessState,
+    /// Fill-level entry statistics inferred across snapshots.
+    pub entry_stats: PositionEntryTracker,
+    /// External holdings taken over with `/adopt`, re-applied on every snapshot.
+    pub adopted_positions: BTreeMap<Instrument, AdoptedPosition>,
+    /// Holdings seen at the last reconciliation that the app did not open.
+    pub external_positions: Vec<ExternalPosition>,
+    /// When the last exchange snapshot was applied.
+    pub refreshed_at: Option<DateTime<Utc>>,
+    /// Adds flexible savings to the balances on every exchange refresh.
+    pub earn_tracking: bool,
+    /// Savings seen at the last refresh; kept when a later lookup fails.
+    pub earn_account: Option<EarnAccount>,
+    /// Leverage and margin type the venue confirmed per futures symbol.
+    pub futures_leverage: BTreeMap<Instrument, FuturesLeverage>,
 }
 
 impl Default for PortfolioStateStore {
@@ -16,14 +43,113 @@ impl Default for PortfolioStateStore {
         Self {
             snapshot: PortfolioStateSnapshot::default(),
             staleness: StalenessState::Fresh,
+            entry_stats: PositionEntryTracker::default(),
+            adopted_positions: BTreeMap::new(),
+            external_positions: Vec::new(),
+            refreshed_at: None,
+            earn_tracking: false,
+            earn_account: None,
+            futures_leverage: BTreeMap::new(),
         }
     }
 }
 
 impl PortfolioStateStore {
     pub fn apply_snapshot(&mut self, snapshot: AuthoritativeSnapshot) {
+        self.apply_snapshot_at(snapshot, Utc::now());
+    }
+
+    pub fn apply_snapshot_at(&mut self, snapshot: AuthoritativeSnapshot, now: DateTime<Utc>) {
         self.snapshot = apply_authoritative_snapshot(snapshot);
+        overlay_adopted_positions(&mut self.snapshot, &mut self.adopted_positions);
         self.staleness = StalenessState::Fresh;
+        self.refreshed_at = Some(now);
+        self.entry_stats.observe(&self.snapshot.positions, now);
+    }
+
+    /// Takes over an external holding at `entry_price`.
+    ///
+    /// Entry statistics restart from the adopted entry so VWAP and break-even
+    /// do not mix in the exchange's figure.
+    pub fn adopt_position(
+        &mut self,
+        external: &ExternalPosition,
+        entry_price: f64,
+        now: DateTime<Utc>,
+    ) -> AdoptedPosition {
+        let adopted = AdoptedPosition {
+            instrument: external.instrument.clone(),
+            market: external.market,
+            entry_price,
+            adopted_at: now,
+        };
+        self.adopted_positions
+            .insert(adopted.instrument.clone(), adopted.clone());
+        overlay_adopted_positions(&mut self.snapshot, &mut self.adopted_positions);
+        self.external_positions
+            .retain(|position| position.instrument != adopted.instrument);
+        self.entry_stats.forget(&adopted.instrument);
+        self.entry_stats.observe(&self.snapshot.positions, now);
+        adopted
+    }
+
+    /// Replaces the order with the same client id, dropping it once it no
+    /// longer rests on the book.
+    pub fn apply_order_update(&mut self, order: OpenOrder) {
+        let orders = self
+            .snapshot
+            .open_orders
+            .entry(order.instrument.clone())
+            .or_default();
+        orders.retain(|open| open.client_order_id != order.client_order_id);
+        let instrument = order.instrument.clone();
+        if order.status == OrderStatus::Submitted {
+            orders.push(order);
+        }
+        if orders.is_empty() {
+            self.snapshot.open_orders.remove(&instrument);
+        }
+    }
+
+    /// Patches the assets and positions a pushed account update changed.
+    ///
+    /// Spot balances lead the merged snapshot and futures balances trail
+    /// it, so an asset held on both is matched from that end.
+    pub fn apply_balance_update(
+        &mut self,
+        market: Market,
+        balances: Vec<BalanceSnapshot>,
+        positions: Vec<PositionSnapshot>,
+        now: DateTime<Utc>,
+    ) {
+        for balance in balances {
+            let held = &mut self.snapshot.balances;
+            let slot = match market {
+                Market::Spot => held.iter_mut().find(|held| held.asset == balance.asset),
+                _ => held
+                    .iter_mut()
+                    .rev()
+                    .find(|held| held.asset == balance.asset),
+            };
+            match slot {
+                Some(slot) => *slot = balance,
+                None => held.push(balance),
+            }
+        }
+        if positions.is_empty() {
+            return;
+        }
+        for position in positions {
+            if position.is_flat() {
+                self.snapshot.positions.remove(&position.instrument);
+            } else {
+                self.snapshot
+                    .positions
+                    .insert(position.instrument.clone(), position);
+            }
+        }
+        overlay_adopted_positions(&mut self.snapshot, &mut self.adopted_positions);
+        self.entry_stats.observe(&self.snapshot.positions, now);
     }
 
     pub fn mark_market_data_stale(&mut self) {
@@ -46,6 +172,14 @@ impl PortfolioStateStore {
     ) -> Result<(), ExchangeError> {
         let snapshot = exchange.load_authoritative_snapshot()?;
         self.apply_snapshot(snapshot);
+        if self.earn_tracking {
+            if let Ok(account) = exchange.load_earn_account(EARN_ASSET) {
+                self.earn_account = account;
+            }
+            if let Some(account) = &self.earn_account {
+                self.snapshot.balances.extend(earn_balances(account));
+            }
+        }
         Ok(())
     }
 }
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::app::bootstrap::BinanceMode;
 use crate::error::storage_error::StorageError;
+use crate::storage::atomic_file::{read_checked, store_exists, write_checked};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RecorderCoordination {
@@ -18,6 +19,21 @@ impl Default for RecorderCoordination {
     }
 }
 
+/// A symbol whose stream tripped the recorder reconnect breaker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DegradedSymbol {
+    pub symbol: String,
+    /// End of the reconnect cool-down, in epoch milliseconds.
+    pub until_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DegradedSymbolFile {
+    mode: String,
+    degraded: Vec<DegradedSymbol>,
+    updated_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct StrategySymbolFile {
     mode: String,
@@ -52,23 +68,62 @@ impl RecorderCoordination {
                 message: error.to_string(),
             }
         })?;
-        atomic_write(self.strategy_symbols_path(mode), &json)
+        write_checked(&self.strategy_symbols_path(mode), &json)
     }
 
     pub fn strategy_symbols(&self, mode: BinanceMode) -> Result<Vec<String>, StorageError> {
         let path = self.strategy_symbols_path(mode);
-        if !path.exists() {
+        if !store_exists(&path) {
             return Ok(Vec::new());
         }
-        let bytes = fs::read(&path).map_err(|error| StorageError::WriteFailedWithContext {
-            message: error.to_string(),
-        })?;
-        let payload: StrategySymbolFile = serde_json::from_slice(&bytes).map_err(|error| {
+        let bytes = read_checked(&path)?;
+        let payload: StrategySymbolFile =
+            serde_json::from_slice(&bytes).map_err(|error| StorageError::ParseFailed {
+                path: path.display().to_string(),
+                message: error.to_string(),
+            })?;
+        Ok(normalize_symbols(payload.strategy_symbols))
+    }
+
+    pub fn sync_degraded_symbols(
+        &self,
+        mode: BinanceMode,
+        degraded: Vec<DegradedSymbol>,
+    ) -> Result<(), StorageError> {
+        let payload = DegradedSymbolFile {
+            mode: mode.as_str().to_string(),
+            degraded,
+            updated_at: Utc::now().to_rfc3339(),
+        };
+        let json = serde_json::to_vec_pretty(&payload).map_err(|error| {
             StorageError::WriteFailedWithContext {
                 message: error.to_string(),
             }
         })?;
-        Ok(normalize_symbols(payload.strategy_symbols))
+        write_checked(&self.degraded_symbols_path(mode), &json)
+    }
+
+    /// Symbols the recorder still reports as degraded at `now_ms`.
+    pub fn degraded_symbols(
+        &self,
+        mode: BinanceMode,
+        now_ms: i64,
+    ) -> Result<Vec<DegradedSymbol>, StorageError> {
+        let path = self.degraded_symbols_path(mode);
+        if !store_exists(&path) {
+            return Ok(Vec::new());
+        }
+        let bytes = read_checked(&path)?;
+        let payload: DegradedSymbolFile =
+            serde_json::from_slice(&bytes).map_err(|error| StorageError::ParseFailed {
+                path: path.display().to_string(),
+                message: error.to_string(),
+            })?;
+        Ok(payload
+            .degraded
+            .into_iter()
+            .filter(|degraded| degraded.until_ms > now_ms)
+            .collect())
     }
 
     pub fn db_path(&self, mode: BinanceMode) -> PathBuf {
@@ -84,6 +139,11 @@ impl RecorderCoordination {
         self.base_dir
             .join(format!("record-{}.strategy-symbols.json", mode.as_str()))
     }
+
+    fn degraded_symbols_path(&self, mode: BinanceMode) -> PathBuf {
+        self.base_dir
+            .join(format!("record-{}.degraded-symbols.json", mode.as_str()))
+    }
 }
 
 fn normalize_symbols(symbols: Vec<String>) -> Vec<String> {
@@ -96,18 +156,3 @@ fn normalize_symbols(symbols: Vec<String>) -> Vec<String> {
     normalized.dedup();
     normalized
 }
-
-fn atomic_write(path: PathBuf, bytes: &[u8]) -> Result<(), StorageError> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|error| StorageError::WriteFailedWithContext {
-            message: error.to_string(),
-        })?;
-    }
-    let tmp_path = path.with_extension("tmp");
-    fs::write(&tmp_path, bytes).map_err(|error| StorageError::WriteFailedWithContext {
-        message: error.to_string(),
-    })?;
-    fs::rename(&tmp_path, &path).map_err(|error| StorageError::WriteFailedWithContext {
-        message: error.to_string(),
-    })
-}
@@ -10,7 +10,7 @@ use std::time::Duration;
 
 use chrono::{DateTime, TimeZone, Utc};
 use duckdb::{params, Connection};
-use futures_util::StreamExt;
+use futures_util::{FutureExt, StreamExt};
 use serde::Deserialize;
 use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::Message;
@@ -19,14 +19,30 @@ use tracing::{error, warn};
 use crate::app::bootstrap::BinanceMode;
 use crate::dataset::query::{backtest_summary_for_path, metrics_for_path};
 use crate::dataset::schema::init_schema_for_path;
-use crate::dataset::types::{BacktestDatasetSummary, RecorderMetrics};
+use crate::dataset::types::{BacktestDatasetSummary, DerivedKlineRow, RecorderMetrics};
+use crate::domain::market::Market;
 use crate::error::storage_error::StorageError;
-use crate::record::coordination::RecorderCoordination;
+use crate::exchange::binance::chaos::{ChaosConfig, ChaosDice};
+use crate::market_data::binance_kline_backfill::{
+    fetch_reference_kline, KlineEndpoint, DEFAULT_BINANCE_BACKFILL_PRODUCT,
+};
+use crate::market_data::candle_consistency::{
+    compare_candles, CandleConsistencyCheck, CandleConsistencyConfig, LocalCandleAggregator,
+};
+use crate::market_data::open_interest::{fetch_open_interest, OpenInterestPollConfig};
+use crate::market_data::reconnect_breaker::{ReconnectBreaker, ReconnectBreakerConfig};
+use crate::market_data::tick_sequencer::{
+    SequencedTrade, TickAdmission, TickSequencer, TickSequencerConfig,
+};
+use crate::market_data::trade_gaps::{
+    fetch_gap_trades, TradeGapConfig, TradeIdGap, TradeIdTracker,
+};
+use crate::record::coordination::{DegradedSymbol, RecorderCoordination};
 use crate::storage::postgres_market_data::{
     connect as connect_postgres, ensure_recorder_schema_ready, insert_agg_trade, insert_book_ticker,
-    insert_liquidation, mask_postgres_url, metrics_for_postgres_url, postgres_url_from_env,
-    CollectorStorageBackend, PostgresAggTradeRecord, PostgresBookTickerRecord,
-    PostgresLiquidationRecord,
+    insert_kline, insert_liquidation, mask_postgres_url, metrics_for_postgres_url,
+    postgres_url_from_env, CollectorStorageBackend, PostgresAggTradeRecord,
+    PostgresBookTickerRecord, PostgresKlineRecord, PostgresLiquidationRecord,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -69,15 +85,13 @@ pub struct RecorderStatus {
 }
 
 impl RecorderStatus {
+    /// A status without symbols; `start` fills them in.
     fn new(
         mode: BinanceMode,
         state: RecorderState,
         db_path: PathBuf,
         storage_backend: String,
         storage_target: String,
-        manual_symbols: Vec<String>,
-        strategy_symbols: Vec<String>,
-        watched_symbols: Vec<String>,
     ) -> Self {
         let now = Utc::now();
         Self {
@@ -92,9 +106,9 @@ impl RecorderStatus {
                 None
             },
             updated_at: now,
-            manual_symbols,
-            strategy_symbols,
-            watched_symbols,
+            manual_symbols: Vec::new(),
+            strategy_symbols: Vec::new(),
+            watched_symbols: Vec::new(),
             reader_alive: true,
             writer_alive: true,
             worker_alive: true,
@@ -112,11 +126,20 @@ struct WorkerSnapshot {
     last_error: Option<String>,
 }
 
+type MarketStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+type SymbolMessage = Result<Message, tokio_tungstenite::tungstenite::Error>;
+
+const FORCE_ORDER_STREAM_KEY: &str = "!forceOrder";
+/// Buffered symbol stream messages handled per wakeup after a burst.
+const SYMBOL_BATCH_MAX: usize = 512;
+
 #[derive(Debug, Clone)]
 enum PostgresWriteCommand {
     Liquidation(PostgresLiquidationRecord),
     BookTicker(PostgresBookTickerRecord),
     AggTrade(PostgresAggTradeRecord),
+    Kline(PostgresKlineRecord),
 }
 
 impl WorkerSnapshot {
@@ -215,18 +238,17 @@ impl MarketDataRecorder {
         let manual_symbols = normalize_symbols(manual_symbols);
         let strategy_symbols = normalize_symbols(strategy_symbols);
         let watched_symbols = merge_symbol_sets(manual_symbols.clone(), strategy_symbols.clone());
-        let status = RecorderStatus::new(
+        let mut status = RecorderStatus::new(
             mode,
             RecorderState::Running,
             db_path.clone(),
             self.storage_backend.as_str().to_string(),
             self.storage_target(mode),
-            manual_symbols,
-            strategy_symbols,
-            watched_symbols.clone(),
         );
+        status.manual_symbols = manual_symbols;
+        status.strategy_symbols = strategy_symbols;
+        status.watched_symbols = watched_symbols.clone();
         let initial_metrics = self.load_metrics(&db_path).unwrap_or_default();
-        let mut status = status;
         status.metrics = initial_metrics.clone();
         if self.network_enabled {
             self.spawn_worker(mode, db_path, watched_symbols, initial_metrics)?;
@@ -243,9 +265,6 @@ impl MarketDataRecorder {
                 self.db_path(mode),
                 self.storage_backend.as_str().to_string(),
                 self.storage_target(mode),
-                Vec::new(),
-                Vec::new(),
-                Vec::new(),
             )
         });
         if let Some(worker) = self.workers.get(&mode) {
@@ -411,6 +430,7 @@ impl MarketDataRecorder {
         let snapshot = Arc::new(Mutex::new(WorkerSnapshot::new(initial_metrics)));
         let worker_snapshot = snapshot.clone();
         let storage_backend = self.storage_backend;
+        let coordination = RecorderCoordination::new(self.base_dir.clone());
         let (postgres_writer, writer_handle) = initialize_postgres_writer(
             storage_backend,
             self.postgres_url.as_deref(),
@@ -445,6 +465,7 @@ impl MarketDataRecorder {
                         duck_connection.as_ref(),
                         postgres_writer.as_ref(),
                         watched_symbols,
+                        coordination,
                         worker_stop_flag,
                         worker_snapshot,
                     )
@@ -518,12 +539,14 @@ fn initialize_duckdb_storage(
         })
 }
 
+type PostgresWriterHandles = (Option<Sender<PostgresWriteCommand>>, Option<JoinHandle<()>>);
+
 fn initialize_postgres_writer(
     storage_backend: CollectorStorageBackend,
     postgres_url: Option<&str>,
     stop_flag: Arc<AtomicBool>,
     snapshot: Arc<Mutex<WorkerSnapshot>>,
-) -> Result<(Option<Sender<PostgresWriteCommand>>, Option<JoinHandle<()>>), StorageError> {
+) -> Result<PostgresWriterHandles, StorageError> {
     if storage_backend != CollectorStorageBackend::Postgres {
         return Ok((None, None));
     }
@@ -568,6 +591,12 @@ fn run_postgres_writer_loop(
                     break;
                 }
             }
+            Ok(PostgresWriteCommand::Kline(record)) => {
+                if let Err(error) = insert_kline(&mut client, &record) {
+                    record_worker_error(&snapshot, error.to_string());
+                    break;
+                }
+            }
             Err(RecvTimeoutError::Timeout) if stop_flag.load(Ordering::Relaxed) => break,
             Err(RecvTimeoutError::Timeout) => continue,
             Err(RecvTimeoutError::Disconnected) => break,
@@ -580,20 +609,48 @@ async fn run_market_data_worker(
     duck_connection: Option<&Connection>,
     postgres_writer: Option<&Sender<PostgresWriteCommand>>,
     watched_symbols: Vec<String>,
+    coordination: RecorderCoordination,
     stop_flag: Arc<AtomicBool>,
     snapshot: Arc<Mutex<WorkerSnapshot>>,
 ) {
-    let mut agg_trade_bar_seconds = BTreeMap::new();
+    let trade_gap_config = TradeGapConfig::from_env();
+    let candle_check = CandleConsistencyConfig::from_env();
+    let candle_checker = if candle_check.enabled {
+        match spawn_candle_checker(mode, candle_check.clone(), stop_flag.clone()) {
+            Ok(checker) => Some(checker),
+            Err(error) => {
+                record_worker_error(&snapshot, error.to_string());
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let mut tape = AggTradeTape::new(
+        TickSequencerConfig::from_env(),
+        candle_checker
+            .is_some()
+            .then(|| LocalCandleAggregator::new(candle_check.interval_ms())),
+    );
+    let open_interest_poll = OpenInterestPollConfig::from_env();
+    let mut last_open_interest_poll: Option<std::time::Instant> = None;
+    let mut breaker = StreamBreaker::new(mode, coordination);
+    let chaos_ws_drop = ChaosConfig::from_env()
+        .for_mode(mode)
+        .map(|chaos| (chaos.ws_drop_pct, ChaosDice::new(chaos.seed)));
+    let force_order_keys = [FORCE_ORDER_STREAM_KEY.to_string()];
 
     loop {
         touch_worker_snapshot(&snapshot);
         if stop_flag.load(Ordering::Relaxed) {
             break;
         }
+        if !breaker.try_connect(&force_order_keys, &snapshot) {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            continue;
+        }
 
         let force_order_url = format!("{}/ws/!forceOrder@arr", market_stream_base_url(mode));
-        let symbol_stream_url = combined_symbol_stream_url(mode, &watched_symbols);
-
         let force_stream = connect_async(force_order_url).await;
         let mut force_stream = match force_stream {
             Ok((stream, _)) => stream,
@@ -605,20 +662,9 @@ async fn run_market_data_worker(
             }
         };
 
-        let mut symbol_stream = match symbol_stream_url {
-            Some(url) => match connect_async(url).await {
-                Ok((stream, _)) => Some(stream),
-                Err(error) => {
-                    record_worker_error(
-                        &snapshot,
-                        format!("symbol stream connect failed: {error}"),
-                    );
-                    warn!(service = "recorder", symbols = %watched_symbols.join(","), error = %error, "failed to connect symbol streams");
-                    None
-                }
-            },
-            None => None,
-        };
+        let mut symbol_stream =
+            connect_symbol_stream(mode, &watched_symbols, &mut breaker, &snapshot).await;
+        let mut last_symbol_connect = std::time::Instant::now();
 
         let mut liquidation_seq = 0i64;
         let mut ticker_seq = 0i64;
@@ -628,6 +674,37 @@ async fn run_market_data_worker(
             if stop_flag.load(Ordering::Relaxed) {
                 return;
             }
+            if symbol_stream.is_none()
+                && !watched_symbols.is_empty()
+                && last_symbol_connect.elapsed() >= Duration::from_secs(1)
+            {
+                last_symbol_connect = std::time::Instant::now();
+                breaker.publish();
+                symbol_stream =
+                    connect_symbol_stream(mode, &watched_symbols, &mut breaker, &snapshot).await;
+            }
+            if let (Some(checker), Some(aggregator)) = (&candle_checker, tape.candles.as_mut()) {
+                if let Err(error) = exchange_candle_checks(
+                    duck_connection,
+                    postgres_writer,
+                    mode,
+                    &candle_check.interval,
+                    checker,
+                    aggregator,
+                ) {
+                    record_worker_error(&snapshot, error.to_string());
+                    error!(service = "recorder", error = %error, "storing checked candles failed");
+                    break;
+                }
+            }
+            if open_interest_poll.enabled()
+                && last_open_interest_poll.is_none_or(|polled| {
+                    polled.elapsed() >= Duration::from_secs(open_interest_poll.poll_every_secs)
+                })
+            {
+                last_open_interest_poll = Some(std::time::Instant::now());
+                record_open_interest(mode, duck_connection, &watched_symbols, &snapshot).await;
+            }
 
             tokio::select! {
                 message = force_stream.next() => {
@@ -659,37 +736,104 @@ async fn run_market_data_worker(
                     }
                 }
                 message = next_symbol_message(&mut symbol_stream), if symbol_stream.is_some() => {
-                    match message {
-                        Some(Ok(message)) => {
-                            if let Err(error) = handle_symbol_message(
-                                duck_connection,
-                                postgres_writer,
-                                mode,
-                                &mut ticker_seq,
-                                &mut trade_seq,
-                                &mut agg_trade_bar_seconds,
-                                &snapshot,
-                                message,
-                            ) {
-                                record_worker_error(&snapshot, error.to_string());
-                                error!(service = "recorder", error = %error, "symbol stream handling failed");
+                    let messages = drain_ready_symbol_messages(message, &mut symbol_stream);
+                    let mut batch = SymbolEventBatch::default();
+                    let mut failed = None;
+                    for message in messages {
+                        match message {
+                            Some(Ok(_))
+                                if chaos_ws_drop
+                                    .as_ref()
+                                    .is_some_and(|(drop_pct, dice)| dice.roll(*drop_pct)) =>
+                            {
+                                batch.publish(&snapshot);
+                                record_worker_error(&snapshot, "symbol stream dropped by chaos mode".to_string());
+                                warn!(service = "recorder", symbols = %watched_symbols.join(","), "symbol stream dropped by chaos mode");
+                                symbol_stream = None;
+                                break;
+                            }
+                            Some(Ok(message)) => {
+                                if let Err(error) = handle_symbol_message(
+                                    SymbolSink {
+                                        duck_connection,
+                                        postgres_writer,
+                                        mode,
+                                    },
+                                    &mut ticker_seq,
+                                    &mut trade_seq,
+                                    &mut tape,
+                                    &mut batch,
+                                    message,
+                                ) {
+                                    failed = Some(error);
+                                    break;
+                                }
+                            }
+                            Some(Err(error)) => {
+                                batch.publish(&snapshot);
+                                record_worker_error(&snapshot, format!("symbol stream disconnected: {error}"));
+                                warn!(service = "recorder", symbols = %watched_symbols.join(","), error = %error, "symbol stream disconnected");
+                                symbol_stream = None;
+                                break;
+                            }
+                            None => {
+                                batch.publish(&snapshot);
+                                record_worker_error(&snapshot, "symbol stream disconnected: eof".to_string());
+                                warn!(service = "recorder", symbols = %watched_symbols.join(","), "symbol stream disconnected: eof");
+                                symbol_stream = None;
                                 break;
                             }
                         }
-                        Some(Err(error)) => {
-                            record_worker_error(&snapshot, format!("symbol stream disconnected: {error}"));
-                            warn!(service = "recorder", symbols = %watched_symbols.join(","), error = %error, "symbol stream disconnected");
-                            break
-                        }
-                        None => {
-                            record_worker_error(&snapshot, "symbol stream disconnected: eof".to_string());
-                            warn!(service = "recorder", symbols = %watched_symbols.join(","), "symbol stream disconnected: eof");
-                            break
-                        }
+                    }
+                    let trade_gaps = std::mem::take(&mut batch.trade_gaps);
+                    batch.publish(&snapshot);
+                    if let Some(error) = failed {
+                        record_worker_error(&snapshot, error.to_string());
+                        error!(service = "recorder", error = %error, "symbol stream handling failed");
+                        break;
+                    }
+                    if trade_gap_config.backfill && !trade_gaps.is_empty() {
+                        backfill_trade_gaps(
+                            mode,
+                            duck_connection,
+                            postgres_writer,
+                            &mut trade_seq,
+                            trade_gaps,
+                            &snapshot,
+                        )
+                        .await;
                     }
                 }
                 _ = tokio::time::sleep(Duration::from_millis(250)) => {
                     touch_worker_snapshot(&snapshot);
+                    let mut batch = SymbolEventBatch::default();
+                    let released = record_released_trades(
+                        duck_connection,
+                        postgres_writer,
+                        mode,
+                        &mut trade_seq,
+                        &mut tape,
+                        &mut batch,
+                        Utc::now().timestamp_millis(),
+                    );
+                    let trade_gaps = std::mem::take(&mut batch.trade_gaps);
+                    batch.publish(&snapshot);
+                    if let Err(error) = released {
+                        record_worker_error(&snapshot, error.to_string());
+                        error!(service = "recorder", error = %error, "releasing held aggTrades failed");
+                        break;
+                    }
+                    if trade_gap_config.backfill && !trade_gaps.is_empty() {
+                        backfill_trade_gaps(
+                            mode,
+                            duck_connection,
+                            postgres_writer,
+                            &mut trade_seq,
+                            trade_gaps,
+                            &snapshot,
+                        )
+                        .await;
+                    }
                 }
             }
         }
@@ -698,19 +842,433 @@ async fn run_market_data_worker(
     }
 }
 
-async fn next_symbol_message(
-    stream: &mut Option<
-        tokio_tungstenite::WebSocketStream<
-            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
-        >,
-    >,
-) -> Option<Result<Message, tokio_tungstenite::tungstenite::Error>> {
+/// Channels to the thread that checks closed local candles against the
+/// exchange klines, so REST calls never stall the stream task.
+struct CandleChecker {
+    closed: Sender<(String, DerivedKlineRow)>,
+    checked: mpsc::Receiver<CandleConsistencyCheck>,
+}
+
+fn spawn_candle_checker(
+    mode: BinanceMode,
+    config: CandleConsistencyConfig,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<CandleChecker, StorageError> {
+    let (closed, closed_receiver) = mpsc::channel();
+    let (checked_sender, checked) = mpsc::channel();
+    std::thread::Builder::new()
+        .name(format!("market-recorder-candle-check-{}", mode.as_str()))
+        .spawn(move || {
+            run_candle_check_loop(mode, config, closed_receiver, checked_sender, stop_flag)
+        })
+        .map_err(|error| StorageError::WriteFailedWithContext {
+            message: error.to_string(),
+        })?;
+    Ok(CandleChecker { closed, checked })
+}
+
+fn run_candle_check_loop(
+    mode: BinanceMode,
+    config: CandleConsistencyConfig,
+    closed: mpsc::Receiver<(String, DerivedKlineRow)>,
+    checked: Sender<CandleConsistencyCheck>,
+    stop_flag: Arc<AtomicBool>,
+) {
+    let http = reqwest::blocking::Client::new();
+    // The streamed trades are futures, so they are checked against futures
+    // klines.
+    let endpoint = match &config.rest_base_url {
+        Some(base_url) => KlineEndpoint::for_market(mode, Market::Futures).with_base_url(base_url),
+        None => KlineEndpoint::for_market(mode, Market::Futures),
+    };
+    let mut pending = Vec::new();
+    let mut last_check = std::time::Instant::now();
+    loop {
+        match closed.recv_timeout(Duration::from_millis(250)) {
+            Ok(candle) => pending.push(candle),
+            Err(RecvTimeoutError::Timeout) if stop_flag.load(Ordering::Relaxed) => break,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if pending.is_empty()
+            || last_check.elapsed() < Duration::from_secs(config.check_every_secs)
+        {
+            continue;
+        }
+        last_check = std::time::Instant::now();
+        for (symbol, local) in pending.drain(..) {
+            let reference = fetch_reference_kline(
+                &http,
+                &endpoint,
+                mode,
+                &symbol,
+                &config.interval,
+                local.open_time_ms,
+            );
+            let check = match reference {
+                Ok(Some(reference)) => {
+                    compare_candles(&symbol, &local, &reference, config.tolerance_bps)
+                }
+                Ok(None) => continue,
+                Err(error) => {
+                    warn!(service = "recorder", mode = mode.as_str(), symbol = %symbol, error = %format!("{error:#}"), "candle consistency check failed");
+                    continue;
+                }
+            };
+            if check.within_tolerance() {
+                tracing::debug!(
+                    service = "recorder",
+                    mode = mode.as_str(),
+                    symbol = %check.symbol,
+                    open_time_ms = check.local.open_time_ms,
+                    max_drift_bps = check.max_drift_bps,
+                    "candle consistency check passed"
+                );
+            } else {
+                warn!(
+                    service = "recorder",
+                    mode = mode.as_str(),
+                    symbol = %check.symbol,
+                    open_time_ms = check.local.open_time_ms,
+                    max_drift_bps = check.max_drift_bps,
+                    tolerance_bps = check.tolerance_bps,
+                    drifted_fields = %check.drifted_fields.join(","),
+                    "candle drift beyond tolerance; corrected from REST kline"
+                );
+            }
+            if checked.send(check).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Hands newly closed local candles to the checker and stores the candles it
+/// has finished checking, corrected where they drifted.
+fn exchange_candle_checks(
+    duck_connection: Option<&Connection>,
+    postgres_writer: Option<&Sender<PostgresWriteCommand>>,
+    mode: BinanceMode,
+    interval: &str,
+    checker: &CandleChecker,
+    aggregator: &mut LocalCandleAggregator,
+) -> Result<(), StorageError> {
+    for candle in aggregator.take_closed() {
+        let _ = checker.closed.send(candle);
+    }
+    for check in checker.checked.try_iter() {
+        store_checked_candle(duck_connection, postgres_writer, mode, interval, &check)?;
+    }
+    Ok(())
+}
+
+fn store_checked_candle(
+    duck_connection: Option<&Connection>,
+    postgres_writer: Option<&Sender<PostgresWriteCommand>>,
+    mode: BinanceMode,
+    interval: &str,
+    check: &CandleConsistencyCheck,
+) -> Result<(), StorageError> {
+    let candle = check.corrected_candle();
+    let raw_payload = serde_json::json!({
+        "source": if check.within_tolerance() { "local" } else { "rest" },
+        "max_drift_bps": check.max_drift_bps,
+    })
+    .to_string();
+    if let Some(connection) = duck_connection {
+        connection
+            .execute(
+                "INSERT INTO raw_klines (
+                    kline_id, mode, product, symbol, interval, open_time, close_time,
+                    open, high, low, close, volume, quote_volume, trade_count,
+                    taker_buy_base_volume, taker_buy_quote_volume, raw_payload
+                 ) SELECT
+                    COALESCE(MAX(kline_id), 0) + 1, ?, ?, ?, ?,
+                    to_timestamp(? / 1000.0), to_timestamp(? / 1000.0),
+                    ?, ?, ?, ?, ?, ?, ?, NULL, NULL, ?
+                 FROM raw_klines",
+                params![
+                    mode.as_str(),
+                    DEFAULT_BINANCE_BACKFILL_PRODUCT,
+                    check.symbol,
+                    interval,
+                    candle.open_time_ms,
+                    candle.close_time_ms,
+                    candle.open,
+                    candle.high,
+                    candle.low,
+                    candle.close,
+                    candle.volume,
+                    candle.quote_volume,
+                    candle.trade_count as i64,
+                    raw_payload,
+                ],
+            )
+            .map_err(|error| StorageError::WriteFailedWithContext {
+                message: error.to_string(),
+            })?;
+    } else if let Some(sender) = postgres_writer {
+        sender
+            .send(PostgresWriteCommand::Kline(PostgresKlineRecord {
+                product: DEFAULT_BINANCE_BACKFILL_PRODUCT.to_string(),
+                symbol: check.symbol.clone(),
+                interval_name: interval.to_string(),
+                open_time_ms: candle.open_time_ms,
+                close_time_ms: candle.close_time_ms,
+                open: candle.open,
+                high: candle.high,
+                low: candle.low,
+                close: candle.close,
+                volume: candle.volume,
+                quote_volume: candle.quote_volume,
+                trade_count: candle.trade_count as i64,
+                taker_buy_base_volume: None,
+                taker_buy_quote_volume: None,
+                raw_payload,
+            }))
+            .map_err(|error| StorageError::WriteFailedWithContext {
+                message: format!("postgres kline writer disconnected: {error}"),
+            })?;
+    }
+    Ok(())
+}
+
+async fn record_open_interest(
+    mode: BinanceMode,
+    duck_connection: Option<&Connection>,
+    watched_symbols: &[String],
+    snapshot: &Arc<Mutex<WorkerSnapshot>>,
+) {
+    // Postgres has no open interest table yet; the chart panes read DuckDB.
+    let Some(connection) = duck_connection else {
+        return;
+    };
+    if watched_symbols.is_empty() {
+        return;
+    }
+    let symbols = watched_symbols.to_vec();
+    let samples = tokio::task::spawn_blocking(move || {
+        let http = reqwest::blocking::Client::new();
+        symbols
+            .into_iter()
+            .map(|symbol| {
+                fetch_open_interest(&http, &symbol)
+                    .map(|row| (symbol.clone(), row))
+                    .map_err(|error| format!("{symbol}: {error:#}"))
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_default();
+
+    for sample in samples {
+        let result = sample.and_then(|(symbol, row)| {
+            connection
+                .execute(
+                    "INSERT INTO raw_open_interest (mode, symbol, event_time, open_interest)
+                     VALUES (?, ?, to_timestamp(? / 1000.0), ?)",
+                    params![mode.as_str(), symbol, row.event_time_ms, row.open_interest],
+                )
+                .map(|_| ())
+                .map_err(|error| format!("{symbol}: {error}"))
+        });
+        if let Err(error) = result {
+            record_worker_error(snapshot, format!("open interest poll failed: {error}"));
+            warn!(service = "recorder", mode = mode.as_str(), error = %error, "open interest poll failed");
+        }
+    }
+}
+
+/// Fetches the trades behind each id gap over REST and stores them like
+/// streamed ones.
+async fn backfill_trade_gaps(
+    mode: BinanceMode,
+    duck_connection: Option<&Connection>,
+    postgres_writer: Option<&Sender<PostgresWriteCommand>>,
+    trade_sequence: &mut i64,
+    gaps: Vec<TradeIdGap>,
+    snapshot: &Arc<Mutex<WorkerSnapshot>>,
+) {
+    let fetched = tokio::task::spawn_blocking(move || {
+        let http = reqwest::blocking::Client::new();
+        gaps.into_iter()
+            .map(|gap| {
+                fetch_gap_trades(&http, &gap)
+                    .map(|rows| (gap.symbol.clone(), rows))
+                    .map_err(|error| format!("{}: {error:#}", gap.symbol))
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_default();
+
+    let receive_time_ms = Utc::now().timestamp_millis();
+    let mut backfilled = 0u64;
+    for result in fetched {
+        let result = result.and_then(|(symbol, rows)| {
+            for row in rows {
+                let record = PostgresAggTradeRecord {
+                    symbol: symbol.clone(),
+                    event_time_ms: row.event_time_ms,
+                    receive_time_ms,
+                    price: row.price,
+                    qty: row.qty,
+                    is_buyer_maker: row.is_buyer_maker,
+                };
+                store_agg_trade(
+                    duck_connection,
+                    postgres_writer,
+                    mode,
+                    trade_sequence,
+                    record,
+                )
+                .map_err(|error| format!("{symbol}: {error}"))?;
+                backfilled += (row.last_id - row.first_id + 1).max(0) as u64;
+            }
+            Ok(())
+        });
+        if let Err(error) = result {
+            record_worker_error(snapshot, format!("trade gap backfill failed: {error}"));
+            warn!(service = "recorder", mode = mode.as_str(), error = %error, "trade gap backfill failed");
+        }
+    }
+    if let Ok(mut snapshot) = snapshot.lock() {
+        snapshot.metrics.backfilled_trades += backfilled;
+    }
+}
+
+/// Connects the combined symbol stream unless the breaker is holding its
+/// symbols in a cool-down.
+async fn connect_symbol_stream(
+    mode: BinanceMode,
+    watched_symbols: &[String],
+    breaker: &mut StreamBreaker,
+    snapshot: &Arc<Mutex<WorkerSnapshot>>,
+) -> Option<MarketStream> {
+    let url = combined_symbol_stream_url(mode, watched_symbols)?;
+    if !breaker.try_connect(watched_symbols, snapshot) {
+        return None;
+    }
+    match connect_async(url).await {
+        Ok((stream, _)) => Some(stream),
+        Err(error) => {
+            record_worker_error(snapshot, format!("symbol stream connect failed: {error}"));
+            warn!(service = "recorder", symbols = %watched_symbols.join(","), error = %error, "failed to connect symbol streams");
+            None
+        }
+    }
+}
+
+/// Applies the reconnect breaker to the recorder streams and publishes the
+/// symbols it is cooling down, so the operator can pause their strategies.
+struct StreamBreaker {
+    mode: BinanceMode,
+    breaker: ReconnectBreaker,
+    coordination: RecorderCoordination,
+    published: Option<Vec<DegradedSymbol>>,
+}
+
+impl StreamBreaker {
+    fn new(mode: BinanceMode, coordination: RecorderCoordination) -> Self {
+        let mut breaker = Self {
+            mode,
+            breaker: ReconnectBreaker::new(ReconnectBreakerConfig::from_env()),
+            coordination,
+            published: None,
+        };
+        // Clears symbols left degraded by an earlier recorder run.
+        breaker.publish();
+        breaker
+    }
+
+    /// Counts one connect attempt per key; `false` means skip connecting
+    /// because a key is cooling down or this attempt tripped the breaker.
+    fn try_connect(&mut self, keys: &[String], snapshot: &Arc<Mutex<WorkerSnapshot>>) -> bool {
+        let now_ms = Utc::now().timestamp_millis();
+        if keys.iter().any(|key| self.breaker.is_open(key, now_ms)) {
+            return false;
+        }
+        let tripped_until = keys
+            .iter()
+            .filter_map(|key| self.breaker.record_attempt(key, now_ms))
+            .max();
+        let Some(until_ms) = tripped_until else {
+            return true;
+        };
+        let until = Utc
+            .timestamp_millis_opt(until_ms)
+            .single()
+            .map(|until| until.to_rfc3339())
+            .unwrap_or_else(|| until_ms.to_string());
+        record_worker_error(
+            snapshot,
+            format!(
+                "reconnect breaker open for {} until {until}",
+                keys.join(",")
+            ),
+        );
+        warn!(
+            service = "recorder",
+            mode = self.mode.as_str(),
+            streams = %keys.join(","),
+            until = %until,
+            "websocket reconnect storm; cooling down and marking degraded"
+        );
+        self.publish();
+        false
+    }
+
+    /// Writes the degraded symbol list when it changed, including expiries.
+    fn publish(&mut self) {
+        let degraded = self
+            .breaker
+            .degraded(Utc::now().timestamp_millis())
+            .into_iter()
+            .filter(|(key, _)| key != FORCE_ORDER_STREAM_KEY)
+            .map(|(symbol, until_ms)| DegradedSymbol { symbol, until_ms })
+            .collect::<Vec<_>>();
+        if self.published.as_ref() == Some(&degraded) {
+            return;
+        }
+        match self
+            .coordination
+            .sync_degraded_symbols(self.mode, degraded.clone())
+        {
+            Ok(()) => self.published = Some(degraded),
+            Err(error) => {
+                warn!(service = "recorder", mode = self.mode.as_str(), error = %error, "failed to publish degraded symbols")
+            }
+        }
+    }
+}
+
+async fn next_symbol_message(stream: &mut Option<MarketStream>) -> Option<SymbolMessage> {
     match stream {
         Some(stream) => stream.next().await,
         None => None,
     }
 }
 
+/// Takes `first` plus whatever the stream already has buffered, up to
+/// `SYMBOL_BATCH_MAX`, so a backlog after a burst or reconnect is handled in
+/// one wakeup instead of one `select!` round per tick.
+fn drain_ready_symbol_messages(
+    first: Option<SymbolMessage>,
+    stream: &mut Option<MarketStream>,
+) -> Vec<Option<SymbolMessage>> {
+    let mut messages = vec![first];
+    if let (Some(Some(Ok(_))), Some(stream)) = (messages.first(), stream.as_mut()) {
+        while messages.len() < SYMBOL_BATCH_MAX {
+            match stream.next().now_or_never() {
+                Some(Some(message)) => messages.push(Some(message)),
+                // Pending, or end of stream left for the next poll to report.
+                _ => break,
+            }
+        }
+    }
+    messages
+}
+
 fn handle_force_order_message(
     duck_connection: Option<&Connection>,
     postgres_writer: Option<&Sender<PostgresWriteCommand>>,
@@ -791,16 +1349,27 @@ fn handle_force_order_message(
     Ok(())
 }
 
-fn handle_symbol_message(
-    duck_connection: Option<&Connection>,
-    postgres_writer: Option<&Sender<PostgresWriteCommand>>,
+/// Where symbol-stream rows are written.
+#[derive(Clone, Copy)]
+struct SymbolSink<'a> {
+    duck_connection: Option<&'a Connection>,
+    postgres_writer: Option<&'a Sender<PostgresWriteCommand>>,
     mode: BinanceMode,
+}
+
+fn handle_symbol_message(
+    sink: SymbolSink<'_>,
     ticker_sequence: &mut i64,
     trade_sequence: &mut i64,
-    agg_trade_bar_seconds: &mut BTreeMap<String, i64>,
-    snapshot: &Arc<Mutex<WorkerSnapshot>>,
+    tape: &mut AggTradeTape,
+    batch: &mut SymbolEventBatch,
     message: Message,
 ) -> Result<(), StorageError> {
+    let SymbolSink {
+        duck_connection,
+        postgres_writer,
+        mode,
+    } = sink;
     let payload = match message {
         Message::Text(text) => text,
         Message::Ping(_) | Message::Pong(_) | Message::Binary(_) => return Ok(()),
@@ -879,7 +1448,7 @@ fn handle_symbol_message(
                 message: "no recorder storage backend available".to_string(),
             });
         }
-        record_book_ticker_event(snapshot, &symbol, event_time);
+        batch.record_book_ticker(&symbol, event_time);
     } else if parsed.data.event_type == "aggTrade" {
         let Some(symbol) = parsed.data.symbol else {
             return Ok(());
@@ -896,53 +1465,123 @@ fn handle_symbol_message(
         let Some(is_buyer_maker) = parsed.data.is_buyer_maker else {
             return Ok(());
         };
-        *trade_sequence += 1;
-        if let Some(connection) = duck_connection {
-            connection
-                .execute(
-                    "INSERT INTO raw_agg_trades (
-                        trade_id, mode, symbol, event_time, receive_time, price, qty, is_buyer_maker
-                     ) VALUES (
-                        ?, ?, ?, to_timestamp(? / 1000.0), to_timestamp(? / 1000.0), ?, ?, ?
-                     )",
-                    params![
-                        *trade_sequence,
-                        mode.as_str(),
-                        symbol,
-                        event_time,
-                        receive_time_ms,
-                        price,
-                        qty,
-                        is_buyer_maker,
-                    ],
-                )
-                .map_err(|error| StorageError::WriteFailedWithContext {
-                    message: error.to_string(),
-                })?;
-        } else if let Some(sender) = postgres_writer {
-            sender
-                .send(PostgresWriteCommand::AggTrade(PostgresAggTradeRecord {
-                    symbol: symbol.clone(),
-                    event_time_ms: event_time,
-                    receive_time_ms,
-                    price,
-                    qty,
-                    is_buyer_maker,
-                }))
-                .map_err(|error| StorageError::WriteFailedWithContext {
-                    message: format!("postgres agg_trade writer disconnected: {error}"),
-                })?;
-        } else {
-            return Err(StorageError::WriteFailedWithContext {
-                message: "no recorder storage backend available".to_string(),
-            });
+        let trade = SequencedTrade {
+            symbol,
+            first_id: parsed.data.first_trade_id,
+            last_id: parsed.data.last_trade_id,
+            event_time_ms: event_time,
+            receive_time_ms,
+            price,
+            qty,
+            is_buyer_maker,
+        };
+        match tape.sequencer.push(trade, receive_time_ms) {
+            TickAdmission::InOrder => {}
+            TickAdmission::Reordered => batch.reordered_ticks += 1,
+            TickAdmission::Late => batch.late_ticks += 1,
+            TickAdmission::Duplicate => batch.duplicate_ticks += 1,
         }
-        record_agg_trade_event(snapshot, &symbol, event_time, agg_trade_bar_seconds);
+        record_released_trades(
+            duck_connection,
+            postgres_writer,
+            mode,
+            trade_sequence,
+            tape,
+            batch,
+            receive_time_ms,
+        )?;
     }
 
     Ok(())
 }
 
+/// Stores the aggTrades the sequencer lets go of and feeds them to the
+/// candles and the trade id tracker, in event order.
+fn record_released_trades(
+    duck_connection: Option<&Connection>,
+    postgres_writer: Option<&Sender<PostgresWriteCommand>>,
+    mode: BinanceMode,
+    trade_sequence: &mut i64,
+    tape: &mut AggTradeTape,
+    batch: &mut SymbolEventBatch,
+    now_ms: i64,
+) -> Result<(), StorageError> {
+    for trade in tape.sequencer.release(now_ms) {
+        store_agg_trade(
+            duck_connection,
+            postgres_writer,
+            mode,
+            trade_sequence,
+            PostgresAggTradeRecord {
+                symbol: trade.symbol.clone(),
+                event_time_ms: trade.event_time_ms,
+                receive_time_ms: trade.receive_time_ms,
+                price: trade.price,
+                qty: trade.qty,
+                is_buyer_maker: trade.is_buyer_maker,
+            },
+        )?;
+        if let Some(aggregator) = tape.candles.as_mut() {
+            aggregator.apply_trade(&trade.symbol, trade.event_time_ms, trade.price, trade.qty);
+        }
+        batch.record_agg_trade(&trade.symbol, trade.event_time_ms, &mut tape.bar_seconds);
+        if let (Some(first_id), Some(last_id)) = (trade.first_id, trade.last_id) {
+            if let Some(gap) =
+                tape.trade_ids
+                    .observe(&trade.symbol, first_id, last_id, trade.event_time_ms)
+            {
+                warn!(service = "recorder", symbol = %trade.symbol, from_id = gap.from_id, to_id = gap.to_id, missed = gap.missed(), "aggTrade id gap detected");
+                batch.record_trade_gap(gap);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn store_agg_trade(
+    duck_connection: Option<&Connection>,
+    postgres_writer: Option<&Sender<PostgresWriteCommand>>,
+    mode: BinanceMode,
+    trade_sequence: &mut i64,
+    record: PostgresAggTradeRecord,
+) -> Result<(), StorageError> {
+    *trade_sequence += 1;
+    if let Some(connection) = duck_connection {
+        connection
+            .execute(
+                "INSERT INTO raw_agg_trades (
+                    trade_id, mode, symbol, event_time, receive_time, price, qty, is_buyer_maker
+                 ) VALUES (
+                    ?, ?, ?, to_timestamp(? / 1000.0), to_timestamp(? / 1000.0), ?, ?, ?
+                 )",
+                params![
+                    *trade_sequence,
+                    mode.as_str(),
+                    record.symbol,
+                    record.event_time_ms,
+                    record.receive_time_ms,
+                    record.price,
+                    record.qty,
+                    record.is_buyer_maker,
+                ],
+            )
+            .map_err(|error| StorageError::WriteFailedWithContext {
+                message: error.to_string(),
+            })?;
+    } else if let Some(sender) = postgres_writer {
+        sender
+            .send(PostgresWriteCommand::AggTrade(record))
+            .map_err(|error| StorageError::WriteFailedWithContext {
+                message: format!("postgres agg_trade writer disconnected: {error}"),
+            })?;
+    } else {
+        return Err(StorageError::WriteFailedWithContext {
+            message: "no recorder storage backend available".to_string(),
+        });
+    }
+    Ok(())
+}
+
 fn market_stream_base_url(mode: BinanceMode) -> &'static str {
     let _ = mode;
     "wss://fstream.binance.com"
@@ -977,7 +1616,7 @@ fn normalize_symbols(symbols: Vec<String>) -> Vec<String> {
 
 fn merge_symbol_sets(left: Vec<String>, right: Vec<String>) -> Vec<String> {
     let mut merged = BTreeSet::new();
-    for symbol in left.into_iter().chain(right.into_iter()) {
+    for symbol in left.into_iter().chain(right) {
         merged.insert(symbol);
     }
     merged.into_iter().collect()
@@ -1061,6 +1700,10 @@ struct CombinedStreamData {
     qty: Option<f64>,
     #[serde(rename = "m")]
     is_buyer_maker: Option<bool>,
+    #[serde(rename = "f")]
+    first_trade_id: Option<i64>,
+    #[serde(rename = "l")]
+    last_trade_id: Option<i64>,
 }
 
 fn deserialize_string_number<'de, D>(deserializer: D) -> Result<f64, D::Error>
@@ -1119,44 +1762,133 @@ fn record_force_order_event(
     }
 }
 
-fn record_book_ticker_event(
-    snapshot: &Arc<Mutex<WorkerSnapshot>>,
-    symbol: &str,
-    event_time_ms: i64,
-) {
-    if let Ok(mut snapshot) = snapshot.lock() {
-        snapshot.updated_at = Utc::now();
-        snapshot.last_error = None;
-        snapshot.metrics.book_ticker_events += 1;
-        snapshot.metrics.last_book_ticker_event_time = timestamp_string(event_time_ms);
-        increment_top_symbol(&mut snapshot.metrics.top_book_ticker_symbols, symbol);
+/// aggTrade state kept across reconnects: repeats and ordering, trade ids,
+/// 1s bar boundaries and the local candles.
+#[derive(Debug)]
+struct AggTradeTape {
+    sequencer: TickSequencer,
+    trade_ids: TradeIdTracker,
+    bar_seconds: BTreeMap<String, i64>,
+    candles: Option<LocalCandleAggregator>,
+}
+
+impl AggTradeTape {
+    fn new(sequencer: TickSequencerConfig, candles: Option<LocalCandleAggregator>) -> Self {
+        Self {
+            sequencer: TickSequencer::new(sequencer),
+            trade_ids: TradeIdTracker::default(),
+            bar_seconds: BTreeMap::new(),
+            candles,
+        }
     }
 }
 
-fn record_agg_trade_event(
-    snapshot: &Arc<Mutex<WorkerSnapshot>>,
-    symbol: &str,
-    event_time_ms: i64,
-    agg_trade_bar_seconds: &mut BTreeMap<String, i64>,
-) {
-    if let Ok(mut snapshot) = snapshot.lock() {
-        snapshot.updated_at = Utc::now();
-        snapshot.last_error = None;
-        snapshot.metrics.agg_trade_events += 1;
-        snapshot.metrics.last_agg_trade_event_time = timestamp_string(event_time_ms);
-        increment_top_symbol(&mut snapshot.metrics.top_agg_trade_symbols, symbol);
+/// Symbol stream counters for one drained batch, published to the worker
+/// snapshot once so the status view updates once per batch, not per tick.
+#[derive(Debug, Default)]
+struct SymbolEventBatch {
+    book_ticker_events: u64,
+    last_book_ticker_event_time: Option<i64>,
+    book_ticker_symbols: BTreeMap<String, u64>,
+    agg_trade_events: u64,
+    last_agg_trade_event_time: Option<i64>,
+    agg_trade_symbols: BTreeMap<String, u64>,
+    derived_kline_1s_bars: u64,
+    trade_id_gaps: u64,
+    missed_trades: u64,
+    duplicate_ticks: u64,
+    reordered_ticks: u64,
+    late_ticks: u64,
+    /// Kept across `publish` for the worker to backfill.
+    trade_gaps: Vec<TradeIdGap>,
+}
+
+impl SymbolEventBatch {
+    fn record_book_ticker(&mut self, symbol: &str, event_time_ms: i64) {
+        self.book_ticker_events += 1;
+        self.last_book_ticker_event_time = Some(event_time_ms);
+        *self
+            .book_ticker_symbols
+            .entry(symbol.to_string())
+            .or_default() += 1;
+    }
+
+    fn record_agg_trade(
+        &mut self,
+        symbol: &str,
+        event_time_ms: i64,
+        agg_trade_bar_seconds: &mut BTreeMap<String, i64>,
+    ) {
+        self.agg_trade_events += 1;
+        self.last_agg_trade_event_time = Some(event_time_ms);
+        *self
+            .agg_trade_symbols
+            .entry(symbol.to_string())
+            .or_default() += 1;
         let bar_second = event_time_ms / 1_000;
         let should_increment_bar = agg_trade_bar_seconds
             .insert(symbol.to_string(), bar_second)
             .map(|previous| previous != bar_second)
             .unwrap_or(true);
         if should_increment_bar {
-            snapshot.metrics.derived_kline_1s_bars += 1;
+            self.derived_kline_1s_bars += 1;
+        }
+    }
+
+    fn record_trade_gap(&mut self, gap: TradeIdGap) {
+        self.trade_id_gaps += 1;
+        self.missed_trades += gap.missed();
+        self.trade_gaps.push(gap);
+    }
+
+    /// Applies and clears the counters; an empty batch leaves the snapshot alone.
+    fn publish(&mut self, snapshot: &Arc<Mutex<WorkerSnapshot>>) {
+        if self.book_ticker_events == 0
+            && self.agg_trade_events == 0
+            && self.duplicate_ticks + self.reordered_ticks + self.late_ticks == 0
+        {
+            return;
+        }
+        let trade_gaps = std::mem::take(&mut self.trade_gaps);
+        let batch = std::mem::replace(
+            self,
+            Self {
+                trade_gaps,
+                ..Self::default()
+            },
+        );
+        if let Ok(mut snapshot) = snapshot.lock() {
+            snapshot.updated_at = Utc::now();
+            snapshot.last_error = None;
+            let metrics = &mut snapshot.metrics;
+            metrics.duplicate_ticks += batch.duplicate_ticks;
+            metrics.reordered_ticks += batch.reordered_ticks;
+            metrics.late_ticks += batch.late_ticks;
+            if let Some(event_time_ms) = batch.last_book_ticker_event_time {
+                metrics.book_ticker_events += batch.book_ticker_events;
+                metrics.last_book_ticker_event_time = timestamp_string(event_time_ms);
+                merge_top_symbols(
+                    &mut metrics.top_book_ticker_symbols,
+                    batch.book_ticker_symbols,
+                );
+            }
+            if let Some(event_time_ms) = batch.last_agg_trade_event_time {
+                metrics.agg_trade_events += batch.agg_trade_events;
+                metrics.last_agg_trade_event_time = timestamp_string(event_time_ms);
+                metrics.derived_kline_1s_bars += batch.derived_kline_1s_bars;
+                metrics.trade_id_gaps += batch.trade_id_gaps;
+                metrics.missed_trades += batch.missed_trades;
+                merge_top_symbols(&mut metrics.top_agg_trade_symbols, batch.agg_trade_symbols);
+            }
         }
     }
 }
 
 fn increment_top_symbol(top_symbols: &mut Vec<String>, symbol: &str) {
+    merge_top_symbols(top_symbols, BTreeMap::from([(symbol.to_string(), 1)]));
+}
+
+fn merge_top_symbols(top_symbols: &mut Vec<String>, additions: BTreeMap<String, u64>) {
     let mut counts = top_symbols
         .iter()
         .filter_map(|entry| {
@@ -1165,7 +1897,9 @@ fn increment_top_symbol(top_symbols: &mut Vec<String>, symbol: &str) {
             Some((symbol.to_string(), count))
         })
         .collect::<BTreeMap<_, _>>();
-    *counts.entry(symbol.to_string()).or_default() += 1;
+    for (symbol, count) in additions {
+        *counts.entry(symbol).or_default() += count;
+    }
     let mut sorted = counts.into_iter().collect::<Vec<_>>();
     sorted.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
     *top_symbols = sorted
@@ -1229,7 +1963,7 @@ mod tests {
             BinanceMode::Demo,
             &mut sequence,
             &snapshot,
-            Message::Text(payload.clone().into()),
+            Message::Text(payload.clone()),
         )
         .expect("handle force order");
 
@@ -1259,7 +1993,7 @@ mod tests {
         assert_eq!(row.3, "SELL");
         assert_eq!(row.4, 68_250.5);
         assert_eq!(row.5, 0.125);
-        assert_eq!(row.6, 8_531.3125);
+        assert_eq!(row.6, 8_531.312_5);
         assert_eq!(row.7, payload);
         assert_eq!(sequence, 1);
 
@@ -1275,4 +2009,171 @@ mod tests {
         );
         assert!(snapshot.last_error.is_none());
     }
+
+    #[test]
+    fn symbol_batch_publishes_metrics_once_for_drained_messages() {
+        let connection = Connection::open_in_memory().expect("open duckdb");
+        connection
+            .execute_batch(
+                "CREATE TABLE raw_book_ticker (
+                    tick_id BIGINT, mode TEXT, symbol TEXT, event_time TIMESTAMP,
+                    receive_time TIMESTAMP, bid DOUBLE, bid_qty DOUBLE, ask DOUBLE, ask_qty DOUBLE
+                );
+                CREATE TABLE raw_agg_trades (
+                    trade_id BIGINT, mode TEXT, symbol TEXT, event_time TIMESTAMP,
+                    receive_time TIMESTAMP, price DOUBLE, qty DOUBLE, is_buyer_maker BOOLEAN
+                );",
+            )
+            .expect("create symbol tables");
+        let snapshot = Arc::new(Mutex::new(WorkerSnapshot::new(RecorderMetrics::default())));
+        let book_ticker = |symbol: &str, event_time: i64| {
+            serde_json::json!({
+                "stream": format!("{}@bookTicker", symbol.to_ascii_lowercase()),
+                "data": {
+                    "e": "bookTicker", "s": symbol, "E": event_time,
+                    "b": "68250.1", "B": "1.5", "a": "68250.2", "A": "2.0"
+                }
+            })
+            .to_string()
+        };
+        let agg_trade = |event_time: i64| {
+            serde_json::json!({
+                "stream": "btcusdt@aggTrade",
+                "data": {
+                    "e": "aggTrade", "s": "BTCUSDT", "E": event_time,
+                    "p": "68250.1", "q": "0.01", "m": true
+                }
+            })
+            .to_string()
+        };
+        let (mut ticker_seq, mut trade_seq) = (0i64, 0i64);
+        let mut tape = AggTradeTape::new(
+            TickSequencerConfig {
+                reorder_window_ms: 0,
+            },
+            None,
+        );
+        let mut batch = SymbolEventBatch::default();
+        for payload in [
+            book_ticker("BTCUSDT", 1_710_000_000_100),
+            book_ticker("ETHUSDT", 1_710_000_000_200),
+            book_ticker("BTCUSDT", 1_710_000_000_300),
+            agg_trade(1_710_000_000_400),
+            agg_trade(1_710_000_000_900),
+            agg_trade(1_710_000_001_100),
+        ] {
+            handle_symbol_message(
+                SymbolSink {
+                    duck_connection: Some(&connection),
+                    postgres_writer: None,
+                    mode: BinanceMode::Demo,
+                },
+                &mut ticker_seq,
+                &mut trade_seq,
+                &mut tape,
+                &mut batch,
+                Message::Text(payload),
+            )
+            .expect("handle symbol message");
+        }
+        assert_eq!(
+            snapshot
+                .lock()
+                .expect("snapshot lock")
+                .metrics
+                .book_ticker_events,
+            0
+        );
+
+        batch.publish(&snapshot);
+        batch.publish(&snapshot);
+
+        let snapshot = snapshot.lock().expect("snapshot lock");
+        assert_eq!(snapshot.metrics.book_ticker_events, 3);
+        assert_eq!(
+            snapshot.metrics.top_book_ticker_symbols,
+            vec!["BTCUSDT:2".to_string(), "ETHUSDT:1".to_string()]
+        );
+        assert_eq!(
+            snapshot.metrics.last_book_ticker_event_time.as_deref(),
+            Some("2024-03-09 16:00:00.300")
+        );
+        assert_eq!(snapshot.metrics.agg_trade_events, 3);
+        assert_eq!(snapshot.metrics.derived_kline_1s_bars, 2);
+        assert_eq!((ticker_seq, trade_seq), (3, 3));
+    }
+
+    #[test]
+    fn symbol_batch_counts_trade_id_gaps_and_keeps_them_for_backfill() {
+        let connection = Connection::open_in_memory().expect("open duckdb");
+        connection
+            .execute_batch(
+                "CREATE TABLE raw_agg_trades (
+                    trade_id BIGINT, mode TEXT, symbol TEXT, event_time TIMESTAMP,
+                    receive_time TIMESTAMP, price DOUBLE, qty DOUBLE, is_buyer_maker BOOLEAN
+                );",
+            )
+            .expect("create agg trade table");
+        let snapshot = Arc::new(Mutex::new(WorkerSnapshot::new(RecorderMetrics::default())));
+        let agg_trade = |first_id: i64, last_id: i64, event_time: i64| {
+            serde_json::json!({
+                "stream": "btcusdt@aggTrade",
+                "data": {
+                    "e": "aggTrade", "s": "BTCUSDT", "E": event_time, "a": first_id,
+                    "p": "68250.1", "q": "0.01", "f": first_id, "l": last_id, "m": true
+                }
+            })
+            .to_string()
+        };
+        let mut trade_seq = 0i64;
+        let mut tape = AggTradeTape::new(
+            TickSequencerConfig {
+                reorder_window_ms: 0,
+            },
+            None,
+        );
+        let mut batch = SymbolEventBatch::default();
+        for payload in [
+            agg_trade(100, 102, 1_710_000_000_100),
+            agg_trade(103, 103, 1_710_000_000_200),
+            agg_trade(110, 111, 1_710_000_000_300),
+            // A replayed message is neither a gap nor a reset, and is
+            // not recorded twice.
+            agg_trade(103, 103, 1_710_000_000_350),
+            agg_trade(112, 112, 1_710_000_000_400),
+        ] {
+            handle_symbol_message(
+                SymbolSink {
+                    duck_connection: Some(&connection),
+                    postgres_writer: None,
+                    mode: BinanceMode::Demo,
+                },
+                &mut 0,
+                &mut trade_seq,
+                &mut tape,
+                &mut batch,
+                Message::Text(payload),
+            )
+            .expect("handle symbol message");
+        }
+
+        batch.publish(&snapshot);
+
+        assert_eq!(
+            batch.trade_gaps,
+            vec![TradeIdGap {
+                symbol: "BTCUSDT".to_string(),
+                from_id: 104,
+                to_id: 109,
+                after_time_ms: 1_710_000_000_200,
+                before_time_ms: 1_710_000_000_300,
+            }]
+        );
+        let snapshot = snapshot.lock().expect("snapshot lock");
+        assert_eq!(snapshot.metrics.trade_id_gaps, 1);
+        assert_eq!(snapshot.metrics.missed_trades, 6);
+        assert_eq!(snapshot.metrics.agg_trade_events, 4);
+        assert_eq!(snapshot.metrics.duplicate_ticks, 1);
+        assert_eq!(trade_seq, 4);
+    }
 }
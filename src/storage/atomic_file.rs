@@ -0,0 +1,131 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::error::storage_error::StorageError;
+
+/// Starts the trailing line that checksums a store file's body.
+///
+/// Example:
+/// - `{"next_id":2,"alerts":[]}` then `sqsum1:<sha256 hex of the body>`
+pub const CHECKSUM_PREFIX: &str = "sqsum1:";
+
+/// Where the last good copy of `path` is kept, e.g. `macros.json.bak`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling(path, "bak")
+}
+
+/// Whether `path`, or the backup it would fall back to, is on disk.
+pub fn store_exists(path: &Path) -> bool {
+    path.exists() || backup_path(path).exists()
+}
+
+/// Appends the body's SHA-256 on a line of its own.
+pub fn with_checksum(body: &[u8]) -> Vec<u8> {
+    let mut framed = body.to_vec();
+    framed.extend_from_slice(format!("\n{CHECKSUM_PREFIX}{}\n", checksum(body)).as_bytes());
+    framed
+}
+
+/// The body of a checksummed file, or `None` when it does not match.
+///
+/// Files written before checksums, or whose checksum line was removed by
+/// hand, have no trailer and are returned whole.
+pub fn verify_checksum(bytes: &[u8]) -> Option<&[u8]> {
+    let text = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    let Some(newline) = text.iter().rposition(|byte| *byte == b'\n') else {
+        return Some(bytes);
+    };
+    let Some(expected) = text[newline + 1..].strip_prefix(CHECKSUM_PREFIX.as_bytes()) else {
+        return Some(bytes);
+    };
+    let body = &bytes[..newline];
+    (checksum(body).as_bytes() == expected).then_some(body)
+}
+
+/// Reads a checksummed store, falling back to its last good backup when
+/// the file is missing, truncated or fails its checksum.
+pub fn read_checked(path: &Path) -> Result<Vec<u8>, StorageError> {
+    if let Some(body) = read_verified(path) {
+        return Ok(body);
+    }
+    let backup = backup_path(path);
+    match read_verified(&backup) {
+        Some(body) => {
+            warn!(
+                path = %path.display(),
+                backup = %backup.display(),
+                "store file unreadable or failed its checksum; loaded the last good backup"
+            );
+            Ok(body)
+        }
+        None => Err(StorageError::CorruptStore {
+            path: path.display().to_string(),
+        }),
+    }
+}
+
+/// Writes `body` with a checksum trailer, keeping the current file as the
+/// backup first when it still verifies.
+pub fn write_checked(path: &Path, body: &[u8]) -> Result<(), StorageError> {
+    if read_verified(path).is_some() {
+        let backup = backup_path(path);
+        let tmp_backup = sibling(&backup, "tmp");
+        fs::copy(path, &tmp_backup).map_err(|error| write_error(path, error))?;
+        File::open(&tmp_backup)
+            .and_then(|file| file.sync_all())
+            .map_err(|error| write_error(path, error))?;
+        fs::rename(&tmp_backup, &backup).map_err(|error| write_error(path, error))?;
+    }
+    write_atomic(path, &with_checksum(body))
+}
+
+/// Replaces `path` so a crash leaves either the old or the new contents:
+/// the bytes go to a synced temp file that is then renamed over it.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), StorageError> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty());
+    if let Some(parent) = parent {
+        fs::create_dir_all(parent).map_err(|error| write_error(path, error))?;
+    }
+    let tmp_path = sibling(path, "tmp");
+    File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(bytes)?;
+            file.sync_all()
+        })
+        .map_err(|error| write_error(path, error))?;
+    fs::rename(&tmp_path, path).map_err(|error| write_error(path, error))?;
+    // The rename itself is only durable once the directory entry is.
+    #[cfg(unix)]
+    if let Ok(dir) = File::open(parent.unwrap_or(Path::new("."))) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+fn read_verified(path: &Path) -> Option<Vec<u8>> {
+    let bytes = fs::read(path).ok()?;
+    verify_checksum(&bytes).map(<[u8]>::to_vec)
+}
+
+fn checksum(body: &[u8]) -> String {
+    hex::encode(Sha256::digest(body))
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+fn write_error(path: &Path, error: std::io::Error) -> StorageError {
+    StorageError::WriteFailedWithContext {
+        message: format!("{}: {error}", path.display()),
+    }
+}
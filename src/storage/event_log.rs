@@ -1,21 +1,50 @@
 use std::fs::{create_dir_all, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::Once;
 
 use chrono::Utc;
+use tracing::warn;
 
+use crate::observability::event_routing::{EventRouting, LogSink};
 use crate::storage::models::EventRecord;
+use crate::storage::sealed;
+
+/// Version written into every event file line as `v`.
+///
+/// Lines written before versioning carry no `v` and read as version `0`;
+/// their `ts`/`kind`/`payload` shape is otherwise the same.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct EventLog {
     pub records: Vec<EventRecord>,
+    /// Decides which records also reach the event file or the notifier.
+    pub routing: EventRouting,
+    /// Records kept in memory after `compact`; `None` keeps them all.
+    pub capacity: Option<usize>,
 }
 
 impl EventLog {
     pub fn append(&mut self, record: EventRecord) {
-        append_record_to_jsonl(&record);
+        if self.routing.sinks_for(&record).contains(&LogSink::File) {
+            append_record_to_jsonl(&record);
+        }
         self.records.push(record);
     }
+
+    /// Drops the oldest in-memory records over `capacity`; routed sinks
+    /// already have them.
+    ///
+    /// Callers hold record indexes across a command to render what it
+    /// logged, so this runs between commands rather than on `append`.
+    pub fn compact(&mut self) -> usize {
+        let excess = self
+            .capacity
+            .map_or(0, |capacity| self.records.len().saturating_sub(capacity));
+        self.records.drain(..excess);
+        excess
+    }
 }
 
 pub fn log(event_log: &mut EventLog, kind: impl Into<String>, payload: serde_json::Value) {
@@ -25,23 +54,54 @@ pub fn log(event_log: &mut EventLog, kind: impl Into<String>, payload: serde_jso
     });
 }
 
-fn append_record_to_jsonl(record: &EventRecord) {
-    let path = std::env::var("SANDBOX_QUANT_EVENT_LOG_PATH")
+/// Reads:
+/// - `SANDBOX_QUANT_EVENT_LOG_PATH`, default `var/operator-events.jsonl`
+pub fn event_log_path() -> PathBuf {
+    std::env::var("SANDBOX_QUANT_EVENT_LOG_PATH")
         .map(PathBuf::from)
-        .unwrap_or_else(|_| PathBuf::from("var/operator-events.jsonl"));
+        .unwrap_or_else(|_| PathBuf::from("var/operator-events.jsonl"))
+}
+
+fn append_record_to_jsonl(record: &EventRecord) {
+    let path = event_log_path();
     if let Some(parent) = path.parent() {
         if create_dir_all(parent).is_err() {
             return;
         }
     }
+    // With sealing on but no key, drop the line rather than leak plaintext;
+    // the binaries refuse to start in that state, so this warns only once.
+    let sealer = match sealed::process_sealer() {
+        Ok(sealer) => sealer,
+        Err(error) => {
+            static WARNED: Once = Once::new();
+            WARNED.call_once(|| {
+                warn!(
+                    error = %error,
+                    "event file disabled: the store key could not be resolved"
+                );
+            });
+            return;
+        }
+    };
+    if let Some(sealer) = sealer {
+        static MIGRATE: Once = Once::new();
+        MIGRATE.call_once(|| {
+            let _ = sealed::migrate_jsonl(&path, sealer);
+        });
+    }
     let mut file = match OpenOptions::new().create(true).append(true).open(&path) {
         Ok(file) => file,
         Err(_) => return,
     };
     let line = serde_json::json!({
+        "v": EVENT_SCHEMA_VERSION,
         "ts": Utc::now().to_rfc3339(),
         "kind": record.kind,
         "payload": record.payload,
     });
+    let Ok(line) = sealed::seal_line(&line.to_string(), sealer) else {
+        return;
+    };
     let _ = writeln!(file, "{}", line);
 }
@@ -0,0 +1,144 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::storage_error::StorageError;
+use crate::observability::event_routing::{event_domain, event_level, LogLevel};
+use crate::storage::event_log::EVENT_SCHEMA_VERSION;
+use crate::storage::models::EventRecord;
+use crate::storage::sealed::{self, Sealer};
+
+/// One event file line read back with the schema it was written under.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistedEvent {
+    pub version: u32,
+    pub ts: Option<DateTime<Utc>>,
+    pub record: EventRecord,
+}
+
+impl PersistedEvent {
+    /// Example:
+    /// - `{"v":1,"ts":"..","kind":"app.execution.completed","payload":{..}}` -> version `1`
+    /// - `{"ts":"..","kind":"..","payload":{..}}` -> version `0`
+    /// - `{"v":9,..}` -> `None`, written by a newer schema
+    pub fn parse(line: &str) -> Option<Self> {
+        let value = serde_json::from_str::<serde_json::Value>(line).ok()?;
+        let version = match value.get("v") {
+            Some(version) => u32::try_from(version.as_u64()?).ok()?,
+            None => 0,
+        };
+        if version > EVENT_SCHEMA_VERSION {
+            return None;
+        }
+        Some(Self {
+            version,
+            ts: value
+                .get("ts")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+                .map(|ts| ts.with_timezone(&Utc)),
+            record: EventRecord {
+                kind: value.get("kind")?.as_str()?.to_string(),
+                payload: value.get("payload").cloned().unwrap_or_default(),
+            },
+        })
+    }
+}
+
+/// Events read back from the event file, oldest first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventReplay {
+    pub events: Vec<PersistedEvent>,
+    /// Lines that did not parse or came from a newer schema.
+    pub skipped: usize,
+}
+
+impl EventReplay {
+    /// Keeps only events whose domain is listed; an empty list keeps all.
+    ///
+    /// Example:
+    /// - `execution,strategy` keeps `app.execution.*` and `app.strategy.*`
+    pub fn from_lines<S: AsRef<str>>(lines: &[S], domains: &BTreeSet<String>) -> Self {
+        let mut replay = Self::default();
+        for line in lines {
+            match PersistedEvent::parse(line.as_ref()) {
+                Some(event)
+                    if domains.is_empty() || domains.contains(event_domain(&event.record.kind)) =>
+                {
+                    replay.events.push(event)
+                }
+                Some(_) => {}
+                None => replay.skipped += 1,
+            }
+        }
+        replay
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &EventRecord> {
+        self.events.iter().map(|event| &event.record)
+    }
+
+    pub fn summary(&self) -> EventReplaySummary {
+        let mut summary = EventReplaySummary {
+            events: self.events.len(),
+            skipped: self.skipped,
+            ..EventReplaySummary::default()
+        };
+        for event in &self.events {
+            let kind = event.record.kind.as_str();
+            *summary.versions.entry(event.version).or_default() += 1;
+            *summary
+                .domains
+                .entry(event_domain(kind).to_string())
+                .or_default() += 1;
+            if event_level(kind) == LogLevel::Error {
+                summary.errors += 1;
+            }
+            if let Some(ts) = event.ts {
+                summary.first_ts = Some(summary.first_ts.map_or(ts, |first| first.min(ts)));
+                summary.last_ts = Some(summary.last_ts.map_or(ts, |last| last.max(ts)));
+            }
+            let payload = &event.record.payload;
+            if kind == "app.execution.completed" {
+                if let Some(outcome) = payload.get("outcome_kind").and_then(|v| v.as_str()) {
+                    *summary.outcomes.entry(outcome.to_string()).or_default() += 1;
+                }
+            }
+            if let Some(instrument) = payload.get("instrument").and_then(|v| v.as_str()) {
+                summary.instruments.insert(instrument.to_string());
+            }
+        }
+        summary
+    }
+}
+
+/// Counts rebuilt from a replayed event file for recovery and audits.
+///
+/// Example:
+/// - `events=120 skipped=1 v0=20 v1=100`
+/// - `execution=80 strategy=40`, outcomes `submitted=12 already-at-target=3`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventReplaySummary {
+    pub events: usize,
+    pub skipped: usize,
+    pub versions: BTreeMap<u32, usize>,
+    pub domains: BTreeMap<String, usize>,
+    /// Events whose name marks them as failures.
+    pub errors: usize,
+    pub first_ts: Option<DateTime<Utc>>,
+    pub last_ts: Option<DateTime<Utc>>,
+    /// `app.execution.completed` events by `outcome_kind`.
+    pub outcomes: BTreeMap<String, usize>,
+    pub instruments: BTreeSet<String>,
+}
+
+/// Reads an event file, opening sealed lines with `sealer`.
+pub fn replay_event_file(
+    path: &Path,
+    sealer: Option<&Sealer>,
+    domains: &BTreeSet<String>,
+) -> Result<EventReplay, StorageError> {
+    let lines = sealed::read_jsonl_lines(path, sealer)?;
+    Ok(EventReplay::from_lines(&lines, domains))
+}
@@ -1,4 +1,8 @@
+pub mod atomic_file;
 pub mod event_log;
+pub mod event_replay;
 pub mod market_data_store;
 pub mod models;
+pub mod order_store;
 pub mod postgres_market_data;
+pub mod sealed;
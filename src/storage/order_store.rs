@@ -0,0 +1,525 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{named_params, Connection, Row};
+
+use crate::app::bootstrap::BinanceMode;
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::domain::order::OrderStatus;
+use crate::domain::position::Side;
+use crate::error::storage_error::StorageError;
+use crate::execution::history::OrderHistoryRow;
+
+pub const DEFAULT_ORDER_RETENTION_DAYS: u32 = 90;
+
+/// Buckets `/history roi` lists, newest first.
+pub const HISTORY_ROI_BUCKETS: usize = 24;
+
+/// Schema steps in order; `PRAGMA user_version` records how many ran.
+const MIGRATIONS: [&str; 3] = [
+    "CREATE TABLE orders (
+        id INTEGER PRIMARY KEY,
+        time_ms INTEGER NOT NULL,
+        instrument TEXT NOT NULL,
+        market TEXT NOT NULL,
+        side TEXT NOT NULL,
+        qty REAL NOT NULL,
+        price REAL,
+        status TEXT NOT NULL,
+        tag TEXT NOT NULL,
+        depth_imbalance REAL,
+        list_id INTEGER,
+        parent_id INTEGER,
+        signal_time_ms INTEGER,
+        exchange_time_ms INTEGER
+    );
+    CREATE TABLE fills (
+        id INTEGER PRIMARY KEY,
+        time_ms INTEGER NOT NULL,
+        instrument TEXT NOT NULL,
+        market TEXT NOT NULL,
+        client_order_id TEXT NOT NULL,
+        tag TEXT NOT NULL,
+        side TEXT NOT NULL,
+        qty REAL NOT NULL,
+        price REAL NOT NULL,
+        realized_pnl REAL NOT NULL DEFAULT 0,
+        commission REAL NOT NULL DEFAULT 0
+    );",
+    "CREATE INDEX orders_time ON orders (time_ms);
+    CREATE INDEX orders_instrument_time ON orders (instrument, time_ms);
+    CREATE INDEX orders_tag_time ON orders (tag, time_ms);
+    CREATE INDEX fills_time ON fills (time_ms);
+    CREATE INDEX fills_instrument_time ON fills (instrument, time_ms);
+    CREATE INDEX fills_tag_time ON fills (tag, time_ms);",
+    // Client order ids restart every session; venue ids tell trades apart.
+    "ALTER TABLE fills ADD COLUMN order_id INTEGER;",
+];
+
+/// One execution reported by the user data stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillRow {
+    pub time: DateTime<Utc>,
+    pub instrument: Instrument,
+    pub market: Market,
+    pub client_order_id: String,
+    /// Venue order id; unlike the client order id, never reused.
+    pub order_id: Option<u64>,
+    /// `strategy:<watch_id>` for strategy orders, `manual` otherwise.
+    pub tag: String,
+    pub side: Side,
+    pub qty: f64,
+    pub price: f64,
+    /// Futures only; spot fills realize nothing on their own.
+    pub realized_pnl: f64,
+    pub commission: f64,
+}
+
+/// Calendar span order and fill totals are grouped by, in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeBucket {
+    Hour,
+    Day,
+    Month,
+}
+
+impl TimeBucket {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "hour" => Some(Self::Hour),
+            "day" => Some(Self::Day),
+            "month" => Some(Self::Month),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Month => "month",
+        }
+    }
+
+    fn strftime(self) -> &'static str {
+        match self {
+            Self::Hour => "%Y-%m-%d %H:00",
+            Self::Day => "%Y-%m-%d",
+            Self::Month => "%Y-%m",
+        }
+    }
+}
+
+/// Order and fill totals for one time bucket.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryBucket {
+    /// Bucket start, e.g. `2024-03-01 14:00`, `2024-03-01` or `2024-03`.
+    pub bucket: String,
+    pub orders: usize,
+    pub rejected: usize,
+    /// `qty * price` over the orders with a known price.
+    pub notional: f64,
+    pub fills: usize,
+    pub filled_notional: f64,
+    pub realized_pnl: f64,
+    pub commission: f64,
+}
+
+impl HistoryBucket {
+    /// Realized PnL as a percent of the notional filled in the bucket.
+    ///
+    /// Example:
+    /// - `realized_pnl=12` over `filled_notional=4000` -> `0.3`
+    pub fn roi_pct(&self) -> Option<f64> {
+        (self.filled_notional > f64::EPSILON)
+            .then(|| self.realized_pnl / self.filled_notional * 100.0)
+    }
+}
+
+/// Narrows `OrderStore::bucket_stats`; `tag` is a prefix, as in `/history`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryQuery {
+    pub instrument: Option<Instrument>,
+    pub tag: Option<String>,
+}
+
+/// Submitted orders and fills kept across restarts in SQLite.
+///
+/// `OrderHistory` stays the in-memory view of recent orders; this store
+/// answers the queries over everything inside the retention window.
+///
+/// Unlike the journal, alerts and macros, the file is not sealed under
+/// `SANDBOX_QUANT_SEAL`: the history queries filter and group rows in SQL.
+#[derive(Debug)]
+pub struct OrderStore {
+    connection: Connection,
+    path: Option<PathBuf>,
+    pub retention_days: u32,
+}
+
+impl OrderStore {
+    pub fn open(path: &Path) -> Result<Self, StorageError> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent).map_err(|error| init_failed(path, error))?;
+        }
+        let connection = Connection::open(path).map_err(|error| init_failed(path, error))?;
+        Self::with_connection(connection, Some(path.to_path_buf()))
+    }
+
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        let connection = Connection::open_in_memory()
+            .map_err(|error| init_failed(Path::new(":memory:"), error))?;
+        Self::with_connection(connection, None)
+    }
+
+    /// Reads:
+    /// - `SANDBOX_QUANT_ORDER_STORE_PATH` (default `var/orders-<mode>.sqlite`)
+    /// - `SANDBOX_QUANT_ORDER_RETENTION_DAYS` (default `90`)
+    pub fn from_env(mode: BinanceMode) -> Result<Self, StorageError> {
+        let path = std::env::var("SANDBOX_QUANT_ORDER_STORE_PATH")
+            .ok()
+            .filter(|raw| !raw.trim().is_empty())
+            .map_or_else(
+                || PathBuf::from(format!("var/orders-{}.sqlite", mode.as_str())),
+                PathBuf::from,
+            );
+        let mut store = Self::open(&path)?;
+        if let Some(days) = std::env::var("SANDBOX_QUANT_ORDER_RETENTION_DAYS")
+            .ok()
+            .and_then(|raw| raw.trim().parse::<u32>().ok())
+            .filter(|days| *days > 0)
+        {
+            store.retention_days = days;
+        }
+        Ok(store)
+    }
+
+    fn with_connection(
+        connection: Connection,
+        path: Option<PathBuf>,
+    ) -> Result<Self, StorageError> {
+        let store = Self {
+            connection,
+            path,
+            retention_days: DEFAULT_ORDER_RETENTION_DAYS,
+        };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Schema version; equals the number of migrations once open.
+    pub fn schema_version(&self) -> Result<usize, StorageError> {
+        self.connection
+            .query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))
+            .map(|version| version as usize)
+            .map_err(query_failed)
+    }
+
+    fn migrate(&self) -> Result<(), StorageError> {
+        let path = self.path.as_deref().unwrap_or(Path::new(":memory:"));
+        let applied = self.schema_version()?;
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+            self.connection
+                .execute_batch(&format!(
+                    "BEGIN; {migration} PRAGMA user_version = {}; COMMIT;",
+                    index + 1
+                ))
+                .map_err(|error| init_failed(path, error))?;
+        }
+        Ok(())
+    }
+
+    pub fn record_order(&self, row: &OrderHistoryRow) -> Result<(), StorageError> {
+        self.connection
+            .execute(
+                "INSERT INTO orders (time_ms, instrument, market, side, qty, price, status, tag,
+                    depth_imbalance, list_id, parent_id, signal_time_ms, exchange_time_ms)
+                 VALUES (:time_ms, :instrument, :market, :side, :qty, :price, :status, :tag,
+                    :depth_imbalance, :list_id, :parent_id, :signal_time_ms, :exchange_time_ms)",
+                named_params! {
+                    ":time_ms": row.time.timestamp_millis(),
+                    ":instrument": row.instrument.0,
+                    ":market": market_text(row.market),
+                    ":side": side_text(row.side),
+                    ":qty": row.qty,
+                    ":price": row.price,
+                    ":status": status_text(row.status),
+                    ":tag": row.tag,
+                    ":depth_imbalance": row.depth_imbalance,
+                    ":list_id": row.list_id.map(|id| id as i64),
+                    ":parent_id": row.parent_id.map(|id| id as i64),
+                    ":signal_time_ms": row.signal_time.map(|time| time.timestamp_millis()),
+                    ":exchange_time_ms": row.exchange_time.map(|time| time.timestamp_millis()),
+                },
+            )
+            .map(|_| ())
+            .map_err(write_failed)
+    }
+
+    pub fn record_fill(&self, fill: &FillRow) -> Result<(), StorageError> {
+        self.connection
+            .execute(
+                "INSERT INTO fills (time_ms, instrument, market, client_order_id, tag, side, qty,
+                    price, realized_pnl, commission, order_id)
+                 VALUES (:time_ms, :instrument, :market, :client_order_id, :tag, :side, :qty,
+                    :price, :realized_pnl, :commission, :order_id)",
+                named_params! {
+                    ":time_ms": fill.time.timestamp_millis(),
+                    ":instrument": fill.instrument.0,
+                    ":market": market_text(fill.market),
+                    ":client_order_id": fill.client_order_id,
+                    ":tag": fill.tag,
+                    ":side": side_text(fill.side),
+                    ":qty": fill.qty,
+                    ":price": fill.price,
+                    ":realized_pnl": fill.realized_pnl,
+                    ":commission": fill.commission,
+                    ":order_id": fill.order_id.map(|id| id as i64),
+                },
+            )
+            .map(|_| ())
+            .map_err(write_failed)
+    }
+
+    /// The newest `limit` orders, oldest first, ready to seed `OrderHistory`.
+    pub fn recent_orders(&self, limit: usize) -> Result<Vec<OrderHistoryRow>, StorageError> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT time_ms, instrument, market, side, qty, price, status, tag, depth_imbalance,
+                    list_id, parent_id, signal_time_ms, exchange_time_ms
+                 FROM orders ORDER BY time_ms DESC, id DESC LIMIT :limit",
+            )
+            .map_err(query_failed)?;
+        let mut rows = statement
+            .query_map(named_params! { ":limit": limit as i64 }, order_row)
+            .map_err(query_failed)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(query_failed)?;
+        rows.reverse();
+        Ok(rows.into_iter().flatten().collect())
+    }
+
+    /// Order and fill totals per bucket, newest bucket first.
+    ///
+    /// Example:
+    /// - `TimeBucket::Day` with `tag=strategy:3` -> one row per UTC day
+    ///   that watch 3 ordered or filled in
+    pub fn bucket_stats(
+        &self,
+        bucket: TimeBucket,
+        query: &HistoryQuery,
+        limit: usize,
+    ) -> Result<Vec<HistoryBucket>, StorageError> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "WITH matched AS (
+                    SELECT time_ms, 1 AS orders, status = 'rejected' AS rejected,
+                        qty * COALESCE(price, 0) AS notional, 0 AS fills,
+                        0.0 AS filled_notional, 0.0 AS realized_pnl, 0.0 AS commission
+                    FROM orders
+                    WHERE (:instrument IS NULL OR instrument = :instrument)
+                        AND (:tag IS NULL OR substr(tag, 1, length(:tag)) = :tag)
+                    UNION ALL
+                    SELECT time_ms, 0, 0, 0.0, 1, qty * price, realized_pnl, commission
+                    FROM fills
+                    WHERE (:instrument IS NULL OR instrument = :instrument)
+                        AND (:tag IS NULL OR substr(tag, 1, length(:tag)) = :tag)
+                 )
+                 SELECT strftime(:format, time_ms / 1000, 'unixepoch') AS bucket,
+                    SUM(orders), SUM(rejected), SUM(notional), SUM(fills),
+                    SUM(filled_notional), SUM(realized_pnl), SUM(commission)
+                 FROM matched GROUP BY bucket ORDER BY bucket DESC LIMIT :limit",
+            )
+            .map_err(query_failed)?;
+        let rows = statement
+            .query_map(
+                named_params! {
+                    ":instrument": query.instrument.as_ref().map(|instrument| &instrument.0),
+                    ":tag": query.tag,
+                    ":format": bucket.strftime(),
+                    ":limit": limit as i64,
+                },
+                |row| {
+                    Ok(HistoryBucket {
+                        bucket: row.get(0)?,
+                        orders: row.get::<_, i64>(1)? as usize,
+                        rejected: row.get::<_, i64>(2)? as usize,
+                        notional: row.get(3)?,
+                        fills: row.get::<_, i64>(4)? as usize,
+                        filled_notional: row.get(5)?,
+                        realized_pnl: row.get(6)?,
+                        commission: row.get(7)?,
+                    })
+                },
+            )
+            .map_err(query_failed)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(query_failed)
+    }
+
+    /// Net PnL, fees included, of each order that realized any since
+    /// `since`, oldest first; partial fills of one order add up to one
+    /// trade.
+    ///
+    /// Only futures fills carry realized PnL, so spot trades never show.
+    pub fn realized_trades(
+        &self,
+        query: &HistoryQuery,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<f64>, StorageError> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT SUM(realized_pnl - commission) FROM fills
+                 WHERE realized_pnl != 0 AND time_ms >= :since
+                    AND (:instrument IS NULL OR instrument = :instrument)
+                    AND (:tag IS NULL OR substr(tag, 1, length(:tag)) = :tag)
+                 GROUP BY instrument, COALESCE(order_id, client_order_id)
+                 ORDER BY MIN(time_ms), MIN(id)",
+            )
+            .map_err(query_failed)?;
+        let rows = statement
+            .query_map(
+                named_params! {
+                    ":since": since.map_or(i64::MIN, |since| since.timestamp_millis()),
+                    ":instrument": query.instrument.as_ref().map(|instrument| &instrument.0),
+                    ":tag": query.tag,
+                },
+                |row| row.get::<_, f64>(0),
+            )
+            .map_err(query_failed)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(query_failed)
+    }
+
+    /// Deletes orders and fills older than the retention window; returns
+    /// how many rows went.
+    pub fn compact(&self, now: DateTime<Utc>) -> Result<usize, StorageError> {
+        let cutoff = (now - Duration::days(i64::from(self.retention_days))).timestamp_millis();
+        let orders = self
+            .connection
+            .execute(
+                "DELETE FROM orders WHERE time_ms < :cutoff",
+                named_params! { ":cutoff": cutoff },
+            )
+            .map_err(write_failed)?;
+        let fills = self
+            .connection
+            .execute(
+                "DELETE FROM fills WHERE time_ms < :cutoff",
+                named_params! { ":cutoff": cutoff },
+            )
+            .map_err(write_failed)?;
+        Ok(orders + fills)
+    }
+}
+
+/// `None` for a row this build cannot decode, e.g. written by a newer one.
+fn order_row(row: &Row<'_>) -> rusqlite::Result<Option<OrderHistoryRow>> {
+    let time = DateTime::<Utc>::from_timestamp_millis(row.get(0)?);
+    let market = parse_market(&row.get::<_, String>(2)?);
+    let side = parse_side(&row.get::<_, String>(3)?);
+    let status = parse_status(&row.get::<_, String>(6)?);
+    let (Some(time), Some(market), Some(side), Some(status)) = (time, market, side, status) else {
+        return Ok(None);
+    };
+    Ok(Some(OrderHistoryRow {
+        time,
+        instrument: Instrument::new(row.get::<_, String>(1)?),
+        market,
+        side,
+        qty: row.get(4)?,
+        price: row.get(5)?,
+        status,
+        tag: row.get(7)?,
+        depth_imbalance: row.get(8)?,
+        list_id: row.get::<_, Option<i64>>(9)?.map(|id| id as u64),
+        parent_id: row.get::<_, Option<i64>>(10)?.map(|id| id as u64),
+        signal_time: row
+            .get::<_, Option<i64>>(11)?
+            .and_then(DateTime::<Utc>::from_timestamp_millis),
+        exchange_time: row
+            .get::<_, Option<i64>>(12)?
+            .and_then(DateTime::<Utc>::from_timestamp_millis),
+    }))
+}
+
+fn market_text(market: Market) -> &'static str {
+    match market {
+        Market::Spot => "spot",
+        Market::Futures => "futures",
+        Market::Options => "options",
+    }
+}
+
+fn parse_market(raw: &str) -> Option<Market> {
+    match raw {
+        "spot" => Some(Market::Spot),
+        "futures" => Some(Market::Futures),
+        "options" => Some(Market::Options),
+        _ => None,
+    }
+}
+
+fn side_text(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "buy",
+        Side::Sell => "sell",
+    }
+}
+
+fn parse_side(raw: &str) -> Option<Side> {
+    match raw {
+        "buy" => Some(Side::Buy),
+        "sell" => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+fn status_text(status: OrderStatus) -> &'static str {
+    match status {
+        OrderStatus::PendingSubmit => "pending_submit",
+        OrderStatus::Submitted => "submitted",
+        OrderStatus::Filled => "filled",
+        OrderStatus::Cancelled => "cancelled",
+        OrderStatus::Rejected => "rejected",
+    }
+}
+
+fn parse_status(raw: &str) -> Option<OrderStatus> {
+    match raw {
+        "pending_submit" => Some(OrderStatus::PendingSubmit),
+        "submitted" => Some(OrderStatus::Submitted),
+        "filled" => Some(OrderStatus::Filled),
+        "cancelled" => Some(OrderStatus::Cancelled),
+        "rejected" => Some(OrderStatus::Rejected),
+        _ => None,
+    }
+}
+
+fn init_failed(path: &Path, error: impl std::fmt::Display) -> StorageError {
+    StorageError::DatabaseInitFailed {
+        path: path.display().to_string(),
+        message: error.to_string(),
+    }
+}
+
+fn write_failed(error: rusqlite::Error) -> StorageError {
+    StorageError::WriteFailedWithContext {
+        message: error.to_string(),
+    }
+}
+
+fn query_failed(error: rusqlite::Error) -> StorageError {
+    StorageError::QueryFailed {
+        message: error.to_string(),
+    }
+}
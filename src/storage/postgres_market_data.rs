@@ -740,6 +740,12 @@ pub fn metrics_for_postgres_url(
         book_ticker_events: query_count(&mut client, "raw_book_ticker")?,
         agg_trade_events: query_count(&mut client, "raw_agg_trades")?,
         derived_kline_1s_bars: 0,
+        trade_id_gaps: 0,
+        missed_trades: 0,
+        backfilled_trades: 0,
+        duplicate_ticks: 0,
+        reordered_ticks: 0,
+        late_ticks: 0,
         schema_version: existing_schema_version(&mut client)?,
         last_liquidation_event_time: query_latest_timestamp(
             &mut client,
@@ -1075,8 +1081,8 @@ pub fn export_snapshot_to_duckdb(
                     message: format!("query raw_klines from postgres failed: {error}"),
                 })?;
 
-            let mut next_id = next_duckdb_kline_id(&duck)?;
-            for row in rows {
+            let first_id = next_duckdb_kline_id(&duck)?;
+            for (next_id, row) in (first_id..).zip(rows) {
                 duck.execute(
                     "INSERT INTO raw_klines (
                         kline_id, mode, product, symbol, interval, open_time, close_time,
@@ -1109,7 +1115,6 @@ pub fn export_snapshot_to_duckdb(
                 .map_err(|error| StorageError::WriteFailedWithContext {
                     message: format!("insert raw_klines into duckdb failed: {error}"),
                 })?;
-                next_id += 1;
                 kline_rows += 1;
             }
         }
@@ -1129,8 +1134,8 @@ pub fn export_snapshot_to_duckdb(
                     &[symbol, &from_time_ms, &to_time_ms],
                 )
                 .map_err(storage_err)?;
-            let mut next_id = next_duckdb_liquidation_event_id(&duck)?;
-            for row in rows {
+            let first_id = next_duckdb_liquidation_event_id(&duck)?;
+            for (next_id, row) in (first_id..).zip(rows) {
                 duck.execute(
                     "INSERT INTO raw_liquidation_events (
                         event_id, mode, symbol, event_time, receive_time, force_side, price, qty, notional, raw_payload
@@ -1151,7 +1156,6 @@ pub fn export_snapshot_to_duckdb(
                     ],
                 )
                 .map_err(storage_err)?;
-                next_id += 1;
                 liquidation_rows += 1;
             }
         }
@@ -1171,8 +1175,8 @@ pub fn export_snapshot_to_duckdb(
                     &[symbol, &from_time_ms, &to_time_ms],
                 )
                 .map_err(storage_err)?;
-            let mut next_id = next_duckdb_book_ticker_id(&duck)?;
-            for row in rows {
+            let first_id = next_duckdb_book_ticker_id(&duck)?;
+            for (next_id, row) in (first_id..).zip(rows) {
                 duck.execute(
                     "INSERT INTO raw_book_ticker (
                         tick_id, mode, symbol, event_time, receive_time, bid, bid_qty, ask, ask_qty
@@ -1192,7 +1196,6 @@ pub fn export_snapshot_to_duckdb(
                     ],
                 )
                 .map_err(storage_err)?;
-                next_id += 1;
                 book_ticker_rows += 1;
             }
         }
@@ -1212,8 +1215,8 @@ pub fn export_snapshot_to_duckdb(
                     &[symbol, &from_time_ms, &to_time_ms],
                 )
                 .map_err(storage_err)?;
-            let mut next_id = next_duckdb_agg_trade_id(&duck)?;
-            for row in rows {
+            let first_id = next_duckdb_agg_trade_id(&duck)?;
+            for (next_id, row) in (first_id..).zip(rows) {
                 duck.execute(
                     "INSERT INTO raw_agg_trades (
                         trade_id, mode, symbol, event_time, receive_time, price, qty, is_buyer_maker
@@ -1232,7 +1235,6 @@ pub fn export_snapshot_to_duckdb(
                     ],
                 )
                 .map_err(storage_err)?;
-                next_id += 1;
                 agg_trade_rows += 1;
             }
         }
@@ -0,0 +1,445 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::error::storage_error::StorageError;
+use crate::storage::atomic_file::{backup_path, read_checked, write_atomic, write_checked};
+
+/// Marks a sealed file body or a sealed JSONL line.
+///
+/// Example:
+/// - `sqseal1:<salt hex>:<nonce hex>:<ciphertext hex>`
+pub const SEALED_PREFIX: &str = "sqseal1:";
+pub const SEAL_PBKDF2_ITERATIONS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+const KEYRING_SERVICE: &str = "sandbox-quant";
+const KEYRING_ACCOUNT: &str = "store-key";
+
+/// Where the at-rest encryption secret comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SealKeySource {
+    /// A random key kept in the OS keyring (`secret-tool` or macOS `security`).
+    Keyring,
+    /// A passphrase from the environment or an interactive prompt.
+    Passphrase,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SealConfig {
+    pub key_source: Option<SealKeySource>,
+}
+
+impl SealConfig {
+    /// Reads:
+    /// - `SANDBOX_QUANT_SEAL` (`off`, `keyring` or `passphrase`, default `off`)
+    pub fn from_env() -> Result<Self, StorageError> {
+        let Ok(raw) = std::env::var("SANDBOX_QUANT_SEAL") else {
+            return Ok(Self::default());
+        };
+        let key_source = match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "off" | "none" => None,
+            "keyring" => Some(SealKeySource::Keyring),
+            "passphrase" => Some(SealKeySource::Passphrase),
+            other => {
+                return Err(StorageError::SealFailed {
+                    message: format!("unknown SANDBOX_QUANT_SEAL value: {other}"),
+                })
+            }
+        };
+        Ok(Self { key_source })
+    }
+
+    /// Fetches the secret and builds a sealer, or `None` when sealing is off.
+    ///
+    /// Reads:
+    /// - `SANDBOX_QUANT_SEAL_PASSPHRASE` (otherwise prompts on the terminal)
+    pub fn resolve(&self) -> Result<Option<Sealer>, StorageError> {
+        let secret = match self.key_source {
+            None => return Ok(None),
+            Some(SealKeySource::Keyring) => keyring_secret()?,
+            Some(SealKeySource::Passphrase) => match std::env::var("SANDBOX_QUANT_SEAL_PASSPHRASE")
+            {
+                Ok(passphrase) => passphrase,
+                Err(_) => prompt_passphrase()?,
+            },
+        };
+        if secret.is_empty() {
+            return Err(StorageError::SealFailed {
+                message: "empty seal passphrase".to_string(),
+            });
+        }
+        Sealer::from_secret(secret.as_bytes()).map(Some)
+    }
+}
+
+/// Encrypts stored files with AES-256-GCM under a PBKDF2-derived key.
+///
+/// Every envelope carries its own salt, so files sealed by earlier sessions
+/// open as long as the secret is the same.
+pub struct Sealer {
+    secret: Vec<u8>,
+    salt: [u8; SALT_LEN],
+    keys: Mutex<BTreeMap<[u8; SALT_LEN], [u8; 32]>>,
+    rng: SystemRandom,
+}
+
+impl std::fmt::Debug for Sealer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sealer").finish_non_exhaustive()
+    }
+}
+
+impl Sealer {
+    pub fn from_secret(secret: &[u8]) -> Result<Self, StorageError> {
+        let rng = SystemRandom::new();
+        let mut salt = [0u8; SALT_LEN];
+        rng.fill(&mut salt).map_err(|_| seal_error("random salt"))?;
+        Ok(Self {
+            secret: secret.to_vec(),
+            salt,
+            keys: Mutex::new(BTreeMap::new()),
+            rng,
+        })
+    }
+
+    pub fn seal(&self, plaintext: &[u8]) -> Result<String, StorageError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| seal_error("random nonce"))?;
+        let mut in_out = plaintext.to_vec();
+        self.key(&self.salt)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| seal_error("encrypt"))?;
+        Ok(format!(
+            "{SEALED_PREFIX}{}:{}:{}",
+            hex::encode(self.salt),
+            hex::encode(nonce),
+            hex::encode(in_out)
+        ))
+    }
+
+    pub fn open(&self, sealed: &str) -> Result<Vec<u8>, StorageError> {
+        let body = sealed
+            .trim()
+            .strip_prefix(SEALED_PREFIX)
+            .ok_or_else(|| seal_error("missing seal header"))?;
+        let mut parts = body.split(':');
+        let (Some(salt), Some(nonce), Some(ciphertext), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(seal_error("malformed sealed envelope"));
+        };
+        let salt: [u8; SALT_LEN] = decode_fixed(salt)?;
+        let nonce: [u8; NONCE_LEN] = decode_fixed(nonce)?;
+        let mut in_out = hex::decode(ciphertext).map_err(|_| seal_error("malformed ciphertext"))?;
+        let plaintext = self
+            .key(&salt)?
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| seal_error("wrong key or corrupted file"))?;
+        Ok(plaintext.to_vec())
+    }
+
+    fn key(&self, salt: &[u8; SALT_LEN]) -> Result<LessSafeKey, StorageError> {
+        let mut keys = self.keys.lock().map_err(|_| seal_error("key cache"))?;
+        let bytes = keys.entry(*salt).or_insert_with(|| {
+            let mut bytes = [0u8; 32];
+            pbkdf2::derive(
+                pbkdf2::PBKDF2_HMAC_SHA256,
+                NonZeroU32::new(SEAL_PBKDF2_ITERATIONS).expect("non-zero iterations"),
+                salt,
+                &self.secret,
+                &mut bytes,
+            );
+            bytes
+        });
+        let unbound =
+            UnboundKey::new(&AES_256_GCM, bytes.as_slice()).map_err(|_| seal_error("key setup"))?;
+        Ok(LessSafeKey::new(unbound))
+    }
+}
+
+pub fn is_sealed(bytes: &[u8]) -> bool {
+    bytes.starts_with(SEALED_PREFIX.as_bytes())
+}
+
+/// The sealer for this process, resolved once from `SealConfig::from_env`.
+pub fn process_sealer() -> Result<Option<&'static Sealer>, StorageError> {
+    static SEALER: OnceLock<Result<Option<Sealer>, StorageError>> = OnceLock::new();
+    SEALER
+        .get_or_init(|| SealConfig::from_env()?.resolve())
+        .as_ref()
+        .map(Option::as_ref)
+        .map_err(Clone::clone)
+}
+
+/// Reads a whole-file store, opening it when sealed.
+///
+/// The checksum is verified first and the last good backup is used when the
+/// file is torn or corrupted. A plaintext file read while sealing is on is
+/// rewritten sealed in place, which is the migration path for files written
+/// by earlier versions.
+pub fn read_store_file(path: &Path, sealer: Option<&Sealer>) -> Result<Vec<u8>, StorageError> {
+    let bytes = read_checked(path)?;
+    if is_sealed(&bytes) {
+        let sealer = sealer.ok_or_else(|| locked_error(path))?;
+        return sealer.open(&String::from_utf8_lossy(&bytes));
+    }
+    if sealer.is_some() {
+        write_store_file(path, &bytes, sealer)?;
+    }
+    Ok(bytes)
+}
+
+/// Writes a whole-file store atomically with a checksum, sealing it when
+/// enabled. The replaced file is kept as the `.bak` backup.
+///
+/// Refuses to replace a sealed file with plaintext when no sealer is set.
+pub fn write_store_file(
+    path: &Path,
+    bytes: &[u8],
+    sealer: Option<&Sealer>,
+) -> Result<(), StorageError> {
+    let body = match sealer {
+        Some(sealer) => sealer.seal(bytes)?.into_bytes(),
+        None => {
+            if fs::read(path).is_ok_and(|existing| is_sealed(&existing)) {
+                return Err(locked_error(path));
+            }
+            bytes.to_vec()
+        }
+    };
+    write_checked(path, &body)?;
+    // A plaintext file replaced by its sealed form must not live on as the backup.
+    let backup = backup_path(path);
+    if sealer.is_some() && fs::read(&backup).is_ok_and(|existing| !is_sealed(&existing)) {
+        fs::remove_file(&backup).map_err(|error| write_error(path, error))?;
+    }
+    Ok(())
+}
+
+/// Seals one JSONL line, or returns it unchanged when sealing is off.
+pub fn seal_line(line: &str, sealer: Option<&Sealer>) -> Result<String, StorageError> {
+    match sealer {
+        Some(sealer) => sealer.seal(line.as_bytes()),
+        None => Ok(line.to_string()),
+    }
+}
+
+/// Reads a JSONL file whose lines may be a mix of sealed and plaintext.
+///
+/// A sealed final line cut off by a crash mid-append, i.e. one with no
+/// trailing newline that fails to open, is dropped rather than failing the
+/// whole read.
+pub fn read_jsonl_lines(path: &Path, sealer: Option<&Sealer>) -> Result<Vec<String>, StorageError> {
+    let text = fs::read_to_string(path).map_err(|error| read_error(path, error))?;
+    let torn_tail = (!text.ends_with('\n'))
+        .then(|| text.lines().last())
+        .flatten();
+    let mut lines = Vec::new();
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        if !is_sealed(line.as_bytes()) {
+            lines.push(line.to_string());
+            continue;
+        }
+        let sealer = sealer.ok_or_else(|| locked_error(path))?;
+        match sealer.open(line) {
+            Ok(plaintext) => lines.push(String::from_utf8_lossy(&plaintext).into_owned()),
+            Err(_) if torn_tail.is_some_and(|tail| std::ptr::eq(tail, line)) => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(lines)
+}
+
+/// Seals every plaintext line of a JSONL file in place.
+///
+/// Example:
+/// - 3 plaintext lines and 1 sealed line -> returns `3`
+pub fn migrate_jsonl(path: &Path, sealer: &Sealer) -> Result<usize, StorageError> {
+    if !path.exists() {
+        return Ok(0);
+    }
+    let text = fs::read_to_string(path).map_err(|error| read_error(path, error))?;
+    let mut migrated = 0;
+    let mut body = String::new();
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        if is_sealed(line.as_bytes()) {
+            body.push_str(line);
+        } else {
+            body.push_str(&sealer.seal(line.as_bytes())?);
+            migrated += 1;
+        }
+        body.push('\n');
+    }
+    if migrated > 0 {
+        write_atomic(path, body.as_bytes())?;
+    }
+    Ok(migrated)
+}
+
+fn keyring_secret() -> Result<String, StorageError> {
+    if let Some(secret) = keyring_lookup() {
+        return Ok(secret);
+    }
+    let mut key = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| seal_error("random keyring key"))?;
+    let secret = hex::encode(key);
+    keyring_store(&secret)?;
+    Ok(secret)
+}
+
+fn keyring_lookup() -> Option<String> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", KEYRING_SERVICE])
+            .args(["-a", KEYRING_ACCOUNT, "-w"])
+            .output()
+    } else {
+        Command::new("secret-tool")
+            .args(["lookup", "service", KEYRING_SERVICE])
+            .args(["account", KEYRING_ACCOUNT])
+            .output()
+    }
+    .ok()?;
+    let secret = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !secret.is_empty()).then_some(secret)
+}
+
+/// Hands `secret` to the keyring over stdin so it never shows up in the
+/// process list; `security -i` reads its command from stdin for that.
+fn keyring_store(secret: &str) -> Result<(), StorageError> {
+    let (mut command, input) = if cfg!(target_os = "macos") {
+        let mut command = Command::new("security");
+        command.arg("-i");
+        (
+            command,
+            format!(
+                "add-generic-password -U -s {KEYRING_SERVICE} -a {KEYRING_ACCOUNT} -w {secret}\n"
+            ),
+        )
+    } else {
+        let mut command = Command::new("secret-tool");
+        command
+            .args(["store", "--label=sandbox-quant store key"])
+            .args(["service", KEYRING_SERVICE, "account", KEYRING_ACCOUNT]);
+        (command, secret.to_string())
+    };
+    let status = command.stdin(Stdio::piped()).spawn().and_then(|mut child| {
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes())?;
+        }
+        child.wait()
+    });
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(seal_error(&format!("keyring store exited with {status}"))),
+        Err(error) => Err(seal_error(&format!("keyring unavailable: {error}"))),
+    }
+}
+
+fn prompt_passphrase() -> Result<String, StorageError> {
+    eprint!("sandbox-quant store passphrase: ");
+    std::io::stderr().flush().ok();
+    let echo = EchoGuard::disable();
+    let mut passphrase = String::new();
+    let read = std::io::stdin().lock().read_line(&mut passphrase);
+    drop(echo);
+    eprintln!();
+    read.map_err(|error| seal_error(&format!("passphrase prompt failed: {error}")))?;
+    Ok(passphrase.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Turns terminal echo off while the passphrase is typed.
+struct EchoGuard {
+    #[cfg(unix)]
+    original: Option<libc::termios>,
+}
+
+impl EchoGuard {
+    #[cfg(unix)]
+    fn disable() -> Self {
+        let mut termios = std::mem::MaybeUninit::<libc::termios>::uninit();
+        unsafe {
+            if libc::tcgetattr(libc::STDIN_FILENO, termios.as_mut_ptr()) != 0 {
+                return Self { original: None };
+            }
+            let original = termios.assume_init();
+            let mut silent = original;
+            silent.c_lflag &= !libc::ECHO;
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &silent);
+            Self {
+                original: Some(original),
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn disable() -> Self {
+        Self {}
+    }
+}
+
+impl Drop for EchoGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(original) = self.original {
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &original);
+            }
+        }
+    }
+}
+
+fn decode_fixed<const N: usize>(raw: &str) -> Result<[u8; N], StorageError> {
+    hex::decode(raw)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| seal_error("malformed sealed envelope"))
+}
+
+fn seal_error(message: &str) -> StorageError {
+    StorageError::SealFailed {
+        message: message.to_string(),
+    }
+}
+
+fn locked_error(path: &Path) -> StorageError {
+    StorageError::SealFailed {
+        message: format!(
+            "{} is sealed; set SANDBOX_QUANT_SEAL to open it",
+            path.display()
+        ),
+    }
+}
+
+fn read_error(path: &Path, error: std::io::Error) -> StorageError {
+    StorageError::ReadFailed {
+        path: path.display().to_string(),
+        message: error.to_string(),
+    }
+}
+
+fn write_error(path: &Path, error: std::io::Error) -> StorageError {
+    StorageError::WriteFailedWithContext {
+        message: format!("{}: {error}", path.display()),
+    }
+}
@@ -0,0 +1,227 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::position::PositionSnapshot;
+use crate::strategy::command::StrategyStartConfig;
+
+/// Used for whichever parts of `--adapt-*` are left out.
+pub const DEFAULT_ADAPT_TRADES: u32 = 10;
+pub const DEFAULT_ADAPT_STEP: f64 = 0.1;
+pub const DEFAULT_ADAPT_MAX_DRIFT: f64 = 0.5;
+
+/// How far under the configured win rate a window has to land before the
+/// watch treats the market as chop.
+const CHOP_WIN_RATE_MARGIN: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TunedParam {
+    RiskPct,
+    RMultiple,
+}
+
+impl TunedParam {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::RiskPct => "risk_pct",
+            Self::RMultiple => "r_multiple",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamChange {
+    pub param: TunedParam,
+    pub from: f64,
+    pub to: f64,
+}
+
+/// One nudge, with the window that justified it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Adaptation {
+    pub at: DateTime<Utc>,
+    pub trades: u32,
+    pub win_rate: f64,
+    pub pnl: f64,
+    pub changes: Vec<ParamChange>,
+}
+
+/// Slow, bounded tuning of a watch's `risk_pct` and `r_multiple` from its
+/// own closed round trips.
+///
+/// After every `window_trades` round trips the window's win rate is held
+/// against the configured `win_rate`:
+/// - more than 10 points under it: chop, so risk less and take profit sooner
+/// - at or above it with a positive PnL: step both back up
+/// - anything else leaves them alone
+///
+/// Neither parameter leaves `start * (1 ± max_drift)`.
+///
+/// Example:
+/// - `--adapt-trades 10 --adapt-step 0.1` on `risk_pct=0.01 win_rate=0.6 r=2`
+/// - 3 winners out of 10 -> `risk_pct 0.01 -> 0.009`, `r_multiple 2 -> 1.8`
+/// - `strategy rollback <id>` puts both back
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveTuning {
+    pub window_trades: u32,
+    /// Fraction of the current value moved per nudge.
+    pub step: f64,
+    pub max_drift: f64,
+    base_risk_pct: f64,
+    base_r_multiple: f64,
+    /// Oldest first.
+    pub history: Vec<Adaptation>,
+    window_wins: u32,
+    window_seen: u32,
+    window_pnl: f64,
+    last_signed_qty: f64,
+    last_entry_price: Option<f64>,
+}
+
+impl AdaptiveTuning {
+    pub fn new(window_trades: u32, step: f64, max_drift: f64) -> Self {
+        Self {
+            window_trades: window_trades.max(1),
+            step: step.clamp(0.0, 1.0),
+            max_drift: max_drift.clamp(0.0, 1.0),
+            base_risk_pct: 0.0,
+            base_r_multiple: 0.0,
+            history: Vec::new(),
+            window_wins: 0,
+            window_seen: 0,
+            window_pnl: 0.0,
+            last_signed_qty: 0.0,
+            last_entry_price: None,
+        }
+    }
+
+    /// Reads:
+    /// - `SANDBOX_QUANT_STRATEGY_ADAPT` (e.g. `10:0.1:0.5`; unset or `off` disables)
+    pub fn from_env() -> Option<Self> {
+        std::env::var("SANDBOX_QUANT_STRATEGY_ADAPT")
+            .ok()
+            .and_then(|raw| Self::parse(&raw))
+    }
+
+    /// Example:
+    /// - `10:0.1:0.5` -> every 10 trades, 10% steps, at most 50% from the start
+    /// - `20` -> every 20 trades with the default step and drift
+    /// - `0`, `10:0`, `off` -> `None`
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut parts = raw.trim().split(':');
+        let window_trades = parts.next()?.trim().parse::<u32>().ok()?;
+        let step = match parts.next() {
+            Some(step) => step.trim().parse::<f64>().ok()?,
+            None => DEFAULT_ADAPT_STEP,
+        };
+        let max_drift = match parts.next() {
+            Some(drift) => drift.trim().parse::<f64>().ok()?,
+            None => DEFAULT_ADAPT_MAX_DRIFT,
+        };
+        (window_trades > 0 && step > 0.0 && step < 1.0 && max_drift > 0.0 && max_drift < 1.0)
+            .then(|| Self::new(window_trades, step, max_drift))
+    }
+
+    /// Pins the bounds to the values the watch started with.
+    pub fn anchor(&mut self, config: &StrategyStartConfig) {
+        self.base_risk_pct = config.risk_pct;
+        self.base_r_multiple = config.r_multiple;
+    }
+
+    /// Example:
+    /// - `3/10 trades step=0.10 drift=0.50 adaptations=1`
+    pub fn label(&self) -> String {
+        format!(
+            "{}/{} trades step={:.2} drift={:.2} adaptations={}",
+            self.window_seen,
+            self.window_trades,
+            self.step,
+            self.max_drift,
+            self.history.len()
+        )
+    }
+
+    /// Folds the latest position in and, once a window of round trips has
+    /// closed, nudges `config` and returns what changed.
+    pub fn observe(
+        &mut self,
+        config: &mut StrategyStartConfig,
+        position: Option<&PositionSnapshot>,
+        mark_price: Option<f64>,
+    ) -> Option<Adaptation> {
+        let signed_qty = position.map_or(0.0, |position| position.signed_qty);
+        let closed = self.last_signed_qty.abs() > f64::EPSILON && signed_qty.abs() <= f64::EPSILON;
+        if let (true, Some(entry), Some(exit)) = (closed, self.last_entry_price, mark_price) {
+            let pnl = (exit - entry) * self.last_signed_qty;
+            self.window_seen += 1;
+            self.window_pnl += pnl;
+            if pnl > 0.0 {
+                self.window_wins += 1;
+            }
+        }
+        self.last_signed_qty = signed_qty;
+        self.last_entry_price = position.and_then(|position| position.entry_price);
+        if self.window_seen < self.window_trades {
+            return None;
+        }
+
+        let trades = self.window_seen;
+        let win_rate = f64::from(self.window_wins) / f64::from(trades);
+        let pnl = self.window_pnl;
+        self.window_seen = 0;
+        self.window_wins = 0;
+        self.window_pnl = 0.0;
+        let factor = if win_rate < config.win_rate - CHOP_WIN_RATE_MARGIN {
+            1.0 - self.step
+        } else if win_rate >= config.win_rate && pnl > 0.0 {
+            1.0 + self.step
+        } else {
+            return None;
+        };
+        let changes = [
+            (TunedParam::RiskPct, self.base_risk_pct),
+            (TunedParam::RMultiple, self.base_r_multiple),
+        ]
+        .into_iter()
+        .filter_map(|(param, base)| {
+            let value = param_mut(config, param);
+            let from = *value;
+            let to =
+                (from * factor).clamp(base * (1.0 - self.max_drift), base * (1.0 + self.max_drift));
+            ((to - from).abs() > f64::EPSILON).then(|| {
+                *value = to;
+                ParamChange { param, from, to }
+            })
+        })
+        .collect::<Vec<_>>();
+        if changes.is_empty() {
+            return None;
+        }
+        let adaptation = Adaptation {
+            at: Utc::now(),
+            trades,
+            win_rate,
+            pnl,
+            changes,
+        };
+        self.history.push(adaptation.clone());
+        Some(adaptation)
+    }
+
+    /// Undoes the latest adaptation and starts a fresh window.
+    pub fn rollback(&mut self, config: &mut StrategyStartConfig) -> Option<Adaptation> {
+        let adaptation = self.history.pop()?;
+        for change in &adaptation.changes {
+            *param_mut(config, change.param) = change.from;
+        }
+        self.window_seen = 0;
+        self.window_wins = 0;
+        self.window_pnl = 0.0;
+        Some(adaptation)
+    }
+}
+
+fn param_mut(config: &mut StrategyStartConfig, param: TunedParam) -> &mut f64 {
+    match param {
+        TunedParam::RiskPct => &mut config.risk_pct,
+        TunedParam::RMultiple => &mut config.r_multiple,
+    }
+}
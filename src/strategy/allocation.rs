@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+
+use crate::domain::instrument::Instrument;
+use crate::strategy::model::{StrategyTemplate, StrategyWatch};
+
+/// Closed days a ledger needs before it is scored on the leaderboard.
+pub const MIN_LEADERBOARD_DAYS: usize = 3;
+/// Tilt toward the top-ranked watch when `SANDBOX_QUANT_AUTO_ALLOCATE`
+/// leaves it out.
+pub const DEFAULT_ALLOCATION_SHIFT: f64 = 0.2;
+/// Smallest reallocation worth moving; rounding noise stays put.
+pub const MIN_CAPITAL_SHIFT_USDT: f64 = 0.01;
+
+/// One allocated watch ranked by risk-adjusted return.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    pub watch_id: u64,
+    pub template: StrategyTemplate,
+    pub instrument: Instrument,
+    pub group: Option<String>,
+    /// Closed days in the rolling window.
+    pub days: usize,
+    pub return_pct: f64,
+    /// Daily volatility; `None` under two closed days.
+    pub volatility_pct: Option<f64>,
+    /// Daily Sharpe ratio; `None` under `MIN_LEADERBOARD_DAYS`.
+    pub score: Option<f64>,
+    pub equity_usdt: f64,
+    /// Share of the allocated equity, `0.0..=1.0`.
+    pub weight: f64,
+}
+
+/// Ranks the allocated watches best score first; unscored watches follow
+/// by id.
+pub fn leaderboard<'a>(
+    watches: impl IntoIterator<Item = &'a StrategyWatch>,
+) -> Vec<LeaderboardEntry> {
+    let mut entries = watches
+        .into_iter()
+        .filter_map(|watch| {
+            let ledger = watch.ledger.as_ref()?;
+            Some(LeaderboardEntry {
+                watch_id: watch.id,
+                template: watch.template,
+                instrument: watch.instrument.clone(),
+                group: watch.group.clone(),
+                days: ledger.daily_returns.len(),
+                return_pct: ledger.window_return() * 100.0,
+                volatility_pct: ledger.daily_volatility().map(|sd| sd * 100.0),
+                score: ledger.risk_adjusted_return(MIN_LEADERBOARD_DAYS),
+                equity_usdt: ledger.equity_usdt(),
+                weight: 0.0,
+            })
+        })
+        .collect::<Vec<_>>();
+    let total = entries
+        .iter()
+        .map(|entry| entry.equity_usdt.max(0.0))
+        .sum::<f64>();
+    if total > f64::EPSILON {
+        for entry in &mut entries {
+            entry.weight = entry.equity_usdt.max(0.0) / total;
+        }
+    }
+    entries.sort_by(|left, right| match (left.score, right.score) {
+        (Some(left_score), Some(right_score)) => right_score
+            .total_cmp(&left_score)
+            .then(left.watch_id.cmp(&right.watch_id)),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => left.watch_id.cmp(&right.watch_id),
+    });
+    entries
+}
+
+/// Daily reweighting of strategy capital toward the leaderboard leaders.
+///
+/// Each scored watch's weight is scaled by `1 + shift * tilt`, where the
+/// tilt runs from `+1` for the leader to `-1` for the laggard; unscored
+/// watches keep their weight. The result is bounded to
+/// `min_weight..=max_weight` and renormalized.
+///
+/// Example:
+/// - `0.1:0.5:0.2`, three watches at 1/3 -> leader `0.40`, middle `0.33`,
+///   laggard `0.27`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoAllocation {
+    pub min_weight: f64,
+    pub max_weight: f64,
+    pub shift: f64,
+}
+
+impl AutoAllocation {
+    /// Reads:
+    /// - `SANDBOX_QUANT_AUTO_ALLOCATE` (e.g. `0.05:0.4` or `0.05:0.4:0.2`;
+    ///   unset or `off` disables)
+    pub fn from_env() -> Option<Self> {
+        std::env::var("SANDBOX_QUANT_AUTO_ALLOCATE")
+            .ok()
+            .and_then(|raw| Self::parse(&raw))
+    }
+
+    /// Example:
+    /// - `0.05:0.4` -> weights within 5%..40%, shift `0.2`
+    /// - `0.05:0.4:0.5` -> same bounds, shift `0.5`
+    /// - `0.5:0.4`, `0:0.4:2`, `off` -> `None`
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut parts = raw.trim().split(':');
+        let min_weight = parts.next()?.trim().parse::<f64>().ok()?;
+        let max_weight = parts.next()?.trim().parse::<f64>().ok()?;
+        let shift = match parts.next() {
+            Some(shift) => shift.trim().parse::<f64>().ok()?,
+            None => DEFAULT_ALLOCATION_SHIFT,
+        };
+        (parts.next().is_none()
+            && (0.0..=1.0).contains(&min_weight)
+            && min_weight <= max_weight
+            && max_weight <= 1.0
+            && max_weight > 0.0
+            && shift > 0.0
+            && shift <= 1.0)
+            .then_some(Self {
+                min_weight,
+                max_weight,
+                shift,
+            })
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "min={:.0}% max={:.0}% shift={}",
+            self.min_weight * 100.0,
+            self.max_weight * 100.0,
+            self.shift
+        )
+    }
+
+    /// Next weight of every entry by watch id; the weights sum to `1`.
+    pub fn target_weights(&self, board: &[LeaderboardEntry]) -> BTreeMap<u64, f64> {
+        if board.is_empty() {
+            return BTreeMap::new();
+        }
+        let scored = board.iter().filter(|entry| entry.score.is_some()).count();
+        let mut weights = board
+            .iter()
+            .enumerate()
+            .map(|(rank, entry)| {
+                let tilt = if entry.score.is_some() && scored > 1 {
+                    1.0 - 2.0 * rank as f64 / (scored - 1) as f64
+                } else {
+                    0.0
+                };
+                entry.weight * (1.0 + self.shift * tilt)
+            })
+            .collect::<Vec<_>>();
+        bound_weights(&mut weights, self.min_weight, self.max_weight);
+        board
+            .iter()
+            .zip(weights)
+            .map(|(entry, weight)| (entry.watch_id, weight))
+            .collect()
+    }
+}
+
+/// Scales `weights` to sum to `1` with every weight in `min..=max`,
+/// pinning the ones that overshoot and spreading the rest pro rata.
+/// Bounds that cannot hold for this many weights are widened to `1/n`.
+fn bound_weights(weights: &mut [f64], min: f64, max: f64) {
+    let equal = 1.0 / weights.len() as f64;
+    let (min, max) = (min.min(equal), max.max(equal));
+    let mut pinned = vec![false; weights.len()];
+    for _ in 0..=weights.len() {
+        let pinned_sum = weights
+            .iter()
+            .zip(&pinned)
+            .filter(|(_, pinned)| **pinned)
+            .map(|(weight, _)| weight)
+            .sum::<f64>();
+        let free_sum = weights
+            .iter()
+            .zip(&pinned)
+            .filter(|(_, pinned)| !**pinned)
+            .map(|(weight, _)| weight)
+            .sum::<f64>();
+        let free_count = pinned.iter().filter(|pinned| !**pinned).count();
+        if free_count == 0 {
+            break;
+        }
+        for (weight, _) in weights.iter_mut().zip(&pinned).filter(|(_, p)| !**p) {
+            *weight = if free_sum > f64::EPSILON {
+                *weight * (1.0 - pinned_sum) / free_sum
+            } else {
+                (1.0 - pinned_sum) / free_count as f64
+            };
+        }
+        let mut moved = false;
+        for (weight, pinned) in weights.iter_mut().zip(pinned.iter_mut()) {
+            if !*pinned && (*weight > max || *weight < min) {
+                *weight = weight.clamp(min, max);
+                *pinned = true;
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+}
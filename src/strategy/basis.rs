@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::app::bootstrap::BinanceMode;
+use crate::domain::instrument::Instrument;
+use crate::domain::market::Market;
+use crate::market_data::synthetic::{SyntheticInstrument, SyntheticLeg};
+use crate::strategy::version::parameter_fingerprint;
+
+/// Binance settles perpetual funding every 8 hours.
+pub const FUNDING_INTERVAL_SECS: i64 = 8 * 60 * 60;
+
+const FUNDING_INTERVALS_PER_YEAR: f64 = 3.0 * 365.0;
+
+/// Entry and exit thresholds for one spot-perp basis watch.
+///
+/// Example:
+/// - `entry=30 exit=5 target=0.1 funding=15`
+/// - enters when the perp trades 30bps over spot or funding pays 15% a year
+/// - unwinds once the basis is back under 5bps and funding is below 15%
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasisArbConfig {
+    pub entry_bps: f64,
+    pub exit_bps: f64,
+    /// Annualized funding that justifies holding the hedge on its own.
+    pub funding_apr_pct: Option<f64>,
+    /// Exposure per leg, e.g. `0.1` buys 10% of equity spot and sells as much perp.
+    pub target: f64,
+}
+
+/// Spot and perp prices with the current funding rate, read together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BasisQuote {
+    pub spot: f64,
+    pub perp: f64,
+    /// Per 8h funding interval, e.g. `0.0001`; `None` when unavailable.
+    pub funding_rate: Option<f64>,
+}
+
+impl BasisQuote {
+    /// Example:
+    /// - spot `50000`, perp `50150` -> `30`
+    pub fn basis_bps(&self) -> f64 {
+        if self.spot <= f64::EPSILON {
+            return 0.0;
+        }
+        (self.perp - self.spot) / self.spot * 10_000.0
+    }
+
+    /// Example:
+    /// - funding `0.0001` -> `10.95`
+    pub fn funding_apr_pct(&self) -> Option<f64> {
+        self.funding_rate
+            .map(|rate| rate * FUNDING_INTERVALS_PER_YEAR * 100.0)
+    }
+}
+
+/// Carry of an open hedge: basis captured plus funding collected on the
+/// short perp.
+///
+/// Example:
+/// - opened at `30bps` on `1000` USDT, now `5bps` -> basis PnL `2.5`
+/// - two funding times at `0.0001` -> funding `0.2`, carry `2.7`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasisCarry {
+    pub opened_at: DateTime<Utc>,
+    pub entry_basis_bps: f64,
+    pub notional_usdt: f64,
+    pub funding_accrued_usdt: f64,
+    pub funding_intervals: u64,
+    pub last_accrued_at: DateTime<Utc>,
+}
+
+impl BasisCarry {
+    pub fn open(entry_basis_bps: f64, notional_usdt: f64, now: DateTime<Utc>) -> Self {
+        Self {
+            opened_at: now,
+            entry_basis_bps,
+            notional_usdt,
+            funding_accrued_usdt: 0.0,
+            funding_intervals: 0,
+            last_accrued_at: now,
+        }
+    }
+
+    /// Credits `funding_rate` once per funding time passed since the last call.
+    pub fn accrue_funding(&mut self, funding_rate: Option<f64>, now: DateTime<Utc>) {
+        let interval = |at: DateTime<Utc>| at.timestamp().div_euclid(FUNDING_INTERVAL_SECS);
+        let crossed = (interval(now) - interval(self.last_accrued_at)).max(0) as u64;
+        if let Some(rate) = funding_rate {
+            self.funding_accrued_usdt += crossed as f64 * rate * self.notional_usdt;
+        }
+        self.funding_intervals += crossed;
+        self.last_accrued_at = self.last_accrued_at.max(now);
+    }
+
+    pub fn basis_pnl_usdt(&self, basis_bps: f64) -> f64 {
+        (self.entry_basis_bps - basis_bps) / 10_000.0 * self.notional_usdt
+    }
+
+    pub fn carry_pnl_usdt(&self, basis_bps: f64) -> f64 {
+        self.basis_pnl_usdt(basis_bps) + self.funding_accrued_usdt
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BasisDecision {
+    Enter,
+    Exit,
+    Hold,
+}
+
+impl BasisDecision {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Enter => "enter",
+            Self::Exit => "exit",
+            Self::Hold => "hold",
+        }
+    }
+}
+
+/// Watches one symbol's spot-perp basis and holds a long spot / short perp
+/// hedge while it pays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasisArbWatch {
+    pub id: u64,
+    pub mode: BinanceMode,
+    pub instrument: Instrument,
+    pub config: BasisArbConfig,
+    /// Open hedge, if any.
+    pub carry: Option<BasisCarry>,
+    pub last_quote: Option<BasisQuote>,
+    /// Hedges opened and unwound so far, with their total carry.
+    pub round_trips: u64,
+    pub realized_carry_usdt: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BasisArbWatch {
+    /// Fingerprint of the thresholds and target, carried on the watch's orders.
+    pub fn version(&self) -> String {
+        parameter_fingerprint([
+            "basis-arb".to_string(),
+            format!("entry_bps={}", self.config.entry_bps),
+            format!("exit_bps={}", self.config.exit_bps),
+            format!("funding_apr_pct={:?}", self.config.funding_apr_pct),
+            format!("target={}", self.config.target),
+        ])
+    }
+
+    /// Example:
+    /// - `BTCUSDT` -> `BTCUSDT_BASIS`
+    pub fn synthetic_name(&self) -> Instrument {
+        Instrument::new(format!("{}_BASIS", self.instrument.0))
+    }
+
+    /// `perp - spot`; a negative target buys spot and sells the perp.
+    pub fn synthetic(&self) -> SyntheticInstrument {
+        let leg = |market, weight| SyntheticLeg {
+            instrument: self.instrument.clone(),
+            market,
+            weight,
+        };
+        SyntheticInstrument {
+            name: self.synthetic_name(),
+            legs: vec![leg(Market::Futures, 1.0), leg(Market::Spot, -1.0)],
+        }
+    }
+
+    pub fn decide(&self, quote: &BasisQuote) -> BasisDecision {
+        let funding_pays = match (quote.funding_apr_pct(), self.config.funding_apr_pct) {
+            (Some(apr), Some(threshold)) => apr >= threshold,
+            _ => false,
+        };
+        let basis_bps = quote.basis_bps();
+        match self.carry {
+            None if basis_bps >= self.config.entry_bps || funding_pays => BasisDecision::Enter,
+            Some(_) if basis_bps <= self.config.exit_bps && !funding_pays => BasisDecision::Exit,
+            _ => BasisDecision::Hold,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BasisArbStore {
+    next_id: u64,
+    watches: BTreeMap<u64, BasisArbWatch>,
+}
+
+impl BasisArbStore {
+    /// Returns `None` when `instrument` already has a watch in `mode`.
+    pub fn start(
+        &mut self,
+        mode: BinanceMode,
+        instrument: Instrument,
+        config: BasisArbConfig,
+    ) -> Option<BasisArbWatch> {
+        if self
+            .watches
+            .values()
+            .any(|watch| watch.mode == mode && watch.instrument == instrument)
+        {
+            return None;
+        }
+        self.next_id += 1;
+        let watch = BasisArbWatch {
+            id: self.next_id,
+            mode,
+            instrument,
+            config,
+            carry: None,
+            last_quote: None,
+            round_trips: 0,
+            realized_carry_usdt: 0.0,
+            created_at: Utc::now(),
+        };
+        self.watches.insert(watch.id, watch.clone());
+        Some(watch)
+    }
+
+    pub fn watches(&self, mode: BinanceMode) -> Vec<&BasisArbWatch> {
+        self.watches
+            .values()
+            .filter(|watch| watch.mode == mode)
+            .collect()
+    }
+
+    pub fn get_mut(&mut self, mode: BinanceMode, id: u64) -> Option<&mut BasisArbWatch> {
+        self.watches.get_mut(&id).filter(|watch| watch.mode == mode)
+    }
+
+    pub fn stop(&mut self, mode: BinanceMode, id: u64) -> Option<BasisArbWatch> {
+        self.get_mut(mode, id)?;
+        self.watches.remove(&id)
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::VecDeque;
+
+use chrono::NaiveDate;
+
+use crate::domain::position::Side;
+
+/// Closed days of returns a ledger keeps for the leaderboard.
+pub const LEDGER_RETURN_DAYS: usize = 30;
+/// Floor on the daily volatility a score divides by, so a handful of
+/// near-identical days cannot produce an outsized score.
+const MIN_DAILY_VOLATILITY: f64 = 0.001;
+
+/// Virtual capital allocated to one watch, with PnL kept apart from the
+/// rest of the account.
+///
+/// Example:
+/// - seed `1000`, realized `-120`, unrealized `+20`
+/// - equity `900`, roi `-10%`, risk budget at `0.5%` = `4.5`
+///
+/// PnL comes from the fills tagged with the watch's client order ids, so
+/// manual trades and other watches on the same instrument stay out of it;
+/// a refresh only revalues the watch's own quantity at the mark.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyLedger {
+    pub seed_capital_usdt: f64,
+    pub realized_pnl_usdt: f64,
+    pub unrealized_pnl_usdt: f64,
+    /// Equity change of each closed UTC day, oldest first, e.g. `0.01`
+    /// for +1%; capped at `LEDGER_RETURN_DAYS`.
+    pub daily_returns: VecDeque<f64>,
+    /// Quantity the watch's own fills add up to, negative when short.
+    signed_qty: f64,
+    /// Average entry of `signed_qty`.
+    entry_price: Option<f64>,
+    /// Day being accrued and the equity it opened with.
+    day_open: Option<(NaiveDate, f64)>,
+}
+
+impl StrategyLedger {
+    pub fn new(seed_capital_usdt: f64) -> Self {
+        Self {
+            seed_capital_usdt,
+            realized_pnl_usdt: 0.0,
+            unrealized_pnl_usdt: 0.0,
+            daily_returns: VecDeque::new(),
+            signed_qty: 0.0,
+            entry_price: None,
+            day_open: None,
+        }
+    }
+
+    pub fn equity_usdt(&self) -> f64 {
+        self.seed_capital_usdt + self.realized_pnl_usdt + self.unrealized_pnl_usdt
+    }
+
+    /// Return on the seed allocation, e.g. `0.05` for +5%.
+    pub fn roi(&self) -> f64 {
+        if self.seed_capital_usdt > f64::EPSILON {
+            (self.equity_usdt() - self.seed_capital_usdt) / self.seed_capital_usdt
+        } else {
+            0.0
+        }
+    }
+
+    /// Amount one entry may risk, sized against the allocation instead of
+    /// the account.
+    pub fn risk_budget_usdt(&self, risk_pct: f64) -> f64 {
+        self.equity_usdt().max(0.0) * risk_pct
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.equity_usdt() <= f64::EPSILON
+    }
+
+    /// Quantity the watch's own fills add up to, negative when short.
+    pub fn signed_qty(&self) -> f64 {
+        self.signed_qty
+    }
+
+    /// Folds one of the watch's fills into its quantity and realized PnL,
+    /// net of `commission_usdt`.
+    ///
+    /// Example:
+    /// - holding `-0.3 @ 50000`, buys `0.2 @ 49000`
+    /// - realizes `(49000 - 50000) * -0.2 = +200`, keeps `-0.1 @ 50000`
+    pub fn apply_fill(&mut self, side: Side, qty: f64, price: f64, commission_usdt: f64) {
+        let delta = match side {
+            Side::Buy => qty,
+            Side::Sell => -qty,
+        };
+        let held = self.signed_qty;
+        let closed_qty = if held * delta < 0.0 {
+            delta.abs().min(held.abs()) * held.signum()
+        } else {
+            0.0
+        };
+        if let Some(entry) = self.entry_price {
+            self.realized_pnl_usdt += (price - entry) * closed_qty;
+        }
+        self.signed_qty = held + delta;
+        self.entry_price = if self.signed_qty.abs() <= f64::EPSILON {
+            self.signed_qty = 0.0;
+            None
+        } else if held * self.signed_qty < 0.0 || held == 0.0 {
+            Some(price)
+        } else if closed_qty == 0.0 {
+            self.entry_price
+                .map(|entry| (entry * held + price * delta) / self.signed_qty)
+        } else {
+            self.entry_price
+        };
+        self.realized_pnl_usdt -= commission_usdt;
+    }
+
+    /// Revalues the watch's own quantity at `mark_price`; without a mark
+    /// the last valuation is kept.
+    pub fn mark(&mut self, mark_price: Option<f64>) {
+        if let (Some(mark), Some(entry)) = (mark_price, self.entry_price) {
+            self.unrealized_pnl_usdt = (mark - entry) * self.signed_qty;
+        } else if self.entry_price.is_none() {
+            self.unrealized_pnl_usdt = 0.0;
+        }
+    }
+
+    /// Closes the accrued day once `today` has moved past it; returns
+    /// whether one closed.
+    ///
+    /// Example:
+    /// - opened 2024-03-01 at `1000`, equity `1010` on 2024-03-02
+    /// - pushes `0.01` and opens 2024-03-02 at `1010`
+    pub fn roll_day(&mut self, today: NaiveDate) -> bool {
+        let equity = self.equity_usdt();
+        let closed = match self.day_open {
+            Some((day, _)) if day >= today => return false,
+            Some((_, open_equity)) => {
+                if open_equity > f64::EPSILON {
+                    self.daily_returns.push_back(equity / open_equity - 1.0);
+                    if self.daily_returns.len() > LEDGER_RETURN_DAYS {
+                        self.daily_returns.pop_front();
+                    }
+                }
+                true
+            }
+            None => false,
+        };
+        self.day_open = Some((today, equity));
+        closed
+    }
+
+    /// Compounded return over the kept days, e.g. `0.05` for +5%.
+    pub fn window_return(&self) -> f64 {
+        self.daily_returns
+            .iter()
+            .fold(1.0, |growth, daily| growth * (1.0 + daily))
+            - 1.0
+    }
+
+    /// Sample standard deviation of the daily returns; `None` under two days.
+    pub fn daily_volatility(&self) -> Option<f64> {
+        let days = self.daily_returns.len();
+        if days < 2 {
+            return None;
+        }
+        let mean = self.daily_returns.iter().sum::<f64>() / days as f64;
+        let variance = self
+            .daily_returns
+            .iter()
+            .map(|daily| (daily - mean).powi(2))
+            .sum::<f64>()
+            / (days - 1) as f64;
+        Some(variance.sqrt())
+    }
+
+    /// Mean daily return over its volatility, a daily Sharpe ratio without
+    /// a risk-free rate; `None` under `min_days` closed days.
+    ///
+    /// Example:
+    /// - returns `+1%`, `-0.5%`, `+1.5%` -> mean `0.67%`, sd `1.04%`, score `0.64`
+    pub fn risk_adjusted_return(&self, min_days: usize) -> Option<f64> {
+        if self.daily_returns.len() < min_days.max(2) {
+            return None;
+        }
+        let mean = self.daily_returns.iter().sum::<f64>() / self.daily_returns.len() as f64;
+        Some(mean / self.daily_volatility()?.max(MIN_DAILY_VOLATILITY))
+    }
+
+    /// Moves `delta_usdt` of capital in or out of the allocation; PnL and
+    /// the day's return are left as they were.
+    pub fn reallocate(&mut self, delta_usdt: f64) {
+        self.seed_capital_usdt += delta_usdt;
+        if let Some((_, open_equity)) = self.day_open.as_mut() {
+            *open_equity += delta_usdt;
+        }
+    }
+}
@@ -1,5 +1,11 @@
+use std::path::PathBuf;
+
 use crate::domain::instrument::Instrument;
+use crate::strategy::adaptive::AdaptiveTuning;
+use crate::strategy::filter::{StrategyFilter, StrategyListQuery};
+use crate::strategy::kpi::KpiTargets;
 use crate::strategy::model::StrategyTemplate;
+use crate::strategy::ramp::SizeRamp;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct StrategyStartConfig {
@@ -7,6 +13,101 @@ pub struct StrategyStartConfig {
     pub win_rate: f64,
     pub r_multiple: f64,
     pub max_entry_slippage_pct: f64,
+    /// Virtual allocation; `None` sizes against the whole account.
+    pub seed_capital_usdt: Option<f64>,
+    /// Starts below `risk_pct` until the watch proves itself; see `SizeRamp`.
+    pub ramp: Option<SizeRamp>,
+    /// Nudges `risk_pct` and `r_multiple` from recent trades; see `AdaptiveTuning`.
+    pub adapt: Option<AdaptiveTuning>,
+    /// Rhai script the `script-long`/`script-short` templates evaluate.
+    pub script: Option<PathBuf>,
+}
+
+impl Default for StrategyStartConfig {
+    /// The ramp and tuning default to `SizeRamp::from_env` and
+    /// `AdaptiveTuning::from_env`.
+    fn default() -> Self {
+        Self {
+            risk_pct: 0.005,
+            win_rate: 0.8,
+            r_multiple: 1.5,
+            max_entry_slippage_pct: 0.001,
+            seed_capital_usdt: None,
+            ramp: SizeRamp::from_env(),
+            adapt: AdaptiveTuning::from_env(),
+            script: None,
+        }
+    }
+}
+
+/// What to do with an open position when its watch is stopped.
+///
+/// Example:
+/// - `strategy stop 3` on a watch holding `BTCUSDT` only previews the PnL impact
+/// - `strategy stop 3 flatten` closes `BTCUSDT` and stops the watch once
+///   the close has filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenPositionResolution {
+    /// Cancel the stop; the watch keeps the position.
+    Keep,
+    Flatten,
+    /// Stop the watch and adopt the position under the `manual` tag.
+    Manual,
+}
+
+impl OpenPositionResolution {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::Flatten => "flatten",
+            Self::Manual => "manual",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().as_str() {
+            "keep" => Some(Self::Keep),
+            "flatten" => Some(Self::Flatten),
+            "manual" => Some(Self::Manual),
+            _ => None,
+        }
+    }
+}
+
+/// Action applied to every active watch in a group.
+///
+/// Example:
+/// - `strategy bulk experimental disable` parks every `experimental` watch
+/// - `strategy bulk experimental stop` stops them; watches holding a
+///   position still need `strategy stop <id> <keep|flatten|manual>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyGroupAction {
+    Enable,
+    Disable,
+    Stop,
+    /// Stops the watches and forgets their saved group labels.
+    Delete,
+}
+
+impl StrategyGroupAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Enable => "enable",
+            Self::Disable => "disable",
+            Self::Stop => "stop",
+            Self::Delete => "delete",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().as_str() {
+            "enable" => Some(Self::Enable),
+            "disable" => Some(Self::Disable),
+            "stop" => Some(Self::Stop),
+            "delete" => Some(Self::Delete),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -17,12 +118,72 @@ pub enum StrategyCommand {
         instrument: Instrument,
         config: StrategyStartConfig,
     },
-    List,
+    /// Active watches matching `query`, one page at a time.
+    List {
+        query: StrategyListQuery,
+    },
     Show {
         watch_id: u64,
     },
+    /// Stops a watch; `resolution` is required while it holds a position.
     Stop {
         watch_id: u64,
+        resolution: Option<OpenPositionResolution>,
+    },
+    /// Finished watches matching `query`, newest first.
+    History {
+        query: StrategyListQuery,
+    },
+    /// Toggles the bounded decision trace for one watch.
+    Debug {
+        watch_id: u64,
+        enabled: bool,
+    },
+    /// Shows the trace buffer, or writes it as JSON lines to `dump_path`.
+    Trace {
+        watch_id: u64,
+        dump_path: Option<String>,
+    },
+    /// Moves a watch into `group`, or out of any group with `None`.
+    Group {
+        watch_id: u64,
+        group: Option<String>,
+    },
+    /// Undoes the latest parameter adaptation of a watch.
+    Rollback {
+        watch_id: u64,
+    },
+    /// Sets a watch's KPI targets, or clears them with `None`.
+    Kpi {
+        watch_id: u64,
+        targets: Option<KpiTargets>,
+    },
+    /// Aggregated state and PnL per group.
+    Groups,
+    /// Allocated watches ranked by risk-adjusted return, with their
+    /// capital weights.
+    Leaderboard,
+    /// Signal-to-fill counts per watch over the last `window_hours`, with
+    /// the reasons signals were dropped.
+    Funnel {
+        window_hours: u32,
+    },
+    /// WASM strategy plugins in the plugin directory and whether each
+    /// implements the strategy interface.
+    Plugins,
+    Bulk {
+        group: String,
+        action: StrategyGroupAction,
+    },
+    /// Lists the active watches matching `filter`; `action` is only applied
+    /// once `confirmed`.
+    ///
+    /// Example:
+    /// - `strategy select kind=sma symbol=BTC* pnl>0 disable` previews
+    /// - the same line ending in `confirm` disables the listed watches
+    Select {
+        filter: StrategyFilter,
+        action: Option<StrategyGroupAction>,
+        confirmed: bool,
     },
-    History,
 }
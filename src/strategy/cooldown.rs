@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+
+/// Wait after each exit before the next entry.
+pub const DEFAULT_COOLDOWN_SECS: i64 = 30;
+pub const DEFAULT_COOLDOWN_WINDOW_TRADES: usize = 10;
+/// Cooldown change per R of expectancy away from the baseline.
+pub const DEFAULT_COOLDOWN_SENSITIVITY: f64 = 0.5;
+
+/// Bounds for scaling the post-exit cooldown by recent expectancy.
+///
+/// The cooldown is `base * (1 - sensitivity * (expectancy - baseline_r))`,
+/// clamped to `min_secs..=max_secs`, where expectancy is the mean R of the
+/// last `window_trades` closed trades.
+///
+/// Example:
+/// - base `30s`, sensitivity `0.5`, baseline `0R`
+/// - recent trades average `+1R` -> `15s`; `-1R` -> `45s`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooldownScaling {
+    pub min_secs: i64,
+    pub max_secs: i64,
+    pub window_trades: usize,
+    pub baseline_r: f64,
+    pub sensitivity: f64,
+}
+
+impl CooldownScaling {
+    /// Reads:
+    /// - `SANDBOX_QUANT_COOLDOWN_SCALING` (e.g. `5:300:10:0`; unset or `off`
+    ///   keeps the cooldown fixed)
+    pub fn from_env() -> Option<Self> {
+        std::env::var("SANDBOX_QUANT_COOLDOWN_SCALING")
+            .ok()
+            .and_then(|raw| Self::parse(&raw))
+    }
+
+    /// `<min_secs>:<max_secs>[:<window_trades>[:<baseline_r>]]`.
+    ///
+    /// Example:
+    /// - `5:300` -> bounds `5s..300s` over the last 10 trades against `0R`
+    /// - `300:5`, `5:300:0`, `off` -> `None`
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut parts = raw.trim().split(':').map(str::trim);
+        let min_secs = parts.next()?.parse::<i64>().ok()?;
+        let max_secs = parts.next()?.parse::<i64>().ok()?;
+        let window_trades = match parts.next() {
+            Some(raw) => raw.parse::<usize>().ok()?,
+            None => DEFAULT_COOLDOWN_WINDOW_TRADES,
+        };
+        let baseline_r = match parts.next() {
+            Some(raw) => raw.parse::<f64>().ok().filter(|value| value.is_finite())?,
+            None => 0.0,
+        };
+        (parts.next().is_none() && min_secs >= 0 && min_secs <= max_secs && window_trades > 0)
+            .then_some(Self {
+                min_secs,
+                max_secs,
+                window_trades,
+                baseline_r,
+                sensitivity: DEFAULT_COOLDOWN_SENSITIVITY,
+            })
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "{}s..{}s over {} trades vs {}R",
+            self.min_secs, self.max_secs, self.window_trades, self.baseline_r
+        )
+    }
+}
+
+/// Holds new entries back for a while after each exit, for one strategy run.
+///
+/// Without scaling every exit waits `base_secs`; with it the wait follows
+/// the run's recent expectancy, so the strategy signals stay untouched and
+/// only the entry gate moves.
+///
+/// Example:
+/// - base `30s`, scaling `5:300`, a `+1.4R` winner closes at `12:00:00`
+/// - `cooldown_secs()` -> `9`, entries allowed again from `12:00:09`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectancyCooldown {
+    base_secs: i64,
+    scaling: Option<CooldownScaling>,
+    recent_r: VecDeque<f64>,
+    until_ms: i64,
+}
+
+impl ExpectancyCooldown {
+    pub fn new(base_secs: i64, scaling: Option<CooldownScaling>) -> Self {
+        Self {
+            base_secs: base_secs.max(0),
+            scaling,
+            recent_r: VecDeque::new(),
+            until_ms: 0,
+        }
+    }
+
+    /// The gate bar strategies enter through, in backtests and live: they
+    /// only wait out `base_secs` once scaling is on, and their exits
+    /// otherwise free the next bar straight away.
+    pub fn for_bars(base_secs: i64, scaling: Option<CooldownScaling>) -> Self {
+        Self::new(if scaling.is_some() { base_secs } else { 0 }, scaling)
+    }
+
+    /// Records a closed trade and starts the cooldown from `exit_ms`.
+    ///
+    /// `risk_usdt` is what the stop put at risk; without it the trade
+    /// leaves the expectancy alone but still starts the cooldown.
+    pub fn record_exit(&mut self, exit_ms: i64, net_pnl: f64, risk_usdt: f64) {
+        if let Some(scaling) = &self.scaling {
+            let r_multiple = net_pnl / risk_usdt;
+            if risk_usdt > f64::EPSILON && r_multiple.is_finite() {
+                self.recent_r.push_back(r_multiple);
+                while self.recent_r.len() > scaling.window_trades {
+                    let _ = self.recent_r.pop_front();
+                }
+            }
+        }
+        self.until_ms = exit_ms + self.cooldown_secs() * 1_000;
+    }
+
+    pub fn allows_entry(&self, time_ms: i64) -> bool {
+        time_ms >= self.until_ms
+    }
+
+    /// Mean R of the trades in the window; `None` before the first one.
+    pub fn expectancy_r(&self) -> Option<f64> {
+        (!self.recent_r.is_empty())
+            .then(|| self.recent_r.iter().sum::<f64>() / self.recent_r.len() as f64)
+    }
+
+    /// The wait the next exit would start.
+    pub fn cooldown_secs(&self) -> i64 {
+        let (Some(scaling), Some(expectancy)) = (&self.scaling, self.expectancy_r()) else {
+            return self.base_secs;
+        };
+        let factor = (1.0 - scaling.sensitivity * (expectancy - scaling.baseline_r)).max(0.0);
+        ((self.base_secs as f64 * factor).round() as i64).clamp(scaling.min_secs, scaling.max_secs)
+    }
+}
@@ -0,0 +1,478 @@
+use std::fmt::Debug;
+
+/// Bumped whenever a model's formula changes, so estimates recorded under
+/// an older formula stay attributable.
+pub const EV_MODEL_REVISION: u32 = 1;
+
+/// Prior weight, in trades, given to a profile's configured win rate.
+pub const DEFAULT_PRIOR_STRENGTH: f64 = 20.0;
+
+/// Bootstrap estimates fall back to the prior below this many outcomes.
+pub const MIN_BOOTSTRAP_OUTCOMES: usize = 5;
+
+const BOOTSTRAP_RESAMPLES: usize = 200;
+
+/// What a probability model sees of a held bracket at one price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvFeatures {
+    /// Move since entry in units of the initial stop distance.
+    pub r_multiple: f64,
+    /// Distance left to the take-profit in the same units.
+    pub reward_left_r: f64,
+}
+
+/// Probability that a held bracket reaches its take-profit before its stop.
+pub trait WinProbabilityModel: Debug {
+    fn win_probability(&self, features: &EvFeatures) -> f64;
+
+    /// Recorded with every estimate for attribution.
+    ///
+    /// Example:
+    /// - `beta_binomial/v1:prior=0.55,strength=20`
+    fn version(&self) -> String;
+}
+
+impl WinProbabilityModel for Box<dyn WinProbabilityModel> {
+    fn win_probability(&self, features: &EvFeatures) -> f64 {
+        self.as_ref().win_probability(features)
+    }
+
+    fn version(&self) -> String {
+        self.as_ref().version()
+    }
+}
+
+/// Posterior mean of a beta prior centred on the configured win rate,
+/// updated with the bracket outcomes seen so far.
+///
+/// Example:
+/// - prior `0.5` with strength `20`, then `8` wins and `2` losses
+/// - `(0.5 * 20 + 8) / (20 + 10) = 0.6`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BetaBinomial {
+    pub prior_win_rate: f64,
+    pub prior_strength: f64,
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl BetaBinomial {
+    /// The configured win rate with no outcomes folded in yet.
+    pub fn fixed(win_rate: f64) -> Self {
+        Self {
+            prior_win_rate: win_rate.clamp(0.0, 1.0),
+            prior_strength: DEFAULT_PRIOR_STRENGTH,
+            wins: 0,
+            losses: 0,
+        }
+    }
+}
+
+impl WinProbabilityModel for BetaBinomial {
+    fn win_probability(&self, _features: &EvFeatures) -> f64 {
+        let alpha = self.prior_win_rate * self.prior_strength + self.wins as f64;
+        let trials = self.prior_strength + (self.wins + self.losses) as f64;
+        if trials <= f64::EPSILON {
+            return self.prior_win_rate;
+        }
+        (alpha / trials).clamp(0.0, 1.0)
+    }
+
+    fn version(&self) -> String {
+        format!(
+            "beta_binomial/v{EV_MODEL_REVISION}:prior={},strength={}",
+            self.prior_win_rate, self.prior_strength
+        )
+    }
+}
+
+/// Logistic regression on the bracket features.
+///
+/// Example:
+/// - `intercept=0`, `r=0.5`, `reward=-0.5` at `r=1`, `reward_left=1`
+/// - `sigmoid(0 + 0.5 - 0.5) = 0.5`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogisticWinModel {
+    pub intercept: f64,
+    pub r_weight: f64,
+    pub reward_weight: f64,
+}
+
+impl WinProbabilityModel for LogisticWinModel {
+    fn win_probability(&self, features: &EvFeatures) -> f64 {
+        let logit = self.intercept
+            + self.r_weight * features.r_multiple
+            + self.reward_weight * features.reward_left_r;
+        1.0 / (1.0 + (-logit).exp())
+    }
+
+    fn version(&self) -> String {
+        format!(
+            "logistic/v{EV_MODEL_REVISION}:intercept={},r={},reward={}",
+            self.intercept, self.r_weight, self.reward_weight
+        )
+    }
+}
+
+/// Win rate resampled from the recorded outcomes, read at `quantile` so a
+/// thin or streaky record is judged conservatively.
+///
+/// Resampling is seeded, so the same outcomes always give the same
+/// estimate. Below `MIN_BOOTSTRAP_OUTCOMES` the prior win rate is used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmpiricalBootstrap {
+    pub outcomes: Vec<bool>,
+    pub quantile: f64,
+    pub prior_win_rate: f64,
+}
+
+impl WinProbabilityModel for EmpiricalBootstrap {
+    fn win_probability(&self, _features: &EvFeatures) -> f64 {
+        let count = self.outcomes.len();
+        if count < MIN_BOOTSTRAP_OUTCOMES {
+            return self.prior_win_rate;
+        }
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64 ^ count as u64;
+        let mut rates = (0..BOOTSTRAP_RESAMPLES)
+            .map(|_| {
+                let wins = (0..count)
+                    .filter(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        self.outcomes[(state % count as u64) as usize]
+                    })
+                    .count();
+                wins as f64 / count as f64
+            })
+            .collect::<Vec<_>>();
+        rates.sort_by(f64::total_cmp);
+        let index = (self.quantile.clamp(0.0, 1.0) * (rates.len() - 1) as f64).round();
+        rates[index as usize]
+    }
+
+    fn version(&self) -> String {
+        format!(
+            "bootstrap/v{EV_MODEL_REVISION}:q={},resamples={BOOTSTRAP_RESAMPLES}",
+            self.quantile
+        )
+    }
+}
+
+/// Which probability model a hold-EV profile uses.
+///
+/// Example:
+/// - `beta` or `beta/40` -> beta-binomial with prior strength `40`
+/// - `logistic/-0.2/0.4/-0.3` -> intercept, r weight, reward-left weight
+/// - `bootstrap` or `bootstrap/0.25` -> 25th percentile of resampled win rates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvModel {
+    BetaBinomial {
+        prior_strength: f64,
+    },
+    Logistic {
+        intercept: f64,
+        r_weight: f64,
+        reward_weight: f64,
+    },
+    Bootstrap {
+        quantile: f64,
+    },
+}
+
+impl Default for EvModel {
+    fn default() -> Self {
+        Self::BetaBinomial {
+            prior_strength: DEFAULT_PRIOR_STRENGTH,
+        }
+    }
+}
+
+impl EvModel {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut parts = raw.trim().split('/').map(str::trim);
+        let kind = parts.next()?.to_ascii_lowercase();
+        let params = parts
+            .map(|part| part.parse::<f64>().ok().filter(|value| value.is_finite()))
+            .collect::<Option<Vec<_>>>()?;
+        match (kind.as_str(), params.as_slice()) {
+            ("beta", []) => Some(Self::default()),
+            ("beta", [prior_strength]) if *prior_strength > 0.0 => Some(Self::BetaBinomial {
+                prior_strength: *prior_strength,
+            }),
+            ("logistic", [intercept, r_weight, reward_weight]) => Some(Self::Logistic {
+                intercept: *intercept,
+                r_weight: *r_weight,
+                reward_weight: *reward_weight,
+            }),
+            ("bootstrap", []) => Some(Self::Bootstrap { quantile: 0.5 }),
+            ("bootstrap", [quantile]) if (0.0..=1.0).contains(quantile) => Some(Self::Bootstrap {
+                quantile: *quantile,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::BetaBinomial { prior_strength } => format!("beta/{prior_strength}"),
+            Self::Logistic {
+                intercept,
+                r_weight,
+                reward_weight,
+            } => format!("logistic/{intercept}/{r_weight}/{reward_weight}"),
+            Self::Bootstrap { quantile } => format!("bootstrap/{quantile}"),
+        }
+    }
+
+    /// The model for one profile, fed the bracket outcomes recorded under
+    /// it (`true` for a take-profit fill).
+    pub fn build(&self, prior_win_rate: f64, outcomes: &[bool]) -> Box<dyn WinProbabilityModel> {
+        let prior_win_rate = prior_win_rate.clamp(0.0, 1.0);
+        match *self {
+            Self::BetaBinomial { prior_strength } => {
+                let wins = outcomes.iter().filter(|won| **won).count() as u32;
+                Box::new(BetaBinomial {
+                    prior_win_rate,
+                    prior_strength,
+                    wins,
+                    losses: outcomes.len() as u32 - wins,
+                })
+            }
+            Self::Logistic {
+                intercept,
+                r_weight,
+                reward_weight,
+            } => Box::new(LogisticWinModel {
+                intercept,
+                r_weight,
+                reward_weight,
+            }),
+            Self::Bootstrap { quantile } => Box::new(EmpiricalBootstrap {
+                outcomes: outcomes.to_vec(),
+                quantile,
+                prior_win_rate,
+            }),
+        }
+    }
+}
+
+/// Whether the entry EV gate only records its estimates or also acts on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvGateMode {
+    /// Estimates are recorded; no entry is held back.
+    #[default]
+    Shadow,
+    /// Entries on either side below the threshold are rejected.
+    Active,
+}
+
+impl EvGateMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Shadow => "shadow",
+            Self::Active => "active",
+        }
+    }
+}
+
+/// Entry gate on the expected R of a new bracket, net of round-trip fees.
+///
+/// Example:
+/// - `shadow` -> every entry is estimated, none is blocked
+/// - `active:0.5` -> long and short entries expected below `0.5R` are rejected
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EvGate {
+    pub mode: EvGateMode,
+    pub min_expected_value_r: f64,
+}
+
+impl EvGate {
+    /// Reads:
+    /// - `SANDBOX_QUANT_EV_GATE` (e.g. `active:0.5`; unset keeps `shadow`)
+    pub fn from_env() -> Self {
+        std::env::var("SANDBOX_QUANT_EV_GATE")
+            .ok()
+            .and_then(|raw| Self::parse(&raw))
+            .unwrap_or_default()
+    }
+
+    /// `<shadow|active>[:<min_expected_value_r>]`; the threshold defaults to `0`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (mode, threshold) = match raw.trim().split_once(':') {
+            Some((mode, threshold)) => (mode, Some(threshold)),
+            None => (raw.trim(), None),
+        };
+        let mode = match mode.trim().to_ascii_lowercase().as_str() {
+            "shadow" => EvGateMode::Shadow,
+            "active" => EvGateMode::Active,
+            _ => return None,
+        };
+        let min_expected_value_r = match threshold {
+            Some(raw) => raw
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())?,
+            None => 0.0,
+        };
+        Some(Self {
+            mode,
+            min_expected_value_r,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        format!("{}:{}", self.mode.as_str(), self.min_expected_value_r)
+    }
+
+    /// The snapshot for one entry; only an active gate blocks.
+    pub fn evaluate(&self, expected_value_r: f64) -> EvSnapshotUpdate {
+        EvSnapshotUpdate {
+            expected_value_r,
+            min_expected_value_r: self.min_expected_value_r,
+            mode: self.mode,
+            gate_blocked: self.mode == EvGateMode::Active
+                && expected_value_r < self.min_expected_value_r,
+        }
+    }
+}
+
+/// What the entry EV gate saw of one entry signal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvSnapshotUpdate {
+    pub expected_value_r: f64,
+    pub min_expected_value_r: f64,
+    pub mode: EvGateMode,
+    pub gate_blocked: bool,
+}
+
+impl EvSnapshotUpdate {
+    /// Example:
+    /// - `blocked` when the active gate rejected the entry
+    /// - `below` when a shadow gate would have
+    /// - `pass` otherwise
+    pub fn gate_label(&self) -> &'static str {
+        if self.gate_blocked {
+            "blocked"
+        } else if self.expected_value_r < self.min_expected_value_r {
+            "below"
+        } else {
+            "pass"
+        }
+    }
+}
+
+/// Expected R of a fresh bracket at `entry_price` under `win_rate`, less
+/// the taker fee paid on both legs in units of the stop distance; long or
+/// short follows from where the take-profit sits.
+///
+/// Example:
+/// - win rate `0.8`, `1.5R` target, `1.2%` stop, `0.05%` fee
+/// - `0.8 * 1.5 - 0.2 - 2 * 0.0005 / 0.012 = 0.92R`, on either side
+pub fn entry_expected_value_r(
+    entry_price: f64,
+    stop_price: f64,
+    take_profit_price: f64,
+    win_rate: f64,
+    taker_fee_rate: f64,
+) -> f64 {
+    let estimator = EvEstimator::new(entry_price, stop_price, take_profit_price, win_rate);
+    let risk = (entry_price - stop_price).abs();
+    let fees_r = if risk <= f64::EPSILON {
+        0.0
+    } else {
+        2.0 * taker_fee_rate * entry_price / risk
+    };
+    estimator.expected_value_r(entry_price) - fees_r
+}
+
+/// Expected R of continuing to hold a bracketed position.
+///
+/// Holding from `price` wins the reward left to the take-profit with the
+/// model's win probability and loses the risk left to the stop otherwise,
+/// both in units of the initial stop distance.
+///
+/// Example:
+/// - short entry `100`, stop `101`, take profit `98`, win rate `0.8`
+/// - at `100` -> `r=0.00 ev=1.40R`
+/// - at `98.5` -> `r=1.50 ev=-0.10R`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvEstimator<M = BetaBinomial> {
+    pub entry_price: f64,
+    pub stop_price: f64,
+    pub take_profit_price: f64,
+    pub model: M,
+}
+
+impl EvEstimator {
+    /// A fixed `win_rate`, i.e. a beta-binomial with no outcomes yet.
+    pub fn new(entry_price: f64, stop_price: f64, take_profit_price: f64, win_rate: f64) -> Self {
+        Self::with_model(
+            entry_price,
+            stop_price,
+            take_profit_price,
+            BetaBinomial::fixed(win_rate),
+        )
+    }
+}
+
+impl<M: WinProbabilityModel> EvEstimator<M> {
+    pub fn with_model(entry_price: f64, stop_price: f64, take_profit_price: f64, model: M) -> Self {
+        Self {
+            entry_price,
+            stop_price,
+            take_profit_price,
+            model,
+        }
+    }
+
+    fn direction(&self) -> f64 {
+        if self.take_profit_price >= self.entry_price {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    fn risk(&self) -> f64 {
+        (self.entry_price - self.stop_price).abs()
+    }
+
+    /// Move since entry in units of the initial stop distance.
+    pub fn r_multiple(&self, price: f64) -> f64 {
+        if self.risk() <= f64::EPSILON {
+            return 0.0;
+        }
+        self.direction() * (price - self.entry_price) / self.risk()
+    }
+
+    pub fn features(&self, price: f64) -> EvFeatures {
+        let risk = self.risk();
+        EvFeatures {
+            r_multiple: self.r_multiple(price),
+            reward_left_r: if risk <= f64::EPSILON {
+                0.0
+            } else {
+                (self.direction() * (self.take_profit_price - price) / risk).max(0.0)
+            },
+        }
+    }
+
+    pub fn win_probability(&self, price: f64) -> f64 {
+        self.model
+            .win_probability(&self.features(price))
+            .clamp(0.0, 1.0)
+    }
+
+    pub fn expected_value_r(&self, price: f64) -> f64 {
+        let risk = self.risk();
+        if risk <= f64::EPSILON {
+            return 0.0;
+        }
+        let reward_left = self.features(price).reward_left_r;
+        let risk_left = (self.direction() * (price - self.stop_price) / risk).max(0.0);
+        let win_probability = self.win_probability(price);
+        win_probability * reward_left - (1.0 - win_probability) * risk_left
+    }
+}
@@ -0,0 +1,310 @@
+use std::fmt;
+
+use crate::strategy::model::{StrategyWatch, StrategyWatchState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PnlComparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl PnlComparison {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Equal => "=",
+            Self::NotEqual => "!=",
+        }
+    }
+
+    fn holds(self, left: f64, right: f64) -> bool {
+        match self {
+            Self::Greater => left > right,
+            Self::GreaterOrEqual => left >= right,
+            Self::Less => left < right,
+            Self::LessOrEqual => left <= right,
+            Self::Equal => (left - right).abs() <= f64::EPSILON,
+            Self::NotEqual => (left - right).abs() > f64::EPSILON,
+        }
+    }
+}
+
+/// Selects active watches for `strategy select`; every clause must hold.
+///
+/// - `kind` is a template slug or one of its words (`sma`, `short`, ...)
+/// - `symbol` and `group` take `*` wildcards; `group=none` is ungrouped
+/// - `state` is `armed`, `paused` or `disabled`
+/// - `pnl` compares realized plus unrealized ledger PnL in USDT; watches
+///   without allocated capital never match it
+///
+/// Example:
+/// - `kind=sma symbol=BTC* pnl>0`
+/// - matches `price-sma-cross-long` on `BTCUSDT` with `+12.5` PnL, not
+///   the same watch on `ETHUSDT`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrategyFilter {
+    pub kind: Option<String>,
+    pub symbol: Option<String>,
+    pub group: Option<String>,
+    pub state: Option<StrategyWatchState>,
+    pub pnl: Option<(PnlComparison, f64)>,
+}
+
+impl StrategyFilter {
+    pub fn parse(terms: &[String]) -> Result<Self, String> {
+        let mut filter = Self::default();
+        for term in terms {
+            let Some((key, comparison, value)) = split_clause(term) else {
+                return Err(format!(
+                    "invalid strategy filter: {term}. expected <key><op><value>, e.g. symbol=BTC*"
+                ));
+            };
+            if value.is_empty() {
+                return Err(format!("strategy filter needs a value: {term}"));
+            }
+            if key != "pnl" && comparison != PnlComparison::Equal {
+                return Err(format!(
+                    "unsupported strategy filter: {term}. only pnl compares with {}",
+                    comparison.as_str()
+                ));
+            }
+            match key {
+                "kind" => filter.kind = Some(value.to_ascii_lowercase()),
+                "symbol" => filter.symbol = Some(value.to_ascii_uppercase()),
+                "group" => filter.group = Some(value.to_ascii_lowercase()),
+                "state" => {
+                    filter.state = Some(match value.to_ascii_lowercase().as_str() {
+                        "armed" => StrategyWatchState::Armed,
+                        "paused" => StrategyWatchState::Paused,
+                        "disabled" => StrategyWatchState::Disabled,
+                        other => {
+                            return Err(format!(
+                                "unsupported strategy state filter: {other}. expected armed, paused or disabled"
+                            ))
+                        }
+                    })
+                }
+                "pnl" => {
+                    let threshold = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|threshold| threshold.is_finite())
+                        .ok_or_else(|| format!("invalid pnl filter value: {value}"))?;
+                    filter.pnl = Some((comparison, threshold));
+                }
+                other => {
+                    return Err(format!(
+                        "unsupported strategy filter: {other}. expected kind, symbol, group, state or pnl"
+                    ))
+                }
+            }
+        }
+        if filter.is_empty() {
+            return Err("strategy filter needs at least one clause".to_string());
+        }
+        Ok(filter)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kind.is_none()
+            && self.symbol.is_none()
+            && self.group.is_none()
+            && self.state.is_none()
+            && self.pnl.is_none()
+    }
+
+    pub fn matches(&self, watch: &StrategyWatch) -> bool {
+        let slug = watch.template.slug();
+        self.kind.as_deref().is_none_or(|kind| {
+            wildcard_match(kind, slug) || slug.split('-').any(|word| word == kind)
+        }) && self
+            .symbol
+            .as_deref()
+            .is_none_or(|pattern| wildcard_match(pattern, &watch.instrument.0))
+            && self
+                .group
+                .as_deref()
+                .is_none_or(|pattern| match watch.group.as_deref() {
+                    Some(group) => wildcard_match(pattern, group),
+                    None => pattern == "none",
+                })
+            && self.state.is_none_or(|state| watch.state == state)
+            && self.pnl.is_none_or(|(comparison, threshold)| {
+                watch.ledger.as_ref().is_some_and(|ledger| {
+                    comparison.holds(
+                        ledger.realized_pnl_usdt + ledger.unrealized_pnl_usdt,
+                        threshold,
+                    )
+                })
+            })
+    }
+}
+
+/// The clauses in a fixed order, e.g. `kind=sma symbol=BTC* pnl>0`.
+impl fmt::Display for StrategyFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut clauses = Vec::new();
+        if let Some(kind) = &self.kind {
+            clauses.push(format!("kind={kind}"));
+        }
+        if let Some(symbol) = &self.symbol {
+            clauses.push(format!("symbol={symbol}"));
+        }
+        if let Some(group) = &self.group {
+            clauses.push(format!("group={group}"));
+        }
+        if let Some(state) = self.state {
+            clauses.push(format!("state={}", state.as_str()));
+        }
+        if let Some((comparison, threshold)) = self.pnl {
+            clauses.push(format!("pnl{}{threshold}", comparison.as_str()));
+        }
+        f.write_str(&clauses.join(" "))
+    }
+}
+
+fn split_clause(term: &str) -> Option<(&str, PnlComparison, &str)> {
+    let at = term.find(['=', '!', '<', '>'])?;
+    let (key, rest) = term.split_at(at);
+    let (comparison, width) = [
+        (">=", PnlComparison::GreaterOrEqual),
+        ("<=", PnlComparison::LessOrEqual),
+        ("!=", PnlComparison::NotEqual),
+        (">", PnlComparison::Greater),
+        ("<", PnlComparison::Less),
+        ("=", PnlComparison::Equal),
+    ]
+    .into_iter()
+    .find(|(op, _)| rest.starts_with(op))
+    .map(|(op, comparison)| (comparison, op.len()))?;
+    let key = key.trim();
+    (!key.is_empty()).then(|| (key, comparison, rest[width..].trim()))
+}
+
+/// `*` matches any run of characters; everything else must match exactly.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wildcard_match;
+
+    #[test]
+    fn wildcard_match_handles_prefix_suffix_and_inner_stars() {
+        assert!(wildcard_match("BTC*", "BTCUSDT"));
+        assert!(wildcard_match("*USDT", "BTCUSDT"));
+        assert!(wildcard_match("B*U*T", "BTCUSDT"));
+        assert!(wildcard_match("BTCUSDT", "BTCUSDT"));
+        assert!(!wildcard_match("BTC", "BTCUSDT"));
+        assert!(!wildcard_match("ETH*", "BTCUSDT"));
+        assert!(!wildcard_match("*USDTX", "BTCUSDT"));
+    }
+}
+
+/// Rows shown per page of `strategy list` and `strategy history`.
+pub const STRATEGY_PAGE_SIZE: usize = 10;
+
+/// Search text and 1-based page for `strategy list` and `strategy history`.
+///
+/// The search matches, case-insensitively, anywhere in a watch's template
+/// slug, instrument, group or state.
+///
+/// Example:
+/// - `strategy list btc page=2` shows matches 11-20 for `btc`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyListQuery {
+    pub search: Option<String>,
+    pub page: usize,
+}
+
+impl Default for StrategyListQuery {
+    fn default() -> Self {
+        Self {
+            search: None,
+            page: 1,
+        }
+    }
+}
+
+impl StrategyListQuery {
+    /// Example:
+    /// - `[]` -> every watch, page 1; `[sma, short, page=3]` -> `sma short`, page 3
+    pub fn parse(terms: &[String]) -> Result<Self, String> {
+        let mut query = Self::default();
+        let mut words = Vec::new();
+        for term in terms {
+            match term.strip_prefix("page=") {
+                Some(page) => {
+                    query.page = page
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|page| *page >= 1)
+                        .ok_or_else(|| format!("invalid page: {page}. expected 1 or more"))?;
+                }
+                None => words.push(term.trim().to_ascii_lowercase()),
+            }
+        }
+        let search = words.join(" ");
+        query.search = (!search.trim().is_empty()).then(|| search.trim().to_string());
+        Ok(query)
+    }
+
+    pub fn matches(&self, watch: &StrategyWatch) -> bool {
+        let Some(search) = &self.search else {
+            return true;
+        };
+        [
+            watch.template.slug(),
+            watch.instrument.0.as_str(),
+            watch.group.as_deref().unwrap_or("none"),
+            watch.state.as_str(),
+        ]
+        .iter()
+        .any(|field| field.to_ascii_lowercase().contains(search.as_str()))
+    }
+
+    /// The rows of the requested page, clamped to the last one, and the
+    /// position line for it.
+    ///
+    /// Example:
+    /// - `34` rows, page `2` -> rows 11-20, `page=2/4 showing=11-20 of 34`
+    pub fn paginate<'a, T>(&self, rows: &'a [T]) -> (&'a [T], String) {
+        let pages = rows.len().div_ceil(STRATEGY_PAGE_SIZE).max(1);
+        let page = self.page.clamp(1, pages);
+        let start = (page - 1) * STRATEGY_PAGE_SIZE;
+        let end = (start + STRATEGY_PAGE_SIZE).min(rows.len());
+        let shown = if rows.is_empty() {
+            format!("page={page}/{pages} showing=0 of 0")
+        } else {
+            format!(
+                "page={page}/{pages} showing={}-{end} of {}",
+                start + 1,
+                rows.len()
+            )
+        };
+        (&rows[start..end], shown)
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::storage_error::StorageError;
+use crate::storage::{atomic_file, sealed};
+use crate::strategy::model::{StrategyWatch, StrategyWatchState};
+
+pub const DEFAULT_STRATEGY_GROUPS_PATH: &str = "var/strategy-groups.json";
+
+/// Aggregated state and ledger PnL for one group of active watches.
+///
+/// Example:
+/// - `trend` holds two armed watches seeded `500` and `1000`
+/// - realized `+30` and `-10` -> `realized_pnl_usdt=20`, `roi_pct=1.33`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrategyGroupStats {
+    /// `None` collects the watches without a group.
+    pub group: Option<String>,
+    pub watches: usize,
+    pub armed: usize,
+    pub paused: usize,
+    pub disabled: usize,
+    pub seed_capital_usdt: f64,
+    pub realized_pnl_usdt: f64,
+    pub unrealized_pnl_usdt: f64,
+}
+
+impl StrategyGroupStats {
+    pub fn label(&self) -> &str {
+        self.group.as_deref().unwrap_or("none")
+    }
+
+    /// PnL over the allocated seed capital; `0` when nothing is allocated.
+    pub fn roi_pct(&self) -> f64 {
+        if self.seed_capital_usdt > f64::EPSILON {
+            (self.realized_pnl_usdt + self.unrealized_pnl_usdt) / self.seed_capital_usdt * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Buckets watches by group name, named groups first and ungrouped last.
+pub fn group_stats<'a>(
+    watches: impl IntoIterator<Item = &'a StrategyWatch>,
+) -> Vec<StrategyGroupStats> {
+    let mut groups: BTreeMap<Option<String>, StrategyGroupStats> = BTreeMap::new();
+    for watch in watches {
+        let stats = groups
+            .entry(watch.group.clone())
+            .or_insert_with(|| StrategyGroupStats {
+                group: watch.group.clone(),
+                ..StrategyGroupStats::default()
+            });
+        stats.watches += 1;
+        match watch.state {
+            StrategyWatchState::Armed => stats.armed += 1,
+            StrategyWatchState::Paused => stats.paused += 1,
+            StrategyWatchState::Disabled => stats.disabled += 1,
+            _ => {}
+        }
+        if let Some(ledger) = &watch.ledger {
+            stats.seed_capital_usdt += ledger.seed_capital_usdt;
+            stats.realized_pnl_usdt += ledger.realized_pnl_usdt;
+            stats.unrealized_pnl_usdt += ledger.unrealized_pnl_usdt;
+        }
+    }
+    let ungrouped = groups.remove(&None);
+    groups.into_values().chain(ungrouped).collect()
+}
+
+/// A saved group label. Watch ids restart every session, so the label is
+/// keyed by what a restarted watch shares with the one that was labelled.
+///
+/// Example:
+/// - `demo` `price-sma-cross-long` on `ETHUSDT` -> `trend`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StrategyGroupLabel {
+    pub mode: String,
+    pub template: String,
+    pub instrument: String,
+    pub group: String,
+}
+
+impl StrategyGroupLabel {
+    fn labels(&self, watch: &StrategyWatch) -> bool {
+        self.mode == watch.mode.as_str()
+            && self.template == watch.template.slug()
+            && self.instrument == watch.instrument.0
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct StrategyGroupFile {
+    labels: Vec<StrategyGroupLabel>,
+}
+
+/// Group labels persisted as JSON, so a watch started again after a
+/// restart rejoins its group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyGroupStore {
+    path: PathBuf,
+    labels: Vec<StrategyGroupLabel>,
+    /// `false` when the file on disk failed to load, so it is never saved
+    /// over.
+    writable: bool,
+}
+
+impl Default for StrategyGroupStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_STRATEGY_GROUPS_PATH)
+    }
+}
+
+impl StrategyGroupStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            labels: Vec::new(),
+            writable: true,
+        }
+    }
+
+    /// An empty store standing in for one that failed to load; `save`
+    /// refuses so the file on disk is left for the operator to repair.
+    pub fn unloaded(path: impl Into<PathBuf>) -> Self {
+        Self {
+            writable: false,
+            ..Self::new(path)
+        }
+    }
+
+    /// Loads labels from `path`, starting empty when the file does not exist.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, StorageError> {
+        let mut store = Self::new(path);
+        if !atomic_file::store_exists(&store.path) {
+            return Ok(store);
+        }
+        let bytes = sealed::read_store_file(&store.path, sealed::process_sealer()?)?;
+        let file: StrategyGroupFile =
+            serde_json::from_slice(&bytes).map_err(|error| StorageError::ParseFailed {
+                path: store.path.display().to_string(),
+                message: error.to_string(),
+            })?;
+        store.labels = file.labels;
+        Ok(store)
+    }
+
+    pub fn save(&self) -> Result<(), StorageError> {
+        if !self.writable {
+            return Err(StorageError::NotLoaded {
+                path: self.path.display().to_string(),
+            });
+        }
+        let file = StrategyGroupFile {
+            labels: self.labels.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&file).map_err(|error| {
+            StorageError::WriteFailedWithContext {
+                message: error.to_string(),
+            }
+        })?;
+        sealed::write_store_file(&self.path, &json, sealed::process_sealer()?)
+    }
+
+    pub fn labels(&self) -> &[StrategyGroupLabel] {
+        &self.labels
+    }
+
+    /// The saved group of a watch like `watch`, if any.
+    pub fn group_for(&self, watch: &StrategyWatch) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|label| label.labels(watch))
+            .map(|label| label.group.as_str())
+    }
+
+    /// Saves `watch`'s current group, or drops its label when it has none.
+    pub fn record(&mut self, watch: &StrategyWatch) {
+        self.forget(watch);
+        if let Some(group) = &watch.group {
+            self.labels.push(StrategyGroupLabel {
+                mode: watch.mode.as_str().to_string(),
+                template: watch.template.slug().to_string(),
+                instrument: watch.instrument.0.clone(),
+                group: group.clone(),
+            });
+        }
+    }
+
+    /// Drops `watch`'s label; returns whether it had one.
+    pub fn forget(&mut self, watch: &StrategyWatch) -> bool {
+        let before = self.labels.len();
+        self.labels.retain(|label| !label.labels(watch));
+        self.labels.len() != before
+    }
+}
@@ -0,0 +1,187 @@
+use std::env;
+
+use crate::dataset::types::DerivedKlineRow;
+use crate::domain::position::Side;
+
+/// Average used when a timeframe is given without one.
+pub const DEFAULT_HTF_INDICATOR: HtfIndicator = HtfIndicator::Ema(20);
+
+/// Trend line whose slope decides the higher-timeframe direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HtfIndicator {
+    Ema(usize),
+    Sma(usize),
+}
+
+impl HtfIndicator {
+    /// Example:
+    /// - `ema20` -> `Ema(20)`; `sma50` -> `Sma(50)`
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim().to_ascii_lowercase();
+        let (kind, period) = (raw.get(..3)?, raw.get(3..)?);
+        let period = period.parse::<usize>().ok().filter(|period| *period >= 2)?;
+        match kind {
+            "ema" => Some(Self::Ema(period)),
+            "sma" => Some(Self::Sma(period)),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Ema(period) => format!("ema{period}"),
+            Self::Sma(period) => format!("sma{period}"),
+        }
+    }
+
+    fn period(&self) -> usize {
+        match *self {
+            Self::Ema(period) | Self::Sma(period) => period,
+        }
+    }
+
+    /// Latest and previous value over `closes`; `None` until one more
+    /// close than the period is in.
+    fn last_two(&self, closes: &[f64]) -> Option<(f64, f64)> {
+        let period = self.period();
+        if closes.len() <= period {
+            return None;
+        }
+        match *self {
+            Self::Sma(_) => {
+                let mean =
+                    |end: usize| closes[end - period..end].iter().sum::<f64>() / period as f64;
+                Some((mean(closes.len()), mean(closes.len() - 1)))
+            }
+            Self::Ema(_) => {
+                // Seeded with the first window's mean, like most charting tools.
+                let alpha = 2.0 / (period as f64 + 1.0);
+                let mut ema = closes[..period].iter().sum::<f64>() / period as f64;
+                let mut previous = ema;
+                for close in &closes[period..] {
+                    previous = ema;
+                    ema = close * alpha + ema * (1.0 - alpha);
+                }
+                Some((ema, previous))
+            }
+        }
+    }
+}
+
+/// Entry filter requiring the higher-timeframe trend to point the way of
+/// the signal.
+///
+/// Base candles are rolled up into `timeframe_secs` bars; only closed bars
+/// count, so a signal never sees the bar it fires in. Until the indicator
+/// has enough bars the filter has no trend and holds entries back.
+///
+/// Example:
+/// - `1h:ema20`: a long SMA cross is taken only while the hourly EMA20 rose
+///   over the last closed hour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HtfConfirm {
+    pub timeframe_secs: i64,
+    pub indicator: HtfIndicator,
+}
+
+impl HtfConfirm {
+    /// Reads `SANDBOX_QUANT_HTF_CONFIRM` (`off`, or `<timeframe>[:<indicator>]`,
+    /// e.g. `1h` or `4h:sma50`); unset or invalid leaves it off.
+    pub fn from_env() -> Option<Self> {
+        env::var("SANDBOX_QUANT_HTF_CONFIRM")
+            .ok()
+            .and_then(|raw| Self::parse(&raw))
+    }
+
+    /// Example:
+    /// - `1h` -> hourly `ema20`; `15m:sma30`; `off` -> `None`
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim().to_ascii_lowercase();
+        let (timeframe, indicator) = match raw.split_once(':') {
+            Some((timeframe, indicator)) => (timeframe, HtfIndicator::parse(indicator)?),
+            None => (raw.as_str(), DEFAULT_HTF_INDICATOR),
+        };
+        let (count, unit_secs) = [("m", 60), ("h", 3_600), ("d", 86_400)]
+            .into_iter()
+            .find_map(|(unit, secs)| Some((timeframe.strip_suffix(unit)?, secs)))?;
+        let count = count.parse::<i64>().ok().filter(|count| *count > 0)?;
+        Some(Self {
+            timeframe_secs: count * unit_secs,
+            indicator,
+        })
+    }
+
+    /// Example:
+    /// - `1h:ema20`, `90m:sma30`
+    pub fn label(&self) -> String {
+        let secs = self.timeframe_secs;
+        let timeframe = if secs % 86_400 == 0 {
+            format!("{}d", secs / 86_400)
+        } else if secs % 3_600 == 0 {
+            format!("{}h", secs / 3_600)
+        } else {
+            format!("{}m", secs / 60)
+        };
+        format!("{timeframe}:{}", self.indicator.label())
+    }
+}
+
+/// Rolls base candles into higher-timeframe closes and reads the trend off
+/// the configured indicator.
+#[derive(Debug, Clone)]
+pub struct HtfTrend {
+    config: HtfConfirm,
+    closes: Vec<f64>,
+    /// Bucket start and latest close of the bar still forming.
+    forming: Option<(i64, f64)>,
+}
+
+impl HtfTrend {
+    pub fn new(config: HtfConfirm) -> Self {
+        Self {
+            config,
+            closes: Vec::new(),
+            forming: None,
+        }
+    }
+
+    pub fn on_candle_close(&mut self, candle: &DerivedKlineRow) {
+        self.on_close(candle.open_time_ms, candle.close_time_ms, candle.close);
+    }
+
+    /// `on_candle_close` for a base bar known only by its span and close,
+    /// as the live bar loop sees minutes.
+    pub fn on_close(&mut self, open_time_ms: i64, close_time_ms: i64, close: f64) {
+        let span_ms = self.config.timeframe_secs * 1_000;
+        let bucket = open_time_ms.div_euclid(span_ms) * span_ms;
+        match self.forming {
+            Some((start, forming_close)) if start != bucket => {
+                self.closes.push(forming_close);
+                self.forming = Some((bucket, close));
+            }
+            _ => self.forming = Some((bucket, close)),
+        }
+        // A base candle that ends the bucket closes the bar right away.
+        if close_time_ms + 1 >= bucket + span_ms {
+            self.closes.push(close);
+            self.forming = None;
+        }
+    }
+
+    /// Indicator change over the last closed bar; `None` while warming up.
+    pub fn slope(&self) -> Option<f64> {
+        self.config
+            .indicator
+            .last_two(&self.closes)
+            .map(|(now, previous)| now - previous)
+    }
+
+    /// Whether the trend agrees with an entry on `side`; a flat or unknown
+    /// trend does not.
+    pub fn confirms(&self, side: Side) -> bool {
+        self.slope().is_some_and(|slope| match side {
+            Side::Buy => slope > 0.0,
+            Side::Sell => slope < 0.0,
+        })
+    }
+}
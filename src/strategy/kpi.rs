@@ -0,0 +1,282 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::position::PositionSnapshot;
+
+/// Round trips needed before the win rate is judged.
+pub const MIN_KPI_TRADES: u32 = 5;
+/// Hours of history needed before the trade rate is judged.
+pub const MIN_KPI_RATE_HOURS: i64 = 24;
+/// Win-rate points under target that still count as amber.
+const WIN_RATE_AMBER_MARGIN: f64 = 0.05;
+/// Share of the drawdown limit from which the light turns amber.
+const DRAWDOWN_AMBER_SHARE: f64 = 0.8;
+/// Share of the trade-rate target that still counts as amber.
+const TRADE_RATE_AMBER_SHARE: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KpiLight {
+    Green,
+    Amber,
+    Red,
+}
+
+impl KpiLight {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Green => "green",
+            Self::Amber => "amber",
+            Self::Red => "red",
+        }
+    }
+}
+
+/// Targets a watch is held to, each optional.
+///
+/// Example:
+/// - `strategy kpi 3 win=55% dd=150 trades=3`
+/// - at least 55% winners, no more than 150 USDT peak-to-trough and
+///   3 round trips a week
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KpiTargets {
+    pub min_win_rate: Option<f64>,
+    pub max_drawdown_usdt: Option<f64>,
+    pub min_trades_per_week: Option<f64>,
+}
+
+impl KpiTargets {
+    /// Example:
+    /// - `win=55%` or `win=0.55` -> `min_win_rate=0.55`
+    /// - `dd=150 trades=3` -> `max_drawdown_usdt=150`, `min_trades_per_week=3`
+    pub fn parse(terms: &[String]) -> Result<Self, String> {
+        let mut targets = Self::default();
+        for term in terms {
+            let (key, value) = term
+                .split_once('=')
+                .ok_or_else(|| format!("invalid kpi target: {term}. expected key=value"))?;
+            let value = value.trim();
+            match key.trim() {
+                "win" => {
+                    let rate = match value.strip_suffix('%') {
+                        Some(pct) => pct.trim().parse::<f64>().ok().map(|pct| pct / 100.0),
+                        None => value.parse::<f64>().ok(),
+                    };
+                    targets.min_win_rate = Some(
+                        rate.filter(|rate| (0.0..=1.0).contains(rate))
+                            .ok_or_else(|| format!("invalid kpi win rate: {value}"))?,
+                    );
+                }
+                "dd" => {
+                    targets.max_drawdown_usdt = Some(
+                        value
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|usdt| usdt.is_finite() && *usdt > 0.0)
+                            .ok_or_else(|| format!("invalid kpi drawdown: {value}"))?,
+                    );
+                }
+                "trades" => {
+                    targets.min_trades_per_week = Some(
+                        value
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|trades| trades.is_finite() && *trades > 0.0)
+                            .ok_or_else(|| format!("invalid kpi trades per week: {value}"))?,
+                    );
+                }
+                other => {
+                    return Err(format!(
+                        "unsupported kpi target: {other}. expected win, dd or trades"
+                    ))
+                }
+            }
+        }
+        if targets.is_empty() {
+            return Err("kpi needs at least one target".to_string());
+        }
+        Ok(targets)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min_win_rate.is_none()
+            && self.max_drawdown_usdt.is_none()
+            && self.min_trades_per_week.is_none()
+    }
+}
+
+/// The targets in a fixed order, e.g. `win>=55% dd<=150 trades>=3/wk`.
+impl fmt::Display for KpiTargets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut targets = Vec::new();
+        if let Some(rate) = self.min_win_rate {
+            targets.push(format!("win>={:.0}%", rate * 100.0));
+        }
+        if let Some(usdt) = self.max_drawdown_usdt {
+            targets.push(format!("dd<={usdt}"));
+        }
+        if let Some(trades) = self.min_trades_per_week {
+            targets.push(format!("trades>={trades}/wk"));
+        }
+        f.write_str(&targets.join(" "))
+    }
+}
+
+/// One target held against the live figure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KpiCheck {
+    pub metric: &'static str,
+    pub actual: f64,
+    pub target: f64,
+    pub light: KpiLight,
+}
+
+/// KPI targets of one watch with the round trips counted since they were
+/// set.
+///
+/// Round trips are taken from the watch instrument's position between
+/// refreshes, like the size ramp; drawdown follows closed PnL plus the
+/// open position's mark.
+///
+/// Example:
+/// - `win>=55%` after 8 trades with 4 winners -> `50%`, amber
+/// - `dd<=150` after falling 160 USDT from the best point -> red
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyKpi {
+    pub targets: KpiTargets,
+    pub since: DateTime<Utc>,
+    pub trades: u32,
+    pub wins: u32,
+    pub closed_pnl_usdt: f64,
+    peak_pnl_usdt: f64,
+    pub max_drawdown_usdt: f64,
+    last_signed_qty: f64,
+    last_entry_price: Option<f64>,
+}
+
+impl StrategyKpi {
+    pub fn new(targets: KpiTargets, since: DateTime<Utc>) -> Self {
+        Self {
+            targets,
+            since,
+            trades: 0,
+            wins: 0,
+            closed_pnl_usdt: 0.0,
+            peak_pnl_usdt: 0.0,
+            max_drawdown_usdt: 0.0,
+            last_signed_qty: 0.0,
+            last_entry_price: None,
+        }
+    }
+
+    /// Folds the latest position and mark in, counting a round trip once
+    /// the position goes flat.
+    pub fn observe(&mut self, position: Option<&PositionSnapshot>, mark_price: Option<f64>) {
+        let signed_qty = position.map_or(0.0, |position| position.signed_qty);
+        let closed = self.last_signed_qty.abs() > f64::EPSILON && signed_qty.abs() <= f64::EPSILON;
+        if let (true, Some(entry), Some(exit)) = (closed, self.last_entry_price, mark_price) {
+            let pnl = (exit - entry) * self.last_signed_qty;
+            self.trades += 1;
+            if pnl > 0.0 {
+                self.wins += 1;
+            }
+            self.closed_pnl_usdt += pnl;
+        }
+        let entry_price = position.and_then(|position| position.entry_price);
+        let open_pnl = match (mark_price, entry_price) {
+            (Some(mark), Some(entry)) => (mark - entry) * signed_qty,
+            _ => 0.0,
+        };
+        let equity = self.closed_pnl_usdt + open_pnl;
+        self.peak_pnl_usdt = self.peak_pnl_usdt.max(equity);
+        self.max_drawdown_usdt = self.max_drawdown_usdt.max(self.peak_pnl_usdt - equity);
+        self.last_signed_qty = signed_qty;
+        self.last_entry_price = entry_price;
+    }
+
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.trades > 0).then(|| f64::from(self.wins) / f64::from(self.trades))
+    }
+
+    pub fn trades_per_week(&self, now: DateTime<Utc>) -> f64 {
+        let weeks = (now - self.since).num_seconds().max(1) as f64 / (7.0 * 86_400.0);
+        f64::from(self.trades) / weeks
+    }
+
+    /// Targets with enough history to judge; the rest are left out.
+    pub fn checks(&self, now: DateTime<Utc>) -> Vec<KpiCheck> {
+        let mut checks = Vec::new();
+        if let (Some(target), Some(actual)) = (self.targets.min_win_rate, self.win_rate()) {
+            if self.trades >= MIN_KPI_TRADES {
+                checks.push(KpiCheck {
+                    metric: "win_rate",
+                    actual,
+                    target,
+                    light: if actual >= target {
+                        KpiLight::Green
+                    } else if actual >= target - WIN_RATE_AMBER_MARGIN {
+                        KpiLight::Amber
+                    } else {
+                        KpiLight::Red
+                    },
+                });
+            }
+        }
+        if let Some(target) = self.targets.max_drawdown_usdt {
+            let actual = self.max_drawdown_usdt;
+            checks.push(KpiCheck {
+                metric: "drawdown_usdt",
+                actual,
+                target,
+                light: if actual > target {
+                    KpiLight::Red
+                } else if actual >= target * DRAWDOWN_AMBER_SHARE {
+                    KpiLight::Amber
+                } else {
+                    KpiLight::Green
+                },
+            });
+        }
+        if let Some(target) = self.targets.min_trades_per_week {
+            if (now - self.since).num_hours() >= MIN_KPI_RATE_HOURS {
+                let actual = self.trades_per_week(now);
+                checks.push(KpiCheck {
+                    metric: "trades_per_week",
+                    actual,
+                    target,
+                    light: if actual >= target {
+                        KpiLight::Green
+                    } else if actual >= target * TRADE_RATE_AMBER_SHARE {
+                        KpiLight::Amber
+                    } else {
+                        KpiLight::Red
+                    },
+                });
+            }
+        }
+        checks
+    }
+
+    /// The worst light among the judged targets; `None` while none can be
+    /// judged yet.
+    pub fn status(&self, now: DateTime<Utc>) -> Option<KpiLight> {
+        self.checks(now).into_iter().map(|check| check.light).max()
+    }
+
+    /// Grid chip, e.g. `red(drawdown_usdt)`, `green` or `pending`.
+    pub fn chip(&self, now: DateTime<Utc>) -> String {
+        let checks = self.checks(now);
+        let Some(worst) = checks.iter().map(|check| check.light).max() else {
+            return "pending".to_string();
+        };
+        if worst == KpiLight::Green {
+            return worst.as_str().to_string();
+        }
+        let missed = checks
+            .iter()
+            .filter(|check| check.light == worst)
+            .map(|check| check.metric)
+            .collect::<Vec<_>>();
+        format!("{}({})", worst.as_str(), missed.join(","))
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::position::Side;
+use crate::domain::reason::SkipReason;
+use crate::error::script_error::ScriptError;
+use crate::strategy::command::StrategyStartConfig;
+use crate::strategy::cooldown::{CooldownScaling, ExpectancyCooldown, DEFAULT_COOLDOWN_SECS};
+use crate::strategy::ev::{entry_expected_value_r, EvGate, EvSnapshotUpdate};
+use crate::strategy::htf_confirm::{HtfConfirm, HtfTrend};
+use crate::strategy::model::StrategyTemplate;
+use crate::strategy::registry::{Strategy, StrategySignal};
+use crate::strategy::script::ScriptStrategy;
+
+/// Stop distance live bar entries are sized against, the backtest runner's
+/// default; the exit itself comes from the strategy.
+pub const LIVE_BAR_STOP_DISTANCE_PCT: f64 = 0.012;
+
+/// A bar strategy fed from live prices: the last price seen in a minute is
+/// that minute's close, handed over once a later minute's price arrives.
+///
+/// Example:
+/// - prices at `12:00:10` and `12:00:50`, then one at `12:01:05`
+/// - the `12:00:50` price is fed as the `12:00` close
+pub struct LiveBarStrategy {
+    strategy: Box<dyn Strategy>,
+    minute: Option<i64>,
+    last_price: Option<f64>,
+    /// Set once an entry has been sent, cleared once its exit has.
+    entry: Option<LiveBarEntry>,
+    /// The post-exit wait the backtest runner's bar loop enters through.
+    cooldown: ExpectancyCooldown,
+    /// Higher-timeframe trend rolled up from the fed minutes; `None` is off.
+    htf_trend: Option<HtfTrend>,
+    ev_gate: EvGate,
+}
+
+/// What a live bar entry was sent at, to score its exit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiveBarEntry {
+    pub close: f64,
+    pub notional_usdt: f64,
+    pub risk_usdt: f64,
+}
+
+impl fmt::Debug for LiveBarStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LiveBarStrategy")
+            .field("side", &self.strategy.side())
+            .field("minute", &self.minute)
+            .field("last_price", &self.last_price)
+            .field("entry", &self.entry)
+            .field("cooldown", &self.cooldown)
+            .field("htf_trend", &self.htf_trend)
+            .field("ev_gate", &self.ev_gate)
+            .finish()
+    }
+}
+
+impl LiveBarStrategy {
+    pub fn new(strategy: Box<dyn Strategy>) -> Self {
+        Self {
+            strategy,
+            minute: None,
+            last_price: None,
+            entry: None,
+            cooldown: ExpectancyCooldown::for_bars(DEFAULT_COOLDOWN_SECS, None),
+            htf_trend: None,
+            ev_gate: EvGate::default(),
+        }
+    }
+
+    pub fn with_htf_confirm(mut self, htf_confirm: Option<HtfConfirm>) -> Self {
+        self.htf_trend = htf_confirm.map(HtfTrend::new);
+        self
+    }
+
+    pub fn with_ev_gate(mut self, ev_gate: EvGate) -> Self {
+        self.ev_gate = ev_gate;
+        self
+    }
+
+    /// Scales the post-exit cooldown by the watch's recent expectancy.
+    pub fn with_cooldown_scaling(mut self, scaling: Option<CooldownScaling>) -> Self {
+        self.cooldown = ExpectancyCooldown::for_bars(DEFAULT_COOLDOWN_SECS, scaling);
+        self
+    }
+
+    /// The strategy a watch started with `config` trades live, reading its
+    /// script from disk; `None` for templates without a live bar path.
+    ///
+    /// Its entry gates read the same settings a backtest defaults to,
+    /// e.g. `CooldownScaling::from_env`, `HtfConfirm::from_env` and
+    /// `EvGate::from_env`.
+    pub fn load(
+        template: StrategyTemplate,
+        config: &StrategyStartConfig,
+    ) -> Result<Option<Self>, ScriptError> {
+        match (template, config.script.as_deref()) {
+            (StrategyTemplate::ScriptLong | StrategyTemplate::ScriptShort, Some(path)) => {
+                let strategy = ScriptStrategy::load(path, template.entry_side())?;
+                Ok(Some(
+                    Self::new(Box::new(strategy))
+                        .with_cooldown_scaling(CooldownScaling::from_env())
+                        .with_htf_confirm(HtfConfirm::from_env())
+                        .with_ev_gate(EvGate::from_env()),
+                ))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub fn side(&self) -> Side {
+        self.strategy.side()
+    }
+
+    /// Records `price` seen at `at`; when it opens a new minute, feeds the
+    /// previous minute's close and returns that close with its signal.
+    ///
+    /// `None` while the minute is still open, during warm-up, and once the
+    /// strategy has faulted.
+    pub fn on_price(&mut self, at: DateTime<Utc>, price: f64) -> Option<(f64, StrategySignal)> {
+        let minute = at.timestamp().div_euclid(60);
+        if self.minute.is_some_and(|current| minute < current) {
+            return None;
+        }
+        let closed = self
+            .minute
+            .filter(|current| minute > *current)
+            .zip(self.last_price);
+        self.minute = Some(minute);
+        self.last_price = Some(price);
+        let (closed_minute, close) = closed?;
+        if let Some(trend) = self.htf_trend.as_mut() {
+            let open_time_ms = closed_minute * 60_000;
+            trend.on_close(open_time_ms, open_time_ms + 59_999, close);
+        }
+        self.strategy.on_tick(close).map(|signal| (close, signal))
+    }
+
+    pub fn in_position(&self) -> bool {
+        self.entry.is_some()
+    }
+
+    /// The EV gate's view of entering at `close` with the stop at
+    /// `LIVE_BAR_STOP_DISTANCE_PCT` and the target `r_multiple` stops away,
+    /// on the strategy's side.
+    ///
+    /// Example:
+    /// - short at `100`: stop `101.2`, take profit `98.2` for `r_multiple=1.5`
+    pub fn entry_ev(
+        &self,
+        close: f64,
+        win_rate: f64,
+        r_multiple: f64,
+        taker_fee_rate: f64,
+    ) -> EvSnapshotUpdate {
+        let direction = match self.side() {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        };
+        let stop_price = close * (1.0 - direction * LIVE_BAR_STOP_DISTANCE_PCT);
+        let take_profit_price = close * (1.0 + direction * LIVE_BAR_STOP_DISTANCE_PCT * r_multiple);
+        self.ev_gate.evaluate(entry_expected_value_r(
+            close,
+            stop_price,
+            take_profit_price,
+            win_rate,
+            taker_fee_rate,
+        ))
+    }
+
+    /// Why an entry signalled at `at` with `ev` is held back, checked in
+    /// the backtest runner's order; `Ok` lets it through.
+    pub fn check_entry(&self, at: DateTime<Utc>, ev: &EvSnapshotUpdate) -> Result<(), SkipReason> {
+        if !self.cooldown.allows_entry(at.timestamp_millis()) {
+            return Err(SkipReason::Cooldown);
+        }
+        if self
+            .htf_trend
+            .as_ref()
+            .is_some_and(|trend| !trend.confirms(self.side()))
+        {
+            return Err(SkipReason::HtfDisagrees);
+        }
+        if ev.gate_blocked {
+            return Err(SkipReason::LowExpectedValue);
+        }
+        Ok(())
+    }
+
+    pub fn on_entered(&mut self, entry: LiveBarEntry) {
+        self.entry = Some(entry);
+    }
+
+    /// Scores the exit sent at `close` against the entry's close, net of
+    /// `taker_fee_rate` on both sides, and starts the cooldown from `at`.
+    ///
+    /// Example:
+    /// - long entry `100` for `5_000` USDT risking `60`, exit `102`, fee `0.0005`
+    /// - net `100 - 5` USDT, `+1.58R` into the expectancy
+    pub fn on_exited(&mut self, at: DateTime<Utc>, close: f64, taker_fee_rate: f64) {
+        let Some(entry) = self.entry.take() else {
+            return;
+        };
+        let direction = match self.side() {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        };
+        let gross_pnl = direction * (close / entry.close - 1.0) * entry.notional_usdt;
+        let fees = 2.0 * taker_fee_rate * entry.notional_usdt;
+        self.cooldown
+            .record_exit(at.timestamp_millis(), gross_pnl - fees, entry.risk_usdt);
+    }
+
+    pub fn cooldown_secs(&self) -> i64 {
+        self.cooldown.cooldown_secs()
+    }
+
+    pub fn indicators(&self) -> BTreeMap<String, f64> {
+        self.strategy.indicators()
+    }
+
+    pub fn fault(&self) -> Option<String> {
+        self.strategy.fault()
+    }
+}
+
+/// Signed target exposure for a live bar entry that loses `risk_usdt` at
+/// the stop distance, as a fraction of `equity_usdt` capped at the whole
+/// account; `None` when there is nothing to risk.
+///
+/// Example:
+/// - `Buy`, risking `60` of `10_000` -> `Some(0.5)`
+/// - `Sell`, risking `6` of an allocation inside `10_000` -> `Some(-0.05)`
+pub fn entry_exposure(side: Side, risk_usdt: f64, equity_usdt: f64) -> Option<f64> {
+    if risk_usdt <= f64::EPSILON || equity_usdt <= f64::EPSILON {
+        return None;
+    }
+    let exposure = (risk_usdt / LIVE_BAR_STOP_DISTANCE_PCT / equity_usdt).min(1.0);
+    Some(match side {
+        Side::Buy => exposure,
+        Side::Sell => -exposure,
+    })
+}
@@ -1,3 +1,24 @@
+pub mod adaptive;
+pub mod allocation;
+pub mod basis;
+pub mod capital;
 pub mod command;
+pub mod cooldown;
+pub mod ev;
+pub mod filter;
+pub mod group;
+pub mod htf_confirm;
+pub mod kpi;
+pub mod live;
 pub mod model;
+pub mod order_throttle;
+pub mod overtrading;
+pub mod plugin;
+pub mod r_multiple;
+pub mod ramp;
+pub mod registry;
+pub mod script;
 pub mod store;
+pub mod trace;
+pub mod trade_stats;
+pub mod version;
@@ -2,15 +2,28 @@ use chrono::{DateTime, Utc};
 
 use crate::app::bootstrap::BinanceMode;
 use crate::domain::instrument::Instrument;
+use crate::domain::position::Side;
+use crate::strategy::adaptive::AdaptiveTuning;
+use crate::strategy::capital::StrategyLedger;
 use crate::strategy::command::StrategyStartConfig;
+use crate::strategy::kpi::StrategyKpi;
+use crate::strategy::r_multiple::RMultipleTracker;
+use crate::strategy::ramp::SizeRamp;
+use crate::strategy::version::parameter_fingerprint;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum StrategyTemplate {
     LiquidationBreakdownShort,
     PriceSmaCrossLong,
     PriceSmaCrossShort,
     PriceSmaCrossLongFast,
     PriceSmaCrossShortFast,
+    /// Enter and exit rules from a Rhai script, given with `--script`.
+    ScriptLong,
+    ScriptShort,
+    /// Signals from a WASM module in the plugin directory; backtest only.
+    PluginLong,
+    PluginShort,
 }
 
 impl StrategyTemplate {
@@ -21,6 +34,10 @@ impl StrategyTemplate {
             Self::PriceSmaCrossShort => "price-sma-cross-short",
             Self::PriceSmaCrossLongFast => "price-sma-cross-long-fast",
             Self::PriceSmaCrossShortFast => "price-sma-cross-short-fast",
+            Self::ScriptLong => "script-long",
+            Self::ScriptShort => "script-short",
+            Self::PluginLong => "plugin-long",
+            Self::PluginShort => "plugin-short",
         }
     }
 
@@ -63,9 +80,76 @@ impl StrategyTemplate {
                 "Manage the position with stop loss, take profit, or bullish cross exit",
                 "Close any remaining position at the end of the backtest window",
             ],
+            Self::ScriptLong => &[
+                "Read kline closes in backtests, or each minute's last price live",
+                "Evaluate the script's enter() and exit() functions on every close",
+                "Enter long on the close enter() turns true",
+                "Close on the close exit() turns true, or on a backtest stop or target",
+                "Close any remaining position at the end of the backtest window",
+            ],
+            Self::ScriptShort => &[
+                "Read kline closes in backtests, or each minute's last price live",
+                "Evaluate the script's enter() and exit() functions on every close",
+                "Enter short on the close enter() turns true",
+                "Close on the close exit() turns true, or on a backtest stop or target",
+                "Close any remaining position at the end of the backtest window",
+            ],
+            Self::PluginLong => &[
+                "Read raw futures kline closes from the selected interval",
+                "Pass every close to the plugin's on_tick export",
+                "Enter long when the plugin signals an entry",
+                "Manage the position with stop loss, take profit, or the plugin's exit",
+                "Close any remaining position at the end of the backtest window",
+            ],
+            Self::PluginShort => &[
+                "Read raw futures kline closes from the selected interval",
+                "Pass every close to the plugin's on_tick export",
+                "Enter short when the plugin signals an entry",
+                "Manage the position with stop loss, take profit, or the plugin's exit",
+                "Close any remaining position at the end of the backtest window",
+            ],
         }
     }
 
+    /// Fast/slow SMA windows the backtest runner trades the price-cross templates on.
+    ///
+    /// Example:
+    /// - `price-sma-cross-long` -> `Some((20, 50))`
+    /// - `liquidation-breakdown-short` -> `None`
+    pub fn sma_windows(self) -> Option<(usize, usize)> {
+        match self {
+            Self::LiquidationBreakdownShort
+            | Self::ScriptLong
+            | Self::ScriptShort
+            | Self::PluginLong
+            | Self::PluginShort => None,
+            Self::PriceSmaCrossLong | Self::PriceSmaCrossShort => Some((20, 50)),
+            Self::PriceSmaCrossLongFast | Self::PriceSmaCrossShortFast => Some((9, 21)),
+        }
+    }
+
+    /// Side the template opens on; shorts close with a buy to cover.
+    ///
+    /// Example:
+    /// - `price-sma-cross-long` -> `Buy`
+    /// - `liquidation-breakdown-short` -> `Sell`
+    pub fn entry_side(self) -> Side {
+        match self {
+            Self::PriceSmaCrossLong
+            | Self::PriceSmaCrossLongFast
+            | Self::ScriptLong
+            | Self::PluginLong => Side::Buy,
+            Self::LiquidationBreakdownShort
+            | Self::PriceSmaCrossShort
+            | Self::PriceSmaCrossShortFast
+            | Self::ScriptShort
+            | Self::PluginShort => Side::Sell,
+        }
+    }
+
+    /// Templates a watch can be armed with by slug alone; the script
+    /// templates also need `--script` and the plugin ones only run in
+    /// backtests.
     pub fn all() -> [Self; 5] {
         [
             Self::LiquidationBreakdownShort,
@@ -80,6 +164,11 @@ impl StrategyTemplate {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StrategyWatchState {
     Armed,
+    /// Held while the recorder reports the instrument as degraded or the
+    /// venue has halted it.
+    Paused,
+    /// Switched off by the operator until re-enabled or stopped.
+    Disabled,
     Triggered,
     Completed,
     Failed,
@@ -90,6 +179,8 @@ impl StrategyWatchState {
     pub fn as_str(self) -> &'static str {
         match self {
             Self::Armed => "armed",
+            Self::Paused => "paused",
+            Self::Disabled => "disabled",
             Self::Triggered => "triggered",
             Self::Completed => "completed",
             Self::Failed => "failed",
@@ -107,6 +198,20 @@ pub struct StrategyWatch {
     pub state: StrategyWatchState,
     pub current_step: usize,
     pub config: StrategyStartConfig,
+    /// Operator label for bulk actions, e.g. `trend` or `experimental`.
+    pub group: Option<String>,
+    /// Records a decision trace on every refresh while enabled.
+    pub debug: bool,
+    /// Isolated PnL against `config.seed_capital_usdt`, when allocated.
+    pub ledger: Option<StrategyLedger>,
+    /// Size ramp still in progress or finished; `None` trades full size.
+    pub ramp: Option<SizeRamp>,
+    /// Online tuning anchored to the starting `config`; `None` keeps it fixed.
+    pub adaptive: Option<AdaptiveTuning>,
+    /// Review targets with the live stats held against them.
+    pub kpi: Option<StrategyKpi>,
+    /// Closed round trips in units of their initial stop distance.
+    pub r_multiples: RMultipleTracker,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -127,9 +232,53 @@ impl StrategyWatch {
             instrument,
             state: StrategyWatchState::Armed,
             current_step: 1,
+            ledger: config.seed_capital_usdt.map(StrategyLedger::new),
+            ramp: config.ramp.clone(),
+            adaptive: config.adapt.clone().map(|mut adaptive| {
+                adaptive.anchor(&config);
+                adaptive
+            }),
             config,
+            group: None,
+            debug: false,
+            kpi: None,
+            r_multiples: RMultipleTracker::default(),
             created_at: now,
             updated_at: now,
         }
     }
+
+    /// Fingerprint of the template and the current `config`; an adaptation
+    /// or rollback moves the watch to a new version.
+    ///
+    pub fn version(&self) -> String {
+        parameter_fingerprint(
+            [
+                self.template.slug().to_string(),
+                format!("risk_pct={}", self.config.risk_pct),
+                format!("win_rate={}", self.config.win_rate),
+                format!("r_multiple={}", self.config.r_multiple),
+                format!(
+                    "max_entry_slippage_pct={}",
+                    self.config.max_entry_slippage_pct
+                ),
+                format!("seed_capital_usdt={:?}", self.config.seed_capital_usdt),
+            ]
+            .into_iter()
+            .chain(
+                self.config
+                    .script
+                    .as_ref()
+                    .map(|script| format!("script={}", script.display())),
+            ),
+        )
+    }
+
+    /// `config.risk_pct` scaled by the size ramp.
+    ///
+    /// Example:
+    /// - `risk_pct=0.01`, ramp at `0.25` -> `0.0025`
+    pub fn effective_risk_pct(&self) -> f64 {
+        self.config.risk_pct * self.ramp.as_ref().map_or(1.0, SizeRamp::fraction)
+    }
 }
@@ -0,0 +1,66 @@
+use std::collections::VecDeque;
+
+pub const DEFAULT_MAX_ORDERS_PER_MIN: usize = 3;
+const MINUTE_MS: i64 = 60_000;
+
+/// Reads:
+/// - `SANDBOX_QUANT_STRATEGY_ORDERS_PER_MIN` (default `3`, `0` turns the
+///   live per-watch entry throttle off)
+pub fn strategy_orders_per_min_from_env() -> usize {
+    std::env::var("SANDBOX_QUANT_STRATEGY_ORDERS_PER_MIN")
+        .ok()
+        .and_then(|raw| raw.trim().parse().ok())
+        .unwrap_or(DEFAULT_MAX_ORDERS_PER_MIN)
+}
+
+/// Caps how many entry orders one strategy may send in any rolling minute.
+///
+/// Exits are never throttled; only new entries count and can be dropped.
+///
+/// Example:
+/// - `max_orders_per_min=3`
+/// - entries at `12:00:00`, `12:00:10` and `12:00:20` pass
+/// - a signal at `12:00:30` is dropped and counted
+/// - the next signal passes again from `12:01:00`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderThrottle {
+    max_orders_per_min: usize,
+    recent: VecDeque<i64>,
+    dropped: usize,
+}
+
+impl OrderThrottle {
+    /// `0` disables the limit.
+    pub fn new(max_orders_per_min: usize) -> Self {
+        Self {
+            max_orders_per_min,
+            recent: VecDeque::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Admits an order at `time_ms`, or drops and counts it when the
+    /// rolling minute is already full.
+    pub fn try_admit(&mut self, time_ms: i64) -> bool {
+        if self.max_orders_per_min == 0 {
+            return true;
+        }
+        while self
+            .recent
+            .front()
+            .is_some_and(|front| *front <= time_ms - MINUTE_MS)
+        {
+            self.recent.pop_front();
+        }
+        if self.recent.len() >= self.max_orders_per_min {
+            self.dropped += 1;
+            return false;
+        }
+        self.recent.push_back(time_ms);
+        true
+    }
+
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::VecDeque;
+
+pub const DEFAULT_OVERTRADING_MAX_TRADES: usize = 5;
+pub const DEFAULT_OVERTRADING_WINDOW_SECS: i64 = 15 * 60;
+pub const DEFAULT_OVERTRADING_PAUSE_SECS: i64 = 30 * 60;
+
+/// Thresholds for the overtrading alarm; off unless configured.
+///
+/// Example:
+/// - `5:900:1800` -> 5 entries within 15m pause entries for 30m
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OvertradingLimit {
+    pub max_trades: usize,
+    pub window_secs: i64,
+    pub pause_secs: i64,
+}
+
+impl OvertradingLimit {
+    /// Reads:
+    /// - `SANDBOX_QUANT_OVERTRADING` (e.g. `5:900:1800`; unset or `off`
+    ///   leaves entries unguarded)
+    pub fn from_env() -> Option<Self> {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// `from_env` with the variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        var("SANDBOX_QUANT_OVERTRADING").and_then(|raw| Self::parse(&raw))
+    }
+
+    /// `<max_trades>:<window_secs>[:<pause_secs>]`.
+    ///
+    /// Example:
+    /// - `5:900` -> 5 entries within 15m, paused for the default 30m
+    /// - `0:900`, `5:0`, `off` -> `None`
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut parts = raw.trim().split(':').map(str::trim);
+        let max_trades = parts.next()?.parse::<usize>().ok()?;
+        let window_secs = parts.next()?.parse::<i64>().ok()?;
+        let pause_secs = match parts.next() {
+            Some(raw) => raw.parse::<i64>().ok()?,
+            None => DEFAULT_OVERTRADING_PAUSE_SECS,
+        };
+        (parts.next().is_none() && max_trades > 0 && window_secs > 0 && pause_secs >= 0).then_some(
+            Self {
+                max_trades,
+                window_secs,
+                pause_secs,
+            },
+        )
+    }
+
+    pub fn guard(&self) -> OvertradingGuard {
+        OvertradingGuard::new(self.max_trades, self.window_secs, self.pause_secs)
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "{} trades within {}s, pause {}s",
+            self.max_trades, self.window_secs, self.pause_secs
+        )
+    }
+}
+
+/// A burst of entries that reached the overtrading threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradeCluster {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub trades: usize,
+}
+
+impl TradeCluster {
+    pub fn span_secs(&self) -> i64 {
+        (self.end_ms - self.start_ms) / 1_000
+    }
+}
+
+/// Tracks entry times for one strategy run and pauses new entries once
+/// `max_trades` entries land inside `window_ms`.
+///
+/// Example:
+/// - `max_trades=3`, `window=10m`, `pause=30m`
+/// - entries at `12:00`, `12:04`, `12:08` raise the alarm
+/// - no new entry is allowed before `12:38`
+#[derive(Debug, Clone, PartialEq)]
+pub struct OvertradingGuard {
+    max_trades: usize,
+    window_ms: i64,
+    pause_ms: i64,
+    recent: VecDeque<i64>,
+    paused_until_ms: i64,
+    clusters: Vec<TradeCluster>,
+}
+
+impl OvertradingGuard {
+    pub fn new(max_trades: usize, window_secs: i64, pause_secs: i64) -> Self {
+        Self {
+            max_trades,
+            window_ms: window_secs.max(0) * 1_000,
+            pause_ms: pause_secs.max(0) * 1_000,
+            recent: VecDeque::new(),
+            paused_until_ms: 0,
+            clusters: Vec::new(),
+        }
+    }
+
+    /// Records an entry and returns the cluster when it trips the alarm.
+    pub fn record_entry(&mut self, time_ms: i64) -> Option<TradeCluster> {
+        if self.max_trades == 0 {
+            return None;
+        }
+        self.recent.push_back(time_ms);
+        while self
+            .recent
+            .front()
+            .is_some_and(|front| *front < time_ms - self.window_ms)
+        {
+            let _ = self.recent.pop_front();
+        }
+        if self.recent.len() < self.max_trades {
+            return None;
+        }
+
+        let cluster = TradeCluster {
+            start_ms: self.recent.front().copied().unwrap_or(time_ms),
+            end_ms: time_ms,
+            trades: self.recent.len(),
+        };
+        self.recent.clear();
+        self.paused_until_ms = self.paused_until_ms.max(time_ms + self.pause_ms);
+        self.clusters.push(cluster);
+        Some(cluster)
+    }
+
+    pub fn allows_entry(&self, time_ms: i64) -> bool {
+        time_ms >= self.paused_until_ms
+    }
+
+    pub fn paused_until_ms(&self) -> i64 {
+        self.paused_until_ms
+    }
+
+    pub fn clusters(&self) -> &[TradeCluster] {
+        &self.clusters
+    }
+}
+
+/// Replays entry times through a fresh guard to recover cluster stats.
+pub fn detect_trade_clusters(
+    entry_times_ms: impl IntoIterator<Item = i64>,
+    max_trades: usize,
+    window_secs: i64,
+) -> Vec<TradeCluster> {
+    let mut guard = OvertradingGuard::new(max_trades, window_secs, 0);
+    for time_ms in entry_times_ms {
+        let _ = guard.record_entry(time_ms);
+    }
+    guard.clusters
+}
@@ -0,0 +1,274 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use wasmi::{
+    Config, EnforcedLimits, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder,
+    TypedFunc,
+};
+
+use crate::domain::position::Side;
+use crate::error::plugin_error::PluginError;
+use crate::strategy::registry::{Strategy, StrategySignal};
+
+/// Directory searched for `*.wasm` strategy plugins when
+/// `SANDBOX_QUANT_PLUGIN_DIR` is unset.
+pub const DEFAULT_PLUGIN_DIR: &str = "plugins";
+
+/// Functions a plugin exports; every one takes and returns plain numbers.
+/// The side comes from the `plugin-long` or `plugin-short` template.
+///
+/// - `on_tick(close: f64) -> i32`: `-1` warming up, `0` hold, `1` enter, `2` exit
+/// - `warmup_bars() -> i32`
+/// - `reset()`
+pub const PLUGIN_EXPORTS: [&str; 3] = ["on_tick", "warmup_bars", "reset"];
+
+/// Instructions one export call may run before it traps, so a plugin
+/// stuck in a loop fails instead of stalling the caller.
+pub const PLUGIN_FUEL_PER_CALL: u64 = 10_000_000;
+
+/// Linear memory a plugin may declare or grow to.
+pub const PLUGIN_MAX_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+
+const WASM_MAGIC: &[u8; 4] = b"\0asm";
+
+/// A `*.wasm` file found in the plugin directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginEntry {
+    /// File stem, e.g. `mean-revert` for `plugins/mean-revert.wasm`.
+    pub name: String,
+    pub path: PathBuf,
+    /// Exported functions when the module implements the strategy
+    /// interface and instantiates, or why it does not.
+    pub status: Result<Vec<String>, PluginError>,
+}
+
+/// Reads:
+/// - `SANDBOX_QUANT_PLUGIN_DIR` (default `plugins`)
+pub fn plugin_dir_from_env() -> PathBuf {
+    std::env::var("SANDBOX_QUANT_PLUGIN_DIR")
+        .ok()
+        .filter(|raw| !raw.trim().is_empty())
+        .map_or_else(|| PathBuf::from(DEFAULT_PLUGIN_DIR), PathBuf::from)
+}
+
+/// Example:
+/// - `mean-revert` -> `plugins/mean-revert.wasm`
+pub fn plugin_path(name: &str) -> PathBuf {
+    plugin_dir_from_env().join(format!("{name}.wasm"))
+}
+
+/// Every `*.wasm` file directly in `dir`, by name; a missing directory
+/// has none.
+pub fn discover_plugins(dir: &Path) -> Vec<PluginEntry> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut plugins = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+        .map(|path| PluginEntry {
+            name: path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            status: plugin_status(&path),
+            path,
+        })
+        .collect::<Vec<_>>();
+    plugins.sort_by(|left, right| left.name.cmp(&right.name));
+    plugins
+}
+
+fn plugin_status(path: &Path) -> Result<Vec<String>, PluginError> {
+    let module = compile(&read_plugin(path)?)?;
+    let exports = inspect_module(&module)?;
+    WasmStrategy::instantiate(&module, Side::Buy)?;
+    Ok(exports)
+}
+
+fn read_plugin(path: &Path) -> Result<Vec<u8>, PluginError> {
+    std::fs::read(path).map_err(|error| PluginError::Read {
+        path: path.display().to_string(),
+        message: error.to_string(),
+    })
+}
+
+/// Checks that `bytes` is a wasm module that imports nothing from the
+/// host and exports every function in [`PLUGIN_EXPORTS`]; returns its
+/// exported function names, sorted.
+///
+/// A module without imports can only compute on the numbers it is
+/// handed, which is what keeps plugins sandboxed.
+pub fn inspect_wasm(bytes: &[u8]) -> Result<Vec<String>, PluginError> {
+    inspect_module(&compile(bytes)?)
+}
+
+/// Parses and validates `bytes` on an engine that meters fuel and
+/// enforces limits, so the module can be inspected and then instantiated.
+fn compile(bytes: &[u8]) -> Result<Module, PluginError> {
+    if !bytes.starts_with(WASM_MAGIC) {
+        return Err(PluginError::NotWasm);
+    }
+    let mut config = Config::default();
+    config
+        .consume_fuel(true)
+        .enforced_limits(EnforcedLimits::strict());
+    Module::new(&Engine::new(&config), bytes)
+        .map_err(|error| PluginError::Malformed(error.to_string()))
+}
+
+fn inspect_module(module: &Module) -> Result<Vec<String>, PluginError> {
+    if let Some(import) = module.imports().next() {
+        return Err(PluginError::Imports(format!(
+            "{}.{}",
+            import.module(),
+            import.name()
+        )));
+    }
+    let mut exports = module
+        .exports()
+        .filter(|export| export.ty().func().is_some())
+        .map(|export| export.name().to_string())
+        .collect::<Vec<_>>();
+    exports.sort();
+    if let Some(missing) = PLUGIN_EXPORTS
+        .into_iter()
+        .find(|required| !exports.iter().any(|export| export == required))
+    {
+        return Err(PluginError::MissingExport(missing));
+    }
+    Ok(exports)
+}
+
+/// A plugin instantiated as a bar strategy.
+///
+/// Every export call gets [`PLUGIN_FUEL_PER_CALL`] fuel and the module
+/// at most [`PLUGIN_MAX_MEMORY_BYTES`] of memory. A call that traps or
+/// answers an unknown signal code faults the strategy: it signals nothing
+/// until a `reset` succeeds, and [`Strategy::fault`] says why.
+pub struct WasmStrategy {
+    side: Side,
+    warmup_bars: usize,
+    store: Store<StoreLimits>,
+    on_tick: TypedFunc<f64, i32>,
+    reset: TypedFunc<(), ()>,
+    fault: Option<PluginError>,
+}
+
+impl WasmStrategy {
+    pub fn load(path: &Path, side: Side) -> Result<Self, PluginError> {
+        Self::from_wasm(&read_plugin(path)?, side)
+    }
+
+    pub fn from_wasm(bytes: &[u8], side: Side) -> Result<Self, PluginError> {
+        let module = compile(bytes)?;
+        inspect_module(&module)?;
+        Self::instantiate(&module, side)
+    }
+
+    /// Instantiates a module that already passed [`inspect_wasm`]'s checks.
+    fn instantiate(module: &Module, side: Side) -> Result<Self, PluginError> {
+        let engine = module.engine();
+        let instantiate = |error: wasmi::Error| PluginError::Instantiate(error.to_string());
+        let mut store = Store::new(
+            engine,
+            StoreLimitsBuilder::new()
+                .memory_size(PLUGIN_MAX_MEMORY_BYTES)
+                .memories(1)
+                .tables(1)
+                .instances(1)
+                .trap_on_grow_failure(true)
+                .build(),
+        );
+        store.limiter(|limits| limits);
+        store
+            .set_fuel(PLUGIN_FUEL_PER_CALL)
+            .map_err(|error| PluginError::Instantiate(error.to_string()))?;
+        let instance = Linker::<StoreLimits>::new(engine)
+            .instantiate(&mut store, module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(instantiate)?;
+        let warmup_bars = instance
+            .get_typed_func::<(), i32>(&store, "warmup_bars")
+            .map_err(instantiate)?;
+        let on_tick = instance
+            .get_typed_func::<f64, i32>(&store, "on_tick")
+            .map_err(instantiate)?;
+        let reset = instance
+            .get_typed_func::<(), ()>(&store, "reset")
+            .map_err(instantiate)?;
+        let warmup_bars = call(&mut store, "warmup_bars", |store| {
+            warmup_bars.call(store, ())
+        })?;
+        Ok(Self {
+            side,
+            warmup_bars: usize::try_from(warmup_bars).unwrap_or(0).max(1),
+            store,
+            on_tick,
+            reset,
+            fault: None,
+        })
+    }
+}
+
+/// Runs one export call on a fresh fuel allowance.
+fn call<T>(
+    store: &mut Store<StoreLimits>,
+    export: &'static str,
+    run: impl FnOnce(&mut Store<StoreLimits>) -> Result<T, wasmi::Error>,
+) -> Result<T, PluginError> {
+    let trap = |message: String| PluginError::Trap { export, message };
+    store
+        .set_fuel(PLUGIN_FUEL_PER_CALL)
+        .map_err(|error| trap(error.to_string()))?;
+    run(store).map_err(|error| trap(error.to_string()))
+}
+
+impl Strategy for WasmStrategy {
+    fn side(&self) -> Side {
+        self.side
+    }
+
+    fn warmup_bars(&self) -> usize {
+        self.warmup_bars
+    }
+
+    fn on_tick(&mut self, close: f64) -> Option<StrategySignal> {
+        if self.fault.is_some() {
+            return None;
+        }
+        let on_tick = self.on_tick;
+        let code = call(&mut self.store, "on_tick", |store| {
+            on_tick.call(store, close)
+        });
+        match code {
+            Ok(-1) => None,
+            Ok(0) => Some(StrategySignal::Hold),
+            Ok(1) => Some(StrategySignal::Enter),
+            Ok(2) => Some(StrategySignal::Exit),
+            Ok(other) => {
+                self.fault = Some(PluginError::UnknownSignal(other));
+                None
+            }
+            Err(error) => {
+                self.fault = Some(error);
+                None
+            }
+        }
+    }
+
+    /// Plugins keep their indicators to themselves.
+    fn indicators(&self) -> BTreeMap<String, f64> {
+        BTreeMap::new()
+    }
+
+    fn reset(&mut self) {
+        let reset = self.reset;
+        self.fault = call(&mut self.store, "reset", |store| reset.call(store, ())).err();
+    }
+
+    fn fault(&self) -> Option<String> {
+        self.fault.as_ref().map(PluginError::to_string)
+    }
+}
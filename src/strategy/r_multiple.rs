@@ -0,0 +1,197 @@
+use crate::domain::position::PositionSnapshot;
+
+/// Upper edges of the R distribution buckets; the last bucket is open.
+///
+/// Example:
+/// - `-1.4R` -> `<-1R`, `0.3R` -> `0..1R`, `3.2R` -> `>=3R`
+pub const R_BUCKET_EDGES: [f64; 5] = [-1.0, 0.0, 1.0, 2.0, 3.0];
+const R_BUCKET_LABELS: [&str; 6] = ["<-1R", "-1..0R", "0..1R", "1..2R", "2..3R", ">=3R"];
+
+/// Outcome of a trade in units of what its initial stop put at risk.
+///
+/// Returns `None` when the trade had no risk to measure against.
+///
+/// Example:
+/// - net `+30` on `20` at risk -> `Some(1.5)`
+pub fn r_multiple(pnl_usdt: f64, initial_risk_usdt: f64) -> Option<f64> {
+    (initial_risk_usdt.is_finite() && initial_risk_usdt > f64::EPSILON)
+        .then(|| pnl_usdt / initial_risk_usdt)
+}
+
+/// Closed trades of one strategy measured in R.
+///
+/// Expectancy is `win_rate * average_win_r - loss_rate * average_loss_r`,
+/// which matches `average_r`; the parts show whether it comes from the hit
+/// rate or the payoff.
+///
+/// Example:
+/// - `+2R`, `-1R`, `+0.5R`, `-1R` -> `average_r=0.13`, win rate `50%`,
+///   `average_win_r=1.25`, `average_loss_r=1.00`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RMultipleStats {
+    pub trades: u32,
+    pub wins: u32,
+    /// Closed trades left out because no initial stop was known.
+    pub unrisked: u32,
+    pub total_r: f64,
+    win_r: f64,
+    loss_r: f64,
+    pub best_r: Option<f64>,
+    pub worst_r: Option<f64>,
+    buckets: [u32; 6],
+}
+
+impl RMultipleStats {
+    pub fn from_r_values(values: impl IntoIterator<Item = Option<f64>>) -> Self {
+        let mut stats = Self::default();
+        for value in values {
+            match value {
+                Some(r) => stats.record(r),
+                None => stats.unrisked += 1,
+            }
+        }
+        stats
+    }
+
+    pub fn record(&mut self, r: f64) {
+        self.trades += 1;
+        self.total_r += r;
+        if r > 0.0 {
+            self.wins += 1;
+            self.win_r += r;
+        } else {
+            self.loss_r -= r;
+        }
+        self.best_r = Some(self.best_r.map_or(r, |best| best.max(r)));
+        self.worst_r = Some(self.worst_r.map_or(r, |worst| worst.min(r)));
+        let bucket = R_BUCKET_EDGES
+            .iter()
+            .position(|edge| r < *edge)
+            .unwrap_or(R_BUCKET_EDGES.len());
+        self.buckets[bucket] += 1;
+    }
+
+    pub fn average_r(&self) -> Option<f64> {
+        (self.trades > 0).then(|| self.total_r / f64::from(self.trades))
+    }
+
+    pub fn average_win_r(&self) -> Option<f64> {
+        (self.wins > 0).then(|| self.win_r / f64::from(self.wins))
+    }
+
+    /// Average loss as a positive R, e.g. `1.0` for full stop-outs.
+    pub fn average_loss_r(&self) -> Option<f64> {
+        let losses = self.trades - self.wins;
+        (losses > 0).then(|| self.loss_r / f64::from(losses))
+    }
+
+    pub fn expectancy_r(&self) -> Option<f64> {
+        if self.trades == 0 {
+            return None;
+        }
+        let trades = f64::from(self.trades);
+        let win_rate = f64::from(self.wins) / trades;
+        Some(
+            win_rate * self.average_win_r().unwrap_or_default()
+                - (1.0 - win_rate) * self.average_loss_r().unwrap_or_default(),
+        )
+    }
+
+    /// Trade count per bucket, lowest first, e.g. `("-1..0R", 2)`.
+    pub fn distribution(&self) -> Vec<(&'static str, u32)> {
+        R_BUCKET_LABELS.into_iter().zip(self.buckets).collect()
+    }
+
+    /// Example:
+    /// - `trades=4 avg=0.13R expectancy=0.13R win=1.25R loss=1.00R`
+    /// - `trades=0` before the first measured trade
+    pub fn summary(&self) -> String {
+        let Some(average) = self.average_r() else {
+            return format!("trades=0 unrisked={}", self.unrisked);
+        };
+        let r = |value: Option<f64>| value.map_or("-".to_string(), |value| format!("{value:.2}R"));
+        format!(
+            "trades={} avg={} expectancy={} win={} loss={} best={} worst={} unrisked={}",
+            self.trades,
+            r(Some(average)),
+            r(self.expectancy_r()),
+            r(self.average_win_r()),
+            r(self.average_loss_r()),
+            r(self.best_r),
+            r(self.worst_r),
+            self.unrisked,
+        )
+    }
+
+    /// Example:
+    /// - `<-1R=0 -1..0R=2 0..1R=1 1..2R=0 2..3R=1 >=3R=0`
+    pub fn distribution_label(&self) -> String {
+        self.distribution()
+            .into_iter()
+            .map(|(bucket, trades)| format!("{bucket}={trades}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Live R tracking for a watch: the initial risk is fixed by the first
+/// protective stop seen while the position is open, and the round trip is
+/// scored once it goes flat.
+///
+/// Example:
+/// - opens `+0.2 @ 50000` with the OCO stop at `49000` -> risk `1000`/unit
+/// - flat at mark `52000` -> `+2.00R`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RMultipleTracker {
+    pub stats: RMultipleStats,
+    last_signed_qty: f64,
+    last_entry_price: Option<f64>,
+    /// Per unit, `|entry - initial stop|`.
+    initial_risk_per_unit: Option<f64>,
+}
+
+impl RMultipleTracker {
+    pub fn initial_risk_per_unit(&self) -> Option<f64> {
+        self.initial_risk_per_unit
+    }
+
+    /// Folds the latest position in and returns the R of a round trip that
+    /// just went flat; `Some(None)` when it closed without a known stop.
+    pub fn observe(
+        &mut self,
+        position: Option<&PositionSnapshot>,
+        mark_price: Option<f64>,
+        protective_stop: Option<f64>,
+    ) -> Option<Option<f64>> {
+        let signed_qty = position.map_or(0.0, |position| position.signed_qty);
+        let entry_price = position.and_then(|position| position.entry_price);
+        let was_open = self.last_signed_qty.abs() > f64::EPSILON;
+        let closed = was_open && signed_qty.abs() <= f64::EPSILON;
+        let scored = match (closed, self.last_entry_price, mark_price) {
+            (true, Some(entry), Some(exit)) => {
+                let r = self.initial_risk_per_unit.and_then(|risk| {
+                    r_multiple((exit - entry) * self.last_signed_qty.signum(), risk)
+                });
+                match r {
+                    Some(r) => self.stats.record(r),
+                    None => self.stats.unrisked += 1,
+                }
+                Some(r)
+            }
+            _ => None,
+        };
+        if signed_qty.abs() <= f64::EPSILON || (was_open && signed_qty * self.last_signed_qty < 0.0)
+        {
+            self.initial_risk_per_unit = None;
+        }
+        if signed_qty.abs() > f64::EPSILON && self.initial_risk_per_unit.is_none() {
+            self.initial_risk_per_unit = entry_price
+                .zip(protective_stop)
+                .map(|(entry, stop)| (entry - stop).abs())
+                .filter(|risk| *risk > f64::EPSILON);
+        }
+        self.last_signed_qty = signed_qty;
+        self.last_entry_price = entry_price;
+        scored
+    }
+}
@@ -0,0 +1,103 @@
+use crate::domain::position::PositionSnapshot;
+
+/// Used for whichever half of `--ramp-start`/`--ramp-trades` is left out.
+pub const DEFAULT_RAMP_START_FRACTION: f64 = 0.25;
+pub const DEFAULT_RAMP_TRADES: u32 = 5;
+
+/// Order-size ramp for a watch whose configuration has not traded yet.
+///
+/// The size fraction climbs linearly from `start_fraction` to `1.0` over
+/// `full_after_trades` round trips that close at break-even or better;
+/// losing trades leave it where it is.
+///
+/// Example:
+/// - `0.25:3` -> `0.25`, then `0.50`, `0.75` and `1.00` after each winner
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeRamp {
+    pub start_fraction: f64,
+    pub full_after_trades: u32,
+    pub qualifying_trades: u32,
+    last_signed_qty: f64,
+    last_entry_price: Option<f64>,
+}
+
+impl SizeRamp {
+    pub fn new(start_fraction: f64, full_after_trades: u32) -> Self {
+        Self {
+            start_fraction: start_fraction.clamp(0.0, 1.0),
+            full_after_trades,
+            qualifying_trades: 0,
+            last_signed_qty: 0.0,
+            last_entry_price: None,
+        }
+    }
+
+    /// Reads:
+    /// - `SANDBOX_QUANT_STRATEGY_RAMP` (e.g. `0.25:5`; unset or `off` disables)
+    pub fn from_env() -> Option<Self> {
+        std::env::var("SANDBOX_QUANT_STRATEGY_RAMP")
+            .ok()
+            .and_then(|raw| Self::parse(&raw))
+    }
+
+    /// Example:
+    /// - `0.25:5` -> start at 25%, full size after 5 qualifying trades
+    /// - `1:5`, `0.25:0`, `off` -> `None`, nothing to ramp
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (fraction, trades) = raw.trim().split_once(':')?;
+        let start_fraction = fraction.trim().parse::<f64>().ok()?;
+        let full_after_trades = trades.trim().parse::<u32>().ok()?;
+        ((0.0..1.0).contains(&start_fraction) && full_after_trades > 0)
+            .then(|| Self::new(start_fraction, full_after_trades))
+    }
+
+    pub fn fraction(&self) -> f64 {
+        if self.is_complete() {
+            return 1.0;
+        }
+        let progress = f64::from(self.qualifying_trades) / f64::from(self.full_after_trades);
+        self.start_fraction + (1.0 - self.start_fraction) * progress
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.qualifying_trades >= self.full_after_trades
+    }
+
+    /// Starts over from `start_fraction`, e.g. when a watch is re-enabled.
+    pub fn restart(&mut self) {
+        self.qualifying_trades = 0;
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "{}/{} size_fraction={:.2}",
+            self.qualifying_trades.min(self.full_after_trades),
+            self.full_after_trades,
+            self.fraction()
+        )
+    }
+
+    /// Folds the latest position in and returns the PnL of a round trip
+    /// that just went flat, counting it when it did not lose.
+    ///
+    /// Example:
+    /// - last `+0.2 @ 50000`, now flat, mark `50100` -> `Some(20.0)`, counted
+    pub fn observe(
+        &mut self,
+        position: Option<&PositionSnapshot>,
+        mark_price: Option<f64>,
+    ) -> Option<f64> {
+        let signed_qty = position.map_or(0.0, |position| position.signed_qty);
+        let closed = self.last_signed_qty.abs() > f64::EPSILON && signed_qty.abs() <= f64::EPSILON;
+        let pnl = match (closed, self.last_entry_price, mark_price) {
+            (true, Some(entry), Some(exit)) => Some((exit - entry) * self.last_signed_qty),
+            _ => None,
+        };
+        if pnl.is_some_and(|pnl| pnl >= 0.0) && !self.is_complete() {
+            self.qualifying_trades += 1;
+        }
+        self.last_signed_qty = signed_qty;
+        self.last_entry_price = position.and_then(|position| position.entry_price);
+        pnl
+    }
+}
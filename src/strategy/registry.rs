@@ -0,0 +1,168 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::domain::position::Side;
+use crate::strategy::model::StrategyTemplate;
+
+/// What a bar-driven strategy asks for once a candle closes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategySignal {
+    Hold,
+    /// Open a position on the strategy's side.
+    Enter,
+    /// Close the open position, if any.
+    Exit,
+}
+
+/// A strategy fed one signal close per candle.
+///
+/// The backtest runner, or the live loop for script watches, owns sizing,
+/// stops, fills and the entry guards; a strategy only turns closes into
+/// signals.
+pub trait Strategy: Send {
+    /// Side its entries take.
+    fn side(&self) -> Side;
+
+    /// Candles needed before the first signal; shorter datasets are
+    /// refused up front.
+    fn warmup_bars(&self) -> usize;
+
+    /// `None` while warming up.
+    fn on_tick(&mut self, close: f64) -> Option<StrategySignal>;
+
+    /// Indicator values as of the last tick, e.g. `fast_sma`, `slow_sma`.
+    fn indicators(&self) -> BTreeMap<String, f64>;
+
+    /// Forgets every tick, as if newly created.
+    fn reset(&mut self);
+
+    /// Why the strategy stopped evaluating, e.g. a plugin that trapped;
+    /// it signals nothing while this is set.
+    fn fault(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Long entries on a fast-over-slow SMA cross, exits on the cross back;
+/// the mirror for shorts.
+///
+/// Example:
+/// - `SmaCross::new(Side::Buy, 20, 50)`: enter when SMA20 crosses above
+///   SMA50, exit when it crosses back below
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmaCross {
+    side: Side,
+    fast_window: usize,
+    slow_window: usize,
+    /// The last `slow_window + 1` closes, oldest first.
+    closes: VecDeque<f64>,
+}
+
+impl SmaCross {
+    pub fn new(side: Side, fast_window: usize, slow_window: usize) -> Self {
+        Self {
+            side,
+            fast_window: fast_window.max(1),
+            slow_window: slow_window.max(1),
+            closes: VecDeque::new(),
+        }
+    }
+
+    /// Mean of the `window` closes ending `back` ticks before the latest.
+    fn mean(&self, window: usize, back: usize) -> Option<f64> {
+        let end = self.closes.len().checked_sub(back)?;
+        let start = end.checked_sub(window)?;
+        Some(self.closes.range(start..end).sum::<f64>() / window as f64)
+    }
+}
+
+impl Strategy for SmaCross {
+    fn side(&self) -> Side {
+        self.side
+    }
+
+    fn warmup_bars(&self) -> usize {
+        self.fast_window.max(self.slow_window)
+    }
+
+    fn on_tick(&mut self, close: f64) -> Option<StrategySignal> {
+        if self.closes.len() > self.warmup_bars() {
+            self.closes.pop_front();
+        }
+        self.closes.push_back(close);
+        let fast_now = self.mean(self.fast_window, 0)?;
+        let slow_now = self.mean(self.slow_window, 0)?;
+        let fast_prev = self.mean(self.fast_window, 1)?;
+        let slow_prev = self.mean(self.slow_window, 1)?;
+        let cross_up = fast_prev <= slow_prev && fast_now > slow_now;
+        let cross_down = fast_prev >= slow_prev && fast_now < slow_now;
+        let (enter, exit) = match self.side {
+            Side::Buy => (cross_up, cross_down),
+            Side::Sell => (cross_down, cross_up),
+        };
+        Some(if enter {
+            StrategySignal::Enter
+        } else if exit {
+            StrategySignal::Exit
+        } else {
+            StrategySignal::Hold
+        })
+    }
+
+    fn indicators(&self) -> BTreeMap<String, f64> {
+        [
+            ("fast_sma", self.mean(self.fast_window, 0)),
+            ("slow_sma", self.mean(self.slow_window, 0)),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?)))
+        .collect()
+    }
+
+    fn reset(&mut self) {
+        self.closes.clear();
+    }
+}
+
+pub type StrategyFactory = fn() -> Box<dyn Strategy>;
+
+/// Bar-driven strategies by template; adding one is a single `register`.
+///
+/// `liquidation-breakdown-short` reads liquidation and book ticker events
+/// rather than candles, so it is not in here.
+#[derive(Debug, Clone, Default)]
+pub struct StrategyRegistry {
+    factories: BTreeMap<StrategyTemplate, StrategyFactory>,
+}
+
+impl StrategyRegistry {
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        registry.register(StrategyTemplate::PriceSmaCrossLong, || {
+            Box::new(SmaCross::new(Side::Buy, 20, 50))
+        });
+        registry.register(StrategyTemplate::PriceSmaCrossShort, || {
+            Box::new(SmaCross::new(Side::Sell, 20, 50))
+        });
+        registry.register(StrategyTemplate::PriceSmaCrossLongFast, || {
+            Box::new(SmaCross::new(Side::Buy, 9, 21))
+        });
+        registry.register(StrategyTemplate::PriceSmaCrossShortFast, || {
+            Box::new(SmaCross::new(Side::Sell, 9, 21))
+        });
+        registry
+    }
+
+    /// Replaces any strategy already registered for `template`.
+    pub fn register(&mut self, template: StrategyTemplate, factory: StrategyFactory) {
+        self.factories.insert(template, factory);
+    }
+
+    /// A fresh instance; `None` when `template` is not bar-driven.
+    pub fn create(&self, template: StrategyTemplate) -> Option<Box<dyn Strategy>> {
+        self.factories.get(&template).map(|factory| factory())
+    }
+
+    pub fn templates(&self) -> impl Iterator<Item = StrategyTemplate> + '_ {
+        self.factories.keys().copied()
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use rhai::{Engine, Scope, AST};
+
+use crate::domain::position::Side;
+use crate::error::script_error::ScriptError;
+use crate::strategy::registry::{Strategy, StrategySignal};
+
+/// Closes a script can look back over; older ones are dropped.
+pub const SCRIPT_HISTORY_BARS: usize = 1_000;
+
+/// Rhai operations one `enter()` or `exit()` call may run, so a script
+/// stuck in a loop fails instead of stalling the caller.
+pub const SCRIPT_MAX_OPERATIONS: u64 = 100_000;
+
+/// A bar strategy written as a Rhai script, so a rule can change without a
+/// rebuild.
+///
+/// The script defines `fn enter()` and `fn exit()`, each returning a bool,
+/// and may define `fn warmup()` returning the closes needed before the
+/// first signal (default `1`); the side comes from the `script-long` or
+/// `script-short` template. A signal fires on the close its function
+/// turns true.
+///
+/// Price helpers take a bar count and an optional number of bars back, and
+/// answer `NaN` until enough closes have arrived:
+/// - `close()`, `close(1)`: the latest close, the one before it
+/// - `sma(20)`, `sma(20, 1)`: mean close of the last 20 bars, a bar earlier
+/// - `highest(20)`, `lowest(20)`: highest and lowest close of 20 bars
+/// - `bars()`: closes seen so far
+///
+/// Example:
+/// - `fn enter() { sma(9) > sma(21) && sma(9, 1) <= sma(21, 1) }`
+/// - `fn exit() { close() < lowest(10, 1) }`
+pub struct ScriptStrategy {
+    side: Side,
+    engine: Engine,
+    ast: AST,
+    warmup_bars: usize,
+    history: Arc<Mutex<ScriptHistory>>,
+    was_entering: bool,
+    was_exiting: bool,
+    fault: Option<ScriptError>,
+}
+
+/// Closes the helpers read and the values they answered on the last close.
+#[derive(Debug, Default)]
+struct ScriptHistory {
+    closes: VecDeque<f64>,
+    indicators: BTreeMap<String, f64>,
+}
+
+impl ScriptHistory {
+    /// The `bars` closes ending `back` bars before the latest, oldest first.
+    fn window(&self, bars: i64, back: i64) -> Option<Vec<f64>> {
+        let bars = usize::try_from(bars).ok().filter(|bars| *bars > 0)?;
+        let back = usize::try_from(back).ok()?;
+        let end = self.closes.len().checked_sub(back)?;
+        let start = end.checked_sub(bars)?;
+        Some(self.closes.range(start..end).copied().collect())
+    }
+
+    fn record(&mut self, label: String, value: f64) -> f64 {
+        if value.is_finite() {
+            self.indicators.insert(label, value);
+        }
+        value
+    }
+}
+
+impl ScriptStrategy {
+    pub fn load(path: &Path, side: Side) -> Result<Self, ScriptError> {
+        let source = std::fs::read_to_string(path).map_err(|error| ScriptError::Read {
+            path: path.display().to_string(),
+            message: error.to_string(),
+        })?;
+        Self::parse(&source, side)
+    }
+
+    pub fn parse(source: &str, side: Side) -> Result<Self, ScriptError> {
+        let history = Arc::new(Mutex::new(ScriptHistory::default()));
+        let engine = script_engine(&history);
+        let ast = engine.compile(source).map_err(|error| ScriptError::Parse {
+            line: error.1.line().unwrap_or(0),
+            message: error.0.to_string(),
+        })?;
+        for rule in ["enter", "exit"] {
+            if !ast
+                .iter_functions()
+                .any(|function| function.name == rule && function.params.is_empty())
+            {
+                return Err(ScriptError::MissingRule(rule));
+            }
+        }
+        let warmup_bars = if ast
+            .iter_functions()
+            .any(|function| function.name == "warmup" && function.params.is_empty())
+        {
+            let bars = call_rule::<i64>(&engine, &ast, "warmup")?;
+            usize::try_from(bars)
+                .ok()
+                .filter(|bars| (1..=SCRIPT_HISTORY_BARS).contains(bars))
+                .ok_or_else(|| {
+                    ScriptError::Runtime(format!(
+                        "warmup() must be between 1 and {SCRIPT_HISTORY_BARS}, got {bars}"
+                    ))
+                })?
+        } else {
+            1
+        };
+        Ok(Self {
+            side,
+            engine,
+            ast,
+            warmup_bars,
+            history,
+            was_entering: false,
+            was_exiting: false,
+            fault: None,
+        })
+    }
+
+    fn evaluate(&mut self) -> Result<StrategySignal, ScriptError> {
+        let entering = call_rule::<bool>(&self.engine, &self.ast, "enter")?;
+        let exiting = call_rule::<bool>(&self.engine, &self.ast, "exit")?;
+        let signal = if entering && !self.was_entering {
+            StrategySignal::Enter
+        } else if exiting && !self.was_exiting {
+            StrategySignal::Exit
+        } else {
+            StrategySignal::Hold
+        };
+        self.was_entering = entering;
+        self.was_exiting = exiting;
+        Ok(signal)
+    }
+}
+
+/// An engine that can only compute on the closes it is handed: no
+/// modules, no `eval`, no printing, and bounded work per call.
+fn script_engine(history: &Arc<Mutex<ScriptHistory>>) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(SCRIPT_MAX_OPERATIONS)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(1_024)
+        .set_max_array_size(SCRIPT_HISTORY_BARS)
+        .set_max_map_size(256)
+        .disable_symbol("eval")
+        .on_print(|_| {})
+        .on_debug(|_, _, _| {});
+
+    let closes = Arc::clone(history);
+    engine.register_fn("bars", move || {
+        closes.lock().expect("lock script history").closes.len() as i64
+    });
+    let closes = Arc::clone(history);
+    engine.register_fn("close", move || close(&closes, 0));
+    let closes = Arc::clone(history);
+    engine.register_fn("close", move |back: i64| close(&closes, back));
+    let helpers: [(&'static str, WindowFold); 3] = [
+        ("sma", |window| {
+            window.iter().sum::<f64>() / window.len() as f64
+        }),
+        ("highest", |window| {
+            window.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+        }),
+        ("lowest", |window| {
+            window.iter().copied().fold(f64::INFINITY, f64::min)
+        }),
+    ];
+    for (name, fold) in helpers {
+        let closes = Arc::clone(history);
+        engine.register_fn(name, move |bars: i64| {
+            window_helper(&closes, name, fold, bars, 0)
+        });
+        let closes = Arc::clone(history);
+        engine.register_fn(name, move |bars: i64, back: i64| {
+            window_helper(&closes, name, fold, bars, back)
+        });
+    }
+    engine
+}
+
+/// Reduces a window of closes, oldest first, to one helper value.
+type WindowFold = fn(&[f64]) -> f64;
+
+fn close(history: &Mutex<ScriptHistory>, back: i64) -> f64 {
+    let mut history = history.lock().expect("lock script history");
+    let value = history.window(1, back).map_or(f64::NAN, |window| window[0]);
+    let label = match back {
+        0 => "close".to_string(),
+        back => format!("close({back})"),
+    };
+    history.record(label, value)
+}
+
+fn window_helper(
+    history: &Mutex<ScriptHistory>,
+    name: &str,
+    fold: WindowFold,
+    bars: i64,
+    back: i64,
+) -> f64 {
+    let mut history = history.lock().expect("lock script history");
+    let value = history
+        .window(bars, back)
+        .map_or(f64::NAN, |window| fold(&window));
+    let label = match back {
+        0 => format!("{name}({bars})"),
+        back => format!("{name}({bars}, {back})"),
+    };
+    history.record(label, value)
+}
+
+fn call_rule<T: Clone + Send + Sync + 'static>(
+    engine: &Engine,
+    ast: &AST,
+    rule: &str,
+) -> Result<T, ScriptError> {
+    engine
+        .call_fn::<T>(&mut Scope::new(), ast, rule, ())
+        .map_err(|error| ScriptError::Runtime(format!("{rule}(): {error}")))
+}
+
+impl Strategy for ScriptStrategy {
+    fn side(&self) -> Side {
+        self.side
+    }
+
+    fn warmup_bars(&self) -> usize {
+        self.warmup_bars
+    }
+
+    fn on_tick(&mut self, close: f64) -> Option<StrategySignal> {
+        let seen = {
+            let mut history = self.history.lock().expect("lock script history");
+            if history.closes.len() == SCRIPT_HISTORY_BARS {
+                history.closes.pop_front();
+            }
+            history.closes.push_back(close);
+            history.indicators.clear();
+            history.closes.len()
+        };
+        if self.fault.is_some() || seen < self.warmup_bars {
+            return None;
+        }
+        match self.evaluate() {
+            Ok(signal) => Some(signal),
+            Err(error) => {
+                self.fault = Some(error);
+                None
+            }
+        }
+    }
+
+    /// Every helper value the last close's `enter()` and `exit()` read,
+    /// e.g. `sma(9)` or `highest(20, 1)`.
+    fn indicators(&self) -> BTreeMap<String, f64> {
+        self.history
+            .lock()
+            .expect("lock script history")
+            .indicators
+            .clone()
+    }
+
+    fn reset(&mut self) {
+        *self.history.lock().expect("lock script history") = ScriptHistory::default();
+        self.was_entering = false;
+        self.was_exiting = false;
+        self.fault = None;
+    }
+
+    fn fault(&self) -> Option<String> {
+        self.fault.as_ref().map(ScriptError::to_string)
+    }
+}
@@ -1,18 +1,47 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 
 use crate::app::bootstrap::BinanceMode;
 use crate::domain::instrument::Instrument;
+use crate::domain::position::{PositionSnapshot, Side};
 use crate::error::strategy_error::StrategyError;
+use crate::strategy::adaptive::Adaptation;
+use crate::strategy::allocation::{
+    leaderboard, AutoAllocation, LeaderboardEntry, MIN_CAPITAL_SHIFT_USDT,
+};
 use crate::strategy::command::StrategyStartConfig;
+use crate::strategy::filter::StrategyFilter;
+use crate::strategy::group::{group_stats, StrategyGroupStats};
+use crate::strategy::kpi::{KpiTargets, StrategyKpi};
 use crate::strategy::model::{StrategyTemplate, StrategyWatch, StrategyWatchState};
+use crate::strategy::overtrading::{OvertradingGuard, OvertradingLimit, TradeCluster};
+use crate::strategy::trace::{StrategyTrace, StrategyTraceEntry};
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct StrategyStore {
     next_watch_id: u64,
     active: BTreeMap<u64, StrategyWatch>,
     history: Vec<StrategyWatch>,
+    traces: BTreeMap<u64, StrategyTrace>,
+    /// Per-watch trace cap; `None` uses `DEFAULT_TRACE_CAPACITY`.
+    trace_capacity: Option<usize>,
+    /// Daily capital reweighting; `None` leaves allocations where they are.
+    pub auto_allocation: Option<AutoAllocation>,
+    /// Pauses a watch after a burst of closed trades; `None` is off.
+    pub overtrading: Option<OvertradingLimit>,
+    overtrading_guards: BTreeMap<u64, OvertradingGuard>,
+    /// Watches stopped with `flatten` whose close has not filled yet.
+    flatten_stops: BTreeSet<u64>,
+}
+
+/// Capital moved into (`delta_usdt > 0`) or out of one watch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapitalShift {
+    pub watch_id: u64,
+    pub from_weight: f64,
+    pub to_weight: f64,
+    pub delta_usdt: f64,
 }
 
 impl StrategyStore {
@@ -27,7 +56,12 @@ impl StrategyStore {
             watch.mode == mode
                 && watch.template == template
                 && watch.instrument == instrument
-                && watch.state == StrategyWatchState::Armed
+                && matches!(
+                    watch.state,
+                    StrategyWatchState::Armed
+                        | StrategyWatchState::Paused
+                        | StrategyWatchState::Disabled
+                )
         }) {
             return Err(StrategyError::DuplicateWatch {
                 template: template.slug(),
@@ -67,6 +101,24 @@ impl StrategyStore {
         &mut self,
         mode: BinanceMode,
         watch_id: u64,
+    ) -> Result<StrategyWatch, StrategyError> {
+        self.finish_watch(mode, watch_id, StrategyWatchState::Stopped)
+    }
+
+    /// Moves a watch whose strategy can no longer run into history.
+    pub fn fail_watch(
+        &mut self,
+        mode: BinanceMode,
+        watch_id: u64,
+    ) -> Result<StrategyWatch, StrategyError> {
+        self.finish_watch(mode, watch_id, StrategyWatchState::Failed)
+    }
+
+    fn finish_watch(
+        &mut self,
+        mode: BinanceMode,
+        watch_id: u64,
+        state: StrategyWatchState,
     ) -> Result<StrategyWatch, StrategyError> {
         if self.active.get(&watch_id).map(|watch| watch.mode) != Some(mode) {
             return Err(StrategyError::WatchNotFound(watch_id));
@@ -75,12 +127,464 @@ impl StrategyStore {
             .active
             .remove(&watch_id)
             .ok_or(StrategyError::WatchNotFound(watch_id))?;
-        watch.state = StrategyWatchState::Stopped;
+        watch.state = state;
         watch.updated_at = Utc::now();
+        self.overtrading_guards.remove(&watch_id);
+        self.traces.remove(&watch_id);
+        self.flatten_stops.remove(&watch_id);
         self.history.push(watch.clone());
         Ok(watch)
     }
 
+    /// Counts a closed trade against the overtrading limit and pauses the
+    /// watch when it completes a cluster, returning that cluster.
+    ///
+    /// Example:
+    /// - limit `3:600:1800`, trades close at `12:00`, `12:04`, `12:08`
+    /// - the watch is `Paused` until `12:38`, then re-armed by `apply_degraded`
+    pub fn record_trade(
+        &mut self,
+        mode: BinanceMode,
+        watch_id: u64,
+        time_ms: i64,
+    ) -> Option<(StrategyWatch, TradeCluster)> {
+        let limit = self.overtrading?;
+        let watch = self
+            .active
+            .get_mut(&watch_id)
+            .filter(|watch| watch.mode == mode)?;
+        let cluster = self
+            .overtrading_guards
+            .entry(watch_id)
+            .or_insert_with(|| limit.guard())
+            .record_entry(time_ms)?;
+        if watch.state == StrategyWatchState::Armed {
+            watch.state = StrategyWatchState::Paused;
+            watch.updated_at = Utc::now();
+        }
+        Some((watch.clone(), cluster))
+    }
+
+    /// Holds the stop of an active watch until its position is flat;
+    /// `false` when it was already held.
+    pub fn hold_stop_until_flat(&mut self, watch_id: u64) -> bool {
+        self.flatten_stops.insert(watch_id)
+    }
+
+    /// Watches whose stop waits for their flatten to fill.
+    pub fn flatten_stops(&self) -> Vec<u64> {
+        self.flatten_stops.iter().copied().collect()
+    }
+
+    /// The watch's overtrading guard, once it has recorded a trade.
+    pub fn overtrading_guard(&self, watch_id: u64) -> Option<&OvertradingGuard> {
+        self.overtrading_guards.get(&watch_id)
+    }
+
+    /// Turns the per-watch debug trace on or off for an active watch.
+    ///
+    /// The buffer is kept after turning debug off so it can still be dumped;
+    /// stopping the watch drops it.
+    pub fn set_debug(
+        &mut self,
+        mode: BinanceMode,
+        watch_id: u64,
+        enabled: bool,
+    ) -> Result<StrategyWatch, StrategyError> {
+        let watch = self
+            .active
+            .get_mut(&watch_id)
+            .filter(|watch| watch.mode == mode)
+            .ok_or(StrategyError::WatchNotFound(watch_id))?;
+        watch.debug = enabled;
+        watch.updated_at = Utc::now();
+        if enabled {
+            let capacity = self.trace_capacity;
+            self.traces.entry(watch_id).or_insert_with(|| {
+                capacity.map_or_else(StrategyTrace::default, StrategyTrace::new)
+            });
+        }
+        Ok(watch.clone())
+    }
+
+    /// Moves an active watch into `group`, or clears it with `None`.
+    pub fn set_group(
+        &mut self,
+        mode: BinanceMode,
+        watch_id: u64,
+        group: Option<String>,
+    ) -> Result<StrategyWatch, StrategyError> {
+        let watch = self
+            .active
+            .get_mut(&watch_id)
+            .filter(|watch| watch.mode == mode)
+            .ok_or(StrategyError::WatchNotFound(watch_id))?;
+        watch.group = group;
+        watch.updated_at = Utc::now();
+        Ok(watch.clone())
+    }
+
+    /// Sets the KPI targets of an active watch, or clears them with `None`.
+    ///
+    /// Replacing targets keeps the stats counted so far; new targets start
+    /// counting from now.
+    pub fn set_kpi(
+        &mut self,
+        mode: BinanceMode,
+        watch_id: u64,
+        targets: Option<KpiTargets>,
+    ) -> Result<StrategyWatch, StrategyError> {
+        let watch = self
+            .active
+            .get_mut(&watch_id)
+            .filter(|watch| watch.mode == mode)
+            .ok_or(StrategyError::WatchNotFound(watch_id))?;
+        let now = Utc::now();
+        watch.kpi = match (targets, watch.kpi.take()) {
+            (Some(targets), Some(mut kpi)) => {
+                kpi.targets = targets;
+                Some(kpi)
+            }
+            (Some(targets), None) => Some(StrategyKpi::new(targets, now)),
+            (None, _) => None,
+        };
+        watch.updated_at = now;
+        Ok(watch.clone())
+    }
+
+    /// Disables an armed or paused watch, or re-arms a disabled one.
+    ///
+    /// Returns `None` when the watch is already in the requested state; a
+    /// re-armed watch on a degraded instrument is paused on the next refresh
+    /// and restarts its size ramp.
+    pub fn set_enabled(
+        &mut self,
+        mode: BinanceMode,
+        watch_id: u64,
+        enabled: bool,
+    ) -> Result<Option<StrategyWatch>, StrategyError> {
+        let watch = self
+            .active
+            .get_mut(&watch_id)
+            .filter(|watch| watch.mode == mode)
+            .ok_or(StrategyError::WatchNotFound(watch_id))?;
+        let next = match (watch.state, enabled) {
+            (StrategyWatchState::Disabled, true) => StrategyWatchState::Armed,
+            (StrategyWatchState::Armed | StrategyWatchState::Paused, false) => {
+                StrategyWatchState::Disabled
+            }
+            _ => return Ok(None),
+        };
+        if next == StrategyWatchState::Armed {
+            if let Some(ramp) = watch.ramp.as_mut() {
+                ramp.restart();
+            }
+        }
+        watch.state = next;
+        watch.updated_at = Utc::now();
+        Ok(Some(watch.clone()))
+    }
+
+    /// Ids of the active watches labelled `group`.
+    pub fn group_watch_ids(&self, mode: BinanceMode, group: &str) -> Vec<u64> {
+        self.active
+            .values()
+            .filter(|watch| watch.mode == mode && watch.group.as_deref() == Some(group))
+            .map(|watch| watch.id)
+            .collect()
+    }
+
+    pub fn matching_watch_ids(&self, mode: BinanceMode, filter: &StrategyFilter) -> Vec<u64> {
+        self.active
+            .values()
+            .filter(|watch| watch.mode == mode && filter.matches(watch))
+            .map(|watch| watch.id)
+            .collect()
+    }
+
+    pub fn group_stats(&self, mode: BinanceMode) -> Vec<StrategyGroupStats> {
+        group_stats(self.active_watches(mode))
+    }
+
+    /// Revalues the ledgers of the watches on `instrument` at `mark_price`,
+    /// returning the ids of those whose capital is exhausted.
+    pub fn update_ledgers(
+        &mut self,
+        mode: BinanceMode,
+        instrument: &Instrument,
+        mark_price: Option<f64>,
+    ) -> Vec<u64> {
+        let mut exhausted = Vec::new();
+        for watch in self
+            .active
+            .values_mut()
+            .filter(|watch| watch.mode == mode && &watch.instrument == instrument)
+        {
+            let Some(ledger) = watch.ledger.as_mut() else {
+                continue;
+            };
+            ledger.mark(mark_price);
+            if ledger.is_exhausted() {
+                exhausted.push(watch.id);
+            }
+        }
+        exhausted
+    }
+
+    /// Books a fill tagged with `watch_id` on that watch's ledger; fills of
+    /// watches without one, or no longer active, are ignored.
+    pub fn record_fill(
+        &mut self,
+        watch_id: u64,
+        side: Side,
+        qty: f64,
+        price: f64,
+        commission_usdt: f64,
+    ) {
+        if let Some(ledger) = self
+            .active
+            .get_mut(&watch_id)
+            .and_then(|watch| watch.ledger.as_mut())
+        {
+            ledger.apply_fill(side, qty, price, commission_usdt);
+        }
+    }
+
+    pub fn leaderboard(&self, mode: BinanceMode) -> Vec<LeaderboardEntry> {
+        leaderboard(self.active_watches(mode))
+    }
+
+    /// Closes the UTC day on every allocated watch and, once a day has
+    /// closed with auto allocation on, moves equity toward the leaders.
+    ///
+    /// Example:
+    /// - first call on 2024-03-02 -> opens the day, nothing moves
+    /// - first call on 2024-03-03 -> closes 2024-03-02 and rebalances
+    pub fn roll_ledger_days(&mut self, mode: BinanceMode, today: NaiveDate) -> Vec<CapitalShift> {
+        let mut closed = false;
+        for ledger in self
+            .active
+            .values_mut()
+            .filter(|watch| watch.mode == mode)
+            .filter_map(|watch| watch.ledger.as_mut())
+        {
+            closed |= ledger.roll_day(today);
+        }
+        let Some(allocation) = self.auto_allocation.filter(|_| closed) else {
+            return Vec::new();
+        };
+        let board = self.leaderboard(mode);
+        let total = board
+            .iter()
+            .map(|entry| entry.equity_usdt.max(0.0))
+            .sum::<f64>();
+        if board.len() < 2 || total <= f64::EPSILON {
+            return Vec::new();
+        }
+        let targets = allocation.target_weights(&board);
+        let mut shifts = Vec::new();
+        for entry in &board {
+            let to_weight = targets
+                .get(&entry.watch_id)
+                .copied()
+                .unwrap_or(entry.weight);
+            let delta_usdt = total * to_weight - entry.equity_usdt.max(0.0);
+            if delta_usdt.abs() < MIN_CAPITAL_SHIFT_USDT {
+                continue;
+            }
+            if let Some(ledger) = self
+                .active
+                .get_mut(&entry.watch_id)
+                .and_then(|watch| watch.ledger.as_mut())
+            {
+                ledger.reallocate(delta_usdt);
+            }
+            shifts.push(CapitalShift {
+                watch_id: entry.watch_id,
+                from_weight: entry.weight,
+                to_weight,
+                delta_usdt,
+            });
+        }
+        shifts
+    }
+
+    /// Feeds the latest position into every unfinished size ramp on
+    /// `instrument` and returns the watches whose ramp moved up.
+    pub fn update_ramps(
+        &mut self,
+        mode: BinanceMode,
+        instrument: &Instrument,
+        position: Option<&PositionSnapshot>,
+        mark_price: Option<f64>,
+    ) -> Vec<StrategyWatch> {
+        let mut advanced = Vec::new();
+        for watch in self
+            .active
+            .values_mut()
+            .filter(|watch| watch.mode == mode && &watch.instrument == instrument)
+        {
+            let Some(ramp) = watch.ramp.as_mut().filter(|ramp| !ramp.is_complete()) else {
+                continue;
+            };
+            let before = ramp.qualifying_trades;
+            ramp.observe(position, mark_price);
+            if ramp.qualifying_trades > before {
+                watch.updated_at = Utc::now();
+                advanced.push(watch.clone());
+            }
+        }
+        advanced
+    }
+
+    /// Feeds the latest position into every adaptive watch on `instrument`
+    /// and returns the watches whose parameters were just nudged.
+    pub fn update_adaptations(
+        &mut self,
+        mode: BinanceMode,
+        instrument: &Instrument,
+        position: Option<&PositionSnapshot>,
+        mark_price: Option<f64>,
+    ) -> Vec<(StrategyWatch, Adaptation)> {
+        let mut adapted = Vec::new();
+        for watch in self
+            .active
+            .values_mut()
+            .filter(|watch| watch.mode == mode && &watch.instrument == instrument)
+        {
+            let Some(adaptive) = watch.adaptive.as_mut() else {
+                continue;
+            };
+            if let Some(adaptation) = adaptive.observe(&mut watch.config, position, mark_price) {
+                watch.updated_at = Utc::now();
+                adapted.push((watch.clone(), adaptation));
+            }
+        }
+        adapted
+    }
+
+    /// Feeds the latest position into the KPI stats of every watch on
+    /// `instrument` that has targets.
+    pub fn update_kpis(
+        &mut self,
+        mode: BinanceMode,
+        instrument: &Instrument,
+        position: Option<&PositionSnapshot>,
+        mark_price: Option<f64>,
+    ) {
+        for kpi in self
+            .active
+            .values_mut()
+            .filter(|watch| watch.mode == mode && &watch.instrument == instrument)
+            .filter_map(|watch| watch.kpi.as_mut())
+        {
+            kpi.observe(position, mark_price);
+        }
+    }
+
+    /// Feeds the latest position and protective stop into the R tracking of
+    /// every watch on `instrument`, returning each round trip that just
+    /// closed with its R (`None` without a known initial stop).
+    pub fn update_r_multiples(
+        &mut self,
+        mode: BinanceMode,
+        instrument: &Instrument,
+        position: Option<&PositionSnapshot>,
+        mark_price: Option<f64>,
+        protective_stop: Option<f64>,
+    ) -> Vec<(u64, Option<f64>)> {
+        self.active
+            .values_mut()
+            .filter(|watch| watch.mode == mode && &watch.instrument == instrument)
+            .filter_map(|watch| {
+                watch
+                    .r_multiples
+                    .observe(position, mark_price, protective_stop)
+                    .map(|r| (watch.id, r))
+            })
+            .collect()
+    }
+
+    /// Restores the parameters from before the latest adaptation.
+    pub fn rollback_adaptation(
+        &mut self,
+        mode: BinanceMode,
+        watch_id: u64,
+    ) -> Result<(StrategyWatch, Adaptation), StrategyError> {
+        let watch = self
+            .active
+            .get_mut(&watch_id)
+            .filter(|watch| watch.mode == mode)
+            .ok_or(StrategyError::WatchNotFound(watch_id))?;
+        let adaptation = watch
+            .adaptive
+            .as_mut()
+            .and_then(|adaptive| adaptive.rollback(&mut watch.config))
+            .ok_or(StrategyError::NoAdaptation(watch_id))?;
+        watch.updated_at = Utc::now();
+        Ok((watch.clone(), adaptation))
+    }
+
+    /// Pauses armed watches whose instrument is degraded and re-arms paused
+    /// ones once it recovers and any overtrading pause has run out,
+    /// returning the watches that changed state.
+    pub fn apply_degraded(
+        &mut self,
+        mode: BinanceMode,
+        is_degraded: impl Fn(&Instrument) -> bool,
+    ) -> Vec<StrategyWatch> {
+        let now_ms = Utc::now().timestamp_millis();
+        let mut changed = Vec::new();
+        for watch in self.active.values_mut().filter(|watch| watch.mode == mode) {
+            let overtraded = self
+                .overtrading_guards
+                .get(&watch.id)
+                .is_some_and(|guard| !guard.allows_entry(now_ms));
+            let next = match (watch.state, is_degraded(&watch.instrument) || overtraded) {
+                (StrategyWatchState::Armed, true) => StrategyWatchState::Paused,
+                (StrategyWatchState::Paused, false) => StrategyWatchState::Armed,
+                _ => continue,
+            };
+            watch.state = next;
+            watch.updated_at = Utc::now();
+            changed.push(watch.clone());
+        }
+        changed
+    }
+
+    pub fn debug_watches(&self, mode: BinanceMode) -> Vec<&StrategyWatch> {
+        self.active
+            .values()
+            .filter(|watch| watch.mode == mode && watch.debug)
+            .collect()
+    }
+
+    pub fn record_trace(&mut self, watch_id: u64, entry: StrategyTraceEntry) {
+        if self.active.get(&watch_id).is_some_and(|watch| watch.debug) {
+            let capacity = self.trace_capacity;
+            self.traces
+                .entry(watch_id)
+                .or_insert_with(|| capacity.map_or_else(StrategyTrace::default, StrategyTrace::new))
+                .push(entry);
+        }
+    }
+
+    pub fn trace(&self, watch_id: u64) -> Option<&StrategyTrace> {
+        self.traces.get(&watch_id)
+    }
+
+    pub fn traces(&self) -> impl Iterator<Item = &StrategyTrace> + Clone {
+        self.traces.values()
+    }
+
+    /// Caps every trace, current and future, dropping their oldest entries.
+    pub fn set_trace_capacity(&mut self, capacity: usize) {
+        self.trace_capacity = Some(capacity);
+        for trace in self.traces.values_mut() {
+            trace.set_capacity(capacity);
+        }
+    }
+
     fn next_id(&mut self) -> u64 {
         self.next_watch_id += 1;
         self.next_watch_id
@@ -0,0 +1,124 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::error::storage_error::StorageError;
+use crate::strategy::registry::{Strategy, StrategySignal};
+
+pub const DEFAULT_TRACE_CAPACITY: usize = 200;
+
+/// One evaluation of a debug-enabled watch.
+///
+/// Example:
+/// - `indicators={price: 64210.5, fast_sma: 64190.2, slow_sma: 64230.8}`
+/// - `decision="hold"`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StrategyTraceEntry {
+    /// On the venue's clock when the price carried a trade time.
+    pub at: DateTime<Utc>,
+    pub indicators: BTreeMap<String, f64>,
+    pub decision: String,
+}
+
+/// Bounded per-watch trace; the oldest entry is dropped once full.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyTrace {
+    capacity: usize,
+    entries: VecDeque<StrategyTraceEntry>,
+}
+
+impl Default for StrategyTrace {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRACE_CAPACITY)
+    }
+}
+
+impl StrategyTrace {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: VecDeque::new(),
+        }
+    }
+
+    pub fn push(&mut self, entry: StrategyTraceEntry) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        let excess = self.entries.len().saturating_sub(self.capacity);
+        self.entries.drain(..excess);
+    }
+
+    pub fn entries(&self) -> &VecDeque<StrategyTraceEntry> {
+        &self.entries
+    }
+
+    pub fn last(&self) -> Option<&StrategyTraceEntry> {
+        self.entries.back()
+    }
+
+    /// The `price` of every entry that has one, oldest first.
+    pub fn prices(&self) -> impl Iterator<Item = f64> + '_ {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.indicators.get("price").copied())
+    }
+
+    /// Writes the buffer as JSON lines, oldest first.
+    pub fn dump(&self, path: &Path) -> Result<usize, StorageError> {
+        let to_storage_error = |error: std::io::Error| StorageError::WriteFailedWithContext {
+            message: error.to_string(),
+        };
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(to_storage_error)?;
+        }
+        let mut file = fs::File::create(path).map_err(to_storage_error)?;
+        for entry in &self.entries {
+            let line = serde_json::to_string(entry).map_err(|error| {
+                StorageError::WriteFailedWithContext {
+                    message: error.to_string(),
+                }
+            })?;
+            writeln!(file, "{line}").map_err(to_storage_error)?;
+        }
+        Ok(self.entries.len())
+    }
+}
+
+/// Feeds `closes` through a fresh `strategy`, one tick each, and returns its
+/// indicators and the signal on the last close.
+///
+/// Example:
+/// - `SmaCross` 9/21 with 30 closes -> `{fast_sma, slow_sma}`, `"enter"`
+/// - with 5 closes -> `{}`, `"warmup: 5 closes"`
+pub fn evaluate_closes(
+    strategy: &mut dyn Strategy,
+    closes: impl IntoIterator<Item = f64>,
+) -> (BTreeMap<String, f64>, String) {
+    strategy.reset();
+    let mut seen = 0;
+    let mut signal = None;
+    for close in closes {
+        seen += 1;
+        signal = strategy.on_tick(close);
+    }
+    let decision = match signal {
+        Some(StrategySignal::Enter) => "enter".to_string(),
+        Some(StrategySignal::Exit) => "exit".to_string(),
+        Some(StrategySignal::Hold) => "hold".to_string(),
+        None => format!("warmup: {seen} closes"),
+    };
+    (strategy.indicators(), decision)
+}
@@ -0,0 +1,70 @@
+use std::fmt::Debug;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::storage_error::StorageError;
+use crate::storage::order_store::{HistoryQuery, OrderStore};
+
+/// Days of realized trades an EV model learns from when
+/// `SANDBOX_QUANT_TRADE_STATS_LOOKBACK_DAYS` is unset.
+pub const DEFAULT_TRADE_STATS_LOOKBACK_DAYS: u32 = 30;
+
+/// Realized trade results for EV models to learn from.
+///
+/// `query.tag` is a source tag prefix (`strategy:3`, `manual`) and
+/// `query.instrument` a symbol; both unset reads every trade.
+pub trait TradeStatsReader: Debug {
+    /// Net PnL of each closed trade since `since`, oldest first.
+    fn realized_trades(
+        &self,
+        query: &HistoryQuery,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<f64>, StorageError>;
+
+    /// `true` for each trade that made money after fees.
+    fn outcomes(
+        &self,
+        query: &HistoryQuery,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<bool>, StorageError> {
+        Ok(self
+            .realized_trades(query, since)?
+            .into_iter()
+            .map(|pnl| pnl > 0.0)
+            .collect())
+    }
+}
+
+/// No trades at all, so estimates stay on their priors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmptyTradeStatsReader;
+
+impl TradeStatsReader for EmptyTradeStatsReader {
+    fn realized_trades(
+        &self,
+        _query: &HistoryQuery,
+        _since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<f64>, StorageError> {
+        Ok(Vec::new())
+    }
+}
+
+impl TradeStatsReader for OrderStore {
+    fn realized_trades(
+        &self,
+        query: &HistoryQuery,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<f64>, StorageError> {
+        OrderStore::realized_trades(self, query, since)
+    }
+}
+
+/// Reads:
+/// - `SANDBOX_QUANT_TRADE_STATS_LOOKBACK_DAYS` (default `30`, `0` reads
+///   every trade the order store keeps)
+pub fn trade_stats_lookback_days_from_env() -> u32 {
+    std::env::var("SANDBOX_QUANT_TRADE_STATS_LOOKBACK_DAYS")
+        .ok()
+        .and_then(|raw| raw.trim().parse().ok())
+        .unwrap_or(DEFAULT_TRADE_STATS_LOOKBACK_DAYS)
+}
@@ -0,0 +1,23 @@
+use sha2::{Digest, Sha256};
+
+/// Short fingerprint of a strategy's parameters, so orders and events made
+/// before and after an edit or fork can be told apart.
+///
+/// Example:
+/// - `["basis-arb", "entry_bps=30", ...]` -> eight hex digits such as `3f9a0c1d`
+/// - the same parameters always map to the same version
+pub fn parameter_fingerprint<I, S>(parts: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_ref().as_bytes());
+        hasher.update([0]);
+    }
+    hasher.finalize()[..4]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
@@ -1,4 +1,8 @@
+use std::time::Duration;
+
 use crate::terminal::completion::ShellCompletion;
+use crate::terminal::loop_shell::IDLE_TICK;
+use crate::ui::theme::UiTheme;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TerminalMode {
@@ -21,7 +25,28 @@ pub trait TerminalApp {
         "slash commands"
     }
     fn help_text(&self) -> String;
+    /// Palette for the shell chrome; defaults to `SANDBOX_QUANT_UI_THEME`.
+    fn theme(&self) -> UiTheme {
+        UiTheme::from_env()
+    }
     fn prompt(&self) -> String;
     fn complete(&self, line: &str) -> Vec<ShellCompletion>;
+    /// Command line bound to `ctrl+<key>` in raw mode, if any.
+    fn hotkey_line(&mut self, _key: char) -> Option<String> {
+        None
+    }
+    /// Command line bound to `F<key>` in raw mode, if any.
+    fn function_key_line(&self, _key: u8) -> Option<String> {
+        None
+    }
     fn execute_line(&mut self, line: &str) -> Result<TerminalEvent, String>;
+    /// How long raw mode waits for a key before calling `idle_tick`.
+    fn idle_tick_every(&self) -> Duration {
+        IDLE_TICK
+    }
+    /// Called about once per `idle_tick_every` while no key arrives in raw
+    /// mode; returned text is printed above the prompt.
+    fn idle_tick(&mut self) -> Option<String> {
+        None
+    }
 }
@@ -1,9 +1,10 @@
 use std::io::{self, Write};
+use std::time::Duration;
 
 use crossterm::cursor::{
     position, MoveToColumn, MoveToNextLine, MoveUp, RestorePosition, SavePosition,
 };
-use crossterm::event::{read, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::event::{poll, read, Event, KeyCode, KeyEventKind, KeyModifiers};
 use crossterm::execute;
 use crossterm::style::{Print, PrintStyledContent, Stylize};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode, size, Clear, ClearType, ScrollUp};
@@ -12,16 +13,22 @@ use crate::terminal::app::{TerminalApp, TerminalEvent, TerminalMode};
 use crate::terminal::completion::{
     next_completion_index, previous_completion_index, scroll_lines_needed, ShellCompletion,
 };
+use crate::ui::theme::{terminal_color, UiTheme};
+
+/// How long raw mode waits for a key before giving the app an idle tick,
+/// unless the app paces itself with `TerminalApp::idle_tick_every`.
+pub const IDLE_TICK: Duration = Duration::from_secs(1);
 
 pub fn run_terminal<A: TerminalApp>(app: &mut A) -> Result<(), Box<dyn std::error::Error>> {
     match app.terminal_mode() {
         TerminalMode::Raw => {
             let intro_panel = app.intro_panel();
+            let palette = app.theme().palette();
             execute!(
                 io::stdout(),
-                PrintStyledContent(intro_panel.cyan().bold()),
+                PrintStyledContent(intro_panel.with(terminal_color(palette.accent)).bold()),
                 Print("\n"),
-                PrintStyledContent(app.help_heading().dark_grey()),
+                PrintStyledContent(app.help_heading().with(terminal_color(palette.muted))),
                 Print("\n"),
                 Print(app.help_text()),
                 Print("\n")
@@ -53,8 +60,37 @@ fn loop_terminal_raw<A: TerminalApp>(app: &mut A) -> Result<(), Box<dyn std::err
         completion_index,
         &mut rendered_menu_lines,
     )?;
+    // Redrawn on idle ticks only when it changed, e.g. its clock moved on.
+    let mut idle_prompt = app.prompt();
 
     loop {
+        if !poll(app.idle_tick_every())? {
+            let prompt = app.prompt();
+            if prompt != idle_prompt {
+                idle_prompt = prompt;
+                render_shell(
+                    &mut stdout,
+                    app,
+                    &buffer,
+                    completion_index,
+                    &mut rendered_menu_lines,
+                )?;
+            }
+            if let Some(output) = app.idle_tick() {
+                clear_completion_menu(&mut stdout, rendered_menu_lines)?;
+                rendered_menu_lines = 0;
+                println!();
+                print_multiline_block(&mut stdout, &output, false, app.theme())?;
+                render_shell(
+                    &mut stdout,
+                    app,
+                    &buffer,
+                    completion_index,
+                    &mut rendered_menu_lines,
+                )?;
+            }
+            continue;
+        }
         if let Event::Key(key) = read()? {
             if key.kind != KeyEventKind::Press {
                 continue;
@@ -64,7 +100,39 @@ fn loop_terminal_raw<A: TerminalApp>(app: &mut A) -> Result<(), Box<dyn std::err
                     println!();
                     break;
                 }
+                KeyCode::F(key) => {
+                    if let Some(line) = app.function_key_line(key) {
+                        if run_hotkey_line(
+                            &mut stdout,
+                            app,
+                            &line,
+                            &buffer,
+                            completion_index,
+                            &mut rendered_menu_lines,
+                        )? {
+                            break;
+                        }
+                    }
+                }
                 KeyCode::Char(ch) => {
+                    if let Some(line) = key
+                        .modifiers
+                        .contains(KeyModifiers::CONTROL)
+                        .then(|| app.hotkey_line(ch))
+                        .flatten()
+                    {
+                        if run_hotkey_line(
+                            &mut stdout,
+                            app,
+                            &line,
+                            &buffer,
+                            completion_index,
+                            &mut rendered_menu_lines,
+                        )? {
+                            break;
+                        }
+                        continue;
+                    }
                     buffer.push(ch);
                     completion_index = 0;
                     completion_query = Some(buffer.clone());
@@ -160,10 +228,10 @@ fn loop_terminal_raw<A: TerminalApp>(app: &mut A) -> Result<(), Box<dyn std::err
                     match app.execute_line(&line) {
                         Ok(TerminalEvent::NoOutput) => {}
                         Ok(TerminalEvent::Output(output)) => {
-                            print_multiline_block(&mut stdout, &output, true)?
+                            print_multiline_block(&mut stdout, &output, true, app.theme())?
                         }
                         Ok(TerminalEvent::Exit) => break,
-                        Err(error) => print_error(&mut stdout, error)?,
+                        Err(error) => print_error(&mut stdout, error, app.theme())?,
                     }
                     render_shell(
                         &mut stdout,
@@ -181,6 +249,32 @@ fn loop_terminal_raw<A: TerminalApp>(app: &mut A) -> Result<(), Box<dyn std::err
     Ok(())
 }
 
+/// Runs a hotkey-bound line without touching the typed buffer.
+///
+/// Returns true when the line asked the shell to exit.
+fn run_hotkey_line<A: TerminalApp>(
+    stdout: &mut io::Stdout,
+    app: &mut A,
+    line: &str,
+    buffer: &str,
+    completion_index: usize,
+    rendered_menu_lines: &mut usize,
+) -> io::Result<bool> {
+    clear_completion_menu(stdout, *rendered_menu_lines)?;
+    *rendered_menu_lines = 0;
+    println!();
+    match app.execute_line(line) {
+        Ok(TerminalEvent::NoOutput) => {}
+        Ok(TerminalEvent::Output(output)) => {
+            print_multiline_block(stdout, &output, true, app.theme())?
+        }
+        Ok(TerminalEvent::Exit) => return Ok(true),
+        Err(error) => print_error(stdout, error, app.theme())?,
+    }
+    render_shell(stdout, app, buffer, completion_index, rendered_menu_lines)?;
+    Ok(false)
+}
+
 fn loop_terminal_line<A: TerminalApp>(app: &mut A) -> Result<(), Box<dyn std::error::Error>> {
     let stdin = io::stdin();
     let mut stdout = io::stdout();
@@ -232,7 +326,7 @@ fn render_shell<A: TerminalApp>(
     render_prompt(stdout, app, buffer)?;
     execute!(stdout, SavePosition)?;
     let menu_lines = if should_show_completion_menu(buffer, &completions) {
-        print_completion_menu(stdout, &completions, completion_index)?
+        print_completion_menu(stdout, &completions, completion_index, app.theme())?
     } else {
         0
     };
@@ -270,13 +364,16 @@ fn print_completion_menu(
     stdout: &mut io::Stdout,
     completions: &[ShellCompletion],
     selected: usize,
+    theme: UiTheme,
 ) -> io::Result<usize> {
+    let accent = terminal_color(theme.palette().accent);
+    let muted = terminal_color(theme.palette().muted);
     execute!(
         stdout,
         MoveToNextLine(1),
         MoveToColumn(0),
         Clear(ClearType::CurrentLine),
-        PrintStyledContent("completions".dark_grey()),
+        PrintStyledContent("completions".with(muted)),
     )?;
 
     for (index, item) in completions.iter().enumerate() {
@@ -289,30 +386,40 @@ fn print_completion_menu(
         if index == selected {
             execute!(
                 stdout,
-                PrintStyledContent(">".cyan().bold()),
+                PrintStyledContent(">".with(accent).bold()),
                 Print(" "),
                 PrintStyledContent(item.value.as_str().black().on_white()),
                 Print("  "),
-                PrintStyledContent(item.description.as_str().dark_grey()),
+                PrintStyledContent(item.description.as_str().with(muted)),
             )?;
         } else {
             execute!(
                 stdout,
                 Print("  "),
-                PrintStyledContent(item.value.as_str().dark_grey()),
+                PrintStyledContent(item.value.as_str().with(muted)),
                 Print("  "),
-                PrintStyledContent(item.description.as_str().dark_grey()),
+                PrintStyledContent(item.description.as_str().with(muted)),
             )?;
         }
     }
     Ok(completions.len() + 1)
 }
 
-fn print_error(stdout: &mut io::Stdout, error: impl std::fmt::Display) -> io::Result<()> {
-    print_multiline_block(stdout, &format!("error: {error}"), false)
+fn print_error(
+    stdout: &mut io::Stdout,
+    error: impl std::fmt::Display,
+    theme: UiTheme,
+) -> io::Result<()> {
+    print_multiline_block(stdout, &format!("error: {error}"), false, theme)
 }
 
-fn print_multiline_block(stdout: &mut io::Stdout, text: &str, cyan_output: bool) -> io::Result<()> {
+fn print_multiline_block(
+    stdout: &mut io::Stdout,
+    text: &str,
+    accent_output: bool,
+    theme: UiTheme,
+) -> io::Result<()> {
+    let palette = theme.palette();
     for (index, line) in text.lines().enumerate() {
         if index == 0 {
             begin_output_block(stdout)?;
@@ -320,10 +427,16 @@ fn print_multiline_block(stdout: &mut io::Stdout, text: &str, cyan_output: bool)
             execute!(stdout, MoveToColumn(0))?;
         }
 
-        if cyan_output {
-            writeln!(stdout, "{}", line.cyan())?;
+        if accent_output {
+            writeln!(stdout, "{}", line.with(terminal_color(palette.accent)))?;
         } else if let Some(rest) = line.strip_prefix("error: ") {
-            writeln!(stdout, "{} {}", "error:".red().bold(), rest.red())?;
+            let negative = terminal_color(palette.negative);
+            writeln!(
+                stdout,
+                "{} {}",
+                "error:".with(negative).bold(),
+                rest.with(negative)
+            )?;
         } else {
             writeln!(stdout, "{line}")?;
         }
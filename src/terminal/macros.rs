@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::storage_error::StorageError;
+use crate::storage::{atomic_file, sealed};
+
+pub const FALLBACK_MACROS_PATH: &str = "var/shell-macros.json";
+
+/// Shell-level macro commands; these are never recorded themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroCommand {
+    Record {
+        name: String,
+    },
+    Stop,
+    Play {
+        name: String,
+    },
+    List,
+    Remove {
+        name: String,
+    },
+    /// Binds a macro to `F<slot>` in the raw terminal.
+    Bind {
+        name: String,
+        slot: u8,
+    },
+}
+
+/// A recorded sequence of shell lines.
+///
+/// Example:
+/// - `lines=["/mode demo", "/set-target-exposure ETHUSDT 0.2", "/strategy start ..."]`
+/// - `hotkey=Some(2)` replays it on `F2`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShellMacro {
+    pub lines: Vec<String>,
+    #[serde(default)]
+    pub hotkey: Option<u8>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ShellMacroFile {
+    macros: BTreeMap<String, ShellMacro>,
+}
+
+/// Per-user macro library plus the in-progress recording, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShellMacroStore {
+    path: PathBuf,
+    macros: BTreeMap<String, ShellMacro>,
+    recording: Option<(String, Vec<String>)>,
+    /// `false` when the library on disk failed to load, so it is never
+    /// saved over.
+    writable: bool,
+}
+
+impl ShellMacroStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            macros: BTreeMap::new(),
+            recording: None,
+            writable: true,
+        }
+    }
+
+    /// An empty library standing in for one that failed to load; `save`
+    /// refuses so the file on disk is left for the operator to repair.
+    pub fn unloaded(path: impl Into<PathBuf>) -> Self {
+        Self {
+            writable: false,
+            ..Self::new(path)
+        }
+    }
+
+    /// Resolves the per-user library path.
+    ///
+    /// Reads:
+    /// - `SANDBOX_QUANT_MACROS_PATH`
+    /// - otherwise `$HOME/.config/sandbox-quant/macros.json`
+    /// - otherwise `var/shell-macros.json`
+    pub fn default_path() -> PathBuf {
+        if let Ok(path) = env::var("SANDBOX_QUANT_MACROS_PATH") {
+            return PathBuf::from(path);
+        }
+        env::var_os("HOME")
+            .map(|home| {
+                PathBuf::from(home)
+                    .join(".config")
+                    .join("sandbox-quant")
+                    .join("macros.json")
+            })
+            .unwrap_or_else(|| PathBuf::from(FALLBACK_MACROS_PATH))
+    }
+
+    /// Loads macros from `path`, starting empty when the file does not exist.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, StorageError> {
+        let mut store = Self::new(path);
+        if !atomic_file::store_exists(&store.path) {
+            return Ok(store);
+        }
+        let bytes = sealed::read_store_file(&store.path, sealed::process_sealer()?)?;
+        let file: ShellMacroFile =
+            serde_json::from_slice(&bytes).map_err(|error| StorageError::ParseFailed {
+                path: store.path.display().to_string(),
+                message: error.to_string(),
+            })?;
+        store.macros = file.macros;
+        Ok(store)
+    }
+
+    pub fn save(&self) -> Result<(), StorageError> {
+        if !self.writable {
+            return Err(StorageError::NotLoaded {
+                path: self.path.display().to_string(),
+            });
+        }
+        let file = ShellMacroFile {
+            macros: self.macros.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&file).map_err(|error| {
+            StorageError::WriteFailedWithContext {
+                message: error.to_string(),
+            }
+        })?;
+        sealed::write_store_file(&self.path, &json, sealed::process_sealer()?)
+    }
+
+    pub fn macros(&self) -> &BTreeMap<String, ShellMacro> {
+        &self.macros
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ShellMacro> {
+        self.macros.get(name)
+    }
+
+    pub fn recording_name(&self) -> Option<&str> {
+        self.recording.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    /// Starts capturing lines, discarding any unfinished recording.
+    pub fn start_recording(&mut self, name: &str) {
+        self.recording = Some((name.to_string(), Vec::new()));
+    }
+
+    /// Appends one successfully executed line while recording.
+    pub fn capture(&mut self, line: &str) {
+        if let Some((_, lines)) = self.recording.as_mut() {
+            lines.push(line.trim().to_string());
+        }
+    }
+
+    /// Finishes the recording and stores it, keeping an existing hotkey.
+    ///
+    /// Returns the name and step count, or `None` when nothing was recording.
+    pub fn finish_recording(&mut self) -> Option<(String, usize)> {
+        let (name, lines) = self.recording.take()?;
+        let steps = lines.len();
+        let hotkey = self.macros.get(&name).and_then(|existing| existing.hotkey);
+        self.macros
+            .insert(name.clone(), ShellMacro { lines, hotkey });
+        Some((name, steps))
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<ShellMacro> {
+        self.macros.remove(name)
+    }
+
+    /// Moves `F<slot>` to `name`; returns false when the macro is unknown.
+    pub fn bind(&mut self, name: &str, slot: u8) -> bool {
+        if !self.macros.contains_key(name) {
+            return false;
+        }
+        for shell_macro in self.macros.values_mut() {
+            if shell_macro.hotkey == Some(slot) {
+                shell_macro.hotkey = None;
+            }
+        }
+        if let Some(shell_macro) = self.macros.get_mut(name) {
+            shell_macro.hotkey = Some(slot);
+        }
+        true
+    }
+
+    pub fn name_for_hotkey(&self, slot: u8) -> Option<&str> {
+        self.macros
+            .iter()
+            .find(|(_, shell_macro)| shell_macro.hotkey == Some(slot))
+            .map(|(name, _)| name.as_str())
+    }
+}
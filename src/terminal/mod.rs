@@ -1,3 +1,4 @@
 pub mod app;
 pub mod completion;
 pub mod loop_shell;
+pub mod macros;
@@ -1,8 +1,20 @@
+use crate::backtest_app::compare::BacktestComparison;
+use crate::backtest_app::monthly::MonthlyPerformance;
 use crate::backtest_app::runner::BacktestReport;
-use crate::dataset::types::BacktestRunSummaryRow;
+use crate::dataset::types::{BacktestRunSummaryRow, ExitReasonBreakdownRow};
+use crate::domain::reason::ExitReason;
 use crate::strategy::model::StrategyTemplate;
+use crate::strategy::overtrading::{
+    detect_trade_clusters, DEFAULT_OVERTRADING_MAX_TRADES, DEFAULT_OVERTRADING_WINDOW_SECS,
+};
+use crate::ui::projection::render_grid;
+use crate::ui::timezone::DisplayTimeZone;
+
+/// Latest EV gate snapshots listed under `[ev_gate]`.
+const EV_GATE_ROWS: usize = 10;
 
 pub fn render_backtest_run(report: &BacktestReport) -> String {
+    let zone = DisplayTimeZone::from_env();
     let realized_trade_count = report
         .trades
         .iter()
@@ -44,6 +56,7 @@ pub fn render_backtest_run(report: &BacktestReport) -> String {
         format!("wins={}", report.wins),
         format!("losses={}", report.losses),
         format!("skipped_triggers={}", report.skipped_triggers),
+        format!("throttled_signals={}", report.throttled_signals),
         format!("starting_equity={:.2}", report.starting_equity),
         format!("ending_equity={:.2}", report.ending_equity),
         format!("net_pnl={:.2}", report.net_pnl),
@@ -71,8 +84,139 @@ pub fn render_backtest_run(report: &BacktestReport) -> String {
             report.config.max_entry_slippage_pct
         ),
         format!("stop_distance_pct={}", report.config.stop_distance_pct),
+        format!("max_orders_per_min={}", report.config.max_orders_per_min),
+        format!(
+            "volatility_stops={}",
+            if report.config.volatility_stop_sigmas > f64::EPSILON {
+                format!(
+                    "{}σ ({})",
+                    report.config.volatility_stop_sigmas,
+                    report.config.volatility_model.label()
+                )
+            } else {
+                "off".to_string()
+            }
+        ),
+        format!(
+            "cooldown={}s{}",
+            report.config.cooldown_secs,
+            report
+                .config
+                .cooldown_scaling
+                .as_ref()
+                .map(|scaling| format!(" scaled {}", scaling.label()))
+                .unwrap_or_default()
+        ),
+        format!("signal_candles={}", report.config.signal_candles.label()),
+        format!(
+            "htf_confirm={}",
+            report
+                .config
+                .htf_confirm
+                .map_or_else(|| "off".to_string(), |confirm| confirm.label())
+        ),
+        format!("ev_gate={}", report.config.ev_gate.label()),
     ];
 
+    lines.push("[exit_reasons]".to_string());
+    let exit_stats = report.exit_reason_stats();
+    if exit_stats.is_empty() {
+        lines.push("exits=none".to_string());
+    }
+    for (reason, stats) in &exit_stats {
+        lines.push(format!(
+            "{} trades={} net_pnl={:.2} average_net_pnl={:.2}",
+            reason.as_str(),
+            stats.trades,
+            stats.net_pnl,
+            stats.average_net_pnl()
+        ));
+    }
+    let r_stats = report.r_multiple_stats();
+    lines.push("[r_multiples]".to_string());
+    lines.push(r_stats.summary());
+    if r_stats.trades > 0 {
+        lines.push(format!("distribution {}", r_stats.distribution_label()));
+    }
+    let skip_counts = report.skip_reason_counts();
+    if !skip_counts.is_empty() {
+        lines.push(format!(
+            "skipped_signals {}",
+            skip_counts
+                .iter()
+                .map(|(reason, count)| format!("{}={count}", reason.as_str()))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ));
+    }
+    let ev_signals = report
+        .signals
+        .iter()
+        .filter_map(|signal| Some((signal, signal.ev?)))
+        .collect::<Vec<_>>();
+    if !ev_signals.is_empty() {
+        lines.push("[ev_gate]".to_string());
+        lines.push(format!(
+            "mode={} snapshots={} blocked={}",
+            report.config.ev_gate.label(),
+            ev_signals.len(),
+            ev_signals.iter().filter(|(_, ev)| ev.gate_blocked).count()
+        ));
+        lines.extend(render_grid(
+            &["time", "price", "ev_r", "gate"],
+            ev_signals
+                .iter()
+                .skip(ev_signals.len().saturating_sub(EV_GATE_ROWS))
+                .map(|(signal, ev)| {
+                    vec![
+                        chrono::DateTime::from_timestamp_millis(signal.time_ms)
+                            .map(|time| zone.rfc3339(time))
+                            .unwrap_or_else(|| signal.time_ms.to_string()),
+                        format!("{:.2}", signal.price),
+                        format!("{:.2}", ev.expected_value_r),
+                        ev.gate_label().to_string(),
+                    ]
+                }),
+        ));
+    }
+
+    // With the guard off, clusters are still reported at the default threshold.
+    let (max_trades, window_secs) = report
+        .config
+        .overtrading
+        .map(|limit| (limit.max_trades, limit.window_secs))
+        .unwrap_or((
+            DEFAULT_OVERTRADING_MAX_TRADES,
+            DEFAULT_OVERTRADING_WINDOW_SECS,
+        ));
+    let clusters = detect_trade_clusters(
+        report
+            .trades
+            .iter()
+            .map(|trade| trade.entry_time.timestamp_millis()),
+        max_trades,
+        window_secs,
+    );
+    lines.push("[overtrading]".to_string());
+    lines.push(match report.config.overtrading {
+        Some(limit) => format!(
+            "threshold={} trades/{}s pause={}s",
+            limit.max_trades, limit.window_secs, limit.pause_secs
+        ),
+        None => format!("threshold={max_trades} trades/{window_secs}s guard=off"),
+    });
+    lines.push(format!("clusters={}", clusters.len()));
+    for cluster in clusters.iter().take(5) {
+        lines.push(format!(
+            "cluster start={} trades={} span_secs={}",
+            chrono::DateTime::from_timestamp_millis(cluster.start_ms)
+                .map(|time| zone.rfc3339(time))
+                .unwrap_or_else(|| cluster.start_ms.to_string()),
+            cluster.trades,
+            cluster.span_secs()
+        ));
+    }
+
     if report.trades.is_empty() {
         lines.push("[trades]".to_string());
         lines.push("trades=none".to_string());
@@ -80,28 +224,230 @@ pub fn render_backtest_run(report: &BacktestReport) -> String {
         lines.push("[trades]".to_string());
         for trade in report.trades.iter().take(5) {
             lines.push(format!(
-                "trade id={} entry_time={} entry_price={:.4} stop={:.4} tp={:.4} exit_reason={} net_pnl={}",
+                "trade id={} side={} entry_time={} entry_price={:.4} stop={:.4} tp={:.4} exit_reason={} net_pnl={} r={}",
                 trade.trade_id,
-                trade.entry_time.to_rfc3339(),
+                trade.side.position_label(),
+                zone.rfc3339(trade.entry_time),
                 trade.entry_price,
                 trade.stop_price,
                 trade.take_profit_price,
                 trade
                     .exit_reason
-                    .as_ref()
-                    .map(|reason| reason.as_str())
+                    .map(ExitReason::as_str)
                     .unwrap_or("open"),
                 trade
                     .net_pnl
                     .map(|value| format!("{value:.2}"))
-                    .unwrap_or_else(|| "open".to_string())
+                    .unwrap_or_else(|| "open".to_string()),
+                trade
+                    .r_multiple()
+                    .map(|r| format!("{r:.2}"))
+                    .unwrap_or_else(|| "-".to_string())
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Example:
+/// - `month=2026-03 trades=2 gross_pnl=12.00 fees=1.20 funding=0.00 net_pnl=10.80 turnover=400.00 fee_drag_pct=10.00 return_pct=0.11 benchmark_pct=0.38 excess_pct=-0.27`
+pub fn render_backtest_monthly(
+    report: &BacktestReport,
+    months: &[MonthlyPerformance],
+    risk_free_rate_pct: f64,
+) -> String {
+    let mut lines = vec![
+        "backtest monthly report".to_string(),
+        format!(
+            "run_id={}",
+            report
+                .run_id
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "n/a".to_string())
+        ),
+        format!("template={}", report.template.slug()),
+        format!("instrument={}", report.instrument),
+        format!("risk_free_rate_pct={risk_free_rate_pct:.2}"),
+        "funding_source=not_modelled".to_string(),
+    ];
+    if months.is_empty() {
+        lines.push("months=none".to_string());
+        return lines.join("\n");
+    }
+    lines.extend(months.iter().map(|month| {
+        format!(
+            "month={} trades={} gross_pnl={:.2} fees={:.2} funding={:.2} net_pnl={:.2} turnover={:.2} fee_drag_pct={} return_pct={:.2} benchmark_pct={:.2} excess_pct={:.2}",
+            month.label(),
+            month.closed_trades,
+            month.gross_pnl,
+            month.fees,
+            month.funding,
+            month.net_pnl,
+            month.turnover,
+            format_fee_drag(month.fee_drag_pct()),
+            month.return_pct(),
+            month.benchmark_pct(),
+            month.excess_return_pct(),
+        )
+    }));
+    let gross_pnl = months.iter().map(|month| month.gross_pnl).sum::<f64>();
+    let fees = months.iter().map(|month| month.fees).sum::<f64>();
+    lines.push(format!(
+        "total trades={} gross_pnl={:.2} fees={:.2} funding={:.2} net_pnl={:.2} turnover={:.2} fee_drag_pct={} benchmark_pnl={:.2}",
+        months.iter().map(|month| month.closed_trades).sum::<usize>(),
+        gross_pnl,
+        fees,
+        months.iter().map(|month| month.funding).sum::<f64>(),
+        months.iter().map(|month| month.net_pnl).sum::<f64>(),
+        months.iter().map(|month| month.turnover).sum::<f64>(),
+        format_fee_drag((gross_pnl.abs() > f64::EPSILON).then(|| fees / gross_pnl.abs() * 100.0)),
+        months.iter().map(|month| month.benchmark_pnl).sum::<f64>(),
+    ));
+    lines.join("\n")
+}
+
+/// Groups rows by template; `share` is the template's trades that ended this
+/// way and `loss_share` its losses that did.
+///
+/// Example:
+/// - `template=price-sma-cross-long trades=20 net_pnl=-4.10`
+/// - `exit=signal_exit trades=12 share=60.00% net_pnl=-9.30 average_net_pnl=-0.78 loss_share=81.20%`
+pub fn render_exit_reason_breakdown(rows: &[ExitReasonBreakdownRow]) -> String {
+    let mut lines = vec!["backtest exit reasons".to_string()];
+    if rows.is_empty() {
+        lines.push("exits=none".to_string());
+        return lines.join("\n");
+    }
+    for group in rows.chunk_by(|a, b| a.template == b.template) {
+        let trades = group.iter().map(|row| row.trades).sum::<u64>();
+        let loss_pnl = group.iter().map(|row| row.loss_pnl).sum::<f64>();
+        lines.push(format!(
+            "template={} trades={} net_pnl={:.2}",
+            group[0].template,
+            trades,
+            group.iter().map(|row| row.net_pnl).sum::<f64>()
+        ));
+        for row in group {
+            lines.push(format!(
+                "exit={} trades={} share={:.2}% net_pnl={:.2} average_net_pnl={:.2} loss_share={:.2}%",
+                row.exit_reason.as_str(),
+                row.trades,
+                row.trades as f64 / trades.max(1) as f64 * 100.0,
+                row.net_pnl,
+                row.net_pnl / row.trades.max(1) as f64,
+                if loss_pnl < -f64::EPSILON {
+                    row.loss_pnl / loss_pnl * 100.0
+                } else {
+                    0.0
+                }
             ));
         }
     }
+    lines.join("\n")
+}
 
+fn format_r(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |r| format!("{r:+.2}R"))
+}
+
+/// Renders two runs as side-by-side columns, then their signal overlap.
+///
+/// Example:
+/// - `win_rate         0.6667                   0.5000`
+/// - `equity           ▁▇▄█                     ▄▁▂▁`
+/// - `signal_overlap shared=3 a=5 b=4 overlap_pct=50.00`
+pub fn render_backtest_comparison(comparison: &BacktestComparison) -> String {
+    let (a, b) = (&comparison.a, &comparison.b);
+    let row = |label: &str, a: String, b: String| format!("{label:<16} {a:<24} {b}");
+    let mut lines = vec![
+        "backtest comparison".to_string(),
+        row("variant", a.label(), b.label()),
+        row("instrument", a.instrument.clone(), b.instrument.clone()),
+        row(
+            "closed_trades",
+            a.closed_trades.to_string(),
+            b.closed_trades.to_string(),
+        ),
+        row(
+            "win_rate",
+            format!("{:.4}", a.win_rate),
+            format!("{:.4}", b.win_rate),
+        ),
+        row(
+            "average_trade",
+            format!("{:.2}", a.average_net_pnl),
+            format!("{:.2}", b.average_net_pnl),
+        ),
+        row("average_r", format_r(a.average_r), format_r(b.average_r)),
+        row(
+            "net_pnl",
+            format!("{:.2}", a.net_pnl),
+            format!("{:.2}", b.net_pnl),
+        ),
+        row(
+            "max_drawdown",
+            format!("{:.2}", a.max_drawdown),
+            format!("{:.2}", b.max_drawdown),
+        ),
+        row(
+            "configured_ev",
+            format!("{:.2}", a.configured_expected_value),
+            format!("{:.2}", b.configured_expected_value),
+        ),
+        row(
+            "ev_gap",
+            format!("{:+.2}", a.ev_gap()),
+            format!("{:+.2}", b.ev_gap()),
+        ),
+        row(
+            "equity",
+            sparkline(&a.equity_curve),
+            sparkline(&b.equity_curve),
+        ),
+    ];
+    lines.push(match comparison.overlap {
+        Some(overlap) => format!(
+            "signal_overlap shared={} a={} b={} overlap_pct={:.2}",
+            overlap.shared,
+            overlap.a_entries,
+            overlap.b_entries,
+            overlap.overlap_pct()
+        ),
+        None => "signal_overlap=n/a (different instruments)".to_string(),
+    });
     lines.join("\n")
 }
 
+/// Scales the last `SPARKLINE_WIDTH` points into block characters.
+fn sparkline(values: &[f64]) -> String {
+    const SPARKLINE_WIDTH: usize = 20;
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let values = &values[values.len().saturating_sub(SPARKLINE_WIDTH)..];
+    let low = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if values.len() < 2 {
+        return "-".to_string();
+    }
+    values
+        .iter()
+        .map(|value| {
+            let level = if high - low > f64::EPSILON {
+                ((value - low) / (high - low) * (BLOCKS.len() - 1) as f64).round() as usize
+            } else {
+                0
+            };
+            BLOCKS[level]
+        })
+        .collect()
+}
+
+fn format_fee_drag(value: Option<f64>) -> String {
+    value
+        .map(|value| format!("{value:.2}"))
+        .unwrap_or_else(|| "n/a".to_string())
+}
+
 fn report_state(report: &BacktestReport, has_dataset_rows: bool) -> &'static str {
     if !report.dataset.symbol_found {
         return "symbol_not_found";
@@ -144,6 +490,16 @@ fn report_state(report: &BacktestReport, has_dataset_rows: bool) -> &'static str
                 "ok"
             }
         }
+        StrategyTemplate::ScriptLong
+        | StrategyTemplate::ScriptShort
+        | StrategyTemplate::PluginLong
+        | StrategyTemplate::PluginShort => {
+            if report.trades.is_empty() {
+                "no_trades"
+            } else {
+                "ok"
+            }
+        }
     }
 }
 
@@ -197,10 +553,15 @@ mod tests {
 
     use super::*;
     use crate::app::bootstrap::BinanceMode;
+    use crate::backtest_app::compare::compare_backtests;
+    use crate::backtest_app::monthly::monthly_performance;
     use crate::backtest_app::runner::{
-        BacktestConfig, BacktestExitReason, BacktestReport, BacktestTrade,
+        BacktestConfig, BacktestReport, BacktestSignal, BacktestTrade, SignalOutcome,
     };
     use crate::dataset::types::BacktestDatasetSummary;
+    use crate::domain::position::Side;
+    use crate::domain::reason::SkipReason;
+    use crate::strategy::ev::EvGate;
     use crate::strategy::model::StrategyTemplate;
 
     #[test]
@@ -252,6 +613,7 @@ mod tests {
         let output = render_backtest_run(&sample_report(
             vec![BacktestTrade {
                 trade_id: 1,
+                side: Side::Sell,
                 trigger_time: Utc.timestamp_millis_opt(1_000).single().expect("timestamp"),
                 entry_time: Utc.timestamp_millis_opt(2_000).single().expect("timestamp"),
                 entry_price: 100.0,
@@ -260,7 +622,7 @@ mod tests {
                 qty: 1.0,
                 exit_time: Some(Utc.timestamp_millis_opt(3_000).single().expect("timestamp")),
                 exit_price: Some(98.0),
-                exit_reason: Some(BacktestExitReason::TakeProfit),
+                exit_reason: Some(ExitReason::TakeProfit),
                 gross_pnl: Some(2.0),
                 fees: Some(0.2),
                 net_pnl: Some(1.8),
@@ -280,7 +642,12 @@ mod tests {
 
         assert!(output.contains("state=ok"));
         assert!(output.contains("summary=state:ok"));
-        assert!(output.contains("trade id=1"));
+        assert!(output.contains("trade id=1 side=short"));
+        assert!(output.contains("net_pnl=1.80 r=1.80"));
+        assert!(output.contains("take_profit trades=1 net_pnl=1.80 average_net_pnl=1.80"));
+        assert!(output.contains(
+            "[r_multiples]\ntrades=1 avg=1.80R expectancy=1.80R win=1.80R loss=- best=1.80R worst=1.80R unrisked=0\ndistribution <-1R=0 -1..0R=0 0..1R=0 1..2R=1 2..3R=0 >=3R=0"
+        ));
     }
 
     #[test]
@@ -303,6 +670,50 @@ mod tests {
         assert!(output.contains("state=symbol_not_found"));
     }
 
+    #[test]
+    fn render_backtest_run_lists_ev_gate_snapshots_with_their_gate() {
+        let mut report = sample_report(
+            Vec::new(),
+            BacktestDatasetSummary {
+                mode: BinanceMode::Demo,
+                symbol: "BTCUSDT".to_string(),
+                symbol_found: true,
+                from: "2026-03-13".to_string(),
+                to: "2026-03-13".to_string(),
+                liquidation_events: 0,
+                book_ticker_events: 0,
+                agg_trade_events: 0,
+                derived_kline_1s_bars: 5,
+            },
+        );
+        let gate = EvGate::parse("active:0.5").expect("gate");
+        report.config.ev_gate = gate;
+        report.signals = vec![
+            BacktestSignal {
+                time_ms: 0,
+                price: 100.0,
+                outcome: SignalOutcome::Ordered,
+                reason: None,
+                ev: Some(gate.evaluate(0.92)),
+            },
+            BacktestSignal {
+                time_ms: 60_000,
+                price: 101.5,
+                outcome: SignalOutcome::Blocked,
+                reason: Some(SkipReason::LowExpectedValue),
+                ev: Some(gate.evaluate(0.31)),
+            },
+        ];
+
+        let output = render_backtest_run(&report);
+
+        assert!(output.contains("ev_gate=active:0.5"));
+        assert!(output.contains("skipped_signals low_ev=1"));
+        assert!(output.contains("[ev_gate]\nmode=active:0.5 snapshots=2 blocked=1\ntime"));
+        assert!(output.contains("101.50  0.31  blocked"));
+        assert!(output.contains("100.00  0.92  pass"));
+    }
+
     fn sample_report(
         trades: Vec<BacktestTrade>,
         dataset: BacktestDatasetSummary,
@@ -331,6 +742,8 @@ mod tests {
                 .filter(|trade| trade.net_pnl.is_none())
                 .count(),
             skipped_triggers: 0,
+            throttled_signals: 0,
+            signals: Vec::new(),
             starting_equity: 10_000.0,
             ending_equity: 10_001.8,
             net_pnl: 1.8,
@@ -382,4 +795,209 @@ mod tests {
         assert!(output.contains("run_id=2 state=open_trades"));
         assert!(output.contains("open_trades=1"));
     }
+
+    #[test]
+    fn render_backtest_monthly_reports_fee_drag_and_benchmark_per_month() {
+        let trade = |trade_id: usize, exit_ms: i64, gross_pnl: f64, fees: f64| BacktestTrade {
+            trade_id,
+            side: Side::Sell,
+            trigger_time: Utc
+                .timestamp_millis_opt(exit_ms - 2_000)
+                .single()
+                .expect("timestamp"),
+            entry_time: Utc
+                .timestamp_millis_opt(exit_ms - 1_000)
+                .single()
+                .expect("timestamp"),
+            entry_price: 100.0,
+            stop_price: 101.0,
+            take_profit_price: 98.0,
+            qty: 1.0,
+            exit_time: Some(
+                Utc.timestamp_millis_opt(exit_ms)
+                    .single()
+                    .expect("timestamp"),
+            ),
+            exit_price: Some(100.0 - gross_pnl),
+            exit_reason: Some(ExitReason::TakeProfit),
+            gross_pnl: Some(gross_pnl),
+            fees: Some(fees),
+            net_pnl: Some(gross_pnl - fees),
+        };
+        let march = Utc
+            .with_ymd_and_hms(2026, 3, 10, 0, 0, 0)
+            .single()
+            .expect("march")
+            .timestamp_millis();
+        let april = Utc
+            .with_ymd_and_hms(2026, 4, 2, 0, 0, 0)
+            .single()
+            .expect("april")
+            .timestamp_millis();
+        let mut trades = vec![
+            trade(1, march, 8.0, 0.8),
+            trade(2, march + 60_000, 4.0, 0.4),
+            trade(3, april, -2.0, 0.2),
+        ];
+        trades[2].net_pnl = Some(-2.2);
+        trades.push(BacktestTrade {
+            exit_time: None,
+            exit_price: None,
+            exit_reason: None,
+            gross_pnl: None,
+            fees: None,
+            net_pnl: None,
+            ..trade(4, april, 0.0, 0.0)
+        });
+        let report = sample_report(
+            trades,
+            BacktestDatasetSummary {
+                mode: BinanceMode::Demo,
+                symbol: "BTCUSDT".to_string(),
+                symbol_found: true,
+                from: "2026-03-01".to_string(),
+                to: "2026-04-30".to_string(),
+                liquidation_events: 3,
+                book_ticker_events: 10,
+                agg_trade_events: 0,
+                derived_kline_1s_bars: 5,
+            },
+        );
+
+        let months = monthly_performance(&report, 4.5, DisplayTimeZone::Utc);
+        let output = render_backtest_monthly(&report, &months, 4.5);
+
+        assert_eq!(months.len(), 2);
+        assert!((months[1].starting_equity - 10_010.8).abs() < 1e-9);
+        assert!((months[0].benchmark_pnl - 38.219_178).abs() < 1e-5);
+        assert!(output.contains(
+            "month=2026-03 trades=2 gross_pnl=12.00 fees=1.20 funding=0.00 net_pnl=10.80 turnover=388.00 fee_drag_pct=10.00"
+        ));
+        assert!(output.contains("month=2026-04 trades=1 gross_pnl=-2.00 fees=0.20"));
+        assert!(output.contains("fee_drag_pct=10.00 return_pct=0.11 benchmark_pct=0.38"));
+        assert!(output.contains("total trades=3 gross_pnl=10.00 fees=1.40"));
+
+        let month_end = Utc
+            .with_ymd_and_hms(2026, 3, 31, 20, 0, 0)
+            .single()
+            .expect("month end")
+            .timestamp_millis();
+        let late = sample_report(vec![trade(1, month_end, 1.0, 0.1)], report.dataset.clone());
+        let seoul = DisplayTimeZone::parse("+09:00").expect("zone");
+        assert_eq!(
+            monthly_performance(&late, 0.0, DisplayTimeZone::Utc)[0].label(),
+            "2026-03"
+        );
+        assert_eq!(monthly_performance(&late, 0.0, seoul)[0].label(), "2026-04");
+    }
+
+    #[test]
+    fn render_exit_reason_breakdown_shares_trades_and_losses_per_template() {
+        let row = |template: &str, exit_reason, trades, net_pnl, loss_pnl| ExitReasonBreakdownRow {
+            template: template.to_string(),
+            exit_reason,
+            trades,
+            net_pnl,
+            loss_pnl,
+        };
+        let output = render_exit_reason_breakdown(&[
+            row(
+                "price-sma-cross-long",
+                ExitReason::SignalExit,
+                3,
+                -4.0,
+                -5.0,
+            ),
+            row("price-sma-cross-long", ExitReason::StopLoss, 1, -1.0, -1.0),
+            row("price-sma-cross-short", ExitReason::TakeProfit, 2, 3.0, 0.0),
+        ]);
+
+        assert!(output.contains("template=price-sma-cross-long trades=4 net_pnl=-5.00"));
+        assert!(output.contains(
+            "exit=signal_exit trades=3 share=75.00% net_pnl=-4.00 average_net_pnl=-1.33 loss_share=83.33%"
+        ));
+        assert!(output.contains("exit=stop_loss trades=1 share=25.00%"));
+        assert!(output.contains(
+            "exit=take_profit trades=2 share=100.00% net_pnl=3.00 average_net_pnl=1.50 loss_share=0.00%"
+        ));
+        assert_eq!(
+            render_exit_reason_breakdown(&[]),
+            "backtest exit reasons\nexits=none"
+        );
+    }
+
+    #[test]
+    fn render_backtest_comparison_lines_up_two_variants() {
+        let trade = |trade_id: usize, entry_ms: i64, net_pnl: f64| BacktestTrade {
+            trade_id,
+            side: Side::Sell,
+            trigger_time: Utc
+                .timestamp_millis_opt(entry_ms)
+                .single()
+                .expect("timestamp"),
+            entry_time: Utc
+                .timestamp_millis_opt(entry_ms)
+                .single()
+                .expect("timestamp"),
+            entry_price: 100.0,
+            stop_price: 101.0,
+            take_profit_price: 98.0,
+            qty: 1.0,
+            exit_time: Some(
+                Utc.timestamp_millis_opt(entry_ms + 60_000)
+                    .single()
+                    .expect("timestamp"),
+            ),
+            exit_price: Some(100.0 - net_pnl),
+            exit_reason: Some(ExitReason::TakeProfit),
+            gross_pnl: Some(net_pnl),
+            fees: Some(0.0),
+            net_pnl: Some(net_pnl),
+        };
+        let dataset = BacktestDatasetSummary {
+            mode: BinanceMode::Demo,
+            symbol: "BTCUSDT".to_string(),
+            symbol_found: true,
+            from: "2026-03-13".to_string(),
+            to: "2026-03-13".to_string(),
+            liquidation_events: 3,
+            book_ticker_events: 10,
+            agg_trade_events: 0,
+            derived_kline_1s_bars: 5,
+        };
+        let mut a = sample_report(
+            vec![
+                trade(1, 1_000_000, 2.0),
+                trade(2, 2_000_000, -1.0),
+                trade(3, 3_000_000, 1.5),
+            ],
+            dataset.clone(),
+        );
+        a.run_id = Some(3);
+        let mut b = sample_report(
+            vec![trade(1, 1_030_000, -1.0), trade(2, 5_000_000, 1.0)],
+            dataset,
+        );
+        b.run_id = Some(4);
+
+        let comparison = compare_backtests(&a, &b);
+        let output = render_backtest_comparison(&comparison);
+
+        assert_eq!(
+            comparison.a.equity_curve,
+            vec![10_000.0, 10_002.0, 10_001.0, 10_002.5]
+        );
+        assert!((comparison.a.max_drawdown - 1.0).abs() < 1e-9);
+        assert!((comparison.a.ev_gap() - (2.5 / 3.0 - 1.0)).abs() < 1e-9);
+        let overlap = comparison.overlap.expect("same instrument");
+        assert_eq!(
+            (overlap.shared, overlap.a_entries, overlap.b_entries),
+            (1, 3, 2)
+        );
+        assert!(output.contains("variant          run 3 liquidation-breakdown-short run 4"));
+        assert!(output.contains("win_rate         0.6667                   0.5000"));
+        assert!(output.contains("average_r        +0.83R                   +0.00R"));
+        assert!(output.contains("equity           ▁▇▄█"));
+        assert!(output.contains("signal_overlap shared=1 a=3 b=2 overlap_pct=25.00"));
+    }
 }
@@ -0,0 +1,163 @@
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UiLocale {
+    #[default]
+    English,
+    Korean,
+}
+
+impl UiLocale {
+    /// Reads the operator UI language from `SANDBOX_QUANT_UI_LOCALE`.
+    ///
+    /// Example:
+    /// - `ko`, `ko-KR`, `korean` -> `Korean`
+    /// - unset or anything else -> `English`
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        var("SANDBOX_QUANT_UI_LOCALE")
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let normalized = value.trim().to_ascii_lowercase();
+        match normalized.as_str() {
+            "en" | "en-us" | "english" => Some(Self::English),
+            "ko" | "ko-kr" | "kr" | "korean" => Some(Self::Korean),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Korean => "ko",
+        }
+    }
+}
+
+/// Operator shell chrome: the intro panel, prompt badges, shell notices,
+/// macro messages and error hints.
+///
+/// Command output stays English `key=value` lines, since scripts and the
+/// golden tests read them, and so do the help text and the GUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiText {
+    IntroMode,
+    IntroDirectory,
+    IntroModeHint,
+    PromptPositions,
+    PromptOrders,
+    StalenessFresh,
+    StalenessMarket,
+    StalenessAccount,
+    StalenessReconcile,
+    ModeSwitched,
+    IntroHelpHint,
+    StatusKey,
+    StatusBlackout,
+    ErrorHint,
+    MacroLibraryNotLoaded,
+    MacroLibraryUnsaved,
+    MacroRecordingStarted,
+    MacroFinishHint,
+    MacroNotRecording,
+    MacroSaved,
+    MacroPlayed,
+    MacroRemoved,
+    MacroBound,
+    MacroList,
+    MacroUnknown,
+    MacroTooDeep,
+}
+
+/// Looks up a UI label in the bundle for `locale`.
+///
+/// Example:
+/// - `(English, PromptPositions)` -> `pos`
+/// - `(Korean, PromptPositions)` -> `포지션`
+pub fn text(locale: UiLocale, key: UiText) -> &'static str {
+    match locale {
+        UiLocale::English => english(key),
+        UiLocale::Korean => korean(key),
+    }
+}
+
+/// Pads `value` with spaces to `width` terminal columns; a Hangul syllable
+/// takes two columns, so `format!("{:<N}")` would under-pad it.
+///
+/// Example:
+/// - `("모드:", 10)` -> `"모드:     "` (5 columns + 5 spaces)
+pub fn pad_display(value: &str, width: usize) -> String {
+    let padding = width.saturating_sub(value.width());
+    format!("{value}{}", " ".repeat(padding))
+}
+
+fn english(key: UiText) -> &'static str {
+    match key {
+        UiText::IntroMode => "mode:",
+        UiText::IntroDirectory => "directory:",
+        UiText::IntroModeHint => "/mode to change",
+        UiText::PromptPositions => "pos",
+        UiText::PromptOrders => "ord",
+        UiText::StalenessFresh => "fresh",
+        UiText::StalenessMarket => "market-stale",
+        UiText::StalenessAccount => "account-stale",
+        UiText::StalenessReconcile => "reconcile-stale",
+        UiText::ModeSwitched => "mode switched to",
+        UiText::IntroHelpHint => "type /help for commands",
+        UiText::StatusKey => "key",
+        UiText::StatusBlackout => "blackout",
+        UiText::ErrorHint => "hint:",
+        UiText::MacroLibraryNotLoaded => "macro library not loaded:",
+        UiText::MacroLibraryUnsaved => {
+            "macros from this session are not saved until it is repaired"
+        }
+        UiText::MacroRecordingStarted => "macro recording started",
+        UiText::MacroFinishHint => "finish with /macro stop",
+        UiText::MacroNotRecording => "no macro is being recorded",
+        UiText::MacroSaved => "macro saved",
+        UiText::MacroPlayed => "macro played",
+        UiText::MacroRemoved => "macro removed",
+        UiText::MacroBound => "macro bound",
+        UiText::MacroList => "macros",
+        UiText::MacroUnknown => "unknown macro:",
+        UiText::MacroTooDeep => "macro nesting deeper than",
+    }
+}
+
+fn korean(key: UiText) -> &'static str {
+    match key {
+        UiText::IntroMode => "모드:",
+        UiText::IntroDirectory => "경로:",
+        UiText::IntroModeHint => "/mode 로 변경",
+        UiText::PromptPositions => "포지션",
+        UiText::PromptOrders => "주문",
+        UiText::StalenessFresh => "최신",
+        UiText::StalenessMarket => "시세-지연",
+        UiText::StalenessAccount => "계정-지연",
+        UiText::StalenessReconcile => "대사-지연",
+        UiText::ModeSwitched => "모드 변경:",
+        UiText::IntroHelpHint => "/help 로 명령어 보기",
+        UiText::StatusKey => "키",
+        UiText::StatusBlackout => "진입-차단",
+        UiText::ErrorHint => "힌트:",
+        UiText::MacroLibraryNotLoaded => "매크로 라이브러리를 불러오지 못함:",
+        UiText::MacroLibraryUnsaved => "복구 전까지 이번 세션의 매크로는 저장되지 않음",
+        UiText::MacroRecordingStarted => "매크로 기록 시작",
+        UiText::MacroFinishHint => "/macro stop 으로 종료",
+        UiText::MacroNotRecording => "기록 중인 매크로 없음",
+        UiText::MacroSaved => "매크로 저장됨",
+        UiText::MacroPlayed => "매크로 실행됨",
+        UiText::MacroRemoved => "매크로 삭제됨",
+        UiText::MacroBound => "매크로 단축키 지정됨",
+        UiText::MacroList => "매크로",
+        UiText::MacroUnknown => "알 수 없는 매크로:",
+        UiText::MacroTooDeep => "매크로 중첩 한도 초과:",
+    }
+}
@@ -1,4 +1,8 @@
 pub mod backtest_output;
+pub mod locale;
 pub mod operator_output;
 pub mod operator_terminal;
+pub mod projection;
 pub mod recorder_output;
+pub mod theme;
+pub mod timezone;
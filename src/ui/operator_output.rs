@@ -1,11 +1,20 @@
+use chrono::Utc;
+
 use crate::app::bootstrap::BinanceMode;
-use crate::app::commands::{AppCommand, PortfolioView};
+use crate::app::commands::{AlertCommand, AppCommand, BasisCommand, OrderFilter, PortfolioView};
+use crate::execution::history::{strategy_version_stats, OrderHistory};
 use crate::execution::price_source::PriceSource;
 use crate::market_data::price_store::PriceStore;
+use crate::observability::event_routing::{event_level, LogSink};
+use crate::portfolio::adoption::ADOPTED_POSITION_TAG;
+use crate::portfolio::asset_exposure::aggregate_base_asset_exposure;
 use crate::portfolio::store::PortfolioStateStore;
 use crate::storage::event_log::EventLog;
-use crate::strategy::command::StrategyCommand;
+use crate::strategy::adaptive::ParamChange;
+use crate::strategy::command::{StrategyCommand, StrategyGroupAction};
+use crate::strategy::kpi::StrategyKpi;
 use crate::strategy::store::StrategyStore;
+use crate::ui::timezone::DisplayTimeZone;
 use std::collections::BTreeMap;
 
 pub fn render_command_output(
@@ -15,14 +24,1345 @@ pub fn render_command_output(
     event_log: &EventLog,
     strategy_store: &StrategyStore,
     mode: BinanceMode,
+    history: &OrderHistory,
 ) -> String {
     match command {
-        AppCommand::Portfolio(view) => render_portfolio_output(view, store, prices, event_log),
+        AppCommand::Portfolio(view) => {
+            render_portfolio_output(view, store, prices, event_log, history)
+        }
         AppCommand::RefreshAuthoritativeState => render_refresh_summary(store, prices, event_log),
         AppCommand::Execution(_) => render_execution_summary(event_log),
         AppCommand::Strategy(command) => {
             render_strategy_output(command, event_log, strategy_store, mode)
         }
+        AppCommand::Alert(command) => render_alert_output(command, event_log),
+        AppCommand::Basis(command) => render_basis_output(command, event_log),
+        AppCommand::ReloadConfig => render_config_reload(event_log),
+        AppCommand::Diagnostics => render_diagnostics(event_log),
+        AppCommand::ExportDiagnostics { .. } => render_diagnostics_export(event_log),
+        AppCommand::Dom(_) => render_dom_ladder(event_log),
+        AppCommand::OcoStatus => render_oco_lists(event_log),
+        AppCommand::IcebergStatus => render_icebergs(event_log),
+        AppCommand::HoldEvStatus => render_hold_ev(event_log),
+        AppCommand::ShadowStatus => render_shadow(event_log),
+        AppCommand::Flatten { .. } => render_flatten(event_log),
+        AppCommand::Panic { .. } => render_panic(event_log),
+        AppCommand::ExternalPositions => render_external_positions(event_log),
+        AppCommand::AdoptPosition { .. } => render_position_adopted(event_log),
+        AppCommand::InactivityStatus | AppCommand::ResumeEntries => {
+            render_inactivity_output(event_log)
+        }
+        AppCommand::News(_) => render_news_output(event_log),
+        AppCommand::RebuildEvents { .. } => render_events_rebuilt(event_log),
+    }
+}
+
+/// Example:
+/// - `events rebuilt from var/operator-events.jsonl (schema v1)`
+/// - `events=120 skipped=1 kept_in_memory=3 errors=2`
+/// - `versions v0=20 v1=100`
+/// - `domains execution=80 strategy=40`
+/// - `outcomes submitted=12 already-at-target=3`
+/// - `span 2026-10-01T09:00:00+00:00 -> 2026-10-02T17:30:00+00:00`
+fn render_events_rebuilt(event_log: &EventLog) -> String {
+    let Some(record) = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.events.rebuilt")
+    else {
+        return "events rebuild unavailable".to_string();
+    };
+    let payload = &record.payload;
+    let counts = |key: &str| {
+        payload[key]
+            .as_object()
+            .filter(|counts| !counts.is_empty())
+            .map(|counts| {
+                counts
+                    .iter()
+                    .map(|(name, count)| format!("{name}={}", count.as_u64().unwrap_or_default()))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let mut lines = vec![
+        format!(
+            "events rebuilt from {} (schema v{})",
+            payload["path"].as_str().unwrap_or("-"),
+            payload["schema_version"].as_u64().unwrap_or_default(),
+        ),
+        format!(
+            "events={} skipped={} kept_in_memory={} errors={}",
+            payload["events"].as_u64().unwrap_or_default(),
+            payload["skipped"].as_u64().unwrap_or_default(),
+            payload["kept_in_memory"].as_u64().unwrap_or_default(),
+            payload["errors"].as_u64().unwrap_or_default(),
+        ),
+    ];
+    if let Some(filter) = payload["domain_filter"]
+        .as_array()
+        .filter(|filter| !filter.is_empty())
+    {
+        lines.push(format!(
+            "domain_filter={}",
+            filter
+                .iter()
+                .filter_map(|domain| domain.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        ));
+    }
+    lines.push(format!("versions {}", counts("versions")));
+    lines.push(format!("domains {}", counts("domains")));
+    lines.push(format!("outcomes {}", counts("outcomes")));
+    let zone = DisplayTimeZone::from_env();
+    lines.push(format!(
+        "span {} -> {}",
+        payload["first_ts"]
+            .as_str()
+            .map_or("-".to_string(), |ts| zone.restamp(ts)),
+        payload["last_ts"]
+            .as_str()
+            .map_or("-".to_string(), |ts| zone.restamp(ts)),
+    ));
+    if let Some(instruments) = payload["instruments"]
+        .as_array()
+        .filter(|instruments| !instruments.is_empty())
+    {
+        lines.push(format!(
+            "instruments {}",
+            instruments
+                .iter()
+                .filter_map(|instrument| instrument.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Renders the latest inactivity flat mode event.
+///
+/// Example:
+/// - `inactivity flattened`
+/// - `idle_secs=1804 timeout_secs=1800 dropped_deferred=0`
+/// - `entries disabled; re-enable with /inactivity resume`
+pub fn render_inactivity_output(event_log: &EventLog) -> String {
+    let Some(record) = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind.starts_with("app.safety."))
+    else {
+        return "inactivity unavailable".to_string();
+    };
+    let payload = &record.payload;
+    let secs = |key: &str| {
+        payload[key]
+            .as_u64()
+            .map(|secs| secs.to_string())
+            .unwrap_or_else(|| "off".to_string())
+    };
+    let time = |key: &str| payload[key].as_str().unwrap_or("-").to_string();
+    let lines = match record.kind.as_str() {
+        "app.safety.inactivity_flattened" | "app.safety.inactivity_flatten_failed" => {
+            let mut lines = vec![
+                if record.kind.ends_with("failed") {
+                    "inactivity flatten failed".to_string()
+                } else {
+                    "inactivity flattened".to_string()
+                },
+                format!(
+                    "idle_secs={} timeout_secs={} dropped_deferred={}",
+                    secs("idle_secs"),
+                    secs("timeout_secs"),
+                    payload["dropped_deferred"].as_u64().unwrap_or_default(),
+                ),
+            ];
+            if let Some(error) = payload["error"].as_str() {
+                lines.push(format!("error={error}"));
+            }
+            if let Some(close_all) = payload["close_all"].as_object() {
+                lines.push(format!(
+                    "close_all submitted={} skipped={} rejected={}",
+                    close_all["submitted"].as_u64().unwrap_or_default(),
+                    close_all["skipped"].as_u64().unwrap_or_default(),
+                    close_all["rejected"].as_u64().unwrap_or_default(),
+                ));
+            }
+            lines.push("entries disabled; re-enable with /inactivity resume".to_string());
+            lines
+        }
+        "app.safety.entries_resumed" => vec![
+            "entries resumed".to_string(),
+            format!("timeout_secs={}", secs("timeout_secs")),
+            format!(
+                "was_disabled={}",
+                payload["was_disabled"].as_bool().unwrap_or_default()
+            ),
+        ],
+        _ => vec![
+            "inactivity".to_string(),
+            format!(
+                "timeout_secs={} idle_secs={}",
+                secs("timeout_secs"),
+                secs("idle_secs")
+            ),
+            format!("last_input_at={}", time("last_input_at")),
+            format!("refreshed_at={}", time("refreshed_at")),
+            format!("tripped_at={}", time("tripped_at")),
+            format!(
+                "entries_disabled={}",
+                payload["entries_disabled"].as_bool().unwrap_or_default()
+            ),
+        ],
+    };
+    lines.join("\n")
+}
+
+/// Example:
+/// - `external positions (1)`
+/// - `  - ETHUSDT market=Futures side=Sell qty=1.00000000 exchange_entry=3200.00000000`
+/// - `adopt with /adopt <instrument> <entry_price>`
+fn render_external_positions(event_log: &EventLog) -> String {
+    let payload = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.portfolio.external_listed")
+        .map(|record| record.payload.clone())
+        .unwrap_or_default();
+    let external = payload["external"].as_array().cloned().unwrap_or_default();
+    let adopted = payload["adopted"].as_array().cloned().unwrap_or_default();
+    let mut lines = vec![format!("external positions ({})", external.len())];
+    if external.is_empty() {
+        lines.push("  - none".to_string());
+    }
+    lines.extend(external.iter().map(|position| {
+        let signed_qty = position["signed_qty"].as_f64().unwrap_or_default();
+        format!(
+            "  - {} market={} side={} qty={:.8} exchange_entry={}",
+            position["instrument"].as_str().unwrap_or("-"),
+            position["market"].as_str().unwrap_or("-"),
+            if signed_qty < 0.0 { "Sell" } else { "Buy" },
+            signed_qty.abs(),
+            position["exchange_entry_price"]
+                .as_f64()
+                .map(|price| format!("{price:.8}"))
+                .unwrap_or_else(|| "-".to_string()),
+        )
+    }));
+    if !external.is_empty() {
+        lines.push("adopt with /adopt <instrument> <entry_price>".to_string());
+    }
+    lines.push(format!("adopted ({})", adopted.len()));
+    if adopted.is_empty() {
+        lines.push("  - none".to_string());
+    }
+    lines.extend(adopted.iter().map(|position| {
+        format!(
+            "  - {} market={} entry={:.8} tag={} adopted_at={}",
+            position["instrument"].as_str().unwrap_or("-"),
+            position["market"].as_str().unwrap_or("-"),
+            position["entry_price"].as_f64().unwrap_or_default(),
+            position["tag"].as_str().unwrap_or("-"),
+            position["adopted_at"]
+                .as_str()
+                .map_or("-".to_string(), |at| DisplayTimeZone::from_env()
+                    .restamp(at)),
+        )
+    }));
+    lines.join("\n")
+}
+
+fn render_position_adopted(event_log: &EventLog) -> String {
+    let Some(record) = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.portfolio.position_adopted")
+    else {
+        return "position adoption unavailable".to_string();
+    };
+    let payload = &record.payload;
+    [
+        "position adopted".to_string(),
+        format!(
+            "instrument={}",
+            payload["instrument"].as_str().unwrap_or("-")
+        ),
+        format!("market={}", payload["market"].as_str().unwrap_or("-")),
+        format!(
+            "signed_qty={:.8}",
+            payload["signed_qty"].as_f64().unwrap_or_default()
+        ),
+        format!(
+            "entry_price={:.8}",
+            payload["entry_price"].as_f64().unwrap_or_default()
+        ),
+        format!(
+            "exchange_entry_price={}",
+            payload["exchange_entry_price"]
+                .as_f64()
+                .map(|price| format!("{price:.8}"))
+                .unwrap_or_else(|| "-".to_string())
+        ),
+        format!("tag={}", payload["tag"].as_str().unwrap_or("-")),
+    ]
+    .join("\n")
+}
+
+/// Example:
+/// - `oco lists (1)`
+/// - `list=3 BTCUSDT sell qty=0.2 tp=66000 (oco-3-tp) sl=62000 (oco-3-sl-r1) status=working repairs=1`
+/// - `list=4 ETHUSDT sell qty=1 tp=3400 (oco-4-tp) sl=3000 limit=2990 (oco-4-sl) status=working repairs=0 venue_list=1929`
+fn render_oco_lists(event_log: &EventLog) -> String {
+    let lists = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.oco.listed")
+        .and_then(|record| record.payload["lists"].as_array().cloned())
+        .unwrap_or_default();
+    let mut lines = vec![format!("oco lists ({})", lists.len())];
+    if lists.is_empty() {
+        lines.push("none".to_string());
+    }
+    lines.extend(lists.iter().map(|list| {
+        let stop_limit = list["stop_limit_price"]
+            .as_f64()
+            .map(|price| format!(" limit={price}"))
+            .unwrap_or_default();
+        let venue_list = list["exchange_list_id"]
+            .as_str()
+            .map(|id| format!(" venue_list={id}"))
+            .unwrap_or_default();
+        format!(
+            "list={} {} {} qty={} tp={} ({}) sl={}{} ({}) status={} repairs={}{}",
+            list["list_id"].as_u64().unwrap_or_default(),
+            list["instrument"].as_str().unwrap_or("-"),
+            list["close_side"].as_str().unwrap_or("-"),
+            list["qty"].as_f64().unwrap_or_default(),
+            list["take_profit_price"].as_f64().unwrap_or_default(),
+            list["take_profit_order_id"].as_str().unwrap_or("-"),
+            list["stop_price"].as_f64().unwrap_or_default(),
+            stop_limit,
+            list["stop_order_id"].as_str().unwrap_or("-"),
+            list["status"].as_str().unwrap_or("-"),
+            list["repairs"].as_u64().unwrap_or_default(),
+            venue_list,
+        )
+    }));
+    lines.join("\n")
+}
+
+/// Example:
+/// - `icebergs (1)`
+/// - `parent=2 BTCUSDT buy 0.4/1 (40.0%) @ 64000 visible=0.2 slice=3 (ice-2-3)`
+fn render_icebergs(event_log: &EventLog) -> String {
+    let icebergs = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.iceberg.listed")
+        .and_then(|record| record.payload["icebergs"].as_array().cloned())
+        .unwrap_or_default();
+    let mut lines = vec![format!("icebergs ({})", icebergs.len())];
+    if icebergs.is_empty() {
+        lines.push("none".to_string());
+    }
+    lines.extend(icebergs.iter().map(|iceberg| {
+        format!(
+            "parent={} {} {} {}/{} ({:.1}%) @ {} visible={} slice={} ({})",
+            iceberg["parent_id"].as_u64().unwrap_or_default(),
+            iceberg["instrument"].as_str().unwrap_or("-"),
+            iceberg["side"].as_str().unwrap_or("-"),
+            iceberg["filled_qty"].as_f64().unwrap_or_default(),
+            iceberg["total_qty"].as_f64().unwrap_or_default(),
+            iceberg["progress_pct"].as_f64().unwrap_or_default(),
+            iceberg["price"].as_f64().unwrap_or_default(),
+            iceberg["visible_qty"].as_f64().unwrap_or_default(),
+            iceberg["slices"].as_u64().unwrap_or_default(),
+            iceberg["child_order_id"].as_str().unwrap_or("-"),
+        )
+    }));
+    lines.join("\n")
+}
+
+/// Example:
+/// - `paper shadow model=2bps (1)`
+/// - `watch=3 orders=2 qty live=0.1/0.1 slippage=4.0bps error=+2.0bps pnl live=10.00 paper=12.56 divergence=-2.56 (slippage -2.56 fills 0.00)`
+fn render_shadow(event_log: &EventLog) -> String {
+    let payload = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.shadow.listed")
+        .map(|record| record.payload.clone())
+        .unwrap_or_default();
+    let Some(modeled_bps) = payload["modeled_slippage_bps"].as_f64() else {
+        return "paper shadow off\nset SANDBOX_QUANT_PAPER_SHADOW to compare strategy fills"
+            .to_string();
+    };
+    let runs = payload["runs"].as_array().cloned().unwrap_or_default();
+    let mut lines = vec![format!(
+        "paper shadow model={modeled_bps}bps ({})",
+        runs.len()
+    )];
+    if runs.is_empty() {
+        lines.push("none".to_string());
+    }
+    lines.extend(runs.iter().map(|run| {
+        let bps = |key: &str, signed: bool| {
+            run[key].as_f64().map_or_else(
+                || "-".to_string(),
+                |value| {
+                    if signed {
+                        format!("{value:+.1}bps")
+                    } else {
+                        format!("{value:.1}bps")
+                    }
+                },
+            )
+        };
+        format!(
+            "watch={} orders={} qty live={}/{} slippage={} error={} pnl live={:.2} paper={:.2} divergence={:.2} (slippage {:.2} fills {:.2})",
+            run["watch_id"].as_u64().unwrap_or_default(),
+            run["orders"].as_u64().unwrap_or_default(),
+            run["live_qty"].as_f64().unwrap_or_default(),
+            run["paper_qty"].as_f64().unwrap_or_default(),
+            bps("realized_slippage_bps", false),
+            bps("model_error_bps", true),
+            run["live_pnl_usdt"].as_f64().unwrap_or_default(),
+            run["paper_pnl_usdt"].as_f64().unwrap_or_default(),
+            run["divergence_usdt"].as_f64().unwrap_or_default(),
+            run["slippage_usdt"].as_f64().unwrap_or_default(),
+            run["fill_gap_usdt"].as_f64().unwrap_or_default(),
+        )
+    }));
+    lines.join("\n")
+}
+
+/// Example:
+/// - `hold ev (1)`
+/// - `BTCUSDT profile=default price=50900 r=1.80 ev=-0.60R action=suggest_exit p_win=0.50 model=beta_binomial/v1:prior=0.5,strength=20`
+fn render_hold_ev(event_log: &EventLog) -> String {
+    let estimates = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.position.hold_ev_listed")
+        .and_then(|record| record.payload["estimates"].as_array().cloned())
+        .unwrap_or_default();
+    let mut lines = vec![format!("hold ev ({})", estimates.len())];
+    if estimates.is_empty() {
+        lines.push("none".to_string());
+    }
+    lines.extend(estimates.iter().map(|estimate| {
+        format!(
+            "{} profile={} price={} r={:.2} ev={:.2}R action={} p_win={:.2} model={} trades={}",
+            estimate["instrument"].as_str().unwrap_or("-"),
+            estimate["profile"].as_str().unwrap_or("-"),
+            estimate["price"].as_f64().unwrap_or_default(),
+            estimate["r_multiple"].as_f64().unwrap_or_default(),
+            estimate["expected_value_r"].as_f64().unwrap_or_default(),
+            estimate["action"].as_str().unwrap_or("-"),
+            estimate["win_probability"].as_f64().unwrap_or_default(),
+            estimate["model_version"].as_str().unwrap_or("-"),
+            estimate["trades"].as_u64().unwrap_or_default(),
+        )
+    }));
+    lines.join("\n")
+}
+
+/// Example:
+/// - `flatten BTCUSDT? signed_qty=0.2 open_orders=2`
+/// - `press ctrl+x again or run /flatten BTCUSDT confirm`
+fn render_flatten(event_log: &EventLog) -> String {
+    let Some(record) = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind.starts_with("app.execution.flatten"))
+    else {
+        return "flatten unavailable".to_string();
+    };
+    let payload = &record.payload;
+    let instrument = payload["instrument"].as_str().unwrap_or("-");
+    match record.kind.as_str() {
+        "app.execution.flatten_confirmation_required" => format!(
+            "flatten {instrument}? signed_qty={} open_orders={}\npress ctrl+x again or run /flatten {instrument} confirm",
+            payload["signed_qty"].as_f64().unwrap_or_default(),
+            payload["open_orders"].as_u64().unwrap_or_default(),
+        ),
+        "app.execution.flattened" => format!(
+            "flattened {instrument} cancelled={} cancel_failed={} close={}",
+            payload["cancelled"].as_array().map_or(0, Vec::len),
+            payload["cancel_failed"].as_array().map_or(0, Vec::len),
+            payload["outcome_kind"].as_str().unwrap_or("-"),
+        ),
+        _ => format!(
+            "flatten unavailable: {}",
+            payload["reason"].as_str().unwrap_or("-")
+        ),
+    }
+}
+
+/// Renders panic progress, one line per instrument, then the
+/// reconciliation summary in a box.
+///
+/// Example:
+/// - `[1/2] BTCUSDT cancelled=1 cancel_failed=0 close=Submitted`
+/// - `│ positions left   none │`
+fn render_panic(event_log: &EventLog) -> String {
+    let Some(record) = event_log.records.iter().rev().find(|record| {
+        record.kind.starts_with("app.safety.panic_") && record.kind != "app.safety.panic_progress"
+    }) else {
+        return "panic unavailable".to_string();
+    };
+    let payload = &record.payload;
+    if record.kind == "app.safety.panic_confirmation_required" {
+        let instruments = payload["instruments"]
+            .as_array()
+            .map(|instruments| {
+                instruments
+                    .iter()
+                    .filter_map(|instrument| instrument.as_str())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .filter(|instruments| !instruments.is_empty())
+            .unwrap_or_else(|| "none".to_string());
+        return format!(
+            "panic? flatten={instruments} open_orders={} active_watches={}\npress ctrl+p again or run /panic confirm",
+            payload["open_orders"].as_u64().unwrap_or_default(),
+            payload["active_watches"].as_u64().unwrap_or_default(),
+        );
+    }
+    let steps = payload["steps"].as_array().cloned().unwrap_or_default();
+    let mut lines = steps
+        .iter()
+        .map(|step| {
+            format!(
+                "[{}/{}] {} cancelled={} cancel_failed={} close={}",
+                step["step"].as_u64().unwrap_or_default(),
+                step["total"].as_u64().unwrap_or_default(),
+                step["instrument"].as_str().unwrap_or("-"),
+                step["cancelled"].as_array().map_or(0, Vec::len),
+                step["cancel_failed"].as_array().map_or(0, Vec::len),
+                step["outcome_kind"].as_str().unwrap_or("-"),
+            )
+        })
+        .collect::<Vec<_>>();
+    let count = |key: &str| payload[key].as_array().map_or(0, Vec::len);
+    let remaining_positions = payload["remaining_positions"]
+        .as_array()
+        .map(|positions| {
+            positions
+                .iter()
+                .filter_map(|instrument| instrument.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .filter(|positions| !positions.is_empty())
+        .unwrap_or_else(|| "none".to_string());
+    let remaining_open_orders = payload["remaining_open_orders"]
+        .as_u64()
+        .unwrap_or_default();
+    let status = if remaining_positions == "none" && remaining_open_orders == 0 {
+        "flat"
+    } else {
+        "CHECK MANUALLY"
+    };
+    let summary = [
+        format!(" panic summary: {status}"),
+        format!(" {:<16} {}", "watches disabled", count("disabled_watches")),
+        format!(" {:<16} {}", "instruments", steps.len()),
+        format!(
+            " {:<16} {}",
+            "orders cancelled",
+            steps
+                .iter()
+                .map(|step| step["cancelled"].as_array().map_or(0, Vec::len))
+                .sum::<usize>()
+        ),
+        format!(" {:<16} {remaining_positions}", "positions left"),
+        format!(" {:<16} {remaining_open_orders}", "orders left"),
+        format!(" {:<16} /inactivity resume", "re-enable"),
+    ];
+    let width = summary
+        .iter()
+        .map(|line| line.chars().count() + 1)
+        .max()
+        .unwrap_or_default();
+    lines.push(format!("╭{}╮", "─".repeat(width)));
+    lines.extend(summary.iter().map(|line| format!("│{line:<width$}│")));
+    lines.push(format!("╰{}╯", "─".repeat(width)));
+    lines.join("\n")
+}
+
+/// Example:
+/// - `dom BTCUSDT market=Futures levels=10 resting=1`
+/// - `    64001.0 |          - |      1.200 |`
+/// - `    64000.0 |      2.500 |          - | buy 0.010 web-1`
+fn render_dom_ladder(event_log: &EventLog) -> String {
+    let Some(record) = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.dom.loaded")
+    else {
+        return "dom unavailable".to_string();
+    };
+    let payload = &record.payload;
+    let mut lines = vec![format!(
+        "dom {} market={} levels={} resting={}",
+        payload["instrument"].as_str().unwrap_or("-"),
+        payload["market"].as_str().unwrap_or("-"),
+        payload["levels"].as_u64().unwrap_or_default(),
+        payload["resting_orders"].as_u64().unwrap_or_default(),
+    )];
+    let action = &payload["action"];
+    match action["command_kind"].as_str() {
+        Some("place_limit_order") => lines.push(format!(
+            "placed {} {} @ {}",
+            action["side"].as_str().unwrap_or("-").to_ascii_lowercase(),
+            action["qty"].as_f64().unwrap_or_default(),
+            action["price"].as_f64().unwrap_or_default(),
+        )),
+        Some("cancel_order") => lines.push(format!(
+            "cancelled {}",
+            action["client_order_id"].as_str().unwrap_or("-")
+        )),
+        _ => {}
+    }
+    let rows = payload["rows"].as_array().cloned().unwrap_or_default();
+    if rows.is_empty() {
+        lines.push("no depth".to_string());
+        return lines.join("\n");
+    }
+    let size = |value: &serde_json::Value| {
+        value
+            .as_f64()
+            .filter(|qty| *qty > f64::EPSILON)
+            .map(|qty| format!("{qty:.3}"))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    lines.push(format!(
+        "{:>12} | {:>10} | {:>10} | own",
+        "price", "bid", "ask"
+    ));
+    for row in rows {
+        let own = row["own_orders"]
+            .as_array()
+            .map(|orders| {
+                orders
+                    .iter()
+                    .map(|order| {
+                        format!(
+                            "{} {:.3} {}",
+                            order["side"].as_str().unwrap_or("-"),
+                            order["qty"].as_f64().unwrap_or_default(),
+                            order["client_order_id"].as_str().unwrap_or("-"),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+        lines.push(
+            format!(
+                "{:>12} | {:>10} | {:>10} | {}",
+                row["price"].as_f64().unwrap_or_default(),
+                size(&row["bid_qty"]),
+                size(&row["ask_qty"]),
+                own,
+            )
+            .trim_end()
+            .to_string(),
+        );
+    }
+    lines.join("\n")
+}
+
+/// Example:
+/// - `rss=182.4MB uptime=3600s`
+/// - `loop_lag last=1.2ms p95=25.0ms max=40.1ms`
+/// - `strategy_tick p95=120us samples=30 ticks_per_sec=0.50`
+/// - `rest order calls=12 errors=1 timeouts=1 mean=84ms p95<=100ms max=310ms`
+/// - `rest_budget fapi weight_per_min=1200 tokens=310 order=12/0 account=40/0 history=180/6 market_data=90/0`
+/// - `memory profile=low buffers=0.2MB events=500/500 orders=12/100 traces=0/0 latency_samples=40/128 news=0/20`
+fn render_diagnostics(event_log: &EventLog) -> String {
+    let Some(record) = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.diagnostics.sampled")
+    else {
+        return "diagnostics unavailable".to_string();
+    };
+    let snapshot = &record.payload["snapshot"];
+    let millis = |key: &str| {
+        snapshot[key]
+            .as_f64()
+            .map(|value| format!("{value:.1}ms"))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let overloaded = record.payload["overloaded"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let mut lines = vec![
+        "diagnostics".to_string(),
+        format!(
+            "rss={} uptime={}s",
+            snapshot["rss_bytes"]
+                .as_u64()
+                .map(|bytes| format!("{:.1}MB", bytes as f64 / 1_048_576.0))
+                .unwrap_or_else(|| "-".to_string()),
+            snapshot["uptime_secs"].as_u64().unwrap_or_default(),
+        ),
+        format!(
+            "loop_lag last={} p95={} max={}",
+            millis("loop_lag_last_ms"),
+            millis("loop_lag_p95_ms"),
+            millis("loop_lag_max_ms"),
+        ),
+        format!(
+            "strategy_tick p95={} samples={} ticks_per_sec={:.2}",
+            snapshot["strategy_tick_p95_us"]
+                .as_f64()
+                .map(|value| format!("{value:.0}us"))
+                .unwrap_or_else(|| "-".to_string()),
+            snapshot["strategy_tick_samples"]
+                .as_u64()
+                .unwrap_or_default(),
+            snapshot["ticks_per_sec"].as_f64().unwrap_or_default(),
+        ),
+        format!(
+            "load active_watches={} strategy_symbols={}",
+            snapshot["active_watches"].as_u64().unwrap_or_default(),
+            snapshot["strategy_symbols"].as_u64().unwrap_or_default(),
+        ),
+    ];
+    let rest = record.payload["rest"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    if rest.is_empty() {
+        lines.push("rest no calls yet".to_string());
+    }
+    lines.extend(rest.iter().map(|endpoint| {
+        let millis = |key: &str| {
+            endpoint[key]
+                .as_f64()
+                .map(|value| format!("{value:.0}ms"))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        format!(
+            "rest {} calls={} errors={} timeouts={} mean={} p95<={} max={}",
+            endpoint["endpoint"].as_str().unwrap_or("-"),
+            endpoint["calls"].as_u64().unwrap_or_default(),
+            endpoint["errors"].as_u64().unwrap_or_default(),
+            endpoint["timeouts"].as_u64().unwrap_or_default(),
+            millis("mean_ms"),
+            millis("p95_ms"),
+            millis("max_ms"),
+        )
+    }));
+    let budget = &record.payload["rest_budget"];
+    let venues = budget["venues"].as_array().into_iter().flatten();
+    lines.extend(venues.map(|venue| {
+        format!(
+            "rest_budget {} weight_per_min={:.0} tokens={:.0} {}",
+            venue["venue"].as_str().unwrap_or("-"),
+            budget["weight_per_min"].as_f64().unwrap_or_default(),
+            venue["tokens"].as_f64().unwrap_or_default(),
+            venue["categories"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|category| format!(
+                    "{}={}/{}",
+                    category["category"].as_str().unwrap_or("-"),
+                    category["granted"].as_u64().unwrap_or_default(),
+                    category["refused"].as_u64().unwrap_or_default(),
+                ))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }));
+    let memory = &record.payload["memory"];
+    if let Some(buffers) = memory["buffers"].as_array() {
+        lines.push(format!(
+            "memory profile={} buffers={:.1}MB {}",
+            memory["profile"].as_str().unwrap_or("-"),
+            memory["buffer_bytes"].as_u64().unwrap_or_default() as f64 / 1_048_576.0,
+            buffers
+                .iter()
+                .map(|buffer| format!(
+                    "{}={}/{}",
+                    buffer["buffer"].as_str().unwrap_or("-"),
+                    buffer["entries"].as_u64().unwrap_or_default(),
+                    buffer["limit"]
+                        .as_u64()
+                        .map(|limit| limit.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ));
+    }
+    if overloaded.is_empty() {
+        lines.push("status=ok".to_string());
+    } else {
+        lines.push(format!(
+            "status=overloaded ({}); stop watches or narrow symbols",
+            overloaded.join(",")
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Example:
+/// - `diagnostics bundle var/diagnostics-20260412-093015.zip (48.2KB)`
+/// - `files version.json config.json session.json network.json events.jsonl`
+/// - `events=500 secrets redacted; attach the file to the bug report`
+fn render_diagnostics_export(event_log: &EventLog) -> String {
+    let Some(record) = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.diagnostics.exported")
+    else {
+        return "diagnostics bundle unavailable".to_string();
+    };
+    let payload = &record.payload;
+    [
+        format!(
+            "diagnostics bundle {} ({:.1}KB)",
+            payload["path"].as_str().unwrap_or("-"),
+            payload["bytes"].as_u64().unwrap_or_default() as f64 / 1024.0,
+        ),
+        format!(
+            "files {}",
+            payload["files"]
+                .as_array()
+                .map(|files| files
+                    .iter()
+                    .filter_map(|file| file.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "))
+                .unwrap_or_default()
+        ),
+        format!(
+            "events={} secrets redacted; attach the file to the bug report",
+            payload["events"].as_u64().unwrap_or_default()
+        ),
+    ]
+    .join("\n")
+}
+
+fn render_config_reload(event_log: &EventLog) -> String {
+    let changes = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.config.reloaded")
+        .and_then(|record| record.payload["changes"].as_array().cloned())
+        .unwrap_or_default();
+    let mut lines = vec![
+        "config reloaded".to_string(),
+        format!("changes={}", changes.len()),
+    ];
+    lines.extend(changes.iter().map(|change| {
+        format!(
+            "- {}: {} -> {}",
+            change["key"].as_str().unwrap_or("unknown"),
+            change["before"].as_str().unwrap_or("none"),
+            change["after"].as_str().unwrap_or("none"),
+        )
+    }));
+    lines.join("\n")
+}
+
+/// Notification lines for alerts that fired after event index `since`, and
+/// for the orders they hold until `/alert confirm`.
+pub fn render_triggered_alerts(event_log: &EventLog, since: usize) -> Option<String> {
+    let lines = event_log
+        .records
+        .iter()
+        .skip(since)
+        .filter_map(|record| {
+            let id = record.payload["alert_id"].as_u64().unwrap_or_default();
+            match record.kind.as_str() {
+                "app.alert.triggered" => Some(format!(
+                    "alert triggered id={id} instrument={} price={:.2} action={}",
+                    record.payload["instrument"].as_str().unwrap_or("unknown"),
+                    record.payload["price"].as_f64().unwrap_or_default(),
+                    record.payload["action"].as_str().unwrap_or("unknown"),
+                )),
+                "app.alert.confirmation_required" => Some(format!(
+                    "alert order held id={id} action={} confirm=/alert confirm {id}",
+                    record.payload["action"].as_str().unwrap_or("unknown"),
+                )),
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Renders the latest `/news` event.
+///
+/// Example:
+/// - `news feed=https://example.com/feed.xml every=300s blackout=1800s`
+/// - `last_polled_at=2026-10-18T09:00:00+00:00 blackout=off`
+/// - `! Binance Will Delist [BTC] Margin Pairs (2026-10-18T08:55:00+00:00)`
+/// - `  ETH ETF inflows slow`
+pub fn render_news_output(event_log: &EventLog) -> String {
+    let Some(record) = event_log.records.iter().rev().find(|record| {
+        matches!(
+            record.kind.as_str(),
+            "app.news.listed" | "app.news.blackout_started" | "app.news.blackout_lifted"
+        )
+    }) else {
+        return "news unavailable".to_string();
+    };
+    let payload = &record.payload;
+    match record.kind.as_str() {
+        "app.news.blackout_started" => [
+            format!(
+                "news blackout: new entries held for {}m",
+                payload["minutes"].as_u64().unwrap_or_default()
+            ),
+            format!("until={}", payload["until"].as_str().unwrap_or("-")),
+            "exits still go through; lift with /news blackout off".to_string(),
+        ]
+        .join("\n"),
+        "app.news.blackout_lifted" => format!(
+            "news blackout lifted was_active={}",
+            payload["was_active"].as_bool().unwrap_or_default()
+        ),
+        _ => {
+            let zone = DisplayTimeZone::from_env();
+            let mut lines =
+                vec![
+                    format!("news feed={}", payload["feed"].as_str().unwrap_or("off")),
+                    format!(
+                        "last_polled_at={} blackout={}",
+                        payload["last_polled_at"]
+                            .as_str()
+                            .map_or("-".to_string(), |at| zone.restamp(at)),
+                        payload["blackout_until"].as_str().map_or(
+                            "off".to_string(),
+                            |until| format!("until {}", zone.restamp(until))
+                        ),
+                    ),
+                ];
+            if let Some(error) = payload["last_error"].as_str() {
+                lines.push(format!("last_error={error}"));
+            }
+            let headlines = payload["headlines"].as_array().cloned().unwrap_or_default();
+            if headlines.is_empty() {
+                lines.push("no headlines".to_string());
+            }
+            lines.extend(headlines.iter().map(|headline| {
+                format!(
+                    "{} {}{}",
+                    if headline["major"].as_bool() == Some(true) {
+                        "!"
+                    } else {
+                        " "
+                    },
+                    headline["highlighted"].as_str().unwrap_or("-"),
+                    headline["published_at"]
+                        .as_str()
+                        .map(|time| format!(" ({})", zone.restamp(time)))
+                        .unwrap_or_default(),
+                )
+            }));
+            lines.join("\n")
+        }
+    }
+}
+
+/// Headlines and poll failures logged after event index `since`.
+///
+/// Example:
+/// - `news MAJOR Binance Will Delist [BTC] Margin Pairs`
+/// - `press ctrl+b to hold new entries (/news blackout)`
+/// - `news [ETH] ETF inflows slow`
+pub fn render_news_headlines(event_log: &EventLog, since: usize) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut major = false;
+    for record in event_log.records.iter().skip(since) {
+        match record.kind.as_str() {
+            "app.news.headline" => {
+                let is_major = record.payload["major"].as_bool() == Some(true);
+                major |= is_major;
+                lines.push(format!(
+                    "news {}{}",
+                    if is_major { "MAJOR " } else { "" },
+                    record.payload["highlighted"].as_str().unwrap_or("-"),
+                ));
+            }
+            "app.news.poll_failed" => lines.push(format!(
+                "news poll failed error={}",
+                record.payload["error"].as_str().unwrap_or("unknown"),
+            )),
+            "app.symbol.halt_notice" => lines.push(format!(
+                "halt notice for {}; checking symbol status",
+                record.payload["assets"]
+                    .as_array()
+                    .map(|assets| assets
+                        .iter()
+                        .filter_map(|asset| asset.as_str())
+                        .collect::<Vec<_>>()
+                        .join(","))
+                    .unwrap_or_default(),
+            )),
+            _ => {}
+        }
+    }
+    if major {
+        lines.push("press ctrl+b to hold new entries (/news blackout)".to_string());
+    }
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Credential rotation and permission probe lines logged after event index
+/// `since`.
+///
+/// Example:
+/// - `api keys rotated mode=real api_key=****Eh8A`
+/// - `api key rotation failed mode=real error=missing configuration: BINANCE_REAL_API_KEY`
+/// - `api key permissions mode=real trade=spot (no futures; symbols resolve to spot)`
+pub fn render_credential_rotation(event_log: &EventLog, since: usize) -> Option<String> {
+    let lines = event_log
+        .records
+        .iter()
+        .skip(since)
+        .filter_map(|record| {
+            let mode = record.payload["mode"].as_str().unwrap_or("unknown");
+            match record.kind.as_str() {
+                "app.config.credentials_rotated" => Some(format!(
+                    "api keys rotated mode={mode} api_key={}",
+                    record.payload["api_key"].as_str().unwrap_or("unknown"),
+                )),
+                "app.config.credentials_rotation_failed" => Some(format!(
+                    "api key rotation failed mode={mode} error={}",
+                    record.payload["error"].as_str().unwrap_or("unknown"),
+                )),
+                "app.account.permissions_probed" => Some(format!(
+                    "api key permissions mode={mode} trade={}{}",
+                    record.payload["permissions"].as_str().unwrap_or("unknown"),
+                    if record.payload["read_only"].as_bool() == Some(true) {
+                        " (read-only; orders disabled)"
+                    } else if record.payload["futures"].as_bool() == Some(false) {
+                        " (no futures; symbols resolve to spot)"
+                    } else {
+                        ""
+                    },
+                )),
+                "app.account.permissions_probe_failed" => Some(format!(
+                    "api key permission probe failed mode={mode} error={}",
+                    record.payload["error"].as_str().unwrap_or("unknown"),
+                )),
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Fills, closed orders and stream drops pushed by the user data stream
+/// after event index `since`.
+///
+/// Example:
+/// - `fill BTCUSDT buy 0.004 @ 64000.5 (0.004/0.01 partially_filled)`
+/// - `order canceled ETHUSDT sq-entry-7`
+/// - `user stream futures dropped; account marked stale error=stream closed`
+pub fn render_user_stream_updates(event_log: &EventLog, since: usize) -> Option<String> {
+    let lines = event_log
+        .records
+        .iter()
+        .skip(since)
+        .filter_map(|record| {
+            let payload = &record.payload;
+            let field = |key: &str| payload[key].as_str().unwrap_or("unknown").to_string();
+            let number = |key: &str| payload[key].as_f64().unwrap_or_default();
+            match record.kind.as_str() {
+                "app.user_stream.order_update" if number("fill_qty") > 0.0 => Some(format!(
+                    "fill {} {} {} @ {} ({}/{} {})",
+                    field("instrument"),
+                    field("side"),
+                    number("fill_qty"),
+                    payload["fill_price"]
+                        .as_f64()
+                        .map(|price| price.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    number("executed_qty"),
+                    number("orig_qty"),
+                    field("status"),
+                )),
+                "app.user_stream.order_update"
+                    if payload["open"].as_bool() == Some(false) && field("status") != "filled" =>
+                {
+                    Some(format!(
+                        "order {} {} {}",
+                        field("status"),
+                        field("instrument"),
+                        field("client_order_id"),
+                    ))
+                }
+                "app.user_stream.disconnected" => Some(format!(
+                    "user stream {} dropped; account marked stale error={}",
+                    field("market"),
+                    field("error"),
+                )),
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| {
+        lines.join(
+            "
+",
+        )
+    })
+}
+
+/// Halt, delisting and resume lines logged after event index `since`.
+///
+/// Example:
+/// - `symbol halted BTCUSDT status=BREAK (entries held)`
+/// - `strategy #3 paused: BTCUSDT halted status=BREAK`
+/// - `symbol flattened BTCUSDT`
+/// - `symbol trading again BTCUSDT (was BREAK)`
+pub fn render_symbol_status(event_log: &EventLog, since: usize) -> Option<String> {
+    let lines = event_log
+        .records
+        .iter()
+        .skip(since)
+        .filter_map(|record| {
+            let payload = &record.payload;
+            let field = |key: &str| payload[key].as_str().unwrap_or("unknown").to_string();
+            match record.kind.as_str() {
+                "app.symbol.halted" => Some(format!(
+                    "symbol {} {} status={} (entries held)",
+                    field("kind"),
+                    field("instrument"),
+                    field("status"),
+                )),
+                "app.symbol.resumed" => Some(format!(
+                    "symbol trading again {} (was {})",
+                    field("instrument"),
+                    field("previous_status"),
+                )),
+                "app.strategy.paused_halted" => Some(format!(
+                    "strategy #{} paused: {} halted status={}",
+                    payload["watch_id"].as_u64().unwrap_or_default(),
+                    field("halted_symbol"),
+                    field("status"),
+                )),
+                "app.symbol.flattened" => Some(format!("symbol flattened {}", field("instrument"))),
+                "app.symbol.flatten_failed" => Some(format!(
+                    "symbol flatten failed {} error={}",
+                    field("instrument"),
+                    field("error"),
+                )),
+                "app.symbol.status_check_failed" => Some(format!(
+                    "symbol status check failed {} error={}",
+                    field("instrument"),
+                    field("error"),
+                )),
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Notice lines for events routed to the notifier after event index `since`.
+///
+/// Example:
+/// - `notice level=error kind=app.execution.deferred_failed instrument=BTCUSDT`
+pub fn render_event_notices(event_log: &EventLog, since: usize) -> Option<String> {
+    let lines = event_log
+        .records
+        .iter()
+        .skip(since)
+        .filter(|record| record.kind != "app.alert.triggered")
+        .filter(|record| {
+            event_log
+                .routing
+                .sinks_for(record)
+                .contains(&LogSink::Notifier)
+        })
+        .map(|record| {
+            let mut line = format!(
+                "notice level={} kind={}",
+                event_level(&record.kind).as_str(),
+                record.kind
+            );
+            for key in ["instrument", "watch_id", "error"] {
+                match &record.payload[key] {
+                    serde_json::Value::Null => {}
+                    serde_json::Value::String(value) => line.push_str(&format!(" {key}={value}")),
+                    value => line.push_str(&format!(" {key}={value}")),
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn render_alert_output(command: &AlertCommand, event_log: &EventLog) -> String {
+    let Some(last_event) = event_log.records.last() else {
+        return "alert\nlast_event=none".to_string();
+    };
+    let describe = |payload: &serde_json::Value| {
+        format!(
+            "- id={} instrument={} price={:.2} action={} state={}",
+            payload["alert_id"].as_u64().unwrap_or_default(),
+            payload["instrument"].as_str().unwrap_or("unknown"),
+            payload["price"].as_f64().unwrap_or_default(),
+            payload["action"].as_str().unwrap_or("unknown"),
+            if payload["triggered_at"].is_null() {
+                "armed"
+            } else if payload["awaiting_confirmation"].as_bool() == Some(true) {
+                "awaiting_confirmation"
+            } else {
+                "triggered"
+            },
+        )
+    };
+    match command {
+        AlertCommand::Add { .. } => {
+            format!("alert added\n{}", describe(&last_event.payload))
+        }
+        AlertCommand::Remove { .. } => {
+            format!("alert removed\n{}", describe(&last_event.payload))
+        }
+        AlertCommand::Confirm { .. } => format!(
+            "alert confirmed\n{}\noutcome={}",
+            describe(&last_event.payload),
+            last_event.payload["outcome"].as_str().unwrap_or("unknown")
+        ),
+        AlertCommand::List => {
+            let alerts = last_event.payload["alerts"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            let mut lines = vec![format!("price alerts ({})", alerts.len())];
+            if alerts.is_empty() {
+                lines.push("- none".to_string());
+            } else {
+                lines.extend(alerts.iter().map(describe));
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+/// Example:
+/// - `basis watches (1)`
+/// - `- id=1 BTCUSDT hedged entry=30.0bps exit=5.0bps funding>=15.00% target=0.10`
+/// - `  basis=12.40bps funding_apr=10.95% carry=1.96 funding=0.20 round_trips=0 realized=0.00 version=3f9a0c1d`
+fn render_basis_output(command: &BasisCommand, event_log: &EventLog) -> String {
+    let latest = |kind: &str| {
+        event_log
+            .records
+            .iter()
+            .rev()
+            .find(|record| record.kind == kind)
+            .map(|record| &record.payload)
+    };
+    let usdt = |value: &serde_json::Value| {
+        value
+            .as_f64()
+            .map(|value| format!("{value:.2}"))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let describe = |payload: &serde_json::Value| {
+        let funding = payload["funding_apr_threshold_pct"]
+            .as_f64()
+            .map(|apr| format!(" funding>={apr:.2}%"))
+            .unwrap_or_default();
+        let basis = payload["basis_bps"]
+            .as_f64()
+            .map(|bps| format!("{bps:.2}bps"))
+            .unwrap_or_else(|| "-".to_string());
+        let funding_apr = payload["funding_apr_pct"]
+            .as_f64()
+            .map(|apr| format!("{apr:.2}%"))
+            .unwrap_or_else(|| "-".to_string());
+        format!(
+            "- id={} {} {} entry={:.1}bps exit={:.1}bps{funding} target={:.2}\n  basis={basis} funding_apr={funding_apr} carry={} funding={} round_trips={} realized={} version={}",
+            payload["watch_id"].as_u64().unwrap_or_default(),
+            payload["instrument"].as_str().unwrap_or("unknown"),
+            if payload["hedged"].as_bool().unwrap_or_default() {
+                "hedged"
+            } else {
+                "flat"
+            },
+            payload["entry_bps"].as_f64().unwrap_or_default(),
+            payload["exit_bps"].as_f64().unwrap_or_default(),
+            payload["target"].as_f64().unwrap_or_default(),
+            usdt(&payload["carry_pnl_usdt"]),
+            usdt(&payload["funding_accrued_usdt"]),
+            payload["round_trips"].as_u64().unwrap_or_default(),
+            usdt(&payload["realized_carry_usdt"]),
+            payload["version"].as_str().unwrap_or("-"),
+        )
+    };
+    match command {
+        BasisCommand::Start { .. } => match latest("app.strategy.basis_started") {
+            Some(payload) => format!(
+                "basis watch started via {}\n{}",
+                payload["synthetic"].as_str().unwrap_or("unknown"),
+                describe(payload)
+            ),
+            None => "basis watch unavailable".to_string(),
+        },
+        BasisCommand::Stop { .. } => {
+            let Some(payload) = latest("app.strategy.basis_stopped") else {
+                return "basis watch unavailable".to_string();
+            };
+            let mut lines = vec!["basis watch stopped".to_string(), describe(payload)];
+            if let Some(exited) = latest("app.strategy.basis_exited")
+                .filter(|exited| exited["watch_id"] == payload["watch_id"])
+            {
+                lines.push(format!(
+                    "unwound basis_pnl={} funding={} carry={}",
+                    usdt(&exited["basis_pnl_usdt"]),
+                    usdt(&exited["funding_accrued_usdt"]),
+                    usdt(&exited["carry_pnl_usdt"]),
+                ));
+            }
+            lines.join("\n")
+        }
+        BasisCommand::List => {
+            let watches = latest("app.strategy.basis_listed")
+                .and_then(|payload| payload["watches"].as_array().cloned())
+                .unwrap_or_default();
+            let mut lines = vec![format!("basis watches ({})", watches.len())];
+            if watches.is_empty() {
+                lines.push("- none".to_string());
+            } else {
+                lines.extend(watches.iter().map(describe));
+            }
+            lines.join("\n")
+        }
     }
 }
 
@@ -48,125 +1388,707 @@ fn render_strategy_output(
             }
             lines.join("\n")
         }
-        StrategyCommand::List => {
-            let watches = store.active_watches(mode);
+        StrategyCommand::List { query } => {
+            let active = store.active_watches(mode);
+            let watches = active
+                .iter()
+                .copied()
+                .filter(|watch| query.matches(watch))
+                .collect::<Vec<_>>();
+            let (rows, shown) = query.paginate(&watches);
             let mut lines = vec![
                 "strategy watches".to_string(),
                 format!("mode={}", mode.as_str()),
-                format!("active={}", watches.len()),
+                format!("active={}", active.len()),
             ];
-            if watches.is_empty() {
+            if let Some(search) = &query.search {
+                lines.push(format!("search={search} matches={}", watches.len()));
+            }
+            lines.push(shown);
+            if rows.is_empty() {
                 lines.push("- none".to_string());
             } else {
-                lines.extend(watches.into_iter().map(|watch| {
+                let now = Utc::now();
+                lines.extend(rows.iter().map(|watch| {
                     format!(
-                        "- id={} template={} instrument={} state={} step={}/{}",
+                        "- id={} template={} instrument={} state={} step={}/{} group={}{}",
                         watch.id,
                         watch.template.slug(),
                         watch.instrument.0,
                         watch.state.as_str(),
                         watch.current_step,
-                        watch.template.steps().len()
+                        watch.template.steps().len(),
+                        watch.group.as_deref().unwrap_or("none"),
+                        watch
+                            .kpi
+                            .as_ref()
+                            .map(|kpi| format!(" kpi={}", kpi.chip(now)))
+                            .unwrap_or_default(),
                     )
                 }));
             }
             lines.join("\n")
         }
-        StrategyCommand::History => {
-            let history = store.history(mode);
+        StrategyCommand::History { query } => {
+            let history = store.history(mode);
+            let runs = history
+                .iter()
+                .rev()
+                .copied()
+                .filter(|watch| query.matches(watch))
+                .collect::<Vec<_>>();
+            let (rows, shown) = query.paginate(&runs);
+            let mut lines = vec![
+                "strategy history".to_string(),
+                format!("mode={}", mode.as_str()),
+                format!("runs={}", history.len()),
+            ];
+            if let Some(search) = &query.search {
+                lines.push(format!("search={search} matches={}", runs.len()));
+            }
+            lines.push(shown);
+            if rows.is_empty() {
+                lines.push("- none".to_string());
+            } else {
+                lines.extend(rows.iter().map(|watch| {
+                    format!(
+                        "- id={} template={} instrument={} state={} updated_at={}",
+                        watch.id,
+                        watch.template.slug(),
+                        watch.instrument.0,
+                        watch.state.as_str(),
+                        DisplayTimeZone::from_env().rfc3339(watch.updated_at),
+                    )
+                }));
+            }
+            lines.join("\n")
+        }
+        StrategyCommand::Show { watch_id } => {
+            let Some(watch) = store.get(mode, *watch_id) else {
+                return format!(
+                    "strategy watch\nmode={}\nwatch_id={watch_id}\nstate=missing",
+                    mode.as_str()
+                );
+            };
+            let mut lines = vec![
+                "strategy watch".to_string(),
+                format!("mode={}", mode.as_str()),
+                format!("watch_id={}", watch.id),
+                format!("template={}", watch.template.slug()),
+                format!("instrument={}", watch.instrument.0),
+                format!("version={}", watch.version()),
+                format!("state={}", watch.state.as_str()),
+                format!(
+                    "current_step={}/{}",
+                    watch.current_step,
+                    watch.template.steps().len()
+                ),
+                format!("risk_pct={}", watch.config.risk_pct),
+            ];
+            if let Some(ramp) = &watch.ramp {
+                lines.push(format!("ramp={}", ramp.label()));
+                lines.push(format!("effective_risk_pct={}", watch.effective_risk_pct()));
+            }
+            lines.extend([
+                format!("win_rate={}", watch.config.win_rate),
+                format!("r_multiple={}", watch.config.r_multiple),
+                format!(
+                    "max_entry_slippage_pct={}",
+                    watch.config.max_entry_slippage_pct
+                ),
+            ]);
+            if let Some(adaptive) = &watch.adaptive {
+                lines.push(format!("adapt={}", adaptive.label()));
+                lines.extend(
+                    adaptive
+                        .history
+                        .iter()
+                        .enumerate()
+                        .map(|(index, adaptation)| {
+                            format!(
+                                "- adaptation={} at={} trades={} win_rate={:.2} pnl={:.2} {}",
+                                index + 1,
+                                DisplayTimeZone::from_env().rfc3339(adaptation.at),
+                                adaptation.trades,
+                                adaptation.win_rate,
+                                adaptation.pnl,
+                                render_param_changes(&adaptation.changes),
+                            )
+                        }),
+                );
+            }
+            if let Some(ledger) = &watch.ledger {
+                lines.push(format!("seed_capital_usdt={:.2}", ledger.seed_capital_usdt));
+                lines.push(format!("equity_usdt={:.2}", ledger.equity_usdt()));
+                lines.push(format!("realized_pnl_usdt={:.2}", ledger.realized_pnl_usdt));
+                lines.push(format!(
+                    "unrealized_pnl_usdt={:.2}",
+                    ledger.unrealized_pnl_usdt
+                ));
+                lines.push(format!("roi_pct={:.2}", ledger.roi() * 100.0));
+                lines.push(format!(
+                    "risk_budget_usdt={:.2}",
+                    ledger.risk_budget_usdt(watch.effective_risk_pct())
+                ));
+            }
+            if let Some(kpi) = &watch.kpi {
+                lines.extend(render_kpi_lines(kpi));
+            }
+            let r_multiples = &watch.r_multiples;
+            lines.push(format!("r_multiples {}", r_multiples.stats.summary()));
+            if r_multiples.stats.trades > 0 {
+                lines.push(format!(
+                    "r_distribution {}",
+                    r_multiples.stats.distribution_label()
+                ));
+            }
+            if let Some(risk) = r_multiples.initial_risk_per_unit() {
+                lines.push(format!("open_trade_risk_per_unit={risk}"));
+            }
+            for (index, step) in watch.template.steps().iter().enumerate() {
+                let marker = if watch.current_step == index + 1 {
+                    ">"
+                } else {
+                    "-"
+                };
+                lines.push(format!("{marker} {}. {}", index + 1, step));
+            }
+            lines.push(format!("debug={}", if watch.debug { "on" } else { "off" }));
+            if watch.debug || store.trace(watch.id).is_some() {
+                lines.push("recent trace".to_string());
+                lines.extend(render_trace_lines(
+                    store,
+                    watch.id,
+                    STRATEGY_SHOW_TRACE_LIMIT,
+                ));
+            }
+            lines.join("\n")
+        }
+        StrategyCommand::Start { template, .. } => {
+            let Some(last_event) = event_log.records.last() else {
+                return "strategy started\nlast_event=none".to_string();
+            };
+            let mut rendered = format!(
+                "strategy started\nmode={}\nwatch_id={}\ntemplate={}\ninstrument={}\nstate={}\nrisk_pct={}\nwin_rate={}\nr_multiple={}\nmax_entry_slippage_pct={}\ncurrent_step={}/{}",
+                last_event.payload["mode"].as_str().unwrap_or("unknown"),
+                last_event.payload["watch_id"].as_u64().unwrap_or_default(),
+                last_event.payload["template"].as_str().unwrap_or("unknown"),
+                last_event.payload["instrument"].as_str().unwrap_or("unknown"),
+                last_event.payload["state"].as_str().unwrap_or("unknown"),
+                last_event.payload["risk_pct"].as_f64().unwrap_or_default(),
+                last_event.payload["win_rate"].as_f64().unwrap_or_default(),
+                last_event.payload["r_multiple"].as_f64().unwrap_or_default(),
+                last_event.payload["max_entry_slippage_pct"].as_f64().unwrap_or_default(),
+                last_event.payload["current_step"].as_u64().unwrap_or_default(),
+                template.steps().len(),
+            );
+            if let Some(capital) = last_event.payload["seed_capital_usdt"].as_f64() {
+                rendered.push_str(&format!("\nseed_capital_usdt={capital:.2}"));
+            }
+            if let Some(ramp) = last_event.payload["ramp"].as_str() {
+                rendered.push_str(&format!("\nramp={ramp}"));
+            }
+            if let Some(adapt) = last_event.payload["adapt"].as_str() {
+                rendered.push_str(&format!("\nadapt={adapt}"));
+            }
+            rendered
+        }
+        StrategyCommand::Stop { watch_id, .. } => {
+            let Some(last_event) = event_log.records.last() else {
+                return "strategy stopped\nlast_event=none".to_string();
+            };
+            match last_event.kind.as_str() {
+                "app.strategy.stop_confirmation_required" => {
+                    return render_stop_confirmation(*watch_id, &last_event.payload)
+                }
+                "app.strategy.stop_cancelled" => {
+                    return format!(
+                        "strategy stop cancelled\nwatch_id={watch_id}\ninstrument={}\nposition_resolution=keep",
+                        last_event.payload["instrument"]
+                            .as_str()
+                            .unwrap_or("unknown"),
+                    )
+                }
+                "app.strategy.stop_pending" => {
+                    return format!(
+                        "strategy stop pending\nwatch_id={watch_id}\ninstrument={}\nposition_resolution=flatten\nstops once the close fills",
+                        last_event.payload["instrument"]
+                            .as_str()
+                            .unwrap_or("unknown"),
+                    )
+                }
+                _ => {}
+            }
+            format!(
+                "strategy stopped\nmode={}\nwatch_id={}\ntemplate={}\ninstrument={}\nstate={}\nposition_resolution={}",
+                last_event.payload["mode"].as_str().unwrap_or("unknown"),
+                last_event.payload["watch_id"].as_u64().unwrap_or_default(),
+                last_event.payload["template"].as_str().unwrap_or("unknown"),
+                last_event.payload["instrument"]
+                    .as_str()
+                    .unwrap_or("unknown"),
+                last_event.payload["state"].as_str().unwrap_or("unknown"),
+                last_event.payload["position_resolution"]
+                    .as_str()
+                    .unwrap_or("none"),
+            )
+        }
+        StrategyCommand::Debug { watch_id, enabled } => format!(
+            "strategy debug\nmode={}\nwatch_id={watch_id}\ndebug={}",
+            mode.as_str(),
+            if *enabled { "on" } else { "off" },
+        ),
+        StrategyCommand::Trace {
+            watch_id,
+            dump_path,
+        } => {
+            let entries = store
+                .trace(*watch_id)
+                .map(|trace| trace.entries().len())
+                .unwrap_or_default();
+            let mut lines = vec![
+                "strategy trace".to_string(),
+                format!("mode={}", mode.as_str()),
+                format!("watch_id={watch_id}"),
+                format!("entries={entries}"),
+            ];
+            match dump_path {
+                Some(path) => lines.push(format!("dumped_to={path}")),
+                None => lines.extend(render_trace_lines(
+                    store,
+                    *watch_id,
+                    STRATEGY_TRACE_VIEW_LIMIT,
+                )),
+            }
+            lines.join("\n")
+        }
+        StrategyCommand::Rollback { watch_id } => {
+            let Some(watch) = store.get(mode, *watch_id) else {
+                return format!(
+                    "strategy rollback\nmode={}\nwatch_id={watch_id}\nstate=missing",
+                    mode.as_str()
+                );
+            };
+            format!(
+                "strategy rollback\nmode={}\nwatch_id={}\ninstrument={}\nrisk_pct={}\nr_multiple={}\nadaptations_left={}",
+                mode.as_str(),
+                watch.id,
+                watch.instrument.0,
+                watch.config.risk_pct,
+                watch.config.r_multiple,
+                watch
+                    .adaptive
+                    .as_ref()
+                    .map_or(0, |adaptive| adaptive.history.len()),
+            )
+        }
+        StrategyCommand::Kpi { watch_id, .. } => {
+            let mut lines = vec![
+                "strategy kpi".to_string(),
+                format!("mode={}", mode.as_str()),
+                format!("watch_id={watch_id}"),
+            ];
+            match store
+                .get(mode, *watch_id)
+                .and_then(|watch| watch.kpi.as_ref())
+            {
+                Some(kpi) => lines.extend(render_kpi_lines(kpi)),
+                None => lines.push("kpi=off".to_string()),
+            }
+            lines.join("\n")
+        }
+        StrategyCommand::Group { watch_id, group } => format!(
+            "strategy group\nmode={}\nwatch_id={watch_id}\ngroup={}",
+            mode.as_str(),
+            group.as_deref().unwrap_or("none"),
+        ),
+        StrategyCommand::Groups => {
+            let groups = store.group_stats(mode);
+            let mut lines = vec![
+                "strategy groups".to_string(),
+                format!("mode={}", mode.as_str()),
+                format!("groups={}", groups.len()),
+            ];
+            if groups.is_empty() {
+                lines.push("- none".to_string());
+            }
+            lines.extend(groups.iter().map(|stats| {
+                format!(
+                    "- group={} watches={} armed={} paused={} disabled={} seed_capital_usdt={:.2} realized_pnl_usdt={:.2} unrealized_pnl_usdt={:.2} roi_pct={:.2}",
+                    stats.label(),
+                    stats.watches,
+                    stats.armed,
+                    stats.paused,
+                    stats.disabled,
+                    stats.seed_capital_usdt,
+                    stats.realized_pnl_usdt,
+                    stats.unrealized_pnl_usdt,
+                    stats.roi_pct(),
+                )
+            }));
+            lines.join("\n")
+        }
+        StrategyCommand::Leaderboard => {
+            let board = store.leaderboard(mode);
+            let mut lines = vec![
+                "strategy leaderboard".to_string(),
+                format!("mode={}", mode.as_str()),
+                format!(
+                    "auto_allocation={}",
+                    store
+                        .auto_allocation
+                        .map_or_else(|| "off".to_string(), |allocation| allocation.label())
+                ),
+                format!("watches={}", board.len()),
+            ];
+            if board.is_empty() {
+                lines.push("- none".to_string());
+            }
+            lines.extend(board.iter().enumerate().map(|(rank, entry)| {
+                format!(
+                    "- rank={} watch_id={} template={} instrument={} group={} days={} return_pct={:.2} volatility_pct={} score={} equity_usdt={:.2} weight_pct={:.1}",
+                    rank + 1,
+                    entry.watch_id,
+                    entry.template.slug(),
+                    entry.instrument.0,
+                    entry.group.as_deref().unwrap_or("none"),
+                    entry.days,
+                    entry.return_pct,
+                    entry
+                        .volatility_pct
+                        .map_or_else(|| "-".to_string(), |volatility| format!("{volatility:.2}")),
+                    entry
+                        .score
+                        .map_or_else(|| "-".to_string(), |score| format!("{score:.2}")),
+                    entry.equity_usdt,
+                    entry.weight * 100.0,
+                )
+            }));
+            lines.join("\n")
+        }
+        StrategyCommand::Funnel { window_hours } => {
+            let payload = event_log
+                .records
+                .iter()
+                .rev()
+                .find(|record| record.kind == "app.strategy.funnel_listed")
+                .map(|record| record.payload.clone())
+                .unwrap_or_default();
+            let watches = payload["watches"].as_array().cloned().unwrap_or_default();
+            let mut lines = vec![
+                "strategy funnel".to_string(),
+                format!("mode={}", mode.as_str()),
+                format!("window_hours={window_hours}"),
+                format!("watches={}", watches.len()),
+            ];
+            if watches.is_empty() {
+                lines.push("- none".to_string());
+            }
+            lines.extend(watches.iter().map(|watch| {
+                let funnel = &watch["funnel"];
+                let count = |key: &str| funnel[key].as_u64().unwrap_or_default();
+                let drops = funnel["drops"]
+                    .as_object()
+                    .filter(|drops| !drops.is_empty())
+                    .map_or_else(
+                        || "none".to_string(),
+                        |drops| {
+                            drops
+                                .iter()
+                                .map(|(reason, count)| format!("{reason}:{count}"))
+                                .collect::<Vec<_>>()
+                                .join(",")
+                        },
+                    );
+                format!(
+                    "- id={} instrument={} signals={} passed_risk={} submitted={} filled={} drops={drops}{}",
+                    count("watch_id"),
+                    watch["instrument"].as_str().unwrap_or("-"),
+                    count("signals"),
+                    count("passed_risk"),
+                    count("submitted"),
+                    count("filled"),
+                    if count("signals") == 0 {
+                        " note=no_signals"
+                    } else {
+                        ""
+                    },
+                )
+            }));
+            lines.join("\n")
+        }
+        StrategyCommand::Plugins => {
+            let payload = event_log
+                .records
+                .iter()
+                .rev()
+                .find(|record| record.kind == "app.strategy.plugins_listed")
+                .map(|record| record.payload.clone())
+                .unwrap_or_default();
+            let plugins = payload["plugins"].as_array().cloned().unwrap_or_default();
             let mut lines = vec![
-                "strategy history".to_string(),
+                "strategy plugins".to_string(),
                 format!("mode={}", mode.as_str()),
-                format!("runs={}", history.len()),
+                format!("dir={}", payload["dir"].as_str().unwrap_or("-")),
+                format!("plugins={}", plugins.len()),
             ];
-            if history.is_empty() {
+            if plugins.is_empty() {
                 lines.push("- none".to_string());
-            } else {
-                lines.extend(history.iter().rev().take(10).map(|watch| {
-                    format!(
-                        "- id={} template={} instrument={} state={} updated_at={}",
-                        watch.id,
-                        watch.template.slug(),
-                        watch.instrument.0,
-                        watch.state.as_str(),
-                        watch.updated_at.to_rfc3339(),
-                    )
-                }));
             }
+            lines.extend(plugins.iter().map(|plugin| {
+                let name = plugin["name"].as_str().unwrap_or("-");
+                let path = plugin["path"].as_str().unwrap_or("-");
+                match plugin["reason"].as_str() {
+                    None => format!(
+                        "- name={name} path={path} status=valid exports={}",
+                        plugin["exports"]
+                            .as_array()
+                            .map(|exports| exports
+                                .iter()
+                                .filter_map(|export| export.as_str())
+                                .collect::<Vec<_>>()
+                                .join(","))
+                            .unwrap_or_default()
+                    ),
+                    Some(reason) => format!(
+                        "- name={name} path={path} status=invalid reason={reason} detail=\"{}\"",
+                        plugin["detail"].as_str().unwrap_or_default()
+                    ),
+                }
+            }));
             lines.join("\n")
         }
-        StrategyCommand::Show { watch_id } => {
-            let Some(watch) = store.get(mode, *watch_id) else {
-                return format!(
-                    "strategy watch\nmode={}\nwatch_id={watch_id}\nstate=missing",
-                    mode.as_str()
+        StrategyCommand::Bulk { group, action } => render_strategy_bulk_applied(
+            "strategy bulk",
+            format!("group={group}"),
+            *action,
+            event_log,
+            mode,
+        ),
+        StrategyCommand::Select {
+            filter,
+            action,
+            confirmed,
+        } => {
+            if let Some(action) = action.filter(|_| *confirmed) {
+                return render_strategy_bulk_applied(
+                    "strategy select",
+                    format!("filter={filter}"),
+                    action,
+                    event_log,
+                    mode,
                 );
-            };
+            }
+            let matched = event_log
+                .records
+                .last()
+                .filter(|event| event.kind == "app.strategy.select_previewed")
+                .and_then(|event| event.payload["matched"].as_array())
+                .map(|ids| ids.iter().filter_map(|id| id.as_u64()).collect::<Vec<_>>())
+                .unwrap_or_default();
             let mut lines = vec![
-                "strategy watch".to_string(),
+                "strategy select preview".to_string(),
                 format!("mode={}", mode.as_str()),
-                format!("watch_id={}", watch.id),
-                format!("template={}", watch.template.slug()),
-                format!("instrument={}", watch.instrument.0),
-                format!("state={}", watch.state.as_str()),
-                format!(
-                    "current_step={}/{}",
-                    watch.current_step,
-                    watch.template.steps().len()
-                ),
-                format!("risk_pct={}", watch.config.risk_pct),
-                format!("win_rate={}", watch.config.win_rate),
-                format!("r_multiple={}", watch.config.r_multiple),
-                format!(
-                    "max_entry_slippage_pct={}",
-                    watch.config.max_entry_slippage_pct
-                ),
+                format!("filter={filter}"),
+                format!("matched={}", matched.len()),
             ];
-            for (index, step) in watch.template.steps().iter().enumerate() {
-                let marker = if watch.current_step == index + 1 {
-                    ">"
-                } else {
-                    "-"
-                };
-                lines.push(format!("{marker} {}. {}", index + 1, step));
+            if matched.is_empty() {
+                lines.push("- none".to_string());
+            }
+            lines.extend(
+                matched
+                    .iter()
+                    .filter_map(|watch_id| store.get(mode, *watch_id))
+                    .map(|watch| {
+                        let pnl = watch
+                            .ledger
+                            .as_ref()
+                            .map(|ledger| {
+                                format!(
+                                    "{:.2}",
+                                    ledger.realized_pnl_usdt + ledger.unrealized_pnl_usdt
+                                )
+                            })
+                            .unwrap_or_else(|| "-".to_string());
+                        format!(
+                            "- id={} template={} instrument={} state={} group={} pnl_usdt={pnl}",
+                            watch.id,
+                            watch.template.slug(),
+                            watch.instrument.0,
+                            watch.state.as_str(),
+                            watch.group.as_deref().unwrap_or("none"),
+                        )
+                    }),
+            );
+            if let Some(action) = action.filter(|_| !matched.is_empty()) {
+                lines.push(format!(
+                    "{} these with /strategy select {filter} {} confirm",
+                    action.as_str(),
+                    action.as_str()
+                ));
             }
             lines.join("\n")
         }
-        StrategyCommand::Start { template, .. } => {
-            let Some(last_event) = event_log.records.last() else {
-                return "strategy started\nlast_event=none".to_string();
-            };
-            format!(
-                "strategy started\nmode={}\nwatch_id={}\ntemplate={}\ninstrument={}\nstate={}\nrisk_pct={}\nwin_rate={}\nr_multiple={}\nmax_entry_slippage_pct={}\ncurrent_step={}/{}",
-                last_event.payload["mode"].as_str().unwrap_or("unknown"),
-                last_event.payload["watch_id"].as_u64().unwrap_or_default(),
-                last_event.payload["template"].as_str().unwrap_or("unknown"),
-                last_event.payload["instrument"].as_str().unwrap_or("unknown"),
-                last_event.payload["state"].as_str().unwrap_or("unknown"),
-                last_event.payload["risk_pct"].as_f64().unwrap_or_default(),
-                last_event.payload["win_rate"].as_f64().unwrap_or_default(),
-                last_event.payload["r_multiple"].as_f64().unwrap_or_default(),
-                last_event.payload["max_entry_slippage_pct"].as_f64().unwrap_or_default(),
-                last_event.payload["current_step"].as_u64().unwrap_or_default(),
-                template.steps().len(),
-            )
+    }
+}
+
+/// Summary of the latest `app.strategy.bulk_applied`, for `bulk` and
+/// confirmed `select` alike.
+fn render_strategy_bulk_applied(
+    title: &str,
+    scope: String,
+    action: StrategyGroupAction,
+    event_log: &EventLog,
+    mode: BinanceMode,
+) -> String {
+    let Some(last_event) = event_log
+        .records
+        .last()
+        .filter(|event| event.kind == "app.strategy.bulk_applied")
+    else {
+        return format!("{title}\nlast_event=none");
+    };
+    let ids = |key: &str| {
+        let ids = last_event.payload[key]
+            .as_array()
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| id.as_u64())
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if ids.is_empty() {
+            "none".to_string()
+        } else {
+            ids.join(",")
         }
-        StrategyCommand::Stop { .. } => {
-            let Some(last_event) = event_log.records.last() else {
-                return "strategy stopped\nlast_event=none".to_string();
-            };
+    };
+    let mut lines = vec![
+        title.to_string(),
+        format!("mode={}", mode.as_str()),
+        scope,
+        format!("action={}", action.as_str()),
+        format!(
+            "watches={}",
+            last_event.payload["watches"].as_u64().unwrap_or_default()
+        ),
+        format!("changed={}", ids("changed")),
+    ];
+    let pending = ids("needs_confirmation");
+    if pending != "none" {
+        lines.push(format!("needs_confirmation={pending}"));
+        lines.push("resolve with /strategy stop <watch_id> <keep|flatten|manual>".to_string());
+    }
+    lines.join("\n")
+}
+
+const STRATEGY_TRACE_VIEW_LIMIT: usize = 20;
+const STRATEGY_SHOW_TRACE_LIMIT: usize = 5;
+
+/// Previews what stopping a watch does to the position it holds.
+///
+/// Example:
+/// - `position=Sell 0.30000000 BTCUSDT entry=50000.00 mark=49000.00`
+/// - `unrealized_pnl_usdt=300.00`, then the three resolution commands
+fn render_stop_confirmation(watch_id: u64, payload: &serde_json::Value) -> String {
+    let price = |key: &str| {
+        payload[key]
+            .as_f64()
+            .map(|value| format!("{value:.2}"))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    [
+        "strategy stop needs confirmation".to_string(),
+        format!("watch_id={watch_id}"),
+        format!(
+            "template={}",
+            payload["template"].as_str().unwrap_or("unknown")
+        ),
+        format!(
+            "position={} {:.8} {} entry={} mark={}",
+            payload["side"].as_str().unwrap_or("Flat"),
+            payload["qty"].as_f64().unwrap_or_default(),
+            payload["instrument"].as_str().unwrap_or("unknown"),
+            price("entry_price"),
+            price("mark_price"),
+        ),
+        format!("unrealized_pnl_usdt={}", price("unrealized_pnl_usdt")),
+        "options".to_string(),
+        format!("  /strategy stop {watch_id} keep     keep the watch and its position"),
+        format!("  /strategy stop {watch_id} flatten  close the position, stop once it fills"),
+        format!("  /strategy stop {watch_id} manual   stop and hand the position to manual"),
+    ]
+    .join("\n")
+}
+
+/// Example:
+/// - `risk_pct=0.01->0.009 r_multiple=2->1.8`
+fn render_param_changes(changes: &[ParamChange]) -> String {
+    changes
+        .iter()
+        .map(|change| format!("{}={}->{}", change.param.as_str(), change.from, change.to))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Example:
+/// - `kpi=amber(win_rate) targets=win>=55% dd<=150`
+/// - `kpi_since=2026-10-18T09:00:00+00:00 trades=8 wins=4 closed_pnl_usdt=12.40`
+/// - `- win_rate actual=0.50 target=0.55 amber`
+fn render_kpi_lines(kpi: &StrategyKpi) -> Vec<String> {
+    let now = Utc::now();
+    let mut lines = vec![
+        format!("kpi={} targets={}", kpi.chip(now), kpi.targets),
+        format!(
+            "kpi_since={} trades={} wins={} closed_pnl_usdt={:.2} max_drawdown_usdt={:.2}",
+            DisplayTimeZone::from_env().rfc3339(kpi.since),
+            kpi.trades,
+            kpi.wins,
+            kpi.closed_pnl_usdt,
+            kpi.max_drawdown_usdt,
+        ),
+    ];
+    lines.extend(kpi.checks(now).into_iter().map(|check| {
+        format!(
+            "- {} actual={:.2} target={:.2} {}",
+            check.metric,
+            check.actual,
+            check.target,
+            check.light.as_str()
+        )
+    }));
+    lines
+}
+
+/// Latest trace entries for one watch, newest last.
+fn render_trace_lines(store: &StrategyStore, watch_id: u64, limit: usize) -> Vec<String> {
+    let Some(trace) = store.trace(watch_id) else {
+        return vec!["- none".to_string()];
+    };
+    if trace.entries().is_empty() {
+        return vec!["- none".to_string()];
+    }
+    let zone = DisplayTimeZone::from_env();
+    trace
+        .entries()
+        .iter()
+        .skip(trace.entries().len().saturating_sub(limit))
+        .map(|entry| {
+            let indicators = entry
+                .indicators
+                .iter()
+                .map(|(name, value)| format!("{name}={value:.4}"))
+                .collect::<Vec<_>>()
+                .join(" ");
             format!(
-                "strategy stopped\nmode={}\nwatch_id={}\ntemplate={}\ninstrument={}\nstate={}",
-                last_event.payload["mode"].as_str().unwrap_or("unknown"),
-                last_event.payload["watch_id"].as_u64().unwrap_or_default(),
-                last_event.payload["template"].as_str().unwrap_or("unknown"),
-                last_event.payload["instrument"]
-                    .as_str()
-                    .unwrap_or("unknown"),
-                last_event.payload["state"].as_str().unwrap_or("unknown"),
+                "- {} {} | {}",
+                zone.format(entry.at, "%H:%M:%S"),
+                indicators,
+                entry.decision
             )
-        }
-    }
+        })
+        .collect()
 }
 
 fn render_portfolio_output(
@@ -174,6 +2096,7 @@ fn render_portfolio_output(
     store: &PortfolioStateStore,
     prices: &PriceStore,
     event_log: &EventLog,
+    history: &OrderHistory,
 ) -> String {
     match view {
         PortfolioView::Overview => render_refresh_summary_with_header(
@@ -194,24 +2117,34 @@ fn render_portfolio_output(
             false,
             false,
         ),
-        PortfolioView::Balances => render_refresh_summary_with_header(
-            "portfolio balances",
-            store,
-            prices,
-            event_log,
-            false,
-            true,
-            false,
-        ),
-        PortfolioView::Orders => render_refresh_summary_with_header(
-            "portfolio orders",
-            store,
-            prices,
-            event_log,
-            false,
-            false,
-            true,
-        ),
+        PortfolioView::Balances => {
+            let mut lines = vec![render_refresh_summary_with_header(
+                "portfolio balances",
+                store,
+                prices,
+                event_log,
+                false,
+                true,
+                false,
+            )];
+            lines.extend(render_asset_exposure_lines(store, prices));
+            lines.join("\n")
+        }
+        PortfolioView::History(filter) => render_order_history(history, filter),
+        PortfolioView::HistoryRoi { .. } => render_history_roi(event_log),
+        PortfolioView::Orders(filter) => {
+            let mut lines = vec![render_refresh_summary_with_header(
+                "portfolio orders",
+                store,
+                prices,
+                event_log,
+                false,
+                false,
+                false,
+            )];
+            lines.extend(render_open_order_lines(store, filter));
+            lines.join("\n")
+        }
     }
 }
 
@@ -288,11 +2221,7 @@ fn render_refresh_summary_with_header(
         .sum::<f64>();
     let unrealized_pnl_usdt = visible_positions
         .iter()
-        .filter_map(|position| {
-            let current_price = prices.current_price(&position.instrument)?;
-            let entry_price = position.entry_price?;
-            Some((current_price - entry_price) * position.signed_qty)
-        })
+        .filter_map(|position| position.unrealized_pnl(prices.current_price(&position.instrument)?))
         .sum::<f64>();
     let gross_exposure_usdt = normalize_display_value(gross_exposure_usdt);
     let net_exposure_usdt = normalize_display_value(net_exposure_usdt);
@@ -371,6 +2300,19 @@ fn render_refresh_summary_with_header(
         } else {
             lines.extend(balance_lines);
         }
+        if let Some(account) = &store.earn_account {
+            lines.push(format!(
+                "earn idle={:.2} saved={:.2} (counted in locked)",
+                account.idle,
+                account.saved()
+            ));
+            lines.extend(account.positions.iter().map(|position| {
+                format!(
+                    "  - {} {} amount={:.2} apr={:.2}%",
+                    position.product_id, position.asset, position.amount, position.apr_pct
+                )
+            }));
+        }
     }
 
     if show_positions {
@@ -405,8 +2347,37 @@ fn render_refresh_summary_with_header(
                     (Some(target), Some(current)) => Some(target - current),
                     _ => None,
                 };
+                let take_profit = latest_take_profit_progress(event_log, &position.instrument)
+                    .map(|progress| format!(" take_profit=[{progress}]"))
+                    .unwrap_or_default();
+                let entry_stats = store
+                    .entry_stats
+                    .get(&position.instrument)
+                    .map(|stats| {
+                        format!(
+                            " entry_stats=[fills={} vwap={:.8} twap={:.8} break_even={:.8}]",
+                            stats.fills.len(),
+                            stats.vwap_entry(),
+                            stats.twap_entry(Utc::now()),
+                            stats.break_even_price(
+                                store.entry_stats.fee_rate(&position.instrument),
+                            ),
+                        )
+                    })
+                    .unwrap_or_default();
+                let adopted = if store.adopted_positions.contains_key(&position.instrument) {
+                    format!(" tag={ADOPTED_POSITION_TAG}")
+                } else {
+                    String::new()
+                };
+                let leverage = store
+                    .futures_leverage
+                    .get(&position.instrument)
+                    .filter(|_| position.market == crate::domain::market::Market::Futures)
+                    .map(|leverage| format!(" leverage=[{}]", leverage.label()))
+                    .unwrap_or_default();
                 format!(
-                    "  - {} market={} side={} qty={:.8} entry={} notional={} current_exposure={} target_exposure={} target_delta={}",
+                    "  - {} market={} side={} qty={:.8} entry={} notional={} current_exposure={} target_exposure={} target_delta={}{}{}{}{}",
                     position.instrument.0,
                     market,
                     side,
@@ -427,6 +2398,10 @@ fn render_refresh_summary_with_header(
                     target_delta
                         .map(|value| format!("{value:.4}"))
                         .unwrap_or_else(|| "-".to_string()),
+                    leverage,
+                    take_profit,
+                    entry_stats,
+                    adopted,
                 )
             })
             .collect::<Vec<_>>();
@@ -436,42 +2411,270 @@ fn render_refresh_summary_with_header(
         } else {
             lines.extend(position_lines);
         }
+        if !store.external_positions.is_empty() {
+            lines.push(format!(
+                "external ({}): {} - adopt with /adopt <instrument> <entry_price>",
+                store.external_positions.len(),
+                store
+                    .external_positions
+                    .iter()
+                    .map(|position| position.instrument.0.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
     }
 
     if show_orders {
+        lines.extend(render_open_order_lines(store, &OrderFilter::default()));
+    }
+
+    lines.join("\n")
+}
+
+/// Renders the order and fill totals per bucket from the last `/history roi`.
+fn render_history_roi(event_log: &EventLog) -> String {
+    let payload = event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.history.roi_listed")
+        .map(|record| record.payload.clone())
+        .unwrap_or_default();
+    let buckets = payload["buckets"].as_array().cloned().unwrap_or_default();
+    let mut lines = vec![
+        "order history roi".to_string(),
+        format!(
+            "bucket={} instrument={} tag={}",
+            payload["bucket"].as_str().unwrap_or("-"),
+            payload["instrument"].as_str().unwrap_or("all"),
+            payload["tag"].as_str().unwrap_or("all"),
+        ),
+        format!("store={}", payload["store"].as_str().unwrap_or("off")),
+    ];
+    if buckets.is_empty() {
+        lines.push("- none".to_string());
+    }
+    lines.extend(buckets.iter().map(|bucket| {
+        format!(
+            "- {} orders={} rejected={} notional={:.2} fills={} filled_notional={:.2} realized_pnl={:.4} commission={:.4} roi={}",
+            bucket["bucket"].as_str().unwrap_or("-"),
+            bucket["orders"].as_u64().unwrap_or(0),
+            bucket["rejected"].as_u64().unwrap_or(0),
+            bucket["notional"].as_f64().unwrap_or(0.0),
+            bucket["fills"].as_u64().unwrap_or(0),
+            bucket["filled_notional"].as_f64().unwrap_or(0.0),
+            bucket["realized_pnl"].as_f64().unwrap_or(0.0),
+            bucket["commission"].as_f64().unwrap_or(0.0),
+            bucket["roi_pct"]
+                .as_f64()
+                .map(|roi| format!("{roi:.2}%"))
+                .unwrap_or_else(|| "-".to_string()),
+        )
+    }));
+    lines.join("\n")
+}
+
+/// Renders typed order history rows newest first.
+///
+/// Example:
+/// - `/history side=buy`
+/// - prints `rows=2/5` and the buy rows with time, price and tag
+pub fn render_order_history(history: &OrderHistory, filter: &OrderFilter) -> String {
+    let zone = DisplayTimeZone::from_env();
+    let rows = history
+        .latest_first()
+        .filter(|row| filter.matches_history(row))
+        .collect::<Vec<_>>();
+    let mut lines = vec![
+        "order history".to_string(),
+        format!("rows={}/{}", rows.len(), history.rows().len()),
+        format!(
+            "filtered_qty={:.8}",
+            rows.iter().map(|row| row.qty).sum::<f64>()
+        ),
+    ];
+    let mut latencies = rows
+        .iter()
+        .filter_map(|row| row.latency_ms())
+        .collect::<Vec<_>>();
+    if !latencies.is_empty() {
+        latencies.sort_unstable();
+        lines.push(format!(
+            "decision_latency p50={}ms max={}ms orders={}",
+            latencies[latencies.len() / 2],
+            latencies[latencies.len() - 1],
+            latencies.len(),
+        ));
+    }
+    lines.extend(
+        strategy_version_stats(rows.iter().copied())
+            .into_iter()
+            .map(|(tag, stats)| {
+                format!(
+                    "strategy_version {tag} orders={} rejected={} notional={:.2}",
+                    stats.orders, stats.rejected, stats.notional
+                )
+            }),
+    );
+    if rows.is_empty() {
+        lines.push("- none".to_string());
+    }
+    lines.extend(rows.iter().take(20).map(|row| {
+        let mut line = format!(
+            "- {} {} {} side={:?} qty={:.8} price={} status={:?} tag={}",
+            zone.format(row.time, "%Y-%m-%d %H:%M:%S"),
+            row.instrument.0,
+            format_market(row.market),
+            row.side,
+            row.qty,
+            row.price
+                .map(|price| format!("{price:.8}"))
+                .unwrap_or_else(|| "-".to_string()),
+            row.status,
+            row.tag,
+        );
+        if let Some(imbalance) = row.depth_imbalance {
+            line.push_str(&format!(" depth_imbalance={imbalance:.3}"));
+        }
+        if let Some(list_id) = row.list_id {
+            line.push_str(&format!(" list={list_id}"));
+        }
+        if let Some(parent_id) = row.parent_id {
+            line.push_str(&format!(" parent={parent_id}"));
+        }
+        if let Some(latency_ms) = row.latency_ms() {
+            line.push_str(&format!(" latency={latency_ms}ms"));
+        }
+        line
+    }));
+    lines.join("\n")
+}
+
+/// Renders open orders, narrowed by `filter` when one is set.
+///
+/// Consolidated exposure per base asset for the balances view, so BTCUSDT
+/// spot and futures show as one BTC figure.
+///
+/// Example:
+/// - a 0.5 BTC balance and a -0.2 BTCUSDT futures position
+/// - prints `  - BTC spot_qty=0.50000000 futures_qty=-0.20000000 net_qty=0.30000000 ...`
+fn render_asset_exposure_lines(store: &PortfolioStateStore, prices: &PriceStore) -> Vec<String> {
+    let total_equity_usdt = aggregate_visible_balances(store)
+        .values()
+        .map(|balance| balance.total())
+        .sum::<f64>();
+    let asset_exposures = aggregate_base_asset_exposure(&store.snapshot, |instrument| {
+        prices.current_price(instrument)
+    });
+    let mut lines = vec![format!("asset exposure ({})", asset_exposures.len())];
+    if asset_exposures.is_empty() {
+        lines.push("  - none".to_string());
+    }
+    for exposure in asset_exposures.values().take(8) {
+        let net_exposure_usdt = exposure.net_exposure_usdt();
+        lines.push(format!(
+            "  - {} spot_qty={:.8} futures_qty={:.8} net_qty={:.8} net_exposure_usdt={} share_of_equity={}",
+            exposure.asset,
+            exposure.spot_qty,
+            exposure.futures_qty,
+            exposure.net_qty(),
+            net_exposure_usdt
+                .map(|value| format!("{:.2}", normalize_display_value(value)))
+                .unwrap_or_else(|| "-".to_string()),
+            net_exposure_usdt
+                .filter(|_| total_equity_usdt > f64::EPSILON)
+                .map(|value| format!("{:.4}", value / total_equity_usdt))
+                .unwrap_or_else(|| "-".to_string()),
+        ));
+    }
+    lines
+}
+
+/// Example:
+/// - `side=sell` over one buy and one sell order
+/// - prints `open orders (1/2)` and `filtered_qty` for the sell only
+fn render_open_order_lines(store: &PortfolioStateStore, filter: &OrderFilter) -> Vec<String> {
+    let orders = store
+        .snapshot
+        .open_orders
+        .iter()
+        .flat_map(|(instrument, orders)| orders.iter().map(move |order| (instrument, order)))
+        .collect::<Vec<_>>();
+    let filtered = orders
+        .iter()
+        .filter(|(_, order)| filter.matches(order))
+        .collect::<Vec<_>>();
+
+    let mut lines = Vec::new();
+    if filter.is_empty() {
         lines.push(format!(
             "open orders ({})",
             store.snapshot.open_orders.len()
         ));
-        let order_lines = store
-            .snapshot
-            .open_orders
-            .iter()
-            .take(12)
-            .flat_map(|(instrument, orders)| {
-                orders.iter().map(move |order| {
-                    format!(
-                        "  - {} {} side={:?} qty={:.8} filled={:.8} reduce_only={} status={:?}",
-                        instrument.0,
-                        format_market(order.market),
-                        order.side,
-                        order.orig_qty,
-                        order.executed_qty,
-                        order.reduce_only,
-                        order.status
-                    )
-                })
+    } else {
+        lines.push(format!("open orders ({}/{})", filtered.len(), orders.len()));
+        lines.push(format!(
+            "  filter side={} status={} tag={}",
+            filter
+                .side
+                .map(|side| format!("{side:?}"))
+                .unwrap_or_else(|| "any".to_string()),
+            filter
+                .status
+                .map(|status| format!("{status:?}"))
+                .unwrap_or_else(|| "any".to_string()),
+            filter.tag.as_deref().unwrap_or("any"),
+        ));
+        lines.push(format!(
+            "  filtered_qty={:.8} filtered_filled_qty={:.8}",
+            filtered
+                .iter()
+                .map(|(_, order)| order.orig_qty)
+                .sum::<f64>(),
+            filtered
+                .iter()
+                .map(|(_, order)| order.executed_qty)
+                .sum::<f64>(),
+        ));
+    }
+
+    // Like the unfiltered list, shows every order of the first 12 instruments.
+    let order_lines = store
+        .snapshot
+        .open_orders
+        .iter()
+        .map(|(instrument, orders)| {
+            let matching = orders
+                .iter()
+                .filter(|order| filter.matches(order))
+                .collect::<Vec<_>>();
+            (instrument, matching)
+        })
+        .filter(|(_, orders)| !orders.is_empty())
+        .take(12)
+        .flat_map(|(instrument, orders)| {
+            orders.into_iter().map(move |order| {
+                format!(
+                    "  - {} {} side={:?} qty={:.8} filled={:.8} reduce_only={} status={:?}",
+                    instrument.0,
+                    format_market(order.market),
+                    order.side,
+                    order.orig_qty,
+                    order.executed_qty,
+                    order.reduce_only,
+                    order.status
+                )
             })
-            .collect::<Vec<_>>();
+        })
+        .collect::<Vec<_>>();
 
-        if order_lines.is_empty() {
-            lines.push("  - none".to_string());
-        } else {
-            lines.extend(order_lines);
-        }
+    if order_lines.is_empty() {
+        lines.push("  - none".to_string());
+    } else {
+        lines.extend(order_lines);
     }
-
-    lines.join("\n")
+    lines
 }
 
 fn aggregate_visible_balances(
@@ -512,6 +2715,19 @@ fn render_execution_summary(event_log: &EventLog) -> String {
         return "execution completed\nlast_event=none".to_string();
     };
 
+    if last_event.kind == "app.execution.deferred" {
+        return format!(
+            "execution deferred\ninstrument={}\nattempts={}\nretry_secs={}\nreason={}",
+            last_event.payload["instrument"]
+                .as_str()
+                .unwrap_or("unknown"),
+            last_event.payload["attempts"].as_u64().unwrap_or_default(),
+            last_event.payload["retry_secs"]
+                .as_u64()
+                .unwrap_or_default(),
+            last_event.payload["detail"].as_str().unwrap_or("unknown"),
+        );
+    }
     if last_event.kind != "app.execution.completed" {
         return format!("execution completed\nlast_event={}", last_event.kind);
     }
@@ -564,6 +2780,55 @@ fn render_execution_summary(event_log: &EventLog) -> String {
             ),
             last_event.payload["outcome_kind"].as_str().unwrap_or("unknown"),
         ),
+        Some("place_trailing_stop") => format!(
+            "execution completed\ncommand=trailing-stop\ninstrument={}\ntrail={}\ncallback_rate_pct={}\nengine={}\nremaining_positions={}\noutcome={}",
+            last_event.payload["instrument"].as_str().unwrap_or("unknown"),
+            last_event.payload["trail"].as_str().unwrap_or("unknown"),
+            last_event.payload["callback_rate_pct"].as_f64().unwrap_or_default(),
+            last_event.payload["engine"].as_str().unwrap_or("unknown"),
+            last_event.payload["remaining_positions"]
+                .as_u64()
+                .unwrap_or_default(),
+            last_event.payload["outcome_kind"].as_str().unwrap_or("unknown"),
+        ),
+        Some("place_take_profit_ladder") => format!(
+            "execution completed\ncommand=take-profit\ninstrument={}\nprofile={}\nprogress={}\nremaining_positions={}\noutcome={}",
+            last_event.payload["instrument"].as_str().unwrap_or("unknown"),
+            last_event.payload["profile"].as_str().unwrap_or("unknown"),
+            last_event.payload["take_profit_progress"]
+                .as_str()
+                .unwrap_or("unknown"),
+            last_event.payload["remaining_positions"]
+                .as_u64()
+                .unwrap_or_default(),
+            last_event.payload["outcome_kind"].as_str().unwrap_or("unknown"),
+        ),
+        Some("place_oco") => format!(
+            "execution completed\ncommand=oco\ninstrument={}\nlist_id={}\nvenue_list_id={}\ntake_profit={}\nstop={}\nstop_limit={}\nremaining_positions={}\noutcome={}",
+            last_event.payload["instrument"].as_str().unwrap_or("unknown"),
+            last_event.payload["list_id"].as_u64().unwrap_or_default(),
+            last_event.payload["exchange_list_id"].as_str().unwrap_or("-"),
+            last_event.payload["take_profit_price"].as_f64().unwrap_or_default(),
+            last_event.payload["stop_price"].as_f64().unwrap_or_default(),
+            last_event.payload["stop_limit_price"]
+                .as_f64()
+                .map_or("-".to_string(), |price| price.to_string()),
+            last_event.payload["remaining_positions"]
+                .as_u64()
+                .unwrap_or_default(),
+            last_event.payload["outcome_kind"].as_str().unwrap_or("unknown"),
+        ),
+        Some("place_iceberg") => format!(
+            "execution completed\ncommand=iceberg\ninstrument={}\nparent_id={}\nside={}\nqty={}\nvisible_qty={}\nprice={}\nfirst_slice={}\noutcome={}",
+            last_event.payload["instrument"].as_str().unwrap_or("unknown"),
+            last_event.payload["parent_id"].as_u64().unwrap_or_default(),
+            last_event.payload["side"].as_str().unwrap_or("unknown"),
+            last_event.payload["qty"].as_f64().unwrap_or_default(),
+            last_event.payload["visible_qty"].as_f64().unwrap_or_default(),
+            last_event.payload["price"].as_f64().unwrap_or_default(),
+            last_event.payload["child_order_id"].as_str().unwrap_or("-"),
+            last_event.payload["outcome_kind"].as_str().unwrap_or("unknown"),
+        ),
         Some("close_all") => format!(
             "execution completed\ncommand=close-all\nbatch_id={}\nsubmitted={}\nskipped={}\nrejected={}\nremaining_positions={}\nflat_confirmed={}\nremaining_gross_exposure_usdt={:.2}\noutcome={}",
             last_event.payload["batch_id"].as_u64().unwrap_or_default(),
@@ -601,6 +2866,32 @@ fn latest_target_exposure(
         .and_then(|event| event.payload["target"].as_f64())
 }
 
+/// Latest ladder progress for an instrument, cleared by a later close.
+///
+/// Example:
+/// - `tp1@50500.00=filled tp2@51000.00=open runner=waiting`
+fn latest_take_profit_progress(
+    event_log: &EventLog,
+    instrument: &crate::domain::instrument::Instrument,
+) -> Option<String> {
+    event_log
+        .records
+        .iter()
+        .rev()
+        .filter(|event| event.payload["instrument"].as_str() == Some(instrument.0.as_str()))
+        .find_map(|event| {
+            if event.kind == "app.execution.trailing_stop_triggered"
+                || event.payload["command_kind"].as_str() == Some("close_symbol")
+            {
+                return Some(None);
+            }
+            event.payload["take_profit_progress"]
+                .as_str()
+                .map(|progress| Some(progress.to_string()))
+        })
+        .flatten()
+}
+
 fn format_market(market: crate::domain::market::Market) -> &'static str {
     match market {
         crate::domain::market::Market::Spot => "SPOT",
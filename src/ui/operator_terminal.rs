@@ -2,21 +2,29 @@ use crossterm::style::{Color, Stylize};
 
 use crate::app::bootstrap::BinanceMode;
 use crate::portfolio::store::PortfolioStateStore;
+use crate::ui::locale::{pad_display, text, UiLocale, UiText};
+use crate::ui::theme::{terminal_color, UiTheme};
 
-pub fn shell_intro_panel(mode: &str, directory: &str) -> String {
+pub fn shell_intro_panel(mode: &str, directory: &str, locale: UiLocale) -> String {
     let width = 46usize;
     let title = format!(" >_ Sandbox Quant (v{})", env!("CARGO_PKG_VERSION"));
-    let mode_line = format!(" mode:      {mode:<18} /mode to change");
-    let dir_line = format!(" directory: {directory}");
+    let mode_line = format!(
+        " {} {mode:<18} {}",
+        pad_display(text(locale, UiText::IntroMode), 10),
+        text(locale, UiText::IntroModeHint)
+    );
+    let dir_line = format!(
+        " {} {directory}",
+        pad_display(text(locale, UiText::IntroDirectory), 10)
+    );
 
     format!(
-        "╭{top}╮\n│{title:<width$}│\n│{blank:<width$}│\n│{mode_line:<width$}│\n│{dir_line:<width$}│\n╰{top}╯",
+        "╭{top}╮\n│{title}│\n│{blank}│\n│{mode_line}│\n│{dir_line}│\n╰{top}╯",
         top = "─".repeat(width),
-        title = title,
-        blank = "",
-        mode_line = mode_line,
-        dir_line = dir_line,
-        width = width,
+        title = pad_display(&title, width),
+        blank = pad_display("", width),
+        mode_line = pad_display(&mode_line, width),
+        dir_line = pad_display(&dir_line, width),
     )
 }
 
@@ -27,14 +35,15 @@ pub fn mode_name(mode: BinanceMode) -> &'static str {
     }
 }
 
-pub fn mode_color(mode: BinanceMode) -> Color {
-    match mode {
-        BinanceMode::Real => Color::Green,
-        BinanceMode::Demo => Color::Yellow,
-    }
+pub fn mode_color(mode: BinanceMode, theme: UiTheme) -> Color {
+    let palette = theme.palette();
+    terminal_color(match mode {
+        BinanceMode::Real => palette.positive,
+        BinanceMode::Demo => palette.warning,
+    })
 }
 
-pub fn prompt_status_from_store(store: &PortfolioStateStore) -> String {
+pub fn prompt_status_from_store(store: &PortfolioStateStore, locale: UiLocale) -> String {
     let position_count = store
         .snapshot
         .positions
@@ -43,27 +52,35 @@ pub fn prompt_status_from_store(store: &PortfolioStateStore) -> String {
         .count();
     let open_order_count: usize = store.snapshot.open_orders.values().map(Vec::len).sum();
     format!(
-        "[{}|{} pos|{} ord]",
-        staleness_label(store.staleness),
+        "[{}|{} {}|{} {}]",
+        staleness_label(store.staleness, locale),
         position_count,
+        text(locale, UiText::PromptPositions),
         open_order_count,
+        text(locale, UiText::PromptOrders),
     )
 }
 
-pub fn operator_prompt(mode: BinanceMode, status: &str) -> String {
+pub fn operator_prompt(mode: BinanceMode, status: &str, theme: UiTheme) -> String {
     format!(
         "{} [{}] {} › ",
-        "●".with(mode_color(mode)),
+        "●".with(mode_color(mode, theme)),
         mode_name(mode),
         status
     )
 }
 
-fn staleness_label(staleness: crate::portfolio::staleness::StalenessState) -> &'static str {
-    match staleness {
-        crate::portfolio::staleness::StalenessState::Fresh => "fresh",
-        crate::portfolio::staleness::StalenessState::MarketDataStale => "market-stale",
-        crate::portfolio::staleness::StalenessState::AccountStateStale => "account-stale",
-        crate::portfolio::staleness::StalenessState::ReconciliationStale => "reconcile-stale",
-    }
+fn staleness_label(
+    staleness: crate::portfolio::staleness::StalenessState,
+    locale: UiLocale,
+) -> &'static str {
+    let key = match staleness {
+        crate::portfolio::staleness::StalenessState::Fresh => UiText::StalenessFresh,
+        crate::portfolio::staleness::StalenessState::MarketDataStale => UiText::StalenessMarket,
+        crate::portfolio::staleness::StalenessState::AccountStateStale => UiText::StalenessAccount,
+        crate::portfolio::staleness::StalenessState::ReconciliationStale => {
+            UiText::StalenessReconcile
+        }
+    };
+    text(locale, key)
 }
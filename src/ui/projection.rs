@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+
+use crate::observability::event_routing::{event_domain, event_level, LogLevel};
+use crate::storage::models::EventRecord;
+
+/// One strategy watch as the events describe it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchRow {
+    pub id: u64,
+    pub template: String,
+    pub instrument: String,
+    pub state: String,
+    pub group: Option<String>,
+    /// Adaptations applied and not rolled back.
+    pub adaptations: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRow {
+    pub id: u64,
+    pub instrument: String,
+    pub price: f64,
+    pub action: String,
+    pub triggered: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionRow {
+    pub command_kind: String,
+    pub instrument: String,
+    pub outcome_kind: String,
+}
+
+/// Operator panel state folded from event records alone.
+///
+/// Nothing is read from the clock, the exchange or the stores, so the same
+/// events always give the same panels. Feed it `EventLog::records` or a
+/// replayed event file to assert on what the operator would have seen.
+///
+/// Example:
+/// - `app.strategy.watch_started` then `app.strategy.watch_stopped` for watch `1`
+/// - `watches[1].state == "stopped"`, `render()` lists it under `[watches]`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UiProjection {
+    pub events: usize,
+    pub domains: BTreeMap<String, usize>,
+    pub watches: BTreeMap<u64, WatchRow>,
+    /// Alerts still configured; removed ones drop out.
+    pub alerts: BTreeMap<u64, AlertRow>,
+    /// `app.execution.completed`, oldest first.
+    pub executions: Vec<ExecutionRow>,
+    /// Kinds of failure events, oldest first.
+    pub errors: Vec<String>,
+}
+
+impl UiProjection {
+    pub fn from_events(records: &[EventRecord]) -> Self {
+        let mut projection = Self::default();
+        for record in records {
+            projection.apply(record);
+        }
+        projection
+    }
+
+    /// Folds one more event in; unknown kinds only count towards `events`.
+    pub fn apply(&mut self, record: &EventRecord) {
+        let kind = record.kind.as_str();
+        let payload = &record.payload;
+        let text = |key: &str| payload[key].as_str().unwrap_or("unknown").to_string();
+        self.events += 1;
+        *self
+            .domains
+            .entry(event_domain(kind).to_string())
+            .or_default() += 1;
+        if event_level(kind) == LogLevel::Error {
+            self.errors.push(kind.to_string());
+        }
+
+        let watch_id = payload["watch_id"].as_u64();
+        match kind {
+            "app.strategy.watch_started" => {
+                if let Some(id) = watch_id {
+                    self.watches.insert(
+                        id,
+                        WatchRow {
+                            id,
+                            template: text("template"),
+                            instrument: text("instrument"),
+                            state: text("state"),
+                            group: None,
+                            adaptations: 0,
+                        },
+                    );
+                }
+            }
+            "app.strategy.watch_stopped"
+            | "app.strategy.paused_degraded"
+            | "app.strategy.resumed" => {
+                if let Some(watch) = watch_id.and_then(|id| self.watches.get_mut(&id)) {
+                    watch.state = text("state");
+                }
+            }
+            "app.strategy.capital_exhausted" => {
+                if let Some(watch) = watch_id.and_then(|id| self.watches.get_mut(&id)) {
+                    watch.state = "stopped".to_string();
+                }
+            }
+            "app.strategy.group_set" => {
+                if let Some(watch) = watch_id.and_then(|id| self.watches.get_mut(&id)) {
+                    watch.group = payload["group"].as_str().map(str::to_string);
+                }
+            }
+            "app.strategy.adapted" => {
+                if let Some(watch) = watch_id.and_then(|id| self.watches.get_mut(&id)) {
+                    watch.adaptations += 1;
+                }
+            }
+            "app.strategy.adaptation_rolled_back" => {
+                if let Some(watch) = watch_id.and_then(|id| self.watches.get_mut(&id)) {
+                    watch.adaptations = watch.adaptations.saturating_sub(1);
+                }
+            }
+            // Bulk stops also log `app.strategy.watch_stopped` per watch.
+            "app.strategy.bulk_applied" => {
+                let state = match payload["action"].as_str() {
+                    Some("enable") => "armed",
+                    Some("disable") => "disabled",
+                    _ => return,
+                };
+                let changed = payload["changed"].as_array().into_iter().flatten();
+                for id in changed.filter_map(|id| id.as_u64()) {
+                    if let Some(watch) = self.watches.get_mut(&id) {
+                        watch.state = state.to_string();
+                    }
+                }
+            }
+            "app.alert.added" | "app.alert.triggered" => {
+                if let Some(id) = payload["alert_id"].as_u64() {
+                    let alert = AlertRow {
+                        id,
+                        instrument: text("instrument"),
+                        price: payload["price"].as_f64().unwrap_or_default(),
+                        action: text("action"),
+                        triggered: kind == "app.alert.triggered",
+                    };
+                    self.alerts.insert(id, alert);
+                }
+            }
+            "app.alert.removed" => {
+                if let Some(id) = payload["alert_id"].as_u64() {
+                    self.alerts.remove(&id);
+                }
+            }
+            "app.execution.completed" => self.executions.push(ExecutionRow {
+                command_kind: text("command_kind"),
+                instrument: text("instrument"),
+                outcome_kind: text("outcome_kind"),
+            }),
+            _ => {}
+        }
+    }
+
+    /// Example:
+    /// - `ui projection`
+    /// - `events=6 errors=1`
+    /// - `domains execution=2 strategy=4`
+    /// - `[watches]`
+    /// - `id  template                     instrument  state    group  adaptations`
+    /// - `1   liquidation-breakdown-short  BTCUSDT     stopped  trend  0`
+    pub fn render(&self) -> String {
+        let mut lines = vec![
+            "ui projection".to_string(),
+            format!("events={} errors={}", self.events, self.errors.len()),
+            format!(
+                "domains {}",
+                if self.domains.is_empty() {
+                    "none".to_string()
+                } else {
+                    self.domains
+                        .iter()
+                        .map(|(domain, count)| format!("{domain}={count}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                }
+            ),
+            "[watches]".to_string(),
+        ];
+        lines.extend(render_grid(
+            &[
+                "id",
+                "template",
+                "instrument",
+                "state",
+                "group",
+                "adaptations",
+            ],
+            self.watches.values().map(|watch| {
+                vec![
+                    watch.id.to_string(),
+                    watch.template.clone(),
+                    watch.instrument.clone(),
+                    watch.state.clone(),
+                    watch.group.clone().unwrap_or_else(|| "-".to_string()),
+                    watch.adaptations.to_string(),
+                ]
+            }),
+        ));
+        lines.push("[alerts]".to_string());
+        lines.extend(render_grid(
+            &["id", "instrument", "price", "action", "triggered"],
+            self.alerts.values().map(|alert| {
+                vec![
+                    alert.id.to_string(),
+                    alert.instrument.clone(),
+                    alert.price.to_string(),
+                    alert.action.clone(),
+                    alert.triggered.to_string(),
+                ]
+            }),
+        ));
+        lines.push("[executions]".to_string());
+        lines.extend(render_grid(
+            &["#", "command", "instrument", "outcome"],
+            self.executions
+                .iter()
+                .enumerate()
+                .map(|(index, execution)| {
+                    vec![
+                        (index + 1).to_string(),
+                        execution.command_kind.clone(),
+                        execution.instrument.clone(),
+                        execution.outcome_kind.clone(),
+                    ]
+                }),
+        ));
+        lines.push("[errors]".to_string());
+        if self.errors.is_empty() {
+            lines.push("- none".to_string());
+        }
+        lines.extend(self.errors.iter().map(|kind| format!("- {kind}")));
+        lines.join("\n")
+    }
+}
+
+/// Left-aligned columns two spaces apart; `- none` when there are no rows.
+pub(crate) fn render_grid(
+    headers: &[&str],
+    rows: impl Iterator<Item = Vec<String>>,
+) -> Vec<String> {
+    let rows = rows.collect::<Vec<_>>();
+    if rows.is_empty() {
+        return vec!["- none".to_string()];
+    }
+    let widths = headers
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([header.chars().count()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    std::iter::once(line(headers.to_vec()))
+        .chain(
+            rows.iter()
+                .map(|row| line(row.iter().map(String::as_str).collect())),
+        )
+        .collect()
+}
@@ -1,6 +1,8 @@
 use crate::recorder_app::runtime::RecorderStatus;
+use crate::ui::timezone::DisplayTimeZone;
 
 pub fn render_live_recorder_status(header: &str, status: &RecorderStatus) -> String {
+    let zone = DisplayTimeZone::from_env();
     let mut lines = vec![
         header.to_string(),
         format!("state={}", status.state.as_str()),
@@ -30,10 +32,10 @@ pub fn render_live_recorder_status(header: &str, status: &RecorderStatus) -> Str
             "started_at={}",
             status
                 .started_at
-                .map(|value| value.to_rfc3339())
+                .map(|value| zone.rfc3339(value))
                 .unwrap_or_else(|| "n/a".to_string())
         ),
-        format!("updated_at={}", status.updated_at.to_rfc3339()),
+        format!("updated_at={}", zone.rfc3339(status.updated_at)),
         format!("manual_symbols={}", status.manual_symbols.len()),
         format!("strategy_symbols={}", status.strategy_symbols.len()),
         format!("watched_symbols={}", status.watched_symbols.len()),
@@ -44,6 +46,18 @@ pub fn render_live_recorder_status(header: &str, status: &RecorderStatus) -> Str
             "derived_kline_1s_bars={}",
             status.metrics.derived_kline_1s_bars
         ),
+        format!(
+            "trade_id_gaps={} missed_trades={} backfilled_trades={}",
+            status.metrics.trade_id_gaps,
+            status.metrics.missed_trades,
+            status.metrics.backfilled_trades
+        ),
+        format!(
+            "duplicate_ticks={} reordered_ticks={} late_ticks={}",
+            status.metrics.duplicate_ticks,
+            status.metrics.reordered_ticks,
+            status.metrics.late_ticks
+        ),
         format!(
             "last_liquidation_event_time={}",
             status
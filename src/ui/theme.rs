@@ -0,0 +1,155 @@
+use crossterm::style::Color;
+
+use crate::charting::style::{ChartTheme, RgbColor};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UiTheme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    /// Blue/orange instead of green/red for gains and losses.
+    DeuteranopiaSafe,
+}
+
+impl UiTheme {
+    /// Reads the color theme from `SANDBOX_QUANT_UI_THEME`.
+    ///
+    /// Example:
+    /// - `light` -> `Light`, `high-contrast` -> `HighContrast`
+    /// - `deuteranopia`, `colorblind` -> `DeuteranopiaSafe`
+    /// - unset or anything else -> `Dark`
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        var("SANDBOX_QUANT_UI_THEME")
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let normalized = value.trim().to_ascii_lowercase().replace('_', "-");
+        match normalized.as_str() {
+            "dark" => Some(Self::Dark),
+            "light" => Some(Self::Light),
+            "high-contrast" | "contrast" | "hc" => Some(Self::HighContrast),
+            "deuteranopia" | "deuteranopia-safe" | "colorblind" | "cb" => {
+                Some(Self::DeuteranopiaSafe)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::HighContrast => "high-contrast",
+            Self::DeuteranopiaSafe => "deuteranopia-safe",
+        }
+    }
+
+    pub fn palette(self) -> &'static ThemePalette {
+        match self {
+            Self::Dark => &DARK,
+            Self::Light => &LIGHT,
+            Self::HighContrast => &HIGH_CONTRAST,
+            Self::DeuteranopiaSafe => &DEUTERANOPIA_SAFE,
+        }
+    }
+}
+
+/// Colors by role, shared by the terminal, the GUI and the charts.
+///
+/// Code asks for `positive`/`negative` rather than green/red, so a theme
+/// can move gains and losses off the red-green axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemePalette {
+    pub background: RgbColor,
+    pub grid: RgbColor,
+    pub axis: RgbColor,
+    pub text: RgbColor,
+    /// Hints and secondary labels.
+    pub muted: RgbColor,
+    /// Headings and command output.
+    pub accent: RgbColor,
+    /// Gains, bull candles, take-profits, the real-mode dot.
+    pub positive: RgbColor,
+    /// Losses, bear candles, stops, errors.
+    pub negative: RgbColor,
+    /// Stale data, blocked signals, the demo-mode dot.
+    pub warning: RgbColor,
+}
+
+impl ThemePalette {
+    pub fn chart_theme(&self) -> ChartTheme {
+        ChartTheme {
+            background: self.background,
+            grid: self.grid,
+            axis: self.axis,
+            text: self.text,
+            bull_candle: self.positive,
+            bear_candle: self.negative,
+        }
+    }
+}
+
+const DARK: ThemePalette = ThemePalette {
+    background: RgbColor::new(16, 20, 24),
+    grid: RgbColor::new(46, 54, 62),
+    axis: RgbColor::new(170, 176, 184),
+    text: RgbColor::new(220, 225, 230),
+    muted: RgbColor::new(128, 134, 142),
+    accent: RgbColor::new(90, 200, 220),
+    positive: RgbColor::new(84, 208, 136),
+    negative: RgbColor::new(255, 110, 110),
+    warning: RgbColor::new(255, 210, 120),
+};
+
+const LIGHT: ThemePalette = ThemePalette {
+    background: RgbColor::new(250, 250, 248),
+    grid: RgbColor::new(222, 224, 228),
+    axis: RgbColor::new(90, 96, 104),
+    text: RgbColor::new(28, 32, 36),
+    muted: RgbColor::new(120, 126, 134),
+    accent: RgbColor::new(0, 120, 160),
+    positive: RgbColor::new(20, 140, 70),
+    negative: RgbColor::new(200, 40, 40),
+    warning: RgbColor::new(170, 110, 0),
+};
+
+const HIGH_CONTRAST: ThemePalette = ThemePalette {
+    background: RgbColor::new(0, 0, 0),
+    grid: RgbColor::new(90, 90, 90),
+    axis: RgbColor::new(255, 255, 255),
+    text: RgbColor::new(255, 255, 255),
+    muted: RgbColor::new(200, 200, 200),
+    accent: RgbColor::new(0, 255, 255),
+    positive: RgbColor::new(0, 255, 0),
+    negative: RgbColor::new(255, 60, 60),
+    warning: RgbColor::new(255, 255, 0),
+};
+
+/// Okabe-Ito hues on the dark background.
+const DEUTERANOPIA_SAFE: ThemePalette = ThemePalette {
+    background: RgbColor::new(16, 20, 24),
+    grid: RgbColor::new(46, 54, 62),
+    axis: RgbColor::new(170, 176, 184),
+    text: RgbColor::new(220, 225, 230),
+    muted: RgbColor::new(128, 134, 142),
+    accent: RgbColor::new(204, 121, 167),
+    positive: RgbColor::new(86, 180, 233),
+    negative: RgbColor::new(230, 159, 0),
+    warning: RgbColor::new(240, 228, 66),
+};
+
+pub fn terminal_color(color: RgbColor) -> Color {
+    Color::Rgb {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+    }
+}
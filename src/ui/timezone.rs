@@ -0,0 +1,141 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, Offset, TimeZone, Utc};
+
+/// Zone timestamps are shown in; storage and the exchange stay in UTC.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DisplayTimeZone {
+    #[default]
+    Utc,
+    /// The host's zone, following its daylight saving changes.
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl DisplayTimeZone {
+    /// Reads the display zone from `SANDBOX_QUANT_DISPLAY_TZ`.
+    ///
+    /// Example:
+    /// - `local` -> `Local`
+    /// - `+09:00`, `UTC+9`, `-0530` -> `Fixed`
+    /// - unset or anything else -> `Utc`
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// `from_env` with every variable looked up through `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        var("SANDBOX_QUANT_DISPLAY_TZ")
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let normalized = value.trim().to_ascii_lowercase();
+        match normalized.as_str() {
+            "utc" | "gmt" | "z" => return Some(Self::Utc),
+            "local" | "host" => return Some(Self::Local),
+            _ => {}
+        }
+        let offset = normalized
+            .strip_prefix("utc")
+            .or_else(|| normalized.strip_prefix("gmt"))
+            .unwrap_or(&normalized);
+        let (sign, digits) = match offset.split_at_checked(1)? {
+            ("+", digits) => (1, digits),
+            ("-", digits) => (-1, digits),
+            _ => return None,
+        };
+        let (hours, minutes) = match digits.split_once(':') {
+            Some((hours, minutes)) => (hours, minutes),
+            None if digits.len() == 4 => digits.split_at(2),
+            None => (digits, "0"),
+        };
+        let hours = hours.parse::<i32>().ok().filter(|hours| *hours <= 14)?;
+        let minutes = minutes
+            .parse::<i32>()
+            .ok()
+            .filter(|minutes| *minutes < 60)?;
+        let offset = FixedOffset::east_opt(sign * (hours * 3_600 + minutes * 60))?;
+        Some(if offset.local_minus_utc() == 0 {
+            Self::Utc
+        } else {
+            Self::Fixed(offset)
+        })
+    }
+
+    /// Example:
+    /// - `UTC`, `local`, `+09:00`
+    pub fn label(self) -> String {
+        match self {
+            Self::Utc => "UTC".to_string(),
+            Self::Local => "local".to_string(),
+            Self::Fixed(offset) => offset.to_string(),
+        }
+    }
+
+    pub fn offset_at(self, at: DateTime<Utc>) -> FixedOffset {
+        match self {
+            Self::Utc => Utc.fix(),
+            Self::Local => at.with_timezone(&Local).offset().fix(),
+            Self::Fixed(offset) => offset,
+        }
+    }
+
+    pub fn to_display(self, at: DateTime<Utc>) -> DateTime<FixedOffset> {
+        at.with_timezone(&self.offset_at(at))
+    }
+
+    /// Example:
+    /// - `2026-03-01T23:30:00Z` with `%m-%d %H:%M` in `+09:00` -> `03-02 08:30`
+    pub fn format(self, at: DateTime<Utc>, fmt: &str) -> String {
+        self.to_display(at).format(fmt).to_string()
+    }
+
+    /// Example:
+    /// - `2026-03-01T23:30:00Z` in `+09:00` -> `2026-03-02T08:30:00+09:00`
+    pub fn rfc3339(self, at: DateTime<Utc>) -> String {
+        self.to_display(at).to_rfc3339()
+    }
+
+    /// `rfc3339` for a timestamp read back from an event payload; anything
+    /// that does not parse is returned as is.
+    pub fn restamp(self, raw: &str) -> String {
+        DateTime::parse_from_rfc3339(raw)
+            .map(|at| self.rfc3339(at.with_timezone(&Utc)))
+            .unwrap_or_else(|_| raw.to_string())
+    }
+
+    /// `format` for epoch milliseconds; `-` when out of range.
+    pub fn format_ms(self, time_ms: i64, fmt: &str) -> String {
+        DateTime::<Utc>::from_timestamp_millis(time_ms)
+            .map(|at| self.format(at, fmt))
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    /// The calendar day `at` falls on, for day and month buckets.
+    pub fn date(self, at: DateTime<Utc>) -> NaiveDate {
+        self.to_display(at).date_naive()
+    }
+
+    /// Midnight of the display day holding `at`, e.g. the start of "today".
+    pub fn start_of_day(self, at: DateTime<Utc>) -> DateTime<Utc> {
+        self.start_of_date(self.date(at))
+    }
+
+    /// Example:
+    /// - `2026-03-02` in `+09:00` -> `2026-03-01T15:00:00Z`
+    pub fn start_of_date(self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_time(chrono::NaiveTime::MIN);
+        let start = match self {
+            Self::Utc => Some(midnight.and_utc()),
+            Self::Local => Local
+                .from_local_datetime(&midnight)
+                .earliest()
+                .map(|start| start.with_timezone(&Utc)),
+            Self::Fixed(offset) => offset
+                .from_local_datetime(&midnight)
+                .single()
+                .map(|start| start.with_timezone(&Utc)),
+        };
+        start.unwrap_or_else(|| midnight.and_utc())
+    }
+}
@@ -0,0 +1,123 @@
+use crate::backtest_app::runner::BacktestTrade;
+
+pub const DEFAULT_MAX_FILL_MARKERS: usize = 500;
+pub const DEFAULT_MAX_ANNOTATIONS: usize = 200;
+
+/// How much the charts keep of the marker and annotation layers.
+///
+/// Age is measured back from the newest annotation rather than the clock,
+/// so a stored run draws the same way whenever it is opened.
+///
+/// Example:
+/// - `max_fill_markers=2` on entries at `t1 t2 t3` -> draws `t2 t3`
+/// - `annotation_max_age_secs=3600` -> drops annotations over an hour older
+///   than the newest one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChartRetention {
+    /// Per marker series; the newest are kept.
+    pub max_fill_markers: usize,
+    pub max_annotations: usize,
+    pub annotation_max_age_secs: Option<i64>,
+}
+
+impl Default for ChartRetention {
+    fn default() -> Self {
+        Self {
+            max_fill_markers: DEFAULT_MAX_FILL_MARKERS,
+            max_annotations: DEFAULT_MAX_ANNOTATIONS,
+            annotation_max_age_secs: None,
+        }
+    }
+}
+
+impl ChartRetention {
+    /// Reads:
+    /// - `SANDBOX_QUANT_CHART_MAX_FILL_MARKERS`
+    /// - `SANDBOX_QUANT_CHART_MAX_ANNOTATIONS`
+    /// - `SANDBOX_QUANT_CHART_ANNOTATION_MAX_AGE_SECS` (unset keeps every age)
+    pub fn from_env() -> Self {
+        let read = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|raw| raw.trim().parse::<usize>().ok())
+        };
+        let defaults = Self::default();
+        Self {
+            max_fill_markers: read("SANDBOX_QUANT_CHART_MAX_FILL_MARKERS")
+                .unwrap_or(defaults.max_fill_markers),
+            max_annotations: read("SANDBOX_QUANT_CHART_MAX_ANNOTATIONS")
+                .unwrap_or(defaults.max_annotations),
+            annotation_max_age_secs: read("SANDBOX_QUANT_CHART_ANNOTATION_MAX_AGE_SECS")
+                .filter(|secs| *secs > 0)
+                .map(|secs| secs as i64),
+        }
+    }
+
+    /// Keeps the newest `max_annotations` inside the age window, in time
+    /// order.
+    pub fn retain_annotations(&self, annotations: &mut Vec<ChartAnnotation>) {
+        annotations.sort_by_key(|annotation| annotation.time_ms);
+        if let (Some(max_age_secs), Some(newest)) = (
+            self.annotation_max_age_secs,
+            annotations.last().map(|annotation| annotation.time_ms),
+        ) {
+            let cutoff = newest - max_age_secs * 1_000;
+            annotations.retain(|annotation| annotation.time_ms >= cutoff);
+        }
+        let excess = annotations.len().saturating_sub(self.max_annotations);
+        annotations.drain(..excess);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationKind {
+    StopArmed,
+    TargetArmed,
+}
+
+impl AnnotationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::StopArmed => "stop_armed",
+            Self::TargetArmed => "target_armed",
+        }
+    }
+}
+
+/// A lifecycle event pinned to a time and price on the chart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartAnnotation {
+    pub time_ms: i64,
+    pub price: f64,
+    pub label: String,
+    pub kind: AnnotationKind,
+}
+
+/// The protective levels each trade armed at entry.
+///
+/// Example:
+/// - trade `#3` entered at `12:00` with stop `101.5` and target `97.0`
+/// - `stop armed #3` at `(12:00, 101.5)`, `target armed #3` at `(12:00, 97.0)`
+pub fn lifecycle_annotations(trades: &[BacktestTrade]) -> Vec<ChartAnnotation> {
+    trades
+        .iter()
+        .flat_map(|trade| {
+            let time_ms = trade.entry_time.timestamp_millis();
+            [
+                ChartAnnotation {
+                    time_ms,
+                    price: trade.stop_price,
+                    label: format!("stop armed #{}", trade.trade_id),
+                    kind: AnnotationKind::StopArmed,
+                },
+                ChartAnnotation {
+                    time_ms,
+                    price: trade.take_profit_price,
+                    label: format!("target armed #{}", trade.trade_id),
+                    kind: AnnotationKind::TargetArmed,
+                },
+            ]
+        })
+        .filter(|annotation| annotation.price.is_finite() && annotation.price > 0.0)
+        .collect()
+}
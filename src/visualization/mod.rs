@@ -1,6 +1,10 @@
+pub mod annotations;
+pub mod replay;
 pub mod service;
 pub mod types;
 
+pub use annotations::{lifecycle_annotations, AnnotationKind, ChartAnnotation, ChartRetention};
+pub use replay::{ReplayDecision, ReplayStep, TradeReplay};
 pub use service::VisualizationService;
 pub use types::{
     BacktestRunRequest, DashboardQuery, DashboardSnapshot, EquityPoint, MarketSeries, PricePoint,
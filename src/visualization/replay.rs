@@ -0,0 +1,191 @@
+use crate::backtest_app::runner::{BacktestConfig, BacktestTrade};
+use crate::domain::reason::ExitReason;
+use crate::strategy::ev::{EvEstimator, WinProbabilityModel};
+use crate::visualization::service::VisualizationService;
+use crate::visualization::types::MarketSeries;
+
+/// What the strategy lifecycle decided at one replay step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayDecision {
+    Signal,
+    Enter,
+    Hold,
+    TakeProfit,
+    StopLoss,
+    SignalExit,
+    OpenAtEnd,
+}
+
+impl ReplayDecision {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Signal => "signal",
+            Self::Enter => "enter",
+            Self::Hold => "hold",
+            Self::TakeProfit => "take_profit",
+            Self::StopLoss => "stop_loss",
+            Self::SignalExit => "signal_exit",
+            Self::OpenAtEnd => "open_at_end",
+        }
+    }
+
+    fn from_exit_reason(reason: Option<&ExitReason>) -> Self {
+        match reason {
+            Some(ExitReason::TakeProfit) => Self::TakeProfit,
+            Some(ExitReason::StopLoss) => Self::StopLoss,
+            Some(ExitReason::SignalExit | ExitReason::MaxAge) => Self::SignalExit,
+            Some(ExitReason::OpenAtEnd) | None => Self::OpenAtEnd,
+        }
+    }
+}
+
+/// One tick of a replayed trade with the EV snapshot at that price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayStep {
+    pub time_ms: i64,
+    pub price: f64,
+    pub decision: ReplayDecision,
+    /// Move since entry in units of the initial stop distance.
+    pub r_multiple: f64,
+    /// Expected R of holding from this price with the configured win rate.
+    pub expected_value_r: f64,
+}
+
+/// Step-through replay of one backtest trade from trigger to exit.
+///
+/// Example:
+/// - short entry `100`, stop `101`, take profit `98`, win rate `0.8`
+/// - `enter` at `100` -> `r=0.00 ev=1.40R`
+/// - `hold` at `100.5` -> `r=-0.50 ev=1.90R`
+/// - `stop_loss` at `101` -> `r=-1.00`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeReplay {
+    pub trade: BacktestTrade,
+    pub steps: Vec<ReplayStep>,
+    pub cursor: usize,
+    /// Probability model behind every step's EV.
+    pub model_version: String,
+}
+
+impl TradeReplay {
+    /// Builds the replay from the market ticks recorded between entry and exit.
+    ///
+    /// Without matching ticks the replay still has its signal, entry and exit steps.
+    pub fn build(trade: &BacktestTrade, config: &BacktestConfig, series: &MarketSeries) -> Self {
+        let entry_ms = trade.entry_time.timestamp_millis();
+        let exit_ms = trade.exit_time.map(|time| time.timestamp_millis());
+        let snapshot = EvSnapshot(EvEstimator::new(
+            trade.entry_price,
+            trade.stop_price,
+            trade.take_profit_price,
+            config.win_rate_assumption,
+        ));
+        let points = VisualizationService::price_points(series);
+        let trigger_ms = trade.trigger_time.timestamp_millis();
+        let signal_price = points
+            .iter()
+            .rev()
+            .find(|point| point.time_ms <= trigger_ms)
+            .map(|point| point.price)
+            .unwrap_or(trade.entry_price);
+
+        let mut steps = vec![
+            snapshot.step(trigger_ms, signal_price, ReplayDecision::Signal),
+            snapshot.step(entry_ms, trade.entry_price, ReplayDecision::Enter),
+        ];
+        steps.extend(
+            points
+                .iter()
+                .filter(|point| {
+                    point.time_ms > entry_ms
+                        && exit_ms.is_none_or(|exit_ms| point.time_ms < exit_ms)
+                })
+                .map(|point| snapshot.step(point.time_ms, point.price, ReplayDecision::Hold)),
+        );
+        if let (Some(exit_ms), Some(exit_price)) = (exit_ms, trade.exit_price) {
+            steps.push(snapshot.step(
+                exit_ms,
+                exit_price,
+                ReplayDecision::from_exit_reason(trade.exit_reason.as_ref()),
+            ));
+        }
+        Self {
+            trade: trade.clone(),
+            steps,
+            cursor: 0,
+            model_version: snapshot.0.model.version(),
+        }
+    }
+
+    pub fn current(&self) -> &ReplayStep {
+        &self.steps[self.cursor]
+    }
+
+    /// Steps shown so far, including the current one.
+    pub fn visible_steps(&self) -> &[ReplayStep] {
+        &self.steps[..=self.cursor]
+    }
+
+    pub fn step_forward(&mut self) -> bool {
+        if self.cursor + 1 >= self.steps.len() {
+            return false;
+        }
+        self.cursor += 1;
+        true
+    }
+
+    pub fn step_back(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+        true
+    }
+
+    pub fn seek(&mut self, index: usize) {
+        self.cursor = index.min(self.steps.len() - 1);
+    }
+
+    /// Next step whose decision is not `hold`, e.g. to jump straight to the exit.
+    pub fn seek_next_decision(&mut self) -> bool {
+        match self.steps[self.cursor + 1..]
+            .iter()
+            .position(|step| step.decision != ReplayDecision::Hold)
+        {
+            Some(offset) => {
+                self.cursor += offset + 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Example:
+    /// - `step 3/12 hold price=100.50 r=-0.50 ev=1.90R`
+    pub fn describe_current(&self) -> String {
+        let step = self.current();
+        format!(
+            "step {}/{} {} price={:.2} r={:.2} ev={:.2}R",
+            self.cursor + 1,
+            self.steps.len(),
+            step.decision.as_str(),
+            step.price,
+            step.r_multiple,
+            step.expected_value_r,
+        )
+    }
+}
+
+struct EvSnapshot(EvEstimator);
+
+impl EvSnapshot {
+    fn step(&self, time_ms: i64, price: f64, decision: ReplayDecision) -> ReplayStep {
+        ReplayStep {
+            time_ms,
+            price,
+            decision,
+            r_multiple: self.0.r_multiple(price),
+            expected_value_r: self.0.expected_value_r(price),
+        }
+    }
+}
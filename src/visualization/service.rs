@@ -1,22 +1,27 @@
 use std::path::Path;
 
 use crate::app::bootstrap::BinanceMode;
-use crate::backtest_app::runner::{run_backtest_for_path, BacktestExitReason, BacktestTrade};
+use crate::backtest_app::runner::{run_backtest_for_path, BacktestTrade};
 use crate::dataset::query::{
     backtest_summary_for_path, latest_market_data_day_for_path, load_backtest_report,
     load_backtest_run_summaries, load_book_ticker_rows_for_path, load_derived_kline_rows_for_path,
-    load_liquidation_events_for_path, load_raw_kline_rows_for_path, load_recorded_symbols_for_path,
-    metrics_for_path, persist_backtest_report,
+    load_liquidation_events_for_path, load_open_interest_rows_for_path,
+    load_raw_kline_rows_for_path, load_recorded_symbols_for_path, metrics_for_path,
+    persist_backtest_report,
 };
 use crate::dataset::schema::init_schema_for_path;
-use crate::dataset::types::BacktestDatasetSummary;
+use crate::dataset::types::{BacktestDatasetSummary, DerivedKlineRow};
+use crate::domain::reason::ExitReason;
 use crate::error::storage_error::StorageError;
 use crate::record::coordination::RecorderCoordination;
+use crate::strategy::model::StrategyTemplate;
 use crate::visualization::types::{
     BacktestRunRequest, DashboardQuery, DashboardSnapshot, EquityPoint, MarketSeries, PricePoint,
-    SignalKind, SignalMarker,
+    SessionVolumeProfile, SignalKind, SignalMarker, StrategyFocusSeries, VolumeProfileLevel,
 };
 
+const SESSION_MS: i64 = 86_400_000;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct VisualizationService;
 
@@ -66,6 +71,40 @@ impl VisualizationService {
         latest_market_data_day_for_path(&db_path, symbol)
     }
 
+    /// Loads the bars `template` evaluates rather than the dashboard's display series.
+    ///
+    /// The price-cross templates read stored raw klines at their recorded
+    /// interval; the liquidation template reads `1s` bars derived from trades.
+    pub fn load_strategy_focus(
+        &self,
+        query: &DashboardQuery,
+        template: StrategyTemplate,
+    ) -> Result<StrategyFocusSeries, StorageError> {
+        let db_path = RecorderCoordination::new(query.base_dir.clone()).db_path(query.mode);
+        init_schema_for_path(&db_path)?;
+        let (interval, klines, liquidations) = match template {
+            StrategyTemplate::LiquidationBreakdownShort => (
+                Some("1s".to_string()),
+                load_derived_kline_rows_for_path(&db_path, &query.symbol, query.from, query.to)?,
+                load_liquidation_events_for_path(&db_path, &query.symbol, query.from, query.to)?,
+            ),
+            _ => match load_raw_kline_rows_for_path(&db_path, &query.symbol, query.from, query.to)?
+            {
+                Some((interval, rows)) => (Some(interval), rows, Vec::new()),
+                None => (None, Vec::new(), Vec::new()),
+            },
+        };
+        Ok(StrategyFocusSeries {
+            template,
+            symbol: query.symbol.clone(),
+            from: query.from,
+            to: query.to,
+            interval,
+            klines,
+            liquidations,
+        })
+    }
+
     pub fn price_points(series: &MarketSeries) -> Vec<PricePoint> {
         if !series.klines.is_empty() {
             return series
@@ -87,6 +126,57 @@ impl VisualizationService {
             .collect()
     }
 
+    /// Splits klines into UTC-day sessions and buckets each session's volume
+    /// by typical price `(high + low + close) / 3`.
+    pub fn session_volume_profiles(
+        klines: &[DerivedKlineRow],
+        buckets: usize,
+    ) -> Vec<SessionVolumeProfile> {
+        let buckets = buckets.max(1);
+        let mut sessions: Vec<(i64, Vec<&DerivedKlineRow>)> = Vec::new();
+        for row in klines {
+            let session_start_ms = row.open_time_ms.div_euclid(SESSION_MS) * SESSION_MS;
+            match sessions.last_mut() {
+                Some((start, rows)) if *start == session_start_ms => rows.push(row),
+                _ => sessions.push((session_start_ms, vec![row])),
+            }
+        }
+        sessions
+            .into_iter()
+            .map(|(session_start_ms, rows)| {
+                let low = rows.iter().map(|row| row.low).fold(f64::INFINITY, f64::min);
+                let high = rows
+                    .iter()
+                    .map(|row| row.high)
+                    .fold(f64::NEG_INFINITY, f64::max);
+                let step = ((high - low) / buckets as f64).max(f64::EPSILON);
+                let mut levels = (0..buckets)
+                    .map(|index| VolumeProfileLevel {
+                        price_low: low + step * index as f64,
+                        price_high: low + step * (index + 1) as f64,
+                        volume: 0.0,
+                    })
+                    .collect::<Vec<_>>();
+                for row in &rows {
+                    let typical = (row.high + row.low + row.close) / 3.0;
+                    let index = (((typical - low) / step) as usize).min(buckets - 1);
+                    levels[index].volume += row.volume;
+                }
+                let point_of_control = levels
+                    .iter()
+                    .max_by(|left, right| left.volume.total_cmp(&right.volume))
+                    .map(|level| (level.price_low + level.price_high) * 0.5)
+                    .unwrap_or(low);
+                SessionVolumeProfile {
+                    session_start_ms,
+                    session_end_ms: session_start_ms + SESSION_MS,
+                    levels,
+                    point_of_control,
+                }
+            })
+            .collect()
+    }
+
     pub fn equity_curve(starting_equity: f64, trades: &[BacktestTrade]) -> Vec<EquityPoint> {
         let mut equity = starting_equity;
         let mut points = Vec::new();
@@ -121,10 +211,10 @@ impl VisualizationService {
                     price: exit_price,
                     label: format!("exit #{}", trade.trade_id),
                     kind: match exit_reason {
-                        BacktestExitReason::TakeProfit => SignalKind::TakeProfit,
-                        BacktestExitReason::StopLoss => SignalKind::StopLoss,
-                        BacktestExitReason::OpenAtEnd => SignalKind::OpenAtEnd,
-                        BacktestExitReason::SignalExit => SignalKind::SignalExit,
+                        ExitReason::TakeProfit => SignalKind::TakeProfit,
+                        ExitReason::StopLoss => SignalKind::StopLoss,
+                        ExitReason::OpenAtEnd => SignalKind::OpenAtEnd,
+                        ExitReason::SignalExit | ExitReason::MaxAge => SignalKind::SignalExit,
                     },
                 });
             }
@@ -214,6 +304,7 @@ fn load_market_series(
             book_tickers: Vec::new(),
             klines: Vec::new(),
             kline_interval: None,
+            open_interest: Vec::new(),
         });
     }
     let derived_klines = load_derived_kline_rows_for_path(db_path, symbol, from, to)?;
@@ -231,6 +322,7 @@ fn load_market_series(
         book_tickers: load_book_ticker_rows_for_path(db_path, symbol, from, to)?,
         klines,
         kline_interval,
+        open_interest: load_open_interest_rows_for_path(db_path, symbol, from, to)?,
     })
 }
 
@@ -248,6 +340,7 @@ mod tests {
     use super::*;
     use crate::app::bootstrap::BinanceMode;
     use crate::dataset::schema::init_schema_for_path;
+    use crate::domain::position::Side;
     use chrono::{TimeZone, Utc};
     use duckdb::Connection;
 
@@ -256,6 +349,7 @@ mod tests {
         let trades = vec![
             BacktestTrade {
                 trade_id: 1,
+                side: Side::Sell,
                 trigger_time: Utc.timestamp_millis_opt(1_000).single().expect("timestamp"),
                 entry_time: Utc.timestamp_millis_opt(2_000).single().expect("timestamp"),
                 entry_price: 100.0,
@@ -264,13 +358,14 @@ mod tests {
                 qty: 1.0,
                 exit_time: Some(Utc.timestamp_millis_opt(3_000).single().expect("timestamp")),
                 exit_price: Some(98.0),
-                exit_reason: Some(BacktestExitReason::TakeProfit),
+                exit_reason: Some(ExitReason::TakeProfit),
                 gross_pnl: Some(2.0),
                 fees: Some(0.2),
                 net_pnl: Some(1.8),
             },
             BacktestTrade {
                 trade_id: 2,
+                side: Side::Sell,
                 trigger_time: Utc.timestamp_millis_opt(4_000).single().expect("timestamp"),
                 entry_time: Utc.timestamp_millis_opt(5_000).single().expect("timestamp"),
                 entry_price: 99.0,
@@ -279,7 +374,7 @@ mod tests {
                 qty: 1.0,
                 exit_time: Some(Utc.timestamp_millis_opt(6_000).single().expect("timestamp")),
                 exit_price: Some(100.0),
-                exit_reason: Some(BacktestExitReason::StopLoss),
+                exit_reason: Some(ExitReason::StopLoss),
                 gross_pnl: Some(-1.0),
                 fees: Some(0.2),
                 net_pnl: Some(-1.2),
@@ -365,4 +460,69 @@ mod tests {
         std::fs::remove_file(db_path).ok();
         std::fs::remove_dir_all(base_dir).ok();
     }
+
+    #[test]
+    fn strategy_focus_reads_the_bars_each_template_evaluates() {
+        let mut base_dir = std::env::temp_dir();
+        base_dir.push(format!(
+            "sandbox_quant_gui_strategy_focus_{}_{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&base_dir).expect("create temp dir");
+        let db_path = base_dir.join("market-v2-demo.duckdb");
+        init_schema_for_path(&db_path).expect("init schema");
+        let connection = Connection::open(&db_path).expect("open db");
+        connection
+            .execute_batch(
+                "INSERT INTO raw_klines (
+                kline_id, mode, product, symbol, interval, open_time, close_time,
+                open, high, low, close, volume, quote_volume, trade_count, raw_payload
+             ) VALUES (
+                1, 'demo', 'um', 'BTCUSDT', '15m',
+                CAST('2026-03-13 00:00:00' AS TIMESTAMP),
+                CAST('2026-03-13 00:14:59' AS TIMESTAMP),
+                100.0, 101.0, 99.5, 100.5, 10.0, 1005.0, 5, '{}'
+             );
+             INSERT INTO raw_agg_trades (
+                trade_id, mode, symbol, event_time, receive_time, price, qty, is_buyer_maker
+             ) VALUES
+                (1, 'demo', 'BTCUSDT', CAST('2026-03-13 00:00:01' AS TIMESTAMP),
+                 CAST('2026-03-13 00:00:01' AS TIMESTAMP), 100.0, 1.0, false),
+                (2, 'demo', 'BTCUSDT', CAST('2026-03-13 00:00:02' AS TIMESTAMP),
+                 CAST('2026-03-13 00:00:02' AS TIMESTAMP), 100.2, 1.0, true);",
+            )
+            .expect("insert market rows");
+        drop(connection);
+        let query = DashboardQuery {
+            mode: BinanceMode::Demo,
+            base_dir: base_dir.clone(),
+            symbol: "BTCUSDT".to_string(),
+            from: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
+            to: chrono::NaiveDate::from_ymd_opt(2026, 3, 13).expect("date"),
+            selected_run_id: None,
+            run_limit: 10,
+        };
+        let service = VisualizationService;
+
+        let dashboard = service.load_dashboard(query.clone()).expect("dashboard");
+        let sma = service
+            .load_strategy_focus(&query, StrategyTemplate::PriceSmaCrossLong)
+            .expect("sma focus");
+        let liquidation = service
+            .load_strategy_focus(&query, StrategyTemplate::LiquidationBreakdownShort)
+            .expect("liquidation focus");
+
+        assert_eq!(
+            dashboard.market_series.kline_interval.as_deref(),
+            Some("1s")
+        );
+        assert_eq!(sma.interval.as_deref(), Some("15m"));
+        assert_eq!(sma.klines.len(), 1);
+        assert_eq!(liquidation.interval.as_deref(), Some("1s"));
+        assert_eq!(liquidation.klines.len(), 2);
+
+        std::fs::remove_file(db_path).ok();
+        std::fs::remove_dir_all(base_dir).ok();
+    }
 }
@@ -4,7 +4,7 @@ use crate::app::bootstrap::BinanceMode;
 use crate::backtest_app::runner::{BacktestConfig, BacktestReport};
 use crate::dataset::types::{
     BacktestDatasetSummary, BacktestRunSummaryRow, BookTickerRow, DerivedKlineRow,
-    LiquidationEventRow, RecorderMetrics,
+    LiquidationEventRow, OpenInterestRow, RecorderMetrics,
 };
 use crate::strategy::model::StrategyTemplate;
 
@@ -38,6 +38,23 @@ pub struct MarketSeries {
     pub book_tickers: Vec<BookTickerRow>,
     pub klines: Vec<DerivedKlineRow>,
     pub kline_interval: Option<String>,
+    pub open_interest: Vec<OpenInterestRow>,
+}
+
+/// Bars loaded the way one strategy template reads them.
+///
+/// Example:
+/// - `price-sma-cross-long` on a store with `15m` raw klines -> `interval=15m`
+/// - `liquidation-breakdown-short` -> `1s` bars derived from trades, plus liquidations
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyFocusSeries {
+    pub template: StrategyTemplate,
+    pub symbol: String,
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    pub interval: Option<String>,
+    pub klines: Vec<DerivedKlineRow>,
+    pub liquidations: Vec<LiquidationEventRow>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -46,6 +63,27 @@ pub struct PricePoint {
     pub price: f64,
 }
 
+/// Traded volume inside one price bucket of a session profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeProfileLevel {
+    pub price_low: f64,
+    pub price_high: f64,
+    pub volume: f64,
+}
+
+/// Volume-at-price histogram for one UTC trading day.
+///
+/// Example:
+/// - klines for `2026-03-13` split into `24` buckets between the day low and high
+/// - `point_of_control` is the midpoint of the bucket with the most volume
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionVolumeProfile {
+    pub session_start_ms: i64,
+    pub session_end_ms: i64,
+    pub levels: Vec<VolumeProfileLevel>,
+    pub point_of_control: f64,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct EquityPoint {
     pub time_ms: i64,
@@ -1,5 +1,13 @@
+use chrono::{Duration, Utc};
 use sandbox_quant::app::bootstrap::{AppBootstrap, BinanceMode};
-use sandbox_quant::app::commands::AppCommand;
+use sandbox_quant::app::commands::{
+    AppCommand, BasisCommand, DomAction, DomCommand, NewsCommand, OrderFilter, PortfolioView,
+};
+use sandbox_quant::app::output::{
+    render_command_output, render_inactivity_output, render_news_headlines, render_news_output,
+    render_order_history, render_symbol_status, render_user_stream_updates,
+};
+use sandbox_quant::app::resource_profile::ResourceProfile;
 use sandbox_quant::app::runtime::AppRuntime;
 use sandbox_quant::domain::balance::BalanceSnapshot;
 use sandbox_quant::domain::exposure::Exposure;
@@ -11,14 +19,48 @@ use sandbox_quant::domain::order_type::OrderType;
 use sandbox_quant::domain::position::PositionSnapshot;
 use sandbox_quant::domain::position::Side;
 use sandbox_quant::error::exchange_error::ExchangeError;
+use sandbox_quant::exchange::binance::user_stream::{parse_user_stream_event, UserStreamEvent};
 use sandbox_quant::exchange::fake::FakeExchange;
 use sandbox_quant::exchange::symbol_rules::SymbolRules;
-use sandbox_quant::exchange::types::AuthoritativeSnapshot;
+use sandbox_quant::exchange::types::{
+    ApiPermissions, AuthoritativeSnapshot, EarnPosition, EarnTransferKind, FuturesLeverage,
+    MarginType, OrderBookDepth, SymbolTradingStatus,
+};
 use sandbox_quant::execution::command::{CommandSource, ExecutionCommand};
+use sandbox_quant::execution::depth_filter::DepthFilterConfig;
+use sandbox_quant::execution::dom_ladder::DomPrice;
+use sandbox_quant::execution::futures::leverage::parse_futures_leverage;
+use sandbox_quant::execution::hold_ev::parse_hold_ev_profiles;
+use sandbox_quant::execution::illiquid_window::parse_illiquid_windows;
+use sandbox_quant::execution::max_age::parse_max_position_age;
+use sandbox_quant::execution::paper_shadow::PaperShadowConfig;
+use sandbox_quant::execution::price_source::PriceSource;
+use sandbox_quant::execution::slippage_guard::SlippageGuardConfig;
+use sandbox_quant::execution::take_profit::{parse_take_profit_profiles, TakeProfitProfile};
+use sandbox_quant::execution::trailing_stop::{TrailDistance, TrailingStopMode};
+use sandbox_quant::market_data::news_feed::{NewsFeedConfig, NewsHeadline};
+use sandbox_quant::market_data::range_stats::SymbolRangeStats;
+use sandbox_quant::market_data::synthetic::parse_synthetic_instruments;
+use sandbox_quant::observability::diagnostics_bundle::{redact_query_secrets, redacted_config};
+use sandbox_quant::observability::event_routing::EventRouting;
+use sandbox_quant::portfolio::earn::EarnConfig;
+use sandbox_quant::portfolio::staleness::StalenessState;
 use sandbox_quant::portfolio::store::PortfolioStateStore;
-use sandbox_quant::record::coordination::RecorderCoordination;
-use sandbox_quant::strategy::command::{StrategyCommand, StrategyStartConfig};
+use sandbox_quant::record::coordination::{DegradedSymbol, RecorderCoordination};
+use sandbox_quant::storage::event_log::log;
+use sandbox_quant::storage::order_store::{FillRow, HistoryQuery, OrderStore, TimeBucket};
+use sandbox_quant::strategy::adaptive::AdaptiveTuning;
+use sandbox_quant::strategy::basis::BasisArbConfig;
+use sandbox_quant::strategy::command::{
+    OpenPositionResolution, StrategyCommand, StrategyGroupAction, StrategyStartConfig,
+};
+use sandbox_quant::strategy::filter::{StrategyFilter, StrategyListQuery};
+use sandbox_quant::strategy::group::StrategyGroupStore;
+use sandbox_quant::strategy::kpi::KpiTargets;
 use sandbox_quant::strategy::model::{StrategyTemplate, StrategyWatchState};
+use sandbox_quant::strategy::ramp::SizeRamp;
+use serde_json::json;
+use std::collections::BTreeSet;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -42,6 +84,7 @@ fn sample_snapshot() -> AuthoritativeSnapshot {
             instrument,
             market: Market::Futures,
             side: sandbox_quant::domain::position::Side::Sell,
+            price: None,
             orig_qty: 0.3,
             executed_qty: 0.0,
             reduce_only: true,
@@ -69,6 +112,7 @@ fn app_runtime_executes_command_and_logs_event() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
@@ -139,6 +183,7 @@ fn app_runtime_executes_target_exposure_from_flat_position() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
@@ -172,6 +217,65 @@ fn app_runtime_executes_target_exposure_from_flat_position() {
     assert_eq!(app.event_log.records[4].kind, "app.execution.completed");
 }
 
+#[test]
+fn app_runtime_measures_decision_latency_on_the_exchange_clock() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 1000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
+    // A venue clock a minute behind the host must not show up as latency.
+    exchange.set_exchange_clock_offset_ms(Some(-60_000));
+
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.portfolio_store
+        .refresh_from_exchange(&app.exchange)
+        .expect("seed snapshot");
+
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+                instrument,
+                target: Exposure::new(0.5).expect("bounded exposure"),
+                order_type: OrderType::Market,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("flat target exposure should succeed");
+
+    let row = app.execution.history.rows().back().expect("order row");
+    let signal_time = row.signal_time.expect("signal stamped");
+    assert!((Utc::now() - signal_time).num_seconds() >= 59);
+    let latency = row.latency_ms().expect("latency");
+    assert!((0..5_000).contains(&latency), "latency={latency}");
+
+    let completed = app.event_log.records.last().expect("completed event");
+    assert_eq!(completed.kind, "app.execution.completed");
+    assert_eq!(completed.payload["order_latency_ms"][0], latency);
+    assert!(
+        render_order_history(&app.execution.history, &OrderFilter::default())
+            .contains("decision_latency p50=")
+    );
+}
+
 #[test]
 fn app_runtime_surfaces_exchange_submit_failure_detail() {
     let instrument = Instrument::new("BTCUSDT");
@@ -191,6 +295,7 @@ fn app_runtime_surfaces_exchange_submit_failure_detail() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
@@ -242,6 +347,7 @@ fn app_runtime_executes_option_order_and_logs_event() {
             min_qty: 0.01,
             max_qty: 100.0,
             step_size: 0.01,
+            tick_size: 0.01,
         },
     );
 
@@ -289,6 +395,7 @@ fn app_runtime_starts_strategy_watch_and_logs_event() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
@@ -306,6 +413,10 @@ fn app_runtime_starts_strategy_watch_and_logs_event() {
                     win_rate: 0.8,
                     r_multiple: 1.5,
                     max_entry_slippage_pct: 0.001,
+                    seed_capital_usdt: None,
+                    ramp: None,
+                    adapt: None,
+                    script: None,
                 },
             }),
         )
@@ -336,6 +447,7 @@ fn app_runtime_stops_strategy_watch_and_moves_it_to_history() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
@@ -353,6 +465,10 @@ fn app_runtime_stops_strategy_watch_and_moves_it_to_history() {
                     win_rate: 0.8,
                     r_multiple: 1.5,
                     max_entry_slippage_pct: 0.001,
+                    seed_capital_usdt: None,
+                    ramp: None,
+                    adapt: None,
+                    script: None,
                 },
             }),
         )
@@ -360,7 +476,10 @@ fn app_runtime_stops_strategy_watch_and_moves_it_to_history() {
     runtime
         .run(
             &mut app,
-            AppCommand::Strategy(StrategyCommand::Stop { watch_id: 1 }),
+            AppCommand::Strategy(StrategyCommand::Stop {
+                watch_id: 1,
+                resolution: None,
+            }),
         )
         .expect("stop should succeed");
 
@@ -372,6 +491,436 @@ fn app_runtime_stops_strategy_watch_and_moves_it_to_history() {
     assert_eq!(app.strategy_store.history(BinanceMode::Demo).len(), 1);
 }
 
+#[test]
+fn app_runtime_stop_with_open_position_previews_pnl_until_resolved() {
+    let mut app = trailing_stop_app();
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("strategy-stop-pnl"));
+    app.exchange
+        .set_last_price(Instrument::new("BTCUSDT"), Market::Futures, 49000.0);
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: Instrument::new("BTCUSDT"),
+                config: StrategyStartConfig::default(),
+            }),
+        )
+        .expect("start should succeed");
+
+    let stop = |resolution| {
+        AppCommand::Strategy(StrategyCommand::Stop {
+            watch_id: 1,
+            resolution,
+        })
+    };
+    runtime
+        .run(&mut app, stop(None))
+        .expect("preview should succeed");
+    let preview = app.event_log.records.last().expect("preview event");
+    assert_eq!(preview.kind, "app.strategy.stop_confirmation_required");
+    assert_eq!(preview.payload["unrealized_pnl_usdt"], 300.0);
+    let rendered = render_command_output(
+        &stop(None),
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("unrealized_pnl_usdt=300.00"));
+    assert!(rendered.contains("/strategy stop 1 flatten"));
+    assert_eq!(app.strategy_store.active_watches(app.mode).len(), 1);
+
+    runtime
+        .run(&mut app, stop(Some(OpenPositionResolution::Keep)))
+        .expect("keep should succeed");
+    assert_eq!(
+        app.event_log.records.last().expect("keep event").kind,
+        "app.strategy.stop_cancelled"
+    );
+    assert_eq!(app.strategy_store.active_watches(app.mode).len(), 1);
+    assert!(app.exchange.close_requests().is_empty());
+
+    runtime
+        .run(&mut app, stop(Some(OpenPositionResolution::Flatten)))
+        .expect("flatten should succeed");
+    assert_eq!(
+        app.event_log.records.last().expect("pending event").kind,
+        "app.strategy.stop_pending"
+    );
+    assert_eq!(app.exchange.close_requests().len(), 1);
+    assert_eq!(app.strategy_store.active_watches(app.mode).len(), 1);
+    runtime
+        .run(&mut app, stop(Some(OpenPositionResolution::Flatten)))
+        .expect("repeated flatten should succeed");
+    assert_eq!(app.exchange.close_requests().len(), 1);
+
+    app.exchange
+        .replace_snapshot(AuthoritativeSnapshot::default());
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let stopped = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.strategy.watch_stopped")
+        .expect("stop event");
+    assert_eq!(stopped.payload["position_resolution"], "flatten");
+    assert!(app.strategy_store.active_watches(app.mode).is_empty());
+}
+
+#[test]
+fn app_runtime_stop_with_manual_resolution_adopts_the_position() {
+    let mut app = trailing_stop_app();
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("strategy-stop-manual"));
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: Instrument::new("BTCUSDT"),
+                config: StrategyStartConfig::default(),
+            }),
+        )
+        .expect("start should succeed");
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Stop {
+                watch_id: 1,
+                resolution: Some(OpenPositionResolution::Manual),
+            }),
+        )
+        .expect("manual stop should succeed");
+
+    let adopted = &app.portfolio_store.adopted_positions[&Instrument::new("BTCUSDT")];
+    assert_eq!(adopted.entry_price, 50000.0);
+    assert!(app.exchange.close_requests().is_empty());
+    assert!(app.strategy_store.active_watches(app.mode).is_empty());
+    let stopped = app.event_log.records.last().expect("stop event");
+    assert_eq!(stopped.payload["position_resolution"], "manual");
+}
+
+#[test]
+fn app_runtime_stops_strategy_watch_when_seed_capital_is_exhausted() {
+    let mut app = trailing_stop_app();
+    app.recorder_coordination =
+        RecorderCoordination::new(unique_test_dir("strategy-capital-exhausted"));
+    app.exchange
+        .set_last_price(Instrument::new("BTCUSDT"), Market::Futures, 49900.0);
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: Instrument::new("BTCUSDT"),
+                config: StrategyStartConfig {
+                    seed_capital_usdt: Some(200.0),
+                    ..StrategyStartConfig::default()
+                },
+            }),
+        )
+        .expect("start should succeed");
+    for client_order_id in ["strategy-1-1", "manual-1"] {
+        let fill = json!({
+            "e": "ORDER_TRADE_UPDATE", "E": 1_700_000_000_000_i64,
+            "o": {
+                "s": "BTCUSDT", "c": client_order_id, "S": "SELL", "q": "0.3", "p": "0",
+                "X": "FILLED", "i": 1, "l": "0.3", "z": "0.3", "L": "50000"
+            }
+        });
+        let event = parse_user_stream_event(&fill, Market::Futures).expect("order update");
+        runtime.ingest_user_stream_event(&mut app, event, Utc::now());
+    }
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    let watch = app.strategy_store.get(app.mode, 1).expect("watch stored");
+    let ledger = watch.ledger.as_ref().expect("ledger allocated");
+    assert!((ledger.unrealized_pnl_usdt - 30.0).abs() < 1e-6);
+    assert!((ledger.roi() - 0.15).abs() < 1e-6);
+    assert!((ledger.risk_budget_usdt(0.005) - 1.15).abs() < 1e-6);
+
+    app.exchange
+        .set_last_price(Instrument::new("BTCUSDT"), Market::Futures, 50700.0);
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    let exhausted = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.strategy.capital_exhausted")
+        .expect("exhaustion event");
+    assert_eq!(exhausted.payload["watch_id"], 1);
+    assert_eq!(exhausted.payload["seed_capital_usdt"], 200.0);
+    assert!(app.strategy_store.active_watches(app.mode).is_empty());
+    assert_eq!(
+        app.strategy_store.get(app.mode, 1).expect("history").state,
+        StrategyWatchState::Stopped
+    );
+}
+
+#[test]
+fn app_runtime_tracks_strategy_kpis_and_shows_a_status_chip() {
+    let mut app = trailing_stop_app();
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("strategy-kpi"));
+    app.exchange
+        .set_last_price(Instrument::new("BTCUSDT"), Market::Futures, 49900.0);
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: Instrument::new("BTCUSDT"),
+                config: StrategyStartConfig::default(),
+            }),
+        )
+        .expect("start should succeed");
+    let kpi = |targets: Option<KpiTargets>| {
+        AppCommand::Strategy(StrategyCommand::Kpi {
+            watch_id: 1,
+            targets,
+        })
+    };
+    runtime
+        .run(
+            &mut app,
+            kpi(Some(KpiTargets {
+                min_win_rate: Some(0.5),
+                max_drawdown_usdt: Some(100.0),
+                ..KpiTargets::default()
+            })),
+        )
+        .expect("kpi should be set");
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    app.exchange.replace_snapshot(AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    let watch = app.strategy_store.get(app.mode, 1).expect("watch stored");
+    let stats = watch.kpi.as_ref().expect("kpi tracked");
+    assert_eq!((stats.trades, stats.wins), (1, 1));
+    let render = |app: &AppBootstrap<FakeExchange>, command: AppCommand| {
+        render_command_output(
+            &command,
+            &app.portfolio_store,
+            &app.price_store,
+            &app.event_log,
+            &app.strategy_store,
+            app.mode,
+            &app.execution.history,
+        )
+    };
+    let list = render(
+        &app,
+        AppCommand::Strategy(StrategyCommand::List {
+            query: StrategyListQuery::default(),
+        }),
+    );
+    assert!(list.contains("group=none kpi=green"), "{list}");
+    let show = render(
+        &app,
+        AppCommand::Strategy(StrategyCommand::Show { watch_id: 1 }),
+    );
+    assert!(show.contains("kpi=green targets=win>=50% dd<=100"));
+    assert!(show.contains("- drawdown_usdt actual=0.00 target=100.00 green"));
+
+    runtime.run(&mut app, kpi(None)).expect("kpi should clear");
+    let list = render(
+        &app,
+        AppCommand::Strategy(StrategyCommand::List {
+            query: StrategyListQuery::default(),
+        }),
+    );
+    assert!(!list.contains("kpi="));
+    assert!(runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Kpi {
+                watch_id: 9,
+                targets: None,
+            })
+        )
+        .is_err());
+}
+
+#[test]
+fn app_runtime_ramps_new_strategy_size_after_non_losing_trades() {
+    let mut app = trailing_stop_app();
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("strategy-size-ramp"));
+    app.exchange
+        .set_last_price(Instrument::new("BTCUSDT"), Market::Futures, 49900.0);
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: Instrument::new("BTCUSDT"),
+                config: StrategyStartConfig {
+                    risk_pct: 0.01,
+                    ramp: Some(SizeRamp::new(0.4, 2)),
+                    ..StrategyStartConfig::default()
+                },
+            }),
+        )
+        .expect("start should succeed");
+    let watch = app.strategy_store.get(app.mode, 1).expect("watch stored");
+    assert!((watch.effective_risk_pct() - 0.004).abs() < 1e-12);
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    // The short closes in profit at the last mark.
+    app.exchange.replace_snapshot(AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    let advanced = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.strategy.ramp_advanced")
+        .expect("ramp should advance");
+    assert_eq!(advanced.payload["qualifying_trades"], 1);
+    assert_eq!(advanced.payload["complete"], false);
+    let watch = app.strategy_store.get(app.mode, 1).expect("watch stored");
+    assert!((watch.effective_risk_pct() - 0.007).abs() < 1e-12);
+    let rendered = render_command_output(
+        &AppCommand::Strategy(StrategyCommand::Show { watch_id: 1 }),
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("ramp=1/2 size_fraction=0.70"));
+
+    // Re-enabling starts the ramp over.
+    app.strategy_store
+        .set_enabled(app.mode, 1, false)
+        .expect("disable should succeed");
+    app.strategy_store
+        .set_enabled(app.mode, 1, true)
+        .expect("enable should succeed");
+    let watch = app.strategy_store.get(app.mode, 1).expect("watch stored");
+    assert!((watch.effective_risk_pct() - 0.004).abs() < 1e-12);
+}
+
+#[test]
+fn app_runtime_adapts_strategy_parameters_and_rolls_back() {
+    let mut app = trailing_stop_app();
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("strategy-adapt"));
+    app.exchange
+        .set_last_price(Instrument::new("BTCUSDT"), Market::Futures, 49900.0);
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: Instrument::new("BTCUSDT"),
+                config: StrategyStartConfig {
+                    risk_pct: 0.01,
+                    r_multiple: 2.0,
+                    ramp: None,
+                    adapt: Some(AdaptiveTuning::new(1, 0.1, 0.5)),
+                    ..StrategyStartConfig::default()
+                },
+            }),
+        )
+        .expect("start should succeed");
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    // The short closes in profit, which beats the configured win rate.
+    app.exchange.replace_snapshot(AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    let adapted = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.strategy.adapted")
+        .expect("parameters should adapt");
+    assert_eq!(adapted.payload["trades"], 1);
+    assert_eq!(adapted.payload["changes"][0]["param"], "risk_pct");
+    let watch = app.strategy_store.get(app.mode, 1).expect("watch stored");
+    assert!((watch.config.risk_pct - 0.011).abs() < 1e-12);
+    assert!((watch.config.r_multiple - 2.2).abs() < 1e-12);
+    let rendered = render_command_output(
+        &AppCommand::Strategy(StrategyCommand::Show { watch_id: 1 }),
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("adapt=0/1 trades step=0.10 drift=0.50 adaptations=1"));
+    assert!(rendered.contains("- adaptation=1 at="));
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Rollback { watch_id: 1 }),
+        )
+        .expect("rollback should succeed");
+    let watch = app.strategy_store.get(app.mode, 1).expect("watch stored");
+    assert_eq!(watch.config.risk_pct, 0.01);
+    assert_eq!(watch.config.r_multiple, 2.0);
+    assert_eq!(
+        app.event_log
+            .records
+            .last()
+            .map(|record| record.kind.as_str()),
+        Some("app.strategy.adaptation_rolled_back")
+    );
+    assert!(runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Rollback { watch_id: 1 }),
+        )
+        .is_err());
+}
+
 #[test]
 fn app_runtime_separates_strategy_watches_by_mode() {
     let instrument = Instrument::new("BTCUSDT");
@@ -387,6 +936,7 @@ fn app_runtime_separates_strategy_watches_by_mode() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
@@ -404,6 +954,10 @@ fn app_runtime_separates_strategy_watches_by_mode() {
                     win_rate: 0.8,
                     r_multiple: 1.5,
                     max_entry_slippage_pct: 0.001,
+                    seed_capital_usdt: None,
+                    ramp: None,
+                    adapt: None,
+                    script: None,
                 },
             }),
         )
@@ -421,6 +975,10 @@ fn app_runtime_separates_strategy_watches_by_mode() {
                     win_rate: 0.8,
                     r_multiple: 1.5,
                     max_entry_slippage_pct: 0.001,
+                    seed_capital_usdt: None,
+                    ramp: None,
+                    adapt: None,
+                    script: None,
                 },
             }),
         )
@@ -460,6 +1018,7 @@ fn app_runtime_refreshes_after_close_all_and_reports_remaining_positions() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
@@ -491,3 +1050,4305 @@ fn app_runtime_refreshes_after_close_all_and_reports_remaining_positions() {
     assert_eq!(app.event_log.records[3].kind, "app.execution.completed");
     assert_eq!(app.event_log.records[3].payload["remaining_positions"], 0);
 }
+
+#[test]
+fn app_runtime_flatten_confirms_then_cancels_orders_and_closes_selected_instrument() {
+    let mut app = trailing_stop_app();
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Flatten {
+                instrument: None,
+                confirmed: false,
+            },
+        )
+        .expect("flatten without a selection should not fail");
+    assert_eq!(
+        app.event_log.records.last().expect("event").kind,
+        "app.execution.flatten_unavailable"
+    );
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Flatten {
+                instrument: Some(Instrument::new("BTCUSDT")),
+                confirmed: false,
+            },
+        )
+        .expect("flatten should ask for confirmation");
+    let pending = app.event_log.records.last().expect("confirmation event");
+    assert_eq!(pending.kind, "app.execution.flatten_confirmation_required");
+    assert_eq!(pending.payload["signed_qty"], -0.3);
+    assert_eq!(pending.payload["open_orders"], 1);
+    assert!(app.exchange.cancel_requests().is_empty());
+    assert!(app.exchange.close_requests().is_empty());
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Flatten {
+                instrument: None,
+                confirmed: true,
+            },
+        )
+        .expect("confirmed flatten should succeed");
+    assert_eq!(
+        app.exchange.cancel_requests(),
+        vec![(
+            Instrument::new("BTCUSDT"),
+            Market::Futures,
+            "close-1".to_string()
+        )]
+    );
+    let requests = app.exchange.close_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].side, Side::Buy);
+    let flattened = app.event_log.records.last().expect("flattened event");
+    assert_eq!(flattened.kind, "app.execution.flattened");
+    assert_eq!(flattened.payload["cancelled"][0], "close-1");
+    assert_eq!(flattened.payload["outcome_kind"], "Submitted");
+    let rendered = render_command_output(
+        &AppCommand::Flatten {
+            instrument: None,
+            confirmed: true,
+        },
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert_eq!(
+        rendered,
+        "flattened BTCUSDT cancelled=1 cancel_failed=0 close=Submitted"
+    );
+}
+
+#[test]
+fn app_runtime_panic_disables_watches_cancels_everything_and_reconciles() {
+    let mut snapshot = sample_snapshot();
+    snapshot.open_orders.push(OpenOrder {
+        order_id: Some(OrderId(9)),
+        client_order_id: "eth-entry".to_string(),
+        instrument: Instrument::new("ETHUSDT"),
+        market: Market::Futures,
+        side: Side::Buy,
+        price: Some(3000.0),
+        orig_qty: 1.0,
+        executed_qty: 0.0,
+        reduce_only: false,
+        status: OrderStatus::Submitted,
+    });
+    let exchange = FakeExchange::new(snapshot);
+    exchange.set_symbol_rules(
+        Instrument::new("BTCUSDT"),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let watch = app
+        .strategy_store
+        .create_watch(
+            app.mode,
+            StrategyTemplate::LiquidationBreakdownShort,
+            Instrument::new("SOLUSDT"),
+            StrategyStartConfig {
+                risk_pct: 0.005,
+                win_rate: 0.8,
+                r_multiple: 1.5,
+                max_entry_slippage_pct: 0.001,
+                seed_capital_usdt: None,
+                ramp: None,
+                adapt: None,
+                script: None,
+            },
+        )
+        .expect("watch");
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(&mut app, AppCommand::Panic { confirmed: false })
+        .expect("panic should ask for confirmation");
+    let pending = app.event_log.records.last().expect("confirmation event");
+    assert_eq!(pending.kind, "app.safety.panic_confirmation_required");
+    assert_eq!(pending.payload["instruments"][0], "BTCUSDT");
+    assert_eq!(pending.payload["instruments"][1], "ETHUSDT");
+    assert_eq!(pending.payload["active_watches"], 1);
+    assert!(app.exchange.cancel_requests().is_empty());
+
+    runtime
+        .run(&mut app, AppCommand::Panic { confirmed: true })
+        .expect("panic should run");
+
+    assert_eq!(
+        app.exchange
+            .cancel_requests()
+            .into_iter()
+            .map(|(_, _, client_order_id)| client_order_id)
+            .collect::<Vec<_>>(),
+        vec!["close-1".to_string(), "eth-entry".to_string()]
+    );
+    assert_eq!(app.exchange.close_requests().len(), 1);
+    assert!(app.execution.entries_disabled);
+    assert_eq!(
+        app.strategy_store
+            .get(app.mode, watch.id)
+            .expect("watch")
+            .state,
+        StrategyWatchState::Disabled
+    );
+    let progress = app
+        .event_log
+        .records
+        .iter()
+        .filter(|record| record.kind == "app.safety.panic_progress")
+        .collect::<Vec<_>>();
+    assert_eq!(progress.len(), 2);
+    assert_eq!(progress[1].payload["step"], 2);
+    assert_eq!(progress[1].payload["outcome_kind"], "SkippedNoPosition");
+    let completed = app.event_log.records.last().expect("completed event");
+    assert_eq!(completed.kind, "app.safety.panic_completed");
+    // The fake venue drops cancelled orders but never fills the close.
+    assert_eq!(completed.payload["remaining_positions"][0], "BTCUSDT");
+    assert_eq!(completed.payload["remaining_open_orders"], 0);
+    let rendered = render_command_output(
+        &AppCommand::Panic { confirmed: true },
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.starts_with("[1/2] BTCUSDT cancelled=1 cancel_failed=0 close=Submitted\n"));
+    assert!(rendered.contains("panic summary: CHECK MANUALLY"));
+    assert!(rendered.contains("positions left   BTCUSDT"));
+}
+
+fn trailing_stop_app() -> AppBootstrap<FakeExchange> {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(sample_snapshot());
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(instrument, Market::Futures, 50000.0);
+    AppBootstrap::new(exchange, PortfolioStateStore::default())
+}
+
+#[test]
+fn app_runtime_places_native_trailing_stop_for_futures_position() {
+    let mut app = trailing_stop_app();
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::PlaceTrailingStop {
+                instrument: Instrument::new("BTCUSDT"),
+                trail: TrailDistance::Percent(1.5),
+                source: CommandSource::User,
+            }),
+        )
+        .expect("trailing stop should be placed");
+
+    let requests = app.exchange.close_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].side, Side::Buy);
+    assert!(requests[0].reduce_only);
+    assert_eq!(
+        requests[0].order_type,
+        OrderType::TrailingStopMarket {
+            callback_rate_pct: 1.5
+        }
+    );
+    assert!(app.execution.internal_trailing_stops.is_empty());
+    let completed = app.event_log.records.last().expect("completion event");
+    assert_eq!(completed.payload["engine"], "exchange");
+}
+
+#[test]
+fn app_runtime_sizes_atr_trailing_stops_from_the_daily_atr() {
+    let mut app = trailing_stop_app();
+    let mut runtime = AppRuntime::default();
+    let instrument = Instrument::new("BTCUSDT");
+    let place = AppCommand::Execution(ExecutionCommand::PlaceTrailingStop {
+        instrument: instrument.clone(),
+        trail: TrailDistance::AtrMultiple(2.0),
+        source: CommandSource::User,
+    });
+
+    let error = runtime
+        .run(&mut app, place.clone())
+        .expect_err("no daily bars means no ATR to size the trail");
+    assert!(error.to_string().contains("no daily ATR for BTCUSDT"));
+    assert!(app.exchange.close_requests().is_empty());
+
+    app.exchange.set_range_stats(
+        instrument,
+        SymbolRangeStats {
+            change_24h_pct: 0.5,
+            atr_pct: 1.2,
+            avg_quote_volume: 1.0e9,
+        },
+    );
+    runtime
+        .run(&mut app, place)
+        .expect("atr trailing stop should be placed");
+
+    let requests = app.exchange.close_requests();
+    assert_eq!(requests.len(), 1);
+    let OrderType::TrailingStopMarket { callback_rate_pct } = requests[0].order_type else {
+        panic!("expected a native trailing stop");
+    };
+    assert!((callback_rate_pct - 2.4).abs() < 1e-9);
+    let completed = app.event_log.records.last().expect("completion event");
+    assert_eq!(completed.payload["trail"], "2atr");
+    assert!((completed.payload["callback_rate_pct"].as_f64().unwrap() - 2.4).abs() < 1e-9);
+    assert_eq!(
+        TrailDistance::AtrMultiple(10.0).callback_rate_pct(Some(3.0)),
+        Some(10.0)
+    );
+}
+
+#[test]
+fn app_runtime_flattens_positions_held_past_their_maximum_age() {
+    let btc = Instrument::new("BTCUSDT");
+    let mut app = trailing_stop_app();
+    app.execution.max_position_age_secs = parse_max_position_age("btcusdt=14400,ETHUSDT=0,bogus");
+    assert_eq!(
+        app.execution
+            .max_position_age_secs
+            .keys()
+            .collect::<Vec<_>>(),
+        vec![&btc]
+    );
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert!(app.exchange.close_requests().is_empty());
+
+    let positions = app.portfolio_store.snapshot.positions.clone();
+    app.portfolio_store.entry_stats.forget(&btc);
+    app.portfolio_store
+        .entry_stats
+        .observe(&positions, Utc::now() - Duration::hours(5));
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let requests = app.exchange.close_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].instrument, btc);
+    assert_eq!(requests[0].order_type, OrderType::Market);
+    let exited = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.position.max_age_exited")
+        .expect("max age exit logged");
+    assert_eq!(exited.payload["reason"], "max_age");
+    assert_eq!(exited.payload["max_age_secs"], 14400);
+    assert_eq!(exited.payload["outcome_kind"], "Submitted");
+    assert!(exited.payload["held_secs"].as_u64().unwrap_or_default() >= 5 * 3600);
+}
+
+#[test]
+fn app_runtime_internal_trailing_stop_closes_after_retrace() {
+    let mut app = trailing_stop_app();
+    app.execution.trailing_stop_mode = TrailingStopMode::Internal;
+    let mut runtime = AppRuntime::default();
+    let instrument = Instrument::new("BTCUSDT");
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::PlaceTrailingStop {
+                instrument: instrument.clone(),
+                trail: TrailDistance::Percent(1.0),
+                source: CommandSource::User,
+            }),
+        )
+        .expect("internal trailing stop should be registered");
+    assert!(app.exchange.close_requests().is_empty());
+    assert_eq!(app.execution.internal_trailing_stops.len(), 1);
+
+    app.exchange
+        .set_last_price(instrument.clone(), Market::Futures, 49000.0);
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert!(app.exchange.close_requests().is_empty());
+
+    app.exchange
+        .set_last_price(instrument.clone(), Market::Futures, 49600.0);
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let requests = app.exchange.close_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].order_type, OrderType::Market);
+    assert!(app.execution.internal_trailing_stops.is_empty());
+    assert!(app
+        .event_log
+        .records
+        .iter()
+        .any(|record| record.kind == "app.execution.trailing_stop_triggered"));
+}
+
+#[test]
+fn take_profit_profiles_parse_named_entries_and_keep_default() {
+    let profiles =
+        parse_take_profit_profiles("scalp=0.5:0.5,1:0.3,trail:0.5;bad=2:0.9,1:0.3,trail:1");
+
+    assert_eq!(profiles.len(), 1);
+    assert!(!profiles.contains_key("default"));
+    assert_eq!(TakeProfitProfile::default().label(), "1:0.5,2:0.3,trail:1");
+    let scalp = &profiles["scalp"];
+    assert_eq!(scalp.levels.len(), 2);
+    assert_eq!(scalp.runner_callback_pct, 0.5);
+    assert!((scalp.runner_fraction() - 0.2).abs() < 1e-9);
+    assert_eq!(scalp.label(), "0.5:0.5,1:0.3,trail:0.5");
+}
+
+#[test]
+fn app_runtime_take_profit_ladder_scales_out_then_trails_runner() {
+    let mut app = trailing_stop_app();
+    let mut runtime = AppRuntime::default();
+    let instrument = Instrument::new("BTCUSDT");
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::PlaceTakeProfitLadder {
+                instrument: instrument.clone(),
+                profile: "default".to_string(),
+                source: CommandSource::User,
+            }),
+        )
+        .expect("ladder should be placed");
+    assert_eq!(app.execution.take_profit_ladders.len(), 1);
+    assert!(app.exchange.close_requests().is_empty());
+
+    app.exchange
+        .set_last_price(instrument.clone(), Market::Futures, 49400.0);
+    app.exchange
+        .set_next_submit_result(Err(ExchangeError::RemoteReject {
+            code: -2022,
+            message: "ReduceOnly Order is rejected.".to_string(),
+        }));
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert_eq!(app.exchange.close_requests().len(), 1);
+    assert_eq!(app.execution.take_profit_ladders[0].filled_levels, 0);
+
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let requests = app.exchange.close_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].side, Side::Buy);
+    assert_eq!(requests[1].qty_text, "0.150");
+    assert_eq!(requests[1].order_type, OrderType::Market);
+
+    app.exchange
+        .set_last_price(instrument.clone(), Market::Futures, 48900.0);
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let requests = app.exchange.close_requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[2].qty_text, "0.090");
+
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let requests = app.exchange.close_requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(
+        requests[3].order_type,
+        OrderType::TrailingStopMarket {
+            callback_rate_pct: 1.0
+        }
+    );
+    assert!(app.execution.take_profit_ladders.is_empty());
+
+    let rendered = render_command_output(
+        &AppCommand::Portfolio(PortfolioView::Positions),
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(
+        rendered.contains("take_profit=[tp1@49500.00=filled tp2@49000.00=filled runner=trailing]")
+    );
+}
+
+#[test]
+fn app_applies_configured_futures_leverage_and_shows_it_on_positions() {
+    let mut app = trailing_stop_app();
+    app.execution.futures_leverage =
+        parse_futures_leverage("btcusdt=10:isolated,ETHUSDT=5,SOLUSDT=500,XRPUSDT=3:hedged");
+    assert_eq!(app.execution.futures_leverage.len(), 2);
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    app.apply_futures_leverage();
+
+    let btc = Instrument::new("BTCUSDT");
+    assert_eq!(
+        app.exchange.futures_settings(&btc),
+        (Some(10), Some(MarginType::Isolated))
+    );
+    assert_eq!(
+        app.exchange.futures_settings(&Instrument::new("ETHUSDT")),
+        (Some(5), None)
+    );
+    assert_eq!(
+        app.portfolio_store.futures_leverage[&btc],
+        FuturesLeverage {
+            leverage: 10,
+            margin_type: Some(MarginType::Isolated),
+        }
+    );
+    let applied = app
+        .event_log
+        .records
+        .iter()
+        .filter(|record| record.kind == "app.account.leverage_applied")
+        .collect::<Vec<_>>();
+    assert_eq!(applied.len(), 2);
+    assert_eq!(applied[0].payload["instrument"], "BTCUSDT");
+    assert_eq!(applied[0].payload["margin_type"], "isolated");
+
+    let rendered = render_command_output(
+        &AppCommand::Portfolio(PortfolioView::Positions),
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("leverage=[10x isolated]"));
+
+    app.execution.futures_leverage = parse_futures_leverage("BTCUSDT=20");
+    app.execution.api_permissions = Some(ApiPermissions {
+        reading: true,
+        spot_trading: true,
+        futures_trading: false,
+        options_trading: false,
+    });
+    app.apply_futures_leverage();
+    assert_eq!(app.exchange.futures_settings(&btc).0, Some(10));
+}
+
+#[test]
+fn user_stream_updates_apply_fills_and_balances_without_a_refresh() {
+    let mut app = AppBootstrap::new(
+        FakeExchange::new(sample_snapshot()),
+        PortfolioStateStore::default(),
+    );
+    app.portfolio_store.apply_snapshot(sample_snapshot());
+    let mut runtime = AppRuntime::default();
+    let btc = Instrument::new("BTCUSDT");
+    let order_update = |status: &str, last: &str, cumulative: &str| {
+        json!({
+            "e": "ORDER_TRADE_UPDATE", "E": 1_700_000_000_000_i64,
+            "o": {
+                "s": "BTCUSDT", "c": "close-1", "S": "SELL", "q": "0.3", "p": "0",
+                "X": status, "i": 1, "l": last, "z": cumulative, "L": "49900.5", "R": true
+            }
+        })
+    };
+    let now = Utc::now();
+    let events_before = app.event_log.records.len();
+
+    for value in [
+        order_update("PARTIALLY_FILLED", "0.1", "0.1"),
+        order_update("FILLED", "0.2", "0.3"),
+    ] {
+        let event = parse_user_stream_event(&value, Market::Futures).expect("order update");
+        runtime.ingest_user_stream_event(&mut app, event, now);
+        if value["o"]["X"] == "PARTIALLY_FILLED" {
+            let open = &app.portfolio_store.snapshot.open_orders[&btc];
+            assert_eq!(open.len(), 1);
+            assert_eq!(open[0].executed_qty, 0.1);
+        }
+    }
+    assert!(!app.portfolio_store.snapshot.open_orders.contains_key(&btc));
+
+    let account = json!({
+        "e": "ACCOUNT_UPDATE", "E": 1_700_000_000_001_i64,
+        "a": {
+            "B": [{ "a": "USDT", "wb": "1014.97", "cw": "1014.97" }],
+            "P": [{ "s": "BTCUSDT", "pa": "0", "ep": "0.0", "ps": "BOTH" }]
+        }
+    });
+    let event = parse_user_stream_event(&account, Market::Futures).expect("account update");
+    runtime.ingest_user_stream_event(&mut app, event, now);
+    assert!(!app.portfolio_store.snapshot.positions.contains_key(&btc));
+    assert_eq!(app.portfolio_store.snapshot.balances.len(), 1);
+    assert_eq!(app.portfolio_store.snapshot.balances[0].free, 1014.97);
+    assert_eq!(app.portfolio_store.staleness, StalenessState::Fresh);
+
+    runtime.ingest_user_stream_event(
+        &mut app,
+        UserStreamEvent::Disconnected {
+            market: Market::Futures,
+            error: "stream closed".to_string(),
+        },
+        now,
+    );
+    assert_eq!(
+        app.portfolio_store.staleness,
+        StalenessState::AccountStateStale
+    );
+
+    let rendered = render_user_stream_updates(&app.event_log, events_before).expect("stream lines");
+    assert_eq!(
+        rendered.lines().collect::<Vec<_>>(),
+        vec![
+            "fill BTCUSDT sell 0.1 @ 49900.5 (0.1/0.3 partially_filled)",
+            "fill BTCUSDT sell 0.2 @ 49900.5 (0.3/0.3 filled)",
+            "user stream futures dropped; account marked stale error=stream closed",
+        ]
+    );
+}
+
+#[test]
+fn user_stream_fills_land_in_the_order_store_for_history_roi() {
+    let mut app = AppBootstrap::new(
+        FakeExchange::new(sample_snapshot()),
+        PortfolioStateStore::default(),
+    );
+    let mut runtime = AppRuntime::default();
+    let roi = AppCommand::Portfolio(PortfolioView::HistoryRoi {
+        bucket: TimeBucket::Day,
+        query: HistoryQuery {
+            instrument: None,
+            tag: Some("strategy:5".to_string()),
+        },
+    });
+    runtime
+        .run(&mut app, roi.clone())
+        .expect("roi without a store");
+    let listed = app.event_log.records.last().expect("roi listed");
+    assert_eq!(listed.kind, "app.history.roi_listed");
+    assert_eq!(listed.payload["store"], "off");
+
+    app.execution.order_store = Some(OrderStore::open_in_memory().expect("in-memory store"));
+    for (client_order_id, fee_asset) in [("strategy-5-1", "USDT"), ("close-1", "BNB")] {
+        let fill = json!({
+            "e": "ORDER_TRADE_UPDATE", "E": 1_700_000_000_000_i64,
+            "o": {
+                "s": "BTCUSDT", "c": client_order_id, "S": "SELL", "q": "0.1", "p": "0",
+                "X": "FILLED", "i": 3, "l": "0.1", "z": "0.1", "L": "50000",
+                "rp": "100", "n": "2", "N": fee_asset, "R": true
+            }
+        });
+        let event = parse_user_stream_event(&fill, Market::Futures).expect("order update");
+        runtime.ingest_user_stream_event(&mut app, event, Utc::now());
+    }
+
+    runtime
+        .run(&mut app, roi.clone())
+        .expect("roi from the store");
+    let listed = app.event_log.records.last().expect("roi listed");
+    assert_eq!(listed.payload["store"], "on");
+    let buckets = listed.payload["buckets"].as_array().expect("buckets");
+    assert_eq!(buckets.len(), 1);
+    assert_eq!(buckets[0]["bucket"], "2023-11-14");
+    assert_eq!(buckets[0]["fills"], 1);
+    assert_eq!(buckets[0]["commission"], 2.0);
+    assert_eq!(buckets[0]["roi_pct"], 2.0);
+    let rendered = render_command_output(
+        &roi,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("bucket=day instrument=all tag=strategy:5"));
+    assert!(rendered.contains("- 2023-11-14 orders=0 rejected=0"));
+    assert!(rendered.contains("realized_pnl=100.0000 commission=2.0000 roi=2.00%"));
+}
+
+#[test]
+fn app_runtime_records_bounded_trace_for_debug_watch_and_dumps_it() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot::default());
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let base_dir = unique_test_dir("strategy-trace");
+    app.recorder_coordination = RecorderCoordination::new(base_dir.clone());
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::PriceSmaCrossLong,
+                instrument: instrument.clone(),
+                config: StrategyStartConfig::default(),
+            }),
+        )
+        .expect("strategy start should succeed");
+
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert!(app.strategy_store.trace(1).is_none());
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Debug {
+                watch_id: 1,
+                enabled: true,
+            }),
+        )
+        .expect("debug toggle should succeed");
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    app.exchange
+        .set_last_price(instrument.clone(), Market::Futures, 50500.0);
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    let trace = app.strategy_store.trace(1).expect("trace buffer");
+    assert_eq!(trace.entries().len(), 2);
+    let last = trace.last().expect("last entry");
+    assert_eq!(last.indicators["price"], 50500.0);
+    assert_eq!(last.decision, "warmup: 2 closes");
+    assert!(!last.indicators.contains_key("fast_sma"));
+
+    let dump_path = base_dir.join("trace.jsonl");
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Trace {
+                watch_id: 1,
+                dump_path: Some(dump_path.display().to_string()),
+            }),
+        )
+        .expect("trace dump should succeed");
+    let dumped = std::fs::read_to_string(&dump_path).expect("dump file");
+    assert_eq!(dumped.lines().count(), 2);
+    assert_eq!(
+        app.event_log.records.last().expect("dump event").payload["entries"],
+        2
+    );
+
+    for step in 1..=50 {
+        app.exchange.set_last_price(
+            instrument.clone(),
+            Market::Futures,
+            50500.0 + f64::from(step) * 10.0,
+        );
+        runtime
+            .run(&mut app, AppCommand::RefreshAuthoritativeState)
+            .expect("refresh should succeed");
+    }
+    let last = app
+        .strategy_store
+        .trace(1)
+        .and_then(|trace| trace.last())
+        .expect("last entry");
+    assert!(last.indicators["fast_sma"] > last.indicators["slow_sma"]);
+    assert!(!last.decision.starts_with("warmup"));
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Stop {
+                watch_id: 1,
+                resolution: None,
+            }),
+        )
+        .expect("strategy stop should succeed");
+    assert!(app.strategy_store.trace(1).is_none());
+}
+
+#[test]
+fn app_runtime_defers_market_buy_into_thin_asks_and_retries_on_refresh() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 1000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
+    exchange.set_order_book_depth(
+        instrument.clone(),
+        Market::Futures,
+        OrderBookDepth {
+            bids: vec![(49990.0, 0.2)],
+            asks: vec![(50010.0, 0.01)],
+        },
+    );
+
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut filter = DepthFilterConfig::new(10.0);
+    filter.retry_secs = 0;
+    app.execution.depth_filter = Some(filter);
+    app.portfolio_store
+        .refresh_from_exchange(&app.exchange)
+        .expect("seed snapshot");
+
+    let mut runtime = AppRuntime::default();
+    let command = AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+        instrument: instrument.clone(),
+        target: Exposure::new(0.5).expect("bounded exposure"),
+        order_type: OrderType::Market,
+        source: CommandSource::User,
+    });
+    runtime
+        .run(&mut app, command.clone())
+        .expect("thin asks should defer, not fail, the buy");
+    let rendered = render_command_output(
+        &command,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.starts_with("execution deferred\ninstrument=BTCUSDT\nattempts=1"));
+    assert!(rendered.contains("reason=ask depth too thin"));
+    assert!(app.exchange.submit_requests().is_empty());
+    assert_eq!(app.execution.deferred_executions.len(), 1);
+    assert!(app
+        .event_log
+        .records
+        .iter()
+        .any(|record| record.kind == "app.execution.deferred"));
+
+    app.exchange.set_order_book_depth(
+        instrument.clone(),
+        Market::Futures,
+        OrderBookDepth {
+            bids: vec![(49990.0, 0.1)],
+            asks: vec![(50010.0, 0.1)],
+        },
+    );
+    app.exchange
+        .set_next_order_submit_result(Err(ExchangeError::RemoteReject {
+            code: -1008,
+            message: "Server is currently overloaded with other requests.".to_string(),
+        }));
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert_eq!(app.exchange.submit_requests().len(), 1);
+    assert_eq!(app.execution.deferred_executions.len(), 1);
+    assert_eq!(app.execution.deferred_executions[0].attempts, 2);
+
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    assert_eq!(app.exchange.submit_requests().len(), 2);
+    assert!(app.execution.deferred_executions.is_empty());
+    let row = app.execution.history.rows().back().expect("history row");
+    let imbalance = row.depth_imbalance.expect("depth imbalance recorded");
+    assert!(imbalance.abs() < 0.001);
+    assert!(app
+        .event_log
+        .records
+        .iter()
+        .any(|record| record.kind == "app.execution.deferred_completed"));
+}
+
+#[test]
+fn app_runtime_sends_guarded_market_entry_as_ioc_limit() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 1000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.1,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.execution.slippage_guard = Some(SlippageGuardConfig::new(20.0));
+    app.portfolio_store
+        .refresh_from_exchange(&app.exchange)
+        .expect("seed snapshot");
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+                instrument: instrument.clone(),
+                target: Exposure::new(0.5).expect("bounded exposure"),
+                order_type: OrderType::Market,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("guarded buy should submit");
+
+    let request = app
+        .exchange
+        .submit_requests()
+        .last()
+        .cloned()
+        .expect("submit");
+    assert_eq!(request.order_type, OrderType::LimitIoc { price: 50100.0 });
+    assert_eq!(
+        app.execution
+            .history
+            .rows()
+            .back()
+            .expect("history row")
+            .price,
+        Some(50100.0)
+    );
+    let checked = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.execution.slippage_checked")
+        .expect("slippage check logged");
+    assert_eq!(checked.payload["limit_price"], 50100.0);
+    assert_eq!(checked.payload["passed"], true);
+}
+
+#[test]
+fn app_runtime_sweeps_idle_usdt_to_earn_and_redeems_for_spot_entries() {
+    let btc = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 3_000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        btc.clone(),
+        Market::Spot,
+        SymbolRules {
+            min_qty: 0.0001,
+            max_qty: 100.0,
+            step_size: 0.0001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(btc.clone(), Market::Spot, 50_000.0);
+    exchange.set_earn_positions(vec![EarnPosition {
+        asset: "USDT".to_string(),
+        product_id: "USDT001".to_string(),
+        amount: 5_000.0,
+        apr_pct: 4.2,
+    }]);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.earn = EarnConfig {
+        tracking: true,
+        sweep_above_usdt: Some(1_000.0),
+    };
+    app.portfolio_store.earn_tracking = true;
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let equity = app
+        .portfolio_store
+        .snapshot
+        .balances
+        .iter()
+        .map(|balance| balance.total())
+        .sum::<f64>();
+    assert_eq!(equity, 8_000.0);
+    let swept = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.earn.transferred")
+        .expect("sweep logged");
+    assert_eq!(swept.payload["kind"], "subscribe");
+    assert_eq!(swept.payload["amount"], 2_000.0);
+    assert_eq!(swept.payload["saved"], 7_000.0);
+
+    let balances = AppCommand::Portfolio(PortfolioView::Balances);
+    runtime.run(&mut app, balances.clone()).expect("balances");
+    let rendered = render_command_output(
+        &balances,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("earn idle=1000.00 saved=7000.00"));
+    assert!(rendered.contains("  - USDT001 USDT amount=7000.00 apr=4.20%"));
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+                instrument: btc.clone(),
+                target: Exposure::new(0.5).expect("valid exposure"),
+                order_type: OrderType::Market,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("spot entry should succeed");
+    let transfers = app.exchange.earn_transfers();
+    assert_eq!(transfers.len(), 2);
+    assert_eq!(transfers[1].kind, EarnTransferKind::Redeem);
+    assert!((transfers[1].amount - 3_040.0).abs() < 1e-9);
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        (requests[0].market, requests[0].side),
+        (Market::Spot, Side::Buy)
+    );
+}
+
+#[test]
+fn app_runtime_prices_synthetic_basis_and_routes_targets_to_legs() {
+    let btc = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 10_000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    for market in [Market::Futures, Market::Spot] {
+        exchange.set_symbol_rules(
+            btc.clone(),
+            market,
+            SymbolRules {
+                min_qty: 0.001,
+                max_qty: 100.0,
+                step_size: 0.001,
+                tick_size: 0.01,
+            },
+        );
+    }
+    exchange.set_last_price(btc.clone(), Market::Futures, 50_100.0);
+    exchange.set_last_price(btc.clone(), Market::Spot, 50_000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("synthetic-basis"));
+    app.execution.synthetic_instruments = parse_synthetic_instruments(
+        "BTC_BASIS=BTCUSDT@futures-BTCUSDT@spot;BTC_DOUBLE=2*BTCUSDT@futures-BTCUSDT@spot",
+    );
+    let basis = Instrument::new("BTC_BASIS");
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: basis.clone(),
+                config: StrategyStartConfig::default(),
+            }),
+        )
+        .expect("synthetic watch should start");
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    let tick = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.market_data.synthetic_tick")
+        .expect("synthetic tick");
+    assert_eq!(tick.payload["instrument"], "BTC_BASIS");
+    assert_eq!(tick.payload["legs"].as_array().map(Vec::len), Some(2));
+    assert_eq!(app.price_store.current_price(&basis), Some(100.0));
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+                instrument: basis,
+                target: Exposure::new(-0.2).expect("valid exposure"),
+                order_type: OrderType::Market,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("basis target should route to legs");
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        (requests[0].market, requests[0].side),
+        (Market::Futures, Side::Sell)
+    );
+    assert_eq!(
+        (requests[1].market, requests[1].side),
+        (Market::Spot, Side::Buy)
+    );
+
+    let error = runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+                instrument: Instrument::new("BTC_DOUBLE"),
+                target: Exposure::new(0.6).expect("valid exposure"),
+                order_type: OrderType::Market,
+                source: CommandSource::User,
+            }),
+        )
+        .expect_err("leg beyond full exposure should be rejected");
+    assert!(error.to_string().contains("BTC_DOUBLE leg BTCUSDT"));
+    assert_eq!(app.exchange.submit_requests().len(), 2);
+
+    // The short perp shares the symbol; the spot leg still reads the BTC
+    // balance, so flattening the spread sells it back.
+    app.exchange.replace_snapshot(AuthoritativeSnapshot {
+        balances: vec![
+            BalanceSnapshot {
+                asset: "USDT".to_string(),
+                free: 9_000.0,
+                locked: 0.0,
+            },
+            BalanceSnapshot {
+                asset: "BTC".to_string(),
+                free: 0.02,
+                locked: 0.0,
+            },
+        ],
+        positions: vec![PositionSnapshot {
+            instrument: btc.clone(),
+            market: Market::Futures,
+            signed_qty: -0.02,
+            entry_price: Some(50_100.0),
+        }],
+        open_orders: vec![],
+    });
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+                instrument: Instrument::new("BTC_BASIS"),
+                target: Exposure::new(0.0).expect("valid exposure"),
+                order_type: OrderType::Market,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("flat basis target should unwind both legs");
+    let unwind = app.exchange.submit_requests()[2..]
+        .iter()
+        .map(|request| (request.market, request.side, request.qty))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        unwind,
+        vec![
+            (Market::Futures, Side::Buy, 0.02),
+            (Market::Spot, Side::Sell, 0.02)
+        ]
+    );
+}
+
+#[test]
+fn basis_watch_hedges_long_spot_short_perp_and_unwinds_at_convergence() {
+    let btc = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 10_000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    for market in [Market::Futures, Market::Spot] {
+        exchange.set_symbol_rules(
+            btc.clone(),
+            market,
+            SymbolRules {
+                min_qty: 0.001,
+                max_qty: 100.0,
+                step_size: 0.001,
+                tick_size: 0.01,
+            },
+        );
+    }
+    exchange.set_last_price(btc.clone(), Market::Spot, 50_000.0);
+    exchange.set_last_price(btc.clone(), Market::Futures, 50_100.0);
+    exchange.set_funding_rate(btc.clone(), 0.0001);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+    let start = AppCommand::Basis(BasisCommand::Start {
+        instrument: btc.clone(),
+        config: BasisArbConfig {
+            entry_bps: 30.0,
+            exit_bps: 5.0,
+            funding_apr_pct: Some(15.0),
+            target: 0.1,
+        },
+    });
+    runtime
+        .run(&mut app, start.clone())
+        .expect("basis watch should start");
+    assert!(
+        runtime.run(&mut app, start).is_err(),
+        "one watch per symbol"
+    );
+
+    // 20bps and 10.95% funding: below both entry thresholds.
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert!(app.exchange.submit_requests().is_empty());
+
+    app.exchange
+        .set_last_price(btc.clone(), Market::Futures, 50_200.0);
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let requests = app.exchange.submit_requests();
+    assert_eq!(
+        requests
+            .iter()
+            .map(|request| (request.market, request.side))
+            .collect::<Vec<_>>(),
+        vec![(Market::Futures, Side::Sell), (Market::Spot, Side::Buy)]
+    );
+    let entered = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.strategy.basis_entered")
+        .expect("entry logged");
+    assert_eq!(entered.payload["synthetic"], "BTCUSDT_BASIS");
+    assert_eq!(entered.payload["notional_usdt"], 1000.0);
+    let version = entered.payload["version"]
+        .as_str()
+        .expect("entry carries the watch version")
+        .to_string();
+    assert_eq!(version.len(), 8);
+    assert!(app
+        .execution
+        .history
+        .rows()
+        .iter()
+        .all(|row| row.tag == format!("strategy:1@{version}")));
+    assert!(
+        render_order_history(&app.execution.history, &OrderFilter::default()).contains(&format!(
+            "strategy_version strategy:1@{version} orders=2 rejected=0"
+        ))
+    );
+
+    app.exchange.replace_snapshot(AuthoritativeSnapshot {
+        balances: vec![
+            BalanceSnapshot {
+                asset: "USDT".to_string(),
+                free: 9_000.0,
+                locked: 0.0,
+            },
+            BalanceSnapshot {
+                asset: "BTC".to_string(),
+                free: 0.02,
+                locked: 0.0,
+            },
+        ],
+        positions: vec![PositionSnapshot {
+            instrument: btc.clone(),
+            market: Market::Futures,
+            signed_qty: -0.019,
+            entry_price: Some(50_200.0),
+        }],
+        open_orders: vec![],
+    });
+    app.exchange
+        .set_last_price(btc.clone(), Market::Futures, 50_010.0);
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let exited = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.strategy.basis_exited")
+        .expect("unwind logged");
+    assert_eq!(exited.payload["entry_basis_bps"], 40.0);
+    assert_eq!(
+        app.exchange.submit_requests()[2..]
+            .iter()
+            .map(|request| (request.market, request.side, request.qty))
+            .collect::<Vec<_>>(),
+        vec![
+            (Market::Futures, Side::Buy, 0.019),
+            (Market::Spot, Side::Sell, 0.02)
+        ]
+    );
+    assert!(
+        (exited.payload["basis_pnl_usdt"]
+            .as_f64()
+            .unwrap_or_default()
+            - 3.8)
+            .abs()
+            < 1e-9
+    );
+
+    let list = AppCommand::Basis(BasisCommand::List);
+    runtime.run(&mut app, list.clone()).expect("list");
+    let rendered = render_command_output(
+        &list,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("basis watches (1)"));
+    assert!(rendered.contains("- id=1 BTCUSDT flat entry=30.0bps exit=5.0bps funding>=15.00%"));
+    assert!(rendered.contains("round_trips=1 realized=3.80"));
+
+    runtime
+        .run(&mut app, AppCommand::Basis(BasisCommand::Stop { id: 1 }))
+        .expect("stop");
+    assert!(app.basis_arbs.watches(app.mode).is_empty());
+    assert!(runtime
+        .run(&mut app, AppCommand::Basis(BasisCommand::Stop { id: 1 }))
+        .is_err());
+}
+
+#[test]
+fn script_watches_trade_their_signals_on_minute_closes_and_fail_on_a_fault() {
+    let btc = Instrument::new("BTCUSDT");
+    let eth = Instrument::new("ETHUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 10_000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    for instrument in [&btc, &eth] {
+        exchange.set_symbol_rules(
+            instrument.clone(),
+            Market::Futures,
+            SymbolRules {
+                min_qty: 0.001,
+                max_qty: 1_000.0,
+                step_size: 0.001,
+                tick_size: 0.01,
+            },
+        );
+        exchange.set_last_price(instrument.clone(), Market::Futures, 100.0);
+    }
+    exchange.set_exchange_clock_offset_ms(Some(0));
+    let dir =
+        std::env::temp_dir().join(format!("sandbox-quant-live-scripts-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("script dir");
+    let momentum = dir.join("momentum.rhai");
+    std::fs::write(
+        &momentum,
+        "fn warmup() { 2 }\n\
+         fn enter() { close() > close(1) }\n\
+         fn exit() { close() < close(1) }\n",
+    )
+    .expect("write script");
+    let broken = dir.join("broken.rhai");
+    std::fs::write(
+        &broken,
+        "fn enter() { rsi(14) > 0.0 }\nfn exit() { false }\n",
+    )
+    .expect("write script");
+    let start = |instrument: &Instrument, script| {
+        AppCommand::Strategy(StrategyCommand::Start {
+            template: StrategyTemplate::ScriptLong,
+            instrument: instrument.clone(),
+            config: StrategyStartConfig {
+                risk_pct: 0.006,
+                ramp: None,
+                adapt: None,
+                script: Some(script),
+                ..StrategyStartConfig::default()
+            },
+        })
+    };
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+    let missing = runtime
+        .run(&mut app, start(&btc, dir.join("missing.rhai")))
+        .expect_err("an unreadable script is refused");
+    assert!(missing.to_string().contains("strategy script read failed"));
+    assert!(app.strategy_store.active_watches(app.mode).is_empty());
+    runtime
+        .run(&mut app, start(&btc, momentum))
+        .expect("script watch should start");
+    runtime
+        .run(&mut app, start(&eth, broken))
+        .expect("helpers are only resolved when called");
+
+    // Each refresh lands a minute later; the previous price is that
+    // minute's close.
+    let mut minute = 0;
+    let mut refresh_at = |app: &mut AppBootstrap<FakeExchange>, price: f64| {
+        app.exchange
+            .set_exchange_clock_offset_ms(Some(minute * 60_000));
+        app.exchange
+            .set_last_price(btc.clone(), Market::Futures, price);
+        minute += 1;
+        runtime
+            .run(app, AppCommand::RefreshAuthoritativeState)
+            .expect("refresh should succeed");
+    };
+    refresh_at(&mut app, 100.0);
+    refresh_at(&mut app, 101.0);
+    assert!(app.exchange.submit_requests().is_empty(), "warming up");
+    let failed = app
+        .strategy_store
+        .history(app.mode)
+        .into_iter()
+        .find(|watch| watch.instrument == eth)
+        .expect("the faulting watch is failed");
+    assert_eq!(failed.state, StrategyWatchState::Failed);
+    let reason = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.strategy.watch_failed")
+        .expect("failure logged")
+        .payload["reason"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    assert!(reason.contains("enter(): "), "{reason}");
+
+    // 101 closes above 100: half the equity at the 102 price.
+    refresh_at(&mut app, 102.0);
+    let requests = app.exchange.submit_requests();
+    assert_eq!(
+        requests
+            .iter()
+            .map(|request| (request.instrument.clone(), request.side, request.qty))
+            .collect::<Vec<_>>(),
+        vec![(btc.clone(), Side::Buy, 49.019)]
+    );
+    let signal = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.strategy.bar_signal")
+        .expect("signal logged");
+    assert_eq!(signal.payload["signal"], "enter");
+    assert_eq!(signal.payload["close"], 101.0);
+    assert_eq!(signal.payload["target"], 0.5);
+    assert_eq!(signal.payload["indicators"]["close(1)"], 100.0);
+
+    app.exchange.replace_snapshot(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 10_000.0,
+            locked: 0.0,
+        }],
+        positions: vec![PositionSnapshot {
+            instrument: btc.clone(),
+            market: Market::Futures,
+            signed_qty: 49.019,
+            entry_price: Some(101.0),
+        }],
+        open_orders: vec![],
+    });
+    // 102 still rises, so no new entry; 90 falls and exits.
+    refresh_at(&mut app, 90.0);
+    assert_eq!(app.exchange.submit_requests().len(), 1);
+    refresh_at(&mut app, 95.0);
+    let requests = app.exchange.submit_requests();
+    assert_eq!(
+        requests[1..]
+            .iter()
+            .map(|request| (request.side, request.qty))
+            .collect::<Vec<_>>(),
+        vec![(Side::Sell, 49.019)]
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn script_watches_with_seed_capital_size_entries_from_their_ledger() {
+    let btc = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 10_000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        btc.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 1_000.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(btc.clone(), Market::Futures, 100.0);
+    exchange.set_exchange_clock_offset_ms(Some(0));
+    let script = std::env::temp_dir().join(format!(
+        "sandbox-quant-ledger-script-{}.rhai",
+        std::process::id()
+    ));
+    std::fs::write(&script, "fn enter() { true }\nfn exit() { false }\n").expect("write script");
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::ScriptLong,
+                instrument: btc.clone(),
+                config: StrategyStartConfig {
+                    risk_pct: 0.006,
+                    seed_capital_usdt: Some(1_000.0),
+                    ramp: None,
+                    adapt: None,
+                    script: Some(script.clone()),
+                    ..StrategyStartConfig::default()
+                },
+            }),
+        )
+        .expect("script watch should start");
+    for minute in 0..2 {
+        app.exchange
+            .set_exchange_clock_offset_ms(Some(minute * 60_000));
+        runtime
+            .run(&mut app, AppCommand::RefreshAuthoritativeState)
+            .expect("refresh should succeed");
+    }
+    let _ = std::fs::remove_file(&script);
+
+    // 0.6% of the 1_000 allocation at a 1.2% stop: 500 USDT, not 5_000.
+    let signal = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.strategy.bar_signal")
+        .expect("entry logged");
+    assert_eq!(signal.payload["risk_usdt"], 6.0);
+    assert_eq!(signal.payload["target"], 0.05);
+    assert_eq!(
+        app.exchange
+            .submit_requests()
+            .iter()
+            .map(|request| (request.side, request.qty))
+            .collect::<Vec<_>>(),
+        vec![(Side::Buy, 5.0)]
+    );
+}
+
+#[test]
+fn live_script_entries_past_the_per_minute_limit_are_dropped_and_counted() {
+    let btc = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 10_000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        btc.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 1_000.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(btc.clone(), Market::Futures, 100.0);
+    exchange.set_exchange_clock_offset_ms(Some(0));
+    let script = std::env::temp_dir().join(format!(
+        "sandbox-quant-throttle-script-{}.rhai",
+        std::process::id()
+    ));
+    std::fs::write(
+        &script,
+        "fn warmup() { 2 }\n\
+         fn enter() { close() > close(1) }\n\
+         fn exit() { close() < close(1) }\n",
+    )
+    .expect("write script");
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.execution.strategy_orders_per_min = 1;
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::ScriptLong,
+                instrument: btc.clone(),
+                config: StrategyStartConfig {
+                    risk_pct: 0.006,
+                    ramp: None,
+                    adapt: None,
+                    script: Some(script.clone()),
+                    ..StrategyStartConfig::default()
+                },
+            }),
+        )
+        .expect("script watch should start");
+    // Venue minutes advance on every refresh while the wall clock stays
+    // inside one minute: enter, exit, then a second entry.
+    let steps = [
+        (100.0, 0.0),
+        (101.0, 0.0),
+        (102.0, 49.019),
+        (90.0, 49.019),
+        (95.0, 0.0),
+        (99.0, 0.0),
+    ];
+    for (minute, (price, held)) in steps.into_iter().enumerate() {
+        app.exchange
+            .set_exchange_clock_offset_ms(Some(minute as i64 * 60_000));
+        app.exchange
+            .set_last_price(btc.clone(), Market::Futures, price);
+        runtime
+            .run(&mut app, AppCommand::RefreshAuthoritativeState)
+            .expect("refresh should succeed");
+        app.exchange.replace_snapshot(AuthoritativeSnapshot {
+            balances: vec![BalanceSnapshot {
+                asset: "USDT".to_string(),
+                free: 10_000.0,
+                locked: 0.0,
+            }],
+            positions: vec![PositionSnapshot {
+                instrument: btc.clone(),
+                market: Market::Futures,
+                signed_qty: held,
+                entry_price: Some(101.0),
+            }],
+            open_orders: vec![],
+        });
+    }
+    let _ = std::fs::remove_file(&script);
+
+    assert_eq!(
+        app.exchange
+            .submit_requests()
+            .iter()
+            .map(|request| request.side)
+            .collect::<Vec<_>>(),
+        vec![Side::Buy, Side::Sell]
+    );
+    let failed = app
+        .event_log
+        .records
+        .iter()
+        .filter(|record| record.kind == "app.strategy.bar_order_failed")
+        .collect::<Vec<_>>();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].payload["signal"], "enter");
+    assert_eq!(failed[0].payload["reason"], "throttled");
+    let dropped = app
+        .execution
+        .funnel
+        .signals()
+        .iter()
+        .filter(|signal| signal.drop_reason == Some("throttled"))
+        .count();
+    assert_eq!(dropped, 1);
+    assert_eq!(
+        app.execution
+            .strategy_throttles
+            .values()
+            .map(|throttle| throttle.dropped())
+            .sum::<usize>(),
+        1
+    );
+}
+
+#[test]
+fn live_script_shorts_sell_to_open_and_buy_back_reduce_only() {
+    let btc = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 10_000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        btc.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 1_000.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(btc.clone(), Market::Futures, 100.0);
+    exchange.set_exchange_clock_offset_ms(Some(0));
+    let script = std::env::temp_dir().join(format!(
+        "sandbox-quant-short-script-{}.rhai",
+        std::process::id()
+    ));
+    std::fs::write(
+        &script,
+        "fn warmup() { 2 }\n\
+         fn enter() { close() < close(1) }\n\
+         fn exit() { close() > close(1) }\n",
+    )
+    .expect("write script");
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::ScriptShort,
+                instrument: btc.clone(),
+                config: StrategyStartConfig {
+                    risk_pct: 0.006,
+                    ramp: None,
+                    adapt: None,
+                    script: Some(script.clone()),
+                    ..StrategyStartConfig::default()
+                },
+            }),
+        )
+        .expect("script watch should start");
+    // Minute closes reach the script one refresh later: the falling close
+    // opens the short, the next rising close covers it.
+    let steps = [(100.0, 0.0), (99.0, 0.0), (101.0, -50.505), (102.0, 0.0)];
+    for (minute, (price, held)) in steps.into_iter().enumerate() {
+        app.exchange
+            .set_exchange_clock_offset_ms(Some(minute as i64 * 60_000));
+        app.exchange
+            .set_last_price(btc.clone(), Market::Futures, price);
+        runtime
+            .run(&mut app, AppCommand::RefreshAuthoritativeState)
+            .expect("refresh should succeed");
+        app.exchange.replace_snapshot(AuthoritativeSnapshot {
+            balances: vec![BalanceSnapshot {
+                asset: "USDT".to_string(),
+                free: 10_000.0,
+                locked: 0.0,
+            }],
+            positions: vec![PositionSnapshot {
+                instrument: btc.clone(),
+                market: Market::Futures,
+                signed_qty: held,
+                entry_price: Some(99.0),
+            }],
+            open_orders: vec![],
+        });
+    }
+    let _ = std::fs::remove_file(&script);
+
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].side, Side::Sell);
+    assert!(!requests[0].reduce_only);
+    assert_eq!(requests[1].side, Side::Buy);
+    assert!(requests[1].reduce_only);
+    assert!((requests[1].qty - 50.505).abs() < 1e-9);
+}
+
+#[test]
+fn basis_entries_signalled_in_illiquid_windows_wait_for_the_liquid_open() {
+    let btc = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 10_000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    for market in [Market::Futures, Market::Spot] {
+        exchange.set_symbol_rules(
+            btc.clone(),
+            market,
+            SymbolRules {
+                min_qty: 0.001,
+                max_qty: 100.0,
+                step_size: 0.001,
+                tick_size: 0.01,
+            },
+        );
+    }
+    exchange.set_last_price(btc.clone(), Market::Spot, 50_000.0);
+    exchange.set_last_price(btc.clone(), Market::Futures, 50_200.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.execution.illiquid_windows = parse_illiquid_windows("* 00:00-24:00");
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Basis(BasisCommand::Start {
+                instrument: btc.clone(),
+                config: BasisArbConfig {
+                    entry_bps: 30.0,
+                    exit_bps: 5.0,
+                    funding_apr_pct: None,
+                    target: 0.1,
+                },
+            }),
+        )
+        .expect("basis watch should start");
+    let count = |app: &AppBootstrap<FakeExchange>, kind: &str| {
+        app.event_log
+            .records
+            .iter()
+            .filter(|record| record.kind == kind)
+            .count()
+    };
+
+    // 40bps inside the window: queued once, not re-queued while waiting.
+    for _ in 0..2 {
+        runtime
+            .run(&mut app, AppCommand::RefreshAuthoritativeState)
+            .expect("refresh should succeed");
+    }
+    assert!(app.exchange.submit_requests().is_empty());
+    assert_eq!(count(&app, "app.strategy.basis_entry_queued"), 1);
+    assert!(app
+        .execution
+        .is_entry_queued(&Instrument::new("BTCUSDT_BASIS")));
+
+    // The basis collapses before the open, so the queued entry is dropped.
+    app.exchange
+        .set_last_price(btc.clone(), Market::Futures, 50_050.0);
+    app.execution.illiquid_windows.clear();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let dropped = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.execution.queued_entry_dropped")
+        .expect("stale entry dropped");
+    assert_eq!(dropped.payload["reason"], "signal_gone");
+    assert_eq!(dropped.payload["instrument"], "BTCUSDT_BASIS");
+    assert!(app.exchange.submit_requests().is_empty());
+
+    app.execution.illiquid_windows = parse_illiquid_windows("* 00:00-24:00");
+    app.exchange
+        .set_last_price(btc.clone(), Market::Futures, 50_200.0);
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert_eq!(count(&app, "app.strategy.basis_entry_queued"), 2);
+
+    // Still 40bps at the open: the re-validated entry goes out.
+    app.execution.illiquid_windows.clear();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let released = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.execution.queued_entry_released")
+        .expect("entry released");
+    assert_eq!(released.payload["watch_id"], 1);
+    assert_eq!(released.payload["basis_bps"], 40.0);
+    assert_eq!(
+        app.exchange
+            .submit_requests()
+            .iter()
+            .map(|request| (request.market, request.side))
+            .collect::<Vec<_>>(),
+        vec![(Market::Futures, Side::Sell), (Market::Spot, Side::Buy)]
+    );
+    assert_eq!(count(&app, "app.strategy.basis_entered"), 1);
+    assert!(app.execution.queued_entries.is_empty());
+}
+
+#[test]
+fn app_runtime_bulk_actions_apply_to_every_watch_in_group() {
+    let mut app = trailing_stop_app();
+    let dir = unique_test_dir("strategy-groups");
+    app.recorder_coordination = RecorderCoordination::new(dir.clone());
+    app.strategy_groups = StrategyGroupStore::new(dir.join("strategy-groups.json"));
+    app.exchange.set_symbol_rules(
+        Instrument::new("ETHUSDT"),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    for (template, symbol, seed_capital_usdt) in [
+        (
+            StrategyTemplate::LiquidationBreakdownShort,
+            "BTCUSDT",
+            Some(1000.0),
+        ),
+        (StrategyTemplate::PriceSmaCrossLong, "ETHUSDT", Some(500.0)),
+        (StrategyTemplate::PriceSmaCrossShort, "ETHUSDT", None),
+    ] {
+        runtime
+            .run(
+                &mut app,
+                AppCommand::Strategy(StrategyCommand::Start {
+                    template,
+                    instrument: Instrument::new(symbol),
+                    config: StrategyStartConfig {
+                        seed_capital_usdt,
+                        ramp: None,
+                        adapt: None,
+                        script: None,
+                        ..StrategyStartConfig::default()
+                    },
+                }),
+            )
+            .expect("start should succeed");
+    }
+    for watch_id in [1, 2] {
+        runtime
+            .run(
+                &mut app,
+                AppCommand::Strategy(StrategyCommand::Group {
+                    watch_id,
+                    group: Some("trend".to_string()),
+                }),
+            )
+            .expect("group should succeed");
+    }
+    let bulk = |action| {
+        AppCommand::Strategy(StrategyCommand::Bulk {
+            group: "trend".to_string(),
+            action,
+        })
+    };
+
+    runtime
+        .run(&mut app, bulk(StrategyGroupAction::Disable))
+        .expect("bulk disable should succeed");
+    let states = |app: &AppBootstrap<FakeExchange>| {
+        (1..=3)
+            .map(|id| app.strategy_store.get(app.mode, id).expect("watch").state)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        states(&app),
+        vec![
+            StrategyWatchState::Disabled,
+            StrategyWatchState::Disabled,
+            StrategyWatchState::Armed,
+        ]
+    );
+    let stats = app.strategy_store.group_stats(app.mode);
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[0].label(), "trend");
+    assert_eq!(stats[0].disabled, 2);
+    assert_eq!(stats[0].seed_capital_usdt, 1500.0);
+    assert_eq!(stats[1].label(), "none");
+    assert_eq!(stats[1].armed, 1);
+
+    runtime
+        .run(&mut app, bulk(StrategyGroupAction::Enable))
+        .expect("bulk enable should succeed");
+    assert_eq!(states(&app)[..2], [StrategyWatchState::Armed; 2]);
+
+    runtime
+        .run(&mut app, bulk(StrategyGroupAction::Stop))
+        .expect("bulk stop should succeed");
+    let summary = app.event_log.records.last().expect("bulk event");
+    assert_eq!(summary.kind, "app.strategy.bulk_applied");
+    assert_eq!(summary.payload["changed"], serde_json::json!([2]));
+    assert_eq!(
+        summary.payload["needs_confirmation"],
+        serde_json::json!([1])
+    );
+    let rendered = render_command_output(
+        &bulk(StrategyGroupAction::Stop),
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("changed=2"));
+    assert!(rendered.contains("needs_confirmation=1"));
+    assert_eq!(app.strategy_store.active_watches(app.mode).len(), 2);
+    assert!(runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Bulk {
+                group: "meanrev".to_string(),
+                action: StrategyGroupAction::Disable,
+            }),
+        )
+        .is_err());
+}
+
+#[test]
+fn strategy_groups_survive_a_restart_until_the_group_is_deleted() {
+    let dir = unique_test_dir("strategy-group-store");
+    let path = dir.join("strategy-groups.json");
+    let start = |symbol: &str| {
+        AppCommand::Strategy(StrategyCommand::Start {
+            template: StrategyTemplate::PriceSmaCrossLong,
+            instrument: Instrument::new(symbol),
+            config: StrategyStartConfig {
+                ramp: None,
+                adapt: None,
+                script: None,
+                ..StrategyStartConfig::default()
+            },
+        })
+    };
+    let session = || {
+        let mut app = trailing_stop_app();
+        app.recorder_coordination = RecorderCoordination::new(dir.clone());
+        app.strategy_groups = StrategyGroupStore::load(&path).expect("groups should load");
+        app.exchange.set_symbol_rules(
+            Instrument::new("ETHUSDT"),
+            Market::Futures,
+            SymbolRules {
+                min_qty: 0.001,
+                max_qty: 100.0,
+                step_size: 0.001,
+                tick_size: 0.01,
+            },
+        );
+        app
+    };
+    let mut runtime = AppRuntime::default();
+    let mut app = session();
+    runtime
+        .run(&mut app, start("ETHUSDT"))
+        .expect("start should succeed");
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Group {
+                watch_id: 1,
+                group: Some("trend".to_string()),
+            }),
+        )
+        .expect("group should succeed");
+
+    // Watch ids restart, so the label follows mode, template and instrument.
+    let mut app = session();
+    runtime
+        .run(&mut app, start("BTCUSDT"))
+        .expect("start should succeed");
+    runtime
+        .run(&mut app, start("ETHUSDT"))
+        .expect("start should succeed");
+    let group = |app: &AppBootstrap<FakeExchange>, id| {
+        app.strategy_store
+            .get(app.mode, id)
+            .expect("watch")
+            .group
+            .clone()
+    };
+    assert_eq!(group(&app, 1), None);
+    assert_eq!(group(&app, 2), Some("trend".to_string()));
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Bulk {
+                group: "trend".to_string(),
+                action: StrategyGroupAction::Delete,
+            }),
+        )
+        .expect("bulk delete should succeed");
+    assert_eq!(
+        app.strategy_store.get(app.mode, 2).expect("watch").state,
+        StrategyWatchState::Stopped
+    );
+    assert!(app.strategy_groups.labels().is_empty());
+
+    let mut app = session();
+    runtime
+        .run(&mut app, start("ETHUSDT"))
+        .expect("start should succeed");
+    assert_eq!(group(&app, 1), None);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn app_runtime_select_previews_then_applies_to_filtered_watches() {
+    let mut app = trailing_stop_app();
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("strategy-select"));
+    app.exchange.set_symbol_rules(
+        Instrument::new("ETHUSDT"),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    for (template, symbol, seed_capital_usdt) in [
+        (StrategyTemplate::PriceSmaCrossLong, "BTCUSDT", Some(1000.0)),
+        (StrategyTemplate::PriceSmaCrossLong, "ETHUSDT", Some(500.0)),
+        (StrategyTemplate::PriceSmaCrossShort, "ETHUSDT", None),
+        (
+            StrategyTemplate::LiquidationBreakdownShort,
+            "ETHUSDT",
+            Some(500.0),
+        ),
+    ] {
+        runtime
+            .run(
+                &mut app,
+                AppCommand::Strategy(StrategyCommand::Start {
+                    template,
+                    instrument: Instrument::new(symbol),
+                    config: StrategyStartConfig {
+                        seed_capital_usdt,
+                        ramp: None,
+                        adapt: None,
+                        script: None,
+                        ..StrategyStartConfig::default()
+                    },
+                }),
+            )
+            .expect("start should succeed");
+    }
+    let filter = StrategyFilter::parse(&["kind=sma", "symbol=ETH*", "pnl>=0"].map(str::to_string))
+        .expect("filter");
+    let select = |confirmed| {
+        AppCommand::Strategy(StrategyCommand::Select {
+            filter: filter.clone(),
+            action: Some(StrategyGroupAction::Disable),
+            confirmed,
+        })
+    };
+    let render = |app: &AppBootstrap<FakeExchange>, command: &AppCommand| {
+        render_command_output(
+            command,
+            &app.portfolio_store,
+            &app.price_store,
+            &app.event_log,
+            &app.strategy_store,
+            app.mode,
+            &app.execution.history,
+        )
+    };
+
+    runtime
+        .run(&mut app, select(false))
+        .expect("preview should succeed");
+    let preview = app.event_log.records.last().expect("preview event");
+    assert_eq!(preview.kind, "app.strategy.select_previewed");
+    assert_eq!(preview.payload["matched"], serde_json::json!([2]));
+    let rendered = render(&app, &select(false));
+    assert!(rendered.contains("matched=1"));
+    assert!(rendered.contains("- id=2 template=price-sma-cross-long instrument=ETHUSDT"));
+    assert!(rendered.contains("/strategy select kind=sma symbol=ETH* pnl>=0 disable confirm"));
+    assert!(app
+        .strategy_store
+        .active_watches(app.mode)
+        .iter()
+        .all(|watch| watch.state == StrategyWatchState::Armed));
+
+    runtime
+        .run(&mut app, select(true))
+        .expect("confirmed select should succeed");
+    let applied = app.event_log.records.last().expect("bulk event");
+    assert_eq!(applied.kind, "app.strategy.bulk_applied");
+    assert_eq!(applied.payload["filter"], "kind=sma symbol=ETH* pnl>=0");
+    assert_eq!(applied.payload["changed"], serde_json::json!([2]));
+    assert!(render(&app, &select(true)).contains("changed=2"));
+    let states = (1..=4)
+        .map(|id| app.strategy_store.get(app.mode, id).expect("watch").state)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        vec![
+            StrategyWatchState::Armed,
+            StrategyWatchState::Disabled,
+            StrategyWatchState::Armed,
+            StrategyWatchState::Armed,
+        ]
+    );
+}
+
+#[test]
+fn app_runtime_pauses_watches_on_degraded_symbols_and_rearms_after_cooldown() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let coordination = RecorderCoordination::new(unique_test_dir("degraded-watch"));
+    app.recorder_coordination = coordination.clone();
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: instrument.clone(),
+                config: StrategyStartConfig {
+                    risk_pct: 0.005,
+                    win_rate: 0.8,
+                    r_multiple: 1.5,
+                    max_entry_slippage_pct: 0.001,
+                    seed_capital_usdt: None,
+                    ramp: None,
+                    adapt: None,
+                    script: None,
+                },
+            }),
+        )
+        .expect("strategy start should succeed");
+    let until_ms = chrono::Utc::now().timestamp_millis() + 300_000;
+    coordination
+        .sync_degraded_symbols(
+            BinanceMode::Demo,
+            vec![DegradedSymbol {
+                symbol: "BTCUSDT".to_string(),
+                until_ms,
+            }],
+        )
+        .expect("publish degraded");
+
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    let watch = app
+        .strategy_store
+        .get(BinanceMode::Demo, 1)
+        .expect("watch stored");
+    assert_eq!(watch.state, StrategyWatchState::Paused);
+    assert!(app
+        .event_log
+        .records
+        .iter()
+        .any(|record| record.kind == "app.strategy.paused_degraded"));
+
+    coordination
+        .sync_degraded_symbols(BinanceMode::Demo, Vec::new())
+        .expect("clear degraded");
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    let watch = app
+        .strategy_store
+        .get(BinanceMode::Demo, 1)
+        .expect("watch stored");
+    assert_eq!(watch.state, StrategyWatchState::Armed);
+    assert!(app
+        .event_log
+        .records
+        .iter()
+        .any(|record| record.kind == "app.strategy.resumed"));
+}
+
+#[test]
+fn app_runtime_diagnostics_samples_loop_lag_and_strategy_ticks() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("diagnostics"));
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::PriceSmaCrossLong,
+                instrument,
+                config: StrategyStartConfig {
+                    risk_pct: 0.005,
+                    win_rate: 0.8,
+                    r_multiple: 1.5,
+                    max_entry_slippage_pct: 0.001,
+                    seed_capital_usdt: None,
+                    ramp: None,
+                    adapt: None,
+                    script: None,
+                },
+            }),
+        )
+        .expect("strategy start should succeed");
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    runtime
+        .run(&mut app, AppCommand::Diagnostics)
+        .expect("diagnostics should succeed");
+
+    let sample = app.event_log.records.last().expect("diagnostics event");
+    assert_eq!(sample.kind, "app.diagnostics.sampled");
+    assert_eq!(sample.payload["snapshot"]["active_watches"], 1);
+    assert_eq!(sample.payload["snapshot"]["strategy_symbols"], 1);
+    assert_eq!(sample.payload["snapshot"]["strategy_tick_samples"], 1);
+    assert!(sample.payload["snapshot"]["loop_lag_p95_ms"].is_number());
+    let rendered = render_command_output(
+        &AppCommand::Diagnostics,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.starts_with("diagnostics\n"));
+    assert!(rendered.contains("loop_lag last="));
+    assert!(rendered.contains("load active_watches=1 strategy_symbols=1"));
+    assert!(rendered.contains("status=ok"));
+}
+
+#[test]
+fn low_resource_profile_caps_buffers_and_reports_memory_in_diagnostics() {
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("low-profile"));
+    app.event_log.routing = EventRouting::parse("market_data=memory");
+    for index in 0..700 {
+        log(
+            &mut app.event_log,
+            "app.market_data.sampled",
+            json!({ "index": index }),
+        );
+    }
+    app.apply_resource_profile(ResourceProfile::Low);
+    let limits = ResourceProfile::Low.limits();
+
+    assert_eq!(app.event_log.records.len(), 500);
+    assert_eq!(app.event_log.records[0].payload["index"], 200);
+    assert_eq!(app.execution.history.capacity(), limits.order_history_rows);
+    assert_eq!(app.news.pane_limit(), limits.news_headlines);
+    assert_eq!(
+        ResourceProfile::Low.idle_tick(),
+        std::time::Duration::from_secs(5)
+    );
+
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::Diagnostics)
+        .expect("diagnostics should succeed");
+    // Appends past the cap stay until the next compaction between commands.
+    assert_eq!(app.event_log.records.len(), 501);
+    assert_eq!(app.event_log.compact(), 1);
+
+    let sample = app.event_log.records.last().expect("diagnostics event");
+    let memory = &sample.payload["memory"];
+    assert_eq!(memory["profile"], "low");
+    assert_eq!(memory["buffers"][0]["buffer"], "events");
+    assert_eq!(memory["buffers"][0]["entries"], 500);
+    assert_eq!(memory["buffers"][0]["limit"], 500);
+    assert!(memory["buffer_bytes"].as_u64().unwrap_or_default() > 0);
+    let rendered = render_command_output(
+        &AppCommand::Diagnostics,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("memory profile=low buffers="));
+    assert!(rendered.contains("events=500/500 orders=0/100"));
+    assert!(rendered.contains("latency_samples=0/128 news=0/20"));
+}
+
+#[test]
+fn fast_resource_profile_skips_daily_account_lookups_on_refresh() {
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_today_realized_pnl_usdt(12.5);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("fast-profile"));
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("standard refresh should succeed");
+    let refreshed = app
+        .event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.portfolio.refreshed")
+        .expect("refresh event");
+    assert_eq!(refreshed.payload["today_realized_pnl_usdt"], 12.5);
+
+    assert_eq!(ResourceProfile::parse("colo"), Some(ResourceProfile::Fast));
+    app.apply_resource_profile(ResourceProfile::Fast);
+    assert!(ResourceProfile::Fast.latency_first());
+    assert!(!ResourceProfile::Fast.full_panels());
+    assert_eq!(
+        ResourceProfile::Fast.idle_tick(),
+        std::time::Duration::from_millis(100)
+    );
+    assert_eq!(app.event_log.capacity, Some(2_000));
+    assert_eq!(
+        app.execution.history.capacity(),
+        ResourceProfile::Standard.limits().order_history_rows
+    );
+
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("fast refresh should succeed");
+    let refreshed = app
+        .event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.portfolio.refreshed")
+        .expect("refresh event");
+    assert!(refreshed.payload["today_realized_pnl_usdt"].is_null());
+    assert!(refreshed.payload["margin_ratio"].is_null());
+}
+
+#[test]
+fn app_runtime_exports_diagnostics_bundle_as_zip() {
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    let dir = unique_test_dir("diagnostics-export");
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.recorder_coordination = RecorderCoordination::new(&dir);
+    let mut runtime = AppRuntime::default();
+    let path = dir.join("report.zip");
+    log(
+        &mut app.event_log,
+        "app.refresh.failed",
+        json!({
+            "error": "error sending request for url (https://fapi.binance.com/fapi/v1/order?symbol=BTCUSDT&timestamp=1712900000000&signature=9f3ac0ffee)",
+        }),
+    );
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::ExportDiagnostics {
+                path: Some(path.clone()),
+            },
+        )
+        .expect("export should succeed");
+
+    let exported = app.event_log.records.last().expect("export event");
+    assert_eq!(exported.kind, "app.diagnostics.exported");
+    assert_eq!(exported.payload["path"], path.display().to_string());
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).expect("bundle file"))
+        .expect("bundle should be a zip");
+    let names = archive
+        .file_names()
+        .map(str::to_string)
+        .collect::<BTreeSet<_>>();
+    for name in [
+        "version.json",
+        "config.json",
+        "session.json",
+        "network.json",
+        "events.jsonl",
+    ] {
+        assert!(names.contains(name), "missing {name}");
+    }
+    let mut version = String::new();
+    std::io::Read::read_to_string(
+        &mut archive.by_name("version.json").expect("version entry"),
+        &mut version,
+    )
+    .expect("version should read");
+    assert!(version.contains(env!("CARGO_PKG_VERSION")));
+    let mut events = String::new();
+    std::io::Read::read_to_string(
+        &mut archive.by_name("events.jsonl").expect("events entry"),
+        &mut events,
+    )
+    .expect("events should read");
+    assert!(events.contains("app.diagnostics.sampled"));
+    assert!(
+        events.contains("/fapi/v1/order?symbol=BTCUSDT&timestamp=<redacted>&signature=<redacted>)")
+    );
+    assert!(!events.contains("9f3ac0ffee"));
+    assert!(!events.contains("1712900000000"));
+
+    let rendered = render_command_output(
+        &AppCommand::ExportDiagnostics { path: None },
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.starts_with(&format!("diagnostics bundle {}", path.display())));
+    assert!(
+        rendered.contains("files version.json config.json session.json network.json events.jsonl")
+    );
+}
+
+#[test]
+fn diagnostics_bundle_masks_config_and_query_secrets() {
+    let config = redacted_config([
+        (
+            "BINANCE_API_KEY".to_string(),
+            "vmPUZE6mv9SD5VNHk4HlEh8A".to_string(),
+        ),
+        ("BINANCE_API_SECRET".to_string(), "s3cr3t".to_string()),
+        ("SANDBOX_QUANT_NOTIFY_TOKEN".to_string(), "tok".to_string()),
+        (
+            "SANDBOX_QUANT_PG_URL".to_string(),
+            "postgres://quant:pw@db/market".to_string(),
+        ),
+        ("SANDBOX_QUANT_UI_THEME".to_string(), "dark".to_string()),
+        ("HOME".to_string(), "/home/quant".to_string()),
+    ]);
+
+    assert_eq!(config["BINANCE_API_KEY"], "****Eh8A");
+    assert_eq!(config["BINANCE_API_SECRET"], "<redacted>");
+    assert_eq!(config["SANDBOX_QUANT_NOTIFY_TOKEN"], "<redacted>");
+    assert_eq!(
+        config["SANDBOX_QUANT_PG_URL"],
+        "postgres://quant:****@db/market"
+    );
+    assert_eq!(config["SANDBOX_QUANT_UI_THEME"], "dark");
+    assert!(!config.contains_key("HOME"));
+
+    assert_eq!(
+        redact_query_secrets(
+            "keepalive failed: wss://fstream.binance.com/ws?listenKey=pqia91 \"/api/v3/klines?symbol=BTCUSDT&startTime=1712\" /sapi/v1/x?apiKey=vmPU&timestamp=1712"
+        ),
+        "keepalive failed: wss://fstream.binance.com/ws?listenKey=<redacted> \"/api/v3/klines?symbol=BTCUSDT&startTime=1712\" /sapi/v1/x?apiKey=<redacted>&timestamp=1712"
+    );
+}
+
+#[test]
+fn app_runtime_dom_places_at_book_level_and_cancels_resting_order() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![],
+        open_orders: vec![OpenOrder {
+            order_id: Some(OrderId(7)),
+            client_order_id: "web-1".to_string(),
+            instrument: instrument.clone(),
+            market: Market::Futures,
+            side: Side::Buy,
+            price: Some(99.0),
+            orig_qty: 0.5,
+            executed_qty: 0.0,
+            reduce_only: false,
+            status: OrderStatus::Submitted,
+        }],
+    });
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.1,
+        },
+    );
+    exchange.set_order_book_depth(
+        instrument.clone(),
+        Market::Futures,
+        OrderBookDepth {
+            bids: vec![(100.0, 3.0), (99.5, 1.0)],
+            asks: vec![(100.5, 2.0), (101.0, 4.0)],
+        },
+    );
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Dom(DomCommand {
+                instrument: instrument.clone(),
+                levels: 10,
+                action: Some(DomAction::Place {
+                    side: Side::Buy,
+                    price: DomPrice::Bid(1),
+                    qty: 0.01,
+                }),
+            }),
+        )
+        .expect("ladder order should succeed");
+
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].order_type, OrderType::Limit { price: 100.0 });
+    assert_eq!(requests[0].side, Side::Buy);
+    assert!(!requests[0].reduce_only);
+    let ladder = app.event_log.records.last().expect("dom event");
+    assert_eq!(ladder.kind, "app.dom.loaded");
+    assert_eq!(
+        ladder.payload["action"]["command_kind"],
+        "place_limit_order"
+    );
+    let rows = ladder.payload["rows"].as_array().expect("rows");
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0]["price"], 101.0);
+    assert_eq!(rows[4]["price"], 99.0);
+    assert_eq!(rows[4]["own_orders"][0]["client_order_id"], "web-1");
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Dom(DomCommand {
+                instrument: instrument.clone(),
+                levels: 10,
+                action: Some(DomAction::Cancel {
+                    client_order_id: "web-1".to_string(),
+                }),
+            }),
+        )
+        .expect("cancel should succeed");
+
+    assert_eq!(
+        app.exchange.cancel_requests(),
+        vec![(instrument.clone(), Market::Futures, "web-1".to_string())]
+    );
+    let rendered = render_command_output(
+        &AppCommand::Dom(DomCommand {
+            instrument: instrument.clone(),
+            levels: 10,
+            action: None,
+        }),
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.starts_with("dom BTCUSDT market=Futures levels=10 resting=0\n"));
+    assert!(rendered.contains("cancelled web-1"));
+    assert!(!rendered.contains("99 |"));
+
+    let error = runtime
+        .run(
+            &mut app,
+            AppCommand::Dom(DomCommand {
+                instrument,
+                levels: 10,
+                action: Some(DomAction::Cancel {
+                    client_order_id: "web-1".to_string(),
+                }),
+            }),
+        )
+        .expect_err("unknown order should be rejected");
+    assert!(error.to_string().contains("no resting order web-1"));
+}
+
+#[test]
+fn app_runtime_oco_rearms_cancelled_leg_and_escalates_when_repair_fails() {
+    let instrument = Instrument::new("BTCUSDT");
+    let position = PositionSnapshot {
+        instrument: instrument.clone(),
+        market: Market::Futures,
+        signed_qty: 0.2,
+        entry_price: Some(64000.0),
+    };
+    let leg = |client_order_id: &str, price: f64| OpenOrder {
+        order_id: None,
+        client_order_id: client_order_id.to_string(),
+        instrument: instrument.clone(),
+        market: Market::Futures,
+        side: Side::Sell,
+        price: Some(price),
+        orig_qty: 0.2,
+        executed_qty: 0.0,
+        reduce_only: true,
+        status: OrderStatus::Submitted,
+    };
+    let snapshot =
+        |positions: Vec<PositionSnapshot>, open_orders: Vec<OpenOrder>| AuthoritativeSnapshot {
+            balances: vec![],
+            positions,
+            open_orders,
+        };
+    let exchange = FakeExchange::new(snapshot(vec![position.clone()], vec![]));
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.1,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 64000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::PlaceOco {
+                instrument: instrument.clone(),
+                take_profit_price: 66000.0,
+                stop_price: 62000.0,
+                stop_limit_price: None,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("oco should be placed");
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].order_type, OrderType::Limit { price: 66000.0 });
+    assert_eq!(requests[0].client_order_id.as_deref(), Some("oco-1-tp"));
+    assert_eq!(
+        requests[1].order_type,
+        OrderType::StopMarket {
+            stop_price: 62000.0
+        }
+    );
+    assert!(requests.iter().all(|request| request.reduce_only));
+    assert!(app
+        .execution
+        .history
+        .rows()
+        .iter()
+        .all(|row| row.list_id == Some(1)));
+
+    // Stop leg cancelled outside the app while the position is intact.
+    app.exchange.replace_snapshot(snapshot(
+        vec![position.clone()],
+        vec![leg("oco-1-tp", 66000.0)],
+    ));
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let rearmed = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.oco.leg_rearmed")
+        .expect("stop leg should be re-armed");
+    assert_eq!(rearmed.payload["client_order_id"], "oco-1-sl-r1");
+    assert_eq!(app.exchange.submit_requests().len(), 3);
+
+    // Cancelled again and the venue rejects the repair.
+    app.exchange
+        .set_next_order_submit_result(Err(ExchangeError::RemoteReject {
+            code: -2021,
+            message: "Order would immediately trigger.".to_string(),
+        }));
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert!(app
+        .event_log
+        .records
+        .iter()
+        .any(|record| record.kind == "app.oco.repair_failed"
+            && record.payload["needs_manual"] == true));
+
+    runtime
+        .run(&mut app, AppCommand::OcoStatus)
+        .expect("oco status should succeed");
+    let rendered = render_command_output(
+        &AppCommand::OcoStatus,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.starts_with("oco lists (1)\n"));
+    assert!(rendered.contains("status=needs_manual repairs=2"));
+
+    // Take-profit fills: the position closes and the list completes.
+    app.exchange.replace_snapshot(snapshot(vec![], vec![]));
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let completed = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.oco.completed")
+        .expect("list should complete");
+    assert_eq!(completed.payload["list_id"], 1);
+    assert!(app.execution.oco_lists.is_empty());
+}
+
+#[test]
+fn app_runtime_tracks_strategy_signals_from_risk_gate_to_fill() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 1000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.portfolio_store
+        .refresh_from_exchange(&app.exchange)
+        .expect("seed snapshot");
+    let mut runtime = AppRuntime::default();
+    let strategy_target = |target: f64| {
+        AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+            instrument: instrument.clone(),
+            target: Exposure::new(target).expect("bounded exposure"),
+            order_type: OrderType::Market,
+            source: CommandSource::Strategy {
+                watch_id: 3,
+                version: "3f9a0c1d".to_string(),
+            },
+        })
+    };
+
+    runtime
+        .run(&mut app, strategy_target(0.5))
+        .expect("strategy order should succeed");
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests[0].client_order_id.as_deref(), Some("strategy-3-1"));
+    app.execution.entries_blackout_until = Some(Utc::now() + Duration::hours(1));
+    assert!(runtime.run(&mut app, strategy_target(0.8)).is_err());
+    app.execution.entries_blackout_until = None;
+
+    let qty = requests[0].qty;
+    let fill = json!({
+        "e": "ORDER_TRADE_UPDATE", "E": 1_700_000_000_000_i64,
+        "o": {
+            "s": "BTCUSDT", "c": "strategy-3-1", "S": "BUY", "q": qty.to_string(), "p": "0",
+            "X": "FILLED", "i": 7, "l": qty.to_string(), "z": qty.to_string(),
+            "L": "50020", "R": false
+        }
+    });
+    let event = parse_user_stream_event(&fill, Market::Futures).expect("order update");
+    runtime.ingest_user_stream_event(&mut app, event, Utc::now());
+
+    let funnel = AppCommand::Strategy(StrategyCommand::Funnel { window_hours: 24 });
+    runtime
+        .run(&mut app, funnel.clone())
+        .expect("funnel should list");
+    let rendered = render_command_output(
+        &funnel,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("window_hours=24"), "{rendered}");
+    assert!(
+        rendered.contains(
+            "- id=3 instrument=- signals=2 passed_risk=1 submitted=1 filled=1 drops=blackout:1"
+        ),
+        "{rendered}"
+    );
+}
+
+#[test]
+fn app_runtime_works_iceberg_slices_until_the_parent_fills() {
+    let instrument = Instrument::new("BTCUSDT");
+    let snapshot = |signed_qty: f64, open_orders: Vec<OpenOrder>| AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![PositionSnapshot {
+            instrument: instrument.clone(),
+            market: Market::Futures,
+            signed_qty,
+            entry_price: Some(64000.0),
+        }],
+        open_orders,
+    };
+    let slice = |client_order_id: &str, executed_qty: f64, status: OrderStatus| OpenOrder {
+        order_id: None,
+        client_order_id: client_order_id.to_string(),
+        instrument: instrument.clone(),
+        market: Market::Futures,
+        side: Side::Buy,
+        price: Some(64000.0),
+        orig_qty: 0.2,
+        executed_qty,
+        reduce_only: false,
+        status,
+    };
+    let exchange = FakeExchange::new(snapshot(0.0, vec![]));
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.1,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 64000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+    let events = |app: &AppBootstrap<FakeExchange>, kind: &str| {
+        app.event_log
+            .records
+            .iter()
+            .filter(|record| record.kind == kind)
+            .map(|record| record.payload.clone())
+            .collect::<Vec<_>>()
+    };
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::PlaceIceberg {
+                instrument: instrument.clone(),
+                side: Side::Buy,
+                qty: 0.5,
+                visible_qty: 0.2,
+                price: 64000.04,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("iceberg should be placed");
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].order_type, OrderType::Limit { price: 64000.0 });
+    assert_eq!(requests[0].client_order_id.as_deref(), Some("ice-1-1"));
+    assert_eq!(requests[0].qty, 0.2);
+
+    app.exchange.replace_snapshot(snapshot(
+        0.05,
+        vec![slice("ice-1-1", 0.05, OrderStatus::Submitted)],
+    ));
+    runtime
+        .run(&mut app, AppCommand::IcebergStatus)
+        .expect("iceberg status should succeed");
+    let rendered = render_command_output(
+        &AppCommand::IcebergStatus,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.starts_with("icebergs (1)\n"));
+    assert!(rendered.contains("parent=1 BTCUSDT buy 0.05/0.5 (10.0%) @ 64000 visible=0.2"));
+
+    // Slices settle by their own client order id, whatever the position
+    // does: the first from its execution report, the rest from the
+    // venue's order status.
+    let report = json!({
+        "e": "ORDER_TRADE_UPDATE", "E": 1_700_000_000_000_i64,
+        "o": {
+            "s": "BTCUSDT", "c": "ice-1-1", "S": "BUY", "q": "0.2", "p": "64000",
+            "X": "FILLED", "i": 11, "l": "0.15", "z": "0.2", "L": "64000", "R": false
+        }
+    });
+    let event = parse_user_stream_event(&report, Market::Futures).expect("order update");
+    runtime.ingest_user_stream_event(&mut app, event, Utc::now());
+    let refresh = |app: &mut AppBootstrap<FakeExchange>, runtime: &mut AppRuntime| {
+        app.exchange.replace_snapshot(snapshot(3.0, vec![]));
+        runtime
+            .run(app, AppCommand::RefreshAuthoritativeState)
+            .expect("refresh should succeed");
+    };
+    refresh(&mut app, &mut runtime);
+    app.exchange
+        .set_order_status(slice("ice-1-2", 0.2, OrderStatus::Filled));
+    refresh(&mut app, &mut runtime);
+    // A slice the venue still reports open waits for the next poll.
+    app.exchange
+        .set_order_status(slice("ice-1-3", 0.05, OrderStatus::Submitted));
+    refresh(&mut app, &mut runtime);
+    assert_eq!(app.execution.icebergs.len(), 1);
+    app.exchange
+        .set_order_status(slice("ice-1-3", 0.1, OrderStatus::Filled));
+    refresh(&mut app, &mut runtime);
+    let slices = events(&app, "app.iceberg.slice_filled");
+    assert_eq!(slices.len(), 3);
+    assert_eq!(slices[0]["client_order_id"], "ice-1-1");
+    assert_eq!(slices[0]["slice_qty"], 0.2);
+    assert_eq!(slices[0]["next_order_id"], "ice-1-2");
+    assert_eq!(slices[1]["client_order_id"], "ice-1-2");
+    assert_eq!(slices[1]["next_order_id"], "ice-1-3");
+    assert_eq!(slices[2]["client_order_id"], "ice-1-3");
+    assert!((slices[2]["slice_qty"].as_f64().unwrap() - 0.1).abs() < 1e-9);
+    assert!(slices[2]["next_order_id"].is_null());
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 3);
+    assert!((requests[2].qty - 0.1).abs() < 1e-9);
+    let completed = events(&app, "app.iceberg.completed");
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0]["slices"], 3);
+    assert!(app.execution.icebergs.is_empty());
+    assert!(app
+        .execution
+        .history
+        .rows()
+        .iter()
+        .all(|row| row.parent_id == Some(1)));
+
+    // A slice cancelled outside the app stops its parent at what the
+    // slice itself filled.
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::PlaceIceberg {
+                instrument: instrument.clone(),
+                side: Side::Sell,
+                qty: 0.5,
+                visible_qty: 0.25,
+                price: 65000.0,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("iceberg should be placed");
+    app.exchange.set_order_status(OpenOrder {
+        side: Side::Sell,
+        ..slice("ice-2-1", 0.1, OrderStatus::Cancelled)
+    });
+    refresh(&mut app, &mut runtime);
+    let stopped = events(&app, "app.iceberg.stopped");
+    assert_eq!(stopped.len(), 1);
+    assert_eq!(stopped[0]["parent_id"], 2);
+    assert_eq!(stopped[0]["filled_qty"], 0.1);
+    assert!(app.execution.icebergs.is_empty());
+}
+
+#[test]
+fn app_runtime_compares_strategy_fills_with_their_paper_shadow() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 1000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.portfolio_store
+        .refresh_from_exchange(&app.exchange)
+        .expect("seed snapshot");
+    app.execution.paper_shadow = PaperShadowConfig::parse("2");
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+                instrument: instrument.clone(),
+                target: Exposure::new(0.5).expect("bounded exposure"),
+                order_type: OrderType::Market,
+                source: CommandSource::Strategy {
+                    watch_id: 3,
+                    version: "3f9a0c1d".to_string(),
+                },
+            }),
+        )
+        .expect("strategy order should succeed");
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].client_order_id.as_deref(), Some("shadow-3-1"));
+    let qty = requests[0].qty;
+    assert_eq!(app.execution.shadow_orders.len(), 1);
+    assert!((app.execution.shadow_orders[0].paper_price - 50010.0).abs() < 1e-6);
+
+    let fill = json!({
+        "e": "ORDER_TRADE_UPDATE", "E": 1_700_000_000_000_i64,
+        "o": {
+            "s": "BTCUSDT", "c": "shadow-3-1", "S": "BUY", "q": qty.to_string(), "p": "0",
+            "X": "FILLED", "i": 7, "l": qty.to_string(), "z": qty.to_string(),
+            "L": "50020", "R": false
+        }
+    });
+    let event = parse_user_stream_event(&fill, Market::Futures).expect("order update");
+    runtime.ingest_user_stream_event(&mut app, event, Utc::now());
+    let live_fill = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.shadow.live_fill")
+        .expect("shadowed fill should be logged");
+    assert_eq!(live_fill.payload["watch_id"], 3);
+    assert_eq!(live_fill.payload["fill_price"], 50020.0);
+
+    runtime
+        .run(&mut app, AppCommand::ShadowStatus)
+        .expect("shadow status should succeed");
+    let rendered = render_command_output(
+        &AppCommand::ShadowStatus,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.starts_with("paper shadow model=2bps (1)\n"));
+    assert!(rendered.contains("watch=3 orders=1"));
+    assert!(rendered.contains("slippage=4.0bps error=+2.0bps"));
+
+    // Orders the operator sends are not shadowed.
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+                instrument,
+                target: Exposure::new(0.2).expect("bounded exposure"),
+                order_type: OrderType::Market,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("user order should succeed");
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].client_order_id, None);
+    assert_eq!(app.execution.shadow_orders.len(), 1);
+}
+
+#[test]
+fn app_runtime_oco_resizes_legs_as_a_partially_filled_entry_keeps_filling() {
+    let instrument = Instrument::new("BTCUSDT");
+    let position = |signed_qty: f64| PositionSnapshot {
+        instrument: instrument.clone(),
+        market: Market::Futures,
+        signed_qty,
+        entry_price: Some(64000.0),
+    };
+    let leg = |client_order_id: &str, price: f64| OpenOrder {
+        order_id: None,
+        client_order_id: client_order_id.to_string(),
+        instrument: instrument.clone(),
+        market: Market::Futures,
+        side: Side::Sell,
+        price: Some(price),
+        orig_qty: 0.1,
+        executed_qty: 0.0,
+        reduce_only: true,
+        status: OrderStatus::Submitted,
+    };
+    let snapshot =
+        |positions: Vec<PositionSnapshot>, open_orders: Vec<OpenOrder>| AuthoritativeSnapshot {
+            balances: vec![],
+            positions,
+            open_orders,
+        };
+    // Entry limit for 0.25 has filled 0.1 so far.
+    let exchange = FakeExchange::new(snapshot(vec![position(0.1)], vec![]));
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.1,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 64000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::PlaceOco {
+                instrument: instrument.clone(),
+                take_profit_price: 66000.0,
+                stop_price: 62000.0,
+                stop_limit_price: None,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("oco should be placed");
+    assert!(app
+        .exchange
+        .submit_requests()
+        .iter()
+        .all(|request| (request.qty - 0.1).abs() < 1e-9));
+
+    // The rest of the entry fills while both legs rest.
+    app.exchange.replace_snapshot(snapshot(
+        vec![position(0.25)],
+        vec![leg("oco-1-tp", 66000.0), leg("oco-1-sl", 62000.0)],
+    ));
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+
+    let resized = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.oco.resized")
+        .expect("legs should be resized");
+    assert_eq!(resized.payload["previous_qty"], 0.1);
+    assert_eq!(resized.payload["qty"], 0.25);
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[2].client_order_id.as_deref(), Some("oco-1-sl-s1"));
+    assert_eq!(requests[3].client_order_id.as_deref(), Some("oco-1-tp-s1"));
+    assert!(requests[2..]
+        .iter()
+        .all(|request| (request.qty - 0.25).abs() < 1e-9 && request.reduce_only));
+    let cancelled = app
+        .exchange
+        .cancel_requests()
+        .into_iter()
+        .map(|(_, _, client_order_id)| client_order_id)
+        .collect::<Vec<_>>();
+    assert_eq!(cancelled, vec!["oco-1-tp", "oco-1-sl"]);
+    assert_eq!(app.execution.oco_lists[0].qty, 0.25);
+
+    // Same size on the next poll: nothing to do.
+    app.exchange.replace_snapshot(snapshot(
+        vec![position(0.25)],
+        vec![leg("oco-1-tp-s1", 66000.0), leg("oco-1-sl-s1", 62000.0)],
+    ));
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert_eq!(app.exchange.submit_requests().len(), 4);
+}
+
+#[test]
+fn app_runtime_suggests_then_takes_an_exit_when_holding_ev_turns_negative() {
+    let instrument = Instrument::new("BTCUSDT");
+    let snapshot = |signed_qty: f64, open_orders: Vec<OpenOrder>| AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![PositionSnapshot {
+            instrument: instrument.clone(),
+            market: Market::Futures,
+            signed_qty,
+            entry_price: Some(64000.0),
+        }],
+        open_orders,
+    };
+    let leg = |client_order_id: &str, price: f64| OpenOrder {
+        order_id: None,
+        client_order_id: client_order_id.to_string(),
+        instrument: instrument.clone(),
+        market: Market::Futures,
+        side: Side::Sell,
+        price: Some(price),
+        orig_qty: 0.1,
+        executed_qty: 0.0,
+        reduce_only: true,
+        status: OrderStatus::Submitted,
+    };
+    let exchange = FakeExchange::new(snapshot(0.1, vec![]));
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.1,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 64000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.execution.hold_ev_profiles =
+        parse_hold_ev_profiles("default=win:0.5,suggest:-0.25,exit:-0.8");
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::PlaceOco {
+                instrument: instrument.clone(),
+                take_profit_price: 66000.0,
+                stop_price: 62000.0,
+                stop_limit_price: None,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("oco should be placed");
+    let legs = vec![leg("oco-1-tp", 66000.0), leg("oco-1-sl", 62000.0)];
+    app.exchange.replace_snapshot(snapshot(0.1, legs.clone()));
+
+    // 0.6R up: 0.5 * 0.4R left to win - 0.5 * 1.6R left to lose.
+    app.exchange
+        .set_last_price(instrument.clone(), Market::Futures, 65200.0);
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let suggested = app
+        .event_log
+        .records
+        .iter()
+        .filter(|record| record.kind == "app.position.hold_ev_exit_suggested")
+        .collect::<Vec<_>>();
+    assert_eq!(suggested.len(), 1);
+    assert_eq!(suggested[0].payload["profile"], "default");
+    assert!((suggested[0].payload["expected_value_r"].as_f64().unwrap() + 0.6).abs() < 1e-9);
+
+    // Still below the threshold: suggested only once.
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert_eq!(
+        app.event_log
+            .records
+            .iter()
+            .filter(|record| record.kind == "app.position.hold_ev_exit_suggested")
+            .count(),
+        1
+    );
+    assert_eq!(app.exchange.submit_requests().len(), 2);
+
+    // 0.9R up: ev=-0.9R crosses the exit line and closes at market.
+    app.exchange
+        .set_last_price(instrument.clone(), Market::Futures, 65800.0);
+    runtime
+        .run(&mut app, AppCommand::HoldEvStatus)
+        .expect("hold ev status should succeed");
+    let exited = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.position.hold_ev_exited")
+        .expect("position should be closed");
+    assert_eq!(exited.payload["action"], "auto_exit");
+    assert_eq!(exited.payload["win_probability"], 0.5);
+    assert_eq!(
+        exited.payload["model_version"],
+        "beta_binomial/v1:prior=0.5,strength=20"
+    );
+    let closes = app.exchange.close_requests();
+    assert_eq!(closes.len(), 1);
+    assert_eq!(closes[0].side, Side::Sell);
+    assert!((closes[0].qty - 0.1).abs() < 1e-9);
+
+    let output = render_command_output(
+        &AppCommand::HoldEvStatus,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(output.starts_with("hold ev (1)"));
+    assert!(
+        output.contains("BTCUSDT profile=default price=65800 r=0.90 ev=-0.90R action=auto_exit")
+    );
+}
+
+#[test]
+fn hold_ev_learns_from_realized_trades_in_the_order_store() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![PositionSnapshot {
+            instrument: instrument.clone(),
+            market: Market::Futures,
+            signed_qty: 0.1,
+            entry_price: Some(64000.0),
+        }],
+        open_orders: vec![],
+    });
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.1,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 64000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.execution.hold_ev_profiles = parse_hold_ev_profiles("default=win:0.5,suggest:-0.9");
+    let store = OrderStore::open_in_memory().expect("in-memory store");
+    let now = Utc::now();
+    let closes = [("BTCUSDT", 40.0); 8]
+        .into_iter()
+        .chain([("BTCUSDT", -50.0); 2])
+        .chain([("ETHUSDT", -10.0); 5]);
+    for (index, (symbol, realized_pnl)) in closes.enumerate() {
+        store
+            .record_fill(&FillRow {
+                time: now - Duration::hours(index as i64 + 1),
+                instrument: Instrument::new(symbol),
+                market: Market::Futures,
+                client_order_id: "close-1".to_string(),
+                order_id: Some(index as u64),
+                tag: "manual".to_string(),
+                side: Side::Sell,
+                qty: 0.1,
+                price: 64000.0,
+                realized_pnl,
+                commission: 1.0,
+            })
+            .expect("record fill");
+    }
+    app.execution.order_store = Some(store);
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::PlaceOco {
+                instrument: instrument.clone(),
+                take_profit_price: 66000.0,
+                stop_price: 62000.0,
+                stop_limit_price: None,
+                source: CommandSource::User,
+            }),
+        )
+        .expect("oco should be placed");
+    runtime
+        .run(&mut app, AppCommand::HoldEvStatus)
+        .expect("hold ev status should succeed");
+
+    let listed = app
+        .event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.position.hold_ev_listed")
+        .expect("hold ev listed");
+    let estimate = &listed.payload["estimates"][0];
+    // Prior 0.5 at strength 20 plus 8 wins and 2 losses: 18 / 30.
+    assert_eq!(estimate["trades"], 10);
+    assert!((estimate["win_probability"].as_f64().unwrap() - 0.6).abs() < 1e-9);
+}
+
+#[test]
+fn app_runtime_oco_places_stop_limit_legs_and_native_spot_lists() {
+    let btc = Instrument::new("BTCUSDT");
+    let eth = Instrument::new("ETHUSDT");
+    let position = |instrument: &Instrument, market: Market, signed_qty: f64| PositionSnapshot {
+        instrument: instrument.clone(),
+        market,
+        signed_qty,
+        entry_price: Some(100.0),
+    };
+    let leg = |instrument: &Instrument, market: Market, client_order_id: &str| OpenOrder {
+        order_id: None,
+        client_order_id: client_order_id.to_string(),
+        instrument: instrument.clone(),
+        market,
+        side: Side::Sell,
+        price: None,
+        orig_qty: 1.0,
+        executed_qty: 0.0,
+        reduce_only: true,
+        status: OrderStatus::Submitted,
+    };
+    let snapshot =
+        |positions: Vec<PositionSnapshot>, open_orders: Vec<OpenOrder>| AuthoritativeSnapshot {
+            balances: vec![],
+            positions,
+            open_orders,
+        };
+    let btc_legs = vec![
+        leg(&btc, Market::Futures, "oco-1-tp"),
+        leg(&btc, Market::Futures, "oco-1-sl"),
+    ];
+    let exchange = FakeExchange::new(snapshot(
+        vec![
+            position(&btc, Market::Futures, 0.2),
+            position(&eth, Market::Spot, 1.0),
+        ],
+        btc_legs.clone(),
+    ));
+    for (instrument, market, price) in [
+        (&btc, Market::Futures, 64000.0),
+        (&eth, Market::Spot, 3200.0),
+    ] {
+        exchange.set_last_price(instrument.clone(), market, price);
+        exchange.set_symbol_rules(
+            instrument.clone(),
+            market,
+            SymbolRules {
+                min_qty: 0.001,
+                max_qty: 100.0,
+                step_size: 0.001,
+                tick_size: 0.1,
+            },
+        );
+    }
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let oco = |instrument: &Instrument, take_profit_price, stop_price, stop_limit_price| {
+        AppCommand::Execution(ExecutionCommand::PlaceOco {
+            instrument: instrument.clone(),
+            take_profit_price,
+            stop_price,
+            stop_limit_price,
+            source: CommandSource::User,
+        })
+    };
+
+    // A sell stop-limit above its trigger could never fill.
+    let error = runtime
+        .run(&mut app, oco(&btc, 66000.0, 62000.0, Some(62100.0)))
+        .expect_err("stop limit above the trigger should be rejected");
+    assert!(error.to_string().contains("stop limit 62100"));
+
+    // Futures has no native list: two linked legs, the stop as STOP limit.
+    runtime
+        .run(&mut app, oco(&btc, 66000.0, 62000.0, Some(61900.0)))
+        .expect("futures oco should be placed");
+    let requests = app.exchange.submit_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[1].order_type,
+        OrderType::StopLimit {
+            stop_price: 62000.0,
+            price: 61900.0
+        }
+    );
+    assert_eq!(app.execution.oco_lists[0].exchange_list_id, None);
+
+    // Spot goes out as one venue list with both legs in the history.
+    runtime
+        .run(&mut app, oco(&eth, 3400.0, 3000.0, Some(2990.0)))
+        .expect("spot oco should be placed");
+    assert_eq!(app.exchange.submit_requests().len(), 2);
+    let lists = app.exchange.oco_requests();
+    let native = lists.last().expect("native oco request");
+    assert_eq!(native.market, Market::Spot);
+    assert_eq!(native.side, Side::Sell);
+    assert_eq!(native.stop_limit_price, Some(2990.0));
+    assert_eq!(native.list_client_order_id, "oco-2");
+    assert_eq!(native.stop_client_order_id, "oco-2-sl");
+    let placed = app
+        .event_log
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.kind == "app.execution.completed")
+        .expect("oco placement should be logged");
+    assert_eq!(placed.payload["exchange_list_id"], "fake-oco-1");
+    assert_eq!(
+        app.execution
+            .history
+            .rows()
+            .iter()
+            .filter(|row| row.list_id == Some(2))
+            .count(),
+        2
+    );
+
+    app.exchange.replace_snapshot(snapshot(
+        vec![
+            position(&btc, Market::Futures, 0.2),
+            position(&eth, Market::Spot, 1.0),
+        ],
+        [
+            btc_legs.clone(),
+            vec![
+                leg(&eth, Market::Spot, "oco-2-tp"),
+                leg(&eth, Market::Spot, "oco-2-sl"),
+            ],
+        ]
+        .concat(),
+    ));
+    runtime
+        .run(&mut app, AppCommand::OcoStatus)
+        .expect("oco status should succeed");
+    let rendered = render_command_output(
+        &AppCommand::OcoStatus,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("sl=62000 limit=61900 (oco-1-sl)"));
+    assert!(rendered
+        .contains("sl=3000 limit=2990 (oco-2-sl) status=working repairs=0 venue_list=fake-oco-1"));
+
+    // The venue cancelled the other leg itself after a partial take-profit.
+    app.exchange.replace_snapshot(snapshot(
+        vec![
+            position(&btc, Market::Futures, 0.2),
+            position(&eth, Market::Spot, 0.4),
+        ],
+        btc_legs,
+    ));
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let completed = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.oco.completed")
+        .expect("native list should complete");
+    assert_eq!(completed.payload["list_id"], 2);
+    assert_eq!(app.execution.oco_lists.len(), 1);
+}
+
+#[test]
+fn app_runtime_detects_and_adopts_positions_opened_outside_the_app() {
+    let eth = Instrument::new("ETHUSDT");
+    let sol = Instrument::new("SOLUSDT");
+    let exchange = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![
+            BalanceSnapshot {
+                asset: "USDT".to_string(),
+                free: 1000.0,
+                locked: 0.0,
+            },
+            BalanceSnapshot {
+                asset: "SOL".to_string(),
+                free: 12.0,
+                locked: 0.0,
+            },
+        ],
+        positions: vec![PositionSnapshot {
+            instrument: eth.clone(),
+            market: Market::Futures,
+            signed_qty: -1.0,
+            entry_price: Some(3200.0),
+        }],
+        open_orders: vec![],
+    });
+    exchange.set_last_price(eth.clone(), Market::Futures, 3000.0);
+    exchange.set_last_price(sol.clone(), Market::Spot, 150.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let external = &app.portfolio_store.external_positions;
+    assert_eq!(external.len(), 2);
+    assert_eq!(external[0].instrument, eth);
+    assert_eq!(external[0].exchange_entry_price, Some(3200.0));
+    assert_eq!(external[1].instrument, sol);
+    assert_eq!(external[1].market, Market::Spot);
+    assert_eq!(external[1].signed_qty, 12.0);
+    let rendered = render_command_output(
+        &AppCommand::RefreshAuthoritativeState,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("external (2): ETHUSDT, SOLUSDT - adopt with /adopt"));
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::AdoptPosition {
+                instrument: sol.clone(),
+                entry_price: 140.0,
+            },
+        )
+        .expect("spot holding should be adopted");
+    runtime
+        .run(
+            &mut app,
+            AppCommand::AdoptPosition {
+                instrument: eth.clone(),
+                entry_price: 3100.0,
+            },
+        )
+        .expect("futures position should be adopted");
+    let error = runtime
+        .run(
+            &mut app,
+            AppCommand::AdoptPosition {
+                instrument: Instrument::new("BTCUSDT"),
+                entry_price: 60000.0,
+            },
+        )
+        .expect_err("nothing to adopt on BTCUSDT");
+    assert_eq!(
+        error.to_string(),
+        "sync error: no external position to adopt for BTCUSDT"
+    );
+
+    runtime
+        .run(&mut app, AppCommand::Portfolio(PortfolioView::Positions))
+        .expect("refresh keeps the adoptions");
+    let positions = &app.portfolio_store.snapshot.positions;
+    assert_eq!(positions[&sol].market, Market::Spot);
+    assert_eq!(positions[&sol].signed_qty, 12.0);
+    assert_eq!(positions[&sol].entry_price, Some(140.0));
+    assert_eq!(positions[&eth].entry_price, Some(3100.0));
+    assert_eq!(
+        app.portfolio_store
+            .entry_stats
+            .get(&eth)
+            .expect("entry stats restart from the adopted entry")
+            .vwap_entry(),
+        3100.0
+    );
+    let rendered = render_command_output(
+        &AppCommand::Portfolio(PortfolioView::Positions),
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("SOLUSDT market=SPOT side=Buy qty=12.00000000 entry=140.00000000"));
+    assert!(rendered.contains("tag=manual"));
+
+    runtime
+        .run(&mut app, AppCommand::ExternalPositions)
+        .expect("listing should succeed");
+    let rendered = render_command_output(
+        &AppCommand::ExternalPositions,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("external positions (0)"));
+    assert!(rendered.contains("adopted (2)"));
+    assert!(rendered.contains("ETHUSDT market=Futures entry=3100.00000000 tag=manual"));
+}
+
+#[test]
+fn app_runtime_inactivity_flattens_and_blocks_entries_until_resumed() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(sample_snapshot());
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    app.inactivity.timeout_secs = Some(600);
+    let now = Utc::now();
+
+    assert!(!runtime.check_inactivity(&mut app, now + Duration::seconds(599)));
+    assert!(runtime.check_inactivity(&mut app, now + Duration::seconds(601)));
+    assert!(
+        !runtime.check_inactivity(&mut app, now + Duration::seconds(1200)),
+        "the guard fires once until resumed"
+    );
+    assert_eq!(app.exchange.close_requests().len(), 1);
+    assert!(app.execution.entries_disabled);
+    let flattened = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.safety.inactivity_flattened")
+        .expect("flatten logged");
+    assert_eq!(flattened.payload["close_all"]["submitted"], 1);
+    assert!(render_inactivity_output(&app.event_log).contains("re-enable with /inactivity resume"));
+
+    let entry = AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+        instrument: instrument.clone(),
+        target: Exposure::new(0.5).expect("bounded exposure"),
+        order_type: OrderType::Market,
+        source: CommandSource::User,
+    });
+    let orders = |app: &AppBootstrap<FakeExchange>| {
+        app.exchange.close_requests().len() + app.exchange.submit_requests().len()
+    };
+    let error = runtime
+        .run(&mut app, entry.clone())
+        .expect_err("entries are disabled");
+    assert!(error.to_string().contains("entries disabled"));
+    assert_eq!(orders(&app), 1);
+
+    runtime
+        .run(&mut app, AppCommand::ResumeEntries)
+        .expect("resume should succeed");
+    assert!(!app.execution.entries_disabled);
+    runtime
+        .run(&mut app, entry)
+        .expect("entries are allowed again");
+    assert_eq!(orders(&app), 2);
+}
+
+#[test]
+fn app_runtime_symbol_halt_pauses_watches_flattens_and_holds_entries() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(sample_snapshot());
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
+    exchange.set_symbol_status(
+        instrument.clone(),
+        Market::Futures,
+        SymbolTradingStatus::Trading,
+    );
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.recorder_coordination = RecorderCoordination::new(unique_test_dir("halted-watch"));
+    app.symbol_status.config.flatten = true;
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    runtime
+        .run(
+            &mut app,
+            AppCommand::Strategy(StrategyCommand::Start {
+                template: StrategyTemplate::LiquidationBreakdownShort,
+                instrument: instrument.clone(),
+                config: StrategyStartConfig {
+                    risk_pct: 0.005,
+                    win_rate: 0.8,
+                    r_multiple: 1.5,
+                    max_entry_slippage_pct: 0.001,
+                    seed_capital_usdt: None,
+                    ramp: None,
+                    adapt: None,
+                    script: None,
+                },
+            }),
+        )
+        .expect("strategy start should succeed");
+    let now = Utc::now();
+
+    assert!(
+        !runtime.check_symbol_status(&mut app, now),
+        "a trading symbol changes nothing"
+    );
+    app.exchange.set_symbol_status(
+        instrument.clone(),
+        Market::Futures,
+        SymbolTradingStatus::Halted("BREAK".to_string()),
+    );
+    assert!(
+        !runtime.check_symbol_status(&mut app, now + Duration::seconds(60)),
+        "not due before the check interval"
+    );
+    let events_before = app.event_log.records.len();
+    assert!(runtime.check_symbol_status(&mut app, now + Duration::seconds(300)));
+
+    let watch = app
+        .strategy_store
+        .get(BinanceMode::Demo, 1)
+        .expect("watch stored");
+    assert_eq!(watch.state, StrategyWatchState::Paused);
+    assert_eq!(app.exchange.close_requests().len(), 1);
+    let rendered =
+        render_symbol_status(&app.event_log, events_before).expect("the halt is announced");
+    assert!(rendered.contains("symbol halted BTCUSDT status=BREAK (entries held)"));
+    assert!(rendered.contains("strategy #1 paused: BTCUSDT halted status=BREAK"));
+    assert!(rendered.contains("symbol flattened BTCUSDT"));
+
+    let entry = AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+        instrument: instrument.clone(),
+        target: Exposure::new(0.5).expect("bounded exposure"),
+        order_type: OrderType::Market,
+        source: CommandSource::User,
+    });
+    let error = runtime
+        .run(&mut app, entry)
+        .expect_err("entries on a halted symbol are refused");
+    assert!(error.to_string().contains("BTCUSDT is not trading (BREAK)"));
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    assert_eq!(
+        app.strategy_store
+            .get(BinanceMode::Demo, 1)
+            .expect("watch stored")
+            .state,
+        StrategyWatchState::Paused,
+        "a refresh keeps the watch paused while the halt lasts"
+    );
+
+    app.symbol_status.last_checked_at = Some(now + Duration::seconds(300));
+    runtime.ingest_news(
+        &mut app,
+        Ok(vec![NewsHeadline {
+            id: "1".to_string(),
+            title: "Binance Will Delist BTC Margin Pairs".to_string(),
+            link: None,
+            published_at: None,
+        }]),
+        now + Duration::seconds(310),
+    );
+    runtime.ingest_news(
+        &mut app,
+        Ok(vec![
+            NewsHeadline {
+                id: "1".to_string(),
+                title: "Binance Will Delist BTC Margin Pairs".to_string(),
+                link: None,
+                published_at: None,
+            },
+            NewsHeadline {
+                id: "2".to_string(),
+                title: "Binance Will Halt BTC Deposits".to_string(),
+                link: None,
+                published_at: None,
+            },
+        ]),
+        now + Duration::seconds(320),
+    );
+    assert!(
+        app.symbol_status.is_due(now + Duration::seconds(320)),
+        "a halt notice for a traded asset moves the check up"
+    );
+
+    app.exchange.set_symbol_status(
+        instrument.clone(),
+        Market::Futures,
+        SymbolTradingStatus::Trading,
+    );
+    let events_before = app.event_log.records.len();
+    assert!(runtime.check_symbol_status(&mut app, now + Duration::seconds(320)));
+    assert!(app.execution.halted_symbols.is_empty());
+    assert_eq!(
+        app.strategy_store
+            .get(BinanceMode::Demo, 1)
+            .expect("watch stored")
+            .state,
+        StrategyWatchState::Armed
+    );
+    assert!(render_symbol_status(&app.event_log, events_before)
+        .expect("the resume is announced")
+        .contains("symbol trading again BTCUSDT (was BREAK)"));
+}
+
+#[test]
+fn news_headlines_flag_traded_assets_and_blackout_holds_entries() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(sample_snapshot());
+    exchange.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    exchange.set_last_price(instrument.clone(), Market::Futures, 50000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.news.config = NewsFeedConfig {
+        url: Some("https://example.com/feed.xml".to_string()),
+        ..NewsFeedConfig::default()
+    };
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    let headline = |id: &str, title: &str| NewsHeadline {
+        id: id.to_string(),
+        title: title.to_string(),
+        link: None,
+        published_at: None,
+    };
+    let now = Utc::now();
+
+    runtime.ingest_news(
+        &mut app,
+        Ok(vec![headline("1", "Bitcoin steady as BTC volumes thin")]),
+        now,
+    );
+    let events_before = app.event_log.records.len();
+    runtime.ingest_news(
+        &mut app,
+        Ok(vec![
+            headline("1", "Bitcoin steady as BTC volumes thin"),
+            headline("2", "Binance Will Delist BTC Margin Pairs"),
+            headline("3", "DOGE community vote opens"),
+        ]),
+        now + Duration::seconds(300),
+    );
+    assert!(!app.news.is_due(now + Duration::seconds(301)));
+    assert_eq!(app.news.headlines().count(), 3);
+    let announced = render_news_headlines(&app.event_log, events_before)
+        .expect("the delisting headline is announced");
+    assert!(announced.contains("news MAJOR Binance Will Delist [BTC] Margin Pairs"));
+    assert!(announced.contains("ctrl+b"));
+    assert!(
+        !announced.contains("Bitcoin steady"),
+        "the first poll only fills the pane"
+    );
+    assert!(
+        !announced.contains("DOGE"),
+        "untraded, non-major headlines stay in the pane"
+    );
+
+    runtime
+        .run(&mut app, AppCommand::News(NewsCommand::Show))
+        .expect("news should list");
+    let pane = render_command_output(
+        &AppCommand::News(NewsCommand::Show),
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(pane.contains("! Binance Will Delist [BTC] Margin Pairs"));
+    assert!(pane.contains("blackout=off"));
+
+    runtime
+        .run(
+            &mut app,
+            AppCommand::News(NewsCommand::Blackout { minutes: Some(15) }),
+        )
+        .expect("blackout should start");
+    assert!(render_news_output(&app.event_log).contains("new entries held for 15m"));
+    let entry = AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+        instrument: instrument.clone(),
+        target: Exposure::new(0.5).expect("bounded exposure"),
+        order_type: OrderType::Market,
+        source: CommandSource::User,
+    });
+    let orders = |app: &AppBootstrap<FakeExchange>| {
+        app.exchange.close_requests().len() + app.exchange.submit_requests().len()
+    };
+    let error = runtime
+        .run(&mut app, entry.clone())
+        .expect_err("entries are blacked out");
+    assert!(error.to_string().contains("blacked out until"));
+    assert_eq!(orders(&app), 0);
+
+    runtime
+        .run(&mut app, AppCommand::News(NewsCommand::LiftBlackout))
+        .expect("lift should succeed");
+    assert!(app.execution.entries_blackout_until.is_none());
+    runtime
+        .run(&mut app, entry)
+        .expect("entries are allowed again");
+    assert_eq!(orders(&app), 1);
+}
+
+#[test]
+fn rebuild_events_replays_the_event_file_and_summarizes_it() {
+    let dir = unique_test_dir("event-rebuild");
+    std::fs::create_dir_all(&dir).expect("create dir");
+    let path = dir.join("operator-events.jsonl");
+    std::fs::write(
+        &path,
+        [
+            r#"{"ts":"2026-10-01T09:00:00+00:00","kind":"app.portfolio.refreshed","payload":{"positions":1}}"#,
+            r#"{"v":1,"ts":"2026-10-01T09:05:00+00:00","kind":"app.execution.completed","payload":{"instrument":"BTCUSDT","outcome_kind":"submitted"}}"#,
+            r#"{"v":1,"ts":"2026-10-01T09:06:00+00:00","kind":"app.execution.deferred_failed","payload":{"instrument":"ETHUSDT"}}"#,
+            r#"{"v":9,"ts":"2026-10-01T09:07:00+00:00","kind":"app.execution.completed","payload":{}}"#,
+            "not json",
+        ]
+        .join("\n"),
+    )
+    .expect("write event file");
+    let exchange = FakeExchange::new(sample_snapshot());
+    exchange.set_last_price(Instrument::new("BTCUSDT"), Market::Futures, 50000.0);
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.event_log.routing = EventRouting::parse("strategy=memory");
+    let mut runtime = AppRuntime::default();
+    runtime
+        .run(&mut app, AppCommand::RefreshAuthoritativeState)
+        .expect("refresh should succeed");
+    log(&mut app.event_log, "app.strategy.checked", json!({}));
+
+    let command = AppCommand::RebuildEvents {
+        path: Some(path.clone()),
+        domains: BTreeSet::from(["execution".to_string(), "strategy".to_string()]),
+    };
+    runtime
+        .run(&mut app, command.clone())
+        .expect("rebuild should succeed");
+
+    let kinds = app
+        .event_log
+        .records
+        .iter()
+        .map(|record| record.kind.as_str())
+        .collect::<Vec<_>>();
+    // The live refresh is outside the filter and the strategy tick never
+    // reached the file, so both stay behind the replayed history.
+    assert_eq!(
+        kinds,
+        vec![
+            "app.execution.completed",
+            "app.execution.deferred_failed",
+            "app.portfolio.refreshed",
+            "app.strategy.checked",
+            "app.events.rebuilt",
+        ]
+    );
+    let payload = &app.event_log.records[4].payload;
+    assert_eq!(payload["events"], 2);
+    assert_eq!(payload["skipped"], 2);
+    assert_eq!(payload["kept_in_memory"], 2);
+    assert_eq!(payload["errors"], 1);
+    assert_eq!(payload["versions"]["v1"], 2);
+    assert_eq!(payload["outcomes"]["submitted"], 1);
+    assert_eq!(payload["first_ts"], "2026-10-01T09:05:00+00:00");
+
+    let rendered = render_command_output(
+        &command,
+        &app.portfolio_store,
+        &app.price_store,
+        &app.event_log,
+        &app.strategy_store,
+        app.mode,
+        &app.execution.history,
+    );
+    assert!(rendered.contains("events=2 skipped=2 kept_in_memory=2 errors=1"));
+    assert!(rendered.contains("domain_filter=execution,strategy"));
+    assert!(rendered.contains("domains execution=2"));
+    assert!(rendered.contains("instruments BTCUSDT, ETHUSDT"));
+
+    let missing = AppCommand::RebuildEvents {
+        path: Some(dir.join("missing.jsonl")),
+        domains: BTreeSet::new(),
+    };
+    assert!(runtime.run(&mut app, missing).is_err());
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn api_permission_probe_degrades_spot_only_and_read_only_keys() {
+    let instrument = Instrument::new("BTCUSDT");
+    let exchange = FakeExchange::new(sample_snapshot());
+    for market in [Market::Futures, Market::Spot] {
+        exchange.set_symbol_rules(
+            instrument.clone(),
+            market,
+            SymbolRules {
+                min_qty: 0.001,
+                max_qty: 100.0,
+                step_size: 0.001,
+                tick_size: 0.01,
+            },
+        );
+        exchange.set_last_price(instrument.clone(), market, 50000.0);
+    }
+    let spot_only = ApiPermissions {
+        reading: true,
+        spot_trading: true,
+        futures_trading: false,
+        options_trading: false,
+    };
+    exchange.set_api_permissions(Some(spot_only));
+    let mut app = AppBootstrap::new(exchange, PortfolioStateStore::default());
+    app.portfolio_store
+        .refresh_from_exchange(&app.exchange)
+        .expect("seed snapshot");
+
+    assert_eq!(app.probe_api_permissions(), Some(spot_only));
+    assert_eq!(app.execution.api_permissions, Some(spot_only));
+    let probed = app
+        .event_log
+        .records
+        .iter()
+        .find(|record| record.kind == "app.account.permissions_probed")
+        .expect("probe logged");
+    assert_eq!(probed.payload["permissions"], "spot");
+
+    let mut runtime = AppRuntime::default();
+    let error = runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::CloseSymbol {
+                instrument: instrument.clone(),
+                source: CommandSource::User,
+            }),
+        )
+        .expect_err("futures close needs futures permission");
+    assert!(error.to_string().contains("no Futures trading permission"));
+    assert!(app.exchange.close_requests().is_empty());
+
+    app.exchange.set_api_permissions(Some(ApiPermissions {
+        spot_trading: false,
+        ..spot_only
+    }));
+    app.probe_api_permissions();
+    let error = runtime
+        .run(
+            &mut app,
+            AppCommand::Execution(ExecutionCommand::SetTargetExposure {
+                instrument,
+                target: Exposure::new(0.5).expect("bounded exposure"),
+                order_type: OrderType::Market,
+                source: CommandSource::User,
+            }),
+        )
+        .expect_err("read-only keys cannot order");
+    assert!(error.to_string().contains("read-only"));
+    assert!(app.exchange.submit_requests().is_empty());
+}
@@ -67,6 +67,15 @@ impl ExchangeFacade for AuthFailExchange {
     ) -> Result<SubmitOrderAccepted, Self::Error> {
         Err(ExchangeError::UnsupportedMarketOperation)
     }
+
+    fn cancel_order(
+        &self,
+        _instrument: &Instrument,
+        _market: Market,
+        _client_order_id: &str,
+    ) -> Result<(), Self::Error> {
+        Err(ExchangeError::UnsupportedMarketOperation)
+    }
 }
 
 #[test]
@@ -1,4 +1,8 @@
+use sandbox_quant::error::app_error::AppError;
+use sandbox_quant::error::binance_error_code::BinanceErrorCode;
+use sandbox_quant::error::error_class::ErrorClass;
 use sandbox_quant::error::exchange_error::ExchangeError;
+use sandbox_quant::error::execution_error::ExecutionError;
 use sandbox_quant::exchange::binance::auth::BinanceAuth;
 use sandbox_quant::exchange::binance::client::map_binance_http_error;
 
@@ -69,3 +73,66 @@ fn binance_http_error_maps_rate_limit_and_remote_rejects() {
         }
     );
 }
+
+#[test]
+fn binance_http_error_codes_classify_into_retry_fatal_and_actionable() {
+    let reject_with = |code: i64, msg: &str| {
+        map_binance_http_error(
+            400,
+            &format!(r#"{{"code":{code},"msg":"{msg}"}}"#),
+            "/fapi/v1/order",
+        )
+    };
+    let reject = |code: i64| reject_with(code, "x");
+
+    let insufficient = reject_with(
+        -2010,
+        "Account has insufficient balance for requested action.",
+    );
+    assert_eq!(
+        insufficient.binance_code(),
+        Some(BinanceErrorCode::NewOrderRejected)
+    );
+    assert_eq!(insufficient.class(), ErrorClass::UserActionable);
+    assert_eq!(
+        insufficient.hint(),
+        Some("insufficient balance for this order")
+    );
+    assert_eq!(
+        reject_with(-2010, "Order would immediately match and take.").hint(),
+        Some("post-only order would take liquidity; move the price away from the book")
+    );
+    assert_eq!(
+        reject(-2010).hint(),
+        Some("new order rejected; see the exchange message")
+    );
+    assert_eq!(reject(-1013).class(), ErrorClass::UserActionable);
+    assert_eq!(reject(-1007).class(), ErrorClass::Retryable);
+    assert_eq!(reject(-1022).class(), ErrorClass::Fatal);
+    assert_eq!(reject(-9999).binance_code(), None);
+    assert_eq!(reject(-9999).hint(), None);
+
+    let skew = reject(-1021);
+    assert_eq!(skew, ExchangeError::InvalidTimestamp);
+    assert!(skew.hint().expect("clock hint").starts_with("clock skew"));
+
+    let throttled = map_binance_http_error(429, r#"{"code":-1003,"msg":"x"}"#, "/fapi/v1/order");
+    assert_eq!(throttled.class(), ErrorClass::Retryable);
+    assert_eq!(ExchangeError::NetworkTimeout.class(), ErrorClass::Retryable);
+    assert_eq!(
+        ExchangeError::MissingConfiguration("BINANCE_API_KEY").class(),
+        ErrorClass::Fatal
+    );
+}
+
+#[test]
+fn app_error_exposes_hint_from_wrapped_exchange_error() {
+    let error = AppError::from(ExecutionError::SubmitFailed(ExchangeError::RemoteReject {
+        code: -2019,
+        message: "Margin is insufficient.".to_string(),
+    }));
+
+    assert_eq!(error.class(), Some(ErrorClass::UserActionable));
+    assert_eq!(error.hint(), Some("insufficient margin for this order"));
+    assert_eq!(AppError::from(ExecutionError::NoOpenPosition).hint(), None);
+}
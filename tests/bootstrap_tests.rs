@@ -1,10 +1,17 @@
 use sandbox_quant::app::bootstrap::{AppBootstrap, BinanceEnvConfig, BinanceMode};
+use sandbox_quant::app::commands::AppCommand;
+use sandbox_quant::app::config_reload::RuntimeConfig;
+use sandbox_quant::app::runtime::AppRuntime;
 use sandbox_quant::error::exchange_error::ExchangeError;
+use sandbox_quant::exchange::fake::FakeExchange;
+use sandbox_quant::exchange::types::AuthoritativeSnapshot;
 use sandbox_quant::portfolio::store::PortfolioStateStore;
+use sandbox_quant::storage::event_log::log;
+use sandbox_quant::ui::locale::UiLocale;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::{Mutex, OnceLock};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 fn env_lock() -> &'static Mutex<()> {
     static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
@@ -177,3 +184,225 @@ fn binance_env_config_reads_dotenv_file() {
         assert_eq!(config.secret_key, "dotenv-real-secret");
     });
 }
+
+#[test]
+fn rotated_dotenv_keys_rebuild_the_client_without_a_restart() {
+    let _guard = env_lock().lock().unwrap_or_else(|error| error.into_inner());
+    with_isolated_cwd("key-rotation", || {
+        clear_binance_env();
+        fs::write(
+            ".env",
+            "BINANCE_MODE=demo\nBINANCE_DEMO_API_KEY=first-key-0001\nBINANCE_DEMO_SECRET_KEY=first-secret\n",
+        )
+        .expect("write .env");
+        let mut app =
+            AppBootstrap::from_env(PortfolioStateStore::default()).expect("bootstrap from .env");
+        assert!(!app.reload_credentials());
+
+        fs::write(
+            ".env",
+            "BINANCE_MODE=demo\nBINANCE_DEMO_API_KEY=second-key-0002\nBINANCE_DEMO_SECRET_KEY=second-secret\n",
+        )
+        .expect("rotate .env");
+        assert!(!app.check_credentials(Instant::now()), "not due yet");
+        assert!(app.reload_credentials());
+        let record = app.event_log.records.last().expect("rotation event");
+        assert_eq!(record.kind, "app.config.credentials_rotated");
+        assert_eq!(record.payload["api_key"], "****0002");
+        assert!(!app.reload_credentials());
+
+        fs::write(".env", "BINANCE_MODE=demo\n").expect("drop keys from .env");
+        clear_binance_env();
+        assert!(!app.reload_credentials());
+        assert!(!app.reload_credentials());
+        let failures = app
+            .event_log
+            .records
+            .iter()
+            .filter(|record| record.kind == "app.config.credentials_rotation_failed")
+            .count();
+        assert_eq!(failures, 1);
+        clear_binance_env();
+    });
+}
+
+#[test]
+fn rotation_keeps_exported_keys_over_a_disagreeing_dotenv() {
+    let _guard = env_lock().lock().unwrap_or_else(|error| error.into_inner());
+    with_isolated_cwd("key-rotation-env", || {
+        clear_binance_env();
+        unsafe {
+            std::env::set_var("BINANCE_DEMO_API_KEY", "shell-key-0003");
+            std::env::set_var("BINANCE_DEMO_SECRET_KEY", "shell-secret");
+        }
+        fs::write(
+            ".env",
+            "BINANCE_MODE=demo\nBINANCE_DEMO_API_KEY=stale-key-0001\nBINANCE_DEMO_SECRET_KEY=stale-secret\n",
+        )
+        .expect("write .env");
+        let mut app =
+            AppBootstrap::from_env(PortfolioStateStore::default()).expect("bootstrap from env");
+        assert!(!app.reload_credentials());
+
+        fs::write(
+            ".env",
+            "BINANCE_MODE=demo\nBINANCE_DEMO_API_KEY=stale-key-0002\nBINANCE_DEMO_SECRET_KEY=stale-secret\n",
+        )
+        .expect("rotate .env");
+        assert!(!app.reload_credentials(), "the exported key still wins");
+        assert!(app
+            .event_log
+            .records
+            .iter()
+            .all(|record| record.kind != "app.config.credentials_rotated"));
+
+        unsafe {
+            std::env::set_var("BINANCE_DEMO_API_KEY", "shell-key-0004");
+        }
+        assert!(app.reload_credentials());
+        let record = app.event_log.records.last().expect("rotation event");
+        assert_eq!(record.kind, "app.config.credentials_rotated");
+        assert_eq!(record.payload["api_key"], "****0004");
+        clear_binance_env();
+    });
+}
+
+#[test]
+fn reload_config_applies_dotenv_changes_and_logs_diff() {
+    let _guard = env_lock().lock().unwrap_or_else(|error| error.into_inner());
+    with_isolated_cwd("reload-config", || {
+        fs::write(
+            ".env",
+            "SANDBOX_QUANT_ASSET_EXPOSURE_CAPS=BTC=0.25,ETH=0.1\nSANDBOX_QUANT_UI_LOCALE=ko\n\
+             SANDBOX_QUANT_TAKER_FEE_RATE=0.0004\nSANDBOX_QUANT_OVERTRADING=3:600\n",
+        )
+        .expect("write .env");
+        let mut app = AppBootstrap::new(
+            FakeExchange::new(AuthoritativeSnapshot::default()),
+            PortfolioStateStore::default(),
+        );
+        app.execution
+            .asset_exposure_caps
+            .insert("BTC".to_string(), 0.5);
+
+        AppRuntime::default()
+            .run(&mut app, AppCommand::ReloadConfig)
+            .expect("reload should succeed");
+
+        assert!(std::env::var_os("SANDBOX_QUANT_UI_LOCALE").is_none());
+        assert_eq!(app.execution.asset_exposure_caps.get("BTC"), Some(&0.25));
+        assert_eq!(app.execution.asset_exposure_caps.get("ETH"), Some(&0.1));
+        assert_eq!(app.locale, UiLocale::Korean);
+        assert_eq!(app.portfolio_store.entry_stats.taker_fee_rate, 0.0004);
+        assert_eq!(
+            app.strategy_store.overtrading.map(|limit| limit.max_trades),
+            Some(3)
+        );
+        let record = app.event_log.records.last().expect("reload event");
+        assert_eq!(record.kind, "app.config.reloaded");
+        let keys = record.payload["changes"]
+            .as_array()
+            .expect("changes array")
+            .iter()
+            .map(|change| change["key"].as_str().unwrap_or_default().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![
+                "asset_exposure_cap.BTC".to_string(),
+                "asset_exposure_cap.ETH".to_string(),
+                "ui_locale".to_string(),
+                "taker_fee_rate".to_string(),
+                "overtrading".to_string(),
+            ]
+        );
+        assert_eq!(record.payload["changes"][0]["before"], "0.5");
+        assert_eq!(record.payload["changes"][1]["before"], "none");
+    });
+}
+
+#[test]
+fn startup_and_reload_both_read_exported_settings_ahead_of_dotenv() {
+    let _guard = env_lock().lock().unwrap_or_else(|error| error.into_inner());
+    with_isolated_cwd("reload-precedence", || {
+        fs::write(
+            ".env",
+            "SANDBOX_QUANT_UI_LOCALE=ko\nSANDBOX_QUANT_TAKER_FEE_RATE=0.0004\n",
+        )
+        .expect("write .env");
+        unsafe {
+            std::env::set_var("SANDBOX_QUANT_UI_LOCALE", "en");
+        }
+
+        let startup = RuntimeConfig::from_env();
+        assert_eq!(startup.locale, UiLocale::English);
+        assert_eq!(startup.taker_fee_rate, 0.0004);
+
+        let mut app = AppBootstrap::new(
+            FakeExchange::new(AuthoritativeSnapshot::default()),
+            PortfolioStateStore::default(),
+        );
+        startup.clone().apply(&mut app);
+        AppRuntime::default()
+            .run(&mut app, AppCommand::ReloadConfig)
+            .expect("reload should succeed");
+        unsafe {
+            std::env::remove_var("SANDBOX_QUANT_UI_LOCALE");
+        }
+
+        assert_eq!(app.locale, UiLocale::English);
+        assert_eq!(RuntimeConfig::capture(&app), startup);
+        let record = app.event_log.records.last().expect("reload event");
+        assert_eq!(record.kind, "app.config.reloaded");
+        assert_eq!(record.payload["changes"], serde_json::json!([]));
+    });
+}
+
+#[test]
+fn reloaded_event_routes_keep_quiet_domains_out_of_the_event_file() {
+    let _guard = env_lock().lock().unwrap_or_else(|error| error.into_inner());
+    with_isolated_cwd("event-routes", || {
+        fs::write(
+            ".env",
+            "SANDBOX_QUANT_EVENT_ROUTES=market_data=memory;execution=file+notifier:warn\n",
+        )
+        .expect("write .env");
+        let mut app = AppBootstrap::new(
+            FakeExchange::new(AuthoritativeSnapshot::default()),
+            PortfolioStateStore::default(),
+        );
+
+        AppRuntime::default()
+            .run(&mut app, AppCommand::ReloadConfig)
+            .expect("reload should succeed");
+        unsafe {
+            std::env::remove_var("SANDBOX_QUANT_EVENT_ROUTES");
+        }
+        let record = app.event_log.records.last().expect("reload event");
+        assert_eq!(record.payload["changes"][0]["key"], "event_route.execution");
+        assert_eq!(record.payload["changes"][0]["after"], "file+notifier:warn");
+
+        log(
+            &mut app.event_log,
+            "app.market_data.synthetic_tick",
+            serde_json::json!({}),
+        );
+        log(
+            &mut app.event_log,
+            "app.execution.depth_checked",
+            serde_json::json!({}),
+        );
+        log(
+            &mut app.event_log,
+            "app.execution.deferred_failed",
+            serde_json::json!({}),
+        );
+
+        let written = fs::read_to_string("var/operator-events.jsonl").expect("event file");
+        assert!(written.contains("app.config.reloaded"));
+        assert!(written.contains("app.execution.deferred_failed"));
+        assert!(!written.contains("synthetic_tick"));
+        assert!(!written.contains("depth_checked"));
+        assert_eq!(app.event_log.records.len(), 4);
+    });
+}
@@ -1,3 +1,4 @@
+use chrono::{FixedOffset, TimeZone, Utc};
 use sandbox_quant::app::bootstrap::BinanceMode;
 use sandbox_quant::app::cli::{
     complete_shell_input, complete_shell_input_with_description,
@@ -5,17 +6,39 @@ use sandbox_quant::app::cli::{
     parse_shell_input, shell_help_text, ShellInput,
 };
 use sandbox_quant::app::commands::AppCommand;
-use sandbox_quant::app::commands::PortfolioView;
+use sandbox_quant::app::commands::{
+    AlertCommand, BasisCommand, DomAction, DomCommand, NewsCommand, OrderFilter, PortfolioView,
+};
 use sandbox_quant::app::shell::{
     format_completion_line, next_completion_index, previous_completion_index, scroll_lines_needed,
 };
 use sandbox_quant::domain::instrument::Instrument;
+use sandbox_quant::domain::order::OrderStatus;
 use sandbox_quant::domain::order_type::OrderType;
 use sandbox_quant::domain::position::Side;
 use sandbox_quant::execution::command::{CommandSource, ExecutionCommand};
-use sandbox_quant::strategy::command::{StrategyCommand, StrategyStartConfig};
+use sandbox_quant::execution::dom_ladder::DomPrice;
+use sandbox_quant::execution::trailing_stop::TrailDistance;
+use sandbox_quant::market_data::price_alert::PriceAlertAction;
+use sandbox_quant::market_data::synthetic::{parse_synthetic_instruments, register_synthetic_names};
+use sandbox_quant::storage::order_store::{HistoryQuery, TimeBucket};
+use sandbox_quant::strategy::adaptive::AdaptiveTuning;
+use sandbox_quant::strategy::basis::BasisArbConfig;
+use sandbox_quant::strategy::command::{
+    OpenPositionResolution, StrategyCommand, StrategyGroupAction, StrategyStartConfig,
+};
+use sandbox_quant::strategy::filter::{PnlComparison, StrategyFilter, StrategyListQuery};
+use sandbox_quant::strategy::kpi::KpiTargets;
 use sandbox_quant::strategy::model::StrategyTemplate;
-use sandbox_quant::ui::operator_terminal::shell_intro_panel;
+use sandbox_quant::strategy::ramp::SizeRamp;
+use sandbox_quant::terminal::macros::{MacroCommand, ShellMacroStore};
+use sandbox_quant::ui::locale::UiLocale;
+use sandbox_quant::ui::operator_terminal::{operator_prompt, shell_intro_panel};
+use sandbox_quant::ui::theme::UiTheme;
+use sandbox_quant::ui::timezone::DisplayTimeZone;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use unicode_width::UnicodeWidthStr;
 
 #[test]
 fn parse_refresh_command_by_default() {
@@ -36,8 +59,76 @@ fn parse_portfolio_subcommands_and_aliases() {
     );
     assert_eq!(
         parse_app_command(&["orders".to_string()]).expect("orders alias should parse"),
-        AppCommand::Portfolio(PortfolioView::Orders)
+        AppCommand::Portfolio(PortfolioView::Orders(OrderFilter::default()))
+    );
+}
+
+#[test]
+fn parse_orders_command_with_side_status_and_tag_filters() {
+    assert_eq!(
+        parse_app_command(&[
+            "orders".to_string(),
+            "side=sell".to_string(),
+            "status=submitted".to_string(),
+            "tag=grid".to_string(),
+        ])
+        .expect("filtered orders should parse"),
+        AppCommand::Portfolio(PortfolioView::Orders(OrderFilter {
+            side: Some(Side::Sell),
+            status: Some(OrderStatus::Submitted),
+            tag: Some("grid".to_string()),
+        }))
+    );
+    assert!(parse_app_command(&["orders".to_string(), "side=long".to_string()]).is_err());
+}
+
+#[test]
+fn order_filter_hotkey_cycles_render_parseable_orders_lines() {
+    let tags = BTreeSet::from(["oco".to_string(), "strategy".to_string()]);
+    let mut filter = OrderFilter::default();
+    filter.cycle_side();
+    filter.cycle_side();
+    filter.cycle_status();
+    filter.cycle_status();
+    filter.cycle_tag(&tags);
+    filter.cycle_tag(&tags);
+
+    let line = filter.orders_line();
+    assert_eq!(line, "/orders side=sell status=submitted tag=strategy");
+    assert_eq!(
+        parse_shell_input(&line).expect("hotkey line should parse"),
+        ShellInput::Command(Box::new(AppCommand::Portfolio(PortfolioView::Orders(
+            filter.clone()
+        ))))
+    );
+
+    filter.cycle_side();
+    filter.cycle_tag(&tags);
+    assert_eq!(filter.orders_line(), "/orders status=submitted");
+}
+
+#[test]
+fn parse_history_roi_with_bucket_and_filters() {
+    assert_eq!(
+        parse_shell_input("/history roi day instrument=btc tag=strategy:3")
+            .expect("history roi should parse"),
+        ShellInput::Command(Box::new(AppCommand::Portfolio(PortfolioView::HistoryRoi {
+            bucket: TimeBucket::Day,
+            query: HistoryQuery {
+                instrument: Some(Instrument::new("BTCUSDT")),
+                tag: Some("strategy:3".to_string()),
+            },
+        })))
     );
+    assert_eq!(
+        parse_shell_input("/portfolio history roi month").expect("portfolio history roi"),
+        ShellInput::Command(Box::new(AppCommand::Portfolio(PortfolioView::HistoryRoi {
+            bucket: TimeBucket::Month,
+            query: HistoryQuery::default(),
+        })))
+    );
+    assert!(parse_shell_input("/history roi week").is_err());
+    assert!(parse_shell_input("/history roi hour side=buy").is_err());
 }
 
 #[test]
@@ -213,6 +304,10 @@ fn parse_strategy_start_command() {
                 win_rate: 0.8,
                 r_multiple: 1.5,
                 max_entry_slippage_pct: 0.001,
+                seed_capital_usdt: None,
+                ramp: None,
+                adapt: None,
+                script: None,
             },
         })
     );
@@ -238,11 +333,112 @@ fn parse_strategy_start_command_uses_defaults_when_flags_are_omitted() {
                 win_rate: 0.8,
                 r_multiple: 1.5,
                 max_entry_slippage_pct: 0.001,
+                seed_capital_usdt: None,
+                ramp: None,
+                adapt: None,
+                script: None,
             },
         })
     );
 }
 
+#[test]
+fn parse_strategy_start_command_accepts_seed_capital() {
+    let command = parse_app_command(&[
+        "strategy".to_string(),
+        "start".to_string(),
+        "liquidation-breakdown-short".to_string(),
+        "btc".to_string(),
+        "--capital".to_string(),
+        "1000".to_string(),
+    ])
+    .expect("strategy start should parse with capital");
+
+    let AppCommand::Strategy(StrategyCommand::Start { config, .. }) = command else {
+        panic!("expected strategy start");
+    };
+    assert_eq!(config.seed_capital_usdt, Some(1000.0));
+    assert!(parse_app_command(&[
+        "strategy".to_string(),
+        "start".to_string(),
+        "liquidation-breakdown-short".to_string(),
+        "btc".to_string(),
+        "--capital".to_string(),
+        "0".to_string(),
+    ])
+    .is_err());
+}
+
+#[test]
+fn parse_strategy_start_command_accepts_size_ramp() {
+    let start = |flags: &[&str]| {
+        let args = ["strategy", "start", "liquidation-breakdown-short", "btc"]
+            .iter()
+            .chain(flags)
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>();
+        parse_app_command(&args)
+    };
+
+    let Ok(AppCommand::Strategy(StrategyCommand::Start { config, .. })) =
+        start(&["--ramp-start", "0.1", "--ramp-trades", "4"])
+    else {
+        panic!("expected strategy start");
+    };
+    assert_eq!(config.ramp, Some(SizeRamp::new(0.1, 4)));
+    let Ok(AppCommand::Strategy(StrategyCommand::Start { config, .. })) =
+        start(&["--ramp-trades", "3"])
+    else {
+        panic!("expected strategy start");
+    };
+    assert_eq!(config.ramp, Some(SizeRamp::new(0.25, 3)));
+    let Ok(AppCommand::Strategy(StrategyCommand::Start { config, .. })) =
+        start(&["--ramp-start", "0.5", "--ramp-trades", "0"])
+    else {
+        panic!("expected strategy start");
+    };
+    assert_eq!(config.ramp, None);
+    assert!(start(&["--ramp-start", "1"]).is_err());
+    assert!(start(&["--ramp-trades", "2.5"]).is_err());
+}
+
+#[test]
+fn parse_strategy_start_command_accepts_adaptive_tuning() {
+    let start = |flags: &[&str]| {
+        let args = ["strategy", "start", "liquidation-breakdown-short", "btc"]
+            .iter()
+            .chain(flags)
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>();
+        parse_app_command(&args)
+    };
+
+    let Ok(AppCommand::Strategy(StrategyCommand::Start { config, .. })) =
+        start(&["--adapt-trades", "20", "--adapt-step", "0.05"])
+    else {
+        panic!("expected strategy start");
+    };
+    assert_eq!(config.adapt, Some(AdaptiveTuning::new(20, 0.05, 0.5)));
+    let Ok(AppCommand::Strategy(StrategyCommand::Start { config, .. })) =
+        start(&["--adapt-trades", "0"])
+    else {
+        panic!("expected strategy start");
+    };
+    assert_eq!(config.adapt, None);
+    assert!(start(&["--adapt-step", "1"]).is_err());
+    assert!(start(&["--adapt-drift", "0"]).is_err());
+    assert_eq!(
+        parse_app_command(&[
+            "strategy".to_string(),
+            "rollback".to_string(),
+            "3".to_string(),
+        ]),
+        Ok(AppCommand::Strategy(StrategyCommand::Rollback {
+            watch_id: 3
+        }))
+    );
+}
+
 #[test]
 fn parse_strategy_surface_commands() {
     assert_eq!(
@@ -253,8 +449,31 @@ fn parse_strategy_surface_commands() {
     assert_eq!(
         parse_app_command(&["strategy".to_string(), "list".to_string()])
             .expect("list should parse"),
-        AppCommand::Strategy(StrategyCommand::List)
+        AppCommand::Strategy(StrategyCommand::List {
+            query: StrategyListQuery::default(),
+        })
+    );
+    assert_eq!(
+        parse_app_command(&[
+            "strategy".to_string(),
+            "list".to_string(),
+            "BTC".to_string(),
+            "page=3".to_string(),
+        ])
+        .expect("list search should parse"),
+        AppCommand::Strategy(StrategyCommand::List {
+            query: StrategyListQuery {
+                search: Some("btc".to_string()),
+                page: 3,
+            },
+        })
     );
+    assert!(parse_app_command(&[
+        "strategy".to_string(),
+        "list".to_string(),
+        "page=0".to_string(),
+    ])
+    .is_err());
     assert_eq!(
         parse_app_command(&["strategy".to_string(), "show".to_string(), "7".to_string()])
             .expect("show should parse"),
@@ -263,13 +482,256 @@ fn parse_strategy_surface_commands() {
     assert_eq!(
         parse_app_command(&["strategy".to_string(), "stop".to_string(), "7".to_string()])
             .expect("stop should parse"),
-        AppCommand::Strategy(StrategyCommand::Stop { watch_id: 7 })
+        AppCommand::Strategy(StrategyCommand::Stop {
+            watch_id: 7,
+            resolution: None,
+        })
+    );
+    assert_eq!(
+        parse_app_command(&[
+            "strategy".to_string(),
+            "stop".to_string(),
+            "7".to_string(),
+            "flatten".to_string(),
+        ])
+        .expect("stop with resolution should parse"),
+        AppCommand::Strategy(StrategyCommand::Stop {
+            watch_id: 7,
+            resolution: Some(OpenPositionResolution::Flatten),
+        })
+    );
+    assert!(parse_app_command(&[
+        "strategy".to_string(),
+        "stop".to_string(),
+        "7".to_string(),
+        "later".to_string(),
+    ])
+    .is_err());
+    assert_eq!(
+        parse_app_command(&[
+            "strategy".to_string(),
+            "funnel".to_string(),
+            "6h".to_string(),
+        ])
+        .expect("funnel should parse"),
+        AppCommand::Strategy(StrategyCommand::Funnel { window_hours: 6 })
+    );
+    assert_eq!(
+        parse_app_command(&["strategy".to_string(), "funnel".to_string()])
+            .expect("funnel should default its window"),
+        AppCommand::Strategy(StrategyCommand::Funnel { window_hours: 24 })
+    );
+    assert_eq!(
+        parse_app_command(&["strategy".to_string(), "plugins".to_string()])
+            .expect("plugins should parse"),
+        AppCommand::Strategy(StrategyCommand::Plugins)
+    );
+    assert!(parse_app_command(&[
+        "strategy".to_string(),
+        "start".to_string(),
+        "plugin-long".to_string(),
+        "BTCUSDT".to_string(),
+    ])
+    .expect_err("plugins cannot be armed")
+    .starts_with("template plugin-long only runs in backtests"));
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    match parse_app_command(&args(
+        "strategy start script-short eth --script strategies/fade.rhai",
+    )) {
+        Ok(AppCommand::Strategy(StrategyCommand::Start {
+            template,
+            instrument,
+            config,
+        })) => {
+            assert_eq!(template, StrategyTemplate::ScriptShort);
+            assert_eq!(instrument, Instrument::new("ETHUSDT"));
+            assert_eq!(
+                config.script.as_deref(),
+                Some(std::path::Path::new("strategies/fade.rhai"))
+            );
+        }
+        other => panic!("script watches should parse: {other:?}"),
+    }
+    assert_eq!(
+        parse_app_command(&args("strategy start script-long btc")),
+        Err("template script-long needs --script <path>".to_string())
+    );
+    assert_eq!(
+        parse_app_command(&args(
+            "strategy start price-sma-cross-long btc --script a.rhai"
+        )),
+        Err("--script only applies to script-long and script-short".to_string())
     );
     assert_eq!(
         parse_app_command(&["strategy".to_string(), "history".to_string()])
             .expect("history should parse"),
-        AppCommand::Strategy(StrategyCommand::History)
+        AppCommand::Strategy(StrategyCommand::History {
+            query: StrategyListQuery::default(),
+        })
+    );
+}
+
+#[test]
+fn parse_strategy_debug_and_trace_commands() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("strategy debug 7 on")).expect("debug on should parse"),
+        AppCommand::Strategy(StrategyCommand::Debug {
+            watch_id: 7,
+            enabled: true,
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("strategy trace 7")).expect("trace should parse"),
+        AppCommand::Strategy(StrategyCommand::Trace {
+            watch_id: 7,
+            dump_path: None,
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("strategy trace 7 dump")).expect("trace dump should parse"),
+        AppCommand::Strategy(StrategyCommand::Trace {
+            watch_id: 7,
+            dump_path: Some("var/strategy-trace-7.jsonl".to_string()),
+        })
+    );
+    assert!(parse_app_command(&args("strategy debug 7 maybe")).is_err());
+}
+
+#[test]
+fn parse_strategy_group_and_bulk_commands() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("strategy group 4 Trend")).expect("group should parse"),
+        AppCommand::Strategy(StrategyCommand::Group {
+            watch_id: 4,
+            group: Some("trend".to_string()),
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("strategy group 4 none")).expect("ungroup should parse"),
+        AppCommand::Strategy(StrategyCommand::Group {
+            watch_id: 4,
+            group: None,
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("strategy bulk experimental delete")).expect("bulk should parse"),
+        AppCommand::Strategy(StrategyCommand::Bulk {
+            group: "experimental".to_string(),
+            action: StrategyGroupAction::Delete,
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("strategy groups")).expect("groups should parse"),
+        AppCommand::Strategy(StrategyCommand::Groups)
+    );
+    assert_eq!(
+        parse_app_command(&args("strategy leaderboard")).expect("leaderboard should parse"),
+        AppCommand::Strategy(StrategyCommand::Leaderboard)
+    );
+    assert!(parse_app_command(&args("strategy group 4 a/b")).is_err());
+    assert!(parse_app_command(&args("strategy bulk trend pause")).is_err());
+}
+
+#[test]
+fn parse_strategy_kpi_targets_and_off() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("strategy kpi 4 win=55% dd=150 trades=3"))
+            .expect("kpi should parse"),
+        AppCommand::Strategy(StrategyCommand::Kpi {
+            watch_id: 4,
+            targets: Some(KpiTargets {
+                min_win_rate: Some(0.55),
+                max_drawdown_usdt: Some(150.0),
+                min_trades_per_week: Some(3.0),
+            }),
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("strategy kpi 4 win=0.6"))
+            .expect("fractional win rate should parse"),
+        AppCommand::Strategy(StrategyCommand::Kpi {
+            watch_id: 4,
+            targets: Some(KpiTargets {
+                min_win_rate: Some(0.6),
+                ..KpiTargets::default()
+            }),
+        })
     );
+    assert_eq!(
+        parse_app_command(&args("strategy kpi 4 off")).expect("off should parse"),
+        AppCommand::Strategy(StrategyCommand::Kpi {
+            watch_id: 4,
+            targets: None,
+        })
+    );
+    assert!(parse_app_command(&args("strategy kpi 4")).is_err());
+    assert!(parse_app_command(&args("strategy kpi 4 win=120%")).is_err());
+    assert!(parse_app_command(&args("strategy kpi 4 dd=0")).is_err());
+    assert!(parse_app_command(&args("strategy kpi 4 sharpe=1")).is_err());
+}
+
+#[test]
+fn parse_strategy_select_filters_with_optional_action_and_confirm() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    let filter = StrategyFilter {
+        kind: Some("sma".to_string()),
+        symbol: Some("BTC*".to_string()),
+        pnl: Some((PnlComparison::Greater, 0.0)),
+        ..StrategyFilter::default()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("strategy select kind=SMA symbol=btc* pnl>0"))
+            .expect("preview should parse"),
+        AppCommand::Strategy(StrategyCommand::Select {
+            filter: filter.clone(),
+            action: None,
+            confirmed: false,
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args(
+            "strategy select kind=sma symbol=BTC* pnl>0 disable confirm"
+        ))
+        .expect("confirmed select should parse"),
+        AppCommand::Strategy(StrategyCommand::Select {
+            filter: filter.clone(),
+            action: Some(StrategyGroupAction::Disable),
+            confirmed: true,
+        })
+    );
+    assert_eq!(filter.to_string(), "kind=sma symbol=BTC* pnl>0");
+    assert!(parse_app_command(&args("strategy select")).is_err());
+    assert!(parse_app_command(&args("strategy select symbol=BTC* confirm")).is_err());
+    assert!(parse_app_command(&args("strategy select symbol>BTC")).is_err());
+    assert!(parse_app_command(&args("strategy select pnl>abc")).is_err());
+    assert!(parse_app_command(&args("strategy select venue=binance")).is_err());
 }
 
 #[test]
@@ -290,9 +752,9 @@ fn parse_shell_input_supports_slash_commands() {
 
     assert_eq!(
         parsed,
-        ShellInput::Command(AppCommand::Execution(ExecutionCommand::CloseAll {
+        ShellInput::Command(Box::new(AppCommand::Execution(ExecutionCommand::CloseAll {
             source: CommandSource::User,
-        }))
+        })))
     );
 }
 
@@ -309,6 +771,78 @@ fn parse_shell_input_supports_help_and_exit() {
     assert!(shell_help_text().contains("/portfolio"));
 }
 
+#[test]
+fn parse_shell_input_supports_macro_commands() {
+    assert_eq!(
+        parse_shell_input("/macro record ETH-Setup").expect("record should parse"),
+        ShellInput::Macro(MacroCommand::Record {
+            name: "eth-setup".to_string()
+        })
+    );
+    assert_eq!(
+        parse_shell_input("/macro stop").expect("stop should parse"),
+        ShellInput::Macro(MacroCommand::Stop)
+    );
+    assert_eq!(
+        parse_shell_input("/macro bind eth-setup 2").expect("bind should parse"),
+        ShellInput::Macro(MacroCommand::Bind {
+            name: "eth-setup".to_string(),
+            slot: 2,
+        })
+    );
+    assert!(parse_shell_input("/macro bind eth-setup 10").is_err());
+    assert!(parse_shell_input("/macro play").is_err());
+    assert!(shell_help_text().contains("/macro"));
+}
+
+#[test]
+fn shell_macro_store_records_binds_and_persists() {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("system time before unix epoch")
+        .as_nanos();
+    let path = std::env::temp_dir()
+        .join(format!("sandbox-quant-macros-{nanos}"))
+        .join("macros.json");
+    let mut store = ShellMacroStore::new(&path);
+
+    store.capture("/positions");
+    assert!(store.finish_recording().is_none());
+    store.start_recording("eth-setup");
+    store.capture("/mode demo");
+    store.capture(" /set-target-exposure ETHUSDT 0.2 ");
+    assert_eq!(store.recording_name(), Some("eth-setup"));
+    assert_eq!(store.finish_recording(), Some(("eth-setup".to_string(), 2)));
+    assert!(store.bind("eth-setup", 2));
+    assert!(!store.bind("missing", 3));
+    store.save().expect("save macros");
+
+    let loaded = ShellMacroStore::load(&path).expect("load macros");
+    assert_eq!(loaded.name_for_hotkey(2), Some("eth-setup"));
+    assert_eq!(
+        loaded.get("eth-setup").expect("macro").lines,
+        vec![
+            "/mode demo".to_string(),
+            "/set-target-exposure ETHUSDT 0.2".to_string(),
+        ]
+    );
+
+    std::fs::write(&path, "{not json").expect("corrupt macros");
+    let error = ShellMacroStore::load(&path).expect_err("corrupt library");
+    assert!(matches!(
+        error,
+        sandbox_quant::error::storage_error::StorageError::ParseFailed { .. }
+    ));
+    let mut unloaded = ShellMacroStore::unloaded(&path);
+    unloaded.start_recording("fresh");
+    unloaded.finish_recording();
+    assert!(unloaded.save().is_err());
+    assert_eq!(
+        std::fs::read_to_string(&path).expect("macros file"),
+        "{not json"
+    );
+}
+
 #[test]
 fn parse_shell_input_supports_mode_switch() {
     assert_eq!(
@@ -421,6 +955,15 @@ fn normalize_instrument_symbol_preserves_option_contract_format() {
     );
 }
 
+#[test]
+fn normalize_instrument_symbol_keeps_only_registered_synthetic_names() {
+    register_synthetic_names(
+        parse_synthetic_instruments("BTC_BASIS=BTCUSDT@futures-BTCUSDT@spot").keys(),
+    );
+    assert_eq!(normalize_instrument_symbol("btc_basis"), "BTC_BASIS");
+    assert_eq!(normalize_instrument_symbol("eth_basis"), "ETH_BASISUSDT");
+}
+
 #[test]
 fn shell_completion_line_marks_selected_item() {
     let line = format_completion_line(&complete_shell_input_with_description("/cl", &[]), 1);
@@ -459,10 +1002,583 @@ fn normalize_instrument_symbol_appends_usdt_for_base_symbol() {
 
 #[test]
 fn shell_intro_panel_contains_version_mode_and_directory() {
-    let panel = shell_intro_panel("demo", "~/project/sandbox-quant");
+    let panel = shell_intro_panel("demo", "~/project/sandbox-quant", UiLocale::English);
 
     assert!(panel.contains("Sandbox Quant"));
     assert!(panel.contains("v"));
     assert!(panel.contains("mode:"));
     assert!(panel.contains("directory: ~/project/sandbox-quant"));
 }
+
+#[test]
+fn shell_intro_panel_uses_korean_bundle_when_selected() {
+    let panel = shell_intro_panel("demo", "~/project/sandbox-quant", UiLocale::Korean);
+
+    assert!(panel.contains("모드:"));
+    assert!(panel.contains("경로:"));
+    assert!(panel.contains("~/project/sandbox-quant"));
+    assert!(panel
+        .lines()
+        .all(|line| line.width() == panel.lines().next().unwrap().width()));
+    assert_eq!(UiLocale::parse("ko-KR"), Some(UiLocale::Korean));
+    assert_eq!(UiLocale::parse("fr"), None);
+}
+
+#[test]
+fn deuteranopia_safe_theme_moves_gains_and_losses_off_red_green() {
+    assert_eq!(
+        UiTheme::parse("deuteranopia"),
+        Some(UiTheme::DeuteranopiaSafe)
+    );
+    assert_eq!(UiTheme::parse("High_Contrast"), Some(UiTheme::HighContrast));
+    assert_eq!(UiTheme::parse("neon"), None);
+
+    let palette = UiTheme::DeuteranopiaSafe.palette();
+    assert!(palette.positive.b > palette.positive.g);
+    assert!(palette.negative.r > palette.negative.b);
+    assert_eq!(palette.chart_theme().bull_candle, palette.positive);
+
+    let prompt = operator_prompt(BinanceMode::Real, "[fresh]", UiTheme::DeuteranopiaSafe);
+    assert!(prompt.contains("38;2;86;180;233"));
+    assert!(prompt.contains("[real]"));
+}
+
+#[test]
+fn display_time_zone_parses_offsets_and_shifts_days() {
+    let seoul = FixedOffset::east_opt(9 * 3_600).expect("offset");
+    assert_eq!(
+        DisplayTimeZone::parse("UTC+9"),
+        Some(DisplayTimeZone::Fixed(seoul))
+    );
+    assert_eq!(
+        DisplayTimeZone::parse("+09:00"),
+        Some(DisplayTimeZone::Fixed(seoul))
+    );
+    assert_eq!(
+        DisplayTimeZone::parse("-0530").map(DisplayTimeZone::label),
+        Some("-05:30".to_string())
+    );
+    assert_eq!(DisplayTimeZone::parse("+00:00"), Some(DisplayTimeZone::Utc));
+    assert_eq!(
+        DisplayTimeZone::parse("local"),
+        Some(DisplayTimeZone::Local)
+    );
+    assert_eq!(DisplayTimeZone::parse("+15"), None);
+    assert_eq!(DisplayTimeZone::parse("Asia/Seoul"), None);
+
+    let zone = DisplayTimeZone::Fixed(seoul);
+    let at = Utc.with_ymd_and_hms(2026, 3, 1, 23, 30, 0).unwrap();
+    assert_eq!(zone.format(at, "%m-%d %H:%M"), "03-02 08:30");
+    assert_eq!(zone.rfc3339(at), "2026-03-02T08:30:00+09:00");
+    assert_eq!(
+        zone.restamp("2026-03-01T23:30:00+00:00"),
+        "2026-03-02T08:30:00+09:00"
+    );
+    assert_eq!(zone.restamp("pending"), "pending");
+    assert_eq!(
+        zone.start_of_day(at),
+        Utc.with_ymd_and_hms(2026, 3, 1, 15, 0, 0).unwrap()
+    );
+    assert_eq!(
+        DisplayTimeZone::Utc.format_ms(at.timestamp_millis(), "%H:%M"),
+        "23:30"
+    );
+}
+
+#[test]
+fn parse_alert_commands_with_actions() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("alert add btc 70000")).expect("alert add should parse"),
+        AppCommand::Alert(AlertCommand::Add {
+            instrument: Instrument::new("BTCUSDT"),
+            price: 70000.0,
+            action: PriceAlertAction::Notify,
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args(
+            "alert add BTCUSDT 70000 strategy price-sma-cross-long"
+        ))
+        .expect("strategy action should parse"),
+        AppCommand::Alert(AlertCommand::Add {
+            instrument: Instrument::new("BTCUSDT"),
+            price: 70000.0,
+            action: PriceAlertAction::StartStrategy {
+                template: "price-sma-cross-long".to_string(),
+            },
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("alert add ETHUSDT 3000 target -0.25"))
+            .expect("target action should parse"),
+        AppCommand::Alert(AlertCommand::Add {
+            instrument: Instrument::new("ETHUSDT"),
+            price: 3000.0,
+            action: PriceAlertAction::SetTargetExposure { target: -0.25 },
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("alert remove 3")).expect("alert remove should parse"),
+        AppCommand::Alert(AlertCommand::Remove { id: 3 })
+    );
+    assert_eq!(
+        parse_app_command(&args("alert confirm 4")).expect("alert confirm should parse"),
+        AppCommand::Alert(AlertCommand::Confirm { id: 4 })
+    );
+    assert_eq!(
+        parse_app_command(&args("alert")).expect("alert list should parse"),
+        AppCommand::Alert(AlertCommand::List)
+    );
+    assert!(parse_app_command(&args("alert add BTCUSDT -1")).is_err());
+    assert!(parse_app_command(&args("alert add BTCUSDT 70000 target 2")).is_err());
+}
+
+#[test]
+fn parse_basis_commands_with_thresholds() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args(
+            "basis start btc entry=30 exit=5 target=0.1 funding=15"
+        ))
+        .expect("basis start should parse"),
+        AppCommand::Basis(BasisCommand::Start {
+            instrument: Instrument::new("BTCUSDT"),
+            config: BasisArbConfig {
+                entry_bps: 30.0,
+                exit_bps: 5.0,
+                funding_apr_pct: Some(15.0),
+                target: 0.1,
+            },
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("basis")).expect("basis list should parse"),
+        AppCommand::Basis(BasisCommand::List)
+    );
+    assert_eq!(
+        parse_app_command(&args("basis stop 2")).expect("basis stop should parse"),
+        AppCommand::Basis(BasisCommand::Stop { id: 2 })
+    );
+    assert!(parse_app_command(&args("basis start BTCUSDT entry=5 exit=5 target=0.1")).is_err());
+    assert!(parse_app_command(&args("basis start BTCUSDT entry=30 target=1.5")).is_err());
+    assert!(parse_app_command(&args("basis start BTCUSDT target=0.1")).is_err());
+    assert!(shell_help_text().contains("/basis <start|list|stop>"));
+}
+
+#[test]
+fn parse_reload_config_command() {
+    assert_eq!(
+        parse_shell_input("/reload-config").expect("reload-config should parse"),
+        ShellInput::Command(Box::new(AppCommand::ReloadConfig))
+    );
+    assert!(shell_help_text().contains("/reload-config"));
+}
+
+#[test]
+fn parse_diagnostics_command() {
+    assert_eq!(
+        parse_shell_input("/diagnostics").expect("diagnostics should parse"),
+        ShellInput::Command(Box::new(AppCommand::Diagnostics))
+    );
+    assert!(shell_help_text().contains("/diagnostics"));
+}
+
+#[test]
+fn parse_diagnostics_export_command() {
+    assert_eq!(
+        parse_shell_input("/diagnostics export").expect("export should parse"),
+        ShellInput::Command(Box::new(AppCommand::ExportDiagnostics { path: None }))
+    );
+    assert_eq!(
+        parse_shell_input("/diagnostics export /tmp/report.zip").expect("export should parse"),
+        ShellInput::Command(Box::new(AppCommand::ExportDiagnostics {
+            path: Some(PathBuf::from("/tmp/report.zip")),
+        }))
+    );
+    assert!(parse_shell_input("/diagnostics dump").is_err());
+    assert!(parse_shell_input("/diagnostics export a.zip b.zip").is_err());
+}
+
+#[test]
+fn parse_trailing_stop_command_checks_callback_range() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("trailing-stop btc 1.5")).expect("trailing stop should parse"),
+        AppCommand::Execution(ExecutionCommand::PlaceTrailingStop {
+            instrument: Instrument::new("BTCUSDT"),
+            trail: TrailDistance::Percent(1.5),
+            source: CommandSource::User,
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("trailing-stop ETHUSDT 2ATR")).expect("atr trail should parse"),
+        AppCommand::Execution(ExecutionCommand::PlaceTrailingStop {
+            instrument: Instrument::new("ETHUSDT"),
+            trail: TrailDistance::AtrMultiple(2.0),
+            source: CommandSource::User,
+        })
+    );
+    assert!(parse_app_command(&args("trailing-stop BTCUSDT 0atr")).is_err());
+    assert!(parse_app_command(&args("trailing-stop BTCUSDT 0.05")).is_err());
+    assert!(parse_app_command(&args("trailing-stop BTCUSDT 12")).is_err());
+    assert!(parse_app_command(&args("trailing-stop BTCUSDT")).is_err());
+}
+
+#[test]
+fn parse_take_profit_command_defaults_profile() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("take-profit btc")).expect("take profit should parse"),
+        AppCommand::Execution(ExecutionCommand::PlaceTakeProfitLadder {
+            instrument: Instrument::new("BTCUSDT"),
+            profile: "default".to_string(),
+            source: CommandSource::User,
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("take-profit ETHUSDT Scalp")).expect("take profit should parse"),
+        AppCommand::Execution(ExecutionCommand::PlaceTakeProfitLadder {
+            instrument: Instrument::new("ETHUSDT"),
+            profile: "scalp".to_string(),
+            source: CommandSource::User,
+        })
+    );
+    assert!(parse_app_command(&args("take-profit")).is_err());
+    assert!(shell_help_text().contains("/take-profit"));
+}
+
+#[test]
+fn parse_dom_command_with_levels_placement_and_cancel() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    let dom = |levels: usize, action: Option<DomAction>| {
+        AppCommand::Dom(DomCommand {
+            instrument: Instrument::new("BTCUSDT"),
+            levels,
+            action,
+        })
+    };
+
+    assert_eq!(
+        parse_app_command(&args("dom btc")).expect("dom should parse"),
+        dom(10, None)
+    );
+    assert_eq!(
+        parse_app_command(&args("dom BTCUSDT 20")).expect("dom levels should parse"),
+        dom(20, None)
+    );
+    assert_eq!(
+        parse_app_command(&args("dom BTCUSDT buy b1 0.01")).expect("level buy should parse"),
+        dom(
+            10,
+            Some(DomAction::Place {
+                side: Side::Buy,
+                price: DomPrice::Bid(1),
+                qty: 0.01,
+            })
+        )
+    );
+    assert_eq!(
+        parse_app_command(&args("dom BTCUSDT sell 64000.5 0.02")).expect("sell should parse"),
+        dom(
+            10,
+            Some(DomAction::Place {
+                side: Side::Sell,
+                price: DomPrice::Exact(64000.5),
+                qty: 0.02,
+            })
+        )
+    );
+    assert_eq!(
+        parse_app_command(&args("dom BTCUSDT cancel web-1")).expect("cancel should parse"),
+        dom(
+            10,
+            Some(DomAction::Cancel {
+                client_order_id: "web-1".to_string(),
+            })
+        )
+    );
+    assert!(parse_app_command(&args("dom BTCUSDT 0")).is_err());
+    assert!(parse_app_command(&args("dom BTCUSDT buy b0 0.01")).is_err());
+    assert!(parse_app_command(&args("dom BTCUSDT buy a2 -1")).is_err());
+    assert!(parse_app_command(&args("dom BTCUSDT cancel")).is_err());
+    assert!(shell_help_text().contains("/dom <instrument>"));
+    assert_eq!(
+        complete_shell_input("/dom BTCUSDT c", &[]),
+        vec!["/dom BTCUSDT cancel".to_string()]
+    );
+}
+
+#[test]
+fn parse_oco_place_and_status_commands() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("oco")).expect("oco should parse"),
+        AppCommand::OcoStatus
+    );
+    assert_eq!(
+        parse_app_command(&args("oco list")).expect("oco list should parse"),
+        AppCommand::OcoStatus
+    );
+    assert_eq!(
+        parse_app_command(&args("oco ev")).expect("oco ev should parse"),
+        AppCommand::HoldEvStatus
+    );
+    assert_eq!(
+        parse_app_command(&args("oco btc 66000 62000")).expect("oco place should parse"),
+        AppCommand::Execution(ExecutionCommand::PlaceOco {
+            instrument: Instrument::new("BTCUSDT"),
+            take_profit_price: 66000.0,
+            stop_price: 62000.0,
+            stop_limit_price: None,
+            source: CommandSource::User,
+        })
+    );
+    assert_eq!(
+        parse_app_command(&args("oco btc 66000 62000 61900"))
+            .expect("oco stop-limit should parse"),
+        AppCommand::Execution(ExecutionCommand::PlaceOco {
+            instrument: Instrument::new("BTCUSDT"),
+            take_profit_price: 66000.0,
+            stop_price: 62000.0,
+            stop_limit_price: Some(61900.0),
+            source: CommandSource::User,
+        })
+    );
+    assert!(parse_app_command(&args("oco BTCUSDT 66000")).is_err());
+    assert!(parse_app_command(&args("oco BTCUSDT 66000 62000 0")).is_err());
+    assert!(parse_app_command(&args("oco BTCUSDT 66000 -1")).is_err());
+    assert!(shell_help_text().contains("/oco"));
+}
+
+#[test]
+fn parse_iceberg_place_and_status_commands() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("iceberg")).expect("iceberg should parse"),
+        AppCommand::IcebergStatus
+    );
+    assert_eq!(
+        parse_app_command(&args("iceberg btc buy 1 0.2 64000")).expect("iceberg should parse"),
+        AppCommand::Execution(ExecutionCommand::PlaceIceberg {
+            instrument: Instrument::new("BTCUSDT"),
+            side: Side::Buy,
+            qty: 1.0,
+            visible_qty: 0.2,
+            price: 64000.0,
+            source: CommandSource::User,
+        })
+    );
+    assert!(parse_app_command(&args("iceberg BTCUSDT hold 1 0.2 64000")).is_err());
+    assert!(parse_app_command(&args("iceberg BTCUSDT buy 1 2 64000")).is_err());
+    assert!(parse_app_command(&args("iceberg BTCUSDT buy 1 0.2")).is_err());
+    assert!(shell_help_text().contains("/iceberg [list|<instrument>"));
+}
+
+#[test]
+fn parse_shadow_status_command() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    for raw in ["shadow", "shadow status"] {
+        assert_eq!(
+            parse_app_command(&args(raw)).expect("shadow should parse"),
+            AppCommand::ShadowStatus
+        );
+    }
+    assert!(parse_app_command(&args("shadow reset")).is_err());
+    assert!(shell_help_text().contains("/shadow [status]"));
+}
+
+#[test]
+fn parse_flatten_selected_and_named_instrument() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("flatten")).expect("flatten should parse"),
+        AppCommand::Flatten {
+            instrument: None,
+            confirmed: false,
+        }
+    );
+    assert_eq!(
+        parse_app_command(&args("flatten confirm")).expect("flatten confirm should parse"),
+        AppCommand::Flatten {
+            instrument: None,
+            confirmed: true,
+        }
+    );
+    assert_eq!(
+        parse_app_command(&args("flatten btc confirm")).expect("named flatten should parse"),
+        AppCommand::Flatten {
+            instrument: Some(Instrument::new("BTCUSDT")),
+            confirmed: true,
+        }
+    );
+    assert!(parse_app_command(&args("flatten BTCUSDT ETHUSDT")).is_err());
+    assert!(shell_help_text().contains("/flatten [instrument] [confirm] (ctrl+x)"));
+}
+
+#[test]
+fn parse_panic_requires_explicit_confirm() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("panic")).expect("panic should parse"),
+        AppCommand::Panic { confirmed: false }
+    );
+    assert_eq!(
+        parse_app_command(&args("panic confirm")).expect("panic confirm should parse"),
+        AppCommand::Panic { confirmed: true }
+    );
+    assert!(parse_app_command(&args("panic now")).is_err());
+    assert!(shell_help_text().contains("/panic [confirm] (ctrl+p)"));
+}
+
+#[test]
+fn parse_events_rebuild_with_domain_and_path_filters() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("events rebuild")).expect("events rebuild should parse"),
+        AppCommand::RebuildEvents {
+            path: None,
+            domains: BTreeSet::new(),
+        }
+    );
+    assert_eq!(
+        parse_app_command(&args(
+            "events rebuild domain=Execution,strategy path=var/old.jsonl"
+        ))
+        .expect("filtered rebuild should parse"),
+        AppCommand::RebuildEvents {
+            path: Some(PathBuf::from("var/old.jsonl")),
+            domains: BTreeSet::from(["execution".to_string(), "strategy".to_string()]),
+        }
+    );
+    assert!(parse_app_command(&args("events")).is_err());
+    assert!(parse_app_command(&args("events rebuild since=yesterday")).is_err());
+    assert!(shell_help_text().contains("/events rebuild [domain=<domain,...>] [path=<file>]"));
+}
+
+#[test]
+fn parse_adopt_list_and_adopt_commands() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("adopt")).expect("adopt should parse"),
+        AppCommand::ExternalPositions
+    );
+    assert_eq!(
+        parse_app_command(&args("adopt sol 142.5")).expect("adopt with entry should parse"),
+        AppCommand::AdoptPosition {
+            instrument: Instrument::new("SOLUSDT"),
+            entry_price: 142.5,
+        }
+    );
+    assert!(parse_app_command(&args("adopt SOLUSDT")).is_err());
+    assert!(parse_app_command(&args("adopt SOLUSDT 0")).is_err());
+    assert!(shell_help_text().contains("/adopt"));
+}
+
+#[test]
+fn parse_inactivity_status_and_resume_commands() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("inactivity")).expect("inactivity should parse"),
+        AppCommand::InactivityStatus
+    );
+    assert_eq!(
+        parse_app_command(&args("inactivity resume")).expect("resume should parse"),
+        AppCommand::ResumeEntries
+    );
+    assert!(parse_app_command(&args("inactivity off")).is_err());
+    assert!(shell_help_text().contains("/inactivity [status|resume]"));
+}
+
+#[test]
+fn parse_news_pane_and_blackout_commands() {
+    let args = |raw: &str| {
+        raw.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse_app_command(&args("news")).expect("news should parse"),
+        AppCommand::News(NewsCommand::Show)
+    );
+    assert_eq!(
+        parse_app_command(&args("news blackout")).expect("blackout should parse"),
+        AppCommand::News(NewsCommand::Blackout { minutes: None })
+    );
+    assert_eq!(
+        parse_app_command(&args("news blackout 45")).expect("minutes should parse"),
+        AppCommand::News(NewsCommand::Blackout { minutes: Some(45) })
+    );
+    assert_eq!(
+        parse_app_command(&args("news blackout off")).expect("off should parse"),
+        AppCommand::News(NewsCommand::LiftBlackout)
+    );
+    assert!(parse_app_command(&args("news blackout 0")).is_err());
+    assert!(parse_app_command(&args("news latest")).is_err());
+    assert!(shell_help_text().contains("/news [blackout [minutes|off]]"));
+}
@@ -1,6 +1,6 @@
 use sandbox_quant::app::bootstrap::BinanceMode;
-use sandbox_quant::app::commands::{AppCommand, PortfolioView};
-use sandbox_quant::app::output::render_command_output;
+use sandbox_quant::app::commands::{AppCommand, OrderFilter, PortfolioView};
+use sandbox_quant::app::output::{render_command_output, render_event_notices};
 use sandbox_quant::domain::balance::BalanceSnapshot;
 use sandbox_quant::domain::instrument::Instrument;
 use sandbox_quant::domain::market::Market;
@@ -9,12 +9,16 @@ use sandbox_quant::domain::order_type::OrderType;
 use sandbox_quant::domain::position::PositionSnapshot;
 use sandbox_quant::domain::position::Side;
 use sandbox_quant::execution::command::{CommandSource, ExecutionCommand};
+use sandbox_quant::execution::history::{OrderHistory, OrderHistoryRow};
 use sandbox_quant::market_data::price_store::PriceStore;
+use sandbox_quant::observability::event_routing::EventRouting;
 use sandbox_quant::portfolio::store::PortfolioStateStore;
 use sandbox_quant::storage::event_log::{log, EventLog};
 use sandbox_quant::strategy::command::{StrategyCommand, StrategyStartConfig};
+use sandbox_quant::strategy::filter::StrategyListQuery;
 use sandbox_quant::strategy::model::StrategyTemplate;
 use sandbox_quant::strategy::store::StrategyStore;
+use sandbox_quant::ui::projection::UiProjection;
 use serde_json::json;
 
 #[test]
@@ -46,6 +50,7 @@ fn refresh_output_includes_store_summary() {
             instrument: Instrument::new("BTCUSDT"),
             market: Market::Futures,
             side: sandbox_quant::domain::position::Side::Sell,
+            price: None,
             orig_qty: 0.25,
             executed_qty: 0.0,
             reduce_only: true,
@@ -84,6 +89,7 @@ fn refresh_output_includes_store_summary() {
         &event_log,
         &StrategyStore::default(),
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("portfolio"));
@@ -134,6 +140,7 @@ fn portfolio_positions_output_hides_balance_and_order_sections() {
             instrument: Instrument::new("BTCUSDT"),
             market: Market::Futures,
             side: sandbox_quant::domain::position::Side::Sell,
+            price: None,
             orig_qty: 0.25,
             executed_qty: 0.0,
             reduce_only: true,
@@ -171,15 +178,63 @@ fn portfolio_positions_output_hides_balance_and_order_sections() {
         &event_log,
         &StrategyStore::default(),
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("portfolio positions"));
     assert!(output.contains("positions (1)"));
+    assert!(output.contains("entry_stats=[fills=1 vwap=65000.00000000"));
+    assert!(output.contains("break_even=65065.03251626]"));
     assert!(!output.contains("account"));
     assert!(!output.contains("balances ("));
     assert!(!output.contains("open orders ("));
 }
 
+#[test]
+fn portfolio_balances_output_consolidates_exposure_by_base_asset() {
+    let mut store = PortfolioStateStore::default();
+    store.apply_snapshot(sandbox_quant::exchange::types::AuthoritativeSnapshot {
+        balances: vec![
+            BalanceSnapshot {
+                asset: "USDT".to_string(),
+                free: 1000.0,
+                locked: 0.0,
+            },
+            BalanceSnapshot {
+                asset: "BTC".to_string(),
+                free: 0.5,
+                locked: 0.0,
+            },
+        ],
+        positions: vec![PositionSnapshot {
+            instrument: Instrument::new("BTCUSDT"),
+            market: Market::Futures,
+            signed_qty: -0.2,
+            entry_price: Some(60000.0),
+        }],
+        open_orders: vec![],
+    });
+    let event_log = EventLog::default();
+    let prices = PriceStore::default();
+    let render = |view| {
+        render_command_output(
+            &AppCommand::Portfolio(view),
+            &store,
+            &prices,
+            &event_log,
+            &StrategyStore::default(),
+            BinanceMode::Demo,
+            &OrderHistory::default(),
+        )
+    };
+
+    let balances = render(PortfolioView::Balances);
+    assert!(balances.contains("asset exposure (1)"));
+    assert!(balances
+        .contains("  - BTC spot_qty=0.50000000 futures_qty=-0.20000000 net_qty=0.30000000"));
+    assert!(!render(PortfolioView::Overview).contains("asset exposure ("));
+}
+
 #[test]
 fn execution_output_includes_last_event_kind() {
     let store = PortfolioStateStore::default();
@@ -210,6 +265,7 @@ fn execution_output_includes_last_event_kind() {
         &event_log,
         &StrategyStore::default(),
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("execution completed"));
@@ -250,6 +306,7 @@ fn execution_output_renders_close_symbol_summary() {
         &event_log,
         &StrategyStore::default(),
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("command=close-symbol"));
@@ -292,6 +349,7 @@ fn execution_output_renders_target_exposure_summary() {
         &event_log,
         &StrategyStore::default(),
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("command=set-target-exposure"));
@@ -335,6 +393,7 @@ fn execution_output_renders_already_at_target_summary() {
         &event_log,
         &StrategyStore::default(),
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("command=set-target-exposure"));
@@ -365,6 +424,7 @@ fn portfolio_output_renders_options_positions_and_orders() {
             instrument: Instrument::new("BTC-260327-200000-C"),
             market: Market::Options,
             side: Side::Buy,
+            price: None,
             orig_qty: 0.01,
             executed_qty: 0.0,
             reduce_only: false,
@@ -381,6 +441,7 @@ fn portfolio_output_renders_options_positions_and_orders() {
         &event_log,
         &StrategyStore::default(),
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("BTC-260327-200000-C market=OPTIONS side=Buy"));
@@ -424,6 +485,7 @@ fn execution_output_renders_option_order_summary() {
         &event_log,
         &StrategyStore::default(),
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("command=option-order"));
@@ -446,6 +508,7 @@ fn strategy_templates_output_renders_steps() {
         &event_log,
         &StrategyStore::default(),
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("strategy templates"));
@@ -470,6 +533,10 @@ fn strategy_start_output_renders_watch_summary() {
                 win_rate: 0.8,
                 r_multiple: 1.5,
                 max_entry_slippage_pct: 0.001,
+                seed_capital_usdt: None,
+                ramp: None,
+                adapt: None,
+                script: None,
             },
         )
         .expect("watch created");
@@ -498,6 +565,10 @@ fn strategy_start_output_renders_watch_summary() {
                 win_rate: 0.8,
                 r_multiple: 1.5,
                 max_entry_slippage_pct: 0.001,
+                seed_capital_usdt: None,
+                ramp: None,
+                adapt: None,
+                script: None,
             },
         }),
         &store,
@@ -505,6 +576,7 @@ fn strategy_start_output_renders_watch_summary() {
         &event_log,
         &strategy_store,
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("strategy started"));
@@ -530,17 +602,24 @@ fn strategy_list_output_renders_active_watch() {
                 win_rate: 0.8,
                 r_multiple: 1.5,
                 max_entry_slippage_pct: 0.001,
+                seed_capital_usdt: None,
+                ramp: None,
+                adapt: None,
+                script: None,
             },
         )
         .expect("watch created");
 
     let output = render_command_output(
-        &AppCommand::Strategy(StrategyCommand::List),
+        &AppCommand::Strategy(StrategyCommand::List {
+            query: StrategyListQuery::default(),
+        }),
         &store,
         &prices,
         &event_log,
         &strategy_store,
         BinanceMode::Demo,
+        &OrderHistory::default(),
     );
 
     assert!(output.contains("strategy watches"));
@@ -548,3 +627,330 @@ fn strategy_list_output_renders_active_watch() {
     assert!(output.contains("template=liquidation-breakdown-short"));
     assert!(output.contains("state=armed"));
 }
+
+#[test]
+fn strategy_list_output_searches_and_pages_watches() {
+    let store = PortfolioStateStore::default();
+    let prices = PriceStore::default();
+    let event_log = EventLog::default();
+    let mut strategy_store = StrategyStore::default();
+    for symbol in (1..=12)
+        .map(|n| format!("ALT{n}USDT"))
+        .chain(["ETHUSDT".to_string()])
+    {
+        strategy_store
+            .create_watch(
+                BinanceMode::Demo,
+                StrategyTemplate::LiquidationBreakdownShort,
+                Instrument::new(symbol),
+                StrategyStartConfig {
+                    risk_pct: 0.005,
+                    win_rate: 0.8,
+                    r_multiple: 1.5,
+                    max_entry_slippage_pct: 0.001,
+                    seed_capital_usdt: None,
+                    ramp: None,
+                    adapt: None,
+                    script: None,
+                },
+            )
+            .expect("watch created");
+    }
+    let render = |query: StrategyListQuery| {
+        render_command_output(
+            &AppCommand::Strategy(StrategyCommand::List { query }),
+            &store,
+            &prices,
+            &event_log,
+            &strategy_store,
+            BinanceMode::Demo,
+            &OrderHistory::default(),
+        )
+    };
+
+    let first = render(StrategyListQuery::default());
+    assert!(first.contains("active=13"));
+    assert!(first.contains("page=1/2 showing=1-10 of 13"), "{first}");
+    assert_eq!(first.matches("- id=").count(), 10);
+
+    let second = render(StrategyListQuery {
+        search: None,
+        page: 2,
+    });
+    assert!(second.contains("page=2/2 showing=11-13 of 13"), "{second}");
+    assert_eq!(second.matches("- id=").count(), 3);
+
+    let searched = render(StrategyListQuery {
+        search: Some("eth".to_string()),
+        page: 5,
+    });
+    assert!(searched.contains("search=eth matches=1"), "{searched}");
+    assert!(searched.contains("page=1/1 showing=1-1 of 1"), "{searched}");
+    assert!(searched.contains("instrument=ETHUSDT"));
+    assert!(!searched.contains("instrument=ALT1USDT"));
+
+    let missing = render(StrategyListQuery {
+        search: Some("doge".to_string()),
+        page: 1,
+    });
+    assert!(missing.contains("page=1/1 showing=0 of 0"));
+    assert!(missing.contains("- none"));
+}
+
+#[test]
+fn orders_output_applies_filter_and_shows_filtered_totals() {
+    let order = |client_order_id: &str, side: Side, qty: f64| OpenOrder {
+        order_id: None,
+        client_order_id: client_order_id.to_string(),
+        instrument: Instrument::new("BTCUSDT"),
+        market: Market::Futures,
+        side,
+        price: None,
+        orig_qty: qty,
+        executed_qty: 0.0,
+        reduce_only: false,
+        status: OrderStatus::Submitted,
+    };
+    let mut store = PortfolioStateStore::default();
+    store.apply_snapshot(sandbox_quant::exchange::types::AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![],
+        open_orders: vec![
+            order("grid-1", Side::Buy, 0.1),
+            order("grid-2", Side::Sell, 0.2),
+            order("manual-1", Side::Sell, 0.3),
+        ],
+    });
+
+    let output = render_command_output(
+        &AppCommand::Portfolio(PortfolioView::Orders(OrderFilter {
+            side: Some(Side::Sell),
+            status: None,
+            tag: Some("grid".to_string()),
+        })),
+        &store,
+        &PriceStore::default(),
+        &EventLog::default(),
+        &StrategyStore::default(),
+        BinanceMode::Demo,
+        &OrderHistory::default(),
+    );
+
+    assert!(output.contains("portfolio orders"));
+    assert!(output.contains("open orders (1/3)"));
+    assert!(output.contains("filter side=Sell status=any tag=grid"));
+    assert!(output.contains("filtered_qty=0.20000000"));
+    assert!(output.contains("qty=0.20000000"));
+    assert!(!output.contains("qty=0.30000000"));
+}
+
+#[test]
+fn history_output_renders_typed_rows_newest_first_with_filter() {
+    let row = |symbol: &str, side: Side, status: OrderStatus, tag: &str| OrderHistoryRow {
+        time: chrono::Utc::now(),
+        instrument: Instrument::new(symbol),
+        market: Market::Futures,
+        side,
+        qty: 0.1,
+        price: Some(100.0),
+        status,
+        tag: tag.to_string(),
+        depth_imbalance: None,
+        list_id: None,
+        parent_id: None,
+        signal_time: None,
+        exchange_time: None,
+    };
+    let mut history = OrderHistory::default();
+    history.record(row("BTCUSDT", Side::Buy, OrderStatus::Submitted, "manual"));
+    history.record(row("ETHUSDT", Side::Sell, OrderStatus::Rejected, "system"));
+    history.record(row("SOLUSDT", Side::Buy, OrderStatus::Submitted, "system"));
+
+    let output = render_command_output(
+        &AppCommand::Portfolio(PortfolioView::History(OrderFilter {
+            side: Some(Side::Buy),
+            status: None,
+            tag: None,
+        })),
+        &PortfolioStateStore::default(),
+        &PriceStore::default(),
+        &EventLog::default(),
+        &StrategyStore::default(),
+        BinanceMode::Demo,
+        &history,
+    );
+
+    assert!(output.contains("order history"));
+    assert!(output.contains("rows=2/3"));
+    assert!(output.contains("filtered_qty=0.20000000"));
+    assert!(!output.contains("ETHUSDT"));
+    let sol = output.find("SOLUSDT").expect("newest row");
+    let btc = output.find("BTCUSDT").expect("oldest row");
+    assert!(sol < btc);
+
+    // Past capacity the oldest rows go first.
+    let mut capped = OrderHistory::with_capacity(2);
+    for row in history.rows() {
+        capped.record(row.clone());
+    }
+    let symbols = |history: &OrderHistory| {
+        history
+            .rows()
+            .iter()
+            .map(|row| row.instrument.0.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(symbols(&capped), ["ETHUSDT", "SOLUSDT"]);
+    capped.set_capacity(1);
+    assert_eq!(symbols(&capped), ["SOLUSDT"]);
+}
+
+#[test]
+fn event_notices_list_only_notifier_routed_events_at_or_above_level() {
+    let mut event_log = EventLog {
+        routing: EventRouting::parse("execution=memory+notifier:warn"),
+        ..EventLog::default()
+    };
+    log(
+        &mut event_log,
+        "app.execution.completed",
+        json!({ "instrument": "BTCUSDT" }),
+    );
+    let since = event_log.records.len();
+    log(
+        &mut event_log,
+        "app.execution.deferred_failed",
+        json!({ "instrument": "BTCUSDT", "error": "thin asks" }),
+    );
+    log(
+        &mut event_log,
+        "app.strategy.capital_exhausted",
+        json!({ "watch_id": 1 }),
+    );
+
+    assert_eq!(
+        render_event_notices(&event_log, 0).as_deref(),
+        Some("notice level=error kind=app.execution.deferred_failed instrument=BTCUSDT error=thin asks")
+    );
+    assert!(render_event_notices(&event_log, since + 1).is_none());
+}
+
+/// Compares against `tests/golden/<name>.txt`; run with
+/// `SANDBOX_QUANT_UPDATE_GOLDEN=1` to rewrite it after an intended change.
+fn assert_golden(name: &str, actual: &str) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.txt"));
+    if std::env::var_os("SANDBOX_QUANT_UPDATE_GOLDEN").is_some() {
+        std::fs::write(&path, format!("{actual}\n")).expect("golden should be written");
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing golden {}", path.display()));
+    assert_eq!(actual, expected.trim_end_matches('\n'), "golden {name} differs");
+}
+
+fn ui_projection_fixture() -> EventLog {
+    let mut event_log = EventLog::default();
+    for (id, template, instrument) in [
+        (1, "liquidation-breakdown-short", "BTCUSDT"),
+        (2, "price-sma-cross-long", "ETHUSDT"),
+    ] {
+        log(
+            &mut event_log,
+            "app.strategy.watch_started",
+            json!({
+                "watch_id": id,
+                "template": template,
+                "instrument": instrument,
+                "state": "armed",
+            }),
+        );
+    }
+    log(
+        &mut event_log,
+        "app.strategy.group_set",
+        json!({ "watch_id": 1, "group": "trend" }),
+    );
+    log(
+        &mut event_log,
+        "app.strategy.adapted",
+        json!({ "watch_id": 1, "trades": 10 }),
+    );
+    log(
+        &mut event_log,
+        "app.strategy.bulk_applied",
+        json!({ "group": "trend", "action": "disable", "changed": [1] }),
+    );
+    log(
+        &mut event_log,
+        "app.strategy.watch_stopped",
+        json!({ "watch_id": 2, "state": "stopped" }),
+    );
+    for id in [1, 2] {
+        log(
+            &mut event_log,
+            "app.alert.added",
+            json!({
+                "alert_id": id,
+                "instrument": "BTCUSDT",
+                "price": 70000.0 + f64::from(id) * 500.0,
+                "action": "notify",
+            }),
+        );
+    }
+    log(
+        &mut event_log,
+        "app.alert.triggered",
+        json!({
+            "alert_id": 1,
+            "instrument": "BTCUSDT",
+            "price": 70500.0,
+            "action": "notify",
+        }),
+    );
+    log(&mut event_log, "app.alert.removed", json!({ "alert_id": 2 }));
+    log(
+        &mut event_log,
+        "app.execution.completed",
+        json!({
+            "command_kind": "set_target_exposure",
+            "instrument": "BTCUSDT",
+            "outcome_kind": "submitted",
+        }),
+    );
+    log(
+        &mut event_log,
+        "app.execution.deferred_failed",
+        json!({ "instrument": "ETHUSDT", "error": "thin asks" }),
+    );
+    event_log
+}
+
+#[test]
+fn ui_projection_renders_golden_panels_from_events() {
+    let event_log = ui_projection_fixture();
+    let projection = UiProjection::from_events(&event_log.records);
+
+    assert_eq!(projection.watches[&1].state, "disabled");
+    assert_eq!(projection.watches[&1].adaptations, 1);
+    assert!(projection.alerts[&1].triggered);
+    assert!(!projection.alerts.contains_key(&2));
+    assert_golden("ui_projection", &projection.render());
+    assert_golden("ui_projection_empty", &UiProjection::default().render());
+}
+
+#[test]
+fn ui_projection_folds_events_the_same_way_one_at_a_time() {
+    let records = ui_projection_fixture().records;
+    let mut incremental = UiProjection::default();
+    for record in &records {
+        incremental.apply(record);
+    }
+
+    assert_eq!(incremental, UiProjection::from_events(&records));
+    assert_eq!(
+        incremental.render(),
+        UiProjection::from_events(&records).render()
+    );
+}
@@ -1,22 +1,83 @@
+use chrono::{Duration, TimeZone, Utc};
+use sandbox_quant::app::bootstrap::BinanceMode;
+use sandbox_quant::dataset::types::DerivedKlineRow;
 use sandbox_quant::domain::balance::BalanceSnapshot;
 use sandbox_quant::domain::exposure::Exposure;
 use sandbox_quant::domain::identifiers::BatchId;
 use sandbox_quant::domain::instrument::Instrument;
 use sandbox_quant::domain::market::Market;
+use sandbox_quant::domain::order::{OpenOrder, OrderStatus};
 use sandbox_quant::domain::order_type::OrderType;
 use sandbox_quant::domain::position::{PositionSnapshot, Side};
+use sandbox_quant::domain::reason::{ExitReason, ReasonCode, SkipReason};
 use sandbox_quant::error::execution_error::ExecutionError;
+use sandbox_quant::error::plugin_error::PluginError;
+use sandbox_quant::error::script_error::ScriptError;
+use sandbox_quant::error::storage_error::StorageError;
 use sandbox_quant::exchange::fake::FakeExchange;
 use sandbox_quant::exchange::symbol_rules::SymbolRules;
-use sandbox_quant::exchange::types::AuthoritativeSnapshot;
+use sandbox_quant::exchange::types::{
+    AuthoritativeSnapshot, EarnAccount, EarnPosition, EarnTransferKind, OrderBookDepth,
+};
 use sandbox_quant::execution::close_all::CloseAllBatchResult;
 use sandbox_quant::execution::close_symbol::{CloseSubmitResult, CloseSymbolResult};
 use sandbox_quant::execution::command::{CommandSource, ExecutionCommand};
+use sandbox_quant::execution::dom_ladder::{build_dom_ladder, round_to_tick, DomPrice};
+use sandbox_quant::execution::history::OrderHistoryRow;
+use sandbox_quant::execution::hold_ev::{parse_hold_ev_profiles, HoldEvAction};
+use sandbox_quant::execution::iceberg::{iceberg_client_order_id, iceberg_parent_id, IcebergOrder};
+use sandbox_quant::execution::illiquid_window::{
+    illiquid_until, parse_illiquid_windows, IlliquidWindow,
+};
+use sandbox_quant::execution::oco::{
+    oco_client_order_id, oco_list_id, oco_resized_order_id, OcoLeg,
+};
+use sandbox_quant::execution::paper_shadow::{
+    shadow_client_order_id, shadow_divergence, shadow_watch_id, PaperShadowConfig, ShadowOrder,
+};
 use sandbox_quant::execution::service::{ExecutionOutcome, ExecutionService};
+use sandbox_quant::execution::slippage_guard::SlippageGuardConfig;
+use sandbox_quant::market_data::candle_mode::{heikin_ashi, renko, CandleMode, RenkoBrick};
 use sandbox_quant::market_data::price_store::PriceStore;
 use sandbox_quant::market_data::service::MarketDataService;
+use sandbox_quant::market_data::synthetic::parse_synthetic_instruments;
+use sandbox_quant::portfolio::asset_exposure::{aggregate_base_asset_exposure, base_asset};
+use sandbox_quant::portfolio::earn::{earn_balances, plan_redeem, EarnConfig};
 use sandbox_quant::portfolio::store::PortfolioStateStore;
+use sandbox_quant::storage::atomic_file::{backup_path, with_checksum};
 use sandbox_quant::storage::event_log::{log, EventLog};
+use sandbox_quant::storage::event_replay::{replay_event_file, PersistedEvent};
+use sandbox_quant::storage::order_store::{FillRow, HistoryQuery, OrderStore, TimeBucket};
+use sandbox_quant::storage::sealed::{
+    is_sealed, migrate_jsonl, read_jsonl_lines, read_store_file, seal_line, write_store_file,
+    Sealer,
+};
+use sandbox_quant::strategy::adaptive::{AdaptiveTuning, TunedParam};
+use sandbox_quant::strategy::allocation::AutoAllocation;
+use sandbox_quant::strategy::basis::{
+    BasisArbConfig, BasisArbStore, BasisCarry, BasisDecision, BasisQuote,
+};
+use sandbox_quant::strategy::command::StrategyStartConfig;
+use sandbox_quant::strategy::cooldown::{CooldownScaling, ExpectancyCooldown};
+use sandbox_quant::strategy::ev::{
+    BetaBinomial, EmpiricalBootstrap, EvEstimator, EvFeatures, EvGate, EvGateMode, EvModel,
+    LogisticWinModel, WinProbabilityModel,
+};
+use sandbox_quant::strategy::htf_confirm::{HtfConfirm, HtfIndicator, HtfTrend};
+use sandbox_quant::strategy::kpi::{KpiLight, KpiTargets, StrategyKpi};
+use sandbox_quant::strategy::live::{LiveBarEntry, LiveBarStrategy};
+use sandbox_quant::strategy::model::{StrategyTemplate, StrategyWatch, StrategyWatchState};
+use sandbox_quant::strategy::order_throttle::OrderThrottle;
+use sandbox_quant::strategy::overtrading::{
+    detect_trade_clusters, OvertradingGuard, OvertradingLimit,
+};
+use sandbox_quant::strategy::plugin::{discover_plugins, inspect_wasm, WasmStrategy};
+use sandbox_quant::strategy::r_multiple::{RMultipleStats, RMultipleTracker};
+use sandbox_quant::strategy::ramp::SizeRamp;
+use sandbox_quant::strategy::registry::{SmaCross, Strategy, StrategyRegistry, StrategySignal};
+use sandbox_quant::strategy::script::ScriptStrategy;
+use sandbox_quant::strategy::store::StrategyStore;
+use sandbox_quant::strategy::trade_stats::{EmptyTradeStatsReader, TradeStatsReader};
 use serde_json::json;
 
 #[test]
@@ -33,6 +94,27 @@ fn position_snapshot_derives_side_and_abs_qty_from_signed_qty() {
     assert!(!short.is_flat());
 }
 
+#[test]
+fn short_positions_profit_when_price_falls() {
+    let short = PositionSnapshot {
+        instrument: Instrument::new("BTCUSDT"),
+        market: Market::Futures,
+        signed_qty: -0.5,
+        entry_price: Some(50000.0),
+    };
+
+    assert_eq!(short.unrealized_pnl(49000.0), Some(500.0));
+    assert_eq!(short.unrealized_pnl(50400.0), Some(-200.0));
+    assert_eq!(Side::Sell.pnl(100.0, 97.0, 2.0), 6.0);
+    assert_eq!(Side::Buy.pnl(100.0, 97.0, 2.0), -6.0);
+    assert_eq!(Side::Sell.position_label(), "short");
+    assert_eq!(
+        StrategyTemplate::PriceSmaCrossShortFast.entry_side(),
+        Side::Sell
+    );
+    assert_eq!(StrategyTemplate::PriceSmaCrossLong.entry_side(), Side::Buy);
+}
+
 #[test]
 fn exposure_is_bounded_to_signed_unit_interval() {
     assert!(Exposure::new(1.0).is_some());
@@ -98,6 +180,7 @@ fn execution_service_submits_close_against_authoritative_store_without_ui() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
 
@@ -217,6 +300,7 @@ fn execution_service_submits_target_exposure_without_ui() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     let mut prices = PriceStore::default();
@@ -272,6 +356,7 @@ fn execution_service_treats_below_min_step_target_delta_as_already_at_target() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     let mut prices = PriceStore::default();
@@ -326,6 +411,7 @@ fn execution_service_dispatches_command_through_single_entrypoint() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     let mut prices = PriceStore::default();
@@ -422,6 +508,7 @@ fn execution_service_submits_target_exposure_from_flat_position() {
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     fake.set_last_price(instrument.clone(), Market::Futures, 50000.0);
@@ -438,6 +525,7 @@ fn execution_service_submits_target_exposure_from_flat_position() {
             open_orders: Default::default(),
         },
         staleness: sandbox_quant::portfolio::staleness::StalenessState::Fresh,
+        ..PortfolioStateStore::default()
     };
 
     let mut service = ExecutionService::default();
@@ -461,6 +549,124 @@ fn execution_service_submits_target_exposure_from_flat_position() {
     assert_eq!(requests[0].order_type, OrderType::Market);
 }
 
+#[test]
+fn execution_service_queues_strategy_entries_inside_illiquid_windows() {
+    let instrument = Instrument::new("BTCUSDT");
+    let fake = FakeExchange::new(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 1000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    fake.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    fake.set_last_price(instrument.clone(), Market::Futures, 50000.0);
+    let prices = PriceStore::default();
+    let store = PortfolioStateStore {
+        snapshot: sandbox_quant::portfolio::snapshot::PortfolioStateSnapshot {
+            balances: vec![BalanceSnapshot {
+                asset: "USDT".to_string(),
+                free: 1000.0,
+                locked: 0.0,
+            }],
+            positions: Default::default(),
+            open_orders: Default::default(),
+        },
+        staleness: sandbox_quant::portfolio::staleness::StalenessState::Fresh,
+        ..PortfolioStateStore::default()
+    };
+    let command = |target: f64, source: CommandSource| ExecutionCommand::SetTargetExposure {
+        instrument: instrument.clone(),
+        target: Exposure::new(target).expect("bounded exposure"),
+        order_type: OrderType::Market,
+        source,
+    };
+    let strategy = CommandSource::Strategy {
+        watch_id: 1,
+        version: "3f9a0c1d".to_string(),
+    };
+    let mut service = ExecutionService {
+        illiquid_windows: parse_illiquid_windows("* 00:00-24:00"),
+        ..ExecutionService::default()
+    };
+
+    let error = service
+        .execute(&fake, &store, &prices, command(0.5, strategy.clone()))
+        .expect_err("strategy entry should wait for the liquid open");
+    assert!(matches!(error, ExecutionError::EntryQueued { .. }));
+    assert!(error.to_string().contains("BTCUSDT entry queued until"));
+    service
+        .execute(&fake, &store, &prices, command(0.25, strategy.clone()))
+        .expect_err("newer signal replaces the queued one");
+    assert!(fake.submit_requests().is_empty());
+    assert_eq!(service.queued_entries.len(), 1);
+    assert_eq!(service.queued_entries[0].command, command(0.25, strategy));
+    assert!(service.is_entry_queued(&instrument));
+    assert!(service.take_released_entries(Utc::now()).is_empty());
+
+    service
+        .execute(&fake, &store, &prices, command(0.5, CommandSource::User))
+        .expect("manual entries are not held back");
+    assert_eq!(fake.submit_requests().len(), 1);
+
+    service.illiquid_windows.clear();
+    assert_eq!(service.take_released_entries(Utc::now()).len(), 1);
+    assert!(!service.is_entry_queued(&instrument));
+}
+
+#[test]
+fn illiquid_windows_parse_weekly_spans_and_chain_back_to_back() {
+    let windows =
+        parse_illiquid_windows("sat-sun 22:00-06:00,tue+thu 02:00-04:00,bogus,* 25:00-01:00");
+    assert_eq!(
+        windows
+            .iter()
+            .map(IlliquidWindow::label)
+            .collect::<Vec<_>>(),
+        vec!["sat+sun 22:00-06:00", "tue+thu 02:00-04:00"]
+    );
+    let weekend = windows[0];
+    // 2026-10-17 is a Saturday.
+    let saturday_late = Utc.with_ymd_and_hms(2026, 10, 17, 23, 0, 0).unwrap();
+    assert_eq!(
+        weekend.end_after(saturday_late),
+        Some(Utc.with_ymd_and_hms(2026, 10, 18, 6, 0, 0).unwrap())
+    );
+    let monday_early = Utc.with_ymd_and_hms(2026, 10, 19, 5, 59, 0).unwrap();
+    assert_eq!(
+        weekend.end_after(monday_early),
+        Some(Utc.with_ymd_and_hms(2026, 10, 19, 6, 0, 0).unwrap())
+    );
+    let saturday_early = Utc.with_ymd_and_hms(2026, 10, 17, 5, 0, 0).unwrap();
+    assert_eq!(weekend.end_after(saturday_early), None);
+    assert_eq!(
+        IlliquidWindow::parse("fri-mon 23:30-01:00").map(|window| window.label()),
+        Some("mon+fri+sat+sun 23:30-01:00".to_string())
+    );
+
+    let chained = parse_illiquid_windows("fri 22:00-24:00,sat 00:00-24:00");
+    let friday_night = Utc.with_ymd_and_hms(2026, 10, 16, 23, 0, 0).unwrap();
+    assert_eq!(
+        illiquid_until(&chained, friday_night),
+        Some(Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap())
+    );
+    assert_eq!(
+        illiquid_until(&chained, saturday_early - Duration::days(2)),
+        None
+    );
+}
+
 #[test]
 fn execution_service_rejects_target_exposure_when_normalized_qty_is_too_small() {
     let instrument = Instrument::new("BTCUSDT");
@@ -480,6 +686,7 @@ fn execution_service_rejects_target_exposure_when_normalized_qty_is_too_small()
             min_qty: 0.001,
             max_qty: 100.0,
             step_size: 0.001,
+            tick_size: 0.01,
         },
     );
     fake.set_last_price(instrument.clone(), Market::Futures, 100_000.0);
@@ -496,6 +703,7 @@ fn execution_service_rejects_target_exposure_when_normalized_qty_is_too_small()
             open_orders: Default::default(),
         },
         staleness: sandbox_quant::portfolio::staleness::StalenessState::Fresh,
+        ..PortfolioStateStore::default()
     };
 
     let mut service = ExecutionService::default();
@@ -516,3 +724,1846 @@ fn execution_service_rejects_target_exposure_when_normalized_qty_is_too_small()
 fn fake_exchange() -> FakeExchange {
     FakeExchange::new(AuthoritativeSnapshot::default())
 }
+
+#[test]
+fn base_asset_exposure_consolidates_spot_balance_and_futures_position() {
+    let instrument = Instrument::new("BTCUSDT");
+    let mut store = PortfolioStateStore::default();
+    store.apply_snapshot(AuthoritativeSnapshot {
+        balances: vec![
+            BalanceSnapshot {
+                asset: "USDT".to_string(),
+                free: 1000.0,
+                locked: 0.0,
+            },
+            BalanceSnapshot {
+                asset: "BTC".to_string(),
+                free: 0.4,
+                locked: 0.1,
+            },
+        ],
+        positions: vec![PositionSnapshot {
+            instrument: instrument.clone(),
+            market: Market::Futures,
+            signed_qty: -0.2,
+            entry_price: Some(50000.0),
+        }],
+        open_orders: vec![],
+    });
+
+    let exposures = aggregate_base_asset_exposure(&store.snapshot, |_| Some(60000.0));
+
+    assert_eq!(base_asset(&instrument), Some("BTC"));
+    assert_eq!(exposures.len(), 1);
+    let btc = &exposures["BTC"];
+    assert!((btc.spot_qty - 0.5).abs() < 1e-9);
+    assert!((btc.futures_qty + 0.2).abs() < 1e-9);
+    assert!((btc.net_qty() - 0.3).abs() < 1e-9);
+    assert_eq!(
+        btc.net_exposure_usdt().map(|value| value.round()),
+        Some(18000.0)
+    );
+    assert_eq!(btc.instruments, vec![instrument]);
+}
+
+#[test]
+fn execution_service_rejects_target_beyond_base_asset_exposure_cap() {
+    let instrument = Instrument::new("BTCUSDT");
+    let fake = fake_exchange();
+    fake.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.01,
+        },
+    );
+    let mut store = PortfolioStateStore::default();
+    let mut prices = PriceStore::default();
+    store.apply_snapshot(AuthoritativeSnapshot {
+        balances: vec![
+            BalanceSnapshot {
+                asset: "USDT".to_string(),
+                free: 1000.0,
+                locked: 0.0,
+            },
+            BalanceSnapshot {
+                asset: "BTC".to_string(),
+                free: 0.006,
+                locked: 0.0,
+            },
+        ],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    MarketDataService.apply_price(&mut prices, instrument.clone(), 50000.0);
+
+    let mut service = ExecutionService::default();
+    service.asset_exposure_caps.insert("BTC".to_string(), 0.5);
+    let error = service
+        .submit_target_exposure(
+            &fake,
+            &store,
+            &prices,
+            &instrument,
+            Exposure::new(0.5).expect("bounded exposure"),
+            OrderType::Market,
+        )
+        .expect_err("cap should reject consolidated BTC exposure");
+
+    assert!(matches!(
+        error,
+        ExecutionError::AssetExposureCapExceeded { ref asset, .. } if asset == "BTC"
+    ));
+    assert!(fake.submit_requests().is_empty());
+
+    service
+        .submit_target_exposure(
+            &fake,
+            &store,
+            &prices,
+            &instrument,
+            Exposure::new(0.1).expect("bounded exposure"),
+            OrderType::Market,
+        )
+        .expect("target within cap should submit");
+    assert_eq!(fake.submit_requests().len(), 1);
+    let history = service.history.rows();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].instrument, instrument);
+    assert_eq!(history[0].side, Side::Buy);
+    assert_eq!(history[0].status, OrderStatus::Submitted);
+    assert_eq!(history[0].price, Some(50000.0));
+    assert_eq!(history[0].tag, "manual");
+}
+
+#[test]
+fn overtrading_guard_raises_alarm_and_pauses_entries_for_dense_cluster() {
+    let minute = 60_000;
+    let mut guard = OvertradingGuard::new(3, 10 * 60, 30 * 60);
+
+    assert!(guard.record_entry(0).is_none());
+    assert!(guard.record_entry(4 * minute).is_none());
+    let cluster = guard
+        .record_entry(8 * minute)
+        .expect("third entry inside 10m should trip the alarm");
+
+    assert_eq!(cluster.trades, 3);
+    assert_eq!(cluster.span_secs(), 8 * 60);
+    assert!(!guard.allows_entry(20 * minute));
+    assert!(guard.allows_entry(38 * minute));
+    assert_eq!(
+        detect_trade_clusters([0, 4 * minute, 30 * minute, 31 * minute], 3, 10 * 60),
+        Vec::new()
+    );
+}
+
+#[test]
+fn overtrading_limit_is_off_unless_configured_and_pauses_live_watches() {
+    assert!(OvertradingLimit::parse("off").is_none());
+    assert!(OvertradingLimit::parse("0:900").is_none());
+    let limit = OvertradingLimit::parse("2:600").expect("limit should parse");
+    assert_eq!(limit.pause_secs, 30 * 60);
+
+    let mode = BinanceMode::Demo;
+    let mut store = StrategyStore::default();
+    let watch = store
+        .create_watch(
+            mode,
+            StrategyTemplate::PriceSmaCrossLong,
+            Instrument::new("BTCUSDT"),
+            StrategyStartConfig::default(),
+        )
+        .expect("watch should start");
+    assert!(store.record_trade(mode, watch.id, 0).is_none());
+
+    store.overtrading = Some(limit);
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    assert!(store.record_trade(mode, watch.id, now_ms).is_none());
+    let (paused, cluster) = store
+        .record_trade(mode, watch.id, now_ms + 60_000)
+        .expect("second trade inside 10m should pause the watch");
+
+    assert_eq!(cluster.trades, 2);
+    assert_eq!(paused.state, StrategyWatchState::Paused);
+    assert!(store.apply_degraded(mode, |_| false).is_empty());
+}
+
+#[test]
+fn expectancy_cooldown_shortens_after_winners_and_lengthens_after_losers() {
+    assert!(CooldownScaling::parse("300:5").is_none());
+    assert!(CooldownScaling::parse("5:300:0").is_none());
+    assert!(CooldownScaling::parse("off").is_none());
+    let scaling = CooldownScaling::parse("5:60:2:0").expect("scaling should parse");
+    assert_eq!(scaling.window_trades, 2);
+
+    let mut fixed = ExpectancyCooldown::new(30, None);
+    fixed.record_exit(0, 200.0, 100.0);
+    assert_eq!(fixed.cooldown_secs(), 30);
+    assert!(!fixed.allows_entry(29_999));
+    assert!(fixed.allows_entry(30_000));
+
+    let mut cooldown = ExpectancyCooldown::new(30, Some(scaling));
+    assert_eq!(cooldown.cooldown_secs(), 30);
+    cooldown.record_exit(0, 100.0, 100.0);
+    assert_eq!(cooldown.expectancy_r(), Some(1.0));
+    assert_eq!(cooldown.cooldown_secs(), 15);
+    assert!(cooldown.allows_entry(15_000));
+
+    cooldown.record_exit(100_000, -100.0, 100.0);
+    assert_eq!(cooldown.cooldown_secs(), 30);
+    cooldown.record_exit(200_000, -300.0, 100.0);
+    assert_eq!(cooldown.expectancy_r(), Some(-2.0));
+    assert_eq!(cooldown.cooldown_secs(), 60, "clamped to max");
+    assert!(!cooldown.allows_entry(259_999));
+
+    cooldown.record_exit(300_000, 800.0, 100.0);
+    cooldown.record_exit(400_000, 800.0, 100.0);
+    assert_eq!(cooldown.cooldown_secs(), 5, "clamped to min");
+    cooldown.record_exit(500_000, 50.0, 0.0);
+    assert_eq!(cooldown.expectancy_r(), Some(8.0), "no risk, no sample");
+}
+
+#[test]
+fn live_bar_entries_wait_out_the_same_expectancy_cooldown_as_backtests() {
+    let script = || {
+        Box::new(
+            ScriptStrategy::parse("fn enter() { true }\nfn exit() { false }", Side::Buy)
+                .expect("valid script"),
+        )
+    };
+    let at = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+    let entry = LiveBarEntry {
+        close: 100.0,
+        notional_usdt: 5_000.0,
+        risk_usdt: 60.0,
+    };
+    let pass = EvGate::default().evaluate(1.0);
+
+    let mut fixed = LiveBarStrategy::new(script());
+    fixed.on_entered(entry);
+    assert!(fixed.in_position());
+    fixed.on_exited(at, 98.0, 0.0);
+    assert!(!fixed.in_position());
+    assert_eq!(fixed.check_entry(at, &pass), Ok(()), "no scaling, no wait");
+
+    // -100 USDT on 60 at risk is -1.67R: 30s stretched to 55s.
+    let mut scaled =
+        LiveBarStrategy::new(script()).with_cooldown_scaling(CooldownScaling::parse("5:60:2:0"));
+    assert_eq!(scaled.check_entry(at, &pass), Ok(()));
+    scaled.on_entered(entry);
+    scaled.on_exited(at, 98.0, 0.0);
+    assert_eq!(scaled.cooldown_secs(), 55);
+    assert_eq!(
+        scaled.check_entry(at + Duration::seconds(54), &pass),
+        Err(SkipReason::Cooldown)
+    );
+    assert_eq!(
+        scaled.check_entry(at + Duration::seconds(55), &pass),
+        Ok(())
+    );
+}
+
+#[test]
+fn live_short_entries_below_an_active_ev_gate_are_rejected() {
+    let short = || {
+        Box::new(
+            ScriptStrategy::parse("fn enter() { true }\nfn exit() { false }", Side::Sell)
+                .expect("valid script"),
+        )
+    };
+    let at = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+
+    // 0.8 * 1.5 - 0.2 - 2 * 0.0005 / 0.012 = 0.9167R, as for a long.
+    let strict = LiveBarStrategy::new(short()).with_ev_gate(EvGate::parse("active:0.95").unwrap());
+    let ev = strict.entry_ev(100.0, 0.8, 1.5, 0.0005);
+    assert!((ev.expected_value_r - 0.9167).abs() < 1e-4);
+    assert_eq!(ev.gate_label(), "blocked");
+    assert_eq!(
+        strict.check_entry(at, &ev),
+        Err(SkipReason::LowExpectedValue)
+    );
+
+    let lenient = LiveBarStrategy::new(short()).with_ev_gate(EvGate::parse("active:0.9").unwrap());
+    let ev = lenient.entry_ev(100.0, 0.8, 1.5, 0.0005);
+    assert_eq!(lenient.check_entry(at, &ev), Ok(()));
+    let shadow = LiveBarStrategy::new(short()).with_ev_gate(EvGate::parse("shadow:0.95").unwrap());
+    let ev = shadow.entry_ev(100.0, 0.8, 1.5, 0.0005);
+    assert_eq!(ev.gate_label(), "below");
+    assert_eq!(shadow.check_entry(at, &ev), Ok(()));
+}
+
+#[test]
+fn live_bar_entries_need_the_higher_timeframe_trend_on_their_side() {
+    let at = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+    let pass = EvGate::default().evaluate(1.0);
+    let fed = |side: Side, step: f64| {
+        let script = ScriptStrategy::parse("fn enter() { true }\nfn exit() { false }", side)
+            .expect("valid script");
+        let mut live =
+            LiveBarStrategy::new(Box::new(script)).with_htf_confirm(HtfConfirm::parse("5m:sma2"));
+        assert_eq!(
+            live.check_entry(at, &pass),
+            Err(SkipReason::HtfDisagrees),
+            "no trend yet"
+        );
+        // 21 minutes close 4 five-minute bars.
+        for minute in 0..22 {
+            live.on_price(at + Duration::minutes(minute), 100.0 + step * minute as f64);
+        }
+        live
+    };
+
+    let rising_short = fed(Side::Sell, 1.0);
+    assert_eq!(
+        rising_short.check_entry(at, &pass),
+        Err(SkipReason::HtfDisagrees)
+    );
+    assert_eq!(fed(Side::Buy, 1.0).check_entry(at, &pass), Ok(()));
+    assert_eq!(fed(Side::Sell, -1.0).check_entry(at, &pass), Ok(()));
+}
+
+#[test]
+fn strategy_kpi_lights_follow_live_win_rate_drawdown_and_trade_rate() {
+    let now = Utc::now();
+    let mut kpi = StrategyKpi::new(
+        KpiTargets {
+            min_win_rate: Some(0.55),
+            max_drawdown_usdt: Some(10.0),
+            min_trades_per_week: Some(3.0),
+        },
+        now - Duration::days(7),
+    );
+    assert_eq!(kpi.chip(now), "red(trades_per_week)");
+
+    let long = |signed_qty: f64| PositionSnapshot {
+        instrument: Instrument::new("BTCUSDT"),
+        market: Market::Futures,
+        signed_qty,
+        entry_price: Some(100.0),
+    };
+    for exit in [103.0, 101.0, 99.0, 98.0] {
+        kpi.observe(Some(&long(1.0)), Some(100.0));
+        kpi.observe(None, Some(exit));
+    }
+    assert_eq!((kpi.trades, kpi.wins), (4, 2));
+    assert_eq!(kpi.max_drawdown_usdt, 3.0);
+    assert!(
+        kpi.checks(now)
+            .iter()
+            .all(|check| check.metric != "win_rate"),
+        "win rate waits for enough trades"
+    );
+    assert_eq!(kpi.status(now), Some(KpiLight::Green));
+
+    kpi.observe(Some(&long(1.0)), Some(100.0));
+    kpi.observe(Some(&long(1.0)), Some(92.0));
+    assert_eq!(kpi.max_drawdown_usdt, 11.0, "open losses count");
+    kpi.observe(None, Some(101.0));
+    assert_eq!(kpi.trades, 5);
+    assert_eq!(kpi.win_rate(), Some(0.6));
+    assert_eq!(kpi.chip(now), "red(drawdown_usdt)");
+
+    let mut quiet = StrategyKpi::new(
+        KpiTargets {
+            min_trades_per_week: Some(3.0),
+            ..KpiTargets::default()
+        },
+        now - Duration::days(14),
+    );
+    assert_eq!(quiet.chip(now - Duration::days(14)), "pending");
+    for _ in 0..4 {
+        quiet.observe(Some(&long(1.0)), Some(100.0));
+        quiet.observe(None, Some(100.5));
+    }
+    assert_eq!(quiet.chip(now), "amber(trades_per_week)");
+    assert_eq!(
+        KpiTargets::parse(&["win=55%".to_string(), "dd=150".to_string()])
+            .expect("targets should parse")
+            .to_string(),
+        "win>=55% dd<=150"
+    );
+}
+
+#[test]
+fn r_multiple_tracker_scores_round_trips_against_the_initial_stop() {
+    let position = |signed_qty: f64, entry_price: f64| PositionSnapshot {
+        instrument: Instrument::new("BTCUSDT"),
+        market: Market::Futures,
+        signed_qty,
+        entry_price: Some(entry_price),
+    };
+    let mut tracker = RMultipleTracker::default();
+
+    // Long from 100 with the stop at 98: 2 per unit at risk.
+    assert_eq!(
+        tracker.observe(Some(&position(0.5, 100.0)), Some(100.0), Some(98.0)),
+        None
+    );
+    assert_eq!(tracker.initial_risk_per_unit(), Some(2.0));
+    // A stop trailed up to break-even does not change the initial risk.
+    tracker.observe(Some(&position(0.5, 100.0)), Some(103.0), Some(100.0));
+    assert_eq!(tracker.initial_risk_per_unit(), Some(2.0));
+    assert_eq!(tracker.observe(None, Some(104.0), None), Some(Some(2.0)));
+    assert_eq!(tracker.initial_risk_per_unit(), None);
+
+    // Short from 50 stopped out beyond its stop at 51.
+    tracker.observe(Some(&position(-3.0, 50.0)), Some(50.0), Some(51.0));
+    assert_eq!(tracker.observe(None, Some(51.5), None), Some(Some(-1.5)));
+
+    // Without any stop the trade is counted as unrisked.
+    tracker.observe(Some(&position(1.0, 10.0)), Some(10.0), None);
+    assert_eq!(tracker.observe(None, Some(11.0), None), Some(None));
+
+    let stats = &tracker.stats;
+    assert_eq!((stats.trades, stats.wins, stats.unrisked), (2, 1, 1));
+    assert_eq!(stats.average_r(), Some(0.25));
+    assert_eq!(stats.expectancy_r(), Some(0.25));
+    assert_eq!(
+        stats.summary(),
+        "trades=2 avg=0.25R expectancy=0.25R win=2.00R loss=1.50R best=2.00R worst=-1.50R unrisked=1"
+    );
+    assert_eq!(
+        stats.distribution_label(),
+        "<-1R=1 -1..0R=0 0..1R=0 1..2R=0 2..3R=1 >=3R=0"
+    );
+
+    let mixed = RMultipleStats::from_r_values([Some(2.0), Some(-1.0), Some(0.5), Some(-1.0)]);
+    assert_eq!(mixed.average_r(), Some(0.125));
+    assert_eq!(mixed.average_win_r(), Some(1.25));
+    assert_eq!(mixed.average_loss_r(), Some(1.0));
+    assert_eq!(RMultipleStats::default().summary(), "trades=0 unrisked=0");
+}
+
+#[test]
+fn leaderboard_ranks_by_risk_adjusted_return_and_auto_allocation_stays_in_bounds() {
+    assert!(AutoAllocation::parse("0.5:0.4").is_none());
+    assert!(AutoAllocation::parse("0:0.4:2").is_none());
+    assert!(AutoAllocation::parse("off").is_none());
+    assert_eq!(
+        AutoAllocation::parse("0.05:0.4")
+            .expect("allocation should parse")
+            .shift,
+        0.2
+    );
+
+    let mode = BinanceMode::Demo;
+    let mut store = StrategyStore::default();
+    for symbol in ["BTCUSDT", "ETHUSDT", "SOLUSDT"] {
+        store
+            .create_watch(
+                mode,
+                StrategyTemplate::PriceSmaCrossLong,
+                Instrument::new(symbol),
+                StrategyStartConfig {
+                    seed_capital_usdt: Some(1000.0),
+                    ramp: None,
+                    adapt: None,
+                    script: None,
+                    ..StrategyStartConfig::default()
+                },
+            )
+            .expect("watch should start");
+    }
+    for watch_id in 1..=3 {
+        store.record_fill(watch_id, Side::Buy, 1.0, 100.0, 0.0);
+    }
+    let mark = |store: &mut StrategyStore, symbol: &str, price: f64| {
+        store.update_ledgers(mode, &Instrument::new(symbol), Some(price));
+    };
+    let day = |offset: i64| {
+        chrono::NaiveDate::from_ymd_opt(2024, 3, 1).expect("date") + Duration::days(offset)
+    };
+    let marks = [
+        [100.0, 100.0, 100.0],
+        [110.0, 101.0, 90.0],
+        [115.0, 99.0, 85.0],
+        [130.0, 100.0, 80.0],
+    ];
+    for (offset, prices) in marks.iter().enumerate() {
+        for (symbol, price) in ["BTCUSDT", "ETHUSDT", "SOLUSDT"].iter().zip(prices) {
+            mark(&mut store, symbol, *price);
+        }
+        assert!(store.roll_ledger_days(mode, day(offset as i64)).is_empty());
+    }
+    assert!(store.roll_ledger_days(mode, day(3)).is_empty());
+
+    let board = store.leaderboard(mode);
+    assert_eq!(
+        board.iter().map(|entry| entry.watch_id).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(board[0].days, 3);
+    assert!((board[0].return_pct - 3.0).abs() < 1e-9);
+    assert!(board[0].score.expect("leader score") > 0.0);
+    assert!(board[2].score.expect("laggard score") < 0.0);
+    let equity_before = board.iter().map(|entry| entry.equity_usdt).sum::<f64>();
+
+    store.auto_allocation = AutoAllocation::parse("0.3:0.4:0.5");
+    for (symbol, price) in ["BTCUSDT", "ETHUSDT", "SOLUSDT"]
+        .iter()
+        .zip([130.0, 100.0, 80.0])
+    {
+        mark(&mut store, symbol, price);
+    }
+    let shifts = store.roll_ledger_days(mode, day(4));
+    assert_eq!(shifts.len(), 3);
+    assert!(shifts[0].delta_usdt > 0.0);
+    assert!(shifts[2].delta_usdt < 0.0);
+    let board = store.leaderboard(mode);
+    let equity_after = board.iter().map(|entry| entry.equity_usdt).sum::<f64>();
+    assert!((equity_after - equity_before).abs() < 1e-6);
+    assert!(board
+        .iter()
+        .all(|entry| (0.3 - 1e-9..=0.4 + 1e-9).contains(&entry.weight)));
+    assert!((board[0].weight - 0.4).abs() < 1e-9);
+    let leader = store.get(mode, 1).and_then(|watch| watch.ledger.as_ref());
+    assert_eq!(leader.map(|ledger| ledger.unrealized_pnl_usdt), Some(30.0));
+}
+
+#[test]
+fn size_ramp_climbs_only_on_round_trips_that_do_not_lose() {
+    assert!(SizeRamp::parse("1:5").is_none());
+    assert!(SizeRamp::parse("0.25:0").is_none());
+    assert!(SizeRamp::parse("off").is_none());
+    let mut ramp = SizeRamp::parse("0.25:3").expect("ramp should parse");
+    assert_eq!(ramp.fraction(), 0.25);
+
+    let long = |signed_qty: f64| PositionSnapshot {
+        instrument: Instrument::new("BTCUSDT"),
+        market: Market::Futures,
+        signed_qty,
+        entry_price: Some(100.0),
+    };
+    ramp.observe(Some(&long(1.0)), Some(100.0));
+    assert_eq!(ramp.observe(None, Some(98.0)), Some(-2.0));
+    assert_eq!(ramp.qualifying_trades, 0);
+    ramp.observe(Some(&long(1.0)), Some(100.0));
+    assert_eq!(ramp.observe(None, Some(100.0)), Some(0.0));
+    assert_eq!(ramp.fraction(), 0.5);
+    for _ in 0..3 {
+        ramp.observe(Some(&long(1.0)), Some(100.0));
+        ramp.observe(None, Some(101.0));
+    }
+    assert!(ramp.is_complete());
+    assert_eq!(ramp.fraction(), 1.0);
+    assert_eq!(ramp.label(), "3/3 size_fraction=1.00");
+    ramp.restart();
+    assert_eq!(ramp.fraction(), 0.25);
+}
+
+#[test]
+fn adaptive_tuning_nudges_within_drift_and_rolls_back() {
+    assert!(AdaptiveTuning::parse("0").is_none());
+    assert!(AdaptiveTuning::parse("10:0").is_none());
+    assert!(AdaptiveTuning::parse("off").is_none());
+    assert_eq!(
+        AdaptiveTuning::parse("20"),
+        Some(AdaptiveTuning::new(20, 0.1, 0.5))
+    );
+    let mut config = StrategyStartConfig {
+        risk_pct: 0.01,
+        win_rate: 0.6,
+        r_multiple: 2.0,
+        max_entry_slippage_pct: 0.001,
+        seed_capital_usdt: None,
+        ramp: None,
+        adapt: None,
+        script: None,
+    };
+    let mut tuning = AdaptiveTuning::parse("2:0.1:0.15").expect("tuning should parse");
+    tuning.anchor(&config);
+
+    let long = PositionSnapshot {
+        instrument: Instrument::new("BTCUSDT"),
+        market: Market::Futures,
+        signed_qty: 1.0,
+        entry_price: Some(100.0),
+    };
+    let losing_trade = |tuning: &mut AdaptiveTuning, config: &mut StrategyStartConfig| {
+        tuning.observe(config, Some(&long), Some(100.0));
+        tuning.observe(config, None, Some(98.0))
+    };
+    assert!(losing_trade(&mut tuning, &mut config).is_none());
+    let adaptation = losing_trade(&mut tuning, &mut config).expect("window should adapt");
+    assert_eq!(adaptation.trades, 2);
+    assert_eq!(adaptation.win_rate, 0.0);
+    assert_eq!(adaptation.changes[0].param, TunedParam::RiskPct);
+    assert!((config.risk_pct - 0.009).abs() < 1e-12);
+    assert!((config.r_multiple - 1.8).abs() < 1e-12);
+
+    // The next window would take both under the 15% drift floor.
+    losing_trade(&mut tuning, &mut config);
+    losing_trade(&mut tuning, &mut config).expect("window should adapt");
+    assert!((config.risk_pct - 0.0085).abs() < 1e-12);
+    assert!((config.r_multiple - 1.7).abs() < 1e-12);
+    assert_eq!(
+        tuning.label(),
+        "0/2 trades step=0.10 drift=0.15 adaptations=2"
+    );
+
+    tuning.rollback(&mut config).expect("rollback should apply");
+    assert!((config.risk_pct - 0.009).abs() < 1e-12);
+    assert!((config.r_multiple - 1.8).abs() < 1e-12);
+    tuning.rollback(&mut config).expect("rollback should apply");
+    assert_eq!(config.risk_pct, 0.01);
+    assert_eq!(config.r_multiple, 2.0);
+    assert!(tuning.rollback(&mut config).is_none());
+}
+
+#[test]
+fn order_throttle_drops_and_counts_entries_over_per_minute_limit() {
+    let second = 1_000;
+    let mut throttle = OrderThrottle::new(3);
+
+    assert!(throttle.try_admit(0));
+    assert!(throttle.try_admit(10 * second));
+    assert!(throttle.try_admit(20 * second));
+    assert!(!throttle.try_admit(30 * second));
+    assert!(!throttle.try_admit(59 * second));
+    assert!(throttle.try_admit(60 * second));
+    assert_eq!(throttle.dropped(), 2);
+
+    let mut unlimited = OrderThrottle::new(0);
+    assert!((0..10).all(|i| unlimited.try_admit(i)));
+    assert_eq!(unlimited.dropped(), 0);
+}
+
+#[test]
+fn slippage_guard_rounds_limit_inside_band_and_flags_adverse_drift() {
+    let guard = SlippageGuardConfig::new(20.0);
+
+    assert_eq!(guard.limit_price(Side::Buy, 50_000.0, 0.1), 50_100.0);
+    assert_eq!(guard.limit_price(Side::Sell, 1.2345, 0.0001), 1.2321);
+    assert_eq!(guard.limit_price(Side::Buy, 3_000.0, 0.0), 3_006.0);
+    assert!(guard.exceeds_band(Side::Buy, 50_000.0, 50_150.0));
+    assert!(!guard.exceeds_band(Side::Sell, 50_000.0, 50_150.0));
+    assert!(guard.exceeds_band(Side::Sell, 50_000.0, 49_850.0));
+}
+
+#[test]
+fn execution_service_rejects_entry_when_last_price_ran_past_slippage_band() {
+    let instrument = Instrument::new("BTCUSDT");
+    let fake = fake_exchange();
+    fake.set_symbol_rules(
+        instrument.clone(),
+        Market::Futures,
+        SymbolRules {
+            min_qty: 0.001,
+            max_qty: 100.0,
+            step_size: 0.001,
+            tick_size: 0.1,
+        },
+    );
+    fake.set_last_price(instrument.clone(), Market::Futures, 50_150.0);
+    let mut prices = PriceStore::default();
+    MarketDataService.apply_price(&mut prices, instrument.clone(), 50_000.0);
+    let mut store = PortfolioStateStore::default();
+    store.apply_snapshot(AuthoritativeSnapshot {
+        balances: vec![BalanceSnapshot {
+            asset: "USDT".to_string(),
+            free: 1000.0,
+            locked: 0.0,
+        }],
+        positions: vec![],
+        open_orders: vec![],
+    });
+    let mut service = ExecutionService {
+        slippage_guard: Some(SlippageGuardConfig::new(20.0)),
+        ..ExecutionService::default()
+    };
+
+    let error = service
+        .submit_target_exposure(
+            &fake,
+            &store,
+            &prices,
+            &instrument,
+            Exposure::new(0.5).expect("bounded exposure"),
+            OrderType::Market,
+        )
+        .expect_err("buy sized before a 30 bps jump should be rejected");
+
+    assert!(matches!(
+        error,
+        ExecutionError::SlippageBandExceeded { drift_bps, .. } if (drift_bps - 30.0).abs() < 1e-9
+    ));
+    assert!(fake.submit_requests().is_empty());
+    assert!(!service.last_slippage_check.expect("check recorded").passed);
+}
+
+#[test]
+fn synthetic_instrument_parses_weighted_legs_and_prices_the_spread() {
+    let synthetics = parse_synthetic_instruments(
+        "btc_basis=BTCUSDT@futures-BTCUSDT@spot;eth_btc=ETHUSDT@perp-0.5*BTCUSDT@futures;bad=BTCUSDT@spot",
+    );
+
+    assert_eq!(synthetics.len(), 2);
+    let basis = &synthetics[&Instrument::new("BTC_BASIS")];
+    assert_eq!(basis.label(), "BTCUSDT@futures-BTCUSDT@spot");
+    assert_eq!(basis.price(&[50_100.0, 50_000.0]), 100.0);
+    let pair = &synthetics[&Instrument::new("ETH_BTC")];
+    assert_eq!(pair.legs[1].weight, -0.5);
+    assert_eq!(pair.legs[0].market, Market::Futures);
+    assert_eq!(
+        pair.leg_targets(0.4)
+            .iter()
+            .map(|(_, target)| *target)
+            .collect::<Vec<_>>(),
+        vec![0.4, -0.2]
+    );
+}
+
+#[test]
+fn sealed_store_file_migrates_plaintext_and_rejects_the_wrong_key() {
+    let dir = std::env::temp_dir().join(format!(
+        "sandbox-quant-sealed-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let path = dir.join("price-alerts.json");
+    std::fs::create_dir_all(&dir).expect("create dir");
+    std::fs::write(&path, br#"{"next_id":2,"alerts":[]}"#).expect("write plaintext");
+    let sealer = Sealer::from_secret(b"correct horse").expect("sealer");
+
+    let migrated = read_store_file(&path, Some(&sealer)).expect("read plaintext");
+    let on_disk = std::fs::read(&path).expect("read disk");
+
+    assert_eq!(migrated, br#"{"next_id":2,"alerts":[]}"#);
+    assert!(is_sealed(&on_disk));
+    assert!(!String::from_utf8_lossy(&on_disk).contains("next_id"));
+    assert!(!backup_path(&path).exists());
+    let reopened = Sealer::from_secret(b"correct horse").expect("sealer");
+    assert_eq!(
+        read_store_file(&path, Some(&reopened)).expect("open sealed"),
+        migrated
+    );
+    let wrong = Sealer::from_secret(b"wrong").expect("sealer");
+    assert!(read_store_file(&path, Some(&wrong)).is_err());
+    assert!(read_store_file(&path, None).is_err());
+    assert!(write_store_file(&path, b"{}", None).is_err());
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn sealed_jsonl_migration_keeps_line_order_and_mixed_lines_readable() {
+    let dir = std::env::temp_dir().join(format!(
+        "sandbox-quant-sealed-jsonl-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let path = dir.join("operator-events.jsonl");
+    std::fs::create_dir_all(&dir).expect("create dir");
+    let sealer = Sealer::from_secret(b"journal").expect("sealer");
+    let sealed_line = seal_line(r#"{"kind":"c"}"#, Some(&sealer)).expect("seal");
+    std::fs::write(
+        &path,
+        format!("{{\"kind\":\"a\"}}\n{{\"kind\":\"b\"}}\n{sealed_line}\n"),
+    )
+    .expect("write journal");
+
+    assert_eq!(migrate_jsonl(&path, &sealer).expect("migrate"), 2);
+    assert_eq!(migrate_jsonl(&path, &sealer).expect("migrate again"), 0);
+    let text = std::fs::read_to_string(&path).expect("read disk");
+    assert!(text.lines().all(|line| is_sealed(line.as_bytes())));
+    assert_eq!(
+        read_jsonl_lines(&path, Some(&sealer)).expect("read lines"),
+        vec![
+            r#"{"kind":"a"}"#.to_string(),
+            r#"{"kind":"b"}"#.to_string(),
+            r#"{"kind":"c"}"#.to_string(),
+        ]
+    );
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn store_files_fall_back_to_the_last_good_backup_when_corrupted() {
+    let dir = std::env::temp_dir().join(format!(
+        "sandbox-quant-atomic-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let path = dir.join("price-alerts.json");
+
+    write_store_file(&path, br#"{"next_id":1,"alerts":[]}"#, None).expect("first write");
+    write_store_file(&path, br#"{"next_id":2,"alerts":[]}"#, None).expect("second write");
+    assert!(backup_path(&path).exists());
+    assert_eq!(
+        read_store_file(&path, None).expect("read"),
+        br#"{"next_id":2,"alerts":[]}"#
+    );
+
+    let written = std::fs::read(&path).expect("read disk");
+    std::fs::write(&path, &written[..written.len() / 2]).expect("truncate");
+    assert_eq!(
+        read_store_file(&path, None).expect("fall back"),
+        br#"{"next_id":1,"alerts":[]}"#
+    );
+    let mut flipped = with_checksum(br#"{"next_id":9,"alerts":[]}"#);
+    flipped[11] = b'8';
+    std::fs::write(&path, flipped).expect("flip byte");
+    assert_eq!(
+        read_store_file(&path, None).expect("checksum mismatch"),
+        br#"{"next_id":1,"alerts":[]}"#
+    );
+
+    std::fs::write(backup_path(&path), b"{\"next_id\":1\nsqsum1:00\n").expect("bad backup");
+    assert!(matches!(
+        read_store_file(&path, None),
+        Err(StorageError::CorruptStore { .. })
+    ));
+
+    std::fs::write(&path, br#"{"next_id":3,"alerts":[]}"#).expect("legacy file");
+    assert_eq!(
+        read_store_file(&path, None).expect("legacy"),
+        br#"{"next_id":3,"alerts":[]}"#
+    );
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn sealed_jsonl_reads_skip_a_torn_final_line() {
+    let dir = std::env::temp_dir().join(format!(
+        "sandbox-quant-torn-jsonl-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let path = dir.join("operator-events.jsonl");
+    std::fs::create_dir_all(&dir).expect("create dir");
+    let sealer = Sealer::from_secret(b"journal").expect("sealer");
+    let first = seal_line(r#"{"kind":"a"}"#, Some(&sealer)).expect("seal");
+    let second = seal_line(r#"{"kind":"b"}"#, Some(&sealer)).expect("seal");
+    std::fs::write(&path, format!("{first}\n{}", &second[..second.len() / 2]))
+        .expect("write journal");
+
+    assert_eq!(
+        read_jsonl_lines(&path, Some(&sealer)).expect("read lines"),
+        vec![r#"{"kind":"a"}"#.to_string()]
+    );
+    std::fs::write(&path, format!("{}\n{second}\n", &first[..first.len() / 2]))
+        .expect("write journal");
+    assert!(read_jsonl_lines(&path, Some(&sealer)).is_err());
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn event_replay_reads_sealed_lines_and_skips_newer_schemas() {
+    let dir = std::env::temp_dir().join(format!(
+        "sandbox-quant-event-replay-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let path = dir.join("operator-events.jsonl");
+    std::fs::create_dir_all(&dir).expect("create dir");
+    let sealer = Sealer::from_secret(b"journal").expect("sealer");
+    let sealed_line = seal_line(
+        r#"{"v":1,"kind":"app.strategy.started","payload":{"watch_id":1}}"#,
+        Some(&sealer),
+    )
+    .expect("seal");
+    std::fs::write(
+        &path,
+        format!(
+            "{{\"kind\":\"app.execution.completed\",\"payload\":{{}}}}\n{sealed_line}\n{{\"v\":2,\"kind\":\"app.execution.completed\"}}\n"
+        ),
+    )
+    .expect("write journal");
+
+    let replay = replay_event_file(&path, Some(&sealer), &Default::default()).expect("replay");
+    assert_eq!(
+        replay
+            .events
+            .iter()
+            .map(|event| (event.version, event.record.kind.as_str()))
+            .collect::<Vec<_>>(),
+        vec![(0, "app.execution.completed"), (1, "app.strategy.started")]
+    );
+    assert_eq!(replay.skipped, 1);
+    assert!(replay_event_file(&path, None, &Default::default()).is_err());
+    let strategy_only = replay_event_file(
+        &path,
+        Some(&sealer),
+        &std::collections::BTreeSet::from(["strategy".to_string()]),
+    )
+    .expect("filtered replay");
+    assert_eq!(strategy_only.summary().domains.len(), 1);
+    assert!(PersistedEvent::parse(r#"{"v":1,"payload":{}}"#).is_none());
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn portfolio_store_tracks_vwap_twap_and_break_even_across_scale_ins() {
+    let instrument = Instrument::new("BTCUSDT");
+    let snapshot = |signed_qty: f64, entry_price: f64| AuthoritativeSnapshot {
+        balances: vec![],
+        positions: vec![PositionSnapshot {
+            instrument: instrument.clone(),
+            market: Market::Futures,
+            signed_qty,
+            entry_price: Some(entry_price),
+        }],
+        open_orders: vec![],
+    };
+    let start = chrono::Utc::now();
+    let minutes = |count: i64| start + chrono::Duration::minutes(count);
+    let mut store = PortfolioStateStore::default();
+
+    store.apply_snapshot_at(snapshot(1.0, 100.0), start);
+    store.apply_snapshot_at(snapshot(2.0, 105.0), minutes(30));
+    store.apply_snapshot_at(snapshot(1.0, 105.0), minutes(45));
+
+    let stats = store.entry_stats.get(&instrument).expect("entry stats");
+    assert_eq!(stats.fills.len(), 2);
+    assert!((stats.fills[1].price - 110.0).abs() < 1e-9);
+    assert!((stats.vwap_entry() - 105.0).abs() < 1e-9);
+    assert!((stats.twap_entry(minutes(60)) - 102.5).abs() < 1e-9);
+    assert!((stats.break_even_price(0.0005) - 105.105_052_526).abs() < 1e-6);
+
+    store.apply_snapshot_at(snapshot(-1.0, 104.0), minutes(70));
+    let stats = store.entry_stats.get(&instrument).expect("flipped stats");
+    assert_eq!(stats.fills.len(), 1);
+    assert!(stats.break_even_price(0.0005) < 104.0);
+
+    store.apply_snapshot_at(
+        AuthoritativeSnapshot {
+            balances: vec![],
+            positions: vec![],
+            open_orders: vec![],
+        },
+        minutes(80),
+    );
+    assert!(store.entry_stats.get(&instrument).is_none());
+}
+
+#[test]
+fn ev_estimator_shrinks_holding_ev_as_price_nears_the_take_profit() {
+    let short = EvEstimator::new(100.0, 101.0, 98.0, 0.8);
+    assert_eq!(short.r_multiple(100.0), 0.0);
+    assert!((short.expected_value_r(100.0) - 1.4).abs() < 1e-9);
+    assert!((short.r_multiple(98.5) - 1.5).abs() < 1e-9);
+    assert!((short.expected_value_r(98.5) + 0.1).abs() < 1e-9);
+    assert_eq!(
+        EvEstimator::new(100.0, 100.0, 102.0, 0.5).expected_value_r(101.0),
+        0.0
+    );
+
+    let profiles = parse_hold_ev_profiles(
+        "default=win:0.5,suggest:-0.25;Scalp=win:0.6,suggest:-0.1,exit:-0.4;bad=win:2,suggest:0;loose=exit:-1",
+    );
+    assert_eq!(
+        profiles.keys().collect::<Vec<_>>(),
+        vec!["default", "scalp"]
+    );
+    let scalp = profiles["scalp"];
+    assert_eq!(scalp.label(), "win:0.6,suggest:-0.1,exit:-0.4");
+    assert_eq!(scalp.action(0.0), HoldEvAction::Hold);
+    assert_eq!(scalp.action(-0.2), HoldEvAction::SuggestExit);
+    assert_eq!(scalp.action(-0.5), HoldEvAction::AutoExit);
+    assert_eq!(profiles["default"].action(-5.0), HoldEvAction::SuggestExit);
+}
+
+#[test]
+fn ev_gate_blocks_entries_only_in_active_mode() {
+    assert_eq!(EvGate::parse("shadow"), Some(EvGate::default()));
+    assert_eq!(EvGate::parse("enforce:1"), None);
+    assert_eq!(EvGate::parse("active:nan"), None);
+    let active = EvGate::parse(" Active:0.25 ").expect("active gate");
+    assert_eq!(active.mode, EvGateMode::Active);
+    assert_eq!(active.label(), "active:0.25");
+
+    let shadow = EvGate {
+        mode: EvGateMode::Shadow,
+        ..active
+    };
+    assert!(active.evaluate(0.1).gate_blocked);
+    assert_eq!(active.evaluate(0.1).gate_label(), "blocked");
+    assert!(!shadow.evaluate(0.1).gate_blocked);
+    assert_eq!(shadow.evaluate(0.1).gate_label(), "below");
+    assert_eq!(active.evaluate(0.25).gate_label(), "pass");
+}
+
+#[test]
+fn ev_probability_models_are_selectable_per_profile_and_versioned() {
+    let features = EvFeatures {
+        r_multiple: 1.0,
+        reward_left_r: 1.0,
+    };
+    let beta = BetaBinomial {
+        prior_win_rate: 0.5,
+        prior_strength: 20.0,
+        wins: 8,
+        losses: 2,
+    };
+    assert!((beta.win_probability(&features) - 0.6).abs() < 1e-9);
+    assert_eq!(beta.version(), "beta_binomial/v1:prior=0.5,strength=20");
+    let logistic = LogisticWinModel {
+        intercept: 0.0,
+        r_weight: 0.5,
+        reward_weight: -0.5,
+    };
+    assert!((logistic.win_probability(&features) - 0.5).abs() < 1e-9);
+
+    let thin = EmpiricalBootstrap {
+        outcomes: vec![true, true],
+        quantile: 0.5,
+        prior_win_rate: 0.4,
+    };
+    assert_eq!(thin.win_probability(&features), 0.4);
+    let record = [
+        true, false, true, true, false, true, false, true, true, false,
+    ];
+    let at = |quantile: f64| {
+        EmpiricalBootstrap {
+            outcomes: record.to_vec(),
+            quantile,
+            prior_win_rate: 0.4,
+        }
+        .win_probability(&features)
+    };
+    assert!(at(0.1) < at(0.5) && at(0.5) < at(0.9));
+    assert!((at(0.5) - 0.6).abs() <= 0.1);
+    assert_eq!(at(0.25), at(0.25));
+
+    // A logistic model that trusts the trend more as the move extends.
+    let trending = EvEstimator::with_model(
+        100.0,
+        99.0,
+        103.0,
+        EvModel::parse("logistic/0/1/0")
+            .expect("logistic spec")
+            .build(0.5, &[]),
+    );
+    assert_eq!(trending.win_probability(100.0), 0.5);
+    assert!(trending.win_probability(102.0) > 0.85);
+    assert!(
+        trending.expected_value_r(102.0)
+            > EvEstimator::new(100.0, 99.0, 103.0, 0.5).expected_value_r(102.0)
+    );
+    assert_eq!(
+        trending.model.version(),
+        "logistic/v1:intercept=0,r=1,reward=0"
+    );
+
+    assert_eq!(EvModel::parse("beta"), Some(EvModel::default()));
+    assert_eq!(EvModel::parse("bootstrap/1.5"), None);
+    assert_eq!(EvModel::parse("logistic/1"), None);
+    assert_eq!(
+        EvModel::parse("bootstrap")
+            .expect("bootstrap spec")
+            .build(0.55, &[true; 5])
+            .version(),
+        "bootstrap/v1:q=0.5,resamples=200"
+    );
+    let profiles = parse_hold_ev_profiles(
+        "default=win:0.5,suggest:-0.25;swing=win:0.55,suggest:-0.2,model:bootstrap/0.25;bad=win:0.5,suggest:0,model:magic",
+    );
+    assert_eq!(
+        profiles.keys().collect::<Vec<_>>(),
+        vec!["default", "swing"]
+    );
+    assert_eq!(profiles["default"].label(), "win:0.5,suggest:-0.25");
+    assert_eq!(
+        profiles["swing"].label(),
+        "win:0.55,suggest:-0.2,model:bootstrap/0.25"
+    );
+}
+
+#[test]
+fn oco_client_order_ids_carry_list_id_across_rearms() {
+    assert_eq!(oco_client_order_id(7, OcoLeg::TakeProfit, 0), "oco-7-tp");
+    assert_eq!(oco_client_order_id(7, OcoLeg::StopLoss, 2), "oco-7-sl-r2");
+    assert_eq!(oco_list_id("oco-7-sl-r2"), Some(7));
+    assert_eq!(
+        oco_resized_order_id(7, OcoLeg::TakeProfit, 1),
+        "oco-7-tp-s1"
+    );
+    assert_eq!(oco_list_id("oco-7-tp-s1"), Some(7));
+    assert_eq!(oco_list_id("web-1"), None);
+}
+
+#[test]
+fn htf_trend_rolls_base_candles_into_closed_higher_timeframe_bars() {
+    assert_eq!(HtfConfirm::parse("off"), None);
+    assert_eq!(HtfConfirm::parse("1h:ema"), None);
+    let hourly = HtfConfirm::parse("1H").expect("timeframe alone");
+    assert_eq!(hourly.indicator, HtfIndicator::Ema(20));
+    assert_eq!(hourly.label(), "1h:ema20");
+    assert_eq!(HtfConfirm::parse("90m:sma30").unwrap().label(), "90m:sma30");
+
+    let minute = |index: i64, close: f64| DerivedKlineRow {
+        open_time_ms: index * 60_000,
+        close_time_ms: index * 60_000 + 59_999,
+        open: close,
+        high: close,
+        low: close,
+        close,
+        volume: 1.0,
+        quote_volume: close,
+        trade_count: 1,
+    };
+    let mut trend = HtfTrend::new(HtfConfirm::parse("5m:sma2").unwrap());
+    // 5m closes 100, 100, then 101 -> SMA2 100, 100.5.
+    for (index, close) in [100.0; 10].into_iter().chain([102.0, 101.0]).enumerate() {
+        trend.on_candle_close(&minute(index as i64, close));
+    }
+    assert_eq!(trend.slope(), None);
+    assert!(!trend.confirms(Side::Buy));
+    for index in 12..15 {
+        trend.on_candle_close(&minute(index, 101.0));
+    }
+    assert_eq!(trend.slope(), Some(0.5));
+    assert!(trend.confirms(Side::Buy));
+    assert!(!trend.confirms(Side::Sell));
+}
+
+#[test]
+fn candle_modes_redraw_klines_as_heikin_ashi_and_renko() {
+    let kline = |second: i64, open: f64, high: f64, low: f64, close: f64| DerivedKlineRow {
+        open_time_ms: second * 1_000,
+        close_time_ms: second * 1_000 + 999,
+        open,
+        high,
+        low,
+        close,
+        volume: 2.0,
+        quote_volume: 2.0 * close,
+        trade_count: 4,
+    };
+    assert_eq!(CandleMode::parse("HA"), Some(CandleMode::HeikinAshi));
+    assert_eq!(
+        CandleMode::parse("renko"),
+        Some(CandleMode::Renko(RenkoBrick::Percent(0.1)))
+    );
+    assert_eq!(
+        CandleMode::parse("renko:0.5%"),
+        Some(CandleMode::Renko(RenkoBrick::Percent(0.5)))
+    );
+    assert_eq!(CandleMode::parse("renko:0"), None);
+    assert_eq!(
+        CandleMode::Renko(RenkoBrick::Price(50.0)).label(),
+        "renko:50"
+    );
+    assert_eq!(
+        CandleMode::HeikinAshi.next(RenkoBrick::Price(50.0)),
+        CandleMode::Renko(RenkoBrick::Price(50.0))
+    );
+
+    let ha = heikin_ashi(&[
+        kline(1, 100.0, 104.0, 98.0, 102.0),
+        kline(2, 102.0, 103.0, 101.0, 103.0),
+    ]);
+    assert_eq!((ha[0].open, ha[0].close), (101.0, 101.0));
+    assert_eq!((ha[0].high, ha[0].low), (104.0, 98.0));
+    assert_eq!((ha[1].open, ha[1].close), (101.0, 102.25));
+    assert_eq!(ha[1].volume, 2.0);
+
+    let flat = |second: i64, close: f64| kline(second, close, close, close, close);
+    let klines = [
+        flat(1, 100.0),
+        flat(2, 100.4),
+        flat(3, 102.5),
+        flat(4, 101.8),
+        flat(5, 99.9),
+    ];
+    let bricks = renko(&klines, RenkoBrick::Price(1.0));
+    assert_eq!(
+        bricks
+            .iter()
+            .map(|brick| (brick.open, brick.close))
+            .collect::<Vec<_>>(),
+        vec![(100.0, 101.0), (101.0, 102.0), (101.0, 100.0)]
+    );
+    // Two bricks in one candle share its span and the volume since the last brick.
+    assert_eq!(bricks[0].open_time_ms, 3_000);
+    assert_eq!(bricks[1].close_time_ms, 3_999);
+    assert_eq!(bricks[0].volume, 3.0);
+    assert_eq!(
+        CandleMode::Renko(RenkoBrick::Price(1.0)).signal_closes(&klines),
+        vec![100.0, 100.0, 102.0, 102.0, 100.0]
+    );
+}
+
+#[test]
+fn iceberg_slices_carry_their_parent_and_report_progress() {
+    assert_eq!(iceberg_client_order_id(4, 3), "ice-4-3");
+    assert_eq!(iceberg_parent_id("ice-4-3"), Some(4));
+    assert_eq!(iceberg_parent_id("oco-4-tp"), None);
+
+    let iceberg = IcebergOrder {
+        parent_id: 4,
+        instrument: Instrument::new("BTCUSDT"),
+        market: Market::Futures,
+        side: Side::Buy,
+        price: 64000.0,
+        total_qty: 1.0,
+        visible_qty: 0.2,
+        filled_qty: 0.4,
+        slices: 3,
+        child_order_id: "ice-4-3".to_string(),
+        child_qty: 0.2,
+        child_executed_qty: 0.1,
+        child_status: OrderStatus::Submitted,
+    };
+    assert_eq!(iceberg.progress_pct(), 50.0);
+    assert_eq!(iceberg.remaining_qty(), 0.6);
+    assert_eq!(
+        IcebergOrder {
+            total_qty: 0.5,
+            ..iceberg
+        }
+        .remaining_qty(),
+        0.1
+    );
+}
+
+#[test]
+fn paper_shadow_splits_divergence_into_slippage_and_fill_gap() {
+    assert_eq!(shadow_client_order_id(3, 12), "shadow-3-12");
+    assert_eq!(shadow_watch_id("shadow-3-12"), Some(3));
+    assert_eq!(shadow_watch_id("ice-4-3"), None);
+    assert_eq!(PaperShadowConfig::parse("off"), None);
+    assert_eq!(PaperShadowConfig::parse("-1"), None);
+    assert_eq!(
+        PaperShadowConfig::parse("on"),
+        Some(PaperShadowConfig {
+            modeled_slippage_bps: 0.0
+        })
+    );
+    let config = PaperShadowConfig::parse("2").expect("bps model");
+    assert!((config.paper_price(Side::Buy, 64000.0) - 64012.8).abs() < 1e-6);
+    assert!((config.paper_price(Side::Sell, 64000.0) - 63987.2).abs() < 1e-6);
+
+    let order = ShadowOrder {
+        client_order_id: "shadow-3-1".to_string(),
+        watch_id: 3,
+        instrument: Instrument::new("BTCUSDT"),
+        market: Market::Futures,
+        side: Side::Buy,
+        qty: 1.0,
+        decision_price: 100.0,
+        paper_price: 100.0,
+        live_filled_qty: 0.5,
+        live_notional: 50.5,
+    };
+    let runs = shadow_divergence(std::slice::from_ref(&order), 0.0, |_| Some(104.0));
+    assert_eq!(runs.len(), 1);
+    let run = &runs[0];
+    assert!((run.paper_pnl_usdt - 4.0).abs() < 1e-9);
+    assert!((run.live_pnl_usdt - 1.5).abs() < 1e-9);
+    assert!((run.slippage_usdt + 0.5).abs() < 1e-9);
+    assert!((run.fill_gap_usdt + 2.0).abs() < 1e-9);
+    assert!((run.divergence_usdt() - (run.slippage_usdt + run.fill_gap_usdt)).abs() < 1e-9);
+    assert!((run.model_error_bps().expect("live fill") - 100.0).abs() < 1e-9);
+    // Orders without a mark price are left out.
+    assert!(shadow_divergence(&[order], 0.0, |_| None).is_empty());
+}
+
+#[test]
+fn dom_ladder_merges_book_and_own_orders_highest_price_first() {
+    let depth = OrderBookDepth {
+        bids: vec![(100.0, 3.0), (99.5, 1.0)],
+        asks: vec![(100.5, 2.0), (101.0, 4.0)],
+    };
+    let own = OpenOrder {
+        order_id: None,
+        client_order_id: "web-1".to_string(),
+        instrument: Instrument::new("BTCUSDT"),
+        market: Market::Futures,
+        side: Side::Buy,
+        price: Some(99.5),
+        orig_qty: 0.5,
+        executed_qty: 0.0,
+        reduce_only: false,
+        status: OrderStatus::Submitted,
+    };
+
+    let ladder = build_dom_ladder(&depth, &[own], 1);
+
+    let prices = ladder.iter().map(|level| level.price).collect::<Vec<_>>();
+    assert_eq!(prices, vec![100.5, 100.0, 99.5]);
+    assert_eq!(ladder[2].bid_qty, 0.0);
+    assert_eq!(ladder[2].own_orders.len(), 1);
+    assert_eq!(
+        DomPrice::parse("a2").and_then(|price| price.resolve(&depth)),
+        Some(101.0)
+    );
+    assert_eq!(
+        DomPrice::parse("b3").and_then(|price| price.resolve(&depth)),
+        None
+    );
+    assert_eq!(round_to_tick(100.26, 0.1), 100.3);
+}
+
+#[test]
+fn basis_watch_enters_on_premium_or_funding_and_accrues_carry() {
+    let quote = |perp: f64, funding_rate: Option<f64>| BasisQuote {
+        spot: 50_000.0,
+        perp,
+        funding_rate,
+    };
+    assert!((quote(50_150.0, None).basis_bps() - 30.0).abs() < 1e-9);
+    assert!((quote(50_000.0, Some(0.0001)).funding_apr_pct().unwrap() - 10.95).abs() < 1e-9);
+
+    let mut store = BasisArbStore::default();
+    let config = BasisArbConfig {
+        entry_bps: 30.0,
+        exit_bps: 5.0,
+        funding_apr_pct: Some(15.0),
+        target: 0.1,
+    };
+    let mut watch = store
+        .start(
+            BinanceMode::Demo,
+            Instrument::new("BTCUSDT"),
+            config.clone(),
+        )
+        .expect("first watch");
+    assert!(store
+        .start(BinanceMode::Demo, Instrument::new("BTCUSDT"), config)
+        .is_none());
+    assert_eq!(watch.synthetic_name(), Instrument::new("BTCUSDT_BASIS"));
+
+    assert_eq!(
+        watch.decide(&quote(50_100.0, Some(0.0001))),
+        BasisDecision::Hold
+    );
+    assert_eq!(watch.decide(&quote(50_150.0, None)), BasisDecision::Enter);
+    assert_eq!(
+        watch.decide(&quote(50_000.0, Some(0.0002))),
+        BasisDecision::Enter
+    );
+
+    let opened_at = Utc.with_ymd_and_hms(2026, 3, 1, 7, 0, 0).unwrap();
+    let mut carry = BasisCarry::open(30.0, 1_000.0, opened_at);
+    carry.accrue_funding(Some(0.0001), opened_at + Duration::minutes(30));
+    assert_eq!(carry.funding_intervals, 0);
+    carry.accrue_funding(Some(0.0001), opened_at + Duration::hours(9));
+    assert_eq!(carry.funding_intervals, 2);
+    assert!((carry.funding_accrued_usdt - 0.2).abs() < 1e-9);
+    assert!((carry.carry_pnl_usdt(5.0) - 2.7).abs() < 1e-9);
+
+    watch.carry = Some(carry);
+    assert_eq!(watch.decide(&quote(50_100.0, None)), BasisDecision::Hold);
+    assert_eq!(
+        watch.decide(&quote(50_025.0, Some(0.0002))),
+        BasisDecision::Hold
+    );
+    assert_eq!(
+        watch.decide(&quote(50_025.0, Some(0.0001))),
+        BasisDecision::Exit
+    );
+}
+
+#[test]
+fn earn_sweeps_idle_above_threshold_and_redeems_order_shortfall() {
+    let account = EarnAccount {
+        idle: 1_800.0,
+        positions: vec![EarnPosition {
+            asset: "USDT".to_string(),
+            product_id: "USDT001".to_string(),
+            amount: 5_000.0,
+            apr_pct: 4.2,
+        }],
+    };
+    let config = EarnConfig {
+        tracking: true,
+        sweep_above_usdt: Some(1_000.0),
+    };
+    let sweep = config.plan_sweep(&account).expect("idle above threshold");
+    assert_eq!(sweep.kind, EarnTransferKind::Subscribe);
+    assert_eq!(sweep.amount, 800.0);
+    assert!(EarnConfig {
+        sweep_above_usdt: Some(1_800.5),
+        ..config
+    }
+    .plan_sweep(&account)
+    .is_none());
+    assert_eq!(config.label(), "sweep>1000");
+
+    let redeem = plan_redeem(&account, 2_500.0).expect("order needs more than idle");
+    assert_eq!(redeem.kind, EarnTransferKind::Redeem);
+    assert_eq!(redeem.amount, 700.0);
+    assert_eq!(
+        plan_redeem(&account, 9_000.0).map(|t| t.amount),
+        Some(5_000.0)
+    );
+    assert!(plan_redeem(&account, 1_800.0).is_none());
+
+    let balances = earn_balances(&account);
+    assert_eq!(balances.len(), 1);
+    assert_eq!((balances[0].free, balances[0].locked), (0.0, 5_000.0));
+}
+
+#[test]
+fn reason_codes_round_trip_through_stable_strings() {
+    let codes = ReasonCode::all().collect::<Vec<_>>();
+    assert_eq!(codes.len(), ExitReason::ALL.len() + SkipReason::ALL.len());
+    for code in &codes {
+        assert_eq!(ReasonCode::parse(code.as_str()), Some(*code));
+    }
+    let strings = codes
+        .iter()
+        .map(|code| code.as_str())
+        .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(strings.len(), codes.len());
+
+    assert_eq!(ExitReason::parse("stop_loss"), Some(ExitReason::StopLoss));
+    assert_eq!(
+        ReasonCode::parse("throttle"),
+        Some(ReasonCode::Skip(SkipReason::Throttle))
+    );
+    assert_eq!(ExitReason::parse("throttle"), None);
+    assert_eq!(ReasonCode::parse("liquidated"), None);
+}
+
+#[test]
+fn strategy_watch_version_follows_parameters_not_identity() {
+    let watch = |id, risk_pct| {
+        StrategyWatch::new(
+            id,
+            BinanceMode::Demo,
+            StrategyTemplate::PriceSmaCrossLong,
+            Instrument::new("BTCUSDT"),
+            StrategyStartConfig {
+                risk_pct,
+                ..StrategyStartConfig::default()
+            },
+        )
+    };
+    let original = watch(1, 0.005);
+
+    assert_eq!(original.version().len(), 8);
+    assert_eq!(original.version(), watch(2, 0.005).version());
+    assert_ne!(original.version(), watch(1, 0.004).version());
+    assert_eq!(
+        CommandSource::Strategy {
+            watch_id: 1,
+            version: original.version(),
+        }
+        .tag(),
+        format!("strategy:1@{}", original.version())
+    );
+}
+
+#[test]
+fn strategy_registry_builds_sma_cross_strategies_that_signal_on_crosses() {
+    let registry = StrategyRegistry::builtin();
+    assert!(registry
+        .create(StrategyTemplate::LiquidationBreakdownShort)
+        .is_none());
+    let fast = registry
+        .create(StrategyTemplate::PriceSmaCrossShortFast)
+        .expect("fast short registered");
+    assert_eq!(fast.side(), Side::Sell);
+    assert_eq!(fast.warmup_bars(), 21);
+    assert_eq!(registry.templates().count(), 4);
+
+    let mut strategy = SmaCross::new(Side::Buy, 2, 3);
+    assert_eq!(strategy.on_tick(10.0), None);
+    assert_eq!(strategy.on_tick(10.0), None);
+    assert_eq!(strategy.on_tick(10.0), None);
+    assert_eq!(strategy.on_tick(10.0), Some(StrategySignal::Hold));
+    assert_eq!(strategy.on_tick(13.0), Some(StrategySignal::Enter));
+    assert_eq!(strategy.indicators()["fast_sma"], 11.5);
+    assert_eq!(strategy.indicators()["slow_sma"], 11.0);
+    assert_eq!(strategy.on_tick(4.0), Some(StrategySignal::Exit));
+
+    strategy.reset();
+    assert!(strategy.indicators().is_empty());
+    assert_eq!(strategy.on_tick(10.0), None);
+
+    let mut custom = StrategyRegistry::default();
+    custom.register(StrategyTemplate::PriceSmaCrossLong, || {
+        Box::new(SmaCross::new(Side::Sell, 3, 5))
+    });
+    let replaced = custom
+        .create(StrategyTemplate::PriceSmaCrossLong)
+        .expect("registered");
+    assert_eq!((replaced.side(), replaced.warmup_bars()), (Side::Sell, 5));
+}
+
+/// A module that exports `exports` as empty functions after `imports`.
+fn wasm_module(imports: &[(&str, &str)], exports: &[&str]) -> Vec<u8> {
+    let imports = imports
+        .iter()
+        .map(|(module, field)| format!(r#"(import "{module}" "{field}" (func))"#))
+        .collect::<String>();
+    let exports = exports
+        .iter()
+        .map(|export| format!(r#"(func (export "{export}"))"#))
+        .collect::<String>();
+    wat::parse_str(format!("(module {imports} {exports})")).expect("valid wat")
+}
+
+#[test]
+fn strategy_plugins_must_be_import_free_wasm_with_the_strategy_exports() {
+    let exports = ["on_tick", "warmup_bars", "reset"];
+    assert_eq!(
+        inspect_wasm(&wasm_module(&[], &exports)).expect("valid plugin"),
+        ["on_tick", "reset", "warmup_bars"]
+            .map(str::to_string)
+            .to_vec()
+    );
+    assert_eq!(inspect_wasm(b"not wasm"), Err(PluginError::NotWasm));
+    assert_eq!(
+        inspect_wasm(&wasm_module(&[], &exports[..2])),
+        Err(PluginError::MissingExport("reset"))
+    );
+    assert_eq!(
+        inspect_wasm(&wasm_module(&[("env", "fetch")], &exports)),
+        Err(PluginError::Imports("env.fetch".to_string()))
+    );
+    let mut truncated = wasm_module(&[], &exports);
+    truncated.pop();
+    assert!(matches!(
+        inspect_wasm(&truncated),
+        Err(PluginError::Malformed(_))
+    ));
+
+    let dir = std::env::temp_dir().join(format!(
+        "sandbox-quant-plugins-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    std::fs::create_dir_all(&dir).expect("create dir");
+    std::fs::write(dir.join("momentum.wasm"), plugin_wasm("(i32.const 0)")).expect("write");
+    std::fs::write(
+        dir.join("fetch.wasm"),
+        wasm_module(&[("env", "fetch")], &exports),
+    )
+    .expect("write");
+    std::fs::write(dir.join("signatures.wasm"), wasm_module(&[], &exports)).expect("write");
+    std::fs::write(dir.join("broken.wasm"), b"nope").expect("write");
+    std::fs::write(dir.join("notes.txt"), b"ignored").expect("write");
+
+    let plugins = discover_plugins(&dir);
+    assert_eq!(
+        plugins
+            .iter()
+            .map(|plugin| (
+                plugin.name.as_str(),
+                plugin.status.as_ref().map_err(PluginError::reason).err()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("broken", Some("not_wasm")),
+            ("fetch", Some("imports")),
+            ("momentum", None),
+            ("signatures", Some("instantiate_failed")),
+        ]
+    );
+    let mut loaded = WasmStrategy::load(&dir.join("momentum.wasm"), Side::Sell).expect("load");
+    assert_eq!((loaded.side(), loaded.warmup_bars()), (Side::Sell, 2));
+    assert_eq!(loaded.on_tick(1.0), None);
+    assert_eq!(loaded.on_tick(1.0), Some(StrategySignal::Hold));
+    assert!(discover_plugins(&dir.join("missing")).is_empty());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A plugin that warms up for two closes and then answers `signal`, which
+/// may read the close as `local.get 0`.
+fn plugin_wasm(signal: &str) -> Vec<u8> {
+    wat::parse_str(format!(
+        r#"(module
+            (global $ticks (mut i32) (i32.const 0))
+            (func (export "warmup_bars") (result i32) (i32.const 2))
+            (func (export "reset") (global.set $ticks (i32.const 0)))
+            (func (export "on_tick") (param f64) (result i32)
+                (global.set $ticks (i32.add (global.get $ticks) (i32.const 1)))
+                (if (result i32) (i32.lt_s (global.get $ticks) (i32.const 2))
+                    (then (i32.const -1))
+                    (else {signal}))))"#
+    ))
+    .expect("valid wat")
+}
+
+#[test]
+fn wasm_plugins_signal_within_their_fuel_and_memory_budget() {
+    let mut breakout = WasmStrategy::from_wasm(
+        &plugin_wasm(
+            "(if (result i32) (f64.gt (local.get 0) (f64.const 100))
+                (then (i32.const 1)) (else (i32.const 2)))",
+        ),
+        Side::Buy,
+    )
+    .expect("runnable plugin");
+    assert_eq!(breakout.on_tick(101.0), None);
+    assert_eq!(breakout.on_tick(101.0), Some(StrategySignal::Enter));
+    assert_eq!(breakout.on_tick(99.0), Some(StrategySignal::Exit));
+    breakout.reset();
+    assert_eq!(breakout.on_tick(101.0), None);
+    assert_eq!(breakout.fault(), None);
+
+    let mut spinning = WasmStrategy::from_wasm(
+        &plugin_wasm("(loop $spin (br $spin)) (i32.const 0)"),
+        Side::Buy,
+    )
+    .expect("runnable plugin");
+    assert_eq!(spinning.on_tick(1.0), None);
+    assert_eq!(spinning.on_tick(1.0), None);
+    assert!(spinning
+        .fault()
+        .expect("out of fuel")
+        .starts_with("plugin trapped in on_tick"));
+    assert_eq!(spinning.on_tick(1.0), None);
+
+    let mut unknown =
+        WasmStrategy::from_wasm(&plugin_wasm("(i32.const 7)"), Side::Buy).expect("runnable");
+    unknown.on_tick(1.0);
+    assert_eq!(unknown.on_tick(1.0), None);
+    assert_eq!(
+        unknown.fault(),
+        Some(PluginError::UnknownSignal(7).to_string())
+    );
+
+    let hungry = wat::parse_str(
+        r#"(module
+            (memory 1024)
+            (func (export "warmup_bars") (result i32) (i32.const 1))
+            (func (export "reset"))
+            (func (export "on_tick") (param f64) (result i32) (i32.const 0)))"#,
+    )
+    .expect("valid wat");
+    assert!(matches!(
+        WasmStrategy::from_wasm(&hungry, Side::Buy).err(),
+        Some(PluginError::Instantiate(_))
+    ));
+}
+
+#[test]
+fn script_strategy_signals_when_its_rules_turn_true() {
+    let mut strategy = ScriptStrategy::parse(
+        "// breakout\n\
+         fn warmup() { 4 }\n\
+         fn enter() { close() > highest(3, 1) && !(close(1) > 110.0) }\n\
+         fn exit() { close() < sma(3) - 1.0 } // trailing mean\n",
+        Side::Buy,
+    )
+    .expect("valid script");
+    assert_eq!((strategy.side(), strategy.warmup_bars()), (Side::Buy, 4));
+
+    assert_eq!(strategy.on_tick(100.0), None);
+    assert_eq!(strategy.on_tick(101.0), None);
+    assert_eq!(strategy.on_tick(100.0), None);
+    assert_eq!(strategy.on_tick(100.5), Some(StrategySignal::Hold));
+    assert_eq!(strategy.on_tick(102.0), Some(StrategySignal::Enter));
+    assert_eq!(strategy.on_tick(103.0), Some(StrategySignal::Hold));
+    assert_eq!(strategy.indicators()["highest(3, 1)"], 102.0);
+    assert!((strategy.indicators()["sma(3)"] - 305.5 / 3.0).abs() < 1e-9);
+    assert_eq!(strategy.on_tick(99.0), Some(StrategySignal::Exit));
+
+    strategy.reset();
+    assert!(strategy.indicators().is_empty());
+
+    assert_eq!(
+        ScriptStrategy::parse("fn enter() { close() > 1.0 }", Side::Sell).err(),
+        Some(ScriptError::MissingRule("exit"))
+    );
+    assert!(matches!(
+        ScriptStrategy::parse("fn enter() { close() > }\nfn exit() { false }", Side::Buy).err(),
+        Some(ScriptError::Parse { line: 1, .. })
+    ));
+    assert!(matches!(
+        ScriptStrategy::parse("fn warmup() { 0 }\nfn enter() { true }\nfn exit() { false }", Side::Buy).err(),
+        Some(ScriptError::Runtime(message)) if message.contains("warmup() must be between 1")
+    ));
+
+    let mut unknown = ScriptStrategy::parse(
+        "fn enter() { rsi(14) < 30.0 }\nfn exit() { false }",
+        Side::Buy,
+    )
+    .expect("helpers resolve when called");
+    assert_eq!(unknown.on_tick(100.0), None);
+    assert!(unknown
+        .fault()
+        .is_some_and(|fault| fault.contains("enter(): ")));
+    assert_eq!(unknown.on_tick(101.0), None);
+
+    let mut spinning =
+        ScriptStrategy::parse("fn enter() { loop {} }\nfn exit() { false }", Side::Buy)
+            .expect("valid script");
+    assert_eq!(spinning.on_tick(100.0), None);
+    assert!(spinning.fault().is_some());
+    spinning.reset();
+    assert_eq!(spinning.fault(), None);
+}
+
+#[test]
+fn order_store_aggregates_buckets_in_sql_and_compacts_past_retention() {
+    let path = std::env::temp_dir().join(format!(
+        "sandbox-quant-orders-{}.sqlite",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 14, 30, 0).unwrap();
+    let order = |time, symbol: &str, tag: &str, status| OrderHistoryRow {
+        time,
+        instrument: Instrument::new(symbol),
+        market: Market::Futures,
+        side: Side::Buy,
+        qty: 0.1,
+        price: Some(60000.0),
+        status,
+        tag: tag.to_string(),
+        depth_imbalance: None,
+        list_id: None,
+        parent_id: Some(4),
+        signal_time: Some(time),
+        exchange_time: None,
+    };
+    let rows = [
+        order(
+            start,
+            "BTCUSDT",
+            "strategy:3@3f9a0c1d",
+            OrderStatus::Submitted,
+        ),
+        order(
+            start + Duration::hours(1),
+            "BTCUSDT",
+            "strategy:3@3f9a0c1d",
+            OrderStatus::Rejected,
+        ),
+        order(
+            start + Duration::days(1),
+            "ETHUSDT",
+            "manual",
+            OrderStatus::Submitted,
+        ),
+    ];
+
+    let store = OrderStore::open(&path).expect("open store");
+    assert_eq!(store.schema_version(), Ok(3));
+    for row in &rows {
+        store.record_order(row).expect("record order");
+    }
+    store
+        .record_fill(&FillRow {
+            time: start + Duration::minutes(1),
+            instrument: Instrument::new("BTCUSDT"),
+            market: Market::Futures,
+            client_order_id: "strategy-3-1".to_string(),
+            order_id: Some(11),
+            tag: "strategy:3".to_string(),
+            side: Side::Sell,
+            qty: 0.1,
+            price: 6000.0,
+            realized_pnl: 3.0,
+            commission: 0.3,
+        })
+        .expect("record fill");
+
+    let watch = HistoryQuery {
+        instrument: None,
+        tag: Some("strategy:3".to_string()),
+    };
+    let days = store
+        .bucket_stats(TimeBucket::Day, &watch, 10)
+        .expect("day buckets");
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[0].bucket, "2024-03-01");
+    assert_eq!((days[0].orders, days[0].rejected, days[0].fills), (2, 1, 1));
+    assert_eq!(days[0].notional, 12000.0);
+    assert_eq!(days[0].roi_pct(), Some(0.5));
+
+    let hours = store
+        .bucket_stats(TimeBucket::Hour, &HistoryQuery::default(), 10)
+        .expect("hour buckets");
+    assert_eq!(
+        hours
+            .iter()
+            .map(|bucket| bucket.bucket.as_str())
+            .collect::<Vec<_>>(),
+        ["2024-03-02 14:00", "2024-03-01 15:00", "2024-03-01 14:00"]
+    );
+    let eth = HistoryQuery {
+        instrument: Some(Instrument::new("ETHUSDT")),
+        tag: None,
+    };
+    let months = store
+        .bucket_stats(TimeBucket::Month, &eth, 10)
+        .expect("month buckets");
+    assert_eq!(
+        (months[0].bucket.as_str(), months[0].orders),
+        ("2024-03", 1)
+    );
+    assert_eq!(months[0].roi_pct(), None);
+    drop(store);
+
+    let mut store = OrderStore::open(&path).expect("reopen store");
+    assert_eq!(store.schema_version(), Ok(3), "migrations run once");
+    assert_eq!(store.recent_orders(2), Ok(rows[1..].to_vec()));
+
+    store.retention_days = 1;
+    assert_eq!(store.compact(start + Duration::days(2)), Ok(3));
+    assert_eq!(store.recent_orders(10), Ok(rows[2..].to_vec()));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn order_store_reads_realized_trades_per_tag_and_instrument_within_lookback() {
+    let store = OrderStore::open_in_memory().expect("in-memory store");
+    let now = Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap();
+    let fill = |days_ago: i64, symbol: &str, tag: &str, order_id, realized_pnl| FillRow {
+        time: now - Duration::days(days_ago),
+        instrument: Instrument::new(symbol),
+        market: Market::Futures,
+        client_order_id: "strategy-3-1".to_string(),
+        order_id: Some(order_id),
+        tag: tag.to_string(),
+        side: Side::Sell,
+        qty: 0.05,
+        price: 60000.0,
+        realized_pnl,
+        commission: 1.0,
+    };
+    for row in [
+        fill(20, "BTCUSDT", "strategy:3", 1, -30.0),
+        // Two partial fills of one close count as one trade.
+        fill(5, "BTCUSDT", "strategy:3", 2, 0.6),
+        fill(5, "BTCUSDT", "strategy:3", 2, 0.6),
+        fill(4, "BTCUSDT", "strategy:3", 3, 25.0),
+        fill(3, "BTCUSDT", "manual", 4, 12.0),
+        fill(2, "ETHUSDT", "strategy:3", 5, 8.0),
+        // Opening fills realize nothing.
+        fill(1, "BTCUSDT", "strategy:3", 6, 0.0),
+    ] {
+        store.record_fill(&row).expect("record fill");
+    }
+
+    let watch_btc = HistoryQuery {
+        instrument: Some(Instrument::new("BTCUSDT")),
+        tag: Some("strategy:3".to_string()),
+    };
+    let trades = store
+        .realized_trades(&watch_btc, None)
+        .expect("realized trades");
+    assert_eq!(trades.len(), 3);
+    assert!((trades[1] - (-0.8)).abs() < 1e-9);
+    assert_eq!(
+        TradeStatsReader::outcomes(&store, &watch_btc, None),
+        Ok(vec![false, false, true])
+    );
+    assert_eq!(
+        TradeStatsReader::outcomes(&store, &watch_btc, Some(now - Duration::days(7))),
+        Ok(vec![false, true])
+    );
+    assert_eq!(
+        store
+            .realized_trades(&HistoryQuery::default(), Some(now - Duration::days(7)))
+            .map(|trades| trades.len()),
+        Ok(4)
+    );
+    assert_eq!(
+        EmptyTradeStatsReader.outcomes(&watch_btc, None),
+        Ok(Vec::new())
+    );
+}
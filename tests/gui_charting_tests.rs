@@ -5,19 +5,30 @@ use std::path::PathBuf;
 use chrono::{NaiveDate, TimeZone, Utc};
 use sandbox_quant::app::bootstrap::BinanceMode;
 use sandbox_quant::backtest_app::runner::{
-    BacktestConfig, BacktestExitReason, BacktestReport, BacktestTrade,
+    BacktestConfig, BacktestReport, BacktestSignal, BacktestTrade, SignalOutcome,
 };
 use sandbox_quant::charting::adapters::sandbox::{
-    equity_scene_from_report, market_scene_from_snapshot, market_scene_from_snapshot_with_overlay,
-    market_scene_from_snapshot_with_timeframe, MarketSeriesKind, MarketTimeframe,
+    add_market_context_panes, apply_chart_retention, apply_ui_theme, equity_scene_from_report,
+    market_scene_from_snapshot, market_scene_from_snapshot_with_candle_mode,
+    market_scene_from_snapshot_with_overlay, market_scene_from_snapshot_with_timeframe,
+    replay_scene, strategy_focus_scene, MarketContextPanes, MarketSeriesKind, MarketTimeframe,
 };
+use sandbox_quant::charting::frame_limiter::{FrameDecision, FrameLimiter};
 use sandbox_quant::charting::scene::Series;
 use sandbox_quant::dataset::types::{
     BacktestDatasetSummary, BacktestRunSummaryRow, BookTickerRow, DerivedKlineRow,
-    LiquidationEventRow, RecorderMetrics,
+    LiquidationEventRow, OpenInterestRow, RecorderMetrics,
 };
+use sandbox_quant::domain::position::Side;
+use sandbox_quant::domain::reason::{ExitReason, SkipReason};
+use sandbox_quant::market_data::candle_mode::{CandleMode, RenkoBrick};
 use sandbox_quant::strategy::model::StrategyTemplate;
-use sandbox_quant::visualization::types::{DashboardSnapshot, MarketSeries};
+use sandbox_quant::ui::theme::UiTheme;
+use sandbox_quant::visualization::annotations::{
+    lifecycle_annotations, AnnotationKind, ChartRetention,
+};
+use sandbox_quant::visualization::replay::{ReplayDecision, TradeReplay};
+use sandbox_quant::visualization::types::{DashboardSnapshot, MarketSeries, StrategyFocusSeries};
 
 fn sample_report(symbol: &str) -> BacktestReport {
     let from = NaiveDate::from_ymd_opt(2026, 3, 10).expect("date");
@@ -45,6 +56,7 @@ fn sample_report(symbol: &str) -> BacktestReport {
         trigger_count: 1,
         trades: vec![BacktestTrade {
             trade_id: 1,
+            side: Side::Sell,
             trigger_time: Utc.timestamp_millis_opt(1_000).single().expect("timestamp"),
             entry_time: Utc.timestamp_millis_opt(2_000).single().expect("timestamp"),
             entry_price: 100.0,
@@ -53,7 +65,7 @@ fn sample_report(symbol: &str) -> BacktestReport {
             qty: 1.0,
             exit_time: Some(Utc.timestamp_millis_opt(3_000).single().expect("timestamp")),
             exit_price: Some(98.0),
-            exit_reason: Some(BacktestExitReason::TakeProfit),
+            exit_reason: Some(ExitReason::TakeProfit),
             gross_pnl: Some(2.0),
             fees: Some(0.2),
             net_pnl: Some(1.8),
@@ -62,6 +74,8 @@ fn sample_report(symbol: &str) -> BacktestReport {
         losses: 0,
         open_trades: 0,
         skipped_triggers: 0,
+        throttled_signals: 0,
+        signals: Vec::new(),
         starting_equity: 10_000.0,
         ending_equity: 10_001.8,
         net_pnl: 1.8,
@@ -111,6 +125,7 @@ fn sample_snapshot(
             book_tickers,
             klines,
             kline_interval: Some("1s".to_string()),
+            open_interest: Vec::new(),
         },
         recent_runs: vec![BacktestRunSummaryRow {
             run_id: 7,
@@ -376,6 +391,53 @@ fn market_scene_can_aggregate_to_minute_timeframe() {
     assert_eq!(candle_count, 1);
 }
 
+#[test]
+fn market_scene_redraws_bars_as_renko_bricks_in_renko_mode() {
+    let kline = |second: i64, close: f64| DerivedKlineRow {
+        open_time_ms: second * 1_000,
+        close_time_ms: second * 1_000 + 999,
+        open: close,
+        high: close,
+        low: close,
+        close,
+        volume: 1.0,
+        quote_volume: close,
+        trade_count: 1,
+    };
+    let snapshot = sample_snapshot(
+        "BTCUSDT",
+        Vec::new(),
+        vec![kline(1, 100.0), kline(2, 102.5), kline(3, 101.8)],
+        None,
+    );
+
+    let scene = market_scene_from_snapshot_with_candle_mode(
+        &snapshot,
+        MarketTimeframe::Tick1s,
+        CandleMode::Renko(RenkoBrick::Price(1.0)),
+    );
+
+    assert_eq!(
+        scene.panes[0].title.as_deref(),
+        Some("Market (1s, Renko 1)")
+    );
+    let bricks = scene.panes[0]
+        .series
+        .iter()
+        .find_map(|series| match series {
+            Series::Candles(candles) => Some(&candles.candles),
+            _ => None,
+        })
+        .expect("bricks present");
+    assert_eq!(
+        bricks
+            .iter()
+            .map(|brick| (brick.open, brick.close))
+            .collect::<Vec<_>>(),
+        vec![(100.0, 101.0), (101.0, 102.0)]
+    );
+}
+
 #[test]
 fn market_scene_can_aggregate_to_five_minute_timeframe() {
     let snapshot = sample_snapshot(
@@ -599,6 +661,86 @@ fn market_scene_caps_default_viewport_for_long_selected_run() {
     assert!((max_x.as_i64() - min_x.as_i64()) <= 27 * 60 * 1_000);
 }
 
+#[test]
+fn chart_retention_keeps_newest_fill_markers_and_adds_lifecycle_annotations() {
+    let report = sample_report("BTCUSDT");
+    let snapshot = sample_snapshot("BTCUSDT", Vec::new(), Vec::new(), Some(report.clone()));
+    let mut scene = market_scene_from_snapshot(&snapshot);
+    let retention = ChartRetention {
+        max_fill_markers: 2,
+        ..ChartRetention::default()
+    };
+
+    apply_chart_retention(&mut scene, &report.trades, &retention);
+
+    let markers = |name: &str| {
+        scene.panes[0]
+            .series
+            .iter()
+            .find_map(|series| match series {
+                Series::Markers(series) if series.name == name => Some(
+                    series
+                        .markers
+                        .iter()
+                        .map(|marker| (marker.label.clone(), marker.time_ms.as_i64()))
+                        .collect::<Vec<_>>(),
+                ),
+                _ => None,
+            })
+            .expect("marker series")
+    };
+    assert_eq!(
+        markers("signals"),
+        vec![
+            ("entry #1".to_string(), 2_000),
+            ("exit #1".to_string(), 3_000),
+        ]
+    );
+    assert_eq!(
+        markers("lifecycle"),
+        vec![
+            ("stop armed #1".to_string(), 2_000),
+            ("target armed #1".to_string(), 2_000),
+        ]
+    );
+}
+
+#[test]
+fn chart_retention_drops_annotations_past_count_and_age() {
+    let mut trades = sample_report("BTCUSDT").trades;
+    let mut later = trades[0].clone();
+    later.trade_id = 2;
+    later.entry_time = Utc
+        .timestamp_millis_opt(62_000)
+        .single()
+        .expect("timestamp");
+    trades.push(later);
+    let mut annotations = lifecycle_annotations(&trades);
+    assert_eq!(annotations.len(), 4);
+
+    ChartRetention {
+        max_annotations: 10,
+        annotation_max_age_secs: Some(30),
+        ..ChartRetention::default()
+    }
+    .retain_annotations(&mut annotations);
+    assert_eq!(
+        annotations
+            .iter()
+            .map(|annotation| annotation.label.as_str())
+            .collect::<Vec<_>>(),
+        vec!["stop armed #2", "target armed #2"]
+    );
+
+    ChartRetention {
+        max_annotations: 1,
+        ..ChartRetention::default()
+    }
+    .retain_annotations(&mut annotations);
+    assert_eq!(annotations.len(), 1);
+    assert_eq!(annotations[0].kind, AnnotationKind::TargetArmed);
+}
+
 #[test]
 fn equity_scene_prepends_starting_equity_point() {
     let report = sample_report("BTCUSDT");
@@ -617,6 +759,384 @@ fn equity_scene_prepends_starting_equity_point() {
     assert_eq!(line.points[1].value, report.ending_equity);
 }
 
+fn replay_snapshot() -> DashboardSnapshot {
+    sample_snapshot(
+        "BTCUSDT",
+        vec![
+            BookTickerRow {
+                event_time_ms: 900,
+                bid: 100.4,
+                ask: 100.6,
+            },
+            BookTickerRow {
+                event_time_ms: 2_400,
+                bid: 100.4,
+                ask: 100.6,
+            },
+            BookTickerRow {
+                event_time_ms: 2_700,
+                bid: 98.9,
+                ask: 99.1,
+            },
+            BookTickerRow {
+                event_time_ms: 3_500,
+                bid: 97.0,
+                ask: 97.2,
+            },
+        ],
+        Vec::new(),
+        Some(sample_report("BTCUSDT")),
+    )
+}
+
+#[test]
+fn trade_replay_steps_from_signal_through_ticks_to_exit() {
+    let snapshot = replay_snapshot();
+    let report = snapshot.selected_report.as_ref().expect("report");
+
+    let replay = TradeReplay::build(&report.trades[0], &report.config, &snapshot.market_series);
+
+    let decisions = replay
+        .steps
+        .iter()
+        .map(|step| step.decision)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        decisions,
+        vec![
+            ReplayDecision::Signal,
+            ReplayDecision::Enter,
+            ReplayDecision::Hold,
+            ReplayDecision::Hold,
+            ReplayDecision::TakeProfit,
+        ]
+    );
+    assert_eq!(replay.steps[0].price, 100.5);
+    assert!((replay.steps[1].expected_value_r - 1.4).abs() < 1e-9);
+    assert!((replay.steps[2].r_multiple + 0.5).abs() < 1e-9);
+    assert!((replay.steps[4].r_multiple - 2.0).abs() < 1e-9);
+    assert_eq!(
+        replay.describe_current(),
+        "step 1/5 signal price=100.50 r=-0.50 ev=1.90R"
+    );
+}
+
+#[test]
+fn trade_replay_cursor_steps_and_jumps_to_next_decision() {
+    let snapshot = replay_snapshot();
+    let report = snapshot.selected_report.as_ref().expect("report");
+    let mut replay = TradeReplay::build(&report.trades[0], &report.config, &snapshot.market_series);
+
+    assert!(!replay.step_back());
+    assert!(replay.step_forward());
+    assert!(replay.step_forward());
+    assert_eq!(replay.current().decision, ReplayDecision::Hold);
+    assert!(replay.seek_next_decision());
+    assert_eq!(replay.current().decision, ReplayDecision::TakeProfit);
+    assert!(!replay.step_forward());
+    assert!(!replay.seek_next_decision());
+    replay.seek(99);
+    assert_eq!(replay.cursor, 4);
+}
+
+#[test]
+fn replay_scene_draws_price_only_up_to_cursor() {
+    let snapshot = replay_snapshot();
+    let report = snapshot.selected_report.as_ref().expect("report");
+    let mut replay = TradeReplay::build(&report.trades[0], &report.config, &snapshot.market_series);
+    replay.seek(2);
+
+    let scene = replay_scene(&replay);
+
+    let series = &scene.panes[0].series;
+    let price = series
+        .iter()
+        .find_map(|series| match series {
+            Series::Line(line) if line.name == "price" => Some(line),
+            _ => None,
+        })
+        .expect("price line");
+    assert_eq!(price.points.len(), 3);
+    assert!(series
+        .iter()
+        .any(|series| matches!(series, Series::Line(line) if line.name == "stop 101.00")));
+    let Some(Series::Markers(markers)) = series.last() else {
+        panic!("expected decision markers");
+    };
+    assert_eq!(
+        markers
+            .markers
+            .iter()
+            .map(|marker| marker.label.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "signal",
+            "enter",
+            "step 3/5 hold price=100.50 r=-0.50 ev=1.90R"
+        ]
+    );
+    assert_eq!(
+        scene
+            .viewport
+            .x_range
+            .map(|(from, to)| (from.as_i64(), to.as_i64())),
+        Some((0, 4_000))
+    );
+}
+
+#[test]
+fn market_context_panes_stack_open_interest_and_volume_profile_below_price() {
+    use sandbox_quant::charting::plotters::PlottersRenderer;
+    use sandbox_quant::charting::render::ChartRenderer;
+    use sandbox_quant::charting::scene::RenderRequest;
+
+    let klines = (0..4)
+        .map(|index| DerivedKlineRow {
+            open_time_ms: index * 60_000,
+            close_time_ms: index * 60_000 + 59_999,
+            open: 100.0 + index as f64,
+            high: 101.0 + index as f64,
+            low: 99.0 + index as f64,
+            close: 100.5 + index as f64,
+            volume: 10.0 + index as f64,
+            quote_volume: 1_000.0,
+            trade_count: 5,
+        })
+        .collect();
+    let mut snapshot = sample_snapshot("BTCUSDT", Vec::new(), klines, None);
+    snapshot.market_series.open_interest = vec![
+        OpenInterestRow {
+            event_time_ms: 0,
+            open_interest: 1_200.0,
+        },
+        OpenInterestRow {
+            event_time_ms: 120_000,
+            open_interest: 1_250.0,
+        },
+    ];
+
+    let mut scene = market_scene_from_snapshot(&snapshot);
+    add_market_context_panes(&mut scene, &snapshot, MarketContextPanes::default());
+    assert_eq!(scene.panes.len(), 2);
+
+    add_market_context_panes(
+        &mut scene,
+        &snapshot,
+        MarketContextPanes {
+            open_interest: true,
+            volume_profile: true,
+        },
+    );
+    let ids = scene
+        .panes
+        .iter()
+        .map(|pane| pane.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec!["market", "volume", "open_interest", "volume_profile"]
+    );
+    match &scene.panes[3].series[0] {
+        Series::Profile(profile) => {
+            assert!(!profile.blocks.is_empty());
+            assert!(profile
+                .blocks
+                .iter()
+                .any(|block| (block.fill_ratio - 1.0).abs() < 1e-9));
+        }
+        other => panic!("expected profile series, got {other:?}"),
+    }
+    let frame = PlottersRenderer
+        .render(
+            &scene,
+            &RenderRequest {
+                width_px: 800,
+                height_px: 600,
+                pixel_ratio: 1.0,
+                oversample: 1,
+            },
+        )
+        .expect("render frame");
+    assert!(!frame.rgb.is_empty());
+}
+
+#[test]
+fn strategy_focus_scene_overlays_the_template_sma_windows_on_its_own_bars() {
+    let klines = (0..25)
+        .map(|index| DerivedKlineRow {
+            open_time_ms: index * 300_000,
+            close_time_ms: index * 300_000 + 299_999,
+            open: 100.0 + index as f64,
+            high: 101.0 + index as f64,
+            low: 99.0 + index as f64,
+            close: 100.0 + index as f64,
+            volume: 1.0,
+            quote_volume: 100.0,
+            trade_count: 1,
+        })
+        .collect::<Vec<_>>();
+    let focus = StrategyFocusSeries {
+        template: StrategyTemplate::PriceSmaCrossLongFast,
+        symbol: "BTCUSDT".to_string(),
+        from: NaiveDate::from_ymd_opt(2026, 3, 10).expect("date"),
+        to: NaiveDate::from_ymd_opt(2026, 3, 11).expect("date"),
+        interval: Some("5m".to_string()),
+        klines,
+        liquidations: Vec::new(),
+    };
+    let mut report = sample_report("BTCUSDT");
+    report.template = StrategyTemplate::PriceSmaCrossLongFast;
+
+    let scene = strategy_focus_scene(&focus, Some(&report));
+
+    assert_eq!(scene.panes[0].id, "strategy");
+    assert_eq!(
+        scene.panes[0].title.as_deref(),
+        Some("price-sma-cross-long-fast (5m)")
+    );
+    let lines = scene.panes[0]
+        .series
+        .iter()
+        .filter_map(|series| match series {
+            Series::Line(line) => Some(line),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].name, "SMA 9");
+    assert_eq!(lines[0].points.len(), 17);
+    assert!((lines[0].points[0].value - 104.0).abs() < 1e-9);
+    assert_eq!(lines[1].name, "SMA 21");
+    assert_eq!(lines[1].points.len(), 5);
+    assert!(scene.panes[0]
+        .series
+        .iter()
+        .any(|series| matches!(series, Series::Markers(markers) if markers.name == "signals")));
+
+    report.template = StrategyTemplate::PriceSmaCrossShort;
+    let other_run = strategy_focus_scene(&focus, Some(&report));
+    assert!(!other_run.panes[0]
+        .series
+        .iter()
+        .any(|series| matches!(series, Series::Markers(_))));
+}
+
+#[test]
+fn strategy_focus_scene_marks_every_signal_by_outcome() {
+    let focus = StrategyFocusSeries {
+        template: StrategyTemplate::PriceSmaCrossLongFast,
+        symbol: "BTCUSDT".to_string(),
+        from: NaiveDate::from_ymd_opt(2026, 3, 10).expect("date"),
+        to: NaiveDate::from_ymd_opt(2026, 3, 11).expect("date"),
+        interval: Some("5m".to_string()),
+        klines: Vec::new(),
+        liquidations: Vec::new(),
+    };
+    let mut report = sample_report("BTCUSDT");
+    report.template = StrategyTemplate::PriceSmaCrossLongFast;
+    report.signals = vec![
+        BacktestSignal {
+            time_ms: 300_000,
+            price: 100.0,
+            outcome: SignalOutcome::Ordered,
+            reason: None,
+            ev: None,
+        },
+        BacktestSignal {
+            time_ms: 600_000,
+            price: 101.0,
+            outcome: SignalOutcome::Blocked,
+            reason: Some(SkipReason::Throttle),
+            ev: None,
+        },
+        BacktestSignal {
+            time_ms: 900_000,
+            price: 102.0,
+            outcome: SignalOutcome::Rejected,
+            reason: Some(SkipReason::InvalidQty),
+            ev: None,
+        },
+    ];
+
+    let scene = strategy_focus_scene(&focus, Some(&report));
+
+    let outcomes = scene.panes[0]
+        .series
+        .iter()
+        .find_map(|series| match series {
+            Series::Markers(markers) if markers.name == "signal outcomes" => Some(markers),
+            _ => None,
+        })
+        .expect("signal outcomes");
+    let labels = outcomes
+        .markers
+        .iter()
+        .map(|marker| marker.label.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        vec!["ordered", "blocked throttle", "rejected invalid_qty"]
+    );
+    assert_ne!(outcomes.markers[0].color, outcomes.markers[1].color);
+    assert_ne!(outcomes.markers[1].color, outcomes.markers[2].color);
+
+    let mut themed = scene.clone();
+    apply_ui_theme(&mut themed, UiTheme::DeuteranopiaSafe);
+    let palette = UiTheme::DeuteranopiaSafe.palette();
+    assert_eq!(themed.theme, palette.chart_theme());
+    let themed_colors = themed.panes[0]
+        .series
+        .iter()
+        .find_map(|series| match series {
+            Series::Markers(markers) if markers.name == "signal outcomes" => Some(
+                markers
+                    .markers
+                    .iter()
+                    .map(|marker| marker.color)
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        })
+        .expect("signal outcomes");
+    assert_eq!(
+        themed_colors,
+        vec![palette.positive, palette.warning, palette.negative]
+    );
+
+    let mut dark = scene.clone();
+    apply_ui_theme(&mut dark, UiTheme::Dark);
+    assert_eq!(dark, scene);
+}
+
+#[test]
+fn frame_limiter_skips_unchanged_frames_and_defers_over_budget() {
+    let start = std::time::Instant::now();
+    let mut limiter = FrameLimiter::new(20);
+
+    assert_eq!(limiter.decide(false, start), FrameDecision::Draw);
+    limiter.record_draw(start);
+
+    assert_eq!(
+        limiter.decide(false, start + std::time::Duration::from_secs(5)),
+        FrameDecision::Skip
+    );
+    assert_eq!(
+        limiter.decide(true, start + std::time::Duration::from_millis(30)),
+        FrameDecision::Defer(std::time::Duration::from_millis(20))
+    );
+    assert_eq!(
+        limiter.decide(true, start + std::time::Duration::from_millis(60)),
+        FrameDecision::Draw
+    );
+
+    let uncapped = {
+        let mut limiter = FrameLimiter::new(0);
+        limiter.record_draw(start);
+        limiter
+    };
+    assert_eq!(uncapped.decide(true, start), FrameDecision::Draw);
+}
+
 #[test]
 fn render_real_btcusdt_market_scene_does_not_panic() {
     use sandbox_quant::charting::plotters::PlottersRenderer;
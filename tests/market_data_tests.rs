@@ -0,0 +1,469 @@
+use chrono::Utc;
+use sandbox_quant::app::bootstrap::BinanceMode;
+use sandbox_quant::dataset::types::DerivedKlineRow;
+use sandbox_quant::domain::instrument::Instrument;
+use sandbox_quant::domain::market::Market;
+use sandbox_quant::exchange::types::DailyBar;
+use sandbox_quant::market_data::binance_kline_backfill::KlineEndpoint;
+use sandbox_quant::market_data::candle_consistency::{
+    candle_interval_ms, compare_candles, LocalCandleAggregator,
+};
+use sandbox_quant::market_data::news_feed::{
+    parse_news_feed, NewsFeed, NewsFeedConfig, NewsHeadline,
+};
+use sandbox_quant::market_data::open_interest::parse_open_interest;
+use sandbox_quant::market_data::price_alert::{PriceAlertAction, PriceAlertStore};
+use sandbox_quant::market_data::range_stats::SymbolRangeStats;
+use sandbox_quant::market_data::reconnect_breaker::{ReconnectBreaker, ReconnectBreakerConfig};
+use sandbox_quant::market_data::tick_sequencer::{
+    SequencedTrade, TickAdmission, TickSequencer, TickSequencerConfig,
+};
+use sandbox_quant::market_data::trade_gaps::{parse_agg_trades, TradeIdTracker};
+use sandbox_quant::market_data::volatility::{VolatilityEstimator, VolatilityModel};
+use sandbox_quant::visualization::service::VisualizationService;
+use std::collections::BTreeSet;
+
+fn kline(open_time_ms: i64, open: f64, high: f64, low: f64, close: f64) -> DerivedKlineRow {
+    DerivedKlineRow {
+        open_time_ms,
+        close_time_ms: open_time_ms + 59_999,
+        open,
+        high,
+        low,
+        close,
+        volume: 1.0,
+        quote_volume: close,
+        trade_count: 1,
+    }
+}
+
+#[test]
+fn local_candle_aggregator_closes_bucket_when_next_interval_trade_arrives() {
+    let mut aggregator = LocalCandleAggregator::new(60_000);
+    aggregator.apply_trade("BTCUSDT", 120_000, 100.0, 0.5);
+    aggregator.apply_trade("BTCUSDT", 130_000, 105.0, 0.25);
+    aggregator.apply_trade("BTCUSDT", 170_000, 99.0, 0.25);
+    assert!(aggregator.take_closed().is_empty());
+
+    aggregator.apply_trade("BTCUSDT", 181_000, 101.0, 1.0);
+    let closed = aggregator.take_closed();
+
+    assert_eq!(closed.len(), 1);
+    let (symbol, candle) = &closed[0];
+    assert_eq!(symbol, "BTCUSDT");
+    assert_eq!(candle.open_time_ms, 120_000);
+    assert_eq!(candle.close_time_ms, 179_999);
+    assert_eq!(
+        (candle.open, candle.high, candle.low, candle.close),
+        (100.0, 105.0, 99.0, 99.0)
+    );
+    assert!((candle.volume - 1.0).abs() < 1e-9);
+    assert_eq!(candle.trade_count, 3);
+    assert_eq!(
+        aggregator
+            .current_candle("BTCUSDT")
+            .map(|candle| candle.open_time_ms),
+        Some(180_000)
+    );
+}
+
+#[test]
+fn candle_interval_ms_accepts_only_supported_intervals() {
+    assert_eq!(candle_interval_ms("1m"), Some(60_000));
+    assert_eq!(candle_interval_ms("5m"), Some(300_000));
+    assert_eq!(candle_interval_ms("1w"), None);
+}
+
+#[test]
+fn candle_consistency_check_corrects_drift_beyond_tolerance() {
+    let local = kline(60_000, 100.0, 101.0, 99.0, 100.0);
+    let within = kline(60_000, 100.0, 101.0, 99.0, 100.01);
+    let drifted = kline(60_000, 100.0, 101.5, 99.0, 100.2);
+
+    let ok = compare_candles("BTCUSDT", &local, &within, 5.0);
+    assert!(ok.within_tolerance());
+    assert_eq!(ok.corrected_candle(), local);
+
+    let check = compare_candles("BTCUSDT", &local, &drifted, 5.0);
+    assert!(!check.within_tolerance());
+    assert_eq!(check.drifted_fields, vec!["high", "close"]);
+    assert!(check.max_drift_bps > 40.0);
+    assert_eq!(check.corrected_candle(), drifted);
+}
+
+#[test]
+fn kline_endpoint_follows_the_market_and_the_configured_host() {
+    let spot = KlineEndpoint::for_market(BinanceMode::Real, Market::Spot);
+    assert_eq!(spot.base_url, "https://api.binance.com");
+    assert_eq!(spot.path(), "/api/v3/klines");
+
+    let futures = KlineEndpoint::for_market(BinanceMode::Demo, Market::Futures);
+    assert_eq!(futures.base_url, "https://demo-fapi.binance.com");
+    assert_eq!(futures.path(), "/fapi/v1/klines");
+
+    let proxied = futures.with_base_url(" http://127.0.0.1:9000/ ");
+    assert_eq!(proxied.base_url, "http://127.0.0.1:9000");
+    assert_eq!(proxied.market, Market::Futures);
+}
+
+#[test]
+fn price_alert_fires_once_when_price_crosses_level() {
+    let instrument = Instrument::new("BTCUSDT");
+    let mut store = PriceAlertStore::new(std::env::temp_dir().join("unused-alerts.json"));
+    let alert = store.add(&instrument, 100.0, PriceAlertAction::Notify, Some(98.0));
+
+    assert!(store.observe(&instrument, 99.0).is_empty());
+    let triggered = store.observe(&instrument, 100.5);
+    assert_eq!(triggered.len(), 1);
+    assert_eq!(triggered[0].id, alert.id);
+    assert!(store.observe(&instrument, 99.0).is_empty());
+    assert!(store.levels_for("BTCUSDT").is_empty());
+}
+
+#[test]
+fn price_alert_order_waits_for_one_confirmation() {
+    let instrument = Instrument::new("ETHUSDT");
+    let mut store = PriceAlertStore::new(std::env::temp_dir().join("unused-alerts.json"));
+    let alert = store.add(
+        &instrument,
+        3000.0,
+        PriceAlertAction::SetTargetExposure { target: -0.25 },
+        Some(3010.0),
+    );
+
+    assert!(store.take_confirmation(alert.id).is_none());
+    assert_eq!(store.observe(&instrument, 2990.0).len(), 1);
+    store.hold_for_confirmation(alert.id);
+    assert!(store.alerts()[0].awaiting_confirmation);
+    assert_eq!(
+        store.take_confirmation(alert.id).map(|alert| alert.id),
+        Some(alert.id)
+    );
+    assert!(store.take_confirmation(alert.id).is_none());
+}
+
+#[test]
+fn price_alerts_survive_reload() {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    let path = std::env::temp_dir().join(format!("sandbox-quant-alerts-{nanos}.json"));
+    let mut store = PriceAlertStore::new(&path);
+    store.add(
+        &Instrument::new("ETHUSDT"),
+        3000.0,
+        PriceAlertAction::SetTargetExposure { target: 0.5 },
+        None,
+    );
+    store.save().expect("save alerts");
+
+    let reloaded = PriceAlertStore::load(&path).expect("load alerts");
+    assert_eq!(reloaded.alerts(), store.alerts());
+    assert_eq!(reloaded.levels_for("ETHUSDT"), vec![3000.0]);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn ewma_volatility_tracks_log_returns_on_candle_close() {
+    let mut estimator = VolatilityEstimator::new(VolatilityModel::Ewma { lambda: 0.5 });
+    estimator.on_candle_close(&kline(0, 100.0, 100.0, 100.0, 100.0));
+    assert_eq!(estimator.sigma(), None);
+
+    estimator.on_candle_close(&kline(60_000, 100.0, 101.0, 100.0, 101.0));
+    let first = (101.0f64 / 100.0).ln();
+    assert!((estimator.sigma().unwrap() - first.abs()).abs() < 1e-12);
+
+    estimator.on_candle_close(&kline(120_000, 101.0, 101.0, 101.0, 101.0));
+    let expected = (0.5 * first * first).sqrt();
+    assert!((estimator.sigma().unwrap() - expected).abs() < 1e-12);
+    assert_eq!(estimator.samples(), 2);
+
+    let bars_per_year = 365.0 * 24.0 * 60.0;
+    let annualized = estimator.annualized_sigma().unwrap();
+    assert!((annualized - expected * f64::sqrt(bars_per_year)).abs() < 1e-9);
+}
+
+#[test]
+fn garch_volatility_targets_the_observed_long_run_level() {
+    let mut estimator = VolatilityEstimator::new(VolatilityModel::garch());
+    estimator.on_candle_close(&kline(0, 10.0, 10.0, 10.0, 10.0));
+    assert_eq!(estimator.annualized_sigma(), None);
+
+    // +-0.1% per 1m bar stays near 72% a year once the long-run level is targeted.
+    let mut close = 10.0;
+    for index in 1..=500 {
+        close *= if index % 2 == 0 { 1.0 / 1.001 } else { 1.001 };
+        estimator.on_candle_close(&kline(index as i64 * 60_000, close, close, close, close));
+    }
+    let annualized = estimator.annualized_sigma().unwrap();
+    assert!((0.6..0.8).contains(&annualized), "annualized={annualized}");
+    assert_eq!(estimator.samples(), 500);
+}
+
+#[test]
+fn open_interest_response_parses_string_quantity_and_time() {
+    let row = parse_open_interest(&serde_json::json!({
+        "openInterest": "10659.509",
+        "symbol": "BTCUSDT",
+        "time": 1_589_437_530_011_i64,
+    }))
+    .expect("open interest row");
+    assert_eq!(row.event_time_ms, 1_589_437_530_011);
+    assert!((row.open_interest - 10_659.509).abs() < 1e-9);
+    assert!(parse_open_interest(&serde_json::json!({ "symbol": "BTCUSDT" })).is_none());
+}
+
+#[test]
+fn trade_id_tracker_reports_gaps_per_symbol_and_parses_backfill_rows() {
+    let mut tracker = TradeIdTracker::default();
+    assert!(tracker.observe("BTCUSDT", 10, 12, 1_000).is_none());
+    assert!(tracker.observe("ETHUSDT", 500, 500, 1_050).is_none());
+    assert!(tracker.observe("BTCUSDT", 13, 13, 1_100).is_none());
+    let gap = tracker
+        .observe("BTCUSDT", 20, 21, 1_300)
+        .expect("skipped ids should be a gap");
+    assert_eq!((gap.from_id, gap.to_id, gap.missed()), (14, 19, 6));
+    assert_eq!((gap.after_time_ms, gap.before_time_ms), (1_100, 1_300));
+    assert!(tracker.observe("BTCUSDT", 15, 15, 1_350).is_none());
+    assert!(tracker.observe("ETHUSDT", 501, 501, 1_400).is_none());
+
+    let rows = parse_agg_trades(&serde_json::json!([
+        {"a": 26129, "p": "0.01633102", "q": "4.70443515", "f": 27781, "l": 27782, "T": 1_498_793_709_153_i64, "m": true}
+    ]))
+    .expect("agg trade rows");
+    assert_eq!(rows.len(), 1);
+    assert_eq!((rows[0].first_id, rows[0].last_id), (27_781, 27_782));
+    assert!(rows[0].is_buyer_maker);
+    assert!(parse_agg_trades(&serde_json::json!([{ "a": 1 }])).is_none());
+}
+
+#[test]
+fn tick_sequencer_drops_repeats_and_releases_in_event_order() {
+    let trade = |symbol: &str, first_id: i64, event_time_ms: i64| SequencedTrade {
+        symbol: symbol.to_string(),
+        first_id: Some(first_id),
+        last_id: Some(first_id),
+        event_time_ms,
+        receive_time_ms: event_time_ms,
+        price: 100.0,
+        qty: 1.0,
+        is_buyer_maker: false,
+    };
+    let ids = |trades: Vec<SequencedTrade>| {
+        trades
+            .iter()
+            .map(|trade| trade.first_id.expect("id"))
+            .collect::<Vec<_>>()
+    };
+    let mut sequencer = TickSequencer::new(TickSequencerConfig {
+        reorder_window_ms: 250,
+    });
+
+    assert_eq!(
+        sequencer.push(trade("BTCUSDT", 2, 20), 0),
+        TickAdmission::InOrder
+    );
+    assert_eq!(
+        sequencer.push(trade("BTCUSDT", 1, 10), 50),
+        TickAdmission::Reordered
+    );
+    // A reconnecting worker replays the same trade.
+    assert_eq!(
+        sequencer.push(trade("BTCUSDT", 2, 20), 60),
+        TickAdmission::Duplicate
+    );
+    assert_eq!(
+        sequencer.push(trade("BTCUSDT", 3, 30), 200),
+        TickAdmission::InOrder
+    );
+    assert!(sequencer.release(100).is_empty());
+    // Trade 1 came in later than 2 but sorts ahead of it.
+    assert_eq!(ids(sequencer.release(250)), vec![1, 2]);
+    assert_eq!(sequencer.pending(), 1);
+
+    assert_eq!(
+        sequencer.push(trade("BTCUSDT", 0, 5), 300),
+        TickAdmission::Late
+    );
+    assert_eq!(ids(sequencer.flush()), vec![0, 3]);
+    assert_eq!(
+        sequencer.push(trade("BTCUSDT", 1, 10), 400),
+        TickAdmission::Duplicate
+    );
+
+    let mut passthrough = TickSequencer::new(TickSequencerConfig {
+        reorder_window_ms: 0,
+    });
+    passthrough.push(trade("ETHUSDT", 7, 70), 1_000);
+    assert_eq!(ids(passthrough.release(1_000)), vec![7]);
+}
+
+#[test]
+fn session_volume_profile_splits_utc_days_and_finds_point_of_control() {
+    let day = 86_400_000;
+    let mut klines = vec![
+        kline(0, 100.0, 101.0, 100.0, 101.0),
+        kline(60_000, 101.0, 110.0, 101.0, 109.0),
+        kline(120_000, 101.0, 102.0, 100.0, 100.0),
+        kline(day, 200.0, 201.0, 199.0, 200.0),
+    ];
+    klines[2].volume = 5.0;
+
+    let profiles = VisualizationService::session_volume_profiles(&klines, 10);
+
+    assert_eq!(profiles.len(), 2);
+    assert_eq!(profiles[0].session_start_ms, 0);
+    assert_eq!(profiles[0].session_end_ms, day);
+    assert_eq!(profiles[0].levels.len(), 10);
+    assert!((profiles[0].levels[0].price_low - 100.0).abs() < 1e-9);
+    assert!((profiles[0].levels[9].price_high - 110.0).abs() < 1e-9);
+    let total: f64 = profiles[0].levels.iter().map(|level| level.volume).sum();
+    assert!((total - 7.0).abs() < 1e-9);
+    assert!((profiles[0].point_of_control - 100.5).abs() < 1e-9);
+    assert_eq!(profiles[1].session_start_ms, day);
+}
+
+#[test]
+fn reconnect_breaker_trips_after_limit_inside_window_and_cools_down() {
+    let mut breaker = ReconnectBreaker::new(ReconnectBreakerConfig {
+        max_attempts: 3,
+        window_secs: 60,
+        cooldown_secs: 300,
+    });
+
+    for at_ms in [0, 5_000, 10_000] {
+        assert_eq!(breaker.record_attempt("BTCUSDT", at_ms), None);
+    }
+    assert_eq!(breaker.record_attempt("ETHUSDT", 12_000), None);
+    assert_eq!(breaker.record_attempt("BTCUSDT", 15_000), Some(315_000));
+
+    assert!(breaker.is_open("BTCUSDT", 314_999));
+    assert!(!breaker.is_open("ETHUSDT", 20_000));
+    assert_eq!(
+        breaker.degraded(100_000),
+        vec![("BTCUSDT".to_string(), 315_000)]
+    );
+    assert!(!breaker.is_open("BTCUSDT", 315_000));
+    assert!(breaker.degraded(315_000).is_empty());
+    assert_eq!(breaker.record_attempt("BTCUSDT", 315_000), None);
+}
+
+#[test]
+fn reconnect_breaker_forgets_attempts_outside_the_window() {
+    let mut breaker = ReconnectBreaker::new(ReconnectBreakerConfig {
+        max_attempts: 2,
+        window_secs: 10,
+        cooldown_secs: 300,
+    });
+
+    assert_eq!(breaker.record_attempt("BTCUSDT", 0), None);
+    assert_eq!(breaker.record_attempt("BTCUSDT", 9_000), None);
+    assert_eq!(breaker.record_attempt("BTCUSDT", 12_000), None);
+    assert_eq!(breaker.record_attempt("BTCUSDT", 13_000), Some(313_000));
+
+    let mut disabled = ReconnectBreaker::new(ReconnectBreakerConfig {
+        max_attempts: 0,
+        ..ReconnectBreakerConfig::default()
+    });
+    assert!((0..100).all(|at_ms| disabled.record_attempt("BTCUSDT", at_ms).is_none()));
+}
+
+#[test]
+fn symbol_range_stats_summarize_daily_bars_for_the_selector() {
+    let bar = |high: f64, low: f64, close: f64, quote_volume: f64| DailyBar {
+        high,
+        low,
+        close,
+        quote_volume,
+    };
+    let bars = [
+        bar(102.0, 98.0, 100.0, 1.0e9),
+        bar(104.0, 100.0, 102.0, 2.0e9),
+        bar(102.5, 99.5, 100.0, 0.4e9),
+    ];
+
+    let stats = SymbolRangeStats::from_daily_bars(&bars).expect("two completed days");
+
+    assert!((stats.change_24h_pct - (-2.0 / 102.0 * 100.0)).abs() < 1e-9);
+    assert!((stats.atr_pct - 4.0).abs() < 1e-9);
+    assert!((stats.avg_quote_volume - 1.5e9).abs() < 1e-3);
+    assert_eq!(stats.label(), "24h -1.96% atr 4.00% vol 1.5B");
+    assert!(SymbolRangeStats::from_daily_bars(&bars[..1]).is_none());
+}
+
+#[test]
+fn news_feed_parses_rss_atom_and_json_headlines() {
+    let rss = r#"<?xml version="1.0"?><rss><channel><title>Exchange news</title>
+        <item><title><![CDATA[Binance Will Delist FOO & BAR]]></title>
+        <link>https://example.com/a</link><guid>a-1</guid>
+        <pubDate>Sun, 18 Oct 2026 08:55:00 GMT</pubDate></item>
+        <item><title>ETH ETF inflows slow</title><link>https://example.com/b</link></item>
+        </channel></rss>"#;
+    let headlines = parse_news_feed(rss);
+    assert_eq!(headlines.len(), 2);
+    assert_eq!(headlines[0].title, "Binance Will Delist FOO & BAR");
+    assert_eq!(headlines[0].id, "a-1");
+    assert_eq!(
+        headlines[0].published_at.map(|time| time.to_rfc3339()),
+        Some("2026-10-18T08:55:00+00:00".to_string())
+    );
+    assert_eq!(headlines[1].id, "https://example.com/b");
+
+    let atom = r#"<feed><title>Feed</title><entry><title>SOL outage resolved</title>
+        <link href="https://example.com/c"/><id>urn:c</id>
+        <updated>2026-10-18T09:00:00Z</updated></entry></feed>"#;
+    let headlines = parse_news_feed(atom);
+    assert_eq!(headlines.len(), 1);
+    assert_eq!(headlines[0].link.as_deref(), Some("https://example.com/c"));
+    assert_eq!(headlines[0].id, "urn:c");
+
+    let json = r#"{"data":{"catalogs":[{"articles":[
+        {"code":"abc","title":"Binance Will List XYZ","releaseDate":1792314000000}]}]}}"#;
+    let headlines = parse_news_feed(json);
+    assert_eq!(headlines.len(), 1);
+    assert_eq!(headlines[0].id, "abc");
+    assert!(headlines[0].published_at.is_some());
+}
+
+#[test]
+fn news_feed_flags_traded_assets_and_major_keywords_after_the_first_poll() {
+    let headline = |id: &str, title: &str| NewsHeadline {
+        id: id.to_string(),
+        title: title.to_string(),
+        link: None,
+        published_at: None,
+    };
+    let traded = ["BTC".to_string(), "ETH".to_string()]
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+    let mut feed = NewsFeed::new(NewsFeedConfig {
+        url: Some("https://example.com/feed.xml".to_string()),
+        ..NewsFeedConfig::default()
+    });
+    let now = Utc::now();
+    assert!(feed.is_due(now));
+
+    assert!(feed
+        .ingest(vec![headline("1", "BTC hits new high")], &traded, now)
+        .is_empty());
+    let fresh = feed.ingest(
+        vec![
+            headline("1", "BTC hits new high"),
+            headline("2", "Exchange delisting ETHBTC and eth pairs"),
+            headline("3", "Weekly market wrap"),
+        ],
+        &traded,
+        now,
+    );
+    assert_eq!(fresh.len(), 2);
+    assert_eq!(fresh[0].assets, vec!["ETH".to_string()]);
+    assert!(fresh[0].major, "delist matches delisting");
+    assert_eq!(
+        fresh[0].highlighted_title(),
+        "Exchange delisting ETHBTC and [eth] pairs"
+    );
+    assert!(!fresh[1].is_notable());
+    assert_eq!(feed.headlines().count(), 3);
+    assert!(!NewsFeed::default().is_due(now), "no url, no polling");
+}
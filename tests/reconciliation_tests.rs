@@ -38,6 +38,7 @@ fn refresh_from_exchange_overwrites_local_state_and_clears_staleness() {
             instrument: eth.clone(),
             market: Market::Spot,
             side: Side::Sell,
+            price: None,
             orig_qty: 1.0,
             executed_qty: 0.0,
             reduce_only: false,
@@ -89,6 +90,7 @@ fn sync_service_refreshes_authoritative_state_and_reports_counts() {
             instrument: btc,
             market: Market::Futures,
             side: Side::Buy,
+            price: None,
             orig_qty: 0.25,
             executed_qty: 0.0,
             reduce_only: true,